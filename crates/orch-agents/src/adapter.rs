@@ -1,7 +1,11 @@
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+use orch_core::config::{CustomModelConfig, CustomOutputFormat, ModelsConfig};
 use orch_core::types::ModelKind;
 
 use crate::error::AgentError;
-use crate::signal::detect_common_signal;
+use crate::signal::{detect_common_signal, parse_confidence_marker};
 use crate::types::{AgentCommand, AgentSignal, EpochRequest};
 
 pub trait AgentAdapter: Send + Sync {
@@ -13,9 +17,19 @@ pub trait AgentAdapter: Send + Sync {
     fn build_interactive_command(&self, request: &EpochRequest) -> AgentCommand {
         self.build_command(request)
     }
+    /// Extra interactive arguments that pin the conversation to
+    /// `session_id`, or pick it back up when `resume` is set. Empty for
+    /// CLIs that cannot resume a conversation.
+    fn session_args(&self, _session_id: &str, _resume: bool) -> Vec<String> {
+        Vec::new()
+    }
     fn detect_signal(&self, line: &str) -> Option<AgentSignal> {
         detect_common_signal(line)
     }
+    /// Self-reported confidence (whole percent) if `line` is a marker.
+    fn detect_confidence(&self, line: &str) -> Option<u8> {
+        parse_confidence_marker(line)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -63,6 +77,11 @@ impl AgentAdapter for ClaudeAdapter {
             env: request.env.clone(),
         }
     }
+
+    fn session_args(&self, session_id: &str, resume: bool) -> Vec<String> {
+        let flag = if resume { "--resume" } else { "--session-id" };
+        vec![flag.to_string(), session_id.to_string()]
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -148,11 +167,122 @@ impl AgentAdapter for GeminiAdapter {
     }
 }
 
+/// Adapter for a CLI configured under `[models.custom.<name>]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenericAdapter {
+    pub model: ModelKind,
+    pub config: CustomModelConfig,
+}
+
+impl GenericAdapter {
+    pub fn new(model: ModelKind, config: CustomModelConfig) -> Self {
+        Self { model, config }
+    }
+
+    fn expand_args(&self, request: &EpochRequest, prompt: Option<&str>) -> Vec<String> {
+        let repo = request.repo_path.display().to_string();
+        let mut args = Vec::new();
+        for template in &self.config.args {
+            if template.contains("{prompt}") {
+                if let Some(prompt) = prompt {
+                    args.push(
+                        template
+                            .replace("{prompt}", prompt)
+                            .replace("{repo}", &repo),
+                    );
+                }
+                continue;
+            }
+            args.push(template.replace("{repo}", &repo));
+        }
+        args.extend(request.extra_args.iter().cloned());
+        if let Some(prompt) = prompt {
+            if !self.config.args.iter().any(|arg| arg.contains("{prompt}")) {
+                args.push(prompt.to_string());
+            }
+        }
+        args
+    }
+
+    /// The text a line carries: the line itself, or for JSON output the
+    /// first string field commonly used for message text.
+    fn line_text<'a>(&self, line: &'a str) -> Option<std::borrow::Cow<'a, str>> {
+        match self.config.output_format {
+            CustomOutputFormat::Text => Some(line.into()),
+            CustomOutputFormat::Json => {
+                let value: serde_json::Value = serde_json::from_str(line.trim()).ok()?;
+                ["text", "content", "message", "result"]
+                    .iter()
+                    .find_map(|key| value.get(key)?.as_str())
+                    .map(|text| text.to_string().into())
+            }
+        }
+    }
+}
+
+impl AgentAdapter for GenericAdapter {
+    fn model(&self) -> ModelKind {
+        self.model
+    }
+
+    fn build_command(&self, request: &EpochRequest) -> AgentCommand {
+        AgentCommand {
+            executable: self.config.command.clone(),
+            args: self.expand_args(request, Some(&request.prompt)),
+            env: request.env.clone(),
+        }
+    }
+
+    fn build_interactive_command(&self, request: &EpochRequest) -> AgentCommand {
+        AgentCommand {
+            executable: self.config.command.clone(),
+            args: self.expand_args(request, None),
+            env: request.env.clone(),
+        }
+    }
+
+    fn detect_signal(&self, line: &str) -> Option<AgentSignal> {
+        let text = self.line_text(line)?;
+        text.lines().find_map(detect_common_signal)
+    }
+
+    fn detect_confidence(&self, line: &str) -> Option<u8> {
+        let text = self.line_text(line)?;
+        text.lines().find_map(parse_confidence_marker)
+    }
+}
+
+fn custom_models() -> &'static RwLock<HashMap<ModelKind, CustomModelConfig>> {
+    static CUSTOM_MODELS: OnceLock<RwLock<HashMap<ModelKind, CustomModelConfig>>> = OnceLock::new();
+    CUSTOM_MODELS.get_or_init(Default::default)
+}
+
+/// Make the custom models in `models` available to `default_adapter_for`,
+/// replacing any registered before.
+pub fn register_custom_models(models: &ModelsConfig) {
+    let registered = models
+        .custom_models()
+        .into_iter()
+        .map(|(model, config)| (model, config.clone()))
+        .collect();
+    *custom_models()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = registered;
+}
+
 pub fn default_adapter_for(model: ModelKind) -> Result<Box<dyn AgentAdapter>, AgentError> {
     match model {
         ModelKind::Claude => Ok(Box::new(ClaudeAdapter::default())),
         ModelKind::Codex => Ok(Box::new(CodexAdapter::default())),
         ModelKind::Gemini => Ok(Box::new(GeminiAdapter::default())),
+        ModelKind::Custom(_) => custom_models()
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(&model)
+            .map(|config| {
+                Box::new(GenericAdapter::new(model, config.clone())) as Box<dyn AgentAdapter>
+            })
+            .ok_or(AgentError::UnsupportedModel { model }),
     }
 }
 
@@ -162,9 +292,14 @@ mod tests {
 
     use orch_core::types::{ModelKind, RepoId, TaskId};
 
-    use crate::types::EpochRequest;
+    use orch_core::config::{CustomModelConfig, CustomOutputFormat, OrgConfig};
 
-    use super::{default_adapter_for, AgentAdapter, ClaudeAdapter, CodexAdapter, GeminiAdapter};
+    use crate::types::{AgentSignalKind, EpochRequest};
+
+    use super::{
+        default_adapter_for, register_custom_models, AgentAdapter, ClaudeAdapter, CodexAdapter,
+        GeminiAdapter, GenericAdapter,
+    };
 
     fn mk_request(model: ModelKind) -> EpochRequest {
         EpochRequest {
@@ -251,6 +386,90 @@ mod tests {
         assert_eq!(gemini.model(), ModelKind::Gemini);
     }
 
+    fn aider_config(output_format: CustomOutputFormat) -> CustomModelConfig {
+        CustomModelConfig {
+            command: "aider".to_string(),
+            args: vec![
+                "--yes".to_string(),
+                "--message={prompt}".to_string(),
+                "--cwd={repo}".to_string(),
+            ],
+            api_key_env: Some("OPENAI_API_KEY".to_string()),
+            output_format,
+        }
+    }
+
+    #[test]
+    fn generic_adapter_expands_args_template() {
+        let model = ModelKind::from_name("aider").unwrap();
+        let adapter = GenericAdapter::new(model, aider_config(CustomOutputFormat::Text));
+        let request = mk_request(model);
+
+        let command = adapter.build_command(&request);
+        assert_eq!(command.executable, "aider");
+        assert_eq!(
+            command.args,
+            vec![
+                "--yes".to_string(),
+                "--message=implement feature".to_string(),
+                "--cwd=/tmp/repo".to_string(),
+                "--flag".to_string(),
+                "--json".to_string(),
+            ]
+        );
+        assert_eq!(command.env, vec![("FOO".to_string(), "BAR".to_string())]);
+
+        let interactive = adapter.build_interactive_command(&request);
+        assert_eq!(
+            interactive.args,
+            vec!["--yes", "--cwd=/tmp/repo", "--flag", "--json"]
+        );
+
+        let mut bare = aider_config(CustomOutputFormat::Text);
+        bare.args.clear();
+        let command = GenericAdapter::new(model, bare).build_command(&request);
+        assert_eq!(
+            command.args.last().map(String::as_str),
+            Some("implement feature")
+        );
+    }
+
+    #[test]
+    fn generic_adapter_reads_signals_from_json_output() {
+        let model = ModelKind::from_name("aider").unwrap();
+        let adapter = GenericAdapter::new(model, aider_config(CustomOutputFormat::Json));
+
+        let signal = adapter
+            .detect_signal(r#"{"type":"text","text":"done\n[patch_ready]"}"#)
+            .expect("signal in json text");
+        assert_eq!(signal.kind, AgentSignalKind::PatchReady);
+        assert!(adapter.detect_signal("[patch_ready]").is_none());
+        assert_eq!(
+            adapter.detect_confidence(r#"{"content":"CONFIDENCE: 80%"}"#),
+            Some(80)
+        );
+    }
+
+    #[test]
+    fn default_adapter_for_resolves_registered_custom_models() {
+        let model = ModelKind::from_name("adapter-test-cli").unwrap();
+        assert!(default_adapter_for(model).is_err());
+
+        let mut models = OrgConfig::default().models;
+        models.custom.insert(
+            model.as_str().to_string(),
+            aider_config(CustomOutputFormat::Text),
+        );
+        register_custom_models(&models);
+
+        let adapter = default_adapter_for(model).expect("custom adapter");
+        assert_eq!(adapter.model(), model);
+        assert_eq!(
+            adapter.build_command(&mk_request(model)).executable,
+            "aider"
+        );
+    }
+
     #[test]
     fn adapters_preserve_empty_prompt_as_last_argument() {
         let mut request = mk_request(ModelKind::Claude);
@@ -303,6 +522,20 @@ mod tests {
         assert!(!command.args.contains(&"implement feature".to_string()));
     }
 
+    #[test]
+    fn claude_session_args_pin_or_resume_the_conversation() {
+        let adapter = ClaudeAdapter::default();
+        assert_eq!(
+            adapter.session_args("abc", false),
+            vec!["--session-id".to_string(), "abc".to_string()]
+        );
+        assert_eq!(
+            adapter.session_args("abc", true),
+            vec!["--resume".to_string(), "abc".to_string()]
+        );
+        assert!(CodexAdapter::default().session_args("abc", true).is_empty());
+    }
+
     #[test]
     fn codex_interactive_command_omits_exec_subcommand() {
         let adapter = CodexAdapter::default();
@@ -3,26 +3,28 @@ pub mod error;
 pub mod runner;
 pub mod setup;
 pub mod signal;
+pub mod signal_rules;
 pub mod types;
-pub(crate) mod util;
 
 pub use adapter::*;
 pub use error::*;
 pub use runner::*;
 pub use setup::*;
 pub use signal::*;
+pub use signal_rules::*;
 pub use types::*;
 
 #[cfg(test)]
 mod tests {
     use super::{
-        default_adapter_for, detect_common_signal, probe_models, probe_models_with_runner,
-        summarize_setup, validate_setup_selection, AgentAdapter, AgentCommand, AgentError,
-        AgentSignal, AgentSignalKind, ClaudeAdapter, CodexAdapter, EnvRequirementGroup,
-        EnvRequirementStatus, EpochRequest, EpochResult, EpochRunner, EpochStopReason,
-        GeminiAdapter, ModelProbeResult, ModelSetupSelection, ProcessSetupCommandRunner, PtyChunk,
-        RunnerPtySize, SetupCommandRunner, SetupError, SetupProbeConfig, SetupProbeReport,
-        SetupSummary, SetupSummaryItem, ValidatedSetupSelection,
+        default_adapter_for, detect_common_signal, parse_confidence_marker, probe_models,
+        probe_models_with_runner, summarize_setup, validate_setup_selection, AgentAdapter,
+        AgentCommand, AgentError, AgentSignal, AgentSignalKind, ChunkSink, ClaudeAdapter,
+        CodexAdapter, EnvRequirementGroup, EnvRequirementStatus, EpochRequest, EpochResult,
+        EpochRunner, EpochStopReason, GeminiAdapter, ModelProbeResult, ModelSetupSelection,
+        ProcessSetupCommandRunner, PtyChunk, RunnerPtySize, SetupCommandRunner, SetupError,
+        SetupProbeConfig, SetupProbeReport, SetupSummary, SetupSummaryItem,
+        ValidatedSetupSelection,
     };
     use orch_core::types::ModelKind;
     use std::any::TypeId;
@@ -37,6 +39,7 @@ mod tests {
         let _ = TypeId::of::<AgentSignal>();
         let _ = TypeId::of::<AgentSignalKind>();
         let _ = TypeId::of::<PtyChunk>();
+        let _ = TypeId::of::<ChunkSink>();
         let _ = TypeId::of::<ClaudeAdapter>();
         let _ = TypeId::of::<CodexAdapter>();
         let _ = TypeId::of::<GeminiAdapter>();
@@ -60,9 +63,12 @@ mod tests {
         let _default_adapter: fn(ModelKind) -> Result<Box<dyn AgentAdapter>, AgentError> =
             default_adapter_for;
         let _detect_signal: fn(&str) -> Option<AgentSignal> = detect_common_signal;
+        let _parse_confidence: fn(&str) -> Option<u8> = parse_confidence_marker;
         let _probe: fn(&SetupProbeConfig) -> SetupProbeReport = probe_models;
-        let _probe_with_runner: fn(&SetupProbeConfig, &dyn SetupCommandRunner) -> SetupProbeReport =
-            probe_models_with_runner;
+        let _probe_with_runner: fn(
+            &SetupProbeConfig,
+            std::sync::Arc<dyn SetupCommandRunner>,
+        ) -> SetupProbeReport = probe_models_with_runner;
         let _validate: fn(
             &SetupProbeReport,
             &ModelSetupSelection,
@@ -8,7 +8,7 @@ use std::time::{Duration, Instant};
 use crate::adapter::AgentAdapter;
 use crate::error::AgentError;
 use crate::types::{
-    AgentSignal, AgentSignalKind, EpochRequest, EpochResult, EpochStopReason, PtyChunk,
+    AgentSignal, AgentSignalKind, ChunkSink, EpochRequest, EpochResult, EpochStopReason, PtyChunk,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -44,10 +44,22 @@ impl Default for EpochRunner {
 }
 
 impl EpochRunner {
+    /// Run the agent to completion.
     pub fn run_epoch(
         &self,
         request: &EpochRequest,
         adapter: &dyn AgentAdapter,
+    ) -> Result<EpochResult, AgentError> {
+        self.run_epoch_with_sink(request, adapter, None)
+    }
+
+    /// [`EpochRunner::run_epoch`], also pushing each chunk to `sink` as soon
+    /// as it is read. `EpochResult::output` still has the full stream.
+    pub fn run_epoch_with_sink(
+        &self,
+        request: &EpochRequest,
+        adapter: &dyn AgentAdapter,
+        sink: Option<&ChunkSink>,
     ) -> Result<EpochResult, AgentError> {
         if request.timeout_secs == 0 {
             return Err(AgentError::InvalidRequest {
@@ -118,7 +130,14 @@ impl EpochRunner {
         let mut wait_status = None;
 
         while stop_reason.is_none() {
-            drain_output(&rx, adapter, &mut output, &mut signals, &mut stop_reason);
+            drain_output(
+                &rx,
+                adapter,
+                sink,
+                &mut output,
+                &mut signals,
+                &mut stop_reason,
+            );
             if stop_reason.is_some() {
                 let _ = child.kill();
                 break;
@@ -155,7 +174,14 @@ impl EpochRunner {
         let exit_code = i32::try_from(final_status.exit_code()).ok();
 
         let _ = reader_handle.join();
-        drain_output(&rx, adapter, &mut output, &mut signals, &mut stop_reason);
+        drain_output(
+            &rx,
+            adapter,
+            sink,
+            &mut output,
+            &mut signals,
+            &mut stop_reason,
+        );
 
         let final_reason = stop_reason.unwrap_or_else(|| {
             if final_status.success() {
@@ -165,6 +191,10 @@ impl EpochRunner {
             }
         });
 
+        let confidence = output
+            .iter()
+            .rev()
+            .find_map(|chunk| adapter.detect_confidence(&chunk.text));
         Ok(EpochResult {
             task_id: request.task_id.clone(),
             repo_id: request.repo_id.clone(),
@@ -175,6 +205,7 @@ impl EpochRunner {
             exit_code,
             output,
             signals,
+            confidence,
         })
     }
 }
@@ -182,6 +213,7 @@ impl EpochRunner {
 fn drain_output(
     rx: &mpsc::Receiver<String>,
     adapter: &dyn AgentAdapter,
+    sink: Option<&ChunkSink>,
     output: &mut Vec<PtyChunk>,
     signals: &mut Vec<AgentSignal>,
     stop_reason: &mut Option<EpochStopReason>,
@@ -194,10 +226,14 @@ fn drain_output(
             signals.push(signal);
         }
 
-        output.push(PtyChunk {
+        let chunk = PtyChunk {
             at: Utc::now(),
             text: line,
-        });
+        };
+        if let Some(sink) = sink {
+            sink.send(chunk.clone());
+        }
+        output.push(chunk);
     }
 }
 
@@ -239,7 +275,7 @@ fn render_shell_invocation(
     rendered
 }
 
-use crate::util::shell_quote;
+use orch_core::shell::shell_quote;
 
 #[cfg(test)]
 mod tests {
@@ -247,12 +283,13 @@ mod tests {
 
     use orch_core::types::{ModelKind, RepoId, TaskId};
 
+    use crate::adapter::AgentAdapter;
     use crate::adapter::ClaudeAdapter;
     use crate::error::AgentError;
-    use crate::types::{AgentCommand, AgentSignalKind, EpochRequest, EpochStopReason};
+    use crate::types::{AgentCommand, AgentSignalKind, ChunkSink, EpochRequest, EpochStopReason};
 
     use super::{render_shell_invocation, signal_to_stop_reason, EpochRunner};
-    use crate::util::shell_quote;
+    use orch_core::shell::shell_quote;
 
     fn mk_request() -> EpochRequest {
         EpochRequest {
@@ -344,4 +381,54 @@ mod tests {
             AgentError::InvalidRequest { message } if message.contains("prompt")
         ));
     }
+
+    /// Prints a line, stalls, then prints another, like an agent mid-task.
+    struct SlowAdapter;
+
+    impl AgentAdapter for SlowAdapter {
+        fn model(&self) -> ModelKind {
+            ModelKind::Claude
+        }
+
+        fn build_command(&self, _request: &EpochRequest) -> AgentCommand {
+            AgentCommand {
+                executable: "sh".to_string(),
+                args: vec![
+                    "-c".to_string(),
+                    "echo first-chunk; sleep 1; echo second-chunk".to_string(),
+                ],
+                env: vec![],
+            }
+        }
+    }
+
+    #[test]
+    fn run_epoch_streams_chunks_before_the_epoch_completes() {
+        let (sink, chunks) = ChunkSink::bounded(16);
+        let mut request = mk_request();
+        request.repo_path = std::env::temp_dir();
+
+        let epoch = std::thread::spawn(move || {
+            EpochRunner::default()
+                .run_epoch_with_sink(&request, &SlowAdapter, Some(&sink))
+                .expect("run epoch")
+        });
+
+        let first = chunks
+            .iter()
+            .find(|chunk| chunk.text.contains("first-chunk"))
+            .expect("first chunk streamed");
+        assert!(!epoch.is_finished(), "chunk arrived only after the epoch");
+        assert!(first.text.contains("first-chunk"));
+
+        let result = epoch.join().expect("epoch thread");
+        assert_eq!(result.stop_reason, EpochStopReason::Completed);
+        assert!(chunks
+            .iter()
+            .any(|chunk| chunk.text.contains("second-chunk")));
+        assert!(result
+            .output
+            .iter()
+            .any(|chunk| chunk.text.contains("second-chunk")));
+    }
 }
@@ -1,7 +1,12 @@
+use orch_core::config::ModelsConfig;
 use orch_core::types::ModelKind;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::process::{Command, Stdio};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+
+const DEFAULT_PROBE_TIMEOUT_SECS: u64 = 10;
 
 #[derive(Debug, thiserror::Error)]
 pub enum SetupError {
@@ -20,6 +25,47 @@ pub struct EnvRequirementGroup {
 pub struct SetupProbeConfig {
     pub executable_by_model: HashMap<ModelKind, String>,
     pub env_requirements_by_model: HashMap<ModelKind, Vec<EnvRequirementGroup>>,
+    /// Custom models probed after the built-in ones.
+    #[serde(default)]
+    pub custom_models: Vec<ModelKind>,
+    /// How long one model's CLI checks may take before it is reported as
+    /// timed out.
+    #[serde(default = "default_probe_timeout_secs")]
+    pub probe_timeout_secs: u64,
+    /// Probe every model at once instead of one after another.
+    #[serde(default = "default_parallel")]
+    pub parallel: bool,
+}
+
+fn default_probe_timeout_secs() -> u64 {
+    DEFAULT_PROBE_TIMEOUT_SECS
+}
+
+fn default_parallel() -> bool {
+    true
+}
+
+impl SetupProbeConfig {
+    /// Also probe the custom models in `models`: their command, and their
+    /// API key variable when one is configured.
+    pub fn with_custom_models(mut self, models: &ModelsConfig) -> Self {
+        for (model, custom) in models.custom_models() {
+            self.executable_by_model
+                .insert(model, custom.command.clone());
+            let requirements = custom
+                .api_key_env
+                .iter()
+                .map(|key| EnvRequirementGroup {
+                    any_of: vec![key.clone()],
+                })
+                .collect();
+            self.env_requirements_by_model.insert(model, requirements);
+            if !self.custom_models.contains(&model) {
+                self.custom_models.push(model);
+            }
+        }
+        self
+    }
 }
 
 impl Default for SetupProbeConfig {
@@ -52,6 +98,9 @@ impl Default for SetupProbeConfig {
         Self {
             executable_by_model,
             env_requirements_by_model,
+            custom_models: Vec::new(),
+            probe_timeout_secs: default_probe_timeout_secs(),
+            parallel: default_parallel(),
         }
     }
 }
@@ -106,7 +155,7 @@ pub struct SetupSummary {
     pub items: Vec<SetupSummaryItem>,
 }
 
-pub trait SetupCommandRunner {
+pub trait SetupCommandRunner: Send + Sync {
     fn command_exists(&self, executable: &str) -> bool;
     fn command_version(&self, executable: &str) -> Result<String, String>;
     fn env_var_present(&self, env_key: &str) -> bool;
@@ -157,35 +206,93 @@ impl SetupCommandRunner for ProcessSetupCommandRunner {
 }
 
 pub fn probe_models(config: &SetupProbeConfig) -> SetupProbeReport {
-    let runner = ProcessSetupCommandRunner;
-    probe_models_with_runner(config, &runner)
+    probe_models_with_runner(config, Arc::new(ProcessSetupCommandRunner))
+}
+
+/// Outcome of a model's CLI checks: whether it is installed, and its
+/// `--version` result when it is.
+type CliProbe = (bool, Option<Result<String, String>>);
+
+/// Run the CLI checks for `executable` on their own thread. A hung CLI keeps
+/// its thread blocked, but the caller stops waiting at its deadline.
+fn spawn_cli_probe(
+    runner: &Arc<dyn SetupCommandRunner>,
+    executable: &str,
+) -> mpsc::Receiver<CliProbe> {
+    let (tx, rx) = mpsc::channel();
+    let runner = Arc::clone(runner);
+    let executable = executable.to_string();
+    std::thread::spawn(move || {
+        let installed = runner.command_exists(&executable);
+        let version = installed.then(|| runner.command_version(&executable));
+        let _ = tx.send((installed, version));
+    });
+    rx
+}
+
+/// Wait for a CLI probe until `deadline`; a probe still running by then is
+/// installed but unhealthy, with the timeout as its version output.
+fn await_cli_probe(
+    rx: &mpsc::Receiver<CliProbe>,
+    deadline: Instant,
+    timeout_secs: u64,
+) -> (bool, bool, Option<String>) {
+    match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+        Ok((installed, Some(Ok(text)))) => (installed, true, Some(text)),
+        Ok((installed, Some(Err(err)))) => (installed, false, Some(err)),
+        Ok((installed, None)) => (installed, false, None),
+        Err(_) => (
+            true,
+            false,
+            Some(format!("probe timed out after {timeout_secs}s")),
+        ),
+    }
 }
 
 pub fn probe_models_with_runner(
     config: &SetupProbeConfig,
-    runner: &dyn SetupCommandRunner,
+    runner: Arc<dyn SetupCommandRunner>,
 ) -> SetupProbeReport {
-    let mut models = vec![ModelKind::Claude, ModelKind::Codex, ModelKind::Gemini];
+    let mut models = ModelKind::all().to_vec();
+    models.extend(config.custom_models.iter().copied());
     models.sort_by_key(model_rank);
 
-    let mut out = Vec::new();
-    for model in models {
-        let executable = config
-            .executable_by_model
-            .get(&model)
-            .cloned()
-            .unwrap_or_else(|| default_executable_for_model(model).to_string());
+    let timeout = Duration::from_secs(config.probe_timeout_secs);
+    let executables = models
+        .iter()
+        .map(|model| {
+            config
+                .executable_by_model
+                .get(model)
+                .cloned()
+                .unwrap_or_else(|| default_executable_for_model(*model).to_string())
+        })
+        .collect::<Vec<_>>();
 
-        let installed = runner.command_exists(&executable);
-        let (version_ok, version_output) = if installed {
-            match runner.command_version(&executable) {
-                Ok(text) => (true, Some(text)),
-                Err(err) => (false, Some(err)),
-            }
-        } else {
-            (false, None)
-        };
+    let cli_probes = if config.parallel {
+        let started = Instant::now();
+        let pending = executables
+            .iter()
+            .map(|executable| spawn_cli_probe(&runner, executable))
+            .collect::<Vec<_>>();
+        pending
+            .iter()
+            .map(|rx| await_cli_probe(rx, started + timeout, config.probe_timeout_secs))
+            .collect::<Vec<_>>()
+    } else {
+        executables
+            .iter()
+            .map(|executable| {
+                let rx = spawn_cli_probe(&runner, executable);
+                await_cli_probe(&rx, Instant::now() + timeout, config.probe_timeout_secs)
+            })
+            .collect::<Vec<_>>()
+    };
 
+    let mut out = Vec::new();
+    for ((model, executable), (installed, version_ok, version_output)) in
+        models.into_iter().zip(executables).zip(cli_probes)
+    {
         let requirements = config
             .env_requirements_by_model
             .get(&model)
@@ -296,6 +403,7 @@ fn model_rank(model: &ModelKind) -> u8 {
         ModelKind::Claude => 0,
         ModelKind::Codex => 1,
         ModelKind::Gemini => 2,
+        ModelKind::Custom(_) => 3,
     }
 }
 
@@ -304,10 +412,11 @@ fn default_executable_for_model(model: ModelKind) -> &'static str {
         ModelKind::Claude => "claude",
         ModelKind::Codex => "codex",
         ModelKind::Gemini => "gemini",
+        ModelKind::Custom(name) => name.as_str(),
     }
 }
 
-use crate::util::shell_quote;
+use orch_core::shell::shell_quote;
 
 #[cfg(test)]
 mod tests {
@@ -316,10 +425,13 @@ mod tests {
         ModelSetupSelection, SetupCommandRunner, SetupError, SetupProbeConfig,
         ValidatedSetupSelection,
     };
+    use orch_core::config::{CustomModelConfig, CustomOutputFormat, OrgConfig};
     use orch_core::types::ModelKind;
     use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
 
-    #[derive(Default)]
+    #[derive(Clone, Default)]
     struct MockRunner {
         installed: HashMap<String, bool>,
         versions: HashMap<String, Result<String, String>>,
@@ -354,7 +466,7 @@ mod tests {
             .env_present
             .insert("OPENAI_API_KEY".to_string(), false);
 
-        let report = probe_models_with_runner(&SetupProbeConfig::default(), &runner);
+        let report = probe_models_with_runner(&SetupProbeConfig::default(), Arc::new(runner));
         let codex = report
             .models
             .iter()
@@ -380,7 +492,7 @@ mod tests {
             .env_present
             .insert("ANTHROPIC_API_KEY".to_string(), true);
 
-        let report = probe_models_with_runner(&SetupProbeConfig::default(), &runner);
+        let report = probe_models_with_runner(&SetupProbeConfig::default(), Arc::new(runner));
         let claude = report
             .models
             .iter()
@@ -390,6 +502,38 @@ mod tests {
         assert!(claude.healthy);
     }
 
+    #[test]
+    fn probe_includes_custom_models_after_builtins() {
+        let mut models = OrgConfig::default().models;
+        models.custom.insert(
+            "aider".to_string(),
+            CustomModelConfig {
+                command: "aider".to_string(),
+                args: Vec::new(),
+                api_key_env: Some("AIDER_KEY".to_string()),
+                output_format: CustomOutputFormat::Text,
+            },
+        );
+        let mut runner = MockRunner::default();
+        runner.installed.insert("aider".to_string(), true);
+        runner
+            .versions
+            .insert("aider".to_string(), Ok("aider 0.50.0".to_string()));
+
+        let config = SetupProbeConfig::default().with_custom_models(&models);
+        let report = probe_models_with_runner(&config, Arc::new(runner.clone()));
+        let aider = report.models.last().cloned().expect("aider probe missing");
+
+        assert_eq!(aider.model.as_str(), "aider");
+        assert!(aider.installed && aider.version_ok);
+        assert!(!aider.healthy, "AIDER_KEY is unset");
+        assert_eq!(aider.env_status[0].any_of, vec!["AIDER_KEY".to_string()]);
+
+        runner.env_present.insert("AIDER_KEY".to_string(), true);
+        let report = probe_models_with_runner(&config, Arc::new(runner));
+        assert!(report.models.last().is_some_and(|probe| probe.healthy));
+    }
+
     #[test]
     fn validate_selection_rejects_unhealthy_selected_model() {
         let report = super::SetupProbeReport {
@@ -505,7 +649,7 @@ mod tests {
             .env_present
             .insert("GEMINI_API_KEY".to_string(), true);
 
-        let report = probe_models_with_runner(&config, &runner);
+        let report = probe_models_with_runner(&config, Arc::new(runner));
 
         let claude = report
             .models
@@ -597,7 +741,7 @@ mod tests {
             .env_present
             .insert("GOOGLE_API_KEY".to_string(), true);
 
-        let report = probe_models_with_runner(&SetupProbeConfig::default(), &runner);
+        let report = probe_models_with_runner(&SetupProbeConfig::default(), Arc::new(runner));
         let gemini = report
             .models
             .iter()
@@ -686,4 +830,93 @@ mod tests {
         let validated = validate_setup_selection(&report, &selection).expect("valid selection");
         assert_eq!(validated.enabled_models, vec![ModelKind::Codex]);
     }
+
+    /// Answers immediately except for `--version` on `hung` CLIs, which
+    /// blocks like a CLI waiting for an interactive login.
+    struct SleepyRunner {
+        hung: Vec<&'static str>,
+    }
+
+    impl SetupCommandRunner for SleepyRunner {
+        fn command_exists(&self, _executable: &str) -> bool {
+            true
+        }
+
+        fn command_version(&self, executable: &str) -> Result<String, String> {
+            if self.hung.contains(&executable) {
+                std::thread::sleep(Duration::from_secs(30));
+            }
+            Ok(format!("{executable} 1.0.0"))
+        }
+
+        fn env_var_present(&self, _env_key: &str) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn hung_probe_times_out_instead_of_blocking() {
+        let config = SetupProbeConfig {
+            probe_timeout_secs: 1,
+            ..SetupProbeConfig::default()
+        };
+        let started = Instant::now();
+        let report = probe_models_with_runner(
+            &config,
+            Arc::new(SleepyRunner {
+                hung: vec!["codex", "gemini"],
+            }),
+        );
+
+        // Both hung CLIs share the one-second window when probed in parallel.
+        assert!(started.elapsed() < Duration::from_millis(1900));
+        let codex = report
+            .models
+            .iter()
+            .find(|m| m.model == ModelKind::Codex)
+            .expect("codex probe missing");
+        assert!(codex.installed);
+        assert!(!codex.version_ok);
+        assert!(!codex.healthy);
+        assert_eq!(
+            codex.version_output.as_deref(),
+            Some("probe timed out after 1s")
+        );
+        let claude = report
+            .models
+            .iter()
+            .find(|m| m.model == ModelKind::Claude)
+            .expect("claude probe missing");
+        assert!(claude.healthy);
+        assert_eq!(claude.version_output.as_deref(), Some("claude 1.0.0"));
+    }
+
+    #[test]
+    fn serial_probing_times_out_each_model_separately() {
+        let config = SetupProbeConfig {
+            probe_timeout_secs: 1,
+            parallel: false,
+            ..SetupProbeConfig::default()
+        };
+        let report = probe_models_with_runner(
+            &config,
+            Arc::new(SleepyRunner {
+                hung: vec!["claude"],
+            }),
+        );
+
+        let healthy = report
+            .models
+            .iter()
+            .map(|m| (m.model, m.healthy))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            healthy,
+            vec![
+                (ModelKind::Claude, false),
+                (ModelKind::Codex, true),
+                (ModelKind::Gemini, true),
+            ]
+        );
+    }
 }
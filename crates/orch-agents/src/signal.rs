@@ -1,6 +1,9 @@
+use std::borrow::Cow;
+
 use chrono::Utc;
 
-use crate::types::{AgentSignal, AgentSignalKind};
+use crate::signal_rules::SignalRuleSet;
+use crate::types::AgentSignal;
 
 /// Returns true for lines that are part of diff or structured output (not agent prose).
 fn is_structured_output_line(line: &str) -> bool {
@@ -14,6 +17,11 @@ fn is_structured_output_line(line: &str) -> bool {
 }
 
 pub fn detect_common_signal(line: &str) -> Option<AgentSignal> {
+    detect_signal_with_rules(line, SignalRuleSet::builtin())
+}
+
+/// Like `detect_common_signal`, but matching `rules` instead of the built-ins.
+pub fn detect_signal_with_rules(line: &str, rules: &SignalRuleSet) -> Option<AgentSignal> {
     // Skip diff / structured output lines — they may contain signal-like substrings.
     if is_structured_output_line(line) {
         return None;
@@ -26,65 +34,86 @@ pub fn detect_common_signal(line: &str) -> Option<AgentSignal> {
         return None;
     }
 
-    let kind = if let Some(tag) = parse_bracket_signal_tag(&lower) {
-        match tag {
-            "needs_human" | "need_human" => Some(AgentSignalKind::NeedHuman),
-            "patch_ready" => Some(AgentSignalKind::PatchReady),
-            "qa_complete" => Some(AgentSignalKind::QAComplete),
-            "conflict_resolved" => Some(AgentSignalKind::ConflictResolved),
-            _ => None,
-        }
-    } else if lower.contains("rate limit")
-        || lower.contains("rate_limit")
-        || lower.contains("too many requests")
-    {
-        Some(AgentSignalKind::RateLimited)
-    } else if lower.contains("error:") || lower.contains("fatal:") || lower.contains("traceback") {
-        Some(AgentSignalKind::ErrorHint)
-    } else {
-        None
-    };
-
-    kind.map(|kind| AgentSignal {
-        kind,
+    rules.first_match(line).map(|rule| AgentSignal {
+        kind: rule.kind,
         at: Utc::now(),
         message: line.trim().to_string(),
         source_line: line.to_string(),
     })
 }
 
-/// Parses a control tag only when it appears as an explicit signal, not as a
-/// substring in code/output. Accepted shapes:
-/// - `[patch_ready]`
-/// - `status: [patch_ready] ...`
-fn parse_bracket_signal_tag(lower_line: &str) -> Option<&str> {
-    let trimmed = lower_line.trim();
-
-    let candidate = if trimmed.starts_with('[') {
-        trimmed
-    } else if let Some((_, after_colon)) = trimmed.split_once(':') {
-        after_colon.trim_start()
-    } else {
+/// Parses a `CONFIDENCE: <score>` marker into whole percent.
+///
+/// The marker has to open the line, after whitespace and markdown emphasis,
+/// and the score must be a fraction in `0..=1` or a percentage ending in `%`.
+/// Anything else, such as the `<0.0-1.0>` placeholder in an echoed prompt or
+/// a score followed by prose, is not a marker.
+pub fn parse_confidence_marker(line: &str) -> Option<u8> {
+    if is_structured_output_line(line) {
         return None;
-    };
-
-    if !candidate.starts_with('[') {
+    }
+    let line = strip_csi(line);
+    let trimmed = line
+        .trim()
+        .trim_start_matches(['*', '_', '`', '>', '#', ' ']);
+    let (key, value) = trimmed.split_once(':')?;
+    if !key
+        .trim_end_matches(['*', '_', '`'])
+        .eq_ignore_ascii_case("confidence")
+    {
         return None;
     }
 
-    let end = candidate.find(']')?;
-    let tag = &candidate[1..end];
-    if tag.is_empty() {
-        return None;
+    let value = value.trim().trim_matches(['*', '_', '`']).trim();
+    let percent = match value.strip_suffix('%') {
+        Some(percent) => percent
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|percent| (0.0..=100.0).contains(percent))?,
+        None => {
+            value
+                .parse::<f64>()
+                .ok()
+                .filter(|fraction| (0.0..=1.0).contains(fraction))?
+                * 100.0
+        }
+    };
+    Some(percent.round() as u8)
+}
+
+/// The last confidence marker in `lines`, so a later score overrides one
+/// printed mid-run.
+pub fn last_confidence_marker<'a>(lines: impl IntoIterator<Item = &'a str>) -> Option<u8> {
+    lines.into_iter().filter_map(parse_confidence_marker).last()
+}
+
+/// Drops ANSI CSI sequences (colors, cursor moves) from PTY output.
+fn strip_csi(line: &str) -> Cow<'_, str> {
+    if !line.contains('\u{1b}') {
+        return Cow::Borrowed(line);
     }
-    Some(tag)
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            out.push(c);
+        } else if chars.next() == Some('[') {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        }
+    }
+    Cow::Owned(out)
 }
 
 #[cfg(test)]
 mod tests {
     use crate::types::AgentSignalKind;
 
-    use super::detect_common_signal;
+    use super::{detect_common_signal, last_confidence_marker, parse_confidence_marker};
 
     #[test]
     fn detects_need_human_variants() {
@@ -214,4 +243,57 @@ mod tests {
         assert!(detect_common_signal("assert!(prompt.contains(\"[patch_ready]\"));").is_none());
         assert!(detect_common_signal("let marker = \"[needs_human]\"; // test fixture").is_none());
     }
+
+    #[test]
+    fn parses_confidence_markers_as_whole_percent() {
+        assert_eq!(parse_confidence_marker("CONFIDENCE: 0.8"), Some(80));
+        assert_eq!(parse_confidence_marker("  confidence:1"), Some(100));
+        assert_eq!(parse_confidence_marker("**Confidence:** 0.65"), Some(65));
+        assert_eq!(parse_confidence_marker("> CONFIDENCE: 72%"), Some(72));
+        assert_eq!(
+            parse_confidence_marker("\u{1b}[32mCONFIDENCE: 0.9\u{1b}[0m"),
+            Some(90)
+        );
+    }
+
+    #[test]
+    fn malformed_confidence_markers_are_unknown() {
+        for line in [
+            "CONFIDENCE: <0.0-1.0>",
+            "CONFIDENCE: high",
+            "CONFIDENCE: 1.5",
+            "CONFIDENCE: -0.2",
+            "CONFIDENCE: 140%",
+            "CONFIDENCE: NaN",
+            "CONFIDENCE: 0.8 (tests were flaky)",
+            "CONFIDENCE:",
+            "+CONFIDENCE: 0.9",
+            "--- CONFIDENCE: 0.9",
+            "my confidence: 0.9",
+            "Print your confidence as `CONFIDENCE: <0.0-1.0>`",
+        ] {
+            assert_eq!(parse_confidence_marker(line), None, "{line:?}");
+        }
+    }
+
+    #[test]
+    fn last_confidence_marker_survives_noisy_output() {
+        let log = "\
+Print your confidence as `CONFIDENCE: <0.0-1.0>` before [patch_ready]
+CONFIDENCE: <0.0-1.0>
+running cargo test
+CONFIDENCE: 0.4
+error: flaky test, retrying
+diff --git a/notes.md b/notes.md
++CONFIDENCE: 0.99
+test result: ok
+**Confidence:** 0.85
+[patch_ready]
+trailing tool output";
+        assert_eq!(last_confidence_marker(log.lines()), Some(85));
+
+        let mid_log = "CONFIDENCE: 0.3\nstill working\nCONFIDENCE: unsure\ndone";
+        assert_eq!(last_confidence_marker(mid_log.lines()), Some(30));
+        assert_eq!(last_confidence_marker("no marker here".lines()), None);
+    }
 }
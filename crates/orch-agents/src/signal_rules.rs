@@ -0,0 +1,410 @@
+//! Regex rules that map agent output lines to `AgentSignalKind`s.
+//!
+//! The built-in rules mirror the markers Othala's prompts ask agents to print.
+//! Repos can add or override rules in `.othala/signals.toml`:
+//!
+//! ```toml
+//! [[rule]]
+//! name = "claude_done"
+//! pattern = "^All changes committed"
+//! kind = "patch_ready"
+//! priority = 120
+//! ```
+//!
+//! A custom rule named like a built-in one replaces it. When several rules
+//! match a line, the highest priority wins; ties go to the rule listed first,
+//! with custom rules ahead of the built-ins.
+
+use std::path::Path;
+use std::sync::OnceLock;
+
+use regex::{Regex, RegexBuilder};
+use serde::Deserialize;
+
+use crate::types::AgentSignalKind;
+
+/// Repo-relative path of the custom rule file.
+pub const SIGNAL_RULES_PATH: &str = ".othala/signals.toml";
+
+const DEFAULT_PRIORITY: i32 = 50;
+
+#[derive(Debug, Clone)]
+pub struct SignalRule {
+    pub name: String,
+    pub pattern: Regex,
+    pub kind: AgentSignalKind,
+    pub priority: i32,
+    /// False for the rules Othala ships with.
+    pub custom: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct SignalRuleSet {
+    rules: Vec<SignalRule>,
+}
+
+/// A rule in `.othala/signals.toml` that could not be loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignalRuleIssue {
+    pub line: usize,
+    pub name: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for SignalRuleIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}: rule '{}': {}",
+            self.line, self.name, self.message
+        )
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SignalRulesError {
+    #[error("failed to read {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("invalid signal rules: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("invalid signal rules:\n{}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n"))]
+    Invalid(Vec<SignalRuleIssue>),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawRuleFile {
+    #[serde(default)]
+    rule: Vec<RawRule>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawRule {
+    name: toml::Spanned<String>,
+    pattern: toml::Spanned<String>,
+    kind: AgentSignalKind,
+    #[serde(default = "default_priority")]
+    priority: i32,
+}
+
+fn default_priority() -> i32 {
+    DEFAULT_PRIORITY
+}
+
+/// Marker tag rule: `[tag]` opening the line or right after its first colon.
+fn bracket_tag(tags: &str) -> String {
+    format!(r"^\s*(?:[^\[:][^:]*:\s*)?\[(?:{tags})\]")
+}
+
+fn builtin_rules() -> Vec<SignalRule> {
+    let rule = |name: &str, pattern: &str, kind, priority| SignalRule {
+        name: name.to_string(),
+        pattern: RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+            .expect("built-in signal pattern"),
+        kind,
+        priority,
+        custom: false,
+    };
+    vec![
+        rule(
+            "need_human",
+            &bracket_tag("needs_human|need_human"),
+            AgentSignalKind::NeedHuman,
+            100,
+        ),
+        rule(
+            "patch_ready",
+            &bracket_tag("patch_ready"),
+            AgentSignalKind::PatchReady,
+            100,
+        ),
+        rule(
+            "qa_complete",
+            &bracket_tag("qa_complete"),
+            AgentSignalKind::QAComplete,
+            100,
+        ),
+        rule(
+            "conflict_resolved",
+            &bracket_tag("conflict_resolved"),
+            AgentSignalKind::ConflictResolved,
+            100,
+        ),
+        rule(
+            "rate_limited",
+            r"rate[ _]limit|too many requests",
+            AgentSignalKind::RateLimited,
+            50,
+        ),
+        rule(
+            "error_hint",
+            r"error:|fatal:|traceback",
+            AgentSignalKind::ErrorHint,
+            10,
+        ),
+    ]
+}
+
+impl Default for SignalRuleSet {
+    fn default() -> Self {
+        Self::builtin().clone()
+    }
+}
+
+impl SignalRuleSet {
+    /// The rules Othala ships with, compiled once.
+    pub fn builtin() -> &'static SignalRuleSet {
+        static BUILTIN: OnceLock<SignalRuleSet> = OnceLock::new();
+        BUILTIN.get_or_init(|| SignalRuleSet {
+            rules: builtin_rules(),
+        })
+    }
+
+    /// Parse custom rules from TOML and merge them over the built-ins. Every
+    /// bad rule is reported, with the line it starts on.
+    pub fn parse(raw: &str) -> Result<Self, SignalRulesError> {
+        let file: RawRuleFile = toml::from_str(raw)?;
+        let line_of = |offset: usize| raw[..offset].matches('\n').count() + 1;
+
+        let mut custom = Vec::new();
+        let mut issues = Vec::new();
+        for rule in file.rule {
+            let name = rule.name.get_ref().trim().to_string();
+            if name.is_empty() {
+                issues.push(SignalRuleIssue {
+                    line: line_of(rule.name.span().start),
+                    name,
+                    message: "name must not be empty".to_string(),
+                });
+                continue;
+            }
+            if custom
+                .iter()
+                .any(|existing: &SignalRule| existing.name == name)
+            {
+                issues.push(SignalRuleIssue {
+                    line: line_of(rule.name.span().start),
+                    name,
+                    message: "duplicate rule name".to_string(),
+                });
+                continue;
+            }
+            match Regex::new(rule.pattern.get_ref()) {
+                Ok(pattern) => custom.push(SignalRule {
+                    name,
+                    pattern,
+                    kind: rule.kind,
+                    priority: rule.priority,
+                    custom: true,
+                }),
+                Err(err) => issues.push(SignalRuleIssue {
+                    line: line_of(rule.pattern.span().start),
+                    name,
+                    message: format!("invalid pattern: {err}"),
+                }),
+            }
+        }
+        if !issues.is_empty() {
+            return Err(SignalRulesError::Invalid(issues));
+        }
+
+        Ok(Self::with_custom(custom))
+    }
+
+    /// Load `.othala/signals.toml` under `repo_root`; the built-ins alone
+    /// when the file doesn't exist.
+    pub fn load(repo_root: &Path) -> Result<Self, SignalRulesError> {
+        let path = repo_root.join(SIGNAL_RULES_PATH);
+        match std::fs::read_to_string(&path) {
+            Ok(raw) => Self::parse(&raw),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(source) => Err(SignalRulesError::Io {
+                path: path.display().to_string(),
+                source,
+            }),
+        }
+    }
+
+    fn with_custom(custom: Vec<SignalRule>) -> Self {
+        let builtins = Self::builtin()
+            .rules
+            .iter()
+            .filter(|builtin| !custom.iter().any(|rule| rule.name == builtin.name))
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut rules = custom;
+        rules.extend(builtins);
+        // Stable, so equal priorities keep custom-before-built-in order.
+        rules.sort_by_key(|rule| std::cmp::Reverse(rule.priority));
+        Self { rules }
+    }
+
+    /// Rules in the order they are tried.
+    pub fn rules(&self) -> &[SignalRule] {
+        &self.rules
+    }
+
+    /// Every rule matching `line`, winner first.
+    pub fn matching<'a>(&'a self, line: &'a str) -> impl Iterator<Item = &'a SignalRule> + 'a {
+        self.rules
+            .iter()
+            .filter(move |rule| rule.pattern.is_match(line))
+    }
+
+    /// The rule that decides `line`'s signal, if any.
+    pub fn first_match(&self, line: &str) -> Option<&SignalRule> {
+        self.rules.iter().find(|rule| rule.pattern.is_match(line))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_rules_merge_over_builtins_by_name_and_priority() {
+        let rules = SignalRuleSet::parse(
+            r#"
+[[rule]]
+name = "claude_done"
+pattern = "^All changes committed"
+kind = "patch_ready"
+priority = 120
+
+[[rule]]
+name = "error_hint"
+pattern = "^ERR "
+kind = "error_hint"
+priority = 10
+
+[[rule]]
+name = "quota"
+pattern = "quota exceeded"
+kind = "rate_limited"
+"#,
+        )
+        .expect("parse rules");
+
+        let names: Vec<_> = rules
+            .rules()
+            .iter()
+            .map(|rule| rule.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "claude_done",
+                "need_human",
+                "patch_ready",
+                "qa_complete",
+                "conflict_resolved",
+                "quota",
+                "rate_limited",
+                "error_hint",
+            ]
+        );
+        let error_hint = &rules.rules()[7];
+        assert!(error_hint.custom);
+        assert!(error_hint.pattern.is_match("ERR boom"));
+        assert!(!error_hint.pattern.is_match("fatal: boom"));
+        assert_eq!(rules.rules()[5].priority, DEFAULT_PRIORITY);
+    }
+
+    #[test]
+    fn higher_priority_rule_wins_when_several_match() {
+        let rules = SignalRuleSet::parse(
+            r#"
+[[rule]]
+name = "stuck"
+pattern = "waiting for input"
+kind = "need_human"
+priority = 5
+"#,
+        )
+        .expect("parse rules");
+
+        let line = "error: waiting for input";
+        let matched: Vec<_> = rules
+            .matching(line)
+            .map(|rule| rule.name.as_str())
+            .collect();
+        assert_eq!(matched, vec!["error_hint", "stuck"]);
+        assert_eq!(
+            rules.first_match(line).map(|rule| rule.kind),
+            Some(AgentSignalKind::ErrorHint)
+        );
+    }
+
+    #[test]
+    fn invalid_rules_are_reported_with_line_numbers() {
+        let err = SignalRuleSet::parse(
+            r#"[[rule]]
+name = "ok"
+pattern = "fine"
+kind = "patch_ready"
+
+[[rule]]
+name = "broken"
+pattern = "(unclosed"
+kind = "patch_ready"
+
+[[rule]]
+name = "ok"
+pattern = "again"
+kind = "need_human"
+"#,
+        )
+        .expect_err("bad rules");
+
+        let SignalRulesError::Invalid(issues) = &err else {
+            panic!("expected validation issues, got {err}");
+        };
+        assert_eq!(issues.len(), 2);
+        assert_eq!((issues[0].line, issues[0].name.as_str()), (8, "broken"));
+        assert!(issues[0].message.starts_with("invalid pattern"));
+        assert_eq!((issues[1].line, issues[1].name.as_str()), (12, "ok"));
+        assert!(err.to_string().contains("line 8: rule 'broken'"));
+    }
+
+    #[test]
+    fn unknown_signal_kind_is_a_parse_error() {
+        let err =
+            SignalRuleSet::parse("[[rule]]\nname = \"x\"\npattern = \"x\"\nkind = \"finished\"\n")
+                .expect_err("unknown kind");
+        assert!(matches!(err, SignalRulesError::Parse(_)));
+        assert!(err.to_string().contains("line 4"), "{err}");
+    }
+
+    #[test]
+    fn load_falls_back_to_builtins_without_a_rule_file() {
+        let dir = std::env::temp_dir().join(format!(
+            "othala-signal-rules-{}-{}",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        std::fs::create_dir_all(dir.join(".othala")).expect("create dir");
+
+        let rules = SignalRuleSet::load(&dir).expect("load builtins");
+        assert_eq!(rules.rules().len(), SignalRuleSet::builtin().rules().len());
+
+        std::fs::write(
+            dir.join(SIGNAL_RULES_PATH),
+            "[[rule]]\nname = \"done\"\npattern = \"^DONE$\"\nkind = \"patch_ready\"\n",
+        )
+        .expect("write rules");
+        let rules = SignalRuleSet::load(&dir).expect("load custom");
+        assert_eq!(
+            rules.first_match("DONE").map(|rule| rule.name.as_str()),
+            Some("done")
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -2,6 +2,9 @@ use chrono::{DateTime, Utc};
 use orch_core::types::{ModelKind, RepoId, TaskId};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::sync::{mpsc, Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AgentCommand {
@@ -34,6 +37,20 @@ pub enum AgentSignalKind {
     QAComplete,
 }
 
+impl AgentSignalKind {
+    /// The serialized name, as written in `.othala/signals.toml`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AgentSignalKind::NeedHuman => "need_human",
+            AgentSignalKind::PatchReady => "patch_ready",
+            AgentSignalKind::ConflictResolved => "conflict_resolved",
+            AgentSignalKind::RateLimited => "rate_limited",
+            AgentSignalKind::ErrorHint => "error_hint",
+            AgentSignalKind::QAComplete => "qa_complete",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AgentSignal {
     pub kind: AgentSignalKind,
@@ -48,6 +65,77 @@ pub struct PtyChunk {
     pub text: String,
 }
 
+/// Delivers `PtyChunk`s from a running epoch to a subscriber.
+///
+/// The channel is bounded: a full one blocks the runner until the subscriber
+/// catches up. An optional byte-rate cap holds back bursts past the cap
+/// until the next one-second window, so a chatty agent can't flood the UI.
+#[derive(Debug, Clone)]
+pub struct ChunkSink {
+    tx: mpsc::SyncSender<PtyChunk>,
+    max_bytes_per_sec: Option<usize>,
+    window: Duration,
+    sent: Arc<Mutex<RateWindow>>,
+}
+
+#[derive(Debug)]
+struct RateWindow {
+    started: Instant,
+    bytes: usize,
+}
+
+impl ChunkSink {
+    /// A sink and the receiver for it, holding at most `capacity`
+    /// undelivered chunks.
+    pub fn bounded(capacity: usize) -> (Self, mpsc::Receiver<PtyChunk>) {
+        let (tx, rx) = mpsc::sync_channel(capacity.max(1));
+        let sink = Self {
+            tx,
+            max_bytes_per_sec: None,
+            window: Duration::from_secs(1),
+            sent: Arc::new(Mutex::new(RateWindow {
+                started: Instant::now(),
+                bytes: 0,
+            })),
+        };
+        (sink, rx)
+    }
+
+    /// Deliver at most `bytes` of chunk text per second.
+    pub fn with_max_bytes_per_sec(mut self, bytes: usize) -> Self {
+        self.max_bytes_per_sec = Some(bytes.max(1));
+        self
+    }
+
+    /// Deliver `chunk`, waiting for room in the channel and under the rate
+    /// cap. Returns `false` once the receiver has been dropped.
+    pub fn send(&self, chunk: PtyChunk) -> bool {
+        if let Some(cap) = self.max_bytes_per_sec {
+            let mut sent = self.sent.lock().unwrap_or_else(PoisonError::into_inner);
+            if sent.started.elapsed() >= self.window {
+                sent.started = Instant::now();
+                sent.bytes = 0;
+            }
+            // A single chunk larger than the cap still goes out on its own.
+            if sent.bytes > 0 && sent.bytes + chunk.text.len() > cap {
+                thread::sleep(self.window.saturating_sub(sent.started.elapsed()));
+                sent.started = Instant::now();
+                sent.bytes = 0;
+            }
+            sent.bytes += chunk.text.len();
+        }
+        self.tx.send(chunk).is_ok()
+    }
+
+    /// [`ChunkSink::send`] for one line of output read now.
+    pub fn send_line(&self, line: &str) -> bool {
+        self.send(PtyChunk {
+            at: Utc::now(),
+            text: line.to_string(),
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum EpochStopReason {
@@ -70,6 +158,10 @@ pub struct EpochResult {
     pub exit_code: Option<i32>,
     pub output: Vec<PtyChunk>,
     pub signals: Vec<AgentSignal>,
+    /// Self-reported confidence in whole percent, from the last
+    /// `CONFIDENCE:` marker in `output`; `None` when it is missing or malformed.
+    #[serde(default)]
+    pub confidence: Option<u8>,
 }
 
 #[cfg(test)]
@@ -77,7 +169,8 @@ mod tests {
     use chrono::Utc;
     use orch_core::types::{ModelKind, RepoId, TaskId};
 
-    use super::{AgentSignal, AgentSignalKind, EpochResult, EpochStopReason, PtyChunk};
+    use super::{AgentSignal, AgentSignalKind, ChunkSink, EpochResult, EpochStopReason, PtyChunk};
+    use std::time::{Duration, Instant};
 
     #[test]
     fn agent_signal_kind_serializes_in_snake_case() {
@@ -148,10 +241,42 @@ mod tests {
                 message: "[patch_ready]".to_string(),
                 source_line: "[patch_ready]".to_string(),
             }],
+            confidence: Some(80),
         };
 
         let encoded = serde_json::to_string(&result).expect("serialize");
         let decoded: EpochResult = serde_json::from_str(&encoded).expect("deserialize");
         assert_eq!(decoded, result);
     }
+
+    fn chunk(text: &str) -> PtyChunk {
+        PtyChunk {
+            at: Utc::now(),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn chunk_sink_is_bounded_and_reports_a_dropped_receiver() {
+        let (sink, rx) = ChunkSink::bounded(1);
+        assert!(sink.send(chunk("one")));
+        assert!(rx.try_recv().is_ok());
+
+        drop(rx);
+        assert!(!sink.send(chunk("two")));
+    }
+
+    #[test]
+    fn chunk_sink_holds_bursts_over_the_byte_rate_cap() {
+        let (mut sink, rx) = ChunkSink::bounded(8);
+        sink = sink.with_max_bytes_per_sec(10);
+        sink.window = Duration::from_millis(200);
+
+        let started = Instant::now();
+        assert!(sink.send(chunk("12345678")));
+        assert!(started.elapsed() < Duration::from_millis(150));
+        assert!(sink.send(chunk("12345678")));
+        assert!(started.elapsed() >= Duration::from_millis(150));
+        assert_eq!(rx.try_iter().count(), 2);
+    }
 }
@@ -1,7 +1,7 @@
 //! Configuration types for the MVP orchestrator.
 
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -118,22 +118,26 @@ impl Default for OrgConfig {
         Self {
             profile: None,
             models: ModelsConfig {
-                enabled: vec![ModelKind::Claude, ModelKind::Codex, ModelKind::Gemini],
+                enabled: ModelKind::all().to_vec(),
                 default: Some(ModelKind::Claude),
+                custom: BTreeMap::new(),
             },
             concurrency: ConcurrencyConfig {
                 per_repo: 10,
                 claude: 10,
                 codex: 10,
                 gemini: 10,
+                custom: BTreeMap::new(),
             },
             graphite: GraphiteOrgConfig {
                 auto_submit: true,
                 submit_mode_default: SubmitMode::Single,
                 allow_move: MovePolicy::Manual,
+                auto_resolve_conflicts: false,
             },
             ui: UiConfig {
                 web_bind: "127.0.0.1:9842".to_string(),
+                web_auth_token: None,
             },
             notifications: NotificationConfig::default(),
             daemon: DaemonOrgConfig::default(),
@@ -227,21 +231,256 @@ pub struct DaemonOrgConfig {
     pub tick_interval_secs: u64,
     #[serde(default = "default_agent_timeout")]
     pub agent_timeout_secs: u64,
+    /// Shell command run once when a task transitions to `Merged`.
+    #[serde(default)]
+    pub post_merge_hook: Option<String>,
+    /// Verify command for task worktrees; `--verify-command` overrides it.
+    #[serde(default)]
+    pub verify_command: Option<String>,
+    #[serde(default)]
+    pub retry_guard: RetryGuardConfig,
+    #[serde(default)]
+    pub pipeline_timeouts: PipelineTimeoutsConfig,
+    /// Escalate agent questions left unanswered this long, in seconds.
+    #[serde(default = "default_question_stale_secs")]
+    pub question_stale_secs: u64,
+    #[serde(default)]
+    pub context_gen: ContextGenOrgConfig,
+    #[serde(default)]
+    pub merge_poll: MergePollConfig,
+    #[serde(default)]
+    pub adaptive_tick: AdaptiveTickConfig,
+    /// Agent output the supervisor drains per session per tick, in bytes.
+    /// Anything beyond it waits for the next tick; the full stream is kept
+    /// only in the on-disk agent log.
+    #[serde(default = "default_output_buffer_bytes")]
+    pub output_buffer_bytes: usize,
+    /// Minimum self-reported agent confidence, in percent, for the daemon to
+    /// approve a finished task on its own. Tasks below it, or without a
+    /// confidence marker, wait for a manual approve. Unset approves on
+    /// success alone.
+    #[serde(default)]
+    pub auto_approve_min_confidence: Option<u8>,
+    /// New agents the daemon starts per tick. Ready tasks beyond it wait for
+    /// later ticks, highest priority first; running agents are unaffected.
+    #[serde(default = "default_max_spawns_per_tick")]
+    pub max_spawns_per_tick: usize,
+    /// Ask a model for a QA spec from the task and its diff when a finished
+    /// task has none, and validate against it.
+    #[serde(default = "default_generate_qa_specs")]
+    pub generate_qa_specs: bool,
 }
 
 fn default_tick_interval() -> u64 {
     2
 }
 
+fn default_max_spawns_per_tick() -> usize {
+    2
+}
+
+fn default_generate_qa_specs() -> bool {
+    true
+}
+
 fn default_agent_timeout() -> u64 {
     1_800
 }
 
+fn default_question_stale_secs() -> u64 {
+    1_800
+}
+
+fn default_output_buffer_bytes() -> usize {
+    4 * 1024 * 1024
+}
+
 impl Default for DaemonOrgConfig {
     fn default() -> Self {
         Self {
             tick_interval_secs: default_tick_interval(),
             agent_timeout_secs: default_agent_timeout(),
+            post_merge_hook: None,
+            verify_command: None,
+            retry_guard: RetryGuardConfig::default(),
+            pipeline_timeouts: PipelineTimeoutsConfig::default(),
+            question_stale_secs: default_question_stale_secs(),
+            context_gen: ContextGenOrgConfig::default(),
+            merge_poll: MergePollConfig::default(),
+            adaptive_tick: AdaptiveTickConfig::default(),
+            output_buffer_bytes: default_output_buffer_bytes(),
+            auto_approve_min_confidence: None,
+            max_spawns_per_tick: default_max_spawns_per_tick(),
+            generate_qa_specs: default_generate_qa_specs(),
+        }
+    }
+}
+
+/// Polling fallback that notices merges for tasks stuck in `AwaitingMerge`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MergePollConfig {
+    /// Only poll tasks that have been awaiting merge at least this long.
+    #[serde(default = "default_merge_poll_min_age_secs")]
+    pub min_age_secs: u64,
+    /// Seconds between polls of the same task.
+    #[serde(default = "default_merge_poll_interval_secs")]
+    pub interval_secs: u64,
+    /// Flag the task for a human once it has waited this long.
+    #[serde(default = "default_merge_poll_stale_secs")]
+    pub stale_secs: u64,
+    /// Also ask the GitHub API (through `gh`) whether the PR was merged.
+    #[serde(default)]
+    pub github_api: bool,
+}
+
+fn default_merge_poll_min_age_secs() -> u64 {
+    600
+}
+
+fn default_merge_poll_interval_secs() -> u64 {
+    300
+}
+
+fn default_merge_poll_stale_secs() -> u64 {
+    259_200
+}
+
+impl Default for MergePollConfig {
+    fn default() -> Self {
+        Self {
+            min_age_secs: default_merge_poll_min_age_secs(),
+            interval_secs: default_merge_poll_interval_secs(),
+            stale_secs: default_merge_poll_stale_secs(),
+            github_api: false,
+        }
+    }
+}
+
+/// Sleep between daemon ticks that tracks load instead of
+/// `tick_interval_secs`: it drops to the floor while there is work and
+/// doubles toward the ceiling while idle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdaptiveTickConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_adaptive_tick_floor_ms")]
+    pub floor_ms: u64,
+    #[serde(default = "default_adaptive_tick_ceiling_ms")]
+    pub ceiling_ms: u64,
+}
+
+fn default_adaptive_tick_floor_ms() -> u64 {
+    500
+}
+
+fn default_adaptive_tick_ceiling_ms() -> u64 {
+    30_000
+}
+
+impl Default for AdaptiveTickConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            floor_ms: default_adaptive_tick_floor_ms(),
+            ceiling_ms: default_adaptive_tick_ceiling_ms(),
+        }
+    }
+}
+
+/// Settings for generating `.othala/context/`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextGenOrgConfig {
+    /// Model used for generation instead of the default model, e.g. a
+    /// cheaper or faster one.
+    #[serde(default)]
+    pub model: Option<ModelKind>,
+    /// Abort a generation that runs longer than this, in seconds.
+    #[serde(default = "default_context_gen_timeout")]
+    pub timeout_secs: u64,
+}
+
+fn default_context_gen_timeout() -> u64 {
+    600
+}
+
+impl Default for ContextGenOrgConfig {
+    fn default() -> Self {
+        Self {
+            model: None,
+            timeout_secs: default_context_gen_timeout(),
+        }
+    }
+}
+
+/// Thresholds for blocking retries that throw away previously passing work.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryGuardConfig {
+    #[serde(default = "default_retry_guard_enabled")]
+    pub enabled: bool,
+    /// Flag a retry once it drops at least this percentage of the hunks from
+    /// the last attempt whose verify passed.
+    #[serde(default = "default_retry_guard_max_removed_percent")]
+    pub max_removed_percent: u8,
+    /// Prior patches with fewer hunks than this are too small to judge.
+    #[serde(default = "default_retry_guard_min_prior_hunks")]
+    pub min_prior_hunks: usize,
+}
+
+fn default_retry_guard_enabled() -> bool {
+    true
+}
+
+fn default_retry_guard_max_removed_percent() -> u8 {
+    50
+}
+
+fn default_retry_guard_min_prior_hunks() -> usize {
+    2
+}
+
+impl Default for RetryGuardConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_removed_percent: default_retry_guard_max_removed_percent(),
+            min_prior_hunks: default_retry_guard_min_prior_hunks(),
+        }
+    }
+}
+
+/// Per-step limits for the submit pipeline; a step that overruns stops the
+/// task for human review.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PipelineTimeoutsConfig {
+    /// Limit for each verify step, in seconds.
+    #[serde(default = "default_pipeline_verify_timeout")]
+    pub verify_secs: u64,
+    /// Limit for restacking onto the parent branch, in seconds.
+    #[serde(default = "default_pipeline_stack_timeout")]
+    pub stack_secs: u64,
+    /// Limit for the Graphite submit, in seconds.
+    #[serde(default = "default_pipeline_submit_timeout")]
+    pub submit_secs: u64,
+}
+
+fn default_pipeline_verify_timeout() -> u64 {
+    1_800
+}
+
+fn default_pipeline_stack_timeout() -> u64 {
+    300
+}
+
+fn default_pipeline_submit_timeout() -> u64 {
+    600
+}
+
+impl Default for PipelineTimeoutsConfig {
+    fn default() -> Self {
+        Self {
+            verify_secs: default_pipeline_verify_timeout(),
+            stack_secs: default_pipeline_stack_timeout(),
+            submit_secs: default_pipeline_submit_timeout(),
         }
     }
 }
@@ -254,6 +493,12 @@ pub struct NotificationConfig {
     pub slack_webhook_url: Option<String>,
     #[serde(default)]
     pub slack_channel: Option<String>,
+    /// Env var holding the Telegram bot token.
+    #[serde(default)]
+    pub telegram_bot_token_env: Option<String>,
+    /// Env var holding the Telegram chat id.
+    #[serde(default)]
+    pub telegram_chat_id_env: Option<String>,
     pub stdout: bool,
 }
 
@@ -264,6 +509,8 @@ impl Default for NotificationConfig {
             webhook_url: None,
             slack_webhook_url: None,
             slack_channel: None,
+            telegram_bot_token_env: None,
+            telegram_chat_id_env: None,
             stdout: true,
         }
     }
@@ -276,6 +523,63 @@ pub struct ModelsConfig {
     /// Default model to use for new chats
     #[serde(default)]
     pub default: Option<ModelKind>,
+    /// Extra CLI tools keyed by model name, from `[models.custom.<name>]`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom: BTreeMap<String, CustomModelConfig>,
+}
+
+impl ModelsConfig {
+    /// The custom models, in name order. Names that are not valid model
+    /// names are skipped; validation reports them.
+    pub fn custom_models(&self) -> Vec<(ModelKind, &CustomModelConfig)> {
+        self.custom
+            .iter()
+            .filter_map(|(name, config)| {
+                let model = ModelKind::from_name(name).ok().filter(|m| m.is_custom())?;
+                Some((model, config))
+            })
+            .collect()
+    }
+
+    /// A built-in model, or a custom model defined in this config. Use this
+    /// for client input: unknown names are rejected before being interned.
+    pub fn resolve(&self, name: &str) -> Option<ModelKind> {
+        let name = name.trim();
+        ModelKind::builtin(name).or_else(|| {
+            if !self.custom.contains_key(name) {
+                return None;
+            }
+            ModelKind::from_name(name).ok().filter(|m| m.is_custom())
+        })
+    }
+}
+
+/// How a custom model's CLI writes its output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomOutputFormat {
+    /// Plain text; signal markers are matched per line.
+    #[default]
+    Text,
+    /// One JSON object per line; markers are matched in its text field.
+    Json,
+}
+
+/// A CLI agent that is not built in, such as `aider` or an internal tool
+/// speaking an OpenAI-compatible API.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomModelConfig {
+    /// Executable to run.
+    pub command: String,
+    /// Argument template. `{prompt}` and `{repo}` are substituted; when no
+    /// argument mentions `{prompt}` the prompt is appended last.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Environment variable holding the API key, checked by `othala wizard`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_env: Option<String>,
+    #[serde(default)]
+    pub output_format: CustomOutputFormat,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -284,6 +588,24 @@ pub struct ConcurrencyConfig {
     pub claude: usize,
     pub codex: usize,
     pub gemini: usize,
+    /// Per-model limits for custom models. Unlisted ones share the repo limit.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom: BTreeMap<String, usize>,
+}
+
+impl ConcurrencyConfig {
+    pub fn limit_for(&self, model: ModelKind) -> usize {
+        match model {
+            ModelKind::Claude => self.claude,
+            ModelKind::Codex => self.codex,
+            ModelKind::Gemini => self.gemini,
+            ModelKind::Custom(name) => self
+                .custom
+                .get(name.as_str())
+                .copied()
+                .unwrap_or(self.per_repo),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -297,11 +619,27 @@ pub struct GraphiteOrgConfig {
     pub auto_submit: bool,
     pub submit_mode_default: SubmitMode,
     pub allow_move: MovePolicy,
+    /// Let the task's agent try to resolve restack conflicts before a human
+    /// is asked.
+    #[serde(default)]
+    pub auto_resolve_conflicts: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct UiConfig {
     pub web_bind: String,
+    /// Bearer token required by the web API. Generated by `othala wizard`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub web_auth_token: Option<String>,
+}
+
+/// Generate a random 256-bit token, hex encoded, for `ui.web_auth_token`.
+pub fn generate_web_auth_token() -> std::io::Result<String> {
+    use std::io::Read;
+
+    let mut bytes = [0u8; 32];
+    fs::File::open("/dev/urandom")?.read_exact(&mut bytes)?;
+    Ok(bytes.iter().map(|byte| format!("{byte:02x}")).collect())
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -338,6 +676,87 @@ impl NixConfig {
 pub struct VerifyConfig {
     /// Command to run for verification (e.g., "cargo check && cargo test")
     pub command: String,
+    /// Commands for the `fast` tier run after each agent epoch; empty means
+    /// per-language defaults for the changed files.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fast_commands: Vec<String>,
+    #[serde(flatten)]
+    pub limits: VerifyLimitsConfig,
+}
+
+/// Time and output limits for a verify command, with per-tier overrides.
+///
+/// ```toml
+/// [verify]
+/// command = "cargo test"
+/// timeout_secs = 900
+///
+/// [verify.tiers.stack]
+/// timeout_secs = 1800
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyLimitsConfig {
+    /// Kill the command after this many seconds.
+    #[serde(default = "default_verify_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Captured bytes kept per output stream; the rest is dropped.
+    #[serde(default = "default_verify_max_output_bytes")]
+    pub max_output_bytes: usize,
+    /// Overrides keyed by tier: `fast` for the per-epoch check, `branch` for
+    /// a task's own branch, `stack` for the restacked branch before submit.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tiers: BTreeMap<String, VerifyTierLimits>,
+}
+
+/// Limits for one verify tier; unset fields fall back to `[verify]`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyTierLimits {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_bytes: Option<usize>,
+}
+
+/// Effective limits for one verify run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifyLimits {
+    pub timeout_secs: u64,
+    pub max_output_bytes: usize,
+}
+
+fn default_verify_timeout_secs() -> u64 {
+    1_200
+}
+
+fn default_verify_max_output_bytes() -> usize {
+    1024 * 1024
+}
+
+impl Default for VerifyLimitsConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: default_verify_timeout_secs(),
+            max_output_bytes: default_verify_max_output_bytes(),
+            tiers: BTreeMap::new(),
+        }
+    }
+}
+
+impl VerifyLimitsConfig {
+    /// Limits for `tier`, falling back to the section defaults.
+    pub fn for_tier(&self, tier: &str) -> VerifyLimits {
+        let tier = self.tiers.get(tier).copied().unwrap_or_default();
+        VerifyLimits {
+            timeout_secs: tier.timeout_secs.unwrap_or(self.timeout_secs).max(1),
+            max_output_bytes: tier.max_output_bytes.unwrap_or(self.max_output_bytes),
+        }
+    }
+}
+
+impl Default for VerifyLimits {
+    fn default() -> Self {
+        VerifyLimitsConfig::default().for_tier("")
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -563,6 +982,21 @@ submit_mode = "single"
         let _ = fs::remove_file(path);
     }
 
+    #[test]
+    fn web_auth_token_is_optional_and_roundtrips() {
+        let mut config = sample_org();
+        assert_eq!(config.ui.web_auth_token, None);
+
+        let token = generate_web_auth_token().expect("generate token");
+        assert_eq!(token.len(), 64);
+        assert!(token.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(token, generate_web_auth_token().expect("second token"));
+
+        config.ui.web_auth_token = Some(token);
+        let body = toml::to_string_pretty(&config).expect("serialize");
+        assert_eq!(parse_org_config(&body).expect("parse"), config);
+    }
+
     #[test]
     fn parse_repo_config_parses_spec_shape() {
         let repo = parse_repo_config(sample_repo()).expect("parse repo config");
@@ -570,14 +1004,40 @@ submit_mode = "single"
         assert_eq!(repo.base_branch, "main");
         assert_eq!(repo.nix.dev_shell, "nix develop");
         assert_eq!(repo.verify.command, "cargo check && cargo test");
+        assert_eq!(repo.verify.limits, VerifyLimitsConfig::default());
         assert_eq!(repo.graphite.submit_mode, Some(SubmitMode::Single));
     }
 
+    #[test]
+    fn verify_tier_limits_override_the_section_defaults() {
+        let body = sample_repo().replace(
+            "command = \"cargo check && cargo test\"\n",
+            "command = \"cargo check && cargo test\"\ntimeout_secs = 600\n\n[verify.tiers.stack]\ntimeout_secs = 1800\n",
+        );
+        let repo = parse_repo_config(&body).expect("parse repo config");
+        assert_eq!(
+            repo.verify.limits.for_tier("branch"),
+            VerifyLimits {
+                timeout_secs: 600,
+                max_output_bytes: 1024 * 1024,
+            }
+        );
+        assert_eq!(repo.verify.limits.for_tier("stack").timeout_secs, 1_800);
+
+        let round_trip = toml::to_string(&repo).expect("serialize repo config");
+        assert_eq!(parse_repo_config(&round_trip).expect("reparse"), repo);
+    }
+
     #[test]
     fn daemon_config_defaults() {
         let config = sample_org();
         assert_eq!(config.daemon.tick_interval_secs, 2);
         assert_eq!(config.daemon.agent_timeout_secs, 1_800);
+        assert_eq!(config.daemon.max_spawns_per_tick, 2);
+        assert!(config.daemon.generate_qa_specs);
+        assert_eq!(config.daemon.merge_poll, MergePollConfig::default());
+        assert!(!config.daemon.adaptive_tick.enabled);
+        assert!(!config.graphite.auto_resolve_conflicts);
     }
 
     #[test]
@@ -597,6 +1057,7 @@ gemini = 1
 auto_submit = false
 submit_mode_default = "single"
 allow_move = "manual"
+auto_resolve_conflicts = true
 
 [ui]
 web_bind = "127.0.0.1:9842"
@@ -608,12 +1069,88 @@ stdout = true
 [daemon]
 tick_interval_secs = 7
 agent_timeout_secs = 90
+verify_command = "npm test"
+
+[daemon.context_gen]
+model = "gemini"
+timeout_secs = 120
+
+[daemon.merge_poll]
+interval_secs = 60
+github_api = true
+
+[daemon.adaptive_tick]
+enabled = true
+ceiling_ms = 10000
 "#,
         )
         .expect("parse org config with custom daemon values");
 
         assert_eq!(config.daemon.tick_interval_secs, 7);
         assert_eq!(config.daemon.agent_timeout_secs, 90);
+        assert_eq!(config.daemon.verify_command.as_deref(), Some("npm test"));
+        assert_eq!(config.daemon.context_gen.model, Some(ModelKind::Gemini));
+        assert_eq!(config.daemon.context_gen.timeout_secs, 120);
+        assert_eq!(config.daemon.merge_poll.interval_secs, 60);
+        assert_eq!(config.daemon.merge_poll.min_age_secs, 600);
+        assert!(config.daemon.merge_poll.github_api);
+        assert!(config.daemon.adaptive_tick.enabled);
+        assert_eq!(config.daemon.adaptive_tick.floor_ms, 500);
+        assert_eq!(config.daemon.adaptive_tick.ceiling_ms, 10_000);
+        assert!(config.graphite.auto_resolve_conflicts);
+    }
+
+    #[test]
+    fn custom_models_parse_with_limits_and_resolve_by_name() {
+        let config = parse_org_config(
+            r#"
+[models]
+enabled = ["claude", "aider"]
+
+[models.custom.aider]
+command = "aider"
+args = ["--yes", "--message", "{prompt}"]
+api_key_env = "OPENAI_API_KEY"
+
+[models.custom.internal]
+command = "/opt/bin/internal-agent"
+output_format = "json"
+
+[concurrency]
+per_repo = 5
+claude = 3
+codex = 1
+gemini = 1
+
+[concurrency.custom]
+aider = 2
+
+[graphite]
+auto_submit = false
+submit_mode_default = "single"
+allow_move = "manual"
+
+[ui]
+web_bind = "127.0.0.1:9842"
+"#,
+        )
+        .expect("parse org config with custom models");
+
+        let aider = config.models.resolve("aider").expect("aider resolves");
+        let internal = config
+            .models
+            .resolve("internal")
+            .expect("internal resolves");
+        assert_eq!(config.models.enabled, vec![ModelKind::Claude, aider]);
+        assert_eq!(config.models.resolve("CODEX"), Some(ModelKind::Codex));
+        assert_eq!(config.models.resolve("missing"), None);
+        assert_eq!(
+            config.models.custom["internal"].output_format,
+            CustomOutputFormat::Json
+        );
+        assert_eq!(config.concurrency.limit_for(aider), 2);
+        assert_eq!(config.concurrency.limit_for(internal), 5);
+        assert_eq!(config.concurrency.limit_for(ModelKind::Claude), 3);
     }
 
     #[test]
@@ -649,6 +1186,9 @@ tick_interval_secs = 11
 
         assert_eq!(config.daemon.tick_interval_secs, 11);
         assert_eq!(config.daemon.agent_timeout_secs, 1_800);
+        assert_eq!(config.daemon.verify_command, None);
+        assert_eq!(config.daemon.retry_guard, RetryGuardConfig::default());
+        assert_eq!(config.daemon.context_gen, ContextGenOrgConfig::default());
     }
 
     #[test]
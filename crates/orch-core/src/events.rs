@@ -5,6 +5,186 @@ use serde::{Deserialize, Serialize};
 
 use crate::types::{EventId, RepoId, SubmitMode, TaskId};
 
+/// Why a task cancellation was requested.
+///
+/// Serialized as a plain string keyword. Strings that are not a known keyword
+/// (including free-form reasons from older event logs) decode as `Other`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum CancelReason {
+    UserRequested,
+    BudgetExceeded,
+    Timeout,
+    DependencyFailed,
+    Other(String),
+}
+
+impl CancelReason {
+    /// Stable label used in event payloads and rendered logs.
+    pub fn as_str(&self) -> &str {
+        match self {
+            CancelReason::UserRequested => "user_requested",
+            CancelReason::BudgetExceeded => "budget_exceeded",
+            CancelReason::Timeout => "timeout",
+            CancelReason::DependencyFailed => "dependency_failed",
+            CancelReason::Other(reason) => reason,
+        }
+    }
+
+    /// Parse a keyword (`user`, `budget`, `timeout`, `dependency`) or one of
+    /// the stable labels. Anything else is kept verbatim as `Other`.
+    pub fn parse(value: &str) -> Self {
+        let trimmed = value.trim();
+        match trimmed.to_lowercase().replace('-', "_").as_str() {
+            "user" | "user_requested" => CancelReason::UserRequested,
+            "budget" | "budget_exceeded" => CancelReason::BudgetExceeded,
+            "timeout" => CancelReason::Timeout,
+            "dependency" | "dependency_failed" => CancelReason::DependencyFailed,
+            _ => CancelReason::Other(trimmed.to_string()),
+        }
+    }
+}
+
+impl From<String> for CancelReason {
+    fn from(value: String) -> Self {
+        CancelReason::parse(&value)
+    }
+}
+
+impl From<CancelReason> for String {
+    fn from(value: CancelReason) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+impl std::fmt::Display for CancelReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CancelReason::Other(reason) => write!(f, "other: {reason}"),
+            known => f.write_str(known.as_str()),
+        }
+    }
+}
+
+/// Coarse bucket for why a task failed or was stopped, used to rank failure
+/// causes in stats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureCause {
+    VerifyFailure,
+    RestackConflict,
+    AgentError,
+    Timeout,
+    Budget,
+    RateLimit,
+    HumanCancel,
+    Other,
+}
+
+impl FailureCause {
+    pub const ALL: [FailureCause; 8] = [
+        FailureCause::VerifyFailure,
+        FailureCause::RestackConflict,
+        FailureCause::AgentError,
+        FailureCause::Timeout,
+        FailureCause::Budget,
+        FailureCause::RateLimit,
+        FailureCause::HumanCancel,
+        FailureCause::Other,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FailureCause::VerifyFailure => "verify_failure",
+            FailureCause::RestackConflict => "restack_conflict",
+            FailureCause::AgentError => "agent_error",
+            FailureCause::Timeout => "timeout",
+            FailureCause::Budget => "budget",
+            FailureCause::RateLimit => "rate_limit",
+            FailureCause::HumanCancel => "human_cancel",
+            FailureCause::Other => "other",
+        }
+    }
+
+    /// Route a free-form failure reason to a cause. Checks run in order, so a
+    /// reason mentioning both a rate limit and the agent counts as a rate limit.
+    pub fn classify(reason: &str) -> Self {
+        const RULES: [(FailureCause, &[&str]); 7] = [
+            (
+                FailureCause::HumanCancel,
+                &[
+                    "cancel",
+                    "user_requested",
+                    "requested by user",
+                    "stopped by user",
+                ],
+            ),
+            (
+                FailureCause::Budget,
+                &["budget", "cost limit", "spend limit"],
+            ),
+            (
+                FailureCause::RateLimit,
+                &[
+                    "rate limit",
+                    "rate_limit",
+                    "ratelimit",
+                    "429",
+                    "too many requests",
+                    "quota",
+                ],
+            ),
+            (
+                FailureCause::RestackConflict,
+                &["conflict", "restack", "could not apply"],
+            ),
+            (
+                FailureCause::Timeout,
+                &["timeout", "timed out", "deadline exceeded"],
+            ),
+            (
+                FailureCause::VerifyFailure,
+                &[
+                    "verify",
+                    "verification",
+                    "test failure",
+                    "tests failed",
+                    "build failed",
+                ],
+            ),
+            (
+                FailureCause::AgentError,
+                &["agent", "exit code", "exited with", "crash", "panicked"],
+            ),
+        ];
+
+        let lower = reason.to_ascii_lowercase();
+        RULES
+            .iter()
+            .find(|(_, needles)| needles.iter().any(|needle| lower.contains(needle)))
+            .map(|(cause, _)| *cause)
+            .unwrap_or(FailureCause::Other)
+    }
+}
+
+impl From<&CancelReason> for FailureCause {
+    fn from(reason: &CancelReason) -> Self {
+        match reason {
+            CancelReason::UserRequested => FailureCause::HumanCancel,
+            CancelReason::BudgetExceeded => FailureCause::Budget,
+            CancelReason::Timeout => FailureCause::Timeout,
+            CancelReason::DependencyFailed => FailureCause::Other,
+            CancelReason::Other(reason) => FailureCause::classify(reason),
+        }
+    }
+}
+
+impl std::fmt::Display for FailureCause {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Simplified event kinds for MVP.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -24,7 +204,16 @@ pub enum EventKind {
     /// Verify started
     VerifyStarted,
     /// Verify completed
-    VerifyCompleted { success: bool },
+    VerifyCompleted {
+        success: bool,
+        /// Reproduction bundle directory when the verify command failed.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        failure_bundle: Option<String>,
+        /// Failing tests and compiler errors found in the output, when the
+        /// verify tool's format is recognized.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        failure_summary: Option<String>,
+    },
     /// Task is ready to submit
     ReadyReached,
     /// Submit started
@@ -51,8 +240,18 @@ pub enum EventKind {
         success: bool,
         duration_secs: u64,
     },
+    /// An idle interactive agent was stopped; its conversation is kept
+    /// under `session_id` for resume.
+    AgentSuspended {
+        session_id: String,
+        idle_secs: u64,
+    },
+    /// A suspended agent session was resumed by a new message.
+    AgentResumed {
+        session_id: String,
+    },
     CancellationRequested {
-        reason: String,
+        reason: CancelReason,
     },
     /// Retry switched to a different model.
     ModelFallback {
@@ -73,6 +272,10 @@ pub enum EventKind {
     TaskFailed {
         reason: String,
         is_final: bool,
+        /// Classified from `reason` when recorded; older events are
+        /// backfilled by `SqliteStore::migrate`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cause: Option<FailureCause>,
     },
     /// Test spec was validated.
     TestSpecValidated {
@@ -108,6 +311,41 @@ pub enum EventKind {
     GraphiteSyncCompleted {
         success: bool,
     },
+    /// Agent stopped to ask the user a question.
+    QuestionAsked {
+        question_id: String,
+        question: String,
+    },
+    /// An open agent question was answered.
+    QuestionAnswered {
+        question_id: String,
+        answer: String,
+    },
+    /// An open agent question went unanswered past the stale threshold.
+    QuestionStale {
+        question_id: String,
+        question: String,
+        open_secs: u64,
+    },
+    /// A submit pipeline step started running.
+    PipelineStepStarted {
+        step: String,
+    },
+    /// A submit pipeline step finished.
+    PipelineStepCompleted {
+        step: String,
+        success: bool,
+    },
+    /// An operator paused, resumed, reloaded or drained the daemon.
+    DaemonControl {
+        action: String,
+        actor: String,
+    },
+    /// Merge polling found the task's branch merged; `method` is how
+    /// (`ancestor`, `graphite` or `github`).
+    MergeDetected {
+        method: String,
+    },
 }
 
 /// An event in the orchestrator.
@@ -144,7 +382,11 @@ mod tests {
                 .with_ymd_and_hms(2026, 2, 8, 12, 30, 45)
                 .single()
                 .expect("valid timestamp"),
-            kind: EventKind::VerifyCompleted { success: true },
+            kind: EventKind::VerifyCompleted {
+                success: true,
+                failure_bundle: None,
+                failure_summary: None,
+            },
         };
 
         let json = serde_json::to_string(&event).unwrap();
@@ -167,7 +409,11 @@ mod tests {
             EventKind::RestackCompleted,
             EventKind::RestackConflict,
             EventKind::VerifyStarted,
-            EventKind::VerifyCompleted { success: false },
+            EventKind::VerifyCompleted {
+                success: false,
+                failure_bundle: Some(".othala/verify-failures/T1/20260101T000000.000Z".to_string()),
+                failure_summary: Some("1 failure: test tests::adds (src/lib.rs:10:9)".to_string()),
+            },
             EventKind::ReadyReached,
             EventKind::SubmitStarted {
                 mode: SubmitMode::Single,
@@ -193,8 +439,18 @@ mod tests {
                 success: false,
                 duration_secs: 42,
             },
+            EventKind::AgentSuspended {
+                session_id: "0b6f3c1e-2d4a-4f60-9a7b-1c2d3e4f5a6b".to_string(),
+                idle_secs: 900,
+            },
+            EventKind::AgentResumed {
+                session_id: "0b6f3c1e-2d4a-4f60-9a7b-1c2d3e4f5a6b".to_string(),
+            },
+            EventKind::CancellationRequested {
+                reason: CancelReason::Other("user requested stop".to_string()),
+            },
             EventKind::CancellationRequested {
-                reason: "user requested stop".to_string(),
+                reason: CancelReason::DependencyFailed,
             },
             EventKind::ModelFallback {
                 from_model: "claude".to_string(),
@@ -209,6 +465,7 @@ mod tests {
             EventKind::TaskFailed {
                 reason: "max retries".to_string(),
                 is_final: true,
+                cause: Some(FailureCause::Other),
             },
             EventKind::TestSpecValidated {
                 passed: true,
@@ -234,6 +491,33 @@ mod tests {
             },
             EventKind::GraphiteSyncStarted,
             EventKind::GraphiteSyncCompleted { success: true },
+            EventKind::QuestionAsked {
+                question_id: "Q1".to_string(),
+                question: "Which database should I target?".to_string(),
+            },
+            EventKind::QuestionAnswered {
+                question_id: "Q1".to_string(),
+                answer: "postgres".to_string(),
+            },
+            EventKind::QuestionStale {
+                question_id: "Q1".to_string(),
+                question: "Which database should I target?".to_string(),
+                open_secs: 3600,
+            },
+            EventKind::PipelineStepStarted {
+                step: "verify_branch".to_string(),
+            },
+            EventKind::PipelineStepCompleted {
+                step: "verify_branch".to_string(),
+                success: false,
+            },
+            EventKind::DaemonControl {
+                action: "pause".to_string(),
+                actor: "ops@example.com".to_string(),
+            },
+            EventKind::MergeDetected {
+                method: "ancestor".to_string(),
+            },
         ];
 
         for kind in kinds {
@@ -260,6 +544,46 @@ mod tests {
         assert_eq!(decoded.repo_id, None);
     }
 
+    #[test]
+    fn cancel_reason_parses_keywords() {
+        assert_eq!(CancelReason::parse("user"), CancelReason::UserRequested);
+        assert_eq!(CancelReason::parse("budget"), CancelReason::BudgetExceeded);
+        assert_eq!(CancelReason::parse("TIMEOUT"), CancelReason::Timeout);
+        assert_eq!(
+            CancelReason::parse("dependency"),
+            CancelReason::DependencyFailed
+        );
+        assert_eq!(
+            CancelReason::parse("dependency-failed"),
+            CancelReason::DependencyFailed
+        );
+        assert_eq!(
+            CancelReason::parse(" flaky CI "),
+            CancelReason::Other("flaky CI".to_string())
+        );
+    }
+
+    #[test]
+    fn cancellation_event_decodes_legacy_plain_string_reason() {
+        let legacy = r#"{"cancellation_requested":{"reason":"requested by user"}}"#;
+        let decoded: EventKind = serde_json::from_str(legacy).expect("decode legacy event");
+        assert_eq!(
+            decoded,
+            EventKind::CancellationRequested {
+                reason: CancelReason::Other("requested by user".to_string()),
+            }
+        );
+
+        let encoded = serde_json::to_string(&EventKind::CancellationRequested {
+            reason: CancelReason::BudgetExceeded,
+        })
+        .expect("encode");
+        assert_eq!(
+            encoded,
+            r#"{"cancellation_requested":{"reason":"budget_exceeded"}}"#
+        );
+    }
+
     #[test]
     fn config_reloaded_event_created() {
         let kind = EventKind::ConfigReloaded {
@@ -270,4 +594,96 @@ mod tests {
         let decoded: EventKind = serde_json::from_str(&encoded).expect("deserialize config reload event");
         assert_eq!(decoded, kind);
     }
+
+    #[test]
+    fn failure_cause_classifies_reason_strings() {
+        let cases = [
+            (
+                "verify failed: cargo test exited 101",
+                FailureCause::VerifyFailure,
+            ),
+            (
+                "Verification failed after 3 attempts",
+                FailureCause::VerifyFailure,
+            ),
+            (
+                "tests failed in crate orch-core",
+                FailureCause::VerifyFailure,
+            ),
+            (
+                "restack failed with conflicts",
+                FailureCause::RestackConflict,
+            ),
+            (
+                "could not apply 1a2b3c... wip",
+                FailureCause::RestackConflict,
+            ),
+            (
+                "merge conflict in src/lib.rs",
+                FailureCause::RestackConflict,
+            ),
+            (
+                "Agent failed with a compile error that needs a human",
+                FailureCause::AgentError,
+            ),
+            ("agent process crashed", FailureCause::AgentError),
+            ("process exited with exit code 1", FailureCause::AgentError),
+            ("epoch timed out after 1800s", FailureCause::Timeout),
+            (
+                "retrying after timeout failure (max retries reached: 3/3)",
+                FailureCause::Timeout,
+            ),
+            ("verify timeout", FailureCause::Timeout),
+            ("budget exceeded: $12.40 of $10.00", FailureCause::Budget),
+            (
+                "Agent failed with a rate_limit error",
+                FailureCause::RateLimit,
+            ),
+            ("HTTP 429 Too Many Requests", FailureCause::RateLimit),
+            ("monthly quota exhausted", FailureCause::RateLimit),
+            ("cancelled by operator", FailureCause::HumanCancel),
+            ("user_requested", FailureCause::HumanCancel),
+            ("task not found for retry evaluation", FailureCause::Other),
+            ("", FailureCause::Other),
+        ];
+
+        for (reason, expected) in cases {
+            assert_eq!(
+                FailureCause::classify(reason),
+                expected,
+                "reason: {reason:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn failure_cause_maps_cancel_reasons_and_serializes_as_label() {
+        assert_eq!(
+            FailureCause::from(&CancelReason::UserRequested),
+            FailureCause::HumanCancel
+        );
+        assert_eq!(
+            FailureCause::from(&CancelReason::BudgetExceeded),
+            FailureCause::Budget
+        );
+        assert_eq!(
+            FailureCause::from(&CancelReason::Other("rate limit hit".to_string())),
+            FailureCause::RateLimit
+        );
+        for cause in FailureCause::ALL {
+            let json = serde_json::to_string(&cause).expect("serialize cause");
+            assert_eq!(json, format!("\"{}\"", cause.as_str()));
+        }
+
+        let legacy = r#"{"task_failed":{"reason":"verify failed","is_final":true}}"#;
+        let decoded: EventKind = serde_json::from_str(legacy).expect("decode legacy event");
+        assert_eq!(
+            decoded,
+            EventKind::TaskFailed {
+                reason: "verify failed".to_string(),
+                is_final: true,
+                cause: None,
+            }
+        );
+    }
 }
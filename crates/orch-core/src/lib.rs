@@ -2,6 +2,8 @@
 
 pub mod config;
 pub mod events;
+pub mod secret;
+pub mod shell;
 pub mod state;
 pub mod types;
 pub mod validation;
@@ -9,6 +11,8 @@ pub mod validation;
 // Re-export core types for convenience
 pub use config::*;
 pub use events::*;
+pub use secret::*;
+pub use shell::*;
 pub use state::*;
 pub use types::*;
 pub use validation::*;
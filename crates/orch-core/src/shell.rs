@@ -1,5 +1,7 @@
+//! Helpers for building shell command lines.
+
 /// Shell-quote a value using POSIX single-quote escaping.
-pub(crate) fn shell_quote(value: &str) -> String {
+pub fn shell_quote(value: &str) -> String {
     let escaped = value.replace('\'', "'\"'\"'");
     format!("'{escaped}'")
 }
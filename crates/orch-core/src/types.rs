@@ -2,8 +2,10 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
 
 use crate::state::{TaskState, VerifyStatus};
 
@@ -143,21 +145,111 @@ impl AsRef<str> for EventId {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ModelKind {
     Claude,
     Codex,
     Gemini,
+    /// A tool configured under `[models.custom.<name>]`.
+    Custom(CustomModelName),
 }
 
 impl ModelKind {
+    /// Every built-in model, in display order. Custom models come from
+    /// `[models.custom]` and are not listed.
+    pub const fn all() -> &'static [ModelKind] {
+        &[ModelKind::Claude, ModelKind::Codex, ModelKind::Gemini]
+    }
+
     pub fn as_str(self) -> &'static str {
         match self {
             ModelKind::Claude => "claude",
             ModelKind::Codex => "codex",
             ModelKind::Gemini => "gemini",
+            ModelKind::Custom(name) => name.as_str(),
+        }
+    }
+
+    /// The built-in model called `name`, ignoring case and surrounding space.
+    pub fn builtin(name: &str) -> Option<ModelKind> {
+        let name = name.trim().to_lowercase();
+        Self::all()
+            .iter()
+            .copied()
+            .find(|model| model.as_str() == name)
+    }
+
+    /// The built-in model called `name`, else a custom model by that name.
+    /// Fails when `name` is not a valid custom model name.
+    ///
+    /// Custom names are interned for the life of the process, so only pass
+    /// names from the config or the task store; check client input against
+    /// `[models.custom]` with [`crate::config::ModelsConfig::resolve`].
+    pub fn from_name(name: &str) -> Result<ModelKind, InvalidModelName> {
+        match Self::builtin(name) {
+            Some(model) => Ok(model),
+            None => CustomModelName::new(name).map(ModelKind::Custom),
+        }
+    }
+
+    pub fn is_custom(self) -> bool {
+        matches!(self, ModelKind::Custom(_))
+    }
+}
+
+/// Longest accepted custom model name.
+pub const MAX_CUSTOM_MODEL_NAME_LEN: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error(
+    "invalid model name '{0}': use lowercase letters, digits, '-' or '_' (at most 64 characters)"
+)]
+pub struct InvalidModelName(pub String);
+
+/// Name of a custom model. Names are interned so `ModelKind` stays `Copy`;
+/// each distinct name is allocated once for the life of the process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CustomModelName(&'static str);
+
+impl CustomModelName {
+    pub fn new(name: &str) -> Result<Self, InvalidModelName> {
+        let valid = !name.is_empty()
+            && name.len() <= MAX_CUSTOM_MODEL_NAME_LEN
+            && name
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-' || b == b'_');
+        if !valid {
+            return Err(InvalidModelName(name.to_string()));
+        }
+
+        static NAMES: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+        let mut names = NAMES
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(interned) = names.get(name) {
+            return Ok(Self(interned));
         }
+        let interned: &'static str = Box::leak(name.to_string().into_boxed_str());
+        names.insert(interned);
+        Ok(Self(interned))
+    }
+
+    pub fn as_str(self) -> &'static str {
+        self.0
+    }
+}
+
+impl Serialize for ModelKind {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ModelKind {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        ModelKind::from_name(&name).map_err(serde::de::Error::custom)
     }
 }
 
@@ -196,6 +288,16 @@ pub struct PullRequestRef {
     pub draft: bool,
 }
 
+/// Issue in an external tracker (Linear, Jira, ...) a task was imported from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ExternalRef {
+    /// Tracker name from the import mapping, e.g. `linear`.
+    pub source: String,
+    /// The tracker's issue key, e.g. `ENG-123`.
+    pub id: String,
+    pub url: Option<String>,
+}
+
 /// Task specification for creating new tasks.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TaskSpec {
@@ -412,12 +514,7 @@ pub fn yaml_spec_to_task(spec: &YamlTaskSpec, repo_id: &str) -> Task {
         worktree_path,
     );
 
-    task.preferred_model = spec.model.as_deref().and_then(|name| match name.trim().to_lowercase().as_str() {
-        "claude" => Some(ModelKind::Claude),
-        "codex" => Some(ModelKind::Codex),
-        "gemini" => Some(ModelKind::Gemini),
-        _ => None,
-    });
+    task.preferred_model = spec.model.as_deref().and_then(ModelKind::builtin);
 
     task.priority = spec
         .priority
@@ -494,6 +591,16 @@ pub struct Task {
     /// Parent task ID (for decomposed sub-tasks).
     #[serde(default)]
     pub parent_task_id: Option<TaskId>,
+    /// Whether the last restack stopped on a conflict. Cleared once a
+    /// restack completes.
+    #[serde(default)]
+    pub restack_conflict: bool,
+    /// Longer description of the work, e.g. an imported issue body.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Tracker issue this task was imported from.
+    #[serde(default)]
+    pub external_ref: Option<ExternalRef>,
 }
 
 fn default_max_retries() -> u32 {
@@ -527,6 +634,9 @@ impl Task {
             task_type: TaskType::default(),
             test_spec_path: None,
             parent_task_id: None,
+            restack_conflict: false,
+            description: None,
+            external_ref: None,
         }
     }
 
@@ -587,6 +697,48 @@ impl Task {
         self.state = TaskState::Merged;
         self.updated_at = Utc::now();
     }
+
+    /// Check for a label, comparing normalized forms (`Bug` matches `bug`).
+    pub fn has_label(&self, label: &str) -> bool {
+        let wanted = normalize_label(label);
+        self.labels
+            .iter()
+            .any(|existing| normalize_label(existing) == wanted)
+    }
+
+    /// Add a label in normalized form. Returns false if it was empty or
+    /// already present.
+    pub fn add_label(&mut self, label: &str) -> bool {
+        let normalized = normalize_label(label);
+        if normalized.is_empty() || self.has_label(&normalized) {
+            return false;
+        }
+        self.labels.push(normalized);
+        true
+    }
+
+    /// Remove every label whose normalized form matches. Returns true if any
+    /// label was removed.
+    pub fn remove_label(&mut self, label: &str) -> bool {
+        let wanted = normalize_label(label);
+        let before = self.labels.len();
+        self.labels.retain(|existing| normalize_label(existing) != wanted);
+        self.labels.len() != before
+    }
+
+    /// Labels joined as `a,b,c` (empty string when there are none).
+    pub fn labels_csv(&self) -> String {
+        self.labels.join(",")
+    }
+}
+
+/// Canonical label form: trimmed, lowercased, inner whitespace collapsed.
+pub fn normalize_label(label: &str) -> String {
+    label
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
 }
 
 #[cfg(test)]
@@ -661,6 +813,33 @@ preferred_model = "codex"
         assert_eq!(json, "\"claude\"");
     }
 
+    #[test]
+    fn model_kind_all_lists_each_builtin_exactly_once() {
+        let mut seen = [0; 3];
+        for model in ModelKind::all() {
+            let slot = match model {
+                ModelKind::Claude => 0,
+                ModelKind::Codex => 1,
+                ModelKind::Gemini => 2,
+                ModelKind::Custom(name) => panic!("custom model {name:?} in all()"),
+            };
+            seen[slot] += 1;
+        }
+        assert_eq!(seen, [1, 1, 1]);
+    }
+
+    #[test]
+    fn custom_model_kind_round_trips_by_name() {
+        let aider: ModelKind = serde_json::from_str("\"aider\"").unwrap();
+        assert_eq!(
+            aider,
+            ModelKind::Custom(CustomModelName::new("aider").unwrap())
+        );
+        assert_eq!(serde_json::to_string(&aider).unwrap(), "\"aider\"");
+        assert_eq!(ModelKind::from_name("Codex"), Ok(ModelKind::Codex));
+        assert!(serde_json::from_str::<ModelKind>("\"not a model\"").is_err());
+    }
+
     #[test]
     fn task_priority_defaults_to_normal() {
         let task = Task::new(
@@ -810,4 +989,34 @@ unknown: nope
         assert!(task.depends_on.is_empty());
         assert!(task.labels.is_empty());
     }
+
+    #[test]
+    fn normalize_label_trims_lowercases_and_collapses_whitespace() {
+        assert_eq!(normalize_label("  Needs   Review \t"), "needs review");
+        assert_eq!(normalize_label("BUG"), "bug");
+        assert_eq!(normalize_label("   "), "");
+    }
+
+    #[test]
+    fn add_label_dedupes_differently_cased_labels() {
+        let mut task = make_task("T-LABEL-1", TaskState::Chatting);
+        assert!(task.add_label("Bug"));
+        assert!(!task.add_label(" bug "));
+        assert!(!task.add_label("   "));
+        assert!(task.add_label("Needs  Review"));
+        assert_eq!(task.labels, vec!["bug", "needs review"]);
+        assert_eq!(task.labels_csv(), "bug,needs review");
+    }
+
+    #[test]
+    fn has_label_matches_case_insensitively() {
+        let mut task = make_task("T-LABEL-2", TaskState::Chatting);
+        task.labels = vec!["Backend".to_string()];
+        assert!(task.has_label("backend"));
+        assert!(task.has_label(" BACKEND "));
+        assert!(!task.has_label("frontend"));
+        assert!(task.remove_label("backend"));
+        assert!(task.labels.is_empty());
+        assert_eq!(task.labels_csv(), "");
+    }
 }
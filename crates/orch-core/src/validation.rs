@@ -47,12 +47,40 @@ impl Validate for OrgConfig {
             }
         }
 
+        for (name, custom) in &self.models.custom {
+            match crate::types::ModelKind::from_name(name) {
+                Ok(model) if model.is_custom() => {}
+                Ok(_) => issues.push(ValidationIssue {
+                    level: ValidationLevel::Error,
+                    code: "models.custom.builtin_name",
+                    message: format!("custom model '{name}' shadows a built-in model"),
+                }),
+                Err(err) => issues.push(ValidationIssue {
+                    level: ValidationLevel::Error,
+                    code: "models.custom.name",
+                    message: err.to_string(),
+                }),
+            }
+            if custom.command.trim().is_empty() {
+                issues.push(ValidationIssue {
+                    level: ValidationLevel::Error,
+                    code: "models.custom.command.empty",
+                    message: format!("custom model '{name}' has no command"),
+                });
+            }
+        }
+
         for model in &self.models.enabled {
-            let concurrency = match model {
-                crate::types::ModelKind::Claude => self.concurrency.claude,
-                crate::types::ModelKind::Codex => self.concurrency.codex,
-                crate::types::ModelKind::Gemini => self.concurrency.gemini,
-            };
+            if model.is_custom() && !self.models.custom.contains_key(model.as_str()) {
+                issues.push(ValidationIssue {
+                    level: ValidationLevel::Error,
+                    code: "models.custom.undefined",
+                    message: format!(
+                        "enabled model '{model}' is neither built in nor defined under [models.custom]"
+                    ),
+                });
+            }
+            let concurrency = self.concurrency.limit_for(*model);
             if concurrency == 0 {
                 issues.push(ValidationIssue {
                     level: ValidationLevel::Error,
@@ -80,6 +108,25 @@ impl Validate for OrgConfig {
             });
         }
 
+        let adaptive_tick = &self.daemon.adaptive_tick;
+        if adaptive_tick.enabled && adaptive_tick.floor_ms == 0 {
+            issues.push(ValidationIssue {
+                level: ValidationLevel::Error,
+                code: "daemon.adaptive_tick.floor.zero",
+                message: "adaptive tick floor cannot be 0".to_string(),
+            });
+        }
+        if adaptive_tick.enabled && adaptive_tick.floor_ms > adaptive_tick.ceiling_ms {
+            issues.push(ValidationIssue {
+                level: ValidationLevel::Error,
+                code: "daemon.adaptive_tick.range",
+                message: format!(
+                    "adaptive tick floor ({}ms) is above its ceiling ({}ms)",
+                    adaptive_tick.floor_ms, adaptive_tick.ceiling_ms
+                ),
+            });
+        }
+
         if self.daemon.agent_timeout_secs > 0 && self.daemon.agent_timeout_secs < 30 {
             issues.push(ValidationIssue {
                 level: ValidationLevel::Warning,
@@ -91,6 +138,18 @@ impl Validate for OrgConfig {
             });
         }
 
+        if let Some(min) = self.daemon.auto_approve_min_confidence {
+            if min > 100 {
+                issues.push(ValidationIssue {
+                    level: ValidationLevel::Error,
+                    code: "daemon.auto_approve_min_confidence.range",
+                    message: format!(
+                        "auto_approve_min_confidence is a percentage; {min} is above 100"
+                    ),
+                });
+            }
+        }
+
         if self.notifications.slack_channel.is_some()
             && self.notifications.slack_webhook_url.is_none()
         {
@@ -101,6 +160,33 @@ impl Validate for OrgConfig {
             });
         }
 
+        if self.notifications.telegram_bot_token_env.is_some()
+            != self.notifications.telegram_chat_id_env.is_some()
+        {
+            issues.push(ValidationIssue {
+                level: ValidationLevel::Warning,
+                code: "notifications.telegram.incomplete",
+                message: "telegram needs both telegram_bot_token_env and telegram_chat_id_env"
+                    .to_string(),
+            });
+        }
+
+        let port_ok = self
+            .ui
+            .web_bind
+            .rsplit_once(':')
+            .is_some_and(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok());
+        if !port_ok {
+            issues.push(ValidationIssue {
+                level: ValidationLevel::Error,
+                code: "ui.web_bind.invalid",
+                message: format!(
+                    "web_bind '{}' must be host:port, e.g. 127.0.0.1:9842",
+                    self.ui.web_bind
+                ),
+            });
+        }
+
         if let Some(url) = &self.notifications.webhook_url {
             if !url.starts_with("http://") && !url.starts_with("https://") {
                 issues.push(ValidationIssue {
@@ -183,11 +269,13 @@ impl Validate for TaskSpec {
 mod tests {
     use super::{Validate, ValidationLevel};
     use crate::config::{
-        BudgetConfig, ConcurrencyConfig, ContextPathsConfig, DaemonOrgConfig, GraphiteOrgConfig,
-        ModelsConfig, MovePolicy, NixConfig, NotificationConfig, OrgConfig, PermissionsConfig,
-        RepoConfig, RepoGraphiteConfig, UiConfig, VerifyConfig,
+        BudgetConfig, ConcurrencyConfig, ContextPathsConfig, CustomModelConfig, CustomOutputFormat,
+        DaemonOrgConfig, GraphiteOrgConfig, ModelsConfig, MovePolicy, NixConfig,
+        NotificationConfig, OrgConfig, PermissionsConfig, RepoConfig, RepoGraphiteConfig, UiConfig,
+        VerifyConfig, VerifyLimitsConfig,
     };
     use crate::types::{ModelKind, RepoId, SubmitMode, TaskId, TaskSpec};
+    use std::collections::BTreeMap;
     use std::path::PathBuf;
 
     fn valid_org_config() -> OrgConfig {
@@ -196,20 +284,24 @@ mod tests {
             models: ModelsConfig {
                 enabled: vec![ModelKind::Claude, ModelKind::Codex],
                 default: Some(ModelKind::Claude),
+                custom: BTreeMap::new(),
             },
             concurrency: ConcurrencyConfig {
                 per_repo: 10,
                 claude: 10,
                 codex: 10,
                 gemini: 10,
+                custom: BTreeMap::new(),
             },
             graphite: GraphiteOrgConfig {
                 auto_submit: true,
                 submit_mode_default: SubmitMode::Single,
                 allow_move: MovePolicy::Manual,
+                auto_resolve_conflicts: false,
             },
             ui: UiConfig {
                 web_bind: "127.0.0.1:9842".to_string(),
+                web_auth_token: None,
             },
             notifications: NotificationConfig::default(),
             daemon: DaemonOrgConfig::default(),
@@ -232,6 +324,8 @@ mod tests {
             },
             verify: VerifyConfig {
                 command: "cargo check && cargo test".to_string(),
+                fast_commands: Vec::new(),
+                limits: VerifyLimitsConfig::default(),
             },
             graphite: RepoGraphiteConfig {
                 draft_on_start: true,
@@ -275,6 +369,77 @@ mod tests {
         }));
     }
 
+    #[test]
+    fn org_config_validation_checks_custom_models() {
+        let mut config = valid_org_config();
+        config.models.custom.insert(
+            "aider".to_string(),
+            CustomModelConfig {
+                command: "aider".to_string(),
+                args: vec!["--message".to_string(), "{prompt}".to_string()],
+                api_key_env: Some("OPENAI_API_KEY".to_string()),
+                output_format: CustomOutputFormat::Text,
+            },
+        );
+        config
+            .models
+            .enabled
+            .push(ModelKind::from_name("aider").unwrap());
+        assert!(config.validate().is_empty());
+
+        config
+            .models
+            .enabled
+            .push(ModelKind::from_name("internal-cli").unwrap());
+        let codes = config
+            .validate()
+            .into_iter()
+            .map(|issue| issue.code)
+            .collect::<Vec<_>>();
+        assert_eq!(codes, vec!["models.custom.undefined"]);
+    }
+
+    #[test]
+    fn org_config_validation_rejects_confidence_above_100_percent() {
+        let mut config = valid_org_config();
+        config.daemon.auto_approve_min_confidence = Some(70);
+        assert!(config.validate().is_empty());
+
+        config.daemon.auto_approve_min_confidence = Some(150);
+        let issues = config.validate();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, "daemon.auto_approve_min_confidence.range");
+    }
+
+    #[test]
+    fn org_config_validation_checks_adaptive_tick_bounds() {
+        let mut config = valid_org_config();
+        config.daemon.adaptive_tick.enabled = true;
+        assert!(config.validate().is_empty());
+
+        config.daemon.adaptive_tick.floor_ms = 60_000;
+        let issues = config.validate();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, "daemon.adaptive_tick.range");
+    }
+
+    #[test]
+    fn org_config_validation_checks_web_bind_and_telegram() {
+        let mut config = valid_org_config();
+        config.ui.web_bind = "localhost:9842".to_string();
+        config.notifications.telegram_bot_token_env = Some("TELEGRAM_BOT_TOKEN".to_string());
+        config.notifications.telegram_chat_id_env = Some("TELEGRAM_CHAT_ID".to_string());
+        assert!(config.validate().is_empty());
+
+        config.ui.web_bind = "9842".to_string();
+        config.notifications.telegram_chat_id_env = None;
+        let codes: Vec<_> = config.validate().iter().map(|issue| issue.code).collect();
+        assert_eq!(
+            codes,
+            vec!["notifications.telegram.incomplete", "ui.web_bind.invalid"]
+        );
+    }
+
     #[test]
     fn repo_config_validation_reports_errors() {
         let mut config = valid_repo_config();
@@ -14,7 +14,8 @@ pub use worktree::*;
 mod tests {
     use super::{
         capture_diff_snapshot, capture_repo_snapshot, capture_status_snapshot, current_branch,
-        discover_repo, head_sha, GitCli, GitError, RepoHandle, RepoSnapshot, StatusSnapshot,
+        discover_repo, head_sha, is_ancestor, GitCli, GitError, RepoHandle, RepoSnapshot,
+        StatusSnapshot,
     };
     use std::any::TypeId;
     use std::path::Path;
@@ -33,6 +34,7 @@ mod tests {
         let _discover: fn(&Path, &GitCli) -> Result<RepoHandle, GitError> = discover_repo;
         let _branch: fn(&RepoHandle, &GitCli) -> Result<String, GitError> = current_branch;
         let _head: fn(&RepoHandle, &GitCli) -> Result<String, GitError> = head_sha;
+        let _ancestor: fn(&RepoHandle, &GitCli, &str, &str) -> Result<bool, GitError> = is_ancestor;
         let _status: fn(&RepoHandle, &GitCli) -> Result<StatusSnapshot, GitError> =
             capture_status_snapshot;
         let _diff: fn(&RepoHandle, &GitCli, Option<&str>) -> Result<super::DiffSnapshot, GitError> =
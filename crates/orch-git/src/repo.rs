@@ -52,6 +52,42 @@ pub fn has_uncommitted_changes(repo: &RepoHandle, git: &GitCli) -> Result<bool,
     Ok(!output.stdout.trim().is_empty())
 }
 
+/// Whether `ancestor` is reachable from `descendant`, e.g. whether a task
+/// branch has landed on the base branch.
+pub fn is_ancestor(
+    repo: &RepoHandle,
+    git: &GitCli,
+    ancestor: &str,
+    descendant: &str,
+) -> Result<bool, GitError> {
+    let args = ["merge-base", "--is-ancestor", ancestor, descendant];
+    match git.run(&repo.root, args) {
+        Ok(_) => Ok(true),
+        // Exit 1 means "not an ancestor"; anything else is a real failure.
+        Err(GitError::CommandFailed {
+            status: Some(1), ..
+        }) => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// `git merge-base a b`, or `None` when the two share no history.
+pub fn merge_base(
+    repo: &RepoHandle,
+    git: &GitCli,
+    a: &str,
+    b: &str,
+) -> Result<Option<String>, GitError> {
+    match git.run(&repo.root, ["merge-base", a, b]) {
+        Ok(output) => Ok(Some(output.stdout.trim().to_string())),
+        // Exit 1 means no common ancestor; anything else is a real failure.
+        Err(GitError::CommandFailed {
+            status: Some(1), ..
+        }) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::fs;
@@ -59,7 +95,7 @@ mod tests {
     use std::process::Command;
     use std::time::{SystemTime, UNIX_EPOCH};
 
-    use super::{current_branch, discover_repo, head_sha};
+    use super::{current_branch, discover_repo, head_sha, is_ancestor};
     use crate::command::GitCli;
     use crate::error::GitError;
 
@@ -164,4 +200,41 @@ mod tests {
 
         let _ = fs::remove_dir_all(&dir);
     }
+
+    #[test]
+    fn is_ancestor_reports_merged_and_unmerged_branches() {
+        let root = init_repo(true);
+        let git = GitCli::default();
+        let repo = discover_repo(&root, &git).expect("discover repo");
+        let base = current_branch(&repo, &git).expect("current branch");
+
+        run_git(&root, &["checkout", "-q", "-b", "task/T1"]);
+        fs::write(root.join("feature.txt"), "feature\n").expect("write file");
+        run_git(&root, &["add", "feature.txt"]);
+        run_git(
+            &root,
+            &[
+                "-c",
+                "user.name=Test User",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-m",
+                "feature",
+            ],
+        );
+
+        assert!(!is_ancestor(&repo, &git, "task/T1", &base).expect("before merge"));
+        assert!(is_ancestor(&repo, &git, &base, "task/T1").expect("base under branch"));
+
+        run_git(&root, &["checkout", "-q", &base]);
+        run_git(&root, &["merge", "-q", "--ff-only", "task/T1"]);
+        assert!(is_ancestor(&repo, &git, "task/T1", &base).expect("after merge"));
+
+        let err = is_ancestor(&repo, &git, "task/missing", &base)
+            .expect_err("unknown branch is an error, not a no");
+        assert!(matches!(err, GitError::CommandFailed { .. }));
+
+        let _ = fs::remove_dir_all(&root);
+    }
 }
@@ -154,6 +154,70 @@ pub fn list_snapshots_for_task(
     list_change_snapshots(repo, git, task_id)
 }
 
+/// The task's snapshot at 1-based `index` in chronological order, or the
+/// latest one when `index` is `None`.
+pub fn find_change_snapshot(
+    repo: &RepoHandle,
+    git: &GitCli,
+    task_id: &str,
+    index: Option<usize>,
+) -> Result<Option<ChangeSnapshot>, GitError> {
+    let mut snapshots = list_change_snapshots(repo, git, task_id)?;
+    Ok(match index {
+        None => snapshots.pop(),
+        Some(0) => None,
+        Some(index) if index > snapshots.len() => None,
+        Some(index) => Some(snapshots.swap_remove(index - 1)),
+    })
+}
+
+/// A stash entry created by [`stash_uncommitted`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StashRef {
+    /// Commit the stash points at; stays valid as newer stashes shift indexes.
+    pub sha: String,
+    pub label: String,
+}
+
+/// Stash tracked and untracked changes under `label` so snapshot checkouts
+/// cannot clobber them. Returns `None` when the worktree is clean.
+pub fn stash_uncommitted(
+    repo: &RepoHandle,
+    git: &GitCli,
+    label: &str,
+) -> Result<Option<StashRef>, GitError> {
+    if capture_status_snapshot(repo, git)?.clean {
+        return Ok(None);
+    }
+
+    git.run(
+        &repo.root,
+        ["stash", "push", "--include-untracked", "--message", label],
+    )?;
+    let sha = git.run(&repo.root, ["rev-parse", "stash@{0}"])?;
+    Ok(Some(StashRef {
+        sha: sha.stdout.trim().to_string(),
+        label: label.to_string(),
+    }))
+}
+
+/// Pop `stash` back onto the worktree, wherever it now sits in the stash list.
+pub fn restore_stash(repo: &RepoHandle, git: &GitCli, stash: &StashRef) -> Result<(), GitError> {
+    let list = git.run(&repo.root, ["stash", "list", "--format=%gd %H"])?;
+    let reference = list
+        .stdout
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .find(|(_, sha)| *sha == stash.sha)
+        .map(|(reference, _)| reference.to_string())
+        .ok_or_else(|| GitError::Parse {
+            context: format!("stash {} ({}) no longer exists", stash.sha, stash.label),
+        })?;
+
+    git.run(&repo.root, ["stash", "pop", reference.as_str()])?;
+    Ok(())
+}
+
 pub fn capture_status_snapshot(
     repo: &RepoHandle,
     git: &GitCli,
@@ -428,7 +492,7 @@ fn remove_worktree_path(repo_root: &Path, path: &Path) -> std::io::Result<()> {
     Ok(())
 }
 
-fn parse_porcelain_status(raw: &str) -> Result<Vec<ChangedFile>, GitError> {
+pub(crate) fn parse_porcelain_status(raw: &str) -> Result<Vec<ChangedFile>, GitError> {
     let mut files = Vec::new();
 
     for line in raw.lines() {
@@ -489,8 +553,9 @@ mod tests {
     use std::time::{SystemTime, UNIX_EPOCH};
 
     use super::{
-        capture_change_snapshot, file_state_from_code, list_change_snapshots, parse_porcelain_status,
-        redo_snapshot, undo_to_snapshot, FileState,
+        capture_change_snapshot, file_state_from_code, find_change_snapshot, list_change_snapshots,
+        parse_porcelain_status, redo_snapshot, restore_stash, stash_uncommitted, undo_to_snapshot,
+        FileState,
     };
     use crate::command::GitCli;
     use crate::repo::discover_repo;
@@ -716,4 +781,78 @@ mod tests {
         assert!(snapshots.is_empty());
         fs::remove_dir_all(root).ok();
     }
+
+    #[test]
+    fn find_change_snapshot_selects_by_one_based_index() {
+        let root = init_repo();
+        commit_file(&root, "README.md", "base\n", "base");
+
+        let git = GitCli::default();
+        let repo = discover_repo(&root, &git).expect("discover repo");
+        let mut shas = Vec::new();
+        for version in ["v1", "v2", "v3"] {
+            commit_file(&root, "README.md", &format!("{version}\n"), version);
+            shas.push(
+                capture_change_snapshot(&repo, &git, "task-index")
+                    .expect("capture snapshot")
+                    .commit_sha,
+            );
+        }
+
+        let pick = |index| {
+            find_change_snapshot(&repo, &git, "task-index", index)
+                .expect("find snapshot")
+                .map(|snapshot| snapshot.commit_sha)
+        };
+        assert_eq!(pick(None), Some(shas[2].clone()));
+        assert_eq!(pick(Some(1)), Some(shas[0].clone()));
+        assert_eq!(pick(Some(2)), Some(shas[1].clone()));
+        assert_eq!(pick(Some(0)), None);
+        assert_eq!(pick(Some(4)), None);
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn stash_uncommitted_protects_dirty_worktree_across_undo() {
+        let root = init_repo();
+        commit_file(&root, "README.md", "before\n", "base");
+        commit_file(&root, "README.md", "after\n", "update");
+
+        let git = GitCli::default();
+        let repo = discover_repo(&root, &git).expect("discover repo");
+        let snapshot =
+            capture_change_snapshot(&repo, &git, "task-stash").expect("capture snapshot");
+        assert_eq!(
+            stash_uncommitted(&repo, &git, "clean").expect("stash clean tree"),
+            None
+        );
+
+        fs::write(root.join("README.md"), "agent edit\n").expect("dirty tracked file");
+        fs::write(root.join("scratch.txt"), "notes\n").expect("write untracked file");
+        let stash = stash_uncommitted(&repo, &git, "othala: before undo task-stash")
+            .expect("stash dirty tree")
+            .expect("dirty tree should be stashed");
+        assert!(!root.join("scratch.txt").exists());
+
+        undo_to_snapshot(&repo, &git, &snapshot).expect("undo snapshot");
+        assert_eq!(
+            fs::read_to_string(root.join("README.md")).expect("read after undo"),
+            "before\n"
+        );
+
+        redo_snapshot(&repo, &git, &snapshot).expect("redo snapshot");
+        restore_stash(&repo, &git, &stash).expect("restore stash");
+        assert_eq!(
+            fs::read_to_string(root.join("README.md")).expect("read after restore"),
+            "agent edit\n"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("scratch.txt")).expect("read untracked"),
+            "notes\n"
+        );
+        assert!(restore_stash(&repo, &git, &stash).is_err());
+
+        fs::remove_dir_all(root).ok();
+    }
 }
@@ -1,13 +1,15 @@
 use std::ffi::OsString;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use chrono::{DateTime, Utc};
 use orch_core::types::TaskId;
 use serde::{Deserialize, Serialize};
 
 use crate::command::GitCli;
 use crate::error::GitError;
 use crate::repo::RepoHandle;
+use crate::snapshot::parse_porcelain_status;
 
 pub const DEFAULT_WORKTREE_ROOT: &str = ".orch/wt";
 
@@ -31,6 +33,14 @@ pub struct ListedWorktree {
     pub head: Option<String>,
 }
 
+/// A local branch and when its tip was committed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalBranch {
+    pub name: String,
+    pub head: String,
+    pub committed_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct WorktreeManager {
     git: GitCli,
@@ -156,6 +166,112 @@ impl WorktreeManager {
     }
 }
 
+/// Files with uncommitted changes, untracked ones included, in the worktree
+/// at `path`.
+pub fn worktree_dirty_files(git: &GitCli, path: &Path) -> Result<Vec<PathBuf>, GitError> {
+    let output = git.run(path, ["status", "--porcelain=v1"])?;
+    Ok(parse_porcelain_status(&output.stdout)?
+        .into_iter()
+        .map(|file| file.path)
+        .collect())
+}
+
+/// Run `git worktree remove <path>`. Git refuses a worktree with uncommitted
+/// changes; use `worktree_dirty_files` to report them first.
+pub fn remove_worktree(repo: &RepoHandle, git: &GitCli, path: &Path) -> Result<(), GitError> {
+    let args = vec![
+        OsString::from("worktree"),
+        OsString::from("remove"),
+        path.as_os_str().to_os_string(),
+    ];
+    git.run(&repo.root, args)?;
+    Ok(())
+}
+
+/// Delete the local branch `name`: `git branch -d`, or `-D` with `force`.
+pub fn delete_branch(
+    repo: &RepoHandle,
+    git: &GitCli,
+    name: &str,
+    force: bool,
+) -> Result<(), GitError> {
+    let flag = if force { "-D" } else { "-d" };
+    git.run(&repo.root, ["branch", flag, name])?;
+    Ok(())
+}
+
+pub fn branch_exists(repo: &RepoHandle, git: &GitCli, name: &str) -> Result<bool, GitError> {
+    let reference = format!("refs/heads/{name}");
+    match git.run(
+        &repo.root,
+        ["rev-parse", "--verify", "--quiet", reference.as_str()],
+    ) {
+        Ok(_) => Ok(true),
+        Err(GitError::CommandFailed { .. }) => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// Whether local branch `name` has commits that are not on `HEAD`.
+pub fn branch_has_unmerged_commits(
+    repo: &RepoHandle,
+    git: &GitCli,
+    name: &str,
+) -> Result<bool, GitError> {
+    let range = format!("HEAD..refs/heads/{name}");
+    let output = git.run(&repo.root, ["rev-list", "--count", range.as_str()])?;
+    let count = output
+        .stdout
+        .trim()
+        .parse::<u64>()
+        .map_err(|_| GitError::Parse {
+            context: format!("rev-list --count {range}: {}", output.stdout.trim()),
+        })?;
+    Ok(count > 0)
+}
+
+/// Every branch under `refs/heads`.
+pub fn list_local_branches(repo: &RepoHandle, git: &GitCli) -> Result<Vec<LocalBranch>, GitError> {
+    let output = git.run(
+        &repo.root,
+        [
+            "for-each-ref",
+            "--format=%(refname:short)%09%(objectname)%09%(committerdate:unix)",
+            "refs/heads",
+        ],
+    )?;
+    parse_local_branches(&output.stdout)
+}
+
+fn parse_local_branches(raw: &str) -> Result<Vec<LocalBranch>, GitError> {
+    raw.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let mut fields = line.split('\t');
+            let (Some(name), Some(head), Some(timestamp)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                return Err(GitError::Parse {
+                    context: format!("unexpected for-each-ref line: {line}"),
+                });
+            };
+            let committed_at = timestamp
+                .trim()
+                .parse::<i64>()
+                .ok()
+                .and_then(|secs| DateTime::from_timestamp(secs, 0))
+                .ok_or_else(|| GitError::Parse {
+                    context: format!("invalid commit timestamp for {name}: {timestamp}"),
+                })?;
+            Ok(LocalBranch {
+                name: name.to_string(),
+                head: head.to_string(),
+                committed_at,
+            })
+        })
+        .collect()
+}
+
 fn parse_worktree_list(raw: &str) -> Result<Vec<ListedWorktree>, GitError> {
     let mut listed = Vec::new();
 
@@ -209,7 +325,10 @@ mod tests {
 
     use orch_core::types::TaskId;
 
-    use super::{parse_worktree_list, WorktreeManager, WorktreeSpec};
+    use super::{
+        branch_has_unmerged_commits, delete_branch, parse_local_branches, parse_worktree_list,
+        remove_worktree, worktree_dirty_files, WorktreeManager, WorktreeSpec,
+    };
     use crate::command::GitCli;
     use crate::repo::discover_repo;
 
@@ -307,6 +426,22 @@ detached
         assert!(matches!(err, crate::error::GitError::Parse { .. }));
     }
 
+    #[test]
+    fn parse_local_branches_reads_name_head_and_commit_time() {
+        let raw = "main\t1111111111111111111111111111111111111111\t1700000000\n\
+                   task/T1\t2222222222222222222222222222222222222222\t1700000600\n";
+
+        let parsed = parse_local_branches(raw).expect("parse branches");
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[1].name, "task/T1");
+        assert_eq!(parsed[1].head, "2222222222222222222222222222222222222222");
+        assert_eq!(parsed[1].committed_at.timestamp(), 1_700_000_600);
+        assert!(matches!(
+            parse_local_branches("main\tabc\tyesterday"),
+            Err(crate::error::GitError::Parse { .. })
+        ));
+    }
+
     #[test]
     fn task_worktree_path_joins_repo_root_relative_root_and_task_id() {
         let manager = WorktreeManager::default();
@@ -350,4 +485,54 @@ detached
 
         let _ = fs::remove_dir_all(&root);
     }
+
+    #[test]
+    fn remove_worktree_and_delete_branch_respect_unmerged_commits() {
+        let root = init_repo_with_branch("task/T2");
+        let git = GitCli::default();
+        let repo = discover_repo(&root, &git).expect("discover repo");
+        let manager = WorktreeManager::default();
+        let info = manager
+            .create_for_existing_branch(
+                &repo,
+                &WorktreeSpec {
+                    task_id: TaskId("T2".to_string()),
+                    branch: "task/T2".to_string(),
+                },
+            )
+            .expect("create worktree");
+        assert!(worktree_dirty_files(&git, &info.path)
+            .expect("clean status")
+            .is_empty());
+        assert!(!branch_has_unmerged_commits(&repo, &git, "task/T2").expect("merged check"));
+
+        fs::write(info.path.join("work.txt"), "wip\n").expect("write file");
+        assert_eq!(
+            worktree_dirty_files(&git, &info.path).expect("dirty status"),
+            vec![PathBuf::from("work.txt")]
+        );
+        assert!(remove_worktree(&repo, &git, &info.path).is_err());
+
+        run_git(&info.path, &["add", "work.txt"]);
+        run_git(
+            &info.path,
+            &[
+                "-c",
+                "user.name=Test User",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-m",
+                "wip",
+            ],
+        );
+        assert!(branch_has_unmerged_commits(&repo, &git, "task/T2").expect("unmerged check"));
+
+        remove_worktree(&repo, &git, &info.path).expect("remove clean worktree");
+        assert!(!info.path.exists());
+        assert!(delete_branch(&repo, &git, "task/T2", false).is_err());
+        delete_branch(&repo, &git, "task/T2", true).expect("force delete branch");
+
+        let _ = fs::remove_dir_all(&root);
+    }
 }
@@ -0,0 +1,118 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Audit log location relative to the repository root.
+pub const GRAPHITE_AUDIT_LOG: &str = ".othala/graphite-audit.jsonl";
+
+/// Stderr kept per audit entry, in characters.
+const MAX_AUDIT_STDERR_CHARS: usize = 2_000;
+
+/// One `gt` invocation, executed or (in dry-run) skipped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphiteAuditEntry {
+    pub at: DateTime<Utc>,
+    pub command: String,
+    pub args: Vec<String>,
+    pub cwd: PathBuf,
+    /// `None` when the command was not run or did not exit normally.
+    pub exit_code: Option<i32>,
+    pub duration_ms: u64,
+    pub stderr: String,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+pub fn audit_log_path(repo_root: &Path) -> PathBuf {
+    repo_root.join(GRAPHITE_AUDIT_LOG)
+}
+
+pub fn truncate_stderr(stderr: &str) -> String {
+    let trimmed = stderr.trim_end();
+    match trimmed.char_indices().nth(MAX_AUDIT_STDERR_CHARS) {
+        Some((cut, _)) => format!("{}...", &trimmed[..cut]),
+        None => trimmed.to_string(),
+    }
+}
+
+pub fn append_audit_entry(path: &Path, entry: &GraphiteAuditEntry) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let line = serde_json::to_string(entry).map_err(io::Error::other)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+/// The last `limit` entries, oldest first. Unparseable lines are skipped and a
+/// missing log reads as empty.
+pub fn read_audit_log(path: &Path, limit: usize) -> io::Result<Vec<GraphiteAuditEntry>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut entries: Vec<GraphiteAuditEntry> = contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    if entries.len() > limit {
+        entries.drain(..entries.len() - limit);
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(args: &[&str]) -> GraphiteAuditEntry {
+        GraphiteAuditEntry {
+            at: Utc::now(),
+            command: "gt".to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            cwd: PathBuf::from("/tmp/repo"),
+            exit_code: Some(0),
+            duration_ms: 5,
+            stderr: String::new(),
+            dry_run: false,
+        }
+    }
+
+    #[test]
+    fn read_audit_log_returns_the_last_entries_and_skips_garbage() {
+        let dir = std::env::temp_dir().join(format!(
+            "othala-graphite-audit-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let path = audit_log_path(&dir);
+        assert!(read_audit_log(&path, 10).expect("missing log").is_empty());
+
+        append_audit_entry(&path, &entry(&["sync"])).expect("append sync");
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| writeln!(file, "not json"))
+            .expect("append garbage");
+        append_audit_entry(&path, &entry(&["restack"])).expect("append restack");
+        append_audit_entry(&path, &entry(&["status"])).expect("append status");
+
+        let entries = read_audit_log(&path, 2).expect("read log");
+        let args: Vec<&str> = entries.iter().map(|entry| entry.args[0].as_str()).collect();
+        assert_eq!(args, vec!["restack", "status"]);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn truncate_stderr_caps_long_output() {
+        assert_eq!(truncate_stderr("boom\n"), "boom");
+        let long = "x".repeat(MAX_AUDIT_STDERR_CHARS + 10);
+        let truncated = truncate_stderr(&long);
+        assert_eq!(truncated.len(), MAX_AUDIT_STDERR_CHARS + 3);
+        assert!(truncated.ends_with("..."));
+    }
+}
@@ -4,16 +4,23 @@ use std::path::{Path, PathBuf};
 use orch_core::types::{SubmitMode, TaskId};
 
 use crate::command::{AllowedAutoCommand, GraphiteCli};
+use crate::conflicts::scan_conflicted_files;
 use crate::error::GraphiteError;
 use crate::types::{
-    infer_task_dependencies_from_stack, parse_gt_log_short, GraphiteStackSnapshot,
-    GraphiteStatusSnapshot, InferredStackDependency,
+    infer_task_dependencies_from_stack, parse_branch_pr_status, parse_gt_log_json,
+    parse_gt_log_short, parse_gt_version, parse_submit_output, supports_log_json,
+    GraphiteStackSnapshot, GraphiteStatusSnapshot, InferredStackDependency, PrStatus, SubmittedPr,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RestackOutcome {
     Restacked,
-    Conflict { stdout: String, stderr: String },
+    Conflict {
+        stdout: String,
+        stderr: String,
+        /// Working-tree files left with conflict markers, repo-relative.
+        conflicted_files: Vec<PathBuf>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -37,6 +44,18 @@ impl GraphiteClient {
         }
     }
 
+    /// Append every `gt` invocation to the JSONL file at `path`.
+    pub fn with_audit_log(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cli.audit_log = Some(path.into());
+        self
+    }
+
+    /// Log mutating commands to the audit log instead of running them.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.cli.dry_run = dry_run;
+        self
+    }
+
     pub fn create_branch(&self, branch: &str, message: &str) -> Result<(), GraphiteError> {
         if branch.trim().is_empty() {
             return Err(GraphiteError::ContractViolation {
@@ -101,7 +120,14 @@ impl GraphiteClient {
             AllowedAutoCommand::Restack,
             ["restack", "--no-interactive"],
         );
-        classify_restack_result(result)
+        let mut outcome = classify_restack_result(result);
+        if let Ok(RestackOutcome::Conflict {
+            conflicted_files, ..
+        }) = &mut outcome
+        {
+            *conflicted_files = scan_conflicted_files(&self.repo_root);
+        }
+        outcome
     }
 
     pub fn move_current_branch_onto(&self, target_branch: &str) -> Result<(), GraphiteError> {
@@ -166,35 +192,80 @@ impl GraphiteClient {
         Ok(parse_gt_log_short(&output.stdout))
     }
 
+    pub fn log_json_snapshot(&self) -> Result<GraphiteStackSnapshot, GraphiteError> {
+        let output = self.cli.run_allowed(
+            self.repo_root.as_path(),
+            AllowedAutoCommand::LogJson,
+            ["log", "--json"],
+        )?;
+        parse_gt_log_json(&output.stdout)
+    }
+
+    /// Installed `gt` version, or `None` when `gt --version` prints
+    /// something unrecognizable.
+    pub fn cli_version(&self) -> Result<Option<(u32, u32, u32)>, GraphiteError> {
+        let output = self.cli.run_allowed(
+            self.repo_root.as_path(),
+            AllowedAutoCommand::Version,
+            ["--version"],
+        )?;
+        Ok(parse_gt_version(&output.stdout))
+    }
+
+    /// Stack snapshot from `gt log --json` when the CLI supports it, falling
+    /// back to scraping `gt log short` on older releases.
+    pub fn stack_snapshot(&self) -> Result<GraphiteStackSnapshot, GraphiteError> {
+        match self.cli_version()? {
+            Some(version) if supports_log_json(version) => self.log_json_snapshot(),
+            _ => self.log_short_snapshot(),
+        }
+    }
+
     pub fn infer_stack_dependencies(
         &self,
         branch_to_task: &HashMap<String, TaskId>,
     ) -> Result<Vec<InferredStackDependency>, GraphiteError> {
-        let snapshot = self.log_short_snapshot()?;
+        let snapshot = self.stack_snapshot()?;
         Ok(infer_task_dependencies_from_stack(
             &snapshot,
             branch_to_task,
         ))
     }
 
-    pub fn submit(&self, mode: SubmitMode) -> Result<(), GraphiteError> {
-        match mode {
-            SubmitMode::Single => {
-                self.cli.run_allowed(
-                    self.repo_root.as_path(),
-                    AllowedAutoCommand::Submit,
-                    ["submit", "--no-edit", "--no-interactive"],
-                )?;
-            }
-            SubmitMode::Stack => {
-                self.cli.run_allowed(
-                    self.repo_root.as_path(),
-                    AllowedAutoCommand::SubmitStack,
-                    ["submit", "--stack", "--no-edit", "--no-interactive"],
-                )?;
-            }
+    /// Submit the branch (or stack) and return the PRs `gt` reported.
+    pub fn submit(&self, mode: SubmitMode) -> Result<Vec<SubmittedPr>, GraphiteError> {
+        let output = match mode {
+            SubmitMode::Single => self.cli.run_allowed(
+                self.repo_root.as_path(),
+                AllowedAutoCommand::Submit,
+                ["submit", "--no-edit", "--no-interactive"],
+            )?,
+            SubmitMode::Stack => self.cli.run_allowed(
+                self.repo_root.as_path(),
+                AllowedAutoCommand::SubmitStack,
+                ["submit", "--stack", "--no-edit", "--no-interactive"],
+            )?,
+        };
+        Ok(parse_submit_output(&format!(
+            "{}\n{}",
+            output.stdout, output.stderr
+        )))
+    }
+
+    /// The state of the PR already attached to `branch`, checked before
+    /// submitting so an open PR is not submitted a second time.
+    pub fn branch_pr_status(&self, branch: &str) -> Result<PrStatus, GraphiteError> {
+        if branch.trim().is_empty() {
+            return Err(GraphiteError::ContractViolation {
+                message: "branch name for gt info must not be empty".to_string(),
+            });
         }
-        Ok(())
+        let output = self.cli.run_allowed(
+            self.repo_root.as_path(),
+            AllowedAutoCommand::BranchInfo,
+            ["info", branch],
+        )?;
+        Ok(parse_branch_pr_status(&output.stdout))
     }
 
     pub fn repo_init(&self, trunk: &str) -> Result<(), GraphiteError> {
@@ -248,7 +319,11 @@ fn classify_restack_result(
         Ok(_) => Ok(RestackOutcome::Restacked),
         Err(err @ GraphiteError::CommandFailed { .. }) if err.is_restack_conflict() => {
             if let GraphiteError::CommandFailed { stdout, stderr, .. } = err {
-                Ok(RestackOutcome::Conflict { stdout, stderr })
+                Ok(RestackOutcome::Conflict {
+                    stdout,
+                    stderr,
+                    conflicted_files: Vec::new(),
+                })
             } else {
                 unreachable!("guard guarantees CommandFailed");
             }
@@ -259,16 +334,129 @@ fn classify_restack_result(
 
 #[cfg(test)]
 mod tests {
-    use std::path::PathBuf;
+    use std::ffi::OsString;
+    use std::io;
+    use std::os::unix::process::ExitStatusExt;
+    use std::path::{Path, PathBuf};
+    use std::process::{ExitStatus, Output};
+    use std::sync::{Arc, Mutex};
 
     use orch_core::types::SubmitMode;
 
-    use crate::command::GraphiteCli;
+    use crate::audit::read_audit_log;
     use crate::command::GraphiteOutput;
+    use crate::command::{GraphiteCli, GraphiteCommandRunner};
     use crate::error::GraphiteError;
 
     use super::{classify_restack_result, GraphiteClient, RestackOutcome};
 
+    /// Records invocations and answers each with the same exit code/stderr.
+    #[derive(Debug)]
+    struct FakeRunner {
+        exit_code: i32,
+        stderr: &'static str,
+        calls: Mutex<Vec<Vec<String>>>,
+    }
+
+    impl FakeRunner {
+        fn new(exit_code: i32, stderr: &'static str) -> Arc<Self> {
+            Arc::new(Self {
+                exit_code,
+                stderr,
+                calls: Mutex::new(Vec::new()),
+            })
+        }
+
+        fn calls(&self) -> Vec<Vec<String>> {
+            self.calls.lock().expect("calls lock").clone()
+        }
+    }
+
+    impl GraphiteCommandRunner for FakeRunner {
+        fn run(&self, _binary: &Path, _cwd: &Path, args: &[OsString]) -> io::Result<Output> {
+            self.calls.lock().expect("calls lock").push(
+                args.iter()
+                    .map(|arg| arg.to_string_lossy().into_owned())
+                    .collect(),
+            );
+            Ok(Output {
+                status: ExitStatus::from_raw(self.exit_code << 8),
+                stdout: Vec::new(),
+                stderr: self.stderr.as_bytes().to_vec(),
+            })
+        }
+    }
+
+    fn audited_client(runner: Arc<FakeRunner>, name: &str) -> (GraphiteClient, PathBuf) {
+        let dir = std::env::temp_dir().join(format!(
+            "othala-graphite-{name}-{}",
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let audit_log = dir.join(".othala/graphite-audit.jsonl");
+        let client =
+            GraphiteClient::with_cli(dir.clone(), GraphiteCli::new("gt").with_runner(runner))
+                .with_audit_log(&audit_log);
+        (client, audit_log)
+    }
+
+    #[test]
+    fn audit_log_records_each_invocation_with_exit_code_and_stderr() {
+        let runner = FakeRunner::new(0, "");
+        let (client, audit_log) = audited_client(runner.clone(), "audit");
+        client.sync_trunk().expect("sync");
+        client.restack().expect("restack");
+
+        let entries = read_audit_log(&audit_log, 10).expect("read audit log");
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].command, "gt");
+        assert_eq!(
+            entries[0].args,
+            vec!["sync", "--no-restack", "--force", "--no-interactive"]
+        );
+        assert_eq!(entries[0].cwd, client.repo_root);
+        assert_eq!(entries[0].exit_code, Some(0));
+        assert!(!entries[0].dry_run);
+        assert_eq!(entries[1].args, vec!["restack", "--no-interactive"]);
+        assert_eq!(runner.calls().len(), 2);
+
+        let failing = FakeRunner::new(1, "authentication failed\n");
+        let (client, audit_log) = audited_client(failing, "audit-fail");
+        assert!(client.sync_trunk().is_err());
+        let entries = read_audit_log(&audit_log, 10).expect("read audit log");
+        assert_eq!(entries[0].exit_code, Some(1));
+        assert_eq!(entries[0].stderr, "authentication failed");
+
+        let _ = std::fs::remove_dir_all(client.repo_root);
+    }
+
+    #[test]
+    fn dry_run_logs_mutating_commands_without_running_them() {
+        let runner = FakeRunner::new(0, "");
+        let (client, audit_log) = audited_client(runner.clone(), "dry-run");
+        let client = client.with_dry_run(true);
+
+        client.create_branch("task/T1", "start T1").expect("create");
+        client.submit(SubmitMode::Single).expect("submit");
+        client.status_snapshot().expect("status still runs");
+
+        assert_eq!(runner.calls(), vec![vec!["status".to_string()]]);
+        let entries = read_audit_log(&audit_log, 10).expect("read audit log");
+        let logged: Vec<(&str, bool, Option<i32>)> = entries
+            .iter()
+            .map(|entry| (entry.args[0].as_str(), entry.dry_run, entry.exit_code))
+            .collect();
+        assert_eq!(
+            logged,
+            vec![
+                ("create", true, None),
+                ("submit", true, None),
+                ("status", false, Some(0)),
+            ]
+        );
+
+        let _ = std::fs::remove_dir_all(client.repo_root);
+    }
+
     #[test]
     fn classifies_successful_restack() {
         let outcome = classify_restack_result(Ok(GraphiteOutput {
@@ -294,6 +482,7 @@ mod tests {
             RestackOutcome::Conflict {
                 stdout: "".to_string(),
                 stderr: "CONFLICT (content)".to_string(),
+                conflicted_files: Vec::new(),
             }
         );
     }
@@ -383,6 +572,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn branch_pr_status_queries_gt_info_for_branch() {
+        let client = GraphiteClient::with_cli(
+            PathBuf::from("."),
+            GraphiteCli::new("/definitely/missing/gt"),
+        );
+        assert!(matches!(
+            client.branch_pr_status("  "),
+            Err(GraphiteError::ContractViolation { .. })
+        ));
+        match client.branch_pr_status("task/T1") {
+            Err(GraphiteError::Io { command, .. }) => {
+                assert!(command.ends_with("gt info task/T1"));
+            }
+            other => panic!("expected io error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn stack_snapshot_detects_cli_version_first() {
+        let client = GraphiteClient::with_cli(
+            PathBuf::from("."),
+            GraphiteCli::new("/definitely/missing/gt"),
+        );
+        match client.stack_snapshot() {
+            Err(GraphiteError::Io { command, .. }) => {
+                assert!(command.ends_with("gt --version"));
+            }
+            other => panic!("expected io error, got {other:?}"),
+        }
+        match client.log_json_snapshot() {
+            Err(GraphiteError::Io { command, .. }) => {
+                assert!(command.ends_with("gt log --json"));
+            }
+            other => panic!("expected io error, got {other:?}"),
+        }
+    }
+
     #[test]
     fn move_current_branch_onto_rejects_blank_target() {
         let client = GraphiteClient::with_cli(
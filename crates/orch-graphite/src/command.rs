@@ -1,7 +1,14 @@
 use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::io;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Output};
+use std::sync::Arc;
+use std::time::Instant;
 
+use chrono::Utc;
+
+use crate::audit::{append_audit_entry, truncate_stderr, GraphiteAuditEntry};
 use crate::error::GraphiteError;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -15,11 +22,42 @@ pub enum AllowedAutoCommand {
     ContinueConflict,
     Abort,
     LogShort,
+    LogJson,
+    Version,
     Status,
     Submit,
     SubmitStack,
     RepoInit,
     Track,
+    BranchInfo,
+}
+
+impl AllowedAutoCommand {
+    /// Commands that only inspect the repo. These still run in dry-run mode.
+    pub fn is_read_only(self) -> bool {
+        matches!(
+            self,
+            AllowedAutoCommand::LogShort
+                | AllowedAutoCommand::LogJson
+                | AllowedAutoCommand::Version
+                | AllowedAutoCommand::Status
+                | AllowedAutoCommand::BranchInfo
+        )
+    }
+}
+
+/// Spawns the `gt` process. Swapped out in tests to observe invocations.
+pub trait GraphiteCommandRunner: fmt::Debug + Send + Sync {
+    fn run(&self, binary: &Path, cwd: &Path, args: &[OsString]) -> io::Result<Output>;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProcessGraphiteRunner;
+
+impl GraphiteCommandRunner for ProcessGraphiteRunner {
+    fn run(&self, binary: &Path, cwd: &Path, args: &[OsString]) -> io::Result<Output> {
+        Command::new(binary).current_dir(cwd).args(args).output()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -28,16 +66,29 @@ pub struct GraphiteOutput {
     pub stderr: String,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct GraphiteCli {
     pub binary: PathBuf,
+    /// Every invocation is appended here when set.
+    pub audit_log: Option<PathBuf>,
+    /// Log mutating commands instead of running them.
+    pub dry_run: bool,
+    runner: Arc<dyn GraphiteCommandRunner>,
 }
 
+impl PartialEq for GraphiteCli {
+    fn eq(&self, other: &Self) -> bool {
+        self.binary == other.binary
+            && self.audit_log == other.audit_log
+            && self.dry_run == other.dry_run
+    }
+}
+
+impl Eq for GraphiteCli {}
+
 impl Default for GraphiteCli {
     fn default() -> Self {
-        Self {
-            binary: PathBuf::from("gt"),
-        }
+        Self::new("gt")
     }
 }
 
@@ -45,9 +96,17 @@ impl GraphiteCli {
     pub fn new(binary: impl Into<PathBuf>) -> Self {
         Self {
             binary: binary.into(),
+            audit_log: None,
+            dry_run: false,
+            runner: Arc::new(ProcessGraphiteRunner),
         }
     }
 
+    pub fn with_runner(mut self, runner: Arc<dyn GraphiteCommandRunner>) -> Self {
+        self.runner = runner;
+        self
+    }
+
     pub fn run_allowed<I, S>(
         &self,
         cwd: &Path,
@@ -64,17 +123,43 @@ impl GraphiteCli {
             .collect();
         validate_contract(allowed, &owned_args)?;
 
-        let mut command = Command::new(&self.binary);
-        command.current_dir(cwd);
-        for arg in &owned_args {
-            command.arg(arg);
+        if self.dry_run && !allowed.is_read_only() {
+            self.audit(cwd, &owned_args, None, 0, "", true);
+            return Ok(GraphiteOutput {
+                stdout: String::new(),
+                stderr: String::new(),
+            });
         }
 
         let rendered = render_command(&self.binary, &owned_args);
-        let output = command.output().map_err(|source| GraphiteError::Io {
-            command: rendered.clone(),
-            source,
-        })?;
+        let started = Instant::now();
+        let result = self.runner.run(&self.binary, cwd, &owned_args);
+        let duration_ms = started.elapsed().as_millis() as u64;
+        let output = match result {
+            Ok(output) => output,
+            Err(source) => {
+                self.audit(
+                    cwd,
+                    &owned_args,
+                    None,
+                    duration_ms,
+                    &source.to_string(),
+                    false,
+                );
+                return Err(GraphiteError::Io {
+                    command: rendered,
+                    source,
+                });
+            }
+        };
+        self.audit(
+            cwd,
+            &owned_args,
+            output.status.code(),
+            duration_ms,
+            &String::from_utf8_lossy(&output.stderr),
+            false,
+        );
 
         let stdout =
             String::from_utf8(output.stdout).map_err(|source| GraphiteError::NonUtf8Output {
@@ -100,6 +185,35 @@ impl GraphiteCli {
 
         Ok(GraphiteOutput { stdout, stderr })
     }
+
+    /// Best effort: a failed audit write never fails the command itself.
+    fn audit(
+        &self,
+        cwd: &Path,
+        args: &[OsString],
+        exit_code: Option<i32>,
+        duration_ms: u64,
+        stderr: &str,
+        dry_run: bool,
+    ) {
+        let Some(path) = &self.audit_log else {
+            return;
+        };
+        let entry = GraphiteAuditEntry {
+            at: Utc::now(),
+            command: self.binary.to_string_lossy().into_owned(),
+            args: args
+                .iter()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect(),
+            cwd: cwd.to_path_buf(),
+            exit_code,
+            duration_ms,
+            stderr: truncate_stderr(stderr),
+            dry_run,
+        };
+        let _ = append_audit_entry(path, &entry);
+    }
 }
 
 fn validate_contract(allowed: AllowedAutoCommand, args: &[OsString]) -> Result<(), GraphiteError> {
@@ -158,6 +272,10 @@ fn validate_contract(allowed: AllowedAutoCommand, args: &[OsString]) -> Result<(
         AllowedAutoCommand::LogShort => {
             args.len() == 2 && arg_eq(args, 0, "log") && arg_eq(args, 1, "short")
         }
+        AllowedAutoCommand::LogJson => {
+            args.len() == 2 && arg_eq(args, 0, "log") && arg_eq(args, 1, "--json")
+        }
+        AllowedAutoCommand::Version => args.len() == 1 && arg_eq(args, 0, "--version"),
         AllowedAutoCommand::Status => args.len() == 1 && arg_eq(args, 0, "status"),
         AllowedAutoCommand::Submit => {
             args.len() == 3
@@ -197,6 +315,12 @@ fn validate_contract(allowed: AllowedAutoCommand, args: &[OsString]) -> Result<(
                 }
                 && arg_eq(args, 4, "--no-interactive")
         }
+        AllowedAutoCommand::BranchInfo => {
+            args.len() == 2 && arg_eq(args, 0, "info") && {
+                let branch = arg_at(args, 1);
+                !branch.trim().is_empty() && !branch.starts_with('-')
+            }
+        }
     };
 
     if ok {
@@ -276,6 +400,8 @@ mod tests {
         );
         assert!(validate_contract(AllowedAutoCommand::Abort, &os(&["abort"])).is_ok());
         assert!(validate_contract(AllowedAutoCommand::LogShort, &os(&["log", "short"])).is_ok());
+        assert!(validate_contract(AllowedAutoCommand::LogJson, &os(&["log", "--json"])).is_ok());
+        assert!(validate_contract(AllowedAutoCommand::Version, &os(&["--version"])).is_ok());
         assert!(validate_contract(AllowedAutoCommand::Status, &os(&["status"])).is_ok());
         assert!(validate_contract(
             AllowedAutoCommand::Submit,
@@ -302,6 +428,9 @@ mod tests {
             &os(&["track", "task/T1", "--parent", "main", "--no-interactive"])
         )
         .is_ok());
+        assert!(
+            validate_contract(AllowedAutoCommand::BranchInfo, &os(&["info", "task/T1"])).is_ok()
+        );
     }
 
     #[test]
@@ -382,6 +511,10 @@ mod tests {
         )
         .expect_err("empty track parent should fail");
         assert!(matches!(err, GraphiteError::ContractViolation { .. }));
+
+        let err = validate_contract(AllowedAutoCommand::BranchInfo, &os(&["info", "--diff"]))
+            .expect_err("info branch must not be flag-like");
+        assert!(matches!(err, GraphiteError::ContractViolation { .. }));
     }
 
     #[test]
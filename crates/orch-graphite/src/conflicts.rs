@@ -0,0 +1,157 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const OURS_MARKER: &str = "<<<<<<<";
+const SEPARATOR_MARKER: &str = "=======";
+const THEIRS_MARKER: &str = ">>>>>>>";
+
+/// Whether `line` is a conflict marker line: the marker on its own or
+/// followed by a space and a label.
+fn is_marker_line(line: &str, marker: &str) -> bool {
+    match line.trim_end().strip_prefix(marker) {
+        Some(rest) => rest.is_empty() || rest.starts_with(' '),
+        None => false,
+    }
+}
+
+/// Whether `content` holds a conflict hunk: `<<<<<<<`, `=======` and
+/// `>>>>>>>` lines in that order. A lone `=======` (e.g. a setext heading or
+/// a divider in a doc) is not a conflict.
+pub fn has_conflict_markers(content: &str) -> bool {
+    const ORDER: [&str; 3] = [OURS_MARKER, SEPARATOR_MARKER, THEIRS_MARKER];
+    let mut seen = 0;
+    for line in content.lines() {
+        if is_marker_line(line, ORDER[seen]) {
+            seen += 1;
+            if seen == ORDER.len() {
+                return true;
+            }
+        } else if seen > 0 && is_marker_line(line, OURS_MARKER) {
+            // A new hunk opened before the previous one closed; start over.
+            seen = 1;
+        }
+    }
+    false
+}
+
+/// Files changed in the working tree of `repo_root` that still contain
+/// conflict markers, repo-relative and sorted. Unreadable or missing files
+/// are skipped; an empty list when git can't be queried.
+pub fn scan_conflicted_files(repo_root: &Path) -> Vec<PathBuf> {
+    let Ok(output) = Command::new("git")
+        .args(["diff", "--name-only", "-z"])
+        .current_dir(repo_root)
+        .output()
+    else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+
+    let mut files = String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .filter(|path| {
+            std::fs::read(repo_root.join(path))
+                .map(|bytes| has_conflict_markers(&String::from_utf8_lossy(&bytes)))
+                .unwrap_or(false)
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+    files.dedup();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    use super::{has_conflict_markers, scan_conflicted_files};
+
+    #[test]
+    fn detects_markers_in_conflict_order() {
+        let content = "fn main() {\n<<<<<<< HEAD\n    old();\n=======\n    new();\n>>>>>>> feature (add new)\n}\n";
+        assert!(has_conflict_markers(content));
+
+        let diff3 = "<<<<<<< ours\na\n||||||| base\nb\n=======\nc\n>>>>>>> theirs\n";
+        assert!(has_conflict_markers(diff3));
+    }
+
+    #[test]
+    fn lone_separator_in_docs_is_not_a_conflict() {
+        assert!(!has_conflict_markers("Title\n=======\n\nBody text.\n"));
+        assert!(!has_conflict_markers("a\n=======\nb\n>>>>>>> x\n"));
+        assert!(!has_conflict_markers("<<<<<<< HEAD\na\n>>>>>>> x\n"));
+    }
+
+    #[test]
+    fn markers_out_of_order_are_not_a_conflict() {
+        assert!(!has_conflict_markers(">>>>>>> x\n=======\n<<<<<<< HEAD\n"));
+        assert!(!has_conflict_markers("=======\n<<<<<<< HEAD\n>>>>>>> x\n"));
+    }
+
+    #[test]
+    fn markers_must_start_the_line() {
+        assert!(!has_conflict_markers(
+            "  <<<<<<< HEAD\n  =======\n  >>>>>>> x\n"
+        ));
+        assert!(!has_conflict_markers(
+            "<<<<<<<< long\n========\n>>>>>>>> long\n"
+        ));
+    }
+
+    #[test]
+    fn unterminated_hunk_restarts_at_the_next_opening_marker() {
+        let content = "<<<<<<< HEAD\na\n<<<<<<< HEAD\nb\n=======\nc\n>>>>>>> x\n";
+        assert!(has_conflict_markers(content));
+    }
+
+    #[test]
+    fn scans_changed_files_for_conflict_markers() {
+        let dir = std::env::temp_dir().join(format!(
+            "othala-conflict-scan-{}-{}",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        std::fs::create_dir_all(dir.join("src")).expect("create dir");
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(args)
+                .current_dir(&dir)
+                .output()
+                .expect("run git")
+                .status;
+            assert!(status.success(), "git {args:?} failed");
+        };
+        git(&["init", "-q"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "test"]);
+        std::fs::write(dir.join("README.md"), "Docs\n").expect("write");
+        std::fs::write(dir.join("src/lib.rs"), "fn a() {}\n").expect("write");
+        std::fs::write(dir.join("src/main.rs"), "fn main() {}\n").expect("write");
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "init"]);
+
+        std::fs::write(dir.join("README.md"), "Docs\n=======\n\nMore.\n").expect("write");
+        std::fs::write(
+            dir.join("src/lib.rs"),
+            "<<<<<<< HEAD\nfn a() {}\n=======\nfn b() {}\n>>>>>>> other\n",
+        )
+        .expect("write");
+        std::fs::write(
+            dir.join("untracked.rs"),
+            "<<<<<<< HEAD\n=======\n>>>>>>> x\n",
+        )
+        .expect("write");
+
+        assert_eq!(
+            scan_conflicted_files(&dir),
+            vec![PathBuf::from("src/lib.rs")]
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,19 +1,24 @@
+pub mod audit;
 pub mod client;
 pub mod command;
+pub mod conflicts;
 pub mod error;
 pub mod types;
 
+pub use audit::*;
 pub use client::*;
 pub use command::*;
+pub use conflicts::*;
 pub use error::*;
 pub use types::*;
 
 #[cfg(test)]
 mod tests {
     use super::{
-        infer_task_dependencies_from_stack, looks_like_restack_conflict, parse_gt_log_short,
-        AllowedAutoCommand, GraphiteCli, GraphiteClient, GraphiteError, GraphiteStackSnapshot,
-        GraphiteStatusSnapshot, InferredStackDependency, RestackOutcome, StackNode,
+        has_conflict_markers, infer_task_dependencies_from_stack, looks_like_restack_conflict,
+        parse_gt_log_json, parse_gt_log_short, AllowedAutoCommand, GraphiteCli, GraphiteClient,
+        GraphiteError, GraphiteStackSnapshot, GraphiteStatusSnapshot, InferredStackDependency,
+        RestackOutcome, StackNode,
     };
     use std::any::TypeId;
     use std::path::{Path, PathBuf};
@@ -34,11 +39,14 @@ mod tests {
     #[test]
     fn crate_root_reexports_helpers_and_methods() {
         let _parse: fn(&str) -> GraphiteStackSnapshot = parse_gt_log_short;
+        let _parse_json: fn(&str) -> Result<GraphiteStackSnapshot, GraphiteError> =
+            parse_gt_log_json;
         let _infer: fn(
             &GraphiteStackSnapshot,
             &std::collections::HashMap<String, orch_core::types::TaskId>,
         ) -> Vec<InferredStackDependency> = infer_task_dependencies_from_stack;
         let _conflict: fn(&str, &str) -> bool = looks_like_restack_conflict;
+        let _markers: fn(&str) -> bool = has_conflict_markers;
 
         let client = GraphiteClient::new(PathBuf::from("/tmp/repo"));
         let root: &Path = client.repo_root();
@@ -3,6 +3,8 @@ use orch_core::types::TaskId;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
+use crate::error::GraphiteError;
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct GraphiteStatusSnapshot {
     pub captured_at: DateTime<Utc>,
@@ -15,6 +17,22 @@ pub struct StackNode {
     pub branch: Option<String>,
     pub depth_hint: usize,
     pub is_current: bool,
+    /// Parent branch, known only when the stack came from `gt log --json`.
+    #[serde(default)]
+    pub parent: Option<String>,
+    #[serde(default)]
+    pub pr: Option<StackPr>,
+    /// Whether the branch must be restacked onto its parent; `None` when the
+    /// CLI output does not say.
+    #[serde(default)]
+    pub needs_restack: Option<bool>,
+}
+
+/// PR attached to a branch in the stack.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StackPr {
+    pub number: u64,
+    pub url: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -23,6 +41,10 @@ pub struct InferredStackDependency {
     pub child_branch: String,
     pub parent_task_id: TaskId,
     pub child_task_id: TaskId,
+    #[serde(default)]
+    pub parent_pr: Option<StackPr>,
+    #[serde(default)]
+    pub child_pr: Option<StackPr>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -31,6 +53,90 @@ pub struct GraphiteStackSnapshot {
     pub nodes: Vec<StackNode>,
 }
 
+/// A pull request reported by `gt submit`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubmittedPr {
+    pub branch: Option<String>,
+    pub number: u64,
+    pub url: String,
+}
+
+/// Extract the PRs listed in `gt submit` output, e.g.
+/// `task/T1: https://app.graphite.dev/github/pr/org/repo/42 (created)`.
+pub fn parse_submit_output(raw: &str) -> Vec<SubmittedPr> {
+    raw.lines().filter_map(parse_submit_line).collect()
+}
+
+fn parse_submit_line(line: &str) -> Option<SubmittedPr> {
+    let url_start = line.find("https://").or_else(|| line.find("http://"))?;
+    let url = line[url_start..]
+        .split_whitespace()
+        .next()?
+        .trim_end_matches([',', ';', ')', '.'])
+        .to_string();
+    let number = pr_number_from_url(&url)?;
+    let branch = line[..url_start]
+        .trim()
+        .trim_end_matches(':')
+        .split_whitespace()
+        .last()
+        .filter(|token| looks_like_branch_token(token))
+        .map(normalize_branch_name);
+
+    Some(SubmittedPr {
+        branch,
+        number,
+        url,
+    })
+}
+
+/// State of the PR attached to a branch, as reported by `gt info`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PrStatus {
+    None,
+    Open(String),
+    Merged,
+    Closed,
+}
+
+/// Read the PR state from `gt info <branch>` output, e.g.
+/// `PR #42 (Open) Add login` followed by the PR link. Drafts and review
+/// states count as open; a branch without a `PR #` line has no PR.
+pub fn parse_branch_pr_status(raw: &str) -> PrStatus {
+    let Some(pr_line) = raw
+        .lines()
+        .find(|line| line.to_ascii_lowercase().contains("pr #"))
+    else {
+        return PrStatus::None;
+    };
+    let lowered = pr_line.to_ascii_lowercase();
+    if lowered.contains("(merged)") {
+        return PrStatus::Merged;
+    }
+    if lowered.contains("(closed)") {
+        return PrStatus::Closed;
+    }
+    let url = raw
+        .lines()
+        .filter_map(parse_submit_line)
+        .map(|pr| pr.url)
+        .next()
+        .unwrap_or_default();
+    PrStatus::Open(url)
+}
+
+/// The PR number is the path segment after `pull/` (GitHub) or the last
+/// segment of a `/pr/<org>/<repo>/<n>` Graphite link.
+fn pr_number_from_url(url: &str) -> Option<u64> {
+    let path = url.split(['?', '#']).next()?;
+    let segments: Vec<&str> = path.trim_end_matches('/').split('/').collect();
+    let is_pr_link = segments.iter().any(|segment| *segment == "pull" || *segment == "pr");
+    if !is_pr_link {
+        return None;
+    }
+    segments.last()?.parse().ok()
+}
+
 pub fn parse_gt_log_short(raw: &str) -> GraphiteStackSnapshot {
     let nodes = raw
         .lines()
@@ -44,10 +150,140 @@ pub fn parse_gt_log_short(raw: &str) -> GraphiteStackSnapshot {
     }
 }
 
+/// Oldest `gt` release whose `gt log` accepts `--json`.
+pub const GT_LOG_JSON_MIN_VERSION: (u32, u32, u32) = (1, 5, 0);
+
+/// Read the `major.minor.patch` version from `gt --version` output, which is
+/// either a bare `1.5.2` or prefixed like `gt version 1.5.2`.
+pub fn parse_gt_version(raw: &str) -> Option<(u32, u32, u32)> {
+    raw.split_whitespace().find_map(|token| {
+        let mut parts = token.trim_start_matches('v').split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.parse().ok()?;
+        let patch = parts
+            .next()
+            .map(|patch| patch.split('-').next().unwrap_or(patch))
+            .unwrap_or("0")
+            .parse()
+            .ok()?;
+        Some((major, minor, patch))
+    })
+}
+
+pub fn supports_log_json(version: (u32, u32, u32)) -> bool {
+    version >= GT_LOG_JSON_MIN_VERSION
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum GtLogJson {
+    Branches(Vec<GtLogBranch>),
+    Wrapped { branches: Vec<GtLogBranch> },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GtLogBranch {
+    #[serde(alias = "branch")]
+    name: String,
+    #[serde(default)]
+    parent: Option<String>,
+    #[serde(default, alias = "current")]
+    is_current: bool,
+    #[serde(default)]
+    pr: Option<GtLogPr>,
+    #[serde(default)]
+    needs_restack: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GtLogPr {
+    number: u64,
+    #[serde(default)]
+    url: String,
+}
+
+/// Parse `gt log --json`: an array of branches (optionally wrapped in
+/// `{"branches": [...]}`) naming each branch's parent, PR and restack state.
+pub fn parse_gt_log_json(raw: &str) -> Result<GraphiteStackSnapshot, GraphiteError> {
+    let parsed: GtLogJson = serde_json::from_str(raw).map_err(|err| GraphiteError::Parse {
+        message: format!("gt log --json: {err}"),
+    })?;
+    let branches = match parsed {
+        GtLogJson::Branches(branches) | GtLogJson::Wrapped { branches } => branches,
+    };
+
+    let parents = branches
+        .iter()
+        .map(|branch| {
+            (
+                normalize_branch_name(&branch.name),
+                branch.parent.as_deref().map(normalize_branch_name),
+            )
+        })
+        .collect::<HashMap<_, _>>();
+
+    let nodes = branches
+        .into_iter()
+        .map(|branch| {
+            let name = normalize_branch_name(&branch.name);
+            StackNode {
+                depth_hint: parent_chain_depth(&name, &parents),
+                raw_line: branch.name,
+                branch: Some(name),
+                is_current: branch.is_current,
+                parent: branch.parent.as_deref().map(normalize_branch_name),
+                pr: branch.pr.map(|pr| StackPr {
+                    number: pr.number,
+                    url: pr.url,
+                }),
+                needs_restack: branch.needs_restack,
+            }
+        })
+        .collect();
+
+    Ok(GraphiteStackSnapshot {
+        captured_at: Utc::now(),
+        nodes,
+    })
+}
+
+/// Number of ancestors of `branch` listed in the stack; stops on cycles.
+fn parent_chain_depth(branch: &str, parents: &HashMap<String, Option<String>>) -> usize {
+    let mut depth = 0;
+    let mut seen = HashSet::from([branch.to_string()]);
+    let mut current = branch.to_string();
+    while let Some(Some(parent)) = parents.get(&current) {
+        if !seen.insert(parent.clone()) {
+            break;
+        }
+        depth += 1;
+        current = parent.clone();
+    }
+    depth
+}
+
 pub fn infer_task_dependencies_from_stack(
     snapshot: &GraphiteStackSnapshot,
     branch_to_task: &HashMap<String, TaskId>,
 ) -> Vec<InferredStackDependency> {
+    let prs = snapshot
+        .nodes
+        .iter()
+        .filter_map(|node| Some((node.branch.clone()?, node.pr.clone()?)))
+        .collect::<HashMap<_, _>>();
+
+    // `gt log --json` names parents outright; only the short format needs
+    // the edges guessed from line order and indentation.
+    if snapshot.nodes.iter().any(|node| node.parent.is_some()) {
+        let edges = snapshot
+            .nodes
+            .iter()
+            .filter_map(|node| Some((node.parent.clone()?, node.branch.clone()?)))
+            .collect::<Vec<_>>();
+        return to_task_dependencies(edges, branch_to_task, &prs);
+    }
+
     let branches = snapshot
         .nodes
         .iter()
@@ -72,7 +308,7 @@ pub fn infer_task_dependencies_from_stack(
         infer_edges_by_depth(&branches)
     };
 
-    to_task_dependencies(branch_edges, branch_to_task)
+    to_task_dependencies(branch_edges, branch_to_task, &prs)
 }
 
 fn parse_stack_line(line: &str) -> StackNode {
@@ -85,6 +321,9 @@ fn parse_stack_line(line: &str) -> StackNode {
         branch,
         depth_hint,
         is_current,
+        parent: None,
+        pr: None,
+        needs_restack: None,
     }
 }
 
@@ -171,6 +410,7 @@ fn infer_edges_by_depth(branches: &[(String, usize)]) -> Vec<(String, String)> {
 fn to_task_dependencies(
     edges: Vec<(String, String)>,
     branch_to_task: &HashMap<String, TaskId>,
+    prs: &HashMap<String, StackPr>,
 ) -> Vec<InferredStackDependency> {
     let normalized_map = branch_to_task
         .iter()
@@ -199,6 +439,8 @@ fn to_task_dependencies(
         }
 
         out.push(InferredStackDependency {
+            parent_pr: prs.get(&normalized_parent).cloned(),
+            child_pr: prs.get(&normalized_child).cloned(),
             parent_branch: normalized_parent,
             child_branch: normalized_child,
             parent_task_id,
@@ -221,7 +463,24 @@ mod tests {
 
     use orch_core::types::TaskId;
 
-    use super::{infer_task_dependencies_from_stack, parse_gt_log_short};
+    use super::{
+        infer_task_dependencies_from_stack, parse_branch_pr_status, parse_gt_log_json,
+        parse_gt_log_short, parse_gt_version, parse_submit_output, supports_log_json, PrStatus,
+        StackPr, SubmittedPr,
+    };
+    use crate::error::GraphiteError;
+
+    const LOG_JSON: &str = include_str!("../fixtures/gt-log/log.json");
+    const LOG_SHORT: &str = include_str!("../fixtures/gt-log/log-short.txt");
+    const LOG_TRUNCATED_JSON: &str = include_str!("../fixtures/gt-log/log-truncated.json");
+
+    fn fixture_mapping() -> HashMap<String, TaskId> {
+        HashMap::from([
+            ("task/T1".to_string(), TaskId("T1".to_string())),
+            ("task/T2".to_string(), TaskId("T2".to_string())),
+            ("task/T3".to_string(), TaskId("T3".to_string())),
+        ])
+    }
 
     #[test]
     fn infers_linear_dependencies_when_depth_is_uniform() {
@@ -366,4 +625,132 @@ o refs/heads/root/main:
         assert_eq!(snapshot.nodes[2].branch.as_deref(), Some("feature/api"));
         assert_eq!(snapshot.nodes[3].branch.as_deref(), Some("feature/web"));
     }
+
+    #[test]
+    fn parses_prs_from_submit_output() {
+        let raw = "\
+🥞 Validating that this Graphite stack is ready to submit...
+✔ task/T1: https://app.graphite.dev/github/pr/acme/app/41 (updated)
+✔ task/T2: https://github.com/acme/app/pull/42 (created)
+Pushed branch task/T3 without a PR
+";
+        assert_eq!(
+            parse_submit_output(raw),
+            vec![
+                SubmittedPr {
+                    branch: Some("task/T1".to_string()),
+                    number: 41,
+                    url: "https://app.graphite.dev/github/pr/acme/app/41".to_string(),
+                },
+                SubmittedPr {
+                    branch: Some("task/T2".to_string()),
+                    number: 42,
+                    url: "https://github.com/acme/app/pull/42".to_string(),
+                },
+            ]
+        );
+        assert!(parse_submit_output("https://docs.graphite.dev/cli").is_empty());
+    }
+
+    #[test]
+    fn maps_gt_info_output_to_pr_status() {
+        let open = "\
+◉  task/T1 (current)
+│  2 hours ago
+│
+│  PR #42 (Draft) Add login
+│  https://app.graphite.dev/github/pr/acme/app/42
+│  Last submitted version: v2
+";
+        assert_eq!(
+            parse_branch_pr_status(open),
+            PrStatus::Open("https://app.graphite.dev/github/pr/acme/app/42".to_string())
+        );
+        assert_eq!(
+            parse_branch_pr_status("PR #7 (Merged) Fix\nhttps://github.com/acme/app/pull/7"),
+            PrStatus::Merged
+        );
+        assert_eq!(
+            parse_branch_pr_status("task/T1\nPR #8 (Closed) Old attempt"),
+            PrStatus::Closed
+        );
+        assert_eq!(
+            parse_branch_pr_status("◉  task/T1 (current)\n│  2 hours ago\n"),
+            PrStatus::None
+        );
+    }
+
+    #[test]
+    fn parses_gt_log_json_fixture_with_prs_and_sync_status() {
+        let snapshot = parse_gt_log_json(LOG_JSON).expect("parse");
+        assert_eq!(snapshot.nodes.len(), 4);
+
+        let t2 = &snapshot.nodes[2];
+        assert_eq!(t2.branch.as_deref(), Some("task/T2"));
+        assert_eq!(t2.parent.as_deref(), Some("task/T1"));
+        assert_eq!(t2.depth_hint, 2);
+        assert!(t2.is_current);
+        assert_eq!(t2.needs_restack, Some(true));
+        assert_eq!(t2.pr.as_ref().map(|pr| pr.number), Some(42));
+        assert_eq!(snapshot.nodes[3].pr, None);
+        assert_eq!(snapshot.nodes[3].needs_restack, None);
+
+        let inferred = infer_task_dependencies_from_stack(&snapshot, &fixture_mapping());
+        let edges = inferred
+            .iter()
+            .map(|x| (x.parent_task_id.0.as_str(), x.child_task_id.0.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(edges, vec![("T1", "T2"), ("T1", "T3")]);
+        assert_eq!(
+            inferred[0].parent_pr,
+            Some(StackPr {
+                number: 41,
+                url: "https://app.graphite.dev/github/pr/acme/app/41".to_string(),
+            })
+        );
+        assert_eq!(inferred[0].child_pr.as_ref().map(|pr| pr.number), Some(42));
+        assert_eq!(inferred[1].child_pr, None);
+    }
+
+    #[test]
+    fn gt_log_short_fixture_infers_same_edges_without_prs() {
+        let snapshot = parse_gt_log_short(LOG_SHORT);
+        assert!(snapshot.nodes[2].is_current);
+        assert!(snapshot.nodes.iter().all(|node| node.parent.is_none()));
+
+        let inferred = infer_task_dependencies_from_stack(&snapshot, &fixture_mapping());
+        let edges = inferred
+            .iter()
+            .map(|x| (x.parent_task_id.0.as_str(), x.child_task_id.0.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(edges, vec![("T1", "T2"), ("T1", "T3")]);
+        assert!(inferred
+            .iter()
+            .all(|x| x.parent_pr.is_none() && x.child_pr.is_none()));
+    }
+
+    #[test]
+    fn corrupted_gt_log_json_is_a_parse_error() {
+        for raw in [
+            LOG_TRUNCATED_JSON,
+            LOG_SHORT,
+            r#"{"branches": [{"parent": "main"}]}"#,
+        ] {
+            let err = parse_gt_log_json(raw).expect_err("corrupted output must not parse");
+            assert!(matches!(err, GraphiteError::Parse { .. }), "{raw}: {err}");
+        }
+    }
+
+    #[test]
+    fn parses_gt_version_and_gates_json_support() {
+        assert_eq!(parse_gt_version("1.5.2\n"), Some((1, 5, 2)));
+        assert_eq!(
+            parse_gt_version("gt version v1.4.0-beta.1"),
+            Some((1, 4, 0))
+        );
+        assert_eq!(parse_gt_version("unknown"), None);
+        assert!(supports_log_json((1, 5, 0)));
+        assert!(supports_log_json((2, 0, 0)));
+        assert!(!supports_log_json((1, 4, 9)));
+    }
 }
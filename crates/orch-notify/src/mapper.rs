@@ -8,12 +8,19 @@ use crate::types::{NotificationMessage, NotificationSeverity, NotificationTopic}
 /// Map an event to a notification, if applicable.
 pub fn notification_for_event(event: &Event) -> Option<NotificationMessage> {
     match &event.kind {
-        EventKind::VerifyCompleted { success: false } => Some(NotificationMessage {
+        EventKind::VerifyCompleted {
+            success: false,
+            failure_summary,
+            ..
+        } => Some(NotificationMessage {
             at: Utc::now(),
             topic: NotificationTopic::VerifyFailed,
             severity: NotificationSeverity::Error,
             title: "Verification failed".to_string(),
-            body: "A verification command failed. Check verify logs for details.".to_string(),
+            body: match failure_summary {
+                Some(summary) => format!("Verification failed with {summary}"),
+                None => "A verification command failed. Check verify logs for details.".to_string(),
+            },
             task_id: event.task_id.clone(),
             repo_id: event.repo_id.clone(),
         }),
@@ -35,6 +42,28 @@ pub fn notification_for_event(event: &Event) -> Option<NotificationMessage> {
             task_id: event.task_id.clone(),
             repo_id: event.repo_id.clone(),
         }),
+        EventKind::QuestionAsked { question, .. } => Some(NotificationMessage {
+            at: Utc::now(),
+            topic: NotificationTopic::NeedsHuman,
+            severity: NotificationSeverity::Warning,
+            title: "Agent is waiting on a question".to_string(),
+            body: question.clone(),
+            task_id: event.task_id.clone(),
+            repo_id: event.repo_id.clone(),
+        }),
+        EventKind::QuestionStale {
+            question,
+            open_secs,
+            ..
+        } => Some(NotificationMessage {
+            at: Utc::now(),
+            topic: NotificationTopic::NeedsHuman,
+            severity: NotificationSeverity::Error,
+            title: "Agent question still unanswered".to_string(),
+            body: format!("Unanswered for {}m: {question}", open_secs / 60),
+            task_id: event.task_id.clone(),
+            repo_id: event.repo_id.clone(),
+        }),
         EventKind::Error { code, message } => Some(NotificationMessage {
             at: Utc::now(),
             topic: NotificationTopic::TaskError,
@@ -57,7 +86,7 @@ pub fn notification_for_event(event: &Event) -> Option<NotificationMessage> {
             task_id: event.task_id.clone(),
             repo_id: event.repo_id.clone(),
         }),
-        EventKind::VerifyCompleted { success: true } => Some(NotificationMessage {
+        EventKind::VerifyCompleted { success: true, .. } => Some(NotificationMessage {
             at: Utc::now(),
             topic: NotificationTopic::VerifyPassed,
             severity: NotificationSeverity::Info,
@@ -233,6 +262,19 @@ pub fn vars_from_event(event: &Event) -> std::collections::HashMap<String, Strin
         EventKind::NeedsHuman { reason } => {
             vars.insert("reason".to_string(), reason.clone());
         }
+        EventKind::QuestionAsked { question, .. } => {
+            vars.insert("reason".to_string(), format!("agent asked: {question}"));
+        }
+        EventKind::QuestionStale {
+            question,
+            open_secs,
+            ..
+        } => {
+            vars.insert(
+                "reason".to_string(),
+                format!("question unanswered for {}m: {question}", open_secs / 60),
+            );
+        }
         EventKind::Error { code, message } => {
             vars.insert("code".to_string(), code.clone());
             vars.insert("message".to_string(), message.clone());
@@ -307,12 +349,27 @@ mod tests {
 
     #[test]
     fn maps_failed_verify_to_error_notification() {
-        let event = mk_event(EventKind::VerifyCompleted { success: false });
+        let event = mk_event(EventKind::VerifyCompleted {
+            success: false,
+            failure_bundle: None,
+            failure_summary: None,
+        });
         let message = notification_for_event(&event).expect("expected notification");
         assert_eq!(message.topic, NotificationTopic::VerifyFailed);
         assert_eq!(message.severity, NotificationSeverity::Error);
         assert_eq!(message.task_id, event.task_id);
         assert_eq!(message.repo_id, event.repo_id);
+
+        let summarized = mk_event(EventKind::VerifyCompleted {
+            success: false,
+            failure_bundle: None,
+            failure_summary: Some("1 failure: test tests::adds".to_string()),
+        });
+        let message = notification_for_event(&summarized).expect("expected notification");
+        assert_eq!(
+            message.body,
+            "Verification failed with 1 failure: test tests::adds"
+        );
     }
 
     #[test]
@@ -335,6 +392,27 @@ mod tests {
         assert_eq!(message.body, "exploded");
     }
 
+    #[test]
+    fn maps_question_events_to_needs_human() {
+        let asked = mk_event(EventKind::QuestionAsked {
+            question_id: "Q-T1-1".to_string(),
+            question: "Which database should I target?".to_string(),
+        });
+        let message = notification_for_event(&asked).expect("expected notification");
+        assert_eq!(message.topic, NotificationTopic::NeedsHuman);
+        assert_eq!(message.severity, NotificationSeverity::Warning);
+        assert_eq!(message.body, "Which database should I target?");
+
+        let stale = mk_event(EventKind::QuestionStale {
+            question_id: "Q-T1-1".to_string(),
+            question: "Which database should I target?".to_string(),
+            open_secs: 3_600,
+        });
+        let message = notification_for_event(&stale).expect("expected notification");
+        assert_eq!(message.severity, NotificationSeverity::Error);
+        assert!(message.body.starts_with("Unanswered for 60m"));
+    }
+
     #[test]
     fn maps_restack_conflict_to_warning_notification() {
         let event = mk_event(EventKind::RestackConflict);
@@ -345,7 +423,11 @@ mod tests {
 
     #[test]
     fn maps_successful_verify_to_info() {
-        let verify_ok = mk_event(EventKind::VerifyCompleted { success: true });
+        let verify_ok = mk_event(EventKind::VerifyCompleted {
+            success: true,
+            failure_bundle: None,
+            failure_summary: None,
+        });
         let message = notification_for_event(&verify_ok).expect("expected notification");
         assert_eq!(message.topic, NotificationTopic::VerifyPassed);
         assert_eq!(message.severity, NotificationSeverity::Info);
@@ -19,6 +19,7 @@ pub enum UiAction {
     PauseTask,
     ResumeTask,
     SendChatMessage,
+    OpenDiff,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -32,6 +33,23 @@ pub enum UiCommand {
     CycleStateFilter,
     ToggleFocusedPane,
     ToggleFocusedTask,
+    OpenTaskDetail,
+    ScrollPageUp,
+    ScrollPageDown,
+    ScrollToTop,
+    ScrollToBottom,
+    StartPaneSearch,
+    NextOlderMatch,
+    NextNewerMatch,
+    CopyPane,
+    OpenNewTaskDialog,
+    CycleSortMode,
+    ReverseSort,
+    ToggleTimeline,
+    ToggleStackView,
+    OpenLogView,
+    PinFile,
+    OpenCommandPalette,
     ShowHelp,
     Quit,
 }
@@ -44,6 +62,9 @@ pub fn map_key_to_command(key: KeyEvent) -> Option<UiCommand> {
     if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
         return Some(UiCommand::Quit);
     }
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('p') {
+        return Some(UiCommand::OpenCommandPalette);
+    }
     if key.code == KeyCode::Esc {
         return Some(UiCommand::Quit);
     }
@@ -57,7 +78,13 @@ pub fn map_key_to_command(key: KeyEvent) -> Option<UiCommand> {
         KeyCode::Right => Some(UiCommand::SelectNextPane),
         KeyCode::Left => Some(UiCommand::SelectPreviousPane),
         KeyCode::Tab => Some(UiCommand::ToggleFocusedPane),
-        KeyCode::Enter => Some(UiCommand::ToggleFocusedTask),
+        KeyCode::Enter => Some(UiCommand::OpenTaskDetail),
+        KeyCode::PageUp => Some(UiCommand::ScrollPageUp),
+        KeyCode::PageDown => Some(UiCommand::ScrollPageDown),
+        KeyCode::Home => Some(UiCommand::ScrollToTop),
+        KeyCode::End => Some(UiCommand::ScrollToBottom),
+        KeyCode::Char('y') => Some(UiCommand::CopyPane),
+        KeyCode::Char('b') => Some(UiCommand::ToggleStackView),
         KeyCode::Char('/') => Some(UiCommand::StartFilter),
         KeyCode::Char('F') => Some(UiCommand::CycleStateFilter),
         KeyCode::Char('?') => Some(UiCommand::ShowHelp),
@@ -80,6 +107,21 @@ pub fn map_key_to_command(key: KeyEvent) -> Option<UiCommand> {
     }
 }
 
+/// Key mapping inside a focused pane or task view, where `/`, `n` and `N`
+/// drive the pane search instead of their dashboard meanings.
+pub fn map_focused_key_to_command(key: KeyEvent) -> Option<UiCommand> {
+    if key.kind != KeyEventKind::Press || key.modifiers.contains(KeyModifiers::CONTROL) {
+        return map_key_to_command(key);
+    }
+    match key.code {
+        KeyCode::Enter => Some(UiCommand::ToggleFocusedTask),
+        KeyCode::Char('/') => Some(UiCommand::StartPaneSearch),
+        KeyCode::Char('n') => Some(UiCommand::NextOlderMatch),
+        KeyCode::Char('N') => Some(UiCommand::NextNewerMatch),
+        _ => map_key_to_command(key),
+    }
+}
+
 pub fn action_label(action: UiAction) -> &'static str {
     match action {
         UiAction::CreateTask => "create_task",
@@ -97,6 +139,7 @@ pub fn action_label(action: UiAction) -> &'static str {
         UiAction::PauseTask => "pause_task",
         UiAction::ResumeTask => "resume_task",
         UiAction::SendChatMessage => "send_chat_message",
+        UiAction::OpenDiff => "open_diff",
     }
 }
 
@@ -104,7 +147,7 @@ pub fn action_label(action: UiAction) -> &'static str {
 mod tests {
     use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
-    use super::{action_label, map_key_to_command, UiAction, UiCommand};
+    use super::{action_label, map_focused_key_to_command, map_key_to_command, UiAction, UiCommand};
 
     #[test]
     fn map_key_to_command_maps_navigation_and_actions() {
@@ -158,7 +201,7 @@ mod tests {
         );
         assert_eq!(
             map_key_to_command(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)),
-            Some(UiCommand::ToggleFocusedTask)
+            Some(UiCommand::OpenTaskDetail)
         );
         assert_eq!(
             map_key_to_command(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE)),
@@ -176,6 +219,10 @@ mod tests {
             map_key_to_command(KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE)),
             Some(UiCommand::Dispatch(UiAction::SendChatMessage))
         );
+        assert_eq!(
+            map_key_to_command(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::NONE)),
+            Some(UiCommand::ToggleStackView)
+        );
     }
 
     #[test]
@@ -188,12 +235,47 @@ mod tests {
             map_key_to_command(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
             Some(UiCommand::Quit)
         );
+        assert_eq!(
+            map_key_to_command(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL)),
+            Some(UiCommand::OpenCommandPalette)
+        );
         assert_eq!(
             map_key_to_command(KeyEvent::new(KeyCode::Char('z'), KeyModifiers::NONE)),
             None
         );
     }
 
+    #[test]
+    fn scroll_search_and_copy_keys_map_to_commands() {
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        assert_eq!(map_key_to_command(key(KeyCode::PageUp)), Some(UiCommand::ScrollPageUp));
+        assert_eq!(map_key_to_command(key(KeyCode::PageDown)), Some(UiCommand::ScrollPageDown));
+        assert_eq!(map_key_to_command(key(KeyCode::Home)), Some(UiCommand::ScrollToTop));
+        assert_eq!(map_key_to_command(key(KeyCode::End)), Some(UiCommand::ScrollToBottom));
+        assert_eq!(map_key_to_command(key(KeyCode::Char('y'))), Some(UiCommand::CopyPane));
+
+        assert_eq!(
+            map_focused_key_to_command(key(KeyCode::Char('/'))),
+            Some(UiCommand::StartPaneSearch)
+        );
+        assert_eq!(
+            map_focused_key_to_command(key(KeyCode::Char('n'))),
+            Some(UiCommand::NextOlderMatch)
+        );
+        assert_eq!(
+            map_focused_key_to_command(KeyEvent::new(KeyCode::Char('N'), KeyModifiers::SHIFT)),
+            Some(UiCommand::NextNewerMatch)
+        );
+        assert_eq!(
+            map_focused_key_to_command(key(KeyCode::Enter)),
+            Some(UiCommand::ToggleFocusedTask)
+        );
+        assert_eq!(
+            map_focused_key_to_command(key(KeyCode::Char('s'))),
+            Some(UiCommand::Dispatch(UiAction::StartAgent))
+        );
+    }
+
     #[test]
     fn action_label_matches_expected_snake_case_values() {
         assert_eq!(action_label(UiAction::CreateTask), "create_task");
@@ -214,5 +296,6 @@ mod tests {
         assert_eq!(action_label(UiAction::PauseTask), "pause_task");
         assert_eq!(action_label(UiAction::ResumeTask), "resume_task");
         assert_eq!(action_label(UiAction::SendChatMessage), "send_chat_message");
+        assert_eq!(action_label(UiAction::OpenDiff), "open_diff");
     }
 }
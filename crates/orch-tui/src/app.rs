@@ -1,12 +1,19 @@
 use chrono::Utc;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use orch_core::state::TaskState;
-use orch_core::types::{ModelKind, Session, Task, TaskId};
+use orch_core::types::{ModelKind, Session, Task, TaskId, TaskPriority};
 use std::collections::{HashMap, HashSet, VecDeque};
 
-use crate::action::{action_label, map_key_to_command, UiAction, UiCommand};
+use crate::action::{
+    action_label, map_focused_key_to_command, map_key_to_command, UiAction, UiCommand,
+};
 use crate::event::TuiEvent;
-use crate::model::{pane_category_of, AgentPane, AgentPaneStatus, DashboardState, SessionDisplay};
+use crate::model::{
+    pane_category_of, stack_tree, AgentPane, AgentPaneStatus, CreateTaskField, CreateTaskForm,
+    DashboardState, PaneCategory, SessionDisplay, TaskOverviewRow, TASK_DETAIL_EVENT_LIMIT,
+};
+use crate::palette::{filter_entries, PaletteEntry, PaletteScope};
+use crate::pane_history::chat_log_path;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum QueuedAction {
@@ -19,28 +26,21 @@ pub enum QueuedAction {
     CreateTask {
         repo: String,
         title: String,
-        model: String,
+        model: ModelKind,
+        priority: TaskPriority,
+        stack: bool,
+        stack_on: Option<TaskId>,
+    },
+    CopyToClipboard {
+        text: String,
     },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum InputMode {
     Normal,
-    NewTaskDialog {
-        /// Which field is active: 0=repo, 1=title, 2=model
-        active_field: usize,
-        repo: String,
-        title: String,
-        model: String,
-    },
-    NewChatPrompt {
-        buffer: String,
-    },
-    ModelSelect {
-        prompt: String,
-        models: Vec<ModelKind>,
-        selected: usize,
-    },
+    /// Editing `DashboardState::create_task_form`.
+    CreateTaskForm,
     DeleteTaskConfirm {
         task_id: TaskId,
         branch: Option<String>,
@@ -49,6 +49,10 @@ pub enum InputMode {
     FilterInput {
         buffer: String,
     },
+    /// Incremental search in the focused pane.
+    PaneSearch {
+        buffer: String,
+    },
     ChatInput {
         buffer: String,
         task_id: TaskId,
@@ -58,10 +62,18 @@ pub enum InputMode {
         history_index: Option<usize>,
     },
     LogView {
+        /// Header label, e.g. "Agent Log" or "Diff".
+        title: String,
         task_id: String,
         log_lines: Vec<String>,
         scroll_offset: usize,
     },
+    /// Ctrl-P fuzzy search over the palette registry.
+    CommandPalette {
+        query: String,
+        /// Index into the filtered entries.
+        selected: usize,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -164,24 +176,49 @@ impl TuiApp {
         self.apply_active_session_filter();
     }
 
+    /// The task list shows top-level tasks; stacked children only appear in
+    /// the stack view.
     fn apply_active_session_filter(&mut self) {
+        let top_level = self
+            .all_tasks
+            .iter()
+            .filter(|task| task.parent_task_id.is_none());
         if let Some(session_id) = self.active_session_id.as_ref() {
             if let Some(task_ids) = self.session_task_ids.get(session_id) {
                 let allowed_ids: HashSet<&str> = task_ids.iter().map(|id| id.0.as_str()).collect();
-                self.state.tasks = self
-                    .all_tasks
-                    .iter()
+                self.state.tasks = top_level
                     .filter(|task| allowed_ids.contains(task.task_id.0.as_str()))
                     .cloned()
                     .collect();
             } else {
                 self.active_session_id = None;
-                self.state.tasks = self.all_tasks.clone();
+                self.state.tasks = top_level.cloned().collect();
             }
         } else {
-            self.state.tasks = self.all_tasks.clone();
+            self.state.tasks = top_level.cloned().collect();
         }
         self.state.ensure_selected_task_visible();
+        self.ensure_stack_selection();
+    }
+
+    /// Every task, including stacked children, in stack order with depths.
+    pub fn stack_nodes(&self) -> Vec<(usize, &TaskOverviewRow)> {
+        stack_tree(&self.all_tasks)
+    }
+
+    /// Keep the stack selection on the same task across refreshes, falling
+    /// back to the first node when it disappears.
+    fn ensure_stack_selection(&mut self) {
+        let nodes = self.stack_nodes();
+        let still_present = self
+            .state
+            .stack_selected
+            .as_ref()
+            .is_some_and(|selected| nodes.iter().any(|(_, row)| &row.task_id == selected));
+        if !still_present {
+            let first = nodes.first().map(|(_, row)| row.task_id.clone());
+            self.state.stack_selected = first;
+        }
     }
 
     fn load_selected_session_tasks(&mut self) {
@@ -239,40 +276,54 @@ impl TuiApp {
             return;
         }
 
-        if matches!(self.input_mode, InputMode::Normal) && key.code == KeyCode::Char('N') {
-            self.begin_new_task_dialog();
+        let focused_view = self.state.focused_task || self.state.focused_pane_idx.is_some();
+
+        if matches!(self.input_mode, InputMode::Normal) && self.state.show_stack && !focused_view {
+            match key.code {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.move_stack_selection(false);
+                    return;
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.move_stack_selection(true);
+                    return;
+                }
+                KeyCode::Char('r') => {
+                    self.queue_stack_restack();
+                    return;
+                }
+                KeyCode::Char('s') => {
+                    self.queue_stack_submit();
+                    return;
+                }
+                KeyCode::Esc => {
+                    self.toggle_stack_view();
+                    return;
+                }
+                _ => {}
+            }
+        }
+
+        if matches!(self.input_mode, InputMode::Normal)
+            && !focused_view
+            && key.code == KeyCode::Char('N')
+        {
+            self.begin_create_task_form();
             return;
         }
 
         if matches!(self.input_mode, InputMode::Normal) && key.code == KeyCode::Char('S') {
-            self.state.sort_mode = self.state.sort_mode.next();
-            self.state.show_sessions = !self.state.show_sessions;
-            self.state.ensure_selected_session_visible();
-            self.state.status_line = if self.state.show_sessions {
-                format!("sort: {} | sessions shown", self.state.sort_mode.label())
-            } else {
-                format!("sort: {} | sessions hidden", self.state.sort_mode.label())
-            };
+            self.cycle_sort_mode();
             return;
         }
 
         if matches!(self.input_mode, InputMode::Normal) && key.code == KeyCode::Char('R') {
-            self.state.sort_reversed = !self.state.sort_reversed;
-            self.state.status_line = if self.state.sort_reversed {
-                "sort direction: reversed".to_string()
-            } else {
-                "sort direction: normal".to_string()
-            };
+            self.reverse_sort();
             return;
         }
 
         if matches!(self.input_mode, InputMode::Normal) && key.code == KeyCode::Char('t') {
-            self.state.show_timeline = !self.state.show_timeline;
-            self.state.status_line = if self.state.show_timeline {
-                "timeline shown".to_string()
-            } else {
-                "timeline hidden".to_string()
-            };
+            self.toggle_timeline();
             return;
         }
 
@@ -314,37 +365,25 @@ impl TuiApp {
             && self.state.focused_task
             && key.code == KeyCode::Char('l')
         {
-            let Some(task_id) = self.state.selected_task().map(|task| task.task_id.0.clone()) else {
-                self.state.status_line = "no task selected for log view".to_string();
-                return;
-            };
-
-            let log_lines = self
-                .state
-                .log_root
-                .as_deref()
-                .map(|root| load_agent_log(root, &task_id))
-                .unwrap_or_else(|| vec!["No log output available.".to_string()]);
-
-            self.input_mode = InputMode::LogView {
-                task_id: task_id.clone(),
-                log_lines,
-                scroll_offset: 0,
-            };
-            self.state.status_line = format!("agent log view: {task_id}");
+            self.open_log_view();
             return;
         }
 
         if key.code == KeyCode::Esc {
+            if focused_view && self.state.focused_search_status().is_some() {
+                self.state.clear_pane_search();
+                self.state.status_line = "search cleared".to_string();
+                return;
+            }
             if self.state.focused_task {
                 self.state.focused_task = false;
-                self.state.scroll_back = 0;
+                self.state.reset_scroll();
                 self.state.status_line = "task detail closed".to_string();
                 return;
             }
             if self.state.focused_pane_idx.is_some() {
                 self.state.focused_pane_idx = None;
-                self.state.scroll_back = 0;
+                self.state.reset_scroll();
                 self.state.status_line = "pane focus cleared".to_string();
                 return;
             }
@@ -368,35 +407,26 @@ impl TuiApp {
                     if self.state.focused_pane_idx.is_some() {
                         self.state.focused_pane_idx = Some(self.state.selected_pane_idx);
                     }
-                    self.state.scroll_back = 0;
-                    return;
-                }
-                KeyCode::PageUp => {
-                    self.state.scroll_up(20);
-                    return;
-                }
-                KeyCode::PageDown => {
-                    self.state.scroll_down(20);
-                    return;
-                }
-                KeyCode::Home => {
-                    self.state.scroll_to_top();
-                    return;
-                }
-                KeyCode::End => {
-                    self.state.scroll_to_bottom();
                     return;
                 }
                 _ => {} // fall through to normal command handling
             }
         }
 
-        let Some(command) = map_key_to_command(key) else {
-            return;
+        let command = if focused_view {
+            map_focused_key_to_command(key)
+        } else {
+            map_key_to_command(key)
         };
+        if let Some(command) = command {
+            self.run_command(command);
+        }
+    }
 
+    /// Execute a command from the keymap or the command palette.
+    pub fn run_command(&mut self, command: UiCommand) {
         match command {
-            UiCommand::Dispatch(UiAction::CreateTask) => self.begin_new_chat_prompt(),
+            UiCommand::Dispatch(UiAction::CreateTask) => self.begin_create_task_form(),
             UiCommand::Dispatch(UiAction::DeleteTask) => self.begin_delete_task_confirmation(),
             UiCommand::Dispatch(UiAction::SendChatMessage) => self.begin_chat_input(),
             UiCommand::Dispatch(action) => self.push_action(action),
@@ -409,12 +439,12 @@ impl TuiApp {
             UiCommand::ToggleFocusedPane => {
                 if self.state.focused_pane_idx.is_some() {
                     self.state.focused_pane_idx = None;
-                    self.state.scroll_back = 0;
+                    self.state.reset_scroll();
                     self.state.status_line = "pane focus cleared".to_string();
                 } else if !self.state.panes.is_empty() {
                     self.state.focused_pane_idx = Some(self.state.selected_pane_idx);
                     self.state.focused_task = false;
-                    self.state.scroll_back = 0;
+                    self.state.reset_scroll();
                     self.state.status_line = format!(
                         "focused pane {}",
                         self.state.selected_pane_idx.saturating_add(1)
@@ -424,20 +454,29 @@ impl TuiApp {
             UiCommand::ToggleFocusedTask => {
                 if self.state.focused_task {
                     self.state.focused_task = false;
-                    self.state.scroll_back = 0;
+                    self.state.reset_scroll();
                     self.state.status_line = "task detail closed".to_string();
-                } else if !self.state.tasks.is_empty() {
-                    self.state.focused_task = true;
-                    self.state.focused_pane_idx = None;
-                    self.state.scroll_back = 0;
-                    let task_id = self
-                        .state
-                        .selected_task()
-                        .map(|t| t.task_id.0.clone())
-                        .unwrap_or_default();
-                    self.state.status_line = format!("task detail: {task_id}");
+                } else {
+                    self.open_task_detail();
                 }
             }
+            UiCommand::OpenTaskDetail => self.open_task_detail(),
+            UiCommand::ScrollPageUp => self.state.scroll_up(SCROLL_PAGE_LINES),
+            UiCommand::ScrollPageDown => self.state.scroll_down(SCROLL_PAGE_LINES),
+            UiCommand::ScrollToTop => self.state.scroll_to_top(),
+            UiCommand::ScrollToBottom => self.state.scroll_to_bottom(),
+            UiCommand::StartPaneSearch => self.begin_pane_search(),
+            UiCommand::NextOlderMatch => self.jump_to_match(true),
+            UiCommand::NextNewerMatch => self.jump_to_match(false),
+            UiCommand::CopyPane => self.queue_copy(),
+            UiCommand::OpenNewTaskDialog => self.begin_create_task_form(),
+            UiCommand::CycleSortMode => self.cycle_sort_mode(),
+            UiCommand::ReverseSort => self.reverse_sort(),
+            UiCommand::ToggleTimeline => self.toggle_timeline(),
+            UiCommand::ToggleStackView => self.toggle_stack_view(),
+            UiCommand::OpenLogView => self.open_log_view(),
+            UiCommand::PinFile => self.begin_pin_file(),
+            UiCommand::OpenCommandPalette => self.begin_command_palette(),
             UiCommand::ShowHelp => self.input_mode = InputMode::HelpOverlay,
             UiCommand::Quit => self.should_quit = true,
         }
@@ -445,45 +484,34 @@ impl TuiApp {
 
     pub fn handle_paste(&mut self, text: &str) {
         match &mut self.input_mode {
-            InputMode::NewChatPrompt { buffer }
-            | InputMode::FilterInput { buffer }
-            | InputMode::ChatInput { buffer, .. } => {
+            InputMode::FilterInput { buffer } | InputMode::ChatInput { buffer, .. } => {
                 buffer.push_str(&normalize_paste_text(text));
             }
-            InputMode::NewTaskDialog {
-                active_field,
-                repo,
-                title,
-                model,
-            } => {
-                let normalized = normalize_paste_text(text);
-                match *active_field {
-                    0 => repo.push_str(&normalized),
-                    1 => title.push_str(&normalized),
-                    _ => model.push_str(&normalized),
+            InputMode::CreateTaskForm => {
+                if let Some(form) = self.state.create_task_form.as_mut() {
+                    if form.active_field == CreateTaskField::Title {
+                        form.title.push_str(&normalize_paste_text(text));
+                    }
                 }
             }
             _ => {}
         }
     }
 
-    fn begin_new_chat_prompt(&mut self) {
-        self.input_mode = InputMode::NewChatPrompt {
-            buffer: String::new(),
-        };
-        self.state.status_line =
-            "new chat prompt: type feature request, Enter=submit Esc=cancel".to_string();
-    }
-
-    fn begin_new_task_dialog(&mut self) {
-        self.input_mode = InputMode::NewTaskDialog {
-            active_field: 0,
-            repo: "default".to_string(),
-            title: String::new(),
-            model: "claude".to_string(),
-        };
-        self.state.status_line =
-            "new task dialog: Tab=next field Enter=create Esc=cancel".to_string();
+    /// Open the create-task form, resuming a kept form if there is one.
+    fn begin_create_task_form(&mut self) {
+        let resumed = self.state.create_task_form.is_some();
+        if !resumed {
+            let form = CreateTaskForm::new(&self.all_tasks, self.state.selected_task());
+            self.state.create_task_form = Some(form);
+        }
+        self.input_mode = InputMode::CreateTaskForm;
+        self.state.status_line = if resumed {
+            "create task (resumed): Tab=next field Left/Right=change Enter=create Esc=discard"
+        } else {
+            "create task: Tab=next field Left/Right=change Enter=create Esc=discard"
+        }
+        .to_string();
     }
 
     fn begin_chat_input(&mut self) {
@@ -502,6 +530,188 @@ impl TuiApp {
             "chat input: type message, Enter=send Up/Down=history Esc=cancel".to_string();
     }
 
+    /// Chat input prefilled with an `@file:` reference for the agent to keep
+    /// in context.
+    fn begin_pin_file(&mut self) {
+        self.begin_chat_input();
+        if let InputMode::ChatInput { buffer, .. } = &mut self.input_mode {
+            buffer.push_str(PIN_FILE_PREFIX);
+            self.state.status_line =
+                "pin file: type a path after @file:, Enter=send Esc=cancel".to_string();
+        }
+    }
+
+    fn begin_command_palette(&mut self) {
+        self.input_mode = InputMode::CommandPalette {
+            query: String::new(),
+            selected: 0,
+        };
+        self.state.status_line =
+            "command palette: type to search, Up/Down=select Enter=run Esc=cancel".to_string();
+    }
+
+    /// Focus the selected task; the tick hook loads its detail since the
+    /// cached copy is dropped here.
+    fn open_task_detail(&mut self) {
+        let Some(task_id) = self.state.selected_task().map(|t| t.task_id.0.clone()) else {
+            return;
+        };
+        self.state.focused_task = true;
+        self.state.focused_pane_idx = None;
+        self.state.task_detail = None;
+        self.state.reset_scroll();
+        self.state.status_line = format!("task detail: {task_id}");
+    }
+
+    fn cycle_sort_mode(&mut self) {
+        self.state.sort_mode = self.state.sort_mode.next();
+        self.state.show_sessions = !self.state.show_sessions;
+        self.state.ensure_selected_session_visible();
+        self.state.status_line = if self.state.show_sessions {
+            format!("sort: {} | sessions shown", self.state.sort_mode.label())
+        } else {
+            format!("sort: {} | sessions hidden", self.state.sort_mode.label())
+        };
+    }
+
+    fn reverse_sort(&mut self) {
+        self.state.sort_reversed = !self.state.sort_reversed;
+        self.state.status_line = if self.state.sort_reversed {
+            "sort direction: reversed".to_string()
+        } else {
+            "sort direction: normal".to_string()
+        };
+    }
+
+    fn toggle_timeline(&mut self) {
+        self.state.show_timeline = !self.state.show_timeline;
+        self.state.status_line = if self.state.show_timeline {
+            "timeline shown".to_string()
+        } else {
+            "timeline hidden".to_string()
+        };
+    }
+
+    fn toggle_stack_view(&mut self) {
+        self.state.show_stack = !self.state.show_stack;
+        if !self.state.show_stack {
+            self.state.status_line = "stack view closed".to_string();
+            return;
+        }
+        self.state.focused_task = false;
+        self.state.focused_pane_idx = None;
+        if let Some(task) = self.state.selected_task() {
+            self.state.stack_selected = Some(task.task_id.clone());
+        }
+        self.ensure_stack_selection();
+        self.state.status_line =
+            "stack view: Up/Down=select r=restack s=submit stack Esc=close".to_string();
+    }
+
+    fn move_stack_selection(&mut self, forward: bool) {
+        let ids: Vec<TaskId> = self
+            .stack_nodes()
+            .iter()
+            .map(|(_, row)| row.task_id.clone())
+            .collect();
+        if ids.is_empty() {
+            return;
+        }
+        let current = self
+            .state
+            .stack_selected
+            .as_ref()
+            .and_then(|selected| ids.iter().position(|id| id == selected));
+        let next = match current {
+            Some(idx) if forward => (idx + 1).min(ids.len() - 1),
+            Some(idx) => idx.saturating_sub(1),
+            None => 0,
+        };
+        self.state.status_line = format!("stack selected: {}", ids[next].0);
+        self.state.stack_selected = Some(ids[next].clone());
+    }
+
+    fn queue_stack_restack(&mut self) {
+        let Some(task_id) = self.state.stack_selected.clone() else {
+            self.state.status_line = "no stack task selected".to_string();
+            return;
+        };
+        self.state.status_line = format!(
+            "queued action={} task={}",
+            action_label(UiAction::TriggerRestack),
+            task_id.0
+        );
+        self.action_queue.push_back(QueuedAction::Dispatch {
+            action: UiAction::TriggerRestack,
+            task_id: Some(task_id),
+            prompt: None,
+            model: None,
+        });
+    }
+
+    /// Queue a submit for every task in the selected node's stack, bottom-up
+    /// so each parent is submitted before its children.
+    fn queue_stack_submit(&mut self) {
+        let Some(selected) = self.state.stack_selected.clone() else {
+            self.state.status_line = "no stack task selected".to_string();
+            return;
+        };
+        let order = self.stack_submit_order(&selected);
+        let Some(root) = order.first().cloned() else {
+            return;
+        };
+        self.state.status_line =
+            format!("queued submit for {} task(s) in stack {}", order.len(), root.0);
+        for task_id in order {
+            self.action_queue.push_back(QueuedAction::Dispatch {
+                action: UiAction::SubmitTask,
+                task_id: Some(task_id),
+                prompt: None,
+                model: None,
+            });
+        }
+    }
+
+    /// The root of `task_id`'s stack followed by its descendants, in tree order.
+    fn stack_submit_order(&self, task_id: &TaskId) -> Vec<TaskId> {
+        let nodes = self.stack_nodes();
+        let Some(position) = nodes.iter().position(|(_, row)| &row.task_id == task_id) else {
+            return Vec::new();
+        };
+        let root = nodes[..=position]
+            .iter()
+            .rposition(|(depth, _)| *depth == 0)
+            .unwrap_or(0);
+        nodes[root..]
+            .iter()
+            .enumerate()
+            .take_while(|(offset, (depth, _))| *offset == 0 || *depth > 0)
+            .map(|(_, (_, row))| row.task_id.clone())
+            .collect()
+    }
+
+    fn open_log_view(&mut self) {
+        let Some(task_id) = self.state.selected_task().map(|task| task.task_id.0.clone()) else {
+            self.state.status_line = "no task selected for log view".to_string();
+            return;
+        };
+
+        let log_lines = self
+            .state
+            .log_root
+            .as_deref()
+            .map(|root| load_agent_log(root, &task_id))
+            .unwrap_or_else(|| vec!["No log output available.".to_string()]);
+
+        self.input_mode = InputMode::LogView {
+            title: "Agent Log".to_string(),
+            task_id: task_id.clone(),
+            log_lines,
+            scroll_offset: 0,
+        };
+        self.state.status_line = format!("agent log view: {task_id}");
+    }
+
     fn begin_filter_input(&mut self) {
         self.input_mode = InputMode::FilterInput {
             buffer: self.state.filter_text.clone().unwrap_or_default(),
@@ -510,6 +720,45 @@ impl TuiApp {
             "filter input: type query, Enter=apply Esc=cancel".to_string();
     }
 
+    fn begin_pane_search(&mut self) {
+        if !(self.state.focused_task || self.state.focused_pane_idx.is_some()) {
+            return;
+        }
+        let buffer = self
+            .state
+            .focused_search_status()
+            .map(|(query, _, _)| query)
+            .unwrap_or_default();
+        self.input_mode = InputMode::PaneSearch { buffer };
+        self.state.status_line =
+            "search pane: type query, Enter=keep n/N=next/prev Esc=cancel".to_string();
+    }
+
+    fn jump_to_match(&mut self, older: bool) {
+        let Some((query, _, _)) = self.state.focused_search_status() else {
+            self.state.status_line = "no active search (press / to search)".to_string();
+            return;
+        };
+        self.state.jump_to_match(older);
+        self.state.status_line = self.search_status_line(&query);
+    }
+
+    fn search_status_line(&self, query: &str) -> String {
+        match self.state.focused_search_status() {
+            Some((_, Some(position), total)) => {
+                format!("search '{query}': match {position}/{total}")
+            }
+            _ => format!("search '{query}': no matches"),
+        }
+    }
+
+    fn queue_copy(&mut self) {
+        match self.state.focused_copy_text(log_view_visible_height()) {
+            Some(text) => self.action_queue.push_back(QueuedAction::CopyToClipboard { text }),
+            None => self.state.status_line = "nothing to copy".to_string(),
+        }
+    }
+
     fn cycle_state_filter(&mut self) {
         self.state.filter_state = match self.state.filter_state {
             None => Some(TaskState::Chatting),
@@ -639,84 +888,50 @@ impl TuiApp {
         }
     }
 
-    fn handle_new_task_dialog_key(&mut self, key: KeyEvent) {
+    fn handle_create_task_form_key(&mut self, key: KeyEvent) {
+        let Some(form) = self.state.create_task_form.as_mut() else {
+            self.input_mode = InputMode::Normal;
+            return;
+        };
         match key.code {
             KeyCode::Esc => {
+                self.state.create_task_form = None;
                 self.input_mode = InputMode::Normal;
-                self.state.status_line = "new task dialog canceled".to_string();
+                self.state.status_line = "create task canceled".to_string();
             }
-            KeyCode::Tab => {
-                if let InputMode::NewTaskDialog { active_field, .. } = &mut self.input_mode {
-                    *active_field = (*active_field + 1) % 3;
-                }
+            KeyCode::Tab | KeyCode::Down => form.active_field = form.active_field.next(),
+            KeyCode::BackTab | KeyCode::Up => form.active_field = form.active_field.previous(),
+            KeyCode::Left => form.cycle(false),
+            KeyCode::Right => form.cycle(true),
+            KeyCode::Backspace if form.active_field == CreateTaskField::Title => {
+                form.title.pop();
             }
-            KeyCode::Backspace => {
-                if let InputMode::NewTaskDialog {
-                    active_field,
-                    repo,
-                    title,
-                    model,
-                } = &mut self.input_mode
-                {
-                    match *active_field {
-                        0 => {
-                            repo.pop();
-                        }
-                        1 => {
-                            title.pop();
-                        }
-                        _ => {
-                            model.pop();
-                        }
-                    }
+            KeyCode::Char(' ') if form.active_field != CreateTaskField::Title => form.cycle(true),
+            KeyCode::Char(ch) if form.active_field == CreateTaskField::Title => {
+                if !key.modifiers.contains(KeyModifiers::CONTROL) {
+                    form.title.push(ch);
                 }
             }
-            KeyCode::Char(ch) => {
-                if key.modifiers.contains(KeyModifiers::CONTROL) {
+            KeyCode::Enter => {
+                let title = form.title.trim().to_string();
+                if title.is_empty() {
+                    form.active_field = CreateTaskField::Title;
+                    self.state.status_line = "task title cannot be empty".to_string();
                     return;
                 }
-                if let InputMode::NewTaskDialog {
-                    active_field,
-                    repo,
-                    title,
-                    model,
-                } = &mut self.input_mode
-                {
-                    match *active_field {
-                        0 => repo.push(ch),
-                        1 => title.push(ch),
-                        _ => model.push(ch),
-                    }
-                }
-            }
-            KeyCode::Enter => {
-                let (repo_raw, title, model_raw) = match &self.input_mode {
-                    InputMode::NewTaskDialog {
-                        repo, title, model, ..
-                    } => (
-                        repo.trim().to_string(),
-                        title.trim().to_string(),
-                        model.trim().to_string(),
-                    ),
-                    _ => return,
-                };
-                let repo = if repo_raw.is_empty() {
-                    "default".to_string()
-                } else {
-                    repo_raw
-                };
-                let model = if model_raw.is_empty() {
-                    "claude".to_string()
-                } else {
-                    model_raw
-                };
+                let repo = form.repo().0.clone();
+                let model = form.model;
                 self.action_queue.push_back(QueuedAction::CreateTask {
                     repo: repo.clone(),
                     title,
-                    model: model.clone(),
+                    model,
+                    priority: form.priority,
+                    stack: form.stack,
+                    stack_on: form.stack_on.clone().filter(|_| form.stack),
                 });
+                // The form stays on the dashboard until the create succeeds.
                 self.input_mode = InputMode::Normal;
-                self.state.status_line = format!("queued create_task repo={repo} model={model}");
+                self.state.status_line = format!("creating task repo={repo} model={model}");
             }
             _ => {}
         }
@@ -724,9 +939,10 @@ impl TuiApp {
 
     fn handle_log_view_key(&mut self, key: KeyEvent) {
         let visible_height = log_view_visible_height();
-        let mut close_requested = false;
+        let mut close_requested = None;
 
         if let InputMode::LogView {
+            title,
             log_lines,
             scroll_offset,
             ..
@@ -734,7 +950,7 @@ impl TuiApp {
         {
             match key.code {
                 KeyCode::Esc => {
-                    close_requested = true;
+                    close_requested = Some(title.to_lowercase());
                 }
                 KeyCode::Char('j') | KeyCode::Down | KeyCode::PageDown => {
                     *scroll_offset = (*scroll_offset + 1).min(log_lines.len());
@@ -752,9 +968,46 @@ impl TuiApp {
             }
         }
 
-        if close_requested {
+        if let Some(title) = close_requested {
             self.input_mode = InputMode::Normal;
-            self.state.status_line = "agent log view closed".to_string();
+            self.state.status_line = format!("{title} view closed");
+        }
+    }
+
+    fn handle_command_palette_key(&mut self, key: KeyEvent) {
+        let scope = PaletteScope::from_state(&self.state);
+        let InputMode::CommandPalette { query, selected } = &mut self.input_mode else {
+            return;
+        };
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let match_count = filter_entries(query, scope).len();
+        match key.code {
+            KeyCode::Esc => {
+                self.input_mode = InputMode::Normal;
+                self.state.status_line = "command palette closed".to_string();
+            }
+            KeyCode::Enter => {
+                let Some(entry) = filter_entries(query, scope).get(*selected).copied() else {
+                    self.state.status_line = "no matching command".to_string();
+                    return;
+                };
+                self.input_mode = InputMode::Normal;
+                self.state.status_line = format!("ran {}", entry.name.to_lowercase());
+                self.run_command(entry.handler);
+            }
+            KeyCode::Up => *selected = wrap_previous(*selected, match_count),
+            KeyCode::Down => *selected = wrap_next(*selected, match_count),
+            KeyCode::Char('p') if ctrl => *selected = wrap_previous(*selected, match_count),
+            KeyCode::Char('n') if ctrl => *selected = wrap_next(*selected, match_count),
+            KeyCode::Backspace => {
+                query.pop();
+                *selected = 0;
+            }
+            KeyCode::Char(ch) if !ctrl => {
+                query.push(ch);
+                *selected = 0;
+            }
+            _ => {}
         }
     }
 
@@ -781,33 +1034,42 @@ impl TuiApp {
     pub fn input_prompt(&self) -> Option<&str> {
         match &self.input_mode {
             InputMode::Normal => None,
-            InputMode::NewTaskDialog { .. } => None,
-            InputMode::NewChatPrompt { buffer } => Some(buffer.as_str()),
+            InputMode::CreateTaskForm => None,
             InputMode::FilterInput { buffer } => Some(buffer.as_str()),
+            InputMode::PaneSearch { buffer } => Some(buffer.as_str()),
             InputMode::ChatInput { buffer, .. } => Some(buffer.as_str()),
-            InputMode::ModelSelect { prompt, .. } => Some(prompt.as_str()),
             InputMode::DeleteTaskConfirm { .. } => None,
             InputMode::HelpOverlay => None,
             InputMode::LogView { .. } => None,
+            InputMode::CommandPalette { .. } => None,
         }
     }
 
-    pub fn log_view_display(&self) -> Option<(&str, &[String], usize)> {
+    pub fn log_view_display(&self) -> Option<(&str, &str, &[String], usize)> {
         match &self.input_mode {
             InputMode::LogView {
+                title,
                 task_id,
                 log_lines,
                 scroll_offset,
-            } => Some((task_id.as_str(), log_lines.as_slice(), *scroll_offset)),
+            } => Some((
+                title.as_str(),
+                task_id.as_str(),
+                log_lines.as_slice(),
+                *scroll_offset,
+            )),
             _ => None,
         }
     }
 
-    pub fn model_select_display(&self) -> Option<(&[ModelKind], usize)> {
+    /// Query, matching entries for the current context, and selected index.
+    pub fn command_palette_display(&self) -> Option<(&str, Vec<&'static PaletteEntry>, usize)> {
         match &self.input_mode {
-            InputMode::ModelSelect {
-                models, selected, ..
-            } => Some((models, *selected)),
+            InputMode::CommandPalette { query, selected } => Some((
+                query.as_str(),
+                filter_entries(query, PaletteScope::from_state(&self.state)),
+                *selected,
+            )),
             _ => None,
         }
     }
@@ -826,14 +1088,9 @@ impl TuiApp {
         }
     }
 
-    pub fn new_task_dialog_display(&self) -> Option<(usize, &str, &str, &str)> {
-        match &self.input_mode {
-            InputMode::NewTaskDialog {
-                active_field,
-                repo,
-                title,
-                model,
-            } => Some((*active_field, repo.as_str(), title.as_str(), model.as_str())),
+    pub fn create_task_form_display(&self) -> Option<&CreateTaskForm> {
+        match self.input_mode {
+            InputMode::CreateTaskForm => self.state.create_task_form.as_ref(),
             _ => None,
         }
     }
@@ -844,53 +1101,29 @@ impl TuiApp {
             self.handle_chat_input_key(key);
             return true;
         }
-        if matches!(self.input_mode, InputMode::NewTaskDialog { .. }) {
-            self.handle_new_task_dialog_key(key);
+        if matches!(self.input_mode, InputMode::CreateTaskForm) {
+            self.handle_create_task_form_key(key);
             return true;
         }
         if matches!(self.input_mode, InputMode::LogView { .. }) {
             self.handle_log_view_key(key);
             return true;
         }
+        if matches!(self.input_mode, InputMode::CommandPalette { .. }) {
+            self.handle_command_palette_key(key);
+            return true;
+        }
         match &mut self.input_mode {
             InputMode::Normal => return false,
-            InputMode::NewTaskDialog { .. } => unreachable!(),
+            InputMode::CreateTaskForm => unreachable!(),
             InputMode::LogView { .. } => unreachable!(),
+            InputMode::CommandPalette { .. } => unreachable!(),
             InputMode::HelpOverlay => match key.code {
                 KeyCode::Esc | KeyCode::Char('?') => {
                     self.input_mode = InputMode::Normal;
                 }
                 _ => {}
             },
-            InputMode::NewChatPrompt { buffer } => match key.code {
-                KeyCode::Esc => {
-                    self.input_mode = InputMode::Normal;
-                    self.state.status_line = "new chat prompt canceled".to_string();
-                }
-                KeyCode::Enter => {
-                    let prompt = buffer.trim().to_string();
-                    if prompt.is_empty() {
-                        self.state.status_line = "new chat prompt cannot be empty".to_string();
-                        return true;
-                    }
-                    self.input_mode = InputMode::ModelSelect {
-                        prompt,
-                        models: vec![ModelKind::Claude, ModelKind::Codex, ModelKind::Gemini],
-                        selected: 0,
-                    };
-                    self.state.status_line =
-                        "select model: Up/Down=cycle Enter=confirm Esc=cancel".to_string();
-                }
-                KeyCode::Backspace => {
-                    buffer.pop();
-                }
-                KeyCode::Char(ch) => {
-                    if !key.modifiers.contains(KeyModifiers::CONTROL) {
-                        buffer.push(ch);
-                    }
-                }
-                _ => {}
-            },
             InputMode::FilterInput { buffer } => match key.code {
                 KeyCode::Esc => {
                     self.input_mode = InputMode::Normal;
@@ -916,38 +1149,32 @@ impl TuiApp {
                 }
                 _ => {}
             },
-            InputMode::ModelSelect {
-                prompt,
-                models,
-                selected,
-            } => match key.code {
+            InputMode::PaneSearch { buffer } => match key.code {
                 KeyCode::Esc => {
                     self.input_mode = InputMode::Normal;
-                    self.state.status_line = "model selection canceled".to_string();
+                    self.state.clear_pane_search();
+                    self.state.status_line = "search canceled".to_string();
                 }
-                KeyCode::Up => {
-                    if *selected == 0 {
-                        *selected = models.len().saturating_sub(1);
+                KeyCode::Enter => {
+                    let query = buffer.clone();
+                    self.input_mode = InputMode::Normal;
+                    self.state.status_line = if query.is_empty() {
+                        "search cleared".to_string()
                     } else {
-                        *selected -= 1;
-                    }
+                        self.search_status_line(&query)
+                    };
                 }
-                KeyCode::Down => {
-                    *selected = (*selected + 1) % models.len();
+                KeyCode::Backspace => {
+                    buffer.pop();
+                    let query = buffer.clone();
+                    self.state.search_focused_pane(&query);
                 }
-                KeyCode::Enter => {
-                    let chosen_model = models[*selected];
-                    let prompt_value = prompt.clone();
-                    let task_id = self.state.selected_task().map(|task| task.task_id.clone());
-                    self.action_queue.push_back(QueuedAction::Dispatch {
-                        action: UiAction::CreateTask,
-                        task_id,
-                        prompt: Some(prompt_value),
-                        model: Some(chosen_model),
-                    });
-                    self.input_mode = InputMode::Normal;
-                    self.state.status_line =
-                        format!("queued action=create_task (chat) model={:?}", chosen_model);
+                KeyCode::Char(ch) => {
+                    if !key.modifiers.contains(KeyModifiers::CONTROL) {
+                        buffer.push(ch);
+                        let query = buffer.clone();
+                        self.state.search_focused_pane(&query);
+                    }
                 }
                 _ => {}
             },
@@ -1013,6 +1240,10 @@ impl TuiApp {
                             self.state.status_line =
                                 format!("pane stopped: {instance_id}");
                         }
+                        AgentPaneStatus::Suspended => {
+                            self.state.status_line =
+                                format!("pane suspended (idle): {instance_id}");
+                        }
                         AgentPaneStatus::Starting
                         | AgentPaneStatus::Running
                         | AgentPaneStatus::Waiting => {}
@@ -1041,6 +1272,44 @@ impl TuiApp {
                     task.qa_targets = targets;
                 }
             }
+            TuiEvent::OpenQuestionsReplaced { questions } => {
+                self.state.open_questions = questions;
+            }
+            TuiEvent::TaskDetailLoaded { mut detail } => {
+                let overflow = detail.events.len().saturating_sub(TASK_DETAIL_EVENT_LIMIT);
+                detail.events.drain(..overflow);
+                self.state.task_detail = Some(detail);
+            }
+            TuiEvent::TaskCreateFinished { task_id } => {
+                self.state.create_task_form = None;
+                self.state.status_line = format!("created task {}", task_id.0);
+            }
+            TuiEvent::TaskCreateFailed { message } => {
+                self.state.status_line =
+                    format!("create failed: {message} (form kept; Enter to retry)");
+                if self.state.create_task_form.is_some()
+                    && matches!(self.input_mode, InputMode::Normal)
+                {
+                    self.input_mode = InputMode::CreateTaskForm;
+                }
+            }
+            TuiEvent::DiffLoaded { task_id, lines } => {
+                if !matches!(self.input_mode, InputMode::Normal) {
+                    return;
+                }
+                let log_lines = if lines.is_empty() {
+                    vec!["No changes against trunk.".to_string()]
+                } else {
+                    lines
+                };
+                self.state.status_line = format!("diff view: {}", task_id.0);
+                self.input_mode = InputMode::LogView {
+                    title: "Diff".to_string(),
+                    task_id: task_id.0,
+                    log_lines,
+                    scroll_offset: 0,
+                };
+            }
         }
     }
 
@@ -1074,11 +1343,20 @@ impl TuiApp {
             pane.instance_id = instance_id.to_string();
             pane.model = model;
             pane.status = AgentPaneStatus::Running;
+            pane.confidence = None;
             return idx;
         }
 
         let mut pane = AgentPane::new(instance_id.to_string(), task_id, model);
         pane.status = AgentPaneStatus::Running;
+        pane.max_bytes = self.state.pane_buffer_bytes;
+        if new_category == PaneCategory::Agent {
+            pane.history_log = self
+                .state
+                .chat_log_dir
+                .as_deref()
+                .map(|dir| chat_log_path(dir, &pane.task_id));
+        }
         self.state.panes.push(pane);
         if self.state.panes.len() == 1 {
             self.state.selected_pane_idx = 0;
@@ -1093,6 +1371,24 @@ fn normalize_paste_text(text: &str) -> String {
 
 const LOG_VIEW_DEFAULT_VISIBLE_HEIGHT: usize = 20;
 
+/// Lines moved by PgUp/PgDn in focused views.
+const SCROLL_PAGE_LINES: usize = 20;
+
+/// Chat text the "pin file" command starts from.
+const PIN_FILE_PREFIX: &str = "Keep this file in context: @file:";
+
+fn wrap_previous(selected: usize, len: usize) -> usize {
+    selected.checked_sub(1).unwrap_or(len.saturating_sub(1))
+}
+
+fn wrap_next(selected: usize, len: usize) -> usize {
+    if selected + 1 >= len {
+        0
+    } else {
+        selected + 1
+    }
+}
+
 fn log_view_visible_height() -> usize {
     crossterm::terminal::size()
         .map(|(_, height)| height.saturating_sub(4) as usize)
@@ -1115,11 +1411,13 @@ mod tests {
     use chrono::Utc;
     use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
     use orch_core::state::TaskState;
-    use orch_core::types::{ModelKind, RepoId, Session, SessionStatus, Task, TaskId};
+    use orch_core::types::{
+        ModelKind, RepoId, Session, SessionStatus, Task, TaskId, TaskPriority,
+    };
     use std::path::PathBuf;
     use crate::{
-        AgentPane, AgentPaneStatus, QueuedAction, SessionDisplay, SortMode, TaskOverviewRow,
-        TuiApp, TuiEvent, UiAction,
+        AgentPane, AgentPaneStatus, CreateTaskField, OpenQuestionDisplay, QueuedAction,
+        SessionDisplay, SortMode, TaskOverviewRow, TuiApp, TuiEvent, UiAction,
     };
 
     fn assert_dispatch_action(
@@ -1141,7 +1439,9 @@ mod tests {
                 assert_eq!(prompt.as_deref(), expected_prompt);
                 assert_eq!(*model, expected_model);
             }
-            QueuedAction::CreateTask { .. } => panic!("expected dispatch action"),
+            QueuedAction::CreateTask { .. } | QueuedAction::CopyToClipboard { .. } => {
+                panic!("expected dispatch action")
+            }
         }
     }
 
@@ -1217,6 +1517,10 @@ mod tests {
             status: AgentPaneStatus::Exited,
             updated_at: Utc::now(),
             lines: std::collections::VecDeque::from(vec!["history".to_string()]),
+            scroll_back: 0,
+            follow: true,
+            search: None,
+            ..AgentPane::new("H-T1", TaskId("T1".to_string()), ModelKind::Claude)
         });
 
         app.apply_event(TuiEvent::AgentPaneOutput {
@@ -1271,6 +1575,8 @@ mod tests {
                 depends_on_display: Vec::new(),
                 pr_url: None,
                 model_display: None,
+                parent_task_id: None,
+                restack_conflict: false,
             },
             TaskOverviewRow {
                 task_id: TaskId("T2".to_string()),
@@ -1292,6 +1598,8 @@ mod tests {
                 depends_on_display: Vec::new(),
                 pr_url: None,
                 model_display: None,
+                parent_task_id: None,
+                restack_conflict: false,
             },
         ];
         app.state.selected_task_idx = 1;
@@ -1361,6 +1669,8 @@ mod tests {
             depends_on_display: Vec::new(),
             pr_url: None,
             model_display: None,
+            parent_task_id: None,
+            restack_conflict: false,
         }];
 
         app.handle_key_event(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE));
@@ -1671,6 +1981,8 @@ mod tests {
             depends_on_display: Vec::new(),
             pr_url: None,
             model_display: None,
+            parent_task_id: None,
+            restack_conflict: false,
         }];
         app.state.focused_task = true;
 
@@ -1738,170 +2050,208 @@ mod tests {
             task_id: TaskId("T1".to_string()),
             model: ModelKind::Codex,
             lines: vec!["after-exit".to_string()],
-        });
-        assert_eq!(app.state.panes[0].status, AgentPaneStatus::Exited);
-    }
-
-    #[test]
-    fn create_task_key_enters_prompt_mode_and_enter_queues_prompted_action() {
-        let mut app = TuiApp::default();
-        app.state.tasks = vec![TaskOverviewRow {
-            task_id: TaskId("T1".to_string()),
-            repo_id: RepoId("example".to_string()),
-            title: "Task T1".to_string(),
-            branch: "task/T1".to_string(),
-            stack_position: None,
-            state: TaskState::Chatting,
-            verify_summary: "not_run".to_string(),
-            last_activity: Utc::now(),
-            display_state: "Chatting".to_string(),
-            qa_status: None,
-            qa_tests: Vec::new(),
-            qa_targets: Vec::new(),
-            estimated_tokens: None,
-            estimated_cost_usd: None,
-            retry_count: 0,
-            retry_history: Vec::new(),
-            depends_on_display: Vec::new(),
-            pr_url: None,
-            model_display: None,
-        }];
-
-        app.handle_key_event(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE));
-        assert!(matches!(
-            app.input_mode,
-            super::InputMode::NewChatPrompt { .. }
-        ));
-        assert_eq!(
-            app.state.status_line,
-            "new chat prompt: type feature request, Enter=submit Esc=cancel"
-        );
+        });
+        assert_eq!(app.state.panes[0].status, AgentPaneStatus::Exited);
+    }
 
-        for ch in "Build OAuth login".chars() {
-            app.handle_key_event(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_text(app: &mut TuiApp, text: &str) {
+        for ch in text.chars() {
+            app.handle_key_event(key(KeyCode::Char(ch)));
         }
-        app.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    }
 
-        // After pressing Enter on the prompt, we should be in ModelSelect mode
-        assert!(matches!(
-            app.input_mode,
-            super::InputMode::ModelSelect { .. }
-        ));
-        assert_eq!(
-            app.state.status_line,
-            "select model: Up/Down=cycle Enter=confirm Esc=cancel"
-        );
+    fn form_task(id: &str, repo: &str) -> Task {
+        Task::new(
+            TaskId(id.to_string()),
+            RepoId(repo.to_string()),
+            format!("Task {id}"),
+            PathBuf::from(format!(".orch/wt/{id}")),
+        )
+    }
 
-        // Confirm model selection (default is Claude at index 0)
-        app.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    #[test]
+    fn create_task_key_opens_form_and_enter_queues_chosen_values() {
+        let mut app = TuiApp::from_tasks(&[form_task("T1", "example")]);
+
+        app.handle_key_event(key(KeyCode::Char('c')));
+        assert!(matches!(app.input_mode, super::InputMode::CreateTaskForm));
+        let form = app.create_task_form_display().expect("form");
+        assert_eq!(form.active_field, CreateTaskField::Title);
+        assert_eq!(form.model, ModelKind::Claude);
+        assert_eq!(form.priority, TaskPriority::Normal);
+        assert_eq!(form.repo(), &RepoId("example".to_string()));
+        assert!(!form.stack);
+        assert_eq!(form.stack_on, Some(TaskId("T1".to_string())));
+
+        type_text(&mut app, "Build OAuth login");
+        app.handle_key_event(key(KeyCode::Tab));
+        app.handle_key_event(key(KeyCode::Right));
+        app.handle_key_event(key(KeyCode::Tab));
+        app.handle_key_event(key(KeyCode::Right));
+        app.handle_key_event(key(KeyCode::Tab));
+        app.handle_key_event(key(KeyCode::Tab));
+        app.handle_key_event(key(KeyCode::Char(' ')));
+        app.handle_key_event(key(KeyCode::Enter));
 
         assert!(matches!(app.input_mode, super::InputMode::Normal));
+        assert!(app.state.create_task_form.is_some());
         let drained = app.drain_actions();
-        assert_eq!(drained.len(), 1);
-        assert_dispatch_action(
-            &drained[0],
-            UiAction::CreateTask,
-            Some(TaskId("T1".to_string())),
-            Some("Build OAuth login"),
-            Some(ModelKind::Claude),
+        assert_eq!(
+            drained,
+            vec![QueuedAction::CreateTask {
+                repo: "example".to_string(),
+                title: "Build OAuth login".to_string(),
+                model: ModelKind::Codex,
+                priority: TaskPriority::High,
+                stack: true,
+                stack_on: Some(TaskId("T1".to_string())),
+            }]
         );
     }
 
     #[test]
-    fn n_key_opens_new_task_dialog() {
+    fn n_key_opens_form_on_default_repo_without_tasks() {
         let mut app = TuiApp::default();
 
         app.handle_key_event(KeyEvent::new(KeyCode::Char('N'), KeyModifiers::SHIFT));
 
-        assert!(matches!(app.input_mode, super::InputMode::NewTaskDialog { .. }));
-        let display = app
-            .new_task_dialog_display()
-            .expect("new task dialog display");
-        assert_eq!(display.0, 0);
-        assert_eq!(display.1, "default");
-        assert_eq!(display.2, "");
-        assert_eq!(display.3, "claude");
+        let form = app.create_task_form_display().expect("form");
+        assert_eq!(form.repo(), &RepoId("default".to_string()));
+        assert_eq!(form.stack_on, None);
     }
 
     #[test]
-    fn tab_cycles_dialog_fields() {
-        let mut app = TuiApp::default();
-        app.handle_key_event(KeyEvent::new(KeyCode::Char('N'), KeyModifiers::SHIFT));
-
-        assert_eq!(app.new_task_dialog_display().expect("dialog").0, 0);
-        app.handle_key_event(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
-        assert_eq!(app.new_task_dialog_display().expect("dialog").0, 1);
-        app.handle_key_event(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
-        assert_eq!(app.new_task_dialog_display().expect("dialog").0, 2);
-        app.handle_key_event(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
-        assert_eq!(app.new_task_dialog_display().expect("dialog").0, 0);
+    fn tab_cycles_form_fields_and_pickers_wrap() {
+        let mut app = TuiApp::from_tasks(&[form_task("T1", "beta"), form_task("T2", "alpha")]);
+        app.handle_key_event(key(KeyCode::Char('c')));
+        let field = |app: &TuiApp| app.create_task_form_display().expect("form").active_field;
+
+        for expected in [
+            CreateTaskField::Model,
+            CreateTaskField::Priority,
+            CreateTaskField::Repo,
+            CreateTaskField::Stack,
+            CreateTaskField::Title,
+        ] {
+            app.handle_key_event(key(KeyCode::Tab));
+            assert_eq!(field(&app), expected);
+        }
+        app.handle_key_event(key(KeyCode::BackTab));
+        assert_eq!(field(&app), CreateTaskField::Stack);
+
+        app.handle_key_event(key(KeyCode::Up));
+        let before = app.create_task_form_display().expect("form").repo().clone();
+        app.handle_key_event(key(KeyCode::Right));
+        let form = app.create_task_form_display().expect("form");
+        assert_eq!(form.repos.len(), 2);
+        assert_ne!(form.repo(), &before);
+
+        app.handle_key_event(key(KeyCode::Up));
+        app.handle_key_event(key(KeyCode::Up));
+        app.handle_key_event(key(KeyCode::Left));
+        let form = app.create_task_form_display().expect("form");
+        assert_eq!(form.active_field, CreateTaskField::Model);
+        assert_eq!(form.model, ModelKind::Gemini);
     }
 
     #[test]
-    fn esc_cancels_dialog() {
+    fn esc_discards_form() {
         let mut app = TuiApp::default();
-        app.handle_key_event(KeyEvent::new(KeyCode::Char('N'), KeyModifiers::SHIFT));
+        app.handle_key_event(key(KeyCode::Char('c')));
+        type_text(&mut app, "draft");
 
-        app.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        app.handle_key_event(key(KeyCode::Esc));
 
         assert!(matches!(app.input_mode, super::InputMode::Normal));
+        assert!(app.state.create_task_form.is_none());
         assert!(app.drain_actions().is_empty());
     }
 
     #[test]
-    fn enter_creates_task() {
+    fn enter_with_empty_title_keeps_form_open() {
         let mut app = TuiApp::default();
-        app.handle_key_event(KeyEvent::new(KeyCode::Char('N'), KeyModifiers::SHIFT));
+        app.handle_key_event(key(KeyCode::Char('c')));
+        type_text(&mut app, "  ");
 
-        for _ in 0..7 {
-            app.handle_key_event(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
-        }
-        for ch in "repo-x".chars() {
-            app.handle_key_event(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
-        }
-
-        app.handle_key_event(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
-        for ch in "Implement OAuth".chars() {
-            app.handle_key_event(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
-        }
+        app.handle_key_event(key(KeyCode::Enter));
 
-        app.handle_key_event(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
-        for _ in 0..6 {
-            app.handle_key_event(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
-        }
-        for ch in "codex".chars() {
-            app.handle_key_event(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
-        }
-
-        app.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(matches!(app.input_mode, super::InputMode::CreateTaskForm));
+        assert_eq!(app.state.status_line, "task title cannot be empty");
+        assert!(app.drain_actions().is_empty());
+    }
 
-        assert!(matches!(app.input_mode, super::InputMode::Normal));
-        let drained = app.drain_actions();
-        assert_eq!(drained.len(), 1);
+    #[test]
+    fn failed_create_reopens_form_with_entered_values() {
+        let mut app = TuiApp::from_tasks(&[form_task("T1", "example")]);
+        app.handle_key_event(key(KeyCode::Char('c')));
+        type_text(&mut app, "Implement OAuth");
+        app.handle_key_event(key(KeyCode::Tab));
+        app.handle_key_event(key(KeyCode::Right));
+        app.handle_key_event(key(KeyCode::Enter));
+        let first = app.drain_actions();
         assert!(matches!(
-            &drained[0],
-            QueuedAction::CreateTask { repo, title, model }
-                if repo == "repo-x" && title == "Implement OAuth" && model == "codex"
+            &first[0],
+            QueuedAction::CreateTask { stack: false, stack_on: None, .. }
         ));
+
+        app.apply_event(TuiEvent::TaskCreateFailed {
+            message: "worktree already exists".to_string(),
+        });
+
+        assert!(matches!(app.input_mode, super::InputMode::CreateTaskForm));
+        assert_eq!(
+            app.state.status_line,
+            "create failed: worktree already exists (form kept; Enter to retry)"
+        );
+        let form = app.create_task_form_display().expect("form");
+        assert_eq!(form.title, "Implement OAuth");
+        assert_eq!(form.model, ModelKind::Codex);
+
+        app.handle_key_event(key(KeyCode::Enter));
+        assert_eq!(app.drain_actions(), first);
+
+        app.apply_event(TuiEvent::TaskCreateFinished {
+            task_id: TaskId("chat-1".to_string()),
+        });
+        assert!(app.state.create_task_form.is_none());
+        app.handle_key_event(key(KeyCode::Char('c')));
+        assert_eq!(app.create_task_form_display().expect("form").title, "");
     }
 
     #[test]
-    fn backspace_deletes_char_in_dialog() {
+    fn closing_a_kept_form_resumes_it_on_reopen() {
         let mut app = TuiApp::default();
-        app.handle_key_event(KeyEvent::new(KeyCode::Char('N'), KeyModifiers::SHIFT));
+        app.handle_key_event(key(KeyCode::Char('c')));
+        type_text(&mut app, "Retry me");
+        app.handle_key_event(key(KeyCode::Enter));
+        app.drain_actions();
 
-        app.handle_key_event(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+        app.handle_key_event(key(KeyCode::Char('c')));
 
-        let display = app.new_task_dialog_display().expect("dialog");
-        assert_eq!(display.1, "defaul");
+        assert_eq!(app.create_task_form_display().expect("form").title, "Retry me");
+        assert!(app.state.status_line.starts_with("create task (resumed)"));
     }
 
     #[test]
-    fn handle_paste_appends_multiline_text_while_in_prompt_mode() {
+    fn handle_paste_appends_multiline_text_to_form_title() {
         let mut app = TuiApp::default();
-        app.handle_key_event(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE));
+        app.handle_key_event(key(KeyCode::Char('c')));
         app.handle_paste("line 1\r\nline 2\nline 3\rline 4");
-        assert_eq!(app.input_prompt(), Some("line 1\nline 2\nline 3\nline 4"));
+        assert_eq!(
+            app.create_task_form_display().expect("form").title,
+            "line 1\nline 2\nline 3\nline 4"
+        );
+
+        app.handle_key_event(key(KeyCode::Tab));
+        app.handle_paste("ignored on pickers");
+        assert_eq!(
+            app.create_task_form_display().expect("form").title,
+            "line 1\nline 2\nline 3\nline 4"
+        );
     }
 
     #[test]
@@ -1934,6 +2284,8 @@ mod tests {
             depends_on_display: Vec::new(),
             pr_url: None,
             model_display: None,
+            parent_task_id: None,
+            restack_conflict: false,
         }];
 
         app.handle_key_event(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE));
@@ -1984,6 +2336,8 @@ mod tests {
             depends_on_display: Vec::new(),
             pr_url: None,
             model_display: None,
+            parent_task_id: None,
+            restack_conflict: false,
         }];
 
         app.handle_key_event(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE));
@@ -1994,48 +2348,6 @@ mod tests {
         assert!(app.drain_actions().is_empty());
     }
 
-    #[test]
-    fn model_select_arrow_keys_cycle_through_models() {
-        let mut app = TuiApp::default();
-
-        app.handle_key_event(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE));
-        for ch in "test prompt".chars() {
-            app.handle_key_event(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
-        }
-        app.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
-
-        // Default selected is 0 (Claude)
-        assert_eq!(app.model_select_display().unwrap().1, 0);
-
-        // Down -> Codex (index 1)
-        app.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
-        assert_eq!(app.model_select_display().unwrap().1, 1);
-
-        // Down -> Gemini (index 2)
-        app.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
-        assert_eq!(app.model_select_display().unwrap().1, 2);
-
-        // Down wraps -> Claude (index 0)
-        app.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
-        assert_eq!(app.model_select_display().unwrap().1, 0);
-
-        // Up wraps -> Gemini (index 2)
-        app.handle_key_event(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
-        assert_eq!(app.model_select_display().unwrap().1, 2);
-
-        // Confirm Gemini
-        app.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
-        let drained = app.drain_actions();
-        assert_eq!(drained.len(), 1);
-        assert_dispatch_action(
-            &drained[0],
-            UiAction::CreateTask,
-            None,
-            Some("test prompt"),
-            Some(ModelKind::Gemini),
-        );
-    }
-
     #[test]
     fn handle_key_event_enter_toggles_focused_task_and_clears_focused_pane() {
         let mut app = TuiApp::default();
@@ -2059,6 +2371,8 @@ mod tests {
             depends_on_display: Vec::new(),
             pr_url: None,
             model_display: None,
+            parent_task_id: None,
+            restack_conflict: false,
         }];
 
         // Set a focused pane to verify it gets cleared
@@ -2083,29 +2397,6 @@ mod tests {
         assert!(!app.state.focused_task);
     }
 
-    #[test]
-    fn model_select_esc_cancels_and_returns_to_normal() {
-        let mut app = TuiApp::default();
-
-        app.handle_key_event(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE));
-        for ch in "test".chars() {
-            app.handle_key_event(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
-        }
-        app.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
-
-        assert!(matches!(
-            app.input_mode,
-            super::InputMode::ModelSelect { .. }
-        ));
-
-        app.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
-
-        assert!(matches!(app.input_mode, super::InputMode::Normal));
-        assert_eq!(app.state.status_line, "model selection canceled");
-        let drained = app.drain_actions();
-        assert!(drained.is_empty());
-    }
-
     #[test]
     fn apply_event_qa_update_sets_task_qa_fields() {
         use crate::model::QATestDisplay;
@@ -2131,6 +2422,8 @@ mod tests {
             depends_on_display: Vec::new(),
             pr_url: None,
             model_display: None,
+            parent_task_id: None,
+            restack_conflict: false,
         }];
 
         app.apply_event(TuiEvent::QAUpdate {
@@ -2173,6 +2466,26 @@ mod tests {
         assert!(app.state.tasks.is_empty());
     }
 
+    #[test]
+    fn apply_event_open_questions_replaced_groups_by_task() {
+        let mut app = TuiApp::default();
+        let question = |id: &str, task: &str| OpenQuestionDisplay {
+            question_id: id.to_string(),
+            task_id: TaskId(task.to_string()),
+            question: format!("question {id}"),
+            asked_at: Utc::now(),
+        };
+        app.apply_event(TuiEvent::OpenQuestionsReplaced {
+            questions: vec![question("Q-T1-1", "T1"), question("Q-T2-1", "T2")],
+        });
+        assert_eq!(app.state.open_questions_for(&TaskId("T1".to_string())).len(), 1);
+
+        app.apply_event(TuiEvent::OpenQuestionsReplaced {
+            questions: vec![question("Q-T2-1", "T2")],
+        });
+        assert!(app.state.open_questions_for(&TaskId("T1".to_string())).is_empty());
+    }
+
     #[test]
     fn chat_input_key_enters_chat_mode_and_enter_queues_send() {
         let mut app = TuiApp::default();
@@ -2196,6 +2509,8 @@ mod tests {
             depends_on_display: Vec::new(),
             pr_url: None,
             model_display: None,
+            parent_task_id: None,
+            restack_conflict: false,
         }];
 
         // Press 'i' to enter chat input mode
@@ -2261,6 +2576,8 @@ mod tests {
             depends_on_display: Vec::new(),
             pr_url: None,
             model_display: None,
+            parent_task_id: None,
+            restack_conflict: false,
         }];
 
         app.handle_key_event(KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE));
@@ -2297,6 +2614,8 @@ mod tests {
             depends_on_display: Vec::new(),
             pr_url: None,
             model_display: None,
+            parent_task_id: None,
+            restack_conflict: false,
         }];
 
         app.handle_key_event(KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE));
@@ -2335,6 +2654,8 @@ mod tests {
             depends_on_display: Vec::new(),
             pr_url: None,
             model_display: None,
+            parent_task_id: None,
+            restack_conflict: false,
         }];
 
         app.handle_key_event(KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE));
@@ -2375,6 +2696,8 @@ mod tests {
             depends_on_display: Vec::new(),
             pr_url: None,
             model_display: None,
+            parent_task_id: None,
+            restack_conflict: false,
         }];
 
         app.handle_key_event(KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE));
@@ -2417,6 +2740,8 @@ mod tests {
             depends_on_display: Vec::new(),
             pr_url: None,
             model_display: None,
+            parent_task_id: None,
+            restack_conflict: false,
         }];
 
         app.handle_key_event(KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE));
@@ -2462,6 +2787,8 @@ mod tests {
             depends_on_display: Vec::new(),
             pr_url: None,
             model_display: None,
+            parent_task_id: None,
+            restack_conflict: false,
         }];
 
         app.handle_key_event(KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE));
@@ -2507,6 +2834,8 @@ mod tests {
             depends_on_display: Vec::new(),
             pr_url: None,
             model_display: None,
+            parent_task_id: None,
+            restack_conflict: false,
         }];
 
         // Simulate a TasksReplaced event (same task, fresh data from DB).
@@ -2611,6 +2940,8 @@ mod tests {
             depends_on_display: Vec::new(),
             pr_url: None,
             model_display: None,
+            parent_task_id: None,
+            restack_conflict: false,
         }];
         app.state.focused_task = true;
         assert_eq!(
@@ -2624,7 +2955,7 @@ mod tests {
             app.state.selected_pane_category,
             crate::model::PaneCategory::QA
         );
-        assert_eq!(app.state.scroll_back, 0);
+        assert_eq!(app.state.scroll_back(), 0);
 
         // Left arrow should toggle back to Agent
         app.handle_key_event(KeyEvent::new(KeyCode::Left, KeyModifiers::NONE));
@@ -2660,6 +2991,8 @@ mod tests {
             depends_on_display: Vec::new(),
             pr_url: None,
             model_display: None,
+            parent_task_id: None,
+            restack_conflict: false,
         }];
         app.apply_event(TuiEvent::AgentPaneOutput {
             instance_id: "agent-T1".to_string(),
@@ -2686,7 +3019,7 @@ mod tests {
         );
         assert_eq!(app.state.selected_pane_idx, 1);
         assert_eq!(app.state.focused_pane_idx, Some(1));
-        assert_eq!(app.state.scroll_back, 0);
+        assert_eq!(app.state.scroll_back(), 0);
 
         // Left arrow toggles back and updates focused_pane_idx
         app.handle_key_event(KeyEvent::new(KeyCode::Left, KeyModifiers::NONE));
@@ -2733,17 +3066,19 @@ mod tests {
             depends_on_display: Vec::new(),
             pr_url: None,
             model_display: None,
+            parent_task_id: None,
+            restack_conflict: false,
         }];
         app.state.focused_pane_idx = Some(0);
 
         // Scroll up to accumulate scroll_back
         app.handle_key_event(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
         app.handle_key_event(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
-        assert!(app.state.scroll_back > 0);
+        assert!(app.state.scroll_back() > 0);
 
         // Toggle category — scroll_back should reset
         app.handle_key_event(KeyEvent::new(KeyCode::Right, KeyModifiers::NONE));
-        assert_eq!(app.state.scroll_back, 0);
+        assert_eq!(app.state.scroll_back(), 0);
     }
 
     #[test]
@@ -2769,6 +3104,8 @@ mod tests {
             depends_on_display: Vec::new(),
             pr_url: None,
             model_display: None,
+            parent_task_id: None,
+            restack_conflict: false,
         }];
         app.apply_event(TuiEvent::AgentPaneOutput {
             instance_id: "agent-T1".to_string(),
@@ -2782,23 +3119,23 @@ mod tests {
         app.handle_key_event(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
         app.handle_key_event(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
         app.handle_key_event(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
-        assert_eq!(app.state.scroll_back, 3);
+        assert_eq!(app.state.scroll_back(), 3);
 
         // Scroll down
         app.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
-        assert_eq!(app.state.scroll_back, 2);
+        assert_eq!(app.state.scroll_back(), 2);
 
         // Page up
         app.handle_key_event(KeyEvent::new(KeyCode::PageUp, KeyModifiers::NONE));
-        assert!(app.state.scroll_back >= 20);
+        assert!(app.state.scroll_back() >= 20);
 
         // End resets to bottom
         app.handle_key_event(KeyEvent::new(KeyCode::End, KeyModifiers::NONE));
-        assert_eq!(app.state.scroll_back, 0);
+        assert_eq!(app.state.scroll_back(), 0);
 
         // Home scrolls to top
         app.handle_key_event(KeyEvent::new(KeyCode::Home, KeyModifiers::NONE));
-        assert!(app.state.scroll_back > 0);
+        assert!(app.state.scroll_back() > 0);
     }
 
     #[test]
@@ -2824,6 +3161,8 @@ mod tests {
             depends_on_display: Vec::new(),
             pr_url: None,
             model_display: None,
+            parent_task_id: None,
+            restack_conflict: false,
         }];
         app.apply_event(TuiEvent::AgentPaneOutput {
             instance_id: "agent-T1".to_string(),
@@ -2835,11 +3174,11 @@ mod tests {
 
         // 'k' scrolls up
         app.handle_key_event(KeyEvent::new(KeyCode::Char('k'), KeyModifiers::NONE));
-        assert_eq!(app.state.scroll_back, 1);
+        assert_eq!(app.state.scroll_back(), 1);
 
         // 'j' scrolls down
         app.handle_key_event(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
-        assert_eq!(app.state.scroll_back, 0);
+        assert_eq!(app.state.scroll_back(), 0);
     }
 
     #[test]
@@ -2866,6 +3205,8 @@ mod tests {
                 depends_on_display: Vec::new(),
                 pr_url: None,
                 model_display: None,
+                parent_task_id: None,
+                restack_conflict: false,
             },
             TaskOverviewRow {
                 task_id: TaskId("T2".to_string()),
@@ -2887,6 +3228,8 @@ mod tests {
                 depends_on_display: Vec::new(),
                 pr_url: None,
                 model_display: None,
+                parent_task_id: None,
+                restack_conflict: false,
             },
         ];
         app.apply_event(TuiEvent::AgentPaneOutput {
@@ -2954,6 +3297,8 @@ mod tests {
                 depends_on_display: Vec::new(),
                 pr_url: None,
                 model_display: None,
+                parent_task_id: None,
+                restack_conflict: false,
             },
             TaskOverviewRow {
                 task_id: TaskId("T2".to_string()),
@@ -2975,6 +3320,8 @@ mod tests {
                 depends_on_display: Vec::new(),
                 pr_url: None,
                 model_display: None,
+                parent_task_id: None,
+                restack_conflict: false,
             },
         ];
         // No panes at all
@@ -3011,6 +3358,8 @@ mod tests {
                 depends_on_display: Vec::new(),
                 pr_url: None,
                 model_display: None,
+                parent_task_id: None,
+                restack_conflict: false,
         }];
         app.apply_event(TuiEvent::AgentPaneOutput {
             instance_id: "agent-T1".to_string(),
@@ -3025,12 +3374,12 @@ mod tests {
 
         // Scroll up
         app.handle_key_event(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
-        assert!(app.state.scroll_back > 0);
+        assert!(app.state.scroll_back() > 0);
 
         // Close task detail — scroll_back should reset
         app.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
         assert!(!app.state.focused_task);
-        assert_eq!(app.state.scroll_back, 0);
+        assert_eq!(app.state.scroll_back(), 0);
     }
 
     #[test]
@@ -3049,12 +3398,12 @@ mod tests {
 
         // Scroll up
         app.handle_key_event(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
-        assert!(app.state.scroll_back > 0);
+        assert!(app.state.scroll_back() > 0);
 
         // Unfocus pane via Tab — scroll_back should reset
         app.handle_key_event(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
         assert_eq!(app.state.focused_pane_idx, None);
-        assert_eq!(app.state.scroll_back, 0);
+        assert_eq!(app.state.scroll_back(), 0);
     }
 
     #[test]
@@ -3068,11 +3417,11 @@ mod tests {
         });
 
         app.state.focused_pane_idx = Some(0);
-        app.state.scroll_back = 5;
+        app.state.scroll_up(5);
 
         app.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
         assert_eq!(app.state.focused_pane_idx, None);
-        assert_eq!(app.state.scroll_back, 0);
+        assert_eq!(app.state.scroll_back(), 0);
         assert!(!app.should_quit);
     }
 
@@ -3099,6 +3448,8 @@ mod tests {
                 depends_on_display: Vec::new(),
                 pr_url: None,
                 model_display: None,
+                parent_task_id: None,
+                restack_conflict: false,
         }];
         app.apply_event(TuiEvent::AgentPaneOutput {
             instance_id: "agent-T1".to_string(),
@@ -3109,13 +3460,13 @@ mod tests {
 
         // Focus a pane and set scroll
         app.state.focused_pane_idx = Some(0);
-        app.state.scroll_back = 3;
+        app.state.scroll_up(3);
 
         // Enter task detail — should clear focused pane and scroll
         app.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
         assert!(app.state.focused_task);
         assert_eq!(app.state.focused_pane_idx, None);
-        assert_eq!(app.state.scroll_back, 0);
+        assert_eq!(app.state.scroll_back(), 0);
     }
 
     #[test]
@@ -3141,6 +3492,8 @@ mod tests {
                 depends_on_display: Vec::new(),
                 pr_url: None,
                 model_display: None,
+                parent_task_id: None,
+                restack_conflict: false,
         }];
         app.apply_event(TuiEvent::AgentPaneOutput {
             instance_id: "agent-T1".to_string(),
@@ -3171,11 +3524,65 @@ mod tests {
 
         // Already at bottom (scroll_back = 0), scroll down should remain 0
         app.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
-        assert_eq!(app.state.scroll_back, 0);
+        assert_eq!(app.state.scroll_back(), 0);
 
         // Page down at bottom should remain 0
         app.handle_key_event(KeyEvent::new(KeyCode::PageDown, KeyModifiers::NONE));
-        assert_eq!(app.state.scroll_back, 0);
+        assert_eq!(app.state.scroll_back(), 0);
+    }
+
+    #[test]
+    fn pane_search_keys_and_copy_in_focused_pane() {
+        let mut app = TuiApp::default();
+        app.apply_event(TuiEvent::AgentPaneOutput {
+            instance_id: "agent-T1".to_string(),
+            task_id: TaskId("T1".to_string()),
+            model: ModelKind::Claude,
+            lines: vec![
+                "panic: first".to_string(),
+                "ok".to_string(),
+                "panic: second".to_string(),
+                "ok".to_string(),
+            ],
+        });
+        app.state.focused_pane_idx = Some(0);
+        let press = |app: &mut TuiApp, code| {
+            app.handle_key_event(KeyEvent::new(code, KeyModifiers::NONE))
+        };
+
+        press(&mut app, KeyCode::Char('/'));
+        assert!(matches!(app.input_mode, super::InputMode::PaneSearch { .. }));
+        for ch in "PANIC".chars() {
+            press(&mut app, KeyCode::Char(ch));
+        }
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.input_mode, super::InputMode::Normal);
+        assert_eq!(app.state.status_line, "search 'PANIC': match 1/2");
+
+        press(&mut app, KeyCode::Char('n'));
+        assert_eq!(app.state.status_line, "search 'PANIC': match 2/2");
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('N'), KeyModifiers::SHIFT));
+        assert_eq!(app.input_mode, super::InputMode::Normal);
+        assert_eq!(app.state.status_line, "search 'PANIC': match 1/2");
+        assert!(app.drain_actions().is_empty());
+
+        press(&mut app, KeyCode::Char('y'));
+        assert_eq!(
+            app.drain_actions(),
+            vec![QueuedAction::CopyToClipboard {
+                text: "panic: second".to_string()
+            }]
+        );
+
+        // Esc drops the search first, then leaves the pane.
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.state.focused_search_status(), None);
+        assert_eq!(app.state.focused_pane_idx, Some(0));
+        press(&mut app, KeyCode::Char('y'));
+        assert!(matches!(
+            app.drain_actions().as_slice(),
+            [QueuedAction::CopyToClipboard { text }] if text.ends_with("panic: second\nok")
+        ));
     }
 
     #[test]
@@ -3201,6 +3608,8 @@ mod tests {
                 depends_on_display: Vec::new(),
                 pr_url: None,
                 model_display: None,
+                parent_task_id: None,
+                restack_conflict: false,
         }];
         app.state.focused_task = true;
 
@@ -3238,7 +3647,7 @@ mod tests {
 
         // From input mode
         let mut app = TuiApp {
-            input_mode: super::InputMode::NewChatPrompt {
+            input_mode: super::InputMode::FilterInput {
                 buffer: "test".to_string(),
             },
             ..Default::default()
@@ -3268,6 +3677,8 @@ mod tests {
             depends_on_display: Vec::new(),
             pr_url: None,
             model_display: None,
+            parent_task_id: None,
+            restack_conflict: false,
         }
     }
 
@@ -3302,10 +3713,12 @@ mod tests {
 
         match &app.input_mode {
             super::InputMode::LogView {
+                title,
                 task_id,
                 log_lines,
                 scroll_offset,
             } => {
+                assert_eq!(title, "Agent Log");
                 assert_eq!(task_id, "T1");
                 assert_eq!(
                     log_lines,
@@ -3327,6 +3740,7 @@ mod tests {
     fn log_view_scroll_down() {
         let mut app = TuiApp {
             input_mode: super::InputMode::LogView {
+                title: "Agent Log".to_string(),
                 task_id: "T1".to_string(),
                 log_lines: vec!["a".to_string(), "b".to_string(), "c".to_string()],
                 scroll_offset: 0,
@@ -3342,6 +3756,7 @@ mod tests {
     fn log_view_scroll_up() {
         let mut app = TuiApp {
             input_mode: super::InputMode::LogView {
+                title: "Agent Log".to_string(),
                 task_id: "T1".to_string(),
                 log_lines: vec!["a".to_string(), "b".to_string(), "c".to_string()],
                 scroll_offset: 2,
@@ -3357,6 +3772,7 @@ mod tests {
     fn log_view_esc_returns_to_normal() {
         let mut app = TuiApp {
             input_mode: super::InputMode::LogView {
+                title: "Agent Log".to_string(),
                 task_id: "T1".to_string(),
                 log_lines: vec!["a".to_string()],
                 scroll_offset: 0,
@@ -3375,6 +3791,7 @@ mod tests {
         let expected = lines.len().saturating_sub(super::log_view_visible_height());
         let mut app = TuiApp {
             input_mode: super::InputMode::LogView {
+                title: "Agent Log".to_string(),
                 task_id: "T1".to_string(),
                 log_lines: lines,
                 scroll_offset: 0,
@@ -3387,6 +3804,137 @@ mod tests {
         assert_eq!(log_view_scroll_offset(&app), expected);
     }
 
+    #[test]
+    fn command_palette_filters_by_context_and_runs_selected_entry() {
+        let ctrl_p = KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL);
+        let type_query = |app: &mut TuiApp, query: &str| {
+            for ch in query.chars() {
+                app.handle_key_event(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+            }
+        };
+
+        let mut app = TuiApp::default();
+        app.handle_key_event(ctrl_p);
+        type_query(&mut app, "approve");
+        let (query, entries, _) = app.command_palette_display().expect("palette open");
+        assert_eq!(query, "approve");
+        assert!(entries.is_empty(), "task actions need a selected task");
+        app.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(app.state.status_line, "no matching command");
+        app.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(matches!(app.input_mode, super::InputMode::Normal));
+
+        app.state.tasks = vec![make_task_row("T1")];
+        app.handle_key_event(ctrl_p);
+        type_query(&mut app, "aprv");
+        assert_eq!(app.command_palette_display().unwrap().1[0].name, "Approve task");
+        app.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(matches!(app.input_mode, super::InputMode::Normal));
+        let queued = app.drain_actions();
+        assert_eq!(queued.len(), 1);
+        assert_dispatch_action(
+            &queued[0],
+            UiAction::ApproveTask,
+            Some(TaskId("T1".to_string())),
+            None,
+            None,
+        );
+
+        app.handle_key_event(ctrl_p);
+        type_query(&mut app, "pin file");
+        app.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        let (buffer, task_id) = app.chat_input_display().expect("chat input open");
+        assert_eq!(buffer, super::PIN_FILE_PREFIX);
+        assert_eq!(task_id.0, "T1");
+    }
+
+    #[test]
+    fn command_palette_selection_wraps() {
+        let mut app = TuiApp::default();
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL));
+        let count = app.command_palette_display().unwrap().1.len();
+        assert!(count > 1);
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
+        assert_eq!(app.command_palette_display().unwrap().2, count - 1);
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL));
+        assert_eq!(app.command_palette_display().unwrap().2, 0);
+    }
+
+    fn mk_detail(task_id: &str, events: usize) -> crate::TaskDetail {
+        crate::TaskDetail {
+            task_id: TaskId(task_id.to_string()),
+            fields: vec![("State".to_string(), "chatting".to_string())],
+            events: (0..events)
+                .map(|i| crate::TaskDetailEvent {
+                    at: Utc::now(),
+                    summary: format!("event {i}"),
+                })
+                .collect(),
+            runs: Vec::new(),
+            diff_stat: Some("1 file changed, 2 insertions(+)".to_string()),
+            diff_files: vec!["src/lib.rs".to_string()],
+            loaded_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn task_detail_loaded_caches_latest_events_for_open_task() {
+        let mut app = TuiApp::default();
+        app.state.tasks = vec![make_task_row("T1"), make_task_row("T2")];
+        app.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(app.state.focused_task);
+        assert_eq!(app.state.task_detail_due(Utc::now()), Some(TaskId("T1".to_string())));
+
+        app.apply_event(TuiEvent::TaskDetailLoaded {
+            detail: mk_detail("T1", 25),
+        });
+        let detail = app.state.focused_task_detail().expect("detail cached");
+        assert_eq!(detail.events.len(), crate::TASK_DETAIL_EVENT_LIMIT);
+        assert_eq!(detail.events[0].summary, "event 5");
+        assert_eq!(app.state.task_detail_due(Utc::now()), None);
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(!app.state.focused_task);
+        assert!(app.state.focused_task_detail().is_none());
+        assert_eq!(app.state.task_detail_due(Utc::now()), None);
+
+        app.state.selected_task_idx = 1;
+        app.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(app.state.focused_task_detail().is_none());
+        assert_eq!(app.state.task_detail_due(Utc::now()), Some(TaskId("T2".to_string())));
+    }
+
+    #[test]
+    fn task_detail_goes_stale_after_threshold() {
+        let mut app = TuiApp::default();
+        app.state.tasks = vec![make_task_row("T1")];
+        app.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        app.apply_event(TuiEvent::TaskDetailLoaded {
+            detail: mk_detail("T1", 1),
+        });
+
+        let later = Utc::now() + chrono::Duration::seconds(crate::TASK_DETAIL_STALE_SECS + 1);
+        assert_eq!(app.state.task_detail_due(later), Some(TaskId("T1".to_string())));
+        assert!(app.state.focused_task_detail().is_some(), "stale data stays visible");
+    }
+
+    #[test]
+    fn diff_loaded_event_opens_titled_log_view() {
+        let mut app = TuiApp::default();
+        app.apply_event(TuiEvent::DiffLoaded {
+            task_id: TaskId("T1".to_string()),
+            lines: vec!["+added".to_string()],
+        });
+        assert_eq!(
+            app.log_view_display(),
+            Some(("Diff", "T1", &["+added".to_string()][..], 0))
+        );
+
+        app.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(app.state.status_line, "diff view closed");
+    }
+
     #[test]
     fn chat_history_up_arrow_recalls_previous_message() {
         let mut app = TuiApp::default();
@@ -3772,4 +4320,119 @@ mod tests {
 
         assert_eq!(app.input_prompt(), Some("test")); // 'x' was NOT appended
     }
+
+    fn mk_stack_task(id: &str, parent: Option<&str>) -> Task {
+        let mut task = Task::new(
+            TaskId(id.to_string()),
+            RepoId("example".to_string()),
+            format!("Task {id}"),
+            PathBuf::from(format!(".orch/wt/{id}")),
+        );
+        task.parent_task_id = parent.map(|p| TaskId(p.to_string()));
+        task
+    }
+
+    fn stack_ids(app: &TuiApp) -> Vec<(usize, String)> {
+        app.stack_nodes()
+            .into_iter()
+            .map(|(depth, row)| (depth, row.task_id.0.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn stack_view_shows_children_that_the_task_list_hides() {
+        let app = TuiApp::from_tasks(&[
+            mk_stack_task("T3", Some("T2")),
+            mk_stack_task("T1", None),
+            mk_stack_task("T2", Some("T1")),
+            mk_stack_task("T9", None),
+        ]);
+
+        let listed: Vec<&str> = app.state.tasks.iter().map(|t| t.task_id.0.as_str()).collect();
+        assert_eq!(listed, vec!["T1", "T9"]);
+        assert_eq!(
+            stack_ids(&app),
+            vec![
+                (0, "T1".to_string()),
+                (1, "T2".to_string()),
+                (2, "T3".to_string()),
+                (0, "T9".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn stack_view_keys_navigate_and_queue_restack_and_bottom_up_submit() {
+        let mut app = TuiApp::from_tasks(&[
+            mk_stack_task("T1", None),
+            mk_stack_task("T2", Some("T1")),
+            mk_stack_task("T3", Some("T2")),
+            mk_stack_task("T9", None),
+        ]);
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        app.handle_key_event(key(KeyCode::Char('b')));
+        assert!(app.state.show_stack);
+        assert_eq!(app.state.stack_selected, Some(TaskId("T1".to_string())));
+
+        app.handle_key_event(key(KeyCode::Down));
+        app.handle_key_event(key(KeyCode::Down));
+        assert_eq!(app.state.stack_selected, Some(TaskId("T3".to_string())));
+
+        app.handle_key_event(key(KeyCode::Char('r')));
+        let drained = app.drain_actions();
+        assert_eq!(drained.len(), 1);
+        assert_dispatch_action(
+            &drained[0],
+            UiAction::TriggerRestack,
+            Some(TaskId("T3".to_string())),
+            None,
+            None,
+        );
+
+        app.handle_key_event(key(KeyCode::Char('s')));
+        let submitted: Vec<_> = app
+            .drain_actions()
+            .into_iter()
+            .map(|queued| match queued {
+                QueuedAction::Dispatch {
+                    action: UiAction::SubmitTask,
+                    task_id: Some(task_id),
+                    ..
+                } => task_id.0,
+                other => panic!("expected submit dispatch, got {other:?}"),
+            })
+            .collect();
+        assert_eq!(submitted, vec!["T1", "T2", "T3"]);
+
+        app.handle_key_event(key(KeyCode::Esc));
+        assert!(!app.state.show_stack);
+        assert!(!app.should_quit);
+    }
+
+    #[test]
+    fn stack_selection_survives_tasks_replaced() {
+        let mut app = TuiApp::from_tasks(&[
+            mk_stack_task("T1", None),
+            mk_stack_task("T2", Some("T1")),
+        ]);
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::NONE));
+        app.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        assert_eq!(app.state.stack_selected, Some(TaskId("T2".to_string())));
+
+        let mut conflicted = mk_stack_task("T2", Some("T1"));
+        conflicted.restack_conflict = true;
+        app.apply_event(TuiEvent::TasksReplaced {
+            tasks: vec![mk_stack_task("T0", None), mk_stack_task("T1", None), conflicted],
+        });
+
+        assert_eq!(app.state.stack_selected, Some(TaskId("T2".to_string())));
+        let (_, row) = app.stack_nodes()[2];
+        assert!(row.restack_conflict);
+
+        app.apply_event(TuiEvent::TasksReplaced {
+            tasks: vec![mk_stack_task("T0", None)],
+        });
+        assert_eq!(app.state.stack_selected, Some(TaskId("T0".to_string())));
+    }
 }
@@ -0,0 +1,104 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Destination for text copied out of the TUI.
+pub trait Clipboard {
+    fn set_text(&mut self, text: &str) -> Result<(), ClipboardError>;
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClipboardError {
+    #[error("no clipboard command found (tried pbcopy, wl-copy, xclip, xsel)")]
+    Unavailable,
+    #[error("clipboard command `{command}` failed: {detail}")]
+    CommandFailed { command: String, detail: String },
+}
+
+/// Commands tried in order; the first one that can be spawned wins.
+const CLIPBOARD_COMMANDS: &[(&str, &[&str])] = &[
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+];
+
+/// Copies through the platform clipboard command.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClipboard;
+
+impl Clipboard for SystemClipboard {
+    fn set_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+        for (program, args) in CLIPBOARD_COMMANDS {
+            let Ok(mut child) = Command::new(program)
+                .args(*args)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+            else {
+                continue;
+            };
+            let failed = |detail: String| ClipboardError::CommandFailed {
+                command: program.to_string(),
+                detail,
+            };
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin
+                    .write_all(text.as_bytes())
+                    .map_err(|err| failed(err.to_string()))?;
+            }
+            let status = child.wait().map_err(|err| failed(err.to_string()))?;
+            return if status.success() {
+                Ok(())
+            } else {
+                Err(failed(status.to_string()))
+            };
+        }
+        Err(ClipboardError::Unavailable)
+    }
+}
+
+/// Copy `text` and describe the outcome for the status line.
+pub fn copy_with_status(clipboard: &mut dyn Clipboard, text: &str) -> String {
+    let lines = text.lines().count().max(1);
+    match clipboard.set_text(text) {
+        Ok(()) if lines == 1 => "copied 1 line to clipboard".to_string(),
+        Ok(()) => format!("copied {lines} lines to clipboard"),
+        Err(err) => format!("copy failed: {err}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct StubClipboard {
+        copied: Vec<String>,
+        fail: bool,
+    }
+
+    impl Clipboard for StubClipboard {
+        fn set_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+            if self.fail {
+                return Err(ClipboardError::Unavailable);
+            }
+            self.copied.push(text.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn copy_with_status_reports_line_count_and_failures() {
+        let mut clipboard = StubClipboard::default();
+        assert_eq!(
+            copy_with_status(&mut clipboard, "one\ntwo"),
+            "copied 2 lines to clipboard"
+        );
+        assert_eq!(copy_with_status(&mut clipboard, "one"), "copied 1 line to clipboard");
+        assert_eq!(clipboard.copied, vec!["one\ntwo", "one"]);
+
+        clipboard.fail = true;
+        assert!(copy_with_status(&mut clipboard, "x").starts_with("copy failed: no clipboard"));
+    }
+}
@@ -1,7 +1,7 @@
 use orch_core::types::{ModelKind, Task, TaskId};
 use serde::{Deserialize, Serialize};
 
-use crate::model::{AgentPaneStatus, QATestDisplay};
+use crate::model::{AgentPaneStatus, OpenQuestionDisplay, QATestDisplay, TaskDetail};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "kind", rename_all = "snake_case")]
@@ -33,6 +33,27 @@ pub enum TuiEvent {
         /// Task-specific acceptance targets.
         targets: Vec<String>,
     },
+    /// Every open agent question, across all tasks.
+    OpenQuestionsReplaced {
+        questions: Vec<OpenQuestionDisplay>,
+    },
+    /// Fresh data for the task detail view.
+    TaskDetailLoaded {
+        detail: TaskDetail,
+    },
+    /// A task submitted from the create form was recorded.
+    TaskCreateFinished {
+        task_id: TaskId,
+    },
+    /// Creating a task failed; the form is reopened with its values.
+    TaskCreateFailed {
+        message: String,
+    },
+    /// Diff of a task's worktree, opened in the log viewer.
+    DiffLoaded {
+        task_id: TaskId,
+        lines: Vec<String>,
+    },
 }
 
 #[cfg(test)]
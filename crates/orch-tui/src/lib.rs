@@ -2,10 +2,13 @@ pub mod action;
 pub mod app;
 pub mod chat_parse;
 pub mod chat_render;
+pub mod clipboard;
 pub mod error;
 pub mod event;
 pub mod model;
 pub mod output_style;
+pub mod palette;
+pub mod pane_history;
 pub mod runner;
 pub mod ui;
 mod ui_activity;
@@ -1,10 +1,17 @@
 use chrono::Utc;
+use orch_agents::SignalRuleSet;
 use orch_core::events::{Event, EventKind};
 use orch_core::state::TaskState;
 use orch_core::types::{EventId, ModelKind, RepoId, SubmitMode, Task, TaskId};
+use orch_graphite::{GraphiteClient, RestackOutcome};
+use orch_tui::clipboard::{copy_with_status, SystemClipboard};
+use orch_tui::pane_history::{chat_log_path, load_log_tail};
 use orch_tui::{
-    run_tui_with_hook, AgentPaneStatus, QATestDisplay, QueuedAction, TuiApp, TuiEvent, UiAction,
+    run_tui_with_hook, AgentPaneStatus, OpenQuestionDisplay, QATestDisplay, QueuedAction,
+    TaskDetail, TaskDetailEvent, TaskDetailRun, TuiApp, TuiEvent, UiAction,
+    DEFAULT_PANE_BUFFER_BYTES, TASK_DETAIL_EVENT_LIMIT,
 };
+use orchd::daemon_loop::record_pipeline_events;
 use orchd::qa_agent;
 use orchd::stack_pipeline::{self, PipelineState};
 use orchd::supervisor::AgentSupervisor;
@@ -15,6 +22,7 @@ use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
 const DEFAULT_TICK_MS: u64 = 250;
+const DEFAULT_IDLE_SUSPEND_SECS: u64 = 900;
 const DEFAULT_SQLITE_PATH: &str = ".orch/state.sqlite";
 const DEFAULT_EVENT_LOG_PATH: &str = ".orch/events";
 const CHAT_LOG_DIR: &str = ".orch/chat";
@@ -109,11 +117,11 @@ fn spawn_qa_agent_with_fallback(
     state: &mut qa_agent::QAState,
 ) -> anyhow::Result<ModelKind> {
     let primary = preferred_qa_model(task_model);
-    match qa_agent::spawn_qa_agent(cwd, prompt, primary, state) {
+    match qa_agent::spawn_qa_agent(cwd, prompt, primary, state, None) {
         Ok(()) => Ok(primary),
         Err(primary_err) => {
             if primary != ModelKind::Claude {
-                match qa_agent::spawn_qa_agent(cwd, prompt, ModelKind::Claude, state) {
+                match qa_agent::spawn_qa_agent(cwd, prompt, ModelKind::Claude, state, None) {
                     Ok(()) => Ok(ModelKind::Claude),
                     Err(fallback_err) => Err(anyhow::anyhow!(
                         "qa spawn with {} failed: {}; fallback {} failed: {}",
@@ -215,6 +223,39 @@ fn resolve_pipeline_parent_branch(
     None
 }
 
+/// Restack the task's branch in its worktree and record whether it hit a
+/// conflict, which the stack view marks. A conflicted rebase is aborted so
+/// the worktree stays usable.
+fn restack_task(service: &OrchdService, task: &Task) -> String {
+    let graphite = GraphiteClient::new(task.worktree_path.clone());
+    match graphite.restack_with_outcome() {
+        Ok(RestackOutcome::Restacked) => {
+            let _ = service.set_restack_conflict(&task.id, false);
+            format!("restacked {}", task.id.0)
+        }
+        Ok(RestackOutcome::Conflict {
+            conflicted_files, ..
+        }) => {
+            let _ = graphite.abort_rebase();
+            let _ = service.set_restack_conflict(&task.id, true);
+            if conflicted_files.is_empty() {
+                format!("restack conflict in {}; rebase aborted", task.id.0)
+            } else {
+                let files = conflicted_files
+                    .iter()
+                    .map(|file| file.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!(
+                    "restack conflict in {} ({files}); rebase aborted",
+                    task.id.0
+                )
+            }
+        }
+        Err(err) => format!("restack failed for {}: {err}", task.id.0),
+    }
+}
+
 fn resolve_validation_previous_result(
     repo_root: &Path,
     service: &OrchdService,
@@ -274,6 +315,24 @@ fn update_stack_head_from_task(
     }
 }
 
+/// Record a suspend/resume event for the task's agent session.
+fn record_session_event(service: &OrchdService, task_id: &TaskId, kind: EventKind) {
+    let at = Utc::now();
+    let seed = at.timestamp_nanos_opt().unwrap_or_default();
+    let event = Event {
+        id: EventId(format!("E-SESSION-{}-{seed}", task_id.0)),
+        task_id: Some(task_id.clone()),
+        repo_id: service
+            .task(task_id)
+            .ok()
+            .flatten()
+            .map(|task| task.repo_id),
+        at,
+        kind,
+    };
+    let _ = service.record_event(&event);
+}
+
 #[allow(clippy::too_many_arguments)]
 fn spawn_validation_qa(
     app: &mut TuiApp,
@@ -384,6 +443,10 @@ struct CliArgs {
     tick_ms: u64,
     sqlite_path: PathBuf,
     event_log_path: PathBuf,
+    pane_buffer_bytes: usize,
+    /// Seconds an interactive agent may sit idle before it is suspended;
+    /// 0 keeps idle agents running.
+    idle_suspend_secs: u64,
 }
 
 fn is_models_command(args: &[String]) -> bool {
@@ -391,7 +454,7 @@ fn is_models_command(args: &[String]) -> bool {
 }
 
 fn available_models_lines() -> Vec<String> {
-    [ModelKind::Claude, ModelKind::Codex, ModelKind::Gemini]
+    ModelKind::all()
         .iter()
         .map(|model| model.as_str().to_string())
         .collect()
@@ -505,17 +568,23 @@ fn run_qa_spec_gen_with_status(repo_root: &Path, template_dir: &Path, model: Mod
 }
 
 fn run_context_gen_with_status(repo_root: &Path, template_dir: &Path, model: ModelKind) {
-    use orchd::context_gen::{check_context_startup, parse_progress_line, ContextStartupStatus};
+    use orchd::context_gen::{
+        check_context_startup, parse_progress_line, plan_context_regen, ContextRegenPlan,
+        ContextStartupStatus,
+    };
 
     match check_context_startup(repo_root) {
         ContextStartupStatus::UpToDate => {
             eprintln!("  \x1b[32mContext up to date \u{2713}\x1b[0m");
             return;
         }
-        ContextStartupStatus::Stale => {
-            eprintln!("  \x1b[33mContext stale — will regenerate in background\x1b[0m");
-            return;
-        }
+        ContextStartupStatus::Stale => match plan_context_regen(repo_root, template_dir) {
+            ContextRegenPlan::Full => {
+                eprintln!("  \x1b[33mContext stale — will regenerate in background\x1b[0m");
+                return;
+            }
+            _ => eprintln!("  \x1b[33mContext stale — updating changed sections...\x1b[0m"),
+        },
         ContextStartupStatus::Missing => {
             eprintln!("  \x1b[33mGenerating context...\x1b[0m");
         }
@@ -523,16 +592,22 @@ fn run_context_gen_with_status(repo_root: &Path, template_dir: &Path, model: Mod
 
     let repo = repo_root.to_path_buf();
     let tmpl = template_dir.to_path_buf();
+    let config = orchd::context_gen::ContextGenConfig::default();
 
     let (result_tx, result_rx) = std::sync::mpsc::channel();
     let (progress_tx, progress_rx) = std::sync::mpsc::channel();
 
     std::thread::spawn(move || {
         let ptx = progress_tx;
-        let result =
-            orchd::context_gen::ensure_context_exists_blocking(&repo, &tmpl, model, move |line| {
+        let result = orchd::context_gen::ensure_context_exists_blocking(
+            &repo,
+            &tmpl,
+            &config,
+            model,
+            move |line| {
                 let _ = ptx.send(line.to_string());
-            });
+            },
+        );
         let _ = result_tx.send(result);
     });
 
@@ -628,12 +703,15 @@ fn run() -> Result<(), MainError> {
     }
     eprintln!();
 
-    let tasks = service.list_top_level_tasks().unwrap_or_default();
+    let tasks = service.list_tasks().unwrap_or_default();
     let mut app = TuiApp::from_tasks(&tasks);
+    app.state.chat_log_dir = Some(chat_log_dir.clone());
+    app.state.pane_buffer_bytes = args.pane_buffer_bytes;
 
-    // Restore chat history from log files.
+    // Restore chat history from log files; older lines stay on disk and are
+    // read back when scrolling.
     for task in &tasks {
-        let lines = load_chat_log(&chat_log_dir, &task.id);
+        let (older_lines, lines) = load_chat_log(&chat_log_dir, &task.id, args.pane_buffer_bytes);
         if !lines.is_empty() {
             let model = task.preferred_model.unwrap_or(ModelKind::Claude);
             let instance_id = format!("agent-{}", task.id.0);
@@ -643,6 +721,14 @@ fn run() -> Result<(), MainError> {
                 model,
                 lines,
             });
+            if let Some(pane) = app
+                .state
+                .panes
+                .iter_mut()
+                .find(|pane| pane.instance_id == instance_id)
+            {
+                pane.spilled_lines += older_lines;
+            }
             // Tasks still Chatting were killed when TUI closed — show "stopped".
             // Tasks that completed (Ready+) show "exited".
             let status = if task.state == TaskState::Chatting {
@@ -663,10 +749,17 @@ fn run() -> Result<(), MainError> {
         tasks.len()
     );
 
-    let mut supervisor = AgentSupervisor::new(ModelKind::Claude);
+    let repo_root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let signal_rules = SignalRuleSet::load(&repo_root).unwrap_or_else(|err| {
+        app.state.status_line = format!("using built-in signal rules: {err}");
+        SignalRuleSet::default()
+    });
+    let mut supervisor = AgentSupervisor::new(ModelKind::Claude)
+        .with_output_buffer_bytes(args.pane_buffer_bytes)
+        .with_idle_suspend(Duration::from_secs(args.idle_suspend_secs))
+        .with_signal_rules(signal_rules);
     let mut tick_counter: u32 = 0;
     let mut qa_agents: HashMap<String, qa_agent::QAState> = HashMap::new();
-    let repo_root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
     let template_dir = PathBuf::from("templates/prompts");
     let cached_main_baseline = qa_agent::load_latest_result(&repo_root, "main");
     let mut global_baseline_result: Option<qa_agent::QAResult> = None;
@@ -680,200 +773,78 @@ fn run() -> Result<(), MainError> {
     let mut pipelines: HashMap<String, PipelineState> = HashMap::new();
     let mut pipeline_procs: HashMap<String, PipelineProc> = HashMap::new();
 
+    if let Some(event) = load_open_questions(&service) {
+        app.apply_event(event);
+    }
+
     run_tui_with_hook(&mut app, Duration::from_millis(args.tick_ms), |app| {
         // Process queued actions from the UI.
         let actions = app.drain_actions();
         for queued in actions {
-            let (action, task_id, prompt, selected_model) = match queued {
+            let (action, task_id, prompt) = match queued {
                 QueuedAction::Dispatch {
                     action,
                     task_id,
                     prompt,
+                    ..
+                } => (action, task_id, prompt),
+                QueuedAction::CopyToClipboard { text } => {
+                    let message = copy_with_status(&mut SystemClipboard, &text);
+                    app.apply_event(TuiEvent::StatusLine { message });
+                    continue;
+                }
+                QueuedAction::CreateTask {
+                    repo,
+                    title,
                     model,
-                } => (action, task_id, prompt, model),
-                QueuedAction::CreateTask { repo, title, model } => {
-                    let model_kind = match model.trim().to_ascii_lowercase().as_str() {
-                        "codex" => ModelKind::Codex,
-                        "gemini" => ModelKind::Gemini,
-                        _ => ModelKind::Claude,
-                    };
-                    let repo_id = if repo.trim().is_empty() {
-                        "default".to_string()
-                    } else {
-                        repo.trim().to_string()
-                    };
-                    let title = title.trim().to_string();
-                    let task_id = TaskId::new(format!("chat-{}", Utc::now().timestamp_millis()));
+                    priority,
+                    stack,
+                    stack_on,
+                } => {
                     let start_path = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-
-                    let base_branch = qa_stack_head
-                        .as_ref()
-                        .map(|(_, branch)| branch.as_str())
-                        .unwrap_or("main");
-                    let (worktree_path, branch_name) = match orchd::provision_chat_workspace_on_base(
-                        &start_path,
-                        &task_id,
-                        Some(base_branch),
-                    ) {
-                        Ok(ws) => (ws.worktree_path, Some(ws.branch_name)),
-                        Err(e) => {
-                            if base_branch != "main" {
-                                app.apply_event(TuiEvent::StatusLine {
-                                    message: format!(
-                                        "workspace on `{base_branch}` failed ({e}); retrying on main"
-                                    ),
-                                });
-                                match orchd::provision_chat_workspace_on_base(
-                                    &start_path,
-                                    &task_id,
-                                    Some("main"),
-                                ) {
-                                    Ok(ws) => (ws.worktree_path, Some(ws.branch_name)),
-                                    Err(main_err) => {
-                                        app.apply_event(TuiEvent::StatusLine {
-                                            message: format!("workspace provision failed: {main_err}"),
-                                        });
-                                        continue;
-                                    }
-                                }
-                            } else {
-                                app.apply_event(TuiEvent::StatusLine {
-                                    message: format!("workspace provision failed: {e}"),
-                                });
-                                continue;
-                            }
-                        }
-                    };
-
-                    let mut task = Task::new(
-                        task_id.clone(),
-                        RepoId(repo_id.clone()),
+                    let request = orchd::NewChatTask {
+                        repo_id: RepoId(repo),
                         title,
-                        worktree_path,
-                    );
-                    task.preferred_model = Some(model_kind);
-                    task.submit_mode = SubmitMode::Stack;
-                    task.branch_name = branch_name.clone();
-                    let event = Event {
-                        id: EventId(format!("E-CREATE-{}", task_id.0)),
-                        task_id: Some(task.id.clone()),
-                        repo_id: Some(task.repo_id.clone()),
-                        at: Utc::now(),
-                        kind: EventKind::TaskCreated,
+                        model,
+                        priority,
+                        stack,
+                        stack_on,
                     };
-
-                    match service.create_task(&task, &event) {
-                        Ok(()) => {
-                            let detail = branch_name.as_deref().unwrap_or("(no branch)");
-                            app.apply_event(TuiEvent::StatusLine {
-                                message: format!(
-                                    "created task {} repo={} model={:?} on {}",
-                                    task_id.0, repo_id, model_kind, detail
-                                ),
-                            });
-                            if let Ok(tasks) = service.list_top_level_tasks() {
+                    match orchd::create_chat_task(&service, &start_path, &request) {
+                        Ok(created) => {
+                            if let Ok(tasks) = service.list_tasks() {
                                 app.apply_event(TuiEvent::TasksReplaced { tasks });
                             }
+                            app.apply_event(TuiEvent::TaskCreateFinished {
+                                task_id: created.task.id,
+                            });
                         }
                         Err(e) => {
-                            app.apply_event(TuiEvent::StatusLine {
-                                message: format!("create failed: {e}"),
+                            app.apply_event(TuiEvent::TaskCreateFailed {
+                                message: format!("{e:#}"),
                             });
                         }
                     }
-
                     continue;
                 }
             };
             match action {
-                UiAction::CreateTask => {
-                    if let Some(prompt) = &prompt {
-                        let model = selected_model.unwrap_or(ModelKind::Claude);
-                        let task_id =
-                            TaskId::new(format!("chat-{}", Utc::now().timestamp_millis()));
-                        let start_path =
-                            std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-
-                        // New chats default to the current QA-confirmed stack head.
-                        let base_branch = qa_stack_head
-                            .as_ref()
-                            .map(|(_, branch)| branch.as_str())
-                            .unwrap_or("main");
-                        let (worktree_path, branch_name) =
-                            match orchd::provision_chat_workspace_on_base(
-                                &start_path,
-                                &task_id,
-                                Some(base_branch),
-                            ) {
-                                Ok(ws) => (ws.worktree_path, Some(ws.branch_name)),
-                                Err(e) => {
-                                    if base_branch != "main" {
-                                        app.apply_event(TuiEvent::StatusLine {
-                                            message: format!(
-                                                "workspace on `{base_branch}` failed ({e}); retrying on main"
-                                            ),
-                                        });
-                                        match orchd::provision_chat_workspace_on_base(
-                                            &start_path,
-                                            &task_id,
-                                            Some("main"),
-                                        ) {
-                                            Ok(ws) => (ws.worktree_path, Some(ws.branch_name)),
-                                            Err(main_err) => {
-                                                app.apply_event(TuiEvent::StatusLine {
-                                                    message: format!(
-                                                        "workspace provision failed: {main_err}"
-                                                    ),
-                                                });
-                                                continue;
-                                            }
-                                        }
-                                    } else {
-                                        app.apply_event(TuiEvent::StatusLine {
-                                            message: format!("workspace provision failed: {e}"),
-                                        });
-                                        continue;
-                                    }
-                                }
-                            };
-
-                        let mut task = Task::new(
-                            task_id.clone(),
-                            RepoId("default".to_string()),
-                            prompt.clone(),
-                            worktree_path,
-                        );
-                        task.preferred_model = Some(model);
-                        task.submit_mode = SubmitMode::Stack;
-                        task.branch_name = branch_name.clone();
-                        let event = Event {
-                            id: EventId(format!("E-CREATE-{}", task_id.0)),
-                            task_id: Some(task.id.clone()),
-                            repo_id: Some(task.repo_id.clone()),
-                            at: Utc::now(),
-                            kind: EventKind::TaskCreated,
-                        };
-                        match service.create_task(&task, &event) {
-                            Ok(()) => {
-                                let detail = branch_name.as_deref().unwrap_or("(no branch)");
-                                app.apply_event(TuiEvent::StatusLine {
-                                    message: format!("created chat {} on {}", task_id.0, detail),
-                                });
-                                // Immediately refresh task list so it appears.
-                                if let Ok(tasks) = service.list_top_level_tasks() {
-                                    app.apply_event(TuiEvent::TasksReplaced { tasks });
-                                }
-                            }
-                            Err(e) => {
-                                app.apply_event(TuiEvent::StatusLine {
-                                    message: format!("create failed: {e}"),
-                                });
-                            }
-                        }
-                    }
-                }
                 UiAction::DeleteTask => {
                     if let Some(task_id) = &task_id {
+                        // Child tasks keep the parent (and its worktree) alive.
+                        let children = service.child_task_ids(task_id).unwrap_or_default();
+                        if !children.is_empty() {
+                            let children: Vec<&str> =
+                                children.iter().map(|id| id.0.as_str()).collect();
+                            app.apply_event(TuiEvent::StatusLine {
+                                message: format!(
+                                    "delete refused: {} has child tasks {}",
+                                    task_id.0,
+                                    children.join(", ")
+                                ),
+                            });
+                            continue;
+                        }
                         supervisor.stop(task_id);
                         // Kill any running QA agents for this task.
                         for prefix in &["qa-", "qa-base-"] {
@@ -899,8 +870,8 @@ fn run() -> Result<(), MainError> {
                         manually_stopped_tasks.remove(task_id);
                         validation_baseline_by_task.remove(&task_id.0);
                         next_agent_restart_at.remove(&task_id.0);
-                        match service.delete_task(task_id) {
-                            Ok(true) => {
+                        match service.delete_task(task_id, false) {
+                            Ok(deleted) if !deleted.is_empty() => {
                                 qa_stack_head = discover_qa_stack_head(
                                     &repo_root,
                                     &service.list_tasks().unwrap_or_default(),
@@ -909,7 +880,7 @@ fn run() -> Result<(), MainError> {
                                     message: format!("deleted {}", task_id.0),
                                 });
                             }
-                            Ok(false) => {
+                            Ok(_) => {
                                 app.apply_event(TuiEvent::StatusLine {
                                     message: format!("not found: {}", task_id.0),
                                 });
@@ -922,7 +893,7 @@ fn run() -> Result<(), MainError> {
                         }
                     }
                 }
-                UiAction::StartAgent => {
+                UiAction::StartAgent | UiAction::ResumeTask => {
                     if let Some(task_id) = &task_id {
                         if let Ok(Some(task)) = service.task(task_id) {
                             // Validate/recover worktree before spawning.
@@ -974,7 +945,9 @@ fn run() -> Result<(), MainError> {
                         }
                     }
                 }
-                UiAction::StopAgent => {
+                // A stopped agent pane is never auto-restarted, so pausing is
+                // a stop until the task is resumed.
+                UiAction::StopAgent | UiAction::PauseTask => {
                     if let Some(task_id) = &task_id {
                         supervisor.stop(task_id);
                         manually_stopped_tasks.insert(task_id.clone());
@@ -985,14 +958,19 @@ fn run() -> Result<(), MainError> {
                         });
                         next_agent_restart_at.remove(&task_id.0);
                         app.apply_event(TuiEvent::StatusLine {
-                            message: format!("stopped agent for {}", task_id.0),
+                            message: if action == UiAction::PauseTask {
+                                format!("paused {}", task_id.0)
+                            } else {
+                                format!("stopped agent for {}", task_id.0)
+                            },
                         });
                     }
                 }
                 UiAction::SendChatMessage => {
                     if let (Some(task_id), Some(message)) = (&task_id, &prompt) {
                         // Auto-spawn interactive session if none exists.
-                        if !supervisor.has_session(task_id) {
+                        // Suspended sessions resume through `send_input`.
+                        if !supervisor.has_session(task_id) && !supervisor.is_suspended(task_id) {
                             if let Ok(Some(task)) = service.task(task_id) {
                                 let model = task.preferred_model.unwrap_or(ModelKind::Claude);
                                 let spawn_mode = if model == ModelKind::Codex {
@@ -1029,6 +1007,7 @@ fn run() -> Result<(), MainError> {
                                     // Echo user message into the pane and log.
                                     let user_line = format!("> {message}");
                                     append_chat_log(&chat_log_dir, task_id, std::slice::from_ref(&user_line));
+                                    answer_open_questions(app, &service, task_id, message);
                                     let instance_id = format!("agent-{}", task_id.0);
                                     app.apply_event(TuiEvent::AgentPaneOutput {
                                         instance_id: instance_id.clone(),
@@ -1050,10 +1029,30 @@ fn run() -> Result<(), MainError> {
                             }
                         } else {
                             // Session exists — send the message.
+                            let resuming = supervisor.is_suspended(task_id);
                             match supervisor.send_input(task_id, message) {
                                 Ok(()) => {
+                                    if resuming {
+                                        let session_id = supervisor
+                                            .session_id(task_id)
+                                            .unwrap_or_default()
+                                            .to_string();
+                                        record_session_event(
+                                            &service,
+                                            task_id,
+                                            EventKind::AgentResumed { session_id },
+                                        );
+                                        app.apply_event(TuiEvent::AgentPaneStatusChanged {
+                                            instance_id: format!("agent-{}", task_id.0),
+                                            status: AgentPaneStatus::Starting,
+                                        });
+                                        app.apply_event(TuiEvent::StatusLine {
+                                            message: format!("resumed agent for {}", task_id.0),
+                                        });
+                                    }
                                     let user_line = format!("> {message}");
                                     append_chat_log(&chat_log_dir, task_id, std::slice::from_ref(&user_line));
+                                    answer_open_questions(app, &service, task_id, message);
                                     let instance_id = format!("agent-{}", task_id.0);
                                     let model = service
                                         .task(task_id)
@@ -1149,7 +1148,7 @@ fn run() -> Result<(), MainError> {
                                 app.apply_event(TuiEvent::StatusLine {
                                     message: format!("{} -> Submitting (manual)", task_id.0),
                                 });
-                                if let Ok(tasks) = service.list_top_level_tasks() {
+                                if let Ok(tasks) = service.list_tasks() {
                                     app.apply_event(TuiEvent::TasksReplaced { tasks });
                                 }
                             } else {
@@ -1163,6 +1162,44 @@ fn run() -> Result<(), MainError> {
                         }
                     }
                 }
+                UiAction::TriggerRestack => {
+                    if let Some(task_id) = &task_id {
+                        match service.task(task_id) {
+                            Ok(Some(task)) => {
+                                let message = restack_task(&service, &task);
+                                app.apply_event(TuiEvent::StatusLine { message });
+                                if let Ok(tasks) = service.list_tasks() {
+                                    app.apply_event(TuiEvent::TasksReplaced { tasks });
+                                }
+                            }
+                            _ => app.apply_event(TuiEvent::StatusLine {
+                                message: format!("task {} not found", task_id.0),
+                            }),
+                        }
+                    }
+                }
+                UiAction::OpenDiff => {
+                    if let Some(task_id) = &task_id {
+                        match service.task(task_id) {
+                            Ok(Some(task)) => {
+                                match orchd::retry_guard::capture_worktree_patch(
+                                    &task.worktree_path,
+                                ) {
+                                    Some(patch) => app.apply_event(TuiEvent::DiffLoaded {
+                                        task_id: task_id.clone(),
+                                        lines: patch.lines().map(str::to_string).collect(),
+                                    }),
+                                    None => app.apply_event(TuiEvent::StatusLine {
+                                        message: format!("git diff failed for {}", task_id.0),
+                                    }),
+                                }
+                            }
+                            _ => app.apply_event(TuiEvent::StatusLine {
+                                message: format!("task {} not found", task_id.0),
+                            }),
+                        }
+                    }
+                }
                 _ => {
                     app.apply_event(TuiEvent::StatusLine {
                         message: format!("action not yet implemented: {:?}", action),
@@ -1173,8 +1210,15 @@ fn run() -> Result<(), MainError> {
 
         // Poll supervisor for output and completions.
         let result = supervisor.poll();
+        let mut questions_changed = false;
         for chunk in result.output {
             append_chat_log(&chat_log_dir, &chunk.task_id, &chunk.lines);
+            match service.capture_questions(&chunk.task_id, &chunk.lines, Utc::now()) {
+                Ok(asked) => questions_changed |= !asked.is_empty(),
+                Err(e) => app.apply_event(TuiEvent::StatusLine {
+                    message: format!("failed to record question for {}: {e}", chunk.task_id.0),
+                }),
+            }
             let instance_id = format!("agent-{}", chunk.task_id.0);
             app.apply_event(TuiEvent::AgentPaneOutput {
                 instance_id,
@@ -1183,6 +1227,25 @@ fn run() -> Result<(), MainError> {
                 lines: chunk.lines,
             });
         }
+        if questions_changed {
+            if let Some(event) = load_open_questions(&service) {
+                app.apply_event(event);
+            }
+        }
+        for suspended in result.suspended {
+            record_session_event(
+                &service,
+                &suspended.task_id,
+                EventKind::AgentSuspended {
+                    session_id: suspended.session_id,
+                    idle_secs: suspended.idle_secs,
+                },
+            );
+            app.apply_event(TuiEvent::AgentPaneStatusChanged {
+                instance_id: format!("agent-{}", suspended.task_id.0),
+                status: AgentPaneStatus::Suspended,
+            });
+        }
         for outcome in &result.completed {
             let instance_id = format!("agent-{}", outcome.task_id.0);
             let now = Utc::now();
@@ -1313,7 +1376,7 @@ fn run() -> Result<(), MainError> {
         // Refresh task list immediately when any agents completed so
         // state changes (Chatting → Ready) show up without delay.
         if !result.completed.is_empty() {
-            if let Ok(tasks) = service.list_top_level_tasks() {
+            if let Ok(tasks) = service.list_tasks() {
                 app.apply_event(TuiEvent::TasksReplaced { tasks });
             }
         }
@@ -1401,7 +1464,7 @@ fn run() -> Result<(), MainError> {
         }
 
         // Auto-spawn agents for Chatting tasks without a running session.
-        // Waiting/Stopped panes are intentional pauses; Failed/Exited are
+        // Waiting/Stopped/Suspended panes are intentional pauses; Failed/Exited are
         // auto-restarted unless QA is currently running for that task.
         if let Ok(chatting) = service.list_tasks_by_state(TaskState::Chatting) {
             for task in &chatting {
@@ -1410,7 +1473,9 @@ fn run() -> Result<(), MainError> {
                         && pane.instance_id.starts_with("agent-")
                         && matches!(
                             pane.status,
-                            AgentPaneStatus::Waiting | AgentPaneStatus::Stopped
+                            AgentPaneStatus::Waiting
+                                | AgentPaneStatus::Stopped
+                                | AgentPaneStatus::Suspended
                         )
                 });
                 let qa_key = format!("qa-{}", task.id.0);
@@ -1422,6 +1487,7 @@ fn run() -> Result<(), MainError> {
                     .unwrap_or(true);
 
                 if !supervisor.has_session(&task.id)
+                    && !supervisor.is_suspended(&task.id)
                     && !pane_paused
                     && !qa_pending
                     && restart_allowed
@@ -1668,7 +1734,7 @@ fn run() -> Result<(), MainError> {
                             }
                         }
                         // Refresh tasks so UI shows the state change.
-                        if let Ok(tasks) = service.list_top_level_tasks() {
+                        if let Ok(tasks) = service.list_tasks() {
                             app.apply_event(TuiEvent::TasksReplaced { tasks });
                         }
                     } else {
@@ -1815,7 +1881,7 @@ fn run() -> Result<(), MainError> {
                             EventId(format!("E-READY-QA-BLOCKED-{}", task_id.0)),
                             Utc::now(),
                         );
-                        if let Ok(tasks) = service.list_top_level_tasks() {
+                        if let Ok(tasks) = service.list_tasks() {
                             app.apply_event(TuiEvent::TasksReplaced { tasks });
                         }
                         update_stack_head_from_task(
@@ -1974,7 +2040,7 @@ fn run() -> Result<(), MainError> {
                             app.apply_event(TuiEvent::StatusLine {
                                 message: format!("{} -> AwaitingMerge (submitted)", task_id.0),
                             });
-                            if let Ok(tasks) = service.list_top_level_tasks() {
+                            if let Ok(tasks) = service.list_tasks() {
                                 app.apply_event(TuiEvent::TasksReplaced { tasks });
                             }
                             None
@@ -2088,7 +2154,7 @@ fn run() -> Result<(), MainError> {
                                 app.apply_event(TuiEvent::StatusLine {
                                     message: format!("{} -> AwaitingMerge (submitted)", task_id.0),
                                 });
-                                if let Ok(tasks) = service.list_top_level_tasks() {
+                                if let Ok(tasks) = service.list_tasks() {
                                     app.apply_event(TuiEvent::TasksReplaced { tasks });
                                 }
                             }
@@ -2113,13 +2179,23 @@ fn run() -> Result<(), MainError> {
             }
         }
 
-        // Clean up terminal pipelines.
+        // Record step progress, then clean up terminal pipelines.
+        for pipeline in pipelines.values_mut() {
+            if let Err(err) = record_pipeline_events(&service, pipeline) {
+                app.apply_event(TuiEvent::StatusLine {
+                    message: format!(
+                        "{} pipeline: failed to record step events — {err}",
+                        pipeline.task_id.0
+                    ),
+                });
+            }
+        }
         pipelines.retain(|_, p| !p.is_terminal());
 
         // Refresh task list periodically (every ~2s at 250ms tick).
         tick_counter = tick_counter.wrapping_add(1);
         if tick_counter.is_multiple_of(8) {
-            if let Ok(tasks) = service.list_top_level_tasks() {
+            if let Ok(tasks) = service.list_tasks() {
                 app.apply_event(TuiEvent::TasksReplaced {
                     tasks: tasks.clone(),
                 });
@@ -2132,6 +2208,15 @@ fn run() -> Result<(), MainError> {
                         app.apply_event(qa_event);
                     }
                 }
+                if let Some(event) = load_open_questions(&service) {
+                    app.apply_event(event);
+                }
+            }
+        }
+
+        if let Some(task_id) = app.state.task_detail_due(Utc::now()) {
+            if let Some(event) = load_task_detail(&service, &task_id, &qa_stack_head) {
+                app.apply_event(event);
             }
         }
     })?;
@@ -2165,6 +2250,96 @@ fn extract_targets(spec: &str) -> Vec<String> {
         .collect()
 }
 
+// -- Task detail loading ----------------------------------------------------
+
+fn load_task_detail(
+    service: &OrchdService,
+    task_id: &TaskId,
+    qa_stack_head: &Option<(TaskId, String)>,
+) -> Option<TuiEvent> {
+    let task = service.task(task_id).ok()??;
+    let events = service.task_events(task_id).unwrap_or_default();
+    let recent = &events[events.len().saturating_sub(TASK_DETAIL_EVENT_LIMIT)..];
+    let parent = resolve_pipeline_parent_branch(service, &task, qa_stack_head)
+        .unwrap_or_else(|| "main".to_string());
+    let git = orch_git::GitCli::default();
+    let diff = orch_git::discover_repo(&task.worktree_path, &git)
+        .and_then(|repo| {
+            orch_git::capture_diff_snapshot(&repo, &git, Some(&format!("{parent}...HEAD")))
+        })
+        .ok();
+
+    let mut fields = vec![
+        ("State".to_string(), task.state.to_string()),
+        ("Priority".to_string(), task.priority.as_str().to_string()),
+        ("Submit".to_string(), format!("{:?}", task.submit_mode).to_lowercase()),
+        ("Parent".to_string(), parent),
+        ("Worktree".to_string(), task.worktree_path.display().to_string()),
+        (
+            "Retries".to_string(),
+            format!("{}/{}", task.retry_count, task.max_retries),
+        ),
+    ];
+    if let Some(reason) = &task.last_failure_reason {
+        fields.push(("Last failure".to_string(), reason.clone()));
+    }
+
+    Some(TuiEvent::TaskDetailLoaded {
+        detail: TaskDetail {
+            task_id: task_id.clone(),
+            fields,
+            events: recent
+                .iter()
+                .map(|event| TaskDetailEvent {
+                    at: event.at,
+                    summary: event_summary(&event.kind),
+                })
+                .collect(),
+            runs: service
+                .task_runs(task_id)
+                .unwrap_or_default()
+                .iter()
+                .map(|run| TaskDetailRun {
+                    model: run.model,
+                    started_at: run.started_at,
+                    duration_secs: run.duration_secs.map(|secs| secs.round() as u64),
+                    outcome: match (&run.finished_at, &run.stop_reason, run.exit_code) {
+                        (None, _, _) => "running".to_string(),
+                        (_, Some(reason), _) => reason.clone(),
+                        (_, None, Some(code)) => format!("exit {code}"),
+                        (_, None, None) => "finished".to_string(),
+                    },
+                })
+                .collect(),
+            diff_stat: diff.as_ref().and_then(|diff| diff.shortstat.clone()),
+            diff_files: diff
+                .map(|diff| {
+                    diff.files
+                        .iter()
+                        .map(|file| file.display().to_string())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            loaded_at: Utc::now(),
+        },
+    })
+}
+
+/// One-line description of an event; the serde tag for kinds without a
+/// more useful summary.
+fn event_summary(kind: &EventKind) -> String {
+    match kind {
+        EventKind::TaskStateChanged { from, to } => format!("state: {from} -> {to}"),
+        EventKind::NeedsHuman { reason } => format!("needs_human: {reason}"),
+        EventKind::Error { code, message } => format!("error [{code}]: {message}"),
+        other => match serde_json::to_value(other) {
+            Ok(serde_json::Value::String(tag)) => tag,
+            Ok(serde_json::Value::Object(map)) => map.keys().next().cloned().unwrap_or_default(),
+            _ => format!("{other:?}"),
+        },
+    }
+}
+
 // -- QA display loading -----------------------------------------------------
 
 fn load_qa_display(repo_root: &Path, task_id: &TaskId, branch: &str) -> Option<TuiEvent> {
@@ -2216,8 +2391,39 @@ fn load_qa_display(repo_root: &Path, task_id: &TaskId, branch: &str) -> Option<T
 
 // -- Chat log persistence ---------------------------------------------------
 
-fn chat_log_path(base: &Path, task_id: &TaskId) -> PathBuf {
-    base.join(format!("{}.log", task_id.0))
+fn load_open_questions(service: &OrchdService) -> Option<TuiEvent> {
+    let questions = service.store.list_open_questions().ok()?;
+    Some(TuiEvent::OpenQuestionsReplaced {
+        questions: questions
+            .into_iter()
+            .map(|question| OpenQuestionDisplay {
+                question_id: question.question_id,
+                task_id: question.task_id,
+                question: question.question,
+                asked_at: question.asked_at,
+            })
+            .collect(),
+    })
+}
+
+/// A chat message answers whatever the agent was waiting on.
+fn answer_open_questions(
+    app: &mut TuiApp,
+    service: &OrchdService,
+    task_id: &TaskId,
+    message: &str,
+) {
+    match service.answer_questions(task_id, message, Utc::now()) {
+        Ok(answered) if !answered.is_empty() => {
+            if let Some(event) = load_open_questions(service) {
+                app.apply_event(event);
+            }
+        }
+        Ok(_) => {}
+        Err(e) => app.apply_event(TuiEvent::StatusLine {
+            message: format!("failed to resolve questions for {}: {e}", task_id.0),
+        }),
+    }
 }
 
 fn append_chat_log(base: &Path, task_id: &TaskId, lines: &[String]) {
@@ -2237,22 +2443,16 @@ fn append_chat_log(base: &Path, task_id: &TaskId, lines: &[String]) {
     }
 }
 
-fn load_chat_log(base: &Path, task_id: &TaskId) -> Vec<String> {
-    let path = chat_log_path(base, task_id);
-    let Ok(content) = std::fs::read_to_string(&path) else {
-        return Vec::new();
-    };
-    let lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
-    // Keep only the last 400 lines to match the in-memory limit.
-    if lines.len() > 400 {
-        lines[lines.len() - 400..].to_vec()
-    } else {
-        lines
-    }
+/// The newest chat lines that fit in a pane's `max_bytes`, plus the number of
+/// older lines left on disk for scrollback.
+fn load_chat_log(base: &Path, task_id: &TaskId, max_bytes: usize) -> (usize, Vec<String>) {
+    load_log_tail(&chat_log_path(base, task_id), max_bytes)
 }
 
 fn parse_cli_args(args: Vec<String>, program: &str) -> Result<CliArgs, MainError> {
     let mut tick_ms = DEFAULT_TICK_MS;
+    let mut pane_buffer_bytes = DEFAULT_PANE_BUFFER_BYTES;
+    let mut idle_suspend_secs = DEFAULT_IDLE_SUSPEND_SECS;
     let mut sqlite_path = PathBuf::from(DEFAULT_SQLITE_PATH);
     let mut event_log_path = PathBuf::from(DEFAULT_EVENT_LOG_PATH);
     let mut idx = 0usize;
@@ -2289,6 +2489,33 @@ fn parse_cli_args(args: Vec<String>, program: &str) -> Result<CliArgs, MainError
                 })?;
                 event_log_path = PathBuf::from(value);
             }
+            "--pane-buffer-bytes" => {
+                idx += 1;
+                let value = args.get(idx).ok_or_else(|| {
+                    MainError::Args("missing value for --pane-buffer-bytes".to_string())
+                })?;
+                pane_buffer_bytes = value.parse::<usize>().map_err(|_| {
+                    MainError::Args(format!(
+                        "invalid --pane-buffer-bytes value: {value} (expected usize)"
+                    ))
+                })?;
+                if pane_buffer_bytes == 0 {
+                    return Err(MainError::Args(
+                        "invalid --pane-buffer-bytes value: 0 (must be > 0)".to_string(),
+                    ));
+                }
+            }
+            "--idle-suspend-secs" => {
+                idx += 1;
+                let value = args.get(idx).ok_or_else(|| {
+                    MainError::Args("missing value for --idle-suspend-secs".to_string())
+                })?;
+                idle_suspend_secs = value.parse::<u64>().map_err(|_| {
+                    MainError::Args(format!(
+                        "invalid --idle-suspend-secs value: {value} (expected u64)"
+                    ))
+                })?;
+            }
             other => {
                 return Err(MainError::Args(format!(
                     "unknown argument: {other}\n\n{}",
@@ -2303,16 +2530,20 @@ fn parse_cli_args(args: Vec<String>, program: &str) -> Result<CliArgs, MainError
         tick_ms,
         sqlite_path,
         event_log_path,
+        pane_buffer_bytes,
+        idle_suspend_secs,
     })
 }
 
 fn usage(program: &str) -> String {
     format!(
-        "Usage: {program} [models] [--tick-ms <u64>] [--sqlite-path <path>] [--event-log-path <path>]\n\
+        "Usage: {program} [models] [--tick-ms <u64>] [--sqlite-path <path>] [--event-log-path <path>] [--pane-buffer-bytes <usize>] [--idle-suspend-secs <u64>]\n\
 Defaults:\n\
   --tick-ms {DEFAULT_TICK_MS}\n\
   --sqlite-path {DEFAULT_SQLITE_PATH}\n\
   --event-log-path {DEFAULT_EVENT_LOG_PATH}\n\
+  --pane-buffer-bytes {DEFAULT_PANE_BUFFER_BYTES}\n\
+  --idle-suspend-secs {DEFAULT_IDLE_SUSPEND_SECS} (0 disables)\n\
 Commands:\n\
   models               list available models"
     )
@@ -2323,6 +2554,7 @@ mod tests {
     use super::{
         append_chat_log, available_models_lines, chat_log_path, discover_qa_stack_head,
         is_models_command, load_chat_log, parse_cli_args, usage, CliArgs,
+        DEFAULT_IDLE_SUSPEND_SECS, DEFAULT_PANE_BUFFER_BYTES,
     };
     use chrono::{Duration as ChronoDuration, Utc};
     use orch_core::state::TaskState;
@@ -2339,6 +2571,8 @@ mod tests {
                 tick_ms: 250,
                 sqlite_path: PathBuf::from(".orch/state.sqlite"),
                 event_log_path: PathBuf::from(".orch/events"),
+                pane_buffer_bytes: DEFAULT_PANE_BUFFER_BYTES,
+                idle_suspend_secs: DEFAULT_IDLE_SUSPEND_SECS,
             }
         );
     }
@@ -2361,6 +2595,8 @@ mod tests {
                 tick_ms: 500,
                 sqlite_path: PathBuf::from("/tmp/state.sqlite"),
                 event_log_path: PathBuf::from(".orch/events"),
+                pane_buffer_bytes: DEFAULT_PANE_BUFFER_BYTES,
+                idle_suspend_secs: DEFAULT_IDLE_SUSPEND_SECS,
             }
         );
     }
@@ -2390,6 +2626,46 @@ mod tests {
         assert_eq!(err.to_string(), "invalid --tick-ms value: 0 (must be > 0)");
     }
 
+    #[test]
+    fn parse_cli_args_applies_pane_buffer_bytes() {
+        let parsed = parse_cli_args(
+            vec!["--pane-buffer-bytes".to_string(), "65536".to_string()],
+            "orch-tui",
+        )
+        .expect("parse");
+        assert_eq!(parsed.pane_buffer_bytes, 65_536);
+
+        let err = parse_cli_args(
+            vec!["--pane-buffer-bytes".to_string(), "0".to_string()],
+            "orch-tui",
+        )
+        .expect_err("should fail");
+        assert_eq!(
+            err.to_string(),
+            "invalid --pane-buffer-bytes value: 0 (must be > 0)"
+        );
+    }
+
+    #[test]
+    fn parse_cli_args_applies_idle_suspend_secs() {
+        let parsed = parse_cli_args(
+            vec!["--idle-suspend-secs".to_string(), "0".to_string()],
+            "orch-tui",
+        )
+        .expect("parse");
+        assert_eq!(parsed.idle_suspend_secs, 0);
+
+        let err = parse_cli_args(
+            vec!["--idle-suspend-secs".to_string(), "soon".to_string()],
+            "orch-tui",
+        )
+        .expect_err("should fail");
+        assert_eq!(
+            err.to_string(),
+            "invalid --idle-suspend-secs value: soon (expected u64)"
+        );
+    }
+
     #[test]
     fn parse_cli_args_help_returns_usage() {
         let err = parse_cli_args(vec!["--help".to_string()], "orch-tui").expect_err("help path");
@@ -2434,7 +2710,8 @@ mod tests {
         let task_id = TaskId("T1".to_string());
 
         // Empty before any writes.
-        assert!(load_chat_log(&dir, &task_id).is_empty());
+        let (_, loaded) = load_chat_log(&dir, &task_id, DEFAULT_PANE_BUFFER_BYTES);
+        assert!(loaded.is_empty());
 
         // Write some lines.
         append_chat_log(
@@ -2442,12 +2719,12 @@ mod tests {
             &task_id,
             &["line one".to_string(), "line two".to_string()],
         );
-        let loaded = load_chat_log(&dir, &task_id);
+        let (_, loaded) = load_chat_log(&dir, &task_id, DEFAULT_PANE_BUFFER_BYTES);
         assert_eq!(loaded, vec!["line one", "line two"]);
 
         // Append more lines.
         append_chat_log(&dir, &task_id, &["line three".to_string()]);
-        let loaded = load_chat_log(&dir, &task_id);
+        let (_, loaded) = load_chat_log(&dir, &task_id, DEFAULT_PANE_BUFFER_BYTES);
         assert_eq!(loaded, vec!["line one", "line two", "line three"]);
     }
 
@@ -2459,7 +2736,9 @@ mod tests {
         let lines: Vec<String> = (0..500).map(|i| format!("line {i}")).collect();
         append_chat_log(&dir, &task_id, &lines);
 
-        let loaded = load_chat_log(&dir, &task_id);
+        // "line 100".."line 499" are 8 bytes each.
+        let (older, loaded) = load_chat_log(&dir, &task_id, 400 * 8);
+        assert_eq!(older, 100);
         assert_eq!(loaded.len(), 400);
         assert_eq!(loaded[0], "line 100");
         assert_eq!(loaded[399], "line 499");
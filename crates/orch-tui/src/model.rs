@@ -2,10 +2,16 @@
 
 use chrono::{DateTime, Utc};
 use orch_core::state::{TaskState, VerifyStatus};
-use orch_core::types::{ModelKind, RepoId, Session, SessionStatus, Task, TaskId};
+use orch_core::types::{ModelKind, RepoId, Session, SessionStatus, Task, TaskId, TaskPriority};
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+
+use crate::pane_history::read_log_lines;
+
+/// In-memory output kept per pane, in bytes, unless overridden.
+pub const DEFAULT_PANE_BUFFER_BYTES: usize = 4 * 1024 * 1024;
 
 /// Display-friendly QA test result for the sidebar.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -53,6 +59,11 @@ pub struct TaskOverviewRow {
     pub pr_url: Option<String>,
     #[serde(default)]
     pub model_display: Option<String>,
+    /// Stack parent, for the stack view.
+    #[serde(default)]
+    pub parent_task_id: Option<TaskId>,
+    #[serde(default)]
+    pub restack_conflict: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -71,6 +82,52 @@ pub struct ErrorEntry {
     pub level: String,
 }
 
+/// An unanswered question an agent raised with `[needs_human]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenQuestionDisplay {
+    pub question_id: String,
+    pub task_id: TaskId,
+    pub question: String,
+    pub asked_at: DateTime<Utc>,
+}
+
+/// Events kept in the task detail view.
+pub const TASK_DETAIL_EVENT_LIMIT: usize = 20;
+
+/// Age after which the tick hook reloads the task detail.
+pub const TASK_DETAIL_STALE_SECS: i64 = 10;
+
+/// Task detail beyond the overview row, loaded by the tick hook from the
+/// service and git.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskDetail {
+    pub task_id: TaskId,
+    /// Label/value pairs for task fields the overview row does not carry.
+    pub fields: Vec<(String, String)>,
+    /// Most recent last.
+    pub events: Vec<TaskDetailEvent>,
+    pub runs: Vec<TaskDetailRun>,
+    /// `git diff --shortstat` against the parent branch.
+    pub diff_stat: Option<String>,
+    pub diff_files: Vec<String>,
+    pub loaded_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskDetailEvent {
+    pub at: DateTime<Utc>,
+    pub summary: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskDetailRun {
+    pub model: ModelKind,
+    pub started_at: DateTime<Utc>,
+    pub duration_secs: Option<u64>,
+    /// Stop reason, exit code, or "running".
+    pub outcome: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TimelineEvent {
     pub timestamp: String,
@@ -288,10 +345,49 @@ impl TaskOverviewRow {
             depends_on_display: task.depends_on.iter().map(|d| d.0.clone()).collect(),
             pr_url: task.pr.as_ref().map(|p| p.url.clone()),
             model_display: task.preferred_model.map(|m| m.as_str().to_string()),
+            parent_task_id: task.parent_task_id.clone(),
+            restack_conflict: task.restack_conflict,
         }
     }
 }
 
+/// Rows in stack order, paired with their depth: roots first, each followed by
+/// its children, the same walk `othala deps` prints. Rows whose parent is not
+/// in `rows` are shown as roots.
+pub fn stack_tree(rows: &[TaskOverviewRow]) -> Vec<(usize, &TaskOverviewRow)> {
+    let known: HashSet<&TaskId> = rows.iter().map(|row| &row.task_id).collect();
+    let mut ordered = Vec::with_capacity(rows.len());
+    let mut visited = HashSet::new();
+    let roots = rows.iter().filter(|row| {
+        row.parent_task_id
+            .as_ref()
+            .is_none_or(|parent| !known.contains(parent))
+    });
+    for root in roots {
+        push_stack_subtree(rows, root, 0, &mut visited, &mut ordered);
+    }
+    ordered
+}
+
+fn push_stack_subtree<'a>(
+    rows: &'a [TaskOverviewRow],
+    row: &'a TaskOverviewRow,
+    depth: usize,
+    visited: &mut HashSet<&'a TaskId>,
+    ordered: &mut Vec<(usize, &'a TaskOverviewRow)>,
+) {
+    if !visited.insert(&row.task_id) {
+        return;
+    }
+    ordered.push((depth, row));
+    for child in rows
+        .iter()
+        .filter(|child| child.parent_task_id.as_ref() == Some(&row.task_id))
+    {
+        push_stack_subtree(rows, child, depth + 1, visited, ordered);
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum PaneCategory {
@@ -327,6 +423,8 @@ pub enum AgentPaneStatus {
     Failed,
     /// Agent was killed because the TUI was closed.
     Stopped,
+    /// Interactive agent was stopped for idleness; the next message resumes it.
+    Suspended,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -337,6 +435,47 @@ pub struct AgentPane {
     pub status: AgentPaneStatus,
     pub updated_at: DateTime<Utc>,
     pub lines: VecDeque<String>,
+    /// Lines scrolled back from the bottom of the pane timeline (including
+    /// previous chats shown above it).
+    #[serde(default)]
+    pub scroll_back: usize,
+    /// Whether the view sticks to the live tail as new output arrives.
+    #[serde(default = "default_follow")]
+    pub follow: bool,
+    #[serde(default)]
+    pub search: Option<PaneSearch>,
+    /// Bytes held in `lines`.
+    #[serde(default)]
+    pub line_bytes: usize,
+    /// Oldest lines are evicted from `lines` once `line_bytes` exceeds this.
+    #[serde(default = "default_pane_buffer_bytes")]
+    pub max_bytes: usize,
+    /// Log on disk that ends with `lines`; evicted output is read back from
+    /// it when scrolling past the in-memory window.
+    #[serde(default)]
+    pub history_log: Option<PathBuf>,
+    /// Lines before `lines` that are only in `history_log`.
+    #[serde(default)]
+    pub spilled_lines: usize,
+    /// Latest `CONFIDENCE:` marker the agent printed, in percent.
+    #[serde(default)]
+    pub confidence: Option<u8>,
+}
+
+/// Active search in a focused pane.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaneSearch {
+    pub query: String,
+    /// Selected match, as its distance from the bottom of the timeline.
+    pub current: Option<usize>,
+}
+
+fn default_follow() -> bool {
+    true
+}
+
+fn default_pane_buffer_bytes() -> usize {
+    DEFAULT_PANE_BUFFER_BYTES
 }
 
 impl AgentPane {
@@ -348,6 +487,14 @@ impl AgentPane {
             status: AgentPaneStatus::Starting,
             updated_at: Utc::now(),
             lines: VecDeque::new(),
+            scroll_back: 0,
+            follow: true,
+            search: None,
+            line_bytes: 0,
+            max_bytes: DEFAULT_PANE_BUFFER_BYTES,
+            history_log: None,
+            spilled_lines: 0,
+            confidence: None,
         }
     }
 
@@ -356,11 +503,44 @@ impl AgentPane {
         let Some(line) = normalize_pane_line(&raw) else {
             return;
         };
+        if let Some(confidence) = orch_agents::parse_confidence_marker(&line) {
+            self.confidence = Some(confidence);
+        }
+        self.line_bytes += line.len();
         self.lines.push_back(line);
         self.updated_at = Utc::now();
-        while self.lines.len() > 400 {
-            self.lines.pop_front();
+        while self.line_bytes > self.max_bytes {
+            let Some(evicted) = self.lines.pop_front() else {
+                break;
+            };
+            self.line_bytes = self.line_bytes.saturating_sub(evicted.len());
+            if self.history_log.is_some() {
+                self.spilled_lines += 1;
+            }
         }
+        // Keep a scrolled-back view anchored on the same output.
+        if !self.follow {
+            self.scroll_back += 1;
+        }
+        if let Some(current) = self.search.as_mut().and_then(|search| search.current.as_mut()) {
+            *current += 1;
+        }
+    }
+
+    pub fn scroll_up(&mut self, amount: usize, max: usize) {
+        self.scroll_back = (self.scroll_back + amount).min(max);
+        self.follow = self.scroll_back == 0;
+    }
+
+    /// Scrolling back down to the live tail re-engages follow mode.
+    pub fn scroll_down(&mut self, amount: usize) {
+        self.scroll_back = self.scroll_back.saturating_sub(amount);
+        self.follow = self.scroll_back == 0;
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll_back = 0;
+        self.follow = true;
     }
 
     pub fn tail(&self, max_lines: usize) -> Vec<String> {
@@ -383,22 +563,22 @@ impl AgentPane {
             .cloned()
             .collect()
     }
-}
 
-fn window_over_lines(lines: &[String], max_lines: usize, scroll_back: usize) -> Vec<String> {
-    let len = lines.len();
-    let max_back = len.saturating_sub(max_lines.min(len));
-    let clamped = scroll_back.min(max_back);
-    let end = len - clamped;
-    let start = end.saturating_sub(max_lines);
-    lines
-        .iter()
-        .skip(start)
-        .take(end - start)
-        .cloned()
-        .collect()
+    /// Spilled lines `start..end`, counted from the oldest one, read back
+    /// from `history_log`.
+    pub fn read_spilled(&self, start: usize, end: usize) -> Vec<String> {
+        let Some(path) = &self.history_log else {
+            return Vec::new();
+        };
+        let end = end.min(self.spilled_lines);
+        let skip_from_end = self.lines.len() + (self.spilled_lines - end);
+        read_log_lines(path, skip_from_end, end.saturating_sub(start))
+    }
 }
 
+/// Lines kept below a search match when jumping to it.
+const SEARCH_CONTEXT_LINES: usize = 3;
+
 /// Normalize raw pane output into stable display text.
 pub fn normalize_pane_line(raw: &str) -> Option<String> {
     let trimmed = raw.trim_end_matches(['\n', '\r']);
@@ -421,6 +601,9 @@ pub fn normalize_pane_line(raw: &str) -> Option<String> {
 }
 
 fn strip_terminal_sequences(input: &str) -> String {
+    if !input.contains('\u{1b}') {
+        return input.to_string();
+    }
     let bytes = input.as_bytes();
     let mut out = String::with_capacity(input.len());
     let mut idx = 0usize;
@@ -545,6 +728,136 @@ impl StatusBarData {
     }
 }
 
+/// Fields of the create-task form, in Tab order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CreateTaskField {
+    Title,
+    Model,
+    Priority,
+    Repo,
+    Stack,
+}
+
+impl CreateTaskField {
+    pub const ALL: [CreateTaskField; 5] = [
+        CreateTaskField::Title,
+        CreateTaskField::Model,
+        CreateTaskField::Priority,
+        CreateTaskField::Repo,
+        CreateTaskField::Stack,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CreateTaskField::Title => "Title",
+            CreateTaskField::Model => "Model",
+            CreateTaskField::Priority => "Priority",
+            CreateTaskField::Repo => "Repo",
+            CreateTaskField::Stack => "Stack",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let idx = Self::ALL.iter().position(|field| *field == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    pub fn previous(self) -> Self {
+        let idx = Self::ALL.iter().position(|field| *field == self).unwrap_or(0);
+        Self::ALL[(idx + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+pub const CREATE_TASK_MODELS: &[ModelKind] = ModelKind::all();
+pub const CREATE_TASK_PRIORITIES: [TaskPriority; 4] = [
+    TaskPriority::Low,
+    TaskPriority::Normal,
+    TaskPriority::High,
+    TaskPriority::Critical,
+];
+
+/// Values entered in the create-task form. Kept on the dashboard until the
+/// task is created so a failed submit can be retried without retyping.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreateTaskForm {
+    pub active_field: CreateTaskField,
+    pub title: String,
+    pub model: ModelKind,
+    pub priority: TaskPriority,
+    /// Distinct repos from the task list; never empty.
+    pub repos: Vec<RepoId>,
+    pub repo_idx: usize,
+    /// Stack on an open task instead of branching from the current branch.
+    pub stack: bool,
+    /// Task selected when the form opened, preferred as the stack parent.
+    pub stack_on: Option<TaskId>,
+}
+
+impl CreateTaskForm {
+    /// Empty form over `tasks`, defaulting the repo to the selected task's.
+    pub fn new(tasks: &[TaskOverviewRow], selected: Option<&TaskOverviewRow>) -> Self {
+        let mut repos: Vec<RepoId> = Vec::new();
+        for task in tasks {
+            if !repos.contains(&task.repo_id) {
+                repos.push(task.repo_id.clone());
+            }
+        }
+        if repos.is_empty() {
+            repos.push(RepoId("default".to_string()));
+        }
+        repos.sort_by(|a, b| a.0.cmp(&b.0));
+        let repo_idx = selected
+            .and_then(|task| repos.iter().position(|repo| *repo == task.repo_id))
+            .unwrap_or(0);
+        Self {
+            active_field: CreateTaskField::Title,
+            title: String::new(),
+            model: ModelKind::Claude,
+            priority: TaskPriority::Normal,
+            repos,
+            repo_idx,
+            stack: false,
+            stack_on: selected.map(|task| task.task_id.clone()),
+        }
+    }
+
+    pub fn repo(&self) -> &RepoId {
+        &self.repos[self.repo_idx.min(self.repos.len() - 1)]
+    }
+
+    /// Step the active picker forward or back; the title is not a picker.
+    pub fn cycle(&mut self, forward: bool) {
+        match self.active_field {
+            CreateTaskField::Title => {}
+            CreateTaskField::Model => {
+                self.model = cycle_value(CREATE_TASK_MODELS, self.model, forward);
+            }
+            CreateTaskField::Priority => {
+                self.priority = cycle_value(&CREATE_TASK_PRIORITIES, self.priority, forward);
+            }
+            CreateTaskField::Repo => {
+                let len = self.repos.len();
+                self.repo_idx = if forward {
+                    (self.repo_idx + 1) % len
+                } else {
+                    (self.repo_idx + len - 1) % len
+                };
+            }
+            CreateTaskField::Stack => self.stack = !self.stack,
+        }
+    }
+}
+
+fn cycle_value<T: Copy + PartialEq>(values: &[T], current: T, forward: bool) -> T {
+    let idx = values.iter().position(|value| *value == current).unwrap_or(0);
+    let next = if forward {
+        (idx + 1) % values.len()
+    } else {
+        (idx + values.len() - 1) % values.len()
+    };
+    values[next]
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DashboardState {
     pub tasks: Vec<TaskOverviewRow>,
@@ -578,14 +891,32 @@ pub struct DashboardState {
     pub status_line: String,
     #[serde(default)]
     pub status_bar: StatusBarData,
-    /// Lines scrolled back from the bottom in focused views.
-    pub scroll_back: usize,
     /// Which pane category (Agent / QA) is currently selected.
     pub selected_pane_category: PaneCategory,
     #[serde(skip)]
     pub current_theme: TuiTheme,
     #[serde(default)]
     pub theme_index: usize,
+    #[serde(default)]
+    pub open_questions: Vec<OpenQuestionDisplay>,
+    /// Cached detail for the task detail view.
+    #[serde(default)]
+    pub task_detail: Option<TaskDetail>,
+    /// Stack view replaces the task list with the parent/child tree.
+    #[serde(default)]
+    pub show_stack: bool,
+    /// Selected stack node, kept by id so it survives task refreshes.
+    #[serde(default)]
+    pub stack_selected: Option<TaskId>,
+    /// Create-task form values, kept while a submit is pending or failed.
+    #[serde(default)]
+    pub create_task_form: Option<CreateTaskForm>,
+    /// Where agent panes' chat logs live; panes spill older output there.
+    #[serde(default)]
+    pub chat_log_dir: Option<PathBuf>,
+    /// In-memory output cap for new panes, in bytes.
+    #[serde(default = "default_pane_buffer_bytes")]
+    pub pane_buffer_bytes: usize,
 }
 
 impl Default for DashboardState {
@@ -612,10 +943,16 @@ impl Default for DashboardState {
             focused_task: false,
             status_line: "ready".to_string(),
             status_bar: StatusBarData::default(),
-            scroll_back: 0,
             selected_pane_category: PaneCategory::Agent,
             current_theme: default_theme(),
             theme_index: 0,
+            open_questions: Vec::new(),
+            task_detail: None,
+            show_stack: false,
+            stack_selected: None,
+            create_task_form: None,
+            chat_log_dir: None,
+            pane_buffer_bytes: DEFAULT_PANE_BUFFER_BYTES,
         }
     }
 }
@@ -629,6 +966,13 @@ impl DashboardState {
         }
     }
 
+    pub fn open_questions_for(&self, task_id: &TaskId) -> Vec<&OpenQuestionDisplay> {
+        self.open_questions
+            .iter()
+            .filter(|question| question.task_id == *task_id)
+            .collect()
+    }
+
     pub fn cycle_theme(&mut self) -> &'static str {
         self.theme_index = (self.theme_index + 1) % THEME_COUNT;
         self.current_theme = theme_for_index(self.theme_index);
@@ -680,6 +1024,30 @@ impl DashboardState {
         self.tasks.get(self.selected_task_idx)
     }
 
+    /// Cached detail for the task shown in the task detail view.
+    pub fn focused_task_detail(&self) -> Option<&TaskDetail> {
+        if !self.focused_task {
+            return None;
+        }
+        let task_id = &self.selected_task()?.task_id;
+        self.task_detail
+            .as_ref()
+            .filter(|detail| detail.task_id == *task_id)
+    }
+
+    /// Task whose detail the tick hook should load: the task in the detail
+    /// view when nothing is cached for it or the cache has gone stale.
+    pub fn task_detail_due(&self, now: DateTime<Utc>) -> Option<TaskId> {
+        if !self.focused_task {
+            return None;
+        }
+        let task_id = &self.selected_task()?.task_id;
+        let fresh = self.focused_task_detail().is_some_and(|detail| {
+            now - detail.loaded_at < chrono::Duration::seconds(TASK_DETAIL_STALE_SECS)
+        });
+        (!fresh).then(|| task_id.clone())
+    }
+
     pub fn selected_session(&self) -> Option<&SessionDisplay> {
         self.sessions.get(self.session_list_index)
     }
@@ -927,9 +1295,32 @@ impl DashboardState {
             return current_pane.window(max_lines, 0);
         }
 
-        let mut timeline = self.pane_history_prefix_lines(pane_idx);
-        timeline.extend(current_pane.lines.iter().cloned());
-        window_over_lines(&timeline, max_lines, scroll_back)
+        let len = self.pane_line_count_with_history(pane_idx);
+        let max_back = len.saturating_sub(max_lines.min(len));
+        let end = len - scroll_back.min(max_back);
+        let start = end.saturating_sub(max_lines);
+        self.pane_timeline_range(pane_idx, start, end)
+    }
+
+    /// Lines `start..end` of what scrolling shows: previous chats, then the
+    /// pane's output spilled to disk, then the lines still in memory.
+    fn pane_timeline_range(&self, pane_idx: usize, start: usize, end: usize) -> Vec<String> {
+        let Some(pane) = self.panes.get(pane_idx) else {
+            return Vec::new();
+        };
+        let prefix = self.pane_history_prefix_lines(pane_idx);
+        let spilled_end = prefix.len() + pane.spilled_lines;
+        let segment = |lo: usize, hi: usize| (start.clamp(lo, hi) - lo, end.clamp(lo, hi) - lo);
+
+        let (from, to) = segment(0, prefix.len());
+        let mut lines = prefix[from..to].to_vec();
+        let (from, to) = segment(prefix.len(), spilled_end);
+        if from < to {
+            lines.extend(pane.read_spilled(from, to));
+        }
+        let (from, to) = segment(spilled_end, spilled_end + pane.lines.len());
+        lines.extend(pane.lines.range(from..to).cloned());
+        lines
     }
 
     fn pane_history_prefix_lines(&self, pane_idx: usize) -> Vec<String> {
@@ -961,39 +1352,180 @@ impl DashboardState {
             .filter(|pane| !pane.lines.is_empty())
             .map(|pane| pane.lines.len() + 2)
             .sum::<usize>();
-        history_lines + current_pane.lines.len()
+        history_lines + current_pane.spilled_lines + current_pane.lines.len()
     }
 
     fn focused_pane_index(&self) -> Option<usize> {
         if self.focused_task {
-            self.selected_task()
-                .and_then(|task| self.panes.iter().position(|p| p.task_id == task.task_id))
+            let task = self.selected_task()?;
+            self.pane_index_for_task_category(&task.task_id, self.selected_pane_category)
+                .or_else(|| self.panes.iter().position(|p| p.task_id == task.task_id))
         } else {
             self.focused_pane_idx
         }
     }
 
+    fn focused_pane_mut(&mut self) -> Option<&mut AgentPane> {
+        let idx = self.focused_pane_index()?;
+        self.panes.get_mut(idx)
+    }
+
     fn focused_pane_line_count(&self) -> usize {
         self.focused_pane_index()
             .map(|idx| self.pane_line_count_with_history(idx))
             .unwrap_or(0)
     }
 
+    /// Lines scrolled back from the bottom in the focused view.
+    pub fn scroll_back(&self) -> usize {
+        self.focused_pane_index()
+            .and_then(|idx| self.panes.get(idx))
+            .map(|pane| pane.scroll_back)
+            .unwrap_or(0)
+    }
+
     pub fn scroll_up(&mut self, amount: usize) {
         let max = self.focused_pane_line_count();
-        self.scroll_back = (self.scroll_back + amount).min(max);
+        if let Some(pane) = self.focused_pane_mut() {
+            pane.scroll_up(amount, max);
+        }
     }
 
     pub fn scroll_down(&mut self, amount: usize) {
-        self.scroll_back = self.scroll_back.saturating_sub(amount);
+        if let Some(pane) = self.focused_pane_mut() {
+            pane.scroll_down(amount);
+        }
     }
 
     pub fn scroll_to_top(&mut self) {
-        self.scroll_back = self.focused_pane_line_count();
+        let max = self.focused_pane_line_count();
+        if let Some(pane) = self.focused_pane_mut() {
+            pane.scroll_up(max, max);
+        }
     }
 
     pub fn scroll_to_bottom(&mut self) {
-        self.scroll_back = 0;
+        if let Some(pane) = self.focused_pane_mut() {
+            pane.scroll_to_bottom();
+        }
+    }
+
+    /// Return every pane to its live tail and drop any pane search.
+    pub fn reset_scroll(&mut self) {
+        for pane in &mut self.panes {
+            pane.scroll_to_bottom();
+            pane.search = None;
+        }
+    }
+
+    /// Start (or refine) a search in the focused pane and jump to the nearest
+    /// match at or above the current view. An empty query clears the search.
+    pub fn search_focused_pane(&mut self, query: &str) {
+        let Some(pane) = self.focused_pane_mut() else {
+            return;
+        };
+        if query.is_empty() {
+            pane.search = None;
+            return;
+        }
+        pane.search = Some(PaneSearch {
+            query: query.to_string(),
+            current: None,
+        });
+        self.jump_to_match(true);
+    }
+
+    pub fn clear_pane_search(&mut self) {
+        if let Some(pane) = self.focused_pane_mut() {
+            pane.search = None;
+        }
+    }
+
+    /// Move the focused pane's search to the next older (`older == true`) or
+    /// newer match, wrapping around. Returns false when nothing matches.
+    pub fn jump_to_match(&mut self, older: bool) -> bool {
+        let Some(idx) = self.focused_pane_index() else {
+            return false;
+        };
+        let Some(search) = self.panes[idx].search.clone() else {
+            return false;
+        };
+        let matches = self.pane_match_distances(idx, &search.query);
+        let pane = &mut self.panes[idx];
+        let anchor = search.current.unwrap_or(pane.scroll_back);
+        let target = match (older, search.current) {
+            (true, None) => matches.iter().find(|d| **d >= anchor),
+            (true, Some(_)) => matches.iter().find(|d| **d > anchor),
+            (false, None) => matches.iter().rev().find(|d| **d <= anchor),
+            (false, Some(_)) => matches.iter().rev().find(|d| **d < anchor),
+        }
+        .or(if older { matches.first() } else { matches.last() });
+        let Some(&distance) = target else {
+            if let Some(search) = pane.search.as_mut() {
+                search.current = None;
+            }
+            return false;
+        };
+
+        if let Some(search) = pane.search.as_mut() {
+            search.current = Some(distance);
+        }
+        pane.scroll_back = distance.saturating_sub(SEARCH_CONTEXT_LINES);
+        pane.follow = pane.scroll_back == 0;
+        true
+    }
+
+    /// Distances from the bottom of every line matching `query`, nearest
+    /// first. Output spilled to disk is skipped.
+    fn pane_match_distances(&self, pane_idx: usize, query: &str) -> Vec<usize> {
+        let needle = query.to_lowercase();
+        let Some(pane) = self.panes.get(pane_idx) else {
+            return Vec::new();
+        };
+        let prefix = self.pane_history_prefix_lines(pane_idx);
+        let prefix_offset = pane.lines.len() + pane.spilled_lines;
+        pane.lines
+            .iter()
+            .rev()
+            .enumerate()
+            .chain(
+                prefix
+                    .iter()
+                    .rev()
+                    .enumerate()
+                    .map(|(distance, line)| (distance + prefix_offset, line)),
+            )
+            .filter(|(_, line)| line.to_lowercase().contains(&needle))
+            .map(|(distance, _)| distance)
+            .collect()
+    }
+
+    /// Search status of the focused pane: query, selected match (1-based,
+    /// newest first) and total matches.
+    pub fn focused_search_status(&self) -> Option<(String, Option<usize>, usize)> {
+        let idx = self.focused_pane_index()?;
+        let search = self.panes.get(idx)?.search.as_ref()?;
+        let matches = self.pane_match_distances(idx, &search.query);
+        let position = search
+            .current
+            .and_then(|current| matches.iter().position(|d| *d == current))
+            .map(|pos| pos + 1);
+        Some((search.query.clone(), position, matches.len()))
+    }
+
+    /// Text for copying out of the focused pane: the selected match line when
+    /// searching, otherwise the `visible_lines` currently on screen.
+    pub fn focused_copy_text(&self, visible_lines: usize) -> Option<String> {
+        let idx = self.focused_pane_index()?;
+        let pane = self.panes.get(idx)?;
+        if let Some(current) = pane.search.as_ref().and_then(|search| search.current) {
+            let line = self
+                .pane_line_count_with_history(idx)
+                .checked_sub(current + 1)?;
+            return self.pane_timeline_range(idx, line, line + 1).pop();
+        }
+        let window = self.pane_window_with_history(idx, visible_lines, pane.scroll_back);
+        (!window.is_empty()).then(|| window.join("\n"))
     }
 }
 
@@ -1538,6 +2070,16 @@ mod tests {
         assert_eq!(tail, vec!["line 1".to_string(), "line 2".to_string()]);
     }
 
+    #[test]
+    fn agent_pane_tracks_latest_confidence_marker() {
+        let mut pane = AgentPane::new("A1", TaskId("T1".to_string()), ModelKind::Claude);
+        pane.append_line("CONFIDENCE: 0.4");
+        pane.append_line("reworked the parser");
+        pane.append_line("**Confidence:** 85%");
+        pane.append_line("CONFIDENCE: very");
+        assert_eq!(pane.confidence, Some(85));
+    }
+
     #[test]
     fn normalize_pane_line_strips_ansi() {
         let value = normalize_pane_line("[stderr] \u{1b}[31mapply failed\u{1b}[0m")
@@ -1599,6 +2141,50 @@ mod tests {
         assert!(window.iter().any(|line| line == "new 1"));
     }
 
+    #[test]
+    fn pane_window_with_history_reads_spilled_output_from_disk() {
+        let dir = std::env::temp_dir().join(format!(
+            "othala-pane-spill-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        let log = dir.join("T1.log");
+        let lines: Vec<String> = (0..10).map(|i| format!("out {i}")).collect();
+        std::fs::write(&log, format!("{}\n", lines.join("\n"))).expect("write log");
+
+        let mut pane = AgentPane::new("agent-T1", TaskId("T1".to_string()), ModelKind::Claude);
+        pane.max_bytes = 14;
+        pane.history_log = Some(log);
+        for line in &lines {
+            pane.append_line(line.as_str());
+        }
+        assert_eq!(pane.lines, ["out 8", "out 9"]);
+        assert_eq!(pane.spilled_lines, 8);
+
+        let state = DashboardState {
+            panes: vec![
+                pane_with_lines("A0", "T0", ModelKind::Codex, &["earlier"]),
+                pane,
+            ],
+            ..DashboardState::default()
+        };
+        assert_eq!(
+            state.pane_window_with_history(1, 3, 2),
+            vec!["out 5", "out 6", "out 7"]
+        );
+        assert_eq!(
+            state.pane_window_with_history(1, 4, 100),
+            vec![
+                "--- previous chat A0 (Codex, task=T0) ---",
+                "earlier",
+                "",
+                "out 0"
+            ]
+        );
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
     #[test]
     fn focused_scroll_budget_includes_previous_chat_history() {
         let mut state = DashboardState {
@@ -1615,7 +2201,72 @@ mod tests {
         };
 
         state.scroll_up(50);
-        assert!(state.scroll_back > state.panes[1].lines.len());
+        assert!(state.scroll_back() > state.panes[1].lines.len());
+    }
+
+    #[test]
+    fn scrolled_pane_stays_anchored_and_follow_reengages_at_bottom() {
+        let lines: Vec<String> = (0..30).map(|i| format!("line {i}")).collect();
+        let refs: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let mut state = DashboardState {
+            panes: vec![pane_with_lines("A1", "T1", ModelKind::Claude, &refs)],
+            focused_pane_idx: Some(0),
+            ..DashboardState::default()
+        };
+
+        state.scroll_up(5);
+        assert!(!state.panes[0].follow);
+        let before = state.pane_window_with_history(0, 3, state.scroll_back());
+        state.panes[0].append_line("line 30");
+        state.panes[0].append_line("line 31");
+        assert_eq!(state.scroll_back(), 7);
+        assert_eq!(state.pane_window_with_history(0, 3, state.scroll_back()), before);
+
+        state.scroll_down(7);
+        assert!(state.panes[0].follow);
+        state.panes[0].append_line("line 32");
+        assert_eq!(state.scroll_back(), 0);
+    }
+
+    #[test]
+    fn pane_search_jumps_between_matches_and_wraps() {
+        let mut state = DashboardState {
+            panes: vec![pane_with_lines(
+                "A1",
+                "T1",
+                ModelKind::Claude,
+                &["error one", "ok", "ok", "ok", "ok", "ERROR two", "ok", "ok", "ok", "ok", "ok"],
+            )],
+            focused_pane_idx: Some(0),
+            ..DashboardState::default()
+        };
+
+        state.search_focused_pane("error");
+        assert_eq!(
+            state.focused_search_status(),
+            Some(("error".to_string(), Some(1), 2))
+        );
+        assert_eq!(state.focused_copy_text(5).as_deref(), Some("ERROR two"));
+        assert_eq!(state.scroll_back(), 2);
+
+        assert!(state.jump_to_match(true));
+        assert_eq!(state.focused_copy_text(5).as_deref(), Some("error one"));
+        assert_eq!(state.scroll_back(), 7);
+
+        // Wraps back around to the newest match.
+        assert!(state.jump_to_match(true));
+        assert_eq!(state.focused_search_status().unwrap().1, Some(1));
+        assert!(state.jump_to_match(false));
+        assert_eq!(state.focused_search_status().unwrap().1, Some(2));
+
+        state.search_focused_pane("missing");
+        assert_eq!(
+            state.focused_search_status(),
+            Some(("missing".to_string(), None, 0))
+        );
+        state.search_focused_pane("");
+        assert_eq!(state.focused_search_status(), None);
+        assert_eq!(state.focused_copy_text(2).as_deref(), Some("ok\nok"));
     }
 
     #[test]
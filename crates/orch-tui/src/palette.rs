@@ -0,0 +1,534 @@
+//! Declarative registry of everything the TUI can do.
+//!
+//! The Ctrl-P command palette and the `?` help overlay are both generated
+//! from [`PALETTE_ENTRIES`], so adding an entry here is enough for a new
+//! action to become searchable and documented.
+
+use crate::action::{UiAction, UiCommand};
+use crate::model::DashboardState;
+
+/// What has to be true for an entry to make sense.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PaletteContext {
+    /// Always available.
+    Global,
+    /// Needs a selected task; the command runs against it.
+    Task,
+    /// Needs a focused pane or task detail view.
+    FocusedView,
+}
+
+impl PaletteContext {
+    pub const ALL: [PaletteContext; 3] = [
+        PaletteContext::Global,
+        PaletteContext::Task,
+        PaletteContext::FocusedView,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PaletteContext::Global => "General",
+            PaletteContext::Task => "Selected task",
+            PaletteContext::FocusedView => "Focused view",
+        }
+    }
+
+    pub fn is_available(self, scope: PaletteScope) -> bool {
+        match self {
+            PaletteContext::Global => true,
+            PaletteContext::Task => scope.task_selected,
+            PaletteContext::FocusedView => scope.focused_view,
+        }
+    }
+}
+
+/// The parts of the dashboard state that decide which entries apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PaletteScope {
+    pub task_selected: bool,
+    pub focused_view: bool,
+}
+
+impl PaletteScope {
+    pub fn from_state(state: &DashboardState) -> Self {
+        Self {
+            task_selected: state.selected_task().is_some(),
+            focused_view: state.focused_task || state.focused_pane_idx.is_some(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaletteEntry {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Key binding shown in the help overlay; empty when palette-only.
+    pub keys: &'static str,
+    pub context: PaletteContext,
+    /// Command run through `TuiApp::run_command` when the entry is chosen.
+    pub handler: UiCommand,
+}
+
+const fn entry(
+    name: &'static str,
+    description: &'static str,
+    keys: &'static str,
+    context: PaletteContext,
+    handler: UiCommand,
+) -> PaletteEntry {
+    PaletteEntry {
+        name,
+        description,
+        keys,
+        context,
+        handler,
+    }
+}
+
+const fn dispatch(action: UiAction) -> UiCommand {
+    UiCommand::Dispatch(action)
+}
+
+use PaletteContext::{FocusedView, Global, Task};
+
+pub const PALETTE_ENTRIES: &[PaletteEntry] = &[
+    entry(
+        "New chat",
+        "Start a task from a prompt",
+        "c",
+        Global,
+        dispatch(UiAction::CreateTask),
+    ),
+    entry(
+        "New task",
+        "Create a task with repo, title and model",
+        "N",
+        Global,
+        UiCommand::OpenNewTaskDialog,
+    ),
+    entry(
+        "Filter tasks",
+        "Filter the task list by text",
+        "/",
+        Global,
+        UiCommand::StartFilter,
+    ),
+    entry(
+        "Cycle state filter",
+        "Show only tasks in the next state",
+        "F",
+        Global,
+        UiCommand::CycleStateFilter,
+    ),
+    entry(
+        "Cycle sort",
+        "Change sort mode and toggle the session list",
+        "S",
+        Global,
+        UiCommand::CycleSortMode,
+    ),
+    entry(
+        "Reverse sort",
+        "Flip the sort direction",
+        "R",
+        Global,
+        UiCommand::ReverseSort,
+    ),
+    entry(
+        "Toggle timeline",
+        "Show or hide the event timeline",
+        "t",
+        Global,
+        UiCommand::ToggleTimeline,
+    ),
+    entry(
+        "Stack view",
+        "Show the stacked-branch tree; r=restack s=submit stack",
+        "b",
+        Global,
+        UiCommand::ToggleStackView,
+    ),
+    entry(
+        "Next task",
+        "Select the next task",
+        "↓",
+        Global,
+        UiCommand::SelectNextTask,
+    ),
+    entry(
+        "Previous task",
+        "Select the previous task",
+        "↑",
+        Global,
+        UiCommand::SelectPreviousTask,
+    ),
+    entry(
+        "Next pane",
+        "Select the next agent pane",
+        "→",
+        Global,
+        UiCommand::SelectNextPane,
+    ),
+    entry(
+        "Previous pane",
+        "Select the previous agent pane",
+        "←",
+        Global,
+        UiCommand::SelectPreviousPane,
+    ),
+    entry(
+        "Focus pane",
+        "Focus or unfocus the selected pane",
+        "Tab",
+        Global,
+        UiCommand::ToggleFocusedPane,
+    ),
+    entry(
+        "Command palette",
+        "Search and run any action",
+        "Ctrl+P",
+        Global,
+        UiCommand::OpenCommandPalette,
+    ),
+    entry(
+        "Help",
+        "List every key binding",
+        "?",
+        Global,
+        UiCommand::ShowHelp,
+    ),
+    entry("Quit", "Exit the dashboard", "Esc", Global, UiCommand::Quit),
+    entry(
+        "Task detail",
+        "Show fields, events, runs and diff stat for the task",
+        "Enter",
+        Task,
+        UiCommand::OpenTaskDetail,
+    ),
+    entry(
+        "Approve task",
+        "Mark the task ready for submit",
+        "a",
+        Task,
+        dispatch(UiAction::ApproveTask),
+    ),
+    entry(
+        "Submit task",
+        "Run the submit pipeline for a ready task",
+        "g",
+        Task,
+        dispatch(UiAction::SubmitTask),
+    ),
+    entry(
+        "Start agent",
+        "Spawn an agent for the task",
+        "s",
+        Task,
+        dispatch(UiAction::StartAgent),
+    ),
+    entry(
+        "Stop agent",
+        "Stop the task's agent",
+        "x",
+        Task,
+        dispatch(UiAction::StopAgent),
+    ),
+    entry(
+        "Restart agent",
+        "Stop and respawn the task's agent",
+        "r",
+        Task,
+        dispatch(UiAction::RestartAgent),
+    ),
+    entry(
+        "Pause task",
+        "Stop the agent and keep it from auto-restarting",
+        "p",
+        Task,
+        dispatch(UiAction::PauseTask),
+    ),
+    entry(
+        "Resume task",
+        "Restart the agent of a paused task",
+        "u",
+        Task,
+        dispatch(UiAction::ResumeTask),
+    ),
+    entry(
+        "Chat",
+        "Send a message to the task's agent",
+        "i",
+        Task,
+        dispatch(UiAction::SendChatMessage),
+    ),
+    entry(
+        "Pin file",
+        "Ask the agent to keep a file in context",
+        "",
+        Task,
+        UiCommand::PinFile,
+    ),
+    entry(
+        "Open diff",
+        "Show the task's diff against trunk",
+        "",
+        Task,
+        dispatch(UiAction::OpenDiff),
+    ),
+    entry(
+        "Delete task",
+        "Delete the task after confirmation",
+        "d",
+        Task,
+        dispatch(UiAction::DeleteTask),
+    ),
+    entry(
+        "Verify quick",
+        "Run quick verification",
+        "q",
+        Task,
+        dispatch(UiAction::RunVerifyQuick),
+    ),
+    entry(
+        "Verify full",
+        "Run full verification",
+        "f",
+        Task,
+        dispatch(UiAction::RunVerifyFull),
+    ),
+    entry(
+        "Restack",
+        "Restack the task's branch",
+        "",
+        Task,
+        dispatch(UiAction::TriggerRestack),
+    ),
+    entry(
+        "Mark needs human",
+        "Flag the task for human attention",
+        "n",
+        Task,
+        dispatch(UiAction::MarkNeedsHuman),
+    ),
+    entry(
+        "Open in web UI",
+        "Open the task in the web dashboard",
+        "w",
+        Task,
+        dispatch(UiAction::OpenWebUiForTask),
+    ),
+    entry(
+        "Copy",
+        "Copy the visible lines or current match",
+        "y",
+        FocusedView,
+        UiCommand::CopyPane,
+    ),
+    entry(
+        "Agent log",
+        "Open the task's full agent log",
+        "l",
+        FocusedView,
+        UiCommand::OpenLogView,
+    ),
+    entry(
+        "Search pane",
+        "Search the focused pane",
+        "/",
+        FocusedView,
+        UiCommand::StartPaneSearch,
+    ),
+    entry(
+        "Older match",
+        "Jump to the previous search match",
+        "n",
+        FocusedView,
+        UiCommand::NextOlderMatch,
+    ),
+    entry(
+        "Newer match",
+        "Jump to the next search match",
+        "N",
+        FocusedView,
+        UiCommand::NextNewerMatch,
+    ),
+    entry(
+        "Page up",
+        "Scroll one page back",
+        "PgUp",
+        FocusedView,
+        UiCommand::ScrollPageUp,
+    ),
+    entry(
+        "Page down",
+        "Scroll one page forward",
+        "PgDn",
+        FocusedView,
+        UiCommand::ScrollPageDown,
+    ),
+    entry(
+        "Oldest output",
+        "Scroll to the top",
+        "Home",
+        FocusedView,
+        UiCommand::ScrollToTop,
+    ),
+    entry(
+        "Live tail",
+        "Scroll to the bottom and follow output",
+        "End",
+        FocusedView,
+        UiCommand::ScrollToBottom,
+    ),
+];
+
+/// Entries usable in `scope` that match `query`, best match first. An empty
+/// query keeps registry order.
+pub fn filter_entries(query: &str, scope: PaletteScope) -> Vec<&'static PaletteEntry> {
+    let mut scored: Vec<(i64, usize, &'static PaletteEntry)> = PALETTE_ENTRIES
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| entry.context.is_available(scope))
+        .filter_map(|(index, entry)| entry_score(query, entry).map(|score| (score, index, entry)))
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    scored.into_iter().map(|(_, _, entry)| entry).collect()
+}
+
+/// Name matches always outrank matches found only in the description.
+fn entry_score(query: &str, entry: &PaletteEntry) -> Option<i64> {
+    const DESCRIPTION_PENALTY: i64 = 1_000;
+    fuzzy_score(query, entry.name)
+        .or_else(|| fuzzy_score(query, entry.description).map(|s| s - DESCRIPTION_PENALTY))
+}
+
+/// Case-insensitive subsequence match. Higher is better; consecutive
+/// characters and matches at word starts score extra, gaps cost a point.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let candidate: Vec<char> = candidate.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0i64;
+    let mut next = 0usize;
+    let mut previous: Option<usize> = None;
+    for (index, ch) in candidate.iter().enumerate() {
+        if next == query.len() {
+            break;
+        }
+        if *ch != query[next] {
+            continue;
+        }
+        score += 1;
+        if index == 0 || !candidate[index - 1].is_alphanumeric() {
+            score += 8;
+        }
+        match previous {
+            Some(prev) if prev + 1 == index => score += 5,
+            Some(prev) => score -= (index - prev - 1) as i64,
+            None => score -= index as i64,
+        }
+        previous = Some(index);
+        next += 1;
+    }
+    (next == query.len()).then_some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(entries: &[&PaletteEntry]) -> Vec<&'static str> {
+        entries.iter().map(|entry| entry.name).collect()
+    }
+
+    #[test]
+    fn fuzzy_score_prefers_word_starts_and_runs() {
+        assert_eq!(fuzzy_score("", "Approve task"), Some(0));
+        assert!(fuzzy_score("xyz", "Approve task").is_none());
+        assert!(fuzzy_score("tsak", "Approve task").is_none());
+
+        let initials = fuzzy_score("at", "Approve task").unwrap();
+        let scattered = fuzzy_score("at", "Chat").unwrap();
+        assert!(initials > scattered);
+
+        let prefix = fuzzy_score("sub", "Submit task").unwrap();
+        let inner = fuzzy_score("sub", "Scroll up by one").unwrap();
+        assert!(prefix > inner);
+        assert_eq!(
+            fuzzy_score("OPEN D", "Open diff"),
+            fuzzy_score("opend", "Open diff")
+        );
+    }
+
+    #[test]
+    fn filter_entries_ranks_matches_and_respects_context() {
+        let dashboard = PaletteScope::default();
+        let with_task = PaletteScope {
+            task_selected: true,
+            focused_view: false,
+        };
+        let focused = PaletteScope {
+            task_selected: true,
+            focused_view: true,
+        };
+
+        assert!(filter_entries("approve", dashboard).is_empty());
+        assert_eq!(
+            names(&filter_entries("approve", with_task))[0],
+            "Approve task"
+        );
+        assert_eq!(
+            names(&filter_entries("diff", with_task)),
+            vec!["Open diff", "Task detail"],
+            "description matches rank below name matches"
+        );
+        assert_eq!(names(&filter_entries("pause", with_task))[0], "Pause task");
+
+        assert!(!names(&filter_entries("", with_task)).contains(&"Search pane"));
+        assert_eq!(
+            names(&filter_entries("search pane", focused))[0],
+            "Search pane"
+        );
+
+        let all = filter_entries("", focused);
+        assert_eq!(all.len(), PALETTE_ENTRIES.len());
+        assert_eq!(all[0].name, PALETTE_ENTRIES[0].name);
+    }
+
+    #[test]
+    fn registry_covers_every_ui_action() {
+        let actions = [
+            UiAction::CreateTask,
+            UiAction::ApproveTask,
+            UiAction::SubmitTask,
+            UiAction::StartAgent,
+            UiAction::StopAgent,
+            UiAction::RestartAgent,
+            UiAction::DeleteTask,
+            UiAction::RunVerifyQuick,
+            UiAction::RunVerifyFull,
+            UiAction::TriggerRestack,
+            UiAction::MarkNeedsHuman,
+            UiAction::OpenWebUiForTask,
+            UiAction::PauseTask,
+            UiAction::ResumeTask,
+            UiAction::SendChatMessage,
+            UiAction::OpenDiff,
+        ];
+        for action in actions {
+            assert!(
+                PALETTE_ENTRIES
+                    .iter()
+                    .any(|entry| entry.handler == UiCommand::Dispatch(action)),
+                "{action:?} missing from the palette registry"
+            );
+        }
+    }
+}
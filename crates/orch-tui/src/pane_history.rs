@@ -0,0 +1,182 @@
+//! Pane output kept on disk: the per-task chat log that panes spill to once
+//! their in-memory buffer is full.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use orch_core::types::TaskId;
+
+use crate::model::normalize_pane_line;
+
+/// Bytes read per step when walking a log backwards.
+const LOG_READ_BLOCK: usize = 64 * 1024;
+
+pub fn chat_log_path(base: &Path, task_id: &TaskId) -> PathBuf {
+    base.join(format!("{}.log", task_id.0))
+}
+
+/// The newest lines of the log at `path` that fit in `max_bytes`, plus how
+/// many older lines were left on disk. Lines are normalized like pane output
+/// and the file is streamed rather than loaded whole.
+pub fn load_log_tail(path: &Path, max_bytes: usize) -> (usize, Vec<String>) {
+    let Ok(file) = File::open(path) else {
+        return (0, Vec::new());
+    };
+    let mut older = 0usize;
+    let mut bytes = 0usize;
+    let mut tail = VecDeque::new();
+    for line in BufReader::new(file).lines().map_while(Result::ok) {
+        let Some(line) = normalize_pane_line(&line) else {
+            continue;
+        };
+        bytes += line.len();
+        tail.push_back(line);
+        while bytes > max_bytes {
+            let Some(dropped) = tail.pop_front() else {
+                break;
+            };
+            bytes -= dropped.len();
+            older += 1;
+        }
+    }
+    (older, tail.into())
+}
+
+/// `count` lines ending `skip_from_end` lines before the end of the log at
+/// `path`, oldest first and normalized like pane output.
+///
+/// The file is read backwards, so the cost grows with how far back the lines
+/// are rather than with the size of the log.
+pub fn read_log_lines(path: &Path, skip_from_end: usize, count: usize) -> Vec<String> {
+    let mut newest_first = Vec::new();
+    if count == 0 {
+        return newest_first;
+    }
+    let Ok(mut file) = File::open(path) else {
+        return newest_first;
+    };
+    let Ok(mut pos) = file.seek(SeekFrom::End(0)) else {
+        return newest_first;
+    };
+
+    let mut seen = 0usize;
+    let mut keep = |raw: &mut Vec<u8>, newest_first: &mut Vec<String>| {
+        raw.reverse();
+        if let Some(line) = normalize_pane_line(&String::from_utf8_lossy(raw)) {
+            if seen >= skip_from_end {
+                newest_first.push(line);
+            }
+            seen += 1;
+        }
+        raw.clear();
+        newest_first.len() >= count
+    };
+
+    // Bytes of the line being assembled, in reverse order.
+    let mut raw = Vec::new();
+    let mut block = vec![0u8; LOG_READ_BLOCK];
+    let mut at_file_end = true;
+    while pos > 0 {
+        let len = (pos as usize).min(LOG_READ_BLOCK);
+        pos -= len as u64;
+        if file.seek(SeekFrom::Start(pos)).is_err() || file.read_exact(&mut block[..len]).is_err() {
+            return Vec::new();
+        }
+        for &byte in block[..len].iter().rev() {
+            if byte != b'\n' {
+                raw.push(byte);
+            } else if !at_file_end && keep(&mut raw, &mut newest_first) {
+                newest_first.reverse();
+                return newest_first;
+            }
+            at_file_end = false;
+        }
+    }
+    if !at_file_end {
+        keep(&mut raw, &mut newest_first);
+    }
+    newest_first.reverse();
+    newest_first
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::AgentPane;
+    use chrono::Utc;
+    use orch_core::types::ModelKind;
+    use std::io::{BufWriter, Write};
+
+    fn temp_log(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "othala-pane-history-{name}-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        dir.join("T1.log")
+    }
+
+    #[test]
+    fn read_log_lines_walks_back_from_the_end() {
+        let path = temp_log("read");
+        let lines: Vec<String> = (0..100).map(|i| format!("line {i}")).collect();
+        std::fs::write(&path, format!("{}\n", lines.join("\n"))).expect("write log");
+
+        assert_eq!(read_log_lines(&path, 0, 2), vec!["line 98", "line 99"]);
+        assert_eq!(
+            read_log_lines(&path, 10, 3),
+            vec!["line 87", "line 88", "line 89"]
+        );
+        assert_eq!(read_log_lines(&path, 98, 5), vec!["line 0", "line 1"]);
+        assert!(read_log_lines(&path, 100, 5).is_empty());
+        assert!(read_log_lines(&path.with_extension("missing"), 0, 5).is_empty());
+
+        let (older, tail) = load_log_tail(&path, 14);
+        assert_eq!(older, 98);
+        assert_eq!(tail, vec!["line 98", "line 99"]);
+
+        let _ = std::fs::remove_dir_all(path.parent().expect("temp dir"));
+    }
+
+    #[test]
+    fn pane_stays_under_its_cap_while_the_log_keeps_everything() {
+        const CAP: usize = 4 * 1024 * 1024;
+        const LINE_BYTES: usize = 1024;
+        const TOTAL_LINES: usize = 300 * 1024;
+
+        let path = temp_log("soak");
+        let mut pane = AgentPane::new("agent-T1", TaskId::new("T1"), ModelKind::Claude);
+        pane.max_bytes = CAP;
+        pane.history_log = Some(path.clone());
+
+        let mut log = BufWriter::new(File::create(&path).expect("create log"));
+        let mut peak = 0usize;
+        for i in 0..TOTAL_LINES {
+            let line = format!("{i:0>width$}", width = LINE_BYTES);
+            writeln!(log, "{line}").expect("write log line");
+            pane.append_line(line);
+            peak = peak.max(pane.line_bytes);
+        }
+        log.flush().expect("flush log");
+
+        assert!(peak <= CAP, "pane buffer peaked at {peak} bytes");
+        assert_eq!(pane.spilled_lines + pane.lines.len(), TOTAL_LINES);
+        let log_len = std::fs::metadata(&path).expect("log metadata").len();
+        assert_eq!(log_len, (TOTAL_LINES * (LINE_BYTES + 1)) as u64);
+
+        // The newest spilled line comes back from disk.
+        let newest = pane.read_spilled(pane.spilled_lines - 1, pane.spilled_lines);
+        assert_eq!(
+            newest,
+            vec![format!(
+                "{:0>width$}",
+                pane.spilled_lines - 1,
+                width = LINE_BYTES
+            )]
+        );
+
+        let _ = std::fs::remove_dir_all(path.parent().expect("temp dir"));
+    }
+}
@@ -120,6 +120,7 @@ mod tests {
             CEvent::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE)),
         );
         handle_terminal_event(&mut app, CEvent::Paste("fn main() {}".to_string()));
-        assert_eq!(app.input_prompt(), Some("fn main() {}"));
+        let form = app.create_task_form_display().expect("create form");
+        assert_eq!(form.title, "fn main() {}");
     }
 }
@@ -9,8 +9,12 @@ use orch_core::types::{ModelKind, SessionStatus};
 use crate::app::{InputMode, TuiApp};
 use crate::chat_parse;
 use crate::chat_render;
-use crate::model::{AgentPane, PaneCategory, TaskOverviewRow, TuiTheme};
+use crate::model::{
+    AgentPane, CreateTaskField, CreateTaskForm, OpenQuestionDisplay, PaneCategory,
+    TaskOverviewRow, TuiTheme,
+};
 use crate::output_style::stylize_output_lines;
+use crate::palette::{PaletteContext, PaletteEntry, PALETTE_ENTRIES};
 use crate::ui_activity::pane_activity_indicator;
 #[cfg(test)]
 use crate::ui_activity::status_activity;
@@ -18,25 +22,28 @@ use crate::ui_activity::status_activity;
 use crate::ui_footer::wrapped_visual_line_count;
 use crate::ui_footer::{build_footer_content, footer_height, render_status_bar};
 use crate::ui_format::{
-    divider_line, format_category_tabs, format_task_row, pane_meta_lines, state_color,
-    status_sidebar_lines, to_local_time,
+    divider_line, format_category_tabs, format_task_row, highlight_matches, pane_meta_lines,
+    pane_search_line, stack_node_line, state_color, status_sidebar_lines, task_detail_lines,
+    to_local_time,
 };
 #[cfg(test)]
 use crate::ui_format::{format_pane_tabs, pane_status_tag, status_line_color};
 
-fn model_rate_per_token(model: ModelKind) -> f64 {
+/// Token price for built-in models; custom models have no known rate.
+fn model_rate_per_token(model: ModelKind) -> Option<f64> {
     match model {
-        ModelKind::Claude => 3.0 / 1_000_000.0,
-        ModelKind::Codex => 2.0 / 1_000_000.0,
-        ModelKind::Gemini => 0.5 / 1_000_000.0,
+        ModelKind::Claude => Some(3.0 / 1_000_000.0),
+        ModelKind::Codex => Some(2.0 / 1_000_000.0),
+        ModelKind::Gemini => Some(0.5 / 1_000_000.0),
+        ModelKind::Custom(_) => None,
     }
 }
 
 fn estimate_task_cost_usd(task: &TaskOverviewRow, model: Option<ModelKind>) -> Option<f64> {
     task.estimated_cost_usd.or_else(|| {
         let tokens = task.estimated_tokens?;
-        let model = model?;
-        Some((tokens as f64) * model_rate_per_token(model))
+        let rate = model_rate_per_token(model?)?;
+        Some((tokens as f64) * rate)
     })
 }
 
@@ -277,6 +284,13 @@ fn render_main_content(frame: &mut Frame<'_>, area: Rect, app: &TuiApp) {
         render_focused_task(frame, area, app);
     } else if app.state.focused_pane_idx.is_some() {
         render_focused_pane(frame, area, app);
+    } else if app.state.show_stack {
+        let body = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(62), Constraint::Percentage(38)])
+            .split(area);
+        render_stack_view(frame, body[0], app);
+        render_pane_summary(frame, body[1], app);
     } else {
         let body = Layout::default()
             .direction(Direction::Horizontal)
@@ -319,8 +333,8 @@ fn focused_block<'a>(title: &'a str, theme: &TuiTheme) -> Block<'a> {
 
 pub fn render_dashboard(frame: &mut Frame<'_>, app: &TuiApp) {
     let theme = &app.state.current_theme;
-    if let Some((task_id, log_lines, scroll_offset)) = app.log_view_display() {
-        render_log_view(frame, task_id, log_lines, scroll_offset, theme);
+    if let Some((title, task_id, log_lines, scroll_offset)) = app.log_view_display() {
+        render_log_view(frame, title, task_id, log_lines, scroll_offset, theme);
         return;
     }
 
@@ -376,8 +390,8 @@ pub fn render_dashboard(frame: &mut Frame<'_>, app: &TuiApp) {
     render_error_summary(frame, root[2], app);
     render_footer(frame, root[3], app);
 
-    if let Some((active_field, repo, title, model)) = app.new_task_dialog_display() {
-        render_new_task_dialog_modal(frame, active_field, repo, title, model, theme);
+    if let Some(form) = app.create_task_form_display() {
+        render_create_task_form_modal(frame, form, theme);
     }
 
     if let Some((task_id, branch)) = app.delete_confirm_display() {
@@ -387,10 +401,15 @@ pub fn render_dashboard(frame: &mut Frame<'_>, app: &TuiApp) {
     if matches!(&app.input_mode, InputMode::HelpOverlay) {
         render_help_overlay(frame, theme);
     }
+
+    if let Some((query, entries, selected)) = app.command_palette_display() {
+        render_command_palette(frame, query, &entries, selected, theme);
+    }
 }
 
 fn render_log_view(
     frame: &mut Frame<'_>,
+    title: &str,
     task_id: &str,
     log_lines: &[String],
     scroll_offset: usize,
@@ -417,7 +436,7 @@ fn render_log_view(
 
     let header = Paragraph::new(Line::from(vec![
         Span::styled(
-            format!(" Agent Log: {task_id}"),
+            format!(" {title}: {task_id}"),
             Style::default()
                 .fg(theme.header_fg)
                 .add_modifier(Modifier::BOLD),
@@ -567,7 +586,12 @@ fn render_task_list(frame: &mut Frame<'_>, area: Rect, app: &TuiApp) {
             .map(|pane| pane.model);
         let cost = format_cost_display(estimate_task_cost_usd(task, task_model));
         let state_style = Style::default().fg(state_color(task.state, theme));
-        lines.push(format_task_row(is_selected, task, cost, state_style, theme));
+        let mut row = format_task_row(is_selected, task, cost, state_style, theme);
+        let question_count = app.state.open_questions_for(&task.task_id).len();
+        if question_count > 0 {
+            row.spans.push(question_badge(question_count));
+        }
+        lines.push(row);
     }
 
     if app.state.tasks.is_empty() {
@@ -595,6 +619,70 @@ fn render_task_list(frame: &mut Frame<'_>, area: Rect, app: &TuiApp) {
     frame.render_widget(widget, area);
 }
 
+fn render_stack_view(frame: &mut Frame<'_>, area: Rect, app: &TuiApp) {
+    let theme = &app.state.current_theme;
+    let nodes = app.stack_nodes();
+    let selected = app.state.stack_selected.as_ref();
+    let selected_line = nodes
+        .iter()
+        .position(|(_, task)| Some(&task.task_id) == selected)
+        .unwrap_or(0);
+
+    let mut lines: Vec<Line<'static>> = nodes
+        .iter()
+        .map(|(depth, task)| {
+            stack_node_line(*depth, task, Some(&task.task_id) == selected, theme)
+        })
+        .collect();
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " no tasks",
+            Style::default().fg(theme.dim),
+        )));
+    }
+
+    // Keep the selected node in view on tall stacks.
+    let visible = area.height.saturating_sub(2) as usize;
+    let scroll = (selected_line + 1).saturating_sub(visible) as u16;
+    let widget = Paragraph::new(lines)
+        .block(normal_block("Stack [r=restack s=submit stack Esc=close]", theme))
+        .scroll((scroll, 0));
+    frame.render_widget(widget, area);
+}
+
+fn question_badge(count: usize) -> Span<'static> {
+    Span::styled(
+        format!("  [?{count}]"),
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    )
+}
+
+/// Sidebar section listing the questions a task's agent is waiting on.
+fn open_question_lines(
+    questions: &[&OpenQuestionDisplay],
+    theme: &TuiTheme,
+) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(Span::styled(
+        format!("Waiting on you ({}):", questions.len()),
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    ))];
+    for question in questions {
+        lines.push(Line::from(vec![
+            Span::styled("? ", Style::default().fg(Color::Yellow)),
+            Span::styled(question.question.clone(), Style::default().fg(theme.header_fg)),
+        ]));
+        lines.push(Line::from(Span::styled(
+            format!("  asked {}", to_local_time(question.asked_at)),
+            Style::default().fg(theme.dim),
+        )));
+    }
+    lines
+}
+
 // -- Pane summary -----------------------------------------------------------
 
 fn render_pane_summary(frame: &mut Frame<'_>, area: Rect, app: &TuiApp) {
@@ -695,18 +783,27 @@ fn render_activity_line(
     pane: Option<&AgentPane>,
     theme: &TuiTheme,
 ) {
-    let line = if let Some((activity, color)) = pane.and_then(pane_activity_indicator) {
-        Line::from(vec![
+    let mut spans = if let Some((activity, color)) = pane.and_then(pane_activity_indicator) {
+        vec![
             Span::styled(" \u{25CF} ", Style::default().fg(color)),
             Span::styled(
                 activity,
                 Style::default().fg(color).add_modifier(Modifier::BOLD),
             ),
-        ])
+        ]
     } else {
-        Line::from(Span::styled(" \u{25CB} idle", Style::default().fg(theme.dim)))
+        vec![Span::styled(
+            " \u{25CB} idle",
+            Style::default().fg(theme.dim),
+        )]
     };
-    frame.render_widget(Paragraph::new(line), area);
+    if let Some(confidence) = pane.and_then(|pane| pane.confidence) {
+        spans.push(Span::styled(
+            format!("  confidence {confidence}%"),
+            Style::default().fg(theme.dim),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
 }
 
 // -- Focused views ----------------------------------------------------------
@@ -723,6 +820,37 @@ fn extend_rendered_chat(lines: &mut Vec<Line<'static>>, window: &[String], width
     lines.extend(chat_render::render_chat_blocks(&blocks, width));
 }
 
+/// Meta header plus the scrolled output window of a focused pane, with
+/// search matches highlighted.
+fn focused_pane_lines(
+    app: &TuiApp,
+    pane_idx: Option<usize>,
+    pane: &AgentPane,
+    viewport_height: usize,
+    width: u16,
+) -> Vec<Line<'static>> {
+    let theme = &app.state.current_theme;
+    let mut lines = pane_meta_lines(pane, Some(pane.scroll_back), theme);
+    let search_status = app.state.focused_search_status();
+    if let Some(status) = &search_status {
+        lines.push(pane_search_line(status, theme));
+    }
+    lines.push(divider_line(width, theme));
+    let header_len = lines.len();
+    let output_cap = viewport_height.saturating_sub(header_len);
+    let window = pane_idx
+        .map(|idx| {
+            app.state
+                .pane_window_with_history(idx, output_cap, pane.scroll_back)
+        })
+        .unwrap_or_default();
+    extend_rendered_chat(&mut lines, &window, width);
+    if let Some((query, _, _)) = &search_status {
+        highlight_matches(&mut lines[header_len..], query);
+    }
+    lines
+}
+
 fn render_focused_pane(frame: &mut Frame<'_>, area: Rect, app: &TuiApp) {
     let theme = &app.state.current_theme;
     let chunks = Layout::default()
@@ -741,19 +869,9 @@ fn render_focused_pane(frame: &mut Frame<'_>, area: Rect, app: &TuiApp) {
     let pane = pane_idx.and_then(|idx| app.state.panes.get(idx));
 
     let viewport_height = chat_area.height.saturating_sub(2) as usize;
-    let scroll_back = app.state.scroll_back;
 
     let (title, lines) = if let Some(pane) = pane {
-        let mut lines = pane_meta_lines(pane, Some(scroll_back), theme);
-        lines.push(divider_line(chat_area.width, theme));
-        let output_cap = viewport_height.saturating_sub(lines.len());
-        let window = pane_idx
-            .map(|idx| {
-                app.state
-                    .pane_window_with_history(idx, output_cap, scroll_back)
-            })
-            .unwrap_or_default();
-        extend_rendered_chat(&mut lines, &window, chat_area.width);
+        let lines = focused_pane_lines(app, pane_idx, pane, viewport_height, chat_area.width);
         (format!("Focused Chat {}", pane.instance_id), lines)
     } else {
         (
@@ -813,7 +931,6 @@ fn render_focused_task(frame: &mut Frame<'_>, area: Rect, app: &TuiApp) {
     let input_area = right_chunks[3];
 
     let viewport_height = chat_area.height.saturating_sub(2) as usize;
-    let scroll_back = app.state.scroll_back;
 
     // Left: task status checklist
     let status_title = format!("Status ({task_id_str})");
@@ -862,11 +979,25 @@ fn render_focused_task(frame: &mut Frame<'_>, area: Rect, app: &TuiApp) {
             Span::styled("Updated: ", Style::default().fg(theme.dim)),
             Span::styled(timestamp, Style::default().fg(theme.muted)),
         ]));
+        let questions = app.state.open_questions_for(&task.task_id);
+        if !questions.is_empty() {
+            status_lines.push(Line::from(""));
+            status_lines.extend(open_question_lines(&questions, theme));
+        }
     }
+    let left_rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(cols[0]);
     let status_widget = Paragraph::new(status_lines)
     .block(focused_block(&status_title, theme))
     .wrap(Wrap { trim: false });
-    frame.render_widget(status_widget, cols[0]);
+    frame.render_widget(status_widget, left_rows[0]);
+
+    let detail_widget = Paragraph::new(task_detail_lines(app.state.focused_task_detail(), theme))
+        .block(normal_block("Detail", theme))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(detail_widget, left_rows[1]);
 
     // Right top: category tabs
     let cat_tabs = format_category_tabs(app);
@@ -882,16 +1013,7 @@ fn render_focused_task(frame: &mut Frame<'_>, area: Rect, app: &TuiApp) {
     };
 
     let (pty_title, pty_lines) = if let Some(pane) = task_pane {
-        let mut lines = pane_meta_lines(pane, Some(scroll_back), theme);
-        lines.push(divider_line(chat_area.width, theme));
-        let output_cap = viewport_height.saturating_sub(lines.len());
-        let window = task_pane_idx
-            .map(|idx| {
-                app.state
-                    .pane_window_with_history(idx, output_cap, scroll_back)
-            })
-            .unwrap_or_default();
-        extend_rendered_chat(&mut lines, &window, chat_area.width);
+        let lines = focused_pane_lines(app, task_pane_idx, pane, viewport_height, chat_area.width);
         (format!("{category_label} {}", pane.instance_id), lines)
     } else {
         let no_pane_msg = match (app.state.selected_pane_category, waiting_on_baseline) {
@@ -1039,17 +1161,14 @@ fn render_delete_confirm_modal(
     frame.render_widget(widget, area);
 }
 
-fn render_new_task_dialog_modal(
+fn render_create_task_form_modal(
     frame: &mut Frame<'_>,
-    active_field: usize,
-    repo: &str,
-    title: &str,
-    model: &str,
+    form: &CreateTaskForm,
     theme: &TuiTheme,
 ) {
     let rows = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(0), Constraint::Length(10), Constraint::Min(0)])
+        .constraints([Constraint::Min(0), Constraint::Length(12), Constraint::Min(0)])
         .split(frame.area());
     let area = centered_rect(60, 100, rows[1]);
 
@@ -1059,16 +1178,28 @@ fn render_new_task_dialog_modal(
         .add_modifier(Modifier::BOLD);
     let normal_style = Style::default().fg(theme.header_fg);
 
-    let field_line = |label: &str, value: &str, idx: usize| {
+    let stack_value = match (form.stack, form.stack_on.as_ref()) {
+        (false, _) => "[ ] branch from current branch".to_string(),
+        (true, Some(task_id)) => format!("[x] stack on {}", task_id.0),
+        (true, None) => "[x] stack on newest open task".to_string(),
+    };
+    let field_line = |field: CreateTaskField| {
+        let value = match field {
+            CreateTaskField::Title if form.title.is_empty() => " ".to_string(),
+            CreateTaskField::Title => form.title.clone(),
+            CreateTaskField::Model => format!("< {} >", form.model),
+            CreateTaskField::Priority => format!("< {} >", form.priority.as_str()),
+            CreateTaskField::Repo => format!("< {} >", form.repo().0),
+            CreateTaskField::Stack => stack_value.clone(),
+        };
         Line::from(vec![
-            Span::styled(format!("{label:<7}"), Style::default().fg(theme.dim)),
             Span::styled(
-                if value.is_empty() {
-                    " ".to_string()
-                } else {
-                    value.to_string()
-                },
-                if active_field == idx {
+                format!("{:<10}", format!("{}:", field.label())),
+                Style::default().fg(theme.dim),
+            ),
+            Span::styled(
+                value,
+                if form.active_field == field {
                     selected_style
                 } else {
                     normal_style
@@ -1077,21 +1208,19 @@ fn render_new_task_dialog_modal(
         ])
     };
 
-    let lines = vec![
+    let mut lines = vec![
         Line::from(Span::styled(
             "Create a new task",
             Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
-        field_line("Repo:", repo, 0),
-        field_line("Title:", title, 1),
-        field_line("Model:", model, 2),
-        Line::from(""),
-        Line::from(Span::styled(
-            "[Tab] Next field  [Enter] Create  [Esc] Cancel",
-            Style::default().fg(theme.dim),
-        )),
     ];
+    lines.extend(CreateTaskField::ALL.into_iter().map(field_line));
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "[Tab] Next  [Left/Right] Change  [Enter] Create  [Esc] Discard",
+        Style::default().fg(theme.dim),
+    )));
 
     let widget = Paragraph::new(lines)
         .block(focused_block("New Task", theme))
@@ -1102,7 +1231,7 @@ fn render_new_task_dialog_modal(
 
 fn render_help_overlay(frame: &mut Frame<'_>, theme: &TuiTheme) {
     let area = centered_rect(72, 86, frame.area());
-    let lines = vec![
+    let mut lines = vec![
         Line::from(Span::styled(
             "KEYBOARD SHORTCUTS",
             Style::default()
@@ -1113,30 +1242,16 @@ fn render_help_overlay(frame: &mut Frame<'_>, theme: &TuiTheme) {
             "──────────────────",
             Style::default().fg(theme.dim),
         )),
-        Line::from("Navigation:"),
-        Line::from("  j/↓    Move down          k/↑    Move up"),
-        Line::from("  Enter  Focus/unfocus      Esc    Back/quit"),
-        Line::from(""),
-        Line::from("Tasks:"),
-        Line::from("  n      New task            d      Delete task"),
-        Line::from("  s      Stop task           r      Resume task"),
-        Line::from(""),
-        Line::from("Filtering:"),
-        Line::from("  /      Text filter         F      State filter"),
-        Line::from(""),
-        Line::from("Views:"),
-        Line::from("  Tab    Switch pane         1-9    Jump to pane"),
-        Line::from("  PgUp   Scroll up           PgDn   Scroll down"),
-        Line::from(""),
-        Line::from("Other:"),
-        Line::from("  ?      This help           q      Quit"),
-        Line::from("  Ctrl+C Force quit"),
-        Line::from(""),
-        Line::from(Span::styled(
-            "Press ? or Esc to close",
-            Style::default().fg(theme.dim),
-        )),
     ];
+    for context in PaletteContext::ALL {
+        lines.push(Line::from(format!("{}:", context.label())));
+        lines.extend(help_binding_rows(context).into_iter().map(Line::from));
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(Span::styled(
+        "Actions without a key run from Ctrl+P. Press ? or Esc to close, Ctrl+C force quits",
+        Style::default().fg(theme.dim),
+    )));
 
     let widget = Paragraph::new(lines)
         .block(focused_block("Keyboard Shortcuts", theme))
@@ -1145,6 +1260,73 @@ fn render_help_overlay(frame: &mut Frame<'_>, theme: &TuiTheme) {
     frame.render_widget(widget, area);
 }
 
+/// Registry entries for `context`, two per row as `key  name` columns.
+fn help_binding_rows(context: PaletteContext) -> Vec<String> {
+    let cells: Vec<String> = PALETTE_ENTRIES
+        .iter()
+        .filter(|entry| entry.context == context)
+        .map(|entry| {
+            let keys = if entry.keys.is_empty() { "-" } else { entry.keys };
+            format!("{keys:<7}{:<22}", entry.name)
+        })
+        .collect();
+    cells
+        .chunks(2)
+        .map(|pair| format!("  {}", pair.join(" ").trim_end()))
+        .collect()
+}
+
+fn render_command_palette(
+    frame: &mut Frame<'_>,
+    query: &str,
+    entries: &[&PaletteEntry],
+    selected: usize,
+    theme: &TuiTheme,
+) {
+    let area = centered_rect(64, 60, frame.area());
+    let visible = usize::from(area.height.saturating_sub(5)).max(1);
+    let start = selected.saturating_sub(visible - 1);
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("> ", Style::default().fg(theme.accent)),
+            Span::styled(query.to_string(), Style::default().fg(theme.header_fg)),
+            Span::styled("_", Style::default().fg(theme.accent)),
+        ]),
+        Line::from(""),
+    ];
+    if entries.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "no matching commands",
+            Style::default().fg(theme.dim),
+        )));
+    }
+    for (index, entry) in entries.iter().enumerate().skip(start).take(visible) {
+        let name_style = if index == selected {
+            Style::default()
+                .fg(Color::Black)
+                .bg(theme.accent)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.header_fg)
+        };
+        let mut spans = vec![
+            Span::styled(format!(" {:<18}", entry.name), name_style),
+            Span::styled(format!(" {}", entry.description), Style::default().fg(theme.muted)),
+        ];
+        if !entry.keys.is_empty() {
+            spans.push(Span::styled(format!("  [{}]", entry.keys), Style::default().fg(theme.dim)));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let widget = Paragraph::new(lines)
+        .block(focused_block("Command Palette", theme))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(Clear, area);
+    frame.render_widget(widget, area);
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
     let vertical = Layout::default()
         .direction(Direction::Vertical)
@@ -1177,7 +1359,7 @@ mod tests {
 
     use super::{
         estimate_task_cost_usd, footer_height, format_cost_display, format_dependency_chain,
-        format_pane_tabs,
+        format_pane_tabs, help_binding_rows,
         format_task_row, pane_status_tag, state_color, status_activity, status_line_color,
         status_sidebar_lines, to_local_time, wrapped_visual_line_count, session_status_color,
         session_status_label,
@@ -1204,9 +1386,30 @@ mod tests {
             depends_on_display: Vec::new(),
             pr_url: None,
             model_display: None,
+            parent_task_id: None,
+            restack_conflict: false,
         }
     }
 
+    #[test]
+    fn help_overlay_lists_every_registry_entry_under_its_context() {
+        use crate::palette::{PaletteContext, PALETTE_ENTRIES};
+
+        for entry in PALETTE_ENTRIES {
+            let rows = help_binding_rows(entry.context);
+            assert!(
+                rows.iter().any(|row| row.contains(entry.name)),
+                "{} missing from {} help",
+                entry.name,
+                entry.context.label()
+            );
+        }
+        let task_rows = help_binding_rows(PaletteContext::Task).join("\n");
+        assert!(task_rows.contains("a      Approve task"));
+        assert!(task_rows.contains("-      Open diff"));
+        assert!(!task_rows.contains("Search pane"));
+    }
+
     #[test]
     fn format_dependency_chain_empty() {
         let row = mk_row("T123");
@@ -1250,6 +1453,9 @@ mod tests {
 
         pane.status = AgentPaneStatus::Failed;
         assert_eq!(pane_status_tag(&pane), "failed");
+
+        pane.status = AgentPaneStatus::Suspended;
+        assert_eq!(pane_status_tag(&pane), "suspended (idle)");
     }
 
     #[test]
@@ -1355,12 +1561,12 @@ mod tests {
         let mut app = TuiApp::default();
         assert_eq!(footer_height(&app, 120), 5);
 
-        app.input_mode = InputMode::NewChatPrompt {
+        app.input_mode = InputMode::FilterInput {
             buffer: "line 1\nline 2".to_string(),
         };
         assert!(footer_height(&app, 120) > 3);
 
-        app.input_mode = InputMode::NewChatPrompt {
+        app.input_mode = InputMode::FilterInput {
             buffer: "x".repeat(4000),
         };
         assert_eq!(footer_height(&app, 40), 13);
@@ -26,7 +26,10 @@ pub(crate) fn status_activity(status: AgentPaneStatus, frame: usize) -> Option<(
         AgentPaneStatus::Starting => Some((format!("starting {pulse}"), Color::Yellow)),
         AgentPaneStatus::Running => Some((format!("thinking {pulse}"), Color::Cyan)),
         AgentPaneStatus::Waiting => Some((format!("percolating {pulse}"), Color::Magenta)),
-        AgentPaneStatus::Exited | AgentPaneStatus::Failed | AgentPaneStatus::Stopped => None,
+        AgentPaneStatus::Exited
+        | AgentPaneStatus::Failed
+        | AgentPaneStatus::Stopped
+        | AgentPaneStatus::Suspended => None,
     }
 }
 
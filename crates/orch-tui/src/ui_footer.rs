@@ -182,33 +182,6 @@ pub(crate) fn build_footer_content(app: &TuiApp) -> FooterContent {
         };
     }
 
-    if let Some((models, selected)) = app.model_select_display() {
-        let mut spans = vec![Span::styled(" model: ", Style::default().fg(DIM))];
-        for (i, m) in models.iter().enumerate() {
-            if i == selected {
-                spans.push(Span::styled(
-                    format!(" {m:?} "),
-                    Style::default()
-                        .fg(Color::Black)
-                        .bg(ACCENT)
-                        .add_modifier(Modifier::BOLD),
-                ));
-            } else {
-                spans.push(Span::styled(format!(" {m:?} "), Style::default().fg(MUTED)));
-            }
-            spans.push(Span::raw(" "));
-        }
-        spans.push(Span::styled(
-            " Up/Down=cycle Enter=confirm Esc=cancel",
-            Style::default().fg(DIM),
-        ));
-        return FooterContent {
-            title: "Select Model",
-            lines: vec![Line::from(spans)],
-            wrap_trim: true,
-        };
-    }
-
     // Skip inline prompt when focused view handles ChatInput display
     let in_focused = app.state.focused_task || app.state.focused_pane_idx.is_some();
     let chat_input_handled_inline = in_focused && app.chat_input_display().is_some();
@@ -280,12 +253,14 @@ pub(crate) fn build_footer_content(app: &TuiApp) -> FooterContent {
     }
     if app.state.focused_task || app.state.focused_pane_idx.is_some() {
         spans.push(Span::styled(
-            "| \u{2191}\u{2193}=scroll PgUp/Dn=page Home/End=top/bottom esc=back",
+            "| \u{2191}\u{2193}=scroll PgUp/Dn=page Home/End=top/bottom /=search y=copy \
+             ^P=palette esc=back",
             Style::default().fg(DIM),
         ));
     } else {
         spans.push(Span::styled(
-            "| \u{2191}\u{2193}=select \u{2190}\u{2192}=pane \u{21B9}=focus \u{23CE}=detail esc=quit",
+            "| \u{2191}\u{2193}=select \u{2190}\u{2192}=pane \u{21B9}=focus \u{23CE}=detail \
+             ^P=palette ?=help esc=quit",
             Style::default().fg(DIM),
         ));
     }
@@ -413,6 +388,8 @@ mod tests {
             depends_on_display: Vec::new(),
             pr_url: None,
             model_display: None,
+            parent_task_id: None,
+            restack_conflict: false,
         }
     }
 
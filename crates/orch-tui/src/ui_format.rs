@@ -5,9 +5,13 @@ use ratatui::text::{Line, Span};
 
 use crate::app::TuiApp;
 use crate::model::{
-    AgentPane, AgentPaneStatus, PaneCategory, QATestDisplay, TaskOverviewRow, TuiTheme,
+    AgentPane, AgentPaneStatus, PaneCategory, QATestDisplay, TaskDetail, TaskOverviewRow,
+    TuiTheme,
 };
 
+/// Changed files listed under the diff stat before collapsing into "+N more".
+const DETAIL_DIFF_FILE_LIMIT: usize = 8;
+
 pub fn state_color(state: TaskState, theme: &TuiTheme) -> Color {
     match state {
         TaskState::Chatting => theme.state_chatting,
@@ -39,6 +43,7 @@ pub(crate) fn pane_status_color(status: AgentPaneStatus) -> Color {
         AgentPaneStatus::Exited => Color::DarkGray,
         AgentPaneStatus::Failed => Color::Red,
         AgentPaneStatus::Stopped => Color::Cyan,
+        AgentPaneStatus::Suspended => Color::Blue,
     }
 }
 
@@ -59,6 +64,47 @@ pub(crate) fn status_line_color(message: &str) -> Color {
     }
 }
 
+/// One node of the stack view: indented by depth like `othala deps`, with
+/// the state colored and a marker when the last restack hit a conflict.
+pub(crate) fn stack_node_line(
+    depth: usize,
+    task: &TaskOverviewRow,
+    is_selected: bool,
+    theme: &TuiTheme,
+) -> Line<'static> {
+    let base_style = if is_selected {
+        Style::default().bg(theme.selected_bg).fg(Color::White)
+    } else {
+        Style::default().fg(theme.muted)
+    };
+    let prefix = if is_selected { "\u{25B6} " } else { "  " };
+    let connector = if depth == 0 { "" } else { "\u{2514}\u{2500} " };
+
+    let mut spans = vec![
+        Span::styled(prefix, Style::default().fg(theme.accent)),
+        Span::styled(
+            format!("{}{connector}", "   ".repeat(depth.saturating_sub(1))),
+            Style::default().fg(theme.dim),
+        ),
+        Span::styled(
+            format!("{:?}", task.state),
+            base_style
+                .fg(state_color(task.state, theme))
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(format!(" {} ", task.task_id.0), base_style),
+        Span::styled(format!("[{}]", task.title), base_style),
+        Span::styled(format!(" ({})", task.branch), Style::default().fg(theme.dim)),
+    ];
+    if task.restack_conflict {
+        spans.push(Span::styled(
+            " \u{26A0} restack conflict",
+            Style::default().fg(theme.state_stopped).add_modifier(Modifier::BOLD),
+        ));
+    }
+    Line::from(spans)
+}
+
 pub(crate) fn format_task_row<'a>(
     is_selected: bool,
     task: &'a TaskOverviewRow,
@@ -253,7 +299,7 @@ pub(crate) fn pane_meta_lines(
             lines.push(Line::from(vec![
                 Span::styled(" scroll ", Style::default().fg(theme.dim)),
                 Span::styled(
-                    format!("+{scroll_back} lines from live tail"),
+                    format!("+{scroll_back} lines from live tail (follow paused, End resumes)"),
                     Style::default()
                         .fg(Color::Yellow)
                         .add_modifier(Modifier::BOLD),
@@ -264,6 +310,76 @@ pub(crate) fn pane_meta_lines(
     lines
 }
 
+/// Search status for a focused pane: `(query, selected match, total)`.
+pub(crate) fn pane_search_line(
+    status: &(String, Option<usize>, usize),
+    theme: &TuiTheme,
+) -> Line<'static> {
+    let (query, position, total) = status;
+    let summary = match position {
+        Some(position) => format!("match {position}/{total}  n=older N=newer"),
+        None if *total > 0 => format!("{total} matches  n=older N=newer"),
+        None => "no matches".to_string(),
+    };
+    Line::from(vec![
+        Span::styled(" search ", Style::default().fg(theme.dim)),
+        Span::styled(format!("'{query}' "), Style::default().fg(theme.accent)),
+        Span::styled(summary, Style::default().fg(theme.muted)),
+    ])
+}
+
+/// Split spans so every case-insensitive occurrence of `query` is highlighted.
+pub(crate) fn highlight_matches(lines: &mut [Line<'static>], query: &str) {
+    if query.is_empty() {
+        return;
+    }
+    let needle = query.to_lowercase();
+    for line in lines.iter_mut() {
+        let spans = std::mem::take(&mut line.spans);
+        for span in spans {
+            let text = span.content.to_string();
+            let ranges = match_ranges(&text, &needle);
+            if ranges.is_empty() {
+                line.spans.push(span);
+                continue;
+            }
+            let mut cursor = 0;
+            for (start, end) in ranges {
+                if start > cursor {
+                    line.spans
+                        .push(Span::styled(text[cursor..start].to_string(), span.style));
+                }
+                line.spans.push(Span::styled(
+                    text[start..end].to_string(),
+                    span.style.bg(Color::Yellow).fg(Color::Black),
+                ));
+                cursor = end;
+            }
+            if cursor < text.len() {
+                line.spans
+                    .push(Span::styled(text[cursor..].to_string(), span.style));
+            }
+        }
+    }
+}
+
+/// Byte ranges of `needle` (already lowercased) in `text`, ignoring case.
+/// Lines whose lowercase form changes length are matched only on ASCII.
+fn match_ranges(text: &str, needle: &str) -> Vec<(usize, usize)> {
+    let lowered = text.to_lowercase();
+    let haystack = if lowered.len() == text.len() {
+        lowered
+    } else {
+        text.to_ascii_lowercase()
+    };
+    haystack
+        .match_indices(needle)
+        .filter(|(start, _)| text.is_char_boundary(*start))
+        .filter(|(start, _)| text.is_char_boundary(start + needle.len()))
+        .map(|(start, _)| (start, start + needle.len()))
+        .collect()
+}
+
 pub(crate) fn divider_line(width: u16, theme: &TuiTheme) -> Line<'static> {
     let len = width.saturating_sub(4).max(8) as usize;
     Line::from(Span::styled("-".repeat(len), Style::default().fg(theme.dim)))
@@ -277,6 +393,7 @@ pub(crate) fn pane_status_tag(pane: &AgentPane) -> &'static str {
         AgentPaneStatus::Exited => "exited",
         AgentPaneStatus::Failed => "failed",
         AgentPaneStatus::Stopped => "stopped",
+        AgentPaneStatus::Suspended => "suspended (idle)",
     }
 }
 
@@ -592,6 +709,82 @@ fn qa_test_lines(tests: &[QATestDisplay], theme: &TuiTheme) -> Vec<Line<'static>
     lines
 }
 
+/// Body of the task detail block: fields, diff stat, run history and the
+/// latest events (newest first).
+pub(crate) fn task_detail_lines(
+    detail: Option<&TaskDetail>,
+    theme: &TuiTheme,
+) -> Vec<Line<'static>> {
+    let Some(detail) = detail else {
+        return vec![Line::from(Span::styled(
+            "loading task detail...",
+            Style::default().fg(theme.dim),
+        ))];
+    };
+    let heading = |text: String| {
+        Line::from(Span::styled(
+            text,
+            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+        ))
+    };
+    let mut lines: Vec<Line<'static>> = detail
+        .fields
+        .iter()
+        .map(|(label, value)| {
+            Line::from(vec![
+                Span::styled(format!("{label}: "), Style::default().fg(theme.dim)),
+                Span::styled(value.clone(), Style::default().fg(theme.header_fg)),
+            ])
+        })
+        .collect();
+
+    lines.push(Line::from(""));
+    lines.push(heading("Diff".to_string()));
+    lines.push(Line::from(Span::styled(
+        detail.diff_stat.clone().unwrap_or_else(|| "no changes".to_string()),
+        Style::default().fg(theme.muted),
+    )));
+    for file in detail.diff_files.iter().take(DETAIL_DIFF_FILE_LIMIT) {
+        lines.push(Line::from(format!("  {file}")));
+    }
+    if detail.diff_files.len() > DETAIL_DIFF_FILE_LIMIT {
+        lines.push(Line::from(Span::styled(
+            format!("  +{} more", detail.diff_files.len() - DETAIL_DIFF_FILE_LIMIT),
+            Style::default().fg(theme.dim),
+        )));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(heading(format!("Runs ({})", detail.runs.len())));
+    for run in &detail.runs {
+        let duration = run
+            .duration_secs
+            .map(|secs| format!("{secs}s"))
+            .unwrap_or_else(|| "-".to_string());
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{} ", run.started_at.with_timezone(&Local).format("%m-%d %H:%M")),
+                Style::default().fg(theme.dim),
+            ),
+            Span::styled(format!("{} ", run.model.as_str()), Style::default().fg(theme.header_fg)),
+            Span::styled(format!("{duration} {}", run.outcome), Style::default().fg(theme.muted)),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(heading(format!("Events (last {})", detail.events.len())));
+    for event in detail.events.iter().rev() {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{} ", event.at.with_timezone(&Local).format("%H:%M:%S")),
+                Style::default().fg(theme.dim),
+            ),
+            Span::raw(event.summary.clone()),
+        ]));
+    }
+    lines
+}
+
 fn format_retry_timestamp(timestamp: &str) -> String {
     chrono::DateTime::parse_from_rfc3339(timestamp)
         .map(|dt| dt.with_timezone(&Local).format("%H:%M:%S").to_string())
@@ -607,10 +800,11 @@ pub(crate) fn to_local_time(value: DateTime<Utc>) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::state_color;
+    use super::{highlight_matches, stack_node_line, state_color};
     use crate::model::default_theme;
     use orch_core::state::TaskState;
     use ratatui::style::Color;
+    use ratatui::text::{Line, Span};
 
     #[test]
     fn state_color_returns_correct_colors() {
@@ -623,4 +817,49 @@ mod tests {
         assert_eq!(state_color(TaskState::Merged, &theme), Color::Green);
         assert_eq!(state_color(TaskState::Stopped, &theme), Color::Red);
     }
+
+    #[test]
+    fn highlight_matches_splits_spans_case_insensitively() {
+        let mut lines = vec![
+            Line::from(vec![Span::raw("Error: "), Span::raw("retry error later")]),
+            Line::from("clean"),
+        ];
+        highlight_matches(&mut lines, "error");
+
+        let texts: Vec<&str> = lines[0].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(texts, vec!["Error", ": ", "retry ", "error", " later"]);
+        let highlighted = lines[0]
+            .spans
+            .iter()
+            .filter(|span| span.style.bg == Some(Color::Yellow))
+            .count();
+        assert_eq!(highlighted, 2);
+        assert_eq!(lines[1].spans.len(), 1);
+    }
+
+    #[test]
+    fn stack_node_line_indents_children_and_marks_conflicts() {
+        let theme = default_theme();
+        let mut task = crate::model::TaskOverviewRow::from_task(&orch_core::types::Task::new(
+            orch_core::types::TaskId::new("T2"),
+            orch_core::types::RepoId("example".to_string()),
+            "Child".to_string(),
+            std::path::PathBuf::from(".orch/wt/T2"),
+        ));
+        task.branch = "task/T2".to_string();
+        let text = |line: Line<'_>| {
+            line.spans
+                .iter()
+                .map(|span| span.content.to_string())
+                .collect::<String>()
+        };
+
+        assert_eq!(
+            text(stack_node_line(2, &task, false, &theme)),
+            "     \u{2514}\u{2500} Chatting T2 [Child] (task/T2)"
+        );
+        task.restack_conflict = true;
+        assert!(text(stack_node_line(0, &task, true, &theme))
+            .ends_with("(task/T2) \u{26A0} restack conflict"));
+    }
 }
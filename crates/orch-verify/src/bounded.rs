@@ -0,0 +1,197 @@
+//! Shell commands run under a time limit with capped output capture.
+
+use std::io::Read;
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use orch_core::config::VerifyLimits;
+
+/// How often a running command is polled for exit.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoundedOutput {
+    pub success: bool,
+    /// `None` when the command was killed by a signal, including on timeout.
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+    /// The command overran `timeout_secs` and its process group was killed.
+    pub timed_out: bool,
+}
+
+/// Run `command` with `bash -lc` in `cwd`, killing its whole process group
+/// after `limits.timeout_secs`. Stdin is closed so a command waiting on
+/// input fails instead of hanging. Each stream keeps its first
+/// `limits.max_output_bytes` bytes, followed by a truncation marker.
+pub fn run_bounded(
+    command: &str,
+    cwd: &Path,
+    limits: VerifyLimits,
+) -> std::io::Result<BoundedOutput> {
+    run_bounded_with(command, cwd, limits, Command::spawn)
+}
+
+/// [`run_bounded`], starting the prepared command through `spawn` so the
+/// caller can track the child, e.g. to kill it early.
+pub fn run_bounded_with<S>(
+    command: &str,
+    cwd: &Path,
+    limits: VerifyLimits,
+    spawn: S,
+) -> std::io::Result<BoundedOutput>
+where
+    S: FnOnce(&mut Command) -> std::io::Result<Child>,
+{
+    let mut cmd = Command::new("bash");
+    cmd.arg("-lc")
+        .arg(command)
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+    let mut child = spawn(&mut cmd)?;
+
+    let max_bytes = limits.max_output_bytes;
+    let stdout = child
+        .stdout
+        .take()
+        .map(|pipe| thread::spawn(move || read_capped(pipe, max_bytes)));
+    let stderr = child
+        .stderr
+        .take()
+        .map(|pipe| thread::spawn(move || read_capped(pipe, max_bytes)));
+
+    let deadline = Instant::now() + Duration::from_secs(limits.timeout_secs);
+    let mut timed_out = false;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            timed_out = true;
+            kill_process_group(&mut child);
+            break child.wait()?;
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    let collect = |reader: Option<thread::JoinHandle<String>>| {
+        reader
+            .and_then(|handle| handle.join().ok())
+            .unwrap_or_default()
+    };
+    Ok(BoundedOutput {
+        success: status.success() && !timed_out,
+        exit_code: status.code(),
+        stdout: collect(stdout),
+        stderr: collect(stderr),
+        timed_out,
+    })
+}
+
+/// Read `pipe` to the end, keeping at most `max_bytes`. The rest is drained
+/// so the writer never blocks on a full pipe.
+fn read_capped(mut pipe: impl Read, max_bytes: usize) -> String {
+    let mut kept = Vec::new();
+    let mut dropped = 0usize;
+    let mut chunk = [0u8; 8192];
+    loop {
+        match pipe.read(&mut chunk) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => {
+                let room = max_bytes.saturating_sub(kept.len()).min(n);
+                kept.extend_from_slice(&chunk[..room]);
+                dropped += n - room;
+            }
+        }
+    }
+    let mut text = String::from_utf8_lossy(&kept).into_owned();
+    if dropped > 0 {
+        text.push_str(&format!("\n[output truncated: {dropped} more bytes]\n"));
+    }
+    text
+}
+
+fn kill_process_group(child: &mut Child) {
+    if !kill_process_group_id(child.id()) {
+        let _ = child.kill();
+    }
+}
+
+/// Send SIGKILL to the process group led by `pgid`. Returns whether the
+/// signal was delivered; always `false` off Unix.
+pub fn kill_process_group_id(pgid: u32) -> bool {
+    #[cfg(unix)]
+    {
+        Command::new("kill")
+            .args(["-KILL", "--", &format!("-{pgid}")])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = pgid;
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(timeout_secs: u64, max_output_bytes: usize) -> VerifyLimits {
+        VerifyLimits {
+            timeout_secs,
+            max_output_bytes,
+        }
+    }
+
+    #[test]
+    fn kills_the_process_group_on_timeout_and_keeps_partial_output() {
+        let start = Instant::now();
+        let output = run_bounded(
+            "echo started; sleep 30 & sleep 30; echo never",
+            Path::new("/tmp"),
+            limits(5, 1024),
+        )
+        .expect("run bounded");
+        assert!(start.elapsed() < Duration::from_secs(15));
+        assert!(output.timed_out);
+        assert!(!output.success);
+        assert_eq!(output.stdout.trim(), "started");
+    }
+
+    #[test]
+    fn closed_stdin_ends_a_command_waiting_for_input() {
+        let output = run_bounded("read line && echo got", Path::new("/tmp"), limits(10, 1024))
+            .expect("run bounded");
+        assert!(!output.timed_out);
+        assert!(!output.success);
+    }
+
+    #[test]
+    fn caps_each_stream_and_marks_the_truncation() {
+        let output = run_bounded(
+            "head -c 5000 /dev/zero | tr '\\0' x; echo err >&2",
+            Path::new("/tmp"),
+            limits(10, 1000),
+        )
+        .expect("run bounded");
+        assert!(output.success);
+        assert!(output.stdout.starts_with(&"x".repeat(1000)));
+        assert!(output
+            .stdout
+            .ends_with("\n[output truncated: 4000 more bytes]\n"));
+        assert!(output.stderr.ends_with("err\n"));
+    }
+}
@@ -0,0 +1,133 @@
+//! Verify command discovery from the build files found in a repo root.
+
+use std::fs;
+use std::path::Path;
+
+/// The placeholder `npm init` writes for `scripts.test`.
+const NPM_PLACEHOLDER_TEST: &str = "echo \"Error: no test specified\" && exit 1";
+
+/// Candidate verify commands for `repo_root`, most specific first. Empty when
+/// no known build file is present.
+pub fn discover_verify_commands(repo_root: &Path) -> Vec<String> {
+    let has = |name: &str| repo_root.join(name).exists();
+    let mut commands = Vec::new();
+
+    if has("Cargo.toml") {
+        commands.push("cargo check && cargo test --workspace".to_string());
+    }
+    if has_npm_script(repo_root, "test") {
+        commands.push(format!("{} test", npm_runner(repo_root)));
+    }
+    if has("go.mod") {
+        commands.push("go test ./...".to_string());
+    }
+    if has("pyproject.toml") || has("setup.py") || has("pytest.ini") {
+        commands.push("pytest".to_string());
+    }
+    if has_make_test_target(repo_root) {
+        commands.push("make test".to_string());
+    }
+    if has("flake.nix") {
+        commands.push("nix flake check".to_string());
+    }
+
+    commands
+}
+
+/// The package manager picked by the lockfile in `repo_root`.
+pub(crate) fn npm_runner(repo_root: &Path) -> &'static str {
+    let has = |name: &str| repo_root.join(name).exists();
+    if has("pnpm-lock.yaml") {
+        "pnpm"
+    } else if has("yarn.lock") {
+        "yarn"
+    } else if has("bun.lockb") {
+        "bun"
+    } else {
+        "npm"
+    }
+}
+
+/// Whether `package.json` defines a real `scripts.<name>` entry.
+pub(crate) fn has_npm_script(repo_root: &Path, name: &str) -> bool {
+    let Ok(raw) = fs::read_to_string(repo_root.join("package.json")) else {
+        return false;
+    };
+    let Ok(package) = serde_json::from_str::<serde_json::Value>(&raw) else {
+        return false;
+    };
+    package
+        .pointer(&format!("/scripts/{name}"))
+        .and_then(|script| script.as_str())
+        .is_some_and(|script| !script.trim().is_empty() && script != NPM_PLACEHOLDER_TEST)
+}
+
+fn has_make_test_target(repo_root: &Path) -> bool {
+    fs::read_to_string(repo_root.join("Makefile"))
+        .map(|makefile| makefile.lines().any(|line| line.starts_with("test:")))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::discover_verify_commands;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn repo(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "othala-verify-discover-{name}-{}-{}",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&root).expect("create repo");
+        for (path, content) in files {
+            fs::write(root.join(path), content).expect("write file");
+        }
+        root
+    }
+
+    #[test]
+    fn discovers_commands_from_build_files_in_order() {
+        let root = repo(
+            "multi",
+            &[
+                ("Cargo.toml", "[workspace]\n"),
+                ("Makefile", "build:\n\tcargo build\ntest:\n\tcargo test\n"),
+                ("flake.nix", "{}\n"),
+            ],
+        );
+        assert_eq!(
+            discover_verify_commands(&root),
+            vec![
+                "cargo check && cargo test --workspace",
+                "make test",
+                "nix flake check",
+            ]
+        );
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn npm_test_script_uses_the_lockfile_runner_and_skips_the_placeholder() {
+        let root = repo(
+            "pnpm",
+            &[
+                ("package.json", r#"{"scripts": {"test": "vitest run"}}"#),
+                ("pnpm-lock.yaml", ""),
+            ],
+        );
+        assert_eq!(discover_verify_commands(&root), vec!["pnpm test"]);
+        let _ = fs::remove_dir_all(&root);
+
+        let root = repo(
+            "placeholder",
+            &[(
+                "package.json",
+                r#"{"scripts": {"test": "echo \"Error: no test specified\" && exit 1"}}"#,
+            )],
+        );
+        assert!(discover_verify_commands(&root).is_empty());
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,478 @@
+//! Structured failures extracted from verify command output.
+//!
+//! Understands `cargo test`/`cargo check`, pytest and jest output. Anything
+//! else yields no details and callers fall back to the raw output.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerifyFailureKind {
+    /// A test that ran and failed.
+    Test,
+    /// A compiler error that stopped the build.
+    Compile,
+    /// The command overran its time limit and was killed.
+    Timeout,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyFailureDetail {
+    pub kind: VerifyFailureKind,
+    /// Failing test name, or the compiler error code (e.g. `E0308`).
+    pub name: String,
+    /// `file:line[:col]` when the output names one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl VerifyFailureDetail {
+    fn new(kind: VerifyFailureKind, name: impl Into<String>) -> Self {
+        Self {
+            kind,
+            name: name.into(),
+            location: None,
+            message: None,
+        }
+    }
+
+    /// The failure recorded for a command killed after `timeout_secs`.
+    pub fn timeout(timeout_secs: u64) -> Self {
+        Self::new(VerifyFailureKind::Timeout, format!("after {timeout_secs}s"))
+    }
+
+    /// Kind, name and location, e.g. `test tests::adds (src/lib.rs:10:5)`.
+    pub fn label(&self) -> String {
+        let kind = match self.kind {
+            VerifyFailureKind::Test => "test",
+            VerifyFailureKind::Compile => "error",
+            VerifyFailureKind::Timeout => "timed out",
+        };
+        let mut label = format!("{kind} {}", self.name);
+        if let Some(location) = &self.location {
+            label.push_str(&format!(" ({location})"));
+        }
+        label
+    }
+
+    /// The label followed by the message, when there is one.
+    pub fn summary(&self) -> String {
+        let mut line = self.label();
+        if let Some(message) = &self.message {
+            line.push_str(&format!(": {message}"));
+        }
+        line
+    }
+}
+
+/// Failing tests and compiler errors found in `output`, in output order and
+/// without duplicates. Empty when the output is from an unknown tool.
+pub fn parse_verify_failures(output: &str) -> Vec<VerifyFailureDetail> {
+    let mut failures = Vec::new();
+    for parsed in [
+        parse_cargo(output),
+        parse_pytest(output),
+        parse_jest(output),
+    ] {
+        for failure in parsed {
+            if !failures.iter().any(|existing: &VerifyFailureDetail| {
+                existing.kind == failure.kind
+                    && existing.name == failure.name
+                    && existing.location == failure.location
+            }) {
+                failures.push(failure);
+            }
+        }
+    }
+    failures
+}
+
+/// Short summary of the first `limit` failures, e.g.
+/// `2 failures: test a (src/lib.rs:3:5); error E0308 (src/main.rs:4:18)`.
+pub fn summarize_failures(failures: &[VerifyFailureDetail], limit: usize) -> Option<String> {
+    if failures.is_empty() {
+        return None;
+    }
+    let shown = failures
+        .iter()
+        .take(limit.max(1))
+        .map(VerifyFailureDetail::label)
+        .collect::<Vec<_>>();
+    let noun = if failures.len() == 1 {
+        "failure"
+    } else {
+        "failures"
+    };
+    let mut summary = format!("{} {noun}: {}", failures.len(), shown.join("; "));
+    if failures.len() > shown.len() {
+        summary.push_str(&format!("; +{} more", failures.len() - shown.len()));
+    }
+    Some(summary)
+}
+
+fn truncate_message(message: &str) -> Option<String> {
+    const MAX_MESSAGE_CHARS: usize = 200;
+    let message = message.trim();
+    if message.is_empty() {
+        return None;
+    }
+    if message.chars().count() <= MAX_MESSAGE_CHARS {
+        return Some(message.to_string());
+    }
+    let cut: String = message.chars().take(MAX_MESSAGE_CHARS).collect();
+    Some(format!("{cut}..."))
+}
+
+/// Whether `candidate` looks like `path:line[:col]`.
+fn is_location(candidate: &str) -> bool {
+    let mut parts = candidate.rsplitn(3, ':');
+    let last = parts.next().unwrap_or_default();
+    let middle = parts.next().unwrap_or_default();
+    let is_num = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    let path = candidate
+        .trim_end_matches(|c: char| c.is_ascii_digit() || c == ':')
+        .trim();
+    is_num(last) && (is_num(middle) || !middle.is_empty()) && !path.is_empty()
+}
+
+// --- cargo -----------------------------------------------------------------
+
+fn parse_cargo(output: &str) -> Vec<VerifyFailureDetail> {
+    let lines: Vec<&str> = output.lines().collect();
+    let mut failures = Vec::new();
+
+    for (index, line) in lines.iter().enumerate() {
+        // `error[E0308]: mismatched types` followed by `  --> src/main.rs:4:18`.
+        if let Some(rest) = line.strip_prefix("error") {
+            let (code, message) = match rest.strip_prefix('[') {
+                Some(coded) => match coded.split_once("]: ") {
+                    Some((code, message)) => (Some(code), message),
+                    None => continue,
+                },
+                None => match rest.strip_prefix(": ") {
+                    Some(message) => (None, message),
+                    None => continue,
+                },
+            };
+            let location = lines[index + 1..]
+                .iter()
+                .take(3)
+                .find_map(|next| next.trim_start().strip_prefix("--> "))
+                .map(|location| location.trim().to_string());
+            // Uncoded errors without a location are cargo's own summaries,
+            // e.g. `error: could not compile`.
+            if code.is_none() && location.is_none() {
+                continue;
+            }
+            let mut failure =
+                VerifyFailureDetail::new(VerifyFailureKind::Compile, code.unwrap_or("error"));
+            failure.location = location;
+            failure.message = truncate_message(message);
+            failures.push(failure);
+            continue;
+        }
+
+        // `test tests::adds ... FAILED`
+        if let Some(name) = line
+            .strip_prefix("test ")
+            .and_then(|rest| rest.strip_suffix(" ... FAILED"))
+        {
+            let mut failure = VerifyFailureDetail::new(VerifyFailureKind::Test, name.trim());
+            if let Some((location, message)) = cargo_panic(&lines, name.trim()) {
+                failure.location = location;
+                failure.message = message;
+            }
+            failures.push(failure);
+        }
+    }
+    failures
+}
+
+/// Location and message from the test's `thread '<name>' panicked at` line.
+/// Newer toolchains put the thread id in between: `thread '<name>' (42)`.
+fn cargo_panic(lines: &[&str], name: &str) -> Option<(Option<String>, Option<String>)> {
+    let prefix = format!("thread '{name}' ");
+    let (index, rest) = lines.iter().enumerate().find_map(|(index, line)| {
+        let (_, rest) = line.strip_prefix(&prefix)?.split_once("panicked at ")?;
+        Some((index, rest))
+    })?;
+
+    // Rust 1.73+: `panicked at src/lib.rs:10:5:` with the message on the
+    // next line.
+    if let Some(location) = rest.strip_suffix(':').filter(|loc| is_location(loc)) {
+        let message = lines.get(index + 1).and_then(|line| truncate_message(line));
+        return Some((Some(location.to_string()), message));
+    }
+    // Older: `panicked at 'message', src/lib.rs:10:5`.
+    if let Some((message, location)) = rest.rsplit_once(", ") {
+        if is_location(location) {
+            let message = message.trim_matches('\'');
+            return Some((Some(location.to_string()), truncate_message(message)));
+        }
+    }
+    Some((None, truncate_message(rest)))
+}
+
+// --- pytest ----------------------------------------------------------------
+
+fn parse_pytest(output: &str) -> Vec<VerifyFailureDetail> {
+    // Section headers (`____ TestCalc.test_add ____`) and the last
+    // `path.py:line: Error` line inside each.
+    let mut locations: Vec<(String, String)> = Vec::new();
+    let mut section: Option<String> = None;
+    let mut failures = Vec::new();
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("___") && trimmed.ends_with("___") {
+            let name = trimmed.trim_matches('_').trim();
+            section = (!name.is_empty()).then(|| name.to_string());
+            continue;
+        }
+        if trimmed.starts_with("===") {
+            section = None;
+        }
+        if let Some(current) = &section {
+            if let Some((location, _)) = line.split_once(": ") {
+                if location.contains(".py:") && is_location(location) {
+                    locations.retain(|(name, _)| name != current);
+                    locations.push((current.clone(), location.to_string()));
+                }
+            }
+        }
+
+        // `FAILED tests/test_app.py::test_add - assert 1 == 2`
+        if let Some(rest) = line.strip_prefix("FAILED ") {
+            let (name, message) = match rest.split_once(" - ") {
+                Some((name, message)) => (name.trim(), truncate_message(message)),
+                None => (rest.trim(), None),
+            };
+            if !name.contains("::") {
+                continue;
+            }
+            let mut failure = VerifyFailureDetail::new(VerifyFailureKind::Test, name);
+            failure.message = message;
+            let section_name = name.split("::").skip(1).collect::<Vec<_>>().join(".");
+            failure.location = locations
+                .iter()
+                .find(|(section, _)| *section == section_name)
+                .map(|(_, location)| location.clone());
+            failures.push(failure);
+        }
+    }
+    failures
+}
+
+// --- jest ------------------------------------------------------------------
+
+fn parse_jest(output: &str) -> Vec<VerifyFailureDetail> {
+    let mut failures: Vec<VerifyFailureDetail> = Vec::new();
+    let mut current: Option<usize> = None;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        // `● Suite › does the thing`
+        if let Some(name) = trimmed.strip_prefix("● ") {
+            let name = name.trim();
+            current = match failures.iter().position(|failure| failure.name == name) {
+                Some(existing) => Some(existing),
+                None => {
+                    failures.push(VerifyFailureDetail::new(VerifyFailureKind::Test, name));
+                    Some(failures.len() - 1)
+                }
+            };
+            continue;
+        }
+        if trimmed.starts_with("Test Suites:")
+            || trimmed.starts_with("FAIL ")
+            || trimmed.starts_with("PASS ")
+        {
+            current = None;
+            continue;
+        }
+        let Some(index) = current else {
+            continue;
+        };
+        let failure = &mut failures[index];
+        if failure.message.is_none() && !trimmed.is_empty() && !trimmed.starts_with("at ") {
+            failure.message = truncate_message(trimmed);
+            continue;
+        }
+        // `at Object.<anonymous> (src/sum.test.js:12:5)` or `at src/sum.test.js:12:5`
+        if failure.location.is_none() {
+            if let Some(frame) = trimmed.strip_prefix("at ") {
+                let location = frame
+                    .rsplit_once(" (")
+                    .map(|(_, location)| location.trim_end_matches(')'))
+                    .unwrap_or(frame);
+                if is_location(location) && !location.contains("node_modules") {
+                    failure.location = Some(location.to_string());
+                }
+            }
+        }
+    }
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CARGO_TEST: &str = "\
+running 3 tests
+test tests::adds ... FAILED
+test tests::subtracts ... ok
+test tests::legacy ... FAILED
+
+failures:
+
+---- tests::adds stdout ----
+
+thread 'tests::adds' (4242) panicked at src/lib.rs:10:9:
+assertion `left == right` failed
+  left: 3
+ right: 4
+
+---- tests::legacy stdout ----
+thread 'tests::legacy' panicked at 'boom', src/legacy.rs:4:5
+
+failures:
+    tests::adds
+    tests::legacy
+
+test result: FAILED. 1 passed; 2 failed; 0 ignored
+error: test failed, to rerun pass `--lib`
+";
+
+    const CARGO_CHECK: &str = "\
+   Compiling app v0.1.0
+error[E0308]: mismatched types
+ --> src/main.rs:4:18
+  |
+4 |     let x: u32 = \"nope\";
+  |            ---   ^^^^^^ expected `u32`, found `&str`
+
+error: cannot find macro `printn` in this scope
+ --> src/main.rs:5:5
+
+error: could not compile `app` (bin \"app\") due to 2 previous errors
+";
+
+    const PYTEST: &str = "\
+============================= FAILURES =============================
+_____________________________ test_add _____________________________
+
+    def test_add():
+>       assert add(1, 1) == 3
+E       assert 2 == 3
+
+tests/test_math.py:5: AssertionError
+_______________________ TestCalc.test_divide _______________________
+
+tests/test_calc.py:12: in test_divide
+    divide(1, 0)
+app/calc.py:3: ZeroDivisionError
+===================== short test summary info ======================
+FAILED tests/test_math.py::test_add - assert 2 == 3
+FAILED tests/test_calc.py::TestCalc::test_divide - ZeroDivisionError: division by zero
+==================== 2 failed, 4 passed in 0.12s ===================
+";
+
+    const JEST: &str = "\
+FAIL src/sum.test.js
+  ● math › adds numbers
+
+    expect(received).toBe(expected) // Object.is equality
+
+    Expected: 4
+    Received: 3
+
+      10 | test('adds numbers', () => {
+    > 11 |   expect(sum(1, 2)).toBe(4);
+
+      at Object.<anonymous> (src/sum.test.js:11:21)
+
+Test Suites: 1 failed, 1 total
+";
+
+    #[test]
+    fn cargo_test_failures_carry_panic_location_and_message() {
+        let failures = parse_verify_failures(CARGO_TEST);
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].kind, VerifyFailureKind::Test);
+        assert_eq!(failures[0].name, "tests::adds");
+        assert_eq!(failures[0].location.as_deref(), Some("src/lib.rs:10:9"));
+        assert_eq!(
+            failures[0].message.as_deref(),
+            Some("assertion `left == right` failed")
+        );
+        assert_eq!(failures[1].name, "tests::legacy");
+        assert_eq!(failures[1].location.as_deref(), Some("src/legacy.rs:4:5"));
+        assert_eq!(failures[1].message.as_deref(), Some("boom"));
+    }
+
+    #[test]
+    fn cargo_compile_errors_keep_code_and_location() {
+        let failures = parse_verify_failures(CARGO_CHECK);
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].kind, VerifyFailureKind::Compile);
+        assert_eq!(failures[0].name, "E0308");
+        assert_eq!(failures[0].location.as_deref(), Some("src/main.rs:4:18"));
+        assert_eq!(failures[0].message.as_deref(), Some("mismatched types"));
+        assert_eq!(failures[1].name, "error");
+        assert_eq!(failures[1].location.as_deref(), Some("src/main.rs:5:5"));
+    }
+
+    #[test]
+    fn pytest_summary_lines_pick_up_section_locations() {
+        let failures = parse_verify_failures(PYTEST);
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].name, "tests/test_math.py::test_add");
+        assert_eq!(
+            failures[0].location.as_deref(),
+            Some("tests/test_math.py:5")
+        );
+        assert_eq!(failures[0].message.as_deref(), Some("assert 2 == 3"));
+        assert_eq!(
+            failures[1].name,
+            "tests/test_calc.py::TestCalc::test_divide"
+        );
+        assert_eq!(failures[1].location.as_deref(), Some("app/calc.py:3"));
+    }
+
+    #[test]
+    fn jest_failures_use_the_first_frame_outside_node_modules() {
+        let failures = parse_verify_failures(JEST);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].name, "math › adds numbers");
+        assert_eq!(
+            failures[0].location.as_deref(),
+            Some("src/sum.test.js:11:21")
+        );
+        assert_eq!(
+            failures[0].message.as_deref(),
+            Some("expect(received).toBe(expected) // Object.is equality")
+        );
+    }
+
+    #[test]
+    fn unknown_output_yields_no_failures() {
+        assert!(parse_verify_failures("make: *** [all] Error 2\nsomething broke\n").is_empty());
+        assert_eq!(summarize_failures(&[], 3), None);
+    }
+
+    #[test]
+    fn summary_lists_the_first_failures_and_counts_the_rest() {
+        let failures = parse_verify_failures(CARGO_TEST);
+        assert_eq!(
+            summarize_failures(&failures, 1).as_deref(),
+            Some("2 failures: test tests::adds (src/lib.rs:10:9); +1 more")
+        );
+        assert_eq!(
+            failures[1].summary(),
+            "test tests::legacy (src/legacy.rs:4:5): boom"
+        );
+    }
+}
@@ -1,7 +1,15 @@
 //! MVP verification - simplified to run a single command.
 
+pub mod bounded;
+pub mod discover;
 pub mod error;
+pub mod failures;
 pub mod runner;
+pub mod tiers;
 
+pub use bounded::*;
+pub use discover::*;
 pub use error::*;
+pub use failures::*;
 pub use runner::*;
+pub use tiers::*;
@@ -1,12 +1,16 @@
 //! MVP verify runner - runs a single verification command.
 
 use std::path::Path;
-use std::process::Command;
 use std::time::Instant;
 
 use orch_core::config::RepoConfig;
 
+use crate::bounded::run_bounded;
 use crate::error::VerifyError;
+use crate::failures::{parse_verify_failures, VerifyFailureDetail};
+
+/// Limits tier used for a single worktree verify.
+const BRANCH_TIER: &str = "branch";
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct VerifyResult {
@@ -16,6 +20,12 @@ pub struct VerifyResult {
     pub stderr: String,
     pub exit_code: Option<i32>,
     pub duration_ms: u64,
+    /// The command was killed after `verify.timeout_secs`; output holds what
+    /// was captured until then.
+    pub timed_out: bool,
+    /// Failing tests and compiler errors parsed from a failed run's output;
+    /// empty on success or when the output format is not recognized.
+    pub failures: Vec<VerifyFailureDetail>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -28,7 +38,8 @@ pub struct MultiVerifyResult {
 /// Run the verification command for a repo.
 ///
 /// When `repo_config.nix.dev_shell` is non-empty the command is wrapped so it
-/// executes inside the nix dev shell, ensuring cargo/rustc are on PATH.
+/// executes inside the nix dev shell, ensuring cargo/rustc are on PATH. The
+/// run is bounded by the `branch` tier of `repo_config.verify` limits.
 pub fn run_verify(
     repo_config: &RepoConfig,
     worktree_path: &Path,
@@ -43,42 +54,43 @@ pub fn run_verify(
             stderr: String::new(),
             exit_code: Some(0),
             duration_ms: 0,
+            timed_out: false,
+            failures: Vec::new(),
         });
     }
 
     let effective_command = repo_config.nix.wrap_command(command);
 
+    let limits = repo_config.verify.limits.for_tier(BRANCH_TIER);
     let start = Instant::now();
-    let output = Command::new("bash")
-        .arg("-lc")
-        .arg(&effective_command)
-        .current_dir(worktree_path)
-        .output()
-        .map_err(|source| VerifyError::Io {
+    let output = run_bounded(&effective_command, worktree_path, limits).map_err(|source| {
+        VerifyError::Io {
             command: command.clone(),
             source,
-        })?;
-    let duration_ms = start.elapsed().as_millis() as u64;
-
-    let stdout = String::from_utf8(output.stdout).map_err(|source| VerifyError::NonUtf8Output {
-        command: command.clone(),
-        stream: "stdout",
-        source,
+        }
     })?;
+    let duration_ms = start.elapsed().as_millis() as u64;
 
-    let stderr = String::from_utf8(output.stderr).map_err(|source| VerifyError::NonUtf8Output {
-        command: command.clone(),
-        stream: "stderr",
-        source,
-    })?;
+    let mut failures = Vec::new();
+    if output.timed_out {
+        failures.push(VerifyFailureDetail::timeout(limits.timeout_secs));
+    }
+    if !output.success {
+        failures.extend(parse_verify_failures(&format!(
+            "{}\n{}",
+            output.stdout, output.stderr
+        )));
+    }
 
     Ok(VerifyResult {
-        success: output.status.success(),
+        success: output.success,
         command: command.clone(),
-        stdout,
-        stderr,
-        exit_code: output.status.code(),
+        stdout: output.stdout,
+        stderr: output.stderr,
+        exit_code: output.exit_code,
         duration_ms,
+        timed_out: output.timed_out,
+        failures,
     })
 }
 
@@ -100,6 +112,8 @@ pub fn run_multi_verify(
             },
             verify: orch_core::config::VerifyConfig {
                 command: cmd.clone(),
+                fast_commands: Vec::new(),
+                limits: Default::default(),
             },
             graphite: orch_core::config::RepoGraphiteConfig {
                 draft_on_start: false,
@@ -126,7 +140,9 @@ pub fn run_multi_verify(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use orch_core::config::{NixConfig, RepoGraphiteConfig, VerifyConfig};
+    use orch_core::config::{
+        NixConfig, RepoGraphiteConfig, VerifyConfig, VerifyLimitsConfig, VerifyTierLimits,
+    };
     use orch_core::types::SubmitMode;
     use std::path::PathBuf;
 
@@ -140,6 +156,8 @@ mod tests {
             },
             verify: VerifyConfig {
                 command: verify_command.to_string(),
+                fast_commands: Vec::new(),
+                limits: VerifyLimitsConfig::default(),
             },
             graphite: RepoGraphiteConfig {
                 draft_on_start: false,
@@ -164,6 +182,39 @@ mod tests {
         assert_eq!(result.exit_code, Some(1));
     }
 
+    #[test]
+    fn run_verify_parses_failures_from_failed_output() {
+        let config = mk_repo_config(
+            "printf 'test tests::adds ... FAILED\\nerror[E0425]: cannot find value `x`\\n --> src/lib.rs:2:5\\n' && false",
+        );
+        let result = run_verify(&config, Path::new("/tmp")).expect("run verify");
+        assert!(!result.success);
+        let names: Vec<&str> = result.failures.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["tests::adds", "E0425"]);
+
+        let config = mk_repo_config("echo 'something odd' && false");
+        let result = run_verify(&config, Path::new("/tmp")).expect("run verify");
+        assert!(result.failures.is_empty());
+    }
+
+    #[test]
+    fn run_verify_times_out_with_the_branch_tier_limit() {
+        let mut config = mk_repo_config("echo partial; sleep 30");
+        config.verify.limits.tiers.insert(
+            "branch".to_string(),
+            VerifyTierLimits {
+                timeout_secs: Some(5),
+                max_output_bytes: None,
+            },
+        );
+        let result = run_verify(&config, Path::new("/tmp")).expect("run verify");
+        assert!(!result.success);
+        assert!(result.timed_out);
+        assert!(result.stdout.contains("partial"));
+        assert_eq!(result.failures, vec![VerifyFailureDetail::timeout(5)]);
+        assert_eq!(result.failures[0].label(), "timed out after 5s");
+    }
+
     #[test]
     fn run_verify_captures_output() {
         let config = mk_repo_config("echo hello && echo world >&2");
@@ -238,6 +289,8 @@ mod tests {
             },
             verify: VerifyConfig {
                 command: "true".to_string(),
+                fast_commands: Vec::new(),
+                limits: VerifyLimitsConfig::default(),
             },
             graphite: RepoGraphiteConfig {
                 draft_on_start: false,
@@ -0,0 +1,335 @@
+//! Verify tiers: a fast check after each agent epoch and the full run before
+//! a task moves on.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use orch_core::shell::shell_quote;
+
+use crate::discover::{discover_verify_commands, has_npm_script, npm_runner};
+
+const JS_EXTENSIONS: [&str; 6] = ["js", "jsx", "ts", "tsx", "mjs", "cjs"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VerifyTier {
+    /// Format, lint and targeted tests for the files an epoch changed.
+    Fast,
+    /// The repo's full verify command.
+    Full,
+}
+
+impl VerifyTier {
+    /// Key for this tier's limits under `[verify.tiers]`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Fast => "fast",
+            Self::Full => "full",
+        }
+    }
+}
+
+/// Commands to run for `tier` in `repo_root`.
+///
+/// Non-empty `configured` commands win. Otherwise `Full` takes the most
+/// specific discovered verify command and `Fast` builds per-language checks
+/// scoped to `changed_files` (repo-relative). The fast tier has nothing to
+/// check when no files changed.
+pub fn commands_for_tier(
+    tier: VerifyTier,
+    repo_root: &Path,
+    configured: &[String],
+    changed_files: &[PathBuf],
+) -> Vec<String> {
+    if tier == VerifyTier::Fast && changed_files.is_empty() {
+        return Vec::new();
+    }
+    let configured = configured
+        .iter()
+        .map(|command| command.trim())
+        .filter(|command| !command.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    if !configured.is_empty() {
+        return configured;
+    }
+    match tier {
+        VerifyTier::Full => discover_verify_commands(repo_root)
+            .into_iter()
+            .take(1)
+            .collect(),
+        VerifyTier::Fast => fast_defaults(repo_root, changed_files),
+    }
+}
+
+fn fast_defaults(repo_root: &Path, changed_files: &[PathBuf]) -> Vec<String> {
+    let has = |name: &str| repo_root.join(name).exists();
+    let with_extension = |extensions: &[&str]| {
+        changed_files
+            .iter()
+            .filter(|path| {
+                path.extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| extensions.contains(&ext))
+            })
+            .collect::<Vec<_>>()
+    };
+    // Deleted files can still be named by a diff; only existing ones are
+    // passed to tools by path.
+    let existing = |paths: &[&PathBuf]| {
+        paths
+            .iter()
+            .filter(|path| repo_root.join(path).is_file())
+            .map(|path| quote_path(path))
+            .collect::<Vec<_>>()
+    };
+    let mut commands = Vec::new();
+
+    if has("Cargo.toml") {
+        let mut rust_files = with_extension(&["rs"]);
+        rust_files.extend(
+            changed_files
+                .iter()
+                .filter(|path| path.file_name().is_some_and(|name| name == "Cargo.toml")),
+        );
+        if !rust_files.is_empty() {
+            let scope = cargo_scope(repo_root, &rust_files);
+            commands.push("cargo fmt --all -- --check".to_string());
+            commands.push(format!("cargo clippy {scope} --all-targets -- -D warnings"));
+            commands.push(format!("cargo test {scope}"));
+        }
+    }
+
+    if has("go.mod") {
+        let go_files = with_extension(&["go"]);
+        let files = existing(&go_files);
+        if !files.is_empty() {
+            let packages = go_files
+                .iter()
+                .map(|path| go_package(path))
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect::<Vec<_>>()
+                .join(" ");
+            commands.push(format!("test -z \"$(gofmt -l {})\"", files.join(" ")));
+            commands.push(format!("go vet {packages}"));
+            commands.push(format!("go test {packages}"));
+        }
+    }
+
+    if has("pyproject.toml") || has("setup.py") || has("pytest.ini") {
+        let py_files = with_extension(&["py"]);
+        let files = existing(&py_files);
+        if !files.is_empty() {
+            commands.push(format!("python -m py_compile {}", files.join(" ")));
+            let tests = py_files
+                .iter()
+                .copied()
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.starts_with("test_") || name.ends_with("_test.py"))
+                })
+                .collect::<Vec<_>>();
+            let tests = existing(&tests);
+            if !tests.is_empty() {
+                commands.push(format!("pytest {}", tests.join(" ")));
+            }
+        }
+    }
+
+    if has_npm_script(repo_root, "lint") && !with_extension(&JS_EXTENSIONS).is_empty() {
+        commands.push(format!("{} run lint", npm_runner(repo_root)));
+    }
+
+    commands
+}
+
+/// `-p` flags for the crates owning `files`, or `--workspace` when any file
+/// falls outside a package (e.g. the root workspace manifest).
+fn cargo_scope(repo_root: &Path, files: &[&PathBuf]) -> String {
+    let mut packages = BTreeSet::new();
+    for file in files {
+        match owning_package(repo_root, file) {
+            Some(name) => packages.insert(name),
+            None => return "--workspace".to_string(),
+        };
+    }
+    packages
+        .iter()
+        .map(|name| format!("-p {name}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Name of the nearest package manifest above `file`, without leaving
+/// `repo_root`.
+fn owning_package(repo_root: &Path, file: &Path) -> Option<String> {
+    let mut dir = file.parent();
+    while let Some(current) = dir {
+        if let Ok(manifest) = fs::read_to_string(repo_root.join(current).join("Cargo.toml")) {
+            if let Some(name) = package_name(&manifest) {
+                return Some(name);
+            }
+        }
+        dir = current.parent();
+    }
+    None
+}
+
+/// `name` from the `[package]` table of a Cargo manifest.
+fn package_name(manifest: &str) -> Option<String> {
+    let mut in_package = false;
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+            continue;
+        }
+        if !in_package {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if key.trim() == "name" {
+            let name = value.trim().trim_matches('"');
+            return (!name.is_empty()).then(|| name.to_string());
+        }
+    }
+    None
+}
+
+/// The `./dir` package path holding a Go file.
+fn go_package(file: &Path) -> String {
+    match file.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        Some(dir) => quote_path(&Path::new(".").join(dir)),
+        None => ".".to_string(),
+    }
+}
+
+/// `path` for a command line, quoted only when it needs to be.
+fn quote_path(path: &Path) -> String {
+    let text = path.display().to_string();
+    let safe = text
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "/._-+".contains(c));
+    if safe {
+        text
+    } else {
+        shell_quote(&text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{commands_for_tier, VerifyTier};
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn repo(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "othala-verify-tiers-{name}-{}-{}",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        for (path, content) in files {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+            fs::write(path, content).expect("write file");
+        }
+        root
+    }
+
+    fn paths(paths: &[&str]) -> Vec<PathBuf> {
+        paths.iter().map(PathBuf::from).collect()
+    }
+
+    #[test]
+    fn configured_commands_win_and_fast_skips_an_unchanged_tree() {
+        let root = repo("configured", &[("Cargo.toml", "[workspace]\n")]);
+        let configured = vec!["make lint".to_string(), "  ".to_string()];
+        let changed = paths(&["src/lib.rs"]);
+
+        assert_eq!(
+            commands_for_tier(VerifyTier::Fast, &root, &configured, &changed),
+            vec!["make lint"]
+        );
+        assert!(commands_for_tier(VerifyTier::Fast, &root, &configured, &[]).is_empty());
+        assert_eq!(
+            commands_for_tier(VerifyTier::Full, &root, &[], &[]),
+            vec!["cargo check && cargo test --workspace"]
+        );
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn rust_defaults_target_the_crates_owning_changed_files() {
+        let root = repo(
+            "rust",
+            &[
+                ("Cargo.toml", "[workspace]\nmembers = [\"crates/*\"]\n"),
+                (
+                    "crates/alpha/Cargo.toml",
+                    "[package]\nname = \"alpha\"\nversion = \"0.1.0\"\n\n[dependencies]\nname = \"x\"\n",
+                ),
+                ("crates/alpha/src/lib.rs", ""),
+                ("crates/beta/Cargo.toml", "[package]\nname = \"beta\"\n"),
+                ("crates/beta/tests/it.rs", ""),
+            ],
+        );
+        let changed = paths(&[
+            "crates/beta/tests/it.rs",
+            "crates/alpha/src/lib.rs",
+            "README.md",
+        ]);
+        assert_eq!(
+            commands_for_tier(VerifyTier::Fast, &root, &[], &changed),
+            vec![
+                "cargo fmt --all -- --check",
+                "cargo clippy -p alpha -p beta --all-targets -- -D warnings",
+                "cargo test -p alpha -p beta",
+            ]
+        );
+
+        let changed = paths(&["Cargo.toml", "crates/alpha/src/lib.rs"]);
+        assert_eq!(
+            commands_for_tier(VerifyTier::Fast, &root, &[], &changed)[2],
+            "cargo test --workspace"
+        );
+        assert!(commands_for_tier(VerifyTier::Fast, &root, &[], &paths(&["README.md"])).is_empty());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn go_and_python_defaults_scope_to_changed_packages_and_tests() {
+        let root = repo(
+            "go-python",
+            &[
+                ("go.mod", "module example\n"),
+                ("main.go", "package main\n"),
+                ("pkg/api/api.go", "package api\n"),
+                ("pyproject.toml", ""),
+                ("tools/gen.py", ""),
+                ("tests/test_gen.py", ""),
+            ],
+        );
+        let changed = paths(&[
+            "main.go",
+            "pkg/api/api.go",
+            "tools/gen.py",
+            "tests/test_gen.py",
+            "tools/removed.py",
+        ]);
+        assert_eq!(
+            commands_for_tier(VerifyTier::Fast, &root, &[], &changed),
+            vec![
+                "test -z \"$(gofmt -l main.go pkg/api/api.go)\"",
+                "go vet . ./pkg/api",
+                "go test . ./pkg/api",
+                "python -m py_compile tools/gen.py tests/test_gen.py",
+                "pytest tests/test_gen.py",
+            ]
+        );
+        let _ = fs::remove_dir_all(&root);
+    }
+}
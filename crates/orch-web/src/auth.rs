@@ -0,0 +1,120 @@
+//! Bearer-token authentication for the web API.
+//!
+//! Every route except `/api/v1/health` requires `Authorization: Bearer
+//! <token>`; stream routes also accept `?access_token=<token>` because
+//! `EventSource` cannot set headers. Without a configured token only health
+//! is served.
+
+use orch_core::secret::constant_time_eq;
+
+use crate::handler::ErrorBody;
+use crate::request::HttpRequest;
+use crate::response::{HttpResponse, json_response};
+
+/// Routes reachable without a token.
+pub const AUTH_EXEMPT_PATHS: &[&str] = &["/api/v1/health"];
+
+/// Query parameter carrying the token for SSE clients.
+pub const TOKEN_QUERY_PARAM: &str = "access_token";
+
+/// Check `request` against the configured token.
+///
+/// `protected` marks mutating routes, which are never exempt, and
+/// `allow_query_token` lets stream routes read the token from the query.
+pub fn authorize(
+    request: &HttpRequest,
+    token: Option<&str>,
+    protected: bool,
+    allow_query_token: bool,
+) -> Result<(), HttpResponse> {
+    if !protected && AUTH_EXEMPT_PATHS.contains(&request.path.as_str()) {
+        return Ok(());
+    }
+    let Some(expected) = token else {
+        return Err(unauthorized("web auth token not configured; set ui.web_auth_token"));
+    };
+
+    let presented = bearer_token(request).or_else(|| {
+        allow_query_token
+            .then(|| request.query_params.get(TOKEN_QUERY_PARAM).map(String::as_str))
+            .flatten()
+    });
+    match presented {
+        Some(presented) if constant_time_eq(presented.as_bytes(), expected.as_bytes()) => Ok(()),
+        Some(_) => Err(unauthorized("invalid bearer token")),
+        None => Err(unauthorized("missing bearer token")),
+    }
+}
+
+/// Token from an `Authorization: Bearer <token>` header.
+pub fn bearer_token(request: &HttpRequest) -> Option<&str> {
+    let value = request.headers.get("authorization")?;
+    let (scheme, token) = value.split_once(' ')?;
+    scheme.eq_ignore_ascii_case("bearer").then(|| token.trim())
+}
+
+pub fn unauthorized(message: &str) -> HttpResponse {
+    let mut response = json_response(
+        401,
+        &ErrorBody { error: message.to_string(), code: Some("unauthorized".to_string()), version: None },
+    );
+    response.headers.insert("WWW-Authenticate".to_string(), "Bearer".to_string());
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::request::HttpMethod;
+
+    use super::*;
+
+    fn request(path: &str, header: Option<&str>, query_token: Option<&str>) -> HttpRequest {
+        let mut headers = HashMap::new();
+        if let Some(value) = header {
+            headers.insert("authorization".to_string(), value.to_string());
+        }
+        let mut query_params = HashMap::new();
+        if let Some(token) = query_token {
+            query_params.insert(TOKEN_QUERY_PARAM.to_string(), token.to_string());
+        }
+        HttpRequest { method: HttpMethod::GET, path: path.to_string(), query_params, headers, body: None }
+    }
+
+    #[test]
+    fn accepts_matching_bearer_token() {
+        let req = request("/api/v1/tasks", Some("Bearer s3cret"), None);
+        assert!(authorize(&req, Some("s3cret"), false, false).is_ok());
+        assert!(authorize(&req, Some("s3cret"), true, false).is_ok());
+    }
+
+    #[test]
+    fn rejects_missing_or_wrong_token_with_unauthorized_code() {
+        for header in [None, Some("Bearer nope"), Some("Basic s3cret")] {
+            let req = request("/api/v1/tasks", header, None);
+            let response = authorize(&req, Some("s3cret"), false, false).expect_err("rejected");
+            assert_eq!(response.status_code, 401);
+            let body: serde_json::Value = serde_json::from_str(&response.body).expect("json body");
+            assert_eq!(body["code"], "unauthorized");
+        }
+    }
+
+    #[test]
+    fn health_is_exempt_and_query_token_only_for_streams() {
+        assert!(authorize(&request("/api/v1/health", None, None), Some("s3cret"), false, false).is_ok());
+
+        let sse = request("/api/v1/events/stream", None, Some("s3cret"));
+        assert!(authorize(&sse, Some("s3cret"), false, true).is_ok());
+        assert!(authorize(&sse, Some("s3cret"), false, false).is_err());
+    }
+
+    #[test]
+    fn only_health_is_served_when_no_token_configured() {
+        assert!(authorize(&request("/api/v1/health", None, None), None, false, false).is_ok());
+        for (path, protected) in [("/api/v1/tasks", false), ("/api/v1/merge-queue/enqueue", true)] {
+            let req = request(path, Some("Bearer anything"), None);
+            assert_eq!(authorize(&req, None, protected, false).expect_err("refused").status_code, 401);
+        }
+    }
+}
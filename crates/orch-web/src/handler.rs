@@ -2,17 +2,28 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 
 use chrono::Utc;
+use orch_core::events::{Event, EventKind};
+use orch_core::types::{EventId, Task, TaskId};
+use orchd::cli::stats;
+use orchd::daemon_control::{DaemonControlAction, DaemonControlError, request_daemon_action};
+use orchd::event_log::JsonlEventLog;
+use orchd::merge_queue::{self, MergeQueueError};
+use orchd::persistence::SqliteStore;
+use orchd::state_machine::task_state_tag;
+use orchd::types::{AgentQuestion, MergeQueueSnapshot};
 use serde::{Deserialize, Serialize};
 
 use crate::request::HttpRequest;
 use crate::response::{HttpResponse, error_response, json_response};
 use crate::router::PathParams;
+use crate::stream::EventBroadcaster;
 
 #[derive(Debug, Clone)]
 pub struct ApiState {
     pub sqlite_path: PathBuf,
     pub event_log_root: PathBuf,
     pub repo_root: PathBuf,
+    pub events: EventBroadcaster,
 }
 
 impl ApiState {
@@ -21,6 +32,7 @@ impl ApiState {
             sqlite_path,
             event_log_root,
             repo_root,
+            events: EventBroadcaster::new(),
         }
     }
 }
@@ -31,6 +43,7 @@ impl Default for ApiState {
             sqlite_path: PathBuf::from(".orch/state.sqlite"),
             event_log_root: PathBuf::from(".orch/events"),
             repo_root: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+            events: EventBroadcaster::new(),
         }
     }
 }
@@ -47,6 +60,29 @@ struct ApiTask {
     updated_at: chrono::DateTime<Utc>,
 }
 
+impl From<&Task> for ApiTask {
+    fn from(task: &Task) -> Self {
+        Self {
+            id: task.id.0.clone(),
+            repo_id: task.repo_id.0.clone(),
+            title: task.title.clone(),
+            state: task_state_tag(task.state).to_ascii_lowercase(),
+            preferred_model: task.preferred_model.map(|model| model.as_str().to_string()),
+            priority: task.priority.to_string(),
+            created_at: task.created_at,
+            updated_at: task.updated_at,
+        }
+    }
+}
+
+/// Single-task view: the task plus the questions its agent is waiting on.
+#[derive(Debug, Clone, Serialize)]
+struct ApiTaskDetail {
+    #[serde(flatten)]
+    task: ApiTask,
+    open_questions: Vec<AgentQuestion>,
+}
+
 #[derive(Debug, Deserialize)]
 struct CreateTaskRequest {
     repo: String,
@@ -88,15 +124,28 @@ pub fn handle_list_tasks(_request: &HttpRequest, _state: &ApiState, _params: &Pa
     json_response(200, &sample_tasks())
 }
 
-pub fn handle_get_task(_request: &HttpRequest, _state: &ApiState, params: &PathParams) -> HttpResponse {
+pub fn handle_get_task(_request: &HttpRequest, state: &ApiState, params: &PathParams) -> HttpResponse {
     let Some(task_id) = params.get("id") else {
         return error_response(400, "missing task id");
     };
 
-    match sample_tasks().into_iter().find(|task| task.id == *task_id) {
-        Some(task) => json_response(200, &task),
-        None => error_response(404, &format!("task '{task_id}' not found")),
-    }
+    let store = open_existing_store(state);
+    let task_ref = TaskId::new(task_id.as_str());
+    let stored = store
+        .as_ref()
+        .and_then(|store| store.load_task(&task_ref).ok().flatten())
+        .map(|task| ApiTask::from(&task));
+    let Some(task) = stored.or_else(|| sample_tasks().into_iter().find(|task| task.id == *task_id)) else {
+        return error_response(404, &format!("task '{task_id}' not found"));
+    };
+    let open_questions = store
+        .and_then(|store| store.list_questions_for_task(&task_ref).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(AgentQuestion::is_open)
+        .collect();
+
+    json_response(200, &ApiTaskDetail { task, open_questions })
 }
 
 pub fn handle_create_task(request: &HttpRequest, _state: &ApiState, _params: &PathParams) -> HttpResponse {
@@ -160,13 +209,20 @@ pub fn handle_task_events(_request: &HttpRequest, _state: &ApiState, params: &Pa
     json_response(200, &events)
 }
 
-pub fn handle_stats(_request: &HttpRequest, _state: &ApiState, _params: &PathParams) -> HttpResponse {
+pub fn handle_stats(_request: &HttpRequest, state: &ApiState, _params: &PathParams) -> HttpResponse {
     let tasks = sample_tasks();
     let events = sample_events();
     let sessions = sample_sessions();
 
     let stopped_count = tasks.iter().filter(|task| task.state == "stopped").count();
     let ready_count = tasks.iter().filter(|task| task.state == "ready").count();
+    let failure_causes = open_existing_store(state)
+        .and_then(|store| {
+            let tasks = store.list_tasks().ok()?;
+            let failures = stats::failure_events(&store).ok()?;
+            Some(stats::failure_causes(&tasks, &failures, Utc::now(), stats::DEFAULT_THROUGHPUT_DAYS))
+        })
+        .unwrap_or_default();
 
     json_response(
         200,
@@ -175,7 +231,8 @@ pub fn handle_stats(_request: &HttpRequest, _state: &ApiState, _params: &PathPar
             "ready_task_count": ready_count,
             "stopped_task_count": stopped_count,
             "event_count": events.len(),
-            "session_count": sessions.len()
+            "session_count": sessions.len(),
+            "failure_causes": failure_causes
         }),
     )
 }
@@ -223,6 +280,226 @@ pub fn handle_health(_request: &HttpRequest, state: &ApiState, _params: &PathPar
     )
 }
 
+/// Structured error payload: merge queue conflicts carry `version`, auth
+/// failures carry `code: "unauthorized"`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ErrorBody {
+    pub error: String,
+    /// Machine-readable error kind, e.g. `unauthorized`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MergeQueueResponse {
+    pub version: u64,
+    pub entries: Vec<MergeQueueEntryResponse>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MergeQueueEntryResponse {
+    pub task_id: String,
+    pub position: usize,
+    pub enqueued_at: chrono::DateTime<Utc>,
+}
+
+impl From<MergeQueueSnapshot> for MergeQueueResponse {
+    fn from(snapshot: MergeQueueSnapshot) -> Self {
+        Self {
+            version: snapshot.version,
+            entries: snapshot
+                .entries
+                .into_iter()
+                .map(|entry| MergeQueueEntryResponse {
+                    task_id: entry.task_id.0,
+                    position: entry.position,
+                    enqueued_at: entry.enqueued_at,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct EnqueueMergeRequest {
+    task_id: String,
+    expected_version: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct CancelMergeRequest {
+    expected_version: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReorderMergeQueueRequest {
+    order: Vec<String>,
+    expected_version: Option<u64>,
+}
+
+pub fn handle_get_merge_queue(_request: &HttpRequest, state: &ApiState, _params: &PathParams) -> HttpResponse {
+    with_merge_queue(state, merge_queue::snapshot)
+}
+
+pub fn handle_enqueue_merge(request: &HttpRequest, state: &ApiState, _params: &PathParams) -> HttpResponse {
+    let payload: EnqueueMergeRequest = match parse_json_body(request) {
+        Ok(payload) => payload,
+        Err(response) => return response,
+    };
+    let task_id = TaskId::new(payload.task_id.trim());
+    with_merge_queue(state, |store| merge_queue::enqueue(store, &task_id, payload.expected_version))
+}
+
+pub fn handle_cancel_merge(request: &HttpRequest, state: &ApiState, params: &PathParams) -> HttpResponse {
+    let Some(task_id) = params.get("task_id") else {
+        return error_response(400, "missing task id");
+    };
+    // The body is optional here; without one the cancel is unconditional.
+    let payload = match request.body.as_deref().map(str::trim) {
+        None | Some("") => CancelMergeRequest::default(),
+        Some(_) => match parse_json_body(request) {
+            Ok(payload) => payload,
+            Err(response) => return response,
+        },
+    };
+    let task_id = TaskId::new(task_id);
+    with_merge_queue(state, |store| merge_queue::cancel(store, &task_id, payload.expected_version))
+}
+
+pub fn handle_reorder_merge_queue(request: &HttpRequest, state: &ApiState, _params: &PathParams) -> HttpResponse {
+    let payload: ReorderMergeQueueRequest = match parse_json_body(request) {
+        Ok(payload) => payload,
+        Err(response) => return response,
+    };
+    let order: Vec<TaskId> = payload.order.iter().map(|id| TaskId::new(id.trim())).collect();
+    with_merge_queue(state, |store| merge_queue::reorder(store, &order, payload.expected_version))
+}
+
+/// Actor recorded for daemon control requests that do not name one.
+pub const DEFAULT_CONTROL_ACTOR: &str = "web-token";
+
+#[derive(Debug, Default, Deserialize)]
+struct DaemonControlRequest {
+    actor: Option<String>,
+}
+
+pub fn handle_daemon_pause(request: &HttpRequest, state: &ApiState, _params: &PathParams) -> HttpResponse {
+    daemon_control_response(request, state, DaemonControlAction::Pause)
+}
+
+pub fn handle_daemon_resume(request: &HttpRequest, state: &ApiState, _params: &PathParams) -> HttpResponse {
+    daemon_control_response(request, state, DaemonControlAction::Resume)
+}
+
+pub fn handle_daemon_reload_config(request: &HttpRequest, state: &ApiState, _params: &PathParams) -> HttpResponse {
+    daemon_control_response(request, state, DaemonControlAction::ReloadConfig)
+}
+
+pub fn handle_daemon_drain(request: &HttpRequest, state: &ApiState, _params: &PathParams) -> HttpResponse {
+    daemon_control_response(request, state, DaemonControlAction::Drain)
+}
+
+/// Forward `action` to the running daemon and record who asked for it.
+fn daemon_control_response(request: &HttpRequest, state: &ApiState, action: DaemonControlAction) -> HttpResponse {
+    // The body is optional; without an `actor` the token holder is recorded.
+    let payload = match request.body.as_deref().map(str::trim) {
+        None | Some("") => DaemonControlRequest::default(),
+        Some(_) => match parse_json_body(request) {
+            Ok(payload) => payload,
+            Err(response) => return response,
+        },
+    };
+    let actor = payload
+        .actor
+        .map(|actor| actor.trim().to_string())
+        .filter(|actor| !actor.is_empty())
+        .unwrap_or_else(|| DEFAULT_CONTROL_ACTOR.to_string());
+
+    let status = match request_daemon_action(&state.repo_root, action) {
+        Ok(status) => status,
+        Err(err @ DaemonControlError::NotRunning) => {
+            let code = Some("daemon_not_running".to_string());
+            return json_response(409, &ErrorBody { error: err.to_string(), code, version: None });
+        }
+        Err(err) => return error_response(500, &err.to_string()),
+    };
+
+    let now = Utc::now();
+    let event = Event {
+        id: EventId(format!(
+            "E-DAEMON-{}-{}",
+            action.as_str().to_ascii_uppercase(),
+            now.timestamp_nanos_opt().unwrap_or_default()
+        )),
+        task_id: None,
+        repo_id: None,
+        at: now,
+        kind: EventKind::DaemonControl { action: action.as_str().to_string(), actor },
+    };
+    if let Err(err) = record_control_event(state, &event) {
+        return error_response(500, &format!("daemon {} applied but audit event failed: {err}", action.as_str()));
+    }
+    state.events.publish_event(&event);
+
+    json_response(200, &status)
+}
+
+fn record_control_event(state: &ApiState, event: &Event) -> Result<(), String> {
+    let store = SqliteStore::open(&state.sqlite_path)
+        .and_then(|store| store.migrate().map(|_| store))
+        .map_err(|err| err.to_string())?;
+    store.append_event(event).map_err(|err| err.to_string())?;
+    JsonlEventLog::new(&state.event_log_root).append_both(event).map_err(|err| err.to_string())
+}
+
+fn parse_json_body<T: serde::de::DeserializeOwned>(request: &HttpRequest) -> Result<T, HttpResponse> {
+    let Some(raw) = request.body.as_deref() else {
+        return Err(error_response(400, "missing request body"));
+    };
+    serde_json::from_str(raw).map_err(|err| error_response(400, &format!("invalid json body: {err}")))
+}
+
+fn with_merge_queue(
+    state: &ApiState,
+    op: impl FnOnce(&SqliteStore) -> Result<MergeQueueSnapshot, MergeQueueError>,
+) -> HttpResponse {
+    let store = match SqliteStore::open(&state.sqlite_path).and_then(|store| store.migrate().map(|_| store)) {
+        Ok(store) => store,
+        Err(err) => return error_response(500, &format!("failed to open state store: {err}")),
+    };
+    match op(&store) {
+        Ok(snapshot) => json_response(200, &MergeQueueResponse::from(snapshot)),
+        Err(err) => {
+            let version = merge_queue::snapshot(&store).ok().map(|queue| queue.version);
+            json_response(merge_queue_error_status(&err), &ErrorBody { error: err.to_string(), code: None, version })
+        }
+    }
+}
+
+/// Open the state store only if it already exists; read-only views fall
+/// back to placeholder data instead of creating a database.
+fn open_existing_store(state: &ApiState) -> Option<SqliteStore> {
+    if !state.sqlite_path.is_file() {
+        return None;
+    }
+    let store = SqliteStore::open(&state.sqlite_path).ok()?;
+    store.migrate().ok()?;
+    Some(store)
+}
+
+fn merge_queue_error_status(err: &MergeQueueError) -> u16 {
+    match err {
+        MergeQueueError::TaskNotFound { .. } | MergeQueueError::NotQueued { .. } => 404,
+        MergeQueueError::NotAwaitingMerge { .. }
+        | MergeQueueError::AlreadyQueued { .. }
+        | MergeQueueError::VersionConflict { .. } => 409,
+        MergeQueueError::InvalidOrder { .. } => 400,
+        MergeQueueError::Persistence(_) => 500,
+    }
+}
+
 fn task_action_response(params: &HashMap<String, String>, state: &str) -> HttpResponse {
     let Some(task_id) = params.get("id") else {
         return error_response(400, "missing task id");
@@ -303,7 +580,18 @@ mod tests {
 
     use crate::request::{HttpMethod, HttpRequest};
 
-    use super::{ApiState, handle_create_task, handle_get_task, handle_health, handle_list_tasks};
+    use std::path::PathBuf;
+
+    use orch_core::events::EventKind;
+    use orch_core::state::TaskState;
+    use orch_core::types::{RepoId, Task, TaskId};
+    use orchd::persistence::SqliteStore;
+
+    use super::{
+        ApiState, handle_cancel_merge, handle_create_task, handle_daemon_pause, handle_daemon_resume,
+        handle_enqueue_merge, handle_get_task, handle_health, handle_list_tasks, handle_reorder_merge_queue,
+        handle_stats,
+    };
 
     fn request(method: HttpMethod, body: Option<&str>) -> HttpRequest {
         HttpRequest {
@@ -366,4 +654,174 @@ mod tests {
         assert!(value.get("sqlite_path").is_some());
         assert!(value.get("event_log_root").is_some());
     }
+
+    fn merge_queue_state(name: &str, tasks: &[(&str, TaskState)]) -> ApiState {
+        let dir = std::env::temp_dir().join(format!("othala-web-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        let sqlite_path = dir.join("state.sqlite");
+        let store = SqliteStore::open(&sqlite_path).expect("open store");
+        store.migrate().expect("migrate");
+        for (id, state) in tasks {
+            let mut task = Task::new(
+                TaskId::new(*id),
+                RepoId("othala".to_string()),
+                format!("Task {id}"),
+                PathBuf::from(format!(".orch/wt/{id}")),
+            );
+            task.state = *state;
+            store.upsert_task(&task).expect("upsert task");
+        }
+        ApiState::new(sqlite_path, dir.join("events"), dir)
+    }
+
+    fn json(response: &crate::response::HttpResponse) -> serde_json::Value {
+        serde_json::from_str(&response.body).expect("valid json")
+    }
+
+    #[test]
+    fn stats_ranks_failure_causes_from_the_store() {
+        let state = merge_queue_state("stats-failures", &[("T1", TaskState::Stopped)]);
+        let store = SqliteStore::open(&state.sqlite_path).expect("open store");
+        store
+            .append_event(&orch_core::events::Event {
+                id: orch_core::types::EventId("E-FAILED-T1-1".to_string()),
+                task_id: Some(TaskId::new("T1")),
+                repo_id: Some(RepoId("othala".to_string())),
+                at: chrono::Utc::now(),
+                kind: EventKind::TaskFailed { reason: "verify failed".to_string(), is_final: true, cause: None },
+            })
+            .expect("append failure");
+
+        let response = handle_stats(&request(HttpMethod::GET, None), &state, &HashMap::new());
+
+        assert_eq!(response.status_code, 200);
+        let value = json(&response);
+        assert_eq!(value["failure_causes"]["overall"][0]["cause"], "verify_failure");
+        assert_eq!(value["failure_causes"]["by_repo"]["othala"][0]["count"], 1);
+    }
+
+    #[test]
+    fn get_task_includes_open_questions() {
+        let state = merge_queue_state("task-questions", &[("T1", TaskState::Chatting)]);
+        let store = SqliteStore::open(&state.sqlite_path).expect("open store");
+        let lines = vec!["[needs_human] Which database should I target?".to_string()];
+        orchd::questions::capture_questions(&store, &TaskId::new("T1"), &lines, chrono::Utc::now())
+            .expect("capture question");
+        let mut params = HashMap::new();
+        params.insert("id".to_string(), "T1".to_string());
+
+        let response = handle_get_task(&request(HttpMethod::GET, None), &state, &params);
+
+        assert_eq!(response.status_code, 200);
+        let value = json(&response);
+        assert_eq!(value["state"], "chatting");
+        assert_eq!(value["open_questions"][0]["question_id"], "Q-T1-1");
+        assert_eq!(value["open_questions"][0]["question"], "Which database should I target?");
+    }
+
+    #[test]
+    fn enqueue_rejects_task_not_awaiting_merge() {
+        let state = merge_queue_state("enqueue-409", &[("T1", TaskState::Chatting)]);
+        let response = handle_enqueue_merge(
+            &request(HttpMethod::POST, Some(r#"{"task_id":"T1"}"#)),
+            &state,
+            &HashMap::new(),
+        );
+
+        assert_eq!(response.status_code, 409);
+        assert_eq!(response.status_text, "Conflict");
+        let value = json(&response);
+        assert!(value["error"].as_str().is_some_and(|e| e.contains("CHATTING")));
+        assert_eq!(value["version"], 0);
+    }
+
+    #[test]
+    fn merge_queue_enqueue_reorder_and_cancel_track_version() {
+        let state = merge_queue_state(
+            "queue-flow",
+            &[("T1", TaskState::AwaitingMerge), ("T2", TaskState::AwaitingMerge)],
+        );
+        for id in ["T1", "T2"] {
+            let body = format!(r#"{{"task_id":"{id}"}}"#);
+            let response = handle_enqueue_merge(&request(HttpMethod::POST, Some(&body)), &state, &HashMap::new());
+            assert_eq!(response.status_code, 200);
+        }
+
+        let stale = handle_reorder_merge_queue(
+            &request(HttpMethod::PUT, Some(r#"{"order":["T2","T1"],"expected_version":1}"#)),
+            &state,
+            &HashMap::new(),
+        );
+        assert_eq!(stale.status_code, 409);
+        assert_eq!(json(&stale)["version"], 2);
+
+        let reordered = handle_reorder_merge_queue(
+            &request(HttpMethod::PUT, Some(r#"{"order":["T2","T1"],"expected_version":2}"#)),
+            &state,
+            &HashMap::new(),
+        );
+        assert_eq!(reordered.status_code, 200);
+        let value = json(&reordered);
+        assert_eq!(value["version"], 3);
+        assert_eq!(value["entries"][0]["task_id"], "T2");
+
+        let mut params = HashMap::new();
+        params.insert("task_id".to_string(), "T2".to_string());
+        let cancelled = handle_cancel_merge(&request(HttpMethod::POST, None), &state, &params);
+        assert_eq!(cancelled.status_code, 200);
+        let value = json(&cancelled);
+        assert_eq!(value["version"], 4);
+        assert_eq!(value["entries"][0]["task_id"], "T1");
+        assert_eq!(value["entries"][0]["position"], 0);
+
+        let missing = handle_cancel_merge(&request(HttpMethod::POST, None), &state, &params);
+        assert_eq!(missing.status_code, 404);
+    }
+
+    #[test]
+    fn daemon_control_conflicts_when_no_daemon_is_running() {
+        let state = merge_queue_state("daemon-none", &[]);
+        let response = handle_daemon_pause(&request(HttpMethod::POST, None), &state, &HashMap::new());
+
+        assert_eq!(response.status_code, 409);
+        assert_eq!(json(&response)["code"], "daemon_not_running");
+    }
+
+    #[test]
+    fn daemon_pause_returns_status_and_records_actor() {
+        let state = merge_queue_state("daemon-pause", &[]);
+        orchd::chat_control::write_daemon_pid(&state.repo_root).expect("write pid");
+
+        let paused = handle_daemon_pause(
+            &request(HttpMethod::POST, Some(r#"{"actor":"ops@example.com"}"#)),
+            &state,
+            &HashMap::new(),
+        );
+        assert_eq!(paused.status_code, 200);
+        assert_eq!(json(&paused)["paused"], true);
+        assert_eq!(json(&paused)["running"], true);
+
+        let resumed = handle_daemon_resume(&request(HttpMethod::POST, None), &state, &HashMap::new());
+        assert_eq!(json(&resumed)["paused"], false);
+
+        let store = SqliteStore::open(&state.sqlite_path).expect("open store");
+        let actors: Vec<(String, String)> = store
+            .list_events_global()
+            .expect("list events")
+            .into_iter()
+            .filter_map(|event| match event.kind {
+                EventKind::DaemonControl { action, actor } => Some((action, actor)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            actors,
+            vec![
+                ("pause".to_string(), "ops@example.com".to_string()),
+                ("resume".to_string(), super::DEFAULT_CONTROL_ACTOR.to_string()),
+            ]
+        );
+        orchd::chat_control::clear_daemon_pid(&state.repo_root);
+    }
 }
@@ -1,13 +1,17 @@
+pub mod auth;
 pub mod error;
 pub mod handler;
 pub mod request;
 pub mod response;
 pub mod router;
 pub mod server;
+pub mod stream;
 
+pub use auth::authorize;
 pub use error::WebError;
 pub use handler::ApiState;
 pub use request::{HttpMethod, HttpRequest, parse_request};
 pub use response::{HttpResponse, error_response, json_response, write_response};
-pub use router::{Route, RouteMatch, Router};
+pub use router::{Route, RouteMatch, Router, StreamRoute, StreamRouteMatch};
 pub use server::WebServer;
+pub use stream::{EventBroadcaster, WebEvent, WebEventKind, web_event_name};
@@ -1,13 +1,17 @@
 use std::path::PathBuf;
 
+use orch_core::config::load_org_config;
 use orch_web::handler::{
-    ApiState, handle_create_task, handle_delete_task, handle_get_session, handle_get_task,
-    handle_health, handle_list_events, handle_list_sessions, handle_list_skills, handle_list_tasks,
+    ApiState, handle_cancel_merge, handle_create_task, handle_daemon_drain, handle_daemon_pause,
+    handle_daemon_reload_config, handle_daemon_resume, handle_delete_task, handle_enqueue_merge,
+    handle_get_merge_queue, handle_get_session, handle_get_task, handle_health, handle_list_events,
+    handle_list_sessions, handle_list_skills, handle_list_tasks, handle_reorder_merge_queue,
     handle_resume_task, handle_stats, handle_stop_task, handle_task_events,
 };
 use orch_web::request::HttpMethod;
 use orch_web::router::Router;
 use orch_web::server::WebServer;
+use orch_web::stream::handle_event_stream;
 
 fn main() {
     let addr = std::env::args()
@@ -17,16 +21,25 @@ fn main() {
     let mut router = Router::new();
     router.add_route(HttpMethod::GET, "/api/v1/tasks", handle_list_tasks);
     router.add_route(HttpMethod::GET, "/api/v1/tasks/:id", handle_get_task);
-    router.add_route(HttpMethod::POST, "/api/v1/tasks", handle_create_task);
-    router.add_route(HttpMethod::DELETE, "/api/v1/tasks/:id", handle_delete_task);
-    router.add_route(HttpMethod::POST, "/api/v1/tasks/:id/stop", handle_stop_task);
-    router.add_route(HttpMethod::POST, "/api/v1/tasks/:id/resume", handle_resume_task);
+    router.add_protected_route(HttpMethod::POST, "/api/v1/tasks", handle_create_task);
+    router.add_protected_route(HttpMethod::DELETE, "/api/v1/tasks/:id", handle_delete_task);
+    router.add_protected_route(HttpMethod::POST, "/api/v1/tasks/:id/stop", handle_stop_task);
+    router.add_protected_route(HttpMethod::POST, "/api/v1/tasks/:id/resume", handle_resume_task);
+    router.add_stream_route(HttpMethod::GET, "/api/v1/events/stream", handle_event_stream);
     router.add_route(HttpMethod::GET, "/api/v1/events", handle_list_events);
     router.add_route(HttpMethod::GET, "/api/v1/events/:task_id", handle_task_events);
     router.add_route(HttpMethod::GET, "/api/v1/stats", handle_stats);
     router.add_route(HttpMethod::GET, "/api/v1/sessions", handle_list_sessions);
     router.add_route(HttpMethod::GET, "/api/v1/sessions/:id", handle_get_session);
     router.add_route(HttpMethod::GET, "/api/v1/skills", handle_list_skills);
+    router.add_route(HttpMethod::GET, "/api/v1/merge-queue", handle_get_merge_queue);
+    router.add_protected_route(HttpMethod::POST, "/api/v1/merge-queue/enqueue", handle_enqueue_merge);
+    router.add_protected_route(HttpMethod::PUT, "/api/v1/merge-queue/reorder", handle_reorder_merge_queue);
+    router.add_protected_route(HttpMethod::POST, "/api/v1/merge-queue/:task_id/cancel", handle_cancel_merge);
+    router.add_protected_route(HttpMethod::POST, "/api/v1/daemon/pause", handle_daemon_pause);
+    router.add_protected_route(HttpMethod::POST, "/api/v1/daemon/resume", handle_daemon_resume);
+    router.add_protected_route(HttpMethod::POST, "/api/v1/daemon/reload-config", handle_daemon_reload_config);
+    router.add_protected_route(HttpMethod::POST, "/api/v1/daemon/drain", handle_daemon_drain);
     router.add_route(HttpMethod::GET, "/api/v1/health", handle_health);
 
     let state = ApiState::new(
@@ -34,7 +47,11 @@ fn main() {
         PathBuf::from(".orch/events"),
         std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
     );
-    let server = WebServer::new(&addr).with_router(router).with_state(state);
+    let auth_token = web_auth_token();
+    if auth_token.is_none() {
+        eprintln!("warning: no ui.web_auth_token configured; only /api/v1/health is served");
+    }
+    let server = WebServer::new(&addr).with_router(router).with_state(state).with_auth_token(auth_token);
 
     println!("Othala web API listening on {addr}");
     if let Err(err) = server.run() {
@@ -42,3 +59,10 @@ fn main() {
         std::process::exit(1);
     }
 }
+
+/// `OTHALA_WEB_TOKEN` overrides `ui.web_auth_token` from `.othala/config.toml`.
+fn web_auth_token() -> Option<String> {
+    std::env::var("OTHALA_WEB_TOKEN").ok().filter(|token| !token.is_empty()).or_else(|| {
+        load_org_config(".othala/config.toml").ok().and_then(|config| config.ui.web_auth_token)
+    })
+}
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use crate::handler::ApiState;
 use crate::request::{HttpMethod, HttpRequest};
 use crate::response::HttpResponse;
+use crate::stream::StreamHandlerFn;
 
 pub type PathParams = HashMap<String, String>;
 pub type HandlerFn = fn(&HttpRequest, &ApiState, &PathParams) -> HttpResponse;
@@ -12,21 +13,61 @@ pub struct Route {
     pub path_pattern: String,
     pub method: HttpMethod,
     pub handler: HandlerFn,
+    /// Mutating route; never exempt from the auth token.
+    pub protected: bool,
+}
+
+/// A route whose handler owns the connection (e.g. server-sent events).
+#[derive(Debug, Clone)]
+pub struct StreamRoute {
+    pub path_pattern: String,
+    pub method: HttpMethod,
+    pub handler: StreamHandlerFn,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct Router {
     routes: Vec<Route>,
+    stream_routes: Vec<StreamRoute>,
 }
 
 pub struct RouteMatch {
     pub handler: HandlerFn,
     pub params: PathParams,
+    pub protected: bool,
+}
+
+pub struct StreamRouteMatch {
+    pub handler: StreamHandlerFn,
+    pub params: PathParams,
 }
 
 impl Router {
     pub fn new() -> Self {
-        Self { routes: Vec::new() }
+        Self {
+            routes: Vec::new(),
+            stream_routes: Vec::new(),
+        }
+    }
+
+    pub fn add_stream_route(&mut self, method: HttpMethod, path_pattern: &str, handler: StreamHandlerFn) {
+        self.stream_routes.push(StreamRoute {
+            path_pattern: path_pattern.to_string(),
+            method,
+            handler,
+        });
+    }
+
+    pub fn match_stream_route(&self, method: &HttpMethod, path: &str) -> Option<StreamRouteMatch> {
+        self.stream_routes
+            .iter()
+            .filter(|route| route.method == *method)
+            .find_map(|route| {
+                match_path(&route.path_pattern, path).map(|params| StreamRouteMatch {
+                    handler: route.handler,
+                    params,
+                })
+            })
     }
 
     pub fn add_route(&mut self, method: HttpMethod, path_pattern: &str, handler: HandlerFn) {
@@ -34,6 +75,17 @@ impl Router {
             path_pattern: path_pattern.to_string(),
             method,
             handler,
+            protected: false,
+        });
+    }
+
+    /// Register a mutating route that is only served to token holders.
+    pub fn add_protected_route(&mut self, method: HttpMethod, path_pattern: &str, handler: HandlerFn) {
+        self.routes.push(Route {
+            path_pattern: path_pattern.to_string(),
+            method,
+            handler,
+            protected: true,
         });
     }
 
@@ -47,6 +99,7 @@ impl Router {
                 return Some(RouteMatch {
                     handler: route.handler,
                     params,
+                    protected: route.protected,
                 });
             }
         }
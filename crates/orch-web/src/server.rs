@@ -1,6 +1,8 @@
 use std::net::{TcpListener, TcpStream};
+use std::thread;
 use std::time::Duration;
 
+use crate::auth::authorize;
 use crate::error::WebError;
 use crate::handler::ApiState;
 use crate::request::parse_request;
@@ -14,6 +16,7 @@ pub struct WebServer {
     state: ApiState,
     read_timeout: Duration,
     write_timeout: Duration,
+    auth_token: Option<String>,
 }
 
 impl WebServer {
@@ -24,6 +27,7 @@ impl WebServer {
             state: ApiState::default(),
             read_timeout: Duration::from_secs(5),
             write_timeout: Duration::from_secs(5),
+            auth_token: None,
         }
     }
 
@@ -37,14 +41,25 @@ impl WebServer {
         self
     }
 
+    /// Require `Authorization: Bearer <token>` on every route but health.
+    pub fn with_auth_token(mut self, token: Option<String>) -> Self {
+        self.auth_token = token.filter(|token| !token.is_empty());
+        self
+    }
+
     pub fn run(&self) -> Result<(), WebError> {
         let listener = TcpListener::bind(&self.addr)?;
         for stream in listener.incoming() {
             match stream {
                 Ok(stream) => {
-                    if let Err(err) = self.handle_connection(stream) {
-                        eprintln!("failed to handle connection: {err}");
-                    }
+                    // Each connection gets its own thread so long-lived event
+                    // streams don't block ordinary requests.
+                    let server = self.clone();
+                    thread::spawn(move || {
+                        if let Err(err) = server.handle_connection(stream) {
+                            eprintln!("failed to handle connection: {err}");
+                        }
+                    });
                 }
                 Err(err) => return Err(WebError::Io(err)),
             }
@@ -71,9 +86,20 @@ impl WebServer {
             }
         };
 
-        let response = match self.router.match_route(&request.method, &request.path) {
-            Some(route_match) => (route_match.handler)(&request, &self.state, &route_match.params),
-            None => error_response(404, "route not found"),
+        let token = self.auth_token.as_deref();
+        if let Some(route_match) = self.router.match_stream_route(&request.method, &request.path) {
+            if let Err(response) = authorize(&request, token, false, true) {
+                return write_response(&mut stream, &response);
+            }
+            return (route_match.handler)(&request, &self.state, &route_match.params, &mut stream);
+        }
+
+        let route_match = self.router.match_route(&request.method, &request.path);
+        let protected = route_match.as_ref().is_some_and(|route_match| route_match.protected);
+        let response = match (authorize(&request, token, protected, false), route_match) {
+            (Err(response), _) => response,
+            (Ok(()), Some(route_match)) => (route_match.handler)(&request, &self.state, &route_match.params),
+            (Ok(()), None) => error_response(404, "route not found"),
         };
 
         write_response(&mut stream, &response)
@@ -137,13 +163,13 @@ mod tests {
     #[test]
     fn run_once_returns_not_found_for_unknown_route() {
         let addr = free_address();
-        let server = WebServer::new(&addr.to_string());
+        let server = WebServer::new(&addr.to_string()).with_auth_token(Some("s3cret".to_string()));
 
         let handle = thread::spawn(move || server.run_once());
 
         let mut client = connect_with_retry(addr);
         client
-            .write_all(b"GET /unknown HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .write_all(b"GET /unknown HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer s3cret\r\n\r\n")
             .expect("write request");
 
         let mut response = String::new();
@@ -155,4 +181,148 @@ mod tests {
         assert!(result.is_ok());
         assert!(response.starts_with("HTTP/1.1 404 Not Found"));
     }
+
+    fn request_once(server: WebServer, addr: SocketAddr, raw: &str) -> String {
+        let handle = thread::spawn(move || server.run_once());
+        let mut client = connect_with_retry(addr);
+        client.write_all(raw.as_bytes()).expect("write request");
+        let mut response = String::new();
+        client.read_to_string(&mut response).expect("read response");
+        assert!(handle.join().expect("join thread").is_ok());
+        response
+    }
+
+    #[test]
+    fn auth_token_gates_routes_except_health() {
+        let mut router = Router::new();
+        router.add_route(HttpMethod::GET, "/api/v1/health", handle_health);
+        router.add_route(HttpMethod::GET, "/api/v1/stats", handle_health);
+        let server = |addr: SocketAddr| {
+            WebServer::new(&addr.to_string())
+                .with_router(router.clone())
+                .with_auth_token(Some("s3cret".to_string()))
+        };
+
+        let addr = free_address();
+        let response = request_once(server(addr), addr, "GET /api/v1/stats HTTP/1.1\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 401 Unauthorized"));
+        assert!(response.contains("\"code\":\"unauthorized\""));
+
+        let addr = free_address();
+        let response = request_once(
+            server(addr),
+            addr,
+            "GET /api/v1/stats HTTP/1.1\r\nAuthorization: Bearer s3cret\r\n\r\n",
+        );
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+
+        let addr = free_address();
+        let response = request_once(server(addr), addr, "GET /api/v1/health HTTP/1.1\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+    }
+
+    #[test]
+    fn routes_are_refused_without_configured_token() {
+        let mut router = Router::new();
+        router.add_route(HttpMethod::GET, "/api/v1/stats", handle_health);
+        router.add_protected_route(HttpMethod::POST, "/api/v1/tasks", handle_health);
+        for raw in [
+            "GET /api/v1/stats HTTP/1.1\r\n\r\n",
+            "POST /api/v1/tasks HTTP/1.1\r\nAuthorization: Bearer anything\r\n\r\n",
+        ] {
+            let addr = free_address();
+            let server = WebServer::new(&addr.to_string()).with_router(router.clone());
+            let response = request_once(server, addr, raw);
+            assert!(response.starts_with("HTTP/1.1 401 Unauthorized"), "{raw}: {response}");
+        }
+    }
+
+    #[test]
+    fn event_stream_receives_task_transition() {
+        use std::io::{BufRead, BufReader};
+
+        use chrono::Utc;
+        use orch_core::events::{Event, EventKind};
+        use orch_core::state::TaskState;
+        use orch_core::types::{EventId, RepoId, Task, TaskId};
+        use orchd::{JsonlEventLog, OrchdService, Scheduler, SchedulerConfig, SqliteStore};
+
+        use crate::stream::handle_event_stream;
+
+        let state = ApiState::default();
+        let dir = std::env::temp_dir().join(format!(
+            "othala-web-sse-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let mut service = OrchdService::new(
+            SqliteStore::open_in_memory().expect("in-memory db"),
+            JsonlEventLog::new(dir.clone()),
+            Scheduler::new(SchedulerConfig {
+                per_repo_limit: 1,
+                per_model_limit: Default::default(),
+            }),
+        );
+        service.bootstrap().expect("bootstrap");
+        let broadcaster = state.events.clone();
+        service.add_event_listener(move |event| broadcaster.publish_event(event));
+
+        let task = Task::new(
+            TaskId::new("T-SSE"),
+            RepoId("example".to_string()),
+            "SSE task".to_string(),
+            dir.join("wt"),
+        );
+        service
+            .create_task(
+                &task,
+                &Event {
+                    id: EventId("E-CREATE-T-SSE".to_string()),
+                    task_id: Some(task.id.clone()),
+                    repo_id: Some(task.repo_id.clone()),
+                    at: Utc::now(),
+                    kind: EventKind::TaskCreated,
+                },
+            )
+            .expect("create task");
+
+        let addr = free_address();
+        let mut router = Router::new();
+        router.add_stream_route(HttpMethod::GET, "/api/v1/events/stream", handle_event_stream);
+        let server = WebServer::new(&addr.to_string())
+            .with_router(router)
+            .with_state(state)
+            .with_auth_token(Some("s3cret".to_string()));
+        thread::spawn(move || server.run_once());
+
+        let mut client = connect_with_retry(addr);
+        client
+            .write_all(b"GET /api/v1/events/stream?task_id=T-SSE&access_token=s3cret HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .expect("write request");
+        let mut reader = BufReader::new(client);
+        let mut line = String::new();
+        reader.read_line(&mut line).expect("read status");
+        assert!(line.starts_with("HTTP/1.1 200 OK"));
+        while line != "\r\n" {
+            line.clear();
+            reader.read_line(&mut line).expect("read header");
+        }
+
+        service
+            .transition_task_state(
+                &task.id,
+                TaskState::Ready,
+                EventId("E-READY-T-SSE".to_string()),
+                Utc::now(),
+            )
+            .expect("transition");
+
+        line.clear();
+        reader.read_line(&mut line).expect("read event name");
+        assert_eq!(line, "event: task_state_changed\n");
+        line.clear();
+        reader.read_line(&mut line).expect("read event data");
+        assert!(line.starts_with("data: "));
+        assert!(line.contains("\"task_id\":\"T-SSE\""));
+        let _ = std::fs::remove_dir_all(dir);
+    }
 }
@@ -0,0 +1,210 @@
+use std::io::Write;
+use std::net::TcpStream;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use orch_core::events::{Event, EventKind};
+use serde::Serialize;
+
+use crate::error::WebError;
+use crate::handler::ApiState;
+use crate::request::HttpRequest;
+use crate::router::PathParams;
+
+/// Interval between SSE heartbeat comments so proxies keep idle streams open.
+pub const SSE_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+
+pub type WebEventKind = EventKind;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WebEvent {
+    pub id: String,
+    pub task_id: Option<String>,
+    pub repo_id: Option<String>,
+    pub at: DateTime<Utc>,
+    pub kind: WebEventKind,
+}
+
+impl From<&Event> for WebEvent {
+    fn from(event: &Event) -> Self {
+        Self {
+            id: event.id.0.clone(),
+            task_id: event.task_id.as_ref().map(|id| id.0.clone()),
+            repo_id: event.repo_id.as_ref().map(|id| id.0.clone()),
+            at: event.at,
+            kind: event.kind.clone(),
+        }
+    }
+}
+
+/// SSE event name for a kind — the snake_case tag used in the event log.
+pub fn web_event_name(kind: &WebEventKind) -> String {
+    match serde_json::to_value(kind) {
+        Ok(serde_json::Value::String(tag)) => tag,
+        Ok(serde_json::Value::Object(map)) => map.keys().next().cloned().unwrap_or_default(),
+        _ => "event".to_string(),
+    }
+}
+
+/// Fan-out of live events to every connected SSE client.
+#[derive(Debug, Clone, Default)]
+pub struct EventBroadcaster {
+    subscribers: Arc<Mutex<Vec<Sender<WebEvent>>>>,
+}
+
+impl EventBroadcaster {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn subscribe(&self) -> Receiver<WebEvent> {
+        let (tx, rx) = mpsc::channel();
+        if let Ok(mut subscribers) = self.subscribers.lock() {
+            subscribers.push(tx);
+        }
+        rx
+    }
+
+    /// Send to all subscribers, dropping any whose client has gone away.
+    pub fn publish(&self, event: WebEvent) {
+        if let Ok(mut subscribers) = self.subscribers.lock() {
+            subscribers.retain(|tx| tx.send(event.clone()).is_ok());
+        }
+    }
+
+    pub fn publish_event(&self, event: &Event) {
+        self.publish(WebEvent::from(event));
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers.lock().map(|s| s.len()).unwrap_or_default()
+    }
+}
+
+pub type StreamHandlerFn =
+    fn(&HttpRequest, &ApiState, &PathParams, &mut TcpStream) -> Result<(), WebError>;
+
+/// `GET /api/v1/events/stream[?task_id=...]` — server-sent events feed.
+pub fn handle_event_stream(
+    request: &HttpRequest,
+    state: &ApiState,
+    _params: &PathParams,
+    stream: &mut TcpStream,
+) -> Result<(), WebError> {
+    let filter = request.query_params.get("task_id").cloned();
+    let events = state.events.subscribe();
+    stream_events(stream, events, filter.as_deref(), SSE_HEARTBEAT_INTERVAL)
+}
+
+pub fn format_sse_event(event: &WebEvent) -> Result<String, WebError> {
+    let data = serde_json::to_string(event)?;
+    Ok(format!("event: {}\ndata: {data}\n\n", web_event_name(&event.kind)))
+}
+
+fn stream_events(
+    stream: &mut TcpStream,
+    events: Receiver<WebEvent>,
+    task_filter: Option<&str>,
+    heartbeat: Duration,
+) -> Result<(), WebError> {
+    stream.write_all(
+        b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n",
+    )?;
+    stream.flush()?;
+
+    loop {
+        let chunk = match events.recv_timeout(heartbeat) {
+            Ok(event) => {
+                if task_filter.is_some_and(|id| event.task_id.as_deref() != Some(id)) {
+                    continue;
+                }
+                format_sse_event(&event)?
+            }
+            Err(RecvTimeoutError::Timeout) => ": heartbeat\n\n".to_string(),
+            Err(RecvTimeoutError::Disconnected) => return Ok(()),
+        };
+        // A failed write means the client disconnected; that ends the stream.
+        if stream
+            .write_all(chunk.as_bytes())
+            .and_then(|_| stream.flush())
+            .is_err()
+        {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::thread;
+
+    use orch_core::types::{EventId, TaskId};
+
+    use super::*;
+
+    fn mk_event(task_id: &str, kind: EventKind) -> WebEvent {
+        WebEvent::from(&Event {
+            id: EventId(format!("E-{task_id}")),
+            task_id: Some(TaskId::new(task_id)),
+            repo_id: None,
+            at: Utc::now(),
+            kind,
+        })
+    }
+
+    #[test]
+    fn event_name_uses_snake_case_tag() {
+        assert_eq!(web_event_name(&EventKind::TaskCreated), "task_created");
+        assert_eq!(
+            web_event_name(&EventKind::CancellationRequested {
+                reason: orch_core::events::CancelReason::Timeout,
+            }),
+            "cancellation_requested"
+        );
+    }
+
+    #[test]
+    fn broadcaster_drops_closed_subscribers() {
+        let broadcaster = EventBroadcaster::new();
+        let rx = broadcaster.subscribe();
+        drop(broadcaster.subscribe());
+
+        broadcaster.publish(mk_event("T1", EventKind::TaskCreated));
+
+        assert_eq!(broadcaster.subscriber_count(), 1);
+        assert_eq!(rx.try_recv().expect("event").task_id.as_deref(), Some("T1"));
+    }
+
+    #[test]
+    fn stream_filters_by_task_and_sends_heartbeats() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind listener");
+        let addr = listener.local_addr().expect("local addr");
+        let (tx, rx) = mpsc::channel();
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("accept");
+            stream_events(&mut stream, rx, Some("T2"), Duration::from_millis(20))
+        });
+
+        let client = TcpStream::connect(addr).expect("connect");
+        let mut reader = BufReader::new(client);
+        tx.send(mk_event("T1", EventKind::TaskCreated)).expect("send");
+        tx.send(mk_event("T2", EventKind::TaskCreated)).expect("send");
+
+        let mut lines = Vec::new();
+        while !lines.iter().any(|line: &String| line.starts_with(": heartbeat")) {
+            let mut line = String::new();
+            reader.read_line(&mut line).expect("read line");
+            lines.push(line);
+        }
+        drop(tx);
+
+        assert!(lines.iter().any(|line| line.starts_with("data: ") && line.contains("E-T2")));
+        assert!(!lines.iter().any(|line| line.contains("E-T1")));
+        assert!(server.join().expect("join").is_ok());
+    }
+}
@@ -1,11 +1,16 @@
+use orch_agents::{ChunkSink, PtyChunk};
 use orch_core::types::TaskId;
+use std::collections::VecDeque;
 use std::fs::{self, OpenOptions};
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::mpsc::Receiver;
 
 const MAX_LOG_SIZE_BYTES: u64 = 10 * 1024 * 1024;
 const MAX_ROTATED_FILES: usize = 5;
 const EDGE_PRESERVE_LINES: usize = 20;
+/// Chunks a log writer holds before the agent's output reader waits for it.
+const LOG_WRITER_CAPACITY: usize = 256;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DiffLine {
@@ -42,8 +47,26 @@ pub struct CompactionResult {
     pub compacted_lines: usize,
     pub summary: String,
     pub compression_ratio: f64,
+    /// The limit that forced compaction; `None` when the input already fit.
+    pub trigger: Option<CompactionTrigger>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompactionTrigger {
+    Lines,
+    Tokens,
+}
+
+/// Limits a compacted context has to fit. Exceeding either one compacts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompactionBudget {
+    pub max_lines: usize,
+    pub max_tokens: u64,
+}
+
+/// Estimates how many tokens a piece of text costs.
+pub type TokenEstimator = fn(&str) -> u64;
+
 pub fn agent_log_dir(repo_root: &Path, task_id: &TaskId) -> PathBuf {
     repo_root.join(".othala/agent-output").join(&task_id.0)
 }
@@ -64,6 +87,35 @@ pub fn append_agent_output(
     Ok(())
 }
 
+/// Append chunks to `latest.log` as they arrive so `tail --follow` sees
+/// output mid-epoch. Returns the number of chunks written once the sender
+/// side hangs up.
+pub fn stream_chunks_to_log(
+    repo_root: &Path,
+    task_id: &TaskId,
+    chunks: &Receiver<PtyChunk>,
+) -> std::io::Result<usize> {
+    let mut written = 0;
+    for chunk in chunks {
+        append_agent_output(repo_root, task_id, &[chunk.text.trim_end().to_string()])?;
+        written += 1;
+    }
+    Ok(written)
+}
+
+/// Start a thread that streams chunks sent to the returned sink into the
+/// task's `latest.log`. It exits once every clone of the sink is dropped.
+pub fn spawn_log_writer(repo_root: &Path, task_id: &TaskId) -> ChunkSink {
+    let (sink, chunks) = ChunkSink::bounded(LOG_WRITER_CAPACITY);
+    let (repo_root, task_id) = (repo_root.to_path_buf(), task_id.clone());
+    std::thread::spawn(move || {
+        if let Err(e) = stream_chunks_to_log(&repo_root, &task_id, &chunks) {
+            eprintln!("[agent-log] Failed to stream output for {}: {e}", task_id.0);
+        }
+    });
+    sink
+}
+
 pub fn rotate_log_if_needed(log_path: &Path) -> std::io::Result<bool> {
     let metadata = std::fs::metadata(log_path);
     match metadata {
@@ -200,6 +252,7 @@ pub fn compact_context(lines: &[String], max_lines: usize) -> CompactionResult {
             compacted_lines: 0,
             summary: String::new(),
             compression_ratio: if original_lines == 0 { 1.0 } else { 0.0 },
+            trigger: (original_lines > 0).then_some(CompactionTrigger::Lines),
         };
     }
 
@@ -210,6 +263,7 @@ pub fn compact_context(lines: &[String], max_lines: usize) -> CompactionResult {
             compacted_lines: original_lines,
             summary,
             compression_ratio: 1.0,
+            trigger: None,
         };
     }
 
@@ -286,7 +340,122 @@ pub fn compact_context(lines: &[String], max_lines: usize) -> CompactionResult {
         compacted_lines,
         summary,
         compression_ratio,
+        trigger: Some(CompactionTrigger::Lines),
+    }
+}
+
+/// Compact `lines` to fit `budget`, triggering on whichever of the line or
+/// token limit is exceeded first. Line-based compaction is tried first; if
+/// its result still costs too many tokens, the most recent lines that fit
+/// are kept behind a one-line summary of everything before them.
+pub fn compact_context_with_budget(
+    lines: &[String],
+    budget: CompactionBudget,
+    estimate: TokenEstimator,
+) -> CompactionResult {
+    let over_lines = lines.len() > budget.max_lines;
+    let over_tokens = estimate(&lines.join("\n")) > budget.max_tokens;
+    if !over_lines && !over_tokens {
+        return compact_context(lines, budget.max_lines);
+    }
+
+    let by_lines = compact_context(lines, budget.max_lines);
+    if over_lines && estimate(&by_lines.summary) <= budget.max_tokens {
+        return by_lines;
+    }
+
+    let kept = keep_recent_within_budget(lines, budget, estimate);
+    let compacted_lines = kept.len();
+    CompactionResult {
+        original_lines: lines.len(),
+        compacted_lines,
+        summary: kept.join("\n"),
+        compression_ratio: compacted_lines as f64 / lines.len() as f64,
+        trigger: Some(if over_lines {
+            CompactionTrigger::Lines
+        } else {
+            CompactionTrigger::Tokens
+        }),
+    }
+}
+
+fn keep_recent_within_budget(
+    lines: &[String],
+    budget: CompactionBudget,
+    estimate: TokenEstimator,
+) -> Vec<String> {
+    if budget.max_lines == 0 || budget.max_tokens == 0 {
+        return Vec::new();
     }
+
+    let fits = |first: &str, rest: &VecDeque<String>| {
+        let mut text = first.to_string();
+        for line in rest {
+            text.push('\n');
+            text.push_str(line);
+        }
+        estimate(&text) <= budget.max_tokens
+    };
+
+    let mut kept: VecDeque<String> = VecDeque::new();
+    let mut start = lines.len();
+    while start > 0 && kept.len() < budget.max_lines {
+        let line = &lines[start - 1];
+        if !fits(line, &kept) {
+            if kept.is_empty() {
+                kept.push_front(truncate_front_to_tokens(line, budget.max_tokens, estimate));
+                start -= 1;
+            }
+            break;
+        }
+        kept.push_front(line.clone());
+        start -= 1;
+    }
+
+    // Summarize what was dropped, giving up older kept lines to make room
+    // but never the most recent one.
+    while start > 0 {
+        let note = dropped_lines_note(&lines[..start], estimate);
+        if kept.len() < budget.max_lines && fits(&note, &kept) {
+            kept.push_front(note);
+            break;
+        }
+        if kept.len() <= 1 {
+            break;
+        }
+        kept.pop_front();
+        start += 1;
+    }
+
+    kept.into()
+}
+
+/// The longest suffix of `line` that fits in `max_tokens`.
+fn truncate_front_to_tokens(line: &str, max_tokens: u64, estimate: TokenEstimator) -> String {
+    let starts = line
+        .char_indices()
+        .map(|(idx, _)| idx)
+        .chain(std::iter::once(line.len()))
+        .collect::<Vec<_>>();
+    let cut = starts.partition_point(|&idx| estimate(&line[idx..]) > max_tokens);
+    line[starts[cut.min(starts.len() - 1)]..].to_string()
+}
+
+fn dropped_lines_note(dropped: &[String], estimate: TokenEstimator) -> String {
+    let sections = extract_key_sections(&dropped.join("\n"));
+    let count = |kind: SectionType| {
+        sections
+            .iter()
+            .filter(|section| section.section_type == kind)
+            .count()
+    };
+    format!(
+        "[compacted {} earlier lines, ~{} tokens: {} errors, {} decisions]",
+        dropped.len(),
+        estimate(&dropped.join("\n")),
+        count(SectionType::Error),
+        count(SectionType::Decision)
+    )
 }
 
 pub fn tail_agent_log(
@@ -434,6 +603,7 @@ pub fn diff_summary(diff: &[DiffLine]) -> DiffSummary {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::auto_compact::estimate_tokens;
     use std::time::{SystemTime, UNIX_EPOCH};
 
     fn unique_test_repo_root() -> PathBuf {
@@ -461,6 +631,65 @@ mod tests {
         let _ = fs::remove_dir_all(&repo_root);
     }
 
+    #[test]
+    fn streamed_chunks_are_flushed_before_the_sender_closes() {
+        let repo_root = unique_test_repo_root();
+        let task_id = TaskId::new("task-stream");
+        let (tx, rx) = std::sync::mpsc::channel();
+        let writer = {
+            let repo_root = repo_root.clone();
+            let task_id = task_id.clone();
+            std::thread::spawn(move || stream_chunks_to_log(&repo_root, &task_id, &rx))
+        };
+        let chunk = |text: &str| PtyChunk {
+            at: chrono::Utc::now(),
+            text: text.to_string(),
+        };
+
+        tx.send(chunk("partial\n")).expect("send chunk");
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while read_agent_log(&repo_root, &task_id).unwrap_or_default() != "partial\n" {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "chunk never hit the log"
+            );
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        tx.send(chunk("done")).expect("send chunk");
+        drop(tx);
+        assert_eq!(writer.join().expect("writer thread").expect("stream"), 2);
+        assert_eq!(
+            read_agent_log(&repo_root, &task_id).expect("read log"),
+            "partial\ndone\n"
+        );
+
+        let _ = fs::remove_dir_all(&repo_root);
+    }
+
+    #[test]
+    fn log_writer_appends_lines_sent_to_its_sink() {
+        let repo_root = unique_test_repo_root();
+        let task_id = TaskId::new("task-writer");
+        let sink = spawn_log_writer(&repo_root, &task_id);
+
+        assert!(sink.send_line("qa: 3 checks"));
+        assert!(sink.send_line("qa: all passed"));
+        drop(sink);
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while read_agent_log(&repo_root, &task_id).unwrap_or_default()
+            != "qa: 3 checks\nqa: all passed\n"
+        {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "lines never hit the log"
+            );
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        let _ = fs::remove_dir_all(&repo_root);
+    }
+
     #[test]
     fn tail_returns_last_n_lines() {
         let repo_root = unique_test_repo_root();
@@ -778,6 +1007,75 @@ mod tests {
         assert_eq!(compacted.compression_ratio, 0.0);
     }
 
+    fn budget(max_lines: usize, max_tokens: u64) -> CompactionBudget {
+        CompactionBudget {
+            max_lines,
+            max_tokens,
+        }
+    }
+
+    #[test]
+    fn compact_with_budget_triggers_on_tokens_before_lines() {
+        let lines = (1..=10)
+            .map(|idx| format!("step {idx}: {}", "x".repeat(400)))
+            .collect::<Vec<_>>();
+
+        let compacted = compact_context_with_budget(&lines, budget(120, 300), estimate_tokens);
+
+        assert_eq!(compacted.trigger, Some(CompactionTrigger::Tokens));
+        assert!(estimate_tokens(&compacted.summary) <= 300);
+        let kept: Vec<&str> = compacted.summary.lines().collect();
+        assert!(kept[0].starts_with("[compacted "), "{}", kept[0]);
+        assert!(kept.last().unwrap().starts_with("step 10:"));
+    }
+
+    #[test]
+    fn compact_with_budget_triggers_on_lines_before_tokens() {
+        let lines = (1..=200).map(|idx| format!("{idx}")).collect::<Vec<_>>();
+
+        let compacted = compact_context_with_budget(&lines, budget(50, 8_000), estimate_tokens);
+
+        assert_eq!(compacted.trigger, Some(CompactionTrigger::Lines));
+        assert!(compacted.compacted_lines <= 50);
+        assert_eq!(compacted.summary.lines().last(), Some("200"));
+    }
+
+    #[test]
+    fn compact_with_budget_stays_under_both_limits() {
+        let mut lines = (1..=400)
+            .map(|idx| format!("line {idx} {}", "y".repeat(60)))
+            .collect::<Vec<_>>();
+        lines[10] = "ERROR: early failure".to_string();
+        lines.push("z".repeat(2_000));
+
+        let compacted = compact_context_with_budget(&lines, budget(40, 250), estimate_tokens);
+
+        assert!(compacted.compacted_lines <= 40);
+        assert!(estimate_tokens(&compacted.summary) <= 250);
+        assert!(
+            compacted.summary.ends_with("zzz"),
+            "most recent content kept"
+        );
+
+        let untouched = compact_context_with_budget(&lines[..3], budget(40, 250), estimate_tokens);
+        assert_eq!(untouched.trigger, None);
+        assert_eq!(untouched.compacted_lines, 3);
+    }
+
+    #[test]
+    fn compact_with_budget_accepts_a_custom_estimator() {
+        fn per_word(text: &str) -> u64 {
+            text.split_whitespace().count() as u64
+        }
+        let lines = vec!["one two three".to_string(), "four five".to_string()];
+
+        let compacted = compact_context_with_budget(&lines, budget(10, 4), per_word);
+
+        assert_eq!(compacted.trigger, Some(CompactionTrigger::Tokens));
+        assert!(per_word(&compacted.summary) <= 4);
+        assert!(compacted.summary.ends_with("four five"));
+    }
+
     #[test]
     fn save_compacted_summary_writes_compacted_log_file() {
         let repo_root = unique_test_repo_root();
@@ -3,6 +3,13 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::agent_log::CompactionBudget;
+
+/// Default line limit for a compacted agent log.
+pub const DEFAULT_COMPACT_MAX_LINES: usize = 120;
+/// Default token limit for a compacted agent log.
+pub const DEFAULT_COMPACT_MAX_TOKENS: u64 = 8_000;
+
 /// Configuration for auto-compact behavior
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AutoCompactConfig {
@@ -14,6 +21,30 @@ pub struct AutoCompactConfig {
     pub context_window_sizes: HashMap<String, u64>,
     /// Minimum number of messages before compaction is allowed
     pub min_messages_before_compact: usize,
+    /// Compact an agent log once it runs past this many lines
+    #[serde(default = "default_compact_max_lines")]
+    pub max_lines: usize,
+    /// Compact an agent log once its estimated tokens pass this
+    #[serde(default = "default_compact_max_tokens")]
+    pub max_tokens: u64,
+}
+
+fn default_compact_max_lines() -> usize {
+    DEFAULT_COMPACT_MAX_LINES
+}
+
+fn default_compact_max_tokens() -> u64 {
+    DEFAULT_COMPACT_MAX_TOKENS
+}
+
+impl AutoCompactConfig {
+    /// Limits for `agent_log::compact_context_with_budget`.
+    pub fn compaction_budget(&self) -> CompactionBudget {
+        CompactionBudget {
+            max_lines: self.max_lines,
+            max_tokens: self.max_tokens,
+        }
+    }
 }
 
 impl Default for AutoCompactConfig {
@@ -28,6 +59,8 @@ impl Default for AutoCompactConfig {
             threshold: 0.95,
             context_window_sizes: sizes,
             min_messages_before_compact: 5,
+            max_lines: DEFAULT_COMPACT_MAX_LINES,
+            max_tokens: DEFAULT_COMPACT_MAX_TOKENS,
         }
     }
 }
@@ -173,6 +206,8 @@ mod tests {
         assert_eq!(config.context_window_sizes.get("claude"), Some(&200_000));
         assert_eq!(config.context_window_sizes.get("codex"), Some(&128_000));
         assert_eq!(config.context_window_sizes.get("gemini"), Some(&1_000_000));
+        assert_eq!(config.compaction_budget().max_lines, 120);
+        assert_eq!(config.compaction_budget().max_tokens, 8_000);
     }
 
     #[test]
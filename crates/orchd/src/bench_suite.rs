@@ -0,0 +1,472 @@
+//! Benchmark suite — run a labeled set of task specs repeatedly and compare
+//! the outcomes recorded under two tags.
+//!
+//! Bench tasks live in their own state database under `.orch/bench/<tag>/`,
+//! so they never appear in normal task lists or stats. Only the per-task
+//! results are written to the main store's `bench_results` table.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use orch_core::events::{Event, EventKind};
+use orch_core::state::TaskState;
+use orch_core::types::{
+    parse_yaml_task_spec, yaml_spec_to_task, EventId, Task, TaskId, YamlTaskSpec,
+};
+
+use crate::daemon_loop::{run_tick, DaemonConfig, DaemonState};
+use crate::persistence::PersistenceError;
+use crate::service::{OrchdService, ServiceError};
+use crate::supervisor::AgentSupervisor;
+use crate::types::{BenchOutcome, BenchResult, TaskRunRecord};
+
+/// Scratch state for bench runs, one directory per tag.
+pub const BENCH_ROOT: &str = ".orch/bench";
+
+/// Label added to every bench task.
+pub const BENCH_LABEL: &str = "bench";
+
+#[derive(Debug, thiserror::Error)]
+pub enum BenchError {
+    #[error("io error at {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("invalid bench spec {path}: {reason}")]
+    InvalidSpec { path: PathBuf, reason: String },
+    #[error("no bench specs (*.yaml, *.yml) found in {dir}")]
+    NoSpecs { dir: PathBuf },
+    #[error(transparent)]
+    Persistence(#[from] PersistenceError),
+    #[error(transparent)]
+    Service(#[from] ServiceError),
+}
+
+/// A task spec from the suite directory, named after its file stem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchSpec {
+    pub name: String,
+    pub spec: YamlTaskSpec,
+}
+
+/// Load every YAML spec in `dir`, sorted by name. Unlike `load-tasks`, an
+/// unparseable spec is an error: a silently skipped task skews comparisons.
+pub fn load_bench_specs(dir: &Path) -> Result<Vec<BenchSpec>, BenchError> {
+    let entries = fs::read_dir(dir).map_err(|source| io_error(dir, source))?;
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| {
+                        ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml")
+                    })
+        })
+        .collect();
+    paths.sort();
+
+    let mut specs = Vec::with_capacity(paths.len());
+    for path in paths {
+        let content = fs::read_to_string(&path).map_err(|source| io_error(&path, source))?;
+        let spec = parse_yaml_task_spec(&content).map_err(|reason| BenchError::InvalidSpec {
+            path: path.clone(),
+            reason,
+        })?;
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        specs.push(BenchSpec { name, spec });
+    }
+    if specs.is_empty() {
+        return Err(BenchError::NoSpecs {
+            dir: dir.to_path_buf(),
+        });
+    }
+    Ok(specs)
+}
+
+/// Directory holding the state database and event log for `tag`.
+pub fn bench_dir(repo_root: &Path, tag: &str) -> PathBuf {
+    repo_root.join(BENCH_ROOT).join(tag)
+}
+
+pub fn bench_task_id(tag: &str, spec: &str, iteration: u32) -> TaskId {
+    TaskId::new(format!("bench-{tag}-{spec}-{iteration}"))
+}
+
+/// The task for one spec/iteration, with its own ID and worktree.
+pub fn bench_task(spec: &BenchSpec, tag: &str, iteration: u32, repo_id: &str) -> Task {
+    let mut task = yaml_spec_to_task(&spec.spec, repo_id);
+    task.id = bench_task_id(tag, &spec.name, iteration);
+    task.worktree_path = PathBuf::from(format!(".orch/wt/{}", task.id.0));
+    if !task.labels.iter().any(|label| label == BENCH_LABEL) {
+        task.labels.push(BENCH_LABEL.to_string());
+    }
+    task
+}
+
+/// Outcome for a task in `state`, or `None` while it is still running.
+pub fn bench_outcome(state: TaskState) -> Option<BenchOutcome> {
+    match state {
+        TaskState::AwaitingMerge | TaskState::Merged => Some(BenchOutcome::Merged),
+        TaskState::Stopped => Some(BenchOutcome::Failed),
+        _ => None,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn bench_result(
+    tag: &str,
+    spec: &str,
+    iteration: u32,
+    task: &Task,
+    runs: &[TaskRunRecord],
+    outcome: BenchOutcome,
+    duration: Duration,
+    at: DateTime<Utc>,
+) -> BenchResult {
+    BenchResult {
+        tag: tag.to_string(),
+        spec: spec.to_string(),
+        iteration,
+        task_id: task.id.clone(),
+        outcome,
+        retries: task.retry_count,
+        tokens: runs.iter().filter_map(|run| run.estimated_tokens).sum(),
+        duration_secs: duration.as_secs_f64(),
+        recorded_at: at,
+    }
+}
+
+/// Create one task per spec and tick the daemon until every task finishes or
+/// `timeout` elapses; unfinished tasks are recorded as failed.
+#[allow(clippy::too_many_arguments)]
+pub fn run_iteration(
+    service: &OrchdService,
+    supervisor: &mut AgentSupervisor,
+    daemon_state: &mut DaemonState,
+    config: &DaemonConfig,
+    specs: &[BenchSpec],
+    tag: &str,
+    iteration: u32,
+    timeout: Duration,
+    tick_interval: Duration,
+) -> Result<Vec<BenchResult>, BenchError> {
+    let repo_id = config
+        .repo_root
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "default".to_string());
+
+    let mut pending: BTreeMap<String, TaskId> = BTreeMap::new();
+    for spec in specs {
+        let task = bench_task(spec, tag, iteration, &repo_id);
+        let event = Event {
+            id: EventId(format!("E-CREATE-{}", task.id.0)),
+            task_id: Some(task.id.clone()),
+            repo_id: Some(task.repo_id.clone()),
+            at: Utc::now(),
+            kind: EventKind::TaskCreated,
+        };
+        service.create_task(&task, &event)?;
+        pending.insert(spec.name.clone(), task.id);
+    }
+
+    let start = Instant::now();
+    let mut results = Vec::with_capacity(specs.len());
+    while !pending.is_empty() {
+        run_tick(service, supervisor, daemon_state, config);
+
+        let timed_out = start.elapsed() >= timeout;
+        let mut finished = Vec::new();
+        for (spec, task_id) in &pending {
+            let Some(task) = service.task(task_id)? else {
+                continue;
+            };
+            let outcome = match bench_outcome(task.state) {
+                Some(outcome) => outcome,
+                None if timed_out => BenchOutcome::Failed,
+                None => continue,
+            };
+            let runs = service.task_runs(task_id)?;
+            results.push(bench_result(
+                tag,
+                spec,
+                iteration,
+                &task,
+                &runs,
+                outcome,
+                start.elapsed(),
+                Utc::now(),
+            ));
+            finished.push(spec.clone());
+        }
+        for spec in finished {
+            pending.remove(&spec);
+        }
+
+        if timed_out {
+            supervisor.stop_all();
+            break;
+        }
+        if !pending.is_empty() {
+            std::thread::sleep(tick_interval);
+        }
+    }
+    Ok(results)
+}
+
+/// Aggregated results for one spec (or the whole suite) under one tag.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct BenchStats {
+    pub runs: usize,
+    pub merged: usize,
+    pub avg_retries: f64,
+    pub avg_tokens: f64,
+    pub avg_duration_secs: f64,
+}
+
+impl BenchStats {
+    pub fn from_results<'a>(results: impl IntoIterator<Item = &'a BenchResult>) -> Self {
+        let mut stats = Self::default();
+        for result in results {
+            stats.runs += 1;
+            if result.outcome == BenchOutcome::Merged {
+                stats.merged += 1;
+            }
+            stats.avg_retries += f64::from(result.retries);
+            stats.avg_tokens += result.tokens as f64;
+            stats.avg_duration_secs += result.duration_secs;
+        }
+        if stats.runs > 0 {
+            let runs = stats.runs as f64;
+            stats.avg_retries /= runs;
+            stats.avg_tokens /= runs;
+            stats.avg_duration_secs /= runs;
+        }
+        stats
+    }
+
+    pub fn merge_rate(&self) -> f64 {
+        if self.runs == 0 {
+            0.0
+        } else {
+            self.merged as f64 / self.runs as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpecComparison {
+    pub spec: String,
+    pub a: BenchStats,
+    pub b: BenchStats,
+}
+
+impl SpecComparison {
+    /// `b` merged less often than `a`, or as often but with more retries.
+    /// Specs missing from either tag are never regressions.
+    pub fn is_regression(&self) -> bool {
+        if self.a.runs == 0 || self.b.runs == 0 {
+            return false;
+        }
+        let (rate_a, rate_b) = (self.a.merge_rate(), self.b.merge_rate());
+        rate_b < rate_a || (rate_b == rate_a && self.b.avg_retries > self.a.avg_retries)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchComparison {
+    pub tag_a: String,
+    pub tag_b: String,
+    pub specs: Vec<SpecComparison>,
+    pub aggregate: SpecComparison,
+}
+
+pub fn compare(tag_a: &str, a: &[BenchResult], tag_b: &str, b: &[BenchResult]) -> BenchComparison {
+    let mut names: Vec<&str> = a.iter().chain(b).map(|result| result.spec.as_str()).collect();
+    names.sort_unstable();
+    names.dedup();
+
+    let specs = names
+        .into_iter()
+        .map(|name| SpecComparison {
+            spec: name.to_string(),
+            a: BenchStats::from_results(a.iter().filter(|result| result.spec == name)),
+            b: BenchStats::from_results(b.iter().filter(|result| result.spec == name)),
+        })
+        .collect();
+    BenchComparison {
+        tag_a: tag_a.to_string(),
+        tag_b: tag_b.to_string(),
+        specs,
+        aggregate: SpecComparison {
+            spec: "TOTAL".to_string(),
+            a: BenchStats::from_results(a),
+            b: BenchStats::from_results(b),
+        },
+    }
+}
+
+/// Per-spec and aggregate table; regressed rows are shown in red.
+pub fn render_comparison(comparison: &BenchComparison) -> String {
+    let mut out = format!(
+        "{:<24} {:>15} {:>13} {:>19} {:>15}\n",
+        "SPEC (A → B)", "MERGED", "RETRIES", "TOKENS", "DURATION"
+    );
+    out.push_str(&format!("{}\n", "-".repeat(90)));
+    for row in comparison.specs.iter().chain([&comparison.aggregate]) {
+        let line = format!(
+            "{:<24} {:>15} {:>13} {:>19} {:>15}",
+            row.spec,
+            pair(&row.a, &row.b, |s| format!("{}/{}", s.merged, s.runs)),
+            pair(&row.a, &row.b, |s| format!("{:.1}", s.avg_retries)),
+            pair(&row.a, &row.b, |s| format!("{:.0}", s.avg_tokens)),
+            pair(&row.a, &row.b, |s| format!("{:.0}s", s.avg_duration_secs)),
+        );
+        if row.is_regression() {
+            out.push_str(&format!("\x1b[31m{line}  REGRESSION\x1b[0m\n"));
+        } else {
+            out.push_str(&format!("{line}\n"));
+        }
+    }
+    let regressions = comparison.specs.iter().filter(|row| row.is_regression()).count();
+    out.push_str(&format!(
+        "\nA = {}, B = {}; {regressions} regression(s)\n",
+        comparison.tag_a, comparison.tag_b
+    ));
+    out
+}
+
+/// `a → b` for one column, with `-` for a tag that has no runs of the spec.
+fn pair(a: &BenchStats, b: &BenchStats, cell: impl Fn(&BenchStats) -> String) -> String {
+    let show = |stats: &BenchStats| {
+        if stats.runs == 0 {
+            "-".to_string()
+        } else {
+            cell(stats)
+        }
+    };
+    format!("{} → {}", show(a), show(b))
+}
+
+fn io_error(path: &Path, source: std::io::Error) -> BenchError {
+    BenchError::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::SqliteStore;
+    use chrono::TimeZone;
+
+    fn result(
+        tag: &str,
+        spec: &str,
+        iteration: u32,
+        outcome: BenchOutcome,
+        retries: u32,
+    ) -> BenchResult {
+        BenchResult {
+            tag: tag.to_string(),
+            spec: spec.to_string(),
+            iteration,
+            task_id: bench_task_id(tag, spec, iteration),
+            outcome,
+            retries,
+            tokens: 1_000,
+            duration_secs: 60.0,
+            recorded_at: Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn load_bench_specs_names_specs_by_file_and_rejects_bad_ones() {
+        let dir = std::env::temp_dir().join(format!(
+            "othala-bench-specs-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("b-fix-parser.yaml"), "title: Fix parser\n").unwrap();
+        fs::write(dir.join("a-add-flag.yml"), "title: Add flag\nlabels:\n  - cli\n").unwrap();
+        fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let specs = load_bench_specs(&dir).unwrap();
+        let names: Vec<&str> = specs.iter().map(|spec| spec.name.as_str()).collect();
+        assert_eq!(names, vec!["a-add-flag", "b-fix-parser"]);
+
+        let task = bench_task(&specs[0], "baseline", 2, "example");
+        assert_eq!(task.id.0, "bench-baseline-a-add-flag-2");
+        assert_eq!(task.worktree_path, PathBuf::from(".orch/wt/bench-baseline-a-add-flag-2"));
+        assert_eq!(task.labels, vec!["cli".to_string(), BENCH_LABEL.to_string()]);
+
+        fs::write(dir.join("c-broken.yaml"), "model: claude\n").unwrap();
+        assert!(matches!(
+            load_bench_specs(&dir),
+            Err(BenchError::InvalidSpec { .. })
+        ));
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn bench_results_round_trip_in_their_own_table() {
+        let store = SqliteStore::open_in_memory().unwrap();
+        store.migrate().unwrap();
+        let first = result("baseline", "fix-parser", 1, BenchOutcome::Merged, 0);
+        let second = result("baseline", "fix-parser", 2, BenchOutcome::Failed, 2);
+        store.insert_bench_result(&second).unwrap();
+        store.insert_bench_result(&first).unwrap();
+        store
+            .insert_bench_result(&result("other", "fix-parser", 1, BenchOutcome::Merged, 0))
+            .unwrap();
+
+        assert_eq!(store.list_bench_results("baseline").unwrap(), vec![first, second]);
+        assert!(store.list_tasks().unwrap().is_empty());
+        assert_eq!(store.delete_bench_results("baseline").unwrap(), 2);
+        assert!(store.list_bench_results("baseline").unwrap().is_empty());
+    }
+
+    #[test]
+    fn compare_flags_specs_that_merge_less_or_retry_more() {
+        let a = vec![
+            result("a", "add-flag", 1, BenchOutcome::Merged, 0),
+            result("a", "add-flag", 2, BenchOutcome::Merged, 0),
+            result("a", "fix-parser", 1, BenchOutcome::Merged, 0),
+            result("a", "rename", 1, BenchOutcome::Merged, 1),
+        ];
+        let b = vec![
+            result("b", "add-flag", 1, BenchOutcome::Merged, 0),
+            result("b", "add-flag", 2, BenchOutcome::Failed, 3),
+            result("b", "fix-parser", 1, BenchOutcome::Merged, 2),
+            result("b", "rename", 1, BenchOutcome::Merged, 0),
+            result("b", "new-spec", 1, BenchOutcome::Failed, 0),
+        ];
+
+        let comparison = compare("a", &a, "b", &b);
+        let regressed: Vec<&str> = comparison
+            .specs
+            .iter()
+            .filter(|row| row.is_regression())
+            .map(|row| row.spec.as_str())
+            .collect();
+        assert_eq!(regressed, vec!["add-flag", "fix-parser"]);
+        assert_eq!(comparison.aggregate.a.merged, 4);
+        assert_eq!(comparison.aggregate.b.merged, 3);
+        assert!(comparison.aggregate.is_regression());
+
+        let table = render_comparison(&comparison);
+        assert!(table.contains("2/2 → 1/2"));
+        assert!(table.contains("new-spec"));
+        assert!(table.contains("2 regression(s)"));
+    }
+}
@@ -0,0 +1,278 @@
+//! Chat control channel — hands CLI messages to the process that owns a
+//! task's live agent session.
+//!
+//! The daemon advertises itself via a pid file under `.othala/control/`.
+//! Other processes queue messages into a JSONL inbox that the daemon drains
+//! on every tick and forwards through its `AgentSupervisor`, writing back a
+//! receipt per message so the sender knows whether it reached a session.
+
+use chrono::{DateTime, Utc};
+use orch_core::types::{ModelKind, TaskId};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Directory (relative to the repo root) holding per-task chat transcripts.
+/// Shared with the TUI, which replays these files into agent panes.
+pub const CHAT_LOG_DIR: &str = ".orch/chat";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueuedChatMessage {
+    /// Names the receipt the daemon writes once it has handled the message.
+    #[serde(default)]
+    pub id: String,
+    pub task_id: TaskId,
+    pub message: String,
+    /// Start a session with this message when the task has none.
+    #[serde(default)]
+    pub spawn: bool,
+    pub queued_at: DateTime<Utc>,
+}
+
+/// What the daemon did with a queued message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum ChatDelivery {
+    /// Written to the task's live session.
+    Delivered,
+    /// Started a new session with the message as its first turn.
+    Spawned { model: ModelKind },
+    /// Not delivered; the message is dropped.
+    Failed { reason: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatReceipt {
+    pub delivery: ChatDelivery,
+    /// `question_id: question` for each open question the message answered.
+    #[serde(default)]
+    pub answered: Vec<String>,
+}
+
+pub fn control_dir(repo_root: &Path) -> PathBuf {
+    repo_root.join(".othala/control")
+}
+
+pub fn daemon_pid_path(repo_root: &Path) -> PathBuf {
+    control_dir(repo_root).join("daemon.pid")
+}
+
+pub fn chat_inbox_path(repo_root: &Path) -> PathBuf {
+    control_dir(repo_root).join("chat-inbox.jsonl")
+}
+
+pub fn chat_receipt_path(repo_root: &Path, id: &str) -> PathBuf {
+    control_dir(repo_root)
+        .join("chat-receipts")
+        .join(format!("{id}.json"))
+}
+
+pub fn chat_log_path(repo_root: &Path, task_id: &TaskId) -> PathBuf {
+    repo_root.join(CHAT_LOG_DIR).join(format!("{}.log", task_id.0))
+}
+
+/// Append lines to the task's chat transcript (the same file the TUI uses).
+pub fn append_chat_log(repo_root: &Path, task_id: &TaskId, lines: &[String]) -> std::io::Result<()> {
+    if lines.is_empty() {
+        return Ok(());
+    }
+    let path = chat_log_path(repo_root, task_id);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    for line in lines {
+        writeln!(file, "{line}")?;
+    }
+    Ok(())
+}
+
+/// Record the current process as the daemon owning agent sessions.
+pub fn write_daemon_pid(repo_root: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(control_dir(repo_root))?;
+    fs::write(daemon_pid_path(repo_root), std::process::id().to_string())
+}
+
+/// Remove the pid file, but only if it still belongs to this process.
+pub fn clear_daemon_pid(repo_root: &Path) {
+    let path = daemon_pid_path(repo_root);
+    let owned = fs::read_to_string(&path)
+        .ok()
+        .and_then(|raw| raw.trim().parse::<u32>().ok())
+        == Some(std::process::id());
+    if owned {
+        let _ = fs::remove_file(path);
+    }
+}
+
+/// Pid of a live daemon for this repo, if one is running.
+pub fn running_daemon_pid(repo_root: &Path) -> Option<u32> {
+    let raw = fs::read_to_string(daemon_pid_path(repo_root)).ok()?;
+    let pid = raw.trim().parse::<u32>().ok()?;
+    process_alive(pid).then_some(pid)
+}
+
+/// Whether a process with `pid` exists, zombies included.
+pub(crate) fn process_alive(pid: u32) -> bool {
+    if Path::new("/proc").is_dir() {
+        return Path::new(&format!("/proc/{pid}")).exists();
+    }
+    std::process::Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+/// Queue a message for delivery by the daemon on its next tick. With `spawn`
+/// the daemon starts a session for the task if it has none.
+pub fn enqueue_chat_message(
+    repo_root: &Path,
+    task_id: &TaskId,
+    message: &str,
+    spawn: bool,
+) -> std::io::Result<QueuedChatMessage> {
+    fs::create_dir_all(control_dir(repo_root))?;
+    let queued_at = Utc::now();
+    let queued = QueuedChatMessage {
+        id: format!(
+            "{}-{}",
+            std::process::id(),
+            queued_at.timestamp_nanos_opt().unwrap_or_default()
+        ),
+        task_id: task_id.clone(),
+        message: message.to_string(),
+        spawn,
+        queued_at,
+    };
+    let line = serde_json::to_string(&queued)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(chat_inbox_path(repo_root))?;
+    writeln!(file, "{line}")?;
+    Ok(queued)
+}
+
+/// Take every queued message, leaving the inbox empty.
+///
+/// The inbox is renamed before reading so messages appended concurrently land
+/// in a fresh file and are picked up on the next call.
+pub fn take_queued_chat_messages(repo_root: &Path) -> Vec<QueuedChatMessage> {
+    let inbox = chat_inbox_path(repo_root);
+    if !inbox.exists() {
+        return Vec::new();
+    }
+    let claimed = inbox.with_extension(format!("jsonl.{}", std::process::id()));
+    if fs::rename(&inbox, &claimed).is_err() {
+        return Vec::new();
+    }
+    let content = fs::read_to_string(&claimed).unwrap_or_default();
+    let _ = fs::remove_file(&claimed);
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str::<QueuedChatMessage>(line).ok())
+        .collect()
+}
+
+/// Record how a queued message was handled. Written to a temp file and
+/// renamed so the sender never reads a partial receipt.
+pub fn write_chat_receipt(
+    repo_root: &Path,
+    id: &str,
+    receipt: &ChatReceipt,
+) -> std::io::Result<()> {
+    let path = chat_receipt_path(repo_root, id);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let raw = serde_json::to_string(receipt)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, raw)?;
+    fs::rename(tmp, path)
+}
+
+/// Read and remove the receipt for message `id`, if the daemon wrote one.
+pub fn take_chat_receipt(repo_root: &Path, id: &str) -> Option<ChatReceipt> {
+    let path = chat_receipt_path(repo_root, id);
+    let raw = fs::read_to_string(&path).ok()?;
+    let _ = fs::remove_file(&path);
+    serde_json::from_str(&raw).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_repo() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "othala-chat-control-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&dir).expect("create temp repo");
+        dir
+    }
+
+    #[test]
+    fn enqueue_then_take_drains_inbox_in_order() {
+        let repo = temp_repo();
+        let task_id = TaskId::new("chat-1");
+        enqueue_chat_message(&repo, &task_id, "first", false).expect("enqueue");
+        enqueue_chat_message(&repo, &task_id, "second", true).expect("enqueue");
+
+        let messages = take_queued_chat_messages(&repo);
+        assert_eq!(
+            messages.iter().map(|m| m.message.as_str()).collect::<Vec<_>>(),
+            vec!["first", "second"]
+        );
+        assert_eq!(
+            messages.iter().map(|m| m.spawn).collect::<Vec<_>>(),
+            vec![false, true]
+        );
+        assert!(take_queued_chat_messages(&repo).is_empty());
+        let _ = fs::remove_dir_all(repo);
+    }
+
+    #[test]
+    fn daemon_pid_roundtrip_detects_current_process() {
+        let repo = temp_repo();
+        assert_eq!(running_daemon_pid(&repo), None);
+        write_daemon_pid(&repo).expect("write pid");
+        assert_eq!(running_daemon_pid(&repo), Some(std::process::id()));
+        clear_daemon_pid(&repo);
+        assert_eq!(running_daemon_pid(&repo), None);
+        let _ = fs::remove_dir_all(repo);
+    }
+
+    #[test]
+    fn receipts_are_taken_once() {
+        let repo = temp_repo();
+        let receipt = ChatReceipt {
+            delivery: ChatDelivery::Failed {
+                reason: "no live session".to_string(),
+            },
+            answered: Vec::new(),
+        };
+        assert_eq!(take_chat_receipt(&repo, "m-1"), None);
+        write_chat_receipt(&repo, "m-1", &receipt).expect("write receipt");
+        assert_eq!(take_chat_receipt(&repo, "m-1"), Some(receipt));
+        assert_eq!(take_chat_receipt(&repo, "m-1"), None);
+        let _ = fs::remove_dir_all(repo);
+    }
+
+    #[test]
+    fn append_chat_log_writes_under_orch_chat() {
+        let repo = temp_repo();
+        let task_id = TaskId::new("chat-2");
+        append_chat_log(&repo, &task_id, &["> hello".to_string()]).expect("append");
+        let content = fs::read_to_string(chat_log_path(&repo, &task_id)).expect("read");
+        assert_eq!(content, "> hello\n");
+        let _ = fs::remove_dir_all(repo);
+    }
+}
@@ -1,7 +1,10 @@
 use anyhow::{anyhow, Context, Result};
-use orch_core::types::TaskId;
+use chrono::Utc;
+use orch_core::events::{Event, EventKind};
+use orch_core::state::TaskState;
+use orch_core::types::{EventId, ModelKind, RepoId, SubmitMode, Task, TaskId, TaskPriority};
 use orch_git::{current_branch, discover_repo, GitCli, RepoHandle, WorktreeManager, WorktreeSpec};
-use orch_graphite::GraphiteClient;
+use orch_graphite::{audit_log_path, GraphiteClient};
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -94,7 +97,8 @@ fn provision_inner(
 
     // Register the branch with Graphite so `gt submit` works later.
     // Run from the worktree directory so graphite sees the correct branch.
-    let wt_graphite = GraphiteClient::new(info.path.clone());
+    let wt_graphite =
+        GraphiteClient::new(info.path.clone()).with_audit_log(audit_log_path(&repo.root));
     if let Err(e) = wt_graphite.track_branch(branch_name, base_branch) {
         // Non-fatal: graphite tracking can be done manually later, and the
         // worktree is already usable for the agent.
@@ -110,6 +114,132 @@ fn provision_inner(
     })
 }
 
+/// What the CLI and TUI need to create a chat task.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewChatTask {
+    pub repo_id: RepoId,
+    pub title: String,
+    pub model: ModelKind,
+    pub priority: TaskPriority,
+    /// Stack on an open task in the same repo instead of the current branch.
+    pub stack: bool,
+    /// Preferred stack parent; falls back to [`find_stack_parent`] when it
+    /// is not an open task with a branch in the same repo.
+    pub stack_on: Option<TaskId>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreatedChatTask {
+    pub task: Task,
+    pub workspace: ChatWorkspace,
+    /// Parent task and branch when the task was stacked.
+    pub parent: Option<(TaskId, String)>,
+}
+
+/// Provision a workspace for `request` and record the new task.
+pub fn create_chat_task(
+    service: &crate::OrchdService,
+    start_path: &Path,
+    request: &NewChatTask,
+) -> Result<CreatedChatTask> {
+    let task_id = TaskId::new(format!("chat-{}", Utc::now().timestamp_millis()));
+    let parent = if request.stack {
+        choose_stack_parent(
+            &service.list_tasks()?,
+            &request.repo_id,
+            request.stack_on.as_ref(),
+        )
+    } else {
+        None
+    };
+    let workspace = provision_chat_workspace_on_base(
+        start_path,
+        &task_id,
+        parent.as_ref().map(|(_, branch)| branch.as_str()),
+    )?;
+
+    let mut task = Task::new(
+        task_id.clone(),
+        request.repo_id.clone(),
+        request.title.clone(),
+        workspace.worktree_path.clone(),
+    );
+    task.branch_name = Some(workspace.branch_name.clone());
+    task.priority = request.priority;
+    task.submit_mode = submit_mode_from_repo_mode(start_path);
+    if let Some((parent_task_id, _)) = parent.as_ref() {
+        task.parent_task_id = Some(parent_task_id.clone());
+        if !task.depends_on.contains(parent_task_id) {
+            task.depends_on.push(parent_task_id.clone());
+        }
+    }
+    task.preferred_model = Some(request.model);
+
+    let event = Event {
+        id: EventId(format!("E-CREATE-{}", task_id.0)),
+        task_id: Some(task.id.clone()),
+        repo_id: Some(task.repo_id.clone()),
+        at: Utc::now(),
+        kind: EventKind::TaskCreated,
+    };
+    service.create_task(&task, &event)?;
+
+    Ok(CreatedChatTask {
+        task,
+        workspace,
+        parent,
+    })
+}
+
+/// The most recently updated open task with a branch in `repo_id`.
+pub fn find_stack_parent(tasks: &[Task], repo_id: &RepoId) -> Option<(TaskId, String)> {
+    tasks
+        .iter()
+        .filter(|task| is_stackable(task, repo_id))
+        .filter_map(|task| {
+            task.branch_name
+                .as_ref()
+                .map(|branch| (task.id.clone(), branch.clone(), task.updated_at))
+        })
+        .max_by(|a, b| a.2.cmp(&b.2))
+        .map(|(task_id, branch, _)| (task_id, branch))
+}
+
+/// `preferred` when it can be stacked on, otherwise [`find_stack_parent`].
+pub fn choose_stack_parent(
+    tasks: &[Task],
+    repo_id: &RepoId,
+    preferred: Option<&TaskId>,
+) -> Option<(TaskId, String)> {
+    preferred
+        .and_then(|id| tasks.iter().find(|task| &task.id == id))
+        .filter(|task| is_stackable(task, repo_id))
+        .and_then(|task| Some((task.id.clone(), task.branch_name.clone()?)))
+        .or_else(|| find_stack_parent(tasks, repo_id))
+}
+
+fn is_stackable(task: &Task, repo_id: &RepoId) -> bool {
+    &task.repo_id == repo_id && !matches!(task.state, TaskState::Merged | TaskState::Stopped)
+}
+
+/// `Stack` when `.othala/repo-mode.toml` selects stack mode.
+pub fn submit_mode_from_repo_mode(repo_root: &Path) -> SubmitMode {
+    let mode_path = repo_root.join(".othala/repo-mode.toml");
+    let Ok(contents) = std::fs::read_to_string(mode_path) else {
+        return SubmitMode::Single;
+    };
+
+    if contents
+        .lines()
+        .map(str::trim)
+        .any(|line| line == "mode = \"stack\"" || line == "mode=\"stack\"")
+    {
+        SubmitMode::Stack
+    } else {
+        SubmitMode::Single
+    }
+}
+
 /// Validate that a task's worktree directory exists, and attempt recovery if it
 /// was deleted externally (e.g. `rm -rf .orch/wt/`).
 ///
@@ -164,8 +294,22 @@ fn sanitize_branch_component(raw: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::branch_name_for_task;
-    use orch_core::types::TaskId;
+    use super::{branch_name_for_task, choose_stack_parent};
+    use orch_core::state::TaskState;
+    use orch_core::types::{RepoId, Task, TaskId};
+    use std::path::PathBuf;
+
+    fn mk_task(id: &str, repo: &str, minutes_ago: i64) -> Task {
+        let mut task = Task::new(
+            TaskId::new(id),
+            RepoId(repo.to_string()),
+            id.to_string(),
+            PathBuf::from(format!(".orch/wt/{id}")),
+        );
+        task.branch_name = Some(format!("task/{id}"));
+        task.updated_at = chrono::Utc::now() - chrono::Duration::minutes(minutes_ago);
+        task
+    }
 
     #[test]
     fn branch_name_uses_task_prefix() {
@@ -183,6 +327,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn stack_parent_prefers_requested_task_then_newest_open_task() {
+        let older = mk_task("T-OLD", "repo", 30);
+        let newest = mk_task("T-NEW", "repo", 1);
+        let mut merged = mk_task("T-MERGED", "repo", 0);
+        merged.state = TaskState::Merged;
+        let other_repo = mk_task("T-OTHER", "other", 0);
+        let tasks = vec![older.clone(), newest, merged.clone(), other_repo.clone()];
+        let repo = RepoId("repo".to_string());
+
+        let parent = |preferred: Option<&Task>| {
+            choose_stack_parent(&tasks, &repo, preferred.map(|task| &task.id))
+                .map(|(task_id, _)| task_id.0)
+        };
+        assert_eq!(parent(Some(&older)).as_deref(), Some("T-OLD"));
+        assert_eq!(parent(None).as_deref(), Some("T-NEW"));
+        assert_eq!(parent(Some(&merged)).as_deref(), Some("T-NEW"));
+        assert_eq!(parent(Some(&other_repo)).as_deref(), Some("T-NEW"));
+    }
+
     #[test]
     fn branch_name_falls_back_when_empty_after_sanitize() {
         assert_eq!(branch_name_for_task(&TaskId::new("...")), "task/chat");
@@ -0,0 +1,814 @@
+//! `othala export-analytics`: denormalized tables for notebooks.
+//!
+//! Everything is read through a read-only [`SqliteStore`] and written either
+//! to one SQLite file or to one CSV per table, next to a `schema.json` that
+//! describes the tables. Derived tables are rebuilt on every export; `events`
+//! is append-only, so an incremental export only copies the events inserted
+//! since the watermark recorded by the previous one.
+
+use chrono::{DateTime, Utc};
+use orch_core::events::{Event, EventKind};
+use orch_core::types::Task;
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection, OptionalExtension};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::Path;
+
+use crate::persistence::SqliteStore;
+use crate::state_machine::task_state_tag;
+use crate::TaskRunRecord;
+
+/// Bumped whenever a table or column in [`ANALYTICS_TABLES`] changes; an
+/// incremental export refuses to append to output of another version.
+pub const ANALYTICS_SCHEMA_VERSION: i64 = 1;
+pub const ANALYTICS_SQLITE_FILE: &str = "analytics.sqlite";
+pub const ANALYTICS_SCHEMA_FILE: &str = "schema.json";
+
+const WATERMARK_TABLE: &str = "export_watermark";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnalyticsFormat {
+    Sqlite,
+    Csv,
+}
+
+impl AnalyticsFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AnalyticsFormat::Sqlite => "sqlite",
+            AnalyticsFormat::Csv => "csv",
+        }
+    }
+}
+
+/// How an export writes a table's rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TableMode {
+    /// Rebuilt from scratch on every export.
+    Replace,
+    /// Appended to by incremental exports; rebuilt by full ones.
+    Append,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ColumnSchema {
+    pub name: &'static str,
+    #[serde(rename = "type")]
+    pub sql_type: &'static str,
+    pub description: &'static str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct TableSchema {
+    pub name: &'static str,
+    pub mode: TableMode,
+    pub description: &'static str,
+    pub columns: &'static [ColumnSchema],
+}
+
+const fn column(
+    name: &'static str,
+    sql_type: &'static str,
+    description: &'static str,
+) -> ColumnSchema {
+    ColumnSchema {
+        name,
+        sql_type,
+        description,
+    }
+}
+
+/// Every exported table, in the order its rows are built.
+pub const ANALYTICS_TABLES: &[TableSchema] = &[
+    TableSchema {
+        name: "tasks",
+        mode: TableMode::Replace,
+        description: "One row per live or archived task.",
+        columns: &[
+            column("task_id", "TEXT", "Task identifier."),
+            column("repo_id", "TEXT", "Repository the task belongs to."),
+            column("title", "TEXT", "Task title."),
+            column("state", "TEXT", "State tag, e.g. CHATTING or MERGED."),
+            column("priority", "TEXT", "low, normal, high or critical."),
+            column("labels", "TEXT", "Comma-separated labels."),
+            column("preferred_model", "TEXT", "Preferred agent model, if any."),
+            column("branch_name", "TEXT", "Task branch, if one was created."),
+            column("parent_task_id", "TEXT", "Parent task, if stacked."),
+            column("retry_count", "INTEGER", "Agent retries so far."),
+            column("created_at", "TEXT", "RFC 3339 creation time."),
+            column("updated_at", "TEXT", "RFC 3339 time of the last change."),
+            column("archived", "INTEGER", "1 for archived tasks, else 0."),
+        ],
+    },
+    TableSchema {
+        name: "runs",
+        mode: TableMode::Replace,
+        description: "One row per agent run.",
+        columns: &[
+            column("run_id", "TEXT", "Run identifier."),
+            column("task_id", "TEXT", "Task the run worked on."),
+            column("repo_id", "TEXT", "Repository of the task."),
+            column("model", "TEXT", "Agent model."),
+            column("started_at", "TEXT", "RFC 3339 start time."),
+            column("finished_at", "TEXT", "RFC 3339 finish time, if done."),
+            column("stop_reason", "TEXT", "Why the run stopped."),
+            column("exit_code", "INTEGER", "Agent process exit code."),
+            column("estimated_tokens", "INTEGER", "Estimated tokens used."),
+            column("duration_secs", "REAL", "Wall-clock duration in seconds."),
+            column("confidence", "INTEGER", "Agent confidence in percent."),
+        ],
+    },
+    TableSchema {
+        name: "events",
+        mode: TableMode::Append,
+        description: "Every event, with commonly used kind fields pulled out.",
+        columns: &[
+            column("source_rowid", "INTEGER", "Row in the state database."),
+            column("event_id", "TEXT", "Event identifier."),
+            column("task_id", "TEXT", "Task the event concerns, if any."),
+            column("repo_id", "TEXT", "Repository the event concerns, if any."),
+            column("at", "TEXT", "RFC 3339 event time."),
+            column("kind", "TEXT", "Event kind, e.g. task_state_changed."),
+            column("state_from", "TEXT", "Previous state of a state change."),
+            column("state_to", "TEXT", "New state of a state change."),
+            column("model", "TEXT", "Model named by agent and retry events."),
+            column("success", "INTEGER", "1 or 0 for outcome events."),
+            column("reason", "TEXT", "Reason or message carried by the event."),
+            column("payload_json", "TEXT", "The full event as JSON."),
+        ],
+    },
+    TableSchema {
+        name: "state_durations",
+        mode: TableMode::Replace,
+        description: "Time each task spent in each state it passed through.",
+        columns: &[
+            column("task_id", "TEXT", "Task identifier."),
+            column("state", "TEXT", "State tag."),
+            column("entered_at", "TEXT", "RFC 3339 time the state was entered."),
+            column("exited_at", "TEXT", "RFC 3339 exit time, if left."),
+            column("duration_secs", "REAL", "Seconds in the state so far."),
+        ],
+    },
+    TableSchema {
+        name: "costs",
+        mode: TableMode::Replace,
+        description: "Run totals per task and model.",
+        columns: &[
+            column("task_id", "TEXT", "Task identifier."),
+            column("model", "TEXT", "Agent model."),
+            column("runs", "INTEGER", "Number of runs."),
+            column("estimated_tokens", "INTEGER", "Sum of estimated tokens."),
+            column("duration_secs", "REAL", "Sum of run durations in seconds."),
+        ],
+    },
+    TableSchema {
+        name: WATERMARK_TABLE,
+        mode: TableMode::Append,
+        description: "One row per export; the last row is the incremental watermark.",
+        columns: &[
+            column("export_id", "INTEGER", "Sequence number of the export."),
+            column("exported_at", "TEXT", "RFC 3339 export time."),
+            column("schema_version", "INTEGER", "Schema version written."),
+            column("incremental", "INTEGER", "1 if the export was incremental."),
+            column("events_exported", "INTEGER", "Events written."),
+            column("last_event_rowid", "INTEGER", "Highest rowid exported."),
+        ],
+    },
+];
+
+/// Contents of the emitted `schema.json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct AnalyticsSchema {
+    pub schema_version: i64,
+    pub format: &'static str,
+    pub tables: &'static [TableSchema],
+}
+
+/// Output of `othala export-analytics`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AnalyticsExportSummary {
+    pub export_id: i64,
+    pub incremental: bool,
+    pub tasks: usize,
+    pub runs: usize,
+    pub events: usize,
+    pub last_event_rowid: i64,
+}
+
+impl AnalyticsExportSummary {
+    pub fn mode_label(&self) -> &'static str {
+        if self.incremental {
+            "incremental"
+        } else {
+            "full"
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Watermark {
+    export_id: i64,
+    schema_version: i64,
+    last_event_rowid: i64,
+}
+
+type Row = Vec<Value>;
+
+/// Export from the state database at `db_path` through a read-only
+/// connection, so the export never migrates or locks the daemon's database.
+pub fn export_from_db(
+    db_path: &Path,
+    output: &Path,
+    format: AnalyticsFormat,
+    since_last: bool,
+) -> anyhow::Result<AnalyticsExportSummary> {
+    let store = SqliteStore::open_read_only(db_path)?;
+    export_analytics(&store, output, format, since_last, Utc::now())
+}
+
+/// Export analytics tables from `store` into `output`.
+///
+/// With `since_last`, append-mode tables only receive rows past the
+/// watermark left in `output` by the previous export; without one this is a
+/// full export.
+pub fn export_analytics(
+    store: &SqliteStore,
+    output: &Path,
+    format: AnalyticsFormat,
+    since_last: bool,
+    now: DateTime<Utc>,
+) -> anyhow::Result<AnalyticsExportSummary> {
+    std::fs::create_dir_all(output)?;
+    let watermark = if since_last {
+        read_watermark(output, format)?
+    } else {
+        None
+    };
+    if let Some(watermark) = watermark {
+        anyhow::ensure!(
+            watermark.schema_version == ANALYTICS_SCHEMA_VERSION,
+            "{} holds schema v{}, this build writes v{}; rerun without --since-last",
+            output.display(),
+            watermark.schema_version,
+            ANALYTICS_SCHEMA_VERSION
+        );
+    }
+    let incremental = watermark.is_some();
+    let after_rowid = watermark.map_or(0, |watermark| watermark.last_event_rowid);
+
+    let mut tasks: Vec<(Task, bool)> = store
+        .list_tasks()?
+        .into_iter()
+        .map(|task| (task, false))
+        .collect();
+    for record in store.list_archived()? {
+        match serde_json::from_str::<Task>(&record.payload_json) {
+            Ok(task) => tasks.push((task, true)),
+            Err(err) => eprintln!("skipping archived task {}: {err}", record.task_id.0),
+        }
+    }
+    let runs = store.list_runs()?;
+    let events = store.list_events_after_rowid(after_rowid)?;
+    let last_event_rowid = events.last().map_or(after_rowid, |(rowid, _)| *rowid);
+
+    let mut state_duration_rows = Vec::new();
+    for (task, _) in &tasks {
+        let history = store.list_events_for_task(&task.id.0)?;
+        state_duration_rows.extend(state_duration_rows_for(task, &history, now));
+    }
+
+    let export_id = watermark.map_or(1, |watermark| watermark.export_id + 1);
+    let tables: Vec<Vec<Row>> = vec![
+        tasks
+            .iter()
+            .map(|(task, archived)| task_row(task, *archived))
+            .collect(),
+        runs.iter().map(run_row).collect(),
+        events
+            .iter()
+            .map(|(rowid, event)| event_row(*rowid, event))
+            .collect(),
+        state_duration_rows,
+        cost_rows(&runs),
+        vec![vec![
+            Value::Integer(export_id),
+            Value::Text(now.to_rfc3339()),
+            Value::Integer(ANALYTICS_SCHEMA_VERSION),
+            Value::Integer(incremental as i64),
+            Value::Integer(events.len() as i64),
+            Value::Integer(last_event_rowid),
+        ]],
+    ];
+
+    match format {
+        AnalyticsFormat::Sqlite => write_sqlite(output, &tables, incremental)?,
+        AnalyticsFormat::Csv => write_csv(output, &tables, incremental)?,
+    }
+    let schema = AnalyticsSchema {
+        schema_version: ANALYTICS_SCHEMA_VERSION,
+        format: format.as_str(),
+        tables: ANALYTICS_TABLES,
+    };
+    std::fs::write(
+        output.join(ANALYTICS_SCHEMA_FILE),
+        serde_json::to_string_pretty(&schema)?,
+    )?;
+
+    Ok(AnalyticsExportSummary {
+        export_id,
+        incremental,
+        tasks: tasks.len(),
+        runs: runs.len(),
+        events: events.len(),
+        last_event_rowid,
+    })
+}
+
+fn optional_text(value: Option<String>) -> Value {
+    value.map_or(Value::Null, Value::Text)
+}
+
+fn task_row(task: &Task, archived: bool) -> Row {
+    vec![
+        Value::Text(task.id.0.clone()),
+        Value::Text(task.repo_id.0.clone()),
+        Value::Text(task.title.clone()),
+        Value::Text(task_state_tag(task.state).to_string()),
+        Value::Text(task.priority.as_str().to_string()),
+        Value::Text(task.labels.join(",")),
+        optional_text(task.preferred_model.map(|model| model.as_str().to_string())),
+        optional_text(task.branch_name.clone()),
+        optional_text(task.parent_task_id.as_ref().map(|id| id.0.clone())),
+        Value::Integer(i64::from(task.retry_count)),
+        Value::Text(task.created_at.to_rfc3339()),
+        Value::Text(task.updated_at.to_rfc3339()),
+        Value::Integer(archived as i64),
+    ]
+}
+
+fn run_row(run: &TaskRunRecord) -> Row {
+    vec![
+        Value::Text(run.run_id.clone()),
+        Value::Text(run.task_id.0.clone()),
+        Value::Text(run.repo_id.0.clone()),
+        Value::Text(run.model.as_str().to_string()),
+        Value::Text(run.started_at.to_rfc3339()),
+        optional_text(run.finished_at.map(|at| at.to_rfc3339())),
+        optional_text(run.stop_reason.clone()),
+        run.exit_code
+            .map_or(Value::Null, |code| Value::Integer(i64::from(code))),
+        run.estimated_tokens
+            .map_or(Value::Null, |tokens| Value::Integer(tokens as i64)),
+        run.duration_secs.map_or(Value::Null, Value::Real),
+        run.confidence.map_or(Value::Null, |confidence| {
+            Value::Integer(i64::from(confidence))
+        }),
+    ]
+}
+
+/// Event kinds serialize as `"name"` or `{"name": {fields}}`; the fields
+/// analysts filter on most are lifted into their own columns.
+fn event_row(rowid: i64, event: &Event) -> Row {
+    let kind = serde_json::to_value(&event.kind).unwrap_or_default();
+    let (name, fields) = match &kind {
+        serde_json::Value::String(name) => (name.clone(), None),
+        serde_json::Value::Object(map) => match map.iter().next() {
+            Some((name, fields)) => (name.clone(), fields.as_object()),
+            None => (String::new(), None),
+        },
+        _ => (String::new(), None),
+    };
+    let field = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| fields.and_then(|fields| fields.get(*key)))
+            .and_then(|value| value.as_str())
+            .map_or(Value::Null, |value| Value::Text(value.to_string()))
+    };
+    let (state_from, state_to) = match &event.kind {
+        EventKind::TaskStateChanged { from, to } => {
+            (Value::Text(from.clone()), Value::Text(to.clone()))
+        }
+        _ => (Value::Null, Value::Null),
+    };
+
+    vec![
+        Value::Integer(rowid),
+        Value::Text(event.id.0.clone()),
+        optional_text(event.task_id.as_ref().map(|id| id.0.clone())),
+        optional_text(event.repo_id.as_ref().map(|id| id.0.clone())),
+        Value::Text(event.at.to_rfc3339()),
+        Value::Text(name),
+        state_from,
+        state_to,
+        field(&["model", "to_model"]),
+        fields
+            .and_then(|fields| fields.get("success"))
+            .and_then(|value| value.as_bool())
+            .map_or(Value::Null, |success| Value::Integer(success as i64)),
+        field(&["reason", "message"]),
+        Value::Text(serde_json::to_string(event).unwrap_or_default()),
+    ]
+}
+
+/// Split a task's lifetime at each state change; the task is taken to start
+/// in the first change's `from` state, or its current state if it never
+/// changed.
+fn state_duration_rows_for(task: &Task, history: &[Event], now: DateTime<Utc>) -> Vec<Row> {
+    let changes: Vec<(DateTime<Utc>, &str, &str)> = history
+        .iter()
+        .filter_map(|event| match &event.kind {
+            EventKind::TaskStateChanged { from, to } => {
+                Some((event.at, from.as_str(), to.as_str()))
+            }
+            _ => None,
+        })
+        .collect();
+
+    let mut state = changes
+        .first()
+        .map_or(task_state_tag(task.state), |(_, from, _)| *from);
+    let mut entered_at = task.created_at;
+    let mut rows = Vec::new();
+    for (at, _, to) in changes {
+        rows.push(state_duration_row(task, state, entered_at, Some(at), now));
+        state = to;
+        entered_at = at;
+    }
+    rows.push(state_duration_row(task, state, entered_at, None, now));
+    rows
+}
+
+fn state_duration_row(
+    task: &Task,
+    state: &str,
+    entered_at: DateTime<Utc>,
+    exited_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Row {
+    let until = exited_at.unwrap_or(now);
+    vec![
+        Value::Text(task.id.0.clone()),
+        Value::Text(state.to_string()),
+        Value::Text(entered_at.to_rfc3339()),
+        optional_text(exited_at.map(|at| at.to_rfc3339())),
+        Value::Real((until - entered_at).num_milliseconds().max(0) as f64 / 1000.0),
+    ]
+}
+
+fn cost_rows(runs: &[TaskRunRecord]) -> Vec<Row> {
+    let mut totals: BTreeMap<(String, &str), (i64, i64, f64)> = BTreeMap::new();
+    for run in runs {
+        let total = totals
+            .entry((run.task_id.0.clone(), run.model.as_str()))
+            .or_default();
+        total.0 += 1;
+        total.1 += run.estimated_tokens.unwrap_or(0) as i64;
+        total.2 += run.duration_secs.unwrap_or(0.0);
+    }
+    totals
+        .into_iter()
+        .map(|((task_id, model), (runs, tokens, duration))| {
+            vec![
+                Value::Text(task_id),
+                Value::Text(model.to_string()),
+                Value::Integer(runs),
+                Value::Integer(tokens),
+                Value::Real(duration),
+            ]
+        })
+        .collect()
+}
+
+fn read_watermark(output: &Path, format: AnalyticsFormat) -> anyhow::Result<Option<Watermark>> {
+    match format {
+        AnalyticsFormat::Sqlite => {
+            let path = output.join(ANALYTICS_SQLITE_FILE);
+            if !path.exists() {
+                return Ok(None);
+            }
+            let conn = Connection::open(path)?;
+            let has_table = conn
+                .query_row(
+                    "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1",
+                    [WATERMARK_TABLE],
+                    |_| Ok(()),
+                )
+                .optional()?
+                .is_some();
+            if !has_table {
+                return Ok(None);
+            }
+            Ok(conn
+                .query_row(
+                    &format!(
+                        "SELECT export_id, schema_version, last_event_rowid FROM {WATERMARK_TABLE} \
+                         ORDER BY export_id DESC LIMIT 1"
+                    ),
+                    [],
+                    |row| {
+                        Ok(Watermark {
+                            export_id: row.get(0)?,
+                            schema_version: row.get(1)?,
+                            last_event_rowid: row.get(2)?,
+                        })
+                    },
+                )
+                .optional()?)
+        }
+        AnalyticsFormat::Csv => {
+            let path = output.join(format!("{WATERMARK_TABLE}.csv"));
+            let Ok(contents) = std::fs::read_to_string(&path) else {
+                return Ok(None);
+            };
+            let Some(last) = contents
+                .lines()
+                .skip(1)
+                .filter(|line| !line.is_empty())
+                .last()
+            else {
+                return Ok(None);
+            };
+            let fields: Vec<&str> = last.split(',').collect();
+            let parse = |index: usize| -> anyhow::Result<i64> {
+                fields
+                    .get(index)
+                    .and_then(|field| field.parse().ok())
+                    .ok_or_else(|| anyhow::anyhow!("malformed watermark row in {}", path.display()))
+            };
+            Ok(Some(Watermark {
+                export_id: parse(0)?,
+                schema_version: parse(2)?,
+                last_event_rowid: parse(5)?,
+            }))
+        }
+    }
+}
+
+fn write_sqlite(output: &Path, tables: &[Vec<Row>], incremental: bool) -> anyhow::Result<()> {
+    let mut conn = Connection::open(output.join(ANALYTICS_SQLITE_FILE))?;
+    let tx = conn.transaction()?;
+    for (schema, rows) in ANALYTICS_TABLES.iter().zip(tables) {
+        if !incremental || schema.mode == TableMode::Replace {
+            tx.execute_batch(&format!("DROP TABLE IF EXISTS {};", schema.name))?;
+        }
+        let columns = schema
+            .columns
+            .iter()
+            .map(|column| format!("{} {}", column.name, column.sql_type))
+            .collect::<Vec<_>>()
+            .join(", ");
+        tx.execute_batch(&format!(
+            "CREATE TABLE IF NOT EXISTS {} ({columns});",
+            schema.name
+        ))?;
+
+        let placeholders = vec!["?"; schema.columns.len()].join(", ");
+        let mut insert = tx.prepare(&format!(
+            "INSERT INTO {} VALUES ({placeholders})",
+            schema.name
+        ))?;
+        for row in rows {
+            insert.execute(params_from_iter(row.iter()))?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+fn write_csv(output: &Path, tables: &[Vec<Row>], incremental: bool) -> anyhow::Result<()> {
+    for (schema, rows) in ANALYTICS_TABLES.iter().zip(tables) {
+        let path = output.join(format!("{}.csv", schema.name));
+        let append = incremental && schema.mode == TableMode::Append && path.exists();
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(append)
+            .truncate(!append)
+            .open(&path)?;
+        if !append {
+            let header: Vec<&str> = schema.columns.iter().map(|column| column.name).collect();
+            writeln!(file, "{}", header.join(","))?;
+        }
+        for row in rows {
+            let fields: Vec<String> = row.iter().map(csv_field).collect();
+            writeln!(file, "{}", fields.join(","))?;
+        }
+    }
+    Ok(())
+}
+
+fn csv_field(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Integer(value) => value.to_string(),
+        Value::Real(value) => value.to_string(),
+        Value::Text(text) => {
+            if text.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", text.replace('"', "\"\""))
+            } else {
+                text.clone()
+            }
+        }
+        Value::Blob(bytes) => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::testing::mk_task;
+    use chrono::Duration;
+    use orch_core::state::TaskState;
+    use orch_core::types::{EventId, ModelKind};
+    use std::path::PathBuf;
+
+    fn temp_dir(tag: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "othala-analytics-{tag}-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    fn state_change(id: &str, task: &Task, at: DateTime<Utc>, from: &str, to: &str) -> Event {
+        Event {
+            id: EventId(id.to_string()),
+            task_id: Some(task.id.clone()),
+            repo_id: Some(task.repo_id.clone()),
+            at,
+            kind: EventKind::TaskStateChanged {
+                from: from.to_string(),
+                to: to.to_string(),
+            },
+        }
+    }
+
+    /// A state database with one merged task, one run and two state changes.
+    fn seeded_db(dir: &Path) -> (PathBuf, Task, DateTime<Utc>) {
+        let db_path = dir.join("state.sqlite");
+        let store = SqliteStore::open(&db_path).expect("open db");
+        store.migrate().expect("migrate");
+
+        let created = Utc::now() - Duration::hours(2);
+        let mut task = mk_task("T-ANALYTICS-1", TaskState::Merged);
+        task.labels = vec!["infra".to_string(), "q3, urgent".to_string()];
+        task.created_at = created;
+        task.updated_at = created + Duration::minutes(90);
+        store.upsert_task(&task).expect("upsert task");
+        store
+            .insert_run(&TaskRunRecord {
+                run_id: "R-1".to_string(),
+                task_id: task.id.clone(),
+                repo_id: task.repo_id.clone(),
+                model: ModelKind::Claude,
+                started_at: created,
+                finished_at: Some(created + Duration::minutes(30)),
+                stop_reason: Some("completed".to_string()),
+                exit_code: Some(0),
+                estimated_tokens: Some(1200),
+                duration_secs: Some(1800.0),
+                confidence: Some(80),
+            })
+            .expect("insert run");
+        store
+            .append_event(&state_change(
+                "E-1",
+                &task,
+                created + Duration::minutes(30),
+                "CHATTING",
+                "READY",
+            ))
+            .expect("append event");
+        store
+            .append_event(&state_change(
+                "E-2",
+                &task,
+                created + Duration::minutes(90),
+                "READY",
+                "MERGED",
+            ))
+            .expect("append event");
+        (db_path, task, created)
+    }
+
+    #[test]
+    fn sqlite_export_appends_only_new_events_since_last_watermark() {
+        let dir = temp_dir("sqlite");
+        let (db_path, task, created) = seeded_db(&dir);
+        let output = dir.join("out");
+        let now = created + Duration::hours(2);
+
+        let store = SqliteStore::open_read_only(&db_path).expect("open read-only");
+        assert!(store.upsert_task(&task).is_err());
+        let first = export_analytics(&store, &output, AnalyticsFormat::Sqlite, true, now)
+            .expect("first export");
+        assert_eq!(
+            first,
+            AnalyticsExportSummary {
+                export_id: 1,
+                incremental: false,
+                tasks: 1,
+                runs: 1,
+                events: 2,
+                last_event_rowid: 2,
+            }
+        );
+
+        SqliteStore::open(&db_path)
+            .expect("open db")
+            .append_event(&state_change("E-3", &task, now, "MERGED", "STOPPED"))
+            .expect("append event");
+        let second = export_analytics(&store, &output, AnalyticsFormat::Sqlite, true, now)
+            .expect("second export");
+        assert!(second.incremental);
+        assert_eq!(second.export_id, 2);
+        assert_eq!(second.events, 1);
+        assert_eq!(second.last_event_rowid, 3);
+
+        let conn = Connection::open(output.join(ANALYTICS_SQLITE_FILE)).expect("open export");
+        let count = |sql: &str| -> i64 { conn.query_row(sql, [], |row| row.get(0)).unwrap() };
+        assert_eq!(count("SELECT COUNT(*) FROM events"), 3);
+        assert_eq!(count("SELECT COUNT(*) FROM tasks"), 1);
+        assert_eq!(count("SELECT COUNT(*) FROM export_watermark"), 2);
+        assert_eq!(
+            count("SELECT COUNT(*) FROM events WHERE kind = 'task_state_changed' AND state_to = 'STOPPED'"),
+            1
+        );
+        assert_eq!(
+            count("SELECT estimated_tokens FROM costs WHERE model = 'claude'"),
+            1200
+        );
+        let ready_secs: f64 = conn
+            .query_row(
+                "SELECT duration_secs FROM state_durations WHERE state = 'READY'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(ready_secs, 3600.0);
+
+        let schema: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(output.join(ANALYTICS_SCHEMA_FILE)).expect("schema.json"),
+        )
+        .expect("schema json");
+        assert_eq!(schema["schema_version"], ANALYTICS_SCHEMA_VERSION);
+        assert_eq!(
+            schema["tables"].as_array().map(Vec::len),
+            Some(ANALYTICS_TABLES.len())
+        );
+    }
+
+    #[test]
+    fn csv_export_quotes_fields_and_appends_events_incrementally() {
+        let dir = temp_dir("csv");
+        let (db_path, task, created) = seeded_db(&dir);
+        let output = dir.join("out");
+        let now = created + Duration::hours(2);
+        let store = SqliteStore::open_read_only(&db_path).expect("open read-only");
+
+        export_analytics(&store, &output, AnalyticsFormat::Csv, false, now).expect("export");
+        let tasks = std::fs::read_to_string(output.join("tasks.csv")).expect("tasks.csv");
+        assert!(tasks.starts_with("task_id,repo_id,title,"));
+        assert!(tasks.contains("\"infra,q3, urgent\""));
+
+        SqliteStore::open(&db_path)
+            .expect("open db")
+            .append_event(&state_change("E-3", &task, now, "MERGED", "STOPPED"))
+            .expect("append event");
+        let summary = export_analytics(&store, &output, AnalyticsFormat::Csv, true, now)
+            .expect("incremental export");
+        assert_eq!(summary.events, 1);
+
+        let events = std::fs::read_to_string(output.join("events.csv")).expect("events.csv");
+        assert_eq!(events.lines().count(), 4);
+        let watermark = read_watermark(&output, AnalyticsFormat::Csv).expect("read watermark");
+        assert_eq!(
+            watermark,
+            Some(Watermark {
+                export_id: 2,
+                schema_version: ANALYTICS_SCHEMA_VERSION,
+                last_event_rowid: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn export_from_db_never_creates_the_state_database() {
+        let dir = temp_dir("missing");
+        let db_path = dir.join("state.sqlite");
+        assert!(
+            export_from_db(&db_path, &dir.join("out"), AnalyticsFormat::Sqlite, false).is_err()
+        );
+        assert!(!db_path.exists());
+
+        let (db_path, _, _) = seeded_db(&dir);
+        let summary = export_from_db(&db_path, &dir.join("out"), AnalyticsFormat::Csv, false)
+            .expect("export");
+        assert_eq!(summary.mode_label(), "full");
+        assert_eq!(summary.tasks, 1);
+    }
+}
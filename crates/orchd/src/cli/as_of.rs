@@ -0,0 +1,173 @@
+//! `othala list --as-of`: the task board reconstructed from the event log.
+
+use chrono::{DateTime, Utc};
+use orch_core::events::{Event, EventKind};
+use orch_core::state::TaskState;
+use orch_core::types::Task;
+use serde::Serialize;
+
+use super::parse_task_state_filter;
+use crate::OrchdService;
+
+/// Output of `othala list --as-of`.
+#[derive(Debug, Clone, Serialize)]
+pub struct AsOfList {
+    pub as_of: DateTime<Utc>,
+    pub tasks: Vec<Task>,
+    /// Events that could not be applied, e.g. state changes with unknown tags.
+    pub warnings: usize,
+}
+
+/// Rebuild the task list as it stood at `at` without touching the store.
+pub fn list(service: &OrchdService, at: DateTime<Utc>) -> anyhow::Result<AsOfList> {
+    let tasks = service.list_tasks()?;
+    let events = service.store.list_all_events(None, None)?;
+    Ok(rebuild(tasks, &events, at))
+}
+
+/// Rewind each task in `tasks` to `at` by replaying `events` (oldest first).
+///
+/// A task's state is the `to` of its last state change at or before `at`, or
+/// the `from` of its first change after it. Priority is not evented, so it is
+/// kept as it is now. Tasks created after `at` are dropped; tasks deleted
+/// since are gone from the store and cannot be shown.
+pub fn rebuild(tasks: Vec<Task>, events: &[Event], at: DateTime<Utc>) -> AsOfList {
+    let mut warnings = 0;
+    let mut rebuilt = Vec::new();
+
+    for mut task in tasks {
+        let mut created_at = task.created_at;
+        let mut state_before: Option<(TaskState, DateTime<Utc>)> = None;
+        let mut state_after: Option<TaskState> = None;
+
+        for event in events {
+            if event.task_id.as_ref() != Some(&task.id) {
+                continue;
+            }
+            match &event.kind {
+                EventKind::TaskCreated => created_at = created_at.min(event.at),
+                EventKind::TaskStateChanged { from, to } => {
+                    if event.at <= at {
+                        match parse_task_state_filter(to) {
+                            Ok(state) => state_before = Some((state, event.at)),
+                            Err(_) => warnings += 1,
+                        }
+                    } else if state_after.is_none() {
+                        match parse_task_state_filter(from) {
+                            Ok(state) => state_after = Some(state),
+                            Err(_) => warnings += 1,
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if created_at > at {
+            continue;
+        }
+        match (state_before, state_after) {
+            (Some((state, changed_at)), _) => {
+                task.state = state;
+                task.updated_at = changed_at;
+            }
+            (None, Some(state)) => {
+                task.state = state;
+                task.updated_at = created_at;
+            }
+            (None, None) => {}
+        }
+        rebuilt.push(task);
+    }
+
+    AsOfList {
+        as_of: at,
+        tasks: rebuilt,
+        warnings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use orch_core::types::EventId;
+
+    use crate::cli::testing::mk_task;
+
+    fn state_changed(task: &Task, at: DateTime<Utc>, from: &str, to: &str) -> Event {
+        Event {
+            id: EventId(format!("E-STATE-{}-{}", task.id.0, at.timestamp_millis())),
+            task_id: Some(task.id.clone()),
+            repo_id: Some(task.repo_id.clone()),
+            at,
+            kind: EventKind::TaskStateChanged {
+                from: from.to_string(),
+                to: to.to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn rebuild_rewinds_states_and_drops_later_tasks() {
+        let t0 = Utc::now() - Duration::hours(3);
+        let at = t0 + Duration::hours(1);
+
+        let mut submitted = mk_task("T-ASOF-SUBMITTED", TaskState::Merged);
+        submitted.created_at = t0;
+        let mut untouched = mk_task("T-ASOF-UNTOUCHED", TaskState::Chatting);
+        untouched.created_at = t0;
+        let mut later = mk_task("T-ASOF-LATER", TaskState::Chatting);
+        later.created_at = at + Duration::minutes(10);
+
+        let events = vec![
+            state_changed(&submitted, t0 + Duration::minutes(10), "CHATTING", "READY"),
+            state_changed(&submitted, t0 + Duration::minutes(20), "READY", "BOGUS"),
+            state_changed(
+                &submitted,
+                at + Duration::minutes(5),
+                "READY",
+                "AWAITING_MERGE",
+            ),
+            state_changed(
+                &submitted,
+                at + Duration::minutes(30),
+                "AWAITING_MERGE",
+                "MERGED",
+            ),
+        ];
+
+        let list = rebuild(vec![submitted.clone(), untouched, later], &events, at);
+        let states: Vec<(&str, TaskState)> = list
+            .tasks
+            .iter()
+            .map(|task| (task.id.0.as_str(), task.state))
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                ("T-ASOF-SUBMITTED", TaskState::Ready),
+                ("T-ASOF-UNTOUCHED", TaskState::Chatting),
+            ]
+        );
+        assert_eq!(list.warnings, 1);
+        assert_eq!(list.tasks[0].priority, submitted.priority);
+    }
+
+    #[test]
+    fn rebuild_uses_the_next_change_when_none_precede_the_timestamp() {
+        let t0 = Utc::now() - Duration::hours(2);
+        let mut task = mk_task("T-ASOF-FROM", TaskState::Stopped);
+        task.created_at = t0;
+        let events = vec![state_changed(
+            &task,
+            t0 + Duration::hours(1),
+            "CHATTING",
+            "STOPPED",
+        )];
+
+        let list = rebuild(vec![task], &events, t0 + Duration::minutes(5));
+        assert_eq!(list.tasks[0].state, TaskState::Chatting);
+        assert_eq!(list.warnings, 0);
+    }
+}
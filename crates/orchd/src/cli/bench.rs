@@ -0,0 +1,134 @@
+//! `othala bench-suite`: run benchmark specs through the daemon and compare
+//! tagged results.
+
+use std::path::{Path, PathBuf};
+
+use orch_agents::register_custom_models;
+use orch_core::config::{load_org_config, OrgConfig};
+use orch_core::types::ModelKind;
+
+use crate::bench_suite::{self, BenchComparison};
+use crate::supervisor::AgentSupervisor;
+use crate::{OrchdService, Scheduler, SchedulerConfig};
+
+/// Run the suite through the normal daemon machinery against a scratch state
+/// database under `.orch/bench/<tag>/`, recording results in the main store.
+pub fn run(
+    service: &OrchdService,
+    repo_root: &Path,
+    dir: &Path,
+    iterations: u32,
+    tag: &str,
+    timeout_secs: u64,
+) -> anyhow::Result<()> {
+    let specs = bench_suite::load_bench_specs(dir)?;
+    let config_path = repo_root.join(".othala/config.toml");
+    let org_config = if config_path.exists() {
+        load_org_config(&config_path)?
+    } else {
+        OrgConfig::default()
+    };
+    register_custom_models(&org_config.models);
+
+    let bench_root = bench_suite::bench_dir(repo_root, tag);
+    if bench_root.exists() {
+        std::fs::remove_dir_all(&bench_root)?;
+    }
+    let bench_events = bench_root.join("events");
+    std::fs::create_dir_all(&bench_events)?;
+    let bench_service = OrchdService::open(
+        bench_root.join("state.sqlite"),
+        &bench_events,
+        Scheduler::new(SchedulerConfig::from_org_config(&org_config)),
+    )?;
+
+    let default_model = org_config.models.default.unwrap_or(ModelKind::Claude);
+    let mut supervisor = AgentSupervisor::new(default_model)
+        .with_output_buffer_bytes(org_config.daemon.output_buffer_bytes);
+    let mut daemon_state = crate::daemon_loop::DaemonState::new();
+    let daemon_config = crate::daemon_loop::DaemonConfig {
+        nix_shell: crate::daemon_loop::detect_nix_shell(repo_root),
+        repo_root: repo_root.to_path_buf(),
+        template_dir: PathBuf::from("templates/prompts"),
+        enabled_models: org_config.models.enabled.clone(),
+        context_config: crate::context_graph::ContextLoadConfig::default(),
+        verify_command: Some(
+            org_config
+                .daemon
+                .verify_command
+                .clone()
+                .unwrap_or_else(|| "cargo check && cargo test --workspace".to_string()),
+        ),
+        context_gen_config: (&org_config.daemon.context_gen).into(),
+        skip_qa: false,
+        skip_context_regen: true,
+        dry_run: false,
+        agent_timeout_secs: org_config.daemon.agent_timeout_secs,
+        drain_timeout_secs: 30,
+        post_merge_hook: None,
+        retry_guard: org_config.daemon.retry_guard.clone(),
+        step_timeouts: (&org_config.daemon.pipeline_timeouts).into(),
+        question_stale_secs: org_config.daemon.question_stale_secs,
+        merge_poll: org_config.daemon.merge_poll.clone(),
+        auto_resolve_conflicts: org_config.graphite.auto_resolve_conflicts,
+        auto_approve_min_confidence: org_config.daemon.auto_approve_min_confidence,
+        pipeline_checkpoints: false,
+        max_spawns_per_tick: org_config.daemon.max_spawns_per_tick,
+        verify_limits: crate::daemon_loop::detect_verify_limits(repo_root),
+        skip_fast_verify: false,
+        fast_verify_commands: crate::daemon_loop::detect_fast_verify_commands(repo_root),
+        generate_qa_specs: org_config.daemon.generate_qa_specs,
+    };
+
+    service.store.delete_bench_results(tag)?;
+    let timeout = std::time::Duration::from_secs(timeout_secs);
+    let tick_interval = std::time::Duration::from_secs(org_config.daemon.tick_interval_secs);
+    for iteration in 1..=iterations {
+        eprintln!(
+            "[bench] {tag}: iteration {iteration}/{iterations} ({} specs)",
+            specs.len()
+        );
+        let results = bench_suite::run_iteration(
+            &bench_service,
+            &mut supervisor,
+            &mut daemon_state,
+            &daemon_config,
+            &specs,
+            tag,
+            iteration,
+            timeout,
+            tick_interval,
+        )?;
+        for result in &results {
+            service.store.insert_bench_result(result)?;
+            println!(
+                "{:<24} #{:<3} {:<7} retries={} tokens={} {:.0}s",
+                result.spec,
+                result.iteration,
+                result.outcome.as_str(),
+                result.retries,
+                result.tokens,
+                result.duration_secs
+            );
+        }
+    }
+    supervisor.stop_all();
+    println!("Recorded bench results under tag '{tag}'");
+    Ok(())
+}
+
+/// Compare the results recorded under two tags; both must have results.
+pub fn compare(
+    service: &OrchdService,
+    tag_a: &str,
+    tag_b: &str,
+) -> anyhow::Result<BenchComparison> {
+    let a = service.store.list_bench_results(tag_a)?;
+    let b = service.store.list_bench_results(tag_b)?;
+    for (tag, results) in [(tag_a, &a), (tag_b, &b)] {
+        if results.is_empty() {
+            anyhow::bail!("no bench results recorded under tag '{tag}'");
+        }
+    }
+    Ok(bench_suite::compare(tag_a, &a, tag_b, &b))
+}
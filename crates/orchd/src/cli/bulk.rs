@@ -0,0 +1,422 @@
+//! `othala bulk` and the `--state` forms of `tag`/`untag`.
+
+use chrono::{Duration, Utc};
+use orch_core::events::CancelReason;
+use orch_core::state::TaskState;
+use orch_core::types::{EventId, Task, TaskPriority};
+use std::collections::HashSet;
+
+use super::parse_task_state_filter;
+use super::tasks::{
+    add_task_labels, cancel_task, ensure_labels_non_empty, remove_task_labels, update_priority,
+};
+use crate::OrchdService;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BulkSummary {
+    pub processed: usize,
+    pub succeeded: usize,
+    pub skipped: usize,
+}
+
+impl BulkSummary {
+    fn new(processed: usize) -> Self {
+        Self {
+            processed,
+            succeeded: 0,
+            skipped: 0,
+        }
+    }
+}
+
+/// Tasks a bulk action applies to: those in `state` (if given) whose IDs are
+/// in `ids` (if any).
+pub fn select_bulk_tasks(
+    service: &OrchdService,
+    state: Option<&str>,
+    ids: &[String],
+) -> anyhow::Result<Vec<Task>> {
+    let state_filter = match state {
+        Some(value) => Some(parse_task_state_filter(value)?),
+        None => None,
+    };
+    let id_filter: HashSet<&str> = ids.iter().map(String::as_str).collect();
+
+    let tasks = service
+        .list_tasks()?
+        .into_iter()
+        .filter(|task| match state_filter {
+            Some(wanted) => task.state == wanted,
+            None => true,
+        })
+        .filter(|task| id_filter.is_empty() || id_filter.contains(task.id.0.as_str()))
+        .collect();
+
+    Ok(tasks)
+}
+
+/// The tasks a bulk action would touch, without touching them. The summary
+/// counts them as processed with none succeeded or skipped.
+pub fn dry_run(
+    service: &OrchdService,
+    state: Option<&str>,
+    ids: &[String],
+) -> anyhow::Result<(Vec<Task>, BulkSummary)> {
+    let tasks = select_bulk_tasks(service, state, ids)?;
+    let summary = BulkSummary::new(tasks.len());
+    Ok((tasks, summary))
+}
+
+/// Move matching tasks back to chatting with their retry count reset.
+pub fn retry(
+    service: &OrchdService,
+    state: Option<&str>,
+    ids: &[String],
+) -> anyhow::Result<BulkSummary> {
+    let tasks = select_bulk_tasks(service, state, ids)?;
+    let mut summary = BulkSummary::new(tasks.len());
+
+    for task in tasks {
+        let now = Utc::now();
+        let event_id = EventId(format!(
+            "E-BULK-RETRY-{}-{}",
+            task.id.0,
+            now.timestamp_millis()
+        ));
+        if let Err(err) =
+            service.transition_task_state(&task.id, TaskState::Chatting, event_id, now)
+        {
+            eprintln!("skipping {}: {err}", task.id.0);
+            summary.skipped += 1;
+            continue;
+        }
+
+        let Some(mut updated) = service.task(&task.id)? else {
+            summary.skipped += 1;
+            continue;
+        };
+        updated.retry_count = 0;
+        updated.updated_at = Utc::now();
+        service.store.upsert_task(&updated)?;
+        summary.succeeded += 1;
+    }
+
+    Ok(summary)
+}
+
+pub fn cancel(
+    service: &OrchdService,
+    state: Option<&str>,
+    ids: &[String],
+) -> anyhow::Result<BulkSummary> {
+    let tasks = select_bulk_tasks(service, state, ids)?;
+    let mut summary = BulkSummary::new(tasks.len());
+
+    for task in tasks {
+        if cancel_task(service, &task.id, CancelReason::UserRequested).is_ok() {
+            summary.succeeded += 1;
+        } else {
+            summary.skipped += 1;
+        }
+    }
+
+    Ok(summary)
+}
+
+pub fn set_priority(
+    service: &OrchdService,
+    priority: TaskPriority,
+    state: Option<&str>,
+    ids: &[String],
+) -> anyhow::Result<BulkSummary> {
+    let tasks = select_bulk_tasks(service, state, ids)?;
+    let mut summary = BulkSummary::new(tasks.len());
+
+    for task in tasks {
+        if update_priority(service, &task.id, priority).is_ok() {
+            summary.succeeded += 1;
+        } else {
+            summary.skipped += 1;
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Tasks `othala bulk delete` considers: those matching `state` and `ids`
+/// that were last updated more than `older_than_days` ago (if given),
+/// children before their parents.
+pub fn delete_candidates(
+    service: &OrchdService,
+    state: Option<&str>,
+    older_than_days: Option<i64>,
+    ids: &[String],
+) -> anyhow::Result<Vec<Task>> {
+    let cutoff = older_than_days.map(|days| Utc::now() - Duration::days(days));
+    let mut tasks: Vec<Task> = select_bulk_tasks(service, state, ids)?
+        .into_iter()
+        .filter(|task| cutoff.is_none_or(|cutoff| task.updated_at < cutoff))
+        .collect();
+    tasks.sort_by_key(|task| task.parent_task_id.is_none());
+    Ok(tasks)
+}
+
+/// What `bulk delete` would remove, without removing it. Tasks that are
+/// still live are counted as skipped.
+pub fn delete_dry_run(
+    service: &OrchdService,
+    state: Option<&str>,
+    older_than_days: Option<i64>,
+    ids: &[String],
+) -> anyhow::Result<(Vec<Task>, BulkSummary)> {
+    let tasks = delete_candidates(service, state, older_than_days, ids)?;
+    let mut summary = BulkSummary::new(tasks.len());
+    summary.skipped = tasks.iter().filter(|t| !t.state.is_terminal()).count();
+    Ok((tasks, summary))
+}
+
+/// Delete the terminal tasks among [`delete_candidates`]. Tasks that are
+/// still live, or that have children left, are skipped.
+pub fn delete(
+    service: &OrchdService,
+    state: Option<&str>,
+    older_than_days: Option<i64>,
+    ids: &[String],
+) -> anyhow::Result<BulkSummary> {
+    let tasks = delete_candidates(service, state, older_than_days, ids)?;
+    let mut summary = BulkSummary::new(tasks.len());
+
+    for task in tasks {
+        if !task.state.is_terminal() {
+            summary.skipped += 1;
+            continue;
+        }
+        match service.delete_task(&task.id, false) {
+            Ok(deleted) if !deleted.is_empty() => summary.succeeded += 1,
+            _ => summary.skipped += 1,
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Add (or with `remove`, drop) `labels` on every task in `state`.
+pub fn tag(
+    service: &OrchdService,
+    labels: &[String],
+    state: &str,
+    remove: bool,
+) -> anyhow::Result<BulkSummary> {
+    ensure_labels_non_empty(labels)?;
+    let tasks = select_bulk_tasks(service, Some(state), &[])?;
+    let mut summary = BulkSummary::new(tasks.len());
+
+    for task in tasks {
+        let result = if remove {
+            remove_task_labels(service, &task.id, labels)
+        } else {
+            add_task_labels(service, &task.id, labels)
+        };
+        if result.is_ok() {
+            summary.succeeded += 1;
+        } else {
+            summary.skipped += 1;
+        }
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orch_core::events::EventKind;
+
+    use crate::cli::testing::{create, mk_service, mk_task};
+
+    #[test]
+    fn dry_run_selects_without_changing_state() {
+        let service = mk_service();
+        let mut stopped = mk_task("T-BULK-DRY-A", TaskState::Stopped);
+        stopped.retry_count = 2;
+        let other = mk_task("T-BULK-DRY-B", TaskState::Stopped);
+        let chatting = mk_task("T-BULK-DRY-C", TaskState::Chatting);
+        for task in [&stopped, &other, &chatting] {
+            create(&service, task);
+        }
+
+        let ids = vec![stopped.id.0.clone(), chatting.id.0.clone()];
+        let (tasks, summary) = dry_run(&service, Some("stopped"), &ids).expect("dry run");
+        assert_eq!(
+            tasks.iter().map(|t| t.id.clone()).collect::<Vec<_>>(),
+            vec![stopped.id.clone()]
+        );
+        assert_eq!(
+            summary,
+            BulkSummary {
+                processed: 1,
+                succeeded: 0,
+                skipped: 0,
+            }
+        );
+
+        let reloaded = service
+            .task(&stopped.id)
+            .expect("load")
+            .expect("task exists");
+        assert_eq!(reloaded.state, TaskState::Stopped);
+        assert_eq!(reloaded.retry_count, 2);
+        assert!(service
+            .store
+            .list_events_for_task(&stopped.id.0)
+            .expect("events")
+            .iter()
+            .all(|event| !matches!(event.kind, EventKind::TaskStateChanged { .. })));
+    }
+
+    #[test]
+    fn bulk_delete_removes_matching_terminal_tasks_only() {
+        let service = mk_service();
+        let mut old_merged = mk_task("T-BULK-DEL-A", TaskState::Merged);
+        old_merged.updated_at = Utc::now() - Duration::days(10);
+        let mut old_stopped = mk_task("T-BULK-DEL-B", TaskState::Stopped);
+        old_stopped.updated_at = Utc::now() - Duration::days(10);
+        let mut old_chatting = mk_task("T-BULK-DEL-C", TaskState::Chatting);
+        old_chatting.updated_at = Utc::now() - Duration::days(10);
+        let recent_merged = mk_task("T-BULK-DEL-D", TaskState::Merged);
+        for task in [&old_merged, &old_stopped, &old_chatting, &recent_merged] {
+            create(&service, task);
+        }
+
+        let (planned, preview) = delete_dry_run(&service, None, Some(7), &[]).expect("dry run");
+        assert_eq!(planned.len(), 3);
+        assert_eq!(
+            preview,
+            BulkSummary {
+                processed: 3,
+                succeeded: 0,
+                skipped: 1,
+            }
+        );
+
+        let summary = delete(&service, None, Some(7), &[]).expect("bulk delete");
+        assert_eq!(
+            summary,
+            BulkSummary {
+                processed: 3,
+                succeeded: 2,
+                skipped: 1,
+            }
+        );
+
+        let exists = |task: &Task| service.task(&task.id).expect("load").is_some();
+        assert!(!exists(&old_merged));
+        assert!(!exists(&old_stopped));
+        assert!(exists(&old_chatting));
+        assert!(exists(&recent_merged));
+    }
+
+    #[test]
+    fn bulk_cancel_by_state() {
+        let service = mk_service();
+        let task_a = mk_task("T-BULK-CANCEL-A", TaskState::Chatting);
+        let task_b = mk_task("T-BULK-CANCEL-B", TaskState::Chatting);
+        let task_c = mk_task("T-BULK-CANCEL-C", TaskState::Ready);
+        for task in [&task_a, &task_b, &task_c] {
+            create(&service, task);
+        }
+
+        let ids = Vec::new();
+        let summary = cancel(&service, Some("chatting"), &ids).expect("bulk cancel");
+        assert_eq!(summary.processed, 2);
+        assert_eq!(summary.succeeded, 2);
+        assert_eq!(summary.skipped, 0);
+
+        assert_eq!(
+            service
+                .task(&task_a.id)
+                .expect("load a")
+                .expect("a exists")
+                .state,
+            TaskState::Stopped
+        );
+        assert_eq!(
+            service
+                .task(&task_b.id)
+                .expect("load b")
+                .expect("b exists")
+                .state,
+            TaskState::Stopped
+        );
+        assert_eq!(
+            service
+                .task(&task_c.id)
+                .expect("load c")
+                .expect("c exists")
+                .state,
+            TaskState::Ready
+        );
+    }
+
+    #[test]
+    fn bulk_retry_by_ids() {
+        let service = mk_service();
+        let mut task_a = mk_task("T-BULK-RETRY-A", TaskState::Stopped);
+        task_a.retry_count = 3;
+        let mut task_b = mk_task("T-BULK-RETRY-B", TaskState::Ready);
+        task_b.retry_count = 1;
+        let mut task_c = mk_task("T-BULK-RETRY-C", TaskState::Merged);
+        task_c.retry_count = 2;
+        for task in [&task_a, &task_b, &task_c] {
+            create(&service, task);
+        }
+
+        let ids = vec!["T-BULK-RETRY-A".to_string(), "T-BULK-RETRY-B".to_string()];
+        let summary = retry(&service, None, &ids).expect("bulk retry");
+        assert_eq!(summary.processed, 2);
+        assert_eq!(summary.succeeded, 2);
+        assert_eq!(summary.skipped, 0);
+
+        let updated_a = service.task(&task_a.id).expect("load a").expect("a exists");
+        let updated_b = service.task(&task_b.id).expect("load b").expect("b exists");
+        let updated_c = service.task(&task_c.id).expect("load c").expect("c exists");
+        assert_eq!(updated_a.state, TaskState::Chatting);
+        assert_eq!(updated_a.retry_count, 0);
+        assert_eq!(updated_b.state, TaskState::Chatting);
+        assert_eq!(updated_b.retry_count, 0);
+        assert_eq!(updated_c.state, TaskState::Merged);
+        assert_eq!(updated_c.retry_count, 2);
+    }
+
+    #[test]
+    fn bulk_set_priority_by_state() {
+        let service = mk_service();
+        let mut task_a = mk_task("T-BULK-PRIO-A", TaskState::Stopped);
+        task_a.priority = TaskPriority::Low;
+        let mut task_b = mk_task("T-BULK-PRIO-B", TaskState::Stopped);
+        task_b.priority = TaskPriority::Normal;
+        let mut task_c = mk_task("T-BULK-PRIO-C", TaskState::Ready);
+        task_c.priority = TaskPriority::Low;
+        for task in [&task_a, &task_b, &task_c] {
+            create(&service, task);
+        }
+
+        let ids = Vec::new();
+        let summary = set_priority(&service, TaskPriority::Critical, Some("stopped"), &ids)
+            .expect("bulk set-priority");
+        assert_eq!(summary.processed, 2);
+        assert_eq!(summary.succeeded, 2);
+        assert_eq!(summary.skipped, 0);
+
+        let priority_of = |task: &Task| {
+            service
+                .task(&task.id)
+                .expect("load task")
+                .expect("task exists")
+                .priority
+        };
+        assert_eq!(priority_of(&task_a), TaskPriority::Critical);
+        assert_eq!(priority_of(&task_b), TaskPriority::Critical);
+        assert_eq!(priority_of(&task_c), TaskPriority::Low);
+    }
+}
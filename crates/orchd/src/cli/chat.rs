@@ -0,0 +1,216 @@
+//! `othala chat send`: hand a message to a task's live agent session.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use orch_core::types::{ModelKind, TaskId};
+
+use crate::agent_log;
+use crate::chat_control::{self, ChatDelivery, ChatReceipt};
+use crate::daemon_loop::spawn_chat_session;
+use crate::supervisor::AgentSupervisor;
+use crate::OrchdService;
+
+use super::watch::read_new_log_lines;
+
+/// Quiet period after the last output line before a `--wait` response is
+/// considered complete.
+const CHAT_RESPONSE_QUIET: Duration = Duration::from_secs(3);
+
+/// How long `chat send` waits for the daemon to confirm a queued message.
+const CHAT_DELIVERY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Whether an agent output line ends the current response turn.
+fn is_chat_turn_end(line: &str) -> bool {
+    orch_agents::detect_common_signal(line).is_some_and(|signal| {
+        matches!(
+            signal.kind,
+            orch_agents::AgentSignalKind::NeedHuman | orch_agents::AgentSignalKind::PatchReady
+        )
+    })
+}
+
+/// A chat message answers whatever the agent was waiting on.
+fn report_answered_questions(
+    service: &OrchdService,
+    task_id: &TaskId,
+    message: &str,
+) -> anyhow::Result<()> {
+    for question in service.answer_questions(task_id, message, Utc::now())? {
+        println!("Answered {}: {}", question.question_id, question.question);
+    }
+    Ok(())
+}
+
+/// Send `message` to the task's session. With a daemon running the message
+/// goes through its inbox and counts only once the daemon confirms delivery;
+/// otherwise `spawn` starts a session owned by this process.
+pub fn send(
+    service: &OrchdService,
+    repo_root: &Path,
+    task_id: &TaskId,
+    message: &str,
+    wait: bool,
+    spawn: bool,
+    timeout: Duration,
+) -> anyhow::Result<()> {
+    let Some(task) = service.task(task_id)? else {
+        anyhow::bail!("task not found: {}", task_id.0);
+    };
+    if message.trim().is_empty() {
+        anyhow::bail!("message cannot be empty");
+    }
+
+    if chat_control::running_daemon_pid(repo_root).is_some() {
+        let log_path = agent_log::agent_log_dir(repo_root, &task.id).join("latest.log");
+        let mut position = fs::metadata(&log_path).map(|m| m.len()).unwrap_or(0);
+        let queued = chat_control::enqueue_chat_message(repo_root, &task.id, message, spawn)?;
+        let receipt = wait_for_chat_receipt(repo_root, &queued.id)?;
+        match receipt.delivery {
+            ChatDelivery::Delivered => {
+                println!("Delivered message to {}", task.id.0);
+            }
+            ChatDelivery::Spawned { model } => {
+                println!("Started {} session for {}", model.as_str(), task.id.0);
+            }
+            ChatDelivery::Failed { reason } => {
+                anyhow::bail!("message not delivered to {}: {reason}", task.id.0);
+            }
+        }
+        for answered in &receipt.answered {
+            println!("Answered {answered}");
+        }
+        if wait || spawn {
+            wait_for_logged_response(&log_path, &mut position, timeout)?;
+        }
+        return Ok(());
+    }
+
+    if !spawn {
+        anyhow::bail!(
+            "no live session for {} (start `othala daemon` or pass --spawn)",
+            task.id.0
+        );
+    }
+
+    // Without a daemon this process owns the session, so it stays attached
+    // and relays output until the session exits or the user interrupts.
+    let mut supervisor = AgentSupervisor::new(task.preferred_model.unwrap_or(ModelKind::Claude));
+    let model = spawn_chat_session(&mut supervisor, repo_root, &task, message)?;
+    chat_control::append_chat_log(repo_root, &task.id, &[format!("> {message}")])?;
+    eprintln!("Started {} session for {}", model.as_str(), task.id.0);
+    report_answered_questions(service, &task.id, message)?;
+
+    let interrupted = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    signal_hook::flag::register(signal_hook::consts::SIGINT, interrupted.clone())?;
+    let deadline = Instant::now() + timeout;
+    let mut responded = false;
+    let mut hinted = false;
+    loop {
+        let poll = supervisor.poll();
+        let mut turn_ended = false;
+        for chunk in &poll.output {
+            agent_log::append_agent_output(repo_root, &chunk.task_id, &chunk.lines)?;
+            chat_control::append_chat_log(repo_root, &chunk.task_id, &chunk.lines)?;
+            service.capture_questions(&chunk.task_id, &chunk.lines, Utc::now())?;
+            for line in &chunk.lines {
+                println!("{line}");
+                turn_ended |= is_chat_turn_end(line);
+            }
+            responded = true;
+        }
+        if !poll.completed.is_empty() {
+            return Ok(());
+        }
+        if interrupted.load(std::sync::atomic::Ordering::Relaxed) {
+            break;
+        }
+        if !hinted && (turn_ended || (!responded && Instant::now() >= deadline)) {
+            if !responded {
+                eprintln!(
+                    "No response from {} after {}s",
+                    task.id.0,
+                    timeout.as_secs()
+                );
+            }
+            eprintln!(
+                "Session for {} is still running; press Ctrl-C to stop it",
+                task.id.0
+            );
+            hinted = true;
+        }
+        std::thread::sleep(Duration::from_millis(200));
+    }
+    supervisor.stop_all();
+    Ok(())
+}
+
+/// Wait for the daemon to report what it did with queued message `id`.
+fn wait_for_chat_receipt(repo_root: &Path, id: &str) -> anyhow::Result<ChatReceipt> {
+    let deadline = Instant::now() + CHAT_DELIVERY_TIMEOUT;
+    loop {
+        if let Some(receipt) = chat_control::take_chat_receipt(repo_root, id) {
+            return Ok(receipt);
+        }
+        if Instant::now() >= deadline {
+            anyhow::bail!(
+                "daemon did not confirm delivery within {}s; the message is still queued",
+                CHAT_DELIVERY_TIMEOUT.as_secs()
+            );
+        }
+        std::thread::sleep(Duration::from_millis(200));
+    }
+}
+
+/// Follow an agent log from `position`, printing lines until the agent ends
+/// its turn, goes quiet after responding, or `timeout` elapses.
+fn wait_for_logged_response(
+    log_path: &Path,
+    position: &mut u64,
+    timeout: Duration,
+) -> anyhow::Result<()> {
+    let deadline = Instant::now() + timeout;
+    let mut last_output: Option<Instant> = None;
+    loop {
+        let lines = match read_new_log_lines(log_path, position) {
+            Ok(lines) => lines,
+            Err(err) if err.kind() == ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(err.into()),
+        };
+        if !lines.is_empty() {
+            last_output = Some(Instant::now());
+        }
+        let mut turn_ended = false;
+        for line in &lines {
+            println!("{line}");
+            turn_ended |= is_chat_turn_end(line);
+        }
+        if turn_ended || last_output.is_some_and(|at| at.elapsed() >= CHAT_RESPONSE_QUIET) {
+            return Ok(());
+        }
+        if Instant::now() >= deadline {
+            anyhow::bail!(
+                "timed out after {}s waiting for a response",
+                timeout.as_secs()
+            );
+        }
+        std::thread::sleep(Duration::from_millis(500));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chat_turn_ends_on_needs_input_signal() {
+        assert!(is_chat_turn_end(
+            "[needs_human] which database should I use?"
+        ));
+        assert!(is_chat_turn_end("[patch_ready]"));
+        assert!(!is_chat_turn_end("working on it"));
+    }
+}
@@ -0,0 +1,675 @@
+//! `othala daemon`: load the org config, then run the orchestration loop
+//! until a timeout, idle exit, drain, or signal.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use chrono::Utc;
+use orch_agents::register_custom_models;
+use orch_core::config::{
+    apply_profile_defaults, load_org_config, ConfigProfile, NotificationConfig, OrgConfig,
+};
+use orch_core::events::{Event, EventKind};
+use orch_core::state::TaskState;
+use orch_core::types::{EventId, ModelKind, Task};
+use orch_notify::{NotificationDispatcher, NotificationSink, StdoutSink, WebhookSink};
+
+use crate::prompt_builder::validate_model_templates;
+use crate::supervisor::AgentSupervisor;
+use crate::{OrchdService, PermissionPolicy, SchedulerConfig};
+
+/// Flags from `othala daemon`.
+#[derive(Debug, Clone, Default)]
+pub struct DaemonOptions {
+    /// Stop after this many seconds.
+    pub timeout: Option<u64>,
+    /// Stop once every task is terminal or awaiting merge.
+    pub exit_on_idle: bool,
+    pub skip_context_gen: bool,
+    /// Overrides `daemon.verify_command` from the config.
+    pub verify_command: Option<String>,
+    pub skip_qa: bool,
+    pub skip_fast_verify: bool,
+    /// Run a single tick and exit.
+    pub once: bool,
+    pub dry_run: bool,
+    /// Overrides the profile set in the config.
+    pub profile: Option<ConfigProfile>,
+}
+
+/// Run the daemon until it is told to stop and return the final task list.
+pub fn run(
+    service: &mut OrchdService,
+    repo_root: PathBuf,
+    options: DaemonOptions,
+) -> anyhow::Result<Vec<Task>> {
+    let DaemonOptions {
+        timeout,
+        exit_on_idle,
+        skip_context_gen,
+        verify_command,
+        skip_qa,
+        skip_fast_verify,
+        once,
+        dry_run,
+        profile: selected_cli_profile,
+    } = options;
+
+    print_banner();
+
+    let template_dir = PathBuf::from("templates/prompts");
+
+    let config_path = PathBuf::from(".othala/config.toml");
+    let (
+        enabled_models,
+        default_model,
+        notification_dispatcher,
+        daemon_org_config,
+        auto_resolve_conflicts,
+        permissions,
+    ) = if config_path.exists() {
+        let mut org_config = load_org_config(&config_path)?;
+        let effective_profile = selected_cli_profile
+            .clone()
+            .or_else(|| org_config.profile.clone());
+        if let Some(profile) = &effective_profile {
+            apply_profile_defaults(profile, &mut org_config);
+            eprintln!("  Profile: {}", profile_label(profile));
+        }
+        use orch_core::validation::{Validate, ValidationLevel};
+        let issues = org_config.validate();
+        for issue in &issues {
+            let prefix = match issue.level {
+                ValidationLevel::Error => "\x1b[31mERROR\x1b[0m",
+                ValidationLevel::Warning => "\x1b[33mWARN\x1b[0m",
+            };
+            eprintln!("  [{prefix}] {}: {}", issue.code, issue.message);
+        }
+        if issues.iter().any(|i| i.level == ValidationLevel::Error) {
+            anyhow::bail!("config validation failed — run `othala wizard` to fix");
+        }
+        register_custom_models(&org_config.models);
+        let default = org_config.models.default.unwrap_or(ModelKind::Claude);
+        let notification_dispatcher = build_notification_dispatcher(&org_config.notifications);
+        (
+            org_config.models.enabled,
+            default,
+            notification_dispatcher,
+            org_config.daemon,
+            org_config.graphite.auto_resolve_conflicts,
+            org_config.permissions,
+        )
+    } else {
+        eprintln!("  \x1b[33mNo .othala/config.toml — using defaults (run `othala wizard` to configure)\x1b[0m");
+        let mut org_config = OrgConfig::default();
+        if let Some(profile) = &selected_cli_profile {
+            apply_profile_defaults(profile, &mut org_config);
+            eprintln!("  Profile: {}", profile_label(profile));
+        }
+        (
+            org_config.models.enabled,
+            org_config.models.default.unwrap_or(ModelKind::Claude),
+            None,
+            org_config.daemon,
+            org_config.graphite.auto_resolve_conflicts,
+            org_config.permissions,
+        )
+    };
+    eprintln!(
+        "  Enabled models: {}",
+        enabled_models
+            .iter()
+            .map(|m| m.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    for missing in validate_model_templates(&template_dir, &enabled_models) {
+        let fallback = missing
+            .fallback
+            .map(|path| format!("using {}", path.display()))
+            .unwrap_or_else(|| "no role template will be used".to_string());
+        eprintln!(
+            "  [\x1b[33mWARN\x1b[0m] template: no {}/{}; {fallback}",
+            missing.model,
+            missing.role.template_file()
+        );
+    }
+
+    let context_gen_config =
+        crate::context_gen::ContextGenConfig::from(&daemon_org_config.context_gen);
+    if !skip_context_gen {
+        if let Err(e) = run_context_gen_with_status(
+            &repo_root,
+            &template_dir,
+            &context_gen_config,
+            default_model,
+        ) {
+            eprintln!("[daemon] Context generation failed (non-fatal): {e}");
+        }
+    } else {
+        eprintln!("  Skipping context generation");
+    }
+    eprintln!();
+
+    if let Some(secs) = timeout {
+        eprintln!("[daemon] Timeout: {}s", secs);
+    }
+    if exit_on_idle {
+        eprintln!("[daemon] Will exit when all tasks reach terminal state");
+    }
+
+    let context_gen_config = crate::context_gen::ContextGenConfig {
+        model: Some(context_gen_config.model_or(default_model)),
+        ..context_gen_config
+    };
+    let signal_rules = orch_agents::SignalRuleSet::load(&repo_root).unwrap_or_else(|err| {
+        eprintln!("[daemon] Using built-in signal rules: {err}");
+        orch_agents::SignalRuleSet::default()
+    });
+    let mut supervisor = AgentSupervisor::new(default_model)
+        .with_output_buffer_bytes(daemon_org_config.output_buffer_bytes)
+        .with_signal_rules(signal_rules);
+    let mut daemon_state = crate::daemon_loop::DaemonState::new();
+    daemon_state.spawn_rate_limiter.stats = crate::rate_limiter::load_stats(&repo_root);
+    daemon_state.notification_dispatcher = notification_dispatcher;
+    daemon_state.permission_policy = repo_permission_policy(&repo_root, &permissions);
+    crate::daemon_loop::check_auxiliary_reload(&repo_root, &mut daemon_state);
+
+    let nix_shell = crate::daemon_loop::detect_nix_shell(&repo_root);
+    if !nix_shell.is_empty() {
+        eprintln!("[daemon] Nix dev shell: {nix_shell}");
+    }
+    let verify_limits = crate::daemon_loop::detect_verify_limits(&repo_root);
+    let fast_verify_commands = crate::daemon_loop::detect_fast_verify_commands(&repo_root);
+
+    let verify_cmd = verify_command
+        .or_else(|| daemon_org_config.verify_command.clone())
+        .unwrap_or_else(|| "cargo check && cargo test --workspace".to_string());
+
+    let mut daemon_config = crate::daemon_loop::DaemonConfig {
+        repo_root,
+        template_dir,
+        enabled_models,
+        context_config: crate::context_graph::ContextLoadConfig::default(),
+        verify_command: Some(verify_cmd),
+        nix_shell,
+        context_gen_config,
+        skip_qa,
+        skip_context_regen: skip_context_gen,
+        dry_run,
+        agent_timeout_secs: daemon_org_config.agent_timeout_secs,
+        drain_timeout_secs: 30,
+        post_merge_hook: daemon_org_config.post_merge_hook.clone(),
+        retry_guard: daemon_org_config.retry_guard.clone(),
+        step_timeouts: (&daemon_org_config.pipeline_timeouts).into(),
+        question_stale_secs: daemon_org_config.question_stale_secs,
+        merge_poll: daemon_org_config.merge_poll.clone(),
+        auto_resolve_conflicts,
+        auto_approve_min_confidence: daemon_org_config.auto_approve_min_confidence,
+        pipeline_checkpoints: !dry_run,
+        max_spawns_per_tick: daemon_org_config.max_spawns_per_tick,
+        verify_limits,
+        skip_fast_verify,
+        fast_verify_commands,
+        generate_qa_specs: daemon_org_config.generate_qa_specs,
+    };
+    let mut tick_interval_secs = daemon_org_config.tick_interval_secs;
+    let mut adaptive_tick_config = daemon_org_config.adaptive_tick.clone();
+    let mut adaptive_tick = crate::tick_interval::AdaptiveTick::from_config(&adaptive_tick_config);
+
+    let shutdown = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    {
+        let flag = shutdown.clone();
+        signal_hook::flag::register(signal_hook::consts::SIGINT, flag.clone())?;
+        signal_hook::flag::register(signal_hook::consts::SIGTERM, flag)?;
+    }
+
+    if let Err(err) = crate::chat_control::write_daemon_pid(&daemon_config.repo_root) {
+        eprintln!("[daemon] Failed to write control pid file: {err}");
+    }
+
+    let start = Instant::now();
+    let mut idle_grace_ticks: u32 = 0;
+    const IDLE_GRACE_MAX: u32 = 3;
+    let mut prev_states: HashMap<String, TaskState> = HashMap::new();
+
+    loop {
+        let repo_root = &daemon_config.repo_root;
+        daemon_state.paused = crate::daemon_control::is_paused(repo_root);
+        if crate::daemon_control::take_reload_request(repo_root) {
+            // Forget the last mtime so the config is re-read even if unchanged.
+            daemon_state.config_last_modified = None;
+        }
+        if crate::daemon_control::drain_requested(repo_root) && !daemon_state.shutdown_requested {
+            eprintln!("[daemon] Drain requested, waiting for running agents");
+            daemon_state.request_shutdown(daemon_config.drain_timeout_secs);
+        }
+
+        let mut changes = Vec::new();
+        let mut reloaded = false;
+        if let Some(new_config) =
+            crate::daemon_loop::check_config_reload(&config_path, &mut daemon_state)
+        {
+            reloaded = true;
+            let mut new_config = new_config;
+            let effective_profile = selected_cli_profile
+                .clone()
+                .or_else(|| new_config.profile.clone());
+            if let Some(profile) = &effective_profile {
+                apply_profile_defaults(profile, &mut new_config);
+            }
+
+            if daemon_config.enabled_models != new_config.models.enabled {
+                changes.push("enabled_models".to_string());
+                daemon_config.enabled_models = new_config.models.enabled.clone();
+            }
+
+            register_custom_models(&new_config.models);
+            let scheduler_config = SchedulerConfig::from_org_config(&new_config);
+            if service.scheduler.config != scheduler_config {
+                changes.push("scheduler".to_string());
+                service.scheduler.config = scheduler_config;
+            }
+
+            if daemon_config.agent_timeout_secs != new_config.daemon.agent_timeout_secs {
+                changes.push("agent_timeout_secs".to_string());
+                daemon_config.agent_timeout_secs = new_config.daemon.agent_timeout_secs;
+            }
+
+            if daemon_config.post_merge_hook != new_config.daemon.post_merge_hook {
+                changes.push("post_merge_hook".to_string());
+                daemon_config.post_merge_hook = new_config.daemon.post_merge_hook.clone();
+            }
+
+            if daemon_config.retry_guard != new_config.daemon.retry_guard {
+                changes.push("retry_guard".to_string());
+                daemon_config.retry_guard = new_config.daemon.retry_guard.clone();
+            }
+
+            let step_timeouts =
+                crate::stack_pipeline::StepTimeouts::from(&new_config.daemon.pipeline_timeouts);
+            if daemon_config.step_timeouts != step_timeouts {
+                changes.push("pipeline_timeouts".to_string());
+                daemon_config.step_timeouts = step_timeouts;
+            }
+
+            if daemon_config.question_stale_secs != new_config.daemon.question_stale_secs {
+                changes.push("question_stale_secs".to_string());
+                daemon_config.question_stale_secs = new_config.daemon.question_stale_secs;
+            }
+
+            if daemon_config.merge_poll != new_config.daemon.merge_poll {
+                changes.push("merge_poll".to_string());
+                daemon_config.merge_poll = new_config.daemon.merge_poll.clone();
+            }
+
+            if daemon_config.auto_resolve_conflicts != new_config.graphite.auto_resolve_conflicts {
+                changes.push("auto_resolve_conflicts".to_string());
+                daemon_config.auto_resolve_conflicts = new_config.graphite.auto_resolve_conflicts;
+            }
+
+            if daemon_config.auto_approve_min_confidence
+                != new_config.daemon.auto_approve_min_confidence
+            {
+                changes.push("auto_approve_min_confidence".to_string());
+                daemon_config.auto_approve_min_confidence =
+                    new_config.daemon.auto_approve_min_confidence;
+            }
+
+            if daemon_config.max_spawns_per_tick != new_config.daemon.max_spawns_per_tick {
+                changes.push("max_spawns_per_tick".to_string());
+                daemon_config.max_spawns_per_tick = new_config.daemon.max_spawns_per_tick;
+            }
+
+            if daemon_config.generate_qa_specs != new_config.daemon.generate_qa_specs {
+                changes.push("generate_qa_specs".to_string());
+                daemon_config.generate_qa_specs = new_config.daemon.generate_qa_specs;
+            }
+
+            if tick_interval_secs != new_config.daemon.tick_interval_secs {
+                changes.push("tick_interval_secs".to_string());
+                tick_interval_secs = new_config.daemon.tick_interval_secs;
+            }
+
+            if adaptive_tick_config != new_config.daemon.adaptive_tick {
+                changes.push("adaptive_tick".to_string());
+                adaptive_tick_config = new_config.daemon.adaptive_tick.clone();
+                adaptive_tick =
+                    crate::tick_interval::AdaptiveTick::from_config(&adaptive_tick_config);
+            }
+
+            let permission_policy = repo_permission_policy(repo_root, &new_config.permissions);
+            if daemon_state.permission_policy != permission_policy {
+                changes.push("permissions".to_string());
+                daemon_state.permission_policy = permission_policy;
+            }
+        }
+
+        let aux_changes = crate::daemon_loop::check_auxiliary_reload(repo_root, &mut daemon_state);
+        if !aux_changes.is_empty() {
+            reloaded = true;
+            changes.extend(aux_changes);
+        }
+
+        if reloaded {
+            let now = Utc::now();
+            let change_summary = if changes.is_empty() {
+                "no effective changes".to_string()
+            } else {
+                changes.join(", ")
+            };
+            let event = Event {
+                id: EventId(format!(
+                    "E-CONFIG-RELOADED-{}",
+                    now.timestamp_nanos_opt().unwrap_or_default()
+                )),
+                task_id: None,
+                repo_id: None,
+                at: now,
+                kind: EventKind::ConfigReloaded {
+                    changes: change_summary,
+                },
+            };
+            if let Err(err) = service.record_event(&event) {
+                eprintln!("[daemon] Failed to record config reload event: {err}");
+            }
+        }
+
+        let drained = crate::daemon_loop::run_tick(
+            service,
+            &mut supervisor,
+            &mut daemon_state,
+            &daemon_config,
+        );
+
+        let tasks = service.list_tasks()?;
+        for task in &tasks {
+            let prev = prev_states.get(&task.id.0);
+            if prev != Some(&task.state) {
+                if let Some(old) = prev {
+                    eprintln!("[daemon] {} -> {} ({})", task.id.0, task.state, old);
+                }
+                prev_states.insert(task.id.0.clone(), task.state);
+            }
+        }
+        if !tasks.is_empty() {
+            let chatting = tasks
+                .iter()
+                .filter(|t| t.state == TaskState::Chatting)
+                .count();
+            let ready = tasks.iter().filter(|t| t.state == TaskState::Ready).count();
+            let submitting = tasks
+                .iter()
+                .filter(|t| t.state == TaskState::Submitting)
+                .count();
+            let awaiting = tasks
+                .iter()
+                .filter(|t| t.state == TaskState::AwaitingMerge)
+                .count();
+            let merged = tasks
+                .iter()
+                .filter(|t| t.state == TaskState::Merged)
+                .count();
+            let stopped = tasks
+                .iter()
+                .filter(|t| t.state == TaskState::Stopped)
+                .count();
+            let mut status = format!(
+                "[{}] {} chatting, {} ready, {} submitting, {} awaiting, {} merged",
+                chrono::Local::now().format("%H:%M:%S"),
+                chatting,
+                ready,
+                submitting,
+                awaiting,
+                merged
+            );
+            if stopped > 0 {
+                status.push_str(&format!(", {} stopped", stopped));
+            }
+            eprintln!("{status}");
+        }
+
+        if let Some(secs) = timeout {
+            if start.elapsed().as_secs() >= secs {
+                eprintln!("[daemon] Timeout reached ({}s), shutting down", secs);
+                supervisor.stop_all();
+                break;
+            }
+        }
+
+        if exit_on_idle && all_tasks_idle(service) {
+            idle_grace_ticks += 1;
+            if idle_grace_ticks >= IDLE_GRACE_MAX {
+                eprintln!("[daemon] All tasks idle, shutting down");
+                supervisor.stop_all();
+                break;
+            }
+        } else {
+            idle_grace_ticks = 0;
+        }
+
+        if once {
+            eprintln!("[daemon] --once mode, exiting after single tick");
+            supervisor.stop_all();
+            break;
+        }
+
+        if drained {
+            eprintln!("[daemon] Drain complete, shutting down");
+            break;
+        }
+
+        if shutdown.load(std::sync::atomic::Ordering::Relaxed) {
+            eprintln!("[daemon] Received signal, shutting down gracefully");
+            supervisor.stop_all();
+            break;
+        }
+
+        let sleep_for = match adaptive_tick.as_mut() {
+            Some(tick) => {
+                let active = supervisor.running_count() > 0
+                    || tasks
+                        .iter()
+                        .any(|t| !t.state.is_terminal() && t.state != TaskState::AwaitingMerge);
+                tick.next(active)
+            }
+            None => std::time::Duration::from_secs(tick_interval_secs),
+        };
+        std::thread::sleep(sleep_for);
+    }
+
+    crate::chat_control::clear_daemon_pid(&daemon_config.repo_root);
+    crate::daemon_control::clear_transient_requests(&daemon_config.repo_root);
+
+    Ok(service.list_tasks()?)
+}
+
+fn all_tasks_idle(service: &OrchdService) -> bool {
+    match service.list_tasks() {
+        Ok(tasks) if tasks.is_empty() => false,
+        Ok(tasks) => tasks
+            .iter()
+            .all(|t| t.state.is_terminal() || t.state == TaskState::AwaitingMerge),
+        Err(_) => false,
+    }
+}
+
+fn profile_label(profile: &ConfigProfile) -> String {
+    match profile {
+        ConfigProfile::Dev => "dev".to_string(),
+        ConfigProfile::Staging => "staging".to_string(),
+        ConfigProfile::Prod => "prod".to_string(),
+        ConfigProfile::Custom(name) => format!("custom({name})"),
+    }
+}
+
+fn build_notification_dispatcher(config: &NotificationConfig) -> Option<NotificationDispatcher> {
+    if !config.enabled {
+        return None;
+    }
+
+    let mut sinks: Vec<Box<dyn NotificationSink>> = Vec::new();
+
+    if config.stdout {
+        sinks.push(Box::new(StdoutSink));
+    }
+
+    if let Some(url) = &config.webhook_url {
+        if !url.trim().is_empty() {
+            sinks.push(Box::new(WebhookSink {
+                url: url.clone(),
+                timeout_secs: 10,
+            }));
+        }
+    }
+
+    if let Some(url) = &config.slack_webhook_url {
+        if !url.trim().is_empty() {
+            sinks.push(Box::new(orch_notify::SlackSink {
+                webhook_url: url.clone(),
+                channel: config.slack_channel.clone(),
+                timeout_secs: 10,
+            }));
+        }
+    }
+
+    if let (Some(bot_token_env), Some(chat_id_env)) =
+        (&config.telegram_bot_token_env, &config.telegram_chat_id_env)
+    {
+        sinks.push(Box::new(orch_notify::TelegramSink {
+            bot_token_env: bot_token_env.clone(),
+            chat_id_env: chat_id_env.clone(),
+            enabled: true,
+        }));
+    }
+
+    if sinks.is_empty() {
+        None
+    } else {
+        Some(NotificationDispatcher::new(sinks))
+    }
+}
+
+/// `[permissions]` from the org config with the rules saved by `othala
+/// permit`/`othala deny` layered on top.
+pub fn repo_permission_policy(
+    repo_root: &Path,
+    permissions: &orch_core::config::PermissionsConfig,
+) -> PermissionPolicy {
+    let mut policy = PermissionPolicy::from_org_permissions(permissions);
+    match PermissionPolicy::load(repo_root) {
+        Ok(persisted) => policy.merge(&persisted),
+        Err(err) => eprintln!("[permissions] Ignoring saved rules: {err}"),
+    }
+    policy
+}
+
+/// The Othala banner, on stderr.
+pub fn print_banner() {
+    eprint!("\x1b[35m");
+    eprintln!();
+    eprintln!("       \u{2554}\u{2557}");
+    eprintln!("      \u{2554}\u{255d}\u{2558}\u{2557}        \u{2554}\u{2550}\u{2557}\u{2554}\u{2566}\u{2557}\u{2566} \u{2566}\u{2554}\u{2550}\u{2557}\u{2566}  \u{2554}\u{2550}\u{2557}");
+    eprintln!("     \u{2554}\u{255d}  \u{2558}\u{2557}       \u{2551} \u{2551} \u{2551} \u{2560}\u{2550}\u{2569}\u{2560}\u{2550}\u{2557}\u{2551}  \u{2560}\u{2550}\u{2557}");
+    eprintln!("     \u{2558}\u{2557}  \u{2554}\u{255d}       \u{255a}\u{2550}\u{255d} \u{2569} \u{2569} \u{2569}\u{2569} \u{2569}\u{2569}\u{2550}\u{255d}\u{2569} \u{2569}");
+    eprintln!("      \u{2558}\u{2557}\u{2554}\u{255d}");
+    eprintln!("      \u{2554}\u{255d}\u{2558}\u{2557}        autonomous code orchestrator");
+    eprintln!("     \u{2554}\u{255d}  \u{2558}\u{2557}");
+    eprintln!();
+    eprint!("\x1b[0m");
+}
+
+/// Bring the generated context up to date, showing progress on stderr. A
+/// full regeneration is left to the daemon's background job.
+pub fn run_context_gen_with_status(
+    repo_root: &Path,
+    template_dir: &Path,
+    config: &crate::context_gen::ContextGenConfig,
+    default_model: ModelKind,
+) -> anyhow::Result<()> {
+    use crate::context_gen::{
+        check_context_startup, parse_progress_line, plan_context_regen, ContextRegenPlan,
+        ContextStartupStatus,
+    };
+
+    match check_context_startup(repo_root) {
+        ContextStartupStatus::UpToDate => {
+            eprintln!("  \x1b[32mContext up to date \u{2713}\x1b[0m");
+            return Ok(());
+        }
+        ContextStartupStatus::Stale => match plan_context_regen(repo_root, template_dir) {
+            ContextRegenPlan::Full => {
+                eprintln!("  \x1b[33mContext stale — will regenerate in background\x1b[0m");
+                return Ok(());
+            }
+            _ => eprintln!("  \x1b[33mContext stale — updating changed sections...\x1b[0m"),
+        },
+        ContextStartupStatus::Missing => {
+            eprintln!("  \x1b[33mGenerating context...\x1b[0m");
+        }
+    }
+
+    let repo = repo_root.to_path_buf();
+    let tmpl = template_dir.to_path_buf();
+    let config = config.clone();
+
+    let (result_tx, result_rx) = std::sync::mpsc::channel();
+    let (progress_tx, progress_rx) = std::sync::mpsc::channel();
+
+    std::thread::spawn(move || {
+        let ptx = progress_tx;
+        let result = crate::context_gen::ensure_context_exists_blocking(
+            &repo,
+            &tmpl,
+            &config,
+            default_model,
+            move |line| {
+                let _ = ptx.send(line.to_string());
+            },
+        );
+        let _ = result_tx.send(result);
+    });
+
+    let spinner_frames = [
+        '\u{280b}', '\u{2819}', '\u{2839}', '\u{2838}', '\u{283c}', '\u{2834}', '\u{2826}',
+        '\u{2827}', '\u{2807}', '\u{280f}',
+    ];
+    let mut frame = 0usize;
+    let mut last_status = String::from("starting agent...");
+
+    loop {
+        while let Ok(raw) = progress_rx.try_recv() {
+            if let Some(parsed) = parse_progress_line(&raw) {
+                last_status = parsed;
+            }
+        }
+
+        match result_rx.try_recv() {
+            Ok(result) => {
+                eprint!("\r\x1b[2K");
+                match &result {
+                    Ok(()) => eprintln!("  \x1b[32mContext generated \u{2713}\x1b[0m"),
+                    Err(e) => eprintln!("  \x1b[31mContext generation failed: {e}\x1b[0m"),
+                }
+                return result;
+            }
+            Err(std::sync::mpsc::TryRecvError::Empty) => {
+                let spinner = spinner_frames[frame % spinner_frames.len()];
+                let display = if last_status.len() > 70 {
+                    format!("{}...", &last_status[..70])
+                } else {
+                    last_status.clone()
+                };
+                eprint!("\r\x1b[2K  \x1b[35m{spinner}\x1b[0m \x1b[2m{display}\x1b[0m");
+                frame += 1;
+                std::thread::sleep(std::time::Duration::from_millis(80));
+            }
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                eprint!("\r\x1b[2K");
+                eprintln!("  \x1b[31mContext generation thread panicked\x1b[0m");
+                anyhow::bail!("context generation thread panicked");
+            }
+        }
+    }
+}
@@ -0,0 +1,395 @@
+//! Health checks behind `othala doctor`, `othala repair` and `othala
+//! self-test`.
+
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use orch_agents::setup::{probe_models, SetupProbeConfig};
+use orch_core::types::ModelKind;
+use serde::{Deserialize, Serialize};
+
+use crate::layout::{LayoutStatus, RepairReport};
+
+use super::default_org_config;
+
+#[derive(Debug, serde::Serialize)]
+pub struct SelfTestCheck {
+    pub name: String,
+    pub ok: bool,
+    pub critical: bool,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DoctorStatus {
+    Ok,
+    Missing,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DoctorCheck {
+    pub name: String,
+    pub ok: bool,
+    pub status: DoctorStatus,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DoctorReport {
+    pub checks: Vec<DoctorCheck>,
+    pub all_ok: bool,
+}
+
+fn command_available_via_which(executable: &str) -> bool {
+    Command::new("which")
+        .arg(executable)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+pub fn doctor_model_checks<F>(which_check: F) -> Vec<DoctorCheck>
+where
+    F: Fn(&str) -> bool,
+{
+    ModelKind::all()
+        .iter()
+        .map(|model| {
+            let model = model.as_str();
+            let found = which_check(model);
+            DoctorCheck {
+                name: format!("model_{model}"),
+                ok: found,
+                status: if found {
+                    DoctorStatus::Ok
+                } else {
+                    DoctorStatus::Missing
+                },
+                detail: if found {
+                    format!("{model} found on PATH")
+                } else {
+                    format!("{model} missing from PATH")
+                },
+            }
+        })
+        .collect()
+}
+
+pub fn collect_doctor_checks<F>(repo_root: &Path, which_check: F) -> Vec<DoctorCheck>
+where
+    F: Fn(&str) -> bool,
+{
+    let mut checks = doctor_model_checks(|model| which_check(model));
+
+    let gt_found = which_check("gt");
+    checks.push(DoctorCheck {
+        name: "graphite".to_string(),
+        ok: gt_found,
+        status: if gt_found {
+            DoctorStatus::Ok
+        } else {
+            DoctorStatus::Missing
+        },
+        detail: if gt_found {
+            "gt found on PATH".to_string()
+        } else {
+            "gt missing from PATH".to_string()
+        },
+    });
+
+    let git_ok = Command::new("git")
+        .arg("status")
+        .current_dir(repo_root)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false);
+    checks.push(DoctorCheck {
+        name: "git".to_string(),
+        ok: git_ok,
+        status: if git_ok {
+            DoctorStatus::Ok
+        } else {
+            DoctorStatus::Error
+        },
+        detail: if git_ok {
+            "git status succeeded".to_string()
+        } else {
+            "git status failed".to_string()
+        },
+    });
+
+    checks.extend(
+        crate::layout::check_layout(repo_root)
+            .into_iter()
+            .map(|check| DoctorCheck {
+                ok: check.ok(),
+                status: layout_doctor_status(check.status),
+                name: check.name,
+                detail: check.detail,
+            }),
+    );
+
+    checks
+}
+
+pub fn doctor_report(repo_root: &Path) -> DoctorReport {
+    let checks = collect_doctor_checks(repo_root, command_available_via_which);
+    let all_ok = checks.iter().all(|check| check.ok);
+    DoctorReport { checks, all_ok }
+}
+
+pub fn doctor_status_label(status: &DoctorStatus) -> &'static str {
+    match status {
+        DoctorStatus::Ok => "ok",
+        DoctorStatus::Missing => "missing",
+        DoctorStatus::Error => "error",
+    }
+}
+
+pub fn layout_doctor_status(status: LayoutStatus) -> DoctorStatus {
+    match status {
+        LayoutStatus::Ok => DoctorStatus::Ok,
+        LayoutStatus::Missing => DoctorStatus::Missing,
+        LayoutStatus::Error => DoctorStatus::Error,
+    }
+}
+
+/// Recreate missing `.othala/` layout pieces, seeding the config with every
+/// built-in model enabled.
+pub fn repair(repo_root: &Path) -> anyhow::Result<RepairReport> {
+    let default_config =
+        default_org_config(vec![ModelKind::Claude, ModelKind::Codex, ModelKind::Gemini]);
+    Ok(crate::layout::repair_layout(repo_root, &default_config)?)
+}
+
+fn command_available(executable: &str) -> bool {
+    Command::new(executable)
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+/// Environment checks behind `othala self-test`, critical and advisory.
+pub fn self_test() -> Vec<SelfTestCheck> {
+    let mut checks = Vec::new();
+
+    let git_available = command_available("git");
+    checks.push(SelfTestCheck {
+        name: "git_available".to_string(),
+        ok: git_available,
+        critical: true,
+        detail: if git_available {
+            "git is available".to_string()
+        } else {
+            "git not found on PATH".to_string()
+        },
+    });
+
+    let in_git_repo = if git_available {
+        Command::new("git")
+            .args(["rev-parse", "--is-inside-work-tree"])
+            .output()
+            .map(|out| {
+                out.status.success() && String::from_utf8_lossy(&out.stdout).trim() == "true"
+            })
+            .unwrap_or(false)
+    } else {
+        false
+    };
+    checks.push(SelfTestCheck {
+        name: "in_git_repo".to_string(),
+        ok: in_git_repo,
+        critical: true,
+        detail: if in_git_repo {
+            "current directory is inside a git repository".to_string()
+        } else {
+            "current directory is not inside a git repository".to_string()
+        },
+    });
+
+    let gt_available = command_available("gt");
+    checks.push(SelfTestCheck {
+        name: "graphite_cli_available".to_string(),
+        ok: gt_available,
+        critical: true,
+        detail: if gt_available {
+            "gt is available".to_string()
+        } else {
+            "gt not found on PATH".to_string()
+        },
+    });
+
+    let model_report = probe_models(&SetupProbeConfig::default());
+    for probe in model_report.models {
+        let missing_env = probe
+            .env_status
+            .iter()
+            .filter(|status| !status.satisfied)
+            .map(|status| format!("({})", status.any_of.join(" or ")))
+            .collect::<Vec<_>>();
+
+        let detail = if probe.healthy {
+            probe
+                .version_output
+                .clone()
+                .unwrap_or_else(|| "healthy".to_string())
+        } else if !probe.installed {
+            format!("{} not installed", probe.executable)
+        } else if !probe.version_ok {
+            probe
+                .version_output
+                .clone()
+                .unwrap_or_else(|| "version check failed".to_string())
+        } else if !missing_env.is_empty() {
+            format!("missing env: {}", missing_env.join(", "))
+        } else {
+            "unhealthy".to_string()
+        };
+
+        checks.push(SelfTestCheck {
+            name: format!("model_{:?}", probe.model).to_lowercase(),
+            ok: probe.healthy,
+            critical: false,
+            detail,
+        });
+    }
+
+    let required_dirs = [
+        Path::new(".othala"),
+        Path::new(".othala/context"),
+        Path::new(".othala/qa"),
+        Path::new(".othala/events"),
+    ];
+    let missing_dirs = required_dirs
+        .iter()
+        .filter(|path| !path.is_dir())
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>();
+    let structure_ok = missing_dirs.is_empty();
+    checks.push(SelfTestCheck {
+        name: "othala_directory_structure".to_string(),
+        ok: structure_ok,
+        critical: true,
+        detail: if structure_ok {
+            ".othala/ structure is present".to_string()
+        } else {
+            format!("missing: {}", missing_dirs.join(", "))
+        },
+    });
+
+    let sqlite_open = rusqlite::Connection::open_with_flags(
+        ".othala/db.sqlite",
+        rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE,
+    );
+    checks.push(SelfTestCheck {
+        name: "sqlite_open".to_string(),
+        ok: sqlite_open.is_ok(),
+        critical: true,
+        detail: match sqlite_open {
+            Ok(_) => "opened .othala/db.sqlite".to_string(),
+            Err(err) => format!("failed to open .othala/db.sqlite: {err}"),
+        },
+    });
+
+    let cargo_available = command_available("cargo");
+    checks.push(SelfTestCheck {
+        name: "cargo_available".to_string(),
+        ok: cargo_available,
+        critical: true,
+        detail: if cargo_available {
+            "cargo is available".to_string()
+        } else {
+            "cargo not found on PATH".to_string()
+        },
+    });
+
+    checks
+}
+
+/// Whether every critical self-test check passed.
+pub fn critical_checks_pass(checks: &[SelfTestCheck]) -> bool {
+    checks.iter().all(|check| !check.critical || check.ok)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use serde_json::Value;
+
+    use super::*;
+
+    #[test]
+    fn doctor_detects_missing_model() {
+        let checks = doctor_model_checks(|name| name != "codex");
+        let codex = checks
+            .iter()
+            .find(|check| check.name == "model_codex")
+            .expect("codex check present");
+        assert!(!codex.ok);
+        assert_eq!(codex.status, DoctorStatus::Missing);
+    }
+
+    #[test]
+    fn doctor_json_output_format() {
+        let report = DoctorReport {
+            checks: vec![DoctorCheck {
+                name: "model_claude".to_string(),
+                ok: true,
+                status: DoctorStatus::Ok,
+                detail: "claude found on PATH".to_string(),
+            }],
+            all_ok: true,
+        };
+
+        let json = serde_json::to_string_pretty(&report).expect("serialize doctor report");
+        let value: Value = serde_json::from_str(&json).expect("parse doctor report json");
+
+        assert!(value.get("checks").is_some());
+        assert_eq!(value.get("all_ok").and_then(Value::as_bool), Some(true));
+        assert_eq!(value["checks"][0]["name"], "model_claude");
+        assert_eq!(value["checks"][0]["status"], "ok");
+    }
+
+    #[test]
+    fn doctor_layout_checks_pass_after_repair() {
+        let root = std::env::temp_dir().join(format!(
+            "othala-doctor-repair-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        std::fs::create_dir_all(&root).expect("create temp root");
+
+        let before = collect_doctor_checks(&root, |_| true);
+        assert!(before
+            .iter()
+            .any(|check| check.name == "sqlite" && !check.ok));
+        assert!(before
+            .iter()
+            .any(|check| check.name == "agent_output_dir" && !check.ok));
+
+        let report = crate::layout::repair_layout(
+            &root,
+            &default_org_config(vec![ModelKind::Claude, ModelKind::Codex, ModelKind::Gemini]),
+        )
+        .expect("repair layout");
+        assert!(report.healthy);
+
+        let after = collect_doctor_checks(&root, |_| true);
+        let failing: Vec<&str> = after
+            .iter()
+            .filter(|check| !check.ok && check.name != "git")
+            .map(|check| check.name.as_str())
+            .collect();
+        assert!(failing.is_empty(), "doctor still failing: {failing:?}");
+
+        std::fs::remove_dir_all(root).ok();
+    }
+}
@@ -0,0 +1,430 @@
+//! `othala gc`: remove old event logs and agent output, or with
+//! `--branches`, task branches no task owns any more.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use chrono::Utc;
+use orch_core::types::Task;
+use orch_git::{
+    branch_exists, delete_branch, list_local_branches, merge_base, GitCli, RepoHandle,
+    WorktreeManager, DEFAULT_WORKTREE_ROOT,
+};
+
+use crate::OrchdService;
+
+/// Name prefixes of the branches the orchestrator creates for tasks.
+const TASK_BRANCH_PREFIXES: [&str; 2] = ["task/", "chat-"];
+
+/// What `gc` removed, or with `dry_run` would remove.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GcSummary {
+    pub event_files: Vec<PathBuf>,
+    pub agent_output_dirs: Vec<PathBuf>,
+    pub bytes_freed: u64,
+}
+
+/// `bytes` scaled to B, KiB, MiB, or GiB for display.
+pub fn format_bytes(bytes: u64) -> String {
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let kib = bytes as f64 / 1024.0;
+    if kib < 1024.0 {
+        return format!("{kib:.1} KiB");
+    }
+    let mib = kib / 1024.0;
+    if mib < 1024.0 {
+        return format!("{mib:.1} MiB");
+    }
+    format!("{:.1} GiB", mib / 1024.0)
+}
+
+fn collect_old_jsonl_files(
+    root: &Path,
+    cutoff: SystemTime,
+    out: &mut Vec<PathBuf>,
+) -> std::io::Result<()> {
+    if !root.exists() {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(root)? {
+        let entry = entry?;
+        let path = entry.path();
+        let metadata = entry.metadata()?;
+        if metadata.is_dir() {
+            collect_old_jsonl_files(&path, cutoff, out)?;
+            continue;
+        }
+
+        if path.extension().and_then(|ext| ext.to_str()) != Some("jsonl") {
+            continue;
+        }
+
+        if metadata
+            .modified()
+            .map(|modified| modified < cutoff)
+            .unwrap_or(false)
+        {
+            out.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+fn collect_old_agent_dirs(root: &Path, cutoff: SystemTime) -> std::io::Result<Vec<PathBuf>> {
+    if !root.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut candidates = Vec::new();
+    for entry in fs::read_dir(root)? {
+        let entry = entry?;
+        let path = entry.path();
+        let metadata = entry.metadata()?;
+        if !metadata.is_dir() {
+            continue;
+        }
+        if metadata
+            .modified()
+            .map(|modified| modified < cutoff)
+            .unwrap_or(false)
+        {
+            candidates.push(path);
+        }
+    }
+    Ok(candidates)
+}
+
+pub(crate) fn dir_size(path: &Path) -> std::io::Result<u64> {
+    let mut total = 0u64;
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_dir() {
+            total += dir_size(&entry.path())?;
+        } else {
+            total += metadata.len();
+        }
+    }
+    Ok(total)
+}
+
+/// Delete `.othala/events` JSONL files and `.othala/agent-output` dirs not
+/// modified in `older_than_days`. With `dry_run` nothing is deleted.
+pub fn gc(repo_root: &Path, older_than_days: u64, dry_run: bool) -> anyhow::Result<GcSummary> {
+    let age = Duration::from_secs(older_than_days.saturating_mul(24 * 60 * 60));
+    let cutoff = SystemTime::now()
+        .checked_sub(age)
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+
+    let events_root = repo_root.join(".othala/events");
+    let agent_output_root = repo_root.join(".othala/agent-output");
+
+    let mut event_files = Vec::new();
+    collect_old_jsonl_files(&events_root, cutoff, &mut event_files)?;
+    let agent_output_dirs = collect_old_agent_dirs(&agent_output_root, cutoff)?;
+
+    let mut bytes_freed = 0u64;
+    for event_file in &event_files {
+        bytes_freed += fs::metadata(event_file).map(|m| m.len()).unwrap_or(0);
+    }
+    for dir in &agent_output_dirs {
+        bytes_freed += dir_size(dir).unwrap_or(0);
+    }
+
+    if !dry_run {
+        for event_file in &event_files {
+            fs::remove_file(event_file)?;
+        }
+        for dir in &agent_output_dirs {
+            fs::remove_dir_all(dir)?;
+        }
+    }
+
+    Ok(GcSummary {
+        event_files,
+        agent_output_dirs,
+        bytes_freed,
+    })
+}
+
+/// Why an orphaned branch was safe to delete.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrphanReason {
+    /// The branch tip is reachable from the base branch.
+    Merged,
+    /// The tip was committed more than the cutoff ago.
+    Stale { age_days: i64 },
+}
+
+/// A task branch with no live or archived task behind it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrphanBranch {
+    pub name: String,
+    pub head: String,
+    /// `git merge-base <base> <branch>`; equal to `head` when merged.
+    pub merge_base: Option<String>,
+    pub reason: OrphanReason,
+}
+
+impl OrphanBranch {
+    /// The merge-base evidence behind deleting this branch.
+    pub fn evidence(&self, base: &str) -> String {
+        let merge_base = self.merge_base.as_deref().map(short_sha).unwrap_or("none");
+        match self.reason {
+            OrphanReason::Merged => format!(
+                "merged: merge-base with {base} is {merge_base}, the branch tip"
+            ),
+            OrphanReason::Stale { age_days } => format!(
+                "stale: tip {} last committed {age_days} days ago, merge-base with {base} is {merge_base}",
+                short_sha(&self.head)
+            ),
+        }
+    }
+}
+
+fn short_sha(sha: &str) -> &str {
+    &sha[..sha.len().min(12)]
+}
+
+/// What `gc --branches` deleted, or with `dry_run` would delete.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BranchGcSummary {
+    pub deleted: Vec<OrphanBranch>,
+    /// Orphans left alone because a worktree has them checked out.
+    pub checked_out: Vec<String>,
+}
+
+/// Delete local `task/*` and `chat-*` branches that no live or archived task
+/// owns and that are merged into `base` or whose tip is older than
+/// `older_than_days`. Branches checked out in any worktree are never
+/// touched. With `dry_run` nothing is deleted.
+pub fn gc_branches(
+    repo: &RepoHandle,
+    git: &GitCli,
+    service: &OrchdService,
+    base: &str,
+    older_than_days: u64,
+    dry_run: bool,
+) -> anyhow::Result<BranchGcSummary> {
+    if !branch_exists(repo, git, base)? {
+        anyhow::bail!("base branch '{base}' not found");
+    }
+
+    let mut tasks = service.list_tasks()?;
+    for archived in service.store.list_archived()? {
+        tasks.push(serde_json::from_str::<Task>(&archived.payload_json)?);
+    }
+    let mut owned = HashSet::new();
+    for task in tasks {
+        owned.insert(format!("task/{}", task.id.0));
+        owned.extend(task.branch_name);
+        owned.insert(task.id.0);
+    }
+
+    let checked_out: HashSet<String> = WorktreeManager::new(git.clone(), DEFAULT_WORKTREE_ROOT)
+        .list(repo)?
+        .into_iter()
+        .filter_map(|worktree| worktree.branch)
+        .collect();
+
+    let now = Utc::now();
+    let cutoff_days = i64::try_from(older_than_days).unwrap_or(i64::MAX);
+    let mut summary = BranchGcSummary::default();
+    for branch in list_local_branches(repo, git)? {
+        if branch.name == base
+            || owned.contains(&branch.name)
+            || !TASK_BRANCH_PREFIXES
+                .iter()
+                .any(|prefix| branch.name.starts_with(prefix))
+        {
+            continue;
+        }
+
+        let merge_base = merge_base(repo, git, base, &branch.name)?;
+        let age_days = (now - branch.committed_at).num_days();
+        let reason = if merge_base.as_deref() == Some(branch.head.as_str()) {
+            OrphanReason::Merged
+        } else if age_days > cutoff_days {
+            OrphanReason::Stale { age_days }
+        } else {
+            continue;
+        };
+
+        if checked_out.contains(&branch.name) {
+            summary.checked_out.push(branch.name);
+            continue;
+        }
+        if !dry_run {
+            delete_branch(repo, git, &branch.name, true)?;
+        }
+        summary.deleted.push(OrphanBranch {
+            name: branch.name,
+            head: branch.head,
+            merge_base,
+            reason,
+        });
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gc_dry_run_does_not_delete() {
+        let root = std::env::temp_dir().join(format!(
+            "othala-gc-dry-run-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let event_file = root.join(".othala/events/tasks/T-GC-1.jsonl");
+        let agent_dir = root.join(".othala/agent-output/T-GC-1");
+        fs::create_dir_all(event_file.parent().expect("event parent")).expect("create event dir");
+        fs::create_dir_all(&agent_dir).expect("create agent dir");
+        fs::write(&event_file, "{}\n").expect("write event file");
+        fs::write(agent_dir.join("latest.log"), "hello\n").expect("write agent log");
+
+        let summary = gc(&root, 0, true).expect("dry run gc");
+        assert_eq!(summary.event_files, vec![event_file.clone()]);
+        assert_eq!(summary.agent_output_dirs, vec![agent_dir.clone()]);
+        assert!(event_file.exists());
+        assert!(agent_dir.exists());
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn gc_deletes_old_files() {
+        let root = std::env::temp_dir().join(format!(
+            "othala-gc-delete-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let event_file = root.join(".othala/events/tasks/T-GC-2.jsonl");
+        let ignored_file = root.join(".othala/events/keep.txt");
+        let agent_dir = root.join(".othala/agent-output/T-GC-2");
+        fs::create_dir_all(event_file.parent().expect("event parent")).expect("create event dir");
+        fs::create_dir_all(agent_dir.parent().expect("agent parent")).expect("create agent parent");
+        fs::create_dir_all(&agent_dir).expect("create agent dir");
+        fs::write(&event_file, "{}\n").expect("write event file");
+        fs::write(&ignored_file, "keep\n").expect("write ignored file");
+        fs::write(agent_dir.join("latest.log"), "hello\n").expect("write agent log");
+
+        let summary = gc(&root, 0, false).expect("gc delete");
+        assert_eq!(summary.event_files, vec![event_file.clone()]);
+        assert_eq!(summary.agent_output_dirs, vec![agent_dir.clone()]);
+        assert!(!event_file.exists());
+        assert!(ignored_file.exists());
+        assert!(!agent_dir.exists());
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    fn git(cwd: &Path, args: &[&str], committed_at: Option<&str>) {
+        let mut command = std::process::Command::new("git");
+        command
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(cwd);
+        if let Some(date) = committed_at {
+            command
+                .env("GIT_COMMITTER_DATE", date)
+                .env("GIT_AUTHOR_DATE", date);
+        }
+        let output = command.output().expect("spawn git");
+        assert!(output.status.success(), "git {args:?} failed");
+    }
+
+    /// Branch off `main` and add one commit on it.
+    fn unmerged_branch(root: &Path, name: &str, committed_at: Option<&str>) {
+        git(root, &["checkout", "-q", "-b", name, "main"], None);
+        let file = format!("{}.txt", name.replace('/', "-"));
+        fs::write(root.join(&file), "work\n").expect("write branch file");
+        git(root, &["add", &file], None);
+        git(root, &["commit", "-q", "-m", name], committed_at);
+        git(root, &["checkout", "-q", "main"], None);
+    }
+
+    #[test]
+    fn gc_branches_deletes_only_unowned_merged_or_stale_branches() {
+        use crate::cli::testing::{create, mk_service, mk_task};
+        use orch_core::state::TaskState;
+
+        let root = std::env::temp_dir().join(format!(
+            "othala-gc-branches-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&root).expect("create repo dir");
+        git(&root, &["init", "-q", "-b", "main"], None);
+        fs::write(root.join("README.md"), "init\n").expect("write readme");
+        git(&root, &["add", "README.md"], None);
+        git(&root, &["commit", "-q", "-m", "init"], None);
+        for merged in ["task/T-LIVE", "task/T-ARCHIVED", "task/T-GONE", "feature/x"] {
+            git(&root, &["branch", merged], None);
+        }
+        unmerged_branch(&root, "chat-old", Some("2020-01-01T00:00:00Z"));
+        unmerged_branch(&root, "task/T-RECENT", None);
+        git(
+            &root,
+            &["worktree", "add", "-q", "-b", "task/T-WT", ".orch/wt/T-WT"],
+            None,
+        );
+
+        let service = mk_service();
+        create(&service, &mk_task("T-LIVE", TaskState::Merged));
+        service
+            .store
+            .archive_task(&mk_task("T-ARCHIVED", TaskState::Merged), Utc::now())
+            .expect("archive task");
+
+        let git_cli = GitCli::default();
+        let repo = orch_git::discover_repo(&root, &git_cli).expect("discover repo");
+        let names = |summary: &BranchGcSummary| {
+            summary
+                .deleted
+                .iter()
+                .map(|branch| branch.name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let preview = gc_branches(&repo, &git_cli, &service, "main", 30, true).expect("dry run");
+        assert_eq!(names(&preview), vec!["chat-old", "task/T-GONE"]);
+        assert_eq!(preview.checked_out, vec!["task/T-WT"]);
+        assert!(branch_exists(&repo, &git_cli, "task/T-GONE").expect("branch exists"));
+
+        let summary = gc_branches(&repo, &git_cli, &service, "main", 30, false).expect("gc");
+        assert_eq!(summary, preview);
+        let stale = &summary.deleted[0];
+        assert!(matches!(stale.reason, OrphanReason::Stale { age_days } if age_days > 30));
+        let gone = &summary.deleted[1];
+        assert_eq!(gone.reason, OrphanReason::Merged);
+        assert_eq!(gone.merge_base.as_deref(), Some(gone.head.as_str()));
+        assert!(gone
+            .evidence("main")
+            .starts_with("merged: merge-base with main is "));
+
+        for (branch, kept) in [
+            ("chat-old", false),
+            ("task/T-GONE", false),
+            ("task/T-LIVE", true),
+            ("task/T-ARCHIVED", true),
+            ("task/T-RECENT", true),
+            ("task/T-WT", true),
+            ("feature/x", true),
+        ] {
+            assert_eq!(
+                branch_exists(&repo, &git_cli, branch).expect("branch exists"),
+                kept,
+                "{branch}"
+            );
+        }
+
+        fs::remove_dir_all(root).ok();
+    }
+}
@@ -0,0 +1,247 @@
+//! Event and run history commands: `logs`, `runs`, and `retries`.
+
+use orch_core::events::{Event, EventKind};
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::{OrchdService, TaskRunRecord};
+
+/// One agent run in a task's retry history.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RetryTimelineEntry {
+    pub attempt: u32,
+    pub model: String,
+    pub started_at: String,
+    pub status: String,
+    pub finished_at: Option<String>,
+    pub reason: Option<String>,
+}
+
+/// Output of `othala retries`.
+#[derive(Debug, Clone, Serialize)]
+pub struct RetryHistory {
+    pub task_id: String,
+    pub retry_events: Vec<Event>,
+    pub timeline: Vec<RetryTimelineEntry>,
+}
+
+/// The last `limit` events for the task behind `id`, or globally without one.
+pub fn logs(service: &OrchdService, id: Option<&str>, limit: usize) -> anyhow::Result<Vec<Event>> {
+    let mut events = match id {
+        Some(id) => service.task_events(&service.resolve_task_id(id)?)?,
+        None => service.global_events()?,
+    };
+    if events.len() > limit {
+        events.drain(..events.len() - limit);
+    }
+    Ok(events)
+}
+
+pub fn runs(service: &OrchdService, id: &str) -> anyhow::Result<Vec<TaskRunRecord>> {
+    Ok(service.task_runs(&service.resolve_task_id(id)?)?)
+}
+
+pub fn retries(service: &OrchdService, id: &str) -> anyhow::Result<RetryHistory> {
+    let task_id = service.resolve_task_id(id)?;
+    let events = service.task_events(&task_id)?;
+    let runs = service.task_runs(&task_id)?;
+    Ok(RetryHistory {
+        task_id: task_id.0,
+        retry_events: collect_retry_events(&events),
+        timeline: build_retry_timeline(&events, &runs),
+    })
+}
+
+fn is_retry_related_event(kind: &EventKind) -> bool {
+    matches!(
+        kind,
+        EventKind::AgentSpawned { .. }
+            | EventKind::RetryScheduled { .. }
+            | EventKind::AgentCompleted { success: false, .. }
+    )
+}
+
+fn collect_retry_events(events: &[Event]) -> Vec<Event> {
+    let mut filtered: Vec<Event> = events
+        .iter()
+        .filter(|event| is_retry_related_event(&event.kind))
+        .cloned()
+        .collect();
+    filtered.sort_by_key(|event| event.at);
+    filtered
+}
+
+fn build_retry_timeline(events: &[Event], runs: &[TaskRunRecord]) -> Vec<RetryTimelineEntry> {
+    let retry_events = collect_retry_events(events);
+    let mut retry_reasons: HashMap<u32, String> = HashMap::new();
+    for event in &retry_events {
+        if let EventKind::RetryScheduled {
+            attempt, reason, ..
+        } = &event.kind
+        {
+            retry_reasons.insert(*attempt, reason.clone());
+        }
+    }
+
+    let mut sorted_runs = runs.to_vec();
+    sorted_runs.sort_by_key(|run| run.started_at);
+
+    sorted_runs
+        .iter()
+        .enumerate()
+        .map(|(index, run)| {
+            let attempt = (index + 1) as u32;
+            let started_at = run.started_at.format("%H:%M:%S").to_string();
+            let finished_at = run.finished_at.map(|at| at.format("%H:%M:%S").to_string());
+
+            let status = if run.finished_at.is_none() {
+                "running".to_string()
+            } else if run.stop_reason.as_deref() == Some("completed") || run.exit_code == Some(0) {
+                "completed".to_string()
+            } else {
+                "failed".to_string()
+            };
+
+            let reason = if status == "failed" {
+                run.stop_reason
+                    .clone()
+                    .filter(|r| r != "failed")
+                    .or_else(|| retry_reasons.get(&attempt).cloned())
+                    .or_else(|| run.stop_reason.clone())
+            } else {
+                None
+            };
+
+            RetryTimelineEntry {
+                attempt,
+                model: run.model.as_str().to_string(),
+                started_at,
+                status,
+                finished_at,
+                reason,
+            }
+        })
+        .collect()
+}
+
+pub fn format_retries_timeline(task_id: &str, timeline: &[RetryTimelineEntry]) -> String {
+    if timeline.is_empty() {
+        return format!("No retry history for task: {task_id}");
+    }
+
+    let mut lines = vec![format!("Retry History for {task_id}:")];
+    for entry in timeline {
+        let end = entry.finished_at.as_deref().unwrap_or("-");
+        let line = if entry.status == "failed" {
+            let reason = entry.reason.as_deref().unwrap_or("unknown");
+            format!(
+                "#{:<2} {:<8} started {}  failed {}  reason: \"{}\"",
+                entry.attempt, entry.model, entry.started_at, end, reason
+            )
+        } else if entry.status == "completed" {
+            format!(
+                "#{:<2} {:<8} started {}  completed {}",
+                entry.attempt, entry.model, entry.started_at, end
+            )
+        } else {
+            format!(
+                "#{:<2} {:<8} started {}  running",
+                entry.attempt, entry.model, entry.started_at
+            )
+        };
+        lines.push(line);
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use orch_core::types::{EventId, ModelKind, RepoId, TaskId};
+
+    #[test]
+    fn retries_formats_timeline() {
+        let task_id = TaskId::new("chat-123");
+        let started_1 = Utc
+            .with_ymd_and_hms(2026, 2, 16, 10, 30, 0)
+            .single()
+            .expect("valid timestamp");
+        let failed_1 = Utc
+            .with_ymd_and_hms(2026, 2, 16, 10, 35, 22)
+            .single()
+            .expect("valid timestamp");
+        let started_2 = Utc
+            .with_ymd_and_hms(2026, 2, 16, 10, 35, 25)
+            .single()
+            .expect("valid timestamp");
+        let completed_2 = Utc
+            .with_ymd_and_hms(2026, 2, 16, 10, 40, 11)
+            .single()
+            .expect("valid timestamp");
+
+        let events = vec![
+            Event {
+                id: EventId("E-R-1".to_string()),
+                task_id: Some(task_id.clone()),
+                repo_id: Some(RepoId("repo-1".to_string())),
+                at: failed_1,
+                kind: EventKind::RetryScheduled {
+                    attempt: 1,
+                    model: "codex".to_string(),
+                    reason: "verify failed".to_string(),
+                },
+            },
+            Event {
+                id: EventId("E-R-2".to_string()),
+                task_id: Some(task_id.clone()),
+                repo_id: Some(RepoId("repo-1".to_string())),
+                at: started_2,
+                kind: EventKind::AgentSpawned {
+                    model: "codex".to_string(),
+                },
+            },
+        ];
+
+        let runs = vec![
+            TaskRunRecord {
+                run_id: "RUN-1".to_string(),
+                task_id: task_id.clone(),
+                repo_id: RepoId("repo-1".to_string()),
+                model: ModelKind::Claude,
+                started_at: started_1,
+                finished_at: Some(failed_1),
+                stop_reason: Some("failed".to_string()),
+                exit_code: Some(1),
+                estimated_tokens: None,
+                duration_secs: Some(322.0),
+                confidence: None,
+            },
+            TaskRunRecord {
+                run_id: "RUN-2".to_string(),
+                task_id,
+                repo_id: RepoId("repo-1".to_string()),
+                model: ModelKind::Codex,
+                started_at: started_2,
+                finished_at: Some(completed_2),
+                stop_reason: Some("completed".to_string()),
+                exit_code: Some(0),
+                estimated_tokens: None,
+                duration_secs: Some(286.0),
+                confidence: None,
+            },
+        ];
+
+        let timeline = build_retry_timeline(&events, &runs);
+        let rendered = format_retries_timeline("chat-123", &timeline);
+
+        assert!(rendered.contains("Retry History for chat-123:"));
+        assert!(rendered.contains("#1"));
+        assert!(rendered.contains("claude"));
+        assert!(rendered.contains("failed 10:35:22"));
+        assert!(rendered.contains("reason: \"verify failed\""));
+        assert!(rendered.contains("codex"));
+        assert!(rendered.contains("completed 10:40:11"));
+    }
+}
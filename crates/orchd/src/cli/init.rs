@@ -0,0 +1,241 @@
+//! `othala init`: scaffold `.othala/` in a repository.
+
+use std::path::Path;
+
+use orch_core::config::save_org_config;
+use orch_core::types::ModelKind;
+
+use super::default_org_config;
+
+/// Language preset for `othala init --template`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InitTemplate {
+    Rust,
+    Node,
+    Python,
+}
+
+impl InitTemplate {
+    pub fn verify_command(self) -> &'static str {
+        match self {
+            InitTemplate::Rust => "cargo check && cargo test --workspace",
+            InitTemplate::Node => "npm ci && npm run lint --if-present && npm test",
+            InitTemplate::Python => "python -m pytest",
+        }
+    }
+
+    pub fn ignore_patterns(self) -> &'static str {
+        match self {
+            InitTemplate::Rust => "target/\n**/*.rs.bk\n",
+            InitTemplate::Node => {
+                "node_modules/\ndist/\nbuild/\ncoverage/\n.next/\n*.log\npackage-lock.json\n"
+            }
+            InitTemplate::Python => {
+                "__pycache__/\n*.pyc\n.venv/\nvenv/\n.pytest_cache/\n.mypy_cache/\n*.egg-info/\n"
+            }
+        }
+    }
+
+    pub fn main_md(self) -> String {
+        let stack = match self {
+            InitTemplate::Rust => "Rust (Cargo)",
+            InitTemplate::Node => "Node.js (npm)",
+            InitTemplate::Python => "Python",
+        };
+        format!(
+            "# Project Context\n\nDescribe your project here.\n\n## Stack\n\n- {stack}\n\n\
+             ## Verification\n\n- `{}` must pass before a task is ready.\n",
+            self.verify_command()
+        )
+    }
+}
+
+/// Create the `.othala/` layout with a default config, `MAIN.md` and ignore
+/// files, returning one line per action taken. Refuses to overwrite an
+/// existing config unless `force` is set.
+pub fn init_project(
+    repo_root: &Path,
+    force: bool,
+    template: Option<InitTemplate>,
+) -> anyhow::Result<Vec<String>> {
+    let othala_dir = repo_root.join(".othala");
+    let config_path = othala_dir.join("config.toml");
+    let ignore_path = othala_dir.join("ignore");
+    let othalaignore_path = repo_root.join(".othalaignore");
+    let context_dir = othala_dir.join("context");
+    let main_context_path = context_dir.join("MAIN.md");
+    let templates_dir = othala_dir.join("templates");
+
+    if config_path.exists() && !force {
+        anyhow::bail!(
+            "{} already exists (pass --force to overwrite)",
+            config_path.display()
+        );
+    }
+
+    let mut actions = Vec::new();
+
+    if !othala_dir.exists() {
+        std::fs::create_dir_all(&othala_dir)?;
+        actions.push("Created .othala/".to_string());
+    }
+    if !context_dir.exists() {
+        std::fs::create_dir_all(&context_dir)?;
+        actions.push("Created .othala/context/".to_string());
+    }
+    if !templates_dir.exists() {
+        std::fs::create_dir_all(&templates_dir)?;
+        actions.push("Created .othala/templates/".to_string());
+    }
+
+    let config_existed = config_path.exists();
+    let mut org_config =
+        default_org_config(vec![ModelKind::Claude, ModelKind::Codex, ModelKind::Gemini]);
+    org_config.daemon.verify_command =
+        template.map(|template| template.verify_command().to_string());
+    save_org_config(&config_path, &org_config)?;
+    if config_existed {
+        actions.push("Overwrote .othala/config.toml".to_string());
+    } else {
+        actions.push("Created .othala/config.toml".to_string());
+    }
+
+    let context_existed = main_context_path.exists();
+    if force || !context_existed {
+        let main_md = template.map_or_else(
+            || crate::layout::MINIMAL_MAIN_MD.to_string(),
+            InitTemplate::main_md,
+        );
+        std::fs::write(&main_context_path, main_md)?;
+        if context_existed {
+            actions.push("Overwrote .othala/context/MAIN.md".to_string());
+        } else {
+            actions.push("Created .othala/context/MAIN.md".to_string());
+        }
+    }
+
+    let othalaignore_existed = othalaignore_path.exists();
+    if force || !othalaignore_existed {
+        std::fs::write(&othalaignore_path, crate::ignore::DEFAULT_OTHALAIGNORE)?;
+        if othalaignore_existed {
+            actions.push("Overwrote .othalaignore".to_string());
+        } else {
+            actions.push("Created .othalaignore".to_string());
+        }
+    }
+
+    if let Some(template) = template {
+        let ignore_existed = ignore_path.exists();
+        if force || !ignore_existed {
+            std::fs::write(&ignore_path, template.ignore_patterns())?;
+            if ignore_existed {
+                actions.push("Overwrote .othala/ignore".to_string());
+            } else {
+                actions.push("Created .othala/ignore".to_string());
+            }
+        }
+    }
+
+    Ok(actions)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use chrono::Utc;
+    use orch_core::config::load_org_config;
+
+    use super::*;
+
+    #[test]
+    fn init_creates_directory_structure() {
+        let root = std::env::temp_dir().join(format!(
+            "othala-init-structure-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&root).expect("create temp root");
+
+        init_project(&root, false, None).expect("initialize project");
+
+        assert!(root.join(".othala").is_dir());
+        assert!(root.join(".othala/context").is_dir());
+        assert!(root.join(".othala/templates").is_dir());
+        assert!(root.join(".othala/config.toml").is_file());
+        assert!(root.join(".othala/context/MAIN.md").is_file());
+        assert_eq!(
+            fs::read_to_string(root.join(".othala/context/MAIN.md")).expect("read main context"),
+            "# Project Context\n\nDescribe your project here.\n"
+        );
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn init_refuses_overwrite_without_force() {
+        let root = std::env::temp_dir().join(format!(
+            "othala-init-no-force-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&root).expect("create temp root");
+
+        init_project(&root, false, None).expect("initialize project");
+        let err = init_project(&root, false, None).expect_err("should reject overwrite");
+        assert!(err.to_string().contains("already exists"));
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn init_seeds_othalaignore_and_force_overwrites_it() {
+        let root = std::env::temp_dir().join(format!(
+            "othala-init-ignore-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&root).expect("create temp root");
+        let ignore_path = root.join(".othalaignore");
+
+        let actions = init_project(&root, false, None).expect("initialize project");
+        assert!(actions.contains(&"Created .othalaignore".to_string()));
+        let rules = crate::ignore::load_ignore_rules(&root);
+        for ignored in ["target/debug/app", "node_modules/x/index.js", ".git/HEAD"] {
+            assert!(rules.is_ignored(ignored), "{ignored} should be ignored");
+        }
+        assert!(!rules.is_ignored("src/main.rs"));
+
+        fs::write(&ignore_path, "custom/\n").expect("edit ignore file");
+        let actions = init_project(&root, true, None).expect("force re-init");
+        assert!(actions.contains(&"Overwrote .othalaignore".to_string()));
+        assert_eq!(
+            fs::read_to_string(&ignore_path).expect("read ignore file"),
+            crate::ignore::DEFAULT_OTHALAIGNORE
+        );
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn init_node_template_seeds_npm_verify_and_node_ignores() {
+        let root = std::env::temp_dir().join(format!(
+            "othala-init-node-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&root).expect("create temp root");
+
+        let actions =
+            init_project(&root, false, Some(InitTemplate::Node)).expect("initialize project");
+        assert!(actions.contains(&"Created .othala/ignore".to_string()));
+
+        let config = load_org_config(root.join(".othala/config.toml")).expect("load config");
+        let verify = config.daemon.verify_command.expect("verify command");
+        assert!(verify.contains("npm"), "{verify}");
+
+        let ignore = fs::read_to_string(root.join(".othala/ignore")).expect("read ignore file");
+        assert!(ignore.lines().any(|line| line == "node_modules/"));
+        let main_md =
+            fs::read_to_string(root.join(".othala/context/MAIN.md")).expect("read MAIN.md");
+        assert!(main_md.contains("Node.js"));
+
+        fs::remove_dir_all(root).ok();
+    }
+}
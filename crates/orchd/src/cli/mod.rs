@@ -0,0 +1,109 @@
+//! Command handlers behind the `othala` CLI.
+//!
+//! Each handler takes the service plus already-parsed arguments and returns a
+//! typed output struct, so `main.rs` is left to parse, dispatch, and print.
+//! Long-running commands (`daemon`, `watch`, `chat send`, `bench-suite run`)
+//! stream their own progress instead.
+
+pub mod analytics;
+pub mod as_of;
+pub mod bench;
+pub mod bulk;
+pub mod chat;
+pub mod daemon;
+pub mod doctor;
+pub mod gc;
+pub mod history;
+pub mod init;
+pub mod models;
+pub mod prompt;
+pub mod prune;
+pub mod repo;
+pub mod stats;
+pub mod tasks;
+pub mod transfer;
+pub mod undo;
+pub mod watch;
+pub mod wizard;
+
+use orch_core::config::OrgConfig;
+use orch_core::state::TaskState;
+use orch_core::types::{ModelKind, TaskPriority};
+
+pub fn parse_task_priority(s: &str) -> anyhow::Result<TaskPriority> {
+    s.parse::<TaskPriority>().map_err(|e| anyhow::anyhow!(e))
+}
+
+/// Parse a `--state` filter; accepts `awaiting-merge` and `awaiting_merge`.
+pub fn parse_task_state_filter(value: &str) -> anyhow::Result<TaskState> {
+    match value.trim().to_lowercase().replace('-', "_").as_str() {
+        "chatting" => Ok(TaskState::Chatting),
+        "ready" => Ok(TaskState::Ready),
+        "submitting" => Ok(TaskState::Submitting),
+        "restacking" => Ok(TaskState::Restacking),
+        "awaiting_merge" => Ok(TaskState::AwaitingMerge),
+        "merged" => Ok(TaskState::Merged),
+        "stopped" => Ok(TaskState::Stopped),
+        other => anyhow::bail!("unknown state filter: {other}"),
+    }
+}
+
+/// Default org config with `enabled_models` on and the first one as default.
+pub fn default_org_config(enabled_models: Vec<ModelKind>) -> OrgConfig {
+    let mut config = OrgConfig::default();
+    let default_model = enabled_models.first().copied();
+    config.models.enabled = enabled_models;
+    config.models.default = default_model;
+    config
+}
+
+#[cfg(test)]
+mod testing {
+    use crate::event_log::JsonlEventLog;
+    use crate::persistence::SqliteStore;
+    use crate::scheduler::{Scheduler, SchedulerConfig};
+    use crate::OrchdService;
+    use chrono::Utc;
+    use orch_core::events::{Event, EventKind};
+    use orch_core::state::TaskState;
+    use orch_core::types::{EventId, RepoId, Task, TaskId};
+    use std::path::PathBuf;
+
+    pub(super) fn mk_service() -> OrchdService {
+        let store = SqliteStore::open_in_memory().expect("in-memory db");
+        let dir = std::env::temp_dir().join(format!(
+            "othala-cli-test-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        let service = OrchdService::new(
+            store,
+            JsonlEventLog::new(dir),
+            Scheduler::new(SchedulerConfig::uniform(10, 10)),
+        );
+        service.bootstrap().expect("bootstrap");
+        service
+    }
+
+    pub(super) fn mk_task(id: &str, state: TaskState) -> Task {
+        let mut task = Task::new(
+            TaskId::new(id),
+            RepoId("repo-test".to_string()),
+            format!("Task {id}"),
+            PathBuf::from(format!(".orch/wt/{id}")),
+        );
+        task.state = state;
+        task
+    }
+
+    pub(super) fn create(service: &OrchdService, task: &Task) {
+        let event = Event {
+            id: EventId(format!("E-CREATE-{}", task.id.0)),
+            task_id: Some(task.id.clone()),
+            repo_id: Some(task.repo_id.clone()),
+            at: Utc::now(),
+            kind: EventKind::TaskCreated,
+        };
+        service.create_task(task, &event).expect("create task");
+    }
+}
@@ -0,0 +1,104 @@
+//! Model name parsing and the model registry, including custom CLIs from
+//! `[models.custom]`.
+
+use std::path::Path;
+
+use orch_core::config::{load_org_config, ModelsConfig, OrgConfig};
+use orch_core::types::ModelKind;
+
+use crate::provider_registry::{ModelRegistry, CUSTOM_PROVIDERS_PATH};
+
+/// A built-in model, or a custom one defined under `[models.custom]`.
+pub fn parse_model_name(s: &str, models: &ModelsConfig) -> Option<ModelKind> {
+    models.resolve(s)
+}
+
+/// Comma-separated model names `parse_model_name` accepts, for errors.
+pub fn valid_model_names(models: &ModelsConfig) -> String {
+    ModelKind::all()
+        .iter()
+        .copied()
+        .chain(models.custom_models().into_iter().map(|(model, _)| model))
+        .map(ModelKind::as_str)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Parse a `--model` value, listing the accepted names when it is unknown.
+pub fn parse_model_arg(value: &str, models: &ModelsConfig) -> anyhow::Result<ModelKind> {
+    parse_model_name(value, models).ok_or_else(|| {
+        anyhow::anyhow!(
+            "unknown model '{value}'. valid values: {}",
+            valid_model_names(models)
+        )
+    })
+}
+
+/// The repo's model settings, or the defaults when there is no usable config.
+pub fn repo_models_config(repo_root: &Path) -> ModelsConfig {
+    load_org_config(repo_root.join(".othala/config.toml"))
+        .map(|config| config.models)
+        .unwrap_or_else(|_| OrgConfig::default().models)
+}
+
+/// Parse a comma-separated `--enable` list, rejecting unknown names.
+pub fn parse_enable_models_csv(raw: &str, models: &ModelsConfig) -> anyhow::Result<Vec<ModelKind>> {
+    let mut out = Vec::new();
+    for token in raw.split(',') {
+        let token = token.trim();
+        if token.is_empty() {
+            continue;
+        }
+        out.push(parse_model_arg(token, models)?);
+    }
+    if out.is_empty() {
+        anyhow::bail!("no models provided. pass --enable claude,codex,gemini");
+    }
+    Ok(out)
+}
+
+/// Built-in models plus any custom providers from `.othala/providers.toml`
+/// and custom CLIs from `[models.custom]`.
+pub fn load_model_registry(repo_root: &Path) -> anyhow::Result<ModelRegistry> {
+    let path = repo_root.join(CUSTOM_PROVIDERS_PATH);
+    let mut registry = ModelRegistry::with_custom(&path).map_err(anyhow::Error::msg)?;
+    registry.merge_models_config(&repo_models_config(repo_root));
+    Ok(registry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_model_name_accepts_configured_custom_models() {
+        let mut models = OrgConfig::default().models;
+        assert_eq!(parse_model_name("aider", &models), None);
+
+        models.custom.insert(
+            "aider".to_string(),
+            orch_core::config::CustomModelConfig {
+                command: "aider".to_string(),
+                args: Vec::new(),
+                api_key_env: None,
+                output_format: Default::default(),
+            },
+        );
+        let aider = parse_model_name("aider", &models).expect("custom model parses");
+        assert_eq!(aider.as_str(), "aider");
+        assert_eq!(
+            parse_model_name(" Gemini ", &models),
+            Some(ModelKind::Gemini)
+        );
+        assert_eq!(valid_model_names(&models), "claude,codex,gemini,aider");
+        assert_eq!(
+            parse_enable_models_csv("claude, aider", &models).unwrap(),
+            vec![ModelKind::Claude, aider]
+        );
+        let err = parse_model_arg("gpt", &models).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown model 'gpt'. valid values: claude,codex,gemini,aider"
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! `othala prompt-preview`: render the prompt a chat's agent would get.
+
+use std::path::{Path, PathBuf};
+
+use orch_core::config::load_org_config;
+use orch_core::types::{ModelKind, Task};
+
+use crate::context_graph::{load_context_graph, ContextLoadConfig};
+use crate::prompt_builder::{build_rich_prompt, resolve_template_path, PromptConfig, PromptRole};
+
+const TEMPLATE_DIR: &str = "templates/prompts";
+const DEFAULT_VERIFY_COMMAND: &str = "cargo check && cargo test --workspace";
+
+/// A rendered prompt and the role template it came from, if any.
+#[derive(Debug, Clone)]
+pub struct PromptPreview {
+    pub template: Option<PathBuf>,
+    pub prompt: String,
+}
+
+/// Render `task`'s prompt for `model` using the repo's templates and
+/// configured verify command.
+pub fn preview(task: &Task, model: ModelKind, repo_root: &Path) -> PromptPreview {
+    let verify_command = load_org_config(repo_root.join(".othala/config.toml"))
+        .ok()
+        .and_then(|config| config.daemon.verify_command)
+        .unwrap_or_else(|| DEFAULT_VERIFY_COMMAND.to_string());
+    let (template, prompt) = build_prompt_preview(
+        task,
+        model,
+        repo_root,
+        Path::new(TEMPLATE_DIR),
+        verify_command,
+    );
+    PromptPreview { template, prompt }
+}
+
+/// The role template `model` resolves to and the prompt it would be spawned
+/// with, without dispatch personas or retry context.
+pub fn build_prompt_preview(
+    task: &Task,
+    model: ModelKind,
+    repo_root: &Path,
+    template_dir: &Path,
+    verify_command: String,
+) -> (Option<PathBuf>, String) {
+    let role = PromptRole::for_task_type(task.task_type);
+    let config = PromptConfig {
+        task_id: task.id.clone(),
+        task_title: task.title.clone(),
+        task_description: task.description.clone(),
+        role,
+        context: load_context_graph(repo_root, &ContextLoadConfig::default()),
+        test_spec: None,
+        retry: None,
+        verify_command: Some(verify_command),
+        qa_failure_context: None,
+        restack_conflict: None,
+        repo_root: Some(repo_root.to_path_buf()),
+        model: Some(model),
+    };
+    (
+        resolve_template_path(template_dir, role, Some(model)),
+        build_rich_prompt(&config, template_dir),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use chrono::Utc;
+    use orch_core::state::TaskState;
+
+    use super::*;
+    use crate::cli::testing::mk_task;
+
+    #[test]
+    fn prompt_preview_renders_the_model_specific_template() {
+        let root = std::env::temp_dir().join(format!(
+            "othala-prompt-preview-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let template_dir = root.join("templates/prompts");
+        fs::create_dir_all(template_dir.join("codex")).expect("create template dirs");
+        fs::write(
+            template_dir.join("implementer.md"),
+            "# Implementer\nShared.\n",
+        )
+        .expect("write shared template");
+        fs::write(
+            template_dir.join("codex/implementer.md"),
+            "# Implementer\nCodex only.\n",
+        )
+        .expect("write codex template");
+        let task = mk_task("T-PREVIEW", TaskState::Chatting);
+
+        let (template, prompt) =
+            build_prompt_preview(&task, ModelKind::Codex, &root, &template_dir, "make".into());
+        assert_eq!(template, Some(template_dir.join("codex/implementer.md")));
+        assert!(prompt.contains("Codex only."));
+
+        let (template, prompt) = build_prompt_preview(
+            &task,
+            ModelKind::Claude,
+            &root,
+            &template_dir,
+            "make".into(),
+        );
+        assert_eq!(template, Some(template_dir.join("implementer.md")));
+        assert!(prompt.contains("Shared."));
+        assert!(prompt.contains("T-PREVIEW"));
+
+        fs::remove_dir_all(root).ok();
+    }
+}
@@ -0,0 +1,328 @@
+//! `othala prune`: drop old terminal tasks along with their worktrees and
+//! branches.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{Duration, Utc};
+use orch_core::types::{Task, TaskId};
+use orch_git::{
+    branch_exists, branch_has_unmerged_commits, delete_branch, discover_repo, remove_worktree,
+    worktree_dirty_files, GitCli, RepoHandle,
+};
+
+use super::gc::dir_size;
+use crate::OrchdService;
+
+/// What `prune` did, or without `force` would do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PruneReport {
+    /// Terminal tasks past the cutoff, children before their parents.
+    pub tasks: Vec<PrunedTask>,
+    pub reclaimed_bytes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrunedTask {
+    pub task: Task,
+    pub age_days: i64,
+    pub outcome: PruneOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PruneOutcome {
+    /// Dry run: the task would be pruned.
+    Candidate,
+    /// Kept because these child tasks still exist.
+    HasChildren(Vec<TaskId>),
+    /// Kept because its worktree has uncommitted changes.
+    Dirty { files: Vec<PathBuf> },
+    /// Kept because removing its worktree or branch failed.
+    CleanupFailed(String),
+    /// Checkout cleaned up; `error` is set if deleting the task then failed.
+    Pruned {
+        branch: BranchCleanup,
+        error: Option<String>,
+    },
+}
+
+/// Find terminal tasks untouched for `older_than_days` and, with `force`,
+/// delete them after cleaning up their checkouts. A task is kept whenever
+/// its checkout can't be cleaned, so no worktree is orphaned; for the same
+/// reason `force` requires `repo_root` to be a git repository.
+pub fn prune(
+    service: &OrchdService,
+    repo_root: &Path,
+    older_than_days: i64,
+    force: bool,
+    delete_branches: bool,
+) -> anyhow::Result<PruneReport> {
+    let now = Utc::now();
+    let cutoff = now - Duration::days(older_than_days);
+    let mut prunable: Vec<Task> = service
+        .list_tasks()?
+        .into_iter()
+        .filter(|t| t.state.is_terminal() && t.updated_at < cutoff)
+        .collect();
+    // Children first, so their parents are free to go afterwards.
+    prunable.sort_by_key(|t| t.parent_task_id.is_none());
+
+    let git = GitCli::default();
+    let repo = if force && !prunable.is_empty() {
+        Some(discover_repo(repo_root, &git).map_err(|_| {
+            anyhow::anyhow!(
+                "{} is not a git repository; prune --force needs it to clean up worktrees",
+                repo_root.display()
+            )
+        })?)
+    } else {
+        None
+    };
+
+    let mut report = PruneReport {
+        tasks: Vec::with_capacity(prunable.len()),
+        reclaimed_bytes: 0,
+    };
+    for task in prunable {
+        let outcome = match &repo {
+            None => PruneOutcome::Candidate,
+            Some(repo) => prune_task(service, repo, &git, &task, delete_branches, &mut report)?,
+        };
+        report.tasks.push(PrunedTask {
+            age_days: (now - task.updated_at).num_days(),
+            task,
+            outcome,
+        });
+    }
+    Ok(report)
+}
+
+fn prune_task(
+    service: &OrchdService,
+    repo: &RepoHandle,
+    git: &GitCli,
+    task: &Task,
+    delete_branches: bool,
+    report: &mut PruneReport,
+) -> anyhow::Result<PruneOutcome> {
+    let children = service.child_task_ids(&task.id)?;
+    if !children.is_empty() {
+        return Ok(PruneOutcome::HasChildren(children));
+    }
+    let branch = match clean_task_checkout(repo, git, task, delete_branches) {
+        Ok(CheckoutCleanup::Dirty { files }) => return Ok(PruneOutcome::Dirty { files }),
+        Ok(CheckoutCleanup::Cleaned {
+            reclaimed_bytes,
+            branch,
+        }) => {
+            report.reclaimed_bytes += reclaimed_bytes;
+            branch
+        }
+        Err(e) => return Ok(PruneOutcome::CleanupFailed(e.to_string())),
+    };
+    let error = service
+        .delete_task(&task.id, false)
+        .err()
+        .map(|e| e.to_string());
+    Ok(PruneOutcome::Pruned { branch, error })
+}
+
+/// What happened to a pruned task's checkout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckoutCleanup {
+    /// The worktree has uncommitted changes and was left alone.
+    Dirty { files: Vec<PathBuf> },
+    Cleaned {
+        /// Size of the removed worktree; zero if there was none on disk.
+        reclaimed_bytes: u64,
+        branch: BranchCleanup,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BranchCleanup {
+    /// The task has no branch, or it is already gone.
+    Absent,
+    Deleted(String),
+    /// The branch has commits not on `HEAD` and `delete_branches` was off.
+    KeptUnmerged(String),
+}
+
+/// Remove `task`'s worktree and local branch. A dirty worktree is skipped
+/// along with its branch; a branch with unmerged commits is only deleted
+/// when `delete_branches` is set.
+pub fn clean_task_checkout(
+    repo: &RepoHandle,
+    git: &GitCli,
+    task: &Task,
+    delete_branches: bool,
+) -> anyhow::Result<CheckoutCleanup> {
+    let path = repo.root.join(&task.worktree_path);
+    let mut reclaimed_bytes = 0;
+    if path.exists() {
+        let files = worktree_dirty_files(git, &path)?;
+        if !files.is_empty() {
+            return Ok(CheckoutCleanup::Dirty { files });
+        }
+        reclaimed_bytes = dir_size(&path).unwrap_or(0);
+        remove_worktree(repo, git, &path)?;
+    }
+
+    let branch = match task.branch_name.as_deref() {
+        Some(name) if branch_exists(repo, git, name)? => {
+            if !delete_branches && branch_has_unmerged_commits(repo, git, name)? {
+                BranchCleanup::KeptUnmerged(name.to_string())
+            } else {
+                delete_branch(repo, git, name, delete_branches)?;
+                BranchCleanup::Deleted(name.to_string())
+            }
+        }
+        _ => BranchCleanup::Absent,
+    };
+
+    Ok(CheckoutCleanup::Cleaned {
+        reclaimed_bytes,
+        branch,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orch_core::state::TaskState;
+    use std::fs;
+    use std::process::Command;
+
+    use crate::cli::testing::{create, mk_service, mk_task};
+
+    fn git(cwd: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(cwd)
+            .output()
+            .expect("spawn git");
+        assert!(output.status.success(), "git {args:?} failed");
+    }
+
+    fn task_with_worktree(root: &Path, id: &str) -> Task {
+        let mut task = mk_task(id, TaskState::Merged);
+        let branch = format!("task/{id}");
+        task.branch_name = Some(branch.clone());
+        task.worktree_path = PathBuf::from(format!(".orch/wt/{id}"));
+        git(
+            root,
+            &["worktree", "add", "-b", &branch, &format!(".orch/wt/{id}")],
+        );
+        task
+    }
+
+    #[test]
+    fn clean_task_checkout_skips_dirty_and_unmerged_work() {
+        let root = std::env::temp_dir().join(format!(
+            "othala-prune-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&root).expect("create repo dir");
+        git(&root, &["init"]);
+        fs::write(root.join("README.md"), "init\n").expect("write readme");
+        git(&root, &["add", "README.md"]);
+        git(&root, &["commit", "-m", "init"]);
+        let git_cli = GitCli::default();
+        let repo = discover_repo(&root, &git_cli).expect("discover repo");
+
+        let merged = task_with_worktree(&root, "T-PRUNE-MERGED");
+        let cleanup = clean_task_checkout(&repo, &git_cli, &merged, false).expect("clean merged");
+        let CheckoutCleanup::Cleaned {
+            reclaimed_bytes,
+            branch,
+        } = cleanup
+        else {
+            panic!("expected the merged checkout to be cleaned");
+        };
+        assert!(reclaimed_bytes > 0);
+        assert_eq!(
+            branch,
+            BranchCleanup::Deleted("task/T-PRUNE-MERGED".to_string())
+        );
+        assert!(!root.join(".orch/wt/T-PRUNE-MERGED").exists());
+
+        let dirty = task_with_worktree(&root, "T-PRUNE-DIRTY");
+        fs::write(root.join(".orch/wt/T-PRUNE-DIRTY/notes.txt"), "wip\n").expect("write wip");
+        assert_eq!(
+            clean_task_checkout(&repo, &git_cli, &dirty, true).expect("clean dirty"),
+            CheckoutCleanup::Dirty {
+                files: vec![PathBuf::from("notes.txt")]
+            }
+        );
+        assert!(root.join(".orch/wt/T-PRUNE-DIRTY").exists());
+
+        let unmerged = task_with_worktree(&root, "T-PRUNE-UNMERGED");
+        let unmerged_wt = root.join(".orch/wt/T-PRUNE-UNMERGED");
+        fs::write(unmerged_wt.join("feature.txt"), "feature\n").expect("write feature");
+        git(&unmerged_wt, &["add", "feature.txt"]);
+        git(&unmerged_wt, &["commit", "-m", "feature"]);
+        let kept = clean_task_checkout(&repo, &git_cli, &unmerged, false).expect("keep branch");
+        assert!(matches!(
+            kept,
+            CheckoutCleanup::Cleaned {
+                branch: BranchCleanup::KeptUnmerged(_),
+                ..
+            }
+        ));
+        let forced = clean_task_checkout(&repo, &git_cli, &unmerged, true).expect("force branch");
+        assert_eq!(
+            forced,
+            CheckoutCleanup::Cleaned {
+                reclaimed_bytes: 0,
+                branch: BranchCleanup::Deleted("task/T-PRUNE-UNMERGED".to_string()),
+            }
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn prune_dry_run_lists_old_terminal_tasks_children_first() {
+        let service = mk_service();
+        let old = Utc::now() - Duration::days(10);
+        let mut parent = mk_task("T-PRUNE-PARENT", TaskState::Merged);
+        parent.updated_at = old;
+        let mut child = mk_task("T-PRUNE-CHILD", TaskState::Stopped);
+        child.parent_task_id = Some(parent.id.clone());
+        child.updated_at = old;
+        let mut live = mk_task("T-PRUNE-LIVE", TaskState::Chatting);
+        live.updated_at = old;
+        let recent = mk_task("T-PRUNE-RECENT", TaskState::Merged);
+        for task in [&parent, &child, &live, &recent] {
+            create(&service, task);
+        }
+
+        let report = prune(&service, Path::new("/nonexistent"), 7, false, false).expect("prune");
+        let ids: Vec<&str> = report.tasks.iter().map(|t| t.task.id.0.as_str()).collect();
+        assert_eq!(ids, ["T-PRUNE-CHILD", "T-PRUNE-PARENT"]);
+        assert!(report
+            .tasks
+            .iter()
+            .all(|t| t.outcome == PruneOutcome::Candidate && t.age_days == 10));
+        assert_eq!(service.list_tasks().expect("list").len(), 4);
+    }
+
+    #[test]
+    fn prune_force_refuses_to_run_outside_a_git_repo() {
+        let service = mk_service();
+        let mut task = mk_task("T-PRUNE-NOREPO", TaskState::Merged);
+        task.updated_at = Utc::now() - Duration::days(10);
+        create(&service, &task);
+        let dir = std::env::temp_dir().join(format!(
+            "othala-prune-norepo-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&dir).expect("create dir");
+
+        let err = prune(&service, &dir, 7, true, false).expect_err("no repo");
+        assert!(err.to_string().contains("not a git repository"), "{err}");
+        assert!(service.task(&task.id).expect("load").is_some());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,83 @@
+//! Which repository a command operates on: `--repo-dir`,
+//! `$OTHALA_REPO_DIR`, or the current directory.
+
+use std::path::{Path, PathBuf};
+
+use orch_git::{discover_repo, GitCli};
+
+/// Environment fallback for `--repo-dir`.
+pub const REPO_DIR_ENV: &str = "OTHALA_REPO_DIR";
+
+/// The repository every command works against: `--repo-dir`, then
+/// `$OTHALA_REPO_DIR`, then the current directory. An explicit root also
+/// becomes the working directory, so relative `.othala/` and `.orch/` paths
+/// and child processes resolve against it.
+pub fn resolve_repo_root(repo_dir: Option<PathBuf>) -> anyhow::Result<PathBuf> {
+    let repo_dir = repo_dir.or_else(|| {
+        std::env::var_os(REPO_DIR_ENV)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    });
+    let Some(repo_dir) = repo_dir else {
+        return Ok(std::env::current_dir()?);
+    };
+    let root = repo_dir
+        .canonicalize()
+        .map_err(|e| anyhow::anyhow!("repo dir {}: {e}", repo_dir.display()))?;
+    if !root.is_dir() {
+        anyhow::bail!("repo dir {} is not a directory", root.display());
+    }
+    std::env::set_current_dir(&root)?;
+    Ok(root)
+}
+
+/// Fail early, naming the directory, when a command that needs git runs
+/// outside a repository.
+pub fn require_git_repo(repo_root: &Path) -> anyhow::Result<()> {
+    discover_repo(repo_root, &GitCli::default()).map_err(|_| {
+        anyhow::anyhow!(
+            "{} is not a git repository (use --repo-dir or ${REPO_DIR_ENV} to pick one)",
+            repo_root.display()
+        )
+    })?;
+    Ok(())
+}
+
+/// `path` relative to `repo_root` for display, or unchanged if it lies
+/// outside it.
+pub fn relative_to_root<'a>(repo_root: &Path, path: &'a Path) -> &'a Path {
+    path.strip_prefix(repo_root).unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::fs;
+
+    #[test]
+    fn repo_dir_errors_name_the_directory() {
+        let root = std::env::temp_dir().join(format!(
+            "othala-repo-dir-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let missing = root.join("missing");
+        let err = resolve_repo_root(Some(missing.clone())).expect_err("missing dir");
+        assert!(err.to_string().contains(&missing.display().to_string()));
+
+        fs::create_dir_all(&root).expect("create dir");
+        let err = require_git_repo(&root).expect_err("not a git repo");
+        assert!(err.to_string().contains("is not a git repository"));
+        assert!(err.to_string().contains(&root.display().to_string()));
+
+        assert_eq!(
+            relative_to_root(&root, &root.join(".orch/wt/T1")),
+            Path::new(".orch/wt/T1")
+        );
+        assert_eq!(
+            relative_to_root(&root, Path::new("/elsewhere/T1")),
+            Path::new("/elsewhere/T1")
+        );
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,462 @@
+//! `othala stats`: task counts, merge times, success rate, and throughput.
+
+use chrono::{DateTime, Duration, Utc};
+use orch_core::events::{Event, EventKind, FailureCause};
+use orch_core::state::TaskState;
+use orch_core::types::Task;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::persistence::{PersistenceError, SqliteStore};
+use crate::OrchdService;
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct StatsSummary {
+    pub total_tasks: usize,
+    pub tasks_by_state: BTreeMap<String, i64>,
+    pub tasks_by_model: BTreeMap<String, i64>,
+    /// Tasks carrying each label; a task with several labels counts once
+    /// in each of their buckets.
+    pub tasks_by_label: BTreeMap<String, i64>,
+    pub avg_time_to_merge_seconds: Option<f64>,
+    /// Merged tasks as a percentage of merged plus stopped ones.
+    pub success_rate: Option<f64>,
+    pub total_events: i64,
+    /// Tasks merged on each UTC day (`YYYY-MM-DD`) of the throughput window,
+    /// ending today; days without merges are listed as 0.
+    pub merged_per_day: BTreeMap<String, i64>,
+    pub failure_causes: FailureCauses,
+}
+
+/// Task failures and cancellations bucketed by `FailureCause`.
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct FailureCauses {
+    /// Every recorded cause, most frequent first.
+    pub overall: Vec<CauseCount>,
+    pub by_model: BTreeMap<String, Vec<CauseCount>>,
+    pub by_repo: BTreeMap<String, Vec<CauseCount>>,
+    /// Cause counts for each UTC day of the throughput window.
+    pub per_day: BTreeMap<String, Vec<CauseCount>>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+pub struct CauseCount {
+    pub cause: FailureCause,
+    pub count: i64,
+}
+
+/// Days of merge throughput `othala stats` reports by default.
+pub const DEFAULT_THROUGHPUT_DAYS: u32 = 7;
+
+pub fn stats(service: &OrchdService, throughput_days: u32) -> anyhow::Result<StatsSummary> {
+    let tasks = service.list_tasks()?;
+    let state_counts = service.store.task_count_by_state()?;
+    let total_events = service.store.total_event_count()?;
+    let failures = failure_events(&service.store)?;
+    Ok(compute_stats_summary(
+        &tasks,
+        state_counts,
+        total_events,
+        &failures,
+        Utc::now(),
+        throughput_days,
+    ))
+}
+
+fn compute_stats_summary(
+    tasks: &[Task],
+    state_counts: Vec<(String, i64)>,
+    total_events: i64,
+    failures: &[Event],
+    now: DateTime<Utc>,
+    throughput_days: u32,
+) -> StatsSummary {
+    const STATE_TAGS: [&str; 7] = [
+        "CHATTING",
+        "READY",
+        "SUBMITTING",
+        "RESTACKING",
+        "AWAITING_MERGE",
+        "MERGED",
+        "STOPPED",
+    ];
+
+    let mut tasks_by_state = BTreeMap::new();
+    for state in STATE_TAGS {
+        tasks_by_state.insert(state.to_string(), 0);
+    }
+    for (state, count) in state_counts {
+        tasks_by_state.insert(state, count);
+    }
+
+    let mut tasks_by_model = BTreeMap::new();
+    for task in tasks {
+        let model = task
+            .preferred_model
+            .map(|model| model.as_str().to_string())
+            .unwrap_or_else(|| "unspecified".to_string());
+        *tasks_by_model.entry(model).or_insert(0) += 1;
+    }
+
+    let mut tasks_by_label = BTreeMap::new();
+    for task in tasks {
+        let labels: BTreeSet<&String> = task.labels.iter().collect();
+        for label in labels {
+            *tasks_by_label.entry(label.clone()).or_insert(0) += 1;
+        }
+    }
+
+    let merged = *tasks_by_state.get("MERGED").unwrap_or(&0);
+    let stopped = *tasks_by_state.get("STOPPED").unwrap_or(&0);
+    let denominator = merged + stopped;
+    let success_rate = if denominator > 0 {
+        Some((merged as f64 / denominator as f64) * 100.0)
+    } else {
+        None
+    };
+
+    let merged_durations: Vec<f64> = tasks
+        .iter()
+        .filter(|task| task.state == TaskState::Merged)
+        .map(|task| (task.updated_at - task.created_at).num_milliseconds() as f64 / 1000.0)
+        .collect();
+    let avg_time_to_merge_seconds = if merged_durations.is_empty() {
+        None
+    } else {
+        Some(merged_durations.iter().sum::<f64>() / merged_durations.len() as f64)
+    };
+
+    StatsSummary {
+        total_tasks: tasks.len(),
+        tasks_by_state,
+        tasks_by_model,
+        tasks_by_label,
+        avg_time_to_merge_seconds,
+        success_rate,
+        total_events,
+        merged_per_day: merged_per_day(tasks, now, throughput_days),
+        failure_causes: failure_causes(tasks, failures, now, throughput_days),
+    }
+}
+
+/// `TaskFailed` and `CancellationRequested` events, the inputs to
+/// [`failure_causes`].
+pub fn failure_events(store: &SqliteStore) -> Result<Vec<Event>, PersistenceError> {
+    let mut events = store.list_events_by_kind("task_failed")?;
+    events.extend(store.list_events_by_kind("cancellation_requested")?);
+    Ok(events)
+}
+
+/// The cause of a failure or cancellation event, classifying the reason of
+/// failures recorded without one.
+fn event_failure_cause(kind: &EventKind) -> Option<FailureCause> {
+    match kind {
+        EventKind::TaskFailed { reason, cause, .. } => {
+            Some(cause.unwrap_or_else(|| FailureCause::classify(reason)))
+        }
+        EventKind::CancellationRequested { reason } => Some(FailureCause::from(reason)),
+        _ => None,
+    }
+}
+
+/// Rank failure causes overall, per task model and repo, and per day of
+/// the `days`-day window ending on `now`'s.
+pub fn failure_causes(
+    tasks: &[Task],
+    failures: &[Event],
+    now: DateTime<Utc>,
+    days: u32,
+) -> FailureCauses {
+    let tasks_by_id: HashMap<&str, &Task> = tasks
+        .iter()
+        .map(|task| (task.id.0.as_str(), task))
+        .collect();
+    let first_day = now.date_naive() - Duration::days(i64::from(days.saturating_sub(1)));
+
+    let mut overall = BTreeMap::new();
+    let mut by_model: BTreeMap<String, BTreeMap<FailureCause, i64>> = BTreeMap::new();
+    let mut by_repo: BTreeMap<String, BTreeMap<FailureCause, i64>> = BTreeMap::new();
+    let mut per_day: BTreeMap<String, BTreeMap<FailureCause, i64>> = BTreeMap::new();
+    for event in failures {
+        let Some(cause) = event_failure_cause(&event.kind) else {
+            continue;
+        };
+        let task = event
+            .task_id
+            .as_ref()
+            .and_then(|task_id| tasks_by_id.get(task_id.0.as_str()));
+        let model = task
+            .and_then(|task| task.preferred_model)
+            .map(|model| model.as_str().to_string())
+            .unwrap_or_else(|| "unspecified".to_string());
+        let repo = event
+            .repo_id
+            .as_ref()
+            .or(task.map(|task| &task.repo_id))
+            .map(|repo| repo.0.clone())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        *overall.entry(cause).or_insert(0) += 1;
+        *by_model.entry(model).or_default().entry(cause).or_insert(0) += 1;
+        *by_repo.entry(repo).or_default().entry(cause).or_insert(0) += 1;
+        let day = event.at.date_naive();
+        if days > 0 && day >= first_day && day <= now.date_naive() {
+            *per_day
+                .entry(day.format("%Y-%m-%d").to_string())
+                .or_default()
+                .entry(cause)
+                .or_insert(0) += 1;
+        }
+    }
+
+    let ranked = |counts: BTreeMap<String, BTreeMap<FailureCause, i64>>| {
+        counts
+            .into_iter()
+            .map(|(key, counts)| (key, rank_causes(counts)))
+            .collect()
+    };
+    FailureCauses {
+        overall: rank_causes(overall),
+        by_model: ranked(by_model),
+        by_repo: ranked(by_repo),
+        per_day: ranked(per_day),
+    }
+}
+
+/// Most frequent cause first; ties keep taxonomy order.
+fn rank_causes(counts: BTreeMap<FailureCause, i64>) -> Vec<CauseCount> {
+    let mut ranked: Vec<CauseCount> = counts
+        .into_iter()
+        .map(|(cause, count)| CauseCount { cause, count })
+        .collect();
+    ranked.sort_by_key(|entry| std::cmp::Reverse(entry.count));
+    ranked
+}
+
+/// Merged tasks bucketed by the UTC day of their `updated_at`, over the
+/// `days` days ending on `now`'s.
+fn merged_per_day(tasks: &[Task], now: DateTime<Utc>, days: u32) -> BTreeMap<String, i64> {
+    let today = now.date_naive();
+    let mut per_day = (0..days)
+        .map(|offset| {
+            let day = today - Duration::days(i64::from(offset));
+            (day.format("%Y-%m-%d").to_string(), 0)
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    for task in tasks.iter().filter(|task| task.state == TaskState::Merged) {
+        let day = task.updated_at.date_naive().format("%Y-%m-%d").to_string();
+        if let Some(count) = per_day.get_mut(&day) {
+            *count += 1;
+        }
+    }
+    per_day
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::testing::mk_task;
+    use orch_core::events::CancelReason;
+    use orch_core::types::{EventId, ModelKind, RepoId, TaskId};
+
+    #[test]
+    fn stats_command_counts_by_state() {
+        let mut chatting = mk_task("T-STATS-1", TaskState::Chatting);
+        chatting.preferred_model = Some(ModelKind::Claude);
+        let mut merged = mk_task("T-STATS-2", TaskState::Merged);
+        merged.preferred_model = Some(ModelKind::Codex);
+        let stopped = mk_task("T-STATS-3", TaskState::Stopped);
+        let tasks = vec![chatting, merged, stopped];
+
+        let summary = compute_stats_summary(
+            &tasks,
+            vec![
+                ("CHATTING".to_string(), 1),
+                ("MERGED".to_string(), 1),
+                ("STOPPED".to_string(), 1),
+            ],
+            9,
+            &[],
+            Utc::now(),
+            DEFAULT_THROUGHPUT_DAYS,
+        );
+
+        assert_eq!(summary.total_tasks, 3);
+        assert_eq!(summary.tasks_by_state.get("CHATTING"), Some(&1));
+        assert_eq!(summary.tasks_by_state.get("MERGED"), Some(&1));
+        assert_eq!(summary.tasks_by_state.get("STOPPED"), Some(&1));
+        assert_eq!(summary.tasks_by_model.get("claude"), Some(&1));
+        assert_eq!(summary.tasks_by_model.get("codex"), Some(&1));
+        assert_eq!(summary.tasks_by_model.get("unspecified"), Some(&1));
+    }
+
+    #[test]
+    fn stats_command_counts_overlapping_labels_into_each_bucket() {
+        let mut frontend = mk_task("T-STATS-L-1", TaskState::Chatting);
+        frontend.labels = vec!["frontend".to_string(), "urgent".to_string()];
+        let mut backend = mk_task("T-STATS-L-2", TaskState::Ready);
+        backend.labels = vec![
+            "backend".to_string(),
+            "urgent".to_string(),
+            "urgent".to_string(),
+        ];
+        let unlabelled = mk_task("T-STATS-L-3", TaskState::Chatting);
+        let tasks = vec![frontend, backend, unlabelled];
+
+        let summary = compute_stats_summary(
+            &tasks,
+            Vec::new(),
+            0,
+            &[],
+            Utc::now(),
+            DEFAULT_THROUGHPUT_DAYS,
+        );
+
+        assert_eq!(summary.tasks_by_label.len(), 3);
+        assert_eq!(summary.tasks_by_label.get("urgent"), Some(&2));
+        assert_eq!(summary.tasks_by_label.get("frontend"), Some(&1));
+        assert_eq!(summary.tasks_by_label.get("backend"), Some(&1));
+    }
+
+    #[test]
+    fn stats_command_computes_success_rate() {
+        let merged = mk_task("T-STATS-SR-1", TaskState::Merged);
+        let stopped_a = mk_task("T-STATS-SR-2", TaskState::Stopped);
+        let stopped_b = mk_task("T-STATS-SR-3", TaskState::Stopped);
+        let tasks = vec![merged, stopped_a, stopped_b];
+
+        let summary = compute_stats_summary(
+            &tasks,
+            vec![("MERGED".to_string(), 1), ("STOPPED".to_string(), 2)],
+            0,
+            &[],
+            Utc::now(),
+            DEFAULT_THROUGHPUT_DAYS,
+        );
+
+        let rate = summary.success_rate.expect("success rate exists");
+        assert!((rate - 33.3333).abs() < 0.01);
+    }
+    #[test]
+    fn stats_command_counts_merges_per_day_in_window() {
+        let now = DateTime::parse_from_rfc3339("2026-03-10T15:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let merged_at = |id: &str, at: &str| {
+            let mut task = mk_task(id, TaskState::Merged);
+            task.updated_at = DateTime::parse_from_rfc3339(at)
+                .unwrap()
+                .with_timezone(&Utc);
+            task
+        };
+        let mut not_merged = mk_task("T-TP-5", TaskState::Stopped);
+        not_merged.updated_at = now;
+        let tasks = vec![
+            merged_at("T-TP-1", "2026-03-09T08:00:00Z"),
+            merged_at("T-TP-2", "2026-03-09T23:59:59Z"),
+            merged_at("T-TP-3", "2026-03-10T00:00:00Z"),
+            merged_at("T-TP-4", "2026-03-01T12:00:00Z"),
+            not_merged,
+        ];
+
+        let summary = compute_stats_summary(&tasks, Vec::new(), 0, &[], now, 3);
+
+        assert_eq!(
+            summary.merged_per_day,
+            BTreeMap::from([
+                ("2026-03-08".to_string(), 0),
+                ("2026-03-09".to_string(), 2),
+                ("2026-03-10".to_string(), 1),
+            ])
+        );
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["merged_per_day"]["2026-03-09"], 2);
+    }
+
+    #[test]
+    fn stats_command_ranks_failure_causes_overall_and_per_model_and_repo() {
+        let now = DateTime::parse_from_rfc3339("2026-03-10T15:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut claude_task = mk_task("T-FC-1", TaskState::Stopped);
+        claude_task.preferred_model = Some(ModelKind::Claude);
+        let mut codex_task = mk_task("T-FC-2", TaskState::Stopped);
+        codex_task.preferred_model = Some(ModelKind::Codex);
+        codex_task.repo_id = RepoId("other".to_string());
+        let tasks = vec![claude_task, codex_task];
+        let event = |task_id: &str, at: &str, kind: EventKind| Event {
+            id: EventId(format!("E-{task_id}-{at}")),
+            task_id: Some(TaskId::new(task_id)),
+            repo_id: None,
+            at: DateTime::parse_from_rfc3339(at)
+                .unwrap()
+                .with_timezone(&Utc),
+            kind,
+        };
+        let failed = |reason: &str, cause: Option<FailureCause>| EventKind::TaskFailed {
+            reason: reason.to_string(),
+            is_final: true,
+            cause,
+        };
+        let failures = vec![
+            event(
+                "T-FC-1",
+                "2026-03-10T01:00:00Z",
+                failed("verify failed", Some(FailureCause::VerifyFailure)),
+            ),
+            event(
+                "T-FC-1",
+                "2026-03-09T01:00:00Z",
+                failed("verify failed again", None),
+            ),
+            event(
+                "T-FC-2",
+                "2026-03-10T02:00:00Z",
+                failed("restack conflict", Some(FailureCause::RestackConflict)),
+            ),
+            event(
+                "T-FC-2",
+                "2026-03-01T02:00:00Z",
+                EventKind::CancellationRequested {
+                    reason: CancelReason::UserRequested,
+                },
+            ),
+        ];
+
+        let summary = compute_stats_summary(&tasks, Vec::new(), 0, &failures, now, 3);
+        let causes = &summary.failure_causes;
+
+        let count = |cause, count| CauseCount { cause, count };
+        assert_eq!(
+            causes.overall,
+            vec![
+                count(FailureCause::VerifyFailure, 2),
+                count(FailureCause::RestackConflict, 1),
+                count(FailureCause::HumanCancel, 1),
+            ]
+        );
+        assert_eq!(
+            causes.by_model["claude"],
+            vec![count(FailureCause::VerifyFailure, 2)]
+        );
+        assert_eq!(causes.by_model["codex"].len(), 2);
+        assert_eq!(
+            causes.by_repo["other"],
+            vec![
+                count(FailureCause::RestackConflict, 1),
+                count(FailureCause::HumanCancel, 1),
+            ]
+        );
+        assert_eq!(
+            causes.per_day.keys().collect::<Vec<_>>(),
+            vec!["2026-03-09", "2026-03-10"]
+        );
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(
+            json["failure_causes"]["overall"][0]["cause"],
+            "verify_failure"
+        );
+        assert_eq!(json["failure_causes"]["overall"][0]["count"], 2);
+    }
+}
@@ -0,0 +1,494 @@
+//! Single-task commands: `list`, `status`, `delete`, `stop`, `cancel`,
+//! `resume`, `set-priority`, `tag`, `untag`, `alias`, and `load-tasks`.
+
+use chrono::Utc;
+use orch_core::events::{CancelReason, Event, EventKind};
+use orch_core::state::TaskState;
+use orch_core::types::{
+    load_task_specs_from_dir, normalize_label, yaml_spec_to_task, EventId, Task, TaskId,
+    TaskPriority,
+};
+use serde::Serialize;
+use std::path::Path;
+
+use super::bulk::{self, BulkSummary};
+use super::parse_task_priority;
+use crate::{AgentQuestion, OrchdService};
+
+/// A task with its open agent questions.
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskDetail {
+    #[serde(flatten)]
+    pub task: Task,
+    pub open_questions: Vec<AgentQuestion>,
+}
+
+/// A state change made by `stop`, `cancel`, or `resume`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateChange {
+    pub task_id: TaskId,
+    pub from: TaskState,
+    pub to: TaskState,
+}
+
+/// Result of `tag`/`untag`, either on one task or on every task in a state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LabelChange {
+    Task {
+        task_id: TaskId,
+        labels: Vec<String>,
+    },
+    State {
+        state: String,
+        labels: Vec<String>,
+        summary: BulkSummary,
+    },
+}
+
+/// Result of `alias`: the alias just set, or every alias when called bare.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AliasChange {
+    Set { task_id: TaskId, alias: String },
+    List(Vec<(String, TaskId)>),
+}
+
+pub fn list(service: &OrchdService) -> anyhow::Result<Vec<Task>> {
+    Ok(service.list_tasks()?)
+}
+
+/// Every task with its open questions, for `list --detail`.
+pub fn list_detailed(service: &OrchdService) -> anyhow::Result<Vec<TaskDetail>> {
+    service
+        .list_tasks()?
+        .into_iter()
+        .map(|task| {
+            let open_questions = service.open_questions(&task.id)?;
+            Ok(TaskDetail {
+                task,
+                open_questions,
+            })
+        })
+        .collect()
+}
+
+/// The task behind `id`, or `None` when it no longer exists.
+pub fn status(service: &OrchdService, id: &str) -> anyhow::Result<Option<TaskDetail>> {
+    let task_id = service.resolve_task_id(id)?;
+    let Some(task) = service.task(&task_id)? else {
+        return Ok(None);
+    };
+    let open_questions = service.open_questions(&task.id)?;
+    Ok(Some(TaskDetail {
+        task,
+        open_questions,
+    }))
+}
+
+/// Delete the task behind `id`, and its child tasks when `cascade` is set.
+/// Returns the removed IDs, children first; empty if nothing was removed.
+pub fn delete(service: &OrchdService, id: &str, cascade: bool) -> anyhow::Result<Vec<TaskId>> {
+    let task_id = service.resolve_task_id(id)?;
+    Ok(service.delete_task(&task_id, cascade)?)
+}
+
+pub fn stop(service: &OrchdService, id: &str) -> anyhow::Result<StateChange> {
+    transition(service, id, TaskState::Stopped, "E-STOP")
+}
+
+pub fn resume(service: &OrchdService, id: &str) -> anyhow::Result<StateChange> {
+    transition(service, id, TaskState::Chatting, "E-RESUME")
+}
+
+/// Cancel the task behind `id`; `reason` is parsed with [`CancelReason::parse`].
+pub fn cancel(service: &OrchdService, id: &str, reason: &str) -> anyhow::Result<StateChange> {
+    let task_id = service.resolve_task_id(id)?;
+    let from = cancel_task(service, &task_id, CancelReason::parse(reason))?;
+    Ok(StateChange {
+        task_id,
+        from,
+        to: TaskState::Stopped,
+    })
+}
+
+fn transition(
+    service: &OrchdService,
+    id: &str,
+    to: TaskState,
+    event_prefix: &str,
+) -> anyhow::Result<StateChange> {
+    let task_id = service.resolve_task_id(id)?;
+    let Some(task) = service.task(&task_id)? else {
+        anyhow::bail!("task not found: {}", task_id.0);
+    };
+    let now = Utc::now();
+    let event_id = EventId(format!("{event_prefix}-{id}-{}", now.timestamp_millis()));
+    service.transition_task_state(&task_id, to, event_id, now)?;
+    Ok(StateChange {
+        task_id,
+        from: task.state,
+        to,
+    })
+}
+
+/// Record a cancellation request and stop the task. Returns the state it was
+/// cancelled from.
+pub fn cancel_task(
+    service: &OrchdService,
+    task_id: &TaskId,
+    reason: CancelReason,
+) -> anyhow::Result<TaskState> {
+    let Some(task) = service.task(task_id)? else {
+        anyhow::bail!("task not found: {}", task_id.0);
+    };
+
+    if !matches!(
+        task.state,
+        TaskState::Chatting | TaskState::Ready | TaskState::Restacking | TaskState::Submitting
+    ) {
+        anyhow::bail!("cannot cancel task in state {}", task.state);
+    }
+
+    let now = Utc::now();
+    service.record_event(&Event {
+        id: EventId(format!("E-CANCEL-{}-{}", task_id.0, now.timestamp_millis())),
+        task_id: Some(task_id.clone()),
+        repo_id: Some(task.repo_id.clone()),
+        at: now,
+        kind: EventKind::CancellationRequested { reason },
+    })?;
+
+    let from_state = task.state;
+    service.transition_task_state(
+        task_id,
+        TaskState::Stopped,
+        EventId(format!(
+            "E-CANCEL-STATE-{}-{}",
+            task_id.0,
+            now.timestamp_millis()
+        )),
+        now,
+    )?;
+
+    Ok(from_state)
+}
+
+/// Set the priority of the task behind `id`.
+pub fn set_priority(
+    service: &OrchdService,
+    id: &str,
+    priority: &str,
+) -> anyhow::Result<(TaskId, TaskPriority)> {
+    let task_id = service.resolve_task_id(id)?;
+    let priority = parse_task_priority(priority)?;
+    update_priority(service, &task_id, priority)?;
+    Ok((task_id, priority))
+}
+
+pub(crate) fn update_priority(
+    service: &OrchdService,
+    task_id: &TaskId,
+    priority: TaskPriority,
+) -> anyhow::Result<()> {
+    let Some(mut task) = service.task(task_id)? else {
+        anyhow::bail!("task not found: {}", task_id.0);
+    };
+    task.priority = priority;
+    task.updated_at = Utc::now();
+    service.store.upsert_task(&task)?;
+    Ok(())
+}
+
+/// `othala alias <task> <alias>`, or with neither argument, list aliases.
+pub fn alias(
+    service: &OrchdService,
+    task_ref: Option<&str>,
+    alias: Option<&str>,
+) -> anyhow::Result<AliasChange> {
+    match (task_ref, alias) {
+        (Some(task_ref), Some(alias)) => {
+            let task_id = service.resolve_task_id(task_ref)?;
+            let alias = service.set_task_alias(&task_id, alias)?;
+            Ok(AliasChange::Set { task_id, alias })
+        }
+        (None, None) => Ok(AliasChange::List(service.task_aliases()?)),
+        _ => anyhow::bail!("usage: othala alias <task-id> <alias>"),
+    }
+}
+
+/// Create a task in `repo_id` for every YAML spec in `specs_dir`. Returns
+/// how many were loaded.
+pub fn load_specs(
+    service: &OrchdService,
+    specs_dir: &Path,
+    repo_id: &str,
+) -> anyhow::Result<usize> {
+    let specs = load_task_specs_from_dir(specs_dir);
+    for spec in &specs {
+        let task = yaml_spec_to_task(spec, repo_id);
+        let event = Event {
+            id: EventId(format!("E-CREATE-{}", task.id.0)),
+            task_id: Some(task.id.clone()),
+            repo_id: Some(task.repo_id.clone()),
+            at: Utc::now(),
+            kind: EventKind::TaskCreated,
+        };
+        service.create_task(&task, &event)?;
+    }
+    Ok(specs.len())
+}
+
+/// `othala tag <task> <label>...` or `othala tag --state <state> <label>...`.
+pub fn tag(
+    service: &OrchdService,
+    args: Vec<String>,
+    state: Option<&str>,
+) -> anyhow::Result<LabelChange> {
+    change_labels(service, args, state, false)
+}
+
+/// `othala untag <task> <label>...` or `othala untag --state <state> <label>...`.
+pub fn untag(
+    service: &OrchdService,
+    args: Vec<String>,
+    state: Option<&str>,
+) -> anyhow::Result<LabelChange> {
+    change_labels(service, args, state, true)
+}
+
+fn change_labels(
+    service: &OrchdService,
+    args: Vec<String>,
+    state: Option<&str>,
+    remove: bool,
+) -> anyhow::Result<LabelChange> {
+    match split_tag_args(args, state)? {
+        (Some(task_ref), labels) => {
+            let task_id = service.resolve_task_id(&task_ref)?;
+            if remove {
+                remove_task_labels(service, &task_id, &labels)?;
+            } else {
+                add_task_labels(service, &task_id, &labels)?;
+            }
+            Ok(LabelChange::Task { task_id, labels })
+        }
+        (None, labels) => {
+            let state = state.unwrap_or_default().to_string();
+            let summary = bulk::tag(service, &labels, &state, remove)?;
+            Ok(LabelChange::State {
+                state,
+                labels,
+                summary,
+            })
+        }
+    }
+}
+
+pub fn add_task_labels(
+    service: &OrchdService,
+    task_id: &TaskId,
+    labels: &[String],
+) -> anyhow::Result<()> {
+    ensure_labels_non_empty(labels)?;
+    let Some(mut task) = service.task(task_id)? else {
+        anyhow::bail!("task not found: {}", task_id.0);
+    };
+    for label in labels {
+        task.add_label(label);
+    }
+    task.updated_at = Utc::now();
+    service.store.upsert_task(&task)?;
+    Ok(())
+}
+
+pub fn remove_task_labels(
+    service: &OrchdService,
+    task_id: &TaskId,
+    labels: &[String],
+) -> anyhow::Result<()> {
+    ensure_labels_non_empty(labels)?;
+    let Some(mut task) = service.task(task_id)? else {
+        anyhow::bail!("task not found: {}", task_id.0);
+    };
+    for label in labels {
+        task.remove_label(label);
+    }
+    task.updated_at = Utc::now();
+    service.store.upsert_task(&task)?;
+    Ok(())
+}
+
+pub(crate) fn ensure_labels_non_empty(labels: &[String]) -> anyhow::Result<()> {
+    if labels.is_empty() || labels.iter().any(|label| normalize_label(label).is_empty()) {
+        anyhow::bail!("label cannot be empty");
+    }
+    Ok(())
+}
+
+/// Split `tag`/`untag` positionals into an optional task ref and the labels.
+/// With `--state` every positional is a label.
+pub fn split_tag_args(
+    args: Vec<String>,
+    state: Option<&str>,
+) -> anyhow::Result<(Option<String>, Vec<String>)> {
+    if state.is_some() {
+        return Ok((None, args));
+    }
+    let mut args = args.into_iter();
+    let task_id = args.next();
+    let labels: Vec<String> = args.collect();
+    if labels.is_empty() {
+        anyhow::bail!("expected `<task> <label>...` or `--state <state> <label>...`");
+    }
+    Ok((task_id, labels))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::testing::{create, mk_service, mk_task};
+    use crate::{ServiceError, TransitionError};
+
+    fn add_task_label(service: &OrchdService, task_id: &TaskId, label: &str) -> anyhow::Result<()> {
+        add_task_labels(service, task_id, &[label.to_string()])
+    }
+
+    fn remove_task_label(
+        service: &OrchdService,
+        task_id: &TaskId,
+        label: &str,
+    ) -> anyhow::Result<()> {
+        remove_task_labels(service, task_id, &[label.to_string()])
+    }
+
+    #[test]
+    fn cancel_transitions_chatting_to_stopped() {
+        let service = mk_service();
+        let task = mk_task("T-CANCEL-1", TaskState::Chatting);
+        create(&service, &task);
+
+        let from_state =
+            cancel_task(&service, &task.id, CancelReason::UserRequested).expect("cancel task");
+        assert_eq!(from_state, TaskState::Chatting);
+        let updated = service
+            .task(&task.id)
+            .expect("load task")
+            .expect("task exists");
+        assert_eq!(updated.state, TaskState::Stopped);
+    }
+
+    #[test]
+    fn cancel_rejects_merged_task() {
+        let service = mk_service();
+        let task = mk_task("T-CANCEL-2", TaskState::Merged);
+        create(&service, &task);
+
+        let result = cancel_task(&service, &task.id, CancelReason::UserRequested);
+        assert!(result.is_err());
+        let err = result.expect_err("error").to_string();
+        assert!(err.contains("cannot cancel task in state MERGED"));
+    }
+
+    #[test]
+    fn cancellation_event_created() {
+        let service = mk_service();
+        let task = mk_task("T-CANCEL-3", TaskState::Chatting);
+        create(&service, &task);
+
+        cancel_task(&service, &task.id, CancelReason::UserRequested).expect("cancel task");
+        let events = service.task_events(&task.id).expect("task events");
+        assert!(events.iter().any(|event| {
+            matches!(
+                &event.kind,
+                EventKind::CancellationRequested { reason } if *reason == CancelReason::UserRequested
+            )
+        }));
+    }
+
+    #[test]
+    fn stop_reports_the_state_it_left() {
+        let service = mk_service();
+        let task = mk_task("T-STOP-1", TaskState::Ready);
+        create(&service, &task);
+
+        let change = stop(&service, "T-STOP-1").expect("stop task");
+        assert_eq!(change.from, TaskState::Ready);
+        assert_eq!(change.to, TaskState::Stopped);
+    }
+
+    #[test]
+    fn stop_names_both_states_when_the_transition_is_illegal() {
+        let service = mk_service();
+        let task = mk_task("T-STOP-2", TaskState::Merged);
+        create(&service, &task);
+
+        let err = stop(&service, "T-STOP-2").expect_err("merged tasks cannot stop");
+        assert_eq!(err.to_string(), "cannot go from MERGED to STOPPED");
+        assert!(matches!(
+            err.downcast_ref::<ServiceError>(),
+            Some(ServiceError::Transition(TransitionError::Illegal {
+                from: TaskState::Merged,
+                to: TaskState::Stopped,
+            }))
+        ));
+    }
+
+    #[test]
+    fn tag_adds_label_to_task() {
+        let service = mk_service();
+        let task = mk_task("T-TAG-1", TaskState::Chatting);
+        create(&service, &task);
+
+        add_task_label(&service, &task.id, "bug").expect("tag task");
+        let updated = service
+            .task(&task.id)
+            .expect("load task")
+            .expect("task exists");
+        assert_eq!(updated.labels, vec!["bug".to_string()]);
+    }
+
+    #[test]
+    fn tag_deduplicates_labels() {
+        let service = mk_service();
+        let task = mk_task("T-TAG-2", TaskState::Chatting);
+        create(&service, &task);
+
+        add_task_label(&service, &task.id, "urgent").expect("first tag");
+        add_task_label(&service, &task.id, "urgent").expect("second tag");
+        let updated = service
+            .task(&task.id)
+            .expect("load task")
+            .expect("task exists");
+        assert_eq!(updated.labels, vec!["urgent".to_string()]);
+    }
+
+    #[test]
+    fn tag_dedupes_differently_cased_labels() {
+        let service = mk_service();
+        let task = mk_task("T-TAG-CASE", TaskState::Chatting);
+        create(&service, &task);
+
+        add_task_label(&service, &task.id, "Bug").expect("first tag");
+        add_task_label(&service, &task.id, "  bug ").expect("second tag");
+        let updated = service
+            .task(&task.id)
+            .expect("load task")
+            .expect("task exists");
+        assert_eq!(updated.labels, vec!["bug".to_string()]);
+        assert!(updated.has_label("BUG"));
+    }
+
+    #[test]
+    fn untag_removes_label() {
+        let service = mk_service();
+        let task = mk_task("T-TAG-3", TaskState::Chatting);
+        create(&service, &task);
+
+        add_task_label(&service, &task.id, "feature").expect("tag task");
+        remove_task_label(&service, &task.id, "feature").expect("untag task");
+        let updated = service
+            .task(&task.id)
+            .expect("load task")
+            .expect("task exists");
+        assert!(updated.labels.is_empty());
+    }
+}
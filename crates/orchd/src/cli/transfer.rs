@@ -0,0 +1,192 @@
+//! `othala export` and `othala import`: tasks as portable JSON records.
+
+use chrono::Utc;
+use orch_core::state::TaskState;
+use orch_core::types::{ModelKind, RepoId, Task, TaskId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use super::parse_task_priority;
+use crate::OrchdService;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskExportRecord {
+    pub task_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub state: String,
+    pub priority: String,
+    pub branch_name: Option<String>,
+    pub parent_branch: Option<String>,
+    pub repo_id: String,
+    pub preferred_model: Option<ModelKind>,
+}
+
+impl TaskExportRecord {
+    pub fn from_task(task: &Task, parent_branch: Option<String>) -> Self {
+        Self {
+            task_id: task.id.0.clone(),
+            title: task.title.clone(),
+            description: None,
+            state: format!("{}", task.state),
+            priority: task.priority.as_str().to_string(),
+            branch_name: task.branch_name.clone(),
+            parent_branch,
+            repo_id: task.repo_id.0.clone(),
+            preferred_model: task.preferred_model,
+        }
+    }
+}
+
+/// Export records for every task, or only the one behind `task_id`.
+pub fn export(
+    service: &OrchdService,
+    task_id: Option<&str>,
+) -> anyhow::Result<Vec<TaskExportRecord>> {
+    let tasks = service.list_tasks()?;
+    let parents: HashMap<String, Option<String>> = tasks
+        .iter()
+        .map(|task| {
+            let parent_branch = task.parent_task_id.as_ref().and_then(|parent_id| {
+                tasks
+                    .iter()
+                    .find(|candidate| candidate.id == *parent_id)
+                    .and_then(|parent| parent.branch_name.clone())
+            });
+            (task.id.0.clone(), parent_branch)
+        })
+        .collect();
+    let record =
+        |task: &Task| TaskExportRecord::from_task(task, parents.get(&task.id.0).cloned().flatten());
+
+    match task_id {
+        Some(id) => {
+            let id = service.resolve_task_id(id)?.0;
+            let task = tasks
+                .iter()
+                .find(|task| task.id.0 == id)
+                .ok_or_else(|| anyhow::anyhow!("task not found: {id}"))?;
+            Ok(vec![record(task)])
+        }
+        None => Ok(tasks.iter().map(record).collect()),
+    }
+}
+
+/// Create or update a task per record. Returns how many were imported.
+pub fn import(service: &OrchdService, records: Vec<TaskExportRecord>) -> anyhow::Result<usize> {
+    let mut imported = 0usize;
+    for record in records {
+        let existing = service.task(&TaskId::new(record.task_id.clone()))?;
+        let task = import_record_to_task(record, existing)?;
+        service.upsert_task(&task)?;
+        imported += 1;
+    }
+    Ok(imported)
+}
+
+fn parse_export_state(state: &str) -> anyhow::Result<TaskState> {
+    match state.trim().to_uppercase().as_str() {
+        "CHATTING" => Ok(TaskState::Chatting),
+        "READY" => Ok(TaskState::Ready),
+        "SUBMITTING" => Ok(TaskState::Submitting),
+        "RESTACKING" => Ok(TaskState::Restacking),
+        "AWAITING_MERGE" => Ok(TaskState::AwaitingMerge),
+        "MERGED" => Ok(TaskState::Merged),
+        "STOPPED" => Ok(TaskState::Stopped),
+        other => anyhow::bail!("unknown task state in import: {other}"),
+    }
+}
+
+fn import_record_to_task(record: TaskExportRecord, existing: Option<Task>) -> anyhow::Result<Task> {
+    let now = Utc::now();
+    let mut task = if let Some(existing_task) = existing {
+        existing_task
+    } else {
+        Task::new(
+            TaskId::new(record.task_id.clone()),
+            RepoId(record.repo_id.clone()),
+            record.title.clone(),
+            PathBuf::from(format!(".orch/wt/{}", record.task_id)),
+        )
+    };
+
+    task.repo_id = RepoId(record.repo_id);
+    task.id = TaskId::new(record.task_id);
+    task.title = record.title;
+    task.state = parse_export_state(&record.state)?;
+    task.priority = parse_task_priority(&record.priority)?;
+    task.branch_name = record.branch_name;
+    task.preferred_model = record.preferred_model;
+    task.updated_at = now;
+    Ok(task)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orch_core::types::TaskPriority;
+
+    #[test]
+    fn export_record_contains_expected_fields() {
+        let mut task = Task::new(
+            TaskId::new("T-EXP-1"),
+            RepoId("repo-a".to_string()),
+            "Export me".to_string(),
+            PathBuf::from(".orch/wt/T-EXP-1"),
+        );
+        task.state = TaskState::Ready;
+        task.priority = TaskPriority::Critical;
+        task.branch_name = Some("task/T-EXP-1".to_string());
+        task.preferred_model = Some(ModelKind::Claude);
+
+        let record = TaskExportRecord::from_task(&task, Some("task/parent".to_string()));
+        assert_eq!(record.task_id, "T-EXP-1");
+        assert_eq!(record.title, "Export me");
+        assert_eq!(record.state, "READY");
+        assert_eq!(record.priority, "critical");
+        assert_eq!(record.parent_branch.as_deref(), Some("task/parent"));
+    }
+
+    #[test]
+    fn import_record_to_task_applies_export_values() {
+        let record = TaskExportRecord {
+            task_id: "T-IMP-1".to_string(),
+            title: "Imported".to_string(),
+            description: Some("ignored".to_string()),
+            state: "CHATTING".to_string(),
+            priority: "high".to_string(),
+            branch_name: Some("task/T-IMP-1".to_string()),
+            parent_branch: None,
+            repo_id: "repo-b".to_string(),
+            preferred_model: Some(ModelKind::Gemini),
+        };
+
+        let task = import_record_to_task(record, None).expect("import record");
+        assert_eq!(task.id.0, "T-IMP-1");
+        assert_eq!(task.repo_id.0, "repo-b");
+        assert_eq!(task.title, "Imported");
+        assert_eq!(task.state, TaskState::Chatting);
+        assert_eq!(task.priority, TaskPriority::High);
+        assert_eq!(task.branch_name.as_deref(), Some("task/T-IMP-1"));
+        assert_eq!(task.preferred_model, Some(ModelKind::Gemini));
+    }
+
+    #[test]
+    fn export_import_json_roundtrip_records() {
+        let records = vec![TaskExportRecord {
+            task_id: "T-JSON-1".to_string(),
+            title: "Roundtrip".to_string(),
+            description: None,
+            state: "STOPPED".to_string(),
+            priority: "normal".to_string(),
+            branch_name: None,
+            parent_branch: None,
+            repo_id: "repo-json".to_string(),
+            preferred_model: Some(ModelKind::Codex),
+        }];
+        let json = serde_json::to_string(&records).expect("serialize");
+        let decoded: Vec<TaskExportRecord> = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(decoded, records);
+    }
+}
@@ -0,0 +1,129 @@
+//! `othala undo` / `othala redo`: move a task's worktree between change
+//! snapshots without losing uncommitted edits.
+
+use orch_git::{
+    discover_repo, find_change_snapshot, redo_snapshot, restore_stash, stash_uncommitted,
+    undo_to_snapshot, ChangeSnapshot, GitCli, StashRef,
+};
+
+use crate::OrchdService;
+
+/// What an undo or redo did to the worktree.
+#[derive(Debug, Clone)]
+pub struct SnapshotChange {
+    pub snapshot: ChangeSnapshot,
+    pub redo: bool,
+    /// Uncommitted changes stashed before the checkout, if any.
+    pub stash: Option<StashRef>,
+    /// Whether `stash` was popped back onto the worktree.
+    pub restored: bool,
+}
+
+/// Undo (or with `redo`, reapply) a task's change snapshot, stashing any
+/// uncommitted edits in its worktree first. `index` is 1-based in capture
+/// order and defaults to the latest snapshot.
+pub fn apply_change_snapshot(
+    service: &OrchdService,
+    task_id: &str,
+    index: Option<usize>,
+    restore: bool,
+    redo: bool,
+) -> anyhow::Result<SnapshotChange> {
+    let task = service
+        .store
+        .load_task(&service.resolve_task_id(task_id)?)?
+        .ok_or_else(|| anyhow::anyhow!("task not found: {task_id}"))?;
+    let git = GitCli::default();
+    let repo = discover_repo(&task.worktree_path, &git)?;
+    let snapshot =
+        find_change_snapshot(&repo, &git, &task.id.0, index)?.ok_or_else(|| match index {
+            Some(index) => anyhow::anyhow!("no snapshot #{index} for task: {task_id}"),
+            None => anyhow::anyhow!("no snapshots found for task: {task_id}"),
+        })?;
+
+    let action = if redo { "redo" } else { "undo" };
+    let stash = stash_uncommitted(
+        &repo,
+        &git,
+        &format!("othala: before {action} {}", task.id.0),
+    )?;
+
+    if redo {
+        redo_snapshot(&repo, &git, &snapshot)?;
+    } else {
+        undo_to_snapshot(&repo, &git, &snapshot)?;
+    }
+
+    let restored = match (&stash, restore) {
+        (Some(stash), true) => {
+            restore_stash(&repo, &git, stash).map_err(|err| {
+                anyhow::anyhow!(
+                    "{action} applied, but failed to restore stash {} (still stashed): {err}",
+                    stash.sha
+                )
+            })?;
+            true
+        }
+        _ => false,
+    };
+    Ok(SnapshotChange {
+        snapshot,
+        redo,
+        stash,
+        restored,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use orch_core::state::TaskState;
+    use std::fs;
+    use std::path::Path;
+    use std::process::Command;
+
+    use crate::cli::testing::{create, mk_service, mk_task};
+
+    fn git(cwd: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(cwd)
+            .output()
+            .expect("spawn git");
+        assert!(output.status.success(), "git {args:?} failed");
+    }
+
+    #[test]
+    fn missing_snapshot_fails_before_stashing() {
+        let root = std::env::temp_dir().join(format!(
+            "othala-undo-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&root).expect("create repo dir");
+        git(&root, &["init", "-q", "-b", "main"]);
+        fs::write(root.join("README.md"), "init\n").expect("write readme");
+        git(&root, &["add", "README.md"]);
+        git(&root, &["commit", "-q", "-m", "init"]);
+        fs::write(root.join("README.md"), "wip\n").expect("dirty worktree");
+
+        let service = mk_service();
+        let mut task = mk_task("T-UNDO", TaskState::Chatting);
+        task.worktree_path = root.clone();
+        create(&service, &task);
+
+        let err = apply_change_snapshot(&service, "T-UNDO", None, false, false).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("no snapshots found for task: T-UNDO"));
+        let err = apply_change_snapshot(&service, "T-UNDO", Some(2), false, true).unwrap_err();
+        assert!(err.to_string().contains("no snapshot #2 for task: T-UNDO"));
+        assert_eq!(
+            fs::read_to_string(root.join("README.md")).expect("read readme"),
+            "wip\n"
+        );
+
+        fs::remove_dir_all(root).ok();
+    }
+}
@@ -0,0 +1,149 @@
+//! `othala watch`: follow the agent logs of every chatting task.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, ErrorKind, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use orch_core::state::TaskState;
+
+use crate::OrchdService;
+
+const WATCH_PREFIX_COLORS: [&str; 6] = [
+    "\x1b[31m", "\x1b[32m", "\x1b[33m", "\x1b[34m", "\x1b[36m", "\x1b[35m",
+];
+
+fn format_watch_line(task_id: &str, color: &str, line: &str) -> String {
+    format!("[{color}{task_id}\x1b[0m] {line}")
+}
+
+fn read_all_log_lines_and_position(path: &Path) -> std::io::Result<(Vec<String>, u64)> {
+    let file = File::open(path)?;
+    let mut reader = BufReader::new(file);
+    let mut lines = Vec::new();
+    let mut buf = String::new();
+
+    loop {
+        let bytes_read = reader.read_line(&mut buf)?;
+        if bytes_read == 0 {
+            break;
+        }
+        lines.push(buf.trim_end_matches(&['\r', '\n'][..]).to_string());
+        buf.clear();
+    }
+
+    let position = reader.stream_position()?;
+    Ok((lines, position))
+}
+
+/// Lines appended to `path` since `position`, advancing it. A truncated file
+/// is read from the start.
+pub(crate) fn read_new_log_lines(path: &Path, position: &mut u64) -> std::io::Result<Vec<String>> {
+    let mut file = File::open(path)?;
+    if file.metadata()?.len() < *position {
+        *position = 0;
+    }
+
+    file.seek(SeekFrom::Start(*position))?;
+    let mut reader = BufReader::new(file);
+    let mut new_lines = Vec::new();
+    let mut buf = String::new();
+
+    loop {
+        let bytes_read = reader.read_line(&mut buf)?;
+        if bytes_read == 0 {
+            break;
+        }
+        *position += bytes_read as u64;
+        new_lines.push(buf.trim_end_matches(&['\r', '\n'][..]).to_string());
+        buf.clear();
+    }
+
+    Ok(new_lines)
+}
+
+/// Print the last `lines` lines of each chatting task's log (or just
+/// `task_filter`), then keep following them until interrupted.
+pub fn run(
+    service: &OrchdService,
+    repo_root: &Path,
+    task_filter: Option<String>,
+    lines: usize,
+) -> anyhow::Result<()> {
+    let mut tasks = service.list_tasks_by_state(TaskState::Chatting)?;
+
+    if let Some(task_id) = task_filter {
+        tasks.retain(|task| task.id.0 == task_id);
+    }
+
+    if tasks.is_empty() {
+        println!("No active chatting tasks to watch.");
+        return Ok(());
+    }
+
+    tasks.sort_by(|a, b| a.id.0.cmp(&b.id.0));
+
+    let mut watch_state: HashMap<String, (PathBuf, u64, &'static str)> = HashMap::new();
+    let mut order = Vec::new();
+
+    for (idx, task) in tasks.iter().enumerate() {
+        let color = WATCH_PREFIX_COLORS[idx % WATCH_PREFIX_COLORS.len()];
+        let log_path = crate::agent_log::agent_log_dir(repo_root, &task.id).join("latest.log");
+        let mut position = 0u64;
+
+        match read_all_log_lines_and_position(&log_path) {
+            Ok((all_lines, end_position)) => {
+                let start = all_lines.len().saturating_sub(lines);
+                for line in &all_lines[start..] {
+                    println!("{}", format_watch_line(&task.id.0, color, line));
+                }
+                position = end_position;
+            }
+            Err(err) => {
+                if err.kind() != ErrorKind::NotFound {
+                    return Err(err.into());
+                }
+            }
+        }
+
+        order.push(task.id.0.clone());
+        watch_state.insert(task.id.0.clone(), (log_path, position, color));
+    }
+
+    let shutdown = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    signal_hook::flag::register(signal_hook::consts::SIGINT, shutdown.clone())?;
+
+    while !shutdown.load(std::sync::atomic::Ordering::Relaxed) {
+        for task_id in &order {
+            if let Some((log_path, position, color)) = watch_state.get_mut(task_id) {
+                match read_new_log_lines(log_path, position) {
+                    Ok(new_lines) => {
+                        for line in new_lines {
+                            println!("{}", format_watch_line(task_id, color, &line));
+                        }
+                    }
+                    Err(err) => {
+                        if err.kind() != ErrorKind::NotFound {
+                            return Err(err.into());
+                        }
+                    }
+                }
+            }
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(500));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn watch_formats_output_with_prefix() {
+        let line = format_watch_line("task-1", "\x1b[31m", "hello world");
+        assert_eq!(line, "[\x1b[31mtask-1\x1b[0m] hello world");
+    }
+}
@@ -0,0 +1,277 @@
+//! `othala wizard`: probe models, then write `.othala/config.toml` with the
+//! chosen models, notifications, web UI and verify command.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use orch_agents::setup::{
+    probe_models, summarize_setup, validate_setup_selection, ModelSetupSelection, SetupProbeConfig,
+};
+use orch_core::config::{
+    apply_setup_selection_to_org_config, generate_web_auth_token, load_org_config, save_org_config,
+    ModelsConfig,
+};
+use orch_core::types::ModelKind;
+use orch_core::validation::{Validate, ValidationLevel};
+
+use super::daemon::{print_banner, run_context_gen_with_status};
+use super::default_org_config;
+use super::models::{parse_enable_models_csv, repo_models_config, valid_model_names};
+use crate::wizard::{ReadinessReport, WizardSettings};
+
+/// Flags from `othala wizard` that shape the written config.
+#[derive(Debug, Clone, Default)]
+pub struct WizardOptions {
+    /// Comma-separated models to enable; prompted for when unset.
+    pub enable: Option<String>,
+    /// Keeps the configured concurrency on a re-run when unset.
+    pub per_model_concurrency: Option<usize>,
+    /// Never prompt; take settings from flags and the existing config.
+    pub non_interactive: bool,
+    pub settings: WizardSettings,
+}
+
+/// Run the setup flow after `readiness` was reported, then save the config
+/// and re-check readiness.
+pub fn run(
+    repo_root: &Path,
+    readiness: &ReadinessReport,
+    options: WizardOptions,
+) -> anyhow::Result<()> {
+    print_banner();
+    eprintln!("\x1b[35mWelcome to Othala first-time setup\x1b[0m");
+    eprintln!();
+
+    // Show pre-setup readiness
+    crate::wizard::print_readiness_report(readiness, false);
+    eprintln!();
+
+    eprintln!("\x1b[33mProbing model availability...\x1b[0m");
+    let models_config = repo_models_config(repo_root);
+    let report = probe_models(&SetupProbeConfig::default().with_custom_models(&models_config));
+    for probe in &report.models {
+        let detected_text = if probe.installed {
+            "\x1b[32mdetected\x1b[0m"
+        } else {
+            "\x1b[31mnot detected\x1b[0m"
+        };
+        let health_text = if probe.healthy {
+            "\x1b[32mhealthy\x1b[0m"
+        } else {
+            "\x1b[33munhealthy\x1b[0m"
+        };
+
+        let reason = match &probe.version_output {
+            Some(output) if probe.installed && !probe.version_ok => {
+                format!(" ({output})")
+            }
+            _ => String::new(),
+        };
+
+        eprintln!(
+            "  - {:<7} : {} / {}{}",
+            probe.model.as_str(),
+            detected_text,
+            health_text,
+            reason
+        );
+    }
+    eprintln!();
+
+    let config_path = PathBuf::from(".othala/config.toml");
+    let existing_config = if config_path.exists() {
+        Some(load_org_config(&config_path)?)
+    } else {
+        None
+    };
+    let current_models = existing_config
+        .as_ref()
+        .map(|config| config.models.enabled.clone())
+        .unwrap_or_default();
+
+    let selected_models = if let Some(raw) = &options.enable {
+        parse_enable_models_csv(raw, &models_config)?
+    } else if options.non_interactive {
+        if current_models.is_empty() {
+            anyhow::bail!("--non-interactive needs --enable when there is no existing config");
+        }
+        current_models.clone()
+    } else {
+        prompt_enabled_models(&models_config, &current_models)?
+    };
+
+    let validated = validate_setup_selection(
+        &report,
+        &ModelSetupSelection {
+            enabled_models: selected_models,
+        },
+    )
+    .map_err(|err| anyhow::anyhow!(err.to_string()))?;
+
+    let setup_summary = summarize_setup(&report, &validated);
+    eprintln!("\x1b[33mSetup summary\x1b[0m");
+    for item in &setup_summary.items {
+        if item.selected {
+            let status = if item.healthy {
+                "\x1b[32mready\x1b[0m"
+            } else {
+                "\x1b[33mselected with warnings\x1b[0m"
+            };
+            eprintln!("  - {:<7} : {}", item.model.as_str(), status);
+        }
+    }
+    eprintln!();
+
+    let mut org_config = existing_config
+        .clone()
+        .unwrap_or_else(|| default_org_config(validated.enabled_models.clone()));
+
+    // Keep configured concurrency on a re-run unless a new one is given.
+    let kept_concurrency = existing_config
+        .as_ref()
+        .filter(|_| options.per_model_concurrency.is_none())
+        .map(|config| config.concurrency.clone());
+    let per_model = options.per_model_concurrency.unwrap_or(10);
+    apply_setup_selection_to_org_config(&mut org_config, &validated.enabled_models, per_model)
+        .map_err(|err| anyhow::anyhow!(err.to_string()))?;
+    if let Some(concurrency) = &kept_concurrency {
+        org_config.concurrency = concurrency.clone();
+    }
+    if org_config.models.default.is_none() {
+        org_config.models.default = validated.enabled_models.first().copied();
+    }
+    let mut stdin_input = crate::wizard::StdinInput;
+    let input: Option<&mut dyn crate::wizard::WizardInput> = if options.non_interactive {
+        None
+    } else {
+        eprintln!();
+        Some(&mut stdin_input)
+    };
+    crate::wizard::configure_sections(&mut org_config, repo_root, &options.settings, input)?;
+
+    if org_config.ui.web_auth_token.is_none() {
+        org_config.ui.web_auth_token = Some(generate_web_auth_token()?);
+        eprintln!("Generated ui.web_auth_token for the web API");
+    }
+
+    let issues = org_config.validate();
+    for issue in &issues {
+        if issue.level == ValidationLevel::Warning {
+            eprintln!("\x1b[33mwarning\x1b[0m [{}] {}", issue.code, issue.message);
+        }
+    }
+    let errors = issues
+        .iter()
+        .filter(|issue| issue.level == ValidationLevel::Error)
+        .map(|issue| format!("  [{}] {}", issue.code, issue.message))
+        .collect::<Vec<_>>();
+    if !errors.is_empty() {
+        anyhow::bail!(
+            "config is invalid, {} was not saved:\n{}",
+            config_path.display(),
+            errors.join("\n")
+        );
+    }
+
+    save_org_config(&config_path, &org_config)?;
+
+    let context_main_path = PathBuf::from(".othala/context/MAIN.md");
+    let context_generated = if context_main_path.exists() {
+        false
+    } else {
+        let template_dir = PathBuf::from("templates/prompts");
+        run_context_gen_with_status(
+            repo_root,
+            &template_dir,
+            &(&org_config.daemon.context_gen).into(),
+            validated
+                .enabled_models
+                .first()
+                .copied()
+                .unwrap_or(ModelKind::Claude),
+        )?;
+        true
+    };
+
+    eprintln!("\x1b[32mSetup complete\x1b[0m");
+    eprintln!("  - Config: {}", config_path.display());
+    eprintln!(
+        "  - Enabled models: {}",
+        validated
+            .enabled_models
+            .iter()
+            .map(|m| m.as_str())
+            .collect::<Vec<_>>()
+            .join(",")
+    );
+    if kept_concurrency.is_some() {
+        eprintln!("  - Per-model concurrency: unchanged");
+    } else {
+        eprintln!("  - Per-model concurrency: {per_model}");
+    }
+    if let Some(verify) = &org_config.daemon.verify_command {
+        eprintln!("  - Verify command: {verify}");
+    }
+    eprintln!(
+        "  - Notifications: {}",
+        if org_config.notifications.enabled {
+            "enabled"
+        } else {
+            "disabled"
+        }
+    );
+    eprintln!("  - Web UI: {}", org_config.ui.web_bind);
+    if context_generated {
+        eprintln!("  - Context: \x1b[32mgenerated\x1b[0m");
+    } else {
+        eprintln!("  - Context: \x1b[33malready present\x1b[0m");
+    }
+    if setup_summary.all_selected_healthy {
+        eprintln!("  - Model health: \x1b[32mall selected models healthy\x1b[0m");
+    } else {
+        eprintln!("  - Model health: \x1b[33msome selected models have warnings\x1b[0m");
+    }
+
+    // Post-setup readiness re-check
+    eprintln!();
+    let post_readiness = crate::wizard::run_readiness_checks(repo_root);
+    crate::wizard::print_readiness_report(&post_readiness, false);
+    Ok(())
+}
+
+/// Ask until the answer parses; an empty answer keeps `current`.
+fn prompt_enabled_models(
+    models: &ModelsConfig,
+    current: &[ModelKind],
+) -> anyhow::Result<Vec<ModelKind>> {
+    let current = current
+        .iter()
+        .map(|model| model.as_str())
+        .collect::<Vec<_>>()
+        .join(",");
+    let mut line = String::new();
+    loop {
+        if current.is_empty() {
+            eprint!(
+                "Enable models (comma-separated: {}): ",
+                valid_model_names(models)
+            );
+        } else {
+            eprint!(
+                "Enable models (comma-separated: {}) [{current}]: ",
+                valid_model_names(models)
+            );
+        }
+        std::io::stderr().flush()?;
+        line.clear();
+        std::io::stdin().read_line(&mut line)?;
+        if line.trim().is_empty() && !current.is_empty() {
+            line = current.clone();
+        }
+
+        match parse_enable_models_csv(&line, models) {
+            Ok(models) => return Ok(models),
+            Err(err) => eprintln!("\x1b[31mInvalid input: {err}\x1b[0m"),
+        }
+    }
+}
@@ -6,15 +6,20 @@
 //! delimited blocks, validated, and written to disk.
 
 use chrono::{DateTime, Utc};
-use orch_agents::{default_adapter_for, EpochRequest};
+use orch_agents::{default_adapter_for, ChunkSink, EpochRequest};
+use orch_core::config::ContextGenOrgConfig;
 use orch_core::types::{ModelKind, RepoId, TaskId};
+use serde::{Deserialize, Serialize};
 
+use std::collections::{BTreeMap, BTreeSet};
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
 use std::sync::mpsc;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+use crate::file_watcher::FileChangeEvent;
 
 // ---------------------------------------------------------------------------
 // Types
@@ -47,25 +52,59 @@ pub struct ContextGenOutput {
 pub struct ContextGenConfig {
     /// Minimum seconds between regenerations.
     pub cooldown_secs: u64,
-    /// Model to use for generation.
-    pub model: ModelKind,
+    /// Model to use for generation; `None` uses the caller's default model.
+    pub model: Option<ModelKind>,
+    /// Kill a generation that runs longer than this many seconds.
+    pub timeout_secs: u64,
+}
+
+impl ContextGenConfig {
+    /// The configured model, falling back to `default`.
+    pub fn model_or(&self, default: ModelKind) -> ModelKind {
+        self.model.unwrap_or(default)
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs)
+    }
 }
 
 impl Default for ContextGenConfig {
     fn default() -> Self {
         Self {
             cooldown_secs: 300,
-            model: ModelKind::Claude,
+            model: None,
+            timeout_secs: 600,
+        }
+    }
+}
+
+impl From<&ContextGenOrgConfig> for ContextGenConfig {
+    fn from(config: &ContextGenOrgConfig) -> Self {
+        Self {
+            model: config.model,
+            timeout_secs: config.timeout_secs,
+            ..Self::default()
         }
     }
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum ContextGenError {
+    #[error("context generation timed out after {0}s")]
+    TimedOut(u64),
+}
+
 /// Mutable state for the background context generation process.
 pub struct ContextGenState {
     pub status: ContextGenStatus,
     pub last_generated_at: Option<DateTime<Utc>>,
     pub result_rx: Option<mpsc::Receiver<String>>,
     pub child_handle: Option<Child>,
+    /// When the running generation is killed for overrunning.
+    pub deadline: Option<DateTime<Utc>>,
+    /// MAIN.md sections being regenerated; `None` for a full regeneration.
+    pub main_sections: Option<Vec<String>>,
 }
 
 impl ContextGenState {
@@ -75,6 +114,8 @@ impl ContextGenState {
             last_generated_at: None,
             result_rx: None,
             child_handle: None,
+            deadline: None,
+            main_sections: None,
         }
     }
 }
@@ -427,6 +468,572 @@ pub fn write_context_files(
     Ok(written)
 }
 
+// ---------------------------------------------------------------------------
+// Incremental regeneration
+// ---------------------------------------------------------------------------
+
+/// Manifest path, relative to the repo root.
+pub const CONTEXT_MANIFEST_PATH: &str = ".othala/context/.manifest.json";
+
+/// Size and mtime of one input file at generation time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputFingerprint {
+    pub len: u64,
+    pub mtime_ms: i64,
+}
+
+/// Record of what the current context was generated from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextManifest {
+    pub template_hash: String,
+    pub generated_at: DateTime<Utc>,
+    /// Context file (relative to `.othala/context/`) -> input files it covers.
+    pub sections: BTreeMap<String, Vec<String>>,
+    /// Input file (relative to the repo root) -> fingerprint.
+    pub inputs: BTreeMap<String, InputFingerprint>,
+}
+
+/// What a stale context needs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContextRegenPlan {
+    /// No input changed since the manifest was written.
+    UpToDate,
+    /// Only these sections are affected by the changed files.
+    Partial {
+        sections: Vec<String>,
+        changed_files: Vec<String>,
+    },
+    /// No manifest, template changed, or changes can't be attributed.
+    Full,
+}
+
+/// Stable 64-bit FNV-1a hash, hex encoded.
+fn fnv1a_hex(bytes: &[u8]) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    format!("{hash:016x}")
+}
+
+/// Hash of the context-generator template (empty template hashes too).
+pub fn context_template_hash(template_dir: &Path) -> String {
+    let template = std::fs::read(template_dir.join("context-generator.md")).unwrap_or_default();
+    fnv1a_hex(&template)
+}
+
+/// Fingerprint every source file that can feed the context snapshot.
+///
+/// Hidden directories, `target/`, and `node_modules/` are skipped.
+pub fn collect_context_inputs(repo_root: &Path) -> BTreeMap<String, InputFingerprint> {
+    fn walk(root: &Path, dir: &Path, out: &mut BTreeMap<String, InputFingerprint>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') || name == "target" || name == "node_modules" {
+                continue;
+            }
+            let path = entry.path();
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            if meta.is_dir() {
+                walk(root, &path, out);
+            } else if meta.is_file() {
+                let Ok(rel) = path.strip_prefix(root) else {
+                    continue;
+                };
+                let mtime_ms = meta
+                    .modified()
+                    .ok()
+                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map(|d| d.as_millis() as i64)
+                    .unwrap_or_default();
+                out.insert(
+                    rel.to_string_lossy().replace('\\', "/"),
+                    InputFingerprint {
+                        len: meta.len(),
+                        mtime_ms,
+                    },
+                );
+            }
+        }
+    }
+
+    let mut inputs = BTreeMap::new();
+    walk(repo_root, repo_root, &mut inputs);
+    inputs
+}
+
+/// List existing context files, relative to `.othala/context/`.
+pub fn list_context_sections(repo_root: &Path) -> Vec<String> {
+    fn walk(root: &Path, dir: &Path, out: &mut Vec<String>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                walk(root, &path, out);
+            } else if path.extension().map(|e| e == "md").unwrap_or(false) {
+                if let Ok(rel) = path.strip_prefix(root) {
+                    out.push(rel.to_string_lossy().replace('\\', "/"));
+                }
+            }
+        }
+    }
+
+    let context_dir = repo_root.join(".othala/context");
+    let mut sections = Vec::new();
+    walk(&context_dir, &context_dir, &mut sections);
+    sections.sort();
+    sections
+}
+
+/// Crate a section is dedicated to, if any (`crates/orchd.md` -> `orchd`).
+fn section_crate<'a>(section: &str, crate_names: &'a BTreeSet<String>) -> Option<&'a str> {
+    section
+        .split('/')
+        .map(|part| part.strip_suffix(".md").unwrap_or(part))
+        .find_map(|part| crate_names.get(part).map(String::as_str))
+}
+
+/// Crate an input file belongs to (`crates/orchd/src/lib.rs` -> `orchd`).
+fn input_crate(input: &str) -> Option<&str> {
+    let rest = input.strip_prefix("crates/")?;
+    let (name, _) = rest.split_once('/')?;
+    Some(name)
+}
+
+/// Attribute input files to sections.
+///
+/// Sections named after a crate cover that crate's files; every other input
+/// is covered by the general sections (MAIN.md and friends).
+pub fn assign_section_inputs<'a>(
+    sections: &[String],
+    inputs: impl IntoIterator<Item = &'a String>,
+) -> BTreeMap<String, Vec<String>> {
+    let inputs: Vec<&String> = inputs.into_iter().collect();
+    let crate_names: BTreeSet<String> = inputs
+        .iter()
+        .filter_map(|input| input_crate(input).map(str::to_string))
+        .collect();
+
+    let mut assigned: BTreeMap<String, Vec<String>> =
+        sections.iter().map(|s| (s.clone(), Vec::new())).collect();
+    let general: Vec<&String> = sections
+        .iter()
+        .filter(|s| section_crate(s, &crate_names).is_none())
+        .collect();
+
+    for input in inputs {
+        let owners: Vec<&String> = match input_crate(input) {
+            Some(name) => sections
+                .iter()
+                .filter(|s| section_crate(s, &crate_names) == Some(name))
+                .collect(),
+            None => Vec::new(),
+        };
+        let owners = if owners.is_empty() {
+            general.clone()
+        } else {
+            owners
+        };
+        for owner in owners {
+            if let Some(list) = assigned.get_mut(owner) {
+                list.push(input.clone());
+            }
+        }
+    }
+    assigned
+}
+
+pub fn read_context_manifest(repo_root: &Path) -> Option<ContextManifest> {
+    let raw = std::fs::read_to_string(repo_root.join(CONTEXT_MANIFEST_PATH)).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+/// Snapshot the current inputs and sections into `.manifest.json`.
+pub fn write_context_manifest(repo_root: &Path, template_dir: &Path) -> std::io::Result<()> {
+    let inputs = collect_context_inputs(repo_root);
+    let sections = assign_section_inputs(&list_context_sections(repo_root), inputs.keys());
+    let manifest = ContextManifest {
+        template_hash: context_template_hash(template_dir),
+        generated_at: Utc::now(),
+        sections,
+        inputs,
+    };
+    let path = repo_root.join(CONTEXT_MANIFEST_PATH);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    std::fs::write(path, json)
+}
+
+/// Work out which sections need regenerating since the manifest was written.
+pub fn plan_context_regen(repo_root: &Path, template_dir: &Path) -> ContextRegenPlan {
+    let Some(manifest) = read_context_manifest(repo_root) else {
+        return ContextRegenPlan::Full;
+    };
+    if manifest.template_hash != context_template_hash(template_dir) {
+        return ContextRegenPlan::Full;
+    }
+
+    let current = collect_context_inputs(repo_root);
+    let changed_files: Vec<String> = current
+        .iter()
+        .filter(|(path, fp)| manifest.inputs.get(*path) != Some(fp))
+        .map(|(path, _)| path.clone())
+        .chain(
+            manifest
+                .inputs
+                .keys()
+                .filter(|path| !current.contains_key(*path))
+                .cloned(),
+        )
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    if changed_files.is_empty() {
+        return ContextRegenPlan::UpToDate;
+    }
+
+    let section_names: Vec<String> = manifest.sections.keys().cloned().collect();
+    let attributed = assign_section_inputs(&section_names, changed_files.iter());
+    let sections: Vec<String> = attributed
+        .into_iter()
+        .filter(|(_, inputs)| !inputs.is_empty())
+        .map(|(section, _)| section)
+        .collect();
+    if sections.is_empty() {
+        return ContextRegenPlan::Full;
+    }
+    ContextRegenPlan::Partial {
+        sections,
+        changed_files,
+    }
+}
+
+/// Prompt asking the agent to rewrite only the affected sections.
+pub fn build_partial_context_gen_prompt(
+    repo_root: &Path,
+    template_dir: &Path,
+    sections: &[String],
+    changed_files: &[String],
+) -> String {
+    let mut prompt = build_context_gen_prompt(repo_root, template_dir);
+    prompt.push_str("## Incremental Update
+
+");
+    prompt.push_str(
+        "Only the sections below are affected by recent changes. Regenerate just these \
+         files and output each one with a `<!-- FILE: name.md -->` delimiter.\n\n",
+    );
+    prompt.push_str("Sections to regenerate:\n");
+    for section in sections {
+        let existing = std::fs::read_to_string(repo_root.join(".othala/context").join(section))
+            .unwrap_or_default();
+        prompt.push_str(&format!("\n### {section} (current)\n{existing}\n"));
+    }
+    prompt.push_str("\nChanged files:\n");
+    for file in changed_files {
+        prompt.push_str(&format!("- {file}\n"));
+    }
+    prompt
+}
+
+/// Regenerate stale context, touching only affected sections when a manifest
+/// allows it. `generate` runs the agent on a prompt and returns its stdout.
+///
+/// Returns the plan that was carried out.
+pub fn regenerate_context_incremental(
+    repo_root: &Path,
+    template_dir: &Path,
+    generate: impl FnOnce(&str) -> anyhow::Result<String>,
+) -> anyhow::Result<ContextRegenPlan> {
+    let plan = plan_context_regen(repo_root, template_dir);
+    let mut parsed = match &plan {
+        ContextRegenPlan::UpToDate => {
+            if let Some(hash) = get_head_sha(repo_root) {
+                write_stored_hash(repo_root, &hash)?;
+            }
+            return Ok(plan);
+        }
+        ContextRegenPlan::Full => {
+            parse_context_gen_output(&generate(&build_context_gen_prompt(repo_root, template_dir))?)
+        }
+        ContextRegenPlan::Partial {
+            sections,
+            changed_files,
+        } => parse_context_gen_output(&generate(&build_partial_context_gen_prompt(
+            repo_root,
+            template_dir,
+            sections,
+            changed_files,
+        ))?),
+    };
+
+    if let ContextRegenPlan::Partial { sections, .. } = &plan {
+        parsed.files.retain(|file| sections.contains(&file.filename));
+    }
+    if parsed.files.is_empty() {
+        anyhow::bail!("context generation agent produced no context files");
+    }
+    write_context_files(repo_root, &parsed)?;
+    write_context_manifest(repo_root, template_dir)?;
+    Ok(plan)
+}
+
+// ---------------------------------------------------------------------------
+// MAIN.md section updates
+// ---------------------------------------------------------------------------
+
+/// MAIN.md path, relative to the repo root.
+pub const MAIN_CONTEXT_PATH: &str = ".othala/context/MAIN.md";
+/// Agent log id that background context generation streams its output to.
+pub const CONTEXT_GEN_LOG_ID: &str = "context-gen";
+
+/// One `## ` section of MAIN.md, kept byte for byte so untouched sections
+/// survive a merge unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MainSection {
+    /// Heading text without the leading `## `.
+    pub heading: String,
+    /// Heading line through the line before the next heading.
+    pub raw: String,
+}
+
+/// Split MAIN.md into the text before the first `## ` heading and its
+/// sections. Concatenating the preamble and every `raw` gives `text` back.
+pub fn split_main_sections(text: &str) -> (String, Vec<MainSection>) {
+    let mut preamble = String::new();
+    let mut sections: Vec<MainSection> = Vec::new();
+    for line in text.split_inclusive('\n') {
+        if let Some(heading) = line.strip_prefix("## ") {
+            sections.push(MainSection {
+                heading: heading.trim().to_string(),
+                raw: String::new(),
+            });
+        }
+        match sections.last_mut() {
+            Some(section) => section.raw.push_str(line),
+            None => preamble.push_str(line),
+        }
+    }
+    (preamble, sections)
+}
+
+/// Paths named in a heading, e.g. ``## `crates/orchd` daemon`` -> `crates/orchd`.
+fn heading_paths(heading: &str) -> Vec<String> {
+    heading
+        .split_whitespace()
+        .map(|token| {
+            token
+                .trim_matches(|c: char| matches!(c, '`' | '(' | ')' | ',' | ':' | '*'))
+                .trim_end_matches('/')
+        })
+        .filter(|token| token.contains('/') || token.contains('.'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Sections covering `changed_files`: each file goes to the section whose
+/// heading names the file or its closest parent directory.
+///
+/// Returns `None` when a file is not covered by any section, since the
+/// change can't be placed without regenerating everything.
+pub fn sections_for_changed_files(
+    sections: &[MainSection],
+    changed_files: &[String],
+) -> Option<Vec<String>> {
+    let mut affected = BTreeSet::new();
+    for file in changed_files {
+        let owner = sections
+            .iter()
+            .flat_map(|section| {
+                heading_paths(&section.heading)
+                    .into_iter()
+                    .map(move |path| (path, &section.heading))
+            })
+            .filter(|(path, _)| file == path || file.starts_with(&format!("{path}/")))
+            .max_by_key(|(path, _)| path.len())?;
+        affected.insert(owner.1.clone());
+    }
+    // Keep document order so the prompt reads like MAIN.md.
+    let ordered: Vec<String> = sections
+        .iter()
+        .filter(|section| affected.contains(&section.heading))
+        .map(|section| section.heading.clone())
+        .collect();
+    (!ordered.is_empty()).then_some(ordered)
+}
+
+/// Repo-relative paths from watcher events, skipping hidden directories such
+/// as `.othala/` so writing the context does not retrigger it.
+pub fn context_changed_paths(repo_root: &Path, events: &[FileChangeEvent]) -> Vec<String> {
+    events
+        .iter()
+        .filter_map(|event| event.path.strip_prefix(repo_root).ok())
+        .map(|rel| rel.to_string_lossy().replace('\\', "/"))
+        .filter(|rel| !rel.split('/').any(|part| part.starts_with('.')))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// MAIN.md sections to regenerate for `changed_files`, or `None` when only a
+/// full regeneration will do.
+pub fn plan_main_sections_regen(repo_root: &Path, changed_files: &[String]) -> Option<Vec<String>> {
+    if changed_files.is_empty() {
+        return None;
+    }
+    let main = std::fs::read_to_string(repo_root.join(MAIN_CONTEXT_PATH)).ok()?;
+    let (_, sections) = split_main_sections(&main);
+    sections_for_changed_files(&sections, changed_files)
+}
+
+/// Prompt asking the agent to rewrite only the given MAIN.md sections.
+pub fn build_main_sections_prompt(
+    repo_root: &Path,
+    template_dir: &Path,
+    headings: &[String],
+    changed_files: &[String],
+) -> String {
+    let main = std::fs::read_to_string(repo_root.join(MAIN_CONTEXT_PATH)).unwrap_or_default();
+    let (_, sections) = split_main_sections(&main);
+
+    let mut prompt = build_context_gen_prompt(repo_root, template_dir);
+    prompt.push_str("## Section Update\n\n");
+    prompt.push_str(
+        "Only the MAIN.md sections below are affected by recent changes. Rewrite just these \
+         sections, without their `## ` heading line, and start each one with a \
+         `<!-- SECTION: heading -->` delimiter using the heading text shown.\n",
+    );
+    for section in sections.iter().filter(|s| headings.contains(&s.heading)) {
+        prompt.push_str(&format!("\n### {} (current)\n{}", section.heading, section.raw));
+    }
+    prompt.push_str("\nChanged files:\n");
+    for file in changed_files {
+        prompt.push_str(&format!("- {file}\n"));
+    }
+    prompt
+}
+
+/// Parse `<!-- SECTION: heading -->` delimited output into heading -> body.
+pub fn parse_main_section_output(raw: &str) -> BTreeMap<String, String> {
+    let mut sections = BTreeMap::new();
+    let mut current: Option<(String, String)> = None;
+    let mut flush = |current: Option<(String, String)>| {
+        if let Some((heading, body)) = current {
+            let body = body.trim().to_string();
+            if !body.is_empty() {
+                sections.insert(heading, body);
+            }
+        }
+    };
+
+    for line in raw.lines() {
+        let marker = line
+            .trim()
+            .strip_prefix("<!-- SECTION:")
+            .and_then(|rest| rest.strip_suffix("-->"));
+        if let Some(heading) = marker {
+            flush(current.take());
+            current = Some((heading.trim().to_string(), String::new()));
+            continue;
+        }
+        if let Some((heading, body)) = current.as_mut() {
+            // Agents sometimes repeat the heading line; it is kept from MAIN.md.
+            if body.is_empty() && line.strip_prefix("## ").map(str::trim) == Some(heading) {
+                continue;
+            }
+            body.push_str(line);
+            body.push('\n');
+        }
+    }
+    flush(current);
+    sections
+}
+
+/// Replace the bodies of `updates` in `main`, leaving every other byte alone.
+/// Returns the merged text and the headings that were replaced.
+pub fn merge_main_sections(
+    main: &str,
+    updates: &BTreeMap<String, String>,
+) -> (String, Vec<String>) {
+    let (mut merged, sections) = split_main_sections(main);
+    let mut replaced = Vec::new();
+    for section in sections {
+        let Some(body) = updates.get(&section.heading) else {
+            merged.push_str(&section.raw);
+            continue;
+        };
+        let heading_line = section.raw.split_inclusive('\n').next().unwrap_or_default();
+        let rest = &section.raw[heading_line.len()..];
+        // Keep the section's own spacing around the body.
+        let leading = if rest.trim().is_empty() {
+            ""
+        } else {
+            &rest[..rest.len() - rest.trim_start().len()]
+        };
+        let trailing = &section.raw[section.raw.trim_end().len()..];
+        merged.push_str(heading_line);
+        if !heading_line.ends_with('\n') {
+            merged.push('\n');
+        }
+        merged.push_str(leading);
+        merged.push_str(body.trim());
+        merged.push_str(if trailing.is_empty() { "\n" } else { trailing });
+        replaced.push(section.heading);
+    }
+    (merged, replaced)
+}
+
+/// Merge agent output for `headings` into MAIN.md and refresh the stored
+/// hash. Sections the agent returns beyond `headings` are ignored.
+pub fn apply_main_section_output(
+    repo_root: &Path,
+    headings: &[String],
+    raw_output: &str,
+) -> anyhow::Result<Vec<String>> {
+    let mut updates = parse_main_section_output(raw_output);
+    updates.retain(|heading, _| headings.contains(heading));
+    if updates.is_empty() {
+        anyhow::bail!("context generation agent produced no MAIN.md sections");
+    }
+    let path = repo_root.join(MAIN_CONTEXT_PATH);
+    let (merged, replaced) = merge_main_sections(&std::fs::read_to_string(&path)?, &updates);
+    std::fs::write(&path, merged)?;
+    if let Some(hash) = get_head_sha(repo_root) {
+        write_stored_hash(repo_root, &hash)?;
+    }
+    Ok(replaced)
+}
+
+/// Regenerate only the MAIN.md sections covering `changed_files`.
+/// `generate` runs the agent on a prompt and returns its stdout.
+///
+/// Returns `None` without running the agent when the changes can't be
+/// attributed to sections, so the caller should regenerate in full.
+pub fn regenerate_main_sections(
+    repo_root: &Path,
+    template_dir: &Path,
+    changed_files: &[String],
+    generate: impl FnOnce(&str) -> anyhow::Result<String>,
+) -> anyhow::Result<Option<Vec<String>>> {
+    let Some(headings) = plan_main_sections_regen(repo_root, changed_files) else {
+        return Ok(None);
+    };
+    let prompt = build_main_sections_prompt(repo_root, template_dir, &headings, changed_files);
+    let raw = generate(&prompt)?;
+    apply_main_section_output(repo_root, &headings, &raw).map(Some)
+}
+
 // ---------------------------------------------------------------------------
 // Process management
 // ---------------------------------------------------------------------------
@@ -434,21 +1041,25 @@ pub fn write_context_files(
 /// Spawn a background agent process for context generation.
 ///
 /// Sets the state to `Running` and stores the child handle + output receiver.
+/// [`poll_context_gen`] kills the process once `timeout_secs` have passed.
+/// With `log`, each output line is also streamed to it as it is read.
 pub fn spawn_context_gen(
     repo_root: &Path,
     prompt: &str,
     model: ModelKind,
+    timeout_secs: u64,
     state: &mut ContextGenState,
+    log: Option<ChunkSink>,
 ) -> anyhow::Result<()> {
     let adapter = default_adapter_for(model)?;
 
     let request = EpochRequest {
-        task_id: TaskId::new("context-gen"),
+        task_id: TaskId::new(CONTEXT_GEN_LOG_ID),
         repo_id: RepoId("default".to_string()),
         model,
         repo_path: repo_root.to_path_buf(),
         prompt: prompt.to_string(),
-        timeout_secs: 600,
+        timeout_secs,
         extra_args: vec![],
         env: vec![],
     };
@@ -469,10 +1080,13 @@ pub fn spawn_context_gen(
 
     // Pipe stdout into the channel.
     if let Some(stdout) = child.stdout.take() {
-        let tx_out = tx.clone();
+        let (tx_out, log_out) = (tx.clone(), log.clone());
         thread::spawn(move || {
             let reader = BufReader::new(stdout);
             for line in reader.lines().map_while(Result::ok) {
+                if let Some(log) = &log_out {
+                    log.send_line(&line);
+                }
                 let _ = tx_out.send(line);
             }
         });
@@ -483,6 +1097,9 @@ pub fn spawn_context_gen(
         thread::spawn(move || {
             let reader = BufReader::new(stderr);
             for line in reader.lines().map_while(Result::ok) {
+                if let Some(log) = &log {
+                    log.send_line(&line);
+                }
                 let _ = tx.send(line);
             }
         });
@@ -491,6 +1108,8 @@ pub fn spawn_context_gen(
     state.status = ContextGenStatus::Running;
     state.result_rx = Some(rx);
     state.child_handle = Some(child);
+    state.deadline = Some(Utc::now() + chrono::Duration::seconds(timeout_secs as i64));
+    state.main_sections = None;
 
     Ok(())
 }
@@ -504,6 +1123,18 @@ pub fn poll_context_gen(repo_root: &Path, state: &mut ContextGenState) -> Option
         return None;
     }
 
+    if state.deadline.is_some_and(|deadline| Utc::now() >= deadline) {
+        if let Some(mut child) = state.child_handle.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        eprintln!("[context-gen] Generation overran its deadline and was killed");
+        state.status = ContextGenStatus::Failed;
+        state.result_rx = None;
+        state.deadline = None;
+        return None;
+    }
+
     // Check if the child has exited.
     let exited = if let Some(child) = state.child_handle.as_mut() {
         match child.try_wait() {
@@ -530,6 +1161,23 @@ pub fn poll_context_gen(repo_root: &Path, state: &mut ContextGenState) -> Option
     }
 
     let raw_output = output_lines.join("\n");
+    if let Some(headings) = state.main_sections.take() {
+        state.child_handle = None;
+        state.result_rx = None;
+        return match apply_main_section_output(repo_root, &headings, &raw_output) {
+            Ok(replaced) => {
+                eprintln!("[context-gen] Updated MAIN.md sections: {}", replaced.join(", "));
+                state.status = ContextGenStatus::Completed;
+                state.last_generated_at = Some(Utc::now());
+                Some(vec![repo_root.join(MAIN_CONTEXT_PATH)])
+            }
+            Err(e) => {
+                eprintln!("[context-gen] Failed to update MAIN.md sections: {e}");
+                state.status = ContextGenStatus::Failed;
+                None
+            }
+        };
+    }
     let parsed = parse_context_gen_output(&raw_output);
 
     if !parsed.files.is_empty() {
@@ -603,21 +1251,74 @@ pub fn check_context_startup(repo_root: &Path) -> ContextStartupStatus {
 /// The `progress` callback receives stderr lines from the agent process so the
 /// caller can display activity (spinner, status line, etc.).
 ///
-/// Only blocks if context is completely missing. Returns early for stale or
-/// up-to-date context. Times out after 600s.
+/// Blocks if context is completely missing, or if it is stale and the manifest
+/// shows only some sections need regenerating. Stale context that needs a full
+/// rebuild is left to the background regeneration.
+///
+/// Generation uses `config.model`, or `default_model` when unset, and fails
+/// with [`ContextGenError::TimedOut`] if it overruns `config.timeout_secs`.
 pub fn ensure_context_exists_blocking(
     repo_root: &Path,
     template_dir: &Path,
-    model: ModelKind,
+    config: &ContextGenConfig,
+    default_model: ModelKind,
     progress: impl Fn(&str) + Send + 'static,
 ) -> anyhow::Result<()> {
+    let model = config.model_or(default_model);
     match check_context_startup(repo_root) {
         ContextStartupStatus::UpToDate => return Ok(()),
-        ContextStartupStatus::Stale => return Ok(()),
+        ContextStartupStatus::Stale => {
+            if plan_context_regen(repo_root, template_dir) == ContextRegenPlan::Full {
+                return Ok(());
+            }
+            let plan = regenerate_context_incremental(repo_root, template_dir, |prompt| {
+                run_context_agent_blocking(repo_root, prompt, model, config.timeout(), progress)
+            })?;
+            if let ContextRegenPlan::Partial { sections, .. } = plan {
+                eprintln!(
+                    "[context-gen] Regenerated {} stale context section(s)",
+                    sections.len()
+                );
+            }
+            return Ok(());
+        }
         ContextStartupStatus::Missing => {}
     }
 
     let prompt = build_context_gen_prompt(repo_root, template_dir);
+    let raw = run_context_agent_blocking(repo_root, &prompt, model, config.timeout(), progress)?;
+    let parsed = parse_context_gen_output(&raw);
+
+    if !parsed.files.is_empty() {
+        // Agent output files via stdout delimiters — write them.
+        let paths = write_context_files(repo_root, &parsed)?;
+        eprintln!(
+            "[context-gen] Generated {} context files at startup",
+            paths.len()
+        );
+    } else if repo_root.join(".othala/context/MAIN.md").exists() {
+        // Agent wrote files directly using its Write tool — just stamp the hash.
+        if let Some(hash) = get_head_sha(repo_root) {
+            write_stored_hash(repo_root, &hash)?;
+        }
+        let count = count_context_files(repo_root);
+        eprintln!("[context-gen] Agent wrote {} context files directly", count);
+    } else {
+        anyhow::bail!("context generation agent produced no context files");
+    }
+    write_context_manifest(repo_root, template_dir)?;
+
+    Ok(())
+}
+
+/// Run the context agent to completion and return its stdout.
+fn run_context_agent_blocking(
+    repo_root: &Path,
+    prompt: &str,
+    model: ModelKind,
+    timeout: Duration,
+    progress: impl Fn(&str) + Send + 'static,
+) -> anyhow::Result<String> {
     let adapter = default_adapter_for(model)?;
 
     let request = EpochRequest {
@@ -625,8 +1326,8 @@ pub fn ensure_context_exists_blocking(
         repo_id: RepoId("default".to_string()),
         model,
         repo_path: repo_root.to_path_buf(),
-        prompt,
-        timeout_secs: 600,
+        prompt: prompt.to_string(),
+        timeout_secs: timeout.as_secs(),
         extra_args: vec![],
         env: vec![],
     };
@@ -665,14 +1366,7 @@ pub fn ensure_context_exists_blocking(
         });
     }
 
-    // Wait for completion (no timeout — agent explores at its own pace).
-    loop {
-        match child.try_wait() {
-            Ok(Some(_)) => break,
-            Ok(None) => thread::sleep(Duration::from_millis(200)),
-            Err(e) => anyhow::bail!("error waiting for context gen process: {e}"),
-        }
-    }
+    wait_with_timeout(&mut child, timeout)?;
 
     // Drain output.
     let mut output_lines = Vec::new();
@@ -680,28 +1374,24 @@ pub fn ensure_context_exists_blocking(
         output_lines.push(line);
     }
 
-    let raw = output_lines.join("\n");
-    let parsed = parse_context_gen_output(&raw);
+    Ok(output_lines.join("\n"))
+}
 
-    if !parsed.files.is_empty() {
-        // Agent output files via stdout delimiters — write them.
-        let paths = write_context_files(repo_root, &parsed)?;
-        eprintln!(
-            "[context-gen] Generated {} context files at startup",
-            paths.len()
-        );
-    } else if repo_root.join(".othala/context/MAIN.md").exists() {
-        // Agent wrote files directly using its Write tool — just stamp the hash.
-        if let Some(hash) = get_head_sha(repo_root) {
-            write_stored_hash(repo_root, &hash)?;
+/// Wait for the context agent to exit, killing it once `timeout` elapses.
+fn wait_with_timeout(child: &mut Child, timeout: Duration) -> anyhow::Result<()> {
+    let started = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => return Ok(()),
+            Ok(None) if started.elapsed() >= timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(ContextGenError::TimedOut(timeout.as_secs()).into());
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(200)),
+            Err(e) => anyhow::bail!("error waiting for context gen process: {e}"),
         }
-        let count = count_context_files(repo_root);
-        eprintln!("[context-gen] Agent wrote {} context files directly", count);
-    } else {
-        anyhow::bail!("context generation agent produced no context files");
     }
-
-    Ok(())
 }
 
 /// Count `.md` files under `.othala/context/`.
@@ -765,7 +1455,7 @@ mod tests {
     fn should_regenerate_respects_cooldown() {
         let config = ContextGenConfig {
             cooldown_secs: 300,
-            model: ModelKind::Claude,
+            ..ContextGenConfig::default()
         };
 
         // Never generated → should regenerate.
@@ -783,6 +1473,37 @@ mod tests {
         assert!(should_regenerate(&state, &config, Utc::now()));
     }
 
+    #[test]
+    fn overrunning_generation_is_killed_with_timeout_error() {
+        let mut child = Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .expect("spawn sleep");
+        let started = Instant::now();
+
+        let err = wait_with_timeout(&mut child, Duration::from_secs(1)).expect_err("timeout");
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(matches!(
+            err.downcast_ref::<ContextGenError>(),
+            Some(ContextGenError::TimedOut(1))
+        ));
+        assert_eq!(err.to_string(), "context generation timed out after 1s");
+        assert!(child.try_wait().expect("reap").is_some());
+    }
+
+    #[test]
+    fn poll_kills_background_generation_past_its_deadline() {
+        let mut state = ContextGenState::new();
+        state.status = ContextGenStatus::Running;
+        state.child_handle = Some(Command::new("sleep").arg("30").spawn().expect("spawn"));
+        state.deadline = Some(Utc::now() - chrono::Duration::seconds(1));
+
+        assert!(poll_context_gen(Path::new("/nonexistent"), &mut state).is_none());
+        assert_eq!(state.status, ContextGenStatus::Failed);
+        assert!(state.child_handle.is_none());
+    }
+
     #[test]
     fn should_regenerate_blocks_while_running() {
         let config = ContextGenConfig::default();
@@ -967,4 +1688,223 @@ Crate layout here.
         assert_eq!(output.files[1].filename, "architecture/overview.md");
         assert_eq!(output.files[2].filename, "crates/orchd/daemon-loop.md");
     }
+
+    fn mk_incremental_fixture(name: &str) -> (PathBuf, PathBuf, PathBuf) {
+        let tmp = std::env::temp_dir().join(format!(
+            "othala-ctxgen-{name}-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let repo = tmp.join("repo");
+        let templates = tmp.join("templates");
+        fs::create_dir_all(repo.join("crates/alpha/src")).unwrap();
+        fs::create_dir_all(repo.join("crates/beta/src")).unwrap();
+        fs::create_dir_all(repo.join(".othala/context/crates")).unwrap();
+        fs::create_dir_all(&templates).unwrap();
+        fs::write(repo.join("Cargo.toml"), "[workspace]").unwrap();
+        fs::write(repo.join("crates/alpha/src/lib.rs"), "pub fn a() {}").unwrap();
+        fs::write(repo.join("crates/beta/src/lib.rs"), "pub fn b() {}").unwrap();
+        fs::write(templates.join("context-generator.md"), "Generate context.").unwrap();
+        for (file, body) in [
+            ("MAIN.md", "# Main v1"),
+            ("crates/alpha.md", "# Alpha v1"),
+            ("crates/beta.md", "# Beta v1"),
+        ] {
+            fs::write(repo.join(".othala/context").join(file), body).unwrap();
+        }
+        write_context_manifest(&repo, &templates).unwrap();
+        (tmp, repo, templates)
+    }
+
+    fn read_section(repo: &Path, section: &str) -> String {
+        fs::read_to_string(repo.join(".othala/context").join(section)).unwrap()
+    }
+
+    #[test]
+    fn incremental_regen_skips_when_nothing_changed() {
+        let (tmp, repo, templates) = mk_incremental_fixture("skip");
+
+        let plan = regenerate_context_incremental(&repo, &templates, |_| {
+            panic!("agent should not run when inputs are unchanged")
+        })
+        .unwrap();
+
+        assert_eq!(plan, ContextRegenPlan::UpToDate);
+        assert_eq!(read_section(&repo, "MAIN.md"), "# Main v1");
+        fs::remove_dir_all(&tmp).ok();
+    }
+
+    #[test]
+    fn incremental_regen_only_touches_changed_crate_section() {
+        let (tmp, repo, templates) = mk_incremental_fixture("partial");
+        fs::write(repo.join("crates/alpha/src/lib.rs"), "pub fn a() { todo!() }").unwrap();
+
+        let plan = regenerate_context_incremental(&repo, &templates, |prompt| {
+            assert!(prompt.contains("### crates/alpha.md (current)"));
+            assert!(prompt.contains("- crates/alpha/src/lib.rs"));
+            assert!(!prompt.contains("### crates/beta.md"));
+            Ok("<!-- FILE: crates/alpha.md -->\n# Alpha v2\n\
+                <!-- FILE: crates/beta.md -->\n# Beta v2\n"
+                .to_string())
+        })
+        .unwrap();
+
+        assert_eq!(
+            plan,
+            ContextRegenPlan::Partial {
+                sections: vec!["crates/alpha.md".to_string()],
+                changed_files: vec!["crates/alpha/src/lib.rs".to_string()],
+            }
+        );
+        assert_eq!(read_section(&repo, "crates/alpha.md"), "# Alpha v2");
+        assert_eq!(read_section(&repo, "crates/beta.md"), "# Beta v1");
+        assert_eq!(read_section(&repo, "MAIN.md"), "# Main v1");
+        assert_eq!(plan_context_regen(&repo, &templates), ContextRegenPlan::UpToDate);
+        fs::remove_dir_all(&tmp).ok();
+    }
+
+    #[test]
+    fn incremental_regen_falls_back_to_full_on_template_change() {
+        let (tmp, repo, templates) = mk_incremental_fixture("template");
+        fs::write(templates.join("context-generator.md"), "Generate better context.").unwrap();
+
+        let plan = regenerate_context_incremental(&repo, &templates, |prompt| {
+            assert!(!prompt.contains("## Incremental Update"));
+            Ok("<!-- FILE: MAIN.md -->\n# Main v2\n\
+                <!-- FILE: crates/alpha.md -->\n# Alpha v2\n\
+                <!-- FILE: crates/beta.md -->\n# Beta v2\n"
+                .to_string())
+        })
+        .unwrap();
+
+        assert_eq!(plan, ContextRegenPlan::Full);
+        assert_eq!(read_section(&repo, "MAIN.md"), "# Main v2");
+        assert_eq!(read_section(&repo, "crates/beta.md"), "# Beta v2");
+        assert_eq!(plan_context_regen(&repo, &templates), ContextRegenPlan::UpToDate);
+        fs::remove_dir_all(&tmp).ok();
+    }
+
+    #[test]
+    fn missing_manifest_plans_full_regen() {
+        let tmp = std::env::temp_dir().join(format!(
+            "othala-ctxgen-nomanifest-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(tmp.join(".othala/context")).unwrap();
+        assert_eq!(plan_context_regen(&tmp, &tmp), ContextRegenPlan::Full);
+        fs::remove_dir_all(&tmp).ok();
+    }
+
+    const SECTIONED_MAIN: &str = "\
+# Project Context
+
+Intro paragraph.
+
+## Overview
+
+Workspace of two crates.
+
+## `crates/alpha` (parser)
+
+Alpha parses input.
+
+## `crates/beta`
+
+Beta renders output.
+";
+
+    fn mk_sectioned_repo(label: &str) -> PathBuf {
+        let repo = std::env::temp_dir().join(format!(
+            "othala-ctxgen-sections-{label}-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(repo.join(".othala/context")).unwrap();
+        fs::write(repo.join(MAIN_CONTEXT_PATH), SECTIONED_MAIN).unwrap();
+        repo
+    }
+
+    fn section_raw(main: &str, heading: &str) -> String {
+        split_main_sections(main)
+            .1
+            .into_iter()
+            .find(|section| section.heading == heading)
+            .map(|section| section.raw)
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn split_main_sections_round_trips() {
+        let (preamble, sections) = split_main_sections(SECTIONED_MAIN);
+        assert_eq!(preamble, "# Project Context\n\nIntro paragraph.\n\n");
+        let headings: Vec<&str> = sections.iter().map(|s| s.heading.as_str()).collect();
+        assert_eq!(headings, ["Overview", "`crates/alpha` (parser)", "`crates/beta`"]);
+        let rejoined: String =
+            std::iter::once(preamble).chain(sections.into_iter().map(|s| s.raw)).collect();
+        assert_eq!(rejoined, SECTIONED_MAIN);
+    }
+
+    #[test]
+    fn single_file_change_rewrites_only_its_main_section() {
+        let repo = mk_sectioned_repo("single");
+        let changed = vec!["crates/alpha/src/lib.rs".to_string()];
+
+        let updated = regenerate_main_sections(&repo, &repo, &changed, |prompt| {
+            assert!(prompt.contains("### `crates/alpha` (parser) (current)"));
+            assert!(prompt.contains("- crates/alpha/src/lib.rs"));
+            assert!(!prompt.contains("### `crates/beta`"));
+            Ok("<!-- SECTION: `crates/alpha` (parser) -->\n\
+                ## `crates/alpha` (parser)\n\
+                Alpha now parses streams.\n\
+                <!-- SECTION: `crates/beta` -->\n\
+                Beta rewritten anyway.\n"
+                .to_string())
+        })
+        .unwrap();
+
+        assert_eq!(updated, Some(vec!["`crates/alpha` (parser)".to_string()]));
+        let main = fs::read_to_string(repo.join(MAIN_CONTEXT_PATH)).unwrap();
+        assert_eq!(
+            section_raw(&main, "`crates/alpha` (parser)"),
+            "## `crates/alpha` (parser)\n\nAlpha now parses streams.\n\n"
+        );
+        for heading in ["Overview", "`crates/beta`"] {
+            assert_eq!(section_raw(&main, heading), section_raw(SECTIONED_MAIN, heading));
+        }
+        assert!(main.starts_with("# Project Context\n\nIntro paragraph.\n\n## Overview\n"));
+        fs::remove_dir_all(&repo).ok();
+    }
+
+    #[test]
+    fn unattributed_change_leaves_main_for_a_full_regen() {
+        let repo = mk_sectioned_repo("unattributed");
+        let changed = vec![
+            "crates/alpha/src/lib.rs".to_string(),
+            "Cargo.lock".to_string(),
+        ];
+
+        let updated = regenerate_main_sections(&repo, &repo, &changed, |_| {
+            panic!("agent must not run when changes can't be attributed")
+        })
+        .unwrap();
+
+        assert_eq!(updated, None);
+        let main = fs::read_to_string(repo.join(MAIN_CONTEXT_PATH)).unwrap();
+        assert_eq!(main, SECTIONED_MAIN);
+        fs::remove_dir_all(&repo).ok();
+    }
+
+    #[test]
+    fn watcher_paths_skip_hidden_directories() {
+        let root = PathBuf::from("/repo");
+        let event = |path: &str| FileChangeEvent {
+            path: root.join(path),
+            kind: crate::file_watcher::ChangeKind::Modified,
+            timestamp: std::time::SystemTime::now(),
+        };
+        let events = [
+            event("crates/alpha/src/lib.rs"),
+            event(".othala/context/MAIN.md"),
+            event("crates/alpha/src/lib.rs"),
+        ];
+        assert_eq!(context_changed_paths(&root, &events), ["crates/alpha/src/lib.rs"]);
+    }
 }
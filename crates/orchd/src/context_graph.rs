@@ -4,6 +4,7 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::{Duration, SystemTime};
 
 const CMD_OUTPUT_LINE_LIMIT: usize = 1000;
 
@@ -55,7 +56,7 @@ impl SkillRegistry {
     }
 }
 
-fn discover_skill_paths(repo_root: &Path) -> Vec<PathBuf> {
+pub(crate) fn discover_skill_paths(repo_root: &Path) -> Vec<PathBuf> {
     let mut paths = Vec::new();
 
     paths.extend(collect_skills_from_root(&repo_root.join(".othala/skills")));
@@ -209,6 +210,9 @@ pub struct ContextNode {
     pub links: Vec<PathBuf>,
     /// References to repo source files found in this node.
     pub source_refs: Vec<PathBuf>,
+    /// Newest modification time of the file and its source references, if
+    /// any of them could be read.
+    pub modified_at: Option<SystemTime>,
 }
 
 /// The fully-loaded context graph.
@@ -218,6 +222,47 @@ pub struct ContextGraph {
     pub total_chars: usize,
 }
 
+impl ContextGraph {
+    /// Drop nodes whose files haven't changed within `max_age` of `now`.
+    ///
+    /// Stale nodes reachable through links from a fresh node are kept, as
+    /// are nodes with no known modification time. Returns the number of
+    /// nodes removed.
+    pub fn prune_stale(&mut self, max_age: Duration, now: SystemTime) -> usize {
+        let is_fresh = |node: &ContextNode| {
+            node.modified_at.is_none_or(|modified| {
+                now.duration_since(modified)
+                    .map(|age| age <= max_age)
+                    .unwrap_or(true)
+            })
+        };
+
+        let by_path: HashMap<&Path, &ContextNode> = self
+            .nodes
+            .iter()
+            .map(|node| (node.path.as_path(), node))
+            .collect();
+        let mut keep: HashSet<PathBuf> = HashSet::new();
+        let mut queue: VecDeque<&ContextNode> =
+            self.nodes.iter().filter(|node| is_fresh(node)).collect();
+        while let Some(node) = queue.pop_front() {
+            if !keep.insert(node.path.clone()) {
+                continue;
+            }
+            for link in &node.links {
+                if let Some(linked) = by_path.get(link.as_path()) {
+                    queue.push_back(linked);
+                }
+            }
+        }
+
+        let before = self.nodes.len();
+        self.nodes.retain(|node| keep.contains(&node.path));
+        self.total_chars = self.nodes.iter().map(|node| node.content.len()).sum();
+        before - self.nodes.len()
+    }
+}
+
 /// Configuration for context loading.
 #[derive(Debug, Clone)]
 pub struct ContextLoadConfig {
@@ -331,11 +376,17 @@ pub fn load_context_graph(repo_root: &Path, config: &ContextLoadConfig) -> Optio
             queue.push_back((link.clone(), next_depth));
         }
 
+        let modified_at = std::iter::once(&rel_path)
+            .chain(&source_refs)
+            .filter_map(|path| std::fs::metadata(repo_root.join(path)).ok())
+            .filter_map(|metadata| metadata.modified().ok())
+            .max();
         nodes.push(ContextNode {
             path: rel_path,
             content,
             links,
             source_refs,
+            modified_at,
         });
     }
 
@@ -973,6 +1024,89 @@ mod tests {
         fs::remove_dir_all(&tmp).ok();
     }
 
+    #[test]
+    fn prune_stale_drops_stale_leaves_but_keeps_nodes_referenced_by_fresh_ones() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let day = Duration::from_secs(24 * 60 * 60);
+        let node = |name: &str, age: Option<Duration>, links: &[&str]| ContextNode {
+            path: PathBuf::from(format!(".othala/context/{name}.md")),
+            content: format!("# {name}\n"),
+            links: links
+                .iter()
+                .map(|link| PathBuf::from(format!(".othala/context/{link}.md")))
+                .collect(),
+            source_refs: vec![],
+            modified_at: age.map(|age| now - age),
+        };
+        let mut graph = ContextGraph {
+            nodes: vec![
+                node("MAIN", Some(day), &["api", "old-notes"]),
+                node("api", Some(day * 30), &["schema"]),
+                node("schema", Some(day * 60), &[]),
+                node("old-notes", Some(day * 30), &[]),
+                node("orphan", Some(day * 30), &["old-notes"]),
+                node("unknown", None, &[]),
+            ],
+            total_chars: 0,
+        };
+
+        let pruned = graph.prune_stale(day * 7, now);
+
+        assert_eq!(pruned, 1);
+        let kept: Vec<_> = graph
+            .nodes
+            .iter()
+            .map(|node| node.path.file_stem().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(kept, vec!["MAIN", "api", "schema", "old-notes", "unknown"]);
+        assert_eq!(
+            graph.total_chars,
+            graph
+                .nodes
+                .iter()
+                .map(|node| node.content.len())
+                .sum::<usize>()
+        );
+        assert_eq!(graph.prune_stale(day * 7, now), 0);
+    }
+
+    #[test]
+    fn prune_stale_removes_every_node_once_nothing_is_fresh() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let stale = ContextNode {
+            path: PathBuf::from(".othala/context/MAIN.md"),
+            content: "# Main\n".to_string(),
+            links: vec![PathBuf::from(".othala/context/MAIN.md")],
+            source_refs: vec![],
+            modified_at: Some(now - Duration::from_secs(3600)),
+        };
+        let mut graph = ContextGraph {
+            nodes: vec![stale],
+            total_chars: 7,
+        };
+
+        assert_eq!(graph.prune_stale(Duration::from_secs(60), now), 1);
+        assert!(graph.nodes.is_empty());
+        assert_eq!(graph.total_chars, 0);
+    }
+
+    #[test]
+    fn loaded_nodes_record_the_newest_source_modification() {
+        let tmp = unique_tmp_dir("othala-context-mtime");
+        setup_context_dir(&tmp);
+        let graph = load_context_graph(&tmp, &ContextLoadConfig::default()).expect("graph");
+
+        assert!(graph.nodes.iter().all(|node| node.modified_at.is_some()));
+        assert_eq!(
+            graph
+                .clone()
+                .prune_stale(Duration::from_secs(3600), SystemTime::now()),
+            0
+        );
+
+        fs::remove_dir_all(&tmp).ok();
+    }
+
     #[test]
     fn render_context_produces_markdown() {
         let graph = ContextGraph {
@@ -981,6 +1115,7 @@ mod tests {
                 content: "# Hello\n".to_string(),
                 links: vec![],
                 source_refs: vec![],
+                modified_at: None,
             }],
             total_chars: 9,
         };
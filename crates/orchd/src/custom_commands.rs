@@ -3,6 +3,11 @@
 //! Commands are stored as .md files in:
 //! - ~/.config/othala/commands/ (user commands, prefixed "user:")
 //! - <PROJECT>/.othala/commands/ (project commands, prefixed "project:")
+//!
+//! A command may start with a `---` front-matter block declaring its
+//! `description` and an `args` schema (`name`, `required`, `default`,
+//! `description` per entry); `render_command` fills defaults and rejects
+//! missing required arguments.
 
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -16,6 +21,9 @@ pub struct CustomCommand {
     pub description: String,
     pub template: String,
     pub arguments: Vec<String>,
+    /// Arguments declared in front-matter; empty when the command has none.
+    #[serde(default)]
+    pub arg_schema: Vec<ArgumentSpec>,
     pub source_path: PathBuf,
 }
 
@@ -53,7 +61,7 @@ pub struct PromptResult {
 
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 pub enum CommandError {
-    #[error("missing required argument: {0}")]
+    #[error("missing required arg: {0}")]
     MissingArgument(String),
     #[error("invalid template: {0}")]
     InvalidTemplate(String),
@@ -402,9 +410,116 @@ pub fn discover_all_commands(project_root: &Path) -> Vec<CustomCommand> {
     commands
 }
 
-/// Render a command template with argument values
+/// Render a command template with argument values.
+///
+/// Schema defaults fill omitted arguments; every required argument still
+/// missing afterwards is reported in a single error.
 pub fn render_command(command: &CustomCommand, args: &HashMap<String, String>) -> Result<String, String> {
-    render_template(&command.template, args).map_err(|err| err.to_string())
+    let mut effective = args.clone();
+    let mut missing = Vec::new();
+    for spec in &command.arg_schema {
+        if effective.contains_key(&spec.name) {
+            continue;
+        }
+        match &spec.default_value {
+            Some(default) => {
+                effective.insert(spec.name.clone(), default.clone());
+            }
+            None if spec.required => missing.push(spec.name.clone()),
+            None => {}
+        }
+    }
+
+    let template = canonicalize_command_template(&command.template);
+    for spec in parse_argument_specs(&template) {
+        if spec.required && !effective.contains_key(&spec.name) && !missing.contains(&spec.name) {
+            missing.push(spec.name);
+        }
+    }
+    if !missing.is_empty() {
+        return Err(CommandError::MissingArgument(missing.join(", ")).to_string());
+    }
+
+    render_template(&template, &effective).map_err(|err| err.to_string())
+}
+
+/// Provided argument names that neither the schema nor the template use.
+pub fn unknown_arguments(command: &CustomCommand, args: &HashMap<String, String>) -> Vec<String> {
+    let mut unknown: Vec<String> = args
+        .keys()
+        .filter(|name| {
+            !command.arguments.contains(name) && !command.arg_schema.iter().any(|spec| &spec.name == *name)
+        })
+        .cloned()
+        .collect();
+    unknown.sort();
+    unknown
+}
+
+/// Description and argument schema from a command's front-matter block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandFrontmatter {
+    pub description: Option<String>,
+    pub args: Vec<ArgumentSpec>,
+}
+
+/// Split a leading `---` front-matter block from the rest of the file.
+/// Returns `(frontmatter, body)`, or `None` when there is no closed block.
+pub fn split_command_frontmatter(content: &str) -> Option<(&str, &str)> {
+    let rest = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))?;
+    let mut offset = 0usize;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return Some((&rest[..offset], &rest[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Parse the front-matter subset commands use. `args` may be a flow list
+/// (`args: [{name: PR, required: true}]`) or a block list of `- name: PR`
+/// entries with indented fields.
+pub fn parse_command_frontmatter(frontmatter: &str) -> CommandFrontmatter {
+    let mut meta = CommandFrontmatter::default();
+    let mut in_args = false;
+    let mut current: Option<Vec<(String, String)>> = None;
+
+    for line in frontmatter.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let indented = line.starts_with(' ') || line.starts_with('\t');
+        let trimmed = line.trim();
+
+        if in_args && (indented || trimmed.starts_with('-')) {
+            if let Some(item) = trimmed.strip_prefix('-') {
+                meta.args.extend(current.take().and_then(|fields| argument_spec_from_fields(&fields)));
+                current = Some(Vec::new());
+                push_frontmatter_field(current.as_mut(), item);
+            } else {
+                push_frontmatter_field(current.as_mut(), trimmed);
+            }
+            continue;
+        }
+
+        meta.args.extend(current.take().and_then(|fields| argument_spec_from_fields(&fields)));
+        in_args = false;
+        let Some((key, value)) = trimmed.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "description" => meta.description = Some(trim_frontmatter_value(value)),
+            "args" if value.is_empty() => in_args = true,
+            "args" => meta.args.extend(parse_flow_args(value)),
+            _ => {}
+        }
+    }
+    meta.args.extend(current.take().and_then(|fields| argument_spec_from_fields(&fields)));
+    meta
 }
 
 /// Format commands as a display table
@@ -499,33 +614,95 @@ fn visit_markdown(base_dir: &Path, current_dir: &Path, prefix: CommandPrefix, ou
             Err(_) => continue,
         };
         let name = command_name_from_relative_path(relative);
-        let (description_raw, template) = parse_command_description(&content);
+        let (frontmatter, body) = match split_command_frontmatter(&content) {
+            Some((raw, body)) => (parse_command_frontmatter(raw), body),
+            None => (CommandFrontmatter::default(), content.as_str()),
+        };
+        let (description_raw, template) = parse_command_description(body);
         let template = canonicalize_command_template(&template);
         if validate_command_template(&template).is_err() {
             continue;
         }
 
-        if let Some(rewritten) = rewrite_command_file_content(&content, &template) {
-            let _ = fs::write(&path, rewritten);
+        if let Some(rewritten) = rewrite_command_file_content(body, &template) {
+            let header = &content[..content.len() - body.len()];
+            let _ = fs::write(&path, format!("{header}{rewritten}"));
         }
 
-        let description = if description_raw.is_empty() {
-            name.clone()
-        } else {
-            description_raw
+        let description = match frontmatter.description {
+            Some(description) if !description.is_empty() => description,
+            _ if !description_raw.is_empty() => description_raw,
+            _ => name.clone(),
         };
 
+        let mut arguments: Vec<String> = frontmatter.args.iter().map(|spec| spec.name.clone()).collect();
+        for argument in extract_arguments(&template) {
+            if !arguments.contains(&argument) {
+                arguments.push(argument);
+            }
+        }
+
         out.push(CustomCommand {
             name,
             prefix,
             description,
-            arguments: extract_arguments(&template),
+            arguments,
+            arg_schema: frontmatter.args,
             template,
             source_path: path,
         });
     }
 }
 
+fn parse_flow_args(value: &str) -> Vec<ArgumentSpec> {
+    let inner = value.trim().trim_start_matches('[').trim_end_matches(']');
+    inner
+        .split('}')
+        .filter_map(|entry| {
+            let entry = entry.trim().trim_start_matches(',').trim().strip_prefix('{')?;
+            let mut fields = Vec::new();
+            for pair in entry.split(',') {
+                push_frontmatter_field(Some(&mut fields), pair);
+            }
+            argument_spec_from_fields(&fields)
+        })
+        .collect()
+}
+
+fn push_frontmatter_field(fields: Option<&mut Vec<(String, String)>>, raw: &str) {
+    let (Some(fields), Some((key, value))) = (fields, raw.split_once(':')) else {
+        return;
+    };
+    fields.push((key.trim().to_string(), trim_frontmatter_value(value)));
+}
+
+fn argument_spec_from_fields(fields: &[(String, String)]) -> Option<ArgumentSpec> {
+    let field = |name: &str| {
+        fields
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.clone())
+    };
+    let name = field("name").filter(|name| !name.is_empty())?;
+    Some(ArgumentSpec {
+        name,
+        description: field("description"),
+        default_value: field("default"),
+        required: field("required").is_some_and(|value| value == "true"),
+    })
+}
+
+fn trim_frontmatter_value(value: &str) -> String {
+    let trimmed = value.trim();
+    if trimmed.len() >= 2
+        && ((trimmed.starts_with('"') && trimmed.ends_with('"'))
+            || (trimmed.starts_with('\'') && trimmed.ends_with('\'')))
+    {
+        return trimmed[1..trimmed.len() - 1].to_string();
+    }
+    trimmed.to_string()
+}
+
 fn rewrite_command_file_content(original_content: &str, canonical_template: &str) -> Option<String> {
     let (description_raw, _) = parse_command_description(original_content);
     let rewritten = if description_raw.is_empty() {
@@ -681,6 +858,7 @@ mod tests {
             description: "Review".to_string(),
             template: "Review $PR in $ENV".to_string(),
             arguments: vec!["PR".to_string(), "ENV".to_string()],
+            arg_schema: Vec::new(),
             source_path: PathBuf::from("review.md"),
         };
         let args = HashMap::from([
@@ -699,11 +877,12 @@ mod tests {
             description: "Review".to_string(),
             template: "Review $PR in $ENV".to_string(),
             arguments: vec!["PR".to_string(), "ENV".to_string()],
+            arg_schema: Vec::new(),
             source_path: PathBuf::from("review.md"),
         };
         let args = HashMap::from([("PR".to_string(), "123".to_string())]);
         let err = render_command(&command, &args).expect_err("expected missing arg");
-        assert_eq!(err, "missing required argument: ENV");
+        assert_eq!(err, "missing required arg: ENV");
     }
 
     #[test]
@@ -714,6 +893,7 @@ mod tests {
             description: "Review changes".to_string(),
             template: "Review $PR".to_string(),
             arguments: vec!["PR".to_string()],
+            arg_schema: Vec::new(),
             source_path: PathBuf::from("review.md"),
         }];
         let table = display_commands_table(&commands);
@@ -788,4 +968,68 @@ mod tests {
         assert!(commands.is_empty());
         let _ = fs::remove_dir_all(&temp_dir);
     }
+
+    fn schema_command(template: &str, frontmatter: &str) -> CustomCommand {
+        let temp_dir = make_temp_dir();
+        fs::write(temp_dir.join("deploy.md"), format!("---\n{frontmatter}---\n{template}"))
+            .expect("write command");
+        let mut commands = discover_commands_from_dir(&temp_dir, CommandPrefix::Project);
+        let _ = fs::remove_dir_all(&temp_dir);
+        assert_eq!(commands.len(), 1);
+        commands.remove(0)
+    }
+
+    #[test]
+    fn frontmatter_schema_supplies_defaults() {
+        let command = schema_command(
+            "Deploy $SERVICE to $ENV",
+            "description: Deploy a service\nargs:\n  - name: SERVICE\n    required: true\n  - name: ENV\n    default: staging\n",
+        );
+        assert_eq!(command.description, "Deploy a service");
+        assert_eq!(command.arguments, vec!["SERVICE", "ENV"]);
+        assert_eq!(command.arg_schema[1].default_value.as_deref(), Some("staging"));
+
+        let args = HashMap::from([("SERVICE".to_string(), "api".to_string())]);
+        assert_eq!(render_command(&command, &args).expect("render"), "Deploy api to staging");
+
+        let args = HashMap::from([
+            ("SERVICE".to_string(), "api".to_string()),
+            ("ENV".to_string(), "prod".to_string()),
+        ]);
+        assert_eq!(render_command(&command, &args).expect("render"), "Deploy api to prod");
+    }
+
+    #[test]
+    fn missing_required_schema_args_are_all_listed() {
+        let command = schema_command(
+            "Release $VERSION",
+            "args: [{name: VERSION, required: true}, {name: TICKET, required: true}]\n",
+        );
+        let err = render_command(&command, &HashMap::new()).expect_err("missing args");
+        assert_eq!(err, "missing required arg: VERSION, TICKET");
+    }
+
+    #[test]
+    fn unknown_args_are_ignored_and_reported() {
+        let command = schema_command("Deploy $ENV", "args:\n  - name: ENV\n    default: staging\n");
+        let args = HashMap::from([("EVN".to_string(), "prod".to_string())]);
+
+        assert_eq!(render_command(&command, &args).expect("render"), "Deploy staging");
+        assert_eq!(unknown_arguments(&command, &args), vec!["EVN"]);
+    }
+
+    #[test]
+    fn legacy_rewrite_preserves_frontmatter() {
+        let temp_dir = make_temp_dir();
+        let path = temp_dir.join("warm.md");
+        fs::write(&path, "---\nargs: [{name: MODEL, default: claude}]\n---\nWarm ${MODEL}").expect("write");
+
+        discover_commands_from_dir(&temp_dir, CommandPrefix::Project);
+
+        assert_eq!(
+            fs::read_to_string(&path).expect("read"),
+            "---\nargs: [{name: MODEL, default: claude}]\n---\nWarm $MODEL"
+        );
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
 }
@@ -0,0 +1,171 @@
+//! Daemon control channel — lets other processes pause, resume, reload or
+//! drain the running daemon.
+//!
+//! Requests are flag files under `.othala/control/`, next to the daemon pid
+//! file from [`crate::chat_control`]. `paused` stays in place until a resume
+//! removes it; `reload-config` is consumed by the daemon on its next tick;
+//! `drain` stays until the daemon exits so status reads keep reporting it.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::chat_control::{control_dir, running_daemon_pid};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DaemonControlAction {
+    Pause,
+    Resume,
+    ReloadConfig,
+    Drain,
+}
+
+impl DaemonControlAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pause => "pause",
+            Self::Resume => "resume",
+            Self::ReloadConfig => "reload_config",
+            Self::Drain => "drain",
+        }
+    }
+}
+
+/// What a control client can observe about the daemon.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaemonControlStatus {
+    pub running: bool,
+    pub pid: Option<u32>,
+    pub paused: bool,
+    pub reload_pending: bool,
+    pub draining: bool,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DaemonControlError {
+    #[error("no daemon is running for this repository")]
+    NotRunning,
+    #[error("failed to update daemon control files: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+pub fn paused_flag_path(repo_root: &Path) -> PathBuf {
+    control_dir(repo_root).join("paused")
+}
+
+pub fn reload_flag_path(repo_root: &Path) -> PathBuf {
+    control_dir(repo_root).join("reload-config")
+}
+
+pub fn drain_flag_path(repo_root: &Path) -> PathBuf {
+    control_dir(repo_root).join("drain")
+}
+
+/// Current control state as seen from the filesystem.
+pub fn daemon_control_status(repo_root: &Path) -> DaemonControlStatus {
+    let pid = running_daemon_pid(repo_root);
+    DaemonControlStatus {
+        running: pid.is_some(),
+        pid,
+        paused: paused_flag_path(repo_root).exists(),
+        reload_pending: reload_flag_path(repo_root).exists(),
+        draining: drain_flag_path(repo_root).exists(),
+    }
+}
+
+/// Ask the running daemon to apply `action`, returning the resulting status.
+pub fn request_daemon_action(
+    repo_root: &Path,
+    action: DaemonControlAction,
+) -> Result<DaemonControlStatus, DaemonControlError> {
+    if running_daemon_pid(repo_root).is_none() {
+        return Err(DaemonControlError::NotRunning);
+    }
+    fs::create_dir_all(control_dir(repo_root))?;
+    match action {
+        DaemonControlAction::Pause => fs::write(paused_flag_path(repo_root), "")?,
+        DaemonControlAction::Resume => remove_if_exists(&paused_flag_path(repo_root))?,
+        DaemonControlAction::ReloadConfig => fs::write(reload_flag_path(repo_root), "")?,
+        DaemonControlAction::Drain => fs::write(drain_flag_path(repo_root), "")?,
+    }
+    Ok(daemon_control_status(repo_root))
+}
+
+/// Whether spawning new agents is paused.
+pub fn is_paused(repo_root: &Path) -> bool {
+    paused_flag_path(repo_root).exists()
+}
+
+/// Consume a pending reload request.
+pub fn take_reload_request(repo_root: &Path) -> bool {
+    fs::remove_file(reload_flag_path(repo_root)).is_ok()
+}
+
+/// Whether a drain has been requested.
+pub fn drain_requested(repo_root: &Path) -> bool {
+    drain_flag_path(repo_root).exists()
+}
+
+/// Drop one-shot requests once the daemon exits. A pause survives restarts so
+/// a restarted daemon does not silently resume work.
+pub fn clear_transient_requests(repo_root: &Path) {
+    let _ = fs::remove_file(reload_flag_path(repo_root));
+    let _ = fs::remove_file(drain_flag_path(repo_root));
+}
+
+fn remove_if_exists(path: &Path) -> std::io::Result<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat_control::{clear_daemon_pid, write_daemon_pid};
+    use chrono::Utc;
+
+    fn temp_repo() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "othala-daemon-control-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&dir).expect("create temp repo");
+        dir
+    }
+
+    #[test]
+    fn actions_require_a_running_daemon() {
+        let repo = temp_repo();
+        let err = request_daemon_action(&repo, DaemonControlAction::Pause).expect_err("no daemon");
+        assert!(matches!(err, DaemonControlError::NotRunning));
+        assert!(!is_paused(&repo));
+    }
+
+    #[test]
+    fn pause_reload_and_drain_round_trip_through_flag_files() {
+        let repo = temp_repo();
+        write_daemon_pid(&repo).expect("write pid");
+
+        let status = request_daemon_action(&repo, DaemonControlAction::Pause).expect("pause");
+        assert!(status.running && status.paused);
+        let status = request_daemon_action(&repo, DaemonControlAction::Resume).expect("resume");
+        assert!(!status.paused);
+
+        let status =
+            request_daemon_action(&repo, DaemonControlAction::ReloadConfig).expect("reload");
+        assert!(status.reload_pending);
+        assert!(take_reload_request(&repo));
+        assert!(!take_reload_request(&repo));
+
+        let status = request_daemon_action(&repo, DaemonControlAction::Drain).expect("drain");
+        assert!(status.draining);
+        assert!(drain_requested(&repo));
+        clear_transient_requests(&repo);
+        assert!(!drain_requested(&repo));
+
+        clear_daemon_pid(&repo);
+    }
+}
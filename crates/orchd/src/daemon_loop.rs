@@ -4,42 +4,78 @@
 //! returns actions for the caller to execute.
 
 use chrono::{DateTime, Datelike, Duration, Utc};
-use orch_core::config::{load_org_config, BudgetConfig, OrgConfig};
-use orch_core::events::{Event, EventKind};
+use orch_agents::ChunkSink;
+use orch_core::config::{
+    load_org_config, BudgetConfig, MergePollConfig, OrgConfig, RetryGuardConfig, VerifyLimits,
+    VerifyLimitsConfig,
+};
+use orch_core::events::{Event, EventKind, FailureCause};
 use orch_core::state::TaskState;
 use orch_core::types::{EventId, ModelKind, SubmitMode, Task, TaskId};
-use orch_git::{discover_repo, has_uncommitted_changes, GitCli};
-use orch_graphite::GraphiteClient;
+use orch_git::{
+    capture_diff_snapshot, capture_status_snapshot, discover_repo, has_uncommitted_changes,
+    merge_base, DiffSnapshot, GitCli,
+};
+use orch_graphite::{audit_log_path, scan_conflicted_files, GraphiteClient, PrStatus, SubmittedPr};
 use orch_notify::{notification_for_event, NotificationDispatcher};
+use orch_verify::{
+    commands_for_tier, parse_verify_failures, run_bounded_with, summarize_failures,
+    VerifyFailureDetail, VerifyTier,
+};
 
 use crate::agent_dispatch::{AgentDispatcher, AgentRole, RepoContext as DispatchRepoContext};
 use crate::agent_log;
+use crate::chat_control;
+use crate::merge_poll::{self, MergePoller};
+use crate::merge_queue;
 use crate::context_gen::{
-    build_context_gen_prompt, context_is_current, poll_context_gen, should_regenerate,
-    spawn_context_gen, ContextGenConfig, ContextGenState, ContextGenStatus,
+    build_context_gen_prompt, build_main_sections_prompt, context_changed_paths,
+    context_is_current, plan_main_sections_regen, poll_context_gen, should_regenerate,
+    spawn_context_gen, write_context_manifest, ContextGenConfig, ContextGenState,
+    ContextGenStatus,
+    CONTEXT_GEN_LOG_ID,
 };
 use crate::context_gen_telemetry::{ContextGenMetrics, estimate_tokens};
 use crate::delta_report::DeltaReporter;
-use crate::context_graph::{load_context_graph, ContextLoadConfig};
-use crate::prompt_builder::{build_rich_prompt, PromptConfig, PromptRole, RetryContext};
+use crate::file_watcher::{FileWatcher, WatcherConfig};
+use crate::context_graph::{
+    discover_skill_paths, load_context_graph, ContextLoadConfig, SkillRegistry,
+};
+use crate::ignore::{load_ignore_rules, IgnoreRules};
+use crate::permissions::PermissionPolicy;
+use crate::prompt_builder::{
+    build_rich_prompt, PromptConfig, PromptRole, RestackConflictContext, RetryContext,
+};
 use crate::qa_agent::{
     build_qa_failure_context, build_qa_prompt, load_baseline, load_latest_result,
-    load_task_spec as load_qa_task_spec, poll_qa_agent, save_qa_result, spawn_qa_agent, QAResult,
-    QAState, QAStatus, QAType,
+    load_task_spec as load_qa_task_spec, parse_qa_spec, poll_qa_agent, save_qa_result,
+    spawn_qa_agent, QAResult, QASpec, QAState, QAStatus, QAType,
 };
+use crate::qa_spec_gen::{generate_qa_spec, load_generated_qa_spec};
+use crate::rate_limiter::RateLimiter;
 use crate::retry::{evaluate_retry, pick_next_model_with_health, ModelHealthTracker};
-use crate::stack_pipeline::{next_action, PipelineAction, PipelineStage, PipelineState};
-use crate::supervisor::{AgentOutcome, AgentSupervisor};
+use crate::retry_guard::{self, RetryGuardVerdict, RunPatchMeta};
+use crate::stack_pipeline::{
+    get_worktree_head_sha, next_action, run_step, PipelineAction, PipelineStage, PipelineState,
+    StepOutcome, StepProcesses, StepTimeouts,
+};
+use crate::questions;
+use crate::supervisor::{AgentOutcome, AgentSupervisor, OutputChunk};
 use crate::orchestration_metrics::OrchestrationMetricsStore;
-use crate::problem_classifier::ProblemClassifier;
-use crate::sisyphus_recovery::{SisyphusRecoveryLoop, RecoveryDecision};
+use crate::problem_classifier::{suggested_action, ProblemClassifier, SuggestedAction};
+use crate::sisyphus_recovery::{
+    conflicted_files, run_conflict_agent, RecoveryDecision, SisyphusRecoveryLoop,
+};
 use crate::test_spec::load_test_spec;
+use crate::verify_failure::{self, VerifyFailureBundle};
 use crate::OrchdService;
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
+use std::process::Command;
+use std::sync::mpsc::{self, TryRecvError};
+use std::sync::Arc;
 
 /// Configuration for the daemon loop.
 #[derive(Debug, Clone)]
@@ -68,6 +104,39 @@ pub struct DaemonConfig {
     pub dry_run: bool,
     pub agent_timeout_secs: u64,
     pub drain_timeout_secs: u64,
+    /// Hook command run after a task transitions to `Merged`.
+    pub post_merge_hook: Option<String>,
+    /// Thresholds for flagging retries that discard previously passing work.
+    pub retry_guard: RetryGuardConfig,
+    /// Per-stage limits for verify/restack/submit pipeline steps.
+    pub step_timeouts: StepTimeouts,
+    /// Open agent questions older than this are escalated.
+    pub question_stale_secs: u64,
+    /// Fallback merge detection for tasks left in `AwaitingMerge`.
+    pub merge_poll: MergePollConfig,
+    /// Let the task's agent resolve restack conflicts before asking a human.
+    pub auto_resolve_conflicts: bool,
+    /// Minimum self-reported agent confidence (percent) for the daemon to mark
+    /// a task ready on its own. Tasks below it, or without a confidence
+    /// marker, wait for a manual approve. `None` disables the check.
+    pub auto_approve_min_confidence: Option<u8>,
+    /// Checkpoint pipelines under `.othala/pipelines` and resume them after
+    /// a restart.
+    pub pipeline_checkpoints: bool,
+    /// Cap on agents started per tick so a burst of ready tasks ramps up
+    /// over several ticks instead of all at once. Treated as at least 1.
+    pub max_spawns_per_tick: usize,
+    /// Time and output limits for verify commands, from the repo's
+    /// `[verify]` section.
+    pub verify_limits: VerifyLimitsConfig,
+    /// Skip the fast verify tier run after each agent epoch.
+    pub skip_fast_verify: bool,
+    /// The repo's `verify.fast_commands`; empty uses per-language defaults
+    /// for the changed files.
+    pub fast_verify_commands: Vec<String>,
+    /// Generate a QA spec for a finished task that has none before
+    /// validation.
+    pub generate_qa_specs: bool,
 }
 
 /// Mutable state carried across daemon ticks.
@@ -76,15 +145,33 @@ pub struct DaemonState {
     pub pipelines: HashMap<String, PipelineState>,
     /// Context generation state.
     pub context_gen: ContextGenState,
+    /// Repo watcher feeding `context_changed_files`, started on first use.
+    pub context_watcher: Option<FileWatcher>,
+    /// Files changed since the last context regeneration was started.
+    pub context_changed_files: BTreeSet<String>,
     /// Per-task QA agent state (keyed by task_id).
     pub qa_agents: HashMap<String, QAState>,
     pub verify_cache: HashMap<String, String>,
+    /// Fast verify runs in flight, keyed by task_id. Their tasks stay in
+    /// `Chatting` without an agent until the result arrives.
+    pub fast_verify: HashMap<String, FastVerifyRun>,
     pub model_health: ModelHealthTracker,
     pub restack_retries: HashMap<String, RestackRetryState>,
+    /// Poll times and staleness flags for `AwaitingMerge` tasks.
+    pub merge_poller: MergePoller,
     pub notification_dispatcher: Option<NotificationDispatcher>,
     pub config_last_modified: Option<std::time::SystemTime>,
+    /// Modification times of the ignore files and skills, once first read.
+    pub aux_config_stamps: Option<AuxConfigStamps>,
+    pub ignore_rules: IgnoreRules,
+    pub skill_registry: SkillRegistry,
+    pub permission_policy: PermissionPolicy,
     pub shutdown_requested: bool,
     pub shutdown_deadline: Option<std::time::Instant>,
+    /// Set by an operator pause; running agents finish but no new ones spawn.
+    pub paused: bool,
+    /// Per-model limit on agent spawns, keyed by `model:<name>`.
+    pub spawn_rate_limiter: RateLimiter,
     pub budget_used_today: u64,
     pub budget_used_month: u64,
     pub budget_last_reset_day: Option<u32>,
@@ -115,6 +202,13 @@ pub struct DaemonState {
 
 const RESTACK_RETRY_MAX_RETRIES: u32 = 3;
 const RESTACK_RETRY_INITIAL_BACKOFF_SECS: u64 = 5;
+/// Time and output limits for a task state hook such as `post_merge`.
+const TASK_STATE_HOOK_LIMITS: VerifyLimits = VerifyLimits {
+    timeout_secs: 60,
+    max_output_bytes: 64 * 1024,
+};
+/// Limit for one agent attempt at resolving a restack conflict.
+const RESTACK_CONFLICT_AGENT_TIMEOUT_SECS: u64 = 600;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum GraphiteRecoveryPlaybook {
@@ -123,6 +217,16 @@ enum GraphiteRecoveryPlaybook {
     Restack,
 }
 
+/// A fast verify tier running on a worker thread after an agent epoch.
+#[derive(Debug)]
+pub struct FastVerifyRun {
+    /// Model to retry with when the tier fails.
+    pub model: ModelKind,
+    processes: Arc<StepProcesses>,
+    /// Retry reason when a command failed; `None` when the tier passed.
+    result_rx: mpsc::Receiver<Option<String>>,
+}
+
 #[derive(Debug, Clone)]
 pub struct RestackRetryState {
     pub attempts: u32,
@@ -168,14 +272,24 @@ impl DaemonState {
         Self {
             pipelines: HashMap::new(),
             context_gen: ContextGenState::new(),
+            context_watcher: None,
+            context_changed_files: BTreeSet::new(),
             qa_agents: HashMap::new(),
             verify_cache: HashMap::new(),
+            fast_verify: HashMap::new(),
             model_health: ModelHealthTracker::new(),
             restack_retries: HashMap::new(),
+            merge_poller: MergePoller::default(),
             notification_dispatcher: None,
             config_last_modified: None,
+            aux_config_stamps: None,
+            ignore_rules: IgnoreRules::default(),
+            skill_registry: SkillRegistry::default(),
+            permission_policy: PermissionPolicy::default_policy(),
             shutdown_requested: false,
             shutdown_deadline: None,
+            paused: false,
+            spawn_rate_limiter: RateLimiter::default(),
             budget_used_today: 0,
             budget_used_month: 0,
             budget_last_reset_day: None,
@@ -222,6 +336,63 @@ pub fn check_config_reload(config_path: &Path, daemon_state: &mut DaemonState) -
     }
 }
 
+/// Modification times of the config files that live outside `config.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuxConfigStamps {
+    /// `.othalaignore` and `.othala/ignore`.
+    pub ignore: Vec<Option<std::time::SystemTime>>,
+    /// Every discovered `SKILL.md`, sorted by path.
+    pub skills: Vec<(PathBuf, Option<std::time::SystemTime>)>,
+}
+
+impl AuxConfigStamps {
+    pub fn read(repo_root: &Path) -> Self {
+        let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        let mut skill_paths = discover_skill_paths(repo_root);
+        skill_paths.sort();
+        Self {
+            ignore: [".othalaignore", ".othala/ignore"]
+                .iter()
+                .map(|name| modified(&repo_root.join(name)))
+                .collect(),
+            skills: skill_paths
+                .into_iter()
+                .map(|path| {
+                    let mtime = modified(&path);
+                    (path, mtime)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Reload ignore rules and skills whose files changed since the last call,
+/// returning the subsystems reloaded. The first call loads both and reports
+/// nothing.
+pub fn check_auxiliary_reload(repo_root: &Path, daemon_state: &mut DaemonState) -> Vec<String> {
+    let stamps = AuxConfigStamps::read(repo_root);
+    let previous = daemon_state.aux_config_stamps.replace(stamps.clone());
+    let mut changes = Vec::new();
+
+    if previous.as_ref().map(|p| &p.ignore) != Some(&stamps.ignore) {
+        daemon_state.ignore_rules = load_ignore_rules(repo_root);
+        if previous.is_some() {
+            changes.push("ignore_rules".to_string());
+        }
+    }
+    if previous.as_ref().map(|p| &p.skills) != Some(&stamps.skills) {
+        daemon_state.skill_registry = SkillRegistry::discover(repo_root);
+        if previous.is_some() {
+            changes.push("skills".to_string());
+        }
+    }
+
+    if !changes.is_empty() {
+        eprintln!("[daemon] Reloaded {}", changes.join(", "));
+    }
+    changes
+}
+
 fn schedule_restack_retry(
     daemon_state: &mut DaemonState,
     task_id: &TaskId,
@@ -337,7 +508,7 @@ fn schedule_restack_playbook_retry_or_stop(
             pipeline.fail(exhausted_reason.to_string());
         }
         daemon_state.restack_retries.remove(&task_id.0);
-        daemon_state.pipelines.remove(&task_id.0);
+        remove_pipeline(service, daemon_state, task_id);
         stop_task_with_failure_reason(
             service,
             daemon_state.notification_dispatcher.as_ref(),
@@ -373,7 +544,7 @@ fn handle_restack_graphite_playbook(
                 pipeline.fail(final_msg.clone());
             }
             daemon_state.restack_retries.remove(&task_id.0);
-            daemon_state.pipelines.remove(&task_id.0);
+            remove_pipeline(service, daemon_state, task_id);
             stop_task_with_failure_reason(
                 service,
                 daemon_state.notification_dispatcher.as_ref(),
@@ -392,7 +563,7 @@ fn handle_restack_graphite_playbook(
                     pipeline.fail(final_msg.clone());
                 }
                 daemon_state.restack_retries.remove(&task_id.0);
-                daemon_state.pipelines.remove(&task_id.0);
+                remove_pipeline(service, daemon_state, task_id);
                 stop_task_with_failure_reason(
                     service,
                     daemon_state.notification_dispatcher.as_ref(),
@@ -417,6 +588,9 @@ fn handle_restack_graphite_playbook(
             }
         }
         GraphiteRecoveryPlaybook::Restack => {
+            if error.is_restack_conflict() {
+                let _ = service.set_restack_conflict(task_id, true);
+            }
             let _ = record_event_with_notification(
                 service,
                 daemon_state.notification_dispatcher.as_ref(),
@@ -429,11 +603,20 @@ fn handle_restack_graphite_playbook(
                 },
             );
 
+            let files = scan_conflicted_files(graphite.repo_root());
             let _ = graphite.abort_rebase();
-            let exhausted_msg = format!(
+            let mut exhausted_msg = format!(
                 "restack recovery retries exhausted for {} while restacking onto `{parent_branch}` (max retries: {})",
                 task_id.0, RESTACK_RETRY_MAX_RETRIES
             );
+            if !files.is_empty() {
+                let file_list = files
+                    .iter()
+                    .map(|file| file.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                exhausted_msg.push_str(&format!("; conflicted files: {file_list}"));
+            }
             schedule_restack_playbook_retry_or_stop(
                 service,
                 daemon_state,
@@ -449,6 +632,223 @@ fn handle_restack_graphite_playbook(
     true
 }
 
+/// Let the task's agent resolve a conflicted restack, then `gt continue` and
+/// verify. Gives up with a needs-human stop listing the conflicted files once
+/// the Sisyphus attempts are spent.
+#[allow(clippy::too_many_arguments)]
+fn auto_resolve_restack_conflict(
+    service: &OrchdService,
+    config: &DaemonConfig,
+    daemon_state: &mut DaemonState,
+    task_id: &TaskId,
+    worktree_path: &Path,
+    parent_branch: &str,
+    graphite: &GraphiteClient,
+    now: DateTime<Utc>,
+    event_seed: i64,
+) {
+    let Ok(Some(task)) = service.task(task_id) else {
+        return;
+    };
+    let _ = service.set_restack_conflict(task_id, true);
+    let _ = record_event_with_notification(
+        service,
+        daemon_state.notification_dispatcher.as_ref(),
+        &Event {
+            id: EventId(format!("E-RESTACK-CONFLICT-{}-{event_seed}", task_id.0)),
+            task_id: Some(task_id.clone()),
+            repo_id: Some(task.repo_id.clone()),
+            at: now,
+            kind: EventKind::RestackConflict,
+        },
+    );
+
+    let model = task
+        .preferred_model
+        .or_else(|| config.enabled_models.first().copied())
+        .unwrap_or(ModelKind::Claude);
+    let verify_cmd = config
+        .verify_command
+        .as_deref()
+        .unwrap_or("cargo check && cargo test --workspace");
+    let mut files = conflicted_files(worktree_path);
+    let mut seen_files: BTreeSet<String> = files.iter().cloned().collect();
+    let mut last_failure: Option<String> = None;
+
+    while let Some(attempt) = daemon_state
+        .sisyphus_recovery
+        .start_conflict_attempt(&task_id.0, &files)
+    {
+        let _ = record_event_with_notification(
+            service,
+            daemon_state.notification_dispatcher.as_ref(),
+            &Event {
+                id: EventId(format!(
+                    "E-RESTACK-RESOLVE-{}-{event_seed}-{attempt}",
+                    task_id.0
+                )),
+                task_id: Some(task_id.clone()),
+                repo_id: Some(task.repo_id.clone()),
+                at: now,
+                kind: EventKind::RestackStarted,
+            },
+        );
+
+        let prompt_config = PromptConfig {
+            task_id: task.id.clone(),
+            task_title: task.title.clone(),
+            task_description: task.description.clone(),
+            role: PromptRole::ResolveConflict,
+            context: None,
+            test_spec: None,
+            retry: last_failure.as_ref().map(|failure| RetryContext {
+                attempt,
+                max_retries: daemon_state
+                    .sisyphus_recovery
+                    .get_state(&task_id.0)
+                    .map(|state| state.max_sisyphus_attempts)
+                    .unwrap_or(attempt),
+                previous_failure: failure.clone(),
+                previous_model: model,
+            }),
+            verify_command: None,
+            qa_failure_context: None,
+            restack_conflict: Some(RestackConflictContext {
+                parent_branch: parent_branch.to_string(),
+                files: files.clone(),
+            }),
+            repo_root: Some(config.repo_root.clone()),
+            model: Some(model),
+        };
+        let prompt = build_rich_prompt(&prompt_config, &config.template_dir);
+
+        let result = resolve_restack_conflict_attempt(
+            graphite,
+            worktree_path,
+            task_id,
+            model,
+            &prompt,
+            !files.is_empty(),
+            verify_cmd,
+            &config.nix_shell,
+            config.verify_limits.for_tier(VERIFY_TIER_BRANCH),
+            agent_log::spawn_log_writer(&config.repo_root, task_id),
+        );
+        match result {
+            Ok(()) => {
+                daemon_state
+                    .sisyphus_recovery
+                    .mark_success(&task_id.0, files.clone());
+                daemon_state.sisyphus_recovery.cleanup(&task_id.0);
+                let _ = service.complete_restack(
+                    task_id,
+                    EventId(format!("E-RESTACK-DONE-{}-{event_seed}", task_id.0)),
+                    now,
+                );
+                if let Some(pipeline) = daemon_state.pipelines.get_mut(&task_id.0) {
+                    pipeline.advance();
+                }
+                daemon_state.restack_retries.remove(&task_id.0);
+                eprintln!(
+                    "[daemon] Resolved restack conflict for {} on attempt {attempt}",
+                    task_id.0
+                );
+                return;
+            }
+            Err(failure) => {
+                eprintln!(
+                    "[daemon] Restack conflict attempt {attempt} failed for {}: {failure}",
+                    task_id.0
+                );
+                daemon_state.sisyphus_recovery.mark_failure(&task_id.0);
+                files = conflicted_files(worktree_path);
+                seen_files.extend(files.iter().cloned());
+                last_failure = Some(failure);
+            }
+        }
+    }
+
+    daemon_state.sisyphus_recovery.cleanup(&task_id.0);
+    let _ = graphite.abort_rebase();
+    let file_list = if seen_files.is_empty() {
+        "unknown files".to_string()
+    } else {
+        seen_files.into_iter().collect::<Vec<_>>().join(", ")
+    };
+    let mut reason = format!(
+        "restack onto `{parent_branch}` still conflicts after agent resolution attempts; conflicted files: {file_list}"
+    );
+    if let Some(failure) = last_failure {
+        reason.push_str(&format!("; last failure: {failure}"));
+    }
+    if let Some(pipeline) = daemon_state.pipelines.get_mut(&task_id.0) {
+        pipeline.fail(reason.clone());
+    }
+    remove_pipeline(service, daemon_state, task_id);
+    daemon_state.restack_retries.remove(&task_id.0);
+    stop_for_human_review(
+        service,
+        daemon_state.notification_dispatcher.as_ref(),
+        task_id,
+        &reason,
+        now,
+    );
+}
+
+/// One agent attempt at a conflicted restack: resolve the conflicts, continue
+/// the rebase when one is still in progress, then verify.
+#[allow(clippy::too_many_arguments)]
+fn resolve_restack_conflict_attempt(
+    graphite: &GraphiteClient,
+    worktree_path: &Path,
+    task_id: &TaskId,
+    model: ModelKind,
+    prompt: &str,
+    rebase_in_progress: bool,
+    verify_cmd: &str,
+    nix_shell: &str,
+    verify_limits: VerifyLimits,
+    log: ChunkSink,
+) -> Result<(), String> {
+    run_conflict_agent(
+        worktree_path,
+        task_id,
+        model,
+        prompt,
+        RESTACK_CONFLICT_AGENT_TIMEOUT_SECS,
+        Some(log),
+    )?;
+
+    if rebase_in_progress {
+        let remaining = conflicted_files(worktree_path);
+        if !remaining.is_empty() {
+            return Err(format!("conflicts remain in {}", remaining.join(", ")));
+        }
+        graphite
+            .begin_conflict_resolution()
+            .and_then(|()| graphite.continue_conflict_resolution())
+            .map_err(|e| format!("continuing restack failed: {e}"))?;
+    }
+
+    run_verify_command(worktree_path, verify_cmd, nix_shell, verify_limits)
+        .map_err(|failure| failure.message)
+}
+
+/// Graphite client for a task worktree. Invocations go to the repo's audit
+/// log, and under `--dry-run` mutating commands are only logged.
+fn graphite_client(config: &DaemonConfig, worktree_path: &Path) -> GraphiteClient {
+    GraphiteClient::new(worktree_path)
+        .with_audit_log(audit_log_path(&config.repo_root))
+        .with_dry_run(config.dry_run)
+}
+
+/// `graphite` with its `gt` invocations owned by a pipeline step.
+fn step_graphite(graphite: &GraphiteClient, processes: &Arc<StepProcesses>) -> GraphiteClient {
+    let mut graphite = graphite.clone();
+    graphite.cli = graphite.cli.with_runner(Arc::clone(processes) as Arc<_>);
+    graphite
+}
+
 fn stop_task_with_failure_reason(
     service: &OrchdService,
     notification_dispatcher: Option<&NotificationDispatcher>,
@@ -468,6 +868,7 @@ fn stop_task_with_failure_reason(
         kind: EventKind::TaskFailed {
             reason: reason.to_string(),
             is_final: true,
+            cause: Some(FailureCause::classify(reason)),
         },
     };
     let _ = record_event_with_notification(service, notification_dispatcher, &event);
@@ -515,6 +916,119 @@ fn record_event_with_notification(
     Ok(())
 }
 
+/// Record the step events `pipeline` queued since they were last drained.
+/// Every event is attempted; the first failure is returned.
+pub fn record_pipeline_events(
+    service: &OrchdService,
+    pipeline: &mut PipelineState,
+) -> Result<(), crate::service::ServiceError> {
+    let events = pipeline.take_events();
+    if events.is_empty() {
+        return Ok(());
+    }
+    let task_id = &pipeline.task_id;
+    let repo_id = service.task(task_id).ok().flatten().map(|task| task.repo_id);
+    let at = Utc::now();
+    let seed = at.timestamp_nanos_opt().unwrap_or_default();
+    let mut result = Ok(());
+    for (index, kind) in events.into_iter().enumerate() {
+        let event = Event {
+            id: EventId(format!("E-PIPELINE-{}-{seed}-{index:02}", task_id.0)),
+            task_id: Some(task_id.clone()),
+            repo_id: repo_id.clone(),
+            at,
+            kind,
+        };
+        if let Err(err) = service.record_event(&event) {
+            if result.is_ok() {
+                result = Err(err);
+            }
+        }
+    }
+    result
+}
+
+fn log_pipeline_events(service: &OrchdService, pipeline: &mut PipelineState) {
+    if let Err(err) = record_pipeline_events(service, pipeline) {
+        eprintln!(
+            "[daemon] Failed to record pipeline event for {}: {err}",
+            pipeline.task_id.0
+        );
+    }
+}
+
+fn flush_pipeline_events(service: &OrchdService, daemon_state: &mut DaemonState) {
+    for pipeline in daemon_state.pipelines.values_mut() {
+        log_pipeline_events(service, pipeline);
+    }
+}
+
+/// Drop the task's pipeline, recording whatever step events it still holds.
+fn remove_pipeline(service: &OrchdService, daemon_state: &mut DaemonState, task_id: &TaskId) {
+    if let Some(mut pipeline) = daemon_state.pipelines.remove(&task_id.0) {
+        log_pipeline_events(service, &mut pipeline);
+        pipeline.clear_checkpoint();
+    }
+}
+
+/// Store `[needs_human]` questions from an output chunk as open questions on
+/// the task and notify about each new one.
+fn capture_agent_questions(
+    service: &OrchdService,
+    notification_dispatcher: Option<&NotificationDispatcher>,
+    chunk: &OutputChunk,
+    now: DateTime<Utc>,
+) {
+    match service.capture_questions(&chunk.task_id, &chunk.lines, now) {
+        Ok(events) => {
+            for event in &events {
+                dispatch_notification(notification_dispatcher, event);
+            }
+        }
+        Err(err) => eprintln!(
+            "[daemon] Failed to record agent question for {}: {err}",
+            chunk.task_id.0
+        ),
+    }
+}
+
+/// Notify once for each open question older than `question_stale_secs`.
+fn escalate_stale_questions(
+    service: &OrchdService,
+    notification_dispatcher: Option<&NotificationDispatcher>,
+    config: &DaemonConfig,
+    now: DateTime<Utc>,
+) {
+    let stale_after = std::time::Duration::from_secs(config.question_stale_secs);
+    let stale = match questions::stale_questions(&service.store, now, stale_after) {
+        Ok(stale) => stale,
+        Err(err) => {
+            eprintln!("[daemon] Failed to load open questions: {err}");
+            return;
+        }
+    };
+    for question in stale {
+        let repo_id = service.task(&question.task_id).ok().flatten().map(|t| t.repo_id);
+        let event = questions::question_stale_event(&question, repo_id, now);
+        if let Err(err) = record_event_with_notification(service, notification_dispatcher, &event) {
+            eprintln!(
+                "[daemon] Failed to escalate question {}: {err}",
+                question.question_id
+            );
+            continue;
+        }
+        if let Err(err) = service
+            .store
+            .mark_question_escalated(&question.question_id, now)
+        {
+            eprintln!(
+                "[daemon] Failed to mark question {} escalated: {err}",
+                question.question_id
+            );
+        }
+    }
+}
+
 fn load_budget_config_for_tick(repo_root: &Path) -> BudgetConfig {
     let config_path = repo_root.join(".othala/config.toml");
     load_org_config(config_path)
@@ -566,6 +1080,140 @@ fn track_output_chars(state: &mut DaemonState, task_id: &TaskId, lines: &[String
         .or_insert(0) += chars;
 }
 
+/// Start a chat session for `task` with `message` as its first turn,
+/// recovering the worktree first. Codex has no interactive mode and runs
+/// one-shot.
+pub fn spawn_chat_session(
+    supervisor: &mut AgentSupervisor,
+    repo_root: &Path,
+    task: &Task,
+    message: &str,
+) -> anyhow::Result<ModelKind> {
+    crate::ensure_worktree_exists(repo_root, task)?;
+    let model = task.preferred_model.unwrap_or(ModelKind::Claude);
+    if model == ModelKind::Codex {
+        supervisor.spawn_agent(
+            &task.id,
+            &task.repo_id,
+            &task.worktree_path,
+            message,
+            Some(model),
+            std::time::Duration::from_secs(1_800),
+        )?;
+    } else {
+        supervisor.spawn_interactive(
+            &task.id,
+            &task.repo_id,
+            &task.worktree_path,
+            message,
+            Some(model),
+        )?;
+    }
+    Ok(model)
+}
+
+fn deliver_chat_message(
+    service: &OrchdService,
+    supervisor: &mut AgentSupervisor,
+    repo_root: &Path,
+    queued: &chat_control::QueuedChatMessage,
+) -> chat_control::ChatDelivery {
+    use chat_control::ChatDelivery;
+
+    let task_id = &queued.task_id;
+    if supervisor.has_session(task_id) || supervisor.is_suspended(task_id) {
+        return match supervisor.send_input(task_id, &queued.message) {
+            Ok(()) => ChatDelivery::Delivered,
+            Err(e) => ChatDelivery::Failed {
+                reason: e.to_string(),
+            },
+        };
+    }
+    if !queued.spawn {
+        return ChatDelivery::Failed {
+            reason: format!(
+                "no live session for {} (pass --spawn to start one)",
+                task_id.0
+            ),
+        };
+    }
+    let task = match service.task(task_id) {
+        Ok(Some(task)) => task,
+        Ok(None) => {
+            return ChatDelivery::Failed {
+                reason: format!("task not found: {}", task_id.0),
+            }
+        }
+        Err(e) => {
+            return ChatDelivery::Failed {
+                reason: e.to_string(),
+            }
+        }
+    };
+    match spawn_chat_session(supervisor, repo_root, &task, &queued.message) {
+        Ok(model) => ChatDelivery::Spawned { model },
+        Err(e) => ChatDelivery::Failed {
+            reason: e.to_string(),
+        },
+    }
+}
+
+/// Forward messages from the chat control inbox to live agent sessions,
+/// starting one when the sender asked for it. Questions are only marked
+/// answered once the message reached a session, and a receipt tells the
+/// sender what happened.
+fn deliver_queued_chat_messages(
+    service: &OrchdService,
+    supervisor: &mut AgentSupervisor,
+    repo_root: &Path,
+) -> Vec<DaemonAction> {
+    let mut actions = Vec::new();
+    for queued in chat_control::take_queued_chat_messages(repo_root) {
+        let delivery = deliver_chat_message(service, supervisor, repo_root, &queued);
+        let mut answered = Vec::new();
+        let message = match &delivery {
+            chat_control::ChatDelivery::Failed { reason } => {
+                format!(
+                    "[chat] could not deliver message to {}: {reason}",
+                    queued.task_id.0
+                )
+            }
+            _ => {
+                let _ = chat_control::append_chat_log(
+                    repo_root,
+                    &queued.task_id,
+                    &[format!("> {}", queued.message)],
+                );
+                match service.answer_questions(&queued.task_id, &queued.message, Utc::now()) {
+                    Ok(questions) => {
+                        answered = questions
+                            .iter()
+                            .map(|q| format!("{}: {}", q.question_id, q.question))
+                            .collect();
+                    }
+                    Err(e) => actions.push(DaemonAction::Log {
+                        message: format!(
+                            "[chat] failed to resolve questions for {}: {e}",
+                            queued.task_id.0
+                        ),
+                    }),
+                }
+                format!("[chat] delivered message to {}", queued.task_id.0)
+            }
+        };
+        if !queued.id.is_empty() {
+            let receipt = chat_control::ChatReceipt { delivery, answered };
+            if let Err(e) = chat_control::write_chat_receipt(repo_root, &queued.id, &receipt) {
+                actions.push(DaemonAction::Log {
+                    message: format!("[chat] failed to write receipt {}: {e}", queued.id),
+                });
+            }
+        }
+        actions.push(DaemonAction::Log { message });
+    }
+    actions
+}
+
 /// Actions that the daemon loop produces for the caller to handle.
 #[derive(Debug)]
 pub enum DaemonAction {
@@ -603,6 +1251,15 @@ pub enum DaemonAction {
     ExecutePipeline {
         action: PipelineAction,
     },
+    /// Start the fast verify tier on a worker thread for a task whose agent
+    /// just finished an epoch.
+    RunFastVerify {
+        task_id: TaskId,
+        model: ModelKind,
+        worktree_path: PathBuf,
+        changed_files: usize,
+        commands: Vec<String>,
+    },
     /// Trigger background context regeneration.
     TriggerContextRegen,
     ContextRegenCompleted {
@@ -675,6 +1332,12 @@ pub fn daemon_tick(
                     chunk.task_id.0
                 );
             }
+            capture_agent_questions(
+                service,
+                daemon_state.notification_dispatcher.as_ref(),
+                chunk,
+                now,
+            );
 
             for line in &chunk.lines {
                 actions.push(DaemonAction::Log {
@@ -720,27 +1383,49 @@ pub fn daemon_tick(
         return actions;
     }
 
+    // --- Phase 0: Forward chat messages queued by `othala chat send` ---
+    if !config.dry_run {
+        actions.extend(deliver_queued_chat_messages(
+            service,
+            supervisor,
+            &config.repo_root,
+        ));
+    }
+
     // --- Phase 1: Spawn agents for Chatting tasks without sessions ---
     let budget_config = load_budget_config_for_tick(&config.repo_root);
-    if let Ok(chatting) = service.list_tasks_by_state(TaskState::Chatting) {
-        for task in &chatting {
-            if !supervisor.has_session(&task.id) {
-                if !check_budget(daemon_state, &budget_config) {
-                    actions.push(DaemonAction::EmitEvent {
-                        task_id: Some(task.id.clone()),
-                        repo_id: Some(task.repo_id.clone()),
-                        kind: EventKind::BudgetExceeded,
-                    });
-                    continue;
-                }
-                // Use next-gen multi-agent dispatch if enabled
-                let action = if daemon_state.use_next_gen {
-                    build_spawn_action_next_gen(task, config, daemon_state)
-                } else {
-                    build_spawn_action(task, config)
-                };
-                if let Some(action) = action {
-                    actions.push(action);
+    if !daemon_state.paused {
+        if let Ok(mut chatting) = service.list_tasks_by_state(TaskState::Chatting) {
+            // Highest priority first; the sort is stable, so ties keep the
+            // store's order.
+            chatting.sort_by_key(|task| std::cmp::Reverse(task.priority));
+            let max_spawns = config.max_spawns_per_tick.max(1);
+            let mut spawned = 0;
+            for task in &chatting {
+                if !supervisor.has_session(&task.id)
+                    && !daemon_state.fast_verify.contains_key(&task.id.0)
+                {
+                    if spawned >= max_spawns {
+                        break;
+                    }
+                    if !check_budget(daemon_state, &budget_config) {
+                        actions.push(DaemonAction::EmitEvent {
+                            task_id: Some(task.id.clone()),
+                            repo_id: Some(task.repo_id.clone()),
+                            kind: EventKind::BudgetExceeded,
+                        });
+                        continue;
+                    }
+                    // Use next-gen multi-agent dispatch if enabled
+                    let action = if daemon_state.use_next_gen {
+                        build_spawn_action_next_gen(task, config, daemon_state)
+                    } else {
+                        build_spawn_action(task, config)
+                    };
+                    if let Some(action) = action {
+                        actions.push(action);
+                        spawned += 1;
+                    }
                 }
             }
         }
@@ -751,7 +1436,7 @@ pub fn daemon_tick(
     // For tasks about to be spawned, check if a baseline QA result exists for
     // the task's branch. If no baseline exists and we have a QA spec, spawn a
     // baseline QA agent first.
-    if !config.skip_qa {
+    if !config.skip_qa && !daemon_state.paused {
         if let Ok(chatting) = service.list_tasks_by_state(TaskState::Chatting) {
             for task in &chatting {
                 let default_branch = format!("task/{}", task.id.0);
@@ -798,6 +1483,12 @@ pub fn daemon_tick(
                 chunk.task_id.0
             );
         }
+        capture_agent_questions(
+            service,
+            daemon_state.notification_dispatcher.as_ref(),
+            chunk,
+            now,
+        );
 
         for line in &chunk.lines {
             actions.push(DaemonAction::Log {
@@ -820,6 +1511,17 @@ pub fn daemon_tick(
     }
     daemon_state.notification_dispatcher = notification_dispatcher;
 
+    // --- Phase 2.3: Collect fast verify results ---
+    actions.extend(collect_fast_verify_results(service, config, daemon_state));
+
+    // --- Phase 2.4: Escalate agent questions nobody has answered ---
+    escalate_stale_questions(
+        service,
+        daemon_state.notification_dispatcher.as_ref(),
+        config,
+        now,
+    );
+
     // --- Phase 2.5: Poll QA agents ---
     //
     // Check running QA agents for completion. On completion:
@@ -847,7 +1549,7 @@ pub fn daemon_tick(
 
                     if qa_type == QAType::Validation {
                         // Validation passed — mark ready.
-                        actions.push(DaemonAction::MarkReady { task_id });
+                        actions.push(auto_approve_action(service, config, task_id));
                     }
                 } else {
                     actions.push(DaemonAction::QAFailed {
@@ -893,23 +1595,49 @@ pub fn daemon_tick(
         for task in &ready_tasks {
             if !daemon_state.pipelines.contains_key(&task.id.0) {
                 // Start a new pipeline for this task.
-                let parent_branch = find_parent_branch(service, task);
-                let pipeline = PipelineState::new(
-                    task.id.clone(),
-                    task.branch_name
-                        .clone()
-                        .unwrap_or_else(|| format!("task/{}", task.id.0)),
-                    task.worktree_path.clone(),
-                    resolve_submit_mode_for_task(task, submit_mode_default),
-                    parent_branch,
-                );
+                let resumed = config
+                    .pipeline_checkpoints
+                    .then(|| PipelineState::resume(&config.repo_root, &task.id))
+                    .flatten();
+                let pipeline = match resumed {
+                    Some(pipeline) => {
+                        eprintln!(
+                            "[daemon] Resuming pipeline for {} at {}",
+                            task.id.0, pipeline.stage
+                        );
+                        pipeline
+                    }
+                    None => {
+                        let parent_branch = find_parent_branch(service, task);
+                        let pipeline = PipelineState::new(
+                            task.id.clone(),
+                            task.branch_name
+                                .clone()
+                                .unwrap_or_else(|| format!("task/{}", task.id.0)),
+                            task.worktree_path.clone(),
+                            resolve_submit_mode_for_task(task, submit_mode_default),
+                            parent_branch,
+                        );
+                        if config.pipeline_checkpoints {
+                            pipeline.with_checkpoints(&config.repo_root)
+                        } else {
+                            pipeline
+                        }
+                    }
+                };
                 daemon_state.pipelines.insert(task.id.0.clone(), pipeline);
             }
         }
     }
 
-    // Drive each pipeline forward.
-    let pipeline_keys: Vec<String> = daemon_state.pipelines.keys().cloned().collect();
+    // Drive each pipeline forward, merge-queue entries first in queue order.
+    let queue = merge_queue::snapshot(&service.store).unwrap_or_default();
+    let mut pipeline_keys: Vec<String> = daemon_state.pipelines.keys().cloned().collect();
+    pipeline_keys.sort_by(|a, b| {
+        merge_queue::queue_rank(&queue, &TaskId(a.clone()))
+            .cmp(&merge_queue::queue_rank(&queue, &TaskId(b.clone())))
+            .then_with(|| a.cmp(b))
+    });
     for key in pipeline_keys {
         if let Some(pipeline) = daemon_state.pipelines.get(&key) {
             if !pipeline.is_terminal() {
@@ -927,12 +1655,15 @@ pub fn daemon_tick(
     }
 
     // Clean up terminal pipelines.
+    flush_pipeline_events(service, daemon_state);
     daemon_state.pipelines.retain(|_, p| !p.is_terminal());
     daemon_state
         .restack_retries
         .retain(|task_id, _| daemon_state.pipelines.contains_key(task_id));
 
-    if let Ok(awaiting) = service.list_tasks_by_state(TaskState::AwaitingMerge) {
+    if let Ok(mut awaiting) = service.list_tasks_by_state(TaskState::AwaitingMerge) {
+        // Stable sort keeps the store's ordering for tasks outside the queue.
+        awaiting.sort_by_key(|task| merge_queue::queue_rank(&queue, &task.id));
         let auto_merge_mode = repo_mode_is_merge(&config.repo_root);
 
         for task in &awaiting {
@@ -965,6 +1696,14 @@ pub fn daemon_tick(
                 }
             }
         }
+
+        actions.extend(poll_awaiting_merge(
+            daemon_state,
+            config,
+            &awaiting,
+            &actions,
+            now,
+        ));
     }
 
     // Poll any running context gen process and record telemetry.
@@ -974,6 +1713,9 @@ pub fn daemon_tick(
             message: format!("[context-gen] Updated {} context files", paths.len()),
         });
         actions.push(DaemonAction::ContextRegenCompleted { success: true });
+        if let Err(e) = write_context_manifest(&config.repo_root, &config.template_dir) {
+            eprintln!("[context-gen] Failed to write context manifest: {e}");
+        }
         // Record success metric — wall-time approximated from generation_start.
         // Precise timing would require a start timestamp; use 0.0 as placeholder
         // since poll_context_gen doesn't expose duration.
@@ -985,8 +1727,21 @@ pub fn daemon_tick(
         daemon_state.context_gen_metrics.record_failure(0.0);
     }
 
-    // Record cache check (stale vs current) for observability.
-    let is_stale = !context_is_current(&config.repo_root);
+    // Track changed files so regeneration can target the affected sections.
+    if !config.skip_context_regen {
+        let watcher = daemon_state.context_watcher.get_or_insert_with(|| {
+            let mut watcher = FileWatcher::new(config.repo_root.clone(), WatcherConfig::default());
+            watcher.initial_scan();
+            watcher
+        });
+        let events = watcher.poll_debounced();
+        daemon_state
+            .context_changed_files
+            .extend(context_changed_paths(&config.repo_root, &events));
+    }
+
+    // Record cache check (stale vs current) for observability.
+    let is_stale = !context_is_current(&config.repo_root);
     daemon_state.context_gen_metrics.record_cache_check(!is_stale);
 
     // Check if we should trigger a regen based on transitions or stale hash.
@@ -1262,12 +2017,7 @@ fn build_spawn_action_next_gen(
         None
     };
 
-    let role = match task.task_type {
-        orch_core::types::TaskType::Implement => PromptRole::Implement,
-        orch_core::types::TaskType::TestSpecWrite => PromptRole::TestSpecWrite,
-        orch_core::types::TaskType::TestValidate => PromptRole::Review,
-        orch_core::types::TaskType::Orchestrate => PromptRole::Implement,
-    };
+    let role = PromptRole::for_task_type(task.task_type);
 
     let qa_failure_context = task
         .last_failure_reason
@@ -1283,13 +2033,16 @@ fn build_spawn_action_next_gen(
     let prompt_config = PromptConfig {
         task_id: task.id.clone(),
         task_title: task.title.clone(),
+        task_description: task.description.clone(),
         role,
         context,
         test_spec: test_spec_content,
         retry,
         verify_command: config.verify_command.clone(),
         qa_failure_context,
+        restack_conflict: None,
         repo_root: Some(config.repo_root.clone()),
+        model: Some(model),
     };
 
     let mut prompt = build_rich_prompt(&prompt_config, &config.template_dir);
@@ -1332,12 +2085,7 @@ fn build_spawn_action(task: &Task, config: &DaemonConfig) -> Option<DaemonAction
         None
     };
 
-    let role = match task.task_type {
-        orch_core::types::TaskType::Implement => PromptRole::Implement,
-        orch_core::types::TaskType::TestSpecWrite => PromptRole::TestSpecWrite,
-        orch_core::types::TaskType::TestValidate => PromptRole::Review,
-        orch_core::types::TaskType::Orchestrate => PromptRole::Implement,
-    };
+    let role = PromptRole::for_task_type(task.task_type);
 
     // If the last failure reason looks like structured QA output, inject it
     // through the dedicated qa_failure_context field so the prompt builder
@@ -1351,13 +2099,16 @@ fn build_spawn_action(task: &Task, config: &DaemonConfig) -> Option<DaemonAction
     let prompt_config = PromptConfig {
         task_id: task.id.clone(),
         task_title: task.title.clone(),
+        task_description: task.description.clone(),
         role,
         context,
         test_spec: test_spec_content,
         retry,
         verify_command: config.verify_command.clone(),
         qa_failure_context,
+        restack_conflict: None,
         repo_root: Some(config.repo_root.clone()),
+        model: Some(model),
     };
 
     let prompt = build_rich_prompt(&prompt_config, &config.template_dir);
@@ -1370,6 +2121,136 @@ fn build_spawn_action(task: &Task, config: &DaemonConfig) -> Option<DaemonAction
     })
 }
 
+/// `MarkReady` for a task whose work the daemon considers done, unless
+/// `auto_approve_min_confidence` is set and the latest run reported less
+/// confidence than that (or none at all).
+fn auto_approve_action(
+    service: &OrchdService,
+    config: &DaemonConfig,
+    task_id: TaskId,
+) -> DaemonAction {
+    let Some(min) = config.auto_approve_min_confidence else {
+        return DaemonAction::MarkReady { task_id };
+    };
+    let confidence = service
+        .store
+        .list_runs_for_task(&task_id)
+        .ok()
+        .and_then(|runs| runs.last().and_then(|run| run.confidence));
+    let reason = match confidence {
+        Some(confidence) if confidence >= min => return DaemonAction::MarkReady { task_id },
+        Some(confidence) => format!(
+            "Agent confidence {confidence}% is below the {min}% auto-approve minimum; approve manually"
+        ),
+        None => format!(
+            "Agent reported no confidence; auto-approve requires {min}%, approve manually"
+        ),
+    };
+    DaemonAction::RecordNeedsHuman { task_id, reason }
+}
+
+/// Next step for a task whose agent finished: a validation QA run when the
+/// task has a spec to check, otherwise the auto-approve decision.
+///
+/// A task without a baseline or task spec gets one generated from its title
+/// and diff first when `generate_qa_specs` is set.
+fn validation_action(
+    service: &OrchdService,
+    config: &DaemonConfig,
+    task_id: &TaskId,
+) -> DaemonAction {
+    if !config.skip_qa
+        && (resolve_qa_spec(&config.repo_root, task_id, QAType::Validation).is_some()
+            || generate_task_qa_spec(service, config, task_id))
+    {
+        return DaemonAction::SpawnQA {
+            task_id: task_id.clone(),
+            qa_type: QAType::Validation,
+        };
+    }
+    auto_approve_action(service, config, task_id.clone())
+}
+
+/// Generate and save a QA spec for a task from its diff against trunk.
+/// Returns whether one was saved.
+fn generate_task_qa_spec(service: &OrchdService, config: &DaemonConfig, task_id: &TaskId) -> bool {
+    if !config.generate_qa_specs || config.dry_run {
+        return false;
+    }
+    let Ok(Some(task)) = service.task(task_id) else {
+        return false;
+    };
+    let Some(diff) = task_diff_snapshot(&task.worktree_path).filter(|d| !d.files.is_empty()) else {
+        return false;
+    };
+    let model = config
+        .enabled_models
+        .first()
+        .copied()
+        .unwrap_or(ModelKind::Claude);
+    match generate_qa_spec(&config.repo_root, &task, &diff, model) {
+        Ok(spec) => {
+            eprintln!(
+                "[daemon] Generated QA spec for {} ({} checks)",
+                task_id.0,
+                spec.tests.len()
+            );
+            true
+        }
+        Err(e) => {
+            eprintln!("[daemon] QA spec generation failed for {}: {e}", task_id.0);
+            false
+        }
+    }
+}
+
+/// The spec a QA run checks and the label its `QAStarted` event carries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct QARunSpec {
+    spec: QASpec,
+    task_spec: Option<String>,
+    label: String,
+}
+
+/// Spec for a QA run of `task_id`.
+///
+/// The baseline is checked first, with the task's hand-written spec as its
+/// acceptance tests. Validation runs without a hand-written spec fall back
+/// to the one generated under `.othala/qa/<task_id>.yaml`, labelled
+/// `generated`.
+fn resolve_qa_spec(repo_root: &Path, task_id: &TaskId, qa_type: QAType) -> Option<QARunSpec> {
+    let task_spec = load_qa_task_spec(repo_root, task_id);
+    let generated = if qa_type == QAType::Validation && task_spec.is_none() {
+        load_generated_qa_spec(repo_root, task_id)
+    } else {
+        None
+    };
+    let generated_label = "generated".to_string();
+    match (load_baseline(repo_root), generated) {
+        (Some(baseline), Some(generated)) => Some(QARunSpec {
+            spec: baseline,
+            task_spec: Some(generated.raw),
+            label: generated_label,
+        }),
+        (Some(baseline), None) => Some(QARunSpec {
+            spec: baseline,
+            task_spec,
+            label: qa_type.to_string(),
+        }),
+        (None, Some(generated)) => Some(QARunSpec {
+            spec: generated,
+            task_spec: None,
+            label: generated_label,
+        }),
+        (None, None) if qa_type == QAType::Validation => task_spec.map(|spec| QARunSpec {
+            spec: parse_qa_spec(&spec),
+            task_spec: None,
+            label: qa_type.to_string(),
+        }),
+        (None, None) => None,
+    }
+}
+
 /// Handle an agent completion — decide whether to mark ready, retry, or fail.
 fn handle_agent_completion(
     service: &OrchdService,
@@ -1423,6 +2304,17 @@ fn handle_agent_completion(
     } else {
         "failed"
     };
+    if let Some(confidence) = outcome.confidence {
+        if let Err(e) = service
+            .store
+            .set_open_run_confidence(&outcome.task_id, confidence)
+        {
+            eprintln!(
+                "[daemon] Failed to persist run confidence for {}: {}",
+                outcome.task_id.0, e
+            );
+        }
+    }
     if let Err(e) = service.store.finish_open_runs_for_task(
         &outcome.task_id,
         now,
@@ -1438,18 +2330,13 @@ fn handle_agent_completion(
 
     if outcome.patch_ready || outcome.success {
         daemon_state.model_health.record_success(outcome.model);
-        // If a QA baseline spec exists and QA is enabled, spawn a validation
-        // QA run instead of immediately marking ready.
-        if !config.skip_qa && load_baseline(&config.repo_root).is_some() {
-            actions.push(DaemonAction::SpawnQA {
-                task_id: outcome.task_id.clone(),
-                qa_type: QAType::Validation,
-            });
-        } else {
-            actions.push(DaemonAction::MarkReady {
-                task_id: outcome.task_id.clone(),
-            });
+        if let Some(fast_verify) = fast_verify_action(service, config, outcome) {
+            actions.push(fast_verify);
+            return actions;
         }
+        // With a QA spec for the task, spawn a validation QA run instead of
+        // immediately marking ready.
+        actions.push(validation_action(service, config, &outcome.task_id));
         return actions;
     }
 
@@ -1472,48 +2359,61 @@ fn handle_agent_completion(
                     outcome.task_id.0
                 );
                 daemon_state.model_health.record_success(outcome.model);
-                if !config.skip_qa && load_baseline(&config.repo_root).is_some() {
-                    actions.push(DaemonAction::SpawnQA {
-                        task_id: outcome.task_id.clone(),
-                        qa_type: QAType::Validation,
-                    });
-                } else {
-                    actions.push(DaemonAction::MarkReady {
-                        task_id: outcome.task_id.clone(),
-                    });
+                if let Some(fast_verify) = fast_verify_action(service, config, outcome) {
+                    actions.push(fast_verify);
+                    return actions;
                 }
+                actions.push(validation_action(service, config, &outcome.task_id));
                 return actions;
             }
         }
     }
 
-    // Agent failed — evaluate retry.
+    // Agent failed — classify the failure and evaluate retry.
     daemon_state
         .model_health
         .record_failure_at(outcome.model, now);
 
+    let failure_output = recent_agent_output(&config.repo_root, &outcome.task_id);
+    let failure_class = daemon_state.problem_classifier.classify(&failure_output).class;
+    let suggestion = suggested_action(failure_class);
+    if suggestion == SuggestedAction::EscalateToHuman {
+        actions.push(DaemonAction::RecordNeedsHuman {
+            task_id: outcome.task_id.clone(),
+            reason: format!("Agent failed with a {failure_class} error that needs a human"),
+        });
+        return actions;
+    }
+
     if let Ok(Some(task)) = service.task(&outcome.task_id) {
         let decision = evaluate_retry(&task, outcome, &config.enabled_models);
 
         if decision.should_retry {
-            let next_model = pick_next_model_with_health(
-                &task,
-                outcome.model,
-                &config.enabled_models,
-                &daemon_state.model_health,
-                now,
-            )
-            .or(decision.next_model);
+            let next_model = match suggestion {
+                SuggestedAction::FallbackModel => pick_next_model_with_health(
+                    &task,
+                    outcome.model,
+                    &config.enabled_models,
+                    &daemon_state.model_health,
+                    now,
+                )
+                .or(decision.next_model),
+                _ => Some(outcome.model),
+            };
+            if suggestion == SuggestedAction::RegenerateContext && !config.skip_context_regen {
+                actions.push(DaemonAction::TriggerContextRegen);
+            }
 
             if let Some(next_model) = next_model {
                 actions.push(DaemonAction::ScheduleRetry {
                     task_id: outcome.task_id.clone(),
                     next_model,
                     reason: format!(
-                        "retrying (attempt {}/{}) with {}",
+                        "retrying (attempt {}/{}) with {} after {} failure",
                         task.retry_count + 1,
                         task.max_retries,
-                        next_model.as_str()
+                        next_model.as_str(),
+                        failure_class
                     ),
                 });
             } else {
@@ -1538,6 +2438,214 @@ fn handle_agent_completion(
     actions
 }
 
+/// Queue the fast verify tier over the files the epoch changed, so the task
+/// stays in `Chatting` until the checks pass. `None` when the tier is skipped
+/// or has nothing to check.
+fn fast_verify_action(
+    service: &OrchdService,
+    config: &DaemonConfig,
+    outcome: &AgentOutcome,
+) -> Option<DaemonAction> {
+    if config.skip_fast_verify {
+        return None;
+    }
+    let task = service.task(&outcome.task_id).ok().flatten()?;
+    let changed = epoch_changed_files(&task.worktree_path);
+    let commands = commands_for_tier(
+        VerifyTier::Fast,
+        &task.worktree_path,
+        &config.fast_verify_commands,
+        &changed,
+    );
+    if commands.is_empty() {
+        return None;
+    }
+    Some(DaemonAction::RunFastVerify {
+        task_id: outcome.task_id.clone(),
+        model: outcome.model,
+        worktree_path: task.worktree_path,
+        changed_files: changed.len(),
+        commands,
+    })
+}
+
+/// Start a fast verify run on a worker thread; its result is picked up by
+/// [`collect_fast_verify_results`] on a later tick.
+fn start_fast_verify(
+    config: &DaemonConfig,
+    daemon_state: &mut DaemonState,
+    task_id: &TaskId,
+    model: ModelKind,
+    worktree_path: &Path,
+    changed_files: usize,
+    commands: &[String],
+) {
+    let processes = Arc::new(StepProcesses::default());
+    let (tx, rx) = mpsc::channel();
+    let worker = {
+        let processes = Arc::clone(&processes);
+        let (repo_root, task_id, worktree_path, commands, nix_shell) = (
+            config.repo_root.clone(),
+            task_id.clone(),
+            worktree_path.to_path_buf(),
+            commands.to_vec(),
+            config.nix_shell.clone(),
+        );
+        let limits = config.verify_limits.for_tier(VerifyTier::Fast.as_str());
+        move || {
+            let _ = tx.send(run_fast_verify(
+                &repo_root,
+                &task_id,
+                &worktree_path,
+                changed_files,
+                &commands,
+                &nix_shell,
+                limits,
+                &processes,
+            ));
+        }
+    };
+    if let Err(e) = std::thread::Builder::new()
+        .name(format!("fast-verify-{}", task_id.0))
+        .spawn(worker)
+    {
+        eprintln!(
+            "[daemon] Failed to start fast verify for {}: {}",
+            task_id.0, e
+        );
+        return;
+    }
+    daemon_state.fast_verify.insert(
+        task_id.0.clone(),
+        FastVerifyRun {
+            model,
+            processes,
+            result_rx: rx,
+        },
+    );
+}
+
+/// Turn finished fast verify runs into a same-model retry on failure or the
+/// validation step on success. Runs whose task left `Chatting` are killed.
+fn collect_fast_verify_results(
+    service: &OrchdService,
+    config: &DaemonConfig,
+    daemon_state: &mut DaemonState,
+) -> Vec<DaemonAction> {
+    let mut actions = Vec::new();
+    daemon_state.fast_verify.retain(|task_id, run| {
+        let task_id = TaskId(task_id.clone());
+        let chatting = service
+            .task(&task_id)
+            .ok()
+            .flatten()
+            .is_some_and(|task| task.state == TaskState::Chatting);
+        if !chatting {
+            run.processes.kill_all();
+            return false;
+        }
+        match run.result_rx.try_recv() {
+            Err(TryRecvError::Empty) => return true,
+            Ok(Some(reason)) => actions.push(DaemonAction::ScheduleRetry {
+                task_id,
+                next_model: run.model,
+                reason,
+            }),
+            Ok(None) => actions.push(validation_action(service, config, &task_id)),
+            Err(TryRecvError::Disconnected) => actions.push(DaemonAction::RecordNeedsHuman {
+                task_id,
+                reason: "fast verify worker panicked".to_string(),
+            }),
+        }
+        false
+    });
+    actions
+}
+
+/// Run the fast verify tier's commands in `worktree`, appending the results
+/// to the task's agent log. Returns the retry reason when a command fails;
+/// `None` when the tier passes.
+#[allow(clippy::too_many_arguments)]
+fn run_fast_verify(
+    repo_root: &Path,
+    task_id: &TaskId,
+    worktree: &Path,
+    changed_files: usize,
+    commands: &[String],
+    nix_shell: &str,
+    limits: VerifyLimits,
+    processes: &StepProcesses,
+) -> Option<String> {
+    let mut lines = vec![format!(
+        "[fast verify] {} changed file(s), {} command(s)",
+        changed_files,
+        commands.len()
+    )];
+    let mut reason = None;
+    for command in commands {
+        match run_verify_command_in(worktree, command, nix_shell, limits, processes) {
+            Ok(()) => lines.push(format!("[fast verify] passed: {command}")),
+            Err(failure) => {
+                lines.push(format!("[fast verify] failed: {command}"));
+                lines.extend(failure.message.lines().map(str::to_string));
+                let headline = failure.message.lines().next().unwrap_or_default();
+                reason = Some(
+                    match summarize_failures(&failure.failures, VERIFY_SUMMARY_FAILURES) {
+                        Some(summary) => format!("fast verify failed: {headline}; {summary}"),
+                        None => format!("fast verify failed: {headline}"),
+                    },
+                );
+                break;
+            }
+        }
+    }
+    if let Err(e) = agent_log::append_agent_output(repo_root, task_id, &lines) {
+        eprintln!(
+            "[daemon] Failed to log fast verify results for {}: {}",
+            task_id.0, e
+        );
+    }
+    reason
+}
+
+/// Files changed in `worktree` since its merge base with trunk, including
+/// uncommitted and untracked ones. Empty when git can't be queried.
+fn epoch_changed_files(worktree: &Path) -> Vec<PathBuf> {
+    let git = GitCli::default();
+    let Ok(repo) = discover_repo(worktree, &git) else {
+        return Vec::new();
+    };
+    let mut files = task_diff_snapshot(worktree)
+        .map(|diff| diff.files)
+        .unwrap_or_default()
+        .into_iter()
+        .collect::<BTreeSet<_>>();
+    if let Ok(status) = capture_status_snapshot(&repo, &git) {
+        files.extend(status.changed_files.into_iter().map(|file| file.path));
+    }
+    files.into_iter().collect()
+}
+
+/// Diff of a task worktree against its merge-base with trunk, including
+/// uncommitted changes to tracked files.
+pub fn task_diff_snapshot(worktree: &Path) -> Option<DiffSnapshot> {
+    let git = GitCli::default();
+    let repo = discover_repo(worktree, &git).ok()?;
+    let base = ["origin/main", "main"]
+        .iter()
+        .find_map(|trunk| merge_base(&repo, &git, "HEAD", trunk).ok().flatten())
+        .unwrap_or_else(|| "HEAD".to_string());
+    capture_diff_snapshot(&repo, &git, Some(&base)).ok()
+}
+
+/// Last lines of the agent's log, which carry the error that ended the run.
+fn recent_agent_output(repo_root: &Path, task_id: &TaskId) -> String {
+    const FAILURE_TAIL_LINES: usize = 50;
+    let log = agent_log::read_agent_log(repo_root, task_id).unwrap_or_default();
+    let lines: Vec<&str> = log.lines().collect();
+    lines[lines.len().saturating_sub(FAILURE_TAIL_LINES)..].join("\n")
+}
+
 /// Look up the parent task's branch name for stacking.
 fn find_parent_branch(service: &OrchdService, task: &Task) -> Option<String> {
     let parent_id = task.parent_task_id.as_ref()?;
@@ -1554,7 +2662,45 @@ fn estimate_tokens_from_char_count(chars: u64) -> u64 {
     chars.div_ceil(4)
 }
 
-fn run_verify_command(cwd: &Path, command: &str, nix_shell: &str) -> Result<(), String> {
+/// Failures named in a `VerifyCompleted` event's summary.
+const VERIFY_SUMMARY_FAILURES: usize = 3;
+
+/// `[verify.tiers]` key for verifying a task's own branch.
+const VERIFY_TIER_BRANCH: &str = "branch";
+/// `[verify.tiers]` key for verifying the restacked branch before submit.
+const VERIFY_TIER_STACK: &str = "stack";
+
+/// A failed verify run, with what a reproduction bundle needs.
+#[derive(Debug)]
+struct VerifyRunFailure {
+    message: String,
+    effective_command: String,
+    exit_code: Option<i32>,
+    output: String,
+    /// Failing tests and compiler errors parsed from `output`.
+    failures: Vec<VerifyFailureDetail>,
+    /// Killed at the time limit; `output` is what was captured until then.
+    timed_out: bool,
+}
+
+fn run_verify_command(
+    cwd: &Path,
+    command: &str,
+    nix_shell: &str,
+    limits: VerifyLimits,
+) -> Result<(), VerifyRunFailure> {
+    run_verify_command_in(cwd, command, nix_shell, limits, &StepProcesses::default())
+}
+
+/// [`run_verify_command`] with the command owned by a pipeline step, so a
+/// timed-out or cancelled step kills it.
+fn run_verify_command_in(
+    cwd: &Path,
+    command: &str,
+    nix_shell: &str,
+    limits: VerifyLimits,
+    processes: &StepProcesses,
+) -> Result<(), VerifyRunFailure> {
     let nix = nix_shell.trim();
     let effective = if nix.is_empty() {
         command.to_string()
@@ -1562,22 +2708,112 @@ fn run_verify_command(cwd: &Path, command: &str, nix_shell: &str) -> Result<(),
         format!("{nix} -c {command}")
     };
 
-    let output = Command::new("bash")
-        .arg("-lc")
-        .arg(&effective)
-        .current_dir(cwd)
-        .output()
-        .map_err(|e| format!("failed to spawn verify command `{effective}`: {e}"))?;
+    let output = processes
+        .run_bounded(&effective, cwd, limits)
+        .map_err(|e| VerifyRunFailure {
+            message: format!("failed to spawn verify command `{effective}`: {e}"),
+            effective_command: effective.clone(),
+            exit_code: None,
+            output: String::new(),
+            failures: Vec::new(),
+            timed_out: false,
+        })?;
+
+    if output.success {
+        return Ok(());
+    }
+
+    let stdout = output.stdout.trim();
+    let stderr = output.stderr.trim();
+    let output_text = format!("{stdout}\n{stderr}");
+    let mut failures = Vec::new();
+    let outcome = if output.timed_out {
+        failures.push(VerifyFailureDetail::timeout(limits.timeout_secs));
+        format!("timed out after {}s", limits.timeout_secs)
+    } else {
+        format!("failed (exit={:?})", output.exit_code)
+    };
+    failures.extend(parse_verify_failures(&output_text));
+    Err(VerifyRunFailure {
+        message: format!(
+            "verify command `{effective}` {outcome}\nstdout: {stdout}\nstderr: {stderr}"
+        ),
+        effective_command: effective,
+        exit_code: output.exit_code,
+        failures,
+        timed_out: output.timed_out,
+        output: output_text,
+    })
+}
 
-    if output.status.success() {
+/// Store a reproduction bundle for a failed verify run, returning its path
+/// relative to the repo root.
+fn save_verify_failure_bundle(
+    repo_root: &Path,
+    task_id: &TaskId,
+    command: &str,
+    cwd: &Path,
+    failure: &VerifyRunFailure,
+    now: DateTime<Utc>,
+) -> Option<String> {
+    let bundle = VerifyFailureBundle::capture(
+        task_id,
+        command,
+        &failure.effective_command,
+        cwd,
+        failure.exit_code,
+        &failure.output,
+        now,
+    );
+    match verify_failure::save_bundle(repo_root, &bundle) {
+        Ok(dir) => Some(dir.strip_prefix(repo_root).unwrap_or(&dir).display().to_string()),
+        Err(err) => {
+            eprintln!("[daemon] Failed to save verify failure bundle for {}: {err}", task_id.0);
+            None
+        }
+    }
+}
+
+/// Run a task state hook command with the task context exported as
+/// `OTHALA_*` environment variables.
+///
+/// Hooks run from the repo root and are killed, with their whole process
+/// group, after [`TASK_STATE_HOOK_LIMITS`]. A failing hook is reported to the
+/// caller but never changes task state.
+fn run_task_state_hook(name: &str, command: &str, task: &Task, cwd: &Path) -> Result<(), String> {
+    let command = command.trim();
+    if command.is_empty() {
         return Ok(());
     }
 
-    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
-    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    let output = run_bounded_with(command, cwd, TASK_STATE_HOOK_LIMITS, |cmd| {
+        cmd.env("OTHALA_HOOK", name)
+            .env("OTHALA_TASK_ID", &task.id.0)
+            .env("OTHALA_TASK_TITLE", &task.title)
+            .env("OTHALA_TASK_STATE", task.state.to_string())
+            .env("OTHALA_REPO_ID", &task.repo_id.0)
+            .env("OTHALA_BRANCH", task.branch_name.as_deref().unwrap_or(""));
+        if let Some(pr) = &task.pr {
+            cmd.env("OTHALA_PR_NUMBER", pr.number.to_string())
+                .env("OTHALA_PR_URL", &pr.url);
+        }
+        cmd.spawn()
+    })
+    .map_err(|e| format!("{name} hook for {} failed to start: {e}", task.id.0))?;
+    if output.success {
+        return Ok(());
+    }
+    if output.timed_out {
+        return Err(format!(
+            "{name} hook for {} timed out after {}s",
+            task.id.0, TASK_STATE_HOOK_LIMITS.timeout_secs
+        ));
+    }
     Err(format!(
-        "verify command `{effective}` failed (exit={:?})\nstdout: {stdout}\nstderr: {stderr}",
-        output.status.code()
+        "{name} hook for {} failed (exit={:?}): {}",
+        task.id.0,
+        output.exit_code,
+        output.stderr.trim()
     ))
 }
 
@@ -1611,11 +2847,171 @@ pub fn detect_nix_shell(repo_root: &Path) -> String {
     String::new()
 }
 
+/// Verify limits from the first `config/repos/*.toml` that parses, or the
+/// defaults when there is none.
+pub fn detect_verify_limits(repo_root: &Path) -> VerifyLimitsConfig {
+    first_repo_config(repo_root)
+        .map(|config| config.verify.limits)
+        .unwrap_or_default()
+}
+
+/// Fast verify tier commands from the first `config/repos/*.toml` that
+/// parses; empty when none are configured.
+pub fn detect_fast_verify_commands(repo_root: &Path) -> Vec<String> {
+    first_repo_config(repo_root)
+        .map(|config| config.verify.fast_commands)
+        .unwrap_or_default()
+}
+
+fn first_repo_config(repo_root: &Path) -> Option<orch_core::config::RepoConfig> {
+    let entries = fs::read_dir(repo_root.join("config/repos")).ok()?;
+    let mut paths = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|e| e == "toml"))
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .find_map(|contents| orch_core::config::parse_repo_config(&contents).ok())
+}
+
+/// Submit the task's branch unless it already has an open PR, in which case
+/// that PR is returned without running `gt submit` again.
+fn submit_unless_open(
+    graphite: &GraphiteClient,
+    mode: SubmitMode,
+    branch: &str,
+    worktree_path: &Path,
+) -> Result<Option<SubmittedPr>, orch_graphite::GraphiteError> {
+    if !branch.trim().is_empty() {
+        match graphite.branch_pr_status(branch) {
+            Ok(PrStatus::Open(url)) => {
+                eprintln!("[daemon] {branch} already has an open PR; skipping gt submit");
+                let existing = orch_graphite::parse_submit_output(&url).into_iter().next();
+                return Ok(existing.or_else(|| lookup_pr_for_branch(branch, worktree_path)));
+            }
+            Ok(_) => {}
+            Err(err) => {
+                eprintln!("[daemon] PR status check failed for {branch}: {err}");
+            }
+        }
+    }
+    graphite
+        .submit(mode)
+        .map(|prs| resolve_submitted_pr(prs, branch, worktree_path))
+}
+
+/// The PR `gt submit` reported for `branch`, falling back to `gh pr view`
+/// when the output did not list it.
+fn resolve_submitted_pr(
+    prs: Vec<SubmittedPr>,
+    branch: &str,
+    repo_root: &Path,
+) -> Option<SubmittedPr> {
+    select_submitted_pr(prs, branch).or_else(|| lookup_pr_for_branch(branch, repo_root))
+}
+
+/// Prefer the PR for `branch`; a lone PR is taken as the task's own.
+fn select_submitted_pr(prs: Vec<SubmittedPr>, branch: &str) -> Option<SubmittedPr> {
+    if prs.len() == 1 {
+        return prs.into_iter().next();
+    }
+    prs.into_iter().find(|pr| pr.branch.as_deref() == Some(branch))
+}
+
+fn lookup_pr_for_branch(branch: &str, repo_root: &Path) -> Option<SubmittedPr> {
+    if branch.trim().is_empty() {
+        return None;
+    }
+    let output = Command::new("gh")
+        .args(["pr", "view", branch, "--json", "number,url"])
+        .current_dir(repo_root)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+    Some(SubmittedPr {
+        branch: Some(branch.to_string()),
+        number: value.get("number")?.as_u64()?,
+        url: value.get("url")?.as_str()?.to_string(),
+    })
+}
+
+/// Move the task to AwaitingMerge, recording the PR when one is known.
+fn complete_submit_with_pr(
+    service: &OrchdService,
+    task_id: &TaskId,
+    pr: Option<SubmittedPr>,
+    event_id: EventId,
+    now: DateTime<Utc>,
+) -> Result<Task, crate::service::ServiceError> {
+    let (url, number) = match pr {
+        Some(pr) => (pr.url, pr.number),
+        None => (format!("graphite://submit/{}", task_id.0), 0),
+    };
+    service.complete_submit(task_id, url, number, event_id, now)
+}
+
+/// Fallback for `AwaitingMerge` tasks the per-tick check above missed: poll
+/// old-enough tasks for a merge and flag ones waiting past the stale threshold.
+fn poll_awaiting_merge(
+    daemon_state: &mut DaemonState,
+    config: &DaemonConfig,
+    awaiting: &[Task],
+    pending: &[DaemonAction],
+    now: DateTime<Utc>,
+) -> Vec<DaemonAction> {
+    daemon_state.merge_poller.retain_awaiting(awaiting);
+    let mut actions = Vec::new();
+    for task in awaiting {
+        let already_merging = pending.iter().any(
+            |action| matches!(action, DaemonAction::MarkMerged { task_id } if *task_id == task.id),
+        );
+        if already_merging {
+            continue;
+        }
+
+        if daemon_state
+            .merge_poller
+            .take_due(task, &config.merge_poll, now)
+        {
+            if let Some(method) =
+                merge_poll::detect_merge(&config.repo_root, task, &config.merge_poll)
+            {
+                actions.push(DaemonAction::EmitEvent {
+                    task_id: Some(task.id.clone()),
+                    repo_id: Some(task.repo_id.clone()),
+                    kind: EventKind::MergeDetected {
+                        method: method.as_str().to_string(),
+                    },
+                });
+                actions.push(DaemonAction::MarkMerged {
+                    task_id: task.id.clone(),
+                });
+                continue;
+            }
+        }
+
+        if let Some(reason) = daemon_state
+            .merge_poller
+            .take_stale(task, &config.merge_poll, now)
+        {
+            actions.push(DaemonAction::RecordNeedsHuman {
+                task_id: task.id.clone(),
+                reason,
+            });
+        }
+    }
+    actions
+}
+
 fn is_gh_pr_state_merged(stdout: &[u8]) -> bool {
     String::from_utf8_lossy(stdout).trim() == "MERGED"
 }
 
-fn check_pr_merged(pr_number: u64, repo_root: &Path) -> bool {
+pub(crate) fn check_pr_merged(pr_number: u64, repo_root: &Path) -> bool {
     let output = std::process::Command::new("gh")
         .args([
             "pr",
@@ -1697,33 +3093,191 @@ fn worktree_has_uncommitted_changes(path: &Path) -> bool {
     }
 }
 
-fn get_worktree_head_sha(path: &Path) -> Option<String> {
-    let output = Command::new("git")
-        .args(["rev-parse", "HEAD"])
-        .current_dir(path)
-        .output()
-        .ok()?;
-    if !output.status.success() {
-        return None;
-    }
-    let sha = String::from_utf8_lossy(&output.stdout).trim().to_string();
-    if sha.is_empty() {
-        None
-    } else {
-        Some(sha)
-    }
-}
-
-fn apply_retry_transition(
+/// Store the worktree patch for a verify run and, when a retry fails verify,
+/// compare it against the last passing attempt. Returns the needs-human
+/// reason when the retry threw away too much of that earlier work.
+fn guard_verify_result(
     service: &OrchdService,
-    notification_dispatcher: Option<&NotificationDispatcher>,
+    config: &DaemonConfig,
     task_id: &TaskId,
-    next_model: ModelKind,
-    reason: &str,
-    at: DateTime<Utc>,
-) -> Result<bool, String> {
-    let Some(mut task) = service.task(task_id).map_err(|e| e.to_string())? else {
-        return Err(format!("task not found for retry: {}", task_id.0));
+    worktree_path: &Path,
+    verify_passed: bool,
+    now: DateTime<Utc>,
+) -> Option<String> {
+    let task = service.task(task_id).ok().flatten()?;
+    let patch = retry_guard::capture_worktree_patch(worktree_path)?;
+
+    let prior = if task.retry_count > 0 && !verify_passed {
+        retry_guard::last_passing_patch(&service.store, task_id)
+            .ok()
+            .flatten()
+    } else {
+        None
+    };
+    let verdict = prior
+        .as_ref()
+        .map(|(_, prior_patch)| {
+            retry_guard::evaluate_retry_diff(prior_patch, &patch, verify_passed, &config.retry_guard)
+        })
+        .unwrap_or(RetryGuardVerdict::Accept);
+
+    let meta = RunPatchMeta {
+        attempt: task.retry_count,
+        verify_passed,
+        flagged: matches!(verdict, RetryGuardVerdict::Regression(_)),
+    };
+    let record = match retry_guard::store_run_patch(
+        &service.store,
+        &config.repo_root,
+        task_id,
+        &meta,
+        &patch,
+        now,
+    ) {
+        Ok(record) => record,
+        Err(e) => {
+            eprintln!("[daemon] Failed to store run patch for {}: {}", task_id.0, e);
+            return None;
+        }
+    };
+
+    let RetryGuardVerdict::Regression(report) = verdict else {
+        return None;
+    };
+    let (prior_record, _) = prior?;
+    if let Err(e) = retry_guard::keep_candidate_patch(&service.store, task_id, &prior_record, now) {
+        eprintln!("[daemon] Failed to record candidate patch for {}: {}", task_id.0, e);
+    }
+    Some(format!(
+        "retry attempt {} removed {}% of the hunks ({}/{}) from the last passing attempt without passing verify; \
+         keeping previous patch {} as the candidate (rejected patch: {})",
+        task.retry_count,
+        report.removed_percent,
+        report.removed_hunks,
+        report.prior_hunks,
+        prior_record.path,
+        record.path
+    ))
+}
+
+/// Run a blocking pipeline step under its stage timeout, polling the store so
+/// `othala cancel` can abort it. Returns `None` once the pipeline has been
+/// failed because the step timed out, was cancelled, or panicked.
+fn run_pipeline_step<T, F>(
+    service: &OrchdService,
+    config: &DaemonConfig,
+    daemon_state: &mut DaemonState,
+    task_id: &TaskId,
+    now: DateTime<Utc>,
+    step: F,
+) -> Option<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&Arc<StepProcesses>) -> T + Send + 'static,
+{
+    let stage = daemon_state
+        .pipelines
+        .get(&task_id.0)
+        .map(|pipeline| pipeline.stage)
+        .unwrap_or(PipelineStage::VerifyBranch);
+    let timeout = config
+        .step_timeouts
+        .for_stage(stage)
+        .unwrap_or(config.step_timeouts.verify);
+    let is_cancelled = || {
+        service
+            .task(task_id)
+            .ok()
+            .flatten()
+            .is_some_and(|task| task.state == TaskState::Stopped)
+    };
+
+    let outcome = run_step(timeout, is_cancelled, step);
+    if let StepOutcome::Finished(value) = outcome {
+        return Some(value);
+    }
+
+    let pipeline = daemon_state.pipelines.get_mut(&task_id.0);
+    let reason = match outcome {
+        StepOutcome::Finished(_) => unreachable!("handled above"),
+        StepOutcome::Cancelled => {
+            if let Some(pipeline) = pipeline {
+                pipeline.cancel("task stopped");
+            }
+            remove_pipeline(service, daemon_state, task_id);
+            daemon_state.restack_retries.remove(&task_id.0);
+            eprintln!("[daemon] Pipeline for {} cancelled during {}", task_id.0, stage);
+            return None;
+        }
+        StepOutcome::TimedOut(timeout) => match pipeline {
+            Some(pipeline) => {
+                pipeline.fail_timed_out(timeout);
+                pipeline.error.clone().unwrap_or_default()
+            }
+            None => format!("step `{stage}` timed out after {timeout:?}"),
+        },
+        StepOutcome::Panicked => format!("step `{stage}` panicked"),
+    };
+    remove_pipeline(service, daemon_state, task_id);
+    daemon_state.restack_retries.remove(&task_id.0);
+    eprintln!("[daemon] Pipeline for {} needs human: {}", task_id.0, reason);
+    stop_for_human_review(
+        service,
+        daemon_state.notification_dispatcher.as_ref(),
+        task_id,
+        &reason,
+        now,
+    );
+    None
+}
+
+/// Stop a task whose retry was flagged by the regression guard, or whose
+/// pipeline step overran, and ask for a human.
+fn stop_for_human_review(
+    service: &OrchdService,
+    notification_dispatcher: Option<&NotificationDispatcher>,
+    task_id: &TaskId,
+    reason: &str,
+    at: DateTime<Utc>,
+) {
+    let Ok(Some(mut task)) = service.task(task_id) else {
+        return;
+    };
+    task.state = TaskState::Stopped;
+    task.last_failure_reason = Some(reason.to_string());
+    task.updated_at = at;
+    if let Err(e) = service.store.upsert_task(&task) {
+        eprintln!("[daemon] Failed to stop {} after retry regression: {}", task_id.0, e);
+    }
+
+    let event = Event {
+        id: EventId(format!(
+            "E-HUMAN-{}-{}",
+            task_id.0,
+            at.timestamp_nanos_opt().unwrap_or_default()
+        )),
+        task_id: Some(task_id.clone()),
+        repo_id: Some(task.repo_id.clone()),
+        at,
+        kind: EventKind::NeedsHuman {
+            reason: reason.to_string(),
+        },
+    };
+    if let Err(e) = record_event_with_notification(service, notification_dispatcher, &event) {
+        eprintln!("[daemon] Failed to record needs_human for {}: {}", task_id.0, e);
+    }
+}
+
+fn apply_retry_transition(
+    service: &OrchdService,
+    notification_dispatcher: Option<&NotificationDispatcher>,
+    task_id: &TaskId,
+    next_model: ModelKind,
+    reason: &str,
+    at: DateTime<Utc>,
+) -> Result<bool, String> {
+    let Some(mut task) = service.task(task_id).map_err(|e| e.to_string())? else {
+        return Err(format!("task not found for retry: {}", task_id.0));
     };
     let previous_model = task.preferred_model;
 
@@ -1743,6 +3297,7 @@ fn apply_retry_transition(
                     reason, task.retry_count, task.max_retries
                 ),
                 is_final: true,
+                cause: Some(FailureCause::classify(reason)),
             },
         };
         let _ = record_event_with_notification(service, notification_dispatcher, &failed_event);
@@ -1854,6 +3409,20 @@ pub fn execute_actions(
                     );
                     continue;
                 }
+                let limiter_key = format!("model:{}", model.as_str());
+                let acquired = daemon_state
+                    .spawn_rate_limiter
+                    .try_acquire(&limiter_key, now);
+                if let Err(e) = daemon_state
+                    .spawn_rate_limiter
+                    .save_stats(&config.repo_root)
+                {
+                    eprintln!("[daemon] Failed to save rate limit stats: {e}");
+                }
+                if let Err(retry) = acquired {
+                    eprintln!("[daemon] Deferring agent spawn for {}: {}", task_id.0, retry);
+                    continue;
+                }
                 if let Ok(Some(task)) = service.task(task_id) {
                     if let Err(e) = supervisor.spawn_agent(
                         task_id,
@@ -1926,8 +3495,26 @@ pub fn execute_actions(
                     task_id.0,
                     now.timestamp_nanos_opt().unwrap_or_default()
                 ));
+                let already_merged = service
+                    .task(task_id)
+                    .ok()
+                    .flatten()
+                    .is_some_and(|t| t.state == TaskState::Merged);
                 match service.mark_merged(task_id, event_id, now) {
-                    Ok(_) => eprintln!("[daemon] {} -> Merged", task_id.0),
+                    Ok(task) => {
+                        eprintln!("[daemon] {} -> Merged", task_id.0);
+                        if let Some(hook) = config
+                            .post_merge_hook
+                            .as_deref()
+                            .filter(|_| !already_merged)
+                        {
+                            if let Err(e) =
+                                run_task_state_hook("post_merge", hook, &task, &config.repo_root)
+                            {
+                                eprintln!("[daemon] {e}");
+                            }
+                        }
+                    }
                     Err(e) => eprintln!("[daemon] Failed to mark {} merged: {}", task_id.0, e),
                 }
             }
@@ -1977,12 +3564,12 @@ pub fn execute_actions(
                     now,
                 ) {
                     Ok(true) => {
-                        daemon_state.pipelines.remove(&task_id.0);
+                        remove_pipeline(service, daemon_state, task_id);
                         daemon_state.restack_retries.remove(&task_id.0);
                         eprintln!("[daemon] {} scheduled retry with {}", task_id.0, next_model);
                     }
                     Ok(false) => {
-                        daemon_state.pipelines.remove(&task_id.0);
+                        remove_pipeline(service, daemon_state, task_id);
                         daemon_state.restack_retries.remove(&task_id.0);
                         eprintln!(
                             "[daemon] {} reached max retries; task moved to STOPPED",
@@ -2052,8 +3639,28 @@ pub fn execute_actions(
                     },
                     );
 
-                    match run_verify_command(worktree_path, verify_cmd, &config.nix_shell) {
+                    let tier = match daemon_state.pipelines.get(&task_id.0).map(|p| p.stage) {
+                        Some(PipelineStage::VerifyStack) => VERIFY_TIER_STACK,
+                        _ => VERIFY_TIER_BRANCH,
+                    };
+                    let step = {
+                        let (cwd, command, nix_shell) =
+                            (worktree_path.clone(), verify_cmd.to_string(), config.nix_shell.clone());
+                        let limits = config.verify_limits.for_tier(tier);
+                        move |processes: &Arc<StepProcesses>| {
+                            run_verify_command_in(&cwd, &command, &nix_shell, limits, processes)
+                        }
+                    };
+                    let Some(verify_result) =
+                        run_pipeline_step(service, config, daemon_state, task_id, now, step)
+                    else {
+                        continue;
+                    };
+
+                    match verify_result {
                         Ok(()) => {
+                            daemon_state.orchestration_metrics.record_verify(true);
+                            guard_verify_result(service, config, task_id, worktree_path, true, now);
                             if let Some(sha) = current_sha {
                                 daemon_state.verify_cache.insert(task_id.0.clone(), sha);
                             }
@@ -2069,15 +3676,37 @@ pub fn execute_actions(
                                 task_id: Some(task_id.clone()),
                                 repo_id: service.task(task_id).ok().flatten().map(|t| t.repo_id),
                                 at: now,
-                                kind: EventKind::VerifyCompleted { success: true },
+                                kind: EventKind::VerifyCompleted {
+                                    success: true,
+                                    failure_bundle: None,
+                                    failure_summary: None,
+                                },
                             },
                             );
                             if let Some(pipeline) = daemon_state.pipelines.get_mut(&task_id.0) {
                                 pipeline.advance();
                             }
                         }
-                        Err(error) => {
+                        Err(failure) => {
                             daemon_state.verify_cache.remove(&task_id.0);
+                            // A timeout is retried like a failure but
+                            // counted on its own.
+                            if failure.timed_out {
+                                daemon_state.orchestration_metrics.record_verify_timeout();
+                            } else {
+                                daemon_state.orchestration_metrics.record_verify(false);
+                            }
+                            let failure_bundle = save_verify_failure_bundle(
+                                &config.repo_root,
+                                task_id,
+                                verify_cmd,
+                                worktree_path,
+                                &failure,
+                                now,
+                            );
+                            let failure_summary =
+                                summarize_failures(&failure.failures, VERIFY_SUMMARY_FAILURES);
+                            let error = failure.message;
                             let _ = record_event_with_notification(
                                 service,
                                 daemon_state.notification_dispatcher.as_ref(),
@@ -2090,10 +3719,32 @@ pub fn execute_actions(
                                 task_id: Some(task_id.clone()),
                                 repo_id: service.task(task_id).ok().flatten().map(|t| t.repo_id),
                                 at: now,
-                                kind: EventKind::VerifyCompleted { success: false },
+                                kind: EventKind::VerifyCompleted {
+                                    success: false,
+                                    failure_bundle,
+                                    failure_summary,
+                                },
                             },
                             );
 
+                            if let Some(pipeline) = daemon_state.pipelines.get_mut(&task_id.0) {
+                                pipeline.fail(error.clone());
+                            }
+                            if let Some(reason) =
+                                guard_verify_result(service, config, task_id, worktree_path, false, now)
+                            {
+                                eprintln!("[daemon] Retry regression for {}: {}", task_id.0, reason);
+                                stop_for_human_review(
+                                    service,
+                                    daemon_state.notification_dispatcher.as_ref(),
+                                    task_id,
+                                    &reason,
+                                    now,
+                                );
+                                remove_pipeline(service, daemon_state, task_id);
+                                daemon_state.restack_retries.remove(&task_id.0);
+                                continue;
+                            }
                             let retry_model = service
                                 .task(task_id)
                                 .ok()
@@ -2101,9 +3752,6 @@ pub fn execute_actions(
                                 .and_then(|t| t.preferred_model)
                                 .or_else(|| config.enabled_models.first().copied())
                                 .unwrap_or(ModelKind::Claude);
-                            if let Some(pipeline) = daemon_state.pipelines.get_mut(&task_id.0) {
-                                pipeline.fail(error.clone());
-                            }
                             match apply_retry_transition(
                                 service,
                                 daemon_state.notification_dispatcher.as_ref(),
@@ -2126,7 +3774,7 @@ pub fn execute_actions(
                                     );
                                 }
                             }
-                            daemon_state.pipelines.remove(&task_id.0);
+                            remove_pipeline(service, daemon_state, task_id);
                             daemon_state.restack_retries.remove(&task_id.0);
                         }
                     }
@@ -2143,8 +3791,21 @@ pub fn execute_actions(
                         now,
                     );
 
-                    let graphite = GraphiteClient::new(worktree_path.clone());
-                    match graphite.move_current_branch_onto(parent_branch) {
+                    let graphite = graphite_client(config, worktree_path);
+                    let step = {
+                        let (graphite, parent_branch) = (graphite.clone(), parent_branch.clone());
+                        move |processes: &Arc<StepProcesses>| {
+                            step_graphite(&graphite, processes)
+                                .move_current_branch_onto(&parent_branch)
+                        }
+                    };
+                    let Some(restack_result) =
+                        run_pipeline_step(service, config, daemon_state, task_id, now, step)
+                    else {
+                        continue;
+                    };
+
+                    match restack_result {
                         Ok(()) => {
                             let _ = service.complete_restack(
                                 task_id,
@@ -2156,6 +3817,21 @@ pub fn execute_actions(
                             }
                             daemon_state.restack_retries.remove(&task_id.0);
                         }
+                        Err(error)
+                            if config.auto_resolve_conflicts && error.is_restack_conflict() =>
+                        {
+                            auto_resolve_restack_conflict(
+                                service,
+                                config,
+                                daemon_state,
+                                task_id,
+                                worktree_path,
+                                parent_branch,
+                                &graphite,
+                                now,
+                                event_seed,
+                            );
+                        }
                         Err(error) => {
                             let err_msg = format!("restack onto `{parent_branch}` failed: {error}");
                             if !handle_restack_graphite_playbook(
@@ -2200,7 +3876,7 @@ pub fn execute_actions(
                                         );
                                     }
                                 }
-                                daemon_state.pipelines.remove(&task_id.0);
+                                remove_pipeline(service, daemon_state, task_id);
                                 daemon_state.restack_retries.remove(&task_id.0);
                             }
                         }
@@ -2222,7 +3898,7 @@ pub fn execute_actions(
                         continue;
                     }
 
-                    let graphite = GraphiteClient::new(worktree_path.clone());
+                    let graphite = graphite_client(config, worktree_path);
 
                     // Ensure agent changes are committed before submit. This captures
                     // untracked/modified files in the task branch so "merged" state
@@ -2263,27 +3939,50 @@ pub fn execute_actions(
                                     );
                                 }
                             }
-                            daemon_state.pipelines.remove(&task_id.0);
+                            remove_pipeline(service, daemon_state, task_id);
                             daemon_state.restack_retries.remove(&task_id.0);
                             continue;
                         }
                     }
 
-                    // Fetch latest trunk before submitting to avoid
-                    // "trunk branch is out of date" errors from Graphite.
-                    let _ = Command::new("git")
-                        .args(["fetch", "origin"])
-                        .current_dir(worktree_path)
-                        .stdout(Stdio::null())
-                        .stderr(Stdio::null())
-                        .status();
+                    let branch = daemon_state
+                        .pipelines
+                        .get(&task_id.0)
+                        .map(|pipeline| pipeline.branch_name.clone())
+                        .unwrap_or_default();
+                    let step = {
+                        let (graphite, worktree_path, mode) =
+                            (graphite.clone(), worktree_path.clone(), *mode);
+                        move |processes: &Arc<StepProcesses>| {
+                            // Fetch latest trunk before submitting to avoid
+                            // "trunk branch is out of date" errors from Graphite.
+                            let _ = processes.output(
+                                Command::new("git")
+                                    .args(["fetch", "origin"])
+                                    .current_dir(&worktree_path),
+                            );
+                            let graphite = step_graphite(&graphite, processes);
+                            submit_unless_open(&graphite, mode, &branch, &worktree_path)
+                        }
+                    };
+                    let Some(submit_result) =
+                        run_pipeline_step(service, config, daemon_state, task_id, now, step)
+                    else {
+                        continue;
+                    };
 
-                    match graphite.submit(*mode) {
-                        Ok(()) => {
-                            if let Err(e) = service.complete_submit(
+                    match submit_result {
+                        Ok(pr) => {
+                            if pr.is_none() {
+                                eprintln!(
+                                    "[daemon] Submit for {} reported no PR; PR tracking disabled",
+                                    task_id.0
+                                );
+                            }
+                            if let Err(e) = complete_submit_with_pr(
+                                service,
                                 task_id,
-                                format!("graphite://submit/{}", task_id.0),
-                                0,
+                                pr,
                                 EventId(format!("E-SUBMIT-DONE-{}-{seed}", task_id.0)),
                                 now,
                             ) {
@@ -2318,7 +4017,7 @@ pub fn execute_actions(
                                 if let Some(pipeline) = daemon_state.pipelines.get_mut(&task_id.0) {
                                     pipeline.fail(reason);
                                 }
-                                daemon_state.pipelines.remove(&task_id.0);
+                                remove_pipeline(service, daemon_state, task_id);
                                 daemon_state.restack_retries.remove(&task_id.0);
                                 continue;
                             }
@@ -2341,7 +4040,7 @@ pub fn execute_actions(
                                 if let Some(pipeline) = daemon_state.pipelines.get_mut(&task_id.0) {
                                     pipeline.fail(reason);
                                 }
-                                daemon_state.pipelines.remove(&task_id.0);
+                                remove_pipeline(service, daemon_state, task_id);
                                 daemon_state.restack_retries.remove(&task_id.0);
                                 continue;
                             }
@@ -2378,7 +4077,7 @@ pub fn execute_actions(
                                     );
                                 }
                             }
-                            daemon_state.pipelines.remove(&task_id.0);
+                            remove_pipeline(service, daemon_state, task_id);
                             daemon_state.restack_retries.remove(&task_id.0);
                         }
                     }
@@ -2398,24 +4097,69 @@ pub fn execute_actions(
                 }
                 }
             }
+            DaemonAction::RunFastVerify {
+                task_id,
+                model,
+                worktree_path,
+                changed_files,
+                commands,
+            } => {
+                if config.dry_run {
+                    eprintln!("[dry-run] Would run fast verify for {}", task_id.0);
+                    continue;
+                }
+                start_fast_verify(
+                    config,
+                    daemon_state,
+                    task_id,
+                    *model,
+                    worktree_path,
+                    *changed_files,
+                    commands,
+                );
+            }
             DaemonAction::TriggerContextRegen => {
                 if should_regenerate(
                     &daemon_state.context_gen,
                     &config.context_gen_config,
                     Utc::now(),
                 ) {
-                    let prompt = build_context_gen_prompt(&config.repo_root, &config.template_dir);
+                    let changed: Vec<String> =
+                        daemon_state.context_changed_files.iter().cloned().collect();
+                    let main_sections = plan_main_sections_regen(&config.repo_root, &changed);
+                    let prompt = match &main_sections {
+                        Some(headings) => build_main_sections_prompt(
+                            &config.repo_root,
+                            &config.template_dir,
+                            headings,
+                            &changed,
+                        ),
+                        None => build_context_gen_prompt(&config.repo_root, &config.template_dir),
+                    };
                     daemon_state.context_gen_metrics.record_start();
                     daemon_state.context_gen_metrics.record_prompt_tokens(estimate_tokens(&prompt));
                     if let Err(e) = spawn_context_gen(
                         &config.repo_root,
                         &prompt,
-                        config.context_gen_config.model,
+                        config.context_gen_config.model_or(ModelKind::Claude),
+                        config.context_gen_config.timeout_secs,
                         &mut daemon_state.context_gen,
+                        Some(agent_log::spawn_log_writer(
+                            &config.repo_root,
+                            &TaskId::new(CONTEXT_GEN_LOG_ID),
+                        )),
                     ) {
                         eprintln!("[daemon] Failed to spawn context gen: {e}");
                     } else {
-                        eprintln!("[daemon] Background context regeneration started");
+                        match &main_sections {
+                            Some(headings) => eprintln!(
+                                "[daemon] Regenerating MAIN.md sections: {}",
+                                headings.join(", ")
+                            ),
+                            None => eprintln!("[daemon] Background context regeneration started"),
+                        }
+                        daemon_state.context_changed_files.clear();
+                        daemon_state.context_gen.main_sections = main_sections;
                         let event = Event {
                             id: EventId(format!(
                                 "E-CTX-REGEN-START-{}",
@@ -2456,10 +4200,9 @@ pub fn execute_actions(
                     eprintln!("[dry-run] Would spawn QA {} for {}", qa_type, task_id.0);
                     continue;
                 }
-                // Load baseline spec and build QA prompt.
-                if let Some(baseline) = load_baseline(&config.repo_root) {
+                // Load the QA spec and build the prompt.
+                if let Some(run_spec) = resolve_qa_spec(&config.repo_root, task_id, *qa_type) {
                     let branch = format!("task/{}", task_id.0);
-                    let task_spec = load_qa_task_spec(&config.repo_root, task_id);
                     let previous = load_latest_result(&config.repo_root, &branch);
 
                     // Determine cwd: baseline runs from repo root,
@@ -2476,8 +4219,8 @@ pub fn execute_actions(
                     };
 
                     let prompt = build_qa_prompt(
-                        &baseline,
-                        task_spec.as_deref(),
+                        &run_spec.spec,
+                        run_spec.task_spec.as_deref(),
                         previous.as_ref(),
                         &cwd,
                         &config.template_dir,
@@ -2490,13 +4233,14 @@ pub fn execute_actions(
                         .unwrap_or(ModelKind::Claude);
 
                     let mut qa_state = QAState::new(*qa_type);
-                    if let Err(e) = spawn_qa_agent(&cwd, &prompt, model, &mut qa_state) {
+                    let log = Some(agent_log::spawn_log_writer(&config.repo_root, task_id));
+                    if let Err(e) = spawn_qa_agent(&cwd, &prompt, model, &mut qa_state, log) {
                         eprintln!(
                             "[daemon] Failed to spawn QA {} for {}: {}",
                             qa_type, task_id.0, e
                         );
                     } else {
-                        eprintln!("[daemon] QA {} started for {}", qa_type, task_id.0);
+                        eprintln!("[daemon] QA {} started for {}", run_spec.label, task_id.0);
                         daemon_state.qa_agents.insert(task_id.0.clone(), qa_state);
 
                         // Record event.
@@ -2506,7 +4250,7 @@ pub fn execute_actions(
                             repo_id: None,
                             at: now,
                             kind: EventKind::QAStarted {
-                                qa_type: qa_type.to_string(),
+                                qa_type: run_spec.label,
                             },
                         };
                         let _ = record_event_with_notification(
@@ -2703,9 +4447,22 @@ pub fn execute_actions(
                         crate::graphite_agent::OperationResult::Success => {
                             eprintln!("[daemon] Graphite sync/restack succeeded");
                         }
-                        crate::graphite_agent::OperationResult::Conflict { details } => {
-                            eprintln!("[daemon] Graphite restack conflict (will retry): {}", 
-                                details.chars().take(200).collect::<String>());
+                        crate::graphite_agent::OperationResult::Conflict { details, files } => {
+                            if files.is_empty() {
+                                eprintln!(
+                                    "[daemon] Graphite restack conflict (will retry): {}",
+                                    details.chars().take(200).collect::<String>()
+                                );
+                            } else {
+                                eprintln!(
+                                    "[daemon] Graphite restack conflict in {} (will retry)",
+                                    files
+                                        .iter()
+                                        .map(|f| f.display().to_string())
+                                        .collect::<Vec<_>>()
+                                        .join(", ")
+                                );
+                            }
                         }
                         crate::graphite_agent::OperationResult::AuthFailure { details } => {
                             eprintln!("[daemon] Graphite auth failure: {}", details);
@@ -2729,6 +4486,9 @@ pub fn execute_actions(
             }
             DaemonAction::ShutdownComplete => {
                 eprintln!("[daemon] Daemon shutdown complete");
+                for (_, run) in daemon_state.fast_verify.drain() {
+                    run.processes.kill_all();
+                }
                 if !config.dry_run {
                     let interrupted_reason = "interrupted by daemon shutdown";
                     if let Ok(open_runs) = service.store.list_open_runs() {
@@ -2776,6 +4536,7 @@ pub fn execute_actions(
         }
     }
 
+    flush_pipeline_events(service, daemon_state);
     should_exit
 }
 
@@ -2796,10 +4557,11 @@ mod tests {
     use crate::event_log::JsonlEventLog;
     use crate::persistence::SqliteStore;
     use crate::scheduler::{Scheduler, SchedulerConfig};
+    use crate::stack_pipeline::checkpoint_path;
     use crate::supervisor::AgentSession;
     use chrono::Duration;
     use orch_core::events::{Event, EventKind};
-    use orch_core::types::{EventId, ModelKind, RepoId, SubmitMode, Task, TaskId};
+    use orch_core::types::{EventId, ModelKind, RepoId, SubmitMode, Task, TaskId, TaskPriority};
     use orch_graphite::{GraphiteCli, GraphiteClient, GraphiteError};
     use std::fs;
     use std::path::PathBuf;
@@ -2819,16 +4581,7 @@ mod tests {
         let svc = OrchdService::new(
             store,
             JsonlEventLog::new(dir),
-            Scheduler::new(SchedulerConfig {
-                per_repo_limit: 10,
-                per_model_limit: vec![
-                    (ModelKind::Claude, 10),
-                    (ModelKind::Codex, 10),
-                    (ModelKind::Gemini, 10),
-                ]
-                .into_iter()
-                .collect(),
-            }),
+            Scheduler::new(SchedulerConfig::uniform(10, 10)),
         );
         svc.bootstrap().expect("bootstrap");
         svc
@@ -2848,6 +4601,19 @@ mod tests {
             dry_run: false,
             agent_timeout_secs: 1_800,
             drain_timeout_secs: 30,
+            post_merge_hook: None,
+            retry_guard: RetryGuardConfig::default(),
+            step_timeouts: StepTimeouts::default(),
+            question_stale_secs: 1_800,
+            merge_poll: MergePollConfig::default(),
+            auto_resolve_conflicts: false,
+            auto_approve_min_confidence: None,
+            pipeline_checkpoints: false,
+            max_spawns_per_tick: 2,
+            verify_limits: VerifyLimitsConfig::default(),
+            skip_fast_verify: false,
+            fast_verify_commands: Vec::new(),
+            generate_qa_specs: false,
         }
     }
 
@@ -2889,6 +4655,9 @@ mod tests {
             patch_ready: false,
             needs_human: false,
             signal_at: None,
+            confidence: None,
+            last_input_at: std::time::Instant::now(),
+            last_output_at: std::time::Instant::now(),
         });
     }
 
@@ -3030,6 +4799,100 @@ monthly_token_limit = {monthly_token_limit}
         assert_eq!(spawn_count, 1);
     }
 
+    #[test]
+    fn queued_chat_message_is_forwarded_to_interactive_session() {
+        let repo_root = std::env::temp_dir().join(format!(
+            "othala-chat-inbox-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&repo_root).expect("create repo root");
+        let task_id = TaskId::new("T-chat");
+        let mut supervisor = AgentSupervisor::new(ModelKind::Claude);
+        let child = Command::new("sleep")
+            .arg("60")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("spawn sleep session");
+        let (_out_tx, out_rx) = mpsc::channel();
+        let (in_tx, in_rx) = mpsc::channel();
+        supervisor.insert_session_for_test(AgentSession {
+            child,
+            output_rx: out_rx,
+            input_tx: Some(in_tx),
+            task_id: task_id.clone(),
+            model: ModelKind::Claude,
+            started_at: Utc::now(),
+            timeout: StdDuration::from_secs(1_800),
+            patch_ready: false,
+            needs_human: false,
+            signal_at: None,
+            confidence: None,
+            last_input_at: std::time::Instant::now(),
+            last_output_at: std::time::Instant::now(),
+        });
+
+        let service = mk_service();
+        let queued =
+            chat_control::enqueue_chat_message(&repo_root, &task_id, "please add tests", false)
+                .expect("enqueue");
+        let actions = deliver_queued_chat_messages(&service, &mut supervisor, &repo_root);
+
+        assert_eq!(in_rx.try_recv().expect("forwarded"), "please add tests");
+        assert!(matches!(
+            actions.as_slice(),
+            [DaemonAction::Log { message }] if message.contains("delivered")
+        ));
+        assert_eq!(
+            chat_control::take_chat_receipt(&repo_root, &queued.id).map(|r| r.delivery),
+            Some(chat_control::ChatDelivery::Delivered)
+        );
+        supervisor.stop_all();
+        let _ = fs::remove_dir_all(repo_root);
+    }
+
+    #[test]
+    fn undeliverable_chat_message_fails_and_leaves_questions_open() {
+        let repo_root = std::env::temp_dir().join(format!(
+            "othala-chat-undeliverable-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&repo_root).expect("create repo root");
+        let service = mk_service();
+        let task = mk_task("T-chat");
+        service
+            .create_task(&task, &mk_created_event(&task))
+            .expect("create");
+        service
+            .capture_questions(
+                &task.id,
+                &["[needs_human] which database?".to_string()],
+                Utc::now(),
+            )
+            .expect("capture question");
+        let mut supervisor = AgentSupervisor::new(ModelKind::Claude);
+
+        let queued = chat_control::enqueue_chat_message(&repo_root, &task.id, "postgres", false)
+            .expect("enqueue");
+        let actions = deliver_queued_chat_messages(&service, &mut supervisor, &repo_root);
+
+        assert!(matches!(
+            actions.as_slice(),
+            [DaemonAction::Log { message }] if message.contains("could not deliver")
+        ));
+        let receipt = chat_control::take_chat_receipt(&repo_root, &queued.id).expect("receipt");
+        assert!(matches!(
+            receipt.delivery,
+            chat_control::ChatDelivery::Failed { reason } if reason.contains("--spawn")
+        ));
+        assert!(receipt.answered.is_empty());
+        assert_eq!(
+            service.open_questions(&task.id).expect("questions").len(),
+            1
+        );
+        let _ = fs::remove_dir_all(repo_root);
+    }
+
     #[test]
     fn daemon_tick_creates_pipeline_for_ready_task() {
         let service = mk_service();
@@ -3127,6 +4990,7 @@ monthly_token_limit = {monthly_token_limit}
             needs_human: false,
             success: true,
             duration_secs: 5,
+            confidence: None,
         };
 
         let mut daemon_state = DaemonState::new();
@@ -3143,6 +5007,70 @@ monthly_token_limit = {monthly_token_limit}
             .any(|a| matches!(a, DaemonAction::MarkReady { .. })));
     }
 
+    #[test]
+    fn auto_approve_requires_minimum_confidence_when_configured() {
+        let service = mk_service();
+        let mut config = mk_config();
+        config.auto_approve_min_confidence = Some(75);
+
+        let completion = |id: &str, confidence: Option<u8>| {
+            let task = mk_task(id);
+            service
+                .create_task(&task, &mk_created_event(&task))
+                .expect("create");
+            service
+                .store
+                .insert_run(&crate::types::TaskRunRecord {
+                    run_id: format!("R-{id}"),
+                    task_id: task.id.clone(),
+                    repo_id: task.repo_id.clone(),
+                    model: ModelKind::Claude,
+                    started_at: Utc::now(),
+                    finished_at: None,
+                    stop_reason: None,
+                    exit_code: None,
+                    estimated_tokens: None,
+                    duration_secs: None,
+                    confidence: None,
+                })
+                .expect("insert run");
+            let outcome = AgentOutcome {
+                task_id: task.id.clone(),
+                model: ModelKind::Claude,
+                exit_code: Some(0),
+                patch_ready: true,
+                needs_human: false,
+                success: true,
+                duration_secs: 5,
+                confidence,
+            };
+            handle_agent_completion(
+                &service,
+                None,
+                &outcome,
+                &config,
+                &mut DaemonState::new(),
+                Utc::now(),
+            )
+        };
+
+        let confident = completion("T-CONF-HIGH", Some(90));
+        assert!(matches!(confident[..], [DaemonAction::MarkReady { .. }]));
+        let runs = service
+            .store
+            .list_runs_for_task(&TaskId::new("T-CONF-HIGH"))
+            .expect("list runs");
+        assert_eq!(runs[0].confidence, Some(90));
+
+        for (id, confidence) in [("T-CONF-LOW", Some(60)), ("T-CONF-NONE", None)] {
+            let actions = completion(id, confidence);
+            assert!(
+                matches!(actions[..], [DaemonAction::RecordNeedsHuman { .. }]),
+                "{id}: {actions:?}"
+            );
+        }
+    }
+
     #[test]
     fn handle_failed_outcome_evaluates_retry() {
         let service = mk_service();
@@ -3162,6 +5090,7 @@ monthly_token_limit = {monthly_token_limit}
             needs_human: false,
             success: false,
             duration_secs: 5,
+            confidence: None,
         };
 
         let mut daemon_state = DaemonState::new();
@@ -3243,6 +5172,76 @@ monthly_token_limit = {monthly_token_limit}
         assert_eq!(state.budget_last_reset_day, Some(now.day()));
     }
 
+    struct SleepAdapter;
+
+    impl orch_agents::AgentAdapter for SleepAdapter {
+        fn model(&self) -> ModelKind {
+            ModelKind::Claude
+        }
+
+        fn build_command(&self, _request: &orch_agents::EpochRequest) -> orch_agents::AgentCommand {
+            orch_agents::AgentCommand {
+                executable: "sleep".to_string(),
+                args: vec!["30".to_string()],
+                env: vec![],
+            }
+        }
+    }
+
+    fn sleep_adapter(
+        _model: ModelKind,
+    ) -> Result<Box<dyn orch_agents::AgentAdapter>, orch_agents::AgentError> {
+        Ok(Box::new(SleepAdapter))
+    }
+
+    #[test]
+    fn run_tick_caps_new_spawns_per_tick_by_priority() {
+        let service = mk_service();
+        let mut config = mk_config();
+        config.skip_qa = true;
+        config.skip_context_regen = true;
+        config.max_spawns_per_tick = 3;
+        let mut supervisor =
+            AgentSupervisor::new(ModelKind::Claude).with_adapter_factory(sleep_adapter);
+        let mut daemon_state = DaemonState::new();
+        let worktree = std::env::temp_dir().join(format!(
+            "othala-spawn-ramp-{}-{}",
+            std::process::id(),
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&worktree).expect("create worktree");
+
+        for i in 0..10 {
+            let mut task = mk_task(&format!("T-{i}"));
+            task.worktree_path = worktree.clone();
+            if i == 7 {
+                task.priority = TaskPriority::Critical;
+            }
+            service
+                .create_task(&task, &mk_created_event(&task))
+                .expect("create");
+        }
+
+        run_tick(&service, &mut supervisor, &mut daemon_state, &config);
+        assert_eq!(supervisor.running_count(), 3);
+        assert!(supervisor.has_session(&TaskId::new("T-7")));
+
+        // The rest are picked up on later ticks; running agents stay put.
+        let next = daemon_tick(&service, &mut supervisor, &mut daemon_state, &config)
+            .into_iter()
+            .filter_map(|action| match action {
+                DaemonAction::SpawnAgent { task_id, .. } => Some(task_id),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(next.len(), 3);
+        assert!(next.iter().all(|task_id| !supervisor.has_session(task_id)));
+        assert_eq!(supervisor.running_count(), 3);
+
+        supervisor.terminate_all_agents();
+        let _ = fs::remove_dir_all(&worktree);
+    }
+
     #[test]
     fn daemon_tick_refuses_spawn_when_budget_exceeded() {
         let service = mk_service();
@@ -3310,6 +5309,7 @@ monthly_token_limit = {monthly_token_limit}
             needs_human: false,
             success: true,
             duration_secs: 1,
+            confidence: None,
         };
 
         let _ = handle_agent_completion(
@@ -3356,6 +5356,59 @@ monthly_token_limit = {monthly_token_limit}
             .any(|a| matches!(a, DaemonAction::SpawnAgent { .. })));
     }
 
+    #[test]
+    fn daemon_tick_skips_spawn_while_paused() {
+        let service = mk_service();
+        let config = mk_config();
+        let mut supervisor = AgentSupervisor::new(ModelKind::Claude);
+        let mut daemon_state = DaemonState::new();
+
+        let task = mk_task("T-PAUSE-1");
+        service
+            .create_task(&task, &mk_created_event(&task))
+            .expect("create");
+
+        daemon_state.paused = true;
+        let actions = daemon_tick(&service, &mut supervisor, &mut daemon_state, &config);
+        assert!(!actions
+            .iter()
+            .any(|a| matches!(a, DaemonAction::SpawnAgent { .. })));
+
+        daemon_state.paused = false;
+        let actions = daemon_tick(&service, &mut supervisor, &mut daemon_state, &config);
+        assert!(actions
+            .iter()
+            .any(|a| matches!(a, DaemonAction::SpawnAgent { .. })));
+    }
+
+    #[test]
+    fn daemon_tick_skips_spawn_while_fast_verify_runs() {
+        let service = mk_service();
+        let config = mk_config();
+        let mut supervisor = AgentSupervisor::new(ModelKind::Claude);
+        let mut daemon_state = DaemonState::new();
+
+        let task = mk_task("T-FAST-PENDING");
+        service
+            .create_task(&task, &mk_created_event(&task))
+            .expect("create");
+
+        let (_tx, result_rx) = mpsc::channel();
+        daemon_state.fast_verify.insert(
+            task.id.0.clone(),
+            FastVerifyRun {
+                model: ModelKind::Codex,
+                processes: Arc::new(StepProcesses::default()),
+                result_rx,
+            },
+        );
+        let actions = daemon_tick(&service, &mut supervisor, &mut daemon_state, &config);
+        assert!(!actions
+            .iter()
+            .any(|a| matches!(a, DaemonAction::SpawnAgent { .. })));
+        assert!(daemon_state.fast_verify.contains_key(&task.id.0));
+    }
+
     #[test]
     fn shutdown_complete_when_no_agents_running() {
         let service = mk_service();
@@ -3453,6 +5506,38 @@ monthly_token_limit = {monthly_token_limit}
         fs::remove_dir_all(config_dir).ok();
     }
 
+    #[test]
+    fn touching_othalaignore_reloads_ignore_rules() {
+        let mut daemon_state = DaemonState::new();
+        let repo_root = std::env::temp_dir().join(format!(
+            "othala-aux-reload-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&repo_root).expect("create repo root");
+        let ignore_path = repo_root.join(".othalaignore");
+        fs::write(&ignore_path, "target/\n").expect("write ignore file");
+
+        assert!(check_auxiliary_reload(&repo_root, &mut daemon_state).is_empty());
+        assert!(daemon_state.ignore_rules.is_ignored("target/debug/app"));
+        assert!(check_auxiliary_reload(&repo_root, &mut daemon_state).is_empty());
+
+        fs::write(&ignore_path, "target/\nsecrets/\n").expect("rewrite ignore file");
+        fs::File::options()
+            .write(true)
+            .open(&ignore_path)
+            .and_then(|file| {
+                file.set_modified(std::time::SystemTime::now() + StdDuration::from_secs(5))
+            })
+            .expect("bump mtime");
+        assert_eq!(
+            check_auxiliary_reload(&repo_root, &mut daemon_state),
+            vec!["ignore_rules".to_string()]
+        );
+        assert!(daemon_state.ignore_rules.is_ignored("secrets/key.pem"));
+
+        fs::remove_dir_all(repo_root).ok();
+    }
+
     #[test]
     fn config_reload_handles_missing_file() {
         let mut daemon_state = DaemonState::new();
@@ -3753,6 +5838,131 @@ monthly_token_limit = {monthly_token_limit}
         assert_eq!(updated.state, TaskState::Ready);
         assert!(daemon_state.restack_retries.contains_key(&task.id.0));
         assert!(daemon_state.pipelines.contains_key(&task.id.0));
+        assert!(!updated.restack_conflict);
+    }
+
+    #[test]
+    fn restack_conflict_is_recorded_on_task_until_restack_completes() {
+        let service = mk_service();
+        let mut daemon_state = DaemonState::new();
+        let task = mk_task("T-RS-CONFLICT");
+        service
+            .create_task(&task, &mk_created_event(&task))
+            .expect("create task");
+
+        let graphite = GraphiteClient::with_cli(
+            PathBuf::from("."),
+            GraphiteCli::new("/definitely/missing/gt"),
+        );
+        let error = GraphiteError::CommandFailed {
+            command: "gt move --onto task/T-parent".to_string(),
+            status: Some(1),
+            stdout: "CONFLICT (content): Merge conflict in src/lib.rs".to_string(),
+            stderr: String::new(),
+        };
+        let now = Utc::now();
+        assert!(handle_restack_graphite_playbook(
+            &service,
+            &mut daemon_state,
+            &task.id,
+            "task/T-parent",
+            &error,
+            &graphite,
+            now,
+            now.timestamp_nanos_opt().unwrap_or_default(),
+        ));
+        let conflicted = service.task(&task.id).expect("load task").expect("task exists");
+        assert!(conflicted.restack_conflict);
+
+        service
+            .start_restack(&task.id, EventId("E-RS-AGAIN".to_string()), now)
+            .expect("start restack");
+        let restacked = service
+            .complete_restack(&task.id, EventId("E-RS-DONE".to_string()), now)
+            .expect("complete restack");
+        assert!(!restacked.restack_conflict);
+    }
+
+    #[test]
+    fn auto_resolve_restack_conflict_needs_human_with_files_once_attempts_are_spent() {
+        let (repo, _) = init_git_repo_with_commit();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args([
+                    "-c",
+                    "user.name=Othala Tests",
+                    "-c",
+                    "user.email=tests@example.com",
+                ])
+                .args(args)
+                .current_dir(&repo)
+                .status()
+                .expect("git")
+        };
+        assert!(git(&["checkout", "-q", "-B", "main"]).success());
+        assert!(git(&["checkout", "-q", "-b", "side"]).success());
+        fs::write(repo.join("README.md"), "side\n").expect("write side");
+        assert!(git(&["commit", "-q", "-am", "side"]).success());
+        assert!(git(&["checkout", "-q", "main"]).success());
+        fs::write(repo.join("README.md"), "main\n").expect("write main");
+        assert!(git(&["commit", "-q", "-am", "main"]).success());
+        assert!(!git(&["merge", "-q", "side"]).success());
+
+        let service = mk_service();
+        let mut config = mk_config();
+        config.auto_resolve_conflicts = true;
+        let mut daemon_state = DaemonState::new();
+        let mut task = mk_task("T-RS-AUTO");
+        task.worktree_path = repo.clone();
+        service
+            .create_task(&task, &mk_created_event(&task))
+            .expect("create task");
+        for _ in 0..2 {
+            daemon_state
+                .sisyphus_recovery
+                .start_conflict_attempt(&task.id.0, &[]);
+            daemon_state.sisyphus_recovery.mark_failure(&task.id.0);
+        }
+
+        let graphite =
+            GraphiteClient::with_cli(repo.clone(), GraphiteCli::new("/definitely/missing/gt"));
+        let now = Utc::now();
+        auto_resolve_restack_conflict(
+            &service,
+            &config,
+            &mut daemon_state,
+            &task.id,
+            &repo,
+            "task/T-parent",
+            &graphite,
+            now,
+            now.timestamp_nanos_opt().unwrap_or_default(),
+        );
+
+        let updated = service
+            .task(&task.id)
+            .expect("load task")
+            .expect("task exists");
+        assert_eq!(updated.state, TaskState::Stopped);
+        assert!(updated.restack_conflict);
+        let events = service.task_events(&task.id).expect("events");
+        assert!(events
+            .iter()
+            .any(|event| matches!(event.kind, EventKind::RestackConflict)));
+        let reason = events
+            .iter()
+            .find_map(|event| match &event.kind {
+                EventKind::NeedsHuman { reason } => Some(reason.clone()),
+                _ => None,
+            })
+            .expect("needs human event");
+        assert!(reason.contains("conflicted files: README.md"), "{reason}");
+        assert!(daemon_state
+            .sisyphus_recovery
+            .get_state(&task.id.0)
+            .is_none());
+
+        let _ = fs::remove_dir_all(repo);
     }
 
     /// Helper: create a DaemonConfig whose repo_root has a `.othala/qa/baseline.md`.
@@ -3782,6 +5992,19 @@ monthly_token_limit = {monthly_token_limit}
             dry_run: false,
             agent_timeout_secs: 1_800,
             drain_timeout_secs: 30,
+            post_merge_hook: None,
+            retry_guard: RetryGuardConfig::default(),
+            step_timeouts: StepTimeouts::default(),
+            question_stale_secs: 1_800,
+            merge_poll: MergePollConfig::default(),
+            auto_resolve_conflicts: false,
+            auto_approve_min_confidence: None,
+            pipeline_checkpoints: false,
+            max_spawns_per_tick: 2,
+            verify_limits: VerifyLimitsConfig::default(),
+            skip_fast_verify: false,
+            fast_verify_commands: Vec::new(),
+            generate_qa_specs: false,
         };
         (config, tmp)
     }
@@ -3907,6 +6130,7 @@ monthly_token_limit = {monthly_token_limit}
             needs_human: false,
             success: true,
             duration_secs: 5,
+            confidence: None,
         };
 
         let mut daemon_state = DaemonState::new();
@@ -3934,6 +6158,54 @@ monthly_token_limit = {monthly_token_limit}
         fs::remove_dir_all(&tmp).ok();
     }
 
+    #[test]
+    fn generated_qa_spec_drives_validation_when_no_spec_exists() {
+        let tmp = std::env::temp_dir().join(format!(
+            "othala-daemon-qa-generated-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let mut config = mk_config();
+        config.repo_root = tmp.clone();
+        let service = mk_service();
+        let task = mk_task("T-QA-GEN");
+        service
+            .create_task(&task, &mk_created_event(&task))
+            .expect("create");
+        let task_id = TaskId::new("T-QA-GEN");
+        assert!(matches!(
+            validation_action(&service, &config, &task_id),
+            DaemonAction::MarkReady { .. }
+        ));
+
+        let generated = parse_qa_spec("## CLI\n- `othala list --json` exits 0\n");
+        crate::qa_spec_gen::save_generated_qa_spec(&tmp, &task_id, &generated)
+            .expect("save generated spec");
+        assert!(matches!(
+            validation_action(&service, &config, &task_id),
+            DaemonAction::SpawnQA {
+                qa_type: QAType::Validation,
+                ..
+            }
+        ));
+        let run_spec = resolve_qa_spec(&tmp, &task_id, QAType::Validation).expect("generated spec");
+        assert_eq!(run_spec.spec, generated);
+        assert_eq!(run_spec.task_spec, None);
+        assert_eq!(run_spec.label, "generated");
+        assert!(resolve_qa_spec(&tmp, &task_id, QAType::Baseline).is_none());
+
+        fs::write(
+            tmp.join(".othala/qa/baseline.md"),
+            "## Build\n- run cargo build\n",
+        )
+        .expect("write baseline");
+        let run_spec = resolve_qa_spec(&tmp, &task_id, QAType::Validation).expect("baseline spec");
+        assert_eq!(run_spec.spec.tests[0].name, "run_cargo_build");
+        assert_eq!(run_spec.task_spec, Some(generated.raw));
+        assert_eq!(run_spec.label, "generated");
+
+        fs::remove_dir_all(&tmp).ok();
+    }
+
     #[test]
     fn qa_validation_pass_produces_completed_and_mark_ready_actions() {
         // When QA validation completes with all tests passing, the daemon
@@ -4093,6 +6365,7 @@ monthly_token_limit = {monthly_token_limit}
             needs_human: true,
             success: false,
             duration_secs: 5,
+            confidence: None,
         };
 
         let mut daemon_state = DaemonState::new();
@@ -4109,6 +6382,81 @@ monthly_token_limit = {monthly_token_limit}
             .any(|a| matches!(a, DaemonAction::RecordNeedsHuman { .. })));
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn submit_output_populates_task_pr() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!(
+            "othala-fake-gt-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&dir).expect("create dir");
+        let fake_gt = dir.join("gt");
+        fs::write(
+            &fake_gt,
+            "#!/bin/sh\n\
+             echo 'task/T-PARENT: https://app.graphite.dev/github/pr/acme/app/7 (updated)'\n\
+             echo 'task/T-PR: https://app.graphite.dev/github/pr/acme/app/8 (created)'\n",
+        )
+        .expect("write fake gt");
+        fs::set_permissions(&fake_gt, fs::Permissions::from_mode(0o755)).expect("chmod");
+
+        let service = mk_service();
+        let task = mk_task("T-PR");
+        service
+            .create_task(&task, &mk_created_event(&task))
+            .expect("create task");
+
+        let graphite = GraphiteClient::with_cli(dir.clone(), GraphiteCli::new(&fake_gt));
+        let prs = graphite.submit(SubmitMode::Stack).expect("fake submit");
+        let pr = select_submitted_pr(prs, "task/T-PR");
+        let updated = complete_submit_with_pr(
+            &service,
+            &task.id,
+            pr,
+            EventId("E-SUBMIT-DONE-T-PR".to_string()),
+            Utc::now(),
+        )
+        .expect("complete submit");
+
+        assert_eq!(updated.state, TaskState::AwaitingMerge);
+        let pr = updated.pr.expect("pr recorded");
+        assert_eq!(pr.number, 8);
+        assert_eq!(pr.url, "https://app.graphite.dev/github/pr/acme/app/8");
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn submit_skips_branch_with_open_pr() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!(
+            "othala-fake-gt-open-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&dir).expect("create dir");
+        let fake_gt = dir.join("gt");
+        fs::write(
+            &fake_gt,
+            "#!/bin/sh\n\
+             if [ \"$1\" = submit ]; then echo 'duplicate submit' >&2; exit 1; fi\n\
+             echo 'PR #9 (Open) Existing work'\n\
+             echo 'https://app.graphite.dev/github/pr/acme/app/9'\n",
+        )
+        .expect("write fake gt");
+        fs::set_permissions(&fake_gt, fs::Permissions::from_mode(0o755)).expect("chmod");
+
+        let graphite = GraphiteClient::with_cli(dir.clone(), GraphiteCli::new(&fake_gt));
+        let pr = submit_unless_open(&graphite, SubmitMode::Single, "task/T-OPEN", &dir)
+            .expect("open PR skips submit")
+            .expect("existing PR returned");
+        assert_eq!(pr.number, 9);
+        assert_eq!(pr.url, "https://app.graphite.dev/github/pr/acme/app/9");
+        fs::remove_dir_all(dir).ok();
+    }
+
     #[test]
     fn check_pr_merged_parses_state() {
         assert!(is_gh_pr_state_merged(b"MERGED\n"));
@@ -4143,6 +6491,131 @@ monthly_token_limit = {monthly_token_limit}
         assert_eq!(updated.state, TaskState::Merged);
     }
 
+    #[test]
+    fn poll_awaiting_merge_detects_merged_branch_and_flags_stale_once() {
+        let (repo, _) = init_git_repo_with_commit();
+        for args in [
+            &["checkout", "-q", "-B", "main"][..],
+            &["branch", "task/T-POLL-MERGED"],
+        ] {
+            let status = Command::new("git")
+                .args(args)
+                .current_dir(&repo)
+                .status()
+                .expect("git");
+            assert!(status.success(), "git {args:?} should succeed");
+        }
+        let mut config = mk_config();
+        config.repo_root = repo.clone();
+        config.merge_poll = MergePollConfig {
+            min_age_secs: 600,
+            interval_secs: 300,
+            stale_secs: 86_400,
+            github_api: false,
+        };
+        let mut daemon_state = DaemonState::new();
+        let now = Utc::now();
+
+        let mut merged = mk_task("T-POLL-MERGED");
+        merged.state = TaskState::AwaitingMerge;
+        merged.branch_name = Some("task/T-POLL-MERGED".to_string());
+        merged.updated_at = now - Duration::seconds(900);
+        let mut stuck = mk_task("T-POLL-STUCK");
+        stuck.state = TaskState::AwaitingMerge;
+        stuck.branch_name = Some("task/T-POLL-STUCK".to_string());
+        stuck.updated_at = now - Duration::seconds(2 * 86_400);
+        let awaiting = vec![merged, stuck];
+
+        let actions = poll_awaiting_merge(&mut daemon_state, &config, &awaiting, &[], now);
+        assert!(actions.iter().any(|a| matches!(
+            a,
+            DaemonAction::EmitEvent {
+                kind: EventKind::MergeDetected { method },
+                ..
+            } if method == "ancestor"
+        )));
+        assert!(actions.iter().any(|a| matches!(
+            a,
+            DaemonAction::MarkMerged { task_id } if task_id.0 == "T-POLL-MERGED"
+        )));
+        assert!(actions.iter().any(|a| matches!(
+            a,
+            DaemonAction::RecordNeedsHuman { task_id, reason }
+                if task_id.0 == "T-POLL-STUCK" && reason.contains("stuck in review")
+        )));
+
+        // Merges already found this tick are left alone, and the stuck task
+        // is not flagged again.
+        let pending = vec![DaemonAction::MarkMerged {
+            task_id: TaskId::new("T-POLL-MERGED"),
+        }];
+        let later = now + Duration::seconds(600);
+        let actions = poll_awaiting_merge(&mut daemon_state, &config, &awaiting, &pending, later);
+        assert!(actions.is_empty(), "unexpected actions: {actions:?}");
+
+        let _ = fs::remove_dir_all(repo);
+    }
+
+    #[test]
+    fn mark_merged_runs_post_merge_hook_with_task_env() {
+        let service = mk_service();
+        let mut supervisor = AgentSupervisor::new(ModelKind::Claude);
+        let mut daemon_state = DaemonState::new();
+        let repo_root = std::env::temp_dir().join(format!(
+            "othala-post-merge-hook-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&repo_root).expect("create repo root");
+        let marker = repo_root.join("hook.out");
+        let mut config = mk_config();
+        config.repo_root = repo_root.clone();
+        config.post_merge_hook = Some(format!(
+            "echo \"$OTHALA_HOOK:$OTHALA_TASK_ID\" >> {}",
+            marker.display()
+        ));
+
+        let mut task = mk_task("T-HOOK-1");
+        task.state = TaskState::AwaitingMerge;
+        service
+            .create_task(&task, &mk_created_event(&task))
+            .expect("create task");
+
+        let actions = vec![DaemonAction::MarkMerged {
+            task_id: task.id.clone(),
+        }];
+        execute_actions(&actions, &service, &mut supervisor, &mut daemon_state, &config);
+        // A second merge signal must not re-run the hook.
+        execute_actions(&actions, &service, &mut supervisor, &mut daemon_state, &config);
+
+        let output = fs::read_to_string(&marker).expect("hook output");
+        assert_eq!(output, "post_merge:T-HOOK-1\n");
+        let _ = fs::remove_dir_all(repo_root);
+    }
+
+    #[test]
+    fn failing_post_merge_hook_is_non_fatal() {
+        let service = mk_service();
+        let mut supervisor = AgentSupervisor::new(ModelKind::Claude);
+        let mut daemon_state = DaemonState::new();
+        let mut config = mk_config();
+        config.repo_root = std::env::temp_dir();
+        config.post_merge_hook = Some("exit 3".to_string());
+
+        let mut task = mk_task("T-HOOK-2");
+        task.state = TaskState::AwaitingMerge;
+        service
+            .create_task(&task, &mk_created_event(&task))
+            .expect("create task");
+
+        let actions = vec![DaemonAction::MarkMerged {
+            task_id: task.id.clone(),
+        }];
+        execute_actions(&actions, &service, &mut supervisor, &mut daemon_state, &config);
+
+        let updated = service.task(&task.id).expect("load task").expect("task exists");
+        assert_eq!(updated.state, TaskState::Merged);
+    }
+
     #[test]
     fn build_spawn_action_injects_qa_failure_context_on_retry() {
         let config = mk_config();
@@ -4238,6 +6711,39 @@ monthly_token_limit = {monthly_token_limit}
         assert!(!supervisor.has_session(&task_id));
     }
 
+    #[test]
+    fn rate_limited_spawn_is_deferred() {
+        let service = mk_service();
+        let config = mk_config();
+        let mut supervisor = AgentSupervisor::new(ModelKind::Claude);
+        let mut daemon_state = DaemonState::new();
+        let limits = crate::rate_limiter::RateLimitConfig {
+            burst_size: 1,
+            ..Default::default()
+        };
+        daemon_state.spawn_rate_limiter = RateLimiter::new(limits);
+        assert!(daemon_state
+            .spawn_rate_limiter
+            .try_acquire("model:claude", Utc::now())
+            .is_ok());
+
+        let task = mk_task("T-RATE-1");
+        service
+            .create_task(&task, &mk_created_event(&task))
+            .expect("create task");
+        let task_id = task.id.clone();
+        let actions = vec![DaemonAction::SpawnAgent {
+            task_id: task_id.clone(),
+            model: ModelKind::Claude,
+            prompt: "rate-limited agent".to_string(),
+            worktree_path: task.worktree_path,
+        }];
+
+        execute_actions(&actions, &service, &mut supervisor, &mut daemon_state, &config);
+
+        assert!(!supervisor.has_session(&task_id));
+    }
+
     #[test]
     fn dry_run_still_logs() {
         let service = mk_service();
@@ -4385,6 +6891,332 @@ monthly_token_limit = {monthly_token_limit}
         fs::remove_dir_all(&repo).ok();
     }
 
+    #[test]
+    fn verify_command_timeout_is_retried_and_counted_separately() {
+        let service = mk_service();
+        let (repo, _) = init_git_repo_with_commit();
+        let mut config = mk_config();
+        config.repo_root = repo.clone();
+        config.verify_command = Some("echo waiting; sleep 30".to_string());
+        config.verify_limits.tiers.insert(
+            "branch".to_string(),
+            orch_core::config::VerifyTierLimits {
+                timeout_secs: Some(3),
+                max_output_bytes: None,
+            },
+        );
+        let mut supervisor = AgentSupervisor::new(ModelKind::Claude);
+        let mut daemon_state = DaemonState::new();
+        let task_id = TaskId::new("T-VERIFY-TIMEOUT");
+
+        let mut task = mk_task("T-VERIFY-TIMEOUT");
+        task.state = TaskState::Ready;
+        task.worktree_path = repo.clone();
+        task.preferred_model = Some(ModelKind::Claude);
+        service
+            .create_task(&task, &mk_created_event(&task))
+            .expect("create task");
+        daemon_state.pipelines.insert(
+            task_id.0.clone(),
+            PipelineState::new(
+                task_id.clone(),
+                "task/T-VERIFY-TIMEOUT".to_string(),
+                repo.clone(),
+                SubmitMode::Single,
+                None,
+            ),
+        );
+
+        let actions = vec![DaemonAction::ExecutePipeline {
+            action: PipelineAction::RunVerify {
+                task_id: task_id.clone(),
+                worktree_path: repo.clone(),
+            },
+        }];
+        let started = std::time::Instant::now();
+        execute_actions(&actions, &service, &mut supervisor, &mut daemon_state, &config);
+
+        assert!(started.elapsed() < std::time::Duration::from_secs(20));
+        let snapshot = &daemon_state.orchestration_metrics.current_snapshot;
+        assert_eq!(snapshot.verify_timeouts, 1);
+        assert_eq!(snapshot.verify_failed, 0);
+        let task = service.task(&task_id).expect("load").expect("task");
+        assert_eq!(task.state, TaskState::Chatting);
+        assert_eq!(task.retry_count, 1);
+        let reason = task.last_failure_reason.expect("failure reason");
+        assert!(reason.contains("timed out after 3s"), "{reason}");
+        assert!(reason.contains("waiting"), "{reason}");
+        fs::remove_dir_all(&repo).ok();
+    }
+
+    #[test]
+    fn failing_fast_verify_keeps_the_task_chatting() {
+        let service = mk_service();
+        let (repo, _) = init_git_repo_with_commit();
+        fs::write(repo.join("README.md"), "# changed\n").expect("edit readme");
+        let mut config = mk_config();
+        config.repo_root = repo.clone();
+        config.fast_verify_commands = vec!["echo lint broke; false".to_string()];
+        let mut supervisor = AgentSupervisor::new(ModelKind::Claude);
+        let mut daemon_state = DaemonState::new();
+
+        let mut task = mk_task("T-FAST-VERIFY");
+        task.state = TaskState::Chatting;
+        task.worktree_path = repo.clone();
+        service
+            .create_task(&task, &mk_created_event(&task))
+            .expect("create task");
+        let outcome = AgentOutcome {
+            task_id: task.id.clone(),
+            model: ModelKind::Codex,
+            exit_code: Some(0),
+            patch_ready: true,
+            needs_human: false,
+            success: true,
+            duration_secs: 5,
+            confidence: None,
+        };
+
+        let actions = handle_agent_completion(
+            &service,
+            None,
+            &outcome,
+            &config,
+            &mut daemon_state,
+            Utc::now(),
+        );
+        assert!(matches!(
+            actions.as_slice(),
+            [DaemonAction::RunFastVerify {
+                model: ModelKind::Codex,
+                changed_files: 1,
+                ..
+            }]
+        ));
+        execute_actions(
+            &actions,
+            &service,
+            &mut supervisor,
+            &mut daemon_state,
+            &config,
+        );
+        assert!(daemon_state.fast_verify.contains_key(&task.id.0));
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+        let actions = loop {
+            let actions = collect_fast_verify_results(&service, &config, &mut daemon_state);
+            if !actions.is_empty() || std::time::Instant::now() >= deadline {
+                break actions;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        };
+        assert!(matches!(
+            actions.as_slice(),
+            [DaemonAction::ScheduleRetry { next_model: ModelKind::Codex, reason, .. }]
+                if reason.starts_with("fast verify failed")
+        ));
+        assert!(daemon_state.fast_verify.is_empty());
+        execute_actions(
+            &actions,
+            &service,
+            &mut supervisor,
+            &mut daemon_state,
+            &config,
+        );
+
+        let updated = service.task(&task.id).expect("load").expect("task");
+        assert_eq!(updated.state, TaskState::Chatting);
+        assert_eq!(updated.retry_count, 1);
+        let log = agent_log::read_agent_log(&repo, &task.id).expect("agent log");
+        assert!(
+            log.contains("[fast verify] 1 changed file(s), 1 command(s)"),
+            "{log}"
+        );
+        assert!(
+            log.contains("[fast verify] failed: echo lint broke; false"),
+            "{log}"
+        );
+        assert!(log.contains("lint broke"), "{log}");
+
+        config.skip_fast_verify = true;
+        let actions = handle_agent_completion(
+            &service,
+            None,
+            &outcome,
+            &config,
+            &mut daemon_state,
+            Utc::now(),
+        );
+        assert!(matches!(
+            actions.as_slice(),
+            [DaemonAction::MarkReady { .. }]
+        ));
+        fs::remove_dir_all(&repo).ok();
+    }
+
+    #[test]
+    fn verify_step_exceeding_timeout_stops_task_for_human() {
+        let service = mk_service();
+        let (repo, _) = init_git_repo_with_commit();
+        let mut config = mk_config();
+        config.repo_root = repo.clone();
+        config.verify_command = Some("sleep 5".to_string());
+        config.step_timeouts.verify = std::time::Duration::from_millis(100);
+        let mut supervisor = AgentSupervisor::new(ModelKind::Claude);
+        let mut daemon_state = DaemonState::new();
+        let task_id = TaskId::new("T-STEP-TIMEOUT");
+
+        let mut task = mk_task("T-STEP-TIMEOUT");
+        task.state = TaskState::Ready;
+        task.worktree_path = repo.clone();
+        service
+            .create_task(&task, &mk_created_event(&task))
+            .expect("create task");
+        daemon_state.pipelines.insert(
+            task_id.0.clone(),
+            PipelineState::new(
+                task_id.clone(),
+                "task/T-STEP-TIMEOUT".to_string(),
+                repo.clone(),
+                SubmitMode::Single,
+                None,
+            )
+            .with_checkpoints(&repo),
+        );
+        assert!(checkpoint_path(&repo, &task_id).exists());
+
+        let actions = vec![DaemonAction::ExecutePipeline {
+            action: PipelineAction::RunVerify {
+                task_id: task_id.clone(),
+                worktree_path: repo.clone(),
+            },
+        }];
+        let started = std::time::Instant::now();
+        execute_actions(&actions, &service, &mut supervisor, &mut daemon_state, &config);
+
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+        assert!(!daemon_state.pipelines.contains_key(&task_id.0));
+        assert!(!checkpoint_path(&repo, &task_id).exists());
+        let task = service.task(&task_id).expect("load").expect("task");
+        assert_eq!(task.state, TaskState::Stopped);
+        assert_eq!(
+            task.last_failure_reason.as_deref(),
+            Some("step `verify_branch` timed out after 100ms")
+        );
+        let events = service.task_events(&task_id).expect("events");
+        assert!(events
+            .iter()
+            .any(|event| matches!(event.kind, EventKind::NeedsHuman { .. })));
+    }
+
+    #[test]
+    fn stale_agent_question_is_escalated_once() {
+        let service = mk_service();
+        let config = mk_config();
+        let task = mk_task("T-QUESTION");
+        service
+            .create_task(&task, &mk_created_event(&task))
+            .expect("create task");
+        let asked_at = Utc::now() - Duration::hours(1);
+        let lines = vec![
+            "Should the migration drop the legacy column?".to_string(),
+            "[needs_human]".to_string(),
+        ];
+        let asked = service
+            .capture_questions(&task.id, &lines, asked_at)
+            .expect("capture");
+        assert_eq!(asked.len(), 1);
+
+        let now = Utc::now();
+        escalate_stale_questions(&service, None, &config, now);
+        escalate_stale_questions(&service, None, &config, now);
+
+        let stale: Vec<_> = service
+            .task_events(&task.id)
+            .expect("events")
+            .into_iter()
+            .filter(|event| matches!(event.kind, EventKind::QuestionStale { .. }))
+            .collect();
+        assert_eq!(stale.len(), 1);
+        let questions = service.open_questions(&task.id).expect("questions");
+        assert_eq!(questions[0].escalated_at, Some(now));
+    }
+
+    #[test]
+    fn failed_retry_that_drops_passing_work_is_stopped_for_human() {
+        let service = mk_service();
+        let (repo, _) = init_git_repo_with_commit();
+        let mut config = mk_config();
+        config.repo_root = repo.clone();
+        config.verify_command = Some("false".to_string());
+        let mut supervisor = AgentSupervisor::new(ModelKind::Claude);
+        let mut daemon_state = DaemonState::new();
+        let task_id = TaskId::new("T-GUARD-1");
+
+        let mut task = mk_task("T-GUARD-1");
+        task.state = TaskState::Ready;
+        task.worktree_path = repo.clone();
+        task.retry_count = 1;
+        service
+            .create_task(&task, &mk_created_event(&task))
+            .expect("create task");
+
+        let prior_patch = "\
+diff --git a/README.md b/README.md
+--- a/README.md
++++ b/README.md
+@@ -1 +1,2 @@
+ # test
++cache layer
+diff --git a/src/cache.rs b/src/cache.rs
+--- /dev/null
++++ b/src/cache.rs
+@@ -0,0 +1 @@
++pub struct Cache;
+";
+        let passing = RunPatchMeta {
+            attempt: 0,
+            verify_passed: true,
+            flagged: false,
+        };
+        let prior = retry_guard::store_run_patch(
+            &service.store,
+            &repo,
+            &task_id,
+            &passing,
+            prior_patch,
+            Utc::now() - chrono::Duration::minutes(5),
+        )
+        .expect("store prior patch");
+        fs::write(repo.join("README.md"), "# rewritten\n").expect("rewrite readme");
+
+        let actions = vec![DaemonAction::ExecutePipeline {
+            action: PipelineAction::RunVerify {
+                task_id: task_id.clone(),
+                worktree_path: repo.clone(),
+            },
+        }];
+        execute_actions(&actions, &service, &mut supervisor, &mut daemon_state, &config);
+
+        let task = service.task(&task_id).expect("load").expect("task");
+        assert_eq!(task.state, TaskState::Stopped);
+        assert_eq!(task.retry_count, 1, "flagged retry must not schedule another attempt");
+        let reason = task.last_failure_reason.expect("failure reason");
+        assert!(reason.contains(&prior.path));
+
+        let events = service.task_events(&task_id).expect("events");
+        assert!(events
+            .iter()
+            .any(|e| matches!(&e.kind, EventKind::NeedsHuman { reason } if reason.contains("100%"))));
+        let candidates = service
+            .store
+            .list_artifacts_for_task(&task_id, retry_guard::CANDIDATE_PATCH_ARTIFACT_KIND)
+            .expect("candidates");
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].path, prior.path);
+        fs::remove_dir_all(&repo).ok();
+    }
+
     #[test]
     fn handle_agent_completion_invalidates_verify_cache_entry() {
         let service = mk_service();
@@ -4407,6 +7239,7 @@ monthly_token_limit = {monthly_token_limit}
             needs_human: false,
             success: true,
             duration_secs: 5,
+            confidence: None,
         };
 
         let _ = handle_agent_completion(
@@ -4421,18 +7254,112 @@ monthly_token_limit = {monthly_token_limit}
         assert!(!daemon_state.verify_cache.contains_key("T-VC-4"));
     }
 
+    #[test]
+    fn failed_agent_follows_classifier_suggestion() {
+        let service = mk_service();
+        let mut config = mk_config();
+        config.repo_root = std::env::temp_dir().join(format!(
+            "othala-suggested-action-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let mut daemon_state = DaemonState::new();
+        let failed = |id: &str| AgentOutcome {
+            task_id: TaskId::new(id),
+            model: ModelKind::Codex,
+            exit_code: Some(1),
+            patch_ready: false,
+            needs_human: false,
+            success: false,
+            duration_secs: 5,
+            confidence: None,
+        };
+
+        for (id, output) in [
+            ("T-SA-NET", "connection refused: timeout after 30s"),
+            ("T-SA-AUTH", "authentication failed: token expired, please run gt auth"),
+        ] {
+            let task = mk_task(id);
+            service
+                .create_task(&task, &mk_created_event(&task))
+                .expect("create");
+            agent_log::append_agent_output(&config.repo_root, &task.id, &[output.to_string()])
+                .expect("write agent log");
+        }
+
+        let actions = handle_agent_completion(
+            &service,
+            None,
+            &failed("T-SA-NET"),
+            &config,
+            &mut daemon_state,
+            Utc::now(),
+        );
+        assert!(actions.iter().any(|a| matches!(
+            a,
+            DaemonAction::ScheduleRetry { next_model: ModelKind::Codex, .. }
+        )));
+
+        let actions = handle_agent_completion(
+            &service,
+            None,
+            &failed("T-SA-AUTH"),
+            &config,
+            &mut daemon_state,
+            Utc::now(),
+        );
+        assert!(matches!(
+            actions.as_slice(),
+            [DaemonAction::RecordNeedsHuman { reason, .. }] if reason.contains("permission")
+        ));
+    }
+
     #[test]
     fn run_verify_command_wraps_with_nix_shell_when_set() {
         // With empty nix_shell, runs command directly.
-        let result = run_verify_command(Path::new("/tmp"), "true", "");
+        let limits = VerifyLimits::default();
+        let result = run_verify_command(Path::new("/tmp"), "true", "", limits);
         assert!(result.is_ok());
 
         // With nix_shell set, command is wrapped as "<shell> -c <cmd>".
         // Using "bash" as a stand-in since it understands "-c".
-        let result = run_verify_command(Path::new("/tmp"), "true", "bash");
+        let result = run_verify_command(Path::new("/tmp"), "true", "bash", limits);
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn failed_verify_command_saves_repro_bundle() {
+        let repo_root = std::env::temp_dir().join(format!(
+            "othala-verify-bundle-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&repo_root).expect("create repo root");
+        let task_id = TaskId::new("T-VB-1");
+
+        let failure = run_verify_command(
+            &repo_root,
+            "echo boom >&2; exit 3",
+            "",
+            VerifyLimits::default(),
+        )
+        .expect_err("verify fails");
+        assert_eq!(failure.exit_code, Some(3));
+        assert!(failure.failures.is_empty());
+        let saved = save_verify_failure_bundle(
+            &repo_root,
+            &task_id,
+            "echo boom >&2; exit 3",
+            &repo_root,
+            &failure,
+            Utc::now(),
+        )
+        .expect("bundle saved");
+
+        assert!(saved.starts_with(".othala/verify-failures/T-VB-1/"));
+        let bundle = verify_failure::load_bundle(&repo_root.join(&saved)).expect("load bundle");
+        assert_eq!(bundle.exit_code, Some(3));
+        assert_eq!(bundle.output_tail.last().map(String::as_str), Some("boom"));
+    }
+
     #[test]
     fn detect_nix_shell_returns_empty_for_missing_dir() {
         let result = detect_nix_shell(Path::new("/tmp/nonexistent-othala-detect-nix-test"));
@@ -29,6 +29,50 @@ impl std::fmt::Display for DelegationStrategy {
     }
 }
 
+/// Rough size of a subtask, see [`SubTaskSpec::estimated_complexity`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubTaskComplexity {
+    Small,
+    Medium,
+    Large,
+}
+
+impl SubTaskComplexity {
+    pub fn estimated_minutes(self) -> u32 {
+        match self {
+            SubTaskComplexity::Small => 15,
+            SubTaskComplexity::Medium => 45,
+            SubTaskComplexity::Large => 120,
+        }
+    }
+}
+
+impl std::fmt::Display for SubTaskComplexity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubTaskComplexity::Small => f.write_str("small"),
+            SubTaskComplexity::Medium => f.write_str("medium"),
+            SubTaskComplexity::Large => f.write_str("large"),
+        }
+    }
+}
+
+/// Words that usually mean a subtask spans a lot of code.
+const LARGE_WORK_KEYWORDS: &[&str] = &[
+    "refactor",
+    "rewrite",
+    "redesign",
+    "migrate",
+    "migration",
+    "overhaul",
+    "architecture",
+];
+/// Words that usually mean new behaviour plus the tests for it.
+const MEDIUM_WORK_KEYWORDS: &[&str] = &["implement", "add", "integrate", "test", "tests"];
+/// Words that usually mean a one-line change.
+const SMALL_WORK_KEYWORDS: &[&str] = &["typo", "rename", "comment", "docs", "bump", "wording"];
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SubTaskSpec {
     pub title: String,
@@ -40,6 +84,59 @@ pub struct SubTaskSpec {
     pub verify_command: Option<String>,
 }
 
+impl SubTaskSpec {
+    /// Size estimate from the description (the title if it is empty).
+    ///
+    /// Points are scored as follows, and 0 or less is small, 1-2 medium and
+    /// 3 or more large:
+    /// - length: +1 above 8 words, +2 above 30;
+    /// - +1 for more than one sentence;
+    /// - +2 for a large-work keyword (refactor, migrate, ...), +1 for a
+    ///   medium one (implement, add, test, ...), -1 for a small one (typo,
+    ///   rename, ...); each group counts once.
+    pub fn estimated_complexity(&self) -> SubTaskComplexity {
+        let text = if self.description.trim().is_empty() {
+            self.title.as_str()
+        } else {
+            self.description.as_str()
+        };
+        let words: Vec<String> = text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_ascii_lowercase)
+            .collect();
+        let has_any = |keywords: &[&str]| words.iter().any(|w| keywords.contains(&w.as_str()));
+
+        let mut score = match words.len() {
+            0..=8 => 0,
+            9..=30 => 1,
+            _ => 2,
+        };
+        let sentences = text
+            .split(['.', '!', '?', '\n'])
+            .filter(|sentence| !sentence.trim().is_empty())
+            .count();
+        if sentences > 1 {
+            score += 1;
+        }
+        if has_any(LARGE_WORK_KEYWORDS) {
+            score += 2;
+        }
+        if has_any(MEDIUM_WORK_KEYWORDS) {
+            score += 1;
+        }
+        if has_any(SMALL_WORK_KEYWORDS) {
+            score -= 1;
+        }
+
+        match score {
+            i32::MIN..=0 => SubTaskComplexity::Small,
+            1..=2 => SubTaskComplexity::Medium,
+            _ => SubTaskComplexity::Large,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DelegationPlan {
     pub parent_task_id: String,
@@ -159,14 +256,36 @@ impl DelegationPlan {
         Ok(waves)
     }
 
+    /// Sum of the subtasks' estimated minutes.
+    pub fn total_estimated_minutes(&self) -> u32 {
+        self.subtasks
+            .iter()
+            .map(|subtask| subtask.estimated_complexity().estimated_minutes())
+            .sum()
+    }
+
+    /// The plan with the size estimates alongside, for JSON output.
+    pub fn sized(&self) -> SizedDelegationPlan<'_> {
+        SizedDelegationPlan {
+            plan: self,
+            estimated_complexity: self
+                .subtasks
+                .iter()
+                .map(SubTaskSpec::estimated_complexity)
+                .collect(),
+            total_estimated_minutes: self.total_estimated_minutes(),
+        }
+    }
+
     pub fn summary(&self) -> String {
         let mut out = format!(
-            "Delegation plan for {}: {} subtask(s), strategy={}, max_parallel={}, fail_fast={}",
+            "Delegation plan for {}: {} subtask(s), strategy={}, max_parallel={}, fail_fast={}, estimated={}m",
             self.parent_task_id,
             self.subtasks.len(),
             self.strategy,
             self.max_parallel,
-            self.fail_fast
+            self.fail_fast,
+            self.total_estimated_minutes()
         );
 
         if let Some(timeout) = self.timeout_secs {
@@ -179,13 +298,31 @@ impl DelegationPlan {
             } else {
                 subtask.depends_on.join(", ")
             };
-            out.push_str(&format!("\n{}: {} (deps: {})", idx, subtask.title, deps));
+            let complexity = subtask.estimated_complexity();
+            out.push_str(&format!(
+                "\n{}: {} [{}, ~{}m] (deps: {})",
+                idx,
+                subtask.title,
+                complexity,
+                complexity.estimated_minutes(),
+                deps
+            ));
         }
 
         out
     }
 }
 
+/// [`DelegationPlan`] serialized with per-subtask size estimates, in subtask
+/// order.
+#[derive(Debug, Clone, Serialize)]
+pub struct SizedDelegationPlan<'a> {
+    #[serde(flatten)]
+    pub plan: &'a DelegationPlan,
+    pub estimated_complexity: Vec<SubTaskComplexity>,
+    pub total_estimated_minutes: u32,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum SubTaskStatus {
@@ -652,6 +789,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn short_typo_fix_is_small_and_multi_sentence_refactor_is_large() {
+        let mut typo = subtask("typo", &[]);
+        typo.description = "fix typo".to_string();
+        assert_eq!(typo.estimated_complexity(), SubTaskComplexity::Small);
+
+        let mut refactor = subtask("refactor", &[]);
+        refactor.description = "Refactor the persistence module into smaller files. \
+            Add tests covering the migrated queries."
+            .to_string();
+        assert_eq!(refactor.estimated_complexity(), SubTaskComplexity::Large);
+
+        let mut feature = subtask("feature", &[]);
+        feature.description = "Implement the retry flag".to_string();
+        assert_eq!(feature.estimated_complexity(), SubTaskComplexity::Medium);
+
+        let mut plan = DelegationPlan::new("T-parent");
+        plan.add_subtask(typo);
+        plan.add_subtask(refactor);
+        plan.add_subtask(feature);
+        assert_eq!(plan.total_estimated_minutes(), 15 + 120 + 45);
+
+        let summary = plan.summary();
+        assert!(summary.contains("estimated=180m"));
+        assert!(summary.contains("0: typo [small, ~15m]"));
+        assert!(summary.contains("1: refactor [large, ~120m]"));
+
+        let json = serde_json::to_value(plan.sized()).expect("serialize sized plan");
+        assert_eq!(json["parent_task_id"], "T-parent");
+        assert_eq!(json["estimated_complexity"][1], "large");
+        assert_eq!(json["total_estimated_minutes"], 180);
+    }
+
     #[test]
     fn new_plan_has_defaults() {
         let plan = DelegationPlan::new("T-parent");
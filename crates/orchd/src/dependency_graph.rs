@@ -1,14 +1,33 @@
 //! Dependency graph for task relationships.
 
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 
-use orch_core::types::{Task, TaskId};
+use chrono::{DateTime, Utc};
+use orch_core::types::{Task, TaskId, TaskPriority};
 
 /// Dependency graph structure.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DependencyGraph {
     pub parents_by_child: HashMap<TaskId, HashSet<TaskId>>,
     pub children_by_parent: HashMap<TaskId, HashSet<TaskId>>,
+    /// Priority and creation time per task, used to break ties in `topo_order`.
+    pub schedule_keys: HashMap<TaskId, (TaskPriority, DateTime<Utc>)>,
+}
+
+/// The graph is not a DAG; `cycle` lists the nodes along one loop.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("dependency cycle detected: {}", format_cycle(.cycle))]
+pub struct CycleError {
+    pub cycle: Vec<TaskId>,
+}
+
+fn format_cycle(cycle: &[TaskId]) -> String {
+    let mut path: Vec<&str> = cycle.iter().map(|id| id.0.as_str()).collect();
+    if let Some(first) = path.first().copied() {
+        path.push(first);
+    }
+    path.join(" -> ")
 }
 
 impl DependencyGraph {
@@ -16,7 +35,123 @@ impl DependencyGraph {
         Self {
             parents_by_child: HashMap::new(),
             children_by_parent: HashMap::new(),
+            schedule_keys: HashMap::new(),
+        }
+    }
+
+    /// Kahn's-algorithm order in which every task follows its dependencies.
+    ///
+    /// Among tasks ready at the same time, higher priority goes first, then
+    /// older tasks, then the lower id.
+    pub fn topo_order(&self) -> Result<Vec<TaskId>, CycleError> {
+        let mut nodes = self
+            .children_by_parent
+            .keys()
+            .cloned()
+            .collect::<HashSet<_>>();
+        nodes.extend(self.parents_by_child.keys().cloned());
+
+        let ready_key = |id: &TaskId| {
+            let (priority, created_at) = self
+                .schedule_keys
+                .get(id)
+                .copied()
+                .unwrap_or((TaskPriority::default(), DateTime::<Utc>::MIN_UTC));
+            (Reverse(priority), created_at, id.0.clone())
+        };
+        let mut remaining_parents = nodes
+            .iter()
+            .map(|id| {
+                let count = self.parents_by_child.get(id).map_or(0, HashSet::len);
+                (id.clone(), count)
+            })
+            .collect::<HashMap<_, _>>();
+        let mut ready = remaining_parents
+            .iter()
+            .filter(|(_, count)| **count == 0)
+            .map(|(id, _)| ready_key(id))
+            .collect::<BTreeSet<_>>();
+
+        let mut order = Vec::with_capacity(nodes.len());
+        while let Some(key) = ready.pop_first() {
+            let id = TaskId(key.2);
+            for child in self.children_by_parent.get(&id).into_iter().flatten() {
+                if let Some(count) = remaining_parents.get_mut(child) {
+                    *count -= 1;
+                    if *count == 0 {
+                        ready.insert(ready_key(child));
+                    }
+                }
+            }
+            order.push(id);
+        }
+
+        if order.len() < nodes.len() {
+            let cycle = self.detect_cycle().unwrap_or_else(|| {
+                let placed = order.iter().collect::<HashSet<_>>();
+                sorted_task_ids(
+                    nodes
+                        .iter()
+                        .filter(|id| !placed.contains(id))
+                        .cloned()
+                        .collect(),
+                )
+            });
+            return Err(CycleError { cycle });
+        }
+        Ok(order)
+    }
+
+    /// The first cycle found, as the nodes along it in traversal order.
+    ///
+    /// Nodes and edges are visited in id order, so the result is stable.
+    pub fn detect_cycle(&self) -> Option<Vec<TaskId>> {
+        let mut visited = HashSet::<TaskId>::new();
+        let mut stack = Vec::<TaskId>::new();
+        let mut roots = self
+            .children_by_parent
+            .keys()
+            .cloned()
+            .collect::<HashSet<_>>();
+        roots.extend(self.parents_by_child.keys().cloned());
+
+        for root in sorted_task_ids(roots) {
+            if !visited.contains(&root) {
+                if let Some(cycle) = self.find_cycle_from(root, &mut visited, &mut stack) {
+                    return Some(cycle);
+                }
+            }
+        }
+        None
+    }
+
+    fn find_cycle_from(
+        &self,
+        node: TaskId,
+        visited: &mut HashSet<TaskId>,
+        stack: &mut Vec<TaskId>,
+    ) -> Option<Vec<TaskId>> {
+        visited.insert(node.clone());
+        stack.push(node.clone());
+
+        let children = self
+            .children_by_parent
+            .get(&node)
+            .cloned()
+            .unwrap_or_default();
+        for child in sorted_task_ids(children) {
+            if let Some(start) = stack.iter().position(|id| *id == child) {
+                return Some(stack[start..].to_vec());
+            }
+            if !visited.contains(&child) {
+                if let Some(cycle) = self.find_cycle_from(child, visited, stack) {
+                    return Some(cycle);
+                }
+            }
         }
+
+        stack.pop();
+        None
     }
 }
 
@@ -31,6 +166,9 @@ pub fn build_dependency_graph(tasks: &[Task]) -> DependencyGraph {
     for task in tasks {
         graph.parents_by_child.entry(task.id.clone()).or_default();
         graph.children_by_parent.entry(task.id.clone()).or_default();
+        graph
+            .schedule_keys
+            .insert(task.id.clone(), (task.priority, task.created_at));
     }
 
     for task in tasks {
@@ -42,6 +180,23 @@ pub fn build_dependency_graph(tasks: &[Task]) -> DependencyGraph {
     graph
 }
 
+/// Like [`build_dependency_graph`], plus an edge from each `parent_task_id`.
+pub fn build_lineage_graph(tasks: &[Task]) -> DependencyGraph {
+    let mut graph = build_dependency_graph(tasks);
+    let task_ids = tasks
+        .iter()
+        .map(|task| task.id.clone())
+        .collect::<HashSet<_>>();
+
+    for task in tasks {
+        if let Some(parent) = &task.parent_task_id {
+            add_edge_if_valid(&mut graph, parent.clone(), task.id.clone(), &task_ids);
+        }
+    }
+
+    graph
+}
+
 /// Get tasks that need restacking when a parent task is updated.
 pub fn restack_descendants_for_parent(
     graph: &DependencyGraph,
@@ -218,4 +373,113 @@ mod tests {
         let unknown_targets = restack_descendants_for_parent(&graph, &TaskId("T9".to_string()));
         assert!(unknown_targets.is_empty());
     }
+
+    fn ids(ids: &[&str]) -> Vec<TaskId> {
+        ids.iter().map(|id| TaskId((*id).to_string())).collect()
+    }
+
+    #[test]
+    fn detect_cycle_none_for_acyclic_graph() {
+        let graph = build_dependency_graph(&[
+            mk_task("T1", &[]),
+            mk_task("T2", &["T1"]),
+            mk_task("T3", &["T1", "T2"]),
+        ]);
+        assert_eq!(graph.detect_cycle(), None);
+    }
+
+    #[test]
+    fn detect_cycle_finds_direct_two_node_cycle() {
+        let graph = build_dependency_graph(&[mk_task("T1", &["T2"]), mk_task("T2", &["T1"])]);
+        assert_eq!(graph.detect_cycle(), Some(ids(&["T1", "T2"])));
+    }
+
+    #[test]
+    fn detect_cycle_returns_longer_cycle_in_traversal_order() {
+        let graph = build_dependency_graph(&[
+            mk_task("T0", &[]),
+            mk_task("T1", &["T0", "T3"]),
+            mk_task("T2", &["T1"]),
+            mk_task("T3", &["T2"]),
+        ]);
+        assert_eq!(graph.detect_cycle(), Some(ids(&["T1", "T2", "T3"])));
+    }
+
+    #[test]
+    fn lineage_graph_includes_parent_task_edges() {
+        let mut t1 = mk_task("T1", &[]);
+        t1.parent_task_id = Some(TaskId("T2".to_string()));
+        let t2 = mk_task("T2", &["T1"]);
+
+        assert_eq!(
+            build_dependency_graph(&[t1.clone(), t2.clone()]).detect_cycle(),
+            None
+        );
+        assert_eq!(
+            build_lineage_graph(&[t1, t2]).detect_cycle(),
+            Some(ids(&["T1", "T2"]))
+        );
+    }
+
+    #[test]
+    fn topo_order_follows_a_linear_chain() {
+        let graph = build_dependency_graph(&[
+            mk_task("T3", &["T2"]),
+            mk_task("T1", &[]),
+            mk_task("T2", &["T1"]),
+        ]);
+        assert_eq!(graph.topo_order(), Ok(ids(&["T1", "T2", "T3"])));
+    }
+
+    #[test]
+    fn topo_order_places_diamond_join_after_both_branches() {
+        let graph = build_dependency_graph(&[
+            mk_task("A", &[]),
+            mk_task("B", &["A"]),
+            mk_task("C", &["A"]),
+            mk_task("D", &["B", "C"]),
+        ]);
+        let order = graph.topo_order().expect("diamond is a DAG");
+        assert_eq!(order.first(), Some(&TaskId("A".to_string())));
+        assert_eq!(order.last(), Some(&TaskId("D".to_string())));
+        assert_eq!(order.len(), 4);
+    }
+
+    #[test]
+    fn topo_order_breaks_ties_by_priority_then_creation_time() {
+        let base = Utc::now();
+        let mut low = mk_task("T-LOW", &[]);
+        low.priority = TaskPriority::Low;
+        low.created_at = base;
+        let mut old_normal = mk_task("T-NORMAL-OLD", &[]);
+        old_normal.created_at = base;
+        let mut new_normal = mk_task("T-NORMAL-NEW", &[]);
+        new_normal.created_at = base + chrono::Duration::seconds(5);
+        let mut critical = mk_task("T-CRITICAL", &[]);
+        critical.priority = TaskPriority::Critical;
+        critical.created_at = base + chrono::Duration::seconds(10);
+
+        let graph = build_dependency_graph(&[low, new_normal, critical, old_normal]);
+        assert_eq!(
+            graph.topo_order(),
+            Ok(ids(&[
+                "T-CRITICAL",
+                "T-NORMAL-OLD",
+                "T-NORMAL-NEW",
+                "T-LOW"
+            ]))
+        );
+    }
+
+    #[test]
+    fn topo_order_rejects_cycles() {
+        let graph = build_dependency_graph(&[
+            mk_task("T0", &[]),
+            mk_task("T1", &["T2"]),
+            mk_task("T2", &["T1"]),
+        ]);
+        let err = graph.topo_order().expect_err("cycle");
+        assert_eq!(err.cycle, ids(&["T1", "T2"]));
+        assert_eq!(err.to_string(), "dependency cycle detected: T1 -> T2 -> T1");
+    }
 }
@@ -743,6 +743,322 @@ impl E2EMetrics {
     }
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// CLI session recording — `.e2e.json` fixtures
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// One recorded CLI call and the JSON it printed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedInvocation {
+    pub args: Vec<String>,
+    pub output: serde_json::Value,
+}
+
+/// A sequence of CLI calls with their JSON output, stored as a `.e2e.json`
+/// fixture and replayed as a regression baseline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CliSession {
+    /// Program the recorded args are passed to, e.g. `othala`.
+    pub program: String,
+    /// JSONPath-style patterns for volatile fields skipped on replay, e.g.
+    /// `$..created_at` or `$.tasks[*].id`. See [`path_is_ignored`].
+    #[serde(default)]
+    pub ignore: Vec<String>,
+    #[serde(default)]
+    pub invocations: Vec<RecordedInvocation>,
+}
+
+/// Where a replayed invocation first diverged from its recording.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayMismatch {
+    /// Index into [`CliSession::invocations`].
+    pub invocation: usize,
+    pub args: Vec<String>,
+    /// Field path such as `$.tasks[0].state`.
+    pub path: String,
+    /// `None` when the field is missing on that side.
+    pub expected: Option<serde_json::Value>,
+    pub actual: Option<serde_json::Value>,
+}
+
+impl std::fmt::Display for ReplayMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let show = |value: &Option<serde_json::Value>| match value {
+            Some(value) => value.to_string(),
+            None => "<missing>".to_string(),
+        };
+        write!(
+            f,
+            "invocation {} ({}): {} expected {}, got {}",
+            self.invocation,
+            self.args.join(" "),
+            self.path,
+            show(&self.expected),
+            show(&self.actual)
+        )
+    }
+}
+
+impl CliSession {
+    pub fn new(program: &str) -> Self {
+        Self {
+            program: program.to_string(),
+            ignore: Vec::new(),
+            invocations: Vec::new(),
+        }
+    }
+
+    /// Run each of `invocations` in `cwd` and record its JSON output.
+    pub fn record(
+        program: &str,
+        invocations: &[Vec<String>],
+        ignore: Vec<String>,
+        cwd: &Path,
+    ) -> Result<Self, String> {
+        let mut session = Self::new(program);
+        session.ignore = ignore;
+        for args in invocations {
+            let output = run_json_invocation(program, args, cwd)?;
+            session.invocations.push(RecordedInvocation {
+                args: args.clone(),
+                output,
+            });
+        }
+        Ok(session)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        serde_json::from_str(&contents)
+            .map_err(|e| format!("failed to parse {}: {e}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("failed to serialize session: {e}"))?;
+        fs::write(path, contents).map_err(|e| format!("failed to write {}: {e}", path.display()))
+    }
+
+    /// Re-run the recorded invocations in `cwd`, stopping at the first one
+    /// whose output differs from the recording outside the ignored paths.
+    pub fn replay(&self, cwd: &Path) -> Result<Option<ReplayMismatch>, String> {
+        for (idx, invocation) in self.invocations.iter().enumerate() {
+            let output = run_json_invocation(&self.program, &invocation.args, cwd)?;
+            if let Some(mismatch) = self.compare_output(idx, &output) {
+                return Ok(Some(mismatch));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Compare `outputs` against the recorded outputs, in order.
+    pub fn compare_outputs(&self, outputs: &[serde_json::Value]) -> Option<ReplayMismatch> {
+        outputs
+            .iter()
+            .enumerate()
+            .find_map(|(idx, output)| self.compare_output(idx, output))
+    }
+
+    fn compare_output(&self, idx: usize, output: &serde_json::Value) -> Option<ReplayMismatch> {
+        let recorded = self.invocations.get(idx)?;
+        let (path, expected, actual) = first_json_mismatch(&recorded.output, output, &self.ignore)?;
+        Some(ReplayMismatch {
+            invocation: idx,
+            args: recorded.args.clone(),
+            path,
+            expected,
+            actual,
+        })
+    }
+}
+
+fn run_json_invocation(
+    program: &str,
+    args: &[String],
+    cwd: &Path,
+) -> Result<serde_json::Value, String> {
+    let rendered = format!("{program} {}", args.join(" "));
+    let output = Command::new(program)
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .map_err(|e| format!("failed to run `{rendered}`: {e}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "`{rendered}` exited with {:?}: {}",
+            output.status.code(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("`{rendered}` did not print JSON: {e}"))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+fn render_path(path: &[PathSegment]) -> String {
+    let mut out = "$".to_string();
+    for segment in path {
+        match segment {
+            PathSegment::Key(key) => {
+                out.push('.');
+                out.push_str(key);
+            }
+            PathSegment::Index(idx) => out.push_str(&format!("[{idx}]")),
+        }
+    }
+    out
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PatternSegment {
+    Key(String),
+    Index(usize),
+    /// `*` or `[*]`: any single key or index.
+    Any,
+    /// `..`: zero or more segments.
+    Descend,
+}
+
+fn parse_ignore_pattern(pattern: &str) -> Vec<PatternSegment> {
+    let rest = pattern.trim();
+    let mut rest = rest.strip_prefix('$').unwrap_or(rest);
+    let mut segments = Vec::new();
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("..") {
+            segments.push(PatternSegment::Descend);
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('.') {
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let end = after.find(']').unwrap_or(after.len());
+            let inner = after[..end].trim().trim_matches(|c| c == '\'' || c == '"');
+            segments.push(match inner {
+                "*" => PatternSegment::Any,
+                _ => inner
+                    .parse()
+                    .map(PatternSegment::Index)
+                    .unwrap_or_else(|_| PatternSegment::Key(inner.to_string())),
+            });
+            rest = after.get(end + 1..).unwrap_or("");
+            continue;
+        }
+        let end = rest.find(['.', '[']).unwrap_or(rest.len());
+        match &rest[..end] {
+            "" => {}
+            "*" => segments.push(PatternSegment::Any),
+            key => segments.push(PatternSegment::Key(key.to_string())),
+        }
+        rest = &rest[end..];
+    }
+    segments
+}
+
+fn pattern_matches(pattern: &[PatternSegment], path: &[PathSegment]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((PatternSegment::Descend, rest)) => {
+            (0..=path.len()).any(|skip| pattern_matches(rest, &path[skip..]))
+        }
+        Some((segment, rest)) => {
+            let Some((head, tail)) = path.split_first() else {
+                return false;
+            };
+            let head_matches = match (segment, head) {
+                (PatternSegment::Any, _) => true,
+                (PatternSegment::Key(want), PathSegment::Key(key)) => want == key,
+                (PatternSegment::Index(want), PathSegment::Index(idx)) => want == idx,
+                _ => false,
+            };
+            head_matches && pattern_matches(rest, tail)
+        }
+    }
+}
+
+/// Whether the field at `path` (as rendered in a [`ReplayMismatch`], e.g.
+/// `$.tasks[0].created_at`) matches one of the `ignore` patterns.
+///
+/// Patterns are a JSONPath subset: `$` for the root, `.key` or `['key']`
+/// for an object field, `[N]` for an array index, `*` or `[*]` for any single
+/// field or index, and `..` for any depth, so `$..created_at` ignores every
+/// `created_at` in the document. An ignored field is skipped with everything
+/// below it.
+pub fn path_is_ignored(path: &str, ignore: &[String]) -> bool {
+    let path = parse_ignore_pattern(path)
+        .into_iter()
+        .filter_map(|segment| match segment {
+            PatternSegment::Index(idx) => Some(PathSegment::Index(idx)),
+            PatternSegment::Key(key) => Some(PathSegment::Key(key)),
+            PatternSegment::Any | PatternSegment::Descend => None,
+        })
+        .collect::<Vec<_>>();
+    ignored(&path, ignore)
+}
+
+fn ignored(path: &[PathSegment], ignore: &[String]) -> bool {
+    ignore
+        .iter()
+        .any(|pattern| pattern_matches(&parse_ignore_pattern(pattern), path))
+}
+
+/// The first field path, in document order, where `actual` differs from
+/// `expected` outside the `ignore` patterns, with the value on each side.
+pub fn first_json_mismatch(
+    expected: &serde_json::Value,
+    actual: &serde_json::Value,
+    ignore: &[String],
+) -> Option<(String, Option<serde_json::Value>, Option<serde_json::Value>)> {
+    let mut path = Vec::new();
+    let (expected, actual) = json_mismatch_at(Some(expected), Some(actual), ignore, &mut path)?;
+    Some((render_path(&path), expected, actual))
+}
+
+type ValuePair = (Option<serde_json::Value>, Option<serde_json::Value>);
+
+/// On a mismatch, `path` is left pointing at it.
+fn json_mismatch_at(
+    expected: Option<&serde_json::Value>,
+    actual: Option<&serde_json::Value>,
+    ignore: &[String],
+    path: &mut Vec<PathSegment>,
+) -> Option<ValuePair> {
+    use serde_json::Value;
+
+    if ignored(path, ignore) {
+        return None;
+    }
+    match (expected, actual) {
+        (Some(Value::Object(want)), Some(Value::Object(got))) => {
+            let extra = got.keys().filter(|key| !want.contains_key(*key));
+            for key in want.keys().chain(extra) {
+                path.push(PathSegment::Key(key.clone()));
+                if let Some(found) = json_mismatch_at(want.get(key), got.get(key), ignore, path) {
+                    return Some(found);
+                }
+                path.pop();
+            }
+            None
+        }
+        (Some(Value::Array(want)), Some(Value::Array(got))) => {
+            for idx in 0..want.len().max(got.len()) {
+                path.push(PathSegment::Index(idx));
+                if let Some(found) = json_mismatch_at(want.get(idx), got.get(idx), ignore, path) {
+                    return Some(found);
+                }
+                path.pop();
+            }
+            None
+        }
+        (want, got) if want == got => None,
+        (want, got) => Some((want.cloned(), got.cloned())),
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Tests
 // ─────────────────────────────────────────────────────────────────────────────
@@ -752,6 +1068,84 @@ mod tests {
     use super::*;
     use std::env::temp_dir;
 
+    fn recorded_session() -> CliSession {
+        let mut session = CliSession::new("othala");
+        session.ignore = vec!["$..created_at".to_string(), "$[*].id".to_string()];
+        session.invocations.push(RecordedInvocation {
+            args: vec!["list".to_string(), "--json".to_string()],
+            output: serde_json::json!([
+                {"id": "chat-1", "state": "CHATTING", "created_at": "2026-01-01T00:00:00Z"},
+            ]),
+        });
+        session
+    }
+
+    #[test]
+    fn replay_ignores_volatile_fields() {
+        let session = recorded_session();
+        let actual = serde_json::json!([
+            {"id": "chat-2", "state": "CHATTING", "created_at": "2026-10-17T09:30:00Z"},
+        ]);
+        assert_eq!(session.compare_outputs(&[actual]), None);
+        assert!(path_is_ignored("$[0].created_at", &session.ignore));
+        assert!(!path_is_ignored("$[0].state", &session.ignore));
+    }
+
+    #[test]
+    fn replay_flags_the_first_changed_field() {
+        let session = recorded_session();
+        let actual = serde_json::json!([
+            {"id": "chat-2", "state": "READY", "created_at": "2026-10-17T09:30:00Z", "extra": 1},
+        ]);
+        let mismatch = session.compare_outputs(&[actual]).expect("state differs");
+        assert_eq!(mismatch.invocation, 0);
+        assert_eq!(mismatch.path, "$[0].state");
+        assert_eq!(mismatch.expected, Some(serde_json::json!("CHATTING")));
+        assert_eq!(mismatch.actual, Some(serde_json::json!("READY")));
+
+        let (path, expected, actual) = first_json_mismatch(
+            &serde_json::json!({"tasks": [1]}),
+            &serde_json::json!({"tasks": [1, 2]}),
+            &[],
+        )
+        .expect("extra element");
+        assert_eq!(path, "$.tasks[1]");
+        assert_eq!((expected, actual), (None, Some(serde_json::json!(2))));
+    }
+
+    #[test]
+    fn cli_session_records_and_replays_through_a_program() {
+        let root = temp_dir().join(format!(
+            "othala-e2e-session-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&root).unwrap();
+        let write_output = |state: &str, created_at: &str| {
+            let output = serde_json::json!({"state": state, "created_at": created_at});
+            fs::write(root.join("out.json"), output.to_string()).unwrap();
+        };
+        write_output("CHATTING", "a");
+        let invocations = vec![vec!["out.json".to_string()]];
+        let ignore = vec!["$..created_at".to_string()];
+        let session = CliSession::record("cat", &invocations, ignore, &root).expect("record");
+
+        let fixture = root.join("session.e2e.json");
+        session.save(&fixture).expect("save");
+        let loaded = CliSession::load(&fixture).expect("load");
+        assert_eq!(loaded, session);
+
+        write_output("CHATTING", "b");
+        assert_eq!(loaded.replay(&root).expect("replay"), None);
+
+        write_output("STOPPED", "b");
+        let mismatch = loaded.replay(&root).expect("replay").expect("mismatch");
+        assert_eq!(mismatch.path, "$.state");
+        let message = mismatch.to_string();
+        assert!(message.contains("expected \"CHATTING\", got \"STOPPED\""));
+
+        fs::remove_dir_all(&root).ok();
+    }
+
     #[test]
     fn default_spec_detects_rust_repo() {
         let root = temp_dir().join(format!("othala-e2e-test-{}", Utc::now().timestamp()));
@@ -310,7 +310,7 @@ fn run_editor_command(editor_command: &str, file_path: &Path) -> Result<(), Stri
     Ok(())
 }
 
-fn encode_base64(data: &[u8]) -> String {
+pub(crate) fn encode_base64(data: &[u8]) -> String {
     if data.is_empty() {
         return String::new();
     }
@@ -1,6 +1,9 @@
 use std::collections::HashMap;
 use std::env;
 
+/// Name fragments marking a variable, or any other key, as holding a secret.
+pub const SECRET_MARKERS: &[&str] = &["TOKEN", "SECRET", "PASSWORD", "KEY", "CREDENTIAL", "AUTH"];
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EnvConfig {
     pub global_vars: HashMap<String, String>,
@@ -32,6 +35,18 @@ impl EnvInjector {
         Self { config }
     }
 
+    /// An injector that only redacts, masking keys that match [`SECRET_MARKERS`].
+    pub fn secret_redactor() -> Self {
+        Self::new(EnvConfig {
+            inherit_env: false,
+            redact_patterns: SECRET_MARKERS
+                .iter()
+                .map(|marker| marker.to_string())
+                .collect(),
+            ..EnvConfig::default()
+        })
+    }
+
     pub fn build_env(&self, task_id: &str, model: &str) -> HashMap<String, String> {
         let mut result = HashMap::new();
 
@@ -102,7 +117,7 @@ impl EnvInjector {
         entry.insert(key.to_string(), value.to_string());
     }
 
-    fn should_redact_key(&self, key: &str) -> bool {
+    pub fn should_redact_key(&self, key: &str) -> bool {
         let key_upper = key.to_ascii_uppercase();
         self.config
             .redact_patterns
@@ -123,13 +123,28 @@ impl FileWatcher {
         self.snapshots.len()
     }
 
-    /// Poll for changes since last scan
+    /// Poll for changes since last scan, one event per detected change.
     pub fn poll(&mut self) -> Vec<FileChangeEvent> {
+        self.scan_changes(SystemTime::now())
+    }
+
+    /// Poll for changes, coalescing each path's changes until it has been
+    /// quiet for `debounce_ms`. A file created and deleted within the window
+    /// produces no event.
+    pub fn poll_debounced(&mut self) -> Vec<FileChangeEvent> {
+        self.poll_debounced_at(SystemTime::now())
+    }
+
+    fn poll_debounced_at(&mut self, now: SystemTime) -> Vec<FileChangeEvent> {
+        let events = self.scan_changes(now);
+        self.apply_debounce(events, now)
+    }
+
+    fn scan_changes(&mut self, now: SystemTime) -> Vec<FileChangeEvent> {
         if !self.config.enabled {
             return Vec::new();
         }
 
-        let now = SystemTime::now();
         let current_files = self.collect_watched_files();
         let current_set: std::collections::HashSet<PathBuf> = current_files.iter().cloned().collect();
         let mut raw_events = Vec::new();
@@ -179,18 +194,26 @@ impl FileWatcher {
             self.snapshots.remove(path);
         }
 
-        // Apply debouncing
-        self.apply_debounce(raw_events, now)
+        self.last_scan = Some(now);
+        raw_events
     }
 
     /// Apply debounce logic
     fn apply_debounce(&mut self, events: Vec<FileChangeEvent>, now: SystemTime) -> Vec<FileChangeEvent> {
         let debounce = Duration::from_millis(self.config.debounce_ms);
 
-        // Add new events to buffer
+        // Merge new events into the buffer, restarting each path's window
         for event in events {
-            self.debounce_buffer
-                .insert(event.path.clone(), (event.kind, event.timestamp));
+            let previous = self.debounce_buffer.get(&event.path).map(|(kind, _)| kind);
+            match coalesce_change(previous, event.kind) {
+                Some(kind) => {
+                    self.debounce_buffer
+                        .insert(event.path, (kind, event.timestamp));
+                }
+                None => {
+                    self.debounce_buffer.remove(&event.path);
+                }
+            }
         }
 
         // Flush events older than debounce window
@@ -209,9 +232,9 @@ impl FileWatcher {
             }
         }
 
+        flushed.sort_by(|a, b| a.path.cmp(&b.path));
         self.debounce_buffer = remaining;
         self.pending_events = flushed.clone();
-        self.last_scan = Some(now);
         flushed
     }
 
@@ -301,6 +324,19 @@ impl FileWatcher {
     }
 }
 
+/// Net effect of a buffered change followed by `next`; `None` when they
+/// cancel out (created, then deleted).
+fn coalesce_change(previous: Option<&ChangeKind>, next: ChangeKind) -> Option<ChangeKind> {
+    match (previous, next) {
+        (Some(ChangeKind::Created), ChangeKind::Deleted) => None,
+        (Some(ChangeKind::Created), _) => Some(ChangeKind::Created),
+        (Some(ChangeKind::Deleted), ChangeKind::Created | ChangeKind::Modified) => {
+            Some(ChangeKind::Modified)
+        }
+        (_, kind) => Some(kind),
+    }
+}
+
 /// Simple glob matching (supports *, **, ?)
 pub fn simple_glob_match(pattern: &str, path: &str) -> bool {
     if let Some(core) = pattern
@@ -538,4 +574,85 @@ mod tests {
 
         let _ = fs::remove_dir_all(dir);
     }
+
+    fn debounce_config() -> WatcherConfig {
+        WatcherConfig {
+            debounce_ms: 300,
+            ..test_config()
+        }
+    }
+
+    fn at_ms(base: SystemTime, ms: u64) -> SystemTime {
+        base + Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn poll_debounced_coalesces_rapid_writes_to_one_event() {
+        let dir = make_temp_dir();
+        let file_path = dir.join("lib.rs");
+        fs::write(&file_path, "").expect("write file");
+        let mut watcher = FileWatcher::new(dir.clone(), debounce_config());
+        watcher.initial_scan();
+
+        let t0 = SystemTime::now();
+        for i in 0..100u64 {
+            // Growing content changes the size even when mtime granularity
+            // hides the write.
+            fs::write(&file_path, "x".repeat(i as usize + 1)).expect("rewrite file");
+            assert!(watcher.poll_debounced_at(at_ms(t0, i)).is_empty());
+        }
+
+        let events = watcher.poll_debounced_at(at_ms(t0, 99 + 300));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].path, file_path);
+        assert_eq!(events[0].kind, ChangeKind::Modified);
+        assert!(watcher.poll_debounced_at(at_ms(t0, 1_000)).is_empty());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn poll_debounced_drops_create_then_delete_within_window() {
+        let dir = make_temp_dir();
+        let mut watcher = FileWatcher::new(dir.clone(), debounce_config());
+        watcher.initial_scan();
+
+        let t0 = SystemTime::now();
+        let file_path = dir.join("scratch.rs");
+        fs::write(&file_path, "fn tmp() {}\n").expect("write file");
+        assert!(watcher.poll_debounced_at(t0).is_empty());
+        fs::remove_file(&file_path).expect("remove file");
+        assert!(watcher.poll_debounced_at(at_ms(t0, 50)).is_empty());
+
+        assert!(watcher.poll_debounced_at(at_ms(t0, 1_000)).is_empty());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn poll_debounced_keeps_distinct_paths_separate() {
+        let dir = make_temp_dir();
+        let mut watcher = FileWatcher::new(dir.clone(), debounce_config());
+        watcher.initial_scan();
+
+        let t0 = SystemTime::now();
+        fs::write(dir.join("a.rs"), "fn a() {}\n").expect("write a");
+        fs::write(dir.join("b.rs"), "fn b() {}\n").expect("write b");
+        assert!(watcher.poll_debounced_at(t0).is_empty());
+        fs::write(dir.join("b.rs"), "fn b() { todo!() }\n").expect("rewrite b");
+        assert!(watcher.poll_debounced_at(at_ms(t0, 100)).is_empty());
+
+        let events = watcher.poll_debounced_at(at_ms(t0, 400));
+        let changes: Vec<(PathBuf, ChangeKind)> =
+            events.into_iter().map(|e| (e.path, e.kind)).collect();
+        assert_eq!(
+            changes,
+            vec![
+                (dir.join("a.rs"), ChangeKind::Created),
+                (dir.join("b.rs"), ChangeKind::Created),
+            ]
+        );
+
+        let _ = fs::remove_dir_all(dir);
+    }
 }
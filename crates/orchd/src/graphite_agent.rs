@@ -9,7 +9,7 @@
 
 use chrono::{DateTime, Duration, Utc};
 use orch_core::types::{RepoId, TaskId};
-use orch_graphite::{GraphiteClient, GraphiteError, RestackOutcome};
+use orch_graphite::{audit_log_path, GraphiteClient, GraphiteError, RestackOutcome};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
@@ -98,12 +98,26 @@ pub struct QueuedOperation {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum OperationResult {
     Success,
-    Conflict { details: String },
-    AuthFailure { details: String },
-    TrunkOutdated { details: String },
-    TrackingDivergence { branches: Vec<String> },
-    Retryable { reason: String },
-    Fatal { reason: String },
+    Conflict {
+        details: String,
+        /// Files the restack left with conflict markers.
+        files: Vec<PathBuf>,
+    },
+    AuthFailure {
+        details: String,
+    },
+    TrunkOutdated {
+        details: String,
+    },
+    TrackingDivergence {
+        branches: Vec<String>,
+    },
+    Retryable {
+        reason: String,
+    },
+    Fatal {
+        reason: String,
+    },
 }
 
 impl OperationResult {
@@ -634,7 +648,7 @@ impl GraphiteMasterAgent {
             }
         }
 
-        let graphite = GraphiteClient::new(repo_root);
+        let graphite = GraphiteClient::new(repo_root).with_audit_log(audit_log_path(repo_root));
 
         // 1. Sync trunk
         match graphite.sync_trunk() {
@@ -659,7 +673,11 @@ impl GraphiteMasterAgent {
             Ok(RestackOutcome::Restacked) => {
                 results.push(OperationResult::Success);
             }
-            Ok(RestackOutcome::Conflict { stdout, stderr }) => {
+            Ok(RestackOutcome::Conflict {
+                stdout,
+                stderr,
+                conflicted_files,
+            }) => {
                 let combined = format!("{stdout}\n{stderr}");
                 if is_benign_noop_restack(&combined) {
                     // Treat Graphite's "no restack needed" output as success.
@@ -667,7 +685,10 @@ impl GraphiteMasterAgent {
                 } else {
                     // Abort the failed rebase
                     let _ = graphite.abort_rebase();
-                    results.push(OperationResult::Conflict { details: combined });
+                    results.push(OperationResult::Conflict {
+                        details: combined,
+                        files: conflicted_files,
+                    });
                 }
             }
             Err(e) => {
@@ -693,7 +714,7 @@ impl GraphiteMasterAgent {
         self.pending_ops.remove(&key);
 
         let error_msg = match result {
-            OperationResult::Conflict { details } => details.clone(),
+            OperationResult::Conflict { details, .. } => details.clone(),
             OperationResult::AuthFailure { details } => details.clone(),
             OperationResult::TrunkOutdated { details } => details.clone(),
             OperationResult::TrackingDivergence { branches } => {
@@ -1139,7 +1160,8 @@ mod tests {
     #[test]
     fn operation_result_recoverability() {
         assert!(OperationResult::Conflict {
-            details: "test".to_string()
+            details: "test".to_string(),
+            files: Vec::new(),
         }
         .is_recoverable());
         assert!(OperationResult::TrunkOutdated {
@@ -6,6 +6,19 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+/// Seeded into `.othalaignore` by `othala init`.
+pub const DEFAULT_OTHALAIGNORE: &str = "\
+# Build output and vendored dependencies, skipped by context gen and search.
+.git/
+target/
+node_modules/
+vendor/
+dist/
+build/
+.venv/
+__pycache__/
+";
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum IgnorePattern {
     Include(String),
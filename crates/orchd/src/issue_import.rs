@@ -0,0 +1,450 @@
+//! Import tasks from issue-tracker exports (Linear, Jira, ...).
+//!
+//! A TOML mapping names the tracker and gives a selector for each field, so
+//! one importer covers any tracker that can export JSON. Selectors are a
+//! small JSONPath subset: dot-separated keys with `[N]` and `[*]` steps, e.g.
+//! `data.issues.nodes[*]` or `labels.nodes[*].name`. A leading `$.` is
+//! accepted and ignored.
+//!
+//! Imported tasks carry an [`ExternalRef`]; re-importing the same issue
+//! updates that task instead of creating a duplicate.
+
+use chrono::Utc;
+use orch_core::types::{ExternalRef, RepoId, Task, TaskId, TaskPriority};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+#[derive(Debug, thiserror::Error)]
+pub enum IssueImportError {
+    #[error("invalid issue mapping: {0}")]
+    Mapping(#[from] toml::de::Error),
+    #[error("invalid selector '{selector}': {reason}")]
+    InvalidSelector { selector: String, reason: String },
+    #[error("mapping needs either `url` or `url_template`, not both")]
+    ConflictingUrl,
+    #[error("issue #{index} has no {field} at '{selector}'")]
+    MissingField {
+        index: usize,
+        field: &'static str,
+        selector: String,
+    },
+}
+
+/// How to read issues out of a tracker export.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct IssueMapping {
+    /// Tracker name recorded on imported tasks, e.g. `linear`.
+    pub source: String,
+    /// Selects the issues; a selector ending on an array uses its elements.
+    pub items: String,
+    pub external_id: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub labels: Option<String>,
+    #[serde(default)]
+    pub priority: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Builds the URL from the external ID, e.g. `https://x/browse/{id}`.
+    #[serde(default)]
+    pub url_template: Option<String>,
+    /// Tracker priority names to task priorities. Values that are not listed
+    /// are parsed as task priorities directly and otherwise ignored.
+    #[serde(default)]
+    pub priority_map: HashMap<String, TaskPriority>,
+}
+
+impl IssueMapping {
+    /// Parse and validate a mapping file.
+    pub fn from_toml(raw: &str) -> Result<Self, IssueImportError> {
+        let mapping: Self = toml::from_str(raw)?;
+        if mapping.url.is_some() && mapping.url_template.is_some() {
+            return Err(IssueImportError::ConflictingUrl);
+        }
+        let selectors = [
+            Some(&mapping.items),
+            Some(&mapping.external_id),
+            Some(&mapping.title),
+            mapping.description.as_ref(),
+            mapping.labels.as_ref(),
+            mapping.priority.as_ref(),
+            mapping.url.as_ref(),
+        ];
+        for selector in selectors.into_iter().flatten() {
+            Selector::parse(selector)?;
+        }
+        Ok(mapping)
+    }
+}
+
+/// One issue read out of an export.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedIssue {
+    pub external_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub labels: Vec<String>,
+    pub priority: Option<TaskPriority>,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Step {
+    Key(String),
+    Index(usize),
+    Wildcard,
+}
+
+/// A parsed field selector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selector {
+    steps: Vec<Step>,
+}
+
+impl Selector {
+    pub fn parse(raw: &str) -> Result<Self, IssueImportError> {
+        let invalid = |reason: &str| IssueImportError::InvalidSelector {
+            selector: raw.to_string(),
+            reason: reason.to_string(),
+        };
+        let trimmed = raw.trim();
+        let body = trimmed
+            .strip_prefix("$.")
+            .or_else(|| trimmed.strip_prefix('$'))
+            .unwrap_or(trimmed);
+
+        let mut steps = Vec::new();
+        for segment in body.split('.') {
+            let (key, mut rest) = match segment.find('[') {
+                Some(open) => segment.split_at(open),
+                None => (segment, ""),
+            };
+            if key.is_empty() && (rest.is_empty() || !steps.is_empty()) {
+                return Err(invalid("empty key"));
+            }
+            if key.contains(']') {
+                return Err(invalid("unexpected ']'"));
+            }
+            if !key.is_empty() {
+                steps.push(Step::Key(key.to_string()));
+            }
+            while let Some(open) = rest.strip_prefix('[') {
+                let Some(close) = open.find(']') else {
+                    return Err(invalid("unclosed '['"));
+                };
+                let inner = &open[..close];
+                steps.push(match inner {
+                    "*" => Step::Wildcard,
+                    index => Step::Index(
+                        index
+                            .parse()
+                            .map_err(|_| invalid("index must be a number or '*'"))?,
+                    ),
+                });
+                rest = &open[close + 1..];
+            }
+            if !rest.is_empty() {
+                return Err(invalid("expected '[' or '.' after ']'"));
+            }
+        }
+        Ok(Self { steps })
+    }
+
+    /// Every value the selector reaches; missing keys yield nothing.
+    pub fn select<'a>(&self, root: &'a Value) -> Vec<&'a Value> {
+        let mut current = vec![root];
+        for step in &self.steps {
+            current = current
+                .into_iter()
+                .flat_map(|value| -> Vec<&'a Value> {
+                    match (step, value) {
+                        (Step::Key(key), Value::Object(map)) => map.get(key).into_iter().collect(),
+                        (Step::Index(index), Value::Array(items)) => {
+                            items.get(*index).into_iter().collect()
+                        }
+                        (Step::Wildcard, Value::Array(items)) => items.iter().collect(),
+                        (Step::Wildcard, Value::Object(map)) => map.values().collect(),
+                        _ => Vec::new(),
+                    }
+                })
+                .collect();
+        }
+        current
+    }
+}
+
+/// Read every issue in `export` according to `mapping`.
+pub fn extract_issues(
+    export: &Value,
+    mapping: &IssueMapping,
+) -> Result<Vec<ImportedIssue>, IssueImportError> {
+    let mut items = Selector::parse(&mapping.items)?.select(export);
+    if let [Value::Array(array)] = items.as_slice() {
+        items = array.iter().collect();
+    }
+
+    let external_id = Selector::parse(&mapping.external_id)?;
+    let title = Selector::parse(&mapping.title)?;
+    let optional = |selector: &Option<String>| selector.as_deref().map(Selector::parse).transpose();
+    let description = optional(&mapping.description)?;
+    let labels = optional(&mapping.labels)?;
+    let priority = optional(&mapping.priority)?;
+    let url = optional(&mapping.url)?;
+
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let required = |selector: &Selector, field: &'static str, raw: &str| {
+                first_scalar(selector, item).ok_or_else(|| IssueImportError::MissingField {
+                    index,
+                    field,
+                    selector: raw.to_string(),
+                })
+            };
+            let external_id = required(&external_id, "external ID", &mapping.external_id)?;
+            let url = match (&url, &mapping.url_template) {
+                (Some(selector), _) => first_scalar(selector, item),
+                (None, Some(template)) => Some(template.replace("{id}", &external_id)),
+                (None, None) => None,
+            };
+            Ok(ImportedIssue {
+                title: required(&title, "title", &mapping.title)?,
+                description: description.as_ref().and_then(|s| first_scalar(s, item)),
+                labels: labels.as_ref().map(|s| all_scalars(s, item)).unwrap_or_default(),
+                priority: priority
+                    .as_ref()
+                    .and_then(|s| first_scalar(s, item))
+                    .and_then(|value| map_priority(&value, &mapping.priority_map)),
+                url,
+                external_id,
+            })
+        })
+        .collect()
+}
+
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) if !text.trim().is_empty() => Some(text.trim().to_string()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(flag) => Some(flag.to_string()),
+        _ => None,
+    }
+}
+
+fn first_scalar(selector: &Selector, item: &Value) -> Option<String> {
+    selector.select(item).into_iter().find_map(scalar_text)
+}
+
+/// Scalars reached by the selector; an array result contributes its elements.
+fn all_scalars(selector: &Selector, item: &Value) -> Vec<String> {
+    selector
+        .select(item)
+        .into_iter()
+        .flat_map(|value| match value {
+            Value::Array(items) => items.iter().filter_map(scalar_text).collect(),
+            other => scalar_text(other).into_iter().collect::<Vec<_>>(),
+        })
+        .collect()
+}
+
+fn map_priority(value: &str, priority_map: &HashMap<String, TaskPriority>) -> Option<TaskPriority> {
+    priority_map
+        .get(value)
+        .copied()
+        .or_else(|| value.parse().ok())
+}
+
+/// Task ID for a newly imported issue, e.g. `linear-ENG-101`.
+pub fn imported_task_id(source: &str, external_id: &str) -> TaskId {
+    let sanitize = |raw: &str| -> String {
+        raw.chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                    c
+                } else {
+                    '-'
+                }
+            })
+            .collect()
+    };
+    TaskId::new(format!(
+        "{}-{}",
+        sanitize(&source.to_ascii_lowercase()),
+        sanitize(external_id)
+    ))
+}
+
+/// Result of applying one issue to the task list.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IssueImportOutcome {
+    Created(Task),
+    Updated(Task),
+}
+
+/// Build the task for `issue`, updating the task already imported from the
+/// same tracker issue when there is one.
+pub fn apply_issue(
+    issue: &ImportedIssue,
+    source: &str,
+    repo_id: &RepoId,
+    existing: &[Task],
+) -> IssueImportOutcome {
+    let external_ref = ExternalRef {
+        source: source.to_string(),
+        id: issue.external_id.clone(),
+        url: issue.url.clone(),
+    };
+    let previous = existing.iter().find(|task| {
+        task.external_ref
+            .as_ref()
+            .is_some_and(|r| r.source == source && r.id == issue.external_id)
+    });
+
+    let (mut task, created) = match previous {
+        Some(task) => (task.clone(), false),
+        None => {
+            let task_id = imported_task_id(source, &issue.external_id);
+            let worktree = PathBuf::from(format!(".orch/wt/{}", task_id.0));
+            let task = Task::new(task_id, repo_id.clone(), issue.title.clone(), worktree);
+            (task, true)
+        }
+    };
+    task.title = issue.title.clone();
+    task.description = issue.description.clone();
+    task.labels = issue.labels.clone();
+    if let Some(priority) = issue.priority {
+        task.priority = priority;
+    }
+    task.external_ref = Some(external_ref);
+    task.updated_at = Utc::now();
+
+    if created {
+        IssueImportOutcome::Created(task)
+    } else {
+        IssueImportOutcome::Updated(task)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const LINEAR_MAPPING: &str = include_str!("../fixtures/issue-import/linear-mapping.toml");
+    const LINEAR_EXPORT: &str = include_str!("../fixtures/issue-import/linear-export.json");
+    const JIRA_MAPPING: &str = include_str!("../fixtures/issue-import/jira-mapping.toml");
+    const JIRA_EXPORT: &str = include_str!("../fixtures/issue-import/jira-export.json");
+
+    fn extract_fixture(mapping: &str, export: &str) -> Vec<ImportedIssue> {
+        let mapping = IssueMapping::from_toml(mapping).expect("mapping");
+        let export: Value = serde_json::from_str(export).expect("export json");
+        extract_issues(&export, &mapping).expect("extract")
+    }
+
+    #[test]
+    fn selector_walks_keys_indexes_and_wildcards() {
+        let value = json!({
+            "a": { "b": [ { "c": 1 }, { "c": 2 }, { "d": 3 } ] }
+        });
+        let select = |raw: &str| Selector::parse(raw).expect("selector").select(&value);
+
+        assert_eq!(select("a.b[1].c"), vec![&json!(2)]);
+        assert_eq!(select("$.a.b[*].c"), vec![&json!(1), &json!(2)]);
+        assert_eq!(select("a.b[*]").len(), 3);
+        assert!(select("a.missing.c").is_empty());
+        assert!(select("a.b[9]").is_empty());
+    }
+
+    #[test]
+    fn selector_rejects_malformed_paths() {
+        for raw in ["a..b", "a.b[", "a.b[x]", "a.b]c", "a[0]b", ""] {
+            assert!(
+                matches!(Selector::parse(raw), Err(IssueImportError::InvalidSelector { .. })),
+                "{raw} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn linear_fixture_maps_issues() {
+        let issues = extract_fixture(LINEAR_MAPPING, LINEAR_EXPORT);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(
+            issues[0],
+            ImportedIssue {
+                external_id: "ENG-101".to_string(),
+                title: "Retry flaky webhook deliveries".to_string(),
+                description: Some(
+                    "Webhook sends fail on transient 502s. Retry with backoff.".to_string()
+                ),
+                labels: vec!["backend".to_string(), "reliability".to_string()],
+                priority: Some(TaskPriority::High),
+                url: Some(
+                    "https://linear.app/acme/issue/ENG-101/retry-flaky-webhook-deliveries"
+                        .to_string()
+                ),
+            }
+        );
+        assert_eq!(issues[1].description, None);
+        assert!(issues[1].labels.is_empty());
+        assert_eq!(issues[1].priority, Some(TaskPriority::Low));
+    }
+
+    #[test]
+    fn jira_fixture_maps_issues_with_url_template() {
+        let issues = extract_fixture(JIRA_MAPPING, JIRA_EXPORT);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].external_id, "PROJ-42");
+        assert_eq!(issues[0].title, "Cache parsed configs between ticks");
+        assert_eq!(issues[0].labels, vec!["performance", "daemon"]);
+        assert_eq!(issues[0].priority, Some(TaskPriority::Critical));
+        assert_eq!(
+            issues[0].url.as_deref(),
+            Some("https://acme.atlassian.net/browse/PROJ-42")
+        );
+        assert_eq!(issues[1].priority, None);
+    }
+
+    #[test]
+    fn missing_required_field_names_the_issue() {
+        let mapping = IssueMapping::from_toml(JIRA_MAPPING).expect("mapping");
+        let export = json!({ "issues": [ { "key": "PROJ-1", "fields": {} } ] });
+        let err = extract_issues(&export, &mapping).expect_err("no summary");
+        assert_eq!(err.to_string(), "issue #0 has no title at 'fields.summary'");
+    }
+
+    #[test]
+    fn reimport_updates_the_existing_task() {
+        let issues = extract_fixture(LINEAR_MAPPING, LINEAR_EXPORT);
+        let repo = RepoId("default".to_string());
+
+        let IssueImportOutcome::Created(created) = apply_issue(&issues[0], "linear", &repo, &[])
+        else {
+            panic!("first import should create");
+        };
+        assert_eq!(created.id.0, "linear-ENG-101");
+        assert_eq!(created.priority, TaskPriority::High);
+
+        let mut changed = issues[0].clone();
+        changed.title = "Retry webhook deliveries with jitter".to_string();
+        match apply_issue(&changed, "linear", &repo, std::slice::from_ref(&created)) {
+            IssueImportOutcome::Updated(task) => {
+                assert_eq!(task.id, created.id);
+                assert_eq!(task.title, "Retry webhook deliveries with jitter");
+                assert_eq!(task.external_ref, created.external_ref);
+            }
+            other => panic!("re-import should update, got {other:?}"),
+        }
+
+        // The same key from another tracker is a different issue.
+        assert!(matches!(
+            apply_issue(&issues[0], "jira", &repo, &[created]),
+            IssueImportOutcome::Created(_)
+        ));
+    }
+}
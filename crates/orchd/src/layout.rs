@@ -0,0 +1,295 @@
+//! Expected on-disk layout of a project: the `.othala/` tree plus the state
+//! database. `othala doctor` reports these checks and `othala repair` fixes
+//! them, so both agree on what "healthy" means.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use orch_core::config::{load_org_config, save_org_config, ConfigError, OrgConfig};
+use serde::{Deserialize, Serialize};
+
+use crate::persistence::{PersistenceError, SqliteStore};
+
+pub const OTHALA_DIR: &str = ".othala";
+pub const CONFIG_PATH: &str = ".othala/config.toml";
+pub const MAIN_CONTEXT_PATH: &str = ".othala/context/MAIN.md";
+pub const STATE_DB_PATH: &str = ".orch/state.sqlite";
+
+/// Directories that must exist, keyed by check name.
+pub const LAYOUT_DIRS: &[(&str, &str)] = &[
+    ("disk", OTHALA_DIR),
+    ("context_dir", ".othala/context"),
+    ("events_dir", ".othala/events"),
+    ("agent_output_dir", ".othala/agent-output"),
+    ("qa_dir", ".othala/qa"),
+    ("templates_dir", ".othala/templates"),
+];
+
+/// Placeholder written when `MAIN.md` is missing.
+pub const MINIMAL_MAIN_MD: &str = "# Project Context\n\nDescribe your project here.\n";
+
+#[derive(Debug, thiserror::Error)]
+pub enum LayoutError {
+    #[error("io error at {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error(transparent)]
+    Persistence(#[from] PersistenceError),
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutStatus {
+    Ok,
+    Missing,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayoutCheck {
+    pub name: String,
+    pub path: PathBuf,
+    pub status: LayoutStatus,
+    pub detail: String,
+}
+
+impl LayoutCheck {
+    pub fn ok(&self) -> bool {
+        self.status == LayoutStatus::Ok
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepairAction {
+    pub check: String,
+    pub path: PathBuf,
+    pub action: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepairReport {
+    pub repairs: Vec<RepairAction>,
+    /// Checks still failing after repair (e.g. an unparseable config, which is
+    /// never overwritten).
+    pub remaining: Vec<LayoutCheck>,
+    pub healthy: bool,
+}
+
+/// Run every layout check against `repo_root`.
+pub fn check_layout(repo_root: &Path) -> Vec<LayoutCheck> {
+    let mut checks: Vec<LayoutCheck> = LAYOUT_DIRS
+        .iter()
+        .map(|(name, relative)| {
+            let path = repo_root.join(relative);
+            if path.is_dir() {
+                check(name, path, LayoutStatus::Ok, format!("{relative} exists"))
+            } else {
+                check(name, path, LayoutStatus::Missing, format!("{relative} missing"))
+            }
+        })
+        .collect();
+
+    let config_path = repo_root.join(CONFIG_PATH);
+    checks.push(if !config_path.exists() {
+        check("config", config_path, LayoutStatus::Missing, "config file missing".to_string())
+    } else {
+        match load_org_config(&config_path) {
+            Ok(_) => check("config", config_path, LayoutStatus::Ok, "config parsed successfully".to_string()),
+            Err(err) => check("config", config_path, LayoutStatus::Error, format!("config parse failed: {err}")),
+        }
+    });
+
+    let main_context = repo_root.join(MAIN_CONTEXT_PATH);
+    checks.push(if main_context.is_file() {
+        check("main_context", main_context, LayoutStatus::Ok, format!("{MAIN_CONTEXT_PATH} exists"))
+    } else {
+        check("main_context", main_context, LayoutStatus::Missing, format!("{MAIN_CONTEXT_PATH} missing"))
+    });
+
+    checks.push(check_sqlite(repo_root));
+    checks
+}
+
+fn check_sqlite(repo_root: &Path) -> LayoutCheck {
+    let path = repo_root.join(STATE_DB_PATH);
+    if !path.is_file() {
+        return check("sqlite", path, LayoutStatus::Missing, format!("{STATE_DB_PATH} missing"));
+    }
+    match SqliteStore::open(&path).and_then(|store| store.missing_schema_tables()) {
+        Ok(missing) if missing.is_empty() => {
+            check("sqlite", path, LayoutStatus::Ok, format!("{STATE_DB_PATH} schema complete"))
+        }
+        Ok(missing) => check(
+            "sqlite",
+            path,
+            LayoutStatus::Missing,
+            format!("missing tables: {}", missing.join(", ")),
+        ),
+        Err(err) => check("sqlite", path, LayoutStatus::Error, format!("{STATE_DB_PATH} unreadable: {err}")),
+    }
+}
+
+fn check(name: &str, path: PathBuf, status: LayoutStatus, detail: String) -> LayoutCheck {
+    LayoutCheck {
+        name: name.to_string(),
+        path,
+        status,
+        detail,
+    }
+}
+
+/// Fix whatever `check_layout` reports as missing. Existing files and
+/// database rows are never overwritten; `default_config` is only written when
+/// there is no config at all.
+pub fn repair_layout(repo_root: &Path, default_config: &OrgConfig) -> Result<RepairReport, LayoutError> {
+    let mut repairs = Vec::new();
+
+    for failing in check_layout(repo_root).into_iter().filter(|check| !check.ok()) {
+        let action = match (failing.name.as_str(), failing.status) {
+            (_, LayoutStatus::Missing) if LAYOUT_DIRS.iter().any(|(name, _)| *name == failing.name) => {
+                fs::create_dir_all(&failing.path).map_err(|source| io_error(&failing.path, source))?;
+                "created directory".to_string()
+            }
+            ("config", LayoutStatus::Missing) => {
+                save_org_config(&failing.path, default_config)?;
+                "wrote default config".to_string()
+            }
+            ("main_context", LayoutStatus::Missing) => {
+                if let Some(parent) = failing.path.parent() {
+                    fs::create_dir_all(parent).map_err(|source| io_error(parent, source))?;
+                }
+                fs::write(&failing.path, MINIMAL_MAIN_MD).map_err(|source| io_error(&failing.path, source))?;
+                "wrote minimal MAIN.md".to_string()
+            }
+            ("sqlite", LayoutStatus::Missing) => {
+                let store = SqliteStore::open(&failing.path)?;
+                let missing = store.missing_schema_tables()?;
+                store.migrate()?;
+                format!("bootstrapped schema (created tables: {})", missing.join(", "))
+            }
+            _ => continue,
+        };
+        repairs.push(RepairAction {
+            check: failing.name,
+            path: failing.path,
+            action,
+        });
+    }
+
+    let remaining: Vec<LayoutCheck> = check_layout(repo_root).into_iter().filter(|check| !check.ok()).collect();
+    Ok(RepairReport {
+        repairs,
+        healthy: remaining.is_empty(),
+        remaining,
+    })
+}
+
+fn io_error(path: &Path, source: std::io::Error) -> LayoutError {
+    LayoutError::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use orch_core::types::{RepoId, Task, TaskId};
+
+    use super::*;
+
+    fn temp_repo(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "othala-layout-{name}-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&root).expect("create temp repo");
+        root
+    }
+
+    #[test]
+    fn empty_repo_reports_every_item_missing() {
+        let root = temp_repo("empty");
+        let checks = check_layout(&root);
+
+        assert_eq!(checks.len(), LAYOUT_DIRS.len() + 3);
+        assert!(checks.iter().all(|check| check.status == LayoutStatus::Missing));
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn repair_recreates_layout_and_is_idempotent() {
+        let root = temp_repo("repair");
+        let report = repair_layout(&root, &OrgConfig::default()).expect("repair");
+
+        assert!(report.healthy, "remaining: {:?}", report.remaining);
+        let repaired: Vec<&str> = report.repairs.iter().map(|r| r.check.as_str()).collect();
+        assert!(repaired.contains(&"agent_output_dir"));
+        assert!(repaired.contains(&"config"));
+        assert!(repaired.contains(&"sqlite"));
+        assert_eq!(
+            fs::read_to_string(root.join(MAIN_CONTEXT_PATH)).expect("MAIN.md"),
+            MINIMAL_MAIN_MD
+        );
+        assert!(check_layout(&root).iter().all(LayoutCheck::ok));
+
+        let again = repair_layout(&root, &OrgConfig::default()).expect("second repair");
+        assert!(again.repairs.is_empty());
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn repair_restores_dropped_tables_and_keeps_data() {
+        let root = temp_repo("sqlite");
+        repair_layout(&root, &OrgConfig::default()).expect("initial repair");
+        let db_path = root.join(STATE_DB_PATH);
+        {
+            let store = SqliteStore::open(&db_path).expect("open store");
+            let task = Task::new(
+                TaskId::new("T-KEEP"),
+                RepoId("example".to_string()),
+                "Keep me".to_string(),
+                root.join("wt"),
+            );
+            store.upsert_task(&task).expect("insert task");
+        }
+        let conn = rusqlite::Connection::open(&db_path).expect("raw connection");
+        conn.execute_batch("DROP TABLE runs; DROP TABLE artifacts;")
+            .expect("drop tables");
+        drop(conn);
+
+        let sqlite = check_layout(&root)
+            .into_iter()
+            .find(|check| check.name == "sqlite")
+            .expect("sqlite check");
+        assert_eq!(sqlite.status, LayoutStatus::Missing);
+        assert_eq!(sqlite.detail, "missing tables: runs, artifacts");
+
+        let report = repair_layout(&root, &OrgConfig::default()).expect("repair");
+        assert_eq!(report.repairs.len(), 1);
+        assert_eq!(report.repairs[0].action, "bootstrapped schema (created tables: runs, artifacts)");
+        let store = SqliteStore::open(&db_path).expect("reopen store");
+        assert!(store.load_task(&TaskId::new("T-KEEP")).expect("load").is_some());
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn unparseable_config_is_reported_but_not_overwritten() {
+        let root = temp_repo("bad-config");
+        fs::create_dir_all(root.join(OTHALA_DIR)).expect("create .othala");
+        fs::write(root.join(CONFIG_PATH), "models = [").expect("write bad config");
+
+        let report = repair_layout(&root, &OrgConfig::default()).expect("repair");
+
+        assert!(!report.healthy);
+        assert_eq!(report.remaining.len(), 1);
+        assert_eq!(report.remaining[0].name, "config");
+        assert_eq!(report.remaining[0].status, LayoutStatus::Error);
+        assert_eq!(fs::read_to_string(root.join(CONFIG_PATH)).expect("config"), "models = [");
+        fs::remove_dir_all(root).ok();
+    }
+}
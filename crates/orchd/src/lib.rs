@@ -6,8 +6,11 @@ pub mod agent_dispatch;
 pub mod agent_log;
 pub mod attribution;
 pub mod auto_compact;
+pub mod bench_suite;
+pub mod chat_control;
 pub mod chat_workspace;
 pub mod ci_gen;
+pub mod cli;
 pub mod code_search;
 pub mod context_manager;
 pub mod conversation;
@@ -15,6 +18,7 @@ pub mod custom_commands;
 pub mod context_gen;
 pub mod context_gen_telemetry;
 pub mod context_graph;
+pub mod daemon_control;
 pub mod daemon_loop;
 pub mod daemon_status;
 pub mod delta_report;
@@ -32,10 +36,14 @@ pub mod env_inject;
 pub mod event_log;
 pub mod file_watcher;
 pub mod ignore;
+pub mod issue_import;
+pub mod layout;
 pub mod lsp;
 pub mod mcp;
 pub mod mcp_resources;
 pub mod mcp_transport;
+pub mod merge_poll;
+pub mod merge_queue;
 pub mod metrics;
 pub mod mission_vault;
 pub mod model_options;
@@ -47,8 +55,10 @@ pub mod provider_registry;
 pub mod qa_agent;
 pub mod qa_spec_gen;
 pub mod qa_self_heal;
+pub mod questions;
 pub mod rate_limiter;
 pub mod retry;
+pub mod retry_guard;
 pub mod scheduler;
 pub mod search;
 pub mod service;
@@ -56,11 +66,14 @@ pub mod shell_config;
 pub mod stack_pipeline;
 pub mod state_machine;
 pub mod supervisor;
+pub mod task_ids;
 pub mod task_timeout;
 pub mod task_templates;
 pub mod test_spec;
+pub mod tick_interval;
 pub mod types;
 pub mod upgrade;
+pub mod verify_failure;
 pub mod wizard;
 
 pub use chat_workspace::*;
@@ -500,6 +500,18 @@ impl LspManager {
         self.diagnostics_cache.clone()
     }
 
+    /// Cached diagnostics for `path`, sorted by line. Empty when no configured
+    /// server owns the file's language.
+    pub fn diagnostics_for(&self, path: &Path) -> Vec<LspDiagnostic> {
+        let file_path = path.to_string_lossy();
+        if self.resolve_language_id(&file_path).is_err() {
+            return Vec::new();
+        }
+        let mut diagnostics = self.get_diagnostics(&file_path);
+        diagnostics.sort_by_key(|diagnostic| (diagnostic.line, diagnostic.column));
+        diagnostics
+    }
+
     pub fn goto_definition(
         &mut self,
         file_path: &str,
@@ -666,7 +678,8 @@ impl LspManager {
             .ok_or_else(|| LspError::ServerNotFound(file_path.to_string()))
     }
 
-    fn language_for_file(&self, file_path: &str) -> Option<String> {
+    /// Configured language whose server handles `file_path`, by extension.
+    pub fn language_for_file(&self, file_path: &str) -> Option<String> {
         self.config
             .language_servers
             .iter()
@@ -1126,6 +1139,48 @@ mod tests {
         assert!(manager.get_diagnostics(&path).is_empty());
     }
 
+    #[test]
+    fn diagnostics_for_returns_only_the_requested_file_sorted_by_line() {
+        let mut manager = LspManager::new(LspConfig::default());
+        let diagnostic = |file_path: &str, line: u32, message: &str| LspDiagnostic {
+            file_path: file_path.to_string(),
+            line,
+            column: 0,
+            severity: DiagnosticSeverity::Warning,
+            message: message.to_string(),
+            source: None,
+            code: None,
+        };
+        manager.diagnostics_cache.insert(
+            "src/lib.rs".to_string(),
+            vec![
+                diagnostic("src/lib.rs", 30, "third"),
+                diagnostic("src/lib.rs", 4, "first"),
+                diagnostic("src/lib.rs", 12, "second"),
+            ],
+        );
+        manager.diagnostics_cache.insert(
+            "src/main.rs".to_string(),
+            vec![diagnostic("src/main.rs", 1, "other file")],
+        );
+        manager.diagnostics_cache.insert(
+            "notes.txt".to_string(),
+            vec![diagnostic("notes.txt", 1, "no server owns this")],
+        );
+
+        let messages = manager
+            .diagnostics_for(Path::new("src/lib.rs"))
+            .into_iter()
+            .map(|diagnostic| diagnostic.message)
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["first", "second", "third"]);
+        assert_eq!(manager.diagnostics_for(Path::new("src/main.rs")).len(), 1);
+        assert!(manager.diagnostics_for(Path::new("notes.txt")).is_empty());
+        assert!(manager
+            .diagnostics_for(Path::new("src/absent.rs"))
+            .is_empty());
+    }
+
     #[test]
     fn multiple_server_management_stop_all_uses_shutdown_exit() {
         let mut manager = LspManager::new(LspConfig::default());
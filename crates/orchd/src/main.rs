@@ -4,43 +4,50 @@
 
 use chrono::{Datelike, Utc};
 use clap::{Parser, Subcommand, ValueEnum};
-use orch_git::{discover_repo, list_change_snapshots, redo_snapshot, undo_to_snapshot, GitCli};
-use orch_agents::setup::{
-    probe_models, summarize_setup, validate_setup_selection, ModelSetupSelection, SetupProbeConfig,
-};
-use orch_core::config::{
-    apply_profile_defaults, apply_setup_selection_to_org_config, load_org_config, save_org_config,
-    ConfigProfile, NotificationConfig, OrgConfig,
-};
+use orch_agents::setup::{probe_models, SetupProbeConfig};
+use orch_core::config::{load_org_config, ConfigProfile};
 use orch_core::events::{Event, EventKind};
 use orch_core::state::TaskState;
 use orch_core::types::{
-    load_task_specs_from_dir, parse_yaml_task_spec, yaml_spec_to_task, EventId, ModelKind, RepoId,
-    Session, Task, TaskId, TaskPriority,
+    parse_yaml_task_spec, EventId, ModelKind, RepoId, Session, Task, TaskId, TaskPriority,
 };
-use orch_core::types::SubmitMode;
-use orch_notify::{NotificationDispatcher, NotificationSink, StdoutSink, WebhookSink};
-use orchd::supervisor::AgentSupervisor;
+use orch_git::{discover_repo, GitCli};
+use orchd::cli::analytics::AnalyticsFormat;
+use orchd::cli::as_of::AsOfList;
+use orchd::cli::daemon::repo_permission_policy;
+use orchd::cli::doctor::{doctor_status_label, layout_doctor_status, DoctorReport, SelfTestCheck};
+use orchd::cli::gc::format_bytes;
+use orchd::cli::init::InitTemplate;
+use orchd::cli::models::{load_model_registry, parse_model_arg, repo_models_config};
+use orchd::cli::prune::{BranchCleanup, PruneOutcome, PruneReport};
+use orchd::cli::repo::{relative_to_root, require_git_repo, resolve_repo_root};
+use orchd::cli::stats::StatsSummary;
+use orchd::cli::tasks::{AliasChange, LabelChange, TaskDetail};
+use orchd::cli::transfer::TaskExportRecord;
+use orchd::cli::undo::SnapshotChange;
+use orchd::cli::wizard::WizardOptions;
+use orchd::cli::{self, parse_task_priority};
 use orchd::{
-    provision_chat_workspace_on_base, AgentCostEstimate, OrchdService, PermissionPolicy,
-    PermissionRule, Scheduler, SchedulerConfig, SkillRegistry, TaskCloneOverrides, ToolCategory,
-    ToolPermission,
+    AgentCostEstimate, AgentQuestion, OrchdService, PermissionPolicy, PermissionRule, Scheduler,
+    SchedulerConfig, ServiceError, SkillRegistry, TaskCloneOverrides, ToolCategory, ToolPermission,
 };
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeMap, HashMap, HashSet};
-use std::fs::{self, File};
-use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::collections::HashMap;
+use std::fs;
 use std::io::ErrorKind;
-use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
-use std::time::{Duration, Instant, SystemTime};
+use std::process::Command;
+use std::time::Duration;
 
 #[derive(Parser)]
 #[command(name = "othala")]
 #[command(about = "AI coding orchestrator")]
 #[command(version)]
 struct Cli {
+    /// Operate on this repository instead of the current directory
+    /// (defaults to $OTHALA_REPO_DIR)
+    #[arg(long, global = true, value_name = "PATH")]
+    repo_dir: Option<PathBuf>,
     #[command(subcommand)]
     command: Commands,
 }
@@ -50,6 +57,9 @@ enum Commands {
     Init {
         #[arg(long)]
         force: bool,
+        /// Seed verify command, ignore rules, and MAIN.md for a language
+        #[arg(long, value_enum)]
+        template: Option<InitTemplateArg>,
     },
     CreateTask {
         /// Repository ID
@@ -78,13 +88,30 @@ enum Commands {
         id: String,
         priority: String,
     },
+    /// Add labels to a task, or to every task in a state with --state
     Tag {
-        task_id: String,
-        label: String,
+        /// `<task> <label>...`, or only `<label>...` when --state is given
+        #[arg(required = true, num_args = 1..)]
+        args: Vec<String>,
+        /// Tag every task in this state instead of a single task
+        #[arg(long)]
+        state: Option<String>,
     },
+    /// Remove labels from a task, or from every task in a state with --state
     Untag {
-        task_id: String,
-        label: String,
+        /// `<task> <label>...`, or only `<label>...` when --state is given
+        #[arg(required = true, num_args = 1..)]
+        args: Vec<String>,
+        /// Untag every task in this state instead of a single task
+        #[arg(long)]
+        state: Option<String>,
+    },
+    /// Assign a short alias to a task, or list aliases when no args are given
+    Alias {
+        /// Task ID, unique prefix, or existing alias
+        task_id: Option<String>,
+        /// New alias (letters, digits, '-', '_', '.')
+        alias: Option<String>,
     },
     Search {
         query: String,
@@ -110,6 +137,12 @@ enum Commands {
         /// Output as JSON (for scripting/E2E tests)
         #[arg(long)]
         json: bool,
+        /// Include open agent questions for each chat
+        #[arg(long)]
+        detail: bool,
+        /// Show the board as it was at this ISO timestamp, rebuilt from events
+        #[arg(long, conflicts_with = "detail")]
+        as_of: Option<String>,
     },
     Sessions {
         /// Output as JSON (for scripting/E2E tests)
@@ -132,6 +165,9 @@ enum Commands {
     Delete {
         /// Chat/task ID
         id: String,
+        /// Also delete child tasks instead of refusing
+        #[arg(long)]
+        cascade: bool,
     },
     Clone {
         task_id: String,
@@ -153,9 +189,21 @@ enum Commands {
     },
     Undo {
         task_id: String,
+        /// Snapshot to undo, 1-based in capture order (default: the latest)
+        #[arg(long)]
+        to: Option<usize>,
+        /// Pop the stash of uncommitted changes after undoing
+        #[arg(long)]
+        restore_stash: bool,
     },
     Redo {
         task_id: String,
+        /// Snapshot to redo, 1-based in capture order (default: the latest)
+        #[arg(long)]
+        to: Option<usize>,
+        /// Pop the stash of uncommitted changes after redoing
+        #[arg(long)]
+        restore_stash: bool,
     },
     /// Run the daemon (orchestration loop)
     Daemon {
@@ -174,9 +222,15 @@ enum Commands {
         /// Skip all QA runs (baseline + validation)
         #[arg(long)]
         skip_qa: bool,
+        /// Skip the fast verify tier run after each agent epoch
+        #[arg(long)]
+        skip_fast_verify: bool,
         /// Run a single daemon tick then exit
         #[arg(long)]
         once: bool,
+        /// Log actions and gt commands instead of executing them
+        #[arg(long)]
+        dry_run: bool,
         #[arg(long, value_enum)]
         profile: Option<ConfigProfileArg>,
     },
@@ -198,6 +252,33 @@ enum Commands {
         /// Only run readiness checks, skip setup
         #[arg(long)]
         check_only: bool,
+        /// Never prompt; take settings from flags and the existing config
+        #[arg(long)]
+        non_interactive: bool,
+        /// Generic webhook URL for notifications
+        #[arg(long)]
+        webhook_url: Option<String>,
+        /// Slack incoming webhook URL
+        #[arg(long)]
+        slack_webhook_url: Option<String>,
+        /// Slack channel to post to
+        #[arg(long)]
+        slack_channel: Option<String>,
+        /// Env var holding the Telegram bot token
+        #[arg(long)]
+        telegram_bot_token_env: Option<String>,
+        /// Env var holding the Telegram chat id
+        #[arg(long)]
+        telegram_chat_id_env: Option<String>,
+        /// Web UI bind address (host:port)
+        #[arg(long)]
+        web_bind: Option<String>,
+        /// Web API auth token (generated when unset)
+        #[arg(long)]
+        web_auth_token: Option<String>,
+        /// Verify command for task worktrees
+        #[arg(long)]
+        verify_command: Option<String>,
     },
     /// Validate Othala installation and environment
     SelfTest {
@@ -209,6 +290,27 @@ enum Commands {
         #[arg(long)]
         json: bool,
     },
+    /// List agent signal rules, merged from .othala/signals.toml
+    Signals {
+        /// Show which rule a line of agent output matches
+        #[arg(long, value_name = "LINE")]
+        test: Option<String>,
+    },
+    /// Recreate missing `.othala` directories, config, MAIN.md and state schema
+    Repair {
+        /// Output the repair report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show recent gt invocations from .othala/graphite-audit.jsonl
+    GraphiteLog {
+        /// Maximum number of entries to show
+        #[arg(short = 'n', long, default_value = "50")]
+        limit: usize,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
     /// Detect and repair Graphite branch tracking divergence
     GraphiteRepair {
         /// Output as JSON
@@ -256,6 +358,14 @@ enum Commands {
         #[arg(long)]
         json: bool,
     },
+    /// Print the prompt an agent would be spawned with for a chat
+    PromptPreview {
+        /// Chat/task ID
+        id: String,
+        /// Model to render for (defaults to the chat's preferred model)
+        #[arg(long)]
+        model: Option<String>,
+    },
     Replay {
         /// Task ID to replay (omit for all tasks)
         task_id: Option<String>,
@@ -283,11 +393,22 @@ enum Commands {
         #[arg(short, long)]
         follow: bool,
     },
+    /// Show reproduction bundles captured for failed verify runs
+    VerifyFailure {
+        /// Task/chat ID
+        task_id: String,
+        /// Only show the most recent bundle
+        #[arg(long)]
+        latest: bool,
+    },
     Compact {
         /// Task/chat ID
         task_id: String,
         #[arg(long)]
         max_lines: Option<usize>,
+        /// Also compact once the log's estimated tokens pass this
+        #[arg(long)]
+        max_tokens: Option<u64>,
     },
     Watch {
         #[arg(long)]
@@ -318,12 +439,23 @@ enum Commands {
     Stats {
         #[arg(long)]
         json: bool,
+        /// Days of merge throughput to report, ending today
+        #[arg(long, default_value_t = cli::stats::DEFAULT_THROUGHPUT_DAYS)]
+        days: u32,
+    },
+    /// Run benchmark task specs repeatedly and compare tagged results
+    BenchSuite {
+        #[command(subcommand)]
+        action: BenchSuiteAction,
     },
     Gc {
         #[arg(long, default_value = "30")]
         older_than_days: u64,
         #[arg(long)]
         dry_run: bool,
+        /// Delete task branches no live or archived task owns instead of old logs
+        #[arg(long)]
+        branches: bool,
     },
     /// Stop a running chat (agent will be killed)
     Stop {
@@ -333,6 +465,9 @@ enum Commands {
     Cancel {
         /// Task/chat ID
         id: String,
+        /// Reason: user, budget, timeout, dependency, or free-form text
+        #[arg(long, default_value = "user")]
+        reason: String,
     },
     /// Resume a stopped chat
     Resume {
@@ -344,6 +479,9 @@ enum Commands {
         /// Output as JSON
         #[arg(long)]
         json: bool,
+        /// Print the order tasks can run in, respecting depends_on
+        #[arg(long)]
+        order: bool,
     },
     Template {
         #[command(subcommand)]
@@ -359,6 +497,31 @@ enum Commands {
         #[arg(long)]
         input: PathBuf,
     },
+    /// Dump denormalized task, run and event tables for notebooks
+    #[command(name = "export-analytics")]
+    ExportAnalytics {
+        /// Directory to write the tables and schema.json into
+        #[arg(long)]
+        output: PathBuf,
+        #[arg(long, value_enum, default_value = "sqlite")]
+        format: AnalyticsFormatArg,
+        /// Only append events recorded since the previous export
+        #[arg(long)]
+        since_last: bool,
+    },
+    /// Import tasks from an issue-tracker JSON export (Linear, Jira, ...)
+    #[command(name = "import-issues")]
+    ImportIssues {
+        /// Tracker export file
+        #[arg(long)]
+        file: PathBuf,
+        /// TOML mapping declaring field selectors for the export
+        #[arg(long)]
+        mapping: PathBuf,
+        /// Repository ID for newly created tasks
+        #[arg(short, long)]
+        repo: String,
+    },
     Costs {
         #[arg(long = "task")]
         task: Option<String>,
@@ -373,6 +536,9 @@ enum Commands {
         /// Actually delete (default is dry-run showing what would be pruned)
         #[arg(long)]
         force: bool,
+        /// Also delete task branches that have commits not merged into HEAD
+        #[arg(long, requires = "force")]
+        delete_branches: bool,
     },
     Archive {
         /// Only archive tasks older than N days
@@ -410,8 +576,26 @@ enum Commands {
         #[arg(long)]
         model: Option<String>,
     },
+    /// Generate the QA specs under .othala/qa/
+    QaSpec {
+        /// Compare freshly generated specs with .othala/qa/ instead of writing them
+        #[arg(long)]
+        check: bool,
+        /// Model that generates the specs
+        #[arg(short, long, default_value = "claude")]
+        model: String,
+    },
+    /// Show or regenerate the QA spec generated for a task
+    Qa {
+        #[command(subcommand)]
+        action: QaAction,
+    },
     /// Start MCP (Model Context Protocol) server on stdin/stdout
-    Mcp,
+    Mcp {
+        /// Largest agent log served as a resource; bigger logs keep their head and tail
+        #[arg(long, default_value_t = orchd::mcp_resources::DEFAULT_LOG_RESOURCE_BYTES)]
+        max_log_bytes: usize,
+    },
     Skills,
     Skill {
         name: String,
@@ -463,6 +647,9 @@ enum Commands {
         /// Output as JSON
         #[arg(long)]
         json: bool,
+        /// Only list models enabled in the org config
+        #[arg(long)]
+        enabled_only: bool,
     },
     /// List provider information
     Providers {
@@ -580,6 +767,14 @@ enum Commands {
         /// Port number
         #[arg(long, default_value = "9898")]
         port: u16,
+        /// Only expose this tool (repeatable); all tools when omitted.
+        /// Clients get full access only by sending $OTHALA_MCP_SECRET, when set.
+        #[arg(long = "allow-tool")]
+        allowed_tools: Vec<String>,
+        /// Append each JSON-RPC request and response to this JSONL file,
+        /// with secret-looking fields redacted
+        #[arg(long)]
+        trace_path: Option<PathBuf>,
     },
     /// Manage conversation history
     Conversations {
@@ -594,6 +789,19 @@ enum Commands {
     },
 }
 
+#[derive(Subcommand)]
+enum QaAction {
+    /// Print the task's generated QA spec
+    Show { task_id: String },
+    /// Generate the task's QA spec again from its title and current diff
+    Regen {
+        task_id: String,
+        /// Model that generates the spec
+        #[arg(short, long, default_value = "claude")]
+        model: String,
+    },
+}
+
 #[derive(Subcommand)]
 enum ConversationAction {
     /// List conversations for a task
@@ -631,6 +839,14 @@ enum LspAction {
         #[arg(long)]
         json: bool,
     },
+    /// Show diagnostics for a file from its language server
+    Diagnostics {
+        /// File to report diagnostics for
+        path: PathBuf,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -656,6 +872,22 @@ enum ChatAction {
         #[arg(long)]
         json: bool,
     },
+    /// Send a message to a chat's live agent session
+    Send {
+        /// Chat/task ID
+        task_id: String,
+        /// Message text
+        message: String,
+        /// Block until the agent responds, then print the response
+        #[arg(long)]
+        wait: bool,
+        /// Start an interactive session when none is running (implies --wait)
+        #[arg(long)]
+        spawn: bool,
+        /// Maximum seconds to wait for a response
+        #[arg(long, default_value = "300")]
+        timeout: u64,
+    },
 }
 
 #[derive(Subcommand)]
@@ -676,18 +908,58 @@ enum BulkAction {
         #[arg(long)]
         state: Option<String>,
         ids: Vec<String>,
+        /// List the tasks that would be affected without changing them
+        #[arg(long)]
+        dry_run: bool,
     },
     Cancel {
         #[arg(long)]
         state: Option<String>,
         ids: Vec<String>,
+        /// List the tasks that would be affected without changing them
+        #[arg(long)]
+        dry_run: bool,
     },
     SetPriority {
         priority: String,
         #[arg(long)]
         state: Option<String>,
         ids: Vec<String>,
+        /// List the tasks that would be affected without changing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Delete terminal (merged/stopped) tasks
+    Delete {
+        #[arg(long)]
+        state: Option<String>,
+        /// Only delete tasks last updated more than N days ago
+        #[arg(long)]
+        older_than_days: Option<i64>,
+        ids: Vec<String>,
+        /// Actually delete (default is dry-run showing what would be deleted)
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum BenchSuiteAction {
+    /// Run every spec in a directory to completion, recording results under a tag
+    Run {
+        /// Directory of YAML task specs
+        dir: PathBuf,
+        #[arg(long, default_value = "1")]
+        iterations: u32,
+        /// Name the results are recorded under (replaces earlier results)
+        #[arg(long)]
+        tag: String,
+        /// Per-iteration limit; tasks still running are recorded as failed
+        #[arg(long, default_value = "3600")]
+        timeout_secs: u64,
     },
+    /// Compare results recorded under two tags
+    Compare { tag_a: String, tag_b: String },
 }
 
 #[derive(Subcommand)]
@@ -703,6 +975,38 @@ enum TemplateAction {
     },
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum InitTemplateArg {
+    Rust,
+    Node,
+    Python,
+}
+
+impl From<InitTemplateArg> for InitTemplate {
+    fn from(value: InitTemplateArg) -> Self {
+        match value {
+            InitTemplateArg::Rust => InitTemplate::Rust,
+            InitTemplateArg::Node => InitTemplate::Node,
+            InitTemplateArg::Python => InitTemplate::Python,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum AnalyticsFormatArg {
+    Sqlite,
+    Csv,
+}
+
+impl From<AnalyticsFormatArg> for AnalyticsFormat {
+    fn from(value: AnalyticsFormatArg) -> Self {
+        match value {
+            AnalyticsFormatArg::Sqlite => AnalyticsFormat::Sqlite,
+            AnalyticsFormatArg::Csv => AnalyticsFormat::Csv,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 enum ConfigProfileArg {
     Dev,
@@ -743,35 +1047,6 @@ impl TaskTemplate {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-struct TaskExportRecord {
-    task_id: String,
-    title: String,
-    description: Option<String>,
-    state: String,
-    priority: String,
-    branch_name: Option<String>,
-    parent_branch: Option<String>,
-    repo_id: String,
-    preferred_model: Option<ModelKind>,
-}
-
-impl TaskExportRecord {
-    fn from_task(task: &Task, parent_branch: Option<String>) -> Self {
-        Self {
-            task_id: task.id.0.clone(),
-            title: task.title.clone(),
-            description: None,
-            state: format!("{}", task.state),
-            priority: task.priority.as_str().to_string(),
-            branch_name: task.branch_name.clone(),
-            parent_branch,
-            repo_id: task.repo_id.0.clone(),
-            preferred_model: task.preferred_model,
-        }
-    }
-}
-
 fn validate_template_name(name: &str) -> anyhow::Result<()> {
     if name.trim().is_empty() {
         anyhow::bail!("template name cannot be empty");
@@ -828,43 +1103,6 @@ fn list_templates(repo_root: &Path) -> anyhow::Result<Vec<String>> {
     Ok(names)
 }
 
-fn parse_export_state(state: &str) -> anyhow::Result<TaskState> {
-    match state.trim().to_uppercase().as_str() {
-        "CHATTING" => Ok(TaskState::Chatting),
-        "READY" => Ok(TaskState::Ready),
-        "SUBMITTING" => Ok(TaskState::Submitting),
-        "RESTACKING" => Ok(TaskState::Restacking),
-        "AWAITING_MERGE" => Ok(TaskState::AwaitingMerge),
-        "MERGED" => Ok(TaskState::Merged),
-        "STOPPED" => Ok(TaskState::Stopped),
-        other => anyhow::bail!("unknown task state in import: {other}"),
-    }
-}
-
-fn import_record_to_task(record: TaskExportRecord, existing: Option<Task>) -> anyhow::Result<Task> {
-    let now = Utc::now();
-    let mut task = if let Some(existing_task) = existing {
-        existing_task
-    } else {
-        Task::new(
-            TaskId::new(record.task_id.clone()),
-            RepoId(record.repo_id.clone()),
-            record.title.clone(),
-            PathBuf::from(format!(".orch/wt/{}", record.task_id)),
-        )
-    };
-
-    task.repo_id = RepoId(record.repo_id);
-    task.id = TaskId::new(record.task_id);
-    task.title = record.title;
-    task.state = parse_export_state(&record.state)?;
-    task.priority = parse_task_priority(&record.priority)?;
-    task.branch_name = record.branch_name;
-    task.preferred_model = record.preferred_model;
-    task.updated_at = now;
-    Ok(task)
-}
-
 fn aggregate_cost_estimates(runs: &[orchd::TaskRunRecord]) -> Vec<AgentCostEstimate> {
     runs.iter()
         .filter_map(|run| {
@@ -894,216 +1132,6 @@ fn aggregate_budget_usage(runs: &[orchd::TaskRunRecord], now: chrono::DateTime<U
     (today, month)
 }
 
-#[derive(Debug, Clone, Serialize)]
-struct RetryTimelineEntry {
-    attempt: u32,
-    model: String,
-    started_at: String,
-    status: String,
-    finished_at: Option<String>,
-    reason: Option<String>,
-}
-
-#[derive(Debug, Serialize)]
-struct RetryHistoryOutput {
-    task_id: String,
-    retry_events: Vec<Event>,
-    timeline: Vec<RetryTimelineEntry>,
-}
-
-fn is_retry_related_event(kind: &EventKind) -> bool {
-    matches!(
-        kind,
-        EventKind::AgentSpawned { .. }
-            | EventKind::RetryScheduled { .. }
-            | EventKind::AgentCompleted { success: false, .. }
-    )
-}
-
-fn collect_retry_events(events: &[Event]) -> Vec<Event> {
-    let mut filtered: Vec<Event> = events
-        .iter()
-        .filter(|event| is_retry_related_event(&event.kind))
-        .cloned()
-        .collect();
-    filtered.sort_by_key(|event| event.at);
-    filtered
-}
-
-fn build_retry_timeline(events: &[Event], runs: &[orchd::TaskRunRecord]) -> Vec<RetryTimelineEntry> {
-    let retry_events = collect_retry_events(events);
-    let mut retry_reasons: HashMap<u32, String> = HashMap::new();
-    for event in &retry_events {
-        if let EventKind::RetryScheduled { attempt, reason, .. } = &event.kind {
-            retry_reasons.insert(*attempt, reason.clone());
-        }
-    }
-
-    let mut sorted_runs = runs.to_vec();
-    sorted_runs.sort_by_key(|run| run.started_at);
-
-    sorted_runs
-        .iter()
-        .enumerate()
-        .map(|(index, run)| {
-            let attempt = (index + 1) as u32;
-            let started_at = run.started_at.format("%H:%M:%S").to_string();
-            let finished_at = run.finished_at.map(|at| at.format("%H:%M:%S").to_string());
-
-            let status = if run.finished_at.is_none() {
-                "running".to_string()
-            } else if run.stop_reason.as_deref() == Some("completed") || run.exit_code == Some(0) {
-                "completed".to_string()
-            } else {
-                "failed".to_string()
-            };
-
-            let reason = if status == "failed" {
-                run.stop_reason
-                    .clone()
-                    .filter(|r| r != "failed")
-                    .or_else(|| retry_reasons.get(&attempt).cloned())
-                    .or_else(|| run.stop_reason.clone())
-            } else {
-                None
-            };
-
-            RetryTimelineEntry {
-                attempt,
-                model: run.model.as_str().to_string(),
-                started_at,
-                status,
-                finished_at,
-                reason,
-            }
-        })
-        .collect()
-}
-
-fn format_retries_timeline(task_id: &str, timeline: &[RetryTimelineEntry]) -> String {
-    if timeline.is_empty() {
-        return format!("No retry history for task: {task_id}");
-    }
-
-    let mut lines = vec![format!("Retry History for {task_id}:")];
-    for entry in timeline {
-        let end = entry.finished_at.as_deref().unwrap_or("-");
-        let line = if entry.status == "failed" {
-            let reason = entry.reason.as_deref().unwrap_or("unknown");
-            format!(
-                "#{:<2} {:<8} started {}  failed {}  reason: \"{}\"",
-                entry.attempt, entry.model, entry.started_at, end, reason
-            )
-        } else if entry.status == "completed" {
-            format!(
-                "#{:<2} {:<8} started {}  completed {}",
-                entry.attempt, entry.model, entry.started_at, end
-            )
-        } else {
-            format!(
-                "#{:<2} {:<8} started {}  running",
-                entry.attempt, entry.model, entry.started_at
-            )
-        };
-        lines.push(line);
-    }
-
-    lines.join("\n")
-}
-
-fn print_banner() {
-    eprint!("\x1b[35m");
-    eprintln!();
-    eprintln!("       \u{2554}\u{2557}");
-    eprintln!("      \u{2554}\u{255d}\u{2558}\u{2557}        \u{2554}\u{2550}\u{2557}\u{2554}\u{2566}\u{2557}\u{2566} \u{2566}\u{2554}\u{2550}\u{2557}\u{2566}  \u{2554}\u{2550}\u{2557}");
-    eprintln!("     \u{2554}\u{255d}  \u{2558}\u{2557}       \u{2551} \u{2551} \u{2551} \u{2560}\u{2550}\u{2569}\u{2560}\u{2550}\u{2557}\u{2551}  \u{2560}\u{2550}\u{2557}");
-    eprintln!("     \u{2558}\u{2557}  \u{2554}\u{255d}       \u{255a}\u{2550}\u{255d} \u{2569} \u{2569} \u{2569}\u{2569} \u{2569}\u{2569}\u{2550}\u{255d}\u{2569} \u{2569}");
-    eprintln!("      \u{2558}\u{2557}\u{2554}\u{255d}");
-    eprintln!("      \u{2554}\u{255d}\u{2558}\u{2557}        autonomous code orchestrator");
-    eprintln!("     \u{2554}\u{255d}  \u{2558}\u{2557}");
-    eprintln!();
-    eprint!("\x1b[0m");
-}
-
-fn run_context_gen_with_status(
-    repo_root: &Path,
-    template_dir: &Path,
-    model: ModelKind,
-) -> anyhow::Result<()> {
-    use orchd::context_gen::{check_context_startup, parse_progress_line, ContextStartupStatus};
-
-    match check_context_startup(repo_root) {
-        ContextStartupStatus::UpToDate => {
-            eprintln!("  \x1b[32mContext up to date \u{2713}\x1b[0m");
-            return Ok(());
-        }
-        ContextStartupStatus::Stale => {
-            eprintln!("  \x1b[33mContext stale — will regenerate in background\x1b[0m");
-            return Ok(());
-        }
-        ContextStartupStatus::Missing => {
-            eprintln!("  \x1b[33mGenerating context...\x1b[0m");
-        }
-    }
-
-    let repo = repo_root.to_path_buf();
-    let tmpl = template_dir.to_path_buf();
-
-    let (result_tx, result_rx) = std::sync::mpsc::channel();
-    let (progress_tx, progress_rx) = std::sync::mpsc::channel();
-
-    std::thread::spawn(move || {
-        let ptx = progress_tx;
-        let result =
-            orchd::context_gen::ensure_context_exists_blocking(&repo, &tmpl, model, move |line| {
-                let _ = ptx.send(line.to_string());
-            });
-        let _ = result_tx.send(result);
-    });
-
-    let spinner_frames = [
-        '\u{280b}', '\u{2819}', '\u{2839}', '\u{2838}', '\u{283c}', '\u{2834}', '\u{2826}',
-        '\u{2827}', '\u{2807}', '\u{280f}',
-    ];
-    let mut frame = 0usize;
-    let mut last_status = String::from("starting agent...");
-
-    loop {
-        while let Ok(raw) = progress_rx.try_recv() {
-            if let Some(parsed) = parse_progress_line(&raw) {
-                last_status = parsed;
-            }
-        }
-
-        match result_rx.try_recv() {
-            Ok(result) => {
-                eprint!("\r\x1b[2K");
-                match &result {
-                    Ok(()) => eprintln!("  \x1b[32mContext generated \u{2713}\x1b[0m"),
-                    Err(e) => eprintln!("  \x1b[31mContext generation failed: {e}\x1b[0m"),
-                }
-                return result;
-            }
-            Err(std::sync::mpsc::TryRecvError::Empty) => {
-                let spinner = spinner_frames[frame % spinner_frames.len()];
-                let display = if last_status.len() > 70 {
-                    format!("{}...", &last_status[..70])
-                } else {
-                    last_status.clone()
-                };
-                eprint!("\r\x1b[2K  \x1b[35m{spinner}\x1b[0m \x1b[2m{display}\x1b[0m");
-                frame += 1;
-                std::thread::sleep(std::time::Duration::from_millis(80));
-            }
-            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
-                eprint!("\r\x1b[2K");
-                eprintln!("  \x1b[31mContext generation thread panicked\x1b[0m");
-                anyhow::bail!("context generation thread panicked");
-            }
-        }
-    }
-}
-
 fn print_task_list(tasks: &[Task], json: bool) {
     if json {
         let out = serde_json::to_string_pretty(tasks).unwrap_or_else(|_| "[]".to_string());
@@ -1126,6 +1154,62 @@ fn print_task_list(tasks: &[Task], json: bool) {
     }
 }
 
+fn print_task_list_as_of(list: &AsOfList, json: bool) {
+    if json {
+        let out = serde_json::to_string_pretty(list).unwrap_or_else(|_| "{}".to_string());
+        println!("{out}");
+        return;
+    }
+    println!("As of {}", list.as_of.format("%Y-%m-%d %H:%M:%S UTC"));
+    print_task_list(&list.tasks, false);
+    if list.warnings > 0 {
+        println!(
+            "warning: {} event(s) could not be replayed; states are best-effort",
+            list.warnings
+        );
+    }
+}
+
+fn print_task_list_detail(tasks: &[TaskDetail], json: bool) {
+    if json {
+        let out = serde_json::to_string_pretty(tasks).unwrap_or_else(|_| "[]".to_string());
+        println!("{out}");
+        return;
+    }
+    if tasks.is_empty() {
+        println!("No chats found.");
+        return;
+    }
+    println!("{:<20} {:<16} {:<10} {:<40}", "ID", "STATE", "QUESTIONS", "TITLE");
+    println!("{}", "-".repeat(86));
+    for TaskDetail {
+        task,
+        open_questions,
+    } in tasks
+    {
+        println!(
+            "{:<20} {:<16} {:<10} {:<40}",
+            task.id.0,
+            format!("{}", task.state),
+            open_questions.len(),
+            task.title
+        );
+        print_open_questions(open_questions, "    ");
+    }
+}
+
+fn print_open_questions(questions: &[AgentQuestion], indent: &str) {
+    let now = Utc::now();
+    for question in questions {
+        println!(
+            "{indent}? {} (asked {}m ago, {})",
+            question.question,
+            orchd::questions::open_for(question, now).as_secs() / 60,
+            question.question_id
+        );
+    }
+}
+
 fn print_task_json(task: &Task) {
     let out = serde_json::to_string_pretty(task).unwrap_or_else(|_| "{}".to_string());
     println!("{out}");
@@ -1193,15 +1277,6 @@ fn parse_model(s: &str) -> ModelKind {
     }
 }
 
-fn parse_model_name(s: &str) -> Option<ModelKind> {
-    match s.trim().to_lowercase().as_str() {
-        "claude" => Some(ModelKind::Claude),
-        "codex" => Some(ModelKind::Codex),
-        "gemini" => Some(ModelKind::Gemini),
-        _ => None,
-    }
-}
-
 fn default_repo_id_from_path(path: &Path) -> String {
     path.file_name()
         .and_then(|name| name.to_str())
@@ -1210,15 +1285,6 @@ fn default_repo_id_from_path(path: &Path) -> String {
         .to_string()
 }
 
-fn profile_label(profile: &ConfigProfile) -> String {
-    match profile {
-        ConfigProfile::Dev => "dev".to_string(),
-        ConfigProfile::Staging => "staging".to_string(),
-        ConfigProfile::Prod => "prod".to_string(),
-        ConfigProfile::Custom(name) => format!("custom({name})"),
-    }
-}
-
 fn print_profiles() {
     println!("{:<10} DEFAULT OVERRIDES", "PROFILE");
     println!("{}", "-".repeat(72));
@@ -1228,63 +1294,6 @@ fn print_profiles() {
     println!("{:<10} no built-in overrides", "custom");
 }
 
-fn parse_task_priority(s: &str) -> anyhow::Result<TaskPriority> {
-    s.parse::<TaskPriority>().map_err(|e| anyhow::anyhow!(e))
-}
-
-fn parse_task_state_filter(value: &str) -> anyhow::Result<TaskState> {
-    match value.trim().to_lowercase().replace('-', "_").as_str() {
-        "chatting" => Ok(TaskState::Chatting),
-        "ready" => Ok(TaskState::Ready),
-        "submitting" => Ok(TaskState::Submitting),
-        "restacking" => Ok(TaskState::Restacking),
-        "awaiting_merge" => Ok(TaskState::AwaitingMerge),
-        "merged" => Ok(TaskState::Merged),
-        "stopped" => Ok(TaskState::Stopped),
-        other => anyhow::bail!("unknown state filter: {other}"),
-    }
-}
-
-fn set_priority(service: &OrchdService, task_id: &TaskId, priority: TaskPriority) -> anyhow::Result<()> {
-    let Some(mut task) = service.task(task_id)? else {
-        anyhow::bail!("task not found: {}", task_id.0);
-    };
-    task.priority = priority;
-    task.updated_at = Utc::now();
-    service.store.upsert_task(&task)?;
-    Ok(())
-}
-
-fn add_task_label(service: &OrchdService, task_id: &TaskId, label: &str) -> anyhow::Result<()> {
-    let normalized = label.trim();
-    if normalized.is_empty() {
-        anyhow::bail!("label cannot be empty");
-    }
-    let Some(mut task) = service.task(task_id)? else {
-        anyhow::bail!("task not found: {}", task_id.0);
-    };
-    if !task.labels.iter().any(|existing| existing == normalized) {
-        task.labels.push(normalized.to_string());
-    }
-    task.updated_at = Utc::now();
-    service.store.upsert_task(&task)?;
-    Ok(())
-}
-
-fn remove_task_label(service: &OrchdService, task_id: &TaskId, label: &str) -> anyhow::Result<()> {
-    let normalized = label.trim();
-    if normalized.is_empty() {
-        anyhow::bail!("label cannot be empty");
-    }
-    let Some(mut task) = service.task(task_id)? else {
-        anyhow::bail!("task not found: {}", task_id.0);
-    };
-    task.labels.retain(|existing| existing != normalized);
-    task.updated_at = Utc::now();
-    service.store.upsert_task(&task)?;
-    Ok(())
-}
-
 fn print_search_results(tasks: &[Task], json: bool) {
     if json {
         println!(
@@ -1305,7 +1314,7 @@ fn print_search_results(tasks: &[Task], json: bool) {
         let labels = if task.labels.is_empty() {
             "-".to_string()
         } else {
-            task.labels.join(",")
+            task.labels_csv()
         };
         println!(
             "{:<20} {:<16} {:<24} {}",
@@ -1317,220 +1326,74 @@ fn print_search_results(tasks: &[Task], json: bool) {
     }
 }
 
-fn init_project(repo_root: &Path, force: bool) -> anyhow::Result<Vec<String>> {
-    let othala_dir = repo_root.join(".othala");
-    let config_path = othala_dir.join("config.toml");
-    let context_dir = othala_dir.join("context");
-    let main_context_path = context_dir.join("MAIN.md");
-    let templates_dir = othala_dir.join("templates");
-
-    if config_path.exists() && !force {
-        anyhow::bail!(
-            "{} already exists (pass --force to overwrite)",
-            config_path.display()
-        );
-    }
-
-    let mut actions = Vec::new();
-
-    if !othala_dir.exists() {
-        std::fs::create_dir_all(&othala_dir)?;
-        actions.push("Created .othala/".to_string());
-    }
-    if !context_dir.exists() {
-        std::fs::create_dir_all(&context_dir)?;
-        actions.push("Created .othala/context/".to_string());
-    }
-    if !templates_dir.exists() {
-        std::fs::create_dir_all(&templates_dir)?;
-        actions.push("Created .othala/templates/".to_string());
-    }
-
-    let config_existed = config_path.exists();
-    let org_config = default_org_config(vec![ModelKind::Claude, ModelKind::Codex, ModelKind::Gemini]);
-    save_org_config(&config_path, &org_config)?;
-    if config_existed {
-        actions.push("Overwrote .othala/config.toml".to_string());
-    } else {
-        actions.push("Created .othala/config.toml".to_string());
-    }
-
-    let context_existed = main_context_path.exists();
-    if force || !context_existed {
-        std::fs::write(
-            &main_context_path,
-            "# Project Context\n\nDescribe your project here.\n",
-        )?;
-        if context_existed {
-            actions.push("Overwrote .othala/context/MAIN.md".to_string());
-        } else {
-            actions.push("Created .othala/context/MAIN.md".to_string());
-        }
-    }
-
-    Ok(actions)
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct BulkSummary {
-    processed: usize,
-    succeeded: usize,
-    skipped: usize,
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct TimeoutRow {
+    task_id: String,
+    remaining_secs: u64,
+    expired: bool,
 }
 
-fn select_bulk_tasks(
+/// Track every task with an open agent run, timed from when the run started.
+fn build_timeout_tracker(
     service: &OrchdService,
-    state: Option<&str>,
-    ids: &[String],
-) -> anyhow::Result<Vec<Task>> {
-    let state_filter = match state {
-        Some(value) => Some(parse_task_state_filter(value)?),
-        None => None,
-    };
-    let id_filter: HashSet<&str> = ids.iter().map(String::as_str).collect();
+    config: orchd::task_timeout::TimeoutConfig,
+) -> anyhow::Result<orchd::task_timeout::TimeoutTracker> {
+    let mut tracker = orchd::task_timeout::TimeoutTracker::new(config);
+    for run in service.store.list_open_runs()? {
+        let state = service
+            .task(&run.task_id)?
+            .map(|task| task.state.to_string().to_ascii_lowercase())
+            .unwrap_or_default();
+        let timeout_secs = tracker.timeout_for_state(&state);
+        tracker.register(&run.task_id, run.started_at, timeout_secs);
+    }
+    Ok(tracker)
+}
 
-    let tasks = service
-        .list_tasks()?
-        .into_iter()
-        .filter(|task| match state_filter {
-            Some(wanted) => task.state == wanted,
-            None => true,
+fn timeout_rows(tracker: &orchd::task_timeout::TimeoutTracker, now: chrono::DateTime<Utc>) -> Vec<TimeoutRow> {
+    let expired = tracker.expired(now);
+    let mut rows: Vec<TimeoutRow> = tracker
+        .entries
+        .keys()
+        .map(|task_id| {
+            let task_id = TaskId::new(task_id);
+            TimeoutRow {
+                remaining_secs: tracker
+                    .remaining(&task_id, now)
+                    .map(|left| left.as_secs())
+                    .unwrap_or_default(),
+                expired: expired.contains(&task_id),
+                task_id: task_id.0,
+            }
         })
-        .filter(|task| id_filter.is_empty() || id_filter.contains(task.id.0.as_str()))
         .collect();
-
-    Ok(tasks)
-}
-
-fn bulk_retry(
-    service: &OrchdService,
-    state: Option<&str>,
-    ids: &[String],
-) -> anyhow::Result<BulkSummary> {
-    let tasks = select_bulk_tasks(service, state, ids)?;
-    let mut summary = BulkSummary {
-        processed: tasks.len(),
-        succeeded: 0,
-        skipped: 0,
-    };
-
-    for task in tasks {
-        let now = Utc::now();
-        let event_id = EventId(format!("E-BULK-RETRY-{}-{}", task.id.0, now.timestamp_millis()));
-        if service
-            .transition_task_state(&task.id, TaskState::Chatting, event_id, now)
-            .is_err()
-        {
-            summary.skipped += 1;
-            continue;
-        }
-
-        let Some(mut updated) = service.task(&task.id)? else {
-            summary.skipped += 1;
-            continue;
-        };
-        updated.retry_count = 0;
-        updated.updated_at = Utc::now();
-        service.store.upsert_task(&updated)?;
-        summary.succeeded += 1;
-    }
-
-    Ok(summary)
-}
-
-fn bulk_cancel(
-    service: &OrchdService,
-    state: Option<&str>,
-    ids: &[String],
-) -> anyhow::Result<BulkSummary> {
-    let tasks = select_bulk_tasks(service, state, ids)?;
-    let mut summary = BulkSummary {
-        processed: tasks.len(),
-        succeeded: 0,
-        skipped: 0,
-    };
-
-    for task in tasks {
-        if cancel_task(service, &task.id, "requested by user").is_ok() {
-            summary.succeeded += 1;
-        } else {
-            summary.skipped += 1;
-        }
-    }
-
-    Ok(summary)
-}
-
-fn bulk_set_priority(
-    service: &OrchdService,
-    priority: TaskPriority,
-    state: Option<&str>,
-    ids: &[String],
-) -> anyhow::Result<BulkSummary> {
-    let tasks = select_bulk_tasks(service, state, ids)?;
-    let mut summary = BulkSummary {
-        processed: tasks.len(),
-        succeeded: 0,
-        skipped: 0,
-    };
-
-    for task in tasks {
-        if set_priority(service, &task.id, priority).is_ok() {
-            summary.succeeded += 1;
-        } else {
-            summary.skipped += 1;
-        }
-    }
-
-    Ok(summary)
+    rows.sort_by(|a, b| a.task_id.cmp(&b.task_id));
+    rows
 }
 
 fn create_task_command(
     service: &OrchdService,
+    repo_root: &Path,
     repo: String,
     title: String,
     model: String,
     priority: TaskPriority,
     json: bool,
 ) -> anyhow::Result<()> {
-    let task_id = format!("chat-{}", Utc::now().timestamp_millis());
-    let task_id = TaskId::new(&task_id);
-    let start_path = std::env::current_dir()?;
-    let repo_id = RepoId(repo.clone());
-    let parent = find_stack_parent(&service.list_tasks()?, &repo_id);
-    let workspace = provision_chat_workspace_on_base(
-        &start_path,
-        &task_id,
-        parent.as_ref().map(|(_, branch)| branch.as_str()),
-    )?;
-
-    let mut task = Task::new(
-        task_id.clone(),
-        repo_id.clone(),
-        title.clone(),
-        workspace.worktree_path.clone(),
-    );
-    task.branch_name = Some(workspace.branch_name.clone());
-    task.priority = priority;
-    task.submit_mode = submit_mode_from_repo_mode(&start_path);
-    if let Some((parent_task_id, _)) = parent.as_ref() {
-        task.parent_task_id = Some(parent_task_id.clone());
-        if !task.depends_on.contains(parent_task_id) {
-            task.depends_on.push(parent_task_id.clone());
-        }
-    }
-
-    task.preferred_model = Some(parse_model(&model));
-
-    let event = Event {
-        id: EventId(format!("E-CREATE-{}", task_id.0)),
-        task_id: Some(task.id.clone()),
-        repo_id: Some(task.repo_id.clone()),
-        at: Utc::now(),
-        kind: EventKind::TaskCreated,
+    let request = orchd::NewChatTask {
+        repo_id: RepoId(repo),
+        title: title.clone(),
+        model: parse_model(&model),
+        priority,
+        stack: true,
+        stack_on: None,
     };
-
-    service.create_task(&task, &event)?;
+    let orchd::CreatedChatTask {
+        task,
+        workspace,
+        parent,
+    } = orchd::create_chat_task(service, repo_root, &request)?;
+    let task_id = task.id.clone();
 
     if json {
         print_task_json(&task);
@@ -1540,7 +1403,7 @@ fn create_task_command(
             task_id.0,
             title,
             workspace.branch_name,
-            workspace.worktree_path.display(),
+            relative_to_root(repo_root, &workspace.worktree_path).display(),
             parent_task_id.0,
             parent_branch
         );
@@ -1550,697 +1413,106 @@ fn create_task_command(
             task_id.0,
             title,
             workspace.branch_name,
-            workspace.worktree_path.display()
+            relative_to_root(repo_root, &workspace.worktree_path).display()
         );
     }
     Ok(())
 }
 
-fn submit_mode_from_repo_mode(repo_root: &Path) -> SubmitMode {
-    let mode_path = repo_root.join(".othala/repo-mode.toml");
-    let Ok(contents) = std::fs::read_to_string(mode_path) else {
-        return SubmitMode::Single;
-    };
-
-    if contents
-        .lines()
-        .map(str::trim)
-        .any(|line| line == "mode = \"stack\"" || line == "mode=\"stack\"")
-    {
-        SubmitMode::Stack
-    } else {
-        SubmitMode::Single
-    }
-}
-
-fn model_name(model: ModelKind) -> &'static str {
-    match model {
-        ModelKind::Claude => "claude",
-        ModelKind::Codex => "codex",
-        ModelKind::Gemini => "gemini",
-    }
-}
-
-fn parse_enable_models_csv(raw: &str) -> anyhow::Result<Vec<ModelKind>> {
-    let mut out = Vec::new();
-    for token in raw.split(',') {
-        let token = token.trim();
-        if token.is_empty() {
-            continue;
-        }
-        let Some(model) = parse_model_name(token) else {
-            anyhow::bail!("unknown model '{token}'. valid values: claude,codex,gemini");
-        };
-        out.push(model);
-    }
-    if out.is_empty() {
-        anyhow::bail!("no models provided. pass --enable claude,codex,gemini");
-    }
-    Ok(out)
-}
-
-fn default_org_config(enabled_models: Vec<ModelKind>) -> OrgConfig {
-    let mut config = OrgConfig::default();
-    let default_model = enabled_models.first().copied();
-    config.models.enabled = enabled_models;
-    config.models.default = default_model;
-    config
+fn print_readiness_score(repo_root: &Path) {
+    let readiness = orchd::wizard::run_readiness_checks(repo_root);
+    println!();
+    println!(
+        "Readiness score: {}/100  ({}/{} checks)",
+        readiness.score, readiness.passed_checks, readiness.total_checks,
+    );
 }
 
-fn build_notification_dispatcher(config: &NotificationConfig) -> Option<NotificationDispatcher> {
-    if !config.enabled {
-        return None;
+fn print_doctor_report(repo_root: &Path, report: &DoctorReport, json: bool) -> anyhow::Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(report)?);
+        return Ok(());
     }
-
-    let mut sinks: Vec<Box<dyn NotificationSink>> = Vec::new();
-
-    if config.stdout {
-        sinks.push(Box::new(StdoutSink));
+    println!("{:<20} {:<10} DETAIL", "CHECK", "STATUS");
+    println!("{}", "-".repeat(80));
+    for check in &report.checks {
+        let status = doctor_status_label(&check.status);
+        println!("{:<20} {:<10} {}", check.name, status, check.detail);
     }
-
-    if let Some(url) = &config.webhook_url {
-        if !url.trim().is_empty() {
-            sinks.push(Box::new(WebhookSink {
-                url: url.clone(),
-                timeout_secs: 10,
-            }));
+    println!();
+    println!(
+        "Overall: {}",
+        if report.all_ok {
+            "healthy"
+        } else {
+            "issues found"
         }
-    }
+    );
+    print_readiness_score(repo_root);
+    Ok(())
+}
 
-    if let Some(url) = &config.slack_webhook_url {
-        if !url.trim().is_empty() {
-            sinks.push(Box::new(orch_notify::SlackSink {
-                webhook_url: url.clone(),
-                channel: config.slack_channel.clone(),
-                timeout_secs: 10,
-            }));
-        }
+fn print_repair_report(report: &orchd::layout::RepairReport, json: bool) -> anyhow::Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(report)?);
+        return Ok(());
     }
-
-    if sinks.is_empty() {
-        None
-    } else {
-        Some(NotificationDispatcher::new(sinks))
+    if report.repairs.is_empty() {
+        println!("Nothing to repair");
     }
-}
-
-fn prompt_enabled_models() -> anyhow::Result<Vec<ModelKind>> {
-    let mut line = String::new();
-    loop {
-        eprint!("Enable models (comma-separated: claude,codex,gemini): ");
-        std::io::stderr().flush()?;
-        line.clear();
-        std::io::stdin().read_line(&mut line)?;
-
-        match parse_enable_models_csv(&line) {
-            Ok(models) => return Ok(models),
-            Err(err) => eprintln!("\x1b[31mInvalid input: {err}\x1b[0m"),
-        }
+    for repair in &report.repairs {
+        println!("{:<20} {}", repair.check, repair.action);
     }
-}
-
-fn all_tasks_idle(service: &OrchdService) -> bool {
-    match service.list_tasks() {
-        Ok(tasks) if tasks.is_empty() => false,
-        Ok(tasks) => tasks
-            .iter()
-            .all(|t| t.state.is_terminal() || t.state == TaskState::AwaitingMerge),
-        Err(_) => false,
+    for check in &report.remaining {
+        println!(
+            "{:<20} {:<10} {}",
+            check.name,
+            doctor_status_label(&layout_doctor_status(check.status)),
+            check.detail
+        );
     }
-}
-
-fn find_stack_parent(tasks: &[Task], repo_id: &RepoId) -> Option<(TaskId, String)> {
-    tasks
-        .iter()
-        .filter(|task| &task.repo_id == repo_id)
-        .filter(|task| !matches!(task.state, TaskState::Merged | TaskState::Stopped))
-        .filter_map(|task| {
-            task.branch_name
-                .as_ref()
-                .map(|branch| (task.id.clone(), branch.clone(), task.updated_at))
-        })
-        .max_by(|a, b| a.2.cmp(&b.2))
-        .map(|(task_id, branch, _)| (task_id, branch))
-}
-
-#[derive(Debug, serde::Serialize)]
-struct SelfTestCheck {
-    name: String,
-    ok: bool,
-    critical: bool,
-    detail: String,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-enum DoctorStatus {
-    Ok,
-    Missing,
-    Error,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-struct DoctorCheck {
-    name: String,
-    ok: bool,
-    status: DoctorStatus,
-    detail: String,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-struct DoctorReport {
-    checks: Vec<DoctorCheck>,
-    all_ok: bool,
-}
-
-fn command_available_via_which(executable: &str) -> bool {
-    Command::new("which")
-        .arg(executable)
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .status()
-        .map(|status| status.success())
-        .unwrap_or(false)
-}
-
-fn doctor_model_checks<F>(which_check: F) -> Vec<DoctorCheck>
-where
-    F: Fn(&str) -> bool,
-{
-    ["claude", "codex", "gemini"]
-        .iter()
-        .map(|model| {
-            let found = which_check(model);
-            DoctorCheck {
-                name: format!("model_{model}"),
-                ok: found,
-                status: if found {
-                    DoctorStatus::Ok
-                } else {
-                    DoctorStatus::Missing
-                },
-                detail: if found {
-                    format!("{model} found on PATH")
-                } else {
-                    format!("{model} missing from PATH")
-                },
-            }
-        })
-        .collect()
-}
-
-fn collect_doctor_checks<F>(repo_root: &Path, which_check: F) -> Vec<DoctorCheck>
-where
-    F: Fn(&str) -> bool,
-{
-    let mut checks = doctor_model_checks(|model| which_check(model));
-
-    let sqlite_path = repo_root.join(".othala/state.sqlite");
-    let sqlite_ok = sqlite_path.is_file() && std::fs::File::open(&sqlite_path).is_ok();
-    checks.push(DoctorCheck {
-        name: "sqlite".to_string(),
-        ok: sqlite_ok,
-        status: if sqlite_ok {
-            DoctorStatus::Ok
-        } else {
-            DoctorStatus::Missing
-        },
-        detail: if sqlite_ok {
-            format!("{} is readable", sqlite_path.display())
-        } else {
-            format!("{} is missing or unreadable", sqlite_path.display())
-        },
-    });
-
-    let gt_found = which_check("gt");
-    checks.push(DoctorCheck {
-        name: "graphite".to_string(),
-        ok: gt_found,
-        status: if gt_found {
-            DoctorStatus::Ok
-        } else {
-            DoctorStatus::Missing
-        },
-        detail: if gt_found {
-            "gt found on PATH".to_string()
-        } else {
-            "gt missing from PATH".to_string()
-        },
-    });
-
-    let git_ok = Command::new("git")
-        .arg("status")
-        .current_dir(repo_root)
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .status()
-        .map(|status| status.success())
-        .unwrap_or(false);
-    checks.push(DoctorCheck {
-        name: "git".to_string(),
-        ok: git_ok,
-        status: if git_ok {
-            DoctorStatus::Ok
-        } else {
-            DoctorStatus::Error
-        },
-        detail: if git_ok {
-            "git status succeeded".to_string()
+    println!();
+    println!(
+        "Overall: {}",
+        if report.healthy {
+            "healthy"
         } else {
-            "git status failed".to_string()
-        },
-    });
-
-    let config_path = repo_root.join(".othala/config.toml");
-    let config_status = if !config_path.exists() {
-        (false, DoctorStatus::Missing, "config file missing".to_string())
-    } else {
-        match load_org_config(&config_path) {
-            Ok(_) => (true, DoctorStatus::Ok, "config parsed successfully".to_string()),
-            Err(err) => (
-                false,
-                DoctorStatus::Error,
-                format!("config parse failed: {err}"),
-            ),
+            "issues remain"
         }
-    };
-    checks.push(DoctorCheck {
-        name: "config".to_string(),
-        ok: config_status.0,
-        status: config_status.1,
-        detail: config_status.2,
-    });
-
-    let othala_dir = repo_root.join(".othala");
-    let disk_ok = othala_dir.is_dir();
-    checks.push(DoctorCheck {
-        name: "disk".to_string(),
-        ok: disk_ok,
-        status: if disk_ok {
-            DoctorStatus::Ok
-        } else {
-            DoctorStatus::Missing
-        },
-        detail: if disk_ok {
-            format!("{} exists", othala_dir.display())
-        } else {
-            format!("{} missing", othala_dir.display())
-        },
-    });
-
-    checks
-}
-
-fn doctor_report(repo_root: &Path) -> DoctorReport {
-    let checks = collect_doctor_checks(repo_root, command_available_via_which);
-    let all_ok = checks.iter().all(|check| check.ok);
-    DoctorReport { checks, all_ok }
-}
-
-fn doctor_status_label(status: &DoctorStatus) -> &'static str {
-    match status {
-        DoctorStatus::Ok => "ok",
-        DoctorStatus::Missing => "missing",
-        DoctorStatus::Error => "error",
-    }
+    );
+    Ok(())
 }
 
-fn run_doctor(json: bool) -> anyhow::Result<bool> {
-    let repo_root = std::env::current_dir()?;
-    let report = doctor_report(&repo_root);
-
+fn print_self_test(repo_root: &Path, checks: &[SelfTestCheck], json: bool) {
     if json {
-        println!("{}", serde_json::to_string_pretty(&report)?);
-    } else {
-        println!("{:<20} {:<10} DETAIL", "CHECK", "STATUS");
-        println!("{}", "-".repeat(80));
-        for check in &report.checks {
-            let status = doctor_status_label(&check.status);
-            println!("{:<20} {:<10} {}", check.name, status, check.detail);
-        }
-        println!();
-        println!(
-            "Overall: {}",
-            if report.all_ok { "healthy" } else { "issues found" }
-        );
-    }
-
-    // Append readiness score from wizard module
-    if !json {
-        let readiness = orchd::wizard::run_readiness_checks(&repo_root);
-        println!();
-        println!(
-            "Readiness score: {}/100  ({}/{} checks)",
-            readiness.score, readiness.passed_checks, readiness.total_checks,
-        );
+        let out = serde_json::to_string_pretty(checks).unwrap_or_else(|_| "[]".to_string());
+        println!("{out}");
+        return;
     }
-
-    Ok(report.all_ok)
-}
-
-fn command_available(executable: &str) -> bool {
-    Command::new(executable)
-        .arg("--version")
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .status()
-        .map(|status| status.success())
-        .unwrap_or(false)
-}
-
-fn run_self_test(json: bool) -> bool {
-    let mut checks = Vec::new();
-
-    let git_available = command_available("git");
-    checks.push(SelfTestCheck {
-        name: "git_available".to_string(),
-        ok: git_available,
-        critical: true,
-        detail: if git_available {
-            "git is available".to_string()
+    println!("Othala Self-Test");
+    for check in checks {
+        let (symbol, color) = if check.ok {
+            ("\u{2713}", "\x1b[32m")
         } else {
-            "git not found on PATH".to_string()
-        },
-    });
-
-    let in_git_repo = if git_available {
-        Command::new("git")
-            .args(["rev-parse", "--is-inside-work-tree"])
-            .output()
-            .map(|out| {
-                out.status.success() && String::from_utf8_lossy(&out.stdout).trim() == "true"
-            })
-            .unwrap_or(false)
-    } else {
-        false
-    };
-    checks.push(SelfTestCheck {
-        name: "in_git_repo".to_string(),
-        ok: in_git_repo,
-        critical: true,
-        detail: if in_git_repo {
-            "current directory is inside a git repository".to_string()
-        } else {
-            "current directory is not inside a git repository".to_string()
-        },
-    });
-
-    let gt_available = command_available("gt");
-    checks.push(SelfTestCheck {
-        name: "graphite_cli_available".to_string(),
-        ok: gt_available,
-        critical: true,
-        detail: if gt_available {
-            "gt is available".to_string()
-        } else {
-            "gt not found on PATH".to_string()
-        },
-    });
-
-    let model_report = probe_models(&SetupProbeConfig::default());
-    for probe in model_report.models {
-        let missing_env = probe
-            .env_status
-            .iter()
-            .filter(|status| !status.satisfied)
-            .map(|status| format!("({})", status.any_of.join(" or ")))
-            .collect::<Vec<_>>();
-
-        let detail = if probe.healthy {
-            probe
-                .version_output
-                .clone()
-                .unwrap_or_else(|| "healthy".to_string())
-        } else if !probe.installed {
-            format!("{} not installed", probe.executable)
-        } else if !probe.version_ok {
-            probe
-                .version_output
-                .clone()
-                .unwrap_or_else(|| "version check failed".to_string())
-        } else if !missing_env.is_empty() {
-            format!("missing env: {}", missing_env.join(", "))
+            ("\u{2717}", "\x1b[31m")
+        };
+        let suffix = if check.critical {
+            ""
         } else {
-            "unhealthy".to_string()
+            " (non-critical)"
         };
-
-        checks.push(SelfTestCheck {
-            name: format!("model_{:?}", probe.model).to_lowercase(),
-            ok: probe.healthy,
-            critical: false,
-            detail,
-        });
+        println!(
+            "  {color}{symbol}\x1b[0m {}{}: {}",
+            check.name, suffix, check.detail
+        );
     }
 
-    let required_dirs = [
-        Path::new(".othala"),
-        Path::new(".othala/context"),
-        Path::new(".othala/qa"),
-        Path::new(".othala/events"),
-    ];
-    let missing_dirs = required_dirs
-        .iter()
-        .filter(|path| !path.is_dir())
-        .map(|path| path.display().to_string())
-        .collect::<Vec<_>>();
-    let structure_ok = missing_dirs.is_empty();
-    checks.push(SelfTestCheck {
-        name: "othala_directory_structure".to_string(),
-        ok: structure_ok,
-        critical: true,
-        detail: if structure_ok {
-            ".othala/ structure is present".to_string()
-        } else {
-            format!("missing: {}", missing_dirs.join(", "))
-        },
-    });
-
-    let sqlite_open = rusqlite::Connection::open_with_flags(
-        ".othala/db.sqlite",
-        rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE,
-    );
-    checks.push(SelfTestCheck {
-        name: "sqlite_open".to_string(),
-        ok: sqlite_open.is_ok(),
-        critical: true,
-        detail: match sqlite_open {
-            Ok(_) => "opened .othala/db.sqlite".to_string(),
-            Err(err) => format!("failed to open .othala/db.sqlite: {err}"),
-        },
-    });
-
-    let cargo_available = command_available("cargo");
-    checks.push(SelfTestCheck {
-        name: "cargo_available".to_string(),
-        ok: cargo_available,
-        critical: true,
-        detail: if cargo_available {
-            "cargo is available".to_string()
-        } else {
-            "cargo not found on PATH".to_string()
-        },
-    });
-
-    let critical_ok = checks.iter().all(|check| !check.critical || check.ok);
-
-    if json {
-        let out = serde_json::to_string_pretty(&checks).unwrap_or_else(|_| "[]".to_string());
-        println!("{out}");
+    if cli::doctor::critical_checks_pass(checks) {
+        println!("\n\x1b[32mAll critical checks passed\x1b[0m");
     } else {
-        println!("Othala Self-Test");
-        for check in &checks {
-            let (symbol, color) = if check.ok {
-                ("\u{2713}", "\x1b[32m")
-            } else {
-                ("\u{2717}", "\x1b[31m")
-            };
-            let suffix = if check.critical {
-                ""
-            } else {
-                " (non-critical)"
-            };
-            println!(
-                "  {color}{symbol}\x1b[0m {}{}: {}",
-                check.name, suffix, check.detail
-            );
-        }
-
-        if critical_ok {
-            println!("\n\x1b[32mAll critical checks passed\x1b[0m");
-        } else {
-            println!("\n\x1b[31mOne or more critical checks failed\x1b[0m");
-        }
-    }
-
-    // Append readiness score from wizard module
-    if !json {
-        if let Ok(repo_root) = std::env::current_dir() {
-            let readiness = orchd::wizard::run_readiness_checks(&repo_root);
-            println!();
-            println!(
-                "Readiness score: {}/100  ({}/{} checks)",
-                readiness.score, readiness.passed_checks, readiness.total_checks,
-            );
-        }
-    }
-
-    critical_ok
-}
-
-const WATCH_PREFIX_COLORS: [&str; 6] = [
-    "\x1b[31m",
-    "\x1b[32m",
-    "\x1b[33m",
-    "\x1b[34m",
-    "\x1b[36m",
-    "\x1b[35m",
-];
-
-fn format_watch_line(task_id: &str, color: &str, line: &str) -> String {
-    format!("[{color}{task_id}\x1b[0m] {line}")
-}
-
-fn read_all_log_lines_and_position(path: &Path) -> std::io::Result<(Vec<String>, u64)> {
-    let file = File::open(path)?;
-    let mut reader = BufReader::new(file);
-    let mut lines = Vec::new();
-    let mut buf = String::new();
-
-    loop {
-        let bytes_read = reader.read_line(&mut buf)?;
-        if bytes_read == 0 {
-            break;
-        }
-        lines.push(buf.trim_end_matches(&['\r', '\n'][..]).to_string());
-        buf.clear();
-    }
-
-    let position = reader.stream_position()?;
-    Ok((lines, position))
-}
-
-fn read_new_log_lines(path: &Path, position: &mut u64) -> std::io::Result<Vec<String>> {
-    let mut file = File::open(path)?;
-    if file.metadata()?.len() < *position {
-        *position = 0;
-    }
-
-    file.seek(SeekFrom::Start(*position))?;
-    let mut reader = BufReader::new(file);
-    let mut new_lines = Vec::new();
-    let mut buf = String::new();
-
-    loop {
-        let bytes_read = reader.read_line(&mut buf)?;
-        if bytes_read == 0 {
-            break;
-        }
-        *position += bytes_read as u64;
-        new_lines.push(buf.trim_end_matches(&['\r', '\n'][..]).to_string());
-        buf.clear();
-    }
-
-    Ok(new_lines)
-}
-
-fn run_watch_command(service: &OrchdService, task_filter: Option<String>, lines: usize) -> anyhow::Result<()> {
-    let repo_root = std::env::current_dir()?;
-    let mut tasks = service.list_tasks_by_state(TaskState::Chatting)?;
-
-    if let Some(task_id) = task_filter {
-        tasks.retain(|task| task.id.0 == task_id);
-    }
-
-    if tasks.is_empty() {
-        println!("No active chatting tasks to watch.");
-        return Ok(());
-    }
-
-    tasks.sort_by(|a, b| a.id.0.cmp(&b.id.0));
-
-    let mut watch_state: HashMap<String, (PathBuf, u64, &'static str)> = HashMap::new();
-    let mut order = Vec::new();
-
-    for (idx, task) in tasks.iter().enumerate() {
-        let color = WATCH_PREFIX_COLORS[idx % WATCH_PREFIX_COLORS.len()];
-        let log_path = orchd::agent_log::agent_log_dir(&repo_root, &task.id).join("latest.log");
-        let mut position = 0u64;
-
-        match read_all_log_lines_and_position(&log_path) {
-            Ok((all_lines, end_position)) => {
-                let start = all_lines.len().saturating_sub(lines);
-                for line in &all_lines[start..] {
-                    println!("{}", format_watch_line(&task.id.0, color, line));
-                }
-                position = end_position;
-            }
-            Err(err) => {
-                if err.kind() != ErrorKind::NotFound {
-                    return Err(err.into());
-                }
-            }
-        }
-
-        order.push(task.id.0.clone());
-        watch_state.insert(task.id.0.clone(), (log_path, position, color));
-    }
-
-    let shutdown = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
-    signal_hook::flag::register(signal_hook::consts::SIGINT, shutdown.clone())?;
-
-    while !shutdown.load(std::sync::atomic::Ordering::Relaxed) {
-        for task_id in &order {
-            if let Some((log_path, position, color)) = watch_state.get_mut(task_id) {
-                match read_new_log_lines(log_path, position) {
-                    Ok(new_lines) => {
-                        for line in new_lines {
-                            println!("{}", format_watch_line(task_id, color, &line));
-                        }
-                    }
-                    Err(err) => {
-                        if err.kind() != ErrorKind::NotFound {
-                            return Err(err.into());
-                        }
-                    }
-                }
-            }
-        }
-
-        std::thread::sleep(std::time::Duration::from_millis(500));
+        println!("\n\x1b[31mOne or more critical checks failed\x1b[0m");
     }
-
-    Ok(())
-}
-
-fn cancel_task(service: &OrchdService, task_id: &TaskId, reason: &str) -> anyhow::Result<TaskState> {
-    let Some(task) = service.task(task_id)? else {
-        anyhow::bail!("task not found: {}", task_id.0);
-    };
-
-    if !matches!(task.state, TaskState::Chatting | TaskState::Ready) {
-        anyhow::bail!("cannot cancel task in state {}", task.state);
-    }
-
-    let now = Utc::now();
-    service.record_event(&Event {
-        id: EventId(format!("E-CANCEL-{}-{}", task_id.0, now.timestamp_millis())),
-        task_id: Some(task_id.clone()),
-        repo_id: Some(task.repo_id.clone()),
-        at: now,
-        kind: EventKind::CancellationRequested {
-            reason: reason.to_string(),
-        },
-    })?;
-
-    let from_state = task.state;
-    service.transition_task_state(
-        task_id,
-        TaskState::Stopped,
-        EventId(format!("E-CANCEL-STATE-{}-{}", task_id.0, now.timestamp_millis())),
-        now,
-    )?;
-
-    Ok(from_state)
+    print_readiness_score(repo_root);
 }
 
 fn resolve_base_branch() -> String {
@@ -2290,79 +1562,27 @@ fn archive_old_tasks(service: &OrchdService, older_than_days: i64) -> anyhow::Re
     Ok(archived)
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq)]
-struct StatsSummary {
-    total_tasks: usize,
-    tasks_by_state: BTreeMap<String, i64>,
-    tasks_by_model: BTreeMap<String, i64>,
-    avg_time_to_merge_seconds: Option<f64>,
-    success_rate: Option<f64>,
-    total_events: i64,
+/// The repo's permission rules, applied to MCP tools by category.
+fn mcp_permission_policy(repo_root: &Path) -> PermissionPolicy {
+    let permissions = load_org_config(repo_root.join(".othala/config.toml"))
+        .map(|config| config.permissions)
+        .unwrap_or_default();
+    repo_permission_policy(repo_root, &permissions)
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct GcSummary {
-    deleted_event_files: usize,
-    deleted_agent_output_dirs: usize,
-    bytes_freed: u64,
-}
-
-fn compute_stats_summary(tasks: &[Task], state_counts: Vec<(String, i64)>, total_events: i64) -> StatsSummary {
-    const STATE_TAGS: [&str; 7] = [
-        "CHATTING",
-        "READY",
-        "SUBMITTING",
-        "RESTACKING",
-        "AWAITING_MERGE",
-        "MERGED",
-        "STOPPED",
-    ];
-
-    let mut tasks_by_state = BTreeMap::new();
-    for state in STATE_TAGS {
-        tasks_by_state.insert(state.to_string(), 0);
-    }
-    for (state, count) in state_counts {
-        tasks_by_state.insert(state, count);
-    }
-
-    let mut tasks_by_model = BTreeMap::new();
-    for task in tasks {
-        let model = task
-            .preferred_model
-            .map(|model| model.as_str().to_string())
-            .unwrap_or_else(|| "unspecified".to_string());
-        *tasks_by_model.entry(model).or_insert(0) += 1;
-    }
-
-    let merged = *tasks_by_state.get("MERGED").unwrap_or(&0);
-    let stopped = *tasks_by_state.get("STOPPED").unwrap_or(&0);
-    let denominator = merged + stopped;
-    let success_rate = if denominator > 0 {
-        Some((merged as f64 / denominator as f64) * 100.0)
-    } else {
-        None
-    };
-
-    let merged_durations: Vec<f64> = tasks
-        .iter()
-        .filter(|task| task.state == TaskState::Merged)
-        .map(|task| (task.updated_at - task.created_at).num_milliseconds() as f64 / 1000.0)
-        .collect();
-    let avg_time_to_merge_seconds = if merged_durations.is_empty() {
-        None
-    } else {
-        Some(merged_durations.iter().sum::<f64>() / merged_durations.len() as f64)
-    };
-
-    StatsSummary {
-        total_tasks: tasks.len(),
-        tasks_by_state,
-        tasks_by_model,
-        avg_time_to_merge_seconds,
-        success_rate,
-        total_events,
+/// Save `rule` to `.othala/permissions.json`, for `model` only when given.
+fn persist_permission_rule(
+    repo_root: &Path,
+    model: Option<&str>,
+    rule: PermissionRule,
+) -> anyhow::Result<()> {
+    let mut policy = PermissionPolicy::load(repo_root)?;
+    match model {
+        Some(model) => policy.add_model_rule(model, rule),
+        None => policy.add_rule(rule),
     }
+    policy.save(repo_root)?;
+    Ok(())
 }
 
 fn print_stats_table(summary: &StatsSummary) {
@@ -2404,156 +1624,271 @@ fn print_stats_table(summary: &StatsSummary) {
             println!("{:<20} {}", model, count);
         }
     }
-}
+    println!();
 
-fn format_bytes(bytes: u64) -> String {
-    if bytes < 1024 {
-        return format!("{} B", bytes);
-    }
-    let kib = bytes as f64 / 1024.0;
-    if kib < 1024.0 {
-        return format!("{kib:.1} KiB");
-    }
-    let mib = kib / 1024.0;
-    if mib < 1024.0 {
-        return format!("{mib:.1} MiB");
+    println!("{:<20} COUNT", "LABEL");
+    println!("{}", "-".repeat(32));
+    if summary.tasks_by_label.is_empty() {
+        println!("{:<20} {}", "(none)", 0);
+    } else {
+        for (label, count) in &summary.tasks_by_label {
+            println!("{:<20} {}", label, count);
+        }
     }
-    format!("{:.1} GiB", mib / 1024.0)
-}
+    println!();
 
-fn collect_old_jsonl_files(root: &Path, cutoff: SystemTime, out: &mut Vec<PathBuf>) -> std::io::Result<()> {
-    if !root.exists() {
-        return Ok(());
+    println!("{:<20} MERGED", "DAY");
+    println!("{}", "-".repeat(32));
+    for (day, count) in &summary.merged_per_day {
+        println!("{:<20} {}", day, count);
     }
+    println!();
 
-    for entry in fs::read_dir(root)? {
-        let entry = entry?;
-        let path = entry.path();
-        let metadata = entry.metadata()?;
-        if metadata.is_dir() {
-            collect_old_jsonl_files(&path, cutoff, out)?;
+    let causes = &summary.failure_causes;
+    println!("{:<20} COUNT", "FAILURE_CAUSE");
+    println!("{}", "-".repeat(32));
+    if causes.overall.is_empty() {
+        println!("{:<20} {}", "(none)", 0);
+    }
+    for entry in &causes.overall {
+        println!("{:<20} {}", entry.cause, entry.count);
+    }
+    for (heading, breakdown) in [
+        ("MODEL", &causes.by_model),
+        ("REPO", &causes.by_repo),
+        ("DAY", &causes.per_day),
+    ] {
+        if breakdown.is_empty() {
             continue;
         }
-
-        if path.extension().and_then(|ext| ext.to_str()) != Some("jsonl") {
-            continue;
+        println!();
+        println!("{:<20} TOP_FAILURE_CAUSES", heading);
+        println!("{}", "-".repeat(48));
+        for (key, ranked) in breakdown {
+            let top = ranked
+                .iter()
+                .take(3)
+                .map(|entry| format!("{}={}", entry.cause, entry.count))
+                .collect::<Vec<_>>()
+                .join(", ");
+            println!("{:<20} {}", key, top);
         }
+    }
+}
 
-        if metadata.modified().map(|modified| modified < cutoff).unwrap_or(false) {
-            out.push(path);
-        }
+fn print_signal_rules(rules: &orch_agents::SignalRuleSet) {
+    println!(
+        "{:<20} {:<18} {:>8} {:<8} PATTERN",
+        "RULE", "KIND", "PRIORITY", "SOURCE"
+    );
+    println!("{}", "-".repeat(72));
+    for rule in rules.rules() {
+        println!(
+            "{:<20} {:<18} {:>8} {:<8} {}",
+            rule.name,
+            rule.kind.as_str(),
+            rule.priority,
+            if rule.custom { "custom" } else { "builtin" },
+            rule.pattern.as_str()
+        );
     }
+}
 
-    Ok(())
+fn print_signal_test(rules: &orch_agents::SignalRuleSet, line: &str) {
+    let matched: Vec<_> = rules.matching(line).collect();
+    let Some(winner) = matched.first() else {
+        println!("No rule matched.");
+        return;
+    };
+    if orch_agents::detect_signal_with_rules(line, rules).is_none() {
+        println!("Ignored: diff and prompt-echo lines never signal.");
+    } else {
+        println!(
+            "Matched {} -> {} (priority {})",
+            winner.name,
+            winner.kind.as_str(),
+            winner.priority
+        );
+    }
+    for rule in &matched[1..] {
+        println!(
+            "  also matches {} -> {} (priority {})",
+            rule.name,
+            rule.kind.as_str(),
+            rule.priority
+        );
+    }
 }
 
-fn collect_old_agent_dirs(root: &Path, cutoff: SystemTime) -> std::io::Result<Vec<PathBuf>> {
-    if !root.exists() {
-        return Ok(Vec::new());
+fn print_task_aliases(aliases: &[(String, TaskId)]) {
+    if aliases.is_empty() {
+        println!("No task aliases.");
     }
+    for (alias, task_id) in aliases {
+        println!("{alias:<24} {}", task_id.0);
+    }
+}
 
-    let mut candidates = Vec::new();
-    for entry in fs::read_dir(root)? {
-        let entry = entry?;
-        let path = entry.path();
-        let metadata = entry.metadata()?;
-        if !metadata.is_dir() {
-            continue;
-        }
-        if metadata.modified().map(|modified| modified < cutoff).unwrap_or(false) {
-            candidates.push(path);
+/// List the tasks a bulk action would touch, with the priority change when
+/// `priority` is given.
+fn print_bulk_dry_run(tasks: &[Task], priority: Option<TaskPriority>) {
+    for task in tasks {
+        match priority {
+            Some(priority) => println!(
+                "[dry-run] {} ({}) - {} [{} -> {}]",
+                task.id.0, task.state, task.title, task.priority, priority
+            ),
+            None => println!("[dry-run] {} ({}) - {}", task.id.0, task.state, task.title),
         }
     }
-    Ok(candidates)
 }
 
-fn dir_size(path: &Path) -> std::io::Result<u64> {
-    let mut total = 0u64;
-    for entry in fs::read_dir(path)? {
-        let entry = entry?;
-        let metadata = entry.metadata()?;
-        if metadata.is_dir() {
-            total += dir_size(&entry.path())?;
+fn print_bulk_delete_dry_run(tasks: &[Task]) {
+    for task in tasks {
+        if task.state.is_terminal() {
+            println!("[dry-run] {} ({}) - {}", task.id.0, task.state, task.title);
         } else {
-            total += metadata.len();
+            println!(
+                "[dry-run] skip {} ({}): not in a terminal state",
+                task.id.0, task.state
+            );
         }
     }
-    Ok(total)
+    println!("Run with --force to actually delete.");
 }
 
-fn gc_logs(repo_root: &Path, older_than_days: u64, dry_run: bool) -> anyhow::Result<GcSummary> {
-    let age = Duration::from_secs(older_than_days.saturating_mul(24 * 60 * 60));
-    let cutoff = SystemTime::now()
-        .checked_sub(age)
-        .unwrap_or(SystemTime::UNIX_EPOCH);
-
-    let events_root = repo_root.join(".othala/events");
-    let agent_output_root = repo_root.join(".othala/agent-output");
-
-    let mut old_event_files = Vec::new();
-    collect_old_jsonl_files(&events_root, cutoff, &mut old_event_files)?;
-    let old_agent_dirs = collect_old_agent_dirs(&agent_output_root, cutoff)?;
-
-    let mut bytes_freed = 0u64;
-    for event_file in &old_event_files {
-        bytes_freed += fs::metadata(event_file).map(|m| m.len()).unwrap_or(0);
-    }
-    for dir in &old_agent_dirs {
-        bytes_freed += dir_size(dir).unwrap_or(0);
+fn print_prune_report(report: &PruneReport, older_than_days: i64, force: bool) {
+    if report.tasks.is_empty() {
+        println!("No tasks to prune (older than {older_than_days} days in terminal state).");
+        return;
     }
-
-    if dry_run {
-        for event_file in &old_event_files {
-            println!("[dry-run] would delete file {}", event_file.display());
+    println!(
+        "{}",
+        if force {
+            "Pruning tasks:"
+        } else {
+            "Would prune (use --force to delete):"
         }
-        for dir in &old_agent_dirs {
-            println!("[dry-run] would delete dir  {}", dir.display());
+    );
+    for pruned in &report.tasks {
+        let task = &pruned.task;
+        println!(
+            "  {} ({}, {} days old) - {}",
+            task.id.0, task.state, pruned.age_days, task.title
+        );
+        match &pruned.outcome {
+            PruneOutcome::Candidate => {}
+            PruneOutcome::HasChildren(children) => {
+                let children: Vec<&str> = children.iter().map(|id| id.0.as_str()).collect();
+                eprintln!("    Skipped: has child tasks {}", children.join(", "));
+            }
+            PruneOutcome::Dirty { files } => {
+                eprintln!(
+                    "    Skipped: worktree {} has uncommitted changes:",
+                    task.worktree_path.display()
+                );
+                for file in files {
+                    eprintln!("      {}", file.display());
+                }
+            }
+            PruneOutcome::CleanupFailed(e) => {
+                eprintln!("    Skipped: failed to clean up worktree: {e}");
+            }
+            PruneOutcome::Pruned { branch, error } => {
+                match branch {
+                    BranchCleanup::Absent => {}
+                    BranchCleanup::Deleted(name) => println!("    Deleted branch {name}"),
+                    BranchCleanup::KeptUnmerged(name) => {
+                        println!("    Kept branch {name} (unmerged commits; use --delete-branches)")
+                    }
+                }
+                if let Some(e) = error {
+                    eprintln!("    Failed to delete: {e}");
+                }
+            }
         }
+    }
+    if force {
+        println!(
+            "Reclaimed {} from worktrees",
+            format_bytes(report.reclaimed_bytes)
+        );
     } else {
-        for event_file in &old_event_files {
-            fs::remove_file(event_file)?;
-        }
-        for dir in &old_agent_dirs {
-            fs::remove_dir_all(dir)?;
-        }
+        println!("\nRun with --force to actually delete.");
     }
+}
 
-    Ok(GcSummary {
-        deleted_event_files: old_event_files.len(),
-        deleted_agent_output_dirs: old_agent_dirs.len(),
-        bytes_freed,
-    })
+impl Commands {
+    /// Commands that provision worktrees or run git against the repo.
+    fn needs_git_repo(&self) -> bool {
+        matches!(
+            self,
+            Commands::CreateTask { .. }
+                | Commands::Chat {
+                    action: ChatAction::New { .. }
+                }
+                | Commands::Diff { .. }
+                | Commands::Undo { .. }
+                | Commands::Redo { .. }
+                | Commands::Daemon { .. }
+                | Commands::Prune { force: true, .. }
+                | Commands::Gc { branches: true, .. }
+        )
+    }
 }
 
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    let repo_root = resolve_repo_root(cli.repo_dir)?;
+
+    // Repair runs before the service opens the state database so it can
+    // report (and perform) the schema bootstrap itself.
+    if let Commands::Repair { json } = cli.command {
+        let report = cli::doctor::repair(&repo_root)?;
+        print_repair_report(&report, json)?;
+        let healthy = report.healthy;
+        std::process::exit(if healthy { 0 } else { 1 });
+    }
+    if cli.command.needs_git_repo() {
+        require_git_repo(&repo_root)?;
+    }
 
-    let cwd = std::env::current_dir()?;
-    let db_path = cwd.join(".orch/state.sqlite");
-    let event_log_path = cwd.join(".orch/events");
+    let db_path = repo_root.join(".orch/state.sqlite");
+    let event_log_path = repo_root.join(".orch/events");
+
+    // Analytics exports must not create, migrate or lock the state database.
+    if let Commands::ExportAnalytics {
+        output,
+        format,
+        since_last,
+    } = &cli.command
+    {
+        let summary =
+            cli::analytics::export_from_db(&db_path, output, (*format).into(), *since_last)?;
+        println!(
+            "Export #{} ({}) to {}: {} task(s), {} run(s), {} event(s)",
+            summary.export_id,
+            summary.mode_label(),
+            output.display(),
+            summary.tasks,
+            summary.runs,
+            summary.events
+        );
+        return Ok(());
+    }
 
     if let Some(parent) = db_path.parent() {
         std::fs::create_dir_all(parent)?;
     }
     std::fs::create_dir_all(&event_log_path)?;
 
-    let scheduler = Scheduler::new(SchedulerConfig {
-        per_repo_limit: 10,
-        per_model_limit: vec![
-            (ModelKind::Claude, 10),
-            (ModelKind::Codex, 10),
-            (ModelKind::Gemini, 10),
-        ]
-        .into_iter()
-        .collect::<HashMap<_, _>>(),
-    });
+    let scheduler = Scheduler::new(SchedulerConfig::uniform(10, 10));
 
     let mut service = OrchdService::open(&db_path, &event_log_path, scheduler)?;
 
     match cli.command {
-        Commands::Init { force } => {
-            let repo_root = std::env::current_dir()?;
-            let actions = init_project(&repo_root, force)?;
+        Commands::Init { force, template } => {
+            let actions = cli::init::init_project(&repo_root, force, template.map(Into::into))?;
             for action in actions {
                 println!("{action}");
             }
@@ -2567,6 +1902,7 @@ fn main() -> anyhow::Result<()> {
         } => {
             create_task_command(
                 &service,
+                &repo_root,
                 repo,
                 title,
                 model,
@@ -2575,28 +1911,10 @@ fn main() -> anyhow::Result<()> {
             )?;
         }
         Commands::LoadTasks { dir } => {
-            let repo_root = std::env::current_dir()?;
             let specs_dir = dir.unwrap_or_else(|| repo_root.join(".othala/tasks"));
             let repo_id = default_repo_id_from_path(&repo_root);
-            let specs = load_task_specs_from_dir(&specs_dir);
-
-            for spec in &specs {
-                let task = yaml_spec_to_task(spec, &repo_id);
-                let event = Event {
-                    id: EventId(format!("E-CREATE-{}", task.id.0)),
-                    task_id: Some(task.id.clone()),
-                    repo_id: Some(task.repo_id.clone()),
-                    at: Utc::now(),
-                    kind: EventKind::TaskCreated,
-                };
-                service.create_task(&task, &event)?;
-            }
-
-            println!(
-                "Loaded {} task spec(s) from {}",
-                specs.len(),
-                specs_dir.display()
-            );
+            let loaded = cli::tasks::load_specs(&service, &specs_dir, &repo_id)?;
+            println!("Loaded {loaded} task spec(s) from {}", specs_dir.display());
         }
         Commands::ValidateSpec { path } => {
             let content = std::fs::read_to_string(&path)?;
@@ -2605,20 +1923,54 @@ fn main() -> anyhow::Result<()> {
             println!("Valid YAML task spec: {}", spec.title);
         }
         Commands::SetPriority { id, priority } => {
-            let task_id = TaskId::new(&id);
-            let parsed = parse_task_priority(&priority)?;
-            set_priority(&service, &task_id, parsed)?;
-            println!("Updated priority: {} -> {}", task_id.0, parsed);
+            let (task_id, priority) = cli::tasks::set_priority(&service, &id, &priority)?;
+            println!("Updated priority: {} -> {}", task_id.0, priority);
         }
-        Commands::Tag { task_id, label } => {
-            let task_id = TaskId::new(&task_id);
-            add_task_label(&service, &task_id, &label)?;
-            println!("Tagged {} with '{}'", task_id.0, label);
+        Commands::Tag { args, state } => match cli::tasks::tag(&service, args, state.as_deref())? {
+            LabelChange::Task { task_id, labels } => {
+                println!("Tagged {} with '{}'", task_id.0, labels.join("', '"));
+            }
+            LabelChange::State {
+                state,
+                labels,
+                summary,
+            } => {
+                println!(
+                    "Tagged {} task(s) in {} with '{}' ({} skipped)",
+                    summary.succeeded,
+                    state,
+                    labels.join("', '"),
+                    summary.skipped
+                );
+            }
+        },
+        Commands::Untag { args, state } => {
+            match cli::tasks::untag(&service, args, state.as_deref())? {
+                LabelChange::Task { task_id, labels } => {
+                    println!("Removed tag '{}' from {}", labels.join("', '"), task_id.0);
+                }
+                LabelChange::State {
+                    state,
+                    labels,
+                    summary,
+                } => {
+                    println!(
+                        "Removed tag '{}' from {} task(s) in {} ({} skipped)",
+                        labels.join("', '"),
+                        summary.succeeded,
+                        state,
+                        summary.skipped
+                    );
+                }
+            }
         }
-        Commands::Untag { task_id, label } => {
-            let task_id = TaskId::new(&task_id);
-            remove_task_label(&service, &task_id, &label)?;
-            println!("Removed tag '{}' from {}", label, task_id.0);
+        Commands::Alias { task_id, alias } => {
+            match cli::tasks::alias(&service, task_id.as_deref(), alias.as_deref())? {
+                AliasChange::Set { task_id, alias } => {
+                    println!("Aliased {} as '{}'", task_id.0, alias);
+                }
+                AliasChange::List(aliases) => print_task_aliases(&aliases),
+            }
         }
         Commands::Search {
             query,
@@ -2631,17 +1983,80 @@ fn main() -> anyhow::Result<()> {
                 .search_tasks(&query, label.as_deref(), state.as_deref())?;
             print_search_results(&matches, json);
         }
+        Commands::BenchSuite { action } => match action {
+            BenchSuiteAction::Run {
+                dir,
+                iterations,
+                tag,
+                timeout_secs,
+            } => cli::bench::run(&service, &repo_root, &dir, iterations, &tag, timeout_secs)?,
+            BenchSuiteAction::Compare { tag_a, tag_b } => {
+                let comparison = cli::bench::compare(&service, &tag_a, &tag_b)?;
+                print!("{}", orchd::bench_suite::render_comparison(&comparison));
+            }
+        },
         Commands::Bulk { action } => {
             let summary = match action {
-                BulkAction::Retry { state, ids } => bulk_retry(&service, state.as_deref(), &ids)?,
-                BulkAction::Cancel { state, ids } => bulk_cancel(&service, state.as_deref(), &ids)?,
+                BulkAction::Retry {
+                    state,
+                    ids,
+                    dry_run: true,
+                }
+                | BulkAction::Cancel {
+                    state,
+                    ids,
+                    dry_run: true,
+                } => {
+                    let (tasks, summary) = cli::bulk::dry_run(&service, state.as_deref(), &ids)?;
+                    print_bulk_dry_run(&tasks, None);
+                    summary
+                }
                 BulkAction::SetPriority {
                     priority,
                     state,
                     ids,
+                    dry_run: true,
                 } => {
                     let parsed = parse_task_priority(&priority)?;
-                    bulk_set_priority(&service, parsed, state.as_deref(), &ids)?
+                    let (tasks, summary) = cli::bulk::dry_run(&service, state.as_deref(), &ids)?;
+                    print_bulk_dry_run(&tasks, Some(parsed));
+                    summary
+                }
+                BulkAction::Delete {
+                    state,
+                    older_than_days,
+                    ids,
+                    force: false,
+                } => {
+                    let (tasks, summary) = cli::bulk::delete_dry_run(
+                        &service,
+                        state.as_deref(),
+                        older_than_days,
+                        &ids,
+                    )?;
+                    print_bulk_delete_dry_run(&tasks);
+                    summary
+                }
+                BulkAction::Delete {
+                    state,
+                    older_than_days,
+                    ids,
+                    force: true,
+                } => cli::bulk::delete(&service, state.as_deref(), older_than_days, &ids)?,
+                BulkAction::Retry { state, ids, .. } => {
+                    cli::bulk::retry(&service, state.as_deref(), &ids)?
+                }
+                BulkAction::Cancel { state, ids, .. } => {
+                    cli::bulk::cancel(&service, state.as_deref(), &ids)?
+                }
+                BulkAction::SetPriority {
+                    priority,
+                    state,
+                    ids,
+                    ..
+                } => {
+                    let parsed = parse_task_priority(&priority)?;
+                    cli::bulk::set_priority(&service, parsed, state.as_deref(), &ids)?
                 }
             };
 
@@ -2659,6 +2074,7 @@ fn main() -> anyhow::Result<()> {
             } => {
                 create_task_command(
                     &service,
+                    &repo_root,
                     repo,
                     title,
                     model,
@@ -2669,9 +2085,36 @@ fn main() -> anyhow::Result<()> {
             ChatAction::List { json } => {
                 print_task_list(&service.list_tasks()?, json);
             }
+            ChatAction::Send {
+                task_id,
+                message,
+                wait,
+                spawn,
+                timeout,
+            } => {
+                cli::chat::send(
+                    &service,
+                    &repo_root,
+                    &service.resolve_task_id(&task_id)?,
+                    &message,
+                    wait,
+                    spawn,
+                    Duration::from_secs(timeout),
+                )?;
+            }
         },
-        Commands::List { json } => {
-            print_task_list(&service.list_tasks()?, json);
+        Commands::List {
+            json,
+            detail,
+            as_of,
+        } => {
+            if let Some(at) = parse_time_filter("as-of", as_of.as_deref())? {
+                print_task_list_as_of(&cli::as_of::list(&service, at)?, json);
+            } else if detail {
+                print_task_list_detail(&cli::tasks::list_detailed(&service)?, json);
+            } else {
+                print_task_list(&cli::tasks::list(&service)?, json);
+            }
         }
         Commands::Sessions { json } => {
             let sessions = service.store.list_sessions()?;
@@ -2703,67 +2146,81 @@ fn main() -> anyhow::Result<()> {
                 }
             }
         },
-        Commands::Status { id, json } => {
-            let task_id = TaskId::new(&id);
-            match service.task(&task_id)? {
-                Some(task) => {
-                    if json {
-                        print_task_json(&task);
-                    } else {
-                        println!("Chat: {}", task.id.0);
-                        println!("Title: {}", task.title);
-                        println!("Repo: {}", task.repo_id.0);
-                        println!("State: {}", task.state);
-                        if let Some(model) = task.preferred_model {
-                            println!("Model: {:?}", model);
-                        }
-                        if let Some(pr) = &task.pr {
-                            println!("PR: {} ({})", pr.number, pr.url);
-                        }
-                        if let Some(branch) = &task.branch_name {
-                            println!("Branch: {}", branch);
-                        }
-                        println!("Worktree: {}", task.worktree_path.display());
-                        println!("Created: {}", task.created_at);
-                        println!("Updated: {}", task.updated_at);
+        Commands::Status { id, json } => match cli::tasks::status(&service, &id)? {
+            Some(TaskDetail {
+                task,
+                open_questions,
+            }) => {
+                if json {
+                    print_task_json(&task);
+                } else {
+                    println!("Chat: {}", task.id.0);
+                    println!("Title: {}", task.title);
+                    println!("Repo: {}", task.repo_id.0);
+                    println!("State: {}", task.state);
+                    if let Some(model) = task.preferred_model {
+                        println!("Model: {:?}", model);
                     }
-                }
-                None => {
-                    if json {
-                        println!("null");
-                    } else {
-                        println!("Chat not found: {}", id);
+                    if let Some(pr) = &task.pr {
+                        println!("PR: #{} {}", pr.number, pr.url);
+                    }
+                    if let Some(branch) = &task.branch_name {
+                        println!("Branch: {}", branch);
+                    }
+                    println!("Worktree: {}", task.worktree_path.display());
+                    println!("Created: {}", task.created_at);
+                    println!("Updated: {}", task.updated_at);
+                    if !open_questions.is_empty() {
+                        println!("Open questions:");
+                        print_open_questions(&open_questions, "  ");
                     }
                 }
             }
-        }
-        Commands::Delete { id } => {
-            let task_id = TaskId::new(&id);
-            if service.delete_task(&task_id)? {
+            None => {
+                if json {
+                    println!("null");
+                } else {
+                    println!("Chat not found: {}", id);
+                }
+            }
+        },
+        Commands::Delete { id, cascade } => match cli::tasks::delete(&service, &id, cascade) {
+            Ok(deleted) if deleted.is_empty() => println!("Chat not found: {}", id),
+            Ok(deleted) => {
                 println!("Deleted chat: {}", id);
-            } else {
-                println!("Chat not found: {}", id);
+                if deleted.len() > 1 {
+                    let children: Vec<&str> = deleted[..deleted.len() - 1]
+                        .iter()
+                        .map(|task_id| task_id.0.as_str())
+                        .collect();
+                    eprintln!(
+                        "warning: also deleted {} child task(s): {}",
+                        children.len(),
+                        children.join(", ")
+                    );
+                }
             }
-        }
+            Err(e) => match e.downcast_ref::<ServiceError>() {
+                Some(ServiceError::HasChildren { .. }) => {
+                    anyhow::bail!("{e}; use --cascade to delete them too")
+                }
+                _ => return Err(e),
+            },
+        },
         Commands::Clone {
             task_id,
             title,
             model,
             priority,
         } => {
-            let source_id = TaskId::new(&task_id);
+            let source_id = service.resolve_task_id(&task_id)?;
             if service.store.load_task(&source_id)?.is_none() {
                 anyhow::bail!("task not found: {task_id}");
             }
 
-            let model_override = if let Some(value) = model {
-                let Some(parsed) = parse_model_name(&value) else {
-                    anyhow::bail!("unknown model '{value}'. valid values: claude,codex,gemini");
-                };
-                Some(parsed)
-            } else {
-                None
-            };
+            let model_override = model
+                .map(|value| parse_model_arg(&value, &repo_models_config(&repo_root)))
+                .transpose()?;
             let priority_override = priority
                 .as_deref()
                 .map(parse_task_priority)
@@ -2785,7 +2242,7 @@ fn main() -> anyhow::Result<()> {
         Commands::Diff { task_id, stat } => {
             let task = service
                 .store
-                .load_task(&TaskId::new(&task_id))?
+                .load_task(&service.resolve_task_id(&task_id)?)?
                 .ok_or_else(|| anyhow::anyhow!("task not found: {task_id}"))?;
 
             let Some(task_branch) = task.branch_name else {
@@ -2802,39 +2259,31 @@ fn main() -> anyhow::Result<()> {
             }
             print!("{}", String::from_utf8_lossy(&output.stdout));
         }
-        Commands::Undo { task_id } => {
-            let task = service
-                .store
-                .load_task(&TaskId::new(&task_id))?
-                .ok_or_else(|| anyhow::anyhow!("task not found: {task_id}"))?;
-            let git = GitCli::default();
-            let repo = discover_repo(&task.worktree_path, &git)?;
-            let snapshots = list_change_snapshots(&repo, &git, &task_id)?;
-            let snapshot = snapshots
-                .last()
-                .ok_or_else(|| anyhow::anyhow!("no snapshots found for task: {task_id}"))?;
-            undo_to_snapshot(&repo, &git, snapshot)?;
-            println!(
-                "Undo applied for {} ({} -> {})",
-                task_id, snapshot.commit_sha, snapshot.parent_sha
-            );
+        Commands::Undo {
+            task_id,
+            to,
+            restore_stash,
+        } => {
+            print_snapshot_change(&cli::undo::apply_change_snapshot(
+                &service,
+                &task_id,
+                to,
+                restore_stash,
+                false,
+            )?);
         }
-        Commands::Redo { task_id } => {
-            let task = service
-                .store
-                .load_task(&TaskId::new(&task_id))?
-                .ok_or_else(|| anyhow::anyhow!("task not found: {task_id}"))?;
-            let git = GitCli::default();
-            let repo = discover_repo(&task.worktree_path, &git)?;
-            let snapshots = list_change_snapshots(&repo, &git, &task_id)?;
-            let snapshot = snapshots
-                .last()
-                .ok_or_else(|| anyhow::anyhow!("no snapshots found for task: {task_id}"))?;
-            redo_snapshot(&repo, &git, snapshot)?;
-            println!(
-                "Redo applied for {} ({} -> {})",
-                task_id, snapshot.parent_sha, snapshot.commit_sha
-            );
+        Commands::Redo {
+            task_id,
+            to,
+            restore_stash,
+        } => {
+            print_snapshot_change(&cli::undo::apply_change_snapshot(
+                &service,
+                &task_id,
+                to,
+                restore_stash,
+                true,
+            )?);
         }
         Commands::Daemon {
             timeout,
@@ -2842,277 +2291,26 @@ fn main() -> anyhow::Result<()> {
             skip_context_gen,
             verify_command,
             skip_qa,
+            skip_fast_verify,
             once,
+            dry_run,
             profile,
         } => {
-            print_banner();
-
-            let repo_root = std::env::current_dir()?;
-            let template_dir = PathBuf::from("templates/prompts");
-            let selected_cli_profile = profile.map(ConfigProfile::from);
-
-            let config_path = PathBuf::from(".othala/config.toml");
-            let (enabled_models, default_model, notification_dispatcher, daemon_org_config) =
-                if config_path.exists() {
-                let mut org_config = load_org_config(&config_path)?;
-                let effective_profile = selected_cli_profile
-                    .clone()
-                    .or_else(|| org_config.profile.clone());
-                if let Some(profile) = &effective_profile {
-                    apply_profile_defaults(profile, &mut org_config);
-                    eprintln!("  Profile: {}", profile_label(profile));
-                }
-                use orch_core::validation::{Validate, ValidationLevel};
-                let issues = org_config.validate();
-                for issue in &issues {
-                    let prefix = match issue.level {
-                        ValidationLevel::Error => "\x1b[31mERROR\x1b[0m",
-                        ValidationLevel::Warning => "\x1b[33mWARN\x1b[0m",
-                    };
-                    eprintln!("  [{prefix}] {}: {}", issue.code, issue.message);
-                }
-                if issues.iter().any(|i| i.level == ValidationLevel::Error) {
-                    anyhow::bail!("config validation failed — run `othala wizard` to fix");
-                }
-                let default = org_config.models.default.unwrap_or(ModelKind::Claude);
-                let notification_dispatcher =
-                    build_notification_dispatcher(&org_config.notifications);
-                (
-                    org_config.models.enabled,
-                    default,
-                    notification_dispatcher,
-                    org_config.daemon,
-                )
-            } else {
-                eprintln!("  \x1b[33mNo .othala/config.toml — using defaults (run `othala wizard` to configure)\x1b[0m");
-                let mut org_config = OrgConfig::default();
-                if let Some(profile) = &selected_cli_profile {
-                    apply_profile_defaults(profile, &mut org_config);
-                    eprintln!("  Profile: {}", profile_label(profile));
-                }
-                (
-                    org_config.models.enabled,
-                    org_config.models.default.unwrap_or(ModelKind::Claude),
-                    None,
-                    org_config.daemon,
-                )
-            };
-            eprintln!(
-                "  Enabled models: {}",
-                enabled_models
-                    .iter()
-                    .map(|m| m.as_str())
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            );
-
-            if !skip_context_gen {
-                if let Err(e) =
-                    run_context_gen_with_status(&repo_root, &template_dir, default_model)
-                {
-                    eprintln!("[daemon] Context generation failed (non-fatal): {e}");
-                }
-            } else {
-                eprintln!("  Skipping context generation");
-            }
-            eprintln!();
-
-            if let Some(secs) = timeout {
-                eprintln!("[daemon] Timeout: {}s", secs);
-            }
-            if exit_on_idle {
-                eprintln!("[daemon] Will exit when all tasks reach terminal state");
-            }
-
-            let context_gen_config = orchd::context_gen::ContextGenConfig::default();
-            let mut supervisor = AgentSupervisor::new(default_model);
-            let mut daemon_state = orchd::daemon_loop::DaemonState::new();
-            daemon_state.notification_dispatcher = notification_dispatcher;
-
-            let nix_shell = orchd::daemon_loop::detect_nix_shell(&repo_root);
-            if !nix_shell.is_empty() {
-                eprintln!("[daemon] Nix dev shell: {nix_shell}");
-            }
-
-            let verify_cmd = verify_command
-                .unwrap_or_else(|| "cargo check && cargo test --workspace".to_string());
-
-            let mut daemon_config = orchd::daemon_loop::DaemonConfig {
+            let final_tasks = cli::daemon::run(
+                &mut service,
                 repo_root,
-                template_dir,
-                enabled_models,
-                context_config: orchd::context_graph::ContextLoadConfig::default(),
-                verify_command: Some(verify_cmd),
-                nix_shell,
-                context_gen_config,
-                skip_qa,
-                skip_context_regen: skip_context_gen,
-                dry_run: false,
-                agent_timeout_secs: daemon_org_config.agent_timeout_secs,
-                drain_timeout_secs: 30,
-            };
-            let mut tick_interval_secs = daemon_org_config.tick_interval_secs;
-
-            let shutdown = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
-            {
-                let flag = shutdown.clone();
-                signal_hook::flag::register(signal_hook::consts::SIGINT, flag.clone())?;
-                signal_hook::flag::register(signal_hook::consts::SIGTERM, flag)?;
-            }
-
-            let start = Instant::now();
-            let mut idle_grace_ticks: u32 = 0;
-            const IDLE_GRACE_MAX: u32 = 3;
-            let mut prev_states: HashMap<String, TaskState> = HashMap::new();
-
-            loop {
-                if let Some(new_config) =
-                    orchd::daemon_loop::check_config_reload(&config_path, &mut daemon_state)
-                {
-                    let mut new_config = new_config;
-                    let effective_profile = selected_cli_profile
-                        .clone()
-                        .or_else(|| new_config.profile.clone());
-                    if let Some(profile) = &effective_profile {
-                        apply_profile_defaults(profile, &mut new_config);
-                    }
-                    let mut changes = Vec::new();
-
-                    if daemon_config.enabled_models != new_config.models.enabled {
-                        changes.push("enabled_models".to_string());
-                        daemon_config.enabled_models = new_config.models.enabled.clone();
-                    }
-
-                    let scheduler_config = SchedulerConfig::from_org_config(&new_config);
-                    if service.scheduler.config != scheduler_config {
-                        changes.push("scheduler".to_string());
-                        service.scheduler.config = scheduler_config;
-                    }
-
-                    if daemon_config.agent_timeout_secs != new_config.daemon.agent_timeout_secs {
-                        changes.push("agent_timeout_secs".to_string());
-                        daemon_config.agent_timeout_secs = new_config.daemon.agent_timeout_secs;
-                    }
-
-                    if tick_interval_secs != new_config.daemon.tick_interval_secs {
-                        changes.push("tick_interval_secs".to_string());
-                        tick_interval_secs = new_config.daemon.tick_interval_secs;
-                    }
-
-                    let now = Utc::now();
-                    let change_summary = if changes.is_empty() {
-                        "no effective changes".to_string()
-                    } else {
-                        changes.join(", ")
-                    };
-                    let event = Event {
-                        id: EventId(format!(
-                            "E-CONFIG-RELOADED-{}",
-                            now.timestamp_nanos_opt().unwrap_or_default()
-                        )),
-                        task_id: None,
-                        repo_id: None,
-                        at: now,
-                        kind: EventKind::ConfigReloaded {
-                            changes: change_summary,
-                        },
-                    };
-                    if let Err(err) = service.record_event(&event) {
-                        eprintln!("[daemon] Failed to record config reload event: {err}");
-                    }
-                }
-
-                orchd::daemon_loop::run_tick(
-                    &service,
-                    &mut supervisor,
-                    &mut daemon_state,
-                    &daemon_config,
-                );
-
-                let tasks = service.list_tasks()?;
-                for task in &tasks {
-                    let prev = prev_states.get(&task.id.0);
-                    if prev != Some(&task.state) {
-                        if let Some(old) = prev {
-                            eprintln!("[daemon] {} -> {} ({})", task.id.0, task.state, old);
-                        }
-                        prev_states.insert(task.id.0.clone(), task.state);
-                    }
-                }
-                if !tasks.is_empty() {
-                    let chatting = tasks
-                        .iter()
-                        .filter(|t| t.state == TaskState::Chatting)
-                        .count();
-                    let ready = tasks.iter().filter(|t| t.state == TaskState::Ready).count();
-                    let submitting = tasks
-                        .iter()
-                        .filter(|t| t.state == TaskState::Submitting)
-                        .count();
-                    let awaiting = tasks
-                        .iter()
-                        .filter(|t| t.state == TaskState::AwaitingMerge)
-                        .count();
-                    let merged = tasks
-                        .iter()
-                        .filter(|t| t.state == TaskState::Merged)
-                        .count();
-                    let stopped = tasks
-                        .iter()
-                        .filter(|t| t.state == TaskState::Stopped)
-                        .count();
-                    let mut status = format!(
-                        "[{}] {} chatting, {} ready, {} submitting, {} awaiting, {} merged",
-                        chrono::Local::now().format("%H:%M:%S"),
-                        chatting,
-                        ready,
-                        submitting,
-                        awaiting,
-                        merged
-                    );
-                    if stopped > 0 {
-                        status.push_str(&format!(", {} stopped", stopped));
-                    }
-                    eprintln!("{status}");
-                }
-
-                if let Some(secs) = timeout {
-                    if start.elapsed().as_secs() >= secs {
-                        eprintln!("[daemon] Timeout reached ({}s), shutting down", secs);
-                        supervisor.stop_all();
-                        break;
-                    }
-                }
-
-                if exit_on_idle && all_tasks_idle(&service) {
-                    idle_grace_ticks += 1;
-                    if idle_grace_ticks >= IDLE_GRACE_MAX {
-                        eprintln!("[daemon] All tasks idle, shutting down");
-                        supervisor.stop_all();
-                        break;
-                    }
-                } else {
-                    idle_grace_ticks = 0;
-                }
-
-                if once {
-                    eprintln!("[daemon] --once mode, exiting after single tick");
-                    supervisor.stop_all();
-                    break;
-                }
-
-                if shutdown.load(std::sync::atomic::Ordering::Relaxed) {
-                    eprintln!("[daemon] Received signal, shutting down gracefully");
-                    supervisor.stop_all();
-                    break;
-                }
-
-                std::thread::sleep(std::time::Duration::from_secs(
-                    tick_interval_secs,
-                ));
-            }
-
-            let final_tasks = service.list_tasks()?;
+                cli::daemon::DaemonOptions {
+                    timeout,
+                    exit_on_idle,
+                    skip_context_gen,
+                    verify_command,
+                    skip_qa,
+                    skip_fast_verify,
+                    once,
+                    dry_run,
+                    profile: profile.map(ConfigProfile::from),
+                },
+            )?;
             let json =
                 serde_json::to_string_pretty(&final_tasks).unwrap_or_else(|_| "[]".to_string());
             println!("{json}");
@@ -3121,15 +2319,55 @@ fn main() -> anyhow::Result<()> {
             print_profiles();
         }
         Commands::SelfTest { json } => {
-            let critical_ok = run_self_test(json);
+            let checks = cli::doctor::self_test();
+            print_self_test(&repo_root, &checks, json);
+            let critical_ok = cli::doctor::critical_checks_pass(&checks);
             std::process::exit(if critical_ok { 0 } else { 1 });
         }
         Commands::Doctor { json } => {
-            let healthy = run_doctor(json)?;
+            let report = cli::doctor::doctor_report(&repo_root);
+            print_doctor_report(&repo_root, &report, json)?;
+            let healthy = report.all_ok;
             std::process::exit(if healthy { 0 } else { 1 });
         }
+        Commands::Signals { test } => {
+            let rules = orch_agents::SignalRuleSet::load(&repo_root)?;
+            match test {
+                Some(line) => print_signal_test(&rules, &line),
+                None => print_signal_rules(&rules),
+            }
+        }
+        Commands::Repair { .. } => unreachable!("handled before the service opens"),
+        Commands::ExportAnalytics { .. } => unreachable!("handled before the service opens"),
+        Commands::GraphiteLog { limit, json } => {
+            let entries =
+                orch_graphite::read_audit_log(&orch_graphite::audit_log_path(&repo_root), limit)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&entries)?);
+            } else if entries.is_empty() {
+                println!("No graphite commands recorded.");
+            } else {
+                for entry in &entries {
+                    let outcome = match (entry.dry_run, entry.exit_code) {
+                        (true, _) => "dry-run".to_string(),
+                        (false, Some(code)) => format!("exit {code}"),
+                        (false, None) => "failed".to_string(),
+                    };
+                    println!(
+                        "{}  {:<8} {:>6}ms  {} {}",
+                        entry.at.format("%Y-%m-%d %H:%M:%S"),
+                        outcome,
+                        entry.duration_ms,
+                        entry.command,
+                        entry.args.join(" ")
+                    );
+                    if !entry.stderr.is_empty() && entry.exit_code != Some(0) {
+                        println!("    {}", entry.stderr.lines().next().unwrap_or_default());
+                    }
+                }
+            }
+        }
         Commands::GraphiteRepair { json, dry_run } => {
-            let repo_root = std::env::current_dir()?;
             let tasks = service.store.list_tasks()?;
             let mut expected: std::collections::HashMap<String, Option<String>> = std::collections::HashMap::new();
             for task in &tasks {
@@ -3164,7 +2402,8 @@ fn main() -> anyhow::Result<()> {
 
                 if !dry_run {
                     println!();
-                    let graphite = orch_graphite::GraphiteClient::new(&repo_root);
+                    let graphite = orch_graphite::GraphiteClient::new(&repo_root)
+                        .with_audit_log(orch_graphite::audit_log_path(&repo_root));
                     let mut repaired = 0;
                     let mut failed = 0;
                     for info in &divergences {
@@ -3187,7 +2426,6 @@ fn main() -> anyhow::Result<()> {
             }
         }
         Commands::ContextStatus { json } => {
-            let repo_root = std::env::current_dir()?;
             let report = orchd::context_gen_telemetry::build_report(&repo_root, None);
             if json {
                 println!("{}", serde_json::to_string_pretty(&report)?);
@@ -3196,7 +2434,6 @@ fn main() -> anyhow::Result<()> {
             }
         }
         Commands::MissionStatus { json } => {
-            let repo_root = std::env::current_dir()?;
             let requirements = orchd::mission_vault::load_all_requirements(&repo_root);
             let tasks_raw = service.store.list_tasks()?;
             let tasks: Vec<orchd::mission_vault::TaskInfo> = tasks_raw
@@ -3263,8 +2500,16 @@ fn main() -> anyhow::Result<()> {
             ci,
             json,
             check_only,
+            non_interactive,
+            webhook_url,
+            slack_webhook_url,
+            slack_channel,
+            telegram_bot_token_env,
+            telegram_chat_id_env,
+            web_bind,
+            web_auth_token,
+            verify_command,
         } => {
-            let repo_root = std::env::current_dir()?;
             let readiness = orchd::wizard::run_readiness_checks(&repo_root);
 
             // CI mode: print report, exit based on score
@@ -3280,143 +2525,28 @@ fn main() -> anyhow::Result<()> {
                 std::process::exit(0);
             }
 
-            // Interactive / scripted wizard flow
-            print_banner();
-            eprintln!("\x1b[35mWelcome to Othala first-time setup\x1b[0m");
-            eprintln!();
-
-            // Show pre-setup readiness
-            orchd::wizard::print_readiness_report(&readiness, false);
-            eprintln!();
-
-            eprintln!("\x1b[33mProbing model availability...\x1b[0m");
-            let report = probe_models(&SetupProbeConfig::default());
-            for probe in &report.models {
-                let detected_text = if probe.installed {
-                    "\x1b[32mdetected\x1b[0m"
-                } else {
-                    "\x1b[31mnot detected\x1b[0m"
-                };
-                let health_text = if probe.healthy {
-                    "\x1b[32mhealthy\x1b[0m"
-                } else {
-                    "\x1b[33munhealthy\x1b[0m"
-                };
-
-                eprintln!(
-                    "  - {:<7} : {} / {}",
-                    model_name(probe.model),
-                    detected_text,
-                    health_text
-                );
-            }
-            eprintln!();
-
-            let selected_models = if let Some(raw) = enable {
-                parse_enable_models_csv(&raw)?
-            } else {
-                prompt_enabled_models()?
-            };
-
-            let validated = validate_setup_selection(
-                &report,
-                &ModelSetupSelection {
-                    enabled_models: selected_models,
+            cli::wizard::run(
+                &repo_root,
+                &readiness,
+                WizardOptions {
+                    enable,
+                    per_model_concurrency,
+                    non_interactive,
+                    settings: orchd::wizard::WizardSettings {
+                        webhook_url,
+                        slack_webhook_url,
+                        slack_channel,
+                        telegram_bot_token_env,
+                        telegram_chat_id_env,
+                        web_bind,
+                        web_auth_token,
+                        verify_command,
+                    },
                 },
-            )
-            .map_err(|err| anyhow::anyhow!(err.to_string()))?;
-
-            let setup_summary = summarize_setup(&report, &validated);
-            eprintln!("\x1b[33mSetup summary\x1b[0m");
-            for item in &setup_summary.items {
-                if item.selected {
-                    let status = if item.healthy {
-                        "\x1b[32mready\x1b[0m"
-                    } else {
-                        "\x1b[33mselected with warnings\x1b[0m"
-                    };
-                    eprintln!("  - {:<7} : {}", model_name(item.model), status);
-                }
-            }
-            eprintln!();
-
-            let config_path = PathBuf::from(".othala/config.toml");
-            let mut org_config = if config_path.exists() {
-                load_org_config(&config_path)?
-            } else {
-                default_org_config(validated.enabled_models.clone())
-            };
-
-            let per_model = per_model_concurrency.unwrap_or(10);
-            apply_setup_selection_to_org_config(
-                &mut org_config,
-                &validated.enabled_models,
-                per_model,
-            )
-            .map_err(|err| anyhow::anyhow!(err.to_string()))?;
-            if org_config.models.default.is_none() {
-                org_config.models.default = validated.enabled_models.first().copied();
-            }
-
-            save_org_config(&config_path, &org_config)?;
-
-            let context_main_path = PathBuf::from(".othala/context/MAIN.md");
-            let context_generated = if context_main_path.exists() {
-                false
-            } else {
-                let template_dir = PathBuf::from("templates/prompts");
-                run_context_gen_with_status(
-                    &repo_root,
-                    &template_dir,
-                    validated
-                        .enabled_models
-                        .first()
-                        .copied()
-                        .unwrap_or(ModelKind::Claude),
-                )?;
-                true
-            };
-
-            eprintln!("\x1b[32mSetup complete\x1b[0m");
-            eprintln!("  - Config: {}", config_path.display());
-            eprintln!(
-                "  - Enabled models: {}",
-                validated
-                    .enabled_models
-                    .iter()
-                    .map(|m| model_name(*m))
-                    .collect::<Vec<_>>()
-                    .join(",")
-            );
-            eprintln!("  - Per-model concurrency: {per_model}");
-            if context_generated {
-                eprintln!("  - Context: \x1b[32mgenerated\x1b[0m");
-            } else {
-                eprintln!("  - Context: \x1b[33malready present\x1b[0m");
-            }
-            if setup_summary.all_selected_healthy {
-                eprintln!("  - Model health: \x1b[32mall selected models healthy\x1b[0m");
-            } else {
-                eprintln!("  - Model health: \x1b[33msome selected models have warnings\x1b[0m");
-            }
-
-            // Post-setup readiness re-check
-            eprintln!();
-            let post_readiness = orchd::wizard::run_readiness_checks(&repo_root);
-            orchd::wizard::print_readiness_report(&post_readiness, false);
+            )?;
         }
         Commands::Logs { id, limit, json } => {
-            let events = if let Some(ref task_id_str) = id {
-                service.task_events(&TaskId::new(task_id_str))?
-            } else {
-                service.global_events()?
-            };
-
-            let display_events: Vec<_> = if events.len() > limit {
-                events[events.len() - limit..].to_vec()
-            } else {
-                events
-            };
+            let display_events = cli::history::logs(&service, id.as_deref(), limit)?;
 
             if json {
                 let out = serde_json::to_string_pretty(&display_events)
@@ -3433,6 +2563,27 @@ fn main() -> anyhow::Result<()> {
                 }
             }
         }
+        Commands::PromptPreview { id, model } => {
+            let task_id = service.resolve_task_id(&id)?;
+            let Some(task) = service.task(&task_id)? else {
+                anyhow::bail!("task not found: {id}");
+            };
+            let model = match model {
+                Some(value) => parse_model_arg(&value, &repo_models_config(&repo_root))?,
+                None => task.preferred_model.unwrap_or(ModelKind::Claude),
+            };
+            let preview = cli::prompt::preview(&task, model, &repo_root);
+            println!("Model: {model}");
+            println!(
+                "Template: {}",
+                preview
+                    .template
+                    .map(|path| path.display().to_string())
+                    .unwrap_or_else(|| "(none)".to_string())
+            );
+            println!("{}", "-".repeat(76));
+            println!("{}", preview.prompt);
+        }
         Commands::Replay {
             task_id,
             since,
@@ -3464,9 +2615,27 @@ fn main() -> anyhow::Result<()> {
                 }
             }
         }
+        Commands::VerifyFailure { task_id, latest } => {
+            let task_id = service.resolve_task_id(&task_id)?;
+            let mut bundles = orchd::verify_failure::list_bundles(&repo_root, &task_id);
+            if bundles.is_empty() {
+                println!("No verify failures recorded for {}", task_id.0);
+                return Ok(());
+            }
+            if latest {
+                bundles.drain(..bundles.len() - 1);
+            }
+            for (index, dir) in bundles.iter().enumerate() {
+                if index > 0 {
+                    println!();
+                }
+                let bundle = orchd::verify_failure::load_bundle(dir)?;
+                println!("Bundle: {}", dir.display());
+                print!("{}", bundle.render());
+            }
+        }
         Commands::Tail { id, lines, follow } => {
-            let repo_root = std::env::current_dir()?;
-            let task_id = TaskId::new(&id);
+            let task_id = service.resolve_task_id(&id)?;
 
             let mut displayed_lines = 0usize;
 
@@ -3510,20 +2679,33 @@ fn main() -> anyhow::Result<()> {
                 }
             }
         }
-        Commands::Compact { task_id, max_lines } => {
-            let repo_root = std::env::current_dir()?;
-            let task = TaskId::new(&task_id);
+        Commands::Compact {
+            task_id,
+            max_lines,
+            max_tokens,
+        } => {
+            let task = service.resolve_task_id(&task_id)?;
             let content = orchd::agent_log::read_agent_log(&repo_root, &task)
                 .map_err(|err| anyhow::anyhow!("failed to read latest agent output for {task_id}: {err}"))?;
             let lines: Vec<String> = content.lines().map(String::from).collect();
 
-            let result = orchd::agent_log::compact_context(&lines, max_lines.unwrap_or(120));
+            let mut budget = orchd::auto_compact::AutoCompactConfig::default().compaction_budget();
+            budget.max_lines = max_lines.unwrap_or(budget.max_lines);
+            budget.max_tokens = max_tokens.unwrap_or(budget.max_tokens);
+            let result = orchd::agent_log::compact_context_with_budget(
+                &lines,
+                budget,
+                orchd::auto_compact::estimate_tokens,
+            );
             let compacted_path =
                 orchd::agent_log::save_compacted_summary(&repo_root, &task, &result.summary)?;
 
             println!(
-                "Compacted {task_id}: {} -> {} lines (ratio {:.3})",
-                result.original_lines, result.compacted_lines, result.compression_ratio
+                "Compacted {task_id}: {} -> {} lines, ~{} tokens (ratio {:.3})",
+                result.original_lines,
+                result.compacted_lines,
+                orchd::auto_compact::estimate_tokens(&result.summary),
+                result.compression_ratio
             );
             println!("Saved compacted summary: {}", compacted_path.display());
             if !result.summary.is_empty() {
@@ -3531,10 +2713,13 @@ fn main() -> anyhow::Result<()> {
             }
         }
         Commands::Watch { task, lines } => {
-            run_watch_command(&service, task, lines)?;
+            let task = task
+                .map(|task| service.resolve_task_id(&task).map(|id| id.0))
+                .transpose()?;
+            cli::watch::run(&service, &repo_root, task, lines)?;
         }
         Commands::Runs { id, json } => {
-            let runs = service.task_runs(&TaskId::new(&id))?;
+            let runs = cli::history::runs(&service, &id)?;
             if json {
                 let out = serde_json::to_string_pretty(&runs).unwrap_or_else(|_| "[]".to_string());
                 println!("{out}");
@@ -3542,8 +2727,14 @@ fn main() -> anyhow::Result<()> {
                 println!("No runs found for task: {id}");
             } else {
                 let header = format!(
-                    "{:<36} {:<8} {:<20} {:<20} {:<12} {}",
-                    "RUN ID", "MODEL", "STARTED", "FINISHED", "EXIT CODE", "STOP REASON"
+                    "{:<36} {:<8} {:<20} {:<20} {:<12} {:<11} {}",
+                    "RUN ID",
+                    "MODEL",
+                    "STARTED",
+                    "FINISHED",
+                    "EXIT CODE",
+                    "CONFIDENCE",
+                    "STOP REASON"
                 );
                 println!("{header}");
                 for run in &runs {
@@ -3556,40 +2747,37 @@ fn main() -> anyhow::Result<()> {
                         .exit_code
                         .map(|c| c.to_string())
                         .unwrap_or_else(|| "-".to_string());
+                    let confidence = run
+                        .confidence
+                        .map(|c| format!("{c}%"))
+                        .unwrap_or_else(|| "-".to_string());
                     let stop_reason = run.stop_reason.as_deref().unwrap_or("-");
                     println!(
-                        "{:<36} {:<8} {:<20} {:<20} {:<12} {}",
+                        "{:<36} {:<8} {:<20} {:<20} {:<12} {:<11} {}",
                         run.run_id,
                         run.model.as_str(),
                         started,
                         finished,
                         exit_code,
+                        confidence,
                         stop_reason
                     );
                 }
             }
         }
         Commands::Retries { id, json } => {
-            let task_id = TaskId::new(&id);
-            let events = service.task_events(&task_id)?;
-            let runs = service.task_runs(&task_id)?;
-            let retry_events = collect_retry_events(&events);
-            let timeline = build_retry_timeline(&events, &runs);
-
+            let history = cli::history::retries(&service, &id)?;
             if json {
-                let payload = RetryHistoryOutput {
-                    task_id: id,
-                    retry_events,
-                    timeline,
-                };
-                println!("{}", serde_json::to_string_pretty(&payload)?);
+                println!("{}", serde_json::to_string_pretty(&history)?);
             } else {
-                println!("{}", format_retries_timeline(&task_id.0, &timeline));
+                println!(
+                    "{}",
+                    cli::history::format_retries_timeline(&history.task_id, &history.timeline)
+                );
             }
         }
         Commands::DiffRetries { task_id } => {
-            let repo_root = std::env::current_dir()?;
-            let task = TaskId::new(&task_id);
+            let task = service.resolve_task_id(&task_id)?;
             let log_dir = orchd::agent_log::agent_log_dir(&repo_root, &task);
             let latest_path = log_dir.join("latest.log");
             let previous_path = log_dir.join("latest.log.1");
@@ -3618,12 +2806,8 @@ fn main() -> anyhow::Result<()> {
                 summary.added, summary.removed, summary.unchanged
             );
         }
-        Commands::Stats { json } => {
-            let tasks = service.list_tasks()?;
-            let state_counts = service.store.task_count_by_state()?;
-            let total_events = service.store.total_event_count()?;
-            let summary = compute_stats_summary(&tasks, state_counts, total_events);
-
+        Commands::Stats { json, days } => {
+            let summary = cli::stats::stats(&service, days)?;
             if json {
                 println!("{}", serde_json::to_string_pretty(&summary)?);
             } else {
@@ -3633,54 +2817,102 @@ fn main() -> anyhow::Result<()> {
         Commands::Gc {
             older_than_days,
             dry_run,
+            branches: true,
         } => {
-            let repo_root = std::env::current_dir()?;
-            let summary = gc_logs(&repo_root, older_than_days, dry_run)?;
+            let git = GitCli::default();
+            let repo = discover_repo(&repo_root, &git)?;
+            let base = resolve_base_branch();
+            let summary =
+                cli::gc::gc_branches(&repo, &git, &service, &base, older_than_days, dry_run)?;
             let action = if dry_run { "Would delete" } else { "Deleted" };
+            for branch in &summary.deleted {
+                println!(
+                    "{action} branch {} ({})",
+                    branch.name,
+                    branch.evidence(&base)
+                );
+            }
+            for name in &summary.checked_out {
+                println!("Kept branch {name}: checked out in a worktree");
+            }
             println!(
-                "{action} {} event files, {} agent output dirs (freed ~{})",
-                summary.deleted_event_files,
-                summary.deleted_agent_output_dirs,
-                format_bytes(summary.bytes_freed)
+                "{action} {} orphaned branches (base {base}, cutoff {older_than_days} days)",
+                summary.deleted.len()
             );
         }
-        Commands::Stop { id } => {
-            let task_id = TaskId::new(&id);
-            let now = Utc::now();
-            let event_id = EventId(format!("E-STOP-{}-{}", id, now.timestamp_millis()));
-            match service.transition_task_state(&task_id, TaskState::Stopped, event_id, now) {
-                Ok(task) => println!("Stopped: {} (was {})", id, task.state),
-                Err(e) => {
-                    eprintln!("Failed to stop {id}: {e}");
-                    std::process::exit(1);
+        Commands::Gc {
+            older_than_days,
+            dry_run,
+            branches: false,
+        } => {
+            let summary = cli::gc::gc(&repo_root, older_than_days, dry_run)?;
+            if dry_run {
+                for event_file in &summary.event_files {
+                    println!(
+                        "[dry-run] would delete file {}",
+                        relative_to_root(&repo_root, event_file).display()
+                    );
                 }
-            }
-        }
-        Commands::Cancel { id } => {
-            let task_id = TaskId::new(&id);
-            match cancel_task(&service, &task_id, "requested by user") {
-                Ok(from_state) => println!("Cancelled: {id} ({from_state} -> STOPPED)"),
-                Err(e) => {
-                    eprintln!("Failed to cancel {id}: {e}");
-                    std::process::exit(1);
+                for dir in &summary.agent_output_dirs {
+                    println!(
+                        "[dry-run] would delete dir  {}",
+                        relative_to_root(&repo_root, dir).display()
+                    );
                 }
             }
+            let action = if dry_run { "Would delete" } else { "Deleted" };
+            println!(
+                "{action} {} event files, {} agent output dirs (freed ~{})",
+                summary.event_files.len(),
+                summary.agent_output_dirs.len(),
+                format_bytes(summary.bytes_freed)
+            );
         }
-        Commands::Resume { id } => {
-            let task_id = TaskId::new(&id);
-            let now = Utc::now();
-            let event_id = EventId(format!("E-RESUME-{}-{}", id, now.timestamp_millis()));
-            match service.transition_task_state(&task_id, TaskState::Chatting, event_id, now) {
-                Ok(_) => println!("Resumed: {id} -> Chatting"),
-                Err(e) => {
-                    eprintln!("Failed to resume {id}: {e}");
-                    std::process::exit(1);
-                }
+        Commands::Stop { id } => match cli::tasks::stop(&service, &id) {
+            Ok(change) => println!("Stopped: {} (was {})", id, change.from),
+            Err(e) => {
+                eprintln!("Failed to stop {id}: {e}");
+                std::process::exit(1);
+            }
+        },
+        Commands::Cancel { id, reason } => match cli::tasks::cancel(&service, &id, &reason) {
+            Ok(change) => println!("Cancelled: {id} ({} -> STOPPED)", change.from),
+            Err(e) => {
+                eprintln!("Failed to cancel {id}: {e}");
+                std::process::exit(1);
+            }
+        },
+        Commands::Resume { id } => match cli::tasks::resume(&service, &id) {
+            Ok(_) => println!("Resumed: {id} -> Chatting"),
+            Err(e) => {
+                eprintln!("Failed to resume {id}: {e}");
+                std::process::exit(1);
             }
-        }
-        Commands::Deps { json } => {
+        },
+        Commands::Deps { json, order } => {
             let tasks = service.list_tasks()?;
-            if json {
+            if order {
+                let order = orchd::build_dependency_graph(&tasks).topo_order()?;
+                let by_id: HashMap<&TaskId, &Task> =
+                    tasks.iter().map(|task| (&task.id, task)).collect();
+                if json {
+                    let ids: Vec<&str> = order.iter().map(|id| id.0.as_str()).collect();
+                    println!("{}", serde_json::to_string_pretty(&ids)?);
+                } else if order.is_empty() {
+                    println!("No tasks.");
+                } else {
+                    for (position, id) in order.iter().enumerate() {
+                        let task = by_id[id];
+                        println!(
+                            "{:>3}. {:<20} {:<8} {}",
+                            position + 1,
+                            id.0,
+                            task.priority.as_str(),
+                            task.title
+                        );
+                    }
+                }
+            } else if json {
                 let deps: Vec<serde_json::Value> = tasks
                     .iter()
                     .map(|t| {
@@ -3699,6 +2931,9 @@ fn main() -> anyhow::Result<()> {
                     serde_json::to_string_pretty(&deps).unwrap_or_else(|_| "[]".to_string())
                 );
             } else {
+                if let Some(cycle) = orchd::build_lineage_graph(&tasks).detect_cycle() {
+                    anyhow::bail!("{}", format_dependency_cycle(&cycle));
+                }
                 let root_tasks: Vec<_> = tasks
                     .iter()
                     .filter(|t| t.parent_task_id.is_none())
@@ -3725,70 +2960,33 @@ fn main() -> anyhow::Result<()> {
                 }
             }
         }
-        Commands::Template { action } => {
-            let repo_root = std::env::current_dir()?;
-            match action {
-                TemplateAction::List => {
-                    let names = list_templates(&repo_root)?;
-                    if names.is_empty() {
-                        println!("No templates found.");
-                    } else {
-                        for name in names {
-                            println!("{name}");
-                        }
+        Commands::Template { action } => match action {
+            TemplateAction::List => {
+                let names = list_templates(&repo_root)?;
+                if names.is_empty() {
+                    println!("No templates found.");
+                } else {
+                    for name in names {
+                        println!("{name}");
                     }
                 }
-                TemplateAction::Create { name, from_task } => {
-                    let task_id = TaskId::new(&from_task);
-                    let Some(task) = service.task(&task_id)? else {
-                        anyhow::bail!("task not found: {from_task}");
-                    };
-                    let template = TaskTemplate::from_task(&task);
-                    save_template(&repo_root, &name, &template)?;
-                    println!("Saved template: {name}");
-                }
-                TemplateAction::Show { name } => {
-                    let template = load_template(&repo_root, &name)?;
-                    println!("{}", serde_json::to_string_pretty(&template)?);
-                }
             }
-        }
+            TemplateAction::Create { name, from_task } => {
+                let task_id = service.resolve_task_id(&from_task)?;
+                let Some(task) = service.task(&task_id)? else {
+                    anyhow::bail!("task not found: {from_task}");
+                };
+                let template = TaskTemplate::from_task(&task);
+                save_template(&repo_root, &name, &template)?;
+                println!("Saved template: {name}");
+            }
+            TemplateAction::Show { name } => {
+                let template = load_template(&repo_root, &name)?;
+                println!("{}", serde_json::to_string_pretty(&template)?);
+            }
+        },
         Commands::Export { output, task_id } => {
-            let tasks = service.list_tasks()?;
-            let parents: HashMap<String, Option<String>> = tasks
-                .iter()
-                .map(|task| {
-                    let parent_branch = task.parent_task_id.as_ref().and_then(|parent_id| {
-                        tasks
-                            .iter()
-                            .find(|candidate| candidate.id == *parent_id)
-                            .and_then(|parent| parent.branch_name.clone())
-                    });
-                    (task.id.0.clone(), parent_branch)
-                })
-                .collect();
-
-            let records: Vec<TaskExportRecord> = if let Some(id) = task_id {
-                let task = tasks
-                    .iter()
-                    .find(|task| task.id.0 == id)
-                    .ok_or_else(|| anyhow::anyhow!("task not found: {id}"))?;
-                vec![TaskExportRecord::from_task(
-                    task,
-                    parents.get(&task.id.0).cloned().flatten(),
-                )]
-            } else {
-                tasks
-                    .iter()
-                    .map(|task| {
-                        TaskExportRecord::from_task(
-                            task,
-                            parents.get(&task.id.0).cloned().flatten(),
-                        )
-                    })
-                    .collect()
-            };
-
+            let records = cli::transfer::export(&service, task_id.as_deref())?;
             let payload = serde_json::to_string_pretty(&records)?;
             std::fs::write(&output, payload)?;
             println!("Exported {} task(s) to {}", records.len(), output.display());
@@ -3796,20 +2994,62 @@ fn main() -> anyhow::Result<()> {
         Commands::Import { input } => {
             let payload = std::fs::read_to_string(&input)?;
             let records: Vec<TaskExportRecord> = serde_json::from_str(&payload)?;
-            let mut imported = 0usize;
+            let imported = cli::transfer::import(&service, records)?;
+            println!("Imported {} task(s) from {}", imported, input.display());
+        }
+        Commands::ImportIssues {
+            file,
+            mapping,
+            repo,
+        } => {
+            use orchd::issue_import::{
+                apply_issue, extract_issues, IssueImportOutcome, IssueMapping,
+            };
 
-            for record in records {
-                let existing = service.task(&TaskId::new(record.task_id.clone()))?;
-                let task = import_record_to_task(record, existing)?;
-                service.upsert_task(&task)?;
-                imported += 1;
+            let mapping = IssueMapping::from_toml(&std::fs::read_to_string(&mapping)?)?;
+            let export: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&file)?)?;
+            let issues = extract_issues(&export, &mapping)?;
+            let repo_id = RepoId(repo);
+            let mut tasks = service.list_tasks()?;
+            let (mut created, mut updated) = (0usize, 0usize);
+
+            for issue in &issues {
+                match apply_issue(issue, &mapping.source, &repo_id, &tasks) {
+                    IssueImportOutcome::Created(task) => {
+                        let event = Event {
+                            id: EventId(format!("E-CREATE-{}", task.id.0)),
+                            task_id: Some(task.id.clone()),
+                            repo_id: Some(task.repo_id.clone()),
+                            at: Utc::now(),
+                            kind: EventKind::TaskCreated,
+                        };
+                        service.create_task(&task, &event)?;
+                        println!("  + {} {} ({})", task.id.0, task.title, issue.external_id);
+                        tasks.push(task);
+                        created += 1;
+                    }
+                    IssueImportOutcome::Updated(task) => {
+                        service.upsert_task(&task)?;
+                        println!("  ~ {} {} ({})", task.id.0, task.title, issue.external_id);
+                        if let Some(slot) = tasks.iter_mut().find(|t| t.id == task.id) {
+                            *slot = task;
+                        }
+                        updated += 1;
+                    }
+                }
             }
 
-            println!("Imported {} task(s) from {}", imported, input.display());
+            println!(
+                "Imported {} {} issue(s) from {}: {} created, {} updated",
+                issues.len(),
+                mapping.source,
+                file.display(),
+                created,
+                updated
+            );
         }
         Commands::Costs { task, budget } => {
             if budget {
-                let repo_root = std::env::current_dir()?;
                 let config_path = repo_root.join(".othala/config.toml");
                 let budget_config = load_org_config(&config_path)
                     .map(|config| config.budget)
@@ -3838,7 +3078,7 @@ fn main() -> anyhow::Result<()> {
             } else {
                 let tasks = service.list_tasks()?;
                 if let Some(task_id) = task {
-                    let runs = service.task_runs(&TaskId::new(&task_id))?;
+                    let runs = service.task_runs(&service.resolve_task_id(&task_id)?)?;
                     let estimates = aggregate_cost_estimates(&runs);
                     let total_tokens: u64 = estimates.iter().map(|e| e.input_tokens).sum();
                     let total_duration: f64 = estimates.iter().map(|e| e.duration_secs).sum();
@@ -3875,44 +3115,16 @@ fn main() -> anyhow::Result<()> {
         Commands::Prune {
             older_than_days,
             force,
+            delete_branches,
         } => {
-            let now = Utc::now();
-            let cutoff = now - chrono::Duration::days(older_than_days);
-            let tasks = service.list_tasks()?;
-            let prunable: Vec<_> = tasks
-                .iter()
-                .filter(|t| t.state.is_terminal() && t.updated_at < cutoff)
-                .collect();
-
-            if prunable.is_empty() {
-                println!(
-                    "No tasks to prune (older than {older_than_days} days in terminal state)."
-                );
-            } else {
-                println!(
-                    "{}",
-                    if force {
-                        "Pruning tasks:"
-                    } else {
-                        "Would prune (use --force to delete):"
-                    }
-                );
-                for task in &prunable {
-                    let age_days = (now - task.updated_at).num_days();
-                    println!(
-                        "  {} ({}, {} days old) - {}",
-                        task.id.0, task.state, age_days, task.title
-                    );
-                    if force {
-                        if let Err(e) = service.delete_task(&task.id) {
-                            eprintln!("    Failed to delete: {e}");
-                        }
-                    }
-                }
-                if !force {
-                    println!("\nRun with --force to actually delete.");
-                }
-            }
+            let report = cli::prune::prune(
+                &service,
+                &repo_root,
+                older_than_days,
+                force,
+                delete_branches,
+            )?;
+            print_prune_report(&report, older_than_days, force);
         }
         Commands::Archive {
             older_than_days,
@@ -3929,7 +3141,7 @@ fn main() -> anyhow::Result<()> {
             }
         }
         Commands::Permissions { json } => {
-            let policy = PermissionPolicy::default_policy();
+            let policy = PermissionPolicy::load(&repo_root)?;
             if json {
                 println!("{}", serde_json::to_string_pretty(&policy).unwrap_or_default());
             } else {
@@ -3950,6 +3162,7 @@ fn main() -> anyhow::Result<()> {
                 path_pattern: path.clone(),
                 reason: None,
             };
+            persist_permission_rule(&repo_root, model.as_deref(), rule)?;
             let model_scope = model
                 .as_deref()
                 .map(|m| format!(" for model {m}"))
@@ -3961,7 +3174,6 @@ fn main() -> anyhow::Result<()> {
                 model_scope,
                 path.map(|p| format!(" (path: {p})")).unwrap_or_default()
             );
-            let _ = rule;
         }
         Commands::Deny {
             category,
@@ -3977,27 +3189,103 @@ fn main() -> anyhow::Result<()> {
                 path_pattern: path.clone(),
                 reason: None,
             };
+            persist_permission_rule(&repo_root, model.as_deref(), rule)?;
             println!(
                 "Denied: {} for {}{}",
                 cat,
                 model.as_deref().unwrap_or("all models"),
                 path.map(|p| format!(" (path: {p})")).unwrap_or_default()
             );
-            let _ = rule;
         }
-        Commands::Mcp => {
+        Commands::Mcp { max_log_bytes } => {
             use orchd::mcp::McpServer;
 
+            let service = std::rc::Rc::new(service);
             let mut server = McpServer::new();
             server.register_builtin_tools();
+            server.register_service_tools(std::rc::Rc::clone(&service));
+            server.register_task_creation_tool(std::rc::Rc::clone(&service), &repo_root);
+            server.register_log_tools(std::rc::Rc::clone(&service), &repo_root);
+            server.register_project_resources(&repo_root, service, max_log_bytes);
+            server.set_permission_policy(mcp_permission_policy(&repo_root));
             eprintln!("Othala MCP server started (stdin/stdout)");
             if let Err(e) = server.run_stdio() {
                 eprintln!("MCP server error: {e}");
                 std::process::exit(1);
             }
         }
+        Commands::QaSpec { check, model } => {
+            let output = orchd::qa_spec_gen::generate_qa_spec_blocking(
+                &repo_root,
+                Path::new("templates/prompts"),
+                parse_model(&model),
+                |_| {},
+            )?;
+            if output.files.is_empty() {
+                anyhow::bail!("QA spec generation agent produced no spec files");
+            }
+            if check {
+                let drift = orchd::qa_spec_gen::check_qa_spec_files(&repo_root, &output);
+                for spec in &drift {
+                    match spec {
+                        orchd::qa_spec_gen::QASpecDrift::Missing { filename } => {
+                            println!("Missing spec: .othala/qa/{filename}");
+                        }
+                        orchd::qa_spec_gen::QASpecDrift::Changed { diff, .. } => print!("{diff}"),
+                    }
+                }
+                if !drift.is_empty() {
+                    std::process::exit(1);
+                }
+                println!("QA specs are up to date ({} files)", output.files.len());
+            } else {
+                let paths = orchd::qa_spec_gen::write_qa_spec_files(&repo_root, &output)?;
+                for path in &paths {
+                    println!("Wrote {}", relative_to_root(&repo_root, path).display());
+                }
+            }
+        }
+        Commands::Qa { action } => match action {
+            QaAction::Show { task_id: id } => {
+                let task_id = service.resolve_task_id(&id)?;
+                let Some(spec) = orchd::qa_spec_gen::load_generated_qa_spec(&repo_root, &task_id)
+                else {
+                    anyhow::bail!("no generated QA spec for {id}; run `othala qa regen {id}`");
+                };
+                let path = orchd::qa_spec_gen::generated_spec_path(&repo_root, &task_id);
+                println!(
+                    "{} ({} checks)\n",
+                    relative_to_root(&repo_root, &path).display(),
+                    spec.tests.len()
+                );
+                println!("{}", spec.raw.trim_end());
+            }
+            QaAction::Regen { task_id: id, model } => {
+                let task_id = service.resolve_task_id(&id)?;
+                let Some(task) = service.task(&task_id)? else {
+                    anyhow::bail!("task not found: {id}");
+                };
+                let Some(diff) = orchd::daemon_loop::task_diff_snapshot(&task.worktree_path) else {
+                    anyhow::bail!("cannot read the diff of {}", task.worktree_path.display());
+                };
+                if diff.files.is_empty() {
+                    anyhow::bail!("task {id} has no changes to generate a QA spec from");
+                }
+                let spec = orchd::qa_spec_gen::generate_qa_spec(
+                    &repo_root,
+                    &task,
+                    &diff,
+                    parse_model(&model),
+                )?;
+                let path = orchd::qa_spec_gen::generated_spec_path(&repo_root, &task_id);
+                println!(
+                    "Wrote {} ({} checks)",
+                    relative_to_root(&repo_root, &path).display(),
+                    spec.tests.len()
+                );
+            }
+        },
         Commands::Skills => {
-            let repo_root = std::env::current_dir()?;
             let registry = SkillRegistry::discover(&repo_root);
             let skills = registry.list_skills();
             if skills.is_empty() {
@@ -4016,7 +3304,6 @@ fn main() -> anyhow::Result<()> {
             }
         }
         Commands::Skill { name } => {
-            let repo_root = std::env::current_dir()?;
             let registry = SkillRegistry::discover(&repo_root);
             if let Some(skill) = registry.load_skill(&name) {
                 print!("{}", skill.content);
@@ -4053,6 +3340,9 @@ fn main() -> anyhow::Result<()> {
                     arg_map.insert(k.to_string(), v.to_string());
                 }
             }
+            for unknown in orchd::custom_commands::unknown_arguments(cmd, &arg_map) {
+                eprintln!("warning: ignoring unknown arg: {unknown}");
+            }
             match orchd::custom_commands::render_command(cmd, &arg_map) {
                 Ok(rendered) => {
                     if json {
@@ -4107,8 +3397,11 @@ fn main() -> anyhow::Result<()> {
                 println!("{}", orchd::upgrade::display_version_check(&info));
             }
         }
-        Commands::Models { json } => {
-            let registry = orchd::provider_registry::ModelRegistry::new();
+        Commands::Models { json, enabled_only } => {
+            let mut registry = load_model_registry(&repo_root)?;
+            if enabled_only {
+                registry.retain_enabled(&repo_models_config(&repo_root).enabled);
+            }
             if json {
                 println!("{}", serde_json::to_string_pretty(&registry).unwrap_or_default());
             } else {
@@ -4116,15 +3409,42 @@ fn main() -> anyhow::Result<()> {
             }
         }
         Commands::Providers { json } => {
-            let registry = orchd::provider_registry::ModelRegistry::new();
+            let registry = load_model_registry(&repo_root)?;
+            let probes = probe_models(
+                &SetupProbeConfig::default().with_custom_models(&repo_models_config(&repo_root)),
+            );
+            let health = |name: &str| {
+                registry.provider_health(name, &probes, |key| std::env::var_os(key).is_some())
+            };
             if json {
-                let providers = registry.list_providers();
+                let providers = registry
+                    .list_providers()
+                    .into_iter()
+                    .map(|p| {
+                        let mut value = serde_json::to_value(p).unwrap_or_default();
+                        if let Some(object) = value.as_object_mut() {
+                            object.insert(
+                                "health".to_string(),
+                                serde_json::to_value(health(&p.name)).unwrap_or_default(),
+                            );
+                        }
+                        value
+                    })
+                    .collect::<Vec<_>>();
                 println!("{}", serde_json::to_string_pretty(&providers).unwrap_or_default());
             } else {
                 for p in registry.list_providers() {
                     println!("{} ({})", p.display_name, p.name);
                     println!("  API: {}", p.api_base);
                     println!("  Auth: ${}", p.auth_env_var);
+                    if let Some(health) = health(&p.name) {
+                        let status = if health.healthy {
+                            "healthy"
+                        } else {
+                            "unhealthy"
+                        };
+                        println!("  Health: {status} ({})", health.detail);
+                    }
                     let models = registry.models_for_provider(&p.name);
                     if !models.is_empty() {
                         println!("  Models:");
@@ -4164,13 +3484,30 @@ fn main() -> anyhow::Result<()> {
                 let collector = orchd::metrics::MetricsCollector::new(
                     orchd::metrics::MetricsConfig::default(),
                 );
+                let history = orchd::orchestration_metrics::OrchestrationMetricsStore::from_history(
+                    repo_root.join("logs/metrics"),
+                    &service.list_tasks()?,
+                    &service.store.list_events_global()?,
+                );
+                let percentiles = history.duration_percentiles();
+                let rate_limits = orchd::rate_limiter::load_stats(&repo_root);
                 if json {
+                    let summary = orchd::metrics::MetricsSummary {
+                        duration_percentiles: Some(percentiles),
+                        rate_limits,
+                        ..collector.summary()
+                    };
                     println!(
                         "{}",
-                        serde_json::to_string_pretty(&collector.summary()).unwrap_or_default()
+                        serde_json::to_string_pretty(&summary).unwrap_or_default()
                     );
                 } else {
                     println!("{}", collector.display_summary());
+                    println!("{}", percentiles.display());
+                    if !rate_limits.is_empty() {
+                        println!("Rate limits:");
+                        println!("{}", orchd::rate_limiter::display_stats(&rate_limits));
+                    }
                 }
             }
         }
@@ -4211,7 +3548,10 @@ fn main() -> anyhow::Result<()> {
         Commands::Delegate { task_id, json } => {
             let plan = orchd::delegation::DelegationPlan::new(&task_id);
             if json {
-                println!("{}", serde_json::to_string_pretty(&plan).unwrap_or_default());
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&plan.sized()).unwrap_or_default()
+                );
             } else {
                 println!("{}", plan.summary());
             }
@@ -4268,23 +3608,60 @@ fn main() -> anyhow::Result<()> {
                     }
                 }
             }
+            LspAction::Diagnostics { path, json } => {
+                let mut manager = orchd::lsp::LspManager::new(orchd::lsp::LspConfig::default());
+                let file_path = path.to_string_lossy();
+                if let Some(language_id) = manager.language_for_file(&file_path) {
+                    let content = std::fs::read_to_string(&path)?;
+                    manager.did_open(&file_path, &language_id, &content)?;
+                    // Shutting down drains diagnostics published since the open.
+                    manager.stop_all();
+                }
+                let diagnostics = manager.diagnostics_for(&path);
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&diagnostics)?);
+                } else if diagnostics.is_empty() {
+                    println!("No diagnostics for {}.", path.display());
+                } else {
+                    for diagnostic in &diagnostics {
+                        println!(
+                            "{:<11} {:>5}  {}",
+                            diagnostic.severity,
+                            diagnostic.line + 1,
+                            diagnostic.message
+                        );
+                    }
+                }
+            }
         },
         Commands::RateLimits { json } => {
             let config = orchd::rate_limiter::RateLimitConfig::default();
+            let stats = orchd::rate_limiter::load_stats(&repo_root);
             if json {
-                println!("{}", serde_json::to_string_pretty(&config).unwrap_or_default());
+                let value = serde_json::json!({ "config": config, "buckets": stats });
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&value).unwrap_or_default()
+                );
             } else {
                 println!("Rate Limits:");
                 println!("  Per-minute: {}", config.requests_per_minute);
                 println!("  Per-hour:   {}", config.requests_per_hour);
                 println!("  Burst:      {}", config.burst_size);
+                if !stats.is_empty() {
+                    println!("Buckets:");
+                    println!("{}", orchd::rate_limiter::display_stats(&stats));
+                }
             }
         }
         Commands::Timeouts { json } => {
             let config = orchd::task_timeout::TimeoutConfig::default();
-            let tracker = orchd::task_timeout::TimeoutTracker::new(config.clone());
+            let tracker = build_timeout_tracker(&service, config.clone())?;
+            let now = Utc::now();
+            let rows = timeout_rows(&tracker, now);
             if json {
-                println!("{}", serde_json::to_string_pretty(&config).unwrap_or_default());
+                let value = serde_json::json!({ "config": config, "tasks": rows });
+                println!("{}", serde_json::to_string_pretty(&value).unwrap_or_default());
             } else {
                 println!("Timeout Config:");
                 println!("  Default:  {}s", config.default_timeout_secs);
@@ -4292,6 +3669,10 @@ fn main() -> anyhow::Result<()> {
                 println!("  Grace:    {}s", config.grace_period_secs);
                 println!("  Interval: {}s", config.check_interval_secs);
                 println!("  Tracked:  {}", tracker.active_count());
+                for row in &rows {
+                    let marker = if row.expired { "  EXPIRED" } else { "" };
+                    println!("  {:<32} {:>8}s{}", row.task_id, row.remaining_secs, marker);
+                }
             }
         }
         Commands::Reconcile { json, stale_secs } => {
@@ -4363,24 +3744,48 @@ fn main() -> anyhow::Result<()> {
                 }
             }
         }
-        Commands::McpHttp { bind, port } => {
+        Commands::McpHttp {
+            bind,
+            port,
+            allowed_tools,
+            trace_path,
+        } => {
             let config = orchd::mcp_transport::TransportConfig {
                 kind: orchd::mcp_transport::TransportKind::Http {
                     bind_addr: bind.clone(),
                     port,
                 },
+                allowed_tools: (!allowed_tools.is_empty()).then_some(allowed_tools),
+                shared_secret: std::env::var("OTHALA_MCP_SECRET")
+                    .ok()
+                    .filter(|secret| !secret.is_empty()),
+                trace_path,
                 ..Default::default()
             };
             println!("Starting MCP HTTP/SSE server on {bind}:{port}");
-            let transport = orchd::mcp_transport::HttpTransport::new(&config);
+            let service = std::rc::Rc::new(service);
+            let mut server = orchd::mcp::McpServer::new();
+            server.register_builtin_tools();
+            server.register_service_tools(std::rc::Rc::clone(&service));
+            server.register_task_creation_tool(std::rc::Rc::clone(&service), &repo_root);
+            server.register_log_tools(std::rc::Rc::clone(&service), &repo_root);
+            server.register_project_resources(
+                &repo_root,
+                service,
+                orchd::mcp_resources::DEFAULT_LOG_RESOURCE_BYTES,
+            );
+            server.set_permission_policy(mcp_permission_policy(&repo_root));
+            let transport = orchd::mcp_transport::HttpTransport::new_uninitialized(&config);
             match transport {
                 Ok(t) => {
                     println!("MCP HTTP transport ready");
                     println!("  POST {bind}:{port}/rpc  - JSON-RPC endpoint");
                     println!("  GET  {bind}:{port}/sse  - SSE event stream");
-                    println!("  GET  {bind}:{port}/health - Health check");
-                    if let Err(e) = t.bind() {
-                        eprintln!("Transport bind error: {e}");
+                    println!("  GET  {bind}:{port}/health - Liveness check");
+                    println!("  GET  {bind}:{port}/ready  - Readiness check");
+                    if let Err(e) = t.serve(server) {
+                        eprintln!("MCP HTTP transport error: {e}");
+                        std::process::exit(1);
                     }
                 }
                 Err(e) => eprintln!("Failed to start MCP HTTP transport: {e}"),
@@ -4467,6 +3872,39 @@ fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+fn print_snapshot_change(change: &SnapshotChange) {
+    if let Some(stash) = &change.stash {
+        println!(
+            "Stashed uncommitted changes as {} ({})",
+            stash.sha, stash.label
+        );
+    }
+    let snapshot = &change.snapshot;
+    if change.redo {
+        println!(
+            "Redo applied for {} ({} -> {})",
+            snapshot.task_id, snapshot.parent_sha, snapshot.commit_sha
+        );
+    } else {
+        println!(
+            "Undo applied for {} ({} -> {})",
+            snapshot.task_id, snapshot.commit_sha, snapshot.parent_sha
+        );
+    }
+    match &change.stash {
+        Some(_) if change.restored => println!("Restored stashed changes"),
+        Some(stash) => println!("Restore later with: git stash apply {}", stash.sha),
+        None => {}
+    }
+}
+
+fn format_dependency_cycle(cycle: &[TaskId]) -> String {
+    let error = orchd::CycleError {
+        cycle: cycle.to_vec(),
+    };
+    format!("{error}; fix depends_on/parent links before rendering the tree")
+}
+
 fn print_dep_tree(tasks: &[Task], task: &Task, depth: usize) {
     let indent = "  ".repeat(depth);
     let state_color = match task.state {
@@ -4526,8 +3964,19 @@ fn format_event(event: &Event) -> String {
         EventKind::RestackCompleted => ("RestackCompleted", "Restack completed".to_string()),
         EventKind::RestackConflict => ("RestackConflict", "Restack conflict".to_string()),
         EventKind::VerifyStarted => ("VerifyStarted", "Verify started".to_string()),
-        EventKind::VerifyCompleted { success } => {
-            ("VerifyCompleted", format!("success={success}"))
+        EventKind::VerifyCompleted {
+            success,
+            failure_bundle,
+            failure_summary,
+        } => {
+            let mut details = format!("success={success}");
+            if let Some(summary) = failure_summary {
+                details.push_str(&format!(", {summary}"));
+            }
+            if let Some(bundle) = failure_bundle {
+                details.push_str(&format!(", bundle={bundle}"));
+            }
+            ("VerifyCompleted", details)
         }
         EventKind::ReadyReached => ("ReadyReached", "Ready reached".to_string()),
         EventKind::SubmitStarted { mode } => ("SubmitStarted", format!("mode={mode:?}")),
@@ -4553,6 +4002,16 @@ fn format_event(event: &Event) -> String {
             "AgentCompleted",
             format!("model={model}, success={success}, duration_secs={duration_secs}"),
         ),
+        EventKind::AgentSuspended {
+            session_id,
+            idle_secs,
+        } => (
+            "AgentSuspended",
+            format!("session_id={session_id}, idle_secs={idle_secs}"),
+        ),
+        EventKind::AgentResumed { session_id } => {
+            ("AgentResumed", format!("session_id={session_id}"))
+        }
         EventKind::CancellationRequested { reason } => {
             ("CancellationRequested", format!("reason={reason}"))
         }
@@ -4571,9 +4030,12 @@ fn format_event(event: &Event) -> String {
             ("ContextRegenCompleted", format!("success={success}"))
         }
         EventKind::ConfigReloaded { changes } => ("ConfigReloaded", format!("changes={changes}")),
-        EventKind::TaskFailed { reason, is_final } => {
-            ("TaskFailed", format!("reason={reason}, is_final={is_final}"))
-        }
+        EventKind::TaskFailed {
+            reason, is_final, ..
+        } => (
+            "TaskFailed",
+            format!("reason={reason}, is_final={is_final}"),
+        ),
         EventKind::TestSpecValidated { passed, details } => (
             "TestSpecValidated",
             format!("passed={passed}, details={details}"),
@@ -4600,6 +4062,28 @@ fn format_event(event: &Event) -> String {
         EventKind::GraphiteSyncCompleted { success } => {
             ("GraphiteSyncCompleted", format!("success={success}"))
         }
+        EventKind::QuestionAsked {
+            question_id,
+            question,
+        } => ("QuestionAsked", format!("id={question_id}, question={question}")),
+        EventKind::QuestionAnswered {
+            question_id,
+            answer,
+        } => ("QuestionAnswered", format!("id={question_id}, answer={answer}")),
+        EventKind::QuestionStale {
+            question_id,
+            open_secs,
+            ..
+        } => ("QuestionStale", format!("id={question_id}, open_secs={open_secs}")),
+        EventKind::PipelineStepStarted { step } => ("PipelineStepStarted", format!("step={step}")),
+        EventKind::PipelineStepCompleted { step, success } => (
+            "PipelineStepCompleted",
+            format!("step={step}, success={success}"),
+        ),
+        EventKind::DaemonControl { action, actor } => {
+            ("DaemonControl", format!("action={action}, actor={actor}"))
+        }
+        EventKind::MergeDetected { method } => ("MergeDetected", format!("method={method}")),
     };
 
     let timestamp = event.at.format("%Y-%m-%d %H:%M:%S");
@@ -4618,11 +4102,27 @@ fn format_event_kind(kind: &EventKind) -> String {
         EventKind::RestackCompleted => "restack_completed".to_string(),
         EventKind::RestackConflict => "\x1b[31mrestack_conflict\x1b[0m".to_string(),
         EventKind::VerifyStarted => "verify_started".to_string(),
-        EventKind::VerifyCompleted { success } => {
-            if *success {
-                "\x1b[32mverify_passed\x1b[0m".to_string()
-            } else {
+        EventKind::VerifyCompleted { success: true, .. } => {
+            "\x1b[32mverify_passed\x1b[0m".to_string()
+        }
+        EventKind::VerifyCompleted {
+            success: false,
+            failure_bundle,
+            failure_summary,
+        } => {
+            let details = failure_summary
+                .iter()
+                .cloned()
+                .chain(
+                    failure_bundle
+                        .iter()
+                        .map(|bundle| format!("repro bundle {bundle}")),
+                )
+                .collect::<Vec<_>>();
+            if details.is_empty() {
                 "\x1b[31mverify_failed\x1b[0m".to_string()
+            } else {
+                format!("\x1b[31mverify_failed\x1b[0m: {}", details.join("; "))
             }
         }
         EventKind::ReadyReached => "\x1b[32mready\x1b[0m".to_string(),
@@ -4647,6 +4147,10 @@ fn format_event_kind(kind: &EventKind) -> String {
                 format!("\x1b[31magent_failed\x1b[0m ({model}, {duration_secs}s)")
             }
         }
+        EventKind::AgentSuspended { idle_secs, .. } => {
+            format!("\x1b[33magent_suspended\x1b[0m (idle {idle_secs}s)")
+        }
+        EventKind::AgentResumed { session_id } => format!("agent_resumed ({session_id})"),
         EventKind::CancellationRequested { reason } => {
             format!("\x1b[33mcancellation_requested\x1b[0m: {reason}")
         }
@@ -4664,7 +4168,9 @@ fn format_event_kind(kind: &EventKind) -> String {
             }
         }
         EventKind::ConfigReloaded { changes } => format!("config_reloaded: {changes}"),
-        EventKind::TaskFailed { reason, is_final } => {
+        EventKind::TaskFailed {
+            reason, is_final, ..
+        } => {
             let label = if *is_final { "FINAL_FAILURE" } else { "failed" };
             format!("\x1b[31m{label}\x1b[0m: {reason}")
         }
@@ -4702,6 +4208,32 @@ fn format_event_kind(kind: &EventKind) -> String {
                 "\x1b[31mgraphite_sync_failed\x1b[0m".to_string()
             }
         }
+        EventKind::QuestionAsked { question, .. } => {
+            format!("\x1b[35mquestion_asked\x1b[0m: {question}")
+        }
+        EventKind::QuestionAnswered { answer, .. } => {
+            format!("\x1b[32mquestion_answered\x1b[0m: {answer}")
+        }
+        EventKind::QuestionStale {
+            question,
+            open_secs,
+            ..
+        } => format!(
+            "\x1b[31mquestion_stale\x1b[0m: unanswered for {}m: {question}",
+            open_secs / 60
+        ),
+        EventKind::PipelineStepStarted { step } => format!("pipeline_step_started: {step}"),
+        EventKind::PipelineStepCompleted { step, success } => {
+            if *success {
+                format!("\x1b[32mpipeline_step_completed\x1b[0m: {step}")
+            } else {
+                format!("\x1b[31mpipeline_step_failed\x1b[0m: {step}")
+            }
+        }
+        EventKind::DaemonControl { action, actor } => format!("daemon_{action} by {actor}"),
+        EventKind::MergeDetected { method } => {
+            format!("\x1b[32mmerge_detected\x1b[0m: via {method}")
+        }
     }
 }
 
@@ -4709,13 +4241,20 @@ fn format_event_kind(kind: &EventKind) -> String {
 mod tests {
     use super::*;
     use chrono::{Duration, TimeZone, Utc};
+    use orch_core::events::{CancelReason, FailureCause};
+    use orchd::cli::bulk;
+    use orchd::cli::tasks::{add_task_labels, cancel_task, remove_task_labels, split_tag_args};
     use orchd::event_log::JsonlEventLog;
     use orchd::persistence::SqliteStore;
     use orchd::scheduler::SchedulerConfig;
+    use orch_core::types::SubmitMode;
     use orchd::TaskRunRecord;
-    use serde_json::Value;
     use std::fs;
 
+    fn add_task_label(service: &OrchdService, task_id: &TaskId, label: &str) -> anyhow::Result<()> {
+        add_task_labels(service, task_id, &[label.to_string()])
+    }
+
     #[test]
     fn create_task_cli_parses_priority_flag() {
         let cli = Cli::try_parse_from([
@@ -4736,6 +4275,142 @@ mod tests {
         }
     }
 
+    #[test]
+    fn chat_send_cli_parses_wait_and_spawn() {
+        let cli = Cli::try_parse_from([
+            "othala", "chat", "send", "chat-1", "add tests", "--wait", "--spawn",
+        ])
+        .expect("parse chat send");
+
+        match cli.command {
+            Commands::Chat {
+                action:
+                    ChatAction::Send {
+                        task_id,
+                        message,
+                        wait,
+                        spawn,
+                        timeout,
+                    },
+            } => {
+                assert_eq!(task_id, "chat-1");
+                assert_eq!(message, "add tests");
+                assert!(wait);
+                assert!(spawn);
+                assert_eq!(timeout, 300);
+            }
+            _ => panic!("expected chat send command"),
+        }
+    }
+
+    #[test]
+    fn alias_cli_parses_task_and_alias() {
+        let cli = Cli::try_parse_from(["othala", "alias", "chat-1760000000123", "auth-fix"])
+            .expect("parse alias");
+        match cli.command {
+            Commands::Alias { task_id, alias } => {
+                assert_eq!(task_id.as_deref(), Some("chat-1760000000123"));
+                assert_eq!(alias.as_deref(), Some("auth-fix"));
+            }
+            _ => panic!("expected alias command"),
+        }
+    }
+
+    #[test]
+    fn repo_dir_is_a_global_flag() {
+        let cli = Cli::try_parse_from(["othala", "list", "--repo-dir", "/tmp/other-repo"])
+            .expect("parse trailing repo-dir");
+        assert_eq!(cli.repo_dir, Some(PathBuf::from("/tmp/other-repo")));
+        let cli = Cli::try_parse_from(["othala", "--repo-dir", "/tmp/other-repo", "doctor"])
+            .expect("parse leading repo-dir");
+        assert_eq!(cli.repo_dir, Some(PathBuf::from("/tmp/other-repo")));
+        assert!(!cli.command.needs_git_repo());
+
+        let cli = Cli::try_parse_from(["othala", "prune", "--force"]).expect("parse prune");
+        assert!(cli.command.needs_git_repo());
+        let cli = Cli::try_parse_from(["othala", "prune"]).expect("parse dry-run prune");
+        assert!(!cli.command.needs_git_repo());
+    }
+
+    #[test]
+    fn parse_signals_test_line() {
+        let cli = Cli::try_parse_from(["othala", "signals", "--test", "status: [patch_ready]"])
+            .expect("parse signals");
+        assert!(matches!(
+            cli.command,
+            Commands::Signals { test: Some(ref line) } if line == "status: [patch_ready]"
+        ));
+        let cli = Cli::try_parse_from(["othala", "signals"]).expect("parse signals list");
+        assert!(matches!(cli.command, Commands::Signals { test: None }));
+    }
+
+    #[test]
+    fn parse_models_enabled_only() {
+        let cli =
+            Cli::try_parse_from(["othala", "models", "--enabled-only"]).expect("parse models");
+        assert!(matches!(
+            cli.command,
+            Commands::Models {
+                json: false,
+                enabled_only: true
+            }
+        ));
+    }
+
+    #[test]
+    fn parse_non_interactive_wizard_flags() {
+        let cli = Cli::try_parse_from([
+            "othala",
+            "wizard",
+            "--non-interactive",
+            "--enable",
+            "claude",
+            "--slack-webhook-url",
+            "https://hooks.slack.com/services/T/B/X",
+            "--web-bind",
+            "0.0.0.0:9842",
+            "--verify-command",
+            "cargo test",
+        ])
+        .expect("parse wizard");
+        let Commands::Wizard {
+            non_interactive,
+            enable,
+            slack_webhook_url,
+            web_bind,
+            verify_command,
+            webhook_url,
+            ..
+        } = cli.command
+        else {
+            panic!("expected wizard command");
+        };
+        assert!(non_interactive);
+        assert_eq!(enable.as_deref(), Some("claude"));
+        assert_eq!(
+            slack_webhook_url.as_deref(),
+            Some("https://hooks.slack.com/services/T/B/X")
+        );
+        assert_eq!(web_bind.as_deref(), Some("0.0.0.0:9842"));
+        assert_eq!(verify_command.as_deref(), Some("cargo test"));
+        assert!(webhook_url.is_none());
+    }
+
+    #[test]
+    fn prune_cli_requires_force_for_delete_branches() {
+        assert!(Cli::try_parse_from(["othala", "prune", "--delete-branches"]).is_err());
+        let cli = Cli::try_parse_from(["othala", "prune", "--force", "--delete-branches"])
+            .expect("parse prune");
+        match cli.command {
+            Commands::Prune {
+                force,
+                delete_branches,
+                ..
+            } => assert!(force && delete_branches),
+            _ => panic!("expected prune command"),
+        }
+    }
+
     #[test]
     fn load_tasks_cli_parses_optional_dir() {
         let cli = Cli::try_parse_from(["othala", "load-tasks", "--dir", ".othala/tasks"])
@@ -4749,6 +4424,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn bench_suite_cli_parses_run_and_compare() {
+        let cli = Cli::try_parse_from([
+            "othala",
+            "bench-suite",
+            "run",
+            "bench/specs",
+            "--iterations",
+            "3",
+            "--tag",
+            "prompt-v2",
+        ])
+        .expect("parse bench-suite run");
+        match cli.command {
+            Commands::BenchSuite {
+                action:
+                    BenchSuiteAction::Run {
+                        dir,
+                        iterations,
+                        tag,
+                        timeout_secs,
+                    },
+            } => {
+                assert_eq!(dir, PathBuf::from("bench/specs"));
+                assert_eq!(iterations, 3);
+                assert_eq!(tag, "prompt-v2");
+                assert_eq!(timeout_secs, 3600);
+            }
+            _ => panic!("expected bench-suite run command"),
+        }
+
+        let cli = Cli::try_parse_from(["othala", "bench-suite", "compare", "v1", "v2"])
+            .expect("parse bench-suite compare");
+        assert!(matches!(
+            cli.command,
+            Commands::BenchSuite {
+                action: BenchSuiteAction::Compare { .. }
+            }
+        ));
+    }
+
     #[test]
     fn validate_spec_cli_parses_path() {
         let cli = Cli::try_parse_from(["othala", "validate-spec", "specs/task.yaml"])
@@ -4776,6 +4492,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn deps_cli_parses_order_flag() {
+        let cli = Cli::try_parse_from(["othala", "deps", "--order", "--json"]).expect("parse deps");
+        match cli.command {
+            Commands::Deps { json, order } => assert!(json && order),
+            _ => panic!("expected deps command"),
+        }
+    }
+
+    #[test]
+    fn dependency_cycle_message_closes_the_loop() {
+        let cycle = vec![TaskId::new("T1"), TaskId::new("T2"), TaskId::new("T3")];
+        assert!(format_dependency_cycle(&cycle).contains("T1 -> T2 -> T3 -> T1"));
+    }
+
     #[test]
     fn replay_cli_parses_task_and_filters() {
         let cli = Cli::try_parse_from([
@@ -4808,6 +4539,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn list_as_of_parses_and_conflicts_with_detail() {
+        let cli = Cli::try_parse_from(["othala", "list", "--as-of", "2026-02-10T14:05:00Z"])
+            .expect("parse list --as-of");
+        match cli.command {
+            Commands::List { as_of, detail, .. } => {
+                assert_eq!(as_of.as_deref(), Some("2026-02-10T14:05:00Z"));
+                assert!(!detail);
+            }
+            _ => panic!("expected list command"),
+        }
+
+        assert!(Cli::try_parse_from([
+            "othala",
+            "list",
+            "--detail",
+            "--as-of",
+            "2026-02-10T14:05:00Z"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn diff_stat_flag_works() {
         let cli = Cli::try_parse_from(["othala", "diff", "T-42", "--stat"]).expect("parse diff");
@@ -4834,7 +4587,26 @@ mod tests {
         let cli = Cli::try_parse_from(["othala", "undo", "T-42"]).expect("parse undo");
 
         match cli.command {
-            Commands::Undo { task_id } => assert_eq!(task_id, "T-42"),
+            Commands::Undo { task_id, .. } => assert_eq!(task_id, "T-42"),
+            _ => panic!("expected undo command"),
+        }
+    }
+
+    #[test]
+    fn undo_cli_parses_snapshot_index_and_restore_stash() {
+        let cli = Cli::try_parse_from(["othala", "undo", "T-1", "--to", "3", "--restore-stash"])
+            .expect("parse undo --to");
+
+        match cli.command {
+            Commands::Undo {
+                task_id,
+                to,
+                restore_stash,
+            } => {
+                assert_eq!(task_id, "T-1");
+                assert_eq!(to, Some(3));
+                assert!(restore_stash);
+            }
             _ => panic!("expected undo command"),
         }
     }
@@ -4844,7 +4616,7 @@ mod tests {
         let cli = Cli::try_parse_from(["othala", "redo", "T-42"]).expect("parse redo");
 
         match cli.command {
-            Commands::Redo { task_id } => assert_eq!(task_id, "T-42"),
+            Commands::Redo { task_id, .. } => assert_eq!(task_id, "T-42"),
             _ => panic!("expected redo command"),
         }
     }
@@ -4970,147 +4742,39 @@ mod tests {
 
     #[test]
     fn compact_cli_parses_task_and_max_lines() {
-        let cli = Cli::try_parse_from(["othala", "compact", "T-88", "--max-lines", "64"])
-            .expect("parse compact");
+        let cli = Cli::try_parse_from([
+            "othala",
+            "compact",
+            "T-88",
+            "--max-lines",
+            "64",
+            "--max-tokens",
+            "4000",
+        ])
+        .expect("parse compact");
 
         match cli.command {
-            Commands::Compact { task_id, max_lines } => {
+            Commands::Compact {
+                task_id,
+                max_lines,
+                max_tokens,
+            } => {
                 assert_eq!(task_id, "T-88");
                 assert_eq!(max_lines, Some(64));
+                assert_eq!(max_tokens, Some(4000));
             }
             _ => panic!("expected compact command"),
         }
     }
 
     #[test]
-    fn stats_command_counts_by_state() {
-        let mut chatting = mk_task("T-STATS-1", TaskState::Chatting);
-        chatting.preferred_model = Some(ModelKind::Claude);
-        let mut merged = mk_task("T-STATS-2", TaskState::Merged);
-        merged.preferred_model = Some(ModelKind::Codex);
-        let stopped = mk_task("T-STATS-3", TaskState::Stopped);
-        let tasks = vec![chatting, merged, stopped];
-
-        let summary = compute_stats_summary(
-            &tasks,
-            vec![
-                ("CHATTING".to_string(), 1),
-                ("MERGED".to_string(), 1),
-                ("STOPPED".to_string(), 1),
-            ],
-            9,
-        );
-
-        assert_eq!(summary.total_tasks, 3);
-        assert_eq!(summary.tasks_by_state.get("CHATTING"), Some(&1));
-        assert_eq!(summary.tasks_by_state.get("MERGED"), Some(&1));
-        assert_eq!(summary.tasks_by_state.get("STOPPED"), Some(&1));
-        assert_eq!(summary.tasks_by_model.get("claude"), Some(&1));
-        assert_eq!(summary.tasks_by_model.get("codex"), Some(&1));
-        assert_eq!(summary.tasks_by_model.get("unspecified"), Some(&1));
-    }
-
-    #[test]
-    fn stats_command_computes_success_rate() {
-        let merged = mk_task("T-STATS-SR-1", TaskState::Merged);
-        let stopped_a = mk_task("T-STATS-SR-2", TaskState::Stopped);
-        let stopped_b = mk_task("T-STATS-SR-3", TaskState::Stopped);
-        let tasks = vec![merged, stopped_a, stopped_b];
-
-        let summary = compute_stats_summary(
-            &tasks,
-            vec![("MERGED".to_string(), 1), ("STOPPED".to_string(), 2)],
-            0,
-        );
-
-        let rate = summary.success_rate.expect("success rate exists");
-        assert!((rate - 33.3333).abs() < 0.01);
-    }
-
-    #[test]
-    fn gc_dry_run_does_not_delete() {
-        let root = std::env::temp_dir().join(format!(
-            "othala-gc-dry-run-{}",
-            Utc::now().timestamp_nanos_opt().unwrap_or_default()
-        ));
-        let event_file = root.join(".othala/events/tasks/T-GC-1.jsonl");
-        let agent_dir = root.join(".othala/agent-output/T-GC-1");
-        fs::create_dir_all(event_file.parent().expect("event parent")).expect("create event dir");
-        fs::create_dir_all(&agent_dir).expect("create agent dir");
-        fs::write(&event_file, "{}\n").expect("write event file");
-        fs::write(agent_dir.join("latest.log"), "hello\n").expect("write agent log");
-
-        let summary = gc_logs(&root, 0, true).expect("dry run gc");
-        assert_eq!(summary.deleted_event_files, 1);
-        assert_eq!(summary.deleted_agent_output_dirs, 1);
-        assert!(event_file.exists());
-        assert!(agent_dir.exists());
-
-        fs::remove_dir_all(root).ok();
-    }
-
-    #[test]
-    fn gc_deletes_old_files() {
-        let root = std::env::temp_dir().join(format!(
-            "othala-gc-delete-{}",
-            Utc::now().timestamp_nanos_opt().unwrap_or_default()
-        ));
-        let event_file = root.join(".othala/events/tasks/T-GC-2.jsonl");
-        let ignored_file = root.join(".othala/events/keep.txt");
-        let agent_dir = root.join(".othala/agent-output/T-GC-2");
-        fs::create_dir_all(event_file.parent().expect("event parent")).expect("create event dir");
-        fs::create_dir_all(agent_dir.parent().expect("agent parent")).expect("create agent parent");
-        fs::create_dir_all(&agent_dir).expect("create agent dir");
-        fs::write(&event_file, "{}\n").expect("write event file");
-        fs::write(&ignored_file, "keep\n").expect("write ignored file");
-        fs::write(agent_dir.join("latest.log"), "hello\n").expect("write agent log");
-
-        let summary = gc_logs(&root, 0, false).expect("gc delete");
-        assert_eq!(summary.deleted_event_files, 1);
-        assert_eq!(summary.deleted_agent_output_dirs, 1);
-        assert!(!event_file.exists());
-        assert!(ignored_file.exists());
-        assert!(!agent_dir.exists());
-
-        fs::remove_dir_all(root).ok();
-    }
-
-    #[test]
-    fn init_creates_directory_structure() {
-        let root = std::env::temp_dir().join(format!(
-            "othala-init-structure-{}",
-            Utc::now().timestamp_nanos_opt().unwrap_or_default()
-        ));
-        fs::create_dir_all(&root).expect("create temp root");
-
-        init_project(&root, false).expect("initialize project");
-
-        assert!(root.join(".othala").is_dir());
-        assert!(root.join(".othala/context").is_dir());
-        assert!(root.join(".othala/templates").is_dir());
-        assert!(root.join(".othala/config.toml").is_file());
-        assert!(root.join(".othala/context/MAIN.md").is_file());
-        assert_eq!(
-            fs::read_to_string(root.join(".othala/context/MAIN.md")).expect("read main context"),
-            "# Project Context\n\nDescribe your project here.\n"
-        );
-
-        fs::remove_dir_all(root).ok();
-    }
-
-    #[test]
-    fn init_refuses_overwrite_without_force() {
-        let root = std::env::temp_dir().join(format!(
-            "othala-init-no-force-{}",
-            Utc::now().timestamp_nanos_opt().unwrap_or_default()
-        ));
-        fs::create_dir_all(&root).expect("create temp root");
-
-        init_project(&root, false).expect("initialize project");
-        let err = init_project(&root, false).expect_err("should reject overwrite");
-        assert!(err.to_string().contains("already exists"));
-
-        fs::remove_dir_all(root).ok();
+    fn init_cli_parses_template() {
+        let cli =
+            Cli::try_parse_from(["othala", "init", "--template", "node"]).expect("parse init");
+        let Commands::Init { template, .. } = cli.command else {
+            panic!("expected init command");
+        };
+        assert_eq!(template, Some(InitTemplateArg::Node));
     }
 
     #[test]
@@ -5159,69 +4823,6 @@ mod tests {
         fs::remove_dir_all(root).ok();
     }
 
-    #[test]
-    fn export_record_contains_expected_fields() {
-        let mut task = Task::new(
-            TaskId::new("T-EXP-1"),
-            RepoId("repo-a".to_string()),
-            "Export me".to_string(),
-            PathBuf::from(".orch/wt/T-EXP-1"),
-        );
-        task.state = TaskState::Ready;
-        task.priority = TaskPriority::Critical;
-        task.branch_name = Some("task/T-EXP-1".to_string());
-        task.preferred_model = Some(ModelKind::Claude);
-
-        let record = TaskExportRecord::from_task(&task, Some("task/parent".to_string()));
-        assert_eq!(record.task_id, "T-EXP-1");
-        assert_eq!(record.title, "Export me");
-        assert_eq!(record.state, "READY");
-        assert_eq!(record.priority, "critical");
-        assert_eq!(record.parent_branch.as_deref(), Some("task/parent"));
-    }
-
-    #[test]
-    fn import_record_to_task_applies_export_values() {
-        let record = TaskExportRecord {
-            task_id: "T-IMP-1".to_string(),
-            title: "Imported".to_string(),
-            description: Some("ignored".to_string()),
-            state: "CHATTING".to_string(),
-            priority: "high".to_string(),
-            branch_name: Some("task/T-IMP-1".to_string()),
-            parent_branch: None,
-            repo_id: "repo-b".to_string(),
-            preferred_model: Some(ModelKind::Gemini),
-        };
-
-        let task = import_record_to_task(record, None).expect("import record");
-        assert_eq!(task.id.0, "T-IMP-1");
-        assert_eq!(task.repo_id.0, "repo-b");
-        assert_eq!(task.title, "Imported");
-        assert_eq!(task.state, TaskState::Chatting);
-        assert_eq!(task.priority, TaskPriority::High);
-        assert_eq!(task.branch_name.as_deref(), Some("task/T-IMP-1"));
-        assert_eq!(task.preferred_model, Some(ModelKind::Gemini));
-    }
-
-    #[test]
-    fn export_import_json_roundtrip_records() {
-        let records = vec![TaskExportRecord {
-            task_id: "T-JSON-1".to_string(),
-            title: "Roundtrip".to_string(),
-            description: None,
-            state: "STOPPED".to_string(),
-            priority: "normal".to_string(),
-            branch_name: None,
-            parent_branch: None,
-            repo_id: "repo-json".to_string(),
-            preferred_model: Some(ModelKind::Codex),
-        }];
-        let json = serde_json::to_string(&records).expect("serialize");
-        let decoded: Vec<TaskExportRecord> = serde_json::from_str(&json).expect("deserialize");
-        assert_eq!(decoded, records);
-    }
-
     #[test]
     fn aggregate_cost_estimates_uses_run_metrics() {
         let run = TaskRunRecord {
@@ -5235,6 +4836,7 @@ mod tests {
             exit_code: None,
             estimated_tokens: Some(250),
             duration_secs: Some(8.0),
+            confidence: None,
         };
         let estimates = aggregate_cost_estimates(&[run]);
         assert_eq!(estimates.len(), 1);
@@ -5255,125 +4857,12 @@ mod tests {
             exit_code: None,
             estimated_tokens: None,
             duration_secs: Some(3.0),
+            confidence: None,
         };
         let estimates = aggregate_cost_estimates(&[run]);
         assert!(estimates.is_empty());
     }
 
-    #[test]
-    fn doctor_detects_missing_model() {
-        let checks = doctor_model_checks(|name| name != "codex");
-        let codex = checks
-            .iter()
-            .find(|check| check.name == "model_codex")
-            .expect("codex check present");
-        assert!(!codex.ok);
-        assert_eq!(codex.status, DoctorStatus::Missing);
-    }
-
-    #[test]
-    fn doctor_json_output_format() {
-        let report = DoctorReport {
-            checks: vec![DoctorCheck {
-                name: "model_claude".to_string(),
-                ok: true,
-                status: DoctorStatus::Ok,
-                detail: "claude found on PATH".to_string(),
-            }],
-            all_ok: true,
-        };
-
-        let json = serde_json::to_string_pretty(&report).expect("serialize doctor report");
-        let value: Value = serde_json::from_str(&json).expect("parse doctor report json");
-
-        assert!(value.get("checks").is_some());
-        assert_eq!(value.get("all_ok").and_then(Value::as_bool), Some(true));
-        assert_eq!(value["checks"][0]["name"], "model_claude");
-        assert_eq!(value["checks"][0]["status"], "ok");
-    }
-
-    #[test]
-    fn retries_formats_timeline() {
-        let task_id = TaskId::new("chat-123");
-        let started_1 = Utc
-            .with_ymd_and_hms(2026, 2, 16, 10, 30, 0)
-            .single()
-            .expect("valid timestamp");
-        let failed_1 = Utc
-            .with_ymd_and_hms(2026, 2, 16, 10, 35, 22)
-            .single()
-            .expect("valid timestamp");
-        let started_2 = Utc
-            .with_ymd_and_hms(2026, 2, 16, 10, 35, 25)
-            .single()
-            .expect("valid timestamp");
-        let completed_2 = Utc
-            .with_ymd_and_hms(2026, 2, 16, 10, 40, 11)
-            .single()
-            .expect("valid timestamp");
-
-        let events = vec![
-            Event {
-                id: EventId("E-R-1".to_string()),
-                task_id: Some(task_id.clone()),
-                repo_id: Some(RepoId("repo-1".to_string())),
-                at: failed_1,
-                kind: EventKind::RetryScheduled {
-                    attempt: 1,
-                    model: "codex".to_string(),
-                    reason: "verify failed".to_string(),
-                },
-            },
-            Event {
-                id: EventId("E-R-2".to_string()),
-                task_id: Some(task_id.clone()),
-                repo_id: Some(RepoId("repo-1".to_string())),
-                at: started_2,
-                kind: EventKind::AgentSpawned {
-                    model: "codex".to_string(),
-                },
-            },
-        ];
-
-        let runs = vec![
-            TaskRunRecord {
-                run_id: "RUN-1".to_string(),
-                task_id: task_id.clone(),
-                repo_id: RepoId("repo-1".to_string()),
-                model: ModelKind::Claude,
-                started_at: started_1,
-                finished_at: Some(failed_1),
-                stop_reason: Some("failed".to_string()),
-                exit_code: Some(1),
-                estimated_tokens: None,
-                duration_secs: Some(322.0),
-            },
-            TaskRunRecord {
-                run_id: "RUN-2".to_string(),
-                task_id,
-                repo_id: RepoId("repo-1".to_string()),
-                model: ModelKind::Codex,
-                started_at: started_2,
-                finished_at: Some(completed_2),
-                stop_reason: Some("completed".to_string()),
-                exit_code: Some(0),
-                estimated_tokens: None,
-                duration_secs: Some(286.0),
-            },
-        ];
-
-        let timeline = build_retry_timeline(&events, &runs);
-        let rendered = format_retries_timeline("chat-123", &timeline);
-
-        assert!(rendered.contains("Retry History for chat-123:"));
-        assert!(rendered.contains("#1"));
-        assert!(rendered.contains("claude"));
-        assert!(rendered.contains("failed 10:35:22"));
-        assert!(rendered.contains("reason: \"verify failed\""));
-        assert!(rendered.contains("codex"));
-        assert!(rendered.contains("completed 10:40:11"));
-    }
-
     #[test]
     fn replay_formats_events_chronologically() {
         let service = mk_test_service();
@@ -5500,7 +4989,11 @@ mod tests {
                 task_id: Some(task_id),
                 repo_id: Some(repo_id),
                 at: third,
-                kind: EventKind::VerifyCompleted { success: true },
+                kind: EventKind::VerifyCompleted {
+                    success: true,
+                    failure_bundle: None,
+                    failure_summary: None,
+                },
             },
         ];
 
@@ -5526,7 +5019,11 @@ mod tests {
             EventKind::RestackCompleted,
             EventKind::RestackConflict,
             EventKind::VerifyStarted,
-            EventKind::VerifyCompleted { success: true },
+            EventKind::VerifyCompleted {
+                success: true,
+                failure_bundle: None,
+                failure_summary: None,
+            },
             EventKind::ReadyReached,
             EventKind::SubmitStarted {
                 mode: SubmitMode::Single,
@@ -5553,7 +5050,7 @@ mod tests {
                 duration_secs: 14,
             },
             EventKind::CancellationRequested {
-                reason: "requested by user".to_string(),
+                reason: CancelReason::UserRequested,
             },
             EventKind::ModelFallback {
                 from_model: "claude".to_string(),
@@ -5568,6 +5065,7 @@ mod tests {
             EventKind::TaskFailed {
                 reason: "max retries".to_string(),
                 is_final: true,
+                cause: Some(FailureCause::Other),
             },
             EventKind::TestSpecValidated {
                 passed: true,
@@ -5613,16 +5111,7 @@ mod tests {
         let service = OrchdService::new(
             store,
             JsonlEventLog::new(dir),
-            Scheduler::new(SchedulerConfig {
-                per_repo_limit: 10,
-                per_model_limit: vec![
-                    (ModelKind::Claude, 10),
-                    (ModelKind::Codex, 10),
-                    (ModelKind::Gemini, 10),
-                ]
-                .into_iter()
-                .collect(),
-            }),
+            Scheduler::new(SchedulerConfig::uniform(10, 10)),
         );
         service.bootstrap().expect("bootstrap");
         service
@@ -5650,241 +5139,151 @@ mod tests {
     }
 
     #[test]
-    fn cancel_transitions_chatting_to_stopped() {
-        let service = mk_test_service();
-        let task = mk_task("T-CANCEL-1", TaskState::Chatting);
-        service
-            .create_task(&task, &mk_created_event(&task))
-            .expect("create task");
-
-        let from_state = cancel_task(&service, &task.id, "requested by user").expect("cancel task");
-        assert_eq!(from_state, TaskState::Chatting);
-        let updated = service
-            .task(&task.id)
-            .expect("load task")
-            .expect("task exists");
-        assert_eq!(updated.state, TaskState::Stopped);
-    }
-
-    #[test]
-    fn cancel_rejects_merged_task() {
-        let service = mk_test_service();
-        let task = mk_task("T-CANCEL-2", TaskState::Merged);
-        service
-            .create_task(&task, &mk_created_event(&task))
-            .expect("create task");
+    fn cancel_cli_parses_reason_keywords() {
+        let cases = [
+            ("user", CancelReason::UserRequested),
+            ("budget", CancelReason::BudgetExceeded),
+            ("timeout", CancelReason::Timeout),
+            ("dependency", CancelReason::DependencyFailed),
+            ("flaky ci", CancelReason::Other("flaky ci".to_string())),
+        ];
+        for (keyword, expected) in cases {
+            let cli = Cli::try_parse_from(["othala", "cancel", "T1", "--reason", keyword])
+                .expect("parse cancel");
+            match cli.command {
+                Commands::Cancel { reason, .. } => {
+                    assert_eq!(CancelReason::parse(&reason), expected)
+                }
+                _ => panic!("expected cancel command"),
+            }
+        }
 
-        let result = cancel_task(&service, &task.id, "requested by user");
-        assert!(result.is_err());
-        let err = result.expect_err("error").to_string();
-        assert!(err.contains("cannot cancel task in state MERGED"));
+        let cli = Cli::try_parse_from(["othala", "cancel", "T1"]).expect("parse cancel");
+        match cli.command {
+            Commands::Cancel { reason, .. } => {
+                assert_eq!(CancelReason::parse(&reason), CancelReason::UserRequested)
+            }
+            _ => panic!("expected cancel command"),
+        }
     }
 
     #[test]
-    fn cancellation_event_created() {
+    fn cancel_reason_roundtrips_through_event_log() {
         let service = mk_test_service();
-        let task = mk_task("T-CANCEL-3", TaskState::Chatting);
+        let task = mk_task("T-CANCEL-REASON", TaskState::Chatting);
         service
             .create_task(&task, &mk_created_event(&task))
             .expect("create task");
 
-        cancel_task(&service, &task.id, "requested by user").expect("cancel task");
+        cancel_task(&service, &task.id, CancelReason::BudgetExceeded).expect("cancel task");
         let events = service.task_events(&task.id).expect("task events");
-        assert!(events.iter().any(|event| {
-            matches!(
-                &event.kind,
-                EventKind::CancellationRequested { reason } if reason == "requested by user"
-            )
-        }));
-    }
-
-    #[test]
-    fn bulk_cancel_by_state() {
-        let service = mk_test_service();
-        let task_a = mk_task("T-BULK-CANCEL-A", TaskState::Chatting);
-        let task_b = mk_task("T-BULK-CANCEL-B", TaskState::Chatting);
-        let task_c = mk_task("T-BULK-CANCEL-C", TaskState::Ready);
-        service
-            .create_task(&task_a, &mk_created_event(&task_a))
-            .expect("create task a");
-        service
-            .create_task(&task_b, &mk_created_event(&task_b))
-            .expect("create task b");
-        service
-            .create_task(&task_c, &mk_created_event(&task_c))
-            .expect("create task c");
-
-        let ids = Vec::new();
-        let summary = bulk_cancel(&service, Some("chatting"), &ids).expect("bulk cancel");
-        assert_eq!(summary.processed, 2);
-        assert_eq!(summary.succeeded, 2);
-        assert_eq!(summary.skipped, 0);
-
-        assert_eq!(
-            service
-                .task(&task_a.id)
-                .expect("load a")
-                .expect("a exists")
-                .state,
-            TaskState::Stopped
-        );
-        assert_eq!(
-            service
-                .task(&task_b.id)
-                .expect("load b")
-                .expect("b exists")
-                .state,
-            TaskState::Stopped
-        );
+        let cancel = events
+            .iter()
+            .find(|event| matches!(event.kind, EventKind::CancellationRequested { .. }))
+            .expect("cancellation event");
         assert_eq!(
-            service
-                .task(&task_c.id)
-                .expect("load c")
-                .expect("c exists")
-                .state,
-            TaskState::Ready
+            cancel.kind,
+            EventKind::CancellationRequested {
+                reason: CancelReason::BudgetExceeded,
+            }
         );
+        assert!(format_event(cancel).ends_with("| CancellationRequested | reason=budget_exceeded"));
+        assert!(format_event_kind(&cancel.kind).ends_with(": budget_exceeded"));
     }
 
     #[test]
-    fn bulk_retry_by_ids() {
-        let service = mk_test_service();
-        let mut task_a = mk_task("T-BULK-RETRY-A", TaskState::Stopped);
-        task_a.retry_count = 3;
-        let mut task_b = mk_task("T-BULK-RETRY-B", TaskState::Ready);
-        task_b.retry_count = 1;
-        let mut task_c = mk_task("T-BULK-RETRY-C", TaskState::Merged);
-        task_c.retry_count = 2;
-
-        service
-            .create_task(&task_a, &mk_created_event(&task_a))
-            .expect("create task a");
-        service
-            .create_task(&task_b, &mk_created_event(&task_b))
-            .expect("create task b");
-        service
-            .create_task(&task_c, &mk_created_event(&task_c))
-            .expect("create task c");
-
-        let ids = vec!["T-BULK-RETRY-A".to_string(), "T-BULK-RETRY-B".to_string()];
-        let summary = bulk_retry(&service, None, &ids).expect("bulk retry");
-        assert_eq!(summary.processed, 2);
-        assert_eq!(summary.succeeded, 2);
-        assert_eq!(summary.skipped, 0);
-
-        let updated_a = service.task(&task_a.id).expect("load a").expect("a exists");
-        let updated_b = service.task(&task_b.id).expect("load b").expect("b exists");
-        let updated_c = service.task(&task_c.id).expect("load c").expect("c exists");
-        assert_eq!(updated_a.state, TaskState::Chatting);
-        assert_eq!(updated_a.retry_count, 0);
-        assert_eq!(updated_b.state, TaskState::Chatting);
-        assert_eq!(updated_b.retry_count, 0);
-        assert_eq!(updated_c.state, TaskState::Merged);
-        assert_eq!(updated_c.retry_count, 2);
-    }
-
-    #[test]
-    fn bulk_set_priority_by_state() {
-        let service = mk_test_service();
-        let mut task_a = mk_task("T-BULK-PRIO-A", TaskState::Stopped);
-        task_a.priority = TaskPriority::Low;
-        let mut task_b = mk_task("T-BULK-PRIO-B", TaskState::Stopped);
-        task_b.priority = TaskPriority::Normal;
-        let mut task_c = mk_task("T-BULK-PRIO-C", TaskState::Ready);
-        task_c.priority = TaskPriority::Low;
-
-        service
-            .create_task(&task_a, &mk_created_event(&task_a))
-            .expect("create task a");
-        service
-            .create_task(&task_b, &mk_created_event(&task_b))
-            .expect("create task b");
-        service
-            .create_task(&task_c, &mk_created_event(&task_c))
-            .expect("create task c");
-
-        let ids = Vec::new();
-        let summary =
-            bulk_set_priority(&service, TaskPriority::Critical, Some("stopped"), &ids)
-                .expect("bulk set-priority");
-        assert_eq!(summary.processed, 2);
-        assert_eq!(summary.succeeded, 2);
-        assert_eq!(summary.skipped, 0);
-
-        assert_eq!(
-            service
-                .task(&task_a.id)
-                .expect("load a")
-                .expect("a exists")
-                .priority,
-            TaskPriority::Critical
-        );
-        assert_eq!(
-            service
-                .task(&task_b.id)
-                .expect("load b")
-                .expect("b exists")
-                .priority,
-            TaskPriority::Critical
-        );
+    fn failed_verify_logs_show_the_failure_summary() {
+        let kind = EventKind::VerifyCompleted {
+            success: false,
+            failure_bundle: Some(".othala/verify-failures/T-1/1".to_string()),
+            failure_summary: Some("1 failure: test tests::adds (src/lib.rs:10:9)".to_string()),
+        };
         assert_eq!(
-            service
-                .task(&task_c.id)
-                .expect("load c")
-                .expect("c exists")
-                .priority,
-            TaskPriority::Low
+            format_event_kind(&kind),
+            "\x1b[31mverify_failed\x1b[0m: 1 failure: test tests::adds (src/lib.rs:10:9); \
+             repro bundle .othala/verify-failures/T-1/1"
         );
+
+        let unknown = EventKind::VerifyCompleted {
+            success: false,
+            failure_bundle: None,
+            failure_summary: None,
+        };
+        assert_eq!(format_event_kind(&unknown), "\x1b[31mverify_failed\x1b[0m");
     }
 
     #[test]
-    fn tag_adds_label_to_task() {
+    fn tag_multiple_labels_adds_all_deduped() {
         let service = mk_test_service();
-        let task = mk_task("T-TAG-1", TaskState::Chatting);
+        let task = mk_task("T-TAG-MULTI", TaskState::Chatting);
         service
             .create_task(&task, &mk_created_event(&task))
             .expect("create task");
 
-        add_task_label(&service, &task.id, "bug").expect("tag task");
+        let cli = Cli::try_parse_from(["othala", "tag", "T-TAG-MULTI", "a", "B", "a", "c"])
+            .expect("parse tag");
+        let Commands::Tag { args, state } = cli.command else {
+            panic!("expected tag command");
+        };
+        let (task_ref, labels) = split_tag_args(args, state.as_deref()).expect("split args");
+        assert_eq!(task_ref.as_deref(), Some("T-TAG-MULTI"));
+
+        add_task_labels(&service, &task.id, &labels).expect("tag task");
         let updated = service
             .task(&task.id)
             .expect("load task")
             .expect("task exists");
-        assert_eq!(updated.labels, vec!["bug".to_string()]);
-    }
-
-    #[test]
-    fn tag_deduplicates_labels() {
-        let service = mk_test_service();
-        let task = mk_task("T-TAG-2", TaskState::Chatting);
-        service
-            .create_task(&task, &mk_created_event(&task))
-            .expect("create task");
+        assert_eq!(updated.labels, vec!["a", "b", "c"]);
 
-        add_task_label(&service, &task.id, "urgent").expect("first tag");
-        add_task_label(&service, &task.id, "urgent").expect("second tag");
+        remove_task_labels(&service, &task.id, &["A".to_string(), "c".to_string()])
+            .expect("untag task");
         let updated = service
             .task(&task.id)
             .expect("load task")
             .expect("task exists");
-        assert_eq!(updated.labels, vec!["urgent".to_string()]);
+        assert_eq!(updated.labels, vec!["b"]);
+        assert!(split_tag_args(vec!["T-TAG-MULTI".to_string()], None).is_err());
     }
 
     #[test]
-    fn untag_removes_label() {
+    fn tag_by_state_only_affects_matching_tasks() {
         let service = mk_test_service();
-        let task = mk_task("T-TAG-3", TaskState::Chatting);
-        service
-            .create_task(&task, &mk_created_event(&task))
-            .expect("create task");
+        let task_a = mk_task("T-TAG-STATE-A", TaskState::Stopped);
+        let task_b = mk_task("T-TAG-STATE-B", TaskState::Stopped);
+        let task_c = mk_task("T-TAG-STATE-C", TaskState::Chatting);
+        for task in [&task_a, &task_b, &task_c] {
+            service
+                .create_task(task, &mk_created_event(task))
+                .expect("create task");
+        }
 
-        add_task_label(&service, &task.id, "feature").expect("tag task");
-        remove_task_label(&service, &task.id, "feature").expect("untag task");
-        let updated = service
-            .task(&task.id)
-            .expect("load task")
-            .expect("task exists");
-        assert!(updated.labels.is_empty());
+        let cli = Cli::try_parse_from(["othala", "tag", "--state", "stopped", "triage", "stale"])
+            .expect("parse tag");
+        let Commands::Tag { args, state } = cli.command else {
+            panic!("expected tag command");
+        };
+        let (task_ref, labels) = split_tag_args(args, state.as_deref()).expect("split args");
+        assert!(task_ref.is_none());
+
+        let summary = bulk::tag(&service, &labels, "stopped", false).expect("bulk tag");
+        assert_eq!(summary.processed, 2);
+        assert_eq!(summary.succeeded, 2);
+
+        let labels_of = |id: &TaskId| {
+            service
+                .task(id)
+                .expect("load task")
+                .expect("task exists")
+                .labels
+        };
+        assert_eq!(labels_of(&task_a.id), vec!["triage", "stale"]);
+        assert_eq!(labels_of(&task_b.id), vec!["triage", "stale"]);
+        assert!(labels_of(&task_c.id).is_empty());
+
+        let summary =
+            bulk::tag(&service, &["stale".to_string()], "stopped", true).expect("bulk untag");
+        assert_eq!(summary.succeeded, 2);
+        assert_eq!(labels_of(&task_a.id), vec!["triage"]);
     }
 
     #[test]
@@ -6014,12 +5413,6 @@ mod tests {
             .is_empty());
     }
 
-    #[test]
-    fn watch_formats_output_with_prefix() {
-        let line = format_watch_line("task-1", "\x1b[31m", "hello world");
-        assert_eq!(line, "[\x1b[31mtask-1\x1b[0m] hello world");
-    }
-
     #[test]
     fn watch_and_cancel_cli_parse() {
         let watch = Cli::try_parse_from(["othala", "watch", "--task", "task-1", "-n", "5"])
@@ -6034,7 +5427,7 @@ mod tests {
 
         let cancel = Cli::try_parse_from(["othala", "cancel", "task-2"]).expect("parse cancel");
         match cancel.command {
-            Commands::Cancel { id } => assert_eq!(id, "task-2"),
+            Commands::Cancel { id, .. } => assert_eq!(id, "task-2"),
             _ => panic!("expected cancel command"),
         }
     }
@@ -6045,10 +5438,71 @@ mod tests {
         assert!(matches!(cli.command, Commands::Skills));
     }
 
+    #[test]
+    fn qa_spec_command_parses_check_flag() {
+        let cli = Cli::try_parse_from(["othala", "qa-spec", "--check"]).expect("parse qa-spec");
+        match cli.command {
+            Commands::QaSpec { check, model } => {
+                assert!(check);
+                assert_eq!(model, "claude");
+            }
+            _ => panic!("expected qa-spec command"),
+        }
+    }
+
+    #[test]
+    fn export_analytics_command_parses_format_and_since_last() {
+        let cli = Cli::try_parse_from([
+            "othala",
+            "export-analytics",
+            "--output",
+            "out",
+            "--format",
+            "csv",
+            "--since-last",
+        ])
+        .expect("parse export-analytics");
+        match cli.command {
+            Commands::ExportAnalytics {
+                output,
+                format,
+                since_last,
+            } => {
+                assert_eq!(output, PathBuf::from("out"));
+                assert_eq!(format, AnalyticsFormatArg::Csv);
+                assert!(since_last);
+            }
+            _ => panic!("expected export-analytics command"),
+        }
+        assert!(Cli::try_parse_from([
+            "othala",
+            "export-analytics",
+            "--output",
+            "out",
+            "--format",
+            "parquet"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn mcp_command_parses() {
         let cli = Cli::try_parse_from(["othala", "mcp"]).expect("parse mcp");
-        assert!(matches!(cli.command, Commands::Mcp));
+        assert!(matches!(
+            cli.command,
+            Commands::Mcp {
+                max_log_bytes: orchd::mcp_resources::DEFAULT_LOG_RESOURCE_BYTES
+            }
+        ));
+
+        let cli = Cli::try_parse_from(["othala", "mcp", "--max-log-bytes", "4096"])
+            .expect("parse mcp log cap");
+        assert!(matches!(
+            cli.command,
+            Commands::Mcp {
+                max_log_bytes: 4096
+            }
+        ));
     }
 
     #[test]
@@ -1,12 +1,30 @@
 //! Model Context Protocol (MCP) server implementation.
 //!
 //! Implements JSON-RPC 2.0 over stdin/stdout for tool discovery and invocation
-//! by external AI agents.
+//! by external AI agents, plus readable (and subscribable) resources.
 
+use orch_core::events::Event;
+use orch_core::secret::constant_time_eq;
+use orch_core::types::{ModelKind, RepoId, TaskId, TaskPriority};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::collections::HashMap;
 use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+use crate::agent_log::tail_agent_log;
+use crate::chat_workspace::{create_chat_task, NewChatTask};
+use crate::cli::models::{parse_model_arg, repo_models_config};
+use crate::file_watcher::{FileWatcher, WatcherConfig};
+use crate::mcp_resources::{ResourceError, ResourceRegistry};
+use crate::orchestration_metrics::OrchestrationMetricsStore;
+use crate::permissions::{PermissionPolicy, ToolCategory, ToolPermission};
+use crate::service::ServiceError;
+use crate::task_ids::TaskIdError;
+use crate::OrchdService;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JsonRpcRequest {
@@ -40,6 +58,31 @@ pub const INVALID_REQUEST: i64 = -32600;
 pub const METHOD_NOT_FOUND: i64 = -32601;
 pub const INVALID_PARAMS: i64 = -32602;
 pub const INTERNAL_ERROR: i64 = -32603;
+/// Server-defined error: the requested task does not exist.
+pub const TASK_NOT_FOUND: i64 = -32004;
+/// MCP error: the requested resource does not exist.
+pub const RESOURCE_NOT_FOUND: i64 = -32002;
+/// Server-defined error: the session may not call the requested tool.
+pub const UNAUTHORIZED: i64 = -32001;
+
+/// Session key used by `handle_request`, i.e. the single stdio client.
+const DEFAULT_SESSION: &str = "";
+
+/// How often `run_stdio` checks subscribed resource files while idle.
+const RESOURCE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Events returned by `list_task_events` when no `limit` is given.
+const DEFAULT_EVENT_LIMIT: u64 = 20;
+
+/// Agent output lines returned by `task_tail` when no `lines` is given.
+const DEFAULT_TAIL_LINES: u64 = 50;
+
+/// Most lines `task_logs` and `task_tail` return, whatever is asked for.
+const MAX_LOG_TOOL_LINES: u64 = 500;
+
+/// Byte budget for the lines in a `task_logs`/`task_tail` result; older
+/// lines are dropped first.
+const MAX_LOG_TOOL_BYTES: usize = 64 * 1024;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerInfo {
@@ -50,6 +93,7 @@ pub struct ServerInfo {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerCapabilities {
     pub tools: Option<ToolsCapability>,
+    pub resources: Option<ResourcesCapability>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,6 +102,13 @@ pub struct ToolsCapability {
     pub list_changed: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourcesCapability {
+    pub subscribe: bool,
+    #[serde(rename = "listChanged")]
+    pub list_changed: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolDefinition {
     pub name: String,
@@ -80,12 +131,69 @@ pub enum ToolContent {
     Text { text: String },
 }
 
+/// What a session negotiated on `initialize`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionAccess {
+    Full,
+    /// The session failed the shared-secret check and may only call
+    /// read-only tools.
+    ReadOnly,
+}
+
+/// Why a session may not see or call a tool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ToolDenial {
+    NotAllowlisted,
+    PermissionDenied(ToolCategory),
+    ReadOnlySession,
+}
+
+impl ToolDenial {
+    /// Key the rejection is counted under in the orchestration metrics.
+    fn as_str(&self) -> &'static str {
+        match self {
+            ToolDenial::NotAllowlisted => "not_allowlisted",
+            ToolDenial::PermissionDenied(_) => "permission_denied",
+            ToolDenial::ReadOnlySession => "read_only_session",
+        }
+    }
+
+    fn message(&self, tool: &str) -> String {
+        match self {
+            ToolDenial::NotAllowlisted => {
+                format!("tool '{tool}' is not in this server's allowed tools")
+            }
+            ToolDenial::PermissionDenied(category) => {
+                format!("tool '{tool}' is blocked by the repo's deny rule for {category}")
+            }
+            ToolDenial::ReadOnlySession => {
+                format!("tool '{tool}' needs an authenticated session; this session is read-only")
+            }
+        }
+    }
+}
+
 type ToolHandler = dyn Fn(&serde_json::Value) -> ToolCallResult;
+type FallibleToolHandler = dyn Fn(&serde_json::Value) -> Result<ToolCallResult, JsonRpcError>;
 
 pub struct McpServer {
     tools: Vec<ToolDefinition>,
-    tool_handlers: HashMap<String, Box<ToolHandler>>,
-    initialized: bool,
+    tool_handlers: HashMap<String, Box<FallibleToolHandler>>,
+    resources: ResourceRegistry,
+    /// Polls resource files for `resources/subscribe`; set up by
+    /// `register_project_resources`.
+    resource_watcher: Option<FileWatcher>,
+    /// Sessions that completed `initialize`, keyed by session ID.
+    sessions: HashMap<String, SessionAccess>,
+    /// When set, only these tools are listed or callable.
+    allowed_tools: Option<Vec<String>>,
+    /// When set, `initialize` must present it as `auth.secret` for full
+    /// access.
+    shared_secret: Option<String>,
+    /// Repo permission rules; tools in a denied category are hidden.
+    permissions: PermissionPolicy,
+    metrics: OrchestrationMetricsStore,
 }
 
 impl Default for McpServer {
@@ -99,17 +207,58 @@ impl McpServer {
         Self {
             tools: Vec::new(),
             tool_handlers: HashMap::new(),
-            initialized: false,
+            resources: ResourceRegistry::new(),
+            resource_watcher: None,
+            sessions: HashMap::new(),
+            allowed_tools: None,
+            shared_secret: None,
+            permissions: PermissionPolicy::default(),
+            metrics: OrchestrationMetricsStore::default(),
         }
     }
 
     /// Register a tool with its handler
     pub fn register_tool(&mut self, def: ToolDefinition, handler: Box<ToolHandler>) {
+        self.register_fallible_tool(def, Box::new(move |params| Ok(handler(params))));
+    }
+
+    /// Register a tool whose handler can reject a call with a JSON-RPC error
+    pub fn register_fallible_tool(
+        &mut self,
+        def: ToolDefinition,
+        handler: Box<FallibleToolHandler>,
+    ) {
         self.tools.retain(|existing| existing.name != def.name);
         self.tool_handlers.insert(def.name.clone(), handler);
         self.tools.push(def);
     }
 
+    /// Number of registered tools.
+    pub fn tool_count(&self) -> usize {
+        self.tools.len()
+    }
+
+    /// Only list and allow calls to `tools`; `None` lifts the restriction.
+    pub fn set_allowed_tools(&mut self, tools: Option<Vec<String>>) {
+        self.allowed_tools = tools;
+    }
+
+    /// Require `secret` on `initialize` for full access; sessions without it
+    /// are limited to read-only tools.
+    pub fn set_shared_secret(&mut self, secret: Option<String>) {
+        self.shared_secret = secret;
+    }
+
+    /// Hide and block tools whose [`tool_category`] the policy denies.
+    pub fn set_permission_policy(&mut self, policy: PermissionPolicy) {
+        self.permissions = policy;
+    }
+
+    /// Counters for rejected tool calls, under `mcp_rejections`.
+    pub fn metrics(&self) -> &OrchestrationMetricsStore {
+        &self.metrics
+    }
+
     /// Register all built-in Othala tools
     pub fn register_builtin_tools(&mut self) {
         self.register_tool(
@@ -358,8 +507,183 @@ impl McpServer {
         );
     }
 
+    /// Register tools that read task state through `service`
+    pub fn register_service_tools(&mut self, service: Rc<OrchdService>) {
+        let events_service = Rc::clone(&service);
+        self.register_fallible_tool(
+            ToolDefinition {
+                name: "list_task_events".to_string(),
+                description: "List the most recent events for a task as JSON".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "required": ["task_id"],
+                    "properties": {
+                        "task_id": { "type": "string", "description": "Task ID or alias" },
+                        "limit": {
+                            "type": "integer",
+                            "description": "Maximum number of events (default 20)"
+                        }
+                    }
+                }),
+            },
+            Box::new(move |params| list_task_events(&events_service, params)),
+        );
+
+        self.register_fallible_tool(
+            ToolDefinition {
+                name: "task_status".to_string(),
+                description: "Get a task's title, state, branch and pull request as JSON"
+                    .to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "required": ["task_id"],
+                    "properties": {
+                        "task_id": { "type": "string", "description": "Task ID or alias" }
+                    }
+                }),
+            },
+            Box::new(move |params| task_status(&service, params)),
+        );
+    }
+
+    /// Register `task_logs` (recent event lines) and `task_tail` (recent
+    /// agent output from `repo_root`'s agent logs) for editors showing
+    /// progress.
+    pub fn register_log_tools(&mut self, service: Rc<OrchdService>, repo_root: &Path) {
+        let logs_service = Rc::clone(&service);
+        self.register_fallible_tool(
+            ToolDefinition {
+                name: "task_logs".to_string(),
+                description: "Recent event log lines for a task, oldest first".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "required": ["task_id"],
+                    "properties": {
+                        "task_id": { "type": "string", "description": "Task ID or alias" },
+                        "limit": {
+                            "type": "integer",
+                            "description": format!(
+                                "Maximum number of lines (default {DEFAULT_EVENT_LIMIT}, \
+                                 at most {MAX_LOG_TOOL_LINES})"
+                            )
+                        }
+                    }
+                }),
+            },
+            Box::new(move |params| task_logs(&logs_service, params)),
+        );
+
+        let repo_root = repo_root.to_path_buf();
+        self.register_fallible_tool(
+            ToolDefinition {
+                name: "task_tail".to_string(),
+                description: "The last lines of a task's agent output".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "required": ["task_id"],
+                    "properties": {
+                        "task_id": { "type": "string", "description": "Task ID or alias" },
+                        "lines": {
+                            "type": "integer",
+                            "description": format!(
+                                "Number of lines (default {DEFAULT_TAIL_LINES}, \
+                                 at most {MAX_LOG_TOOL_LINES})"
+                            )
+                        }
+                    }
+                }),
+            },
+            Box::new(move |params| task_tail(&service, &repo_root, params)),
+        );
+    }
+
+    /// Replace the `create_task` stub with one that provisions a worktree
+    /// from `repo_root` and records the task, as `othala chat new` does.
+    pub fn register_task_creation_tool(&mut self, service: Rc<OrchdService>, repo_root: &Path) {
+        let repo_root = repo_root.to_path_buf();
+        self.register_fallible_tool(
+            ToolDefinition {
+                name: "create_task".to_string(),
+                description: "Create a task with its own worktree and return its ID".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "required": ["repo", "title"],
+                    "properties": {
+                        "repo": { "type": "string", "description": "Repository ID" },
+                        "title": { "type": "string", "description": "Task title" },
+                        "model": {
+                            "type": "string",
+                            "description": "Preferred model (default claude)"
+                        },
+                        "priority": {
+                            "type": "string",
+                            "description": "low, normal, high or critical (default normal)"
+                        }
+                    }
+                }),
+            },
+            Box::new(move |params| create_task(&service, &repo_root, params)),
+        );
+    }
+
+    /// Serve the context graph, agent logs and dependency graph as resources,
+    /// watching their files under `.othala/` for subscribers.
+    pub fn register_project_resources(
+        &mut self,
+        repo_root: &Path,
+        service: Rc<OrchdService>,
+        max_log_bytes: usize,
+    ) {
+        self.resources
+            .register_project_resources(repo_root, service, max_log_bytes);
+        let mut watcher = FileWatcher::new(
+            repo_root.join(".othala"),
+            WatcherConfig {
+                include_patterns: vec![
+                    "context/MAIN.md".to_string(),
+                    "agent-output/*/latest.log".to_string(),
+                ],
+                ignore_patterns: Vec::new(),
+                ..WatcherConfig::default()
+            },
+        );
+        watcher.initial_scan();
+        self.resource_watcher = Some(watcher);
+    }
+
+    /// `notifications/resources/updated` messages for subscribed resources
+    /// whose files changed since the last poll.
+    pub fn poll_resource_updates(&mut self) -> Vec<String> {
+        let Some(watcher) = self.resource_watcher.as_mut() else {
+            return Vec::new();
+        };
+        let events = watcher.poll();
+        self.resources
+            .updated_resources(&events)
+            .into_iter()
+            .filter_map(|uri| {
+                serde_json::to_string(&json!({
+                    "jsonrpc": "2.0",
+                    "method": "notifications/resources/updated",
+                    "params": { "uri": uri },
+                }))
+                .ok()
+            })
+            .collect()
+    }
+
     /// Handle a single JSON-RPC request and return response
     pub fn handle_request(&mut self, request: &JsonRpcRequest) -> JsonRpcResponse {
+        self.handle_session_request(DEFAULT_SESSION, request)
+    }
+
+    /// Handle a request on behalf of `session`, which transports serving
+    /// several clients use to keep their grants apart.
+    pub fn handle_session_request(
+        &mut self,
+        session: &str,
+        request: &JsonRpcRequest,
+    ) -> JsonRpcResponse {
         if request.jsonrpc != "2.0" {
             return Self::error_response(
                 request.id.clone(),
@@ -375,32 +699,29 @@ impl McpServer {
             .unwrap_or(serde_json::Value::Object(serde_json::Map::new()));
 
         match request.method.as_str() {
-            "initialize" => self.handle_initialize(request.id.clone(), &params),
+            "initialize" => self.handle_initialize(session, request.id.clone(), &params),
             "initialized" => {
-                self.initialized = true;
+                let access = self.default_access();
+                self.sessions.entry(session.to_string()).or_insert(access);
                 Self::success_response(request.id.clone(), serde_json::Value::Null)
             }
-            "tools/list" => {
-                if !self.initialized {
-                    return Self::error_response(
-                        request.id.clone(),
-                        INVALID_REQUEST,
-                        "Server not initialized",
-                        None,
-                    );
-                }
-                self.handle_tools_list(request.id.clone())
+            method if !self.sessions.contains_key(session) && is_session_method(method) => {
+                Self::error_response(
+                    request.id.clone(),
+                    INVALID_REQUEST,
+                    "Server not initialized",
+                    None,
+                )
             }
-            "tools/call" => {
-                if !self.initialized {
-                    return Self::error_response(
-                        request.id.clone(),
-                        INVALID_REQUEST,
-                        "Server not initialized",
-                        None,
-                    );
-                }
-                self.handle_tools_call(request.id.clone(), &params)
+            "tools/list" => self.handle_tools_list(session, request.id.clone()),
+            "tools/call" => self.handle_tools_call(session, request.id.clone(), &params),
+            "resources/list" => self.handle_resources_list(request.id.clone(), &params),
+            "resources/read" => self.handle_resources_read(request.id.clone(), &params),
+            "resources/subscribe" => {
+                self.handle_resources_subscribe(request.id.clone(), &params, true)
+            }
+            "resources/unsubscribe" => {
+                self.handle_resources_subscribe(request.id.clone(), &params, false)
             }
             _ => Self::error_response(
                 request.id.clone(),
@@ -411,9 +732,41 @@ impl McpServer {
         }
     }
 
+    /// Access granted to sessions that present no secret.
+    fn default_access(&self) -> SessionAccess {
+        if self.shared_secret.is_some() {
+            SessionAccess::ReadOnly
+        } else {
+            SessionAccess::Full
+        }
+    }
+
+    /// Why `session` may not use `tool`, or `None` when it may.
+    fn tool_denial(&self, session: &str, tool: &str) -> Option<ToolDenial> {
+        if let Some(allowed) = &self.allowed_tools {
+            if !allowed.iter().any(|name| name == tool) {
+                return Some(ToolDenial::NotAllowlisted);
+            }
+        }
+        let category = tool_category(tool);
+        if self.permissions.check(&category, None, None) == ToolPermission::Deny {
+            return Some(ToolDenial::PermissionDenied(category));
+        }
+        let access = self
+            .sessions
+            .get(session)
+            .copied()
+            .unwrap_or_else(|| self.default_access());
+        if access == SessionAccess::ReadOnly && category != ToolCategory::FileRead {
+            return Some(ToolDenial::ReadOnlySession);
+        }
+        None
+    }
+
     /// Handle `initialize` method
     fn handle_initialize(
         &mut self,
+        session: &str,
         id: Option<serde_json::Value>,
         params: &serde_json::Value,
     ) -> JsonRpcResponse {
@@ -426,7 +779,19 @@ impl McpServer {
             );
         }
 
-        self.initialized = true;
+        let presented = params
+            .pointer("/auth/secret")
+            .and_then(serde_json::Value::as_str);
+        let access = match (&self.shared_secret, presented) {
+            (None, _) => SessionAccess::Full,
+            (Some(secret), Some(presented))
+                if constant_time_eq(secret.as_bytes(), presented.as_bytes()) =>
+            {
+                SessionAccess::Full
+            }
+            (Some(_), _) => SessionAccess::ReadOnly,
+        };
+        self.sessions.insert(session.to_string(), access);
 
         let server_info = ServerInfo {
             name: "othala".to_string(),
@@ -434,30 +799,41 @@ impl McpServer {
         };
         let capabilities = ServerCapabilities {
             tools: Some(ToolsCapability { list_changed: false }),
+            resources: Some(ResourcesCapability {
+                subscribe: true,
+                list_changed: false,
+            }),
         };
 
         Self::success_response(
             id,
             json!({
                 "serverInfo": server_info,
-                "capabilities": capabilities
+                "capabilities": capabilities,
+                "access": access
             }),
         )
     }
 
     /// Handle `tools/list` method
-    fn handle_tools_list(&self, id: Option<serde_json::Value>) -> JsonRpcResponse {
+    fn handle_tools_list(&self, session: &str, id: Option<serde_json::Value>) -> JsonRpcResponse {
+        let tools: Vec<&ToolDefinition> = self
+            .tools
+            .iter()
+            .filter(|tool| self.tool_denial(session, &tool.name).is_none())
+            .collect();
         Self::success_response(
             id,
             json!({
-                "tools": self.tools
+                "tools": tools
             }),
         )
     }
 
     /// Handle `tools/call` method
     fn handle_tools_call(
-        &self,
+        &mut self,
+        session: &str,
         id: Option<serde_json::Value>,
         params: &serde_json::Value,
     ) -> JsonRpcResponse {
@@ -483,7 +859,27 @@ impl McpServer {
             );
         };
 
-        let tool_result = handler(&arguments);
+        if let Some(denial) = self.tool_denial(session, name) {
+            self.metrics.record_mcp_rejection(denial.as_str());
+            return Self::error_response(
+                id,
+                UNAUTHORIZED,
+                &denial.message(name),
+                Some(json!({ "name": name, "reason": denial.as_str() })),
+            );
+        }
+
+        let tool_result = match handler(&arguments) {
+            Ok(result) => result,
+            Err(error) => {
+                return JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id,
+                    result: None,
+                    error: Some(error),
+                }
+            }
+        };
         match serde_json::to_value(tool_result) {
             Ok(result) => Self::success_response(id, result),
             Err(err) => Self::error_response(
@@ -495,19 +891,110 @@ impl McpServer {
         }
     }
 
-    /// Run the MCP server loop reading from stdin and writing to stdout
+    /// Handle `resources/list` method
+    fn handle_resources_list(
+        &self,
+        id: Option<serde_json::Value>,
+        params: &serde_json::Value,
+    ) -> JsonRpcResponse {
+        let cursor = params.get("cursor").and_then(serde_json::Value::as_str);
+        let page = match self.resources.list_resources_page(cursor, None) {
+            Ok(page) => page,
+            Err(err) => return Self::error_response(id, INVALID_PARAMS, &err.to_string(), None),
+        };
+        match serde_json::to_value(page) {
+            Ok(result) => Self::success_response(id, result),
+            Err(err) => Self::error_response(
+                id,
+                INTERNAL_ERROR,
+                "Failed to serialize resource list",
+                Some(json!({ "reason": err.to_string() })),
+            ),
+        }
+    }
+
+    /// Handle `resources/read` method
+    fn handle_resources_read(
+        &self,
+        id: Option<serde_json::Value>,
+        params: &serde_json::Value,
+    ) -> JsonRpcResponse {
+        let Some(uri) = params.get("uri").and_then(serde_json::Value::as_str) else {
+            return Self::error_response(
+                id,
+                INVALID_PARAMS,
+                "resources/read missing string field 'uri'",
+                None,
+            );
+        };
+        match self.resources.read_resource(uri) {
+            Ok(content) => Self::success_response(id, json!({ "contents": [content] })),
+            Err(err) => Self::resource_error_response(id, uri, err),
+        }
+    }
+
+    /// Handle `resources/subscribe` and `resources/unsubscribe`
+    fn handle_resources_subscribe(
+        &mut self,
+        id: Option<serde_json::Value>,
+        params: &serde_json::Value,
+        subscribe: bool,
+    ) -> JsonRpcResponse {
+        let Some(uri) = params.get("uri").and_then(serde_json::Value::as_str) else {
+            return Self::error_response(id, INVALID_PARAMS, "missing string field 'uri'", None);
+        };
+        if !subscribe {
+            self.resources.unsubscribe(uri);
+            return Self::success_response(id, json!({}));
+        }
+        match self.resources.subscribe(uri) {
+            Ok(()) => Self::success_response(id, json!({})),
+            Err(err) => Self::resource_error_response(id, uri, err),
+        }
+    }
+
+    fn resource_error_response(
+        id: Option<serde_json::Value>,
+        uri: &str,
+        err: ResourceError,
+    ) -> JsonRpcResponse {
+        let code = match err {
+            ResourceError::NotFound(_) => RESOURCE_NOT_FOUND,
+            ResourceError::InvalidUri(_) | ResourceError::NotSubscribable(_) => INVALID_PARAMS,
+            _ => INTERNAL_ERROR,
+        };
+        Self::error_response(id, code, &err.to_string(), Some(json!({ "uri": uri })))
+    }
+
+    /// Run the MCP server loop reading from stdin and writing to stdout.
+    /// Between requests, subscribed resources are polled and change
+    /// notifications written out.
     pub fn run_stdio(&mut self) -> io::Result<()> {
-        let stdin = io::stdin();
+        let (lines_tx, lines) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in io::stdin().lock().lines() {
+                let done = line.is_err();
+                if lines_tx.send(line).is_err() || done {
+                    break;
+                }
+            }
+        });
+
         let stdout = io::stdout();
         let mut out = stdout.lock();
-
-        for line in stdin.lock().lines() {
-            let line = line?;
-            if let Some(response) = self.process_line(&line) {
-                out.write_all(response.as_bytes())?;
+        loop {
+            let mut messages = Vec::new();
+            match lines.recv_timeout(RESOURCE_POLL_INTERVAL) {
+                Ok(line) => messages.extend(self.process_line(&line?)),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+            messages.extend(self.poll_resource_updates());
+            for message in messages {
+                out.write_all(message.as_bytes())?;
                 out.write_all(b"\n")?;
-                out.flush()?;
             }
+            out.flush()?;
         }
 
         Ok(())
@@ -584,9 +1071,261 @@ impl McpServer {
     }
 }
 
+/// Permission category an MCP tool falls under, so repo rules such as
+/// `deny file_write` also cover MCP clients. Tools outside the built-in set
+/// map to `Custom(name)` and can be denied by name.
+pub fn tool_category(name: &str) -> ToolCategory {
+    match name {
+        "list_tasks" | "get_task" | "list_events" | "get_stats" | "list_sessions"
+        | "list_skills" | "search_tasks" | "get_health" | "list_task_events" | "task_status"
+        | "task_logs" | "task_tail" => ToolCategory::FileRead,
+        "create_task" | "delete_task" => ToolCategory::FileWrite,
+        "stop_task" | "resume_task" => ToolCategory::Process,
+        other => ToolCategory::Custom(other.to_string()),
+    }
+}
+
+/// Methods that need a completed `initialize` handshake.
+fn is_session_method(method: &str) -> bool {
+    method.starts_with("tools/") || method.starts_with("resources/")
+}
+
+fn list_task_events(
+    service: &OrchdService,
+    params: &serde_json::Value,
+) -> Result<ToolCallResult, JsonRpcError> {
+    let task_id = resolve_task_param(service, params)?;
+    let limit = optional_positive(params, "limit", DEFAULT_EVENT_LIMIT)?;
+    let events = service.task_events(&task_id).map_err(service_error)?;
+    let skip = events.len().saturating_sub(limit as usize);
+    json_tool_result(&json!({
+        "task_id": task_id.0,
+        "events": &events[skip..],
+    }))
+}
+
+/// `task_status` result: `{ "task_id", "title", "state", "branch", "pr" }`,
+/// where `branch` and `pr` (`{ "number", "url", "draft" }`) are null until
+/// the task has them. Unknown ids fail with `TASK_NOT_FOUND`.
+fn task_status(
+    service: &OrchdService,
+    params: &serde_json::Value,
+) -> Result<ToolCallResult, JsonRpcError> {
+    let task_id = resolve_task_param(service, params)?;
+    let task = service
+        .task(&task_id)
+        .map_err(service_error)?
+        .ok_or_else(|| task_not_found(&task_id.0))?;
+    json_tool_result(&json!({
+        "task_id": task.id.0,
+        "title": task.title,
+        "state": task.state.to_string(),
+        "branch": task.branch_name,
+        "pr": task.pr,
+    }))
+}
+
+/// `task_logs` result: `{ "task_id", "lines", "truncated" }`, one
+/// `<timestamp> <kind> [fields]` line per event, oldest first.
+fn task_logs(
+    service: &OrchdService,
+    params: &serde_json::Value,
+) -> Result<ToolCallResult, JsonRpcError> {
+    let task_id = resolve_task_param(service, params)?;
+    let limit = optional_positive(params, "limit", DEFAULT_EVENT_LIMIT)?.min(MAX_LOG_TOOL_LINES);
+    let events = service.task_events(&task_id).map_err(service_error)?;
+    let skip = events.len().saturating_sub(limit as usize);
+    let lines = events[skip..].iter().map(event_line).collect();
+    capped_lines_result(&task_id, lines)
+}
+
+/// `task_tail` result: `{ "task_id", "lines", "truncated" }` with the last
+/// lines of the task's latest agent log; no lines when it has none yet.
+fn task_tail(
+    service: &OrchdService,
+    repo_root: &Path,
+    params: &serde_json::Value,
+) -> Result<ToolCallResult, JsonRpcError> {
+    let task_id = resolve_task_param(service, params)?;
+    service
+        .task(&task_id)
+        .map_err(service_error)?
+        .ok_or_else(|| task_not_found(&task_id.0))?;
+    let count = optional_positive(params, "lines", DEFAULT_TAIL_LINES)?.min(MAX_LOG_TOOL_LINES);
+    let lines = match tail_agent_log(repo_root, &task_id, count as usize) {
+        Ok(lines) => lines,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+        Err(err) => {
+            return Err(rpc_error(
+                INTERNAL_ERROR,
+                &format!("failed to read agent log for {}: {err}", task_id.0),
+            ))
+        }
+    };
+    capped_lines_result(&task_id, lines)
+}
+
+/// One log line for `event`: its time, serde tag and any fields.
+fn event_line(event: &Event) -> String {
+    let at = event.at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    match serde_json::to_value(&event.kind) {
+        Ok(serde_json::Value::Object(map)) => match map.into_iter().next() {
+            Some((tag, fields)) => format!("{at} {tag} {fields}"),
+            None => at,
+        },
+        Ok(serde_json::Value::String(tag)) => format!("{at} {tag}"),
+        _ => format!("{at} {:?}", event.kind),
+    }
+}
+
+/// Keep the newest `lines` that fit in `MAX_LOG_TOOL_BYTES`.
+fn capped_lines_result(
+    task_id: &TaskId,
+    mut lines: Vec<String>,
+) -> Result<ToolCallResult, JsonRpcError> {
+    let mut budget = MAX_LOG_TOOL_BYTES;
+    let keep = lines
+        .iter()
+        .rev()
+        .take_while(|line| {
+            let fits = line.len() < budget;
+            budget = budget.saturating_sub(line.len() + 1);
+            fits
+        })
+        .count();
+    let truncated = keep < lines.len();
+    let lines = lines.split_off(lines.len() - keep);
+    json_tool_result(&json!({
+        "task_id": task_id.0,
+        "lines": lines,
+        "truncated": truncated,
+    }))
+}
+
+fn create_task(
+    service: &OrchdService,
+    repo_root: &Path,
+    params: &serde_json::Value,
+) -> Result<ToolCallResult, JsonRpcError> {
+    let repo = required_str(params, "repo")?;
+    let title = required_str(params, "title")?;
+    let model = match optional_str(params, "model")? {
+        Some(name) => parse_model_arg(name, &repo_models_config(repo_root))
+            .map_err(|err| rpc_error(INVALID_PARAMS, &format!("invalid 'model': {err}")))?,
+        None => ModelKind::Claude,
+    };
+    let priority = match optional_str(params, "priority")? {
+        Some(raw) => raw
+            .parse::<TaskPriority>()
+            .map_err(|err| rpc_error(INVALID_PARAMS, &err))?,
+        None => TaskPriority::Normal,
+    };
+
+    let request = NewChatTask {
+        repo_id: RepoId(repo.to_string()),
+        title: title.to_string(),
+        model,
+        priority,
+        stack: true,
+        stack_on: None,
+    };
+    let created = create_chat_task(service, repo_root, &request)
+        .map_err(|err| rpc_error(INTERNAL_ERROR, &format!("{err:#}")))?;
+    json_tool_result(&json!({
+        "task_id": created.task.id.0,
+        "branch": created.workspace.branch_name,
+        "worktree_path": created.workspace.worktree_path,
+        "parent_task_id": created.parent.map(|(parent, _)| parent.0),
+    }))
+}
+
+/// The non-empty string argument `name`.
+fn required_str<'a>(params: &'a serde_json::Value, name: &str) -> Result<&'a str, JsonRpcError> {
+    optional_str(params, name)?
+        .ok_or_else(|| rpc_error(INVALID_PARAMS, &format!("missing string argument '{name}'")))
+}
+
+/// The string argument `name`, `None` when absent, null or blank.
+fn optional_str<'a>(
+    params: &'a serde_json::Value,
+    name: &str,
+) -> Result<Option<&'a str>, JsonRpcError> {
+    match params.get(name) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::String(value)) => {
+            Ok(Some(value.trim()).filter(|value| !value.is_empty()))
+        }
+        Some(_) => Err(rpc_error(
+            INVALID_PARAMS,
+            &format!("argument '{name}' must be a string"),
+        )),
+    }
+}
+
+/// The positive integer argument `name`, or `default` when absent or null.
+fn optional_positive(
+    params: &serde_json::Value,
+    name: &str,
+    default: u64,
+) -> Result<u64, JsonRpcError> {
+    match params.get(name) {
+        None | Some(serde_json::Value::Null) => Ok(default),
+        Some(value) => value.as_u64().filter(|value| *value > 0).ok_or_else(|| {
+            rpc_error(
+                INVALID_PARAMS,
+                &format!("'{name}' must be a positive integer"),
+            )
+        }),
+    }
+}
+
+/// Validate the `task_id` argument and resolve aliases/prefixes.
+fn resolve_task_param(
+    service: &OrchdService,
+    params: &serde_json::Value,
+) -> Result<TaskId, JsonRpcError> {
+    let raw = required_str(params, "task_id")?;
+    service.resolve_task_id(raw).map_err(service_error)
+}
+
+fn service_error(err: ServiceError) -> JsonRpcError {
+    match err {
+        ServiceError::TaskNotFound { task_id } => task_not_found(&task_id),
+        ServiceError::TaskId(TaskIdError::NotFound { input, .. }) => task_not_found(&input),
+        ServiceError::TaskId(err) => rpc_error(INVALID_PARAMS, &err.to_string()),
+        other => rpc_error(INTERNAL_ERROR, &other.to_string()),
+    }
+}
+
+fn task_not_found(task_id: &str) -> JsonRpcError {
+    JsonRpcError {
+        code: TASK_NOT_FOUND,
+        message: format!("task not found: {task_id}"),
+        data: Some(json!({ "task_id": task_id })),
+    }
+}
+
+fn rpc_error(code: i64, message: &str) -> JsonRpcError {
+    JsonRpcError {
+        code,
+        message: message.to_string(),
+        data: None,
+    }
+}
+
+fn json_tool_result(value: &serde_json::Value) -> Result<ToolCallResult, JsonRpcError> {
+    let text = serde_json::to_string_pretty(value)
+        .map_err(|err| rpc_error(INTERNAL_ERROR, &err.to_string()))?;
+    Ok(ToolCallResult {
+        content: vec![ToolContent::Text { text }],
+        is_error: false,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::permissions::PermissionRule;
 
     fn parse_response(raw: &str) -> JsonRpcResponse {
         serde_json::from_str(raw).expect("parse response")
@@ -848,10 +1587,559 @@ mod tests {
         assert_eq!(error.code, INVALID_REQUEST);
     }
 
+    fn session_rpc(
+        server: &mut McpServer,
+        session: &str,
+        method: &str,
+        params: serde_json::Value,
+    ) -> JsonRpcResponse {
+        server.handle_session_request(
+            session,
+            &JsonRpcRequest {
+                jsonrpc: "2.0".to_string(),
+                id: Some(json!(40)),
+                method: method.to_string(),
+                params: Some(params),
+            },
+        )
+    }
+
+    fn listed_tools(server: &mut McpServer, session: &str) -> Vec<String> {
+        let result = session_rpc(server, session, "tools/list", json!({}))
+            .result
+            .expect("tools list result");
+        result["tools"]
+            .as_array()
+            .expect("tools array")
+            .iter()
+            .filter_map(|tool| tool["name"].as_str().map(str::to_string))
+            .collect()
+    }
+
+    #[test]
+    fn sessions_without_the_shared_secret_only_get_read_only_tools() {
+        let mut server = McpServer::new();
+        server.register_builtin_tools();
+        server.set_shared_secret(Some("s3cret".to_string()));
+
+        let anonymous = session_rpc(&mut server, "anon", "initialize", json!({}));
+        assert_eq!(
+            anonymous.result.expect("initialize")["access"],
+            json!("read_only")
+        );
+        let authed = session_rpc(
+            &mut server,
+            "authed",
+            "initialize",
+            json!({ "auth": { "secret": "s3cret" } }),
+        );
+        assert_eq!(authed.result.expect("initialize")["access"], json!("full"));
+
+        let anonymous_tools = listed_tools(&mut server, "anon");
+        assert!(anonymous_tools.contains(&"list_tasks".to_string()));
+        assert!(!anonymous_tools.contains(&"create_task".to_string()));
+        assert!(listed_tools(&mut server, "authed").contains(&"create_task".to_string()));
+
+        let call = json!({ "name": "create_task", "arguments": { "repo": "r", "title": "t" } });
+        let rejected = session_rpc(&mut server, "anon", "tools/call", call.clone());
+        let error = rejected.error.expect("read-only session is rejected");
+        assert_eq!(error.code, UNAUTHORIZED);
+        assert!(error.message.contains("read-only"));
+        assert!(session_rpc(&mut server, "authed", "tools/call", call)
+            .error
+            .is_none());
+        assert_eq!(
+            server
+                .metrics()
+                .current_snapshot
+                .mcp_rejections
+                .get("read_only_session"),
+            Some(&1)
+        );
+    }
+
+    #[test]
+    fn unknown_sessions_fall_back_to_the_default_access() {
+        let mut server = McpServer::new();
+        server.register_builtin_tools();
+        assert_eq!(server.tool_denial("unknown", "create_task"), None);
+
+        server.set_shared_secret(Some("s3cret".to_string()));
+        assert_eq!(
+            server.tool_denial("unknown", "create_task"),
+            Some(ToolDenial::ReadOnlySession)
+        );
+    }
+
+    #[test]
+    fn allowlist_and_repo_deny_rules_hide_and_block_tools() {
+        let mut server = McpServer::new();
+        server.register_builtin_tools();
+        server.set_allowed_tools(Some(vec![
+            "list_tasks".to_string(),
+            "stop_task".to_string(),
+        ]));
+        let mut policy = PermissionPolicy::permissive();
+        policy.add_rule(PermissionRule {
+            category: ToolCategory::Process,
+            permission: ToolPermission::Deny,
+            path_pattern: None,
+            reason: None,
+        });
+        server.set_permission_policy(policy);
+        init_server(&mut server);
+
+        assert_eq!(
+            listed_tools(&mut server, DEFAULT_SESSION),
+            vec!["list_tasks"]
+        );
+
+        let denied = rpc(&mut server, "tools/call", json!({ "name": "stop_task" }))
+            .error
+            .expect("denied category");
+        assert_eq!(denied.code, UNAUTHORIZED);
+        assert!(denied.message.contains("process"));
+        let unlisted = rpc(&mut server, "tools/call", json!({ "name": "get_task" }))
+            .error
+            .expect("not allowlisted");
+        assert_eq!(unlisted.code, UNAUTHORIZED);
+        assert_eq!(
+            unlisted.data.expect("data")["reason"],
+            json!("not_allowlisted")
+        );
+        let rejections = &server.metrics().current_snapshot.mcp_rejections;
+        assert_eq!(rejections.get("permission_denied"), Some(&1));
+        assert_eq!(rejections.get("not_allowlisted"), Some(&1));
+    }
+
+    fn mk_service_server() -> McpServer {
+        let mut server = McpServer::new();
+        server.register_service_tools(Rc::new(mk_service()));
+        init_server(&mut server);
+        server
+    }
+
+    /// A service holding task `T-MCP` with a few events.
+    fn mk_service() -> OrchdService {
+        use crate::event_log::JsonlEventLog;
+        use crate::persistence::SqliteStore;
+        use crate::scheduler::{Scheduler, SchedulerConfig};
+        use orch_core::events::{Event, EventKind};
+        use orch_core::types::{EventId, ModelKind, RepoId, Task};
+
+        let dir = std::env::temp_dir().join(format!(
+            "othala-mcp-test-{}",
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        let service = OrchdService::new(
+            SqliteStore::open_in_memory().expect("in-memory db"),
+            JsonlEventLog::new(dir),
+            Scheduler::new(SchedulerConfig {
+                per_repo_limit: 10,
+                per_model_limit: [(ModelKind::Claude, 10)].into_iter().collect(),
+            }),
+        );
+        service.bootstrap().expect("bootstrap");
+
+        let mut task = Task::new(
+            TaskId::new("T-MCP"),
+            RepoId("example".to_string()),
+            "MCP task".to_string(),
+            std::path::PathBuf::from("/tmp/wt"),
+        );
+        task.branch_name = Some("task/T-MCP".to_string());
+        task.mark_submitted("https://github.com/acme/app/pull/9".to_string(), 9);
+        let created = |n: usize, kind: EventKind| Event {
+            id: EventId(format!("E-MCP-{n}")),
+            task_id: Some(task.id.clone()),
+            repo_id: Some(task.repo_id.clone()),
+            at: chrono::Utc::now(),
+            kind,
+        };
+        service
+            .create_task(&task, &created(0, EventKind::TaskCreated))
+            .expect("create task");
+        for n in 1..=3 {
+            service
+                .record_event(&created(n, EventKind::NeedsHuman {
+                    reason: format!("question {n}"),
+                }))
+                .expect("record event");
+        }
+        service
+    }
+
+    fn call_tool(
+        server: &mut McpServer,
+        name: &str,
+        arguments: serde_json::Value,
+    ) -> JsonRpcResponse {
+        server.handle_request(&JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: Some(json!(20)),
+            method: "tools/call".to_string(),
+            params: Some(json!({ "name": name, "arguments": arguments })),
+        })
+    }
+
+    fn rpc(server: &mut McpServer, method: &str, params: serde_json::Value) -> JsonRpcResponse {
+        server.handle_request(&JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: Some(json!(30)),
+            method: method.to_string(),
+            params: Some(params),
+        })
+    }
+
+    fn tool_json(response: JsonRpcResponse) -> serde_json::Value {
+        let result = response.result.expect("tool result");
+        let text = result["content"][0]["text"].as_str().expect("text content");
+        serde_json::from_str(text).expect("tool returns json")
+    }
+
+    #[test]
+    fn list_task_events_returns_most_recent_events() {
+        let mut server = mk_service_server();
+
+        let body = tool_json(call_tool(
+            &mut server,
+            "list_task_events",
+            json!({ "task_id": "T-MCP", "limit": 2 }),
+        ));
+        assert_eq!(body["task_id"], json!("T-MCP"));
+        let events = body["events"].as_array().expect("events array");
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1]["id"], json!("E-MCP-3"));
+        assert_eq!(events[1]["task_id"], json!("T-MCP"));
+
+        let all = tool_json(call_tool(
+            &mut server,
+            "list_task_events",
+            json!({ "task_id": "T-MCP" }),
+        ));
+        assert_eq!(all["events"].as_array().expect("events").len(), 4);
+    }
+
+    #[test]
+    fn task_status_reports_state_branch_and_pr() {
+        let mut server = mk_service_server();
+
+        let body = tool_json(call_tool(&mut server, "task_status", json!({ "task_id": "T-MCP" })));
+        assert_eq!(body["task_id"], json!("T-MCP"));
+        assert_eq!(body["title"], json!("MCP task"));
+        assert_eq!(body["state"], json!("AWAITING_MERGE"));
+        assert_eq!(body["branch"], json!("task/T-MCP"));
+        assert_eq!(body["pr"]["number"], json!(9));
+        assert_eq!(body["pr"]["url"], json!("https://github.com/acme/app/pull/9"));
+    }
+
+    #[test]
+    fn task_logs_returns_recent_event_lines() {
+        let mut server = mk_service_server();
+        server.register_log_tools(Rc::new(mk_service()), Path::new("/nonexistent"));
+
+        let body = tool_json(call_tool(
+            &mut server,
+            "task_logs",
+            json!({ "task_id": "T-MCP", "limit": 2 }),
+        ));
+        assert_eq!(body["task_id"], json!("T-MCP"));
+        assert_eq!(body["truncated"], json!(false));
+        let lines = body["lines"].as_array().expect("lines");
+        assert_eq!(lines.len(), 2);
+        let last = lines[1].as_str().expect("line");
+        assert!(
+            last.ends_with(r#" needs_human {"reason":"question 3"}"#),
+            "{last}"
+        );
+
+        let all = tool_json(call_tool(
+            &mut server,
+            "task_logs",
+            json!({ "task_id": "T-MCP" }),
+        ));
+        let first = all["lines"][0].as_str().expect("line");
+        assert!(first.ends_with(" task_created"), "{first}");
+
+        let missing = call_tool(&mut server, "task_logs", json!({ "task_id": "T-NOPE" }));
+        assert_eq!(missing.error.expect("unknown task").code, TASK_NOT_FOUND);
+    }
+
+    #[test]
+    fn task_tail_returns_the_last_agent_output_lines() {
+        use crate::agent_log::agent_log_dir;
+
+        let root = std::env::temp_dir().join(format!(
+            "othala-mcp-tail-{}-{}",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let log_dir = agent_log_dir(&root, &TaskId::new("T-MCP"));
+        std::fs::create_dir_all(&log_dir).expect("create log dir");
+        let output: String = (1..=5).map(|n| format!("line {n}\n")).collect();
+        std::fs::write(log_dir.join("latest.log"), output).expect("write log");
+
+        let mut server = McpServer::new();
+        server.register_log_tools(Rc::new(mk_service()), &root);
+        init_server(&mut server);
+
+        let body = tool_json(call_tool(
+            &mut server,
+            "task_tail",
+            json!({ "task_id": "T-MCP", "lines": 2 }),
+        ));
+        assert_eq!(body["lines"], json!(["line 4", "line 5"]));
+        assert_eq!(body["truncated"], json!(false));
+
+        let huge = "y".repeat(MAX_LOG_TOOL_BYTES / 2);
+        std::fs::write(
+            log_dir.join("latest.log"),
+            format!("{huge}\n{huge}\n{huge}\n"),
+        )
+        .expect("write log");
+        let capped = tool_json(call_tool(
+            &mut server,
+            "task_tail",
+            json!({ "task_id": "T-MCP" }),
+        ));
+        assert_eq!(capped["lines"].as_array().expect("lines").len(), 1);
+        assert_eq!(capped["truncated"], json!(true));
+
+        let missing = call_tool(&mut server, "task_tail", json!({ "task_id": "T-NOPE" }));
+        assert_eq!(missing.error.expect("unknown task").code, TASK_NOT_FOUND);
+        let bad_lines = call_tool(
+            &mut server,
+            "task_tail",
+            json!({ "task_id": "T-MCP", "lines": -1 }),
+        );
+        assert_eq!(bad_lines.error.expect("bad lines").code, INVALID_PARAMS);
+
+        let _ = std::fs::remove_dir_all(&root);
+        let mut server = McpServer::new();
+        server.register_log_tools(Rc::new(mk_service()), &root);
+        init_server(&mut server);
+        let no_log = tool_json(call_tool(
+            &mut server,
+            "task_tail",
+            json!({ "task_id": "T-MCP" }),
+        ));
+        assert_eq!(no_log["lines"], json!([]));
+    }
+
+    /// A git repo with one commit on `main`, for worktree provisioning.
+    fn mk_git_repo() -> std::path::PathBuf {
+        let repo = std::env::temp_dir().join(format!(
+            "othala-mcp-create-{}-{}",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        std::fs::create_dir_all(&repo).expect("create repo dir");
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args(args)
+                .current_dir(&repo)
+                .output()
+                .expect("run git")
+                .status;
+            assert!(status.success(), "git {args:?} failed");
+        };
+        git(&["init", "-q", "-b", "main"]);
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "test"]);
+        std::fs::write(repo.join("README.md"), "hello\n").expect("write readme");
+        git(&["add", "."]);
+        git(&["commit", "-q", "-m", "init"]);
+        repo
+    }
+
+    #[test]
+    fn create_task_provisions_a_task_and_returns_its_id() {
+        let repo = mk_git_repo();
+        let service = Rc::new(mk_service());
+        let mut server = McpServer::new();
+        server.register_builtin_tools();
+        server.register_task_creation_tool(Rc::clone(&service), &repo);
+        init_server(&mut server);
+
+        let body = tool_json(call_tool(
+            &mut server,
+            "create_task",
+            json!({ "repo": "editor", "title": "From the editor", "model": "codex" }),
+        ));
+        let task_id = body["task_id"].as_str().expect("task id");
+        let task = service
+            .task(&TaskId::new(task_id))
+            .expect("load task")
+            .expect("task was created");
+        assert_eq!(task.title, "From the editor");
+        assert_eq!(task.preferred_model, Some(ModelKind::Codex));
+        assert_eq!(body["branch"], json!(task.branch_name.expect("branch")));
+
+        let missing_title = call_tool(&mut server, "create_task", json!({ "repo": "editor" }));
+        assert_eq!(
+            missing_title.error.expect("missing title").code,
+            INVALID_PARAMS
+        );
+        let bad_priority = call_tool(
+            &mut server,
+            "create_task",
+            json!({ "repo": "editor", "title": "t", "priority": "urgent" }),
+        );
+        assert_eq!(
+            bad_priority.error.expect("bad priority").code,
+            INVALID_PARAMS
+        );
+        let unknown_model = call_tool(
+            &mut server,
+            "create_task",
+            json!({ "repo": "editor", "title": "t", "model": "not-configured" }),
+        );
+        let error = unknown_model.error.expect("unknown model");
+        assert_eq!(error.code, INVALID_PARAMS);
+        assert!(error.message.contains("unknown model 'not-configured'"));
+
+        server.set_shared_secret(Some("s3cret".to_string()));
+        session_rpc(&mut server, "anon", "initialize", json!({}));
+        let call = json!({
+            "name": "create_task",
+            "arguments": { "repo": "editor", "title": "denied" }
+        });
+        let denied = session_rpc(&mut server, "anon", "tools/call", call);
+        assert_eq!(denied.error.expect("read-only session").code, UNAUTHORIZED);
+
+        let _ = std::process::Command::new("git")
+            .args(["worktree", "prune"])
+            .current_dir(&repo)
+            .output();
+        let _ = std::fs::remove_dir_all(&repo);
+    }
+
+    #[test]
+    fn service_tools_reject_bad_arguments_with_rpc_errors() {
+        let mut server = mk_service_server();
+
+        let missing = call_tool(&mut server, "task_status", json!({}));
+        assert_eq!(missing.error.expect("missing task_id").code, INVALID_PARAMS);
+
+        let unknown = call_tool(&mut server, "task_status", json!({ "task_id": "T-NOPE" }));
+        let error = unknown.error.expect("unknown task");
+        assert_eq!(error.code, TASK_NOT_FOUND);
+        assert_eq!(error.data, Some(json!({ "task_id": "T-NOPE" })));
+
+        let bad_limit = call_tool(
+            &mut server,
+            "list_task_events",
+            json!({ "task_id": "T-MCP", "limit": 0 }),
+        );
+        assert_eq!(bad_limit.error.expect("bad limit").code, INVALID_PARAMS);
+    }
+
     #[test]
     fn notifications_return_no_output_from_process_line() {
         let mut server = McpServer::new();
         let no_output = server.process_line(r#"{"jsonrpc":"2.0","method":"initialized","params":{}}"#);
         assert!(no_output.is_none());
     }
+
+    #[test]
+    fn project_resources_are_listed_read_and_subscribable() {
+        use crate::agent_log::agent_log_dir;
+        use crate::context_gen::MAIN_CONTEXT_PATH;
+
+        let root = std::env::temp_dir().join(format!(
+            "othala-mcp-resources-{}",
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        std::fs::create_dir_all(root.join(".othala/context")).expect("create context dir");
+        std::fs::write(root.join(MAIN_CONTEXT_PATH), "# Context\n").expect("write MAIN.md");
+        let log_path = agent_log_dir(&root, &TaskId::new("T-MCP")).join("latest.log");
+        std::fs::create_dir_all(log_path.parent().expect("log dir")).expect("create log dir");
+        std::fs::write(&log_path, "x".repeat(100)).expect("write log");
+
+        let mut server = McpServer::new();
+        server.register_project_resources(&root, Rc::new(mk_service()), 40);
+        init_server(&mut server);
+
+        let listed = rpc(&mut server, "resources/list", json!({}))
+            .result
+            .expect("resource list");
+        let uris: Vec<&str> = listed["resources"]
+            .as_array()
+            .expect("resources array")
+            .iter()
+            .map(|resource| resource["uri"].as_str().expect("uri"))
+            .collect();
+        assert_eq!(
+            uris,
+            vec![
+                "othala://context/main",
+                "othala://graph/dependencies",
+                "othala://tasks/T-MCP/log",
+            ]
+        );
+        assert_eq!(listed["resources"][2]["mimeType"], "text/plain");
+
+        let read = |server: &mut McpServer, uri: &str| {
+            let result = rpc(server, "resources/read", json!({ "uri": uri }))
+                .result
+                .expect("resource contents");
+            result["contents"][0]["text"]
+                .as_str()
+                .expect("text")
+                .to_string()
+        };
+        assert_eq!(read(&mut server, "othala://context/main"), "# Context\n");
+        assert_eq!(
+            read(&mut server, "othala://tasks/T-MCP/log"),
+            format!(
+                "{}\n[... 60 bytes truncated ...]\n{}",
+                "x".repeat(20),
+                "x".repeat(20)
+            )
+        );
+        let graph: serde_json::Value =
+            serde_json::from_str(&read(&mut server, "othala://graph/dependencies"))
+                .expect("graph json");
+        assert_eq!(graph["nodes"][0]["id"], "T-MCP");
+        assert_eq!(graph["order"], json!(["T-MCP"]));
+
+        let missing = rpc(
+            &mut server,
+            "resources/read",
+            json!({ "uri": "othala://nope" }),
+        );
+        assert_eq!(
+            missing.error.expect("missing resource").code,
+            RESOURCE_NOT_FOUND
+        );
+        let graph_sub = rpc(
+            &mut server,
+            "resources/subscribe",
+            json!({ "uri": "othala://graph/dependencies" }),
+        );
+        assert_eq!(
+            graph_sub.error.expect("not file backed").code,
+            INVALID_PARAMS
+        );
+
+        let log_uri = json!({ "uri": "othala://tasks/T-MCP/log" });
+        let subscribed = rpc(&mut server, "resources/subscribe", log_uri.clone());
+        assert!(subscribed.error.is_none());
+        assert!(server.poll_resource_updates().is_empty());
+        std::fs::write(&log_path, "y".repeat(10)).expect("rewrite log");
+        let updates = server.poll_resource_updates();
+        assert_eq!(updates.len(), 1);
+        let update: serde_json::Value =
+            serde_json::from_str(&updates[0]).expect("notification json");
+        assert_eq!(update["method"], "notifications/resources/updated");
+        assert_eq!(update["params"]["uri"], "othala://tasks/T-MCP/log");
+
+        let unsubscribed = rpc(&mut server, "resources/unsubscribe", log_uri);
+        assert!(unsubscribed.error.is_none());
+        std::fs::write(&log_path, "z".repeat(5)).expect("rewrite log again");
+        assert!(server.poll_resource_updates().is_empty());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
 }
@@ -1,9 +1,22 @@
+use orch_core::types::{Task, TaskId};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 use thiserror::Error;
 
+use crate::agent_log::agent_log_dir;
+use crate::context_gen::MAIN_CONTEXT_PATH;
+use crate::dependency_graph::build_dependency_graph;
+use crate::file_watcher::FileChangeEvent;
+use crate::OrchdService;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
 pub struct ResourceDefinition {
     pub uri: String,
     pub name: String,
@@ -12,6 +25,7 @@ pub struct ResourceDefinition {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
 pub struct ResourceContent {
     pub uri: String,
     pub mime_type: Option<String>,
@@ -20,6 +34,7 @@ pub struct ResourceContent {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
 pub struct ResourceTemplate {
     pub uri_template: String,
     pub name: String,
@@ -27,12 +42,43 @@ pub struct ResourceTemplate {
     pub mime_type: Option<String>,
 }
 
+/// Page size used when a `resources/list` request does not set `limit`.
+pub const DEFAULT_RESOURCE_PAGE_SIZE: usize = 50;
+/// Upper bound on `limit` so one request cannot ask for everything.
+pub const MAX_RESOURCE_PAGE_SIZE: usize = 200;
+/// Default cap on the text returned for one agent log resource.
+pub const DEFAULT_LOG_RESOURCE_BYTES: usize = 64 * 1024;
+
+pub const MAIN_CONTEXT_URI: &str = "othala://context/main";
+pub const DEPENDENCY_GRAPH_URI: &str = "othala://graph/dependencies";
+pub const TASK_LOG_URI_TEMPLATE: &str = "othala://tasks/{id}/log";
+
+const MARKDOWN_MIME: &str = "text/markdown";
+const TEXT_MIME: &str = "text/plain";
+const JSON_MIME: &str = "application/json";
+
+/// One page of `resources/list`, shaped like the MCP result.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourcePage {
+    pub resources: Vec<ResourceDefinition>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
 type ResourceHandler = dyn Fn(&str) -> Result<ResourceContent, ResourceError>;
+type ResourceLister = dyn Fn() -> Vec<ResourceDefinition>;
+/// The file a resource URI is read from, if it is backed by one.
+type SourcePath = dyn Fn(&str) -> Option<PathBuf>;
 
 pub struct ResourceRegistry {
     resources: Vec<ResourceDefinition>,
     templates: Vec<ResourceTemplate>,
     handlers: HashMap<String, Box<ResourceHandler>>,
+    listers: Vec<Box<ResourceLister>>,
+    source_paths: HashMap<String, Box<SourcePath>>,
+    /// Subscribed URIs and the file each one is read from.
+    subscriptions: BTreeMap<String, PathBuf>,
 }
 
 impl Default for ResourceRegistry {
@@ -47,6 +93,9 @@ impl ResourceRegistry {
             resources: Vec::new(),
             templates: Vec::new(),
             handlers: HashMap::new(),
+            listers: Vec::new(),
+            source_paths: HashMap::new(),
+            subscriptions: BTreeMap::new(),
         }
     }
 
@@ -71,10 +120,52 @@ impl ResourceRegistry {
         self.templates.push(template);
     }
 
+    /// Add resources that are only known at list time, such as one per
+    /// task. Reads still go through a registered resource or template.
+    pub fn register_lister(&mut self, lister: Box<ResourceLister>) {
+        self.listers.push(lister);
+    }
+
+    /// Declare the file behind the resource or template registered under
+    /// `key`, which makes its URIs subscribable.
+    pub fn register_source_path(&mut self, key: &str, source: Box<SourcePath>) {
+        self.source_paths.insert(key.to_string(), source);
+    }
+
     pub fn list_resources(&self) -> &[ResourceDefinition] {
         &self.resources
     }
 
+    /// Resources ordered by URI, starting at the opaque `cursor` returned by
+    /// the previous page. `next_cursor` is set while more resources remain.
+    pub fn list_resources_page(
+        &self,
+        cursor: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<ResourcePage, ResourceError> {
+        let offset = cursor.map(decode_cursor).transpose()?.unwrap_or(0);
+        let limit = limit
+            .unwrap_or(DEFAULT_RESOURCE_PAGE_SIZE)
+            .clamp(1, MAX_RESOURCE_PAGE_SIZE);
+
+        let mut sorted: Vec<ResourceDefinition> = self.resources.clone();
+        for lister in &self.listers {
+            for resource in lister() {
+                if !sorted.iter().any(|existing| existing.uri == resource.uri) {
+                    sorted.push(resource);
+                }
+            }
+        }
+        sorted.sort_by(|left, right| left.uri.cmp(&right.uri));
+
+        let end = offset.saturating_add(limit).min(sorted.len());
+        let resources = sorted.get(offset..end).unwrap_or_default().to_vec();
+        Ok(ResourcePage {
+            resources,
+            next_cursor: (end < sorted.len()).then(|| encode_cursor(end)),
+        })
+    }
+
     pub fn list_templates(&self) -> &[ResourceTemplate] {
         &self.templates
     }
@@ -105,6 +196,134 @@ impl ResourceRegistry {
         Err(ResourceError::NotFound(uri.to_string()))
     }
 
+    /// The file `uri` is read from, for resources registered with one.
+    pub fn source_path(&self, uri: &str) -> Option<PathBuf> {
+        if let Some(source) = self.source_paths.get(uri) {
+            return source(uri);
+        }
+        self.templates
+            .iter()
+            .filter(|template| uri_matches_template(&template.uri_template, uri))
+            .find_map(|template| {
+                self.source_paths
+                    .get(&template.uri_template)
+                    .and_then(|source| source(uri))
+            })
+    }
+
+    /// Report changes to `uri`'s backing file from `updated_resources`.
+    pub fn subscribe(&mut self, uri: &str) -> Result<(), ResourceError> {
+        if !uri.starts_with("othala://") {
+            return Err(ResourceError::InvalidUri(uri.to_string()));
+        }
+        let path = self
+            .source_path(uri)
+            .ok_or_else(|| ResourceError::NotSubscribable(uri.to_string()))?;
+        self.subscriptions.insert(uri.to_string(), path);
+        Ok(())
+    }
+
+    /// Stop reporting changes to `uri`; false if it was not subscribed.
+    pub fn unsubscribe(&mut self, uri: &str) -> bool {
+        self.subscriptions.remove(uri).is_some()
+    }
+
+    /// Subscribed URIs whose backing file changed in `events`, in URI order.
+    pub fn updated_resources(&self, events: &[FileChangeEvent]) -> Vec<String> {
+        self.subscriptions
+            .iter()
+            .filter(|(_, path)| events.iter().any(|event| &event.path == *path))
+            .map(|(uri, _)| uri.clone())
+            .collect()
+    }
+
+    /// Resources agents read mid-run: the generated `MAIN.md` context, each
+    /// task's latest agent log and the task dependency graph. Everything is
+    /// read from disk or `service` when requested; logs larger than
+    /// `max_log_bytes` keep their head and tail around a truncation marker.
+    pub fn register_project_resources(
+        &mut self,
+        repo_root: &Path,
+        service: Rc<OrchdService>,
+        max_log_bytes: usize,
+    ) {
+        let main_path = repo_root.join(MAIN_CONTEXT_PATH);
+        let context_path = main_path.clone();
+        self.register_resource(
+            ResourceDefinition {
+                uri: MAIN_CONTEXT_URI.to_string(),
+                name: "context-main".to_string(),
+                description: Some(format!(
+                    "Generated repository context ({MAIN_CONTEXT_PATH})"
+                )),
+                mime_type: Some(MARKDOWN_MIME.to_string()),
+            },
+            Box::new(move |uri| {
+                let text = std::fs::read_to_string(&context_path)
+                    .map_err(|err| read_failed(&context_path, err))?;
+                Ok(text_resource(uri, MARKDOWN_MIME, text))
+            }),
+        );
+        self.register_source_path(MAIN_CONTEXT_URI, Box::new(move |_| Some(main_path.clone())));
+
+        let log_root = repo_root.to_path_buf();
+        let log_service = Rc::clone(&service);
+        self.register_template(
+            ResourceTemplate {
+                uri_template: TASK_LOG_URI_TEMPLATE.to_string(),
+                name: "task-log".to_string(),
+                description: Some("Latest agent log for a task".to_string()),
+                mime_type: Some(TEXT_MIME.to_string()),
+            },
+            Box::new(move |uri| {
+                let path = task_log_path(&log_root, &log_service, uri)
+                    .ok_or_else(|| ResourceError::NotFound(uri.to_string()))?;
+                let text =
+                    read_capped(&path, max_log_bytes).map_err(|err| read_failed(&path, err))?;
+                Ok(text_resource(uri, TEXT_MIME, text))
+            }),
+        );
+        let source_root = repo_root.to_path_buf();
+        let source_service = Rc::clone(&service);
+        self.register_source_path(
+            TASK_LOG_URI_TEMPLATE,
+            Box::new(move |uri| task_log_path(&source_root, &source_service, uri)),
+        );
+        let list_root = repo_root.to_path_buf();
+        let list_service = Rc::clone(&service);
+        self.register_lister(Box::new(move || {
+            list_service
+                .list_tasks()
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|task| latest_log_path(&list_root, &task.id).exists())
+                .map(|task| ResourceDefinition {
+                    uri: task_log_uri(&task.id),
+                    name: format!("{}-log", task.id.0),
+                    description: Some(format!("Latest agent log for {}", task.title)),
+                    mime_type: Some(TEXT_MIME.to_string()),
+                })
+                .collect()
+        }));
+
+        self.register_resource(
+            ResourceDefinition {
+                uri: DEPENDENCY_GRAPH_URI.to_string(),
+                name: "dependency-graph".to_string(),
+                description: Some("Task dependency graph with a topological order".to_string()),
+                mime_type: Some(JSON_MIME.to_string()),
+            },
+            Box::new(move |uri| {
+                let tasks = service
+                    .list_tasks()
+                    .map_err(|err| ResourceError::ReadFailed(err.to_string()))?;
+                let text = serde_json::to_string_pretty(&dependency_graph_json(&tasks))
+                    .map_err(|err| ResourceError::ReadFailed(err.to_string()))?;
+                Ok(text_resource(uri, JSON_MIME, text))
+            }),
+        );
+    }
+
     pub fn register_builtin_resources(&mut self) {
         self.register_resource(
             ResourceDefinition {
@@ -493,6 +712,146 @@ pub enum ResourceError {
     InvalidArguments(String),
     #[error("prompt not found: {0}")]
     PromptNotFound(String),
+    #[error("invalid cursor: {0}")]
+    InvalidCursor(String),
+    #[error("resource does not support subscriptions: {0}")]
+    NotSubscribable(String),
+}
+
+pub fn task_log_uri(task_id: &TaskId) -> String {
+    format!("othala://tasks/{}/log", task_id.0)
+}
+
+fn latest_log_path(repo_root: &Path, task_id: &TaskId) -> PathBuf {
+    agent_log_dir(repo_root, task_id).join("latest.log")
+}
+
+/// Latest log path for the task named in a `othala://tasks/{id}/log` URI.
+/// Unknown tasks resolve to `None`, so the id never reaches the filesystem
+/// unchecked.
+fn task_log_path(repo_root: &Path, service: &OrchdService, uri: &str) -> Option<PathBuf> {
+    let id = uri.strip_prefix("othala://tasks/")?.strip_suffix("/log")?;
+    let task = service.task(&TaskId::new(id)).ok().flatten()?;
+    Some(latest_log_path(repo_root, &task.id))
+}
+
+fn text_resource(uri: &str, mime_type: &str, text: String) -> ResourceContent {
+    ResourceContent {
+        uri: uri.to_string(),
+        mime_type: Some(mime_type.to_string()),
+        text: Some(text),
+        blob: None,
+    }
+}
+
+fn read_failed(path: &Path, err: io::Error) -> ResourceError {
+    ResourceError::ReadFailed(format!("{}: {err}", path.display()))
+}
+
+/// Nodes, `from -> to` dependency edges and either a topological `order` or
+/// the `cycle` that prevents one.
+fn dependency_graph_json(tasks: &[Task]) -> serde_json::Value {
+    let graph = build_dependency_graph(tasks);
+    let mut nodes: Vec<&Task> = tasks.iter().collect();
+    nodes.sort_by(|left, right| left.id.0.cmp(&right.id.0));
+    let mut edges: Vec<(&str, &str)> = graph
+        .children_by_parent
+        .iter()
+        .flat_map(|(parent, children)| {
+            children
+                .iter()
+                .map(move |child| (parent.0.as_str(), child.0.as_str()))
+        })
+        .collect();
+    edges.sort();
+    let (order, cycle) = match graph.topo_order() {
+        Ok(order) => (Some(order), None),
+        Err(err) => (None, Some(err.cycle)),
+    };
+    let ids = |ids: Option<Vec<TaskId>>| {
+        ids.map(|ids| ids.into_iter().map(|id| id.0).collect::<Vec<_>>())
+    };
+
+    json!({
+        "nodes": nodes
+            .iter()
+            .map(|task| json!({
+                "id": task.id.0,
+                "title": task.title,
+                "state": task.state.to_string(),
+            }))
+            .collect::<Vec<_>>(),
+        "edges": edges
+            .iter()
+            .map(|(from, to)| json!({ "from": from, "to": to }))
+            .collect::<Vec<_>>(),
+        "order": ids(order),
+        "cycle": ids(cycle),
+    })
+}
+
+/// Read `path` as text. A file over `max_bytes` keeps its first and last
+/// `max_bytes / 2` bytes, joined by a marker giving the number cut; only
+/// those ends are read.
+fn read_capped(path: &Path, max_bytes: usize) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    if len <= max_bytes as u64 {
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+        return Ok(String::from_utf8_lossy(&bytes).into_owned());
+    }
+
+    let mut head = vec![0; max_bytes / 2];
+    file.read_exact(&mut head)?;
+    let mut tail = vec![0; max_bytes - head.len()];
+    file.seek(SeekFrom::End(-(tail.len() as i64)))?;
+    file.read_exact(&mut tail)?;
+
+    // Cut at character boundaries so neither end starts or ends mid-char.
+    let head = match std::str::from_utf8(&head) {
+        Err(err) if err.error_len().is_none() => &head[..err.valid_up_to()],
+        _ => &head[..],
+    };
+    let continuation = tail
+        .iter()
+        .take(3)
+        .take_while(|byte| **byte & 0xC0 == 0x80)
+        .count();
+    let tail = &tail[continuation..];
+    let omitted = len - (head.len() + tail.len()) as u64;
+    Ok(format!(
+        "{}\n[... {omitted} bytes truncated ...]\n{}",
+        String::from_utf8_lossy(head),
+        String::from_utf8_lossy(tail)
+    ))
+}
+
+fn encode_cursor(offset: usize) -> String {
+    crate::editor::encode_base64(offset.to_string().as_bytes())
+}
+
+fn decode_cursor(cursor: &str) -> Result<usize, ResourceError> {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let invalid = || ResourceError::InvalidCursor(cursor.to_string());
+
+    let mut bits: u32 = 0;
+    let mut bit_count = 0;
+    let mut decoded = Vec::new();
+    for byte in cursor.trim_end_matches('=').bytes() {
+        let value = TABLE.iter().position(|c| *c == byte).ok_or_else(invalid)? as u32;
+        bits = (bits << 6) | value;
+        bit_count += 6;
+        if bit_count >= 8 {
+            bit_count -= 8;
+            decoded.push((bits >> bit_count) as u8);
+            bits &= (1 << bit_count) - 1;
+        }
+    }
+    std::str::from_utf8(&decoded)
+        .ok()
+        .and_then(|text| text.parse().ok())
+        .ok_or_else(invalid)
 }
 
 fn uri_matches_template(template: &str, candidate: &str) -> bool {
@@ -954,4 +1313,123 @@ mod tests {
             "prompt not found: y"
         );
     }
+
+    #[test]
+    fn list_resources_page_walks_every_resource_once_in_uri_order() {
+        let mut registry = ResourceRegistry::new();
+        for index in (0..25).rev() {
+            let uri = format!("othala://context/{index:02}");
+            let handler_uri = uri.clone();
+            registry.register_resource(
+                ResourceDefinition {
+                    uri,
+                    name: format!("context {index}"),
+                    description: None,
+                    mime_type: Some("text/markdown".to_string()),
+                },
+                Box::new(move |_| Ok(text_content(&handler_uri, "ctx"))),
+            );
+        }
+
+        let mut seen = Vec::new();
+        let mut page_sizes = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let page = registry
+                .list_resources_page(cursor.as_deref(), Some(10))
+                .expect("page");
+            page_sizes.push(page.resources.len());
+            seen.extend(page.resources.into_iter().map(|resource| resource.uri));
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        assert_eq!(page_sizes, vec![10, 10, 5]);
+        let expected = (0..25)
+            .map(|index| format!("othala://context/{index:02}"))
+            .collect::<Vec<_>>();
+        assert_eq!(seen, expected);
+    }
+
+    #[test]
+    fn list_resources_page_rejects_malformed_cursor() {
+        let registry = ResourceRegistry::new();
+        assert_eq!(
+            registry.list_resources_page(Some("not a cursor!"), None),
+            Err(ResourceError::InvalidCursor("not a cursor!".to_string()))
+        );
+        let page = registry.list_resources_page(None, None).expect("empty page");
+        assert!(page.resources.is_empty());
+        assert_eq!(
+            serde_json::to_value(&page).expect("serialize"),
+            serde_json::json!({ "resources": [] })
+        );
+    }
+
+    #[test]
+    fn read_capped_keeps_head_and_tail_on_char_boundaries() {
+        let dir = std::env::temp_dir().join(format!(
+            "othala-mcp-resources-{}",
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        let path = dir.join("latest.log");
+        std::fs::write(&path, "é".repeat(10)).expect("write log");
+
+        assert_eq!(read_capped(&path, 20).expect("read whole"), "é".repeat(10));
+        assert_eq!(
+            read_capped(&path, 7).expect("read capped"),
+            "é\n[... 14 bytes truncated ...]\néé"
+        );
+        assert!(read_capped(&dir.join("missing.log"), 7).is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn subscriptions_report_changes_to_their_source_files() {
+        let mut registry = ResourceRegistry::new();
+        registry.register_template(
+            ResourceTemplate {
+                uri_template: "othala://files/{name}".to_string(),
+                name: "file".to_string(),
+                description: None,
+                mime_type: None,
+            },
+            Box::new(|uri| Ok(text_content(uri, "file"))),
+        );
+        registry.register_source_path(
+            "othala://files/{name}",
+            Box::new(|uri| {
+                let name = uri.strip_prefix("othala://files/")?;
+                Some(PathBuf::from("/watched").join(name))
+            }),
+        );
+        registry.register_builtin_resources();
+
+        registry.subscribe("othala://files/a").expect("subscribe a");
+        registry.subscribe("othala://files/b").expect("subscribe b");
+        assert_eq!(
+            registry.subscribe("othala://tasks"),
+            Err(ResourceError::NotSubscribable("othala://tasks".to_string()))
+        );
+        assert!(registry.unsubscribe("othala://files/b"));
+
+        let changed = |path: &str| FileChangeEvent {
+            path: PathBuf::from(path),
+            kind: crate::file_watcher::ChangeKind::Modified,
+            timestamp: std::time::SystemTime::now(),
+        };
+        let events = vec![
+            changed("/watched/a"),
+            changed("/watched/b"),
+            changed("/other"),
+        ];
+        assert_eq!(
+            registry.updated_resources(&events),
+            vec!["othala://files/a"]
+        );
+    }
 }
@@ -1,20 +1,36 @@
+use crate::env_inject::EnvInjector;
 use crate::mcp::{
     INTERNAL_ERROR, JsonRpcError, JsonRpcRequest, JsonRpcResponse, McpServer, PARSE_ERROR,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::hash_map::RandomState;
 use std::collections::HashMap;
 use std::fmt::{self, Display};
+use std::fs::{self, File, OpenOptions};
+use std::hash::BuildHasher;
 use std::io::{self, Read, Write};
 use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 const DEFAULT_CORS_ORIGIN: &str = "*";
 const DEFAULT_MAX_REQUEST_SIZE_BYTES: usize = 1024 * 1024;
 const DEFAULT_TIMEOUT_MS: u64 = 30_000;
 const HTTP_HEADER_TERMINATOR: &[u8; 4] = b"\r\n\r\n";
+const DEFAULT_HEALTH_PATH: &str = "/health";
+const DEFAULT_READY_PATH: &str = "/ready";
+/// How often an idle `serve` loop checks for shutdown and resource changes.
+const SERVE_POLL_INTERVAL: Duration = Duration::from_millis(200);
+/// Comment line sent to `/sse` listeners to keep idle connections open.
+const SSE_KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+/// Carries the MCP session ID; minted on a request without one and echoed
+/// back so later requests keep the grant negotiated on `initialize`.
+const SESSION_HEADER: &str = "Mcp-Session-Id";
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TransportKind {
@@ -40,6 +56,29 @@ pub struct TransportConfig {
     pub max_request_size_bytes: usize,
     pub read_timeout_ms: u64,
     pub write_timeout_ms: u64,
+    /// Liveness probe path: answers 200 whenever the process is serving.
+    #[serde(default = "default_health_path")]
+    pub health_path: String,
+    /// Readiness probe path: answers 503 until initialization completes.
+    #[serde(default = "default_ready_path")]
+    pub ready_path: String,
+    /// Sent as `Access-Control-Allow-Methods`.
+    #[serde(default = "default_cors_methods")]
+    pub cors_methods: Vec<String>,
+    /// Sent as `Access-Control-Allow-Headers`.
+    #[serde(default = "default_cors_headers")]
+    pub cors_headers: Vec<String>,
+    /// When set, only these MCP tools are listed or callable.
+    #[serde(default)]
+    pub allowed_tools: Option<Vec<String>>,
+    /// When set, clients must send it as `auth.secret` on `initialize`;
+    /// sessions that don't are limited to read-only tools.
+    #[serde(default)]
+    pub shared_secret: Option<String>,
+    /// When set, each JSON-RPC request and response is appended here as a
+    /// JSON line, with secret-looking fields redacted.
+    #[serde(default)]
+    pub trace_path: Option<PathBuf>,
 }
 
 impl Default for TransportConfig {
@@ -50,10 +89,33 @@ impl Default for TransportConfig {
             max_request_size_bytes: DEFAULT_MAX_REQUEST_SIZE_BYTES,
             read_timeout_ms: DEFAULT_TIMEOUT_MS,
             write_timeout_ms: DEFAULT_TIMEOUT_MS,
+            health_path: default_health_path(),
+            ready_path: default_ready_path(),
+            cors_methods: default_cors_methods(),
+            cors_headers: default_cors_headers(),
+            allowed_tools: None,
+            shared_secret: None,
+            trace_path: None,
         }
     }
 }
 
+fn default_health_path() -> String {
+    DEFAULT_HEALTH_PATH.to_string()
+}
+
+fn default_ready_path() -> String {
+    DEFAULT_READY_PATH.to_string()
+}
+
+fn default_cors_methods() -> Vec<String> {
+    CorsConfig::default().allowed_methods
+}
+
+fn default_cors_headers() -> Vec<String> {
+    CorsConfig::default().allowed_headers
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct HttpRequest {
     pub method: String,
@@ -133,8 +195,12 @@ impl SseStream {
     }
 
     pub fn broadcast(&mut self, event: &SseEvent) -> Vec<usize> {
-        let payload = event.to_bytes();
-        Self::broadcast_to_writers(self.listeners.as_mut_slice(), &payload)
+        self.broadcast_bytes(&event.to_bytes())
+    }
+
+    /// Write `payload` as-is to every listener, e.g. an SSE comment line.
+    pub fn broadcast_bytes(&mut self, payload: &[u8]) -> Vec<usize> {
+        Self::broadcast_to_writers(self.listeners.as_mut_slice(), payload)
     }
 
     fn broadcast_to_writers<W: Write>(writers: &mut [W], payload: &[u8]) -> Vec<usize> {
@@ -222,7 +288,11 @@ impl Default for CorsConfig {
         Self {
             allowed_origins: vec![DEFAULT_CORS_ORIGIN.to_string()],
             allowed_methods: vec!["POST".to_string(), "GET".to_string(), "OPTIONS".to_string()],
-            allowed_headers: vec!["Content-Type".to_string(), "Authorization".to_string()],
+            allowed_headers: vec![
+                "Content-Type".to_string(),
+                "Authorization".to_string(),
+                SESSION_HEADER.to_string(),
+            ],
         }
     }
 }
@@ -260,12 +330,84 @@ impl CorsConfig {
     }
 }
 
+/// JSONL trace of JSON-RPC traffic. Values under keys matching the env
+/// secret markers are replaced with `***` before they are written, and
+/// session IDs, which carry the `initialize` grant, are logged only as a
+/// keyed hash that correlates lines within one run.
+struct RpcTrace {
+    file: Mutex<File>,
+    redactor: EnvInjector,
+    session_keys: RandomState,
+}
+
+impl RpcTrace {
+    fn open(path: &Path) -> io::Result<Self> {
+        if let Some(parent) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let mut options = OpenOptions::new();
+        options.create(true).append(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let file = options.open(path)?;
+        Ok(Self {
+            file: Mutex::new(file),
+            redactor: EnvInjector::secret_redactor(),
+            session_keys: RandomState::new(),
+        })
+    }
+
+    /// Append one message. Bodies that are not JSON are recorded by size only.
+    fn record(&self, direction: &str, session: &str, body: &str) {
+        let message = match serde_json::from_str::<serde_json::Value>(body) {
+            Ok(mut value) => {
+                self.redact(&mut value);
+                value
+            }
+            Err(_) => json!({ "unparsed_bytes": body.len() }),
+        };
+        let line = json!({
+            "at": chrono::Utc::now().to_rfc3339(),
+            "direction": direction,
+            "session": self.session_tag(session),
+            "message": message,
+        });
+        if let Ok(mut file) = self.file.lock() {
+            let _ = writeln!(file, "{line}");
+        }
+    }
+
+    fn session_tag(&self, session: &str) -> String {
+        if session.is_empty() {
+            return String::new();
+        }
+        format!("{:016x}", self.session_keys.hash_one(session))
+    }
+
+    fn redact(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(fields) => {
+                for (key, field) in fields.iter_mut() {
+                    if self.redactor.should_redact_key(key) {
+                        *field = json!("***");
+                    } else {
+                        self.redact(field);
+                    }
+                }
+            }
+            serde_json::Value::Array(items) => items.iter_mut().for_each(|item| self.redact(item)),
+            _ => {}
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Route {
     Rpc,
     Sse,
     Options,
     Health,
+    Ready,
     NotFound,
 }
 
@@ -274,25 +416,61 @@ pub struct HttpTransport {
     cors: CorsConfig,
     server: Mutex<McpServer>,
     sse_stream: Mutex<SseStream>,
+    ready: AtomicBool,
+    started_at: Instant,
+    session_keys: RandomState,
+    sessions_minted: AtomicU64,
+    trace: Option<RpcTrace>,
 }
 
 impl HttpTransport {
+    /// Create a transport with its tools registered, ready to serve.
     pub fn new(config: &TransportConfig) -> Result<Self, TransportError> {
-        let mut server = McpServer::new();
-        server.register_builtin_tools();
-
+        let transport = Self::new_uninitialized(config)?;
+        transport.initialize();
+        Ok(transport)
+    }
+
+    /// Create a transport that reports not-ready until [`Self::initialize`].
+    /// Fails when `config.trace_path` can't be opened for appending.
+    pub fn new_uninitialized(config: &TransportConfig) -> Result<Self, TransportError> {
+        let trace = config
+            .trace_path
+            .as_deref()
+            .map(RpcTrace::open)
+            .transpose()?;
         Ok(Self {
             config: config.clone(),
             cors: CorsConfig {
                 allowed_origins: config.cors_origins.clone(),
-                ..CorsConfig::default()
+                allowed_methods: config.cors_methods.clone(),
+                allowed_headers: config.cors_headers.clone(),
             },
-            server: Mutex::new(server),
+            server: Mutex::new(McpServer::new()),
             sse_stream: Mutex::new(SseStream::new()),
+            ready: AtomicBool::new(false),
+            started_at: Instant::now(),
+            session_keys: RandomState::new(),
+            sessions_minted: AtomicU64::new(0),
+            trace,
         })
     }
 
-    pub fn bind(&self) -> Result<(), TransportError> {
+    /// Register the builtin tools and start reporting ready.
+    pub fn initialize(&self) {
+        if let Ok(mut server) = self.server.lock() {
+            server.register_builtin_tools();
+        }
+        self.ready.store(true, Ordering::Release);
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::Acquire)
+    }
+
+    /// Bind the configured address and serve `server` until the process
+    /// exits.
+    pub fn serve(&self, server: McpServer) -> Result<(), TransportError> {
         let bind_target = match &self.config.kind {
             TransportKind::Stdio => {
                 return Err(TransportError::InvalidHttpRequest(
@@ -305,16 +483,97 @@ impl HttpTransport {
         };
 
         let listener = TcpListener::bind(&bind_target)?;
-        for stream in listener.incoming() {
-            match stream {
-                Ok(stream) => {
-                    if let Err(err) = self.handle_connection(stream) {
-                        eprintln!("mcp transport connection error: {err}");
+        self.serve_listener(listener, server, &AtomicBool::new(false))
+    }
+
+    /// Serve `server` on `listener` until `shutdown` is set.
+    ///
+    /// Each connection is read on its own thread and queued; requests are
+    /// dispatched on this thread in arrival order, since the server is not
+    /// thread-safe. Between requests, subscribed resource changes are pushed
+    /// to `/sse` listeners along with periodic keep-alives.
+    pub fn serve_listener(
+        &self,
+        listener: TcpListener,
+        server: McpServer,
+        shutdown: &AtomicBool,
+    ) -> Result<(), TransportError> {
+        let mut server = server;
+        server.set_allowed_tools(self.config.allowed_tools.clone());
+        server.set_shared_secret(self.config.shared_secret.clone());
+        if let Ok(mut current) = self.server.lock() {
+            *current = server;
+        }
+        self.ready.store(true, Ordering::Release);
+        listener.set_nonblocking(true)?;
+
+        let (queue_tx, queue) = mpsc::channel();
+        let max_request_size = self.config.max_request_size_bytes;
+        let read_timeout = Duration::from_millis(self.config.read_timeout_ms);
+        let write_timeout = Duration::from_millis(self.config.write_timeout_ms);
+
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                while !shutdown.load(Ordering::Acquire) {
+                    let stream = match listener.accept() {
+                        Ok((stream, _)) => stream,
+                        Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                            thread::sleep(SERVE_POLL_INTERVAL);
+                            continue;
+                        }
+                        Err(err) => {
+                            eprintln!("mcp transport accept error: {err}");
+                            continue;
+                        }
+                    };
+                    let queue_tx = queue_tx.clone();
+                    scope.spawn(move || {
+                        match read_queued_request(
+                            stream,
+                            max_request_size,
+                            read_timeout,
+                            write_timeout,
+                        ) {
+                            Ok(queued) => {
+                                let _ = queue_tx.send(queued);
+                            }
+                            Err(err) => eprintln!("mcp transport connection error: {err}"),
+                        }
+                    });
+                }
+            });
+
+            let mut last_keep_alive = Instant::now();
+            while !shutdown.load(Ordering::Acquire) {
+                match queue.recv_timeout(SERVE_POLL_INTERVAL) {
+                    Ok((request, stream)) => {
+                        if let Err(err) = self.dispatch_queued(request, stream) {
+                            eprintln!("mcp transport connection error: {err}");
+                        }
                     }
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+
+                let updates = self
+                    .server
+                    .lock()
+                    .map(|mut server| server.poll_resource_updates())
+                    .unwrap_or_default();
+                for data in updates {
+                    self.broadcast_event(&SseEvent {
+                        event: Some("message".to_string()),
+                        data,
+                        id: None,
+                        retry: None,
+                    });
+                }
+                if last_keep_alive.elapsed() >= SSE_KEEP_ALIVE_INTERVAL {
+                    self.broadcast_bytes(b": keep-alive\n\n");
+                    last_keep_alive = Instant::now();
                 }
-                Err(err) => return Err(TransportError::Io(err)),
             }
-        }
+        });
 
         Ok(())
     }
@@ -323,11 +582,24 @@ impl HttpTransport {
         stream.set_read_timeout(Some(Duration::from_millis(self.config.read_timeout_ms)))?;
         stream.set_write_timeout(Some(Duration::from_millis(self.config.write_timeout_ms)))?;
 
-        let raw_request = self.read_http_request(&mut stream)?;
+        let raw_request = read_http_request(&mut stream, self.config.max_request_size_bytes)?;
         let request = Self::parse_http_request(&raw_request)?;
         self.dispatch_request(request, &mut stream)
     }
 
+    /// Answer a request queued by [`Self::serve_listener`]. `/sse` streams
+    /// are registered as listeners and left open for the serve loop.
+    fn dispatch_queued(
+        &self,
+        request: HttpRequest,
+        mut stream: TcpStream,
+    ) -> Result<(), TransportError> {
+        if let Ok(Route::Sse) = Self::route_request(&self.config, &request) {
+            return self.open_sse(&mut stream);
+        }
+        self.dispatch_request(request, &mut stream)
+    }
+
     pub fn parse_http_request(raw: &[u8]) -> Result<HttpRequest, TransportError> {
         let header_end = raw
             .windows(HTTP_HEADER_TERMINATOR.len())
@@ -442,6 +714,24 @@ impl HttpTransport {
     }
 
     pub fn handle_post_rpc(&self, body: &str) -> String {
+        self.handle_session_rpc("", body)
+    }
+
+    /// Answer a `/rpc` body within MCP session `session`, tracing both
+    /// sides when `trace_path` is configured.
+    pub fn handle_session_rpc(&self, session: &str, body: &str) -> String {
+        let Some(trace) = &self.trace else {
+            return self.answer_session_rpc(session, body);
+        };
+        trace.record("request", session, body);
+        let response = self.answer_session_rpc(session, body);
+        if !response.is_empty() {
+            trace.record("response", session, &response);
+        }
+        response
+    }
+
+    fn answer_session_rpc(&self, session: &str, body: &str) -> String {
         let request = match serde_json::from_str::<JsonRpcRequest>(body) {
             Ok(request) => request,
             Err(err) => {
@@ -461,7 +751,7 @@ impl HttpTransport {
 
         let is_notification = request.id.is_none();
         let response = match self.server.lock() {
-            Ok(mut server) => server.handle_request(&request),
+            Ok(mut server) => server.handle_session_request(session, &request),
             Err(err) => JsonRpcResponse {
                 jsonrpc: "2.0".to_string(),
                 id: request.id.clone(),
@@ -482,44 +772,51 @@ impl HttpTransport {
     }
 
     pub fn handle_get_sse(&self, stream: &mut TcpStream) -> Result<(), TransportError> {
-        let mut headers = vec![
-            ("Content-Type", "text/event-stream"),
-            ("Cache-Control", "no-cache"),
-            ("Connection", "keep-alive"),
-        ];
-        let cors_headers = self.cors.cors_headers();
-        let mut owned_pairs = Vec::new();
-        for (name, value) in cors_headers {
-            owned_pairs.push((name, value));
-        }
-        for (name, value) in &owned_pairs {
-            headers.push((name.as_str(), value.as_str()));
+        self.open_sse(stream)?;
+        loop {
+            if stream.write_all(b": keep-alive\n\n").is_err() {
+                return Err(TransportError::ConnectionClosed);
+            }
+            stream.flush()?;
+            thread::sleep(Duration::from_secs(15));
         }
+    }
 
-        let response = Self::build_http_response(200, &headers, "");
-        stream.write_all(&response)?;
+    /// Send the event-stream response head and register `stream` for
+    /// broadcasts. The head carries no `Content-Length`, so the body stays
+    /// open.
+    fn open_sse(&self, stream: &mut TcpStream) -> Result<(), TransportError> {
+        let mut head = String::from(
+            "HTTP/1.1 200 OK\r\n\
+             Content-Type: text/event-stream\r\n\
+             Cache-Control: no-cache\r\n\
+             Connection: keep-alive\r\n",
+        );
+        for (name, value) in self.cors.cors_headers() {
+            head.push_str(&format!("{name}: {value}\r\n"));
+        }
+        head.push_str("\r\n");
+        stream.write_all(head.as_bytes())?;
         stream.flush()?;
 
         let listener = stream.try_clone()?;
         if let Ok(mut sse_stream) = self.sse_stream.lock() {
             sse_stream.add_listener(listener);
         }
-
-        loop {
-            if stream.write_all(b": keep-alive\n\n").is_err() {
-                return Err(TransportError::ConnectionClosed);
-            }
-            stream.flush()?;
-            thread::sleep(Duration::from_secs(15));
-        }
+        Ok(())
     }
 
     pub fn broadcast_event(&self, event: &SseEvent) -> Vec<usize> {
+        self.broadcast_bytes(&event.to_bytes())
+    }
+
+    /// Write `payload` to every SSE listener, dropping the ones that fail.
+    fn broadcast_bytes(&self, payload: &[u8]) -> Vec<usize> {
         let Ok(mut stream) = self.sse_stream.lock() else {
             return Vec::new();
         };
 
-        let mut failed = stream.broadcast(event);
+        let mut failed = stream.broadcast_bytes(payload);
         failed.sort_unstable();
         for &index in failed.iter().rev() {
             stream.remove_listener(index);
@@ -533,7 +830,7 @@ impl HttpTransport {
         request: HttpRequest,
         stream: &mut TcpStream,
     ) -> Result<(), TransportError> {
-        let route = Self::route_request(&request);
+        let route = Self::route_request(&self.config, &request);
         match route {
             Ok(Route::Rpc) => {
                 if request.body.trim().is_empty() {
@@ -546,15 +843,23 @@ impl HttpTransport {
                     return Ok(());
                 }
 
-                let rpc_response = self.handle_post_rpc(&request.body);
-                if rpc_response.is_empty() {
-                    let response = self.build_plain_response(204, "");
-                    stream.write_all(&response)?;
-                    stream.flush()?;
-                    return Ok(());
-                }
-
-                let response = self.build_json_response(200, &rpc_response);
+                let session = match request.headers.get(&SESSION_HEADER.to_ascii_lowercase()) {
+                    Some(session) => session.clone(),
+                    None => self.mint_session_id(),
+                };
+                let rpc_response = self.handle_session_rpc(&session, &request.body);
+                let status = if rpc_response.is_empty() { 204 } else { 200 };
+                let mut headers = vec![
+                    ("Content-Type".to_string(), "application/json".to_string()),
+                    (SESSION_HEADER.to_string(), session),
+                    (
+                        "Access-Control-Expose-Headers".to_string(),
+                        SESSION_HEADER.to_string(),
+                    ),
+                ];
+                headers.extend(self.cors.cors_headers());
+                let response =
+                    self.build_response_with_owned_headers(status, &headers, &rpc_response);
                 stream.write_all(&response)?;
                 stream.flush()?;
                 Ok(())
@@ -567,7 +872,27 @@ impl HttpTransport {
                 Ok(())
             }
             Ok(Route::Health) => {
-                let response = self.build_json_response(200, &json!({"status": "ok"}).to_string());
+                let tools = self
+                    .server
+                    .lock()
+                    .map(|server| server.tool_count())
+                    .unwrap_or_default();
+                let body = json!({
+                    "status": "ok",
+                    "uptime_secs": self.started_at.elapsed().as_secs(),
+                    "tools": tools,
+                });
+                let response = self.build_json_response(200, &body.to_string());
+                stream.write_all(&response)?;
+                stream.flush()?;
+                Ok(())
+            }
+            Ok(Route::Ready) => {
+                let response = if self.is_ready() {
+                    self.build_json_response(200, &json!({"status": "ready"}).to_string())
+                } else {
+                    self.build_json_response(503, &json!({"status": "initializing"}).to_string())
+                };
                 stream.write_all(&response)?;
                 stream.flush()?;
                 Ok(())
@@ -590,71 +915,29 @@ impl HttpTransport {
         }
     }
 
-    fn route_request(request: &HttpRequest) -> Result<Route, TransportError> {
+    fn route_request(
+        config: &TransportConfig,
+        request: &HttpRequest,
+    ) -> Result<Route, TransportError> {
         let method = request.method.as_str();
         let path = request.path.as_str();
+        let is_probe = path == config.health_path || path == config.ready_path;
 
         match (method, path) {
             ("POST", "/rpc") => Ok(Route::Rpc),
             ("GET", "/sse") => Ok(Route::Sse),
             ("OPTIONS", _) => Ok(Route::Options),
-            ("GET", "/health") => Ok(Route::Health),
-            ("POST", "/sse") | ("GET", "/rpc") | ("POST", "/health") | ("GET", "/") => {
-                Err(TransportError::MethodNotAllowed(method.to_string()))
-            }
-            (_, "/rpc") | (_, "/sse") | (_, "/health") => {
+            ("GET", _) if path == config.health_path => Ok(Route::Health),
+            ("GET", _) if path == config.ready_path => Ok(Route::Ready),
+            ("POST", "/sse") | ("GET", "/rpc") | ("GET", "/") => {
                 Err(TransportError::MethodNotAllowed(method.to_string()))
             }
+            (_, "/rpc") | (_, "/sse") => Err(TransportError::MethodNotAllowed(method.to_string())),
+            _ if is_probe => Err(TransportError::MethodNotAllowed(method.to_string())),
             _ => Ok(Route::NotFound),
         }
     }
 
-    fn read_http_request(&self, stream: &mut TcpStream) -> Result<Vec<u8>, TransportError> {
-        let mut bytes = Vec::new();
-        let mut header_end = None;
-
-        loop {
-            let mut chunk = [0_u8; 1024];
-            let read = stream.read(&mut chunk)?;
-            if read == 0 {
-                if bytes.is_empty() {
-                    return Err(TransportError::ConnectionClosed);
-                }
-                break;
-            }
-
-            bytes.extend_from_slice(&chunk[..read]);
-            Self::enforce_request_size(bytes.len(), self.config.max_request_size_bytes)?;
-
-            if let Some(pos) = bytes
-                .windows(HTTP_HEADER_TERMINATOR.len())
-                .position(|window| window == HTTP_HEADER_TERMINATOR)
-            {
-                header_end = Some(pos + HTTP_HEADER_TERMINATOR.len());
-                break;
-            }
-        }
-
-        let header_end = header_end.ok_or_else(|| {
-            TransportError::InvalidHttpRequest("request missing header terminator".to_string())
-        })?;
-
-        let content_length = parse_content_length(&bytes[..header_end])?;
-        let target_len = header_end + content_length;
-        while bytes.len() < target_len {
-            let mut chunk = vec![0_u8; target_len - bytes.len()];
-            let read = stream.read(&mut chunk)?;
-            if read == 0 {
-                return Err(TransportError::ConnectionClosed);
-            }
-
-            bytes.extend_from_slice(&chunk[..read]);
-            Self::enforce_request_size(bytes.len(), self.config.max_request_size_bytes)?;
-        }
-
-        Ok(bytes)
-    }
-
     fn enforce_request_size(size: usize, max_request_size_bytes: usize) -> Result<(), TransportError> {
         if size > max_request_size_bytes {
             return Err(TransportError::RequestTooLarge(size));
@@ -662,6 +945,15 @@ impl HttpTransport {
         Ok(())
     }
 
+    /// An unguessable session ID: two SipHash outputs under this process's
+    /// random keys.
+    fn mint_session_id(&self) -> String {
+        let sequence = self.sessions_minted.fetch_add(1, Ordering::Relaxed);
+        (0u8..2)
+            .map(|half| format!("{:016x}", self.session_keys.hash_one((sequence, half))))
+            .collect()
+    }
+
     fn build_json_response(&self, status: u16, body: &str) -> Vec<u8> {
         let mut headers = vec![("Content-Type".to_string(), "application/json".to_string())];
         headers.extend(self.cors.cors_headers());
@@ -688,6 +980,73 @@ impl HttpTransport {
     }
 }
 
+/// Read one HTTP request (headers plus `Content-Length` body) off `stream`.
+fn read_http_request(
+    stream: &mut TcpStream,
+    max_request_size_bytes: usize,
+) -> Result<Vec<u8>, TransportError> {
+    let mut bytes = Vec::new();
+    let mut header_end = None;
+
+    loop {
+        let mut chunk = [0_u8; 1024];
+        let read = stream.read(&mut chunk)?;
+        if read == 0 {
+            if bytes.is_empty() {
+                return Err(TransportError::ConnectionClosed);
+            }
+            break;
+        }
+
+        bytes.extend_from_slice(&chunk[..read]);
+        HttpTransport::enforce_request_size(bytes.len(), max_request_size_bytes)?;
+
+        if let Some(pos) = bytes
+            .windows(HTTP_HEADER_TERMINATOR.len())
+            .position(|window| window == HTTP_HEADER_TERMINATOR)
+        {
+            header_end = Some(pos + HTTP_HEADER_TERMINATOR.len());
+            break;
+        }
+    }
+
+    let header_end = header_end.ok_or_else(|| {
+        TransportError::InvalidHttpRequest("request missing header terminator".to_string())
+    })?;
+
+    let content_length = parse_content_length(&bytes[..header_end])?;
+    let target_len = header_end + content_length;
+    while bytes.len() < target_len {
+        let mut chunk = vec![0_u8; target_len - bytes.len()];
+        let read = stream.read(&mut chunk)?;
+        if read == 0 {
+            return Err(TransportError::ConnectionClosed);
+        }
+
+        bytes.extend_from_slice(&chunk[..read]);
+        HttpTransport::enforce_request_size(bytes.len(), max_request_size_bytes)?;
+    }
+
+    Ok(bytes)
+}
+
+/// Read and parse a request off a freshly accepted connection for the serve
+/// loop, handing the connection back for the response.
+fn read_queued_request(
+    mut stream: TcpStream,
+    max_request_size_bytes: usize,
+    read_timeout: Duration,
+    write_timeout: Duration,
+) -> Result<(HttpRequest, TcpStream), TransportError> {
+    // Accepted sockets may inherit the listener's non-blocking mode.
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(read_timeout))?;
+    stream.set_write_timeout(Some(write_timeout))?;
+    let raw_request = read_http_request(&mut stream, max_request_size_bytes)?;
+    let request = HttpTransport::parse_http_request(&raw_request)?;
+    Ok((request, stream))
+}
+
 fn parse_content_length(header_bytes: &[u8]) -> Result<usize, TransportError> {
     let text = std::str::from_utf8(header_bytes)
         .map_err(|_| TransportError::InvalidHttpRequest("headers must be valid UTF-8".to_string()))?;
@@ -733,6 +1092,7 @@ fn status_text(status: u16) -> &'static str {
         405 => "Method Not Allowed",
         413 => "Payload Too Large",
         500 => "Internal Server Error",
+        503 => "Service Unavailable",
         _ => "OK",
     }
 }
@@ -755,6 +1115,10 @@ mod tests {
         }
     }
 
+    fn default_route(request: &HttpRequest) -> Result<Route, TransportError> {
+        HttpTransport::route_request(&TransportConfig::default(), request)
+    }
+
     fn make_http_transport(kind: TransportKind) -> HttpTransport {
         HttpTransport::new(&TransportConfig {
             kind,
@@ -784,6 +1148,13 @@ mod tests {
             max_request_size_bytes: 2048,
             read_timeout_ms: 10_000,
             write_timeout_ms: 12_000,
+            health_path: "/livez".to_string(),
+            ready_path: "/readyz".to_string(),
+            cors_methods: vec!["POST".to_string()],
+            cors_headers: vec!["Content-Type".to_string(), "X-Trace".to_string()],
+            allowed_tools: Some(vec!["list_tasks".to_string()]),
+            shared_secret: Some("s3cret".to_string()),
+            trace_path: Some(PathBuf::from(".othala/mcp-trace.jsonl")),
         };
 
         let serialized = serde_json::to_string(&config).expect("serialize config");
@@ -1010,7 +1381,7 @@ mod tests {
             body: "{}".to_string(),
         };
 
-        let route = HttpTransport::route_request(&request).expect("route");
+        let route = default_route(&request).expect("route");
         assert_eq!(route, Route::Rpc);
     }
 
@@ -1023,7 +1394,7 @@ mod tests {
             body: String::new(),
         };
 
-        let route = HttpTransport::route_request(&request).expect("route");
+        let route = default_route(&request).expect("route");
         assert_eq!(route, Route::Sse);
     }
 
@@ -1036,7 +1407,7 @@ mod tests {
             body: String::new(),
         };
 
-        let route = HttpTransport::route_request(&request).expect("route");
+        let route = default_route(&request).expect("route");
         assert_eq!(route, Route::Health);
     }
 
@@ -1049,7 +1420,7 @@ mod tests {
             body: "{}".to_string(),
         };
 
-        let error = HttpTransport::route_request(&request).expect_err("method not allowed");
+        let error = default_route(&request).expect_err("method not allowed");
         assert!(matches!(error, TransportError::MethodNotAllowed(method) if method == "PUT"));
     }
 
@@ -1062,7 +1433,7 @@ mod tests {
             body: String::new(),
         };
 
-        let route = HttpTransport::route_request(&request).expect("route");
+        let route = default_route(&request).expect("route");
         assert_eq!(route, Route::NotFound);
     }
 
@@ -1129,6 +1500,59 @@ mod tests {
         assert_eq!(response_raw, "");
     }
 
+    #[test]
+    fn trace_path_logs_rpc_traffic_with_secret_fields_redacted() {
+        let dir = std::env::temp_dir().join(format!(
+            "othala-mcp-trace-{}-{}",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let trace_path = dir.join("trace/mcp.jsonl");
+        let transport = HttpTransport::new(&TransportConfig {
+            trace_path: Some(trace_path.clone()),
+            ..TransportConfig::default()
+        })
+        .expect("transport creates");
+
+        let response_raw = transport.handle_session_rpc(
+            "session-1",
+            r#"{"jsonrpc":"2.0","id":7,"method":"tools/list","params":{"api_token":"ghp_abc123","nested":[{"Password":"hunter2","name":"demo"}]}}"#,
+        );
+        assert!(!response_raw.is_empty());
+
+        let raw = fs::read_to_string(&trace_path).expect("read trace");
+        assert!(!raw.contains("ghp_abc123"), "{raw}");
+        assert!(!raw.contains("hunter2"), "{raw}");
+        let lines = raw
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).expect("trace line"))
+            .collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["direction"], "request");
+        assert!(!raw.contains("session-1"), "{raw}");
+        let session = lines[0]["session"].as_str().expect("session tag");
+        assert_eq!(session.len(), 16);
+        assert_eq!(lines[1]["session"], session);
+        let params = &lines[0]["message"]["params"];
+        assert_eq!(params["api_token"], "***");
+        assert_eq!(params["nested"][0]["Password"], "***");
+        assert_eq!(params["nested"][0]["name"], "demo");
+        assert_eq!(lines[1]["direction"], "response");
+        assert_eq!(lines[1]["message"]["id"], 7);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&trace_path)
+                .expect("trace metadata")
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn empty_body_handling_for_rpc_route() {
         let transport = make_http_transport(TransportKind::Http {
@@ -1143,7 +1567,7 @@ mod tests {
             body: String::new(),
         };
 
-        let route = HttpTransport::route_request(&request).expect("route");
+        let route = default_route(&request).expect("route");
         assert_eq!(route, Route::Rpc);
 
         let response = transport.build_json_response(400, &json!({ "error": "empty request body" }).to_string());
@@ -1163,6 +1587,69 @@ mod tests {
         assert!(text.ends_with("{\"status\":\"ok\"}"));
     }
 
+    #[test]
+    fn route_request_honours_configured_probe_paths() {
+        let config = TransportConfig {
+            health_path: "/livez".to_string(),
+            ready_path: "/readyz".to_string(),
+            ..TransportConfig::default()
+        };
+        let get = |path: &str| HttpRequest {
+            method: "GET".to_string(),
+            path: path.to_string(),
+            headers: HashMap::new(),
+            body: String::new(),
+        };
+
+        let route = |path: &str| HttpTransport::route_request(&config, &get(path)).expect("route");
+        assert_eq!(route("/livez"), Route::Health);
+        assert_eq!(route("/readyz"), Route::Ready);
+        assert_eq!(route("/health"), Route::NotFound);
+    }
+
+    #[test]
+    fn ready_endpoint_flips_from_503_to_200_after_initialize() {
+        let transport = HttpTransport::new_uninitialized(&TransportConfig {
+            kind: TransportKind::Http {
+                bind_addr: "127.0.0.1".to_string(),
+                port: 0,
+            },
+            ..TransportConfig::default()
+        })
+        .expect("transport creates");
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind listener");
+        let addr = listener.local_addr().expect("local addr");
+        let client = thread::spawn(move || {
+            ["/ready", "/health", "/ready"]
+                .iter()
+                .map(|path| {
+                    let mut stream = TcpStream::connect(addr).expect("connect");
+                    write!(stream, "GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n")
+                        .expect("write");
+                    let mut response = String::new();
+                    stream.read_to_string(&mut response).expect("read");
+                    response
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let accept = || {
+            let (stream, _) = listener.accept().expect("accept");
+            transport.handle_connection(stream).expect("handle connection");
+        };
+        accept();
+        accept();
+        // Simulated init step: the third request is served only after this.
+        transport.initialize();
+        accept();
+
+        let responses = client.join().expect("client thread");
+        assert!(responses[0].starts_with("HTTP/1.1 503 Service Unavailable\r\n"));
+        assert!(responses[1].starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(responses[2].starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(responses[2].ends_with("{\"status\":\"ready\"}"));
+    }
+
     #[test]
     fn parse_content_length_reads_value_case_insensitive() {
         let header = b"POST /rpc HTTP/1.1\r\nContent-Length: 13\r\n\r\n";
@@ -0,0 +1,258 @@
+//! Polling fallback for tasks stuck in `AwaitingMerge`.
+//!
+//! The per-tick merge check only covers tasks with a recorded PR. Once a task
+//! has waited `min_age_secs`, it is also polled here at most every
+//! `interval_secs`: has the branch landed on the base branch, does Graphite
+//! report the PR merged, or (when enabled) does the GitHub API. Tasks still
+//! waiting past `stale_secs` are flagged for a human once.
+
+use chrono::{DateTime, Utc};
+use orch_core::config::MergePollConfig;
+use orch_core::types::{Task, TaskId};
+use orch_git::{discover_repo, is_ancestor, GitCli};
+use orch_graphite::{audit_log_path, GraphiteClient, PrStatus};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Base branches a merged task branch is looked for in, remote first.
+const BASE_REFS: [&str; 2] = ["origin/main", "main"];
+
+/// How a merge was noticed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeDetection {
+    /// The task branch is an ancestor of the base branch.
+    Ancestor,
+    /// `gt info` reports the branch's PR as merged.
+    Graphite,
+    /// The GitHub API reports the PR as merged.
+    GitHub,
+}
+
+impl MergeDetection {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MergeDetection::Ancestor => "ancestor",
+            MergeDetection::Graphite => "graphite",
+            MergeDetection::GitHub => "github",
+        }
+    }
+}
+
+/// Per-task poll times and staleness flags, kept across daemon ticks.
+#[derive(Debug, Default)]
+pub struct MergePoller {
+    last_polled: HashMap<TaskId, DateTime<Utc>>,
+    flagged_stale: HashSet<TaskId>,
+}
+
+impl MergePoller {
+    /// Whether `task` is old enough and was not polled within the interval.
+    /// Records `now` as its poll time when it is due.
+    pub fn take_due(&mut self, task: &Task, config: &MergePollConfig, now: DateTime<Utc>) -> bool {
+        if awaiting_secs(task, now) < config.min_age_secs as i64 {
+            return false;
+        }
+        let interval = chrono::Duration::seconds(config.interval_secs as i64);
+        if let Some(last) = self.last_polled.get(&task.id) {
+            if now - *last < interval {
+                return false;
+            }
+        }
+        self.last_polled.insert(task.id.clone(), now);
+        true
+    }
+
+    /// Needs-human reason the first time `task` waits past `stale_secs`.
+    pub fn take_stale(
+        &mut self,
+        task: &Task,
+        config: &MergePollConfig,
+        now: DateTime<Utc>,
+    ) -> Option<String> {
+        let waited = awaiting_secs(task, now);
+        if waited < config.stale_secs as i64 || !self.flagged_stale.insert(task.id.clone()) {
+            return None;
+        }
+        Some(stale_reason(task, waited))
+    }
+
+    /// Forget tasks that are no longer awaiting merge.
+    pub fn retain_awaiting(&mut self, awaiting: &[Task]) {
+        let ids: HashSet<&TaskId> = awaiting.iter().map(|task| &task.id).collect();
+        self.last_polled.retain(|id, _| ids.contains(id));
+        self.flagged_stale.retain(|id| ids.contains(id));
+    }
+}
+
+fn awaiting_secs(task: &Task, now: DateTime<Utc>) -> i64 {
+    (now - task.updated_at).num_seconds()
+}
+
+fn stale_reason(task: &Task, waited_secs: i64) -> String {
+    let pr = match &task.pr {
+        Some(pr) if pr.number > 0 => format!("PR #{}", pr.number),
+        _ => "its PR".to_string(),
+    };
+    format!(
+        "{} has been awaiting merge for {}h; {pr} may be stuck in review",
+        task.id.0,
+        waited_secs / 3_600
+    )
+}
+
+/// Check whether the task's branch was merged, cheapest source first.
+pub fn detect_merge(
+    repo_root: &Path,
+    task: &Task,
+    config: &MergePollConfig,
+) -> Option<MergeDetection> {
+    let branch = task.branch_name.as_deref().filter(|b| !b.trim().is_empty());
+    if let Some(branch) = branch {
+        if branch_landed_on_base(repo_root, branch) {
+            return Some(MergeDetection::Ancestor);
+        }
+        let graphite = GraphiteClient::new(repo_root).with_audit_log(audit_log_path(repo_root));
+        if matches!(graphite.branch_pr_status(branch), Ok(PrStatus::Merged)) {
+            return Some(MergeDetection::Graphite);
+        }
+    }
+    let pr_number = task.pr.as_ref().map(|pr| pr.number).filter(|n| *n > 0);
+    if let Some(number) = pr_number.filter(|_| config.github_api) {
+        if crate::daemon_loop::check_pr_merged(number, repo_root) {
+            return Some(MergeDetection::GitHub);
+        }
+    }
+    None
+}
+
+fn branch_landed_on_base(repo_root: &Path, branch: &str) -> bool {
+    let git = GitCli::default();
+    let Ok(repo) = discover_repo(repo_root, &git) else {
+        return false;
+    };
+    BASE_REFS
+        .iter()
+        .any(|base| matches!(is_ancestor(&repo, &git, branch, base), Ok(true)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orch_core::state::TaskState;
+    use orch_core::types::{PullRequestRef, RepoId};
+    use std::fs;
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    fn mk_task(id: &str, waited_secs: i64, now: DateTime<Utc>) -> Task {
+        let mut task = Task::new(
+            TaskId::new(id),
+            RepoId("repo".to_string()),
+            format!("Task {id}"),
+            PathBuf::from(format!(".orch/wt/{id}")),
+        );
+        task.state = TaskState::AwaitingMerge;
+        task.updated_at = now - chrono::Duration::seconds(waited_secs);
+        task
+    }
+
+    fn git(repo: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args([
+                "-c",
+                "user.name=Othala Tests",
+                "-c",
+                "user.email=tests@example.com",
+            ])
+            .args(args)
+            .current_dir(repo)
+            .status()
+            .expect("run git");
+        assert!(status.success(), "git {args:?} should succeed");
+    }
+
+    fn init_repo_with_branch(branch: &str) -> PathBuf {
+        let repo = std::env::temp_dir().join(format!(
+            "othala-merge-poll-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&repo).expect("create repo dir");
+        git(&repo, &["init", "-q", "-b", "main"]);
+        fs::write(repo.join("README.md"), "# test\n").expect("write readme");
+        git(&repo, &["add", "README.md"]);
+        git(&repo, &["commit", "-q", "-m", "initial"]);
+        git(&repo, &["checkout", "-q", "-b", branch]);
+        fs::write(repo.join("feature.txt"), "feature\n").expect("write feature");
+        git(&repo, &["add", "feature.txt"]);
+        git(&repo, &["commit", "-q", "-m", "feature"]);
+        git(&repo, &["checkout", "-q", "main"]);
+        repo
+    }
+
+    #[test]
+    fn polls_only_old_enough_tasks_once_per_interval() {
+        let now = Utc::now();
+        let config = MergePollConfig {
+            min_age_secs: 600,
+            interval_secs: 300,
+            ..MergePollConfig::default()
+        };
+        let mut poller = MergePoller::default();
+
+        assert!(!poller.take_due(&mk_task("T-young", 60, now), &config, now));
+
+        let task = mk_task("T-old", 900, now);
+        assert!(poller.take_due(&task, &config, now));
+        let soon = now + chrono::Duration::seconds(120);
+        assert!(!poller.take_due(&task, &config, soon));
+        let later = now + chrono::Duration::seconds(300);
+        assert!(poller.take_due(&task, &config, later));
+    }
+
+    #[test]
+    fn flags_stale_task_once_until_it_leaves_awaiting_merge() {
+        let now = Utc::now();
+        let config = MergePollConfig {
+            stale_secs: 86_400,
+            ..MergePollConfig::default()
+        };
+        let mut poller = MergePoller::default();
+        let mut task = mk_task("T-stale", 2 * 86_400, now);
+        task.pr = Some(PullRequestRef {
+            number: 42,
+            url: "https://github.com/acme/app/pull/42".to_string(),
+            draft: false,
+        });
+
+        assert_eq!(
+            poller.take_stale(&mk_task("T-fresh", 3_600, now), &config, now),
+            None
+        );
+        let reason = poller.take_stale(&task, &config, now).expect("stale");
+        assert!(reason.contains("48h"), "{reason}");
+        assert!(reason.contains("PR #42 may be stuck in review"), "{reason}");
+        assert_eq!(poller.take_stale(&task, &config, now), None);
+
+        poller.retain_awaiting(&[]);
+        assert!(poller.take_stale(&task, &config, now).is_some());
+    }
+
+    #[test]
+    fn detects_branch_merged_into_base_as_ancestor() {
+        let repo = init_repo_with_branch("task/T-merged");
+        let now = Utc::now();
+        let config = MergePollConfig::default();
+        let mut task = mk_task("T-merged", 900, now);
+        task.branch_name = Some("task/T-merged".to_string());
+
+        assert_eq!(detect_merge(&repo, &task, &config), None);
+
+        git(&repo, &["merge", "-q", "--ff-only", "task/T-merged"]);
+        assert_eq!(
+            detect_merge(&repo, &task, &config),
+            Some(MergeDetection::Ancestor)
+        );
+
+        let _ = fs::remove_dir_all(repo);
+    }
+}
@@ -0,0 +1,274 @@
+//! Persisted merge queue — explicit ordering for tasks awaiting merge.
+//!
+//! Every mutation takes an optional expected version; when given it must match
+//! the stored version or the call fails with `VersionConflict`. The daemon
+//! merges and restacks in queue order, falling back to its implicit ordering
+//! for tasks that are not queued.
+
+use orch_core::state::TaskState;
+use orch_core::types::TaskId;
+
+use crate::persistence::{PersistenceError, SqliteStore};
+use crate::types::MergeQueueSnapshot;
+
+#[derive(Debug, thiserror::Error)]
+pub enum MergeQueueError {
+    #[error(transparent)]
+    Persistence(#[from] PersistenceError),
+    #[error("task not found: {task_id}")]
+    TaskNotFound { task_id: String },
+    #[error("task {task_id} is {state}; only AWAITING_MERGE tasks can be queued")]
+    NotAwaitingMerge { task_id: String, state: TaskState },
+    #[error("task {task_id} is already in the merge queue")]
+    AlreadyQueued { task_id: String },
+    #[error("task {task_id} is not in the merge queue")]
+    NotQueued { task_id: String },
+    #[error("merge queue version conflict: expected {expected}, current {actual}")]
+    VersionConflict { expected: u64, actual: u64 },
+    #[error("invalid merge queue order: {reason}")]
+    InvalidOrder { reason: String },
+}
+
+pub fn snapshot(store: &SqliteStore) -> Result<MergeQueueSnapshot, MergeQueueError> {
+    Ok(store.load_merge_queue()?)
+}
+
+/// Append an `AwaitingMerge` task to the end of the queue.
+pub fn enqueue(
+    store: &SqliteStore,
+    task_id: &TaskId,
+    expected_version: Option<u64>,
+) -> Result<MergeQueueSnapshot, MergeQueueError> {
+    let task = store
+        .load_task(task_id)?
+        .ok_or_else(|| MergeQueueError::TaskNotFound {
+            task_id: task_id.0.clone(),
+        })?;
+    if task.state != TaskState::AwaitingMerge {
+        return Err(MergeQueueError::NotAwaitingMerge {
+            task_id: task_id.0.clone(),
+            state: task.state,
+        });
+    }
+
+    let current = checked_snapshot(store, expected_version)?;
+    if current.position_of(task_id).is_some() {
+        return Err(MergeQueueError::AlreadyQueued {
+            task_id: task_id.0.clone(),
+        });
+    }
+    let mut order = current.task_ids();
+    order.push(task_id.clone());
+    commit(store, current.version, &order)
+}
+
+/// Remove a task from the queue.
+pub fn cancel(
+    store: &SqliteStore,
+    task_id: &TaskId,
+    expected_version: Option<u64>,
+) -> Result<MergeQueueSnapshot, MergeQueueError> {
+    let current = checked_snapshot(store, expected_version)?;
+    if current.position_of(task_id).is_none() {
+        return Err(MergeQueueError::NotQueued {
+            task_id: task_id.0.clone(),
+        });
+    }
+    let order: Vec<TaskId> = current
+        .task_ids()
+        .into_iter()
+        .filter(|id| id != task_id)
+        .collect();
+    commit(store, current.version, &order)
+}
+
+/// Replace the queue order. `order` must be a permutation of the queued IDs.
+pub fn reorder(
+    store: &SqliteStore,
+    order: &[TaskId],
+    expected_version: Option<u64>,
+) -> Result<MergeQueueSnapshot, MergeQueueError> {
+    let current = checked_snapshot(store, expected_version)?;
+    let mut wanted: Vec<&str> = order.iter().map(|id| id.0.as_str()).collect();
+    let mut queued: Vec<&str> = current.entries.iter().map(|e| e.task_id.0.as_str()).collect();
+    wanted.sort_unstable();
+    queued.sort_unstable();
+    if wanted.windows(2).any(|pair| pair[0] == pair[1]) {
+        return Err(MergeQueueError::InvalidOrder {
+            reason: "order contains duplicate task ids".to_string(),
+        });
+    }
+    if wanted != queued {
+        return Err(MergeQueueError::InvalidOrder {
+            reason: "order must list exactly the queued task ids".to_string(),
+        });
+    }
+    commit(store, current.version, order)
+}
+
+/// Drop a task from the queue if present, ignoring the version. Used when a
+/// task leaves `AwaitingMerge` (merged, deleted, cancelled).
+pub fn remove_if_queued(store: &SqliteStore, task_id: &TaskId) -> Result<(), MergeQueueError> {
+    match cancel(store, task_id, None) {
+        Ok(_) | Err(MergeQueueError::NotQueued { .. }) => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Sort key placing queued tasks first (in queue order), then everything else.
+pub fn queue_rank(queue: &MergeQueueSnapshot, task_id: &TaskId) -> (bool, usize) {
+    match queue.position_of(task_id) {
+        Some(position) => (false, position),
+        None => (true, 0),
+    }
+}
+
+fn checked_snapshot(
+    store: &SqliteStore,
+    expected_version: Option<u64>,
+) -> Result<MergeQueueSnapshot, MergeQueueError> {
+    let current = store.load_merge_queue()?;
+    match expected_version {
+        Some(expected) if expected != current.version => Err(MergeQueueError::VersionConflict {
+            expected,
+            actual: current.version,
+        }),
+        _ => Ok(current),
+    }
+}
+
+fn commit(
+    store: &SqliteStore,
+    version: u64,
+    order: &[TaskId],
+) -> Result<MergeQueueSnapshot, MergeQueueError> {
+    match store.replace_merge_queue(version, order)? {
+        Some(snapshot) => Ok(snapshot),
+        None => Err(MergeQueueError::VersionConflict {
+            expected: version,
+            actual: store.load_merge_queue()?.version,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orch_core::types::{RepoId, Task};
+    use std::path::PathBuf;
+
+    fn mk_store_with(tasks: &[(&str, TaskState)]) -> SqliteStore {
+        let store = SqliteStore::open_in_memory().expect("in-memory store");
+        store.migrate().expect("migrate");
+        for (id, state) in tasks {
+            let mut task = Task::new(
+                TaskId::new(*id),
+                RepoId("example".to_string()),
+                format!("Task {id}"),
+                PathBuf::from(format!(".orch/wt/{id}")),
+            );
+            task.state = *state;
+            store.upsert_task(&task).expect("upsert");
+        }
+        store
+    }
+
+    fn ids(snapshot: &MergeQueueSnapshot) -> Vec<String> {
+        snapshot.entries.iter().map(|e| e.task_id.0.clone()).collect()
+    }
+
+    #[test]
+    fn enqueue_appends_and_bumps_version() {
+        let store = mk_store_with(&[
+            ("T1", TaskState::AwaitingMerge),
+            ("T2", TaskState::AwaitingMerge),
+        ]);
+        assert_eq!(snapshot(&store).unwrap().version, 0);
+
+        let first = enqueue(&store, &TaskId::new("T1"), Some(0)).unwrap();
+        assert_eq!(first.version, 1);
+        let second = enqueue(&store, &TaskId::new("T2"), None).unwrap();
+        assert_eq!(second.version, 2);
+        assert_eq!(ids(&second), vec!["T1", "T2"]);
+        assert_eq!(second.position_of(&TaskId::new("T2")), Some(1));
+
+        assert!(matches!(
+            enqueue(&store, &TaskId::new("T1"), None),
+            Err(MergeQueueError::AlreadyQueued { .. })
+        ));
+    }
+
+    #[test]
+    fn enqueue_rejects_tasks_not_awaiting_merge() {
+        let store = mk_store_with(&[("T1", TaskState::Chatting)]);
+        let err = enqueue(&store, &TaskId::new("T1"), None).unwrap_err();
+        assert!(matches!(
+            err,
+            MergeQueueError::NotAwaitingMerge {
+                state: TaskState::Chatting,
+                ..
+            }
+        ));
+        assert!(err.to_string().contains("CHATTING"));
+    }
+
+    #[test]
+    fn stale_version_is_rejected() {
+        let store = mk_store_with(&[
+            ("T1", TaskState::AwaitingMerge),
+            ("T2", TaskState::AwaitingMerge),
+        ]);
+        enqueue(&store, &TaskId::new("T1"), None).unwrap();
+        let err = enqueue(&store, &TaskId::new("T2"), Some(0)).unwrap_err();
+        assert!(matches!(
+            err,
+            MergeQueueError::VersionConflict {
+                expected: 0,
+                actual: 1
+            }
+        ));
+    }
+
+    #[test]
+    fn reorder_and_cancel_update_positions() {
+        let store = mk_store_with(&[
+            ("T1", TaskState::AwaitingMerge),
+            ("T2", TaskState::AwaitingMerge),
+            ("T3", TaskState::AwaitingMerge),
+        ]);
+        for id in ["T1", "T2", "T3"] {
+            enqueue(&store, &TaskId::new(id), None).unwrap();
+        }
+
+        let order = vec![TaskId::new("T3"), TaskId::new("T1"), TaskId::new("T2")];
+        let reordered = reorder(&store, &order, Some(3)).unwrap();
+        assert_eq!(ids(&reordered), vec!["T3", "T1", "T2"]);
+
+        assert!(matches!(
+            reorder(&store, &order[..2], None),
+            Err(MergeQueueError::InvalidOrder { .. })
+        ));
+
+        let cancelled = cancel(&store, &TaskId::new("T1"), Some(reordered.version)).unwrap();
+        assert_eq!(ids(&cancelled), vec!["T3", "T2"]);
+        assert_eq!(cancelled.position_of(&TaskId::new("T2")), Some(1));
+        assert!(matches!(
+            cancel(&store, &TaskId::new("T1"), None),
+            Err(MergeQueueError::NotQueued { .. })
+        ));
+    }
+
+    #[test]
+    fn queue_rank_puts_queued_tasks_first() {
+        let store = mk_store_with(&[
+            ("T1", TaskState::AwaitingMerge),
+            ("T2", TaskState::AwaitingMerge),
+        ]);
+        enqueue(&store, &TaskId::new("T2"), None).unwrap();
+        let queue = snapshot(&store).unwrap();
+
+        let mut tasks = vec![TaskId::new("T1"), TaskId::new("T2")];
+        tasks.sort_by_key(|id| queue_rank(&queue, id));
+        assert_eq!(tasks, vec![TaskId::new("T2"), TaskId::new("T1")]);
+    }
+}
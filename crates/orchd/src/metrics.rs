@@ -1,9 +1,12 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 
+use crate::orchestration_metrics::DurationPercentiles;
+use crate::rate_limiter::BucketStats;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetricsConfig {
     pub enabled: bool,
@@ -66,6 +69,13 @@ pub struct MetricsSummary {
     pub total_agent_invocations: u64,
     pub models_used: HashMap<String, u64>,
     pub avg_task_duration_secs: Option<f64>,
+    /// Latency percentiles from the task store, when the caller has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_percentiles: Option<DurationPercentiles>,
+    /// Allowed and throttled requests per rate limiter bucket, when the
+    /// caller has them.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub rate_limits: BTreeMap<String, BucketStats>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
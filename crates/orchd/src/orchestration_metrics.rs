@@ -5,8 +5,11 @@
 //! - Time-to-merge tracking
 //! - E2E pass rates
 //! - Error recovery success rates
+//! - p50/p90/p99 of agent run durations and time-to-ready
 
 use chrono::{DateTime, Duration, Utc};
+use orch_core::events::{Event, EventKind};
+use orch_core::types::Task;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -41,6 +44,12 @@ pub struct TaskMetrics {
     pub error_classes: Vec<String>,
     /// Time spent in each agent (seconds)
     pub agent_durations: HashMap<String, f64>,
+    /// Duration of each agent run, in seconds, in attempt order
+    #[serde(default)]
+    pub run_durations_secs: Vec<f64>,
+    /// Time when task first reached Ready
+    #[serde(default)]
+    pub ready_at: Option<DateTime<Utc>>,
 }
 
 impl TaskMetrics {
@@ -56,6 +65,8 @@ impl TaskMetrics {
             e2e_passed: None,
             error_classes: Vec::new(),
             agent_durations: HashMap::new(),
+            run_durations_secs: Vec::new(),
+            ready_at: None,
         }
     }
 
@@ -67,12 +78,26 @@ impl TaskMetrics {
         })
     }
 
+    /// Seconds from start until the task first reached Ready.
+    pub fn time_to_ready_secs(&self) -> Option<f64> {
+        self.ready_at.map(|ready| {
+            let duration = ready.signed_duration_since(self.started_at);
+            duration.num_milliseconds().max(0) as f64 / 1_000.0
+        })
+    }
+
     /// Record an agent attempt.
     pub fn record_attempt(&mut self, role: AgentRole, duration_secs: f64) {
         let role_name = role.name().to_string();
         self.agents_used.push(role_name.clone());
         self.total_attempts += 1;
         *self.agent_durations.entry(role_name).or_insert(0.0) += duration_secs;
+        self.run_durations_secs.push(duration_secs);
+    }
+
+    /// Mark the task as Ready, keeping the first time it got there.
+    pub fn mark_ready(&mut self) {
+        self.ready_at.get_or_insert_with(Utc::now);
     }
 
     /// Record an error.
@@ -171,6 +196,71 @@ impl AgentMetrics {
     }
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Percentiles
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Nearest-rank percentiles of a set of samples. With few samples the higher
+/// percentiles collapse onto the largest one; with none they are all `None`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Percentiles {
+    pub samples: usize,
+    pub p50: Option<f64>,
+    pub p90: Option<f64>,
+    pub p99: Option<f64>,
+}
+
+impl Percentiles {
+    pub fn from_samples(samples: &[f64]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        Self {
+            samples: sorted.len(),
+            p50: nearest_rank(&sorted, 50),
+            p90: nearest_rank(&sorted, 90),
+            p99: nearest_rank(&sorted, 99),
+        }
+    }
+
+    fn display(&self, label: &str) -> String {
+        match (self.p50, self.p90, self.p99) {
+            (Some(p50), Some(p90), Some(p99)) => format!(
+                "{label} (n={}): p50 {p50:.1}s  p90 {p90:.1}s  p99 {p99:.1}s",
+                self.samples
+            ),
+            _ => format!("{label} (n=0): no samples"),
+        }
+    }
+}
+
+/// The smallest sample with at least `percentile`% of samples at or below it.
+fn nearest_rank(sorted: &[f64], percentile: usize) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (percentile * sorted.len())
+        .div_ceil(100)
+        .clamp(1, sorted.len());
+    Some(sorted[rank - 1])
+}
+
+/// Latency percentiles for SLO tracking, in seconds.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DurationPercentiles {
+    pub agent_run_secs: Percentiles,
+    pub time_to_ready_secs: Percentiles,
+}
+
+impl DurationPercentiles {
+    pub fn display(&self) -> String {
+        [
+            self.agent_run_secs.display("Agent run duration"),
+            self.time_to_ready_secs.display("Time to ready"),
+        ]
+        .join("\n")
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Orchestration Snapshot
 // ─────────────────────────────────────────────────────────────────────────────
@@ -184,6 +274,9 @@ pub struct OrchestrationSnapshot {
     /// Verify results
     pub verify_passed: u32,
     pub verify_failed: u32,
+    /// Verify runs killed at their time limit; not counted in `verify_failed`
+    #[serde(default)]
+    pub verify_timeouts: u32,
     /// E2E results
     pub e2e_passed: u32,
     pub e2e_failed: u32,
@@ -198,6 +291,9 @@ pub struct OrchestrationSnapshot {
     pub agent_dispatches: HashMap<String, u32>,
     /// Error class counts
     pub error_classes: HashMap<String, u32>,
+    /// MCP tool calls rejected with -32001, by reason
+    #[serde(default)]
+    pub mcp_rejections: HashMap<String, u32>,
 }
 
 impl Default for OrchestrationSnapshot {
@@ -207,6 +303,7 @@ impl Default for OrchestrationSnapshot {
             state_counts: HashMap::new(),
             verify_passed: 0,
             verify_failed: 0,
+            verify_timeouts: 0,
             e2e_passed: 0,
             e2e_failed: 0,
             e2e_skipped: 0,
@@ -215,6 +312,7 @@ impl Default for OrchestrationSnapshot {
             stops: 0,
             agent_dispatches: HashMap::new(),
             error_classes: HashMap::new(),
+            mcp_rejections: HashMap::new(),
         }
     }
 }
@@ -314,6 +412,60 @@ impl OrchestrationMetricsStore {
         Ok(())
     }
 
+    /// Rebuild per-task samples from recorded history: each
+    /// `AgentCompleted` event is a run, and the first `ReadyReached` (or
+    /// state change to READY) marks when the task got there.
+    pub fn from_history(metrics_dir: PathBuf, tasks: &[Task], events: &[Event]) -> Self {
+        let mut store = Self::new(metrics_dir);
+        for task in tasks {
+            let metrics = store.get_task(&task.id.0, &task.repo_id.0);
+            metrics.started_at = task.created_at;
+        }
+
+        let mut events: Vec<&Event> = events.iter().collect();
+        events.sort_by_key(|event| event.at);
+        for event in events {
+            let Some(task_id) = &event.task_id else {
+                continue;
+            };
+            let Some(metrics) = store.tasks.get_mut(&task_id.0) else {
+                continue;
+            };
+            match &event.kind {
+                EventKind::AgentCompleted { duration_secs, .. } => {
+                    metrics.run_durations_secs.push(*duration_secs as f64);
+                }
+                EventKind::ReadyReached => {
+                    metrics.ready_at.get_or_insert(event.at);
+                }
+                EventKind::TaskStateChanged { to, .. } if to == "READY" => {
+                    metrics.ready_at.get_or_insert(event.at);
+                }
+                _ => {}
+            }
+        }
+        store
+    }
+
+    /// p50/p90/p99 of every recorded agent run and of each task's time to
+    /// first reach Ready.
+    pub fn duration_percentiles(&self) -> DurationPercentiles {
+        let runs: Vec<f64> = self
+            .tasks
+            .values()
+            .flat_map(|task| task.run_durations_secs.iter().copied())
+            .collect();
+        let ready: Vec<f64> = self
+            .tasks
+            .values()
+            .filter_map(TaskMetrics::time_to_ready_secs)
+            .collect();
+        DurationPercentiles {
+            agent_run_secs: Percentiles::from_samples(&runs),
+            time_to_ready_secs: Percentiles::from_samples(&ready),
+        }
+    }
+
     /// Get or create task metrics.
     pub fn get_task(&mut self, task_id: &str, repo_id: &str) -> &mut TaskMetrics {
         self.tasks
@@ -357,6 +509,11 @@ impl OrchestrationMetricsStore {
         }
     }
 
+    /// Record a verify run killed at its time limit.
+    pub fn record_verify_timeout(&mut self) {
+        self.current_snapshot.verify_timeouts += 1;
+    }
+
     /// Record an E2E result.
     pub fn record_e2e(&mut self, result: &E2EResult) {
         if result.passed {
@@ -376,6 +533,15 @@ impl OrchestrationMetricsStore {
         self.current_snapshot.stops += 1;
     }
 
+    /// Record an MCP tool call rejected for `reason`.
+    pub fn record_mcp_rejection(&mut self, reason: &str) {
+        *self
+            .current_snapshot
+            .mcp_rejections
+            .entry(reason.to_string())
+            .or_insert(0) += 1;
+    }
+
     /// Record a Graphite error.
     pub fn record_graphite_error(&mut self) {
         self.current_snapshot.graphite_errors += 1;
@@ -448,6 +614,9 @@ impl OrchestrationMetricsStore {
             .collect();
         summary.recent_merges = recent.iter().map(|s| s.merges).sum();
         summary.recent_stops = recent.iter().map(|s| s.stops).sum();
+        summary.recent_mcp_rejections = recent.iter().flat_map(|s| s.mcp_rejections.values()).sum();
+
+        summary.duration_percentiles = self.duration_percentiles();
 
         summary
     }
@@ -476,6 +645,10 @@ pub struct OrchestrationSummary {
     pub error_class_counts: HashMap<String, u32>,
     pub recent_merges: u32,
     pub recent_stops: u32,
+    #[serde(default)]
+    pub recent_mcp_rejections: u32,
+    #[serde(default)]
+    pub duration_percentiles: DurationPercentiles,
 }
 
 impl OrchestrationSummary {
@@ -507,6 +680,12 @@ impl OrchestrationSummary {
         ));
         md.push_str(&format!("- **E2E Pass Rate:** {:.1}%\n\n", self.e2e_pass_rate * 100.0));
 
+        md.push_str("## Latency Percentiles\n\n");
+        for line in self.duration_percentiles.display().lines() {
+            md.push_str(&format!("- {line}\n"));
+        }
+        md.push('\n');
+
         md.push_str("## Agent Performance\n\n");
         md.push_str("| Agent | Tasks | Success Rate | Avg Duration | Recovery Rate |\n");
         md.push_str("|-------|-------|--------------|--------------|---------------|\n");
@@ -533,6 +712,10 @@ impl OrchestrationSummary {
         md.push_str("## Recent Activity (30 min)\n\n");
         md.push_str(&format!("- **Merges:** {}\n", self.recent_merges));
         md.push_str(&format!("- **Stops:** {}\n", self.recent_stops));
+        md.push_str(&format!(
+            "- **MCP Rejections:** {}\n",
+            self.recent_mcp_rejections
+        ));
 
         md
     }
@@ -640,4 +823,102 @@ mod tests {
         assert_eq!(store.current_snapshot.verify_passed, 1);
         assert_eq!(store.current_snapshot.merges, 1);
     }
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        let samples: Vec<f64> = (1..=100).rev().map(f64::from).collect();
+        let percentiles = Percentiles::from_samples(&samples);
+        assert_eq!(percentiles.samples, 100);
+        assert_eq!(percentiles.p50, Some(50.0));
+        assert_eq!(percentiles.p90, Some(90.0));
+        assert_eq!(percentiles.p99, Some(99.0));
+
+        let percentiles = Percentiles::from_samples(&[15.0, 20.0, 35.0, 40.0, 50.0]);
+        assert_eq!(percentiles.p50, Some(35.0));
+        assert_eq!(percentiles.p90, Some(50.0));
+        assert_eq!(percentiles.p99, Some(50.0));
+    }
+
+    #[test]
+    fn percentiles_degrade_gracefully_with_few_samples() {
+        let percentiles = Percentiles::from_samples(&[9.0, 3.0, 6.0]);
+        assert_eq!(percentiles.p50, Some(6.0));
+        assert_eq!(percentiles.p90, Some(9.0));
+        assert_eq!(percentiles.p99, Some(9.0));
+
+        let single = Percentiles::from_samples(&[4.0]);
+        assert_eq!((single.p50, single.p99), (Some(4.0), Some(4.0)));
+
+        let empty = Percentiles::from_samples(&[]);
+        assert_eq!(empty, Percentiles::default());
+        assert_eq!(
+            empty.display("Time to ready"),
+            "Time to ready (n=0): no samples"
+        );
+    }
+
+    #[test]
+    fn duration_percentiles_come_from_task_runs_and_ready_times() {
+        let mut store = OrchestrationMetricsStore::default();
+        let task = store.get_task("T1", "repo");
+        task.record_attempt(AgentRole::Hephaestus, 30.0);
+        task.record_attempt(AgentRole::Sisyphus, 90.0);
+        task.started_at = Utc::now() - Duration::seconds(120);
+        task.mark_ready();
+        store
+            .get_task("T2", "repo")
+            .record_attempt(AgentRole::Hephaestus, 60.0);
+
+        let percentiles = store.duration_percentiles();
+        assert_eq!(percentiles.agent_run_secs.samples, 3);
+        assert_eq!(percentiles.agent_run_secs.p50, Some(60.0));
+        assert_eq!(percentiles.agent_run_secs.p99, Some(90.0));
+        assert_eq!(percentiles.time_to_ready_secs.samples, 1);
+        let ready = percentiles.time_to_ready_secs.p50.unwrap();
+        assert!((119.0..=121.0).contains(&ready));
+        assert!(store.generate_summary().to_markdown().contains("p99 90.0s"));
+    }
+
+    #[test]
+    fn from_history_reads_agent_runs_and_first_ready() {
+        use orch_core::types::{EventId, RepoId, TaskId};
+
+        let mut task = Task::new(
+            TaskId::new("T1"),
+            RepoId("repo".to_string()),
+            "title".to_string(),
+            PathBuf::from(".orch/wt/T1"),
+        );
+        task.created_at = Utc::now() - Duration::seconds(600);
+        let event = |offset_secs: i64, kind: EventKind| Event {
+            id: EventId(format!("E-{offset_secs}")),
+            task_id: Some(task.id.clone()),
+            repo_id: Some(task.repo_id.clone()),
+            at: task.created_at + Duration::seconds(offset_secs),
+            kind,
+        };
+        let completed = |duration_secs| EventKind::AgentCompleted {
+            model: "claude".to_string(),
+            success: true,
+            duration_secs,
+        };
+        let events = vec![
+            event(500, EventKind::ReadyReached),
+            event(100, completed(80)),
+            event(
+                300,
+                EventKind::TaskStateChanged {
+                    from: "CHATTING".to_string(),
+                    to: "READY".to_string(),
+                },
+            ),
+            event(200, completed(40)),
+        ];
+
+        let store = OrchestrationMetricsStore::from_history(PathBuf::new(), &[task], &events);
+        let percentiles = store.duration_percentiles();
+        assert_eq!(percentiles.agent_run_secs.samples, 2);
+        assert_eq!(percentiles.agent_run_secs.p50, Some(40.0));
+        assert_eq!(percentiles.time_to_ready_secs.p50, Some(300.0));
+    }
 }
@@ -2,8 +2,28 @@ use orch_core::config::{PermissionRuleConfig, PermissionsConfig};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
+use std::path::Path;
 use std::str::FromStr;
 
+/// Rules saved by `othala permit`/`othala deny`, relative to the repo root.
+pub const PERMISSIONS_PATH: &str = ".othala/permissions.json";
+
+#[derive(Debug, thiserror::Error)]
+pub enum PermissionsError {
+    #[error("failed to access {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("invalid permissions file {path}: {source}")]
+    Parse {
+        path: String,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ToolPermission {
@@ -135,7 +155,7 @@ pub struct PermissionRule {
     pub reason: Option<String>,
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PermissionPolicy {
     /// Default permission for unconfigured categories
     pub default_permission: ToolPermission,
@@ -177,9 +197,94 @@ impl PermissionPolicy {
         effective
     }
 
-    /// Add a rule
+    /// Permission for `path` under `category` from the global rules.
+    ///
+    /// Unlike `check`, where the last matching rule wins, this resolves by
+    /// precedence: any matching deny wins, otherwise the most specific
+    /// matching pattern does (a rule without a pattern is the least
+    /// specific), with later rules breaking ties. Falls back to the default
+    /// permission when no rule for the category matches.
+    pub fn evaluate(&self, category: &ToolCategory, path: &str) -> ToolPermission {
+        let matching = self
+            .rules
+            .iter()
+            .filter(|rule| &rule.category == category)
+            .filter(|rule| match &rule.path_pattern {
+                None => true,
+                Some(pattern) => Self::matches_path(pattern, path),
+            })
+            .collect::<Vec<_>>();
+        if matching
+            .iter()
+            .any(|rule| rule.permission == ToolPermission::Deny)
+        {
+            return ToolPermission::Deny;
+        }
+        matching
+            .into_iter()
+            .enumerate()
+            .max_by_key(|(index, rule)| (pattern_specificity(rule.path_pattern.as_deref()), *index))
+            .map(|(_, rule)| rule.permission.clone())
+            .unwrap_or_else(|| self.default_permission.clone())
+    }
+
+    /// Add a rule, replacing any rule for the same category and path
+    /// pattern so the new one is the last word.
     pub fn add_rule(&mut self, rule: PermissionRule) {
-        self.rules.push(rule);
+        push_replacing(&mut self.rules, rule);
+    }
+
+    /// Add a rule that applies only to `model`, de-duplicated like
+    /// `add_rule`.
+    pub fn add_model_rule(&mut self, model: &str, rule: PermissionRule) {
+        push_replacing(
+            self.model_overrides.entry(model.to_string()).or_default(),
+            rule,
+        );
+    }
+
+    /// Layer `other`'s rules and model overrides on top of these. The
+    /// default permission is kept.
+    pub fn merge(&mut self, other: &PermissionPolicy) {
+        for rule in &other.rules {
+            self.add_rule(rule.clone());
+        }
+        for (model, rules) in &other.model_overrides {
+            for rule in rules {
+                self.add_model_rule(model, rule.clone());
+            }
+        }
+    }
+
+    /// Load `.othala/permissions.json` under `repo_root`; an empty policy
+    /// when the file doesn't exist.
+    pub fn load(repo_root: &Path) -> Result<Self, PermissionsError> {
+        let path = repo_root.join(PERMISSIONS_PATH);
+        match std::fs::read_to_string(&path) {
+            Ok(raw) => serde_json::from_str(&raw).map_err(|source| PermissionsError::Parse {
+                path: path.display().to_string(),
+                source,
+            }),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::new()),
+            Err(source) => Err(PermissionsError::Io {
+                path: path.display().to_string(),
+                source,
+            }),
+        }
+    }
+
+    /// Write the policy to `.othala/permissions.json` under `repo_root`.
+    pub fn save(&self, repo_root: &Path) -> Result<(), PermissionsError> {
+        let path = repo_root.join(PERMISSIONS_PATH);
+        let io_error = |source| PermissionsError::Io {
+            path: path.display().to_string(),
+            source,
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        let body = serde_json::to_string_pretty(self).map_err(|err| io_error(err.into()))?;
+        std::fs::write(&path, format!("{body}\n")).map_err(io_error)
     }
 
     /// Remove rules for a category
@@ -388,6 +493,25 @@ impl From<&PermissionRule> for PermissionRuleConfig {
     }
 }
 
+/// Append `rule` after dropping any rule it supersedes.
+fn push_replacing(rules: &mut Vec<PermissionRule>, rule: PermissionRule) {
+    rules.retain(|existing| {
+        existing.category != rule.category || existing.path_pattern != rule.path_pattern
+    });
+    rules.push(rule);
+}
+
+/// How narrowly a path pattern matches: `None` for a rule without one,
+/// otherwise the number of literal (non-wildcard) characters.
+fn pattern_specificity(pattern: Option<&str>) -> Option<usize> {
+    pattern.map(|pattern| {
+        normalize_path_component(pattern)
+            .chars()
+            .filter(|ch| !matches!(ch, '*' | '?'))
+            .count()
+    })
+}
+
 fn normalize_token(value: &str) -> String {
     value.trim().to_lowercase().replace('-', "_")
 }
@@ -684,6 +808,154 @@ mod tests {
         assert_eq!(decoded.rules.len(), policy.rules.len());
     }
 
+    fn rule(category: ToolCategory, permission: ToolPermission, path: &str) -> PermissionRule {
+        PermissionRule {
+            category,
+            permission,
+            path_pattern: Some(path.to_string()),
+            reason: None,
+        }
+    }
+
+    fn shell_rule(permission: ToolPermission, path: &str) -> PermissionRule {
+        rule(ToolCategory::ShellExec, permission, path)
+    }
+
+    fn write_rule(permission: ToolPermission, path: &str) -> PermissionRule {
+        rule(ToolCategory::FileWrite, permission, path)
+    }
+
+    #[test]
+    fn add_rule_allows_then_a_deny_replaces_the_same_rule() {
+        let mut policy = PermissionPolicy::new();
+        policy.add_rule(shell_rule(ToolPermission::Allow, "src/**"));
+        policy.add_rule(shell_rule(ToolPermission::Allow, "src/**"));
+        assert_eq!(policy.rules.len(), 1);
+        assert_eq!(
+            policy.check(&ToolCategory::ShellExec, Some("src/lib.rs"), None),
+            ToolPermission::Allow
+        );
+
+        policy.add_rule(shell_rule(ToolPermission::Deny, "src/**"));
+        assert_eq!(policy.rules.len(), 1);
+        assert_eq!(
+            policy.check(&ToolCategory::ShellExec, Some("src/lib.rs"), None),
+            ToolPermission::Deny
+        );
+
+        policy.add_rule(shell_rule(ToolPermission::Allow, "docs/**"));
+        assert_eq!(policy.rules.len(), 2);
+    }
+
+    #[test]
+    fn persisted_rules_round_trip_and_layer_over_config_rules() {
+        let root = std::env::temp_dir().join(format!(
+            "othala-permissions-{}-{}",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        assert_eq!(
+            PermissionPolicy::load(&root).expect("missing file"),
+            PermissionPolicy::new()
+        );
+
+        let mut persisted = PermissionPolicy::new();
+        persisted.add_rule(shell_rule(ToolPermission::Allow, "src/**"));
+        persisted.add_model_rule(
+            "codex",
+            rule(ToolCategory::Network, ToolPermission::Deny, "**"),
+        );
+        persisted.save(&root).expect("save");
+        let loaded = PermissionPolicy::load(&root).expect("load");
+        assert_eq!(loaded, persisted);
+
+        let mut policy = PermissionPolicy::restrictive();
+        policy.merge(&loaded);
+        assert_eq!(policy.default_permission, ToolPermission::Deny);
+        assert_eq!(
+            policy.check(&ToolCategory::ShellExec, Some("src/main.rs"), None),
+            ToolPermission::Allow
+        );
+        assert_eq!(
+            policy.check(&ToolCategory::Network, Some("api"), Some("codex")),
+            ToolPermission::Deny
+        );
+
+        std::fs::write(root.join(PERMISSIONS_PATH), "{not json").expect("corrupt file");
+        assert!(matches!(
+            PermissionPolicy::load(&root),
+            Err(PermissionsError::Parse { .. })
+        ));
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn evaluate_matches_globs_and_absent_patterns_cover_the_category() {
+        let mut policy = PermissionPolicy::new();
+        policy.add_rule(write_rule(ToolPermission::Allow, "src/**"));
+        assert_eq!(
+            policy.evaluate(&ToolCategory::FileWrite, "src/a/b.rs"),
+            ToolPermission::Allow
+        );
+        assert_eq!(
+            policy.evaluate(&ToolCategory::FileWrite, "docs/a.md"),
+            ToolPermission::Ask
+        );
+
+        policy.add_rule(PermissionRule {
+            category: ToolCategory::FileWrite,
+            permission: ToolPermission::Ask,
+            path_pattern: None,
+            reason: None,
+        });
+        policy.add_rule(write_rule(ToolPermission::Ask, "src/*.rs"));
+        assert_eq!(
+            policy.evaluate(&ToolCategory::FileWrite, "docs/a.md"),
+            ToolPermission::Ask
+        );
+        // `src/*.rs` is more specific than `src/**`, whichever was added last.
+        assert_eq!(
+            policy.evaluate(&ToolCategory::FileWrite, "src/lib.rs"),
+            ToolPermission::Ask
+        );
+        assert_eq!(
+            policy.evaluate(&ToolCategory::FileWrite, "src/a/b.rs"),
+            ToolPermission::Allow
+        );
+    }
+
+    #[test]
+    fn evaluate_prefers_a_deny_over_a_broad_allow() {
+        let mut policy = PermissionPolicy::new();
+        policy.add_rule(write_rule(ToolPermission::Deny, "src/secrets/**"));
+        policy.add_rule(write_rule(ToolPermission::Allow, "**"));
+        policy.add_rule(write_rule(ToolPermission::Allow, "src/secrets/keep.txt"));
+
+        assert_eq!(
+            policy.evaluate(&ToolCategory::FileWrite, "src/secrets/keep.txt"),
+            ToolPermission::Deny
+        );
+        assert_eq!(
+            policy.evaluate(&ToolCategory::FileWrite, "src/main.rs"),
+            ToolPermission::Allow
+        );
+    }
+
+    #[test]
+    fn evaluate_falls_through_to_the_default_on_a_category_mismatch() {
+        let mut policy = PermissionPolicy::permissive();
+        policy.add_rule(write_rule(ToolPermission::Deny, "**"));
+
+        assert_eq!(
+            policy.evaluate(&ToolCategory::FileRead, "src/lib.rs"),
+            ToolPermission::Allow
+        );
+        assert_eq!(
+            policy.evaluate(&ToolCategory::FileWrite, "src/lib.rs"),
+            ToolPermission::Deny
+        );
+    }
+
     #[test]
     fn wildcard_match_supports_prefix_suffix_and_double_star() {
         assert!(PermissionPolicy::matches_path("src/**", "src/bin/main.rs"));
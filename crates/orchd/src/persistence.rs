@@ -1,14 +1,17 @@
 //! MVP persistence layer using SQLite.
 
 use chrono::{DateTime, Utc};
-use orch_core::events::{Event, EventKind};
+use orch_core::events::{Event, EventKind, FailureCause};
 use orch_core::state::TaskState;
 use orch_core::types::{ModelKind, Session, SessionStatus, Task, TaskId, TaskPriority};
 use rusqlite::{params, Connection, OptionalExtension};
 use std::path::{Path, PathBuf};
 
 use crate::state_machine::task_state_tag;
-use crate::types::{ArtifactRecord, TaskRunRecord};
+use crate::types::{
+    AgentQuestion, ArtifactRecord, BenchResult, MergeQueueEntry, MergeQueueSnapshot,
+    TaskRunRecord,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ArchivedTaskRecord {
@@ -51,6 +54,21 @@ pub enum PersistenceError {
     SessionNotFound { session_id: String },
 }
 
+/// Tables created by `SqliteStore::migrate`.
+pub const SCHEMA_TABLES: &[&str] = &[
+    "tasks",
+    "events",
+    "runs",
+    "artifacts",
+    "sessions",
+    "archived_tasks",
+    "task_aliases",
+    "merge_queue",
+    "merge_queue_meta",
+    "agent_questions",
+    "bench_results",
+];
+
 /// SQLite-based store for tasks and events.
 #[derive(Debug)]
 pub struct SqliteStore {
@@ -82,6 +100,17 @@ impl SqliteStore {
         Ok(Self { conn })
     }
 
+    /// Open an existing database without write access, so readers such as
+    /// `othala export-analytics` cannot disturb a running daemon.
+    pub fn open_read_only(path: impl AsRef<Path>) -> Result<Self, PersistenceError> {
+        let conn = Connection::open_with_flags(
+            path.as_ref(),
+            rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )?;
+        conn.execute_batch("PRAGMA busy_timeout=5000;")?;
+        Ok(Self { conn })
+    }
+
     pub fn open_in_memory() -> Result<Self, PersistenceError> {
         let conn = Connection::open_in_memory()?;
         Ok(Self { conn })
@@ -129,6 +158,21 @@ impl SqliteStore {
         Ok(canonical_parent.join(file_name))
     }
 
+    /// Tables from `SCHEMA_TABLES` that don't exist in this database.
+    pub fn missing_schema_tables(&self) -> Result<Vec<String>, PersistenceError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT name FROM sqlite_master WHERE type = 'table'")?;
+        let existing = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(SCHEMA_TABLES
+            .iter()
+            .filter(|table| !existing.iter().any(|name| name == *table))
+            .map(|table| table.to_string())
+            .collect())
+    }
+
     pub fn migrate(&self) -> Result<(), PersistenceError> {
         self.conn.execute_batch(
             r#"
@@ -206,6 +250,52 @@ CREATE TABLE IF NOT EXISTS archived_tasks (
 
 CREATE INDEX IF NOT EXISTS idx_archived_tasks_repo ON archived_tasks(repo_id);
 CREATE INDEX IF NOT EXISTS idx_archived_tasks_archived_at ON archived_tasks(archived_at);
+
+CREATE TABLE IF NOT EXISTS task_aliases (
+    alias TEXT PRIMARY KEY,
+    task_id TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_task_aliases_task ON task_aliases(task_id);
+
+CREATE TABLE IF NOT EXISTS merge_queue (
+    task_id TEXT PRIMARY KEY,
+    position INTEGER NOT NULL,
+    enqueued_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS merge_queue_meta (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    version INTEGER NOT NULL
+);
+
+INSERT OR IGNORE INTO merge_queue_meta (id, version) VALUES (1, 0);
+
+CREATE TABLE IF NOT EXISTS agent_questions (
+    question_id TEXT PRIMARY KEY,
+    task_id TEXT NOT NULL,
+    question TEXT NOT NULL,
+    asked_at TEXT NOT NULL,
+    answer TEXT,
+    answered_at TEXT,
+    escalated_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_agent_questions_task ON agent_questions(task_id, asked_at);
+
+CREATE TABLE IF NOT EXISTS bench_results (
+    tag TEXT NOT NULL,
+    spec TEXT NOT NULL,
+    iteration INTEGER NOT NULL,
+    task_id TEXT NOT NULL,
+    outcome TEXT NOT NULL,
+    retries INTEGER NOT NULL,
+    tokens INTEGER NOT NULL,
+    duration_secs REAL NOT NULL,
+    recorded_at TEXT NOT NULL,
+    PRIMARY KEY (tag, spec, iteration)
+);
 "#,
         )?;
 
@@ -260,9 +350,56 @@ CREATE INDEX IF NOT EXISTS idx_archived_tasks_archived_at ON archived_tasks(arch
                 return Err(err.into());
             }
         }
+
+        if let Err(err) = self.conn.execute(
+            "ALTER TABLE runs ADD COLUMN confidence INTEGER DEFAULT NULL",
+            [],
+        ) {
+            if !matches!(
+                &err,
+                rusqlite::Error::SqliteFailure(_, Some(message))
+                    if message.contains("duplicate column name: confidence")
+            ) {
+                return Err(err.into());
+            }
+        }
+
+        self.backfill_failure_causes()?;
         Ok(())
     }
 
+    /// Classify `TaskFailed` events recorded before failures carried a
+    /// cause. Returns the number of events rewritten.
+    fn backfill_failure_causes(&self) -> Result<usize, PersistenceError> {
+        let pending = {
+            let mut stmt = self.conn.prepare(
+                "SELECT event_id, payload_json FROM events WHERE kind_tag = 'task_failed' AND payload_json NOT LIKE '%\"cause\"%'",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?;
+            rows.collect::<Result<Vec<_>, _>>()?
+        };
+
+        let mut updated = 0;
+        for (event_id, payload) in pending {
+            let mut event = serde_json::from_str::<Event>(&payload)?;
+            let EventKind::TaskFailed { reason, cause, .. } = &mut event.kind else {
+                continue;
+            };
+            if cause.is_some() {
+                continue;
+            }
+            *cause = Some(FailureCause::classify(reason));
+            self.conn.execute(
+                "UPDATE events SET payload_json = ?1 WHERE event_id = ?2",
+                params![serde_json::to_string(&event)?, event_id],
+            )?;
+            updated += 1;
+        }
+        Ok(updated)
+    }
+
     // --- Task CRUD ---
 
     pub fn upsert_task(&self, task: &Task) -> Result<(), PersistenceError> {
@@ -550,7 +687,6 @@ WHERE session_id = ?1
         state: Option<&str>,
     ) -> Result<Vec<Task>, PersistenceError> {
         let query_lc = query.to_lowercase();
-        let label_lc = label.map(|value| value.to_lowercase());
         let state_lc = state.map(|value| value.trim().to_lowercase().replace('-', "_"));
 
         let mut tasks = self.list_tasks()?;
@@ -565,12 +701,8 @@ WHERE session_id = ?1
                 return false;
             }
 
-            if let Some(ref expected_label) = label_lc {
-                let has_label = task
-                    .labels
-                    .iter()
-                    .any(|existing| existing.eq_ignore_ascii_case(expected_label));
-                if !has_label {
+            if let Some(expected_label) = label {
+                if !task.has_label(expected_label) {
                     return false;
                 }
             }
@@ -593,6 +725,24 @@ WHERE session_id = ?1
             "DELETE FROM runs WHERE task_id = ?1",
             params![task_id.0.as_str()],
         )?;
+        self.conn.execute(
+            "DELETE FROM task_aliases WHERE task_id = ?1",
+            params![task_id.0.as_str()],
+        )?;
+        let dequeued = self.conn.execute(
+            "DELETE FROM merge_queue WHERE task_id = ?1",
+            params![task_id.0.as_str()],
+        )?;
+        if dequeued > 0 {
+            self.conn.execute(
+                "UPDATE merge_queue_meta SET version = version + 1 WHERE id = 1",
+                [],
+            )?;
+        }
+        self.conn.execute(
+            "DELETE FROM agent_questions WHERE task_id = ?1",
+            params![task_id.0.as_str()],
+        )?;
         self.conn.execute(
             "DELETE FROM artifacts WHERE task_id = ?1",
             params![task_id.0.as_str()],
@@ -608,6 +758,325 @@ WHERE session_id = ?1
         Ok(deleted > 0)
     }
 
+    pub fn list_task_ids(&self) -> Result<Vec<TaskId>, PersistenceError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT task_id FROM tasks ORDER BY task_id ASC")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        let mut ids = Vec::new();
+        for row in rows {
+            ids.push(TaskId(row?));
+        }
+        Ok(ids)
+    }
+
+    // --- Task aliases ---
+
+    pub fn upsert_task_alias(&self, alias: &str, task_id: &TaskId) -> Result<(), PersistenceError> {
+        self.conn.execute(
+            r#"
+INSERT INTO task_aliases (alias, task_id, created_at)
+VALUES (?1, ?2, ?3)
+ON CONFLICT(alias) DO UPDATE SET task_id = excluded.task_id
+"#,
+            params![alias, task_id.0.as_str(), Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+
+    pub fn load_task_alias(&self, alias: &str) -> Result<Option<TaskId>, PersistenceError> {
+        self.conn
+            .query_row(
+                "SELECT task_id FROM task_aliases WHERE alias = ?1",
+                params![alias],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()
+            .map(|id| id.map(TaskId))
+            .map_err(PersistenceError::from)
+    }
+
+    pub fn list_task_aliases(&self) -> Result<Vec<(String, TaskId)>, PersistenceError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT alias, task_id FROM task_aliases ORDER BY alias ASC")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, TaskId(row.get::<_, String>(1)?)))
+        })?;
+        let mut aliases = Vec::new();
+        for row in rows {
+            aliases.push(row?);
+        }
+        Ok(aliases)
+    }
+
+    // --- Merge queue ---
+
+    pub fn load_merge_queue(&self) -> Result<MergeQueueSnapshot, PersistenceError> {
+        let version = self.conn.query_row(
+            "SELECT version FROM merge_queue_meta WHERE id = 1",
+            [],
+            |row| row.get::<_, i64>(0),
+        )?;
+        let mut stmt = self.conn.prepare(
+            "SELECT task_id, position, enqueued_at FROM merge_queue ORDER BY position ASC",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?;
+        let mut entries = Vec::new();
+        for row in rows {
+            let (task_id, position, enqueued_at) = row?;
+            entries.push(MergeQueueEntry {
+                task_id: TaskId(task_id),
+                position: position as usize,
+                enqueued_at: DateTime::parse_from_rfc3339(&enqueued_at)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .map_err(|source| PersistenceError::TimestampParse {
+                        value: enqueued_at.clone(),
+                        source,
+                    })?,
+            });
+        }
+        Ok(MergeQueueSnapshot {
+            version: version as u64,
+            entries,
+        })
+    }
+
+    /// Replace the queue with `order` if the stored version still equals
+    /// `expected_version`. Returns `None` on a version mismatch.
+    ///
+    /// Entries that were already queued keep their original `enqueued_at`.
+    pub fn replace_merge_queue(
+        &self,
+        expected_version: u64,
+        order: &[TaskId],
+    ) -> Result<Option<MergeQueueSnapshot>, PersistenceError> {
+        let tx = self.conn.unchecked_transaction()?;
+        let current = self.load_merge_queue()?;
+        if current.version != expected_version {
+            return Ok(None);
+        }
+
+        let now = Utc::now();
+        tx.execute("DELETE FROM merge_queue", [])?;
+        for (position, task_id) in order.iter().enumerate() {
+            let enqueued_at = current
+                .entries
+                .iter()
+                .find(|entry| entry.task_id == *task_id)
+                .map(|entry| entry.enqueued_at)
+                .unwrap_or(now);
+            tx.execute(
+                "INSERT INTO merge_queue (task_id, position, enqueued_at) VALUES (?1, ?2, ?3)",
+                params![task_id.0.as_str(), position as i64, enqueued_at.to_rfc3339()],
+            )?;
+        }
+        tx.execute(
+            "UPDATE merge_queue_meta SET version = version + 1 WHERE id = 1",
+            [],
+        )?;
+        tx.commit()?;
+        self.load_merge_queue().map(Some)
+    }
+
+    // --- Agent questions ---
+
+    pub fn insert_question(&self, question: &AgentQuestion) -> Result<(), PersistenceError> {
+        self.conn.execute(
+            r#"
+INSERT INTO agent_questions (question_id, task_id, question, asked_at, answer, answered_at, escalated_at)
+VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+"#,
+            params![
+                question.question_id,
+                question.task_id.0.as_str(),
+                question.question,
+                question.asked_at.to_rfc3339(),
+                question.answer,
+                question.answered_at.map(|at| at.to_rfc3339()),
+                question.escalated_at.map(|at| at.to_rfc3339()),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Every question for a task, oldest first.
+    pub fn list_questions_for_task(
+        &self,
+        task_id: &TaskId,
+    ) -> Result<Vec<AgentQuestion>, PersistenceError> {
+        self.query_questions(
+            "SELECT question_id, task_id, question, asked_at, answer, answered_at, escalated_at \
+             FROM agent_questions WHERE task_id = ?1 ORDER BY asked_at ASC, question_id ASC",
+            params![task_id.0.as_str()],
+        )
+    }
+
+    /// Unanswered questions across all tasks, oldest first.
+    pub fn list_open_questions(&self) -> Result<Vec<AgentQuestion>, PersistenceError> {
+        self.query_questions(
+            "SELECT question_id, task_id, question, asked_at, answer, answered_at, escalated_at \
+             FROM agent_questions WHERE answered_at IS NULL ORDER BY asked_at ASC, question_id ASC",
+            [],
+        )
+    }
+
+    /// Resolve every open question on a task with `answer`, returning the
+    /// questions that were resolved.
+    pub fn answer_open_questions(
+        &self,
+        task_id: &TaskId,
+        answer: &str,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<AgentQuestion>, PersistenceError> {
+        let mut open: Vec<AgentQuestion> = self
+            .list_questions_for_task(task_id)?
+            .into_iter()
+            .filter(AgentQuestion::is_open)
+            .collect();
+        if open.is_empty() {
+            return Ok(open);
+        }
+        self.conn.execute(
+            "UPDATE agent_questions SET answer = ?1, answered_at = ?2 WHERE task_id = ?3 AND answered_at IS NULL",
+            params![answer, at.to_rfc3339(), task_id.0.as_str()],
+        )?;
+        for question in &mut open {
+            question.answer = Some(answer.to_string());
+            question.answered_at = Some(at);
+        }
+        Ok(open)
+    }
+
+    pub fn mark_question_escalated(
+        &self,
+        question_id: &str,
+        at: DateTime<Utc>,
+    ) -> Result<(), PersistenceError> {
+        self.conn.execute(
+            "UPDATE agent_questions SET escalated_at = ?1 WHERE question_id = ?2",
+            params![at.to_rfc3339(), question_id],
+        )?;
+        Ok(())
+    }
+
+    // --- Benchmark results ---
+
+    pub fn insert_bench_result(&self, result: &BenchResult) -> Result<(), PersistenceError> {
+        self.conn.execute(
+            r#"
+INSERT OR REPLACE INTO bench_results
+    (tag, spec, iteration, task_id, outcome, retries, tokens, duration_secs, recorded_at)
+VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+"#,
+            params![
+                result.tag,
+                result.spec,
+                result.iteration,
+                result.task_id.0.as_str(),
+                result.outcome.as_str(),
+                result.retries,
+                result.tokens,
+                result.duration_secs,
+                result.recorded_at.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Results recorded under `tag`, ordered by spec then iteration.
+    pub fn list_bench_results(&self, tag: &str) -> Result<Vec<BenchResult>, PersistenceError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tag, spec, iteration, task_id, outcome, retries, tokens, duration_secs, recorded_at \
+             FROM bench_results WHERE tag = ?1 ORDER BY spec ASC, iteration ASC",
+        )?;
+        let rows = stmt.query_map(params![tag], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, u32>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, u32>(5)?,
+                row.get::<_, u64>(6)?,
+                row.get::<_, f64>(7)?,
+                row.get::<_, String>(8)?,
+            ))
+        })?;
+        let mut results = Vec::new();
+        for row in rows {
+            let (tag, spec, iteration, task_id, outcome, retries, tokens, duration_secs, at) = row?;
+            results.push(BenchResult {
+                tag,
+                spec,
+                iteration,
+                task_id: TaskId(task_id),
+                outcome: serde_json::from_value(serde_json::Value::String(outcome))?,
+                retries,
+                tokens,
+                duration_secs,
+                recorded_at: DateTime::parse_from_rfc3339(&at)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .map_err(|source| PersistenceError::TimestampParse {
+                        value: at.clone(),
+                        source,
+                    })?,
+            });
+        }
+        Ok(results)
+    }
+
+    /// Drop every result recorded under `tag` so a re-run starts clean.
+    pub fn delete_bench_results(&self, tag: &str) -> Result<usize, PersistenceError> {
+        Ok(self
+            .conn
+            .execute("DELETE FROM bench_results WHERE tag = ?1", params![tag])?)
+    }
+
+    fn query_questions(
+        &self,
+        sql: &str,
+        params: impl rusqlite::Params,
+    ) -> Result<Vec<AgentQuestion>, PersistenceError> {
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt.query_map(params, |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, Option<String>>(4)?,
+                row.get::<_, Option<String>>(5)?,
+                row.get::<_, Option<String>>(6)?,
+            ))
+        })?;
+        let mut questions = Vec::new();
+        for row in rows {
+            let (question_id, task_id, question, asked_at, answer, answered_at, escalated_at) = row?;
+            questions.push(AgentQuestion {
+                question_id,
+                task_id: TaskId(task_id),
+                question,
+                asked_at: DateTime::parse_from_rfc3339(&asked_at)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .map_err(|source| PersistenceError::TimestampParse {
+                        value: asked_at.clone(),
+                        source,
+                    })?,
+                answer,
+                answered_at: parse_optional_rfc3339(answered_at)?,
+                escalated_at: parse_optional_rfc3339(escalated_at)?,
+            });
+        }
+        Ok(questions)
+    }
+
     pub fn list_tasks_by_state(&self, state: TaskState) -> Result<Vec<Task>, PersistenceError> {
         let mut stmt = self.conn.prepare(
             "SELECT payload_json, priority, labels_json FROM tasks WHERE state_tag = ?1 ORDER BY updated_at DESC, task_id ASC",
@@ -792,10 +1261,43 @@ VALUES (?1, ?2, ?3, ?4, ?5, ?6)
         Ok(events)
     }
 
+    pub fn list_events_by_kind(&self, kind_tag: &str) -> Result<Vec<Event>, PersistenceError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT payload_json FROM events WHERE kind_tag = ?1 ORDER BY at ASC, event_id ASC",
+        )?;
+        let rows = stmt.query_map(params![kind_tag], |row| row.get::<_, String>(0))?;
+        let mut events = Vec::new();
+        for row in rows {
+            let payload = row?;
+            events.push(serde_json::from_str::<Event>(&payload)?);
+        }
+        Ok(events)
+    }
+
     pub fn list_events_global(&self) -> Result<Vec<Event>, PersistenceError> {
         self.list_all_events(None, None)
     }
 
+    /// Events inserted after row `after_rowid`, with their rowids, in
+    /// insertion order.
+    pub fn list_events_after_rowid(
+        &self,
+        after_rowid: i64,
+    ) -> Result<Vec<(i64, Event)>, PersistenceError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT rowid, payload_json FROM events WHERE rowid > ?1 ORDER BY rowid ASC",
+        )?;
+        let rows = stmt.query_map(params![after_rowid], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?;
+        let mut events = Vec::new();
+        for row in rows {
+            let (rowid, payload) = row?;
+            events.push((rowid, serde_json::from_str::<Event>(&payload)?));
+        }
+        Ok(events)
+    }
+
     pub fn task_count_by_state(&self) -> Result<Vec<(String, i64)>, PersistenceError> {
         let mut stmt = self
             .conn
@@ -823,8 +1325,8 @@ VALUES (?1, ?2, ?3, ?4, ?5, ?6)
         let payload = serde_json::to_string(run)?;
         self.conn.execute(
             r#"
-INSERT INTO runs (run_id, task_id, model, started_at, finished_at, stop_reason, exit_code, estimated_tokens, duration_secs, payload_json)
-VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+INSERT INTO runs (run_id, task_id, model, started_at, finished_at, stop_reason, exit_code, estimated_tokens, duration_secs, confidence, payload_json)
+VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
 "#,
             params![
                 run.run_id,
@@ -836,6 +1338,7 @@ VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
                 run.exit_code,
                 run.estimated_tokens,
                 run.duration_secs,
+                run.confidence,
                 payload
             ],
         )?;
@@ -858,6 +1361,22 @@ WHERE task_id = ?2 AND finished_at IS NULL
         Ok(updated)
     }
 
+    pub fn set_open_run_confidence(
+        &self,
+        task_id: &TaskId,
+        confidence: u8,
+    ) -> Result<usize, PersistenceError> {
+        let updated = self.conn.execute(
+            r#"
+UPDATE runs
+SET confidence = ?1
+WHERE task_id = ?2 AND finished_at IS NULL
+"#,
+            params![confidence, task_id.0],
+        )?;
+        Ok(updated)
+    }
+
     pub fn finish_open_runs_for_task(
         &self,
         task_id: &TaskId,
@@ -884,42 +1403,38 @@ WHERE task_id = ?5 AND finished_at IS NULL
     }
 
     pub fn list_open_runs(&self) -> Result<Vec<TaskRunRecord>, PersistenceError> {
-        let mut stmt = self.conn.prepare(
-            "SELECT payload_json, finished_at, stop_reason, exit_code, estimated_tokens, duration_secs FROM runs WHERE finished_at IS NULL ORDER BY started_at ASC, run_id ASC",
-        )?;
-        let rows = stmt.query_map([], |row| {
-            Ok((
-                row.get::<_, String>(0)?,
-                row.get::<_, Option<String>>(1)?,
-                row.get::<_, Option<String>>(2)?,
-                row.get::<_, Option<i32>>(3)?,
-                row.get::<_, Option<u64>>(4)?,
-                row.get::<_, Option<f64>>(5)?,
-            ))
-        })?;
-        let mut runs = Vec::new();
-        for row in rows {
-            let (payload, finished_at, stop_reason, exit_code, estimated_tokens, duration_secs) =
-                row?;
-            let mut run = serde_json::from_str::<TaskRunRecord>(&payload)?;
-            run.finished_at = parse_optional_rfc3339(finished_at)?;
-            run.stop_reason = stop_reason;
-            run.exit_code = exit_code;
-            run.estimated_tokens = estimated_tokens.or(run.estimated_tokens);
-            run.duration_secs = duration_secs.or(run.duration_secs);
-            runs.push(run);
-        }
-        Ok(runs)
+        self.query_runs(
+            "SELECT payload_json, finished_at, stop_reason, exit_code, estimated_tokens, duration_secs, confidence FROM runs WHERE finished_at IS NULL ORDER BY started_at ASC, run_id ASC",
+            [],
+        )
     }
 
     pub fn list_runs_for_task(
         &self,
         task_id: &TaskId,
     ) -> Result<Vec<TaskRunRecord>, PersistenceError> {
-        let mut stmt = self.conn.prepare(
-            "SELECT payload_json, finished_at, stop_reason, exit_code, estimated_tokens, duration_secs FROM runs WHERE task_id = ?1 ORDER BY started_at ASC, run_id ASC",
-        )?;
-        let rows = stmt.query_map(params![task_id.0], |row| {
+        self.query_runs(
+            "SELECT payload_json, finished_at, stop_reason, exit_code, estimated_tokens, duration_secs, confidence FROM runs WHERE task_id = ?1 ORDER BY started_at ASC, run_id ASC",
+            params![task_id.0],
+        )
+    }
+
+    pub fn list_runs(&self) -> Result<Vec<TaskRunRecord>, PersistenceError> {
+        self.query_runs(
+            "SELECT payload_json, finished_at, stop_reason, exit_code, estimated_tokens, duration_secs, confidence FROM runs ORDER BY started_at ASC, run_id ASC",
+            [],
+        )
+    }
+
+    /// Decode run rows selected as `payload_json, finished_at, stop_reason,
+    /// exit_code, estimated_tokens, duration_secs, confidence`.
+    fn query_runs(
+        &self,
+        sql: &str,
+        params: impl rusqlite::Params,
+    ) -> Result<Vec<TaskRunRecord>, PersistenceError> {
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt.query_map(params, |row| {
             Ok((
                 row.get::<_, String>(0)?,
                 row.get::<_, Option<String>>(1)?,
@@ -927,18 +1442,27 @@ WHERE task_id = ?5 AND finished_at IS NULL
                 row.get::<_, Option<i32>>(3)?,
                 row.get::<_, Option<u64>>(4)?,
                 row.get::<_, Option<f64>>(5)?,
+                row.get::<_, Option<u8>>(6)?,
             ))
         })?;
         let mut runs = Vec::new();
         for row in rows {
-            let (payload, finished_at, stop_reason, exit_code, estimated_tokens, duration_secs) =
-                row?;
+            let (
+                payload,
+                finished_at,
+                stop_reason,
+                exit_code,
+                estimated_tokens,
+                duration_secs,
+                confidence,
+            ) = row?;
             let mut run = serde_json::from_str::<TaskRunRecord>(&payload)?;
             run.finished_at = parse_optional_rfc3339(finished_at)?;
             run.stop_reason = stop_reason;
             run.exit_code = exit_code;
             run.estimated_tokens = estimated_tokens.or(run.estimated_tokens);
             run.duration_secs = duration_secs.or(run.duration_secs);
+            run.confidence = confidence.or(run.confidence);
             runs.push(run);
         }
         Ok(runs)
@@ -979,6 +1503,22 @@ VALUES (?1, ?2, ?3, ?4, ?5, ?6)
         Ok(())
     }
 
+    pub fn list_artifacts_for_task(
+        &self,
+        task_id: &TaskId,
+        kind: &str,
+    ) -> Result<Vec<ArtifactRecord>, PersistenceError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT payload_json FROM artifacts WHERE task_id = ?1 AND kind = ?2 ORDER BY created_at ASC, artifact_id ASC",
+        )?;
+        let rows = stmt.query_map(params![task_id.0, kind], |row| row.get::<_, String>(0))?;
+        let mut artifacts = Vec::new();
+        for row in rows {
+            artifacts.push(serde_json::from_str::<ArtifactRecord>(&row?)?);
+        }
+        Ok(artifacts)
+    }
+
     pub fn latest_event_at_for_task(
         &self,
         task_id: &TaskId,
@@ -1053,6 +1593,8 @@ fn event_kind_tag(kind: &EventKind) -> &'static str {
         EventKind::RetryScheduled { .. } => "retry_scheduled",
         EventKind::AgentSpawned { .. } => "agent_spawned",
         EventKind::AgentCompleted { .. } => "agent_completed",
+        EventKind::AgentSuspended { .. } => "agent_suspended",
+        EventKind::AgentResumed { .. } => "agent_resumed",
         EventKind::CancellationRequested { .. } => "cancellation_requested",
         EventKind::ModelFallback { .. } => "model_fallback",
         EventKind::ContextRegenStarted => "context_regen_started",
@@ -1068,6 +1610,13 @@ fn event_kind_tag(kind: &EventKind) -> &'static str {
         EventKind::TaskRespawned { .. } => "task_respawned",
         EventKind::GraphiteSyncStarted => "graphite_sync_started",
         EventKind::GraphiteSyncCompleted { .. } => "graphite_sync_completed",
+        EventKind::QuestionAsked { .. } => "question_asked",
+        EventKind::QuestionAnswered { .. } => "question_answered",
+        EventKind::QuestionStale { .. } => "question_stale",
+        EventKind::PipelineStepStarted { .. } => "pipeline_step_started",
+        EventKind::PipelineStepCompleted { .. } => "pipeline_step_completed",
+        EventKind::DaemonControl { .. } => "daemon_control",
+        EventKind::MergeDetected { .. } => "merge_detected",
     }
 }
 
@@ -1356,6 +1905,7 @@ mod tests {
             exit_code: None,
             estimated_tokens: Some(42),
             duration_secs: None,
+            confidence: None,
         };
 
         store.insert_run(&run).expect("insert");
@@ -1378,6 +1928,7 @@ mod tests {
             exit_code: None,
             estimated_tokens: Some(88),
             duration_secs: None,
+            confidence: None,
         };
 
         store.insert_run(&run).expect("insert");
@@ -1410,6 +1961,7 @@ mod tests {
             exit_code: None,
             estimated_tokens: None,
             duration_secs: None,
+            confidence: None,
         };
 
         store.insert_run(&run).expect("insert");
@@ -1613,4 +2165,53 @@ mod tests {
             .expect("query journal_mode");
         assert_eq!(mode, "wal");
     }
+
+    #[test]
+    fn migrate_backfills_failure_causes_on_legacy_task_failed_events() {
+        let store = mk_store();
+        let legacy = r#"{"id":"E-FAILED-T1-1","task_id":"T1","repo_id":null,"at":"2026-03-01T00:00:00Z","kind":{"task_failed":{"reason":"restack failed with conflicts","is_final":true}}}"#;
+        store
+            .conn
+            .execute(
+                "INSERT INTO events (event_id, task_id, repo_id, at, kind_tag, payload_json) VALUES ('E-FAILED-T1-1', 'T1', NULL, '2026-03-01T00:00:00Z', 'task_failed', ?1)",
+                params![legacy],
+            )
+            .expect("insert legacy event");
+
+        store.migrate().expect("migrate again");
+
+        let events = store.list_events_by_kind("task_failed").expect("list");
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            events[0].kind,
+            EventKind::TaskFailed {
+                cause: Some(FailureCause::RestackConflict),
+                ..
+            }
+        ));
+        assert_eq!(store.backfill_failure_causes().expect("backfill"), 0);
+    }
+
+    #[test]
+    fn task_aliases_roundtrip_and_are_removed_with_task() {
+        let store = mk_store();
+        let task = mk_task("chat-1760000000123", TaskState::Chatting);
+        store.upsert_task(&task).expect("upsert");
+
+        store
+            .upsert_task_alias("auth-fix", &task.id)
+            .expect("alias");
+        assert_eq!(
+            store.load_task_alias("auth-fix").expect("load alias"),
+            Some(task.id.clone())
+        );
+        assert_eq!(
+            store.list_task_aliases().expect("list aliases"),
+            vec![("auth-fix".to_string(), task.id.clone())]
+        );
+        assert_eq!(store.list_task_ids().expect("ids"), vec![task.id.clone()]);
+
+        store.delete_task(&task.id).expect("delete");
+        assert_eq!(store.load_task_alias("auth-fix").expect("load alias"), None);
+    }
 }
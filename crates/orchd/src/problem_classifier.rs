@@ -365,6 +365,31 @@ pub enum RecoveryAction {
     Stop,
 }
 
+/// What the daemon's failure handler should do next for a failed agent run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuggestedAction {
+    /// Run the same model again; the failure is transient or self-fixable.
+    RetrySameModel,
+    /// Hand the task to the next healthy model.
+    FallbackModel,
+    /// Refresh repo context before retrying; the agent misread its environment.
+    RegenerateContext,
+    /// Stop retrying and ask a human.
+    EscalateToHuman,
+}
+
+/// Map an error class to the path the failure handler takes.
+pub fn suggested_action(class: ErrorClass) -> SuggestedAction {
+    match class {
+        ErrorClass::Compile | ErrorClass::Logic => SuggestedAction::RetrySameModel,
+        ErrorClass::Network | ErrorClass::Resource => SuggestedAction::RetrySameModel,
+        ErrorClass::Config | ErrorClass::Environment => SuggestedAction::RegenerateContext,
+        ErrorClass::Permission | ErrorClass::Git => SuggestedAction::EscalateToHuman,
+        ErrorClass::Agent | ErrorClass::Unknown => SuggestedAction::FallbackModel,
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Problem Classifier
 // ─────────────────────────────────────────────────────────────────────────────
@@ -623,6 +648,25 @@ mod tests {
         assert!(!ErrorClass::Permission.is_agent_fixable());
     }
 
+    #[test]
+    fn suggested_action_per_class() {
+        let expected = [
+            (ErrorClass::Compile, SuggestedAction::RetrySameModel),
+            (ErrorClass::Logic, SuggestedAction::RetrySameModel),
+            (ErrorClass::Network, SuggestedAction::RetrySameModel),
+            (ErrorClass::Resource, SuggestedAction::RetrySameModel),
+            (ErrorClass::Config, SuggestedAction::RegenerateContext),
+            (ErrorClass::Environment, SuggestedAction::RegenerateContext),
+            (ErrorClass::Permission, SuggestedAction::EscalateToHuman),
+            (ErrorClass::Git, SuggestedAction::EscalateToHuman),
+            (ErrorClass::Agent, SuggestedAction::FallbackModel),
+            (ErrorClass::Unknown, SuggestedAction::FallbackModel),
+        ];
+        for (class, action) in expected {
+            assert_eq!(suggested_action(class), action, "class {class}");
+        }
+    }
+
     #[test]
     fn retry_delays() {
         assert_eq!(ErrorClass::Network.retry_delay_secs(), Some(30));
@@ -4,11 +4,15 @@
 //! builder that injects context graph, test spec, retry info, and signal
 //! definitions.
 
-use orch_core::types::{ModelKind, TaskId};
-use std::path::Path;
+use orch_core::types::{ModelKind, TaskId, TaskType};
+use orch_verify::parse_verify_failures;
+use std::path::{Path, PathBuf};
 
 use crate::context_graph::{render_context_with_sources, ContextGraph};
 
+/// Failing tests and compiler errors listed in a retry prompt.
+pub const RETRY_PROMPT_MAX_FAILURES: usize = 5;
+
 /// The type of task being performed — drives which template to use.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PromptRole {
@@ -17,6 +21,47 @@ pub enum PromptRole {
     Review,
     StackCaptain,
     QAValidate,
+    ResolveConflict,
+}
+
+impl PromptRole {
+    pub const ALL: [PromptRole; 6] = [
+        PromptRole::Implement,
+        PromptRole::TestSpecWrite,
+        PromptRole::Review,
+        PromptRole::StackCaptain,
+        PromptRole::QAValidate,
+        PromptRole::ResolveConflict,
+    ];
+
+    pub fn for_task_type(task_type: TaskType) -> Self {
+        match task_type {
+            TaskType::Implement | TaskType::Orchestrate => PromptRole::Implement,
+            TaskType::TestSpecWrite => PromptRole::TestSpecWrite,
+            TaskType::TestValidate => PromptRole::Review,
+        }
+    }
+
+    /// Template file name for this role, relative to a template directory.
+    pub fn template_file(self) -> &'static str {
+        match self {
+            PromptRole::Implement => "implementer.md",
+            PromptRole::TestSpecWrite => "tests-specialist.md",
+            PromptRole::Review => "reviewer.md",
+            PromptRole::StackCaptain => "stack-captain.md",
+            PromptRole::QAValidate => "qa-validator.md",
+            PromptRole::ResolveConflict => "conflict-resolver.md",
+        }
+    }
+}
+
+/// A role template that `model` cannot load from its own override directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateFallback {
+    pub model: ModelKind,
+    pub role: PromptRole,
+    /// The template that will be used instead, if any.
+    pub fallback: Option<PathBuf>,
 }
 
 /// Retry context injected when a task is being retried.
@@ -28,11 +73,21 @@ pub struct RetryContext {
     pub previous_model: ModelKind,
 }
 
+/// Conflict details injected when an agent is asked to finish a restack.
+#[derive(Debug, Clone)]
+pub struct RestackConflictContext {
+    pub parent_branch: String,
+    /// Files `git status --porcelain` reports as unmerged.
+    pub files: Vec<String>,
+}
+
 /// Configuration for building a rich prompt.
 #[derive(Debug, Clone)]
 pub struct PromptConfig {
     pub task_id: TaskId,
     pub task_title: String,
+    /// Optional longer description, e.g. the body of an imported issue.
+    pub task_description: Option<String>,
     pub role: PromptRole,
     pub context: Option<ContextGraph>,
     pub test_spec: Option<String>,
@@ -40,8 +95,68 @@ pub struct PromptConfig {
     pub verify_command: Option<String>,
     /// QA failure details injected when retrying after a QA validation failure.
     pub qa_failure_context: Option<String>,
+    /// Restack conflict to resolve, for `PromptRole::ResolveConflict`.
+    pub restack_conflict: Option<RestackConflictContext>,
     /// Repository root path — used for inlining source files from context graph @file: refs.
     pub repo_root: Option<std::path::PathBuf>,
+    /// Model the prompt is for; selects `<template_dir>/<model>/` overrides.
+    pub model: Option<ModelKind>,
+}
+
+/// Paths tried for a role template, most specific first: the model's own
+/// directory (e.g. `claude/implementer.md`), then the shared template.
+pub fn template_candidates(
+    template_dir: &Path,
+    role: PromptRole,
+    model: Option<ModelKind>,
+) -> Vec<PathBuf> {
+    let file = role.template_file();
+    let mut candidates = Vec::new();
+    if let Some(model) = model {
+        candidates.push(template_dir.join(model.as_str()).join(file));
+    }
+    candidates.push(template_dir.join(file));
+    candidates
+}
+
+/// The first existing template from [`template_candidates`].
+pub fn resolve_template_path(
+    template_dir: &Path,
+    role: PromptRole,
+    model: Option<ModelKind>,
+) -> Option<PathBuf> {
+    template_candidates(template_dir, role, model)
+        .into_iter()
+        .find(|path| path.is_file())
+}
+
+/// Role templates each model will not find where expected.
+///
+/// A model without an override directory silently uses the shared templates;
+/// one with a directory gets an entry for each role it does not override, and
+/// every model gets one for roles with no template at all.
+pub fn validate_model_templates(
+    template_dir: &Path,
+    models: &[ModelKind],
+) -> Vec<TemplateFallback> {
+    let mut fallbacks = Vec::new();
+    for &model in models {
+        let has_overrides = template_dir.join(model.as_str()).is_dir();
+        for role in PromptRole::ALL {
+            let resolved = resolve_template_path(template_dir, role, Some(model));
+            let overridden = resolved
+                .as_ref()
+                .is_some_and(|path| path.starts_with(template_dir.join(model.as_str())));
+            if resolved.is_none() || (has_overrides && !overridden) {
+                fallbacks.push(TemplateFallback {
+                    model,
+                    role,
+                    fallback: resolved,
+                });
+            }
+        }
+    }
+    fallbacks
 }
 
 /// Build a rich prompt from config and template directory.
@@ -60,16 +175,10 @@ pub fn build_rich_prompt(config: &PromptConfig, template_dir: &Path) -> String {
         }
     }
 
-    // 1. Role template (from disk).
-    let template_file = match config.role {
-        PromptRole::Implement => "implementer.md",
-        PromptRole::TestSpecWrite => "tests-specialist.md",
-        PromptRole::Review => "reviewer.md",
-        PromptRole::StackCaptain => "stack-captain.md",
-        PromptRole::QAValidate => "qa-validator.md",
-    };
-    let template_path = template_dir.join(template_file);
-    if let Ok(template) = std::fs::read_to_string(&template_path) {
+    // 1. Role template (from disk), preferring the model's override.
+    let template = resolve_template_path(template_dir, config.role, config.model)
+        .and_then(|path| std::fs::read_to_string(path).ok());
+    if let Some(template) = template {
         let content = template.trim();
         if content.lines().count() > 1 {
             // Only include if the template has real content (not just a header).
@@ -78,12 +187,21 @@ pub fn build_rich_prompt(config: &PromptConfig, template_dir: &Path) -> String {
     }
 
     // 2. Task assignment.
-    sections.push(format!(
+    let mut assignment = format!(
         "# Task Assignment\n\n\
          **Task ID:** {}\n\
          **Title:** {}\n",
         config.task_id.0, config.task_title
-    ));
+    );
+    if let Some(description) = config
+        .task_description
+        .as_deref()
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+    {
+        assignment.push_str(&format!("\n## Description\n\n{description}\n"));
+    }
+    sections.push(assignment);
 
     // 3. Repository context (from context graph), with source inlining when repo_root is available.
     if let Some(ctx) = &config.context {
@@ -134,11 +252,13 @@ pub fn build_rich_prompt(config: &PromptConfig, template_dir: &Path) -> String {
             "# Retry Context\n\n\
              This is attempt **{}/{}**.\n\n\
              **Previous model:** {}\n\
+             {}\
              **Previous failure:**\n```\n{}\n```\n\n\
              Fix the issue described above. Do NOT repeat the same mistake.\n",
             retry.attempt,
             retry.max_retries,
             retry.previous_model.as_str(),
+            render_verify_failures(&retry.previous_failure),
             retry.previous_failure,
         ));
     }
@@ -148,6 +268,25 @@ pub fn build_rich_prompt(config: &PromptConfig, template_dir: &Path) -> String {
         sections.push(qa_ctx.clone());
     }
 
+    // 5c. Restack conflict (when resolving a conflicted restack).
+    if let Some(conflict) = &config.restack_conflict {
+        let files: Vec<String> = conflict
+            .files
+            .iter()
+            .map(|file| format!("- `{file}`"))
+            .collect();
+        sections.push(format!(
+            "# Restack Conflict\n\n\
+             Restacking this branch onto `{}` stopped on conflicts in:\n\n\
+             {}\n\n\
+             Resolve every conflict marker in these files, keeping the intent of both sides. \
+             Do not commit, run `gt continue`, or switch branches; the orchestrator continues \
+             the restack and runs verification when you are done.\n",
+            conflict.parent_branch,
+            files.join("\n"),
+        ));
+    }
+
     // 6. Verify command.
     if let Some(cmd) = &config.verify_command {
         sections.push(format!(
@@ -163,9 +302,31 @@ pub fn build_rich_prompt(config: &PromptConfig, template_dir: &Path) -> String {
     sections.join("\n---\n\n")
 }
 
+/// Bullet list of the failures parsed from verify output, ending in a blank
+/// line; empty when the output format is not recognized.
+fn render_verify_failures(output: &str) -> String {
+    let failures = parse_verify_failures(output);
+    if failures.is_empty() {
+        return String::new();
+    }
+    let mut out = format!("**Failing checks ({}):**\n", failures.len());
+    for failure in failures.iter().take(RETRY_PROMPT_MAX_FAILURES) {
+        out.push_str(&format!("- {}\n", failure.summary()));
+    }
+    if failures.len() > RETRY_PROMPT_MAX_FAILURES {
+        out.push_str(&format!(
+            "- ...and {} more\n",
+            failures.len() - RETRY_PROMPT_MAX_FAILURES
+        ));
+    }
+    out.push('\n');
+    out
+}
+
 fn signal_definitions() -> String {
     "# Signals\n\n\
      When you are done and the code is ready, print exactly: `[patch_ready]`\n\
+     Just before it, print how sure you are the change is correct: `CONFIDENCE: <0.0-1.0>`\n\
      If you are blocked and need human help, print exactly: `[needs_human]`\n\
      If you have a plan ready for decomposition, print exactly: `[plan_ready]`\n"
         .to_string()
@@ -180,16 +341,30 @@ mod tests {
         PromptConfig {
             task_id: TaskId::new("T-42"),
             task_title: "Add authentication".to_string(),
+            task_description: None,
             role: PromptRole::Implement,
             context: None,
             test_spec: None,
             retry: None,
             verify_command: None,
             qa_failure_context: None,
+            restack_conflict: None,
             repo_root: None,
+            model: None,
         }
     }
 
+    #[test]
+    fn prompt_includes_task_description_when_present() {
+        let mut config = mk_config();
+        config.task_description = Some("Use OAuth via the existing session store.".to_string());
+        let prompt = build_rich_prompt(&config, Path::new("/nonexistent"));
+        assert!(prompt.contains("## Description\n\nUse OAuth via the existing session store."));
+
+        let prompt = build_rich_prompt(&mk_config(), Path::new("/nonexistent"));
+        assert!(!prompt.contains("## Description"));
+    }
+
     #[test]
     fn basic_prompt_includes_task_info() {
         let config = mk_config();
@@ -217,6 +392,38 @@ mod tests {
         assert!(prompt.contains("claude"));
     }
 
+    #[test]
+    fn retry_context_lists_parsed_verify_failures_first() {
+        let mut config = mk_config();
+        let mut output = String::from("running 7 tests\n");
+        for i in 0..7 {
+            output.push_str(&format!("test tests::case_{i} ... FAILED\n"));
+        }
+        output.push_str("thread 'tests::case_0' panicked at src/lib.rs:3:5:\nboom\n");
+        config.retry = Some(RetryContext {
+            attempt: 2,
+            max_retries: 3,
+            previous_failure: output,
+            previous_model: ModelKind::Claude,
+        });
+
+        let prompt = build_rich_prompt(&config, Path::new("/nonexistent"));
+        assert!(prompt.contains("**Failing checks (7):**"));
+        assert!(prompt.contains("- test tests::case_0 (src/lib.rs:3:5): boom"));
+        assert!(prompt.contains("- test tests::case_4\n"));
+        assert!(!prompt.contains("- test tests::case_5"));
+        assert!(prompt.contains("- ...and 2 more"));
+
+        config.retry = Some(RetryContext {
+            attempt: 2,
+            max_retries: 3,
+            previous_failure: "cargo test failed: assertion error".to_string(),
+            previous_model: ModelKind::Claude,
+        });
+        let prompt = build_rich_prompt(&config, Path::new("/nonexistent"));
+        assert!(!prompt.contains("Failing checks"));
+    }
+
     #[test]
     fn prompt_includes_test_spec() {
         let mut config = mk_config();
@@ -228,6 +435,22 @@ mod tests {
         assert!(prompt.contains("Login returns 200"));
     }
 
+    #[test]
+    fn prompt_lists_restack_conflict_files() {
+        let mut config = mk_config();
+        config.role = PromptRole::ResolveConflict;
+        config.restack_conflict = Some(RestackConflictContext {
+            parent_branch: "task/T-41".to_string(),
+            files: vec!["src/lib.rs".to_string(), "Cargo.toml".to_string()],
+        });
+
+        let prompt = build_rich_prompt(&config, Path::new("/nonexistent"));
+        assert!(prompt.contains("onto `task/T-41` stopped on conflicts"));
+        assert!(prompt.contains("- `src/lib.rs`\n- `Cargo.toml`"));
+        assert!(!build_rich_prompt(&mk_config(), Path::new("/nonexistent"))
+            .contains("Restack Conflict"));
+    }
+
     #[test]
     fn prompt_includes_verify_command() {
         let mut config = mk_config();
@@ -266,6 +489,7 @@ mod tests {
                 content: "# Main\nUse Rust.\n".to_string(),
                 links: vec![],
                 source_refs: vec![],
+                modified_at: None,
             }],
             total_chars: 16,
         });
@@ -356,6 +580,7 @@ mod tests {
                 content: "# Main\nSee @file:lib.rs for the greeting.\n".to_string(),
                 links: vec![],
                 source_refs: vec![PathBuf::from("lib.rs")],
+                modified_at: None,
             }],
             total_chars: 44,
         });
@@ -416,4 +641,75 @@ mod tests {
 
         fs::remove_dir_all(&tmp).ok();
     }
+
+    #[test]
+    fn template_candidates_prefer_model_directory_then_shared() {
+        let dir = Path::new("/tmp/prompts");
+        assert_eq!(
+            template_candidates(dir, PromptRole::Review, Some(ModelKind::Codex)),
+            vec![dir.join("codex/reviewer.md"), dir.join("reviewer.md")]
+        );
+        assert_eq!(
+            template_candidates(dir, PromptRole::Review, None),
+            vec![dir.join("reviewer.md")]
+        );
+    }
+
+    #[test]
+    fn model_override_template_replaces_shared_one() {
+        let tmp = std::env::temp_dir().join(format!("othala-model-tmpl-{}", std::process::id()));
+        fs::create_dir_all(tmp.join("claude")).unwrap();
+        fs::write(tmp.join("implementer.md"), "# Implementer\nShared steps.\n").unwrap();
+        fs::write(
+            tmp.join("claude/implementer.md"),
+            "# Implementer\nUse <thinking> tags.\n",
+        )
+        .unwrap();
+
+        let mut config = mk_config();
+        config.model = Some(ModelKind::Claude);
+        let claude = build_rich_prompt(&config, &tmp);
+        assert!(claude.contains("Use <thinking> tags."));
+        assert!(!claude.contains("Shared steps."));
+
+        config.model = Some(ModelKind::Gemini);
+        assert!(build_rich_prompt(&config, &tmp).contains("Shared steps."));
+
+        fs::remove_dir_all(&tmp).ok();
+    }
+
+    #[test]
+    fn validate_model_templates_reports_partial_overrides_and_missing_roles() {
+        let tmp = std::env::temp_dir().join(format!("othala-tmpl-check-{}", std::process::id()));
+        fs::create_dir_all(tmp.join("codex")).unwrap();
+        for role in PromptRole::ALL {
+            if role != PromptRole::StackCaptain {
+                fs::write(tmp.join(role.template_file()), "# Role\nShared.\n").unwrap();
+            }
+        }
+        fs::write(tmp.join("codex/implementer.md"), "# Role\nCodex.\n").unwrap();
+
+        let fallbacks = validate_model_templates(&tmp, &[ModelKind::Claude, ModelKind::Codex]);
+        let claude: Vec<_> = fallbacks
+            .iter()
+            .filter(|f| f.model == ModelKind::Claude)
+            .collect();
+        assert_eq!(claude.len(), 1);
+        assert_eq!(claude[0].role, PromptRole::StackCaptain);
+        assert_eq!(claude[0].fallback, None);
+
+        let codex: Vec<_> = fallbacks
+            .iter()
+            .filter(|f| f.model == ModelKind::Codex)
+            .collect();
+        assert_eq!(codex.len(), PromptRole::ALL.len() - 1);
+        assert!(codex.iter().all(|f| f.role != PromptRole::Implement));
+        let review = codex
+            .iter()
+            .find(|f| f.role == PromptRole::Review)
+            .expect("review falls back");
+        assert_eq!(review.fallback, Some(tmp.join("reviewer.md")));
+
+        fs::remove_dir_all(&tmp).ok();
+    }
 }
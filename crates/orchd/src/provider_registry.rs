@@ -1,9 +1,18 @@
+use orch_agents::setup::SetupProbeReport;
+use orch_core::config::ModelsConfig;
+use orch_core::types::ModelKind;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 use std::process::Command;
 
+/// Team-specific providers and models, merged over the built-in set.
+pub const CUSTOM_PROVIDERS_PATH: &str = ".othala/providers.toml";
+
+/// Provider that models from `[models.custom.<name>]` are listed under.
+pub const CUSTOM_MODELS_PROVIDER: &str = "custom";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelInfo {
     pub id: String,
@@ -28,6 +37,67 @@ pub struct ProviderInfo {
     pub models: Vec<String>,
 }
 
+/// Whether a provider can be used right now: its auth variable is set and,
+/// when an agent CLI runs its models, that CLI is installed and answers
+/// `--version`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderHealth {
+    pub healthy: bool,
+    /// True when the provider needs no auth variable.
+    pub auth_env_set: bool,
+    /// `None` when no probed CLI runs the provider's models.
+    pub cli_installed: Option<bool>,
+    pub detail: String,
+}
+
+/// Provider entry in the custom providers file. Fields left out keep the
+/// built-in value when `name` matches an existing provider.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct CustomProvider {
+    pub name: String,
+    pub display_name: Option<String>,
+    pub api_base: Option<String>,
+    pub auth_env_var: Option<String>,
+}
+
+/// Model entry in the custom providers file.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomModel {
+    pub id: String,
+    pub provider: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub context_window: u64,
+    #[serde(default)]
+    pub max_output_tokens: u64,
+    #[serde(default)]
+    pub input_price_per_mtok: f64,
+    #[serde(default)]
+    pub output_price_per_mtok: f64,
+    #[serde(default)]
+    pub supports_images: bool,
+    #[serde(default)]
+    pub supports_tools: bool,
+    #[serde(default = "default_true")]
+    pub supports_streaming: bool,
+    #[serde(default)]
+    pub deprecated: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Contents of the custom providers file (TOML, or JSON for `.json` paths).
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct CustomRegistry {
+    pub providers: Vec<CustomProvider>,
+    pub models: Vec<CustomModel>,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ModelRegistry {
     pub models: HashMap<String, ModelInfo>,
@@ -243,6 +313,187 @@ impl ModelRegistry {
         registry
     }
 
+    /// Built-in registry merged with the custom entries at `path`. A missing
+    /// file yields the built-in set unchanged.
+    pub fn with_custom(path: &Path) -> Result<Self, String> {
+        let mut registry = Self::new();
+        if !path.exists() {
+            return Ok(registry);
+        }
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        let custom: CustomRegistry = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::from_str(&content)
+                .map_err(|e| format!("failed to parse {}: {e}", path.display()))?
+        } else {
+            toml::from_str(&content)
+                .map_err(|e| format!("failed to parse {}: {e}", path.display()))?
+        };
+        registry.merge_custom(custom)?;
+        Ok(registry)
+    }
+
+    /// Add or override providers by name, then add or replace models. Every
+    /// model must reference a provider that is built in or defined alongside it.
+    pub fn merge_custom(&mut self, custom: CustomRegistry) -> Result<(), String> {
+        for model in &custom.models {
+            if model.id.trim().is_empty() {
+                return Err("custom model id cannot be empty".to_string());
+            }
+            let known = self.providers.contains_key(&model.provider)
+                || custom.providers.iter().any(|p| p.name == model.provider);
+            if !known {
+                return Err(format!(
+                    "custom model '{}' references unknown provider '{}'",
+                    model.id, model.provider
+                ));
+            }
+        }
+
+        for entry in custom.providers {
+            if entry.name.trim().is_empty() {
+                return Err("custom provider name cannot be empty".to_string());
+            }
+            let provider = self
+                .providers
+                .entry(entry.name.clone())
+                .or_insert_with(|| ProviderInfo {
+                    name: entry.name.clone(),
+                    display_name: entry.name.clone(),
+                    api_base: String::new(),
+                    auth_env_var: String::new(),
+                    models: Vec::new(),
+                });
+            if let Some(display_name) = entry.display_name {
+                provider.display_name = display_name;
+            }
+            if let Some(api_base) = entry.api_base {
+                provider.api_base = api_base;
+            }
+            if let Some(auth_env_var) = entry.auth_env_var {
+                provider.auth_env_var = auth_env_var;
+            }
+        }
+
+        for model in custom.models {
+            if let Some(previous) = self.models.get(&model.id) {
+                if let Some(provider) = self.providers.get_mut(&previous.provider) {
+                    provider.models.retain(|id| id != &model.id);
+                }
+            }
+            self.insert_model(ModelInfo {
+                display_name: model.display_name.unwrap_or_else(|| model.id.clone()),
+                id: model.id,
+                provider: model.provider,
+                context_window: model.context_window,
+                max_output_tokens: model.max_output_tokens,
+                input_price_per_mtok: model.input_price_per_mtok,
+                output_price_per_mtok: model.output_price_per_mtok,
+                supports_images: model.supports_images,
+                supports_tools: model.supports_tools,
+                supports_streaming: model.supports_streaming,
+                deprecated: model.deprecated,
+            });
+        }
+        Ok(())
+    }
+
+    /// List the `[models.custom.<name>]` CLIs from the org config under the
+    /// `custom` provider. Built-in or already listed ids are left alone.
+    pub fn merge_models_config(&mut self, models: &ModelsConfig) {
+        for (model, config) in models.custom_models() {
+            if self.models.contains_key(model.as_str()) {
+                continue;
+            }
+            let provider = self
+                .providers
+                .entry(CUSTOM_MODELS_PROVIDER.to_string())
+                .or_insert_with(|| ProviderInfo {
+                    name: CUSTOM_MODELS_PROVIDER.to_string(),
+                    display_name: "Custom CLI".to_string(),
+                    api_base: String::new(),
+                    auth_env_var: String::new(),
+                    models: Vec::new(),
+                });
+            if provider.auth_env_var.is_empty() {
+                provider.auth_env_var = config.api_key_env.clone().unwrap_or_default();
+            }
+            self.insert_model(ModelInfo {
+                id: model.as_str().to_string(),
+                provider: CUSTOM_MODELS_PROVIDER.to_string(),
+                display_name: format!("{} ({})", model, config.command),
+                context_window: 0,
+                max_output_tokens: 0,
+                input_price_per_mtok: 0.0,
+                output_price_per_mtok: 0.0,
+                supports_images: false,
+                supports_tools: false,
+                supports_streaming: true,
+                deprecated: false,
+            });
+        }
+    }
+
+    /// Drop every model that none of the `enabled` Othala models run, along
+    /// with providers left without models.
+    pub fn retain_enabled(&mut self, enabled: &[ModelKind]) {
+        self.models
+            .retain(|_, model| enabled.iter().any(|kind| runs_as(model, *kind)));
+        for provider in self.providers.values_mut() {
+            provider.models.retain(|id| self.models.contains_key(id));
+        }
+        self.providers
+            .retain(|_, provider| !provider.models.is_empty());
+    }
+
+    /// Health of provider `name` from a model probe and the environment.
+    pub fn provider_health(
+        &self,
+        name: &str,
+        probes: &SetupProbeReport,
+        env_present: impl Fn(&str) -> bool,
+    ) -> Option<ProviderHealth> {
+        let provider = self.providers.get(name)?;
+        let auth_env_set = provider.auth_env_var.is_empty() || env_present(&provider.auth_env_var);
+
+        let clis = probes
+            .models
+            .iter()
+            .filter(|probe| {
+                provider.models.iter().any(|id| {
+                    self.models
+                        .get(id)
+                        .is_some_and(|model| runs_as(model, probe.model))
+                })
+            })
+            .collect::<Vec<_>>();
+        let cli_installed = (!clis.is_empty())
+            .then(|| clis.iter().any(|probe| probe.installed && probe.version_ok));
+
+        let mut problems = Vec::new();
+        if !auth_env_set {
+            problems.push(format!("${} not set", provider.auth_env_var));
+        }
+        if cli_installed == Some(false) {
+            let executables = clis
+                .iter()
+                .map(|probe| probe.executable.as_str())
+                .collect::<Vec<_>>();
+            problems.push(format!("{} not installed", executables.join("/")));
+        }
+
+        Some(ProviderHealth {
+            healthy: problems.is_empty(),
+            auth_env_set,
+            cli_installed,
+            detail: if problems.is_empty() {
+                "ok".to_string()
+            } else {
+                problems.join(", ")
+            },
+        })
+    }
+
     pub fn get_model(&self, id: &str) -> Option<&ModelInfo> {
         self.models.get(id)
     }
@@ -503,6 +754,19 @@ fn version_is_newer(candidate: &str, current: &str) -> bool {
     false
 }
 
+/// Whether `kind` runs `model`: the built-in CLIs cover their provider's
+/// models, a `[models.custom]` tool only its own entry.
+fn runs_as(model: &ModelInfo, kind: ModelKind) -> bool {
+    match kind {
+        ModelKind::Claude => model.provider == "anthropic",
+        ModelKind::Codex => model.provider == "openai",
+        ModelKind::Gemini => model.provider == "google",
+        ModelKind::Custom(_) => {
+            model.provider == CUSTOM_MODELS_PROVIDER && model.id == kind.as_str()
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -651,6 +915,197 @@ mod tests {
         fs::remove_file(path).expect("expected temp file cleanup to succeed");
     }
 
+    fn write_custom(name: &str, content: &str) -> std::path::PathBuf {
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock should be after epoch")
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!("orchd-providers-{name}-{nonce}.toml"));
+        fs::write(&path, content).expect("write custom providers");
+        path
+    }
+
+    #[test]
+    fn with_custom_adds_new_provider_and_model() {
+        let path = write_custom(
+            "add",
+            r#"
+            [[providers]]
+            name = "internal"
+            display_name = "Internal Proxy"
+            api_base = "https://llm.internal.example"
+            auth_env_var = "INTERNAL_LLM_KEY"
+
+            [[models]]
+            id = "internal-coder"
+            provider = "internal"
+            context_window = 64000
+            supports_tools = true
+            "#,
+        );
+
+        let registry = ModelRegistry::with_custom(&path).expect("merge custom providers");
+        let provider = registry.get_provider("internal").expect("custom provider");
+        assert_eq!(provider.api_base, "https://llm.internal.example");
+        assert_eq!(provider.models, vec!["internal-coder".to_string()]);
+        let model = registry.get_model("internal-coder").expect("custom model");
+        assert_eq!(model.display_name, "internal-coder");
+        assert_eq!(model.context_window, 64_000);
+        assert!(model.supports_streaming);
+        assert!(registry.get_model("claude-sonnet-4-20250514").is_some());
+
+        fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn with_custom_overrides_fields_of_existing_provider() {
+        let path = write_custom(
+            "override",
+            r#"
+            [[providers]]
+            name = "anthropic"
+            api_base = "https://proxy.example/anthropic"
+            "#,
+        );
+
+        let registry = ModelRegistry::with_custom(&path).expect("merge custom providers");
+        let provider = registry.get_provider("anthropic").expect("anthropic");
+        assert_eq!(provider.api_base, "https://proxy.example/anthropic");
+        assert_eq!(provider.auth_env_var, "ANTHROPIC_API_KEY");
+        assert_eq!(provider.display_name, "Anthropic");
+        assert!(!registry.models_for_provider("anthropic").is_empty());
+
+        fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn with_custom_rejects_model_with_unknown_provider() {
+        let path = write_custom(
+            "dangling",
+            r#"
+            [[models]]
+            id = "mystery"
+            provider = "nowhere"
+            "#,
+        );
+
+        let err = ModelRegistry::with_custom(&path).unwrap_err();
+        assert_eq!(err, "custom model 'mystery' references unknown provider 'nowhere'");
+
+        fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn with_custom_missing_file_returns_builtins() {
+        let path = std::env::temp_dir().join("orchd-providers-does-not-exist.toml");
+        let registry = ModelRegistry::with_custom(&path).expect("missing file is fine");
+        assert_eq!(registry.models.len(), ModelRegistry::new().models.len());
+    }
+
+    #[test]
+    fn merge_models_config_lists_custom_models() {
+        let mut models = orch_core::config::OrgConfig::default().models;
+        models.custom.insert(
+            "aider".to_string(),
+            orch_core::config::CustomModelConfig {
+                command: "aider".to_string(),
+                args: Vec::new(),
+                api_key_env: Some("OPENAI_API_KEY".to_string()),
+                output_format: Default::default(),
+            },
+        );
+
+        let mut registry = ModelRegistry::new();
+        registry.merge_models_config(&models);
+
+        let aider = registry.get_model("aider").expect("custom model listed");
+        assert_eq!(aider.provider, CUSTOM_MODELS_PROVIDER);
+        let provider = registry
+            .get_provider(CUSTOM_MODELS_PROVIDER)
+            .expect("custom provider");
+        assert_eq!(provider.models, vec!["aider".to_string()]);
+        assert_eq!(provider.auth_env_var, "OPENAI_API_KEY");
+        assert!(registry.display_table().contains("aider"));
+    }
+
+    fn probe_report(installed: &[ModelKind]) -> SetupProbeReport {
+        SetupProbeReport {
+            models: ModelKind::all()
+                .iter()
+                .map(|model| orch_agents::setup::ModelProbeResult {
+                    model: *model,
+                    executable: model.as_str().to_string(),
+                    installed: installed.contains(model),
+                    version_ok: installed.contains(model),
+                    version_output: None,
+                    env_status: Vec::new(),
+                    healthy: installed.contains(model),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn provider_health_requires_the_auth_var() {
+        let registry = ModelRegistry::new();
+        let probes = probe_report(&[ModelKind::Claude]);
+
+        let missing = registry
+            .provider_health("anthropic", &probes, |_| false)
+            .expect("anthropic health");
+        assert!(!missing.healthy);
+        assert!(!missing.auth_env_set);
+        assert_eq!(missing.cli_installed, Some(true));
+        assert_eq!(missing.detail, "$ANTHROPIC_API_KEY not set");
+
+        let present = registry
+            .provider_health("anthropic", &probes, |key| key == "ANTHROPIC_API_KEY")
+            .expect("anthropic health");
+        assert!(present.healthy);
+        assert_eq!(present.detail, "ok");
+    }
+
+    #[test]
+    fn provider_health_checks_the_cli_only_where_one_runs_its_models() {
+        let registry = ModelRegistry::new();
+        let probes = probe_report(&[]);
+
+        let openai = registry
+            .provider_health("openai", &probes, |_| true)
+            .expect("openai health");
+        assert!(!openai.healthy);
+        assert_eq!(openai.cli_installed, Some(false));
+        assert_eq!(openai.detail, "codex not installed");
+
+        let xai = registry
+            .provider_health("xai", &probes, |_| true)
+            .expect("xai health");
+        assert!(xai.healthy);
+        assert_eq!(xai.cli_installed, None);
+        assert!(registry
+            .provider_health("nope", &probes, |_| true)
+            .is_none());
+    }
+
+    #[test]
+    fn retain_enabled_keeps_only_models_enabled_in_org_config() {
+        let mut registry = ModelRegistry::new();
+        registry.retain_enabled(&[ModelKind::Claude]);
+
+        let table = registry.display_table();
+        assert!(table.contains("claude-sonnet-4-20250514"));
+        assert!(table.contains("claude-opus-4-20250514"));
+        assert!(!table.contains("codex"));
+        assert!(!table.contains("gemini-2.5-pro"));
+        assert!(!table.contains("openrouter"));
+        let providers: Vec<_> = registry
+            .list_providers()
+            .iter()
+            .map(|provider| provider.name.as_str())
+            .collect();
+        assert_eq!(providers, vec!["anthropic"]);
+    }
+
     #[test]
     fn xai_provider_is_registered_with_models() {
         let registry = ModelRegistry::new();
@@ -10,7 +10,9 @@
 //! - The "after" result becomes the baseline for the next task on that branch
 
 use chrono::{DateTime, Utc};
-use orch_agents::{default_adapter_for, detect_common_signal, AgentSignalKind, EpochRequest};
+use orch_agents::{
+    default_adapter_for, detect_common_signal, AgentSignalKind, ChunkSink, EpochRequest,
+};
 use orch_core::types::{ModelKind, RepoId, TaskId};
 use serde::{Deserialize, Serialize};
 
@@ -472,12 +474,14 @@ pub fn build_qa_failure_context(result: &QAResult) -> String {
 // Agent management
 // ---------------------------------------------------------------------------
 
-/// Spawn a QA agent process.
+/// Spawn a QA agent process. With `log`, each output line is also streamed
+/// to it as it is read.
 pub fn spawn_qa_agent(
     cwd: &Path,
     prompt: &str,
     model: ModelKind,
     state: &mut QAState,
+    log: Option<ChunkSink>,
 ) -> anyhow::Result<()> {
     let adapter = default_adapter_for(model)?;
 
@@ -508,10 +512,13 @@ pub fn spawn_qa_agent(
 
     // Pipe stdout.
     if let Some(stdout) = child.stdout.take() {
-        let tx_out = tx.clone();
+        let (tx_out, log_out) = (tx.clone(), log.clone());
         thread::spawn(move || {
             let reader = BufReader::new(stdout);
             for line in reader.lines().map_while(Result::ok) {
+                if let Some(log) = &log_out {
+                    log.send_line(&line);
+                }
                 let _ = tx_out.send(line);
             }
         });
@@ -522,6 +529,9 @@ pub fn spawn_qa_agent(
         thread::spawn(move || {
             let reader = BufReader::new(stderr);
             for line in reader.lines().map_while(Result::ok) {
+                if let Some(log) = &log {
+                    log.send_line(&line);
+                }
                 let _ = tx.send(line);
             }
         });
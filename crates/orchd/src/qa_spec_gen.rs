@@ -11,7 +11,8 @@
 
 use chrono::{DateTime, Utc};
 use orch_agents::{default_adapter_for, EpochRequest};
-use orch_core::types::{ModelKind, RepoId, TaskId};
+use orch_core::types::{ModelKind, RepoId, Task, TaskId};
+use orch_git::DiffSnapshot;
 
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
@@ -20,6 +21,8 @@ use std::sync::mpsc;
 use std::thread;
 use std::time::Duration;
 
+use crate::qa_agent::{parse_qa_spec, qa_dir, QASpec};
+
 // ---------------------------------------------------------------------------
 // Types
 // ---------------------------------------------------------------------------
@@ -628,14 +631,62 @@ pub fn ensure_qa_spec_exists_blocking(
         QASpecStartupStatus::Missing => {}
     }
 
+    let parsed = generate_qa_spec_blocking(repo_root, template_dir, model, progress)?;
+
+    if !parsed.files.is_empty() {
+        let paths = write_qa_spec_files(repo_root, &parsed)?;
+        eprintln!(
+            "[qa-spec-gen] Generated {} QA spec files at startup",
+            paths.len()
+        );
+    } else if repo_root.join(".othala/qa/baseline.md").exists() {
+        if let Some(hash) = crate::context_gen::get_head_sha(repo_root) {
+            write_stored_hash(repo_root, &hash)?;
+        }
+        eprintln!("[qa-spec-gen] Agent wrote QA spec files directly");
+    } else {
+        anyhow::bail!("QA spec generation agent produced no spec files");
+    }
+
+    Ok(())
+}
+
+/// Run the QA spec generation agent to completion and parse its output,
+/// without writing anything to `.othala/qa/`.
+///
+/// The `progress` callback receives stderr lines from the agent process.
+pub fn generate_qa_spec_blocking(
+    repo_root: &Path,
+    template_dir: &Path,
+    model: ModelKind,
+    progress: impl Fn(&str) + Send + 'static,
+) -> anyhow::Result<QASpecGenOutput> {
     let prompt = build_qa_spec_gen_prompt(repo_root, template_dir);
+    let raw = run_spec_agent_blocking(
+        repo_root,
+        TaskId::new("qa-spec-gen-startup"),
+        model,
+        prompt,
+        progress,
+    )?;
+    Ok(parse_qa_spec_gen_output(&raw))
+}
+
+/// Run a spec generation agent in `cwd` to completion and return its stdout.
+fn run_spec_agent_blocking(
+    cwd: &Path,
+    task_id: TaskId,
+    model: ModelKind,
+    prompt: String,
+    progress: impl Fn(&str) + Send + 'static,
+) -> anyhow::Result<String> {
     let adapter = default_adapter_for(model)?;
 
     let request = EpochRequest {
-        task_id: TaskId::new("qa-spec-gen-startup"),
+        task_id,
         repo_id: RepoId("default".to_string()),
         model,
-        repo_path: repo_root.to_path_buf(),
+        repo_path: cwd.to_path_buf(),
         prompt,
         timeout_secs: 600,
         extra_args: vec![],
@@ -648,7 +699,7 @@ pub fn ensure_qa_spec_exists_blocking(
         .args(&cmd.args)
         .envs(cmd.env.iter().map(|(k, v)| (k.as_str(), v.as_str())))
         .env_remove("CLAUDECODE")
-        .current_dir(repo_root)
+        .current_dir(cwd)
         .stdin(Stdio::null())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -687,25 +738,239 @@ pub fn ensure_qa_spec_exists_blocking(
         output_lines.push(line);
     }
 
-    let raw = output_lines.join("\n");
-    let parsed = parse_qa_spec_gen_output(&raw);
+    Ok(output_lines.join("\n"))
+}
 
-    if !parsed.files.is_empty() {
-        let paths = write_qa_spec_files(repo_root, &parsed)?;
-        eprintln!(
-            "[qa-spec-gen] Generated {} QA spec files at startup",
-            paths.len()
-        );
-    } else if repo_root.join(".othala/qa/baseline.md").exists() {
-        if let Some(hash) = crate::context_gen::get_head_sha(repo_root) {
-            write_stored_hash(repo_root, &hash)?;
+// ---------------------------------------------------------------------------
+// Golden-file check
+// ---------------------------------------------------------------------------
+
+/// Lines of context around each change in a spec drift diff.
+const DRIFT_DIFF_CONTEXT: usize = 3;
+
+/// A generated spec that does not match `.othala/qa/`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QASpecDrift {
+    /// The spec has no file on disk.
+    Missing { filename: String },
+    /// The file on disk differs; `diff` is a unified diff from the file to
+    /// the generated spec.
+    Changed { filename: String, diff: String },
+}
+
+/// Compare `output` with the spec files under `.othala/qa/` without writing
+/// anything. Differences in trailing whitespace are ignored. Returns no
+/// drift when every generated spec matches its file.
+pub fn check_qa_spec_files(repo_root: &Path, output: &QASpecGenOutput) -> Vec<QASpecDrift> {
+    let qa_dir = repo_root.join(".othala/qa");
+    let mut drift = Vec::new();
+    for file in &output.files {
+        let Ok(on_disk) = std::fs::read_to_string(qa_dir.join(&file.filename)) else {
+            drift.push(QASpecDrift::Missing {
+                filename: file.filename.clone(),
+            });
+            continue;
+        };
+        let label = format!(".othala/qa/{}", file.filename);
+        if let Some(diff) = unified_diff(&label, &on_disk, &file.content) {
+            drift.push(QASpecDrift::Changed {
+                filename: file.filename.clone(),
+                diff,
+            });
         }
-        eprintln!("[qa-spec-gen] Agent wrote QA spec files directly");
-    } else {
-        anyhow::bail!("QA spec generation agent produced no spec files");
     }
+    drift
+}
 
-    Ok(())
+/// Lines of `text` with trailing whitespace (and trailing blank lines) removed.
+fn comparable_lines(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = text.lines().map(|l| l.trim_end().to_string()).collect();
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+/// Unified diff of `old` against `new`, or `None` if they match.
+fn unified_diff(label: &str, old: &str, new: &str) -> Option<String> {
+    use crate::agent_log::{diff_agent_outputs, DiffLine};
+
+    let diff = diff_agent_outputs(&comparable_lines(old), &comparable_lines(new));
+    let changed: Vec<usize> = diff
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, DiffLine::Unchanged(_)))
+        .map(|(i, _)| i)
+        .collect();
+    if changed.is_empty() {
+        return None;
+    }
+
+    // Merge changes whose context windows touch into one hunk.
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for i in changed {
+        let start = i.saturating_sub(DRIFT_DIFF_CONTEXT);
+        let end = (i + DRIFT_DIFF_CONTEXT + 1).min(diff.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    // Old/new line numbers reached before each diff entry.
+    let mut old_line = vec![0usize; diff.len() + 1];
+    let mut new_line = vec![0usize; diff.len() + 1];
+    for (i, line) in diff.iter().enumerate() {
+        old_line[i + 1] = old_line[i] + usize::from(!matches!(line, DiffLine::Added(_)));
+        new_line[i + 1] = new_line[i] + usize::from(!matches!(line, DiffLine::Removed(_)));
+    }
+
+    let mut out = format!("--- a/{label}\n+++ b/{label}\n");
+    for (start, end) in hunks {
+        let old_count = old_line[end] - old_line[start];
+        let new_count = new_line[end] - new_line[start];
+        let old_start = old_line[start] + usize::from(old_count > 0);
+        let new_start = new_line[start] + usize::from(new_count > 0);
+        out.push_str(&format!(
+            "@@ -{old_start},{old_count} +{new_start},{new_count} @@\n"
+        ));
+        for line in &diff[start..end] {
+            let (marker, text) = match line {
+                DiffLine::Unchanged(text) => (' ', text),
+                DiffLine::Removed(text) => ('-', text),
+                DiffLine::Added(text) => ('+', text),
+            };
+            out.push(marker);
+            out.push_str(text);
+            out.push('\n');
+        }
+    }
+    Some(out)
+}
+
+// ---------------------------------------------------------------------------
+// Per-task specs
+// ---------------------------------------------------------------------------
+
+/// Changed files listed in a per-task spec prompt before the rest are elided.
+const TASK_SPEC_MAX_FILES: usize = 50;
+
+/// Path of the spec generated for a task: `.othala/qa/<task_id>.yaml`.
+pub fn generated_spec_path(repo_root: &Path, task_id: &TaskId) -> PathBuf {
+    qa_dir(repo_root).join(format!("{}.yaml", task_id.0))
+}
+
+/// Load the spec generated for a task, if one was saved and still parses.
+pub fn load_generated_qa_spec(repo_root: &Path, task_id: &TaskId) -> Option<QASpec> {
+    let content = std::fs::read_to_string(generated_spec_path(repo_root, task_id)).ok()?;
+    serde_yaml::from_str(&content).ok()
+}
+
+/// Save a generated spec to `.othala/qa/<task_id>.yaml`. Returns the path
+/// written.
+pub fn save_generated_qa_spec(
+    repo_root: &Path,
+    task_id: &TaskId,
+    spec: &QASpec,
+) -> std::io::Result<PathBuf> {
+    let path = generated_spec_path(repo_root, task_id);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let yaml = serde_yaml::to_string(spec).map_err(std::io::Error::other)?;
+    std::fs::write(&path, yaml)?;
+    Ok(path)
+}
+
+/// Build the prompt asking an agent for a checklist of verifiable assertions
+/// about a task's change.
+pub fn build_task_qa_spec_prompt(task: &Task, diff: &DiffSnapshot) -> String {
+    let mut prompt = format!(
+        "# Generate QA acceptance checks\n\n\
+         Write a checklist of assertions a QA agent can verify by running the \
+         code in this worktree to confirm the task below is done. Each \
+         assertion must be observable: a command, its expected output, a file \
+         or state that must exist. Do not restate the task or describe the diff.\n\n\
+         ## Task\n\n\
+         Title: {}\n",
+        task.title
+    );
+    if let Some(description) = task
+        .description
+        .as_deref()
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+    {
+        prompt.push_str(&format!("\n{description}\n"));
+    }
+
+    prompt.push_str("\n## Diff\n\n");
+    if let Some(shortstat) = &diff.shortstat {
+        prompt.push_str(&format!("{shortstat}\n\n"));
+    }
+    for file in diff.files.iter().take(TASK_SPEC_MAX_FILES) {
+        prompt.push_str(&format!("- {}\n", file.display()));
+    }
+    if diff.files.len() > TASK_SPEC_MAX_FILES {
+        prompt.push_str(&format!(
+            "- ... and {} more\n",
+            diff.files.len() - TASK_SPEC_MAX_FILES
+        ));
+    }
+
+    prompt.push_str(&format!(
+        "\n## Output format\n\n\
+         Output a single spec file. Group assertions under `## Suite` headings, \
+         one `- assertion` per line:\n\n\
+         ```text\n\
+         <!-- QA_SPEC_FILE: {}.md -->\n\
+         ## cli\n\
+         - `othala list` prints the new column, exits 0\n\
+         ```\n",
+        task.id.0
+    ));
+    prompt
+}
+
+/// Parse a per-task spec agent's output. The first `QA_SPEC_FILE` block is
+/// used when present, otherwise the whole output.
+pub fn parse_task_qa_spec_output(raw: &str) -> QASpec {
+    let parsed = parse_qa_spec_gen_output(raw);
+    match parsed.files.into_iter().next() {
+        Some(file) => parse_qa_spec(&file.content),
+        None => parse_qa_spec(raw.trim()),
+    }
+}
+
+/// Generate a QA spec for `task` from its title, description and diff, and
+/// save it to `.othala/qa/<task_id>.yaml`.
+///
+/// The agent runs in the task's worktree when it exists. Fails when the
+/// agent produced no assertions.
+pub fn generate_qa_spec(
+    repo_root: &Path,
+    task: &Task,
+    diff: &DiffSnapshot,
+    model: ModelKind,
+) -> anyhow::Result<QASpec> {
+    let cwd = if task.worktree_path.is_dir() {
+        task.worktree_path.as_path()
+    } else {
+        repo_root
+    };
+    let raw = run_spec_agent_blocking(
+        cwd,
+        TaskId::new(format!("qa-spec-{}", task.id.0)),
+        model,
+        build_task_qa_spec_prompt(task, diff),
+        |_| {},
+    )?;
+    let spec = parse_task_qa_spec_output(&raw);
+    if spec.tests.is_empty() {
+        anyhow::bail!("QA spec agent produced no assertions for {}", task.id.0);
+    }
+    save_generated_qa_spec(repo_root, &task.id, &spec)?;
+    Ok(spec)
 }
 
 /// Re-export progress line parsing from context_gen (same agent output format).
@@ -744,6 +1009,98 @@ mod tests {
         fs::remove_dir_all(&tmp).ok();
     }
 
+    fn spec_output(files: &[(&str, &str)]) -> QASpecGenOutput {
+        QASpecGenOutput {
+            files: files
+                .iter()
+                .map(|(filename, content)| QASpecFile {
+                    filename: filename.to_string(),
+                    content: content.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    fn golden_repo(name: &str) -> PathBuf {
+        let tmp = std::env::temp_dir().join(format!(
+            "othala-qaspec-check-{name}-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let qa_dir = tmp.join(".othala/qa/flows");
+        fs::create_dir_all(&qa_dir).unwrap();
+        fs::write(
+            tmp.join(".othala/qa/baseline.md"),
+            "# Baseline  \n\n1. build\n2. test\t\n\n",
+        )
+        .unwrap();
+        fs::write(qa_dir.join("tui.md"), "# TUI\nopen\nquit\n").unwrap();
+        tmp
+    }
+
+    #[test]
+    fn check_qa_spec_files_passes_when_only_trailing_whitespace_differs() {
+        let tmp = golden_repo("same");
+        let output = spec_output(&[
+            ("baseline.md", "# Baseline\n\n1. build\n2. test"),
+            ("flows/tui.md", "# TUI\nopen\nquit"),
+        ]);
+
+        assert!(check_qa_spec_files(&tmp, &output).is_empty());
+
+        fs::remove_dir_all(&tmp).ok();
+    }
+
+    #[test]
+    fn check_qa_spec_files_reports_drift_as_unified_diff() {
+        let tmp = golden_repo("drift");
+        let output = spec_output(&[
+            ("baseline.md", "# Baseline\n\n1. build\n2. lint\n3. test"),
+            ("flows/tui.md", "# TUI\nopen\nquit"),
+        ]);
+
+        let drift = check_qa_spec_files(&tmp, &output);
+        assert_eq!(
+            drift,
+            vec![QASpecDrift::Changed {
+                filename: "baseline.md".to_string(),
+                diff: "--- a/.othala/qa/baseline.md\n\
+                       +++ b/.othala/qa/baseline.md\n\
+                       @@ -1,4 +1,5 @@\n \
+                       # Baseline\n \n \
+                       1. build\n\
+                       -2. test\n\
+                       +2. lint\n\
+                       +3. test\n"
+                    .to_string(),
+            }]
+        );
+        // Nothing is overwritten.
+        assert!(fs::read_to_string(tmp.join(".othala/qa/baseline.md"))
+            .unwrap()
+            .contains("2. test"));
+
+        fs::remove_dir_all(&tmp).ok();
+    }
+
+    #[test]
+    fn check_qa_spec_files_reports_missing_spec() {
+        let tmp = golden_repo("missing");
+        let output = spec_output(&[
+            ("baseline.md", "# Baseline\n\n1. build\n2. test"),
+            ("flows/db.md", "# DB\nmigrate"),
+        ]);
+
+        assert_eq!(
+            check_qa_spec_files(&tmp, &output),
+            vec![QASpecDrift::Missing {
+                filename: "flows/db.md".to_string(),
+            }]
+        );
+        assert!(!tmp.join(".othala/qa/flows/db.md").exists());
+
+        fs::remove_dir_all(&tmp).ok();
+    }
+
     #[test]
     fn check_qa_spec_startup_returns_missing() {
         let tmp =
@@ -928,4 +1285,58 @@ Use tmux for TUI tests.
 
         fs::remove_dir_all(&tmp).ok();
     }
+
+    #[test]
+    fn task_qa_spec_prompt_carries_task_and_diff_stat() {
+        let mut task = Task::new(
+            TaskId::new("T7"),
+            RepoId("example".to_string()),
+            "Add --json to othala list".to_string(),
+            PathBuf::from(".orch/wt/T7"),
+        );
+        task.description = Some("Machine-readable task listing.".to_string());
+        let diff = DiffSnapshot {
+            files: (0..52)
+                .map(|i| PathBuf::from(format!("src/f{i}.rs")))
+                .collect(),
+            shortstat: Some("52 files changed, 90 insertions(+)".to_string()),
+        };
+
+        let prompt = build_task_qa_spec_prompt(&task, &diff);
+        assert!(prompt.contains("Title: Add --json to othala list"));
+        assert!(prompt.contains("Machine-readable task listing."));
+        assert!(prompt.contains("52 files changed, 90 insertions(+)"));
+        assert!(prompt.contains("- src/f49.rs\n- ... and 2 more"));
+        assert!(prompt.contains("<!-- QA_SPEC_FILE: T7.md -->"));
+    }
+
+    #[test]
+    fn task_qa_spec_output_parses_and_roundtrips_through_yaml() {
+        let raw = "Checked the CLI.\n\
+                   <!-- QA_SPEC_FILE: T7.md -->\n\
+                   ## CLI\n\
+                   - `othala list --json` prints a JSON array, exits 0\n\
+                   - `othala list` output is unchanged\n";
+        let spec = parse_task_qa_spec_output(raw);
+        assert_eq!(spec.tests.len(), 2);
+        assert_eq!(spec.tests[0].suite, "cli");
+        assert!(!spec.raw.contains("Checked the CLI."));
+        assert_eq!(
+            parse_task_qa_spec_output("- exits 0\n").tests[0].steps,
+            "exits 0"
+        );
+
+        let tmp = std::env::temp_dir().join(format!(
+            "othala-qaspec-task-{}-{}",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let task_id = TaskId::new("T7");
+        assert!(load_generated_qa_spec(&tmp, &task_id).is_none());
+        let path = save_generated_qa_spec(&tmp, &task_id, &spec).unwrap();
+        assert_eq!(path, tmp.join(".othala/qa/T7.yaml"));
+        assert_eq!(load_generated_qa_spec(&tmp, &task_id), Some(spec));
+
+        fs::remove_dir_all(&tmp).ok();
+    }
 }
@@ -0,0 +1,269 @@
+//! Questions agents raise with `[needs_human]`.
+//!
+//! The daemon captures the question text from agent output and stores it as an
+//! open item on the task. Answering through `othala chat send` or the TUI
+//! resolves it; questions left open past the stale threshold are escalated.
+
+use chrono::{DateTime, Utc};
+use orch_agents::{detect_common_signal, AgentSignalKind};
+use orch_core::events::{Event, EventKind};
+use orch_core::types::{EventId, RepoId, TaskId};
+use std::time::Duration;
+
+use crate::persistence::{PersistenceError, SqliteStore};
+use crate::types::AgentQuestion;
+
+/// Recorded when the agent signals without any question text.
+pub const FALLBACK_QUESTION: &str = "Agent requested human assistance";
+
+/// How many lines before the signal are joined into the question.
+const MAX_QUESTION_LINES: usize = 5;
+
+/// Question text for every `[needs_human]` signal in `lines`.
+///
+/// Text following the tag on the same line wins; otherwise the paragraph
+/// right before the signal is used.
+pub fn extract_questions(lines: &[String]) -> Vec<String> {
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| is_need_human(line))
+        .map(|(index, line)| {
+            text_after_tag(line)
+                .or_else(|| paragraph_before(lines, index))
+                .unwrap_or_else(|| FALLBACK_QUESTION.to_string())
+        })
+        .collect()
+}
+
+fn is_need_human(line: &str) -> bool {
+    detect_common_signal(line).is_some_and(|signal| signal.kind == AgentSignalKind::NeedHuman)
+}
+
+fn text_after_tag(line: &str) -> Option<String> {
+    let tag_start = line.to_ascii_lowercase().find("[need")?;
+    let tag_end = tag_start + line[tag_start..].find(']')?;
+    let text = line[tag_end + 1..]
+        .trim_start_matches(|c: char| c == ':' || c == '-' || c.is_whitespace())
+        .trim_end();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn paragraph_before(lines: &[String], signal_index: usize) -> Option<String> {
+    let mut paragraph: Vec<&str> = lines[..signal_index]
+        .iter()
+        .rev()
+        .map(|line| line.trim())
+        .skip_while(|line| line.is_empty())
+        .take_while(|line| !line.is_empty() && detect_common_signal(line).is_none())
+        .take(MAX_QUESTION_LINES)
+        .collect();
+    if paragraph.is_empty() {
+        return None;
+    }
+    paragraph.reverse();
+    Some(paragraph.join(" "))
+}
+
+/// Store the questions found in `lines` as open questions on the task,
+/// skipping any that repeat a question still open. Returns the new ones.
+pub fn capture_questions(
+    store: &SqliteStore,
+    task_id: &TaskId,
+    lines: &[String],
+    at: DateTime<Utc>,
+) -> Result<Vec<AgentQuestion>, PersistenceError> {
+    let extracted = extract_questions(lines);
+    if extracted.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut known = store.list_questions_for_task(task_id)?;
+    let mut captured = Vec::new();
+    for text in extracted {
+        if known.iter().any(|q| q.is_open() && q.question == text) {
+            continue;
+        }
+        let question = AgentQuestion {
+            question_id: format!("Q-{}-{}", task_id.0, known.len() + 1),
+            task_id: task_id.clone(),
+            question: text,
+            asked_at: at,
+            answer: None,
+            answered_at: None,
+            escalated_at: None,
+        };
+        store.insert_question(&question)?;
+        known.push(question.clone());
+        captured.push(question);
+    }
+    Ok(captured)
+}
+
+/// Open questions older than `stale_after` that have not been escalated yet.
+pub fn stale_questions(
+    store: &SqliteStore,
+    now: DateTime<Utc>,
+    stale_after: Duration,
+) -> Result<Vec<AgentQuestion>, PersistenceError> {
+    Ok(store
+        .list_open_questions()?
+        .into_iter()
+        .filter(|q| q.escalated_at.is_none() && open_for(q, now) >= stale_after)
+        .collect())
+}
+
+/// How long a question has been waiting as of `now`.
+pub fn open_for(question: &AgentQuestion, now: DateTime<Utc>) -> Duration {
+    (now - question.asked_at).to_std().unwrap_or_default()
+}
+
+pub fn question_asked_event(question: &AgentQuestion, repo_id: Option<RepoId>) -> Event {
+    question_event(
+        "ASKED",
+        question,
+        repo_id,
+        question.asked_at,
+        EventKind::QuestionAsked {
+            question_id: question.question_id.clone(),
+            question: question.question.clone(),
+        },
+    )
+}
+
+pub fn question_answered_event(question: &AgentQuestion, repo_id: Option<RepoId>) -> Event {
+    question_event(
+        "ANSWERED",
+        question,
+        repo_id,
+        question.answered_at.unwrap_or_else(Utc::now),
+        EventKind::QuestionAnswered {
+            question_id: question.question_id.clone(),
+            answer: question.answer.clone().unwrap_or_default(),
+        },
+    )
+}
+
+pub fn question_stale_event(
+    question: &AgentQuestion,
+    repo_id: Option<RepoId>,
+    now: DateTime<Utc>,
+) -> Event {
+    question_event(
+        "STALE",
+        question,
+        repo_id,
+        now,
+        EventKind::QuestionStale {
+            question_id: question.question_id.clone(),
+            question: question.question.clone(),
+            open_secs: open_for(question, now).as_secs(),
+        },
+    )
+}
+
+fn question_event(
+    label: &str,
+    question: &AgentQuestion,
+    repo_id: Option<RepoId>,
+    at: DateTime<Utc>,
+    kind: EventKind,
+) -> Event {
+    Event {
+        id: EventId(format!("E-QUESTION-{label}-{}", question.question_id)),
+        task_id: Some(question.task_id.clone()),
+        repo_id,
+        at,
+        kind,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn lines(raw: &[&str]) -> Vec<String> {
+        raw.iter().map(|line| line.to_string()).collect()
+    }
+
+    fn mk_store() -> SqliteStore {
+        let store = SqliteStore::open_in_memory().expect("in-memory store");
+        store.migrate().expect("migrate");
+        store
+    }
+
+    #[test]
+    fn extracts_text_after_tag_or_preceding_paragraph() {
+        let output = lines(&[
+            "Reading the schema...",
+            "",
+            "The spec mentions both Postgres and SQLite.",
+            "Which database should I target?",
+            "[needs_human]",
+            "[needs_human]: Should the old endpoint be kept?",
+        ]);
+        assert_eq!(
+            extract_questions(&output),
+            vec![
+                "The spec mentions both Postgres and SQLite. Which database should I target?",
+                "Should the old endpoint be kept?",
+            ]
+        );
+        assert_eq!(
+            extract_questions(&lines(&["[needs_human]"])),
+            vec![FALLBACK_QUESTION]
+        );
+    }
+
+    #[test]
+    fn capture_dedupes_open_questions_and_answer_resolves_them() {
+        let store = mk_store();
+        let task_id = TaskId::new("T1");
+        let at = Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap();
+        let output = lines(&["Which database?", "[needs_human]"]);
+
+        let first = capture_questions(&store, &task_id, &output, at).unwrap();
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].question_id, "Q-T1-1");
+        assert!(capture_questions(&store, &task_id, &output, at)
+            .unwrap()
+            .is_empty());
+
+        let answered = store
+            .answer_open_questions(&task_id, "postgres", at)
+            .unwrap();
+        assert_eq!(answered.len(), 1);
+        assert_eq!(answered[0].answer.as_deref(), Some("postgres"));
+        assert!(store.list_open_questions().unwrap().is_empty());
+
+        let again = capture_questions(&store, &task_id, &output, at).unwrap();
+        assert_eq!(again[0].question_id, "Q-T1-2");
+    }
+
+    #[test]
+    fn stale_questions_are_reported_until_escalated() {
+        let store = mk_store();
+        let task_id = TaskId::new("T1");
+        let asked = Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap();
+        let captured =
+            capture_questions(&store, &task_id, &lines(&["[needs_human] Ok?"]), asked).unwrap();
+        let stale_after = Duration::from_secs(1_800);
+
+        let early = asked + chrono::Duration::minutes(10);
+        assert!(stale_questions(&store, early, stale_after).unwrap().is_empty());
+
+        let late = asked + chrono::Duration::minutes(45);
+        let stale = stale_questions(&store, late, stale_after).unwrap();
+        assert_eq!(stale, captured);
+        assert!(matches!(
+            question_stale_event(&stale[0], None, late).kind,
+            EventKind::QuestionStale { open_secs: 2_700, .. }
+        ));
+
+        store
+            .mark_question_escalated(&stale[0].question_id, late)
+            .unwrap();
+        assert!(stale_questions(&store, late, stale_after).unwrap().is_empty());
+    }
+}
@@ -1,9 +1,14 @@
 use chrono::{DateTime, Utc};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
+use std::path::Path;
+use std::time::Duration;
 
 const CLEANUP_IDLE_SECS: i64 = 7_200;
 
+/// Per-bucket counts saved by the daemon, relative to the repo root.
+pub const RATE_LIMIT_STATS_PATH: &str = ".othala/rate-limits.json";
+
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct RateLimitConfig {
     pub requests_per_minute: u32,
@@ -97,6 +102,23 @@ impl TokenBucket {
     }
 }
 
+/// Cumulative outcomes of the token requests made against one bucket.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BucketStats {
+    pub allowed: u64,
+    pub throttled: u64,
+}
+
+impl BucketStats {
+    fn count(&mut self, allowed: bool) {
+        if allowed {
+            self.allowed += 1;
+        } else {
+            self.throttled += 1;
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum RateLimitResult {
     Allowed { remaining: u32 },
@@ -114,6 +136,16 @@ impl fmt::Display for RateLimitResult {
     }
 }
 
+/// Returned by [`RateLimiter::try_acquire`]: how long until a token refills.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryAfter(pub Duration);
+
+impl fmt::Display for RetryAfter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rate limited (retry after {:.3}s)", self.0.as_secs_f64())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RateLimitError {
     ConfigError(String),
@@ -133,6 +165,9 @@ impl fmt::Display for RateLimitError {
 pub struct RateLimiter {
     pub config: RateLimitConfig,
     pub buckets: HashMap<String, TokenBucket>,
+    /// Allowed and throttled requests per bucket key. Kept across resets and
+    /// cleanup.
+    pub stats: BTreeMap<String, BucketStats>,
 }
 
 impl RateLimiter {
@@ -140,6 +175,7 @@ impl RateLimiter {
         Self {
             config,
             buckets: HashMap::new(),
+            stats: BTreeMap::new(),
         }
     }
 
@@ -149,13 +185,66 @@ impl RateLimiter {
 
     pub fn record_request(&mut self, key: &str) {
         let now = Utc::now();
-        let bucket = self.bucket_for_key(key);
-        let _ = bucket.consume_at(1.0, now);
+        let bucket = self.bucket_for_key(key, now);
+        let allowed = bucket.consume_at(1.0, now);
+        self.count(key, allowed);
+    }
+
+    /// Take one token for `key` at `now`, or report how long until one is
+    /// available. Tokens refill continuously up to `burst_size`.
+    pub fn try_acquire(&mut self, key: &str, now: DateTime<Utc>) -> Result<(), RetryAfter> {
+        let bucket = self.bucket_for_key(key, now);
+        let result = if bucket.consume_at(1.0, now) {
+            Ok(())
+        } else {
+            Err(RetryAfter(Duration::from_secs_f64(bucket.retry_after_secs(1.0))))
+        };
+        self.count(key, result.is_ok());
+        result
+    }
+
+    /// Allowed and throttled counts for `key`; zero for an unused key.
+    pub fn bucket_stats(&self, key: &str) -> BucketStats {
+        self.stats.get(key).copied().unwrap_or_default()
+    }
+
+    /// Write the per-bucket counts to [`RATE_LIMIT_STATS_PATH`] so the CLI
+    /// can report them. Written to a temp file and renamed so readers never
+    /// see a partial file.
+    pub fn save_stats(&self, repo_root: &Path) -> std::io::Result<()> {
+        let path = repo_root.join(RATE_LIMIT_STATS_PATH);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(&self.stats)?)?;
+        std::fs::rename(tmp, path)
+    }
+
+    /// Wait until a token for `key` is available, then take it.
+    pub fn acquire_blocking(&mut self, key: &str) -> Duration {
+        self.acquire_blocking_with(key, Utc::now, std::thread::sleep)
+    }
+
+    /// [`Self::acquire_blocking`] with an injected clock and sleep. Returns
+    /// the total time spent waiting.
+    pub fn acquire_blocking_with(
+        &mut self,
+        key: &str,
+        mut now: impl FnMut() -> DateTime<Utc>,
+        mut sleep: impl FnMut(Duration),
+    ) -> Duration {
+        let mut waited = Duration::ZERO;
+        while let Err(RetryAfter(wait)) = self.try_acquire(key, now()) {
+            sleep(wait);
+            waited += wait;
+        }
+        waited
     }
 
     pub fn remaining_tokens(&mut self, key: &str) -> u32 {
         let now = Utc::now();
-        let bucket = self.bucket_for_key(key);
+        let bucket = self.bucket_for_key(key, now);
         bucket.remaining_whole_tokens_at(now)
     }
 
@@ -176,8 +265,15 @@ impl RateLimiter {
         });
     }
 
+    fn count(&mut self, key: &str, allowed: bool) {
+        self.stats
+            .entry(key.to_string())
+            .or_default()
+            .count(allowed);
+    }
+
     fn check_rate_limit_at(&mut self, key: &str, now: DateTime<Utc>) -> RateLimitResult {
-        let bucket = self.bucket_for_key(key);
+        let bucket = self.bucket_for_key(key, now);
         bucket.refill_at(now);
 
         if bucket.tokens >= 1.0 {
@@ -190,11 +286,14 @@ impl RateLimiter {
         }
     }
 
-    fn bucket_for_key(&mut self, key: &str) -> &mut TokenBucket {
+    fn bucket_for_key(&mut self, key: &str, now: DateTime<Utc>) -> &mut TokenBucket {
         let spec = self.bucket_spec_for_key(key);
         self.buckets
             .entry(key.to_string())
-            .or_insert_with(|| TokenBucket::new(spec.0, spec.1))
+            .or_insert_with(|| TokenBucket {
+                last_refill: now,
+                ..TokenBucket::new(spec.0, spec.1)
+            })
     }
 
     fn bucket_spec_for_key(&self, key: &str) -> (f64, f64) {
@@ -225,6 +324,38 @@ impl Default for RateLimiter {
     }
 }
 
+/// One `key  allowed=N throttled=M` line per bucket, in key order.
+pub fn display_stats(stats: &BTreeMap<String, BucketStats>) -> String {
+    stats
+        .iter()
+        .map(|(key, stats)| {
+            format!(
+                "  {key:<24} allowed={} throttled={}",
+                stats.allowed, stats.throttled
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Per-bucket counts last saved by the daemon; empty when it never saved
+/// any, or when the file cannot be read or parsed.
+pub fn load_stats(repo_root: &Path) -> BTreeMap<String, BucketStats> {
+    let path = repo_root.join(RATE_LIMIT_STATS_PATH);
+    let raw = match std::fs::read_to_string(&path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return BTreeMap::new(),
+        Err(err) => {
+            eprintln!("warning: failed to read {}: {err}", path.display());
+            return BTreeMap::new();
+        }
+    };
+    serde_json::from_str(&raw).unwrap_or_else(|err| {
+        eprintln!("warning: ignoring malformed {}: {err}", path.display());
+        BTreeMap::new()
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -415,6 +546,111 @@ mod tests {
         }
     }
 
+    #[test]
+    fn try_acquire_exhausts_burst_then_reports_retry_after() {
+        let mut cfg = mk_config();
+        cfg.burst_size = 3;
+        let mut limiter = RateLimiter::new(cfg);
+        let t0 = Utc::now();
+
+        for _ in 0..3 {
+            assert_eq!(limiter.try_acquire("model:codex", t0), Ok(()));
+        }
+        let RetryAfter(wait) = limiter
+            .try_acquire("model:codex", t0)
+            .expect_err("burst exhausted");
+        assert_eq!(wait, std::time::Duration::from_secs(1));
+    }
+
+    #[test]
+    fn try_acquire_refills_after_clock_advances() {
+        let mut cfg = mk_config();
+        cfg.burst_size = 2;
+        let mut limiter = RateLimiter::new(cfg);
+        let t0 = Utc::now();
+        assert!(limiter.try_acquire("k", t0).is_ok());
+        assert!(limiter.try_acquire("k", t0).is_ok());
+        assert!(limiter.try_acquire("k", t0).is_err());
+
+        let half = t0 + Duration::milliseconds(500);
+        assert!(limiter.try_acquire("k", half).is_err());
+        let later = t0 + Duration::seconds(1);
+        assert!(limiter.try_acquire("k", later).is_ok());
+
+        // Refill is capped at the burst size.
+        let much_later = later + Duration::minutes(10);
+        assert!(limiter.try_acquire("k", much_later).is_ok());
+        assert!(limiter.try_acquire("k", much_later).is_ok());
+        assert!(limiter.try_acquire("k", much_later).is_err());
+    }
+
+    #[test]
+    fn burst_past_the_limit_counts_throttled_requests() {
+        let mut cfg = mk_config();
+        cfg.burst_size = 3;
+        let mut limiter = RateLimiter::new(cfg);
+        let t0 = Utc::now();
+
+        let rejected = (0..8)
+            .filter(|_| limiter.try_acquire("model:codex", t0).is_err())
+            .count();
+        limiter.record_request("model:codex");
+
+        assert_eq!(rejected, 5);
+        assert_eq!(
+            limiter.bucket_stats("model:codex"),
+            BucketStats {
+                allowed: 3,
+                throttled: 6,
+            }
+        );
+        assert_eq!(limiter.bucket_stats("model:claude"), BucketStats::default());
+
+        limiter.reset_all();
+        assert_eq!(limiter.bucket_stats("model:codex").throttled, 6);
+
+        let root = std::env::temp_dir().join(format!(
+            "othala-rate-limits-{}-{}",
+            std::process::id(),
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        assert!(load_stats(&root).is_empty());
+        limiter.save_stats(&root).expect("save stats");
+        assert_eq!(load_stats(&root), limiter.stats);
+        assert!(!root
+            .join(RATE_LIMIT_STATS_PATH)
+            .with_extension("json.tmp")
+            .exists());
+
+        let mut restarted = RateLimiter::new(mk_config());
+        restarted.stats = load_stats(&root);
+        restarted.record_request("model:codex");
+        assert_eq!(restarted.bucket_stats("model:codex").allowed, 4);
+
+        std::fs::write(root.join(RATE_LIMIT_STATS_PATH), "{not json").expect("corrupt stats");
+        assert!(load_stats(&root).is_empty());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn acquire_blocking_sleeps_for_retry_after() {
+        let mut cfg = mk_config();
+        cfg.burst_size = 1;
+        let mut limiter = RateLimiter::new(cfg);
+        let t0 = Utc::now();
+        let clock = std::cell::Cell::new(t0);
+        assert!(limiter.try_acquire("k", t0).is_ok());
+
+        let waited = limiter.acquire_blocking_with(
+            "k",
+            || clock.get(),
+            |wait| clock.set(clock.get() + Duration::from_std(wait).expect("duration")),
+        );
+
+        assert_eq!(waited, std::time::Duration::from_secs(1));
+        assert!(limiter.try_acquire("k", clock.get()).is_err());
+    }
+
     #[test]
     fn rate_limit_result_formatting_is_readable() {
         let allowed = RateLimitResult::Allowed { remaining: 4 }.to_string();
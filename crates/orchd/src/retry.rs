@@ -293,6 +293,7 @@ mod tests {
             needs_human: false,
             success,
             duration_secs: 1,
+            confidence: None,
         }
     }
 
@@ -0,0 +1,449 @@
+//! Retry diff regression guard.
+//!
+//! Every verify run stores the worktree patch as a `run_patch` artifact. When
+//! a retry fails verify, its patch is compared hunk-by-hunk against the last
+//! attempt whose verify passed; if too much of that earlier work is gone, the
+//! retry is flagged and the earlier patch is kept as the task's candidate.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use chrono::{DateTime, Utc};
+use orch_core::config::RetryGuardConfig;
+use orch_core::types::TaskId;
+use serde::{Deserialize, Serialize};
+
+use crate::persistence::{PersistenceError, SqliteStore};
+use crate::types::ArtifactRecord;
+
+pub const RUN_PATCH_ARTIFACT_KIND: &str = "run_patch";
+pub const CANDIDATE_PATCH_ARTIFACT_KIND: &str = "candidate_patch";
+
+#[derive(Debug, thiserror::Error)]
+pub enum RetryGuardError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Persistence(#[from] PersistenceError),
+    #[error("json error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// One `@@` hunk of a unified diff, reduced to the lines it changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatchHunk {
+    pub file: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl PatchHunk {
+    fn changed_line_count(&self) -> usize {
+        self.added.len() + self.removed.len()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryDiffReport {
+    pub prior_hunks: usize,
+    pub retained_hunks: usize,
+    pub removed_hunks: usize,
+    pub removed_percent: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RetryGuardVerdict {
+    Accept,
+    Regression(RetryDiffReport),
+}
+
+/// Metadata stored alongside each `run_patch` artifact.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunPatchMeta {
+    pub attempt: u32,
+    pub verify_passed: bool,
+    #[serde(default)]
+    pub flagged: bool,
+}
+
+/// Split a unified diff into hunks. Context lines and blank changes are
+/// ignored so whitespace-only churn doesn't count as work.
+pub fn parse_patch_hunks(patch: &str) -> Vec<PatchHunk> {
+    let mut hunks = Vec::new();
+    let mut file = String::new();
+    let mut current: Option<PatchHunk> = None;
+
+    for line in patch.lines() {
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            hunks.extend(current.take());
+            file = rest
+                .rsplit_once(" b/")
+                .map(|(_, path)| path.to_string())
+                .unwrap_or_default();
+        } else if let Some(path) = line.strip_prefix("+++ b/") {
+            file = path.to_string();
+        } else if line.starts_with("+++ ") || line.starts_with("--- ") {
+            // `/dev/null` on either side keeps the name from `diff --git`.
+        } else if line.starts_with("@@") {
+            hunks.extend(current.take());
+            current = Some(PatchHunk {
+                file: file.clone(),
+                added: Vec::new(),
+                removed: Vec::new(),
+            });
+        } else if let Some(hunk) = current.as_mut() {
+            if let Some(added) = line.strip_prefix('+') {
+                if !added.trim().is_empty() {
+                    hunk.added.push(added.trim().to_string());
+                }
+            } else if let Some(removed) = line.strip_prefix('-') {
+                if !removed.trim().is_empty() {
+                    hunk.removed.push(removed.trim().to_string());
+                }
+            }
+        }
+    }
+    hunks.extend(current);
+    hunks.retain(|hunk| hunk.changed_line_count() > 0);
+    hunks
+}
+
+/// Count how many hunks of `prior` survive in `candidate`. A hunk survives
+/// when at least half of its changed lines are changed the same way in the
+/// same file of the candidate.
+pub fn compare_patches(prior: &str, candidate: &str) -> RetryDiffReport {
+    let prior_hunks = parse_patch_hunks(prior);
+    let candidate_hunks = parse_patch_hunks(candidate);
+
+    let retained_hunks = prior_hunks
+        .iter()
+        .filter(|hunk| {
+            let in_file: Vec<&PatchHunk> = candidate_hunks
+                .iter()
+                .filter(|other| other.file == hunk.file)
+                .collect();
+            let kept_added = hunk
+                .added
+                .iter()
+                .filter(|line| in_file.iter().any(|other| other.added.contains(line)))
+                .count();
+            let kept_removed = hunk
+                .removed
+                .iter()
+                .filter(|line| in_file.iter().any(|other| other.removed.contains(line)))
+                .count();
+            (kept_added + kept_removed) * 2 >= hunk.changed_line_count()
+        })
+        .count();
+
+    let removed_hunks = prior_hunks.len() - retained_hunks;
+    let removed_percent = if prior_hunks.is_empty() {
+        0
+    } else {
+        (removed_hunks * 100 / prior_hunks.len()) as u8
+    };
+    RetryDiffReport {
+        prior_hunks: prior_hunks.len(),
+        retained_hunks,
+        removed_hunks,
+        removed_percent,
+    }
+}
+
+/// Decide whether a retry's patch should be accepted. A candidate whose
+/// verify passes is always accepted — by the verify signal it is at least as
+/// good as the attempt it replaced.
+pub fn evaluate_retry_diff(
+    prior_passing: &str,
+    candidate: &str,
+    candidate_verify_passed: bool,
+    config: &RetryGuardConfig,
+) -> RetryGuardVerdict {
+    if !config.enabled || candidate_verify_passed {
+        return RetryGuardVerdict::Accept;
+    }
+    let report = compare_patches(prior_passing, candidate);
+    if report.prior_hunks < config.min_prior_hunks.max(1)
+        || report.removed_percent < config.max_removed_percent
+    {
+        return RetryGuardVerdict::Accept;
+    }
+    RetryGuardVerdict::Regression(report)
+}
+
+/// Directory holding stored patches for a task.
+pub fn patch_dir(repo_root: &Path, task_id: &TaskId) -> PathBuf {
+    repo_root.join(".orch/patches").join(&task_id.0)
+}
+
+/// Diff the worktree (committed and uncommitted changes) against its merge
+/// base with trunk, falling back to `HEAD` when no trunk is found.
+pub fn capture_worktree_patch(worktree: &Path) -> Option<String> {
+    let base = ["origin/main", "main"]
+        .iter()
+        .find_map(|trunk| git_stdout(worktree, &["merge-base", "HEAD", trunk]))
+        .unwrap_or_else(|| "HEAD".to_string());
+    let output = Command::new("git")
+        .args(["diff", "--no-color", base.trim()])
+        .current_dir(worktree)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn git_stdout(cwd: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !stdout.is_empty()).then_some(stdout)
+}
+
+/// Write a run patch to disk and record it as an artifact.
+pub fn store_run_patch(
+    store: &SqliteStore,
+    repo_root: &Path,
+    task_id: &TaskId,
+    meta: &RunPatchMeta,
+    patch: &str,
+    at: DateTime<Utc>,
+) -> Result<ArtifactRecord, RetryGuardError> {
+    let stamp = at.timestamp_nanos_opt().unwrap_or_default();
+    let dir = patch_dir(repo_root, task_id);
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("attempt-{}-{stamp}.patch", meta.attempt));
+    fs::write(&path, patch)?;
+
+    let record = ArtifactRecord {
+        artifact_id: format!("A-PATCH-{}-{stamp}", task_id.0),
+        task_id: task_id.clone(),
+        kind: RUN_PATCH_ARTIFACT_KIND.to_string(),
+        path: path.display().to_string(),
+        created_at: at,
+        metadata_json: Some(serde_json::to_string(meta)?),
+    };
+    store.insert_artifact(&record)?;
+    Ok(record)
+}
+
+/// Most recent stored patch whose verify passed, with its contents.
+pub fn last_passing_patch(
+    store: &SqliteStore,
+    task_id: &TaskId,
+) -> Result<Option<(ArtifactRecord, String)>, RetryGuardError> {
+    let artifacts = store.list_artifacts_for_task(task_id, RUN_PATCH_ARTIFACT_KIND)?;
+    for artifact in artifacts.into_iter().rev() {
+        let passed = artifact
+            .metadata_json
+            .as_deref()
+            .and_then(|raw| serde_json::from_str::<RunPatchMeta>(raw).ok())
+            .is_some_and(|meta| meta.verify_passed);
+        if !passed {
+            continue;
+        }
+        // A patch file that was cleaned up can't be compared; keep looking.
+        if let Ok(contents) = fs::read_to_string(&artifact.path) {
+            return Ok(Some((artifact, contents)));
+        }
+    }
+    Ok(None)
+}
+
+/// Record `prior` as the task's candidate patch after a flagged retry.
+pub fn keep_candidate_patch(
+    store: &SqliteStore,
+    task_id: &TaskId,
+    prior: &ArtifactRecord,
+    at: DateTime<Utc>,
+) -> Result<ArtifactRecord, RetryGuardError> {
+    let record = ArtifactRecord {
+        artifact_id: format!(
+            "A-CANDIDATE-{}-{}",
+            task_id.0,
+            at.timestamp_nanos_opt().unwrap_or_default()
+        ),
+        task_id: task_id.clone(),
+        kind: CANDIDATE_PATCH_ARTIFACT_KIND.to_string(),
+        path: prior.path.clone(),
+        created_at: at,
+        metadata_json: Some(serde_json::json!({ "source_artifact_id": prior.artifact_id }).to_string()),
+    };
+    store.insert_artifact(&record)?;
+    Ok(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIOR: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,6 @@
+ pub mod api;
++pub mod cache;
++pub mod retry;
+@@ -20,2 +23,5 @@ fn run() {
+-    todo!()
++    let cache = cache::Cache::new();
++    retry::run_with(&cache)
+diff --git a/src/cache.rs b/src/cache.rs
+new file mode 100644
+--- /dev/null
++++ b/src/cache.rs
+@@ -0,0 +1,3 @@
++pub struct Cache;
++impl Cache { pub fn new() -> Self { Cache } }
++
+";
+
+    fn config() -> RetryGuardConfig {
+        RetryGuardConfig::default()
+    }
+
+    #[test]
+    fn parses_hunks_per_file() {
+        let hunks = parse_patch_hunks(PRIOR);
+        assert_eq!(hunks.len(), 3);
+        assert_eq!(hunks[0].file, "src/lib.rs");
+        assert_eq!(hunks[0].added, vec!["pub mod cache;", "pub mod retry;"]);
+        assert_eq!(hunks[1].removed, vec!["todo!()"]);
+        assert_eq!(hunks[2].file, "src/cache.rs");
+        assert_eq!(hunks[2].added.len(), 2);
+    }
+
+    #[test]
+    fn identical_patch_retains_every_hunk() {
+        let report = compare_patches(PRIOR, PRIOR);
+        assert_eq!(report.prior_hunks, 3);
+        assert_eq!(report.removed_hunks, 0);
+        assert_eq!(report.removed_percent, 0);
+    }
+
+    #[test]
+    fn rewrite_that_drops_prior_work_is_flagged() {
+        let candidate = "\
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -20,2 +20,2 @@ fn run() {
+-    todo!()
++    unimplemented!(\"rewrite\")
+";
+        let report = compare_patches(PRIOR, candidate);
+        assert_eq!(report.retained_hunks, 0);
+        assert_eq!(report.removed_percent, 100);
+
+        match evaluate_retry_diff(PRIOR, candidate, false, &config()) {
+            RetryGuardVerdict::Regression(report) => assert_eq!(report.removed_hunks, 3),
+            other => panic!("expected regression, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn passing_verify_or_small_loss_is_accepted() {
+        let partial = "\
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,6 @@
+ pub mod api;
++pub mod cache;
++pub mod retry;
+@@ -20,2 +23,5 @@ fn run() {
+-    todo!()
++    let cache = cache::Cache::new();
++    retry::run_with(&cache)
+";
+        assert_eq!(compare_patches(PRIOR, partial).removed_percent, 33);
+        assert_eq!(
+            evaluate_retry_diff(PRIOR, partial, false, &config()),
+            RetryGuardVerdict::Accept
+        );
+        assert_eq!(
+            evaluate_retry_diff(PRIOR, "", true, &config()),
+            RetryGuardVerdict::Accept
+        );
+    }
+
+    #[test]
+    fn thresholds_are_configurable() {
+        let strict = RetryGuardConfig {
+            max_removed_percent: 30,
+            ..RetryGuardConfig::default()
+        };
+        let partial = PRIOR.split("diff --git a/src/cache.rs").next().unwrap();
+        assert!(matches!(
+            evaluate_retry_diff(PRIOR, partial, false, &strict),
+            RetryGuardVerdict::Regression(_)
+        ));
+
+        let large_prior_only = RetryGuardConfig {
+            min_prior_hunks: 4,
+            ..RetryGuardConfig::default()
+        };
+        assert_eq!(
+            evaluate_retry_diff(PRIOR, "", false, &large_prior_only),
+            RetryGuardVerdict::Accept
+        );
+
+        let disabled = RetryGuardConfig {
+            enabled: false,
+            ..RetryGuardConfig::default()
+        };
+        assert_eq!(
+            evaluate_retry_diff(PRIOR, "", false, &disabled),
+            RetryGuardVerdict::Accept
+        );
+    }
+
+    #[test]
+    fn last_passing_patch_skips_failed_attempts() {
+        let store = SqliteStore::open_in_memory().expect("in-memory store");
+        store.migrate().expect("migrate");
+        let root = std::env::temp_dir().join(format!(
+            "othala-retry-guard-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let task_id = TaskId::new("T-GUARD");
+        let t0 = Utc::now();
+
+        let passing = RunPatchMeta {
+            attempt: 1,
+            verify_passed: true,
+            flagged: false,
+        };
+        let failing = RunPatchMeta {
+            attempt: 2,
+            verify_passed: false,
+            flagged: false,
+        };
+        store_run_patch(&store, &root, &task_id, &passing, PRIOR, t0).expect("store passing");
+        store_run_patch(
+            &store,
+            &root,
+            &task_id,
+            &failing,
+            "",
+            t0 + chrono::Duration::seconds(1),
+        )
+        .expect("store failing");
+
+        let (artifact, contents) = last_passing_patch(&store, &task_id)
+            .expect("lookup")
+            .expect("passing patch");
+        assert_eq!(contents, PRIOR);
+        assert!(artifact.path.contains("attempt-1-"));
+
+        let candidate = keep_candidate_patch(&store, &task_id, &artifact, t0).expect("candidate");
+        assert_eq!(candidate.path, artifact.path);
+        fs::remove_dir_all(&root).ok();
+    }
+}
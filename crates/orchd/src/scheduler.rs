@@ -15,11 +15,25 @@ pub struct SchedulerConfig {
 }
 
 impl SchedulerConfig {
+    /// The same concurrency limit for every built-in model.
+    pub fn uniform(per_repo_limit: usize, per_model_limit: usize) -> Self {
+        Self {
+            per_repo_limit,
+            per_model_limit: ModelKind::all()
+                .iter()
+                .map(|model| (*model, per_model_limit))
+                .collect(),
+        }
+    }
+
     pub fn from_org_config(config: &OrgConfig) -> Self {
         let mut per_model_limit = HashMap::new();
-        per_model_limit.insert(ModelKind::Claude, config.concurrency.claude);
-        per_model_limit.insert(ModelKind::Codex, config.concurrency.codex);
-        per_model_limit.insert(ModelKind::Gemini, config.concurrency.gemini);
+        for model in ModelKind::all() {
+            per_model_limit.insert(*model, config.concurrency.limit_for(*model));
+        }
+        for (model, _) in config.models.custom_models() {
+            per_model_limit.insert(model, config.concurrency.limit_for(model));
+        }
 
         Self {
             per_repo_limit: config.concurrency.per_repo,
@@ -289,6 +303,39 @@ mod tests {
         assert!(plan.blocked.is_empty());
     }
 
+    #[test]
+    fn from_org_config_applies_custom_model_limits() {
+        let mut config = OrgConfig::default();
+        config.models.custom.insert(
+            "aider".to_string(),
+            orch_core::config::CustomModelConfig {
+                command: "aider".to_string(),
+                args: Vec::new(),
+                api_key_env: None,
+                output_format: Default::default(),
+            },
+        );
+        config.concurrency.custom.insert("aider".to_string(), 1);
+        let aider = config.models.resolve("aider").expect("aider resolves");
+
+        let scheduler = Scheduler::new(SchedulerConfig::from_org_config(&config));
+        assert_eq!(scheduler.config.per_model_limit.get(&aider), Some(&1));
+
+        let plan = scheduler.plan(SchedulingInput {
+            queued: vec![mk_queued("T2", "repo", TaskPriority::Normal, Some(aider))],
+            running: vec![RunningTask {
+                task_id: TaskId("T1".to_string()),
+                repo_id: RepoId("repo".to_string()),
+                model: aider,
+            }],
+            all_task_states: HashMap::new(),
+            enabled_models: vec![aider, ModelKind::Claude],
+            availability: Vec::new(),
+        });
+        assert_eq!(plan.assignments.len(), 1);
+        assert_eq!(plan.assignments[0].model, ModelKind::Claude);
+    }
+
     #[test]
     fn plan_blocks_when_repo_limit_reached() {
         let scheduler = mk_scheduler(1, &[(ModelKind::Claude, 10)]);
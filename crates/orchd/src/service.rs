@@ -8,18 +8,21 @@ use orch_core::events::{Event, EventKind};
 use orch_core::state::TaskState;
 use orch_core::types::{EventId, SubmitMode, Task, TaskId};
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
 use crate::dependency_graph::{build_dependency_graph, restack_descendants_for_parent};
 use crate::event_log::{EventLogError, JsonlEventLog};
+use crate::merge_queue::{self, MergeQueueError};
 use crate::persistence::{PersistenceError, SqliteStore};
+use crate::questions;
 use crate::scheduler::{
     BlockedTask, ModelAvailability, QueuedTask, RunningTask, SchedulePlan, ScheduledAssignment,
     Scheduler, SchedulingInput,
 };
-use crate::state_machine::{task_state_tag, transition_task, StateMachineError};
-use crate::types::TaskRunRecord;
+use crate::state_machine::{task_state_tag, transition_task, TransitionError};
+use crate::task_ids::{normalize_alias, resolve_task_ref, TaskIdError};
+use crate::types::{AgentQuestion, TaskRunRecord};
 
 #[derive(Debug, thiserror::Error)]
 pub enum ServiceError {
@@ -28,9 +31,18 @@ pub enum ServiceError {
     #[error(transparent)]
     EventLog(#[from] EventLogError),
     #[error(transparent)]
-    StateMachine(#[from] StateMachineError),
+    Transition(#[from] TransitionError),
     #[error("task not found: {task_id}")]
     TaskNotFound { task_id: String },
+    #[error(transparent)]
+    TaskId(#[from] TaskIdError),
+    #[error(transparent)]
+    MergeQueue(#[from] MergeQueueError),
+    #[error("task {task_id} has child tasks: {}", children.join(", "))]
+    HasChildren {
+        task_id: String,
+        children: Vec<String>,
+    },
 }
 
 /// Event IDs for state transitions.
@@ -46,11 +58,15 @@ pub struct SchedulingTickOutcome {
     pub blocked: Vec<BlockedTask>,
 }
 
+/// Callback invoked with every event after it has been recorded.
+pub type EventListener = Box<dyn Fn(&Event) + Send + Sync>;
+
 /// The main service.
 pub struct OrchdService {
     pub store: SqliteStore,
     pub event_log: JsonlEventLog,
     pub scheduler: Scheduler,
+    event_listeners: Vec<EventListener>,
 }
 
 impl OrchdService {
@@ -59,9 +75,16 @@ impl OrchdService {
             store,
             event_log,
             scheduler,
+            event_listeners: Vec::new(),
         }
     }
 
+    /// Register a listener (e.g. the web SSE broadcaster) that is called for
+    /// every event passed through `record_event`.
+    pub fn add_event_listener(&mut self, listener: impl Fn(&Event) + Send + Sync + 'static) {
+        self.event_listeners.push(Box::new(listener));
+    }
+
     pub fn open(
         sqlite_path: impl Into<PathBuf>,
         event_log_root: impl Into<PathBuf>,
@@ -115,8 +138,118 @@ impl OrchdService {
             .collect())
     }
 
-    pub fn delete_task(&self, task_id: &TaskId) -> Result<bool, ServiceError> {
-        Ok(self.store.delete_task(task_id)?)
+    /// Tasks whose `parent_task_id` is `task_id`.
+    pub fn child_task_ids(&self, task_id: &TaskId) -> Result<Vec<TaskId>, ServiceError> {
+        Ok(self
+            .store
+            .list_tasks()?
+            .into_iter()
+            .filter(|t| t.parent_task_id.as_ref() == Some(task_id))
+            .map(|t| t.id)
+            .collect())
+    }
+
+    /// Delete a task. If other tasks name it as their parent this fails with
+    /// [`ServiceError::HasChildren`], unless `cascade` is set, in which case
+    /// the whole subtree is deleted. Returns the deleted IDs, descendants
+    /// before their parents; empty if the task does not exist.
+    pub fn delete_task(
+        &self,
+        task_id: &TaskId,
+        cascade: bool,
+    ) -> Result<Vec<TaskId>, ServiceError> {
+        let tasks = self.store.list_tasks()?;
+        if !tasks.iter().any(|t| &t.id == task_id) {
+            return Ok(Vec::new());
+        }
+        let mut children: HashMap<&TaskId, Vec<&TaskId>> = HashMap::new();
+        for task in &tasks {
+            if let Some(parent) = &task.parent_task_id {
+                children.entry(parent).or_default().push(&task.id);
+            }
+        }
+
+        if !cascade {
+            if let Some(direct) = children.get(task_id) {
+                return Err(ServiceError::HasChildren {
+                    task_id: task_id.0.clone(),
+                    children: direct.iter().map(|id| id.0.clone()).collect(),
+                });
+            }
+        }
+
+        // Post-order walk; `visited` guards against parent cycles.
+        let mut order = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = vec![(task_id, false)];
+        while let Some((id, expanded)) = stack.pop() {
+            if expanded {
+                order.push(id.clone());
+                continue;
+            }
+            if !visited.insert(id) {
+                continue;
+            }
+            stack.push((id, true));
+            for child in children.get(id).into_iter().flatten() {
+                stack.push((child, false));
+            }
+        }
+
+        let mut deleted = Vec::new();
+        for id in order {
+            if self.store.delete_task(&id)? {
+                deleted.push(id);
+            }
+        }
+        Ok(deleted)
+    }
+
+    // --- Task IDs & aliases ---
+
+    /// Resolve a user-supplied reference (full ID, unique prefix, or alias).
+    pub fn resolve_task_id(&self, input: &str) -> Result<TaskId, ServiceError> {
+        let task_ids = self.store.list_task_ids()?;
+        let aliases = self.store.list_task_aliases()?;
+        Ok(resolve_task_ref(input, &task_ids, &aliases)?)
+    }
+
+    /// Assign `alias` to a task. Re-aliasing the same task is a no-op; an alias
+    /// that matches another task's ID or alias is rejected.
+    pub fn set_task_alias(&self, task_id: &TaskId, alias: &str) -> Result<String, ServiceError> {
+        let alias = normalize_alias(alias)?;
+        if self.store.load_task(task_id)?.is_none() {
+            return Err(ServiceError::TaskNotFound {
+                task_id: task_id.0.clone(),
+            });
+        }
+        if let Some(owner) = self
+            .store
+            .list_task_ids()?
+            .into_iter()
+            .find(|id| id.0.to_lowercase() == alias && id != task_id)
+        {
+            return Err(TaskIdError::AliasCollision {
+                alias,
+                task_id: owner.0,
+            }
+            .into());
+        }
+        if let Some(owner) = self.store.load_task_alias(&alias)? {
+            if owner != *task_id {
+                return Err(TaskIdError::AliasCollision {
+                    alias,
+                    task_id: owner.0,
+                }
+                .into());
+            }
+        }
+        self.store.upsert_task_alias(&alias, task_id)?;
+        Ok(alias)
+    }
+
+    pub fn task_aliases(&self) -> Result<Vec<(String, TaskId)>, ServiceError> {
+        Ok(self.store.list_task_aliases()?)
     }
 
     // --- Events ---
@@ -124,6 +257,9 @@ impl OrchdService {
     pub fn record_event(&self, event: &Event) -> Result<(), ServiceError> {
         self.store.append_event(event)?;
         self.event_log.append_both(event)?;
+        for listener in &self.event_listeners {
+            listener(event);
+        }
         Ok(())
     }
 
@@ -146,6 +282,60 @@ impl OrchdService {
         Ok(self.store.count_runs_by_model()?)
     }
 
+    // --- Agent questions ---
+
+    pub fn task_questions(&self, task_id: &TaskId) -> Result<Vec<AgentQuestion>, ServiceError> {
+        Ok(self.store.list_questions_for_task(task_id)?)
+    }
+
+    pub fn open_questions(&self, task_id: &TaskId) -> Result<Vec<AgentQuestion>, ServiceError> {
+        Ok(self
+            .task_questions(task_id)?
+            .into_iter()
+            .filter(AgentQuestion::is_open)
+            .collect())
+    }
+
+    /// Capture `[needs_human]` questions from agent output and record a
+    /// `QuestionAsked` event for each new one. Returns the recorded events.
+    pub fn capture_questions(
+        &self,
+        task_id: &TaskId,
+        lines: &[String],
+        at: DateTime<Utc>,
+    ) -> Result<Vec<Event>, ServiceError> {
+        let captured = questions::capture_questions(&self.store, task_id, lines, at)?;
+        if captured.is_empty() {
+            return Ok(Vec::new());
+        }
+        let repo_id = self.store.load_task(task_id)?.map(|task| task.repo_id);
+        let mut events = Vec::with_capacity(captured.len());
+        for question in &captured {
+            let event = questions::question_asked_event(question, repo_id.clone());
+            self.record_event(&event)?;
+            events.push(event);
+        }
+        Ok(events)
+    }
+
+    /// Resolve a task's open questions with the user's reply and record a
+    /// `QuestionAnswered` event for each.
+    pub fn answer_questions(
+        &self,
+        task_id: &TaskId,
+        answer: &str,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<AgentQuestion>, ServiceError> {
+        let answered = self.store.answer_open_questions(task_id, answer, at)?;
+        if !answered.is_empty() {
+            let repo_id = self.store.load_task(task_id)?.map(|task| task.repo_id);
+            for question in &answered {
+                self.record_event(&questions::question_answered_event(question, repo_id.clone()))?;
+            }
+        }
+        Ok(answered)
+    }
+
     // --- State Transitions ---
 
     pub fn transition_task_state(
@@ -163,6 +353,9 @@ impl OrchdService {
                 })?;
         let transition = transition_task(&mut task, to, at)?;
         self.store.upsert_task(&task)?;
+        if to.is_terminal() {
+            merge_queue::remove_if_queued(&self.store, task_id)?;
+        }
 
         let event = Event {
             id: event_id,
@@ -303,7 +496,12 @@ impl OrchdService {
         event_id: EventId,
         at: DateTime<Utc>,
     ) -> Result<Task, ServiceError> {
-        let task = self.transition_task_state(task_id, TaskState::Ready, event_id.clone(), at)?;
+        let mut task =
+            self.transition_task_state(task_id, TaskState::Ready, event_id.clone(), at)?;
+        if task.restack_conflict {
+            task.restack_conflict = false;
+            self.store.upsert_task(&task)?;
+        }
 
         self.record_event(&Event {
             id: EventId(format!("{}-restack-completed", event_id.0)),
@@ -316,6 +514,26 @@ impl OrchdService {
         Ok(task)
     }
 
+    /// Record whether the task's last restack stopped on a conflict, so the
+    /// TUI stack view can mark it.
+    pub fn set_restack_conflict(
+        &self,
+        task_id: &TaskId,
+        conflict: bool,
+    ) -> Result<Task, ServiceError> {
+        let mut task = self
+            .store
+            .load_task(task_id)?
+            .ok_or_else(|| ServiceError::TaskNotFound {
+                task_id: task_id.0.clone(),
+            })?;
+        if task.restack_conflict != conflict {
+            task.restack_conflict = conflict;
+            self.store.upsert_task(&task)?;
+        }
+        Ok(task)
+    }
+
     // --- Dependency Graph ---
 
     /// Get tasks that need restacking when a parent task is updated.
@@ -406,6 +624,7 @@ impl OrchdService {
                 exit_code: None,
                 estimated_tokens: None,
                 duration_secs: None,
+                confidence: None,
             };
             self.store.insert_run(&run)?;
         }
@@ -540,7 +759,7 @@ mod tests {
     use super::*;
     use crate::scheduler::SchedulerConfig;
     use chrono::Utc;
-    use orch_core::types::{ModelKind, RepoId};
+    use orch_core::types::RepoId;
     use std::fs;
     use std::path::PathBuf;
 
@@ -555,16 +774,7 @@ mod tests {
         let svc = OrchdService::new(
             store,
             JsonlEventLog::new(dir),
-            Scheduler::new(SchedulerConfig {
-                per_repo_limit: 10,
-                per_model_limit: vec![
-                    (ModelKind::Claude, 10),
-                    (ModelKind::Codex, 10),
-                    (ModelKind::Gemini, 10),
-                ]
-                .into_iter()
-                .collect(),
-            }),
+            Scheduler::new(SchedulerConfig::uniform(10, 10)),
         );
         svc.bootstrap().expect("bootstrap");
         svc
@@ -616,6 +826,46 @@ mod tests {
         assert_eq!(updated.state, TaskState::Ready);
     }
 
+    #[test]
+    fn transition_task_state_rejects_illegal_moves_with_both_states() {
+        let svc = mk_service();
+        let merged = mk_task("T-MERGED", TaskState::Merged);
+        svc.create_task(&merged, &mk_created_event(&merged))
+            .expect("create task");
+
+        let err = svc
+            .transition_task_state(
+                &merged.id,
+                TaskState::Stopped,
+                EventId("E-STOP".to_string()),
+                Utc::now(),
+            )
+            .expect_err("merged tasks cannot be stopped");
+        assert!(matches!(
+            err,
+            ServiceError::Transition(TransitionError::Illegal {
+                from: TaskState::Merged,
+                to: TaskState::Stopped,
+            })
+        ));
+        assert_eq!(err.to_string(), "cannot go from MERGED to STOPPED");
+        let unchanged = svc.task(&merged.id).expect("load").expect("task");
+        assert_eq!(unchanged.state, TaskState::Merged);
+
+        let chatting = mk_task("T-CHATTING", TaskState::Chatting);
+        svc.create_task(&chatting, &mk_created_event(&chatting))
+            .expect("create task");
+        let stopped = svc
+            .transition_task_state(
+                &chatting.id,
+                TaskState::Stopped,
+                EventId("E-STOP-2".to_string()),
+                Utc::now(),
+            )
+            .expect("chatting tasks can be stopped");
+        assert_eq!(stopped.state, TaskState::Stopped);
+    }
+
     #[test]
     fn full_submit_flow() {
         let svc = mk_service();
@@ -683,8 +933,66 @@ mod tests {
         svc.create_task(&task, &mk_created_event(&task))
             .expect("create task");
 
-        assert!(svc.delete_task(&task.id).expect("delete"));
+        assert_eq!(
+            svc.delete_task(&task.id, false).expect("delete"),
+            vec![task.id.clone()]
+        );
         assert!(svc.task(&task.id).expect("load").is_none());
+        assert!(svc
+            .delete_task(&task.id, false)
+            .expect("delete missing")
+            .is_empty());
+    }
+
+    #[test]
+    fn delete_task_refuses_parent_with_children() {
+        let svc = mk_service();
+        let parent = mk_task("T-PARENT", TaskState::Chatting);
+        let mut child = mk_task("T-CHILD", TaskState::Chatting);
+        child.parent_task_id = Some(parent.id.clone());
+        for task in [&parent, &child] {
+            svc.create_task(task, &mk_created_event(task))
+                .expect("create task");
+        }
+
+        let err = svc.delete_task(&parent.id, false).expect_err("refuse");
+        match err {
+            ServiceError::HasChildren { task_id, children } => {
+                assert_eq!(task_id, "T-PARENT");
+                assert_eq!(children, vec!["T-CHILD".to_string()]);
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+        assert!(svc.task(&parent.id).expect("load").is_some());
+        assert!(svc.task(&child.id).expect("load").is_some());
+    }
+
+    #[test]
+    fn delete_task_cascade_removes_the_subtree() {
+        let svc = mk_service();
+        let root = mk_task("T-ROOT", TaskState::Chatting);
+        let mut child = mk_task("T-CHILD", TaskState::Chatting);
+        child.parent_task_id = Some(root.id.clone());
+        let mut grandchild = mk_task("T-GRANDCHILD", TaskState::Chatting);
+        grandchild.parent_task_id = Some(child.id.clone());
+        let unrelated = mk_task("T-OTHER", TaskState::Chatting);
+        for task in [&root, &child, &grandchild, &unrelated] {
+            svc.create_task(task, &mk_created_event(task))
+                .expect("create task");
+        }
+
+        let deleted = svc.delete_task(&root.id, true).expect("cascade");
+        assert_eq!(
+            deleted,
+            vec![grandchild.id.clone(), child.id.clone(), root.id.clone()]
+        );
+        let remaining: Vec<TaskId> = svc
+            .list_tasks()
+            .expect("list")
+            .into_iter()
+            .map(|t| t.id)
+            .collect();
+        assert_eq!(remaining, vec![unrelated.id]);
     }
 
     #[test]
@@ -792,4 +1100,75 @@ mod tests {
         let json = serde_json::to_string(&report).expect("serialize");
         assert!(json.contains("\"has_issues\":false"));
     }
+
+    #[test]
+    fn aliases_resolve_and_reject_collisions() {
+        let svc = mk_service();
+        let first = mk_task("chat-1760000000123", TaskState::Chatting);
+        let second = mk_task("chat-1760000999000", TaskState::Chatting);
+        for task in [&first, &second] {
+            svc.create_task(task, &mk_created_event(task))
+                .expect("create task");
+        }
+
+        assert_eq!(
+            svc.set_task_alias(&first.id, "Auth-Fix").expect("alias"),
+            "auth-fix"
+        );
+        assert_eq!(svc.resolve_task_id("auth-fix").expect("resolve"), first.id);
+        assert_eq!(
+            svc.resolve_task_id("chat-17600009").expect("resolve prefix"),
+            second.id
+        );
+
+        let err = svc.set_task_alias(&second.id, "auth-fix").unwrap_err();
+        assert!(matches!(
+            err,
+            ServiceError::TaskId(TaskIdError::AliasCollision { ref task_id, .. })
+                if *task_id == first.id.0
+        ));
+        let err = svc
+            .set_task_alias(&first.id, "chat-1760000999000")
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ServiceError::TaskId(TaskIdError::AliasCollision { .. })
+        ));
+        assert!(matches!(
+            svc.resolve_task_id("chat-176").unwrap_err(),
+            ServiceError::TaskId(TaskIdError::Ambiguous { .. })
+        ));
+    }
+
+    #[test]
+    fn event_listeners_receive_recorded_events() {
+        let mut svc = mk_service();
+        let seen = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        svc.add_event_listener(move |event| {
+            sink.lock().expect("lock").push(event.id.0.clone());
+        });
+
+        let task = mk_task("T1", TaskState::Chatting);
+        svc.create_task(&task, &mk_created_event(&task))
+            .expect("create task");
+
+        assert_eq!(*seen.lock().expect("lock"), vec!["E-CREATE-T1".to_string()]);
+    }
+
+    #[test]
+    fn merged_task_leaves_merge_queue() {
+        let svc = mk_service();
+        let task = mk_task("T1", TaskState::AwaitingMerge);
+        svc.create_task(&task, &mk_created_event(&task))
+            .expect("create task");
+        merge_queue::enqueue(&svc.store, &task.id, None).expect("enqueue");
+
+        svc.mark_merged(&task.id, EventId("E-MERGED".to_string()), Utc::now())
+            .expect("merge");
+
+        let queue = merge_queue::snapshot(&svc.store).expect("queue");
+        assert!(queue.entries.is_empty());
+        assert_eq!(queue.version, 2);
+    }
 }
@@ -12,11 +12,15 @@
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+use std::process::{Command, Stdio};
 
 use crate::agent_dispatch::{AgentDispatcher, AgentRole, RepoContext};
 use crate::context_manager::{ContextManager, RichContext};
 use crate::problem_classifier::{ClassificationResult, ErrorClass, ProblemClassifier, RecoveryAction};
-use orch_core::types::{ModelKind, Task};
+use orch_agents::{default_adapter_for, ChunkSink, EpochRequest};
+use orch_core::types::{ModelKind, RepoId, Task, TaskId};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Recovery State
@@ -510,6 +514,142 @@ pub fn is_recoverable_failure(reason: &str) -> bool {
     classification.class.is_agent_fixable()
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Restack Conflict Resolution
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// `git status --porcelain` codes for unmerged paths.
+const UNMERGED_STATUS_CODES: [&str; 7] = ["DD", "AU", "UD", "UA", "DU", "AA", "UU"];
+
+/// Unmerged paths in `git status --porcelain` output.
+pub fn parse_conflicted_files(porcelain: &str) -> Vec<String> {
+    porcelain
+        .lines()
+        .filter_map(|line| {
+            let code = line.get(..2)?;
+            let path = line.get(3..)?.trim();
+            UNMERGED_STATUS_CODES
+                .contains(&code)
+                .then(|| path.to_string())
+        })
+        .collect()
+}
+
+/// Files a stopped restack left conflicted in `worktree`.
+pub fn conflicted_files(worktree: &Path) -> Vec<String> {
+    Command::new("git")
+        .args(["status", "--porcelain"])
+        .current_dir(worktree)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| parse_conflicted_files(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default()
+}
+
+impl SisyphusRecoveryLoop {
+    /// Start the next agent attempt at a restack conflict. Returns the attempt
+    /// number, or `None` once `max_sisyphus_attempts` have been used.
+    pub fn start_conflict_attempt(&mut self, task_id: &str, files: &[String]) -> Option<u32> {
+        let recovery = self
+            .recoveries
+            .entry(task_id.to_string())
+            .or_insert_with(|| RecoveryState::new(task_id));
+        if recovery.should_escalate() {
+            return None;
+        }
+        recovery.current_error = Some(format!("restack conflict in {}", files.join(", ")));
+        recovery.error_class = Some(ErrorClass::Git);
+        recovery.record_sisyphus_attempt(ErrorClass::Git);
+        Some(recovery.sisyphus_attempts)
+    }
+}
+
+/// Run `model` on a conflict-resolution prompt in `worktree`, killing it once
+/// `timeout_secs` have passed. With `log`, the agent's output is streamed to
+/// it as it is read; otherwise it is discarded.
+pub fn run_conflict_agent(
+    worktree: &Path,
+    task_id: &TaskId,
+    model: ModelKind,
+    prompt: &str,
+    timeout_secs: u64,
+    log: Option<ChunkSink>,
+) -> Result<(), String> {
+    let adapter = default_adapter_for(model).map_err(|e| e.to_string())?;
+    let request = EpochRequest {
+        task_id: task_id.clone(),
+        repo_id: RepoId("default".to_string()),
+        model,
+        repo_path: worktree.to_path_buf(),
+        prompt: prompt.to_string(),
+        timeout_secs,
+        extra_args: vec![],
+        env: vec![],
+    };
+    let cmd = adapter.build_command(&request);
+
+    let mut child = Command::new(&cmd.executable)
+        .args(&cmd.args)
+        .envs(cmd.env.iter().map(|(k, v)| (k.as_str(), v.as_str())))
+        .env_remove("CLAUDECODE")
+        .current_dir(worktree)
+        .stdin(Stdio::null())
+        .stdout(output_stdio(&log))
+        .stderr(output_stdio(&log))
+        .spawn()
+        .map_err(|e| {
+            format!(
+                "failed to spawn {} for conflict resolution: {e}",
+                model.as_str()
+            )
+        })?;
+    if let Some(log) = log {
+        if let Some(stdout) = child.stdout.take() {
+            stream_lines(stdout, log.clone());
+        }
+        if let Some(stderr) = child.stderr.take() {
+            stream_lines(stderr, log);
+        }
+    }
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(timeout_secs);
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => return Ok(()),
+            Ok(Some(status)) => {
+                return Err(format!("conflict resolution agent exited with {status}"));
+            }
+            Ok(None) if std::time::Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!(
+                    "conflict resolution agent timed out after {timeout_secs}s"
+                ));
+            }
+            Ok(None) => std::thread::sleep(std::time::Duration::from_millis(250)),
+            Err(e) => return Err(format!("failed to wait for conflict resolution agent: {e}")),
+        }
+    }
+}
+
+fn output_stdio(log: &Option<ChunkSink>) -> Stdio {
+    if log.is_some() {
+        Stdio::piped()
+    } else {
+        Stdio::null()
+    }
+}
+
+/// Forward each line read from `output` to `log` on a background thread.
+fn stream_lines(output: impl Read + Send + 'static, log: ChunkSink) {
+    std::thread::spawn(move || {
+        for line in BufReader::new(output).lines().map_while(Result::ok) {
+            log.send_line(&line);
+        }
+    });
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Tests
 // ─────────────────────────────────────────────────────────────────────────────
@@ -618,4 +758,34 @@ mod tests {
         assert!(is_recoverable_failure("test failed: assertion error"));
         assert!(!is_recoverable_failure("authentication failed: token expired"));
     }
+
+    #[test]
+    fn parses_unmerged_paths_from_porcelain_status() {
+        let porcelain = "UU src/lib.rs\n M src/main.rs\nAA Cargo.toml\n?? notes.txt\nDU old.rs\n";
+        assert_eq!(
+            parse_conflicted_files(porcelain),
+            vec!["src/lib.rs", "Cargo.toml", "old.rs"]
+        );
+        assert!(parse_conflicted_files(" M src/main.rs\n").is_empty());
+    }
+
+    #[test]
+    fn conflict_attempts_stop_after_max_sisyphus_attempts() {
+        let mut loop_ = SisyphusRecoveryLoop::default();
+        let files = vec!["src/lib.rs".to_string()];
+
+        assert_eq!(loop_.start_conflict_attempt("T1", &files), Some(1));
+        loop_.mark_failure("T1");
+        assert_eq!(loop_.start_conflict_attempt("T1", &files), Some(2));
+        loop_.mark_failure("T1");
+        assert_eq!(loop_.start_conflict_attempt("T1", &files), None);
+
+        let state = loop_.get_state("T1").expect("recovery state");
+        assert_eq!(state.error_class, Some(ErrorClass::Git));
+        assert_eq!(state.history.len(), 2);
+        assert!(state
+            .history
+            .iter()
+            .all(|attempt| attempt.outcome == "failed"));
+    }
 }
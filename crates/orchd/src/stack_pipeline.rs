@@ -2,12 +2,41 @@
 //! stack a task's branch on its parent, verify, and submit.
 //!
 //! Pipeline stages: VerifyBranch → StackOnParent → VerifyStack → Submit
+//!
+//! Each step runs under a per-stage timeout (see [`run_step`]); a step that
+//! overruns or is cancelled fails the pipeline at that stage.
+//!
+//! Stage transitions queue `PipelineStepStarted`/`PipelineStepCompleted`
+//! events; whoever drives the pipeline drains them with
+//! [`PipelineState::take_events`] and records them.
+//!
+//! With [`PipelineState::with_checkpoints`], every transition is saved to
+//! `.othala/pipelines/<task>.json` so a restarted daemon can
+//! [`PipelineState::resume`] from the last completed stage.
 
+use orch_core::config::{PipelineTimeoutsConfig, VerifyLimits};
+use orch_core::events::EventKind;
 use orch_core::types::{SubmitMode, TaskId};
-use std::path::PathBuf;
+use orch_graphite::GraphiteCommandRunner;
+use orch_verify::{kill_process_group_id, run_bounded_with, BoundedOutput};
+use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// How often an in-flight step is checked for cancellation.
+pub const STEP_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Repo-relative directory holding pipeline checkpoints.
+pub const PIPELINE_CHECKPOINT_DIR: &str = ".othala/pipelines";
 
 /// Pipeline stage identifiers.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum PipelineStage {
     /// Run verification on the task's own branch.
     VerifyBranch,
@@ -37,7 +66,7 @@ impl std::fmt::Display for PipelineStage {
 }
 
 /// Tracks the state of a pipeline run for one task.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PipelineState {
     pub task_id: TaskId,
     /// Current stage in the pipeline.
@@ -52,6 +81,43 @@ pub struct PipelineState {
     pub submit_mode: SubmitMode,
     /// Error message if pipeline failed.
     pub error: Option<String>,
+    /// Stage that was running when the pipeline failed.
+    pub failed_stage: Option<PipelineStage>,
+    /// Worktree head when the checkpoint was saved; a checkpoint whose head
+    /// no longer matches is ignored on resume.
+    #[serde(default)]
+    pub head_sha: Option<String>,
+    /// Step events not yet drained by [`PipelineState::take_events`].
+    #[serde(skip)]
+    pending_events: Vec<EventKind>,
+    /// Repo root whose `.othala/pipelines` receives checkpoints, if enabled.
+    #[serde(skip)]
+    checkpoint_root: Option<PathBuf>,
+}
+
+/// Checkpoint file for `task_id` under `repo_root`.
+pub fn checkpoint_path(repo_root: &Path, task_id: &TaskId) -> PathBuf {
+    repo_root
+        .join(PIPELINE_CHECKPOINT_DIR)
+        .join(format!("{}.json", task_id.0))
+}
+
+/// Commit checked out in the worktree at `path`, if it is a git checkout.
+pub fn get_worktree_head_sha(path: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(path)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let sha = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if sha.is_empty() {
+        None
+    } else {
+        Some(sha)
+    }
 }
 
 impl PipelineState {
@@ -70,6 +136,70 @@ impl PipelineState {
             worktree_path,
             submit_mode,
             error: None,
+            failed_stage: None,
+            head_sha: None,
+            pending_events: vec![step_started(PipelineStage::VerifyBranch)],
+            checkpoint_root: None,
+        }
+    }
+
+    /// Reload the checkpoint saved for `task_id`, picking up at the first
+    /// stage that hasn't completed; a failed pipeline retries the stage it
+    /// failed at. `None` when there is no checkpoint, it is unreadable or
+    /// corrupt, the branch has moved since it was saved, or the pipeline had
+    /// already finished.
+    pub fn resume(repo_root: &Path, task_id: &TaskId) -> Option<Self> {
+        let raw = std::fs::read_to_string(checkpoint_path(repo_root, task_id)).ok()?;
+        let mut state: Self = serde_json::from_str(&raw).ok()?;
+        if state.task_id != *task_id
+            || state.head_sha != get_worktree_head_sha(&state.worktree_path)
+        {
+            return None;
+        }
+        if state.stage == PipelineStage::Failed {
+            state.stage = state.failed_stage.take()?;
+            state.error = None;
+        }
+        if state.is_terminal() {
+            return None;
+        }
+        state.pending_events = vec![step_started(state.stage)];
+        state.checkpoint_root = Some(repo_root.to_path_buf());
+        Some(state)
+    }
+
+    /// Save a checkpoint under `repo_root` now and after every transition.
+    pub fn with_checkpoints(mut self, repo_root: &Path) -> Self {
+        self.checkpoint_root = Some(repo_root.to_path_buf());
+        let _ = self.save_checkpoint();
+        self
+    }
+
+    /// Write the checkpoint with the worktree's current head, or drop it once
+    /// the pipeline is done. A no-op without [`PipelineState::with_checkpoints`].
+    pub fn save_checkpoint(&mut self) -> std::io::Result<()> {
+        let Some(root) = &self.checkpoint_root else {
+            return Ok(());
+        };
+        let path = checkpoint_path(root, &self.task_id);
+        if self.stage == PipelineStage::Done {
+            return match std::fs::remove_file(&path) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err),
+                _ => Ok(()),
+            };
+        }
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        self.head_sha = get_worktree_head_sha(&self.worktree_path);
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, json)
+    }
+
+    /// Delete the checkpoint, e.g. when the pipeline is abandoned.
+    pub fn clear_checkpoint(&self) {
+        if let Some(root) = &self.checkpoint_root {
+            let _ = std::fs::remove_file(checkpoint_path(root, &self.task_id));
         }
     }
 
@@ -80,6 +210,10 @@ impl PipelineState {
 
     /// Advance to the next stage after a successful step.
     pub fn advance(&mut self) {
+        if self.is_terminal() {
+            return;
+        }
+        self.pending_events.push(step_completed(self.stage, true));
         self.stage = match self.stage {
             PipelineStage::VerifyBranch => {
                 if self.parent_branch.is_some() && self.submit_mode == SubmitMode::Stack {
@@ -94,15 +228,239 @@ impl PipelineState {
             PipelineStage::Submit => PipelineStage::Done,
             PipelineStage::Done | PipelineStage::Failed => self.stage,
         };
+        if !self.is_terminal() {
+            self.pending_events.push(step_started(self.stage));
+        }
+        let _ = self.save_checkpoint();
     }
 
     /// Mark the pipeline as failed with an error message.
     pub fn fail(&mut self, error: String) {
+        if !self.is_terminal() {
+            self.failed_stage = Some(self.stage);
+            self.pending_events.push(step_completed(self.stage, false));
+        }
         self.error = Some(error);
         self.stage = PipelineStage::Failed;
+        let _ = self.save_checkpoint();
+    }
+
+    /// Fail the pipeline because the current step ran past `timeout`.
+    pub fn fail_timed_out(&mut self, timeout: Duration) {
+        let error = format!("step `{}` timed out after {timeout:?}", self.stage);
+        self.fail(error);
+    }
+
+    /// Abort the pipeline at its current step.
+    pub fn cancel(&mut self, reason: &str) {
+        let error = format!("cancelled during step `{}`: {reason}", self.stage);
+        self.fail(error);
+    }
+
+    /// Drain the step events queued since the last call, oldest first.
+    pub fn take_events(&mut self) -> Vec<EventKind> {
+        std::mem::take(&mut self.pending_events)
+    }
+}
+
+fn step_started(stage: PipelineStage) -> EventKind {
+    EventKind::PipelineStepStarted {
+        step: stage.to_string(),
+    }
+}
+
+fn step_completed(stage: PipelineStage, success: bool) -> EventKind {
+    EventKind::PipelineStepCompleted {
+        step: stage.to_string(),
+        success,
+    }
+}
+
+/// Per-stage time limits for pipeline steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StepTimeouts {
+    /// Limit for `VerifyBranch` and `VerifyStack`.
+    pub verify: Duration,
+    /// Limit for `StackOnParent`.
+    pub stack: Duration,
+    /// Limit for `Submit`.
+    pub submit: Duration,
+}
+
+impl Default for StepTimeouts {
+    fn default() -> Self {
+        Self::from(&PipelineTimeoutsConfig::default())
+    }
+}
+
+impl From<&PipelineTimeoutsConfig> for StepTimeouts {
+    fn from(config: &PipelineTimeoutsConfig) -> Self {
+        Self {
+            verify: Duration::from_secs(config.verify_secs),
+            stack: Duration::from_secs(config.stack_secs),
+            submit: Duration::from_secs(config.submit_secs),
+        }
+    }
+}
+
+impl StepTimeouts {
+    /// Limit for the step run at `stage`; `None` for terminal stages.
+    pub fn for_stage(&self, stage: PipelineStage) -> Option<Duration> {
+        match stage {
+            PipelineStage::VerifyBranch | PipelineStage::VerifyStack => Some(self.verify),
+            PipelineStage::StackOnParent => Some(self.stack),
+            PipelineStage::Submit => Some(self.submit),
+            PipelineStage::Done | PipelineStage::Failed => None,
+        }
+    }
+}
+
+/// Result of running one pipeline step under [`run_step`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepOutcome<T> {
+    /// The step returned before its deadline.
+    Finished(T),
+    /// The step was still running when the timeout elapsed.
+    TimedOut(Duration),
+    /// `is_cancelled` returned true while the step was running.
+    Cancelled,
+    /// The step's thread panicked before producing a result.
+    Panicked,
+}
+
+/// Child processes started by a running pipeline step.
+///
+/// Each child gets its own process group, so when the step times out or is
+/// cancelled [`run_step`] can kill it together with everything it spawned
+/// (cargo, git hooks, `gt`'s node workers). Once stopped, no new child can be
+/// started for the step.
+#[derive(Debug, Default)]
+pub struct StepProcesses {
+    inner: Mutex<StepProcessGroups>,
+}
+
+#[derive(Debug, Default)]
+struct StepProcessGroups {
+    stopped: bool,
+    running: Vec<u32>,
+}
+
+impl StepProcesses {
+    /// Spawn `command` as the leader of a new process group owned by the step.
+    pub fn spawn(&self, command: &mut Command) -> io::Result<Child> {
+        let mut groups = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        if groups.stopped {
+            return Err(io::Error::new(
+                io::ErrorKind::Interrupted,
+                "pipeline step was stopped",
+            ));
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            command.process_group(0);
+        }
+        let child = command.spawn()?;
+        groups.running.push(child.id());
+        Ok(child)
+    }
+
+    /// Run `command` to completion with stdin closed and output captured.
+    pub fn output(&self, command: &mut Command) -> io::Result<Output> {
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        let child = self.spawn(command)?;
+        let pid = child.id();
+        let output = child.wait_with_output();
+        self.release(pid);
+        output
+    }
+
+    /// [`orch_verify::run_bounded`] with the command owned by the step.
+    pub fn run_bounded(
+        &self,
+        command: &str,
+        cwd: &Path,
+        limits: VerifyLimits,
+    ) -> io::Result<BoundedOutput> {
+        let mut pid = None;
+        let output = run_bounded_with(command, cwd, limits, |cmd| {
+            let child = self.spawn(cmd)?;
+            pid = Some(child.id());
+            Ok(child)
+        });
+        if let Some(pid) = pid {
+            self.release(pid);
+        }
+        output
+    }
+
+    /// Kill every process group the step still has running and refuse to
+    /// start new ones.
+    pub fn kill_all(&self) {
+        let mut groups = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        groups.stopped = true;
+        for pgid in groups.running.drain(..) {
+            kill_process_group_id(pgid);
+        }
+    }
+
+    /// Forget a reaped child so its group id is never signalled after reuse.
+    fn release(&self, pid: u32) {
+        let mut groups = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        groups.running.retain(|running| *running != pid);
+    }
+}
+
+impl GraphiteCommandRunner for StepProcesses {
+    fn run(&self, binary: &Path, cwd: &Path, args: &[OsString]) -> io::Result<Output> {
+        self.output(Command::new(binary).current_dir(cwd).args(args))
     }
 }
 
+/// Run `step` on a worker thread, waiting at most `timeout` for it to return.
+///
+/// `is_cancelled` is polled every [`STEP_POLL_INTERVAL`] on the calling
+/// thread. The step must start its child processes through the
+/// [`StepProcesses`] it is given: on timeout or cancel they are killed, so
+/// an abandoned step cannot go on to restack or push.
+pub fn run_step<T, F, C>(timeout: Duration, mut is_cancelled: C, step: F) -> StepOutcome<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&Arc<StepProcesses>) -> T + Send + 'static,
+    C: FnMut() -> bool,
+{
+    let processes = Arc::new(StepProcesses::default());
+    let (tx, rx) = mpsc::channel();
+    {
+        let processes = Arc::clone(&processes);
+        std::thread::spawn(move || {
+            let _ = tx.send(step(&processes));
+        });
+    }
+
+    let deadline = Instant::now() + timeout;
+    let outcome = loop {
+        let now = Instant::now();
+        if now >= deadline {
+            break StepOutcome::TimedOut(timeout);
+        }
+        match rx.recv_timeout(STEP_POLL_INTERVAL.min(deadline - now)) {
+            Ok(value) => return StepOutcome::Finished(value),
+            Err(RecvTimeoutError::Timeout) => {
+                if is_cancelled() {
+                    break StepOutcome::Cancelled;
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => break StepOutcome::Panicked,
+        }
+    };
+    processes.kill_all();
+    outcome
+}
+
 /// Action returned by the pipeline, telling the daemon what to do next.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PipelineAction {
@@ -164,7 +522,7 @@ pub fn next_action(state: &PipelineState) -> PipelineAction {
         },
         PipelineStage::Failed => PipelineAction::Failed {
             task_id: state.task_id.clone(),
-            stage: state.stage,
+            stage: state.failed_stage.unwrap_or(state.stage),
             error: state.error.clone().unwrap_or_default(),
         },
     }
@@ -253,6 +611,50 @@ mod tests {
         assert!(matches!(next_action(&p), PipelineAction::Failed { .. }));
     }
 
+    #[test]
+    fn stage_transitions_emit_ordered_step_events() {
+        let started = |step: &str| EventKind::PipelineStepStarted {
+            step: step.to_string(),
+        };
+        let completed = |step: &str, success| EventKind::PipelineStepCompleted {
+            step: step.to_string(),
+            success,
+        };
+
+        let mut p = mk_pipeline(Some("task/T-0"), SubmitMode::Stack);
+        while !p.is_terminal() {
+            p.advance();
+        }
+        assert_eq!(
+            p.take_events(),
+            vec![
+                started("verify_branch"),
+                completed("verify_branch", true),
+                started("stack_on_parent"),
+                completed("stack_on_parent", true),
+                started("verify_stack"),
+                completed("verify_stack", true),
+                started("submit"),
+                completed("submit", true),
+            ]
+        );
+        assert!(p.take_events().is_empty());
+
+        let mut p = mk_pipeline(None, SubmitMode::Single);
+        p.advance();
+        p.fail("submit rejected".to_string());
+        p.cancel("too late");
+        assert_eq!(
+            p.take_events(),
+            vec![
+                started("verify_branch"),
+                completed("verify_branch", true),
+                started("submit"),
+                completed("submit", false),
+            ]
+        );
+    }
+
     #[test]
     fn pipeline_stage_display() {
         assert_eq!(PipelineStage::VerifyBranch.to_string(), "verify_branch");
@@ -285,4 +687,244 @@ mod tests {
         p.advance();
         assert_eq!(p.stage, PipelineStage::Failed);
     }
+
+    #[test]
+    fn slow_step_times_out_and_fails_pipeline_at_that_step() {
+        let mut p = mk_pipeline(Some("task/T-0"), SubmitMode::Stack);
+        p.advance();
+        assert_eq!(p.stage, PipelineStage::StackOnParent);
+
+        let timeout = Duration::from_millis(50);
+        let outcome = run_step(
+            timeout,
+            || false,
+            |_| {
+                std::thread::sleep(Duration::from_secs(2));
+            },
+        );
+        assert_eq!(outcome, StepOutcome::TimedOut(timeout));
+
+        p.fail_timed_out(timeout);
+        assert!(p.is_terminal());
+        assert_eq!(
+            p.error.as_deref(),
+            Some("step `stack_on_parent` timed out after 50ms")
+        );
+        assert!(matches!(
+            next_action(&p),
+            PipelineAction::Failed {
+                stage: PipelineStage::StackOnParent,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn fast_step_finishes_within_timeout() {
+        let outcome = run_step(Duration::from_secs(5), || false, |_| 42);
+        assert_eq!(outcome, StepOutcome::Finished(42));
+    }
+
+    #[test]
+    fn cancelled_step_stops_waiting() {
+        let started = Instant::now();
+        let outcome = run_step(
+            Duration::from_secs(30),
+            || true,
+            |_| {
+                std::thread::sleep(Duration::from_secs(2));
+            },
+        );
+        assert_eq!(outcome, StepOutcome::Cancelled);
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        let mut p = mk_pipeline(None, SubmitMode::Single);
+        p.advance();
+        p.cancel("user requested");
+        assert_eq!(p.failed_stage, Some(PipelineStage::Submit));
+        assert_eq!(
+            p.error.as_deref(),
+            Some("cancelled during step `submit`: user requested")
+        );
+    }
+
+    #[test]
+    fn timed_out_step_kills_its_children_and_starts_no_more() {
+        let (pid_tx, pid_rx) = mpsc::channel();
+        let outcome = run_step(
+            Duration::from_millis(300),
+            || false,
+            move |processes| {
+                let mut child = processes
+                    .spawn(Command::new("sh").args(["-c", "sleep 30 & wait"]))
+                    .expect("spawn child");
+                let _ = pid_tx.send(child.id());
+                let _ = child.wait();
+            },
+        );
+        assert_eq!(outcome, StepOutcome::TimedOut(Duration::from_millis(300)));
+
+        let pid = pid_rx.recv().expect("child pid");
+        let started = Instant::now();
+        while Command::new("kill")
+            .args(["-0", "--", &format!("-{pid}")])
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+        {
+            assert!(
+                started.elapsed() < Duration::from_secs(5),
+                "step process group still alive"
+            );
+            std::thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    #[test]
+    fn stopped_step_refuses_to_start_follow_up_commands() {
+        let processes = StepProcesses::default();
+        let output = processes
+            .output(Command::new("sh").args(["-c", "echo ok"]))
+            .expect("run before stop");
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "ok");
+
+        processes.kill_all();
+        let err = processes
+            .output(&mut Command::new("true"))
+            .expect_err("no new child after stop");
+        assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+    }
+
+    #[test]
+    fn step_timeouts_cover_every_running_stage() {
+        let timeouts = StepTimeouts::default();
+        assert_eq!(
+            timeouts.for_stage(PipelineStage::VerifyStack),
+            Some(timeouts.verify)
+        );
+        assert_eq!(
+            timeouts.for_stage(PipelineStage::Submit),
+            Some(timeouts.submit)
+        );
+        assert_eq!(timeouts.for_stage(PipelineStage::Done), None);
+    }
+
+    fn checkpoint_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "othala-pipeline-{name}-{}-{}",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        std::fs::create_dir_all(&root).expect("create root");
+        root
+    }
+
+    #[test]
+    fn resume_continues_after_the_last_completed_stage() {
+        let root = checkpoint_root("resume");
+        let mut p = mk_pipeline(Some("task/T-0"), SubmitMode::Stack).with_checkpoints(&root);
+        assert!(checkpoint_path(&root, &p.task_id).exists());
+        p.advance(); // VerifyBranch done
+        p.advance(); // StackOnParent done
+        drop(p);
+
+        let mut resumed = PipelineState::resume(&root, &TaskId::new("T-1")).expect("checkpoint");
+        assert_eq!(resumed.stage, PipelineStage::VerifyStack);
+        assert_eq!(resumed.parent_branch.as_deref(), Some("task/T-0"));
+        assert_eq!(resumed.submit_mode, SubmitMode::Stack);
+        assert!(matches!(
+            next_action(&resumed),
+            PipelineAction::RunVerify { .. }
+        ));
+        assert_eq!(
+            resumed.take_events(),
+            vec![step_started(PipelineStage::VerifyStack)]
+        );
+
+        resumed.advance();
+        assert_eq!(
+            PipelineState::resume(&root, &TaskId::new("T-1")).map(|p| p.stage),
+            Some(PipelineStage::Submit)
+        );
+        resumed.advance();
+        assert_eq!(resumed.stage, PipelineStage::Done);
+        assert!(!checkpoint_path(&root, &resumed.task_id).exists());
+        assert!(PipelineState::resume(&root, &TaskId::new("T-1")).is_none());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn failed_pipeline_resumes_at_the_failed_stage() {
+        let root = checkpoint_root("failed");
+        let mut p = mk_pipeline(None, SubmitMode::Single).with_checkpoints(&root);
+        p.advance();
+        p.fail("gt submit failed".to_string());
+
+        let resumed = PipelineState::resume(&root, &TaskId::new("T-1")).expect("checkpoint");
+        assert_eq!(resumed.stage, PipelineStage::Submit);
+        assert_eq!(resumed.error, None);
+        assert_eq!(resumed.failed_stage, None);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn checkpoint_is_ignored_once_the_branch_moves() {
+        let root = checkpoint_root("moved");
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+                .args(args)
+                .current_dir(&root)
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .expect("run git");
+            assert!(status.success(), "git {args:?}");
+        };
+        git(&["init", "-q"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "one"]);
+
+        let task_id = TaskId::new("T-1");
+        let mut p = PipelineState::new(
+            task_id.clone(),
+            "task/T-1".to_string(),
+            root.clone(),
+            SubmitMode::Single,
+            None,
+        )
+        .with_checkpoints(&root);
+        p.advance();
+        assert_eq!(p.head_sha, get_worktree_head_sha(&root));
+        assert!(p.head_sha.is_some());
+        assert!(PipelineState::resume(&root, &task_id).is_some());
+
+        git(&["commit", "-q", "--allow-empty", "-m", "two"]);
+        assert!(PipelineState::resume(&root, &task_id).is_none());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn corrupt_or_missing_checkpoint_is_ignored() {
+        let root = checkpoint_root("corrupt");
+        let task_id = TaskId::new("T-1");
+        assert!(PipelineState::resume(&root, &task_id).is_none());
+
+        let path = checkpoint_path(&root, &task_id);
+        std::fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+        std::fs::write(&path, "{\"task_id\": \"T-1\", \"stage\": ").expect("write");
+        assert!(PipelineState::resume(&root, &task_id).is_none());
+
+        let other = mk_pipeline(None, SubmitMode::Single);
+        std::fs::write(
+            checkpoint_path(&root, &TaskId::new("T-2")),
+            serde_json::to_string(&other).expect("serialize"),
+        )
+        .expect("write");
+        assert!(PipelineState::resume(&root, &TaskId::new("T-2")).is_none());
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
 }
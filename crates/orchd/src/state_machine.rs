@@ -4,10 +4,11 @@ use chrono::{DateTime, Utc};
 use orch_core::state::TaskState;
 use orch_core::types::Task;
 
-#[derive(Debug, thiserror::Error)]
-pub enum StateMachineError {
-    #[error("invalid task state transition: {from:?} -> {to:?}")]
-    InvalidTransition { from: TaskState, to: TaskState },
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum TransitionError {
+    /// `is_transition_allowed` rejects `from -> to`.
+    #[error("cannot go from {from} to {to}")]
+    Illegal { from: TaskState, to: TaskState },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -22,10 +23,10 @@ pub fn transition_task(
     task: &mut Task,
     to: TaskState,
     at: DateTime<Utc>,
-) -> Result<StateTransition, StateMachineError> {
+) -> Result<StateTransition, TransitionError> {
     let from = task.state;
     if !is_transition_allowed(from, to) {
-        return Err(StateMachineError::InvalidTransition { from, to });
+        return Err(TransitionError::Illegal { from, to });
     }
 
     task.state = to;
@@ -69,6 +70,8 @@ pub fn is_transition_allowed(from: TaskState, to: TaskState) -> bool {
         // Any state can go back to Chatting (retry/fix)
         (_, Chatting) => true,
         (Chatting, Stopped) | (Ready, Stopped) => true,
+        // In-flight pipeline steps can be cancelled
+        (Restacking, Stopped) | (Submitting, Stopped) => true,
         _ => false,
     }
 }
@@ -172,6 +175,13 @@ mod tests {
         assert!(is_transition_allowed(TaskState::Ready, TaskState::Stopped));
     }
 
+    #[test]
+    fn allows_cancelling_in_flight_pipeline_steps() {
+        assert!(is_transition_allowed(TaskState::Submitting, TaskState::Stopped));
+        assert!(is_transition_allowed(TaskState::Restacking, TaskState::Stopped));
+        assert!(!is_transition_allowed(TaskState::AwaitingMerge, TaskState::Stopped));
+    }
+
     #[test]
     fn transition_updates_task_state() {
         let mut task = mk_task(TaskState::Chatting);
@@ -190,13 +200,14 @@ mod tests {
         let at = Utc::now();
         let err = transition_task(&mut task, TaskState::Merged, at).expect_err("should fail");
 
-        assert!(matches!(
+        assert_eq!(
             err,
-            StateMachineError::InvalidTransition {
+            TransitionError::Illegal {
                 from: TaskState::Chatting,
                 to: TaskState::Merged
             }
-        ));
+        );
+        assert_eq!(err.to_string(), "cannot go from CHATTING to MERGED");
         assert_eq!(task.state, TaskState::Chatting);
     }
 
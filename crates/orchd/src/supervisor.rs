@@ -2,10 +2,13 @@
 
 use chrono::{DateTime, Utc};
 use orch_agents::{
-    default_adapter_for, detect_common_signal, AgentAdapter, AgentSignalKind, EpochRequest,
+    default_adapter_for, detect_signal_with_rules, parse_confidence_marker, AgentAdapter,
+    AgentError, AgentSignalKind, EpochRequest, SignalRuleSet,
 };
 use orch_core::types::{ModelKind, RepoId, TaskId};
+use std::collections::hash_map::RandomState;
 use std::collections::HashMap;
+use std::hash::BuildHasher;
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
@@ -17,6 +20,12 @@ use crate::context_graph::{load_context_graph, render_context_with_sources, Cont
 
 const DEFAULT_AGENT_TIMEOUT_SECS: u64 = 1_800;
 
+/// Output drained per session per poll, in bytes, unless overridden.
+pub const DEFAULT_OUTPUT_BUFFER_BYTES: usize = 4 * 1024 * 1024;
+
+/// Lines an agent may have queued before its pipe readers block.
+const OUTPUT_CHANNEL_LINES: usize = 8_192;
+
 /// A running agent session.
 pub struct AgentSession {
     pub child: Child,
@@ -32,6 +41,19 @@ pub struct AgentSession {
     /// When the agent signaled completion (patch_ready or needs_human).
     /// Used to enforce a grace period before killing the process.
     pub signal_at: Option<Instant>,
+    /// Latest `CONFIDENCE:` marker seen in the output, in whole percent.
+    pub confidence: Option<u8>,
+    /// When a message was last written to the agent's stdin.
+    pub last_input_at: Instant,
+    /// When the agent last produced output.
+    pub last_output_at: Instant,
+}
+
+impl AgentSession {
+    /// Time since the last input or output.
+    pub fn idle_for(&self) -> Duration {
+        self.last_input_at.max(self.last_output_at).elapsed()
+    }
 }
 
 pub type AgentProcess = AgentSession;
@@ -46,6 +68,8 @@ pub struct AgentOutcome {
     pub needs_human: bool,
     pub success: bool,
     pub duration_secs: u64,
+    /// Self-reported confidence from the session's last marker, if any.
+    pub confidence: Option<u8>,
 }
 
 /// A batch of output lines from one agent session.
@@ -56,16 +80,30 @@ pub struct OutputChunk {
     pub lines: Vec<String>,
 }
 
+/// An interactive session stopped for idleness. Its conversation lives on
+/// under `session_id` and resumes on the next `send_input`.
+#[derive(Debug, Clone)]
+pub struct SuspendedSession {
+    pub task_id: TaskId,
+    pub model: ModelKind,
+    pub session_id: String,
+    pub idle_secs: u64,
+}
+
 /// Result of a single poll cycle.
 pub struct PollResult {
     pub output: Vec<OutputChunk>,
     pub completed: Vec<AgentOutcome>,
+    /// Sessions suspended this cycle; they are not in `completed`.
+    pub suspended: Vec<SuspendedSession>,
 }
 
 /// Spawn background threads that pipe stdout and stderr lines into `tx`.
 ///
-/// Consumes `tx` (the last clone goes to the stderr thread).
-fn pipe_child_output(child: &mut Child, tx: mpsc::Sender<String>) {
+/// Consumes `tx` (the last clone goes to the stderr thread). The channel is
+/// bounded, so an agent that outpaces `poll` is held back by its pipe instead
+/// of growing the queue.
+fn pipe_child_output(child: &mut Child, tx: mpsc::SyncSender<String>) {
     if let Some(stdout) = child.stdout.take() {
         let tx_out = tx.clone();
         thread::spawn(move || {
@@ -92,24 +130,119 @@ fn rotate_task_log_if_needed(task_id: &TaskId) {
     let _ = crate::agent_log::rotate_log_if_needed(&log_path);
 }
 
+/// Picks the adapter that builds the command for a model.
+pub type AdapterFactory = fn(ModelKind) -> Result<Box<dyn AgentAdapter>, AgentError>;
+
+/// What a session was launched with, kept after it ends so it can be restarted.
+#[derive(Debug, Clone)]
+struct AgentLaunch {
+    instance_id: String,
+    repo_id: RepoId,
+    repo_path: PathBuf,
+    /// The caller's prompt, before `build_prompt` wraps it.
+    prompt: String,
+    model: ModelKind,
+    timeout: Duration,
+    interactive: bool,
+    /// Conversation id handed to CLIs that can resume sessions.
+    session_id: String,
+}
+
+/// A random UUID-shaped id, as agent CLIs expect for session ids.
+fn new_session_id() -> String {
+    let keys = RandomState::new();
+    let high = keys.hash_one(0u8);
+    let low = keys.hash_one(1u8);
+    format!(
+        "{:08x}-{:04x}-4{:03x}-{:04x}-{:012x}",
+        high >> 32,
+        (high >> 16) & 0xffff,
+        high & 0x0fff,
+        0x8000 | ((low >> 48) & 0x3fff),
+        low & 0xffff_ffff_ffff
+    )
+}
+
+/// Whether `model`'s CLI can resume `session_id`, so suspending the session
+/// for idleness loses none of its state.
+fn can_resume(adapter_for: AdapterFactory, model: ModelKind, session_id: &str) -> bool {
+    adapter_for(model).is_ok_and(|adapter| !adapter.session_args(session_id, true).is_empty())
+}
+
 /// Manages running agent sessions.
 pub struct AgentSupervisor {
     sessions: HashMap<TaskId, AgentSession>,
+    launches: HashMap<TaskId, AgentLaunch>,
+    suspended: HashMap<TaskId, SuspendedSession>,
     default_model: ModelKind,
+    adapter_for: AdapterFactory,
+    output_buffer_bytes: usize,
+    idle_suspend_after: Option<Duration>,
+    signal_rules: SignalRuleSet,
 }
 
 impl AgentSupervisor {
     pub fn new(default_model: ModelKind) -> Self {
         Self {
             sessions: HashMap::new(),
+            launches: HashMap::new(),
+            suspended: HashMap::new(),
             default_model,
+            adapter_for: default_adapter_for,
+            output_buffer_bytes: DEFAULT_OUTPUT_BUFFER_BYTES,
+            idle_suspend_after: None,
+            signal_rules: SignalRuleSet::default(),
         }
     }
 
+    /// Build agent commands with `factory` instead of the stock adapters.
+    pub fn with_adapter_factory(mut self, factory: AdapterFactory) -> Self {
+        self.adapter_for = factory;
+        self
+    }
+
+    /// Cap the output drained from one session per poll at roughly `bytes`.
+    pub fn with_output_buffer_bytes(mut self, bytes: usize) -> Self {
+        self.output_buffer_bytes = bytes.max(1);
+        self
+    }
+
+    /// Suspend interactive sessions that see no input or output for `idle`.
+    /// A zero duration leaves idle sessions running.
+    pub fn with_idle_suspend(mut self, idle: Duration) -> Self {
+        self.idle_suspend_after = (!idle.is_zero()).then_some(idle);
+        self
+    }
+
+    /// Detect agent signals with `rules` instead of the built-in markers.
+    pub fn with_signal_rules(mut self, rules: SignalRuleSet) -> Self {
+        self.signal_rules = rules;
+        self
+    }
+
+    /// Pane instance id for the task's agent, stable across restarts.
+    pub fn instance_id(&self, task_id: &TaskId) -> Option<&str> {
+        self.launches
+            .get(task_id)
+            .map(|launch| launch.instance_id.as_str())
+    }
+
     pub fn has_session(&self, task_id: &TaskId) -> bool {
         self.sessions.contains_key(task_id)
     }
 
+    /// Whether the task's interactive session is suspended for idleness.
+    pub fn is_suspended(&self, task_id: &TaskId) -> bool {
+        self.suspended.contains_key(task_id)
+    }
+
+    /// Conversation id of the task's latest launch.
+    pub fn session_id(&self, task_id: &TaskId) -> Option<&str> {
+        self.launches
+            .get(task_id)
+            .map(|launch| launch.session_id.as_str())
+    }
+
     #[cfg(test)]
     pub(crate) fn insert_session_for_test(&mut self, session: AgentSession) {
         self.sessions.insert(session.task_id.clone(), session);
@@ -120,54 +253,13 @@ impl AgentSupervisor {
         &mut self,
         task_id: &TaskId,
         repo_id: &RepoId,
-        repo_path: &PathBuf,
+        repo_path: &Path,
         prompt: &str,
         model: Option<ModelKind>,
         timeout: Duration,
     ) -> anyhow::Result<()> {
-        let model = model.unwrap_or(self.default_model);
-        let adapter: Box<dyn AgentAdapter> = default_adapter_for(model)?;
-
-        let request = EpochRequest {
-            task_id: task_id.clone(),
-            repo_id: repo_id.clone(),
-            model,
-            repo_path: repo_path.clone(),
-            prompt: build_prompt(task_id, prompt, repo_path),
-            timeout_secs: timeout.as_secs(),
-            extra_args: vec![],
-            env: vec![],
-        };
-
-        let cmd = adapter.build_command(&request);
-
-        let mut child = Command::new(&cmd.executable)
-            .args(&cmd.args)
-            .envs(cmd.env.iter().map(|(k, v)| (k.as_str(), v.as_str())))
-            .env_remove("CLAUDECODE")
-            .current_dir(repo_path)
-            .stdin(Stdio::null())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
-
-        let (tx, rx) = mpsc::channel();
-        pipe_child_output(&mut child, tx);
-
-        let session = AgentSession {
-            child,
-            output_rx: rx,
-            input_tx: None,
-            task_id: task_id.clone(),
-            model,
-            started_at: Utc::now(),
-            timeout,
-            patch_ready: false,
-            needs_human: false,
-            signal_at: None,
-        };
-
-        self.sessions.insert(task_id.clone(), session);
+        let launch = self.new_launch(task_id, repo_id, repo_path, prompt, model, timeout, false);
+        self.launch(task_id, launch, None)?;
         Ok(())
     }
 
@@ -180,101 +272,236 @@ impl AgentSupervisor {
         &mut self,
         task_id: &TaskId,
         repo_id: &RepoId,
-        repo_path: &PathBuf,
+        repo_path: &Path,
         initial_prompt: &str,
         model: Option<ModelKind>,
     ) -> anyhow::Result<()> {
-        let model = model.unwrap_or(self.default_model);
-        let adapter: Box<dyn AgentAdapter> = default_adapter_for(model)?;
+        let timeout = Duration::from_secs(DEFAULT_AGENT_TIMEOUT_SECS);
+        let launch = self.new_launch(
+            task_id,
+            repo_id,
+            repo_path,
+            initial_prompt,
+            model,
+            timeout,
+            true,
+        );
+        self.launch(task_id, launch, None)?;
+        Ok(())
+    }
 
-        let request = EpochRequest {
-            task_id: task_id.clone(),
+    /// Stop the task's agent, wait for it to exit, and start it again with
+    /// the same model, worktree and prompt under the same instance id.
+    ///
+    /// Without a running session this just spawns from the last launch. A
+    /// session that ignores SIGTERM (e.g. one already being stopped) is
+    /// killed and reaped before the new process starts.
+    pub fn restart(&mut self, task_id: &TaskId) -> Result<(), AgentError> {
+        let mut launch =
+            self.launches
+                .get(task_id)
+                .cloned()
+                .ok_or_else(|| AgentError::InvalidRequest {
+                    message: format!("no previous agent launch for task {}", task_id.0),
+                })?;
+        if let Some(mut session) = self.sessions.remove(task_id) {
+            shutdown_process(&mut session);
+        }
+        self.suspended.remove(task_id);
+        launch.session_id = new_session_id();
+        self.launch(task_id, launch, None)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn new_launch(
+        &self,
+        task_id: &TaskId,
+        repo_id: &RepoId,
+        repo_path: &Path,
+        prompt: &str,
+        model: Option<ModelKind>,
+        timeout: Duration,
+        interactive: bool,
+    ) -> AgentLaunch {
+        let instance_id = self
+            .instance_id(task_id)
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("agent-{}", task_id.0));
+        AgentLaunch {
+            instance_id,
             repo_id: repo_id.clone(),
-            model,
-            repo_path: repo_path.clone(),
-            prompt: build_prompt(task_id, initial_prompt, repo_path),
-            timeout_secs: DEFAULT_AGENT_TIMEOUT_SECS,
+            repo_path: repo_path.to_path_buf(),
+            prompt: prompt.to_string(),
+            model: model.unwrap_or(self.default_model),
+            timeout,
+            interactive,
+            session_id: new_session_id(),
+        }
+    }
+
+    /// Start a process for `launch`. With `resume_with`, the launch's
+    /// conversation is resumed and the message sent as the next turn.
+    fn launch(
+        &mut self,
+        task_id: &TaskId,
+        launch: AgentLaunch,
+        resume_with: Option<&str>,
+    ) -> Result<(), AgentError> {
+        let adapter = (self.adapter_for)(launch.model)?;
+
+        let mut request = EpochRequest {
+            task_id: task_id.clone(),
+            repo_id: launch.repo_id.clone(),
+            model: launch.model,
+            repo_path: launch.repo_path.clone(),
+            prompt: build_prompt(task_id, &launch.prompt, &launch.repo_path),
+            timeout_secs: launch.timeout.as_secs(),
             extra_args: vec![],
             env: vec![],
         };
 
-        let cmd = adapter.build_interactive_command(&request);
+        let session_args = if launch.interactive {
+            adapter.session_args(&launch.session_id, resume_with.is_some())
+        } else {
+            Vec::new()
+        };
+        let resumes_conversation = resume_with.is_some() && !session_args.is_empty();
+        request.extra_args.extend(session_args);
+
+        let cmd = if launch.interactive {
+            adapter.build_interactive_command(&request)
+        } else {
+            adapter.build_command(&request)
+        };
 
         let mut child = Command::new(&cmd.executable)
             .args(&cmd.args)
             .envs(cmd.env.iter().map(|(k, v)| (k.as_str(), v.as_str())))
             .env_remove("CLAUDECODE")
-            .current_dir(repo_path)
-            .stdin(Stdio::piped())
+            .current_dir(&launch.repo_path)
+            .stdin(if launch.interactive {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .spawn()?;
+            .spawn()
+            .map_err(|e| AgentError::Spawn {
+                message: format!("{}: {e}", cmd.executable),
+            })?;
 
-        let (out_tx, out_rx) = mpsc::channel();
+        let (out_tx, out_rx) = mpsc::sync_channel(OUTPUT_CHANNEL_LINES);
         pipe_child_output(&mut child, out_tx);
 
-        // Create a channel + background thread for stdin writes.
-        let (in_tx, in_rx) = mpsc::channel::<String>();
-        if let Some(stdin) = child.stdin.take() {
-            use std::io::Write;
-            thread::spawn(move || {
-                let mut stdin = stdin;
-                while let Ok(msg) = in_rx.recv() {
-                    if writeln!(stdin, "{msg}").is_err() {
-                        break;
-                    }
-                    if stdin.flush().is_err() {
-                        break;
+        let input_tx = if launch.interactive {
+            // Create a channel + background thread for stdin writes.
+            let (in_tx, in_rx) = mpsc::channel::<String>();
+            if let Some(stdin) = child.stdin.take() {
+                use std::io::Write;
+                thread::spawn(move || {
+                    let mut stdin = stdin;
+                    while let Ok(msg) = in_rx.recv() {
+                        if writeln!(stdin, "{msg}").is_err() {
+                            break;
+                        }
+                        if stdin.flush().is_err() {
+                            break;
+                        }
                     }
-                }
-            });
-        }
-
-        // Send the initial prompt as the first message.
-        let _ = in_tx.send(request.prompt.clone());
+                });
+            }
+            // Send the initial prompt as the first message, unless the CLI
+            // already has it in the resumed conversation.
+            if !resumes_conversation {
+                let _ = in_tx.send(request.prompt.clone());
+            }
+            if let Some(message) = resume_with {
+                let _ = in_tx.send(message.to_string());
+            }
+            Some(in_tx)
+        } else {
+            None
+        };
 
         let session = AgentSession {
             child,
             output_rx: out_rx,
-            input_tx: Some(in_tx),
+            input_tx,
             task_id: task_id.clone(),
-            model,
+            model: launch.model,
             started_at: Utc::now(),
-            timeout: Duration::from_secs(DEFAULT_AGENT_TIMEOUT_SECS),
+            timeout: launch.timeout,
             patch_ready: false,
             needs_human: false,
             signal_at: None,
+            confidence: None,
+            last_input_at: Instant::now(),
+            last_output_at: Instant::now(),
         };
 
         self.sessions.insert(task_id.clone(), session);
+        self.launches.insert(task_id.clone(), launch);
         Ok(())
     }
 
     /// Send a message to the stdin of a running interactive agent session.
-    pub fn send_input(&self, task_id: &TaskId, message: &str) -> anyhow::Result<()> {
+    ///
+    /// A session suspended for idleness is resumed first, with `message` as
+    /// its next turn.
+    pub fn send_input(&mut self, task_id: &TaskId, message: &str) -> anyhow::Result<()> {
+        if self.suspended.contains_key(task_id) {
+            let launch = self.launches.get(task_id).cloned().ok_or_else(|| {
+                anyhow::anyhow!("no previous agent launch for task {}", task_id.0)
+            })?;
+            self.launch(task_id, launch, Some(message))?;
+            self.suspended.remove(task_id);
+            return Ok(());
+        }
         let session = self
             .sessions
-            .get(task_id)
+            .get_mut(task_id)
             .ok_or_else(|| anyhow::anyhow!("no session for task {}", task_id.0))?;
         let tx = session
             .input_tx
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("session for {} is not interactive", task_id.0))?;
         tx.send(message.to_string())
-            .map_err(|_| anyhow::anyhow!("stdin channel closed for {}", task_id.0))
+            .map_err(|_| anyhow::anyhow!("stdin channel closed for {}", task_id.0))?;
+        session.last_input_at = Instant::now();
+        Ok(())
     }
 
     /// Non-blocking poll: drain output, detect signals, collect finished sessions.
     pub fn poll(&mut self) -> PollResult {
         let mut output = Vec::new();
         let mut completed = Vec::new();
+        let mut suspended = Vec::new();
         let mut finished_keys = Vec::new();
+        let output_buffer_bytes = self.output_buffer_bytes;
+        let idle_suspend_after = self.idle_suspend_after;
+        let adapter_for = self.adapter_for;
+        let signal_rules = &self.signal_rules;
 
         for (key, session) in self.sessions.iter_mut() {
-            // Drain output lines and check for signals.
+            // Drain output lines and check for signals. Output past the byte
+            // cap stays queued for the next poll.
             let mut lines = Vec::new();
-            while let Ok(line) = session.output_rx.try_recv() {
-                if let Some(signal) = detect_common_signal(&line) {
+            let mut drained_bytes = 0usize;
+            let mut backlogged = false;
+            loop {
+                if drained_bytes >= output_buffer_bytes {
+                    backlogged = true;
+                    break;
+                }
+                let Ok(line) = session.output_rx.try_recv() else {
+                    break;
+                };
+                drained_bytes += line.len();
+                if let Some(confidence) = parse_confidence_marker(&line) {
+                    session.confidence = Some(confidence);
+                }
+                if let Some(signal) = detect_signal_with_rules(&line, signal_rules) {
                     match signal.kind {
                         AgentSignalKind::PatchReady => {
                             session.patch_ready = true;
@@ -294,6 +521,7 @@ impl AgentSupervisor {
                 lines.push(line);
             }
             if !lines.is_empty() {
+                session.last_output_at = Instant::now();
                 rotate_task_log_if_needed(&session.task_id);
                 output.push(OutputChunk {
                     task_id: session.task_id.clone(),
@@ -337,11 +565,17 @@ impl AgentSupervisor {
                     needs_human: false,
                     success: false,
                     duration_secs: elapsed_secs,
+                    confidence: session.confidence,
                 });
                 finished_keys.push(key.clone());
                 continue;
             }
 
+            // Leave a session with queued output running until it is drained.
+            if backlogged {
+                continue;
+            }
+
             // Kill process if it signaled completion but hasn't exited.
             if let Some(t) = session.signal_at {
                 if t.elapsed() > Duration::from_secs(5) {
@@ -366,10 +600,32 @@ impl AgentSupervisor {
                         needs_human: session.needs_human,
                         success,
                         duration_secs,
+                        confidence: session.confidence,
                     });
                     finished_keys.push(key.clone());
                 }
-                Ok(None) => {} // still running
+                Ok(None) => {
+                    let idle = session.idle_for();
+                    let idle_expired = idle_suspend_after.is_some_and(|after| idle >= after);
+                    let session_id = self
+                        .launches
+                        .get(key)
+                        .map_or("", |launch| launch.session_id.as_str());
+                    if session.input_tx.is_some()
+                        && session.signal_at.is_none()
+                        && idle_expired
+                        && can_resume(adapter_for, session.model, session_id)
+                    {
+                        shutdown_process(session);
+                        suspended.push(SuspendedSession {
+                            task_id: session.task_id.clone(),
+                            model: session.model,
+                            session_id: session_id.to_string(),
+                            idle_secs: idle.as_secs(),
+                        });
+                        finished_keys.push(key.clone());
+                    }
+                }
                 Err(_) => {
                     let duration_secs = Utc::now()
                         .signed_duration_since(session.started_at)
@@ -383,6 +639,7 @@ impl AgentSupervisor {
                         needs_human: false,
                         success: false,
                         duration_secs,
+                        confidence: session.confidence,
                     });
                     finished_keys.push(key.clone());
                 }
@@ -392,8 +649,16 @@ impl AgentSupervisor {
         for key in finished_keys {
             self.sessions.remove(&key);
         }
+        for session in &suspended {
+            self.suspended
+                .insert(session.task_id.clone(), session.clone());
+        }
 
-        PollResult { output, completed }
+        PollResult {
+            output,
+            completed,
+            suspended,
+        }
     }
 
     pub fn running_count(&self) -> usize {
@@ -414,11 +679,13 @@ impl AgentSupervisor {
 
     /// Kill all running agent processes.
     pub fn stop_all(&mut self) {
+        self.suspended.clear();
         terminate_all_agents(&mut self.sessions);
     }
 
     /// Stop the agent for a specific task.
     pub fn stop(&mut self, task_id: &TaskId) {
+        self.suspended.remove(task_id);
         if let Some(mut session) = self.sessions.remove(task_id) {
             let _ = session.child.kill();
             let _ = session.child.wait();
@@ -432,25 +699,30 @@ fn send_sigterm(child: &mut Child) {
         .status();
 }
 
+/// SIGTERM the agent, give it a short grace period, then kill and reap it.
+fn shutdown_process(process: &mut AgentProcess) {
+    send_sigterm(&mut process.child);
+
+    let grace_deadline = Instant::now() + Duration::from_millis(500);
+    while Instant::now() < grace_deadline {
+        match process.child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) => thread::sleep(Duration::from_millis(25)),
+            Err(_) => break,
+        }
+    }
+
+    let still_running = matches!(process.child.try_wait(), Ok(None));
+    if still_running {
+        let _ = process.child.kill();
+        let _ = process.child.wait();
+    }
+}
+
 pub fn terminate_all_agents(active_processes: &mut HashMap<TaskId, AgentProcess>) {
     for (task_id, mut process) in active_processes.drain() {
         eprintln!("[supervisor] Sending SIGTERM to agent for task {}", task_id.0);
-        send_sigterm(&mut process.child);
-
-        let grace_deadline = Instant::now() + Duration::from_millis(500);
-        while Instant::now() < grace_deadline {
-            match process.child.try_wait() {
-                Ok(Some(_)) => break,
-                Ok(None) => thread::sleep(Duration::from_millis(25)),
-                Err(_) => break,
-            }
-        }
-
-        let still_running = matches!(process.child.try_wait(), Ok(None));
-        if still_running {
-            let _ = process.child.kill();
-            let _ = process.child.wait();
-        }
+        shutdown_process(&mut process);
     }
 }
 
@@ -513,6 +785,7 @@ pub fn build_prompt(task_id: &TaskId, title: &str, repo_root: &Path) -> String {
     sections.push(
         "# Signals\n\n\
          - When you are done and the code is ready, print exactly: `[patch_ready]`\n\
+         - Just before it, print how sure you are the change is correct: `CONFIDENCE: <0.0-1.0>`\n\
          - If you are blocked and need human help, print exactly: `[needs_human]`\n"
             .to_string(),
     );
@@ -523,6 +796,8 @@ pub fn build_prompt(task_id: &TaskId, title: &str, repo_root: &Path) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::chat_control::process_alive;
+    use orch_agents::ClaudeAdapter;
     use orch_core::types::{ModelKind, TaskId};
     use std::path::Path;
     use std::process::{Command, Stdio};
@@ -624,7 +899,7 @@ mod tests {
 
     #[test]
     fn send_input_fails_for_missing_session() {
-        let sup = AgentSupervisor::new(ModelKind::Claude);
+        let mut sup = AgentSupervisor::new(ModelKind::Claude);
         let err = sup
             .send_input(&TaskId::new("T-missing"), "hello")
             .expect_err("should fail for missing session");
@@ -648,7 +923,7 @@ mod tests {
             .spawn()
             .expect("spawn echo");
 
-        let (tx, rx) = mpsc::channel();
+        let (tx, rx) = mpsc::sync_channel(OUTPUT_CHANNEL_LINES);
         pipe_child_output(&mut child, tx);
 
         let session = AgentSession {
@@ -662,6 +937,9 @@ mod tests {
             patch_ready: false,
             needs_human: false,
             signal_at: None,
+            confidence: None,
+            last_input_at: Instant::now(),
+            last_output_at: Instant::now(),
         };
         sup.sessions.insert(task_id.clone(), session);
         assert!(sup.has_session(&task_id));
@@ -691,7 +969,7 @@ mod tests {
             .spawn()
             .expect("spawn echo");
 
-        let (tx, rx) = mpsc::channel();
+        let (tx, rx) = mpsc::sync_channel(OUTPUT_CHANNEL_LINES);
         pipe_child_output(&mut child, tx);
 
         let session = AgentSession {
@@ -705,6 +983,9 @@ mod tests {
             patch_ready: false,
             needs_human: false,
             signal_at: None,
+            confidence: None,
+            last_input_at: Instant::now(),
+            last_output_at: Instant::now(),
         };
         sup.sessions.insert(task_id.clone(), session);
 
@@ -729,7 +1010,7 @@ mod tests {
             .spawn()
             .expect("spawn echo");
 
-        let (tx, rx) = mpsc::channel();
+        let (tx, rx) = mpsc::sync_channel(OUTPUT_CHANNEL_LINES);
         pipe_child_output(&mut child, tx);
 
         let session = AgentSession {
@@ -743,6 +1024,9 @@ mod tests {
             patch_ready: false,
             needs_human: false,
             signal_at: None,
+            confidence: None,
+            last_input_at: Instant::now(),
+            last_output_at: Instant::now(),
         };
         sup.sessions.insert(task_id.clone(), session);
 
@@ -766,7 +1050,7 @@ mod tests {
             .spawn()
             .expect("spawn echo");
 
-        let (tx, rx) = mpsc::channel();
+        let (tx, rx) = mpsc::sync_channel(OUTPUT_CHANNEL_LINES);
         pipe_child_output(&mut child, tx);
 
         let session = AgentSession {
@@ -780,6 +1064,9 @@ mod tests {
             patch_ready: false,
             needs_human: false,
             signal_at: None,
+            confidence: None,
+            last_input_at: Instant::now(),
+            last_output_at: Instant::now(),
         };
         sup.sessions.insert(task_id.clone(), session);
 
@@ -803,7 +1090,7 @@ mod tests {
             .spawn()
             .expect("spawn sleep");
 
-        let (tx, rx) = mpsc::channel();
+        let (tx, rx) = mpsc::sync_channel(OUTPUT_CHANNEL_LINES);
         pipe_child_output(&mut child, tx);
 
         let session = AgentSession {
@@ -817,6 +1104,9 @@ mod tests {
             patch_ready: false,
             needs_human: false,
             signal_at: None,
+            confidence: None,
+            last_input_at: Instant::now(),
+            last_output_at: Instant::now(),
         };
         sup.sessions.insert(task_id.clone(), session);
         assert!(sup.has_session(&task_id));
@@ -838,7 +1128,7 @@ mod tests {
                 .spawn()
                 .expect("spawn sleep");
 
-            let (tx, rx) = mpsc::channel();
+            let (tx, rx) = mpsc::sync_channel(OUTPUT_CHANNEL_LINES);
             pipe_child_output(&mut child, tx);
 
             let session = AgentSession {
@@ -852,6 +1142,9 @@ mod tests {
                 patch_ready: false,
                 needs_human: false,
                 signal_at: None,
+                confidence: None,
+                last_input_at: Instant::now(),
+                last_output_at: Instant::now(),
             };
             sup.sessions.insert(task_id.clone(), session);
         }
@@ -880,7 +1173,7 @@ mod tests {
             .spawn()
             .expect("spawn sh");
 
-        let (tx, rx) = mpsc::channel();
+        let (tx, rx) = mpsc::sync_channel(OUTPUT_CHANNEL_LINES);
         pipe_child_output(&mut child, tx);
 
         let session = AgentSession {
@@ -894,6 +1187,9 @@ mod tests {
             patch_ready: false,
             needs_human: false,
             signal_at: None,
+            confidence: None,
+            last_input_at: Instant::now(),
+            last_output_at: Instant::now(),
         };
         sup.sessions.insert(task_id.clone(), session);
 
@@ -917,7 +1213,7 @@ mod tests {
             .spawn()
             .expect("spawn sleep");
 
-        let (tx, rx) = mpsc::channel();
+        let (tx, rx) = mpsc::sync_channel(OUTPUT_CHANNEL_LINES);
         pipe_child_output(&mut child, tx);
 
         let session = AgentSession {
@@ -931,6 +1227,9 @@ mod tests {
             patch_ready: false,
             needs_human: false,
             signal_at: None,
+            confidence: None,
+            last_input_at: Instant::now(),
+            last_output_at: Instant::now(),
         };
         sup.sessions.insert(task_id.clone(), session);
 
@@ -956,7 +1255,7 @@ mod tests {
             .stderr(Stdio::piped())
             .spawn()
             .expect("spawn fast");
-        let (fast_tx, fast_rx) = mpsc::channel();
+        let (fast_tx, fast_rx) = mpsc::sync_channel(OUTPUT_CHANNEL_LINES);
         pipe_child_output(&mut fast_child, fast_tx);
 
         let mut slow_child = Command::new("sleep")
@@ -965,7 +1264,7 @@ mod tests {
             .stderr(Stdio::piped())
             .spawn()
             .expect("spawn slow");
-        let (slow_tx, slow_rx) = mpsc::channel();
+        let (slow_tx, slow_rx) = mpsc::sync_channel(OUTPUT_CHANNEL_LINES);
         pipe_child_output(&mut slow_child, slow_tx);
 
         sup.sessions.insert(
@@ -981,6 +1280,9 @@ mod tests {
                 patch_ready: false,
                 needs_human: false,
                 signal_at: None,
+                confidence: None,
+                last_input_at: Instant::now(),
+                last_output_at: Instant::now(),
             },
         );
         sup.sessions.insert(
@@ -996,6 +1298,9 @@ mod tests {
                 patch_ready: false,
                 needs_human: false,
                 signal_at: None,
+                confidence: None,
+                last_input_at: Instant::now(),
+                last_output_at: Instant::now(),
             },
         );
 
@@ -1024,7 +1329,7 @@ mod tests {
             .stderr(Stdio::piped())
             .spawn()
             .expect("spawn sleep");
-        let (tx, rx) = mpsc::channel();
+        let (tx, rx) = mpsc::sync_channel(OUTPUT_CHANNEL_LINES);
         pipe_child_output(&mut child, tx);
 
         sup.sessions.insert(
@@ -1040,6 +1345,9 @@ mod tests {
                 patch_ready: false,
                 needs_human: false,
                 signal_at: None,
+                confidence: None,
+                last_input_at: Instant::now(),
+                last_output_at: Instant::now(),
             },
         );
 
@@ -1048,6 +1356,54 @@ mod tests {
         assert!(!sup.has_session(&task_id));
     }
 
+    #[test]
+    fn poll_caps_output_per_session_and_keeps_the_rest_queued() {
+        let mut sup = AgentSupervisor::new(ModelKind::Claude).with_output_buffer_bytes(250);
+        let task_id = TaskId::new("T-output-cap");
+
+        let child = Command::new("true").spawn().expect("spawn true");
+        let (tx, rx) = mpsc::channel();
+        for i in 0..10 {
+            tx.send(format!("{i:0>100}")).expect("queue line");
+        }
+        drop(tx);
+        sup.sessions.insert(
+            task_id.clone(),
+            AgentSession {
+                child,
+                output_rx: rx,
+                input_tx: None,
+                task_id: task_id.clone(),
+                model: ModelKind::Claude,
+                started_at: Utc::now(),
+                timeout: Duration::from_secs(DEFAULT_AGENT_TIMEOUT_SECS),
+                patch_ready: false,
+                needs_human: false,
+                signal_at: None,
+                confidence: None,
+                last_input_at: Instant::now(),
+                last_output_at: Instant::now(),
+            },
+        );
+        std::thread::sleep(std::time::Duration::from_millis(200));
+
+        let first = sup.poll();
+        assert_eq!(first.output[0].lines.len(), 3);
+        assert!(first.completed.is_empty());
+        assert!(sup.has_session(&task_id));
+
+        let mut drained = 3;
+        while sup.has_session(&task_id) {
+            let result = sup.poll();
+            drained += result
+                .output
+                .iter()
+                .map(|chunk| chunk.lines.len())
+                .sum::<usize>();
+        }
+        assert_eq!(drained, 10);
+    }
+
     #[test]
     fn test_no_timeout_when_within_limit() {
         let mut sup = AgentSupervisor::new(ModelKind::Claude);
@@ -1059,7 +1415,7 @@ mod tests {
             .stderr(Stdio::piped())
             .spawn()
             .expect("spawn sleep");
-        let (tx, rx) = mpsc::channel();
+        let (tx, rx) = mpsc::sync_channel(OUTPUT_CHANNEL_LINES);
         pipe_child_output(&mut child, tx);
 
         sup.sessions.insert(
@@ -1075,6 +1431,9 @@ mod tests {
                 patch_ready: false,
                 needs_human: false,
                 signal_at: None,
+                confidence: None,
+                last_input_at: Instant::now(),
+                last_output_at: Instant::now(),
             },
         );
 
@@ -1096,7 +1455,7 @@ mod tests {
             .stderr(Stdio::piped())
             .spawn()
             .expect("spawn sleep");
-        let (tx, rx) = mpsc::channel();
+        let (tx, rx) = mpsc::sync_channel(OUTPUT_CHANNEL_LINES);
         pipe_child_output(&mut child, tx);
 
         sup.sessions.insert(
@@ -1112,6 +1471,9 @@ mod tests {
                 patch_ready: true,
                 needs_human: true,
                 signal_at: None,
+                confidence: None,
+                last_input_at: Instant::now(),
+                last_output_at: Instant::now(),
             },
         );
 
@@ -1137,6 +1499,7 @@ mod tests {
             needs_human: false,
             success: true,
             duration_secs: 12,
+            confidence: None,
         };
         assert_eq!(outcome.task_id.0, "T-1");
         assert_eq!(outcome.model, ModelKind::Gemini);
@@ -1157,4 +1520,288 @@ mod tests {
         assert_eq!(chunk.lines.len(), 2);
         assert_eq!(chunk.task_id.0, "T-1");
     }
+
+    // -----------------------------------------------------------------------
+    // restart
+    // -----------------------------------------------------------------------
+
+    /// Runs a long sleep that ignores SIGTERM, like an agent stuck mid-stop.
+    struct StubbornAdapter;
+
+    impl AgentAdapter for StubbornAdapter {
+        fn model(&self) -> ModelKind {
+            ModelKind::Claude
+        }
+
+        fn build_command(&self, _request: &EpochRequest) -> orch_agents::AgentCommand {
+            orch_agents::AgentCommand {
+                executable: "sh".to_string(),
+                args: vec!["-c".to_string(), "trap '' TERM; exec sleep 30".to_string()],
+                env: vec![],
+            }
+        }
+    }
+
+    fn stubborn_adapter(_model: ModelKind) -> Result<Box<dyn AgentAdapter>, AgentError> {
+        Ok(Box::new(StubbornAdapter))
+    }
+
+    fn session_pid(sup: &AgentSupervisor, task_id: &TaskId) -> u32 {
+        sup.sessions.get(task_id).expect("session").child.id()
+    }
+
+    #[test]
+    fn restart_replaces_process_and_keeps_instance_id() {
+        let mut sup =
+            AgentSupervisor::new(ModelKind::Claude).with_adapter_factory(stubborn_adapter);
+        let task_id = TaskId::new("T-restart");
+        sup.spawn_agent(
+            &task_id,
+            &RepoId("example".to_string()),
+            &std::env::temp_dir(),
+            "fix the bug",
+            None,
+            Duration::from_secs(60),
+        )
+        .expect("spawn");
+        let instance_id = sup.instance_id(&task_id).map(str::to_string);
+        let old_pid = session_pid(&sup, &task_id);
+
+        // SIGTERM is ignored, so this is a stop that never finishes on its own.
+        send_sigterm(&mut sup.sessions.get_mut(&task_id).expect("session").child);
+        sup.restart(&task_id).expect("restart");
+
+        assert_eq!(sup.running_count(), 1);
+        assert!(!process_alive(old_pid));
+        let new_pid = session_pid(&sup, &task_id);
+        assert_ne!(new_pid, old_pid);
+        assert!(process_alive(new_pid));
+        assert_eq!(sup.instance_id(&task_id).map(str::to_string), instance_id);
+        assert_eq!(instance_id.as_deref(), Some("agent-T-restart"));
+
+        sup.stop(&task_id);
+        assert!(!process_alive(new_pid));
+    }
+
+    #[test]
+    fn restart_without_session_respawns_last_launch() {
+        let mut sup =
+            AgentSupervisor::new(ModelKind::Claude).with_adapter_factory(stubborn_adapter);
+        let task_id = TaskId::new("T-restart-idle");
+
+        let err = sup.restart(&task_id).expect_err("nothing to restart");
+        assert!(matches!(err, AgentError::InvalidRequest { .. }));
+
+        sup.spawn_interactive(
+            &task_id,
+            &RepoId("example".to_string()),
+            &std::env::temp_dir(),
+            "hello",
+            Some(ModelKind::Codex),
+        )
+        .expect("spawn");
+        sup.stop(&task_id);
+        assert!(!sup.has_session(&task_id));
+
+        sup.restart(&task_id).expect("restart");
+        sup.restart(&task_id).expect("restart again");
+
+        assert_eq!(sup.running_count(), 1);
+        let session = sup.sessions.get(&task_id).expect("session");
+        assert_eq!(session.model, ModelKind::Codex);
+        assert!(session.input_tx.is_some());
+        sup.stop_all();
+    }
+
+    // -----------------------------------------------------------------------
+    // idle suspend
+    // -----------------------------------------------------------------------
+
+    /// Prints its session arguments, then echoes stdin like a chat agent.
+    struct EchoAdapter;
+
+    impl AgentAdapter for EchoAdapter {
+        fn model(&self) -> ModelKind {
+            ModelKind::Claude
+        }
+
+        fn build_command(&self, request: &EpochRequest) -> orch_agents::AgentCommand {
+            let mut args = vec![
+                "-c".to_string(),
+                "echo \"args: $*\"; exec cat".to_string(),
+                "sh".to_string(),
+            ];
+            args.extend(request.extra_args.iter().cloned());
+            orch_agents::AgentCommand {
+                executable: "sh".to_string(),
+                args,
+                env: vec![],
+            }
+        }
+
+        fn session_args(&self, session_id: &str, resume: bool) -> Vec<String> {
+            ClaudeAdapter::default().session_args(session_id, resume)
+        }
+    }
+
+    fn echo_adapter(_model: ModelKind) -> Result<Box<dyn AgentAdapter>, AgentError> {
+        Ok(Box::new(EchoAdapter))
+    }
+
+    /// [`EchoAdapter`] for a model whose CLI has no session to resume.
+    struct CodexEchoAdapter;
+
+    impl AgentAdapter for CodexEchoAdapter {
+        fn model(&self) -> ModelKind {
+            ModelKind::Codex
+        }
+
+        fn build_command(&self, request: &EpochRequest) -> orch_agents::AgentCommand {
+            EchoAdapter.build_command(request)
+        }
+    }
+
+    fn codex_echo_adapter(_model: ModelKind) -> Result<Box<dyn AgentAdapter>, AgentError> {
+        Ok(Box::new(CodexEchoAdapter))
+    }
+
+    /// Poll until the task prints a line containing `needle`, returning all
+    /// lines seen on the way.
+    fn poll_until_line(sup: &mut AgentSupervisor, task_id: &TaskId, needle: &str) -> Vec<String> {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut seen = Vec::new();
+        while Instant::now() < deadline {
+            for chunk in sup.poll().output {
+                if &chunk.task_id == task_id {
+                    seen.extend(chunk.lines);
+                }
+            }
+            if seen.iter().any(|line| line.contains(needle)) {
+                return seen;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        panic!("no line containing {needle:?}; saw {seen:?}");
+    }
+
+    #[test]
+    fn idle_interactive_session_is_suspended_and_resumed_by_input() {
+        let mut sup = AgentSupervisor::new(ModelKind::Claude)
+            .with_adapter_factory(echo_adapter)
+            .with_idle_suspend(Duration::from_millis(300));
+        let task_id = TaskId::new("T-idle");
+        sup.spawn_interactive(
+            &task_id,
+            &RepoId("example".to_string()),
+            &std::env::temp_dir(),
+            "hello",
+            None,
+        )
+        .expect("spawn");
+        let session_id = sup.session_id(&task_id).expect("session id").to_string();
+        assert_eq!(session_id.len(), 36);
+
+        let seen = poll_until_line(&mut sup, &task_id, "hello");
+        assert!(seen[0].contains(&format!("--session-id {session_id}")));
+        let pid = session_pid(&sup, &task_id);
+
+        thread::sleep(Duration::from_millis(400));
+        let result = sup.poll();
+        assert!(result.completed.is_empty());
+        assert_eq!(result.suspended.len(), 1);
+        assert_eq!(result.suspended[0].session_id, session_id);
+        assert!(result.suspended[0].idle_secs < 5);
+        assert!(sup.is_suspended(&task_id));
+        assert!(!sup.has_session(&task_id));
+        assert_eq!(sup.running_count(), 0);
+        assert!(!process_alive(pid));
+
+        sup.send_input(&task_id, "next step").expect("resume");
+        assert!(!sup.is_suspended(&task_id));
+        assert!(sup.has_session(&task_id));
+        let seen = poll_until_line(&mut sup, &task_id, "next step");
+        assert!(seen[0].contains(&format!("--resume {session_id}")));
+        assert!(!seen.iter().any(|line| line.contains("hello")));
+        assert_eq!(sup.session_id(&task_id), Some(session_id.as_str()));
+        sup.stop_all();
+    }
+
+    #[test]
+    fn idle_session_without_resume_support_is_never_suspended() {
+        let mut sup = AgentSupervisor::new(ModelKind::Claude)
+            .with_adapter_factory(codex_echo_adapter)
+            .with_idle_suspend(Duration::from_millis(50));
+        let task_id = TaskId::new("T-idle-codex");
+        sup.spawn_interactive(
+            &task_id,
+            &RepoId("example".to_string()),
+            &std::env::temp_dir(),
+            "hello",
+            Some(ModelKind::Codex),
+        )
+        .expect("spawn");
+        poll_until_line(&mut sup, &task_id, "hello");
+        let pid = session_pid(&sup, &task_id);
+
+        thread::sleep(Duration::from_millis(150));
+        let result = sup.poll();
+        assert!(result.suspended.is_empty());
+        assert!(result.completed.is_empty());
+        assert!(!sup.is_suspended(&task_id));
+        assert!(sup.has_session(&task_id));
+        assert!(process_alive(pid));
+        sup.stop_all();
+    }
+
+    #[test]
+    fn custom_signal_rules_mark_sessions_patch_ready() {
+        let rules = SignalRuleSet::parse(
+            "[[rule]]\nname = \"done\"\npattern = \"^All changes committed\"\nkind = \"patch_ready\"\n",
+        )
+        .expect("rules");
+        let mut sup = AgentSupervisor::new(ModelKind::Claude)
+            .with_adapter_factory(echo_adapter)
+            .with_signal_rules(rules);
+        let task_id = TaskId::new("T-rules");
+        sup.spawn_interactive(
+            &task_id,
+            &RepoId("example".to_string()),
+            &std::env::temp_dir(),
+            "working",
+            None,
+        )
+        .expect("spawn");
+        poll_until_line(&mut sup, &task_id, "working");
+        assert!(!sup.sessions[&task_id].patch_ready);
+
+        sup.send_input(&task_id, "All changes committed")
+            .expect("send");
+        poll_until_line(&mut sup, &task_id, "All changes committed");
+        assert!(sup.sessions[&task_id].patch_ready);
+        sup.stop_all();
+    }
+
+    #[test]
+    fn idle_suspend_skips_one_shot_sessions_and_zero_disables_it() {
+        let mut sup = AgentSupervisor::new(ModelKind::Claude)
+            .with_adapter_factory(stubborn_adapter)
+            .with_idle_suspend(Duration::from_millis(1));
+        let task_id = TaskId::new("T-oneshot");
+        sup.spawn_agent(
+            &task_id,
+            &RepoId("example".to_string()),
+            &std::env::temp_dir(),
+            "fix the bug",
+            None,
+            Duration::from_secs(60),
+        )
+        .expect("spawn");
+        thread::sleep(Duration::from_millis(20));
+        assert!(sup.poll().suspended.is_empty());
+        assert!(sup.has_session(&task_id));
+        sup.stop_all();
+
+        let sup = AgentSupervisor::new(ModelKind::Claude).with_idle_suspend(Duration::ZERO);
+        assert_eq!(sup.idle_suspend_after, None);
+    }
 }
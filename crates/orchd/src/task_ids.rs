@@ -0,0 +1,247 @@
+//! Task ID validation and resolution.
+//!
+//! Users can refer to a task by its full ID, by a unique ID prefix
+//! (`chat-176003`), or by a user-assigned alias (`auth-fix`). Anything that
+//! resolves to nothing or to more than one task produces a typed error listing
+//! the candidates.
+
+use orch_core::types::TaskId;
+
+use crate::search::fuzzy_match;
+
+const MAX_TASK_ID_LEN: usize = 128;
+const MAX_SUGGESTIONS: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TaskIdError {
+    #[error("invalid task id '{input}': {reason}")]
+    Invalid { input: String, reason: String },
+    #[error("no such task '{input}'{}", format_closest(.suggestions))]
+    NotFound {
+        input: String,
+        suggestions: Vec<String>,
+    },
+    #[error("ambiguous task id '{input}'; candidates: {}", .candidates.join(", "))]
+    Ambiguous {
+        input: String,
+        candidates: Vec<String>,
+    },
+    #[error("alias '{alias}' is already used by task {task_id}")]
+    AliasCollision { alias: String, task_id: String },
+}
+
+fn format_closest(suggestions: &[String]) -> String {
+    if suggestions.is_empty() {
+        String::new()
+    } else {
+        format!("; closest matches: {}", suggestions.join(", "))
+    }
+}
+
+fn invalid(input: &str, reason: &str) -> TaskIdError {
+    TaskIdError::Invalid {
+        input: input.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+/// Trim a user-supplied task reference (ID, prefix, or alias) and check its
+/// length. Task IDs come from many sources, so no character set is enforced.
+pub fn normalize_task_ref(input: &str) -> Result<String, TaskIdError> {
+    let trimmed = input.trim();
+    if trimmed.is_empty() {
+        return Err(invalid(input, "must not be empty"));
+    }
+    if trimmed.len() > MAX_TASK_ID_LEN {
+        return Err(invalid(
+            input,
+            &format!("must be at most {MAX_TASK_ID_LEN} characters"),
+        ));
+    }
+    Ok(trimmed.to_string())
+}
+
+/// Validate an alias name. Aliases are case-insensitive and stored lowercase,
+/// and are limited to letters, digits, `-`, `_` and `.`.
+pub fn normalize_alias(alias: &str) -> Result<String, TaskIdError> {
+    let trimmed = normalize_task_ref(alias)?;
+    if let Some(bad) = trimmed
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+    {
+        return Err(invalid(
+            alias,
+            &format!("unexpected character '{bad}' (allowed: letters, digits, '-', '_', '.')"),
+        ));
+    }
+    Ok(trimmed.to_lowercase())
+}
+
+/// Resolve a reference against the known task IDs and aliases.
+///
+/// Resolution order: exact ID, exact alias, then unique prefix across both.
+/// An exact ID match wins before any validation, so existing tasks stay
+/// reachable whatever their ID looks like.
+pub fn resolve_task_ref(
+    input: &str,
+    task_ids: &[TaskId],
+    aliases: &[(String, TaskId)],
+) -> Result<TaskId, TaskIdError> {
+    if let Some(id) = task_ids.iter().find(|id| id.0 == input.trim()) {
+        return Ok(id.clone());
+    }
+    let needle = normalize_task_ref(input)?;
+
+    let needle_lc = needle.to_lowercase();
+    if let Some((_, id)) = aliases.iter().find(|(alias, _)| *alias == needle_lc) {
+        return Ok(id.clone());
+    }
+
+    let mut matches: Vec<TaskId> = task_ids
+        .iter()
+        .filter(|id| id.0.starts_with(&needle))
+        .cloned()
+        .chain(
+            aliases
+                .iter()
+                .filter(|(alias, _)| alias.starts_with(&needle_lc))
+                .map(|(_, id)| id.clone()),
+        )
+        .collect();
+    matches.sort_by(|a, b| a.0.cmp(&b.0));
+    matches.dedup();
+
+    match matches.len() {
+        1 => Ok(matches.remove(0)),
+        0 => Err(TaskIdError::NotFound {
+            input: needle,
+            suggestions: closest_matches(input, task_ids, aliases),
+        }),
+        _ => Err(TaskIdError::Ambiguous {
+            input: needle,
+            candidates: matches.into_iter().map(|id| id.0).collect(),
+        }),
+    }
+}
+
+/// Best-scoring IDs and aliases for an unresolved reference.
+pub fn closest_matches(
+    input: &str,
+    task_ids: &[TaskId],
+    aliases: &[(String, TaskId)],
+) -> Vec<String> {
+    let needle = input.trim();
+    let mut scored: Vec<(f64, String)> = task_ids
+        .iter()
+        .map(|id| id.0.clone())
+        .chain(aliases.iter().map(|(alias, _)| alias.clone()))
+        .filter_map(|candidate| {
+            fuzzy_match(needle, &candidate)
+                .or_else(|| fuzzy_match(&candidate, needle))
+                .map(|score| (score, candidate))
+        })
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+    scored.dedup_by(|a, b| a.1 == b.1);
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(raw: &[&str]) -> Vec<TaskId> {
+        raw.iter().map(|id| TaskId::new(*id)).collect()
+    }
+
+    #[test]
+    fn normalize_rejects_empty_and_invalid_characters() {
+        assert_eq!(normalize_task_ref("  chat-1 ").unwrap(), "chat-1");
+        assert!(matches!(
+            normalize_task_ref("   "),
+            Err(TaskIdError::Invalid { .. })
+        ));
+        assert!(matches!(
+            normalize_alias("auth fix"),
+            Err(TaskIdError::Invalid { .. })
+        ));
+        assert_eq!(normalize_alias("Auth-Fix").unwrap(), "auth-fix");
+    }
+
+    #[test]
+    fn exact_ids_resolve_regardless_of_characters() {
+        let task_ids = ids(&["feature/login", "T:42 retry"]);
+        assert_eq!(
+            resolve_task_ref("feature/login", &task_ids, &[]).unwrap(),
+            TaskId::new("feature/login")
+        );
+        assert_eq!(
+            resolve_task_ref(" T:42 retry ", &task_ids, &[]).unwrap(),
+            TaskId::new("T:42 retry")
+        );
+        assert_eq!(
+            resolve_task_ref("feature/", &task_ids, &[]).unwrap(),
+            TaskId::new("feature/login")
+        );
+    }
+
+    #[test]
+    fn resolves_exact_id_alias_and_unique_prefix() {
+        let task_ids = ids(&["chat-1760000000123", "chat-1760000999000"]);
+        let aliases = vec![("auth-fix".to_string(), TaskId::new("chat-1760000000123"))];
+
+        assert_eq!(
+            resolve_task_ref("chat-1760000999000", &task_ids, &aliases).unwrap(),
+            TaskId::new("chat-1760000999000")
+        );
+        assert_eq!(
+            resolve_task_ref("AUTH-FIX", &task_ids, &aliases).unwrap(),
+            TaskId::new("chat-1760000000123")
+        );
+        assert_eq!(
+            resolve_task_ref("chat-17600009", &task_ids, &aliases).unwrap(),
+            TaskId::new("chat-1760000999000")
+        );
+        assert_eq!(
+            resolve_task_ref("auth", &task_ids, &aliases).unwrap(),
+            TaskId::new("chat-1760000000123")
+        );
+    }
+
+    #[test]
+    fn ambiguous_prefix_lists_candidates() {
+        let task_ids = ids(&["chat-1760000000123", "chat-1760000999000"]);
+        let err = resolve_task_ref("chat-176", &task_ids, &[]).unwrap_err();
+        assert_eq!(
+            err,
+            TaskIdError::Ambiguous {
+                input: "chat-176".to_string(),
+                candidates: vec![
+                    "chat-1760000000123".to_string(),
+                    "chat-1760000999000".to_string()
+                ],
+            }
+        );
+        assert!(err.to_string().contains("candidates: chat-1760000000123"));
+    }
+
+    #[test]
+    fn unknown_id_suggests_closest_matches() {
+        let task_ids = ids(&["chat-1760000000123", "T-deploy"]);
+        let aliases = vec![("auth-fix".to_string(), TaskId::new("T-deploy"))];
+        let err = resolve_task_ref("auth-fx", &task_ids, &aliases).unwrap_err();
+        match &err {
+            TaskIdError::NotFound { suggestions, .. } => {
+                assert_eq!(suggestions.first().map(String::as_str), Some("auth-fix"));
+            }
+            other => panic!("expected NotFound, got {other:?}"),
+        }
+        assert!(err
+            .to_string()
+            .starts_with("no such task 'auth-fx'; closest matches: auth-fix"));
+    }
+}
@@ -1,4 +1,5 @@
 use chrono::{DateTime, Duration, Utc};
+use orch_core::types::TaskId;
 use std::collections::HashMap;
 use std::fmt;
 
@@ -96,6 +97,27 @@ impl TimeoutTracker {
         entry
     }
 
+    /// Track a task that started at `started_at` with an explicit timeout,
+    /// capped at the configured maximum.
+    pub fn register(
+        &mut self,
+        task_id: &TaskId,
+        started_at: DateTime<Utc>,
+        timeout_secs: u64,
+    ) -> TimeoutEntry {
+        let timeout_secs = timeout_secs.min(self.config.max_timeout_secs);
+        let entry = TimeoutEntry {
+            task_id: task_id.0.clone(),
+            started_at,
+            deadline: started_at + Duration::seconds(timeout_secs as i64),
+            state_at_start: String::new(),
+            grace_expires: None,
+        };
+
+        self.entries.insert(task_id.0.clone(), entry.clone());
+        entry
+    }
+
     pub fn stop_tracking(&mut self, task_id: &str) -> Option<TimeoutEntry> {
         self.entries.remove(task_id)
     }
@@ -129,11 +151,40 @@ impl TimeoutTracker {
         })
     }
 
+    /// Time left before the task is killed: until the grace period ends, or
+    /// until deadline plus grace when the grace period hasn't started yet.
+    /// Zero once that point has passed.
+    pub fn remaining(&self, task_id: &TaskId, now: DateTime<Utc>) -> Option<std::time::Duration> {
+        self.entries.get(&task_id.0).map(|entry| {
+            (self.kill_at(entry) - now)
+                .to_std()
+                .unwrap_or(std::time::Duration::ZERO)
+        })
+    }
+
+    /// Tasks whose timeout and grace period have both run out, sorted by ID.
+    pub fn expired(&self, now: DateTime<Utc>) -> Vec<TaskId> {
+        let mut expired: Vec<TaskId> = self
+            .entries
+            .values()
+            .filter(|entry| now >= self.kill_at(entry))
+            .map(|entry| TaskId::new(&entry.task_id))
+            .collect();
+        expired.sort_by(|a, b| a.0.cmp(&b.0));
+        expired
+    }
+
+    fn kill_at(&self, entry: &TimeoutEntry) -> DateTime<Utc> {
+        entry.grace_expires.unwrap_or_else(|| {
+            entry.deadline + Duration::seconds(self.config.grace_period_secs as i64)
+        })
+    }
+
     pub fn active_count(&self) -> usize {
         self.entries.len()
     }
 
-    fn timeout_for_state(&self, state: &str) -> u64 {
+    pub fn timeout_for_state(&self, state: &str) -> u64 {
         let timeout = self
             .config
             .per_state_timeouts
@@ -456,4 +507,53 @@ mod tests {
         assert!(missing.contains("abc"));
         assert!(max.contains("999"));
     }
+
+    #[test]
+    fn register_uses_explicit_start_and_caps_timeout() {
+        let mut tracker = TimeoutTracker::default();
+        let started_at = DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+            .expect("timestamp")
+            .with_timezone(&Utc);
+
+        let entry = tracker.register(&TaskId::new("T-REG"), started_at, 200_000);
+
+        assert_eq!(entry.started_at, started_at);
+        assert_eq!(entry.deadline, started_at + Duration::seconds(86_400));
+        assert!(tracker.is_tracked("T-REG"));
+    }
+
+    #[test]
+    fn remaining_and_expired_partition_at_fixed_now() {
+        let mut tracker = TimeoutTracker::default();
+        let now = DateTime::parse_from_rfc3339("2026-01-01T12:00:00Z")
+            .expect("timestamp")
+            .with_timezone(&Utc);
+        // Fresh: 600s timeout started 100s ago → 500s + 60s grace left.
+        tracker.register(&TaskId::new("fresh"), now - Duration::seconds(100), 600);
+        // Past deadline but inside the default 60s grace period.
+        tracker.register(&TaskId::new("grace"), now - Duration::seconds(630), 600);
+        // Past deadline and grace.
+        tracker.register(&TaskId::new("dead"), now - Duration::seconds(1_000), 600);
+        // Grace period already started by check_timeouts and now over.
+        tracker.register(&TaskId::new("killed"), now - Duration::seconds(700), 600);
+        tracker.entries.get_mut("killed").expect("entry").grace_expires =
+            Some(now - Duration::seconds(1));
+
+        let secs = |id: &str| {
+            tracker
+                .remaining(&TaskId::new(id), now)
+                .expect("tracked")
+                .as_secs()
+        };
+        assert_eq!(secs("fresh"), 560);
+        assert_eq!(secs("grace"), 30);
+        assert_eq!(secs("dead"), 0);
+        assert_eq!(secs("killed"), 0);
+        assert!(tracker.remaining(&TaskId::new("missing"), now).is_none());
+
+        assert_eq!(
+            tracker.expired(now),
+            vec![TaskId::new("dead"), TaskId::new("killed")]
+        );
+    }
 }
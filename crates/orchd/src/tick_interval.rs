@@ -0,0 +1,90 @@
+//! Adaptive sleep between daemon ticks.
+//!
+//! While there is work the daemon ticks at the floor; each idle tick doubles
+//! the interval until it reaches the ceiling, and any activity snaps it back.
+
+use orch_core::config::AdaptiveTickConfig;
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdaptiveTick {
+    floor: Duration,
+    ceiling: Duration,
+    current: Duration,
+}
+
+impl AdaptiveTick {
+    /// Start at `floor`. A ceiling below the floor is raised to it.
+    pub fn new(floor: Duration, ceiling: Duration) -> Self {
+        let floor = floor.max(Duration::from_millis(1));
+        Self {
+            floor,
+            ceiling: ceiling.max(floor),
+            current: floor,
+        }
+    }
+
+    /// `None` when adaptive ticking is disabled in the config.
+    pub fn from_config(config: &AdaptiveTickConfig) -> Option<Self> {
+        config.enabled.then(|| {
+            Self::new(
+                Duration::from_millis(config.floor_ms),
+                Duration::from_millis(config.ceiling_ms),
+            )
+        })
+    }
+
+    pub fn current(&self) -> Duration {
+        self.current
+    }
+
+    /// Record whether the last tick saw active work and return how long to
+    /// sleep before the next one.
+    pub fn next(&mut self, active: bool) -> Duration {
+        self.current = if active {
+            self.floor
+        } else {
+            self.current.saturating_mul(2).min(self.ceiling)
+        };
+        self.current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn idle_ticks_back_off_to_the_ceiling_and_activity_resets_to_the_floor() {
+        let mut tick = AdaptiveTick::new(Duration::from_millis(500), Duration::from_secs(3));
+        assert_eq!(tick.current(), Duration::from_millis(500));
+
+        let idle: Vec<Duration> = (0..5).map(|_| tick.next(false)).collect();
+        assert_eq!(
+            idle,
+            vec![
+                Duration::from_secs(1),
+                Duration::from_secs(2),
+                Duration::from_secs(3),
+                Duration::from_secs(3),
+                Duration::from_secs(3),
+            ]
+        );
+
+        assert_eq!(tick.next(true), Duration::from_millis(500));
+        assert_eq!(tick.next(false), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn from_config_is_none_unless_enabled() {
+        let mut config = AdaptiveTickConfig::default();
+        assert_eq!(AdaptiveTick::from_config(&config), None);
+
+        config.enabled = true;
+        config.floor_ms = 2_000;
+        config.ceiling_ms = 1_000;
+        let mut tick = AdaptiveTick::from_config(&config).expect("enabled");
+        assert_eq!(tick.current(), Duration::from_secs(2));
+        assert_eq!(tick.next(false), Duration::from_secs(2));
+    }
+}
@@ -23,6 +23,10 @@ pub struct TaskRunRecord {
     pub estimated_tokens: Option<u64>,
     #[serde(default)]
     pub duration_secs: Option<f64>,
+    /// The agent's self-reported confidence in whole percent; `None` when it
+    /// printed no well-formed `CONFIDENCE:` marker.
+    #[serde(default)]
+    pub confidence: Option<u8>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -35,6 +39,86 @@ pub struct ArtifactRecord {
     pub metadata_json: Option<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MergeQueueEntry {
+    pub task_id: TaskId,
+    pub position: usize,
+    pub enqueued_at: DateTime<Utc>,
+}
+
+/// A question an agent raised with `[needs_human]`, open until answered.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentQuestion {
+    pub question_id: String,
+    pub task_id: TaskId,
+    pub question: String,
+    pub asked_at: DateTime<Utc>,
+    pub answer: Option<String>,
+    pub answered_at: Option<DateTime<Utc>>,
+    /// When the stale-question escalation fired, if it has.
+    pub escalated_at: Option<DateTime<Utc>>,
+}
+
+impl AgentQuestion {
+    pub fn is_open(&self) -> bool {
+        self.answered_at.is_none()
+    }
+}
+
+/// How a benchmark task ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BenchOutcome {
+    /// Reached `AwaitingMerge` or `Merged`.
+    Merged,
+    /// Stopped, or still unfinished when the iteration timed out.
+    Failed,
+}
+
+impl BenchOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BenchOutcome::Merged => "merged",
+            BenchOutcome::Failed => "failed",
+        }
+    }
+}
+
+/// Result of one benchmark task run, recorded under a tag.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchResult {
+    pub tag: String,
+    /// Spec file stem the task was created from.
+    pub spec: String,
+    pub iteration: u32,
+    pub task_id: TaskId,
+    pub outcome: BenchOutcome,
+    pub retries: u32,
+    pub tokens: u64,
+    pub duration_secs: f64,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Ordered merge queue plus the version used for optimistic concurrency.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct MergeQueueSnapshot {
+    pub version: u64,
+    pub entries: Vec<MergeQueueEntry>,
+}
+
+impl MergeQueueSnapshot {
+    pub fn position_of(&self, task_id: &TaskId) -> Option<usize> {
+        self.entries
+            .iter()
+            .find(|entry| entry.task_id == *task_id)
+            .map(|entry| entry.position)
+    }
+
+    pub fn task_ids(&self) -> Vec<TaskId> {
+        self.entries.iter().map(|entry| entry.task_id.clone()).collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use chrono::Utc;
@@ -56,6 +140,7 @@ mod tests {
             exit_code: Some(0),
             estimated_tokens: Some(128),
             duration_secs: Some(3.25),
+            confidence: None,
         };
 
         let encoded = serde_json::to_string(&record).expect("serialize");
@@ -76,6 +161,7 @@ mod tests {
             exit_code: None,
             estimated_tokens: None,
             duration_secs: None,
+            confidence: None,
         };
 
         let encoded = serde_json::to_string(&record).expect("serialize");
@@ -113,6 +199,7 @@ mod tests {
             exit_code: Some(124),
             estimated_tokens: Some(512),
             duration_secs: Some(30.0),
+            confidence: None,
         };
 
         let encoded = serde_json::to_string(&record).expect("serialize");
@@ -10,8 +10,21 @@ pub struct VersionInfo {
     pub latest: Option<String>,
     pub update_available: bool,
     pub release_url: Option<String>,
+    /// Body of the latest release, shown before installing it.
+    #[serde(default)]
+    pub release_notes: Option<String>,
 }
 
+/// The latest published release.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LatestRelease {
+    pub version: String,
+    pub notes: Option<String>,
+}
+
+/// Release notes longer than this are cut off in `display_version_check`.
+const MAX_RELEASE_NOTE_LINES: usize = 30;
+
 /// Check current version
 pub fn current_version() -> String {
     env!("CARGO_PKG_VERSION").to_string()
@@ -19,27 +32,45 @@ pub fn current_version() -> String {
 
 /// Check for updates by querying GitHub releases API
 pub fn check_for_update() -> VersionInfo {
-    let current = current_version();
+    check_for_update_with(current_version(), fetch_latest_release)
+}
 
-    // Try to get latest release from GitHub
-    let latest = fetch_latest_version();
+/// Compare `current` against the release returned by `fetch_latest`.
+pub fn check_for_update_with(
+    current: String,
+    fetch_latest: impl FnOnce() -> Option<LatestRelease>,
+) -> VersionInfo {
+    let latest = fetch_latest();
     let update_available = latest
-        .as_deref()
-        .map(|l| version_is_newer(l, &current))
+        .as_ref()
+        .map(|l| version_is_newer(&l.version, &current))
         .unwrap_or(false);
 
     VersionInfo {
         current,
-        latest: latest.clone(),
+        latest: latest.as_ref().map(|l| l.version.clone()),
         update_available,
-        release_url: latest.map(|v| format!("https://github.com/0xMugen/Othala/releases/tag/v{v}")),
+        release_url: latest.as_ref().map(|l| {
+            format!(
+                "https://github.com/0xMugen/Othala/releases/tag/v{}",
+                l.version
+            )
+        }),
+        release_notes: latest.and_then(|l| l.notes),
     }
 }
 
-/// Fetch latest version from GitHub API using gh CLI
-fn fetch_latest_version() -> Option<String> {
+#[derive(Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    #[serde(default)]
+    body: Option<String>,
+}
+
+/// Fetch the latest release from GitHub API using gh CLI
+fn fetch_latest_release() -> Option<LatestRelease> {
     let output = Command::new("gh")
-        .args(["api", "repos/0xMugen/Othala/releases/latest", "--jq", ".tag_name"])
+        .args(["api", "repos/0xMugen/Othala/releases/latest"])
         .output()
         .ok()?;
 
@@ -47,13 +78,21 @@ fn fetch_latest_version() -> Option<String> {
         return None;
     }
 
-    let tag = String::from_utf8_lossy(&output.stdout).trim().to_string();
-    let version = tag.strip_prefix('v').unwrap_or(&tag).to_string();
+    parse_latest_release(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn parse_latest_release(json: &str) -> Option<LatestRelease> {
+    let release: GithubRelease = serde_json::from_str(json).ok()?;
+    let tag = release.tag_name.trim();
+    let version = tag.strip_prefix('v').unwrap_or(tag).to_string();
     if version.is_empty() {
-        None
-    } else {
-        Some(version)
+        return None;
     }
+    let notes = release
+        .body
+        .map(|body| body.trim().to_string())
+        .filter(|body| !body.is_empty());
+    Some(LatestRelease { version, notes })
 }
 
 /// Compare version strings (simple semver comparison)
@@ -112,6 +151,15 @@ pub fn display_version_check(info: &VersionInfo) -> String {
             if let Some(url) = &info.release_url {
                 out.push_str(&format!("Release: {}\n", url));
             }
+            if let Some(notes) = &info.release_notes {
+                out.push_str(&format!("\nWhat's new in {}:\n", latest));
+                for line in notes.lines().take(MAX_RELEASE_NOTE_LINES) {
+                    out.push_str(&format!("  {}\n", line.trim_end()));
+                }
+                if notes.lines().count() > MAX_RELEASE_NOTE_LINES {
+                    out.push_str("  ... (see the release page for the full notes)\n");
+                }
+            }
             out.push_str("\nRun 'othala upgrade --install' to update.");
         } else {
             out.push_str("You're up to date.");
@@ -164,6 +212,7 @@ mod tests {
             latest: Some("1.1.0".to_string()),
             update_available: true,
             release_url: Some("https://github.com/0xMugen/Othala/releases/tag/v1.1.0".to_string()),
+            release_notes: None,
         };
 
         let output = display_version_check(&info);
@@ -172,4 +221,52 @@ mod tests {
         assert!(output.contains("Update available!"));
         assert!(output.contains("othala upgrade --install"));
     }
+
+    #[test]
+    fn display_version_check_includes_fetched_release_notes() {
+        let info = check_for_update_with("1.0.0".to_string(), || {
+            Some(LatestRelease {
+                version: "1.1.0".to_string(),
+                notes: Some("## Changes\n- Add merge polling\n- Fix watcher storms".to_string()),
+            })
+        });
+
+        assert!(info.update_available);
+        let output = display_version_check(&info);
+        assert!(output.contains("What's new in 1.1.0:"));
+        assert!(output.contains("  - Add merge polling\n"));
+        assert!(output.contains("  - Fix watcher storms\n"));
+        assert!(output.ends_with("othala upgrade --install' to update."));
+    }
+
+    #[test]
+    fn display_version_check_omits_notes_when_up_to_date() {
+        let info = check_for_update_with("1.1.0".to_string(), || {
+            Some(LatestRelease {
+                version: "1.1.0".to_string(),
+                notes: Some("- Add merge polling".to_string()),
+            })
+        });
+
+        assert!(!info.update_available);
+        assert!(!display_version_check(&info).contains("What's new"));
+    }
+
+    #[test]
+    fn parse_latest_release_reads_tag_and_body() {
+        let release = parse_latest_release(
+            r#"{"tag_name": "v1.2.0", "body": "  - Faster restacks\n", "draft": false}"#,
+        )
+        .expect("release");
+        assert_eq!(
+            release,
+            LatestRelease {
+                version: "1.2.0".to_string(),
+                notes: Some("- Faster restacks".to_string()),
+            }
+        );
+        let bare = parse_latest_release(r#"{"tag_name": "1.2.0", "body": ""}"#).expect("release");
+        assert_eq!(bare.notes, None);
+        assert_eq!(parse_latest_release("not json"), None);
+    }
 }
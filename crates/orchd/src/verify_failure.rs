@@ -0,0 +1,265 @@
+//! Reproduction bundles for failed verify commands.
+//!
+//! When the daemon's verify step fails it snapshots what is needed to rerun
+//! the command by hand — command, cwd, relevant environment, toolchain
+//! versions and the tail of the output — under
+//! `.othala/verify-failures/<task>/<timestamp>/`.
+
+use chrono::{DateTime, Utc};
+use orch_core::shell::shell_quote;
+use orch_core::types::TaskId;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::env_inject::EnvInjector;
+
+pub const VERIFY_FAILURES_DIR: &str = ".othala/verify-failures";
+pub const BUNDLE_FILE: &str = "bundle.json";
+/// Output lines kept in a bundle.
+pub const OUTPUT_TAIL_LINES: usize = 200;
+
+/// Variables captured when present, matched by exact name or `PREFIX*`.
+const RELEVANT_ENV: &[&str] = &[
+    "PATH", "HOME", "SHELL", "LANG", "CI", "CARGO*", "RUST*", "NODE*", "NPM*", "PYTHON*",
+    "VIRTUAL_ENV", "NIX*", "IN_NIX_SHELL", "OTHALA*",
+];
+
+/// Toolchains probed with `<program> --version`; missing ones are skipped.
+const TOOLCHAINS: &[&str] = &["rustc", "cargo", "node", "python3"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyFailureBundle {
+    pub task_id: TaskId,
+    pub captured_at: DateTime<Utc>,
+    /// The configured verify command.
+    pub command: String,
+    /// What actually ran, e.g. wrapped in a nix shell.
+    pub effective_command: String,
+    pub cwd: PathBuf,
+    pub exit_code: Option<i32>,
+    pub env: BTreeMap<String, String>,
+    pub toolchains: BTreeMap<String, String>,
+    pub output_tail: Vec<String>,
+}
+
+impl VerifyFailureBundle {
+    /// Snapshot the current environment and toolchains for a failed run.
+    pub fn capture(
+        task_id: &TaskId,
+        command: &str,
+        effective_command: &str,
+        cwd: &Path,
+        exit_code: Option<i32>,
+        output: &str,
+        captured_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            task_id: task_id.clone(),
+            captured_at,
+            command: command.to_string(),
+            effective_command: effective_command.to_string(),
+            cwd: cwd.to_path_buf(),
+            exit_code,
+            env: relevant_env(std::env::vars()),
+            toolchains: toolchain_versions(cwd),
+            output_tail: tail_lines(output, OUTPUT_TAIL_LINES),
+        }
+    }
+
+    /// Shell line that reruns the command the way the daemon did.
+    pub fn repro_command(&self) -> String {
+        format!(
+            "cd {} && bash -lc {}",
+            shell_quote(&self.cwd.display().to_string()),
+            shell_quote(&self.effective_command)
+        )
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        out.push_str(&format!("Verify failure for {}\n", self.task_id.0));
+        out.push_str(&format!("Captured: {}\n", self.captured_at.to_rfc3339()));
+        out.push_str(&format!("Command:  {}\n", self.command));
+        if self.effective_command != self.command {
+            out.push_str(&format!("Ran as:   {}\n", self.effective_command));
+        }
+        out.push_str(&format!("Cwd:      {}\n", self.cwd.display()));
+        let exit = self.exit_code.map(|code| code.to_string());
+        out.push_str(&format!("Exit:     {}\n", exit.as_deref().unwrap_or("signal")));
+        out.push_str("\nToolchains:\n");
+        for (name, version) in &self.toolchains {
+            out.push_str(&format!("  {name}: {version}\n"));
+        }
+        out.push_str("\nEnvironment:\n");
+        for (name, value) in &self.env {
+            out.push_str(&format!("  {name}={value}\n"));
+        }
+        out.push_str(&format!("\nOutput (last {} lines):\n", self.output_tail.len()));
+        for line in &self.output_tail {
+            out.push_str(&format!("  {line}\n"));
+        }
+        out.push_str(&format!("\nReproduce:\n  {}\n", self.repro_command()));
+        out
+    }
+}
+
+pub fn task_failures_dir(repo_root: &Path, task_id: &TaskId) -> PathBuf {
+    repo_root.join(VERIFY_FAILURES_DIR).join(&task_id.0)
+}
+
+/// Write the bundle and return its directory.
+pub fn save_bundle(repo_root: &Path, bundle: &VerifyFailureBundle) -> std::io::Result<PathBuf> {
+    let stamp = bundle.captured_at.format("%Y%m%dT%H%M%S%.3fZ").to_string();
+    let dir = task_failures_dir(repo_root, &bundle.task_id).join(stamp);
+    fs::create_dir_all(&dir)?;
+    let json = serde_json::to_string_pretty(bundle)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    fs::write(dir.join(BUNDLE_FILE), json)?;
+    Ok(dir)
+}
+
+/// Bundle directories for a task, oldest first.
+pub fn list_bundles(repo_root: &Path, task_id: &TaskId) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(task_failures_dir(repo_root, task_id)) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.join(BUNDLE_FILE).is_file())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+pub fn load_bundle(dir: &Path) -> std::io::Result<VerifyFailureBundle> {
+    let raw = fs::read_to_string(dir.join(BUNDLE_FILE))?;
+    serde_json::from_str(&raw).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+fn relevant_env(vars: impl Iterator<Item = (String, String)>) -> BTreeMap<String, String> {
+    let redactor = EnvInjector::secret_redactor();
+    vars.filter(|(name, _)| {
+        RELEVANT_ENV.iter().any(|pattern| match pattern.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => name == pattern,
+        })
+    })
+    .map(|(name, value)| {
+        let value = redactor.redact_value(&name, &value);
+        (name, value)
+    })
+    .collect()
+}
+
+fn toolchain_versions(cwd: &Path) -> BTreeMap<String, String> {
+    TOOLCHAINS
+        .iter()
+        .filter_map(|program| {
+            let output = Command::new(program)
+                .arg("--version")
+                .current_dir(cwd)
+                .output()
+                .ok()
+                .filter(|output| output.status.success())?;
+            // python2 printed its version to stderr; take whichever has it.
+            let text = [output.stdout, output.stderr]
+                .iter()
+                .map(|bytes| String::from_utf8_lossy(bytes).trim().to_string())
+                .find(|text| !text.is_empty())?;
+            Some((program.to_string(), text.lines().next().unwrap_or_default().to_string()))
+        })
+        .collect()
+}
+
+fn tail_lines(output: &str, max: usize) -> Vec<String> {
+    let lines: Vec<&str> = output.lines().collect();
+    lines[lines.len().saturating_sub(max)..]
+        .iter()
+        .map(|line| line.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_repo() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "othala-verify-failure-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(&dir).expect("create temp repo");
+        dir
+    }
+
+    #[test]
+    fn relevant_env_keeps_toolchain_vars_and_masks_secrets() {
+        let vars = [
+            ("CARGO_HOME", "/home/dev/.cargo"),
+            ("CARGO_REGISTRY_TOKEN", "hunter2"),
+            ("PATH", "/usr/bin"),
+            ("EDITOR", "vim"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()));
+
+        let env = relevant_env(vars);
+        assert_eq!(env.get("CARGO_HOME").map(String::as_str), Some("/home/dev/.cargo"));
+        assert_eq!(env.get("CARGO_REGISTRY_TOKEN").map(String::as_str), Some("***"));
+        assert_eq!(env.get("PATH").map(String::as_str), Some("/usr/bin"));
+        assert!(!env.contains_key("EDITOR"));
+    }
+
+    #[test]
+    fn saved_bundle_round_trips_and_lists_latest_last() {
+        let repo = temp_repo();
+        let task_id = TaskId::new("T-VF-1");
+        let output: String = (1..=250).map(|n| format!("line {n}\n")).collect();
+        let first = VerifyFailureBundle::capture(
+            &task_id,
+            "cargo test",
+            "cargo test",
+            &repo,
+            Some(101),
+            &output,
+            Utc::now(),
+        );
+        let mut second = first.clone();
+        second.captured_at = first.captured_at + chrono::Duration::seconds(5);
+        second.effective_command = "nix develop -c cargo test".to_string();
+
+        save_bundle(&repo, &first).expect("save first");
+        let latest_dir = save_bundle(&repo, &second).expect("save second");
+
+        let dirs = list_bundles(&repo, &task_id);
+        assert_eq!(dirs.len(), 2);
+        assert_eq!(dirs.last(), Some(&latest_dir));
+        let loaded = load_bundle(&latest_dir).expect("load");
+        assert_eq!(loaded, second);
+        assert_eq!(loaded.output_tail.len(), OUTPUT_TAIL_LINES);
+        assert_eq!(loaded.output_tail.first().map(String::as_str), Some("line 51"));
+        assert!(loaded.render().contains("Ran as:   nix develop -c cargo test"));
+    }
+
+    #[test]
+    fn repro_command_quotes_cwd_and_command() {
+        let bundle = VerifyFailureBundle {
+            task_id: TaskId::new("T-VF-2"),
+            captured_at: Utc::now(),
+            command: "echo 'hi'".to_string(),
+            effective_command: "echo 'hi'".to_string(),
+            cwd: PathBuf::from("/tmp/my repo"),
+            exit_code: Some(1),
+            env: BTreeMap::new(),
+            toolchains: BTreeMap::new(),
+            output_tail: Vec::new(),
+        };
+        assert_eq!(
+            bundle.repro_command(),
+            r#"cd '/tmp/my repo' && bash -lc 'echo '"'"'hi'"'"''"#
+        );
+    }
+}
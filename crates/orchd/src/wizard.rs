@@ -2,8 +2,11 @@
 //! guided remediation, and non-interactive CI mode.
 
 use orch_agents::setup::{probe_models, SetupProbeConfig};
-use orch_core::config::load_org_config;
+use orch_core::config::{
+    load_org_config, DaemonOrgConfig, NotificationConfig, OrgConfig, UiConfig,
+};
 use serde::{Deserialize, Serialize};
+use std::io::Write;
 use std::path::Path;
 use std::process::{Command, Stdio};
 
@@ -549,6 +552,226 @@ pub fn is_ci_ready(report: &ReadinessReport) -> bool {
     report.score >= 80 && report.all_critical_passed
 }
 
+// ---------------------------------------------------------------------------
+// Config sections
+// ---------------------------------------------------------------------------
+
+/// Answer typed to clear an optional setting.
+pub const CLEAR_ANSWER: &str = "-";
+
+/// Source of answers for the wizard's questions.
+pub trait WizardInput {
+    /// Ask `question`; an empty answer returns `default`.
+    fn ask(&mut self, question: &str, default: Option<&str>) -> std::io::Result<String>;
+}
+
+/// Prompts on stderr and reads answers from stdin.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdinInput;
+
+impl WizardInput for StdinInput {
+    fn ask(&mut self, question: &str, default: Option<&str>) -> std::io::Result<String> {
+        match default {
+            Some(default) if !default.is_empty() => eprint!("{question} [{default}]: "),
+            _ => eprint!("{question}: "),
+        }
+        std::io::stderr().flush()?;
+        let mut line = String::new();
+        std::io::stdin().read_line(&mut line)?;
+        let answer = line.trim();
+        Ok(if answer.is_empty() {
+            default.unwrap_or_default().to_string()
+        } else {
+            answer.to_string()
+        })
+    }
+}
+
+/// Settings passed as wizard flags. A section with any of its flags set is
+/// applied as given instead of being asked about.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WizardSettings {
+    pub webhook_url: Option<String>,
+    pub slack_webhook_url: Option<String>,
+    pub slack_channel: Option<String>,
+    pub telegram_bot_token_env: Option<String>,
+    pub telegram_chat_id_env: Option<String>,
+    pub web_bind: Option<String>,
+    pub web_auth_token: Option<String>,
+    pub verify_command: Option<String>,
+}
+
+fn confirm(input: &mut dyn WizardInput, question: &str) -> std::io::Result<bool> {
+    let answer = input.ask(&format!("{question} (y/N)"), Some("n"))?;
+    Ok(matches!(answer.to_ascii_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Ask for an optional setting, offering its current value; `-` clears it.
+fn ask_optional(
+    input: &mut dyn WizardInput,
+    question: &str,
+    current: &Option<String>,
+) -> std::io::Result<Option<String>> {
+    let answer = input.ask(
+        &format!("{question} ('{CLEAR_ANSWER}' clears)"),
+        current.as_deref(),
+    )?;
+    Ok(match answer.trim() {
+        "" | CLEAR_ANSWER => None,
+        value => Some(value.to_string()),
+    })
+}
+
+/// Webhook, Slack and Telegram settings. Notifications are switched on once
+/// any sink is configured.
+pub fn configure_notifications(
+    config: &mut NotificationConfig,
+    settings: &WizardSettings,
+    input: Option<&mut dyn WizardInput>,
+) -> std::io::Result<()> {
+    let flags = [
+        &settings.webhook_url,
+        &settings.slack_webhook_url,
+        &settings.slack_channel,
+        &settings.telegram_bot_token_env,
+        &settings.telegram_chat_id_env,
+    ];
+    if flags.iter().any(|flag| flag.is_some()) {
+        let apply = |target: &mut Option<String>, flag: &Option<String>| {
+            if let Some(value) = flag {
+                *target = Some(value.clone()).filter(|value| !value.is_empty());
+            }
+        };
+        apply(&mut config.webhook_url, &settings.webhook_url);
+        apply(&mut config.slack_webhook_url, &settings.slack_webhook_url);
+        apply(&mut config.slack_channel, &settings.slack_channel);
+        apply(
+            &mut config.telegram_bot_token_env,
+            &settings.telegram_bot_token_env,
+        );
+        apply(
+            &mut config.telegram_chat_id_env,
+            &settings.telegram_chat_id_env,
+        );
+    } else {
+        let Some(input) = input else {
+            return Ok(());
+        };
+        if !confirm(input, "Configure notifications?")? {
+            return Ok(());
+        }
+        config.webhook_url = ask_optional(input, "Webhook URL", &config.webhook_url)?;
+        config.slack_webhook_url =
+            ask_optional(input, "Slack webhook URL", &config.slack_webhook_url)?;
+        config.slack_channel = ask_optional(input, "Slack channel", &config.slack_channel)?;
+        config.telegram_bot_token_env = ask_optional(
+            input,
+            "Telegram bot token env var",
+            &config.telegram_bot_token_env,
+        )?;
+        config.telegram_chat_id_env = ask_optional(
+            input,
+            "Telegram chat id env var",
+            &config.telegram_chat_id_env,
+        )?;
+    }
+
+    if config.webhook_url.is_some()
+        || config.slack_webhook_url.is_some()
+        || config.telegram_bot_token_env.is_some()
+    {
+        config.enabled = true;
+    }
+    Ok(())
+}
+
+/// Web UI bind address and auth token. Answering `generate` for the token
+/// drops it so a fresh one is generated before saving.
+pub fn configure_web_ui(
+    config: &mut UiConfig,
+    settings: &WizardSettings,
+    input: Option<&mut dyn WizardInput>,
+) -> std::io::Result<()> {
+    if settings.web_bind.is_some() || settings.web_auth_token.is_some() {
+        if let Some(bind) = &settings.web_bind {
+            config.web_bind = bind.clone();
+        }
+        if let Some(token) = &settings.web_auth_token {
+            config.web_auth_token = Some(token.clone()).filter(|token| !token.is_empty());
+        }
+        return Ok(());
+    }
+    let Some(input) = input else {
+        return Ok(());
+    };
+    if !confirm(input, "Configure the web UI?")? {
+        return Ok(());
+    }
+    config.web_bind = input.ask("Web UI bind address", Some(&config.web_bind))?;
+    let answer = input.ask(
+        "Web auth token (empty keeps the current one, 'generate' makes a new one)",
+        None,
+    )?;
+    match answer.trim() {
+        "" => {}
+        "generate" => config.web_auth_token = None,
+        token => config.web_auth_token = Some(token.to_string()),
+    }
+    Ok(())
+}
+
+/// Verify command for task worktrees. Without a flag or an answer, an unset
+/// command is filled from `discovered`.
+pub fn configure_verify_command(
+    config: &mut DaemonOrgConfig,
+    discovered: &[String],
+    settings: &WizardSettings,
+    input: Option<&mut dyn WizardInput>,
+) -> std::io::Result<()> {
+    if let Some(command) = &settings.verify_command {
+        config.verify_command = Some(command.clone()).filter(|command| !command.is_empty());
+        return Ok(());
+    }
+    let suggested = config
+        .verify_command
+        .clone()
+        .or_else(|| discovered.first().cloned());
+    let Some(input) = input else {
+        config.verify_command = suggested;
+        return Ok(());
+    };
+    if !confirm(input, "Configure the verify command?")? {
+        if config.verify_command.is_none() {
+            config.verify_command = suggested;
+        }
+        return Ok(());
+    }
+    if !discovered.is_empty() {
+        eprintln!("  Discovered: {}", discovered.join(" | "));
+    }
+    config.verify_command = ask_optional(input, "Verify command", &suggested)?;
+    Ok(())
+}
+
+fn reborrow<'a>(input: &'a mut Option<&mut dyn WizardInput>) -> Option<&'a mut dyn WizardInput> {
+    input
+        .as_mut()
+        .map(|input| &mut **input as &mut dyn WizardInput)
+}
+
+/// Run the notification, web UI and verify command sections over `config`.
+pub fn configure_sections(
+    config: &mut OrgConfig,
+    repo_root: &Path,
+    settings: &WizardSettings,
+    mut input: Option<&mut dyn WizardInput>,
+) -> std::io::Result<()> {
+    configure_notifications(&mut config.notifications, settings, reborrow(&mut input))?;
+    configure_web_ui(&mut config.ui, settings, reborrow(&mut input))?;
+    let discovered = orch_verify::discover_verify_commands(repo_root);
+    configure_verify_command(&mut config.daemon, &discovered, settings, input)
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -665,4 +888,131 @@ mod tests {
         ]);
         assert!(!is_ci_ready(&crit_fail));
     }
+
+    /// Replays canned answers and records the default offered per question.
+    struct ScriptedInput {
+        answers: std::collections::VecDeque<&'static str>,
+        offered: Vec<(String, Option<String>)>,
+    }
+
+    impl ScriptedInput {
+        fn new(answers: &[&'static str]) -> Self {
+            Self {
+                answers: answers.iter().copied().collect(),
+                offered: Vec::new(),
+            }
+        }
+    }
+
+    impl WizardInput for ScriptedInput {
+        fn ask(&mut self, question: &str, default: Option<&str>) -> std::io::Result<String> {
+            self.offered
+                .push((question.to_string(), default.map(str::to_string)));
+            let answer = self.answers.pop_front().expect("unexpected question");
+            Ok(if answer.is_empty() {
+                default.unwrap_or_default().to_string()
+            } else {
+                answer.to_string()
+            })
+        }
+    }
+
+    fn configured_org() -> OrgConfig {
+        let mut config = OrgConfig::default();
+        config.notifications.webhook_url = Some("https://hooks.example.com/othala".to_string());
+        config.notifications.slack_channel = Some("#builds".to_string());
+        config.ui.web_bind = "0.0.0.0:9000".to_string();
+        config.ui.web_auth_token = Some("secret".to_string());
+        config.daemon.verify_command = Some("make check".to_string());
+        config
+    }
+
+    #[test]
+    fn rerunning_the_wizard_prefills_existing_values() {
+        let mut config = configured_org();
+        let mut input = ScriptedInput::new(&["y", "", "", "-", "", "", "y", "", "", "y", ""]);
+        configure_sections(
+            &mut config,
+            Path::new("/nonexistent"),
+            &WizardSettings::default(),
+            Some(&mut input),
+        )
+        .expect("configure");
+
+        let offered: Vec<_> = input
+            .offered
+            .iter()
+            .filter_map(|(_, default)| default.as_deref())
+            .collect();
+        assert!(offered.contains(&"https://hooks.example.com/othala"));
+        assert!(offered.contains(&"0.0.0.0:9000"));
+        assert!(offered.contains(&"make check"));
+        assert_eq!(
+            config.notifications.webhook_url.as_deref(),
+            Some("https://hooks.example.com/othala")
+        );
+        assert_eq!(config.notifications.slack_channel, None, "'-' clears");
+        assert!(config.notifications.enabled);
+        assert_eq!(config.ui.web_bind, "0.0.0.0:9000");
+        assert_eq!(config.ui.web_auth_token.as_deref(), Some("secret"));
+        assert_eq!(config.daemon.verify_command.as_deref(), Some("make check"));
+    }
+
+    #[test]
+    fn skipped_sections_leave_the_config_alone() {
+        let mut config = configured_org();
+        let mut input = ScriptedInput::new(&["n", "n", "n"]);
+        configure_sections(
+            &mut config,
+            Path::new("/nonexistent"),
+            &WizardSettings::default(),
+            Some(&mut input),
+        )
+        .expect("configure");
+        assert_eq!(config, configured_org());
+        assert_eq!(input.offered.len(), 3);
+    }
+
+    #[test]
+    fn non_interactive_flags_apply_and_discovery_fills_an_unset_verify_command() {
+        let mut config = OrgConfig::default();
+        let settings = WizardSettings {
+            slack_webhook_url: Some("https://hooks.slack.com/x".to_string()),
+            web_bind: Some("127.0.0.1:8080".to_string()),
+            ..WizardSettings::default()
+        };
+        configure_notifications(&mut config.notifications, &settings, None).expect("notify");
+        configure_web_ui(&mut config.ui, &settings, None).expect("web");
+        configure_verify_command(
+            &mut config.daemon,
+            &["cargo check && cargo test --workspace".to_string()],
+            &settings,
+            None,
+        )
+        .expect("verify");
+
+        assert!(config.notifications.enabled);
+        assert_eq!(
+            config.notifications.slack_webhook_url.as_deref(),
+            Some("https://hooks.slack.com/x")
+        );
+        assert_eq!(config.ui.web_bind, "127.0.0.1:8080");
+        assert_eq!(
+            config.daemon.verify_command.as_deref(),
+            Some("cargo check && cargo test --workspace")
+        );
+
+        let mut existing = configured_org();
+        configure_verify_command(
+            &mut existing.daemon,
+            &["npm test".to_string()],
+            &WizardSettings::default(),
+            None,
+        )
+        .expect("verify");
+        assert_eq!(
+            existing.daemon.verify_command.as_deref(),
+            Some("make check")
+        );
+    }
 }
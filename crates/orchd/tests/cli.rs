@@ -0,0 +1,354 @@
+//! Drives the `orchd::cli` handlers against an in-memory service.
+
+use chrono::{Duration, Utc};
+use orch_core::events::{CancelReason, Event, EventKind};
+use orch_core::state::TaskState;
+use orch_core::types::{EventId, ModelKind, RepoId, Task, TaskId, TaskPriority};
+use orchd::cli::tasks::{LabelChange, StateChange};
+use orchd::cli::{bench, bulk, history, stats, tasks, transfer};
+use orchd::event_log::JsonlEventLog;
+use orchd::persistence::SqliteStore;
+use orchd::scheduler::{Scheduler, SchedulerConfig};
+use orchd::{OrchdService, TaskRunRecord};
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+struct Harness {
+    service: OrchdService,
+    _events: TempDir,
+}
+
+fn harness() -> Harness {
+    let events = tempfile::tempdir().expect("temp dir");
+    let service = OrchdService::new(
+        SqliteStore::open_in_memory().expect("in-memory db"),
+        JsonlEventLog::new(events.path()),
+        Scheduler::new(SchedulerConfig {
+            per_repo_limit: 10,
+            per_model_limit: vec![(ModelKind::Claude, 10), (ModelKind::Codex, 10)]
+                .into_iter()
+                .collect(),
+        }),
+    );
+    service.bootstrap().expect("bootstrap");
+    Harness {
+        service,
+        _events: events,
+    }
+}
+
+fn mk_task(id: &str, state: TaskState) -> Task {
+    let mut task = Task::new(
+        TaskId::new(id),
+        RepoId("repo-cli".to_string()),
+        format!("Task {id}"),
+        PathBuf::from(format!(".orch/wt/{id}")),
+    );
+    task.state = state;
+    task
+}
+
+fn event(task: &Task, suffix: &str, kind: EventKind) -> Event {
+    Event {
+        id: EventId(format!("E-{suffix}-{}", task.id.0)),
+        task_id: Some(task.id.clone()),
+        repo_id: Some(task.repo_id.clone()),
+        at: Utc::now(),
+        kind,
+    }
+}
+
+fn create(service: &OrchdService, task: &Task) {
+    service
+        .create_task(task, &event(task, "CREATE", EventKind::TaskCreated))
+        .expect("create task");
+}
+
+fn load(service: &OrchdService, id: &str) -> Task {
+    service
+        .task(&TaskId::new(id))
+        .expect("load task")
+        .expect("task exists")
+}
+
+#[test]
+fn list_returns_every_task_and_detail_adds_questions() {
+    let h = harness();
+    create(&h.service, &mk_task("T-LIST-1", TaskState::Chatting));
+    create(&h.service, &mk_task("T-LIST-2", TaskState::Ready));
+
+    let mut ids: Vec<String> = tasks::list(&h.service)
+        .expect("list")
+        .into_iter()
+        .map(|task| task.id.0)
+        .collect();
+    ids.sort();
+    assert_eq!(ids, vec!["T-LIST-1", "T-LIST-2"]);
+
+    let detailed = tasks::list_detailed(&h.service).expect("list detailed");
+    assert_eq!(detailed.len(), 2);
+    assert!(detailed
+        .iter()
+        .all(|detail| detail.open_questions.is_empty()));
+}
+
+#[test]
+fn status_returns_task_and_rejects_unknown_ids() {
+    let h = harness();
+    create(&h.service, &mk_task("T-STATUS", TaskState::Ready));
+
+    let detail = tasks::status(&h.service, "T-STATUS")
+        .expect("status")
+        .expect("task found");
+    assert_eq!(detail.task.state, TaskState::Ready);
+    assert_eq!(detail.task.title, "Task T-STATUS");
+
+    assert!(tasks::status(&h.service, "T-MISSING").is_err());
+}
+
+#[test]
+fn delete_removes_the_task() {
+    let h = harness();
+    create(&h.service, &mk_task("T-DELETE", TaskState::Stopped));
+
+    assert_eq!(
+        tasks::delete(&h.service, "T-DELETE", false).expect("delete"),
+        vec![TaskId::new("T-DELETE")]
+    );
+    assert!(tasks::list(&h.service).expect("list").is_empty());
+}
+
+#[test]
+fn stop_then_resume_round_trips_through_stopped() {
+    let h = harness();
+    create(&h.service, &mk_task("T-STOP", TaskState::Chatting));
+
+    let stopped = tasks::stop(&h.service, "T-STOP").expect("stop");
+    assert_eq!(
+        stopped,
+        StateChange {
+            task_id: TaskId::new("T-STOP"),
+            from: TaskState::Chatting,
+            to: TaskState::Stopped,
+        }
+    );
+
+    let resumed = tasks::resume(&h.service, "T-STOP").expect("resume");
+    assert_eq!(resumed.from, TaskState::Stopped);
+    assert_eq!(resumed.to, TaskState::Chatting);
+    assert_eq!(load(&h.service, "T-STOP").state, TaskState::Chatting);
+}
+
+#[test]
+fn cancel_records_reason_and_rejects_finished_tasks() {
+    let h = harness();
+    create(&h.service, &mk_task("T-CANCEL", TaskState::Ready));
+    create(&h.service, &mk_task("T-CANCEL-MERGED", TaskState::Merged));
+
+    let change = tasks::cancel(&h.service, "T-CANCEL", "budget").expect("cancel");
+    assert_eq!(change.from, TaskState::Ready);
+    assert_eq!(change.to, TaskState::Stopped);
+
+    let events = history::logs(&h.service, Some("T-CANCEL"), 50).expect("logs");
+    assert!(events.iter().any(|event| matches!(
+        &event.kind,
+        EventKind::CancellationRequested {
+            reason: CancelReason::BudgetExceeded
+        }
+    )));
+
+    assert!(tasks::cancel(&h.service, "T-CANCEL-MERGED", "user").is_err());
+}
+
+#[test]
+fn set_priority_parses_and_persists() {
+    let h = harness();
+    create(&h.service, &mk_task("T-PRIO", TaskState::Chatting));
+
+    let (task_id, priority) =
+        tasks::set_priority(&h.service, "T-PRIO", "critical").expect("set priority");
+    assert_eq!(task_id, TaskId::new("T-PRIO"));
+    assert_eq!(priority, TaskPriority::Critical);
+    assert_eq!(load(&h.service, "T-PRIO").priority, TaskPriority::Critical);
+
+    assert!(tasks::set_priority(&h.service, "T-PRIO", "urgent-ish").is_err());
+}
+
+#[test]
+fn tag_and_untag_single_task_and_by_state() {
+    let h = harness();
+    create(&h.service, &mk_task("T-TAG-1", TaskState::Chatting));
+    create(&h.service, &mk_task("T-TAG-2", TaskState::Chatting));
+    create(&h.service, &mk_task("T-TAG-3", TaskState::Ready));
+
+    let change = tasks::tag(
+        &h.service,
+        vec!["T-TAG-1".to_string(), "infra".to_string()],
+        None,
+    )
+    .expect("tag task");
+    assert_eq!(
+        change,
+        LabelChange::Task {
+            task_id: TaskId::new("T-TAG-1"),
+            labels: vec!["infra".to_string()],
+        }
+    );
+
+    let LabelChange::State { summary, .. } =
+        tasks::tag(&h.service, vec!["wip".to_string()], Some("chatting")).expect("tag state")
+    else {
+        panic!("expected a state-wide label change");
+    };
+    assert_eq!((summary.processed, summary.succeeded), (2, 2));
+    assert_eq!(load(&h.service, "T-TAG-1").labels, vec!["infra", "wip"]);
+    assert!(load(&h.service, "T-TAG-3").labels.is_empty());
+
+    tasks::untag(
+        &h.service,
+        vec!["T-TAG-1".to_string(), "infra".to_string()],
+        None,
+    )
+    .expect("untag task");
+    assert_eq!(load(&h.service, "T-TAG-1").labels, vec!["wip"]);
+}
+
+#[test]
+fn bulk_retry_resets_retry_counts() {
+    let h = harness();
+    let mut stopped = mk_task("T-BULK-1", TaskState::Stopped);
+    stopped.retry_count = 4;
+    create(&h.service, &stopped);
+    create(&h.service, &mk_task("T-BULK-2", TaskState::Merged));
+
+    let summary = bulk::retry(&h.service, Some("stopped"), &[]).expect("bulk retry");
+    assert_eq!(
+        (summary.processed, summary.succeeded, summary.skipped),
+        (1, 1, 0)
+    );
+    let retried = load(&h.service, "T-BULK-1");
+    assert_eq!(retried.state, TaskState::Chatting);
+    assert_eq!(retried.retry_count, 0);
+    assert_eq!(load(&h.service, "T-BULK-2").state, TaskState::Merged);
+}
+
+#[test]
+fn logs_keeps_the_most_recent_events() {
+    let h = harness();
+    let task = mk_task("T-LOGS", TaskState::Chatting);
+    create(&h.service, &task);
+    for attempt in 1..=3 {
+        h.service
+            .record_event(&event(
+                &task,
+                &format!("RETRY-{attempt}"),
+                EventKind::RetryScheduled {
+                    attempt,
+                    model: "claude".to_string(),
+                    reason: "timeout".to_string(),
+                },
+            ))
+            .expect("record event");
+    }
+
+    let events = history::logs(&h.service, Some("T-LOGS"), 2).expect("logs");
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[1].id, EventId("E-RETRY-3-T-LOGS".to_string()));
+}
+
+#[test]
+fn runs_and_retries_report_recorded_attempts() {
+    let h = harness();
+    let task = mk_task("T-RUNS", TaskState::Chatting);
+    create(&h.service, &task);
+    let started = Utc::now() - Duration::minutes(5);
+    h.service
+        .store
+        .insert_run(&TaskRunRecord {
+            run_id: "run-1".to_string(),
+            task_id: task.id.clone(),
+            repo_id: task.repo_id.clone(),
+            model: ModelKind::Claude,
+            started_at: started,
+            finished_at: Some(started + Duration::minutes(2)),
+            stop_reason: Some("failed".to_string()),
+            exit_code: Some(1),
+            estimated_tokens: None,
+            duration_secs: None,
+            confidence: Some(72),
+        })
+        .expect("insert run");
+    h.service
+        .record_event(&event(
+            &task,
+            "SPAWN",
+            EventKind::AgentSpawned {
+                model: "claude".to_string(),
+            },
+        ))
+        .expect("record spawn");
+
+    let runs = history::runs(&h.service, "T-RUNS").expect("runs");
+    assert_eq!(runs.len(), 1);
+    assert_eq!(runs[0].exit_code, Some(1));
+    assert_eq!(runs[0].confidence, Some(72));
+
+    let retries = history::retries(&h.service, "T-RUNS").expect("retries");
+    assert_eq!(retries.task_id, "T-RUNS");
+    assert_eq!(retries.retry_events.len(), 1);
+    assert_eq!(retries.timeline.len(), 1);
+    assert_eq!(retries.timeline[0].attempt, 1);
+}
+
+#[test]
+fn stats_counts_tasks_by_state() {
+    let h = harness();
+    create(&h.service, &mk_task("T-STATS-1", TaskState::Chatting));
+    create(&h.service, &mk_task("T-STATS-2", TaskState::Merged));
+    create(&h.service, &mk_task("T-STATS-3", TaskState::Stopped));
+
+    let summary = stats::stats(&h.service, stats::DEFAULT_THROUGHPUT_DAYS).expect("stats");
+    assert_eq!(summary.total_tasks, 3);
+    assert_eq!(
+        summary.merged_per_day.len(),
+        stats::DEFAULT_THROUGHPUT_DAYS as usize
+    );
+    assert_eq!(summary.tasks_by_state.get("MERGED"), Some(&1));
+    assert_eq!(summary.success_rate, Some(50.0));
+    assert!(summary.total_events >= 3);
+}
+
+#[test]
+fn export_then_import_recreates_tasks() {
+    let source = harness();
+    let mut parent = mk_task("T-EXPORT-PARENT", TaskState::Ready);
+    parent.branch_name = Some("task/parent".to_string());
+    let mut child = mk_task("T-EXPORT-CHILD", TaskState::Chatting);
+    child.parent_task_id = Some(parent.id.clone());
+    child.priority = TaskPriority::High;
+    create(&source.service, &parent);
+    create(&source.service, &child);
+
+    let only_child = transfer::export(&source.service, Some("T-EXPORT-CHILD")).expect("export one");
+    assert_eq!(only_child.len(), 1);
+    assert_eq!(only_child[0].parent_branch.as_deref(), Some("task/parent"));
+
+    let records = transfer::export(&source.service, None).expect("export all");
+    assert_eq!(records.len(), 2);
+
+    let target = harness();
+    assert_eq!(
+        transfer::import(&target.service, records).expect("import"),
+        2
+    );
+    let imported = load(&target.service, "T-EXPORT-CHILD");
+    assert_eq!(imported.state, TaskState::Chatting);
+    assert_eq!(imported.priority, TaskPriority::High);
+}
+
+#[test]
+fn bench_compare_requires_results_for_both_tags() {
+    let h = harness();
+    let err = bench::compare(&h.service, "v1", "v2").expect_err("no results recorded");
+    assert!(err.to_string().contains("'v1'"));
+}
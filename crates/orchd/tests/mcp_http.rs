@@ -0,0 +1,174 @@
+//! Drives `HttpTransport::serve_listener` over a real loopback socket.
+
+use orchd::mcp::McpServer;
+use orchd::mcp_transport::{HttpTransport, TransportConfig, TransportKind};
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+struct Response {
+    status: u16,
+    session: Option<String>,
+    body: String,
+}
+
+fn request(addr: &str, method: &str, path: &str, session: Option<&str>, body: &str) -> Response {
+    let mut stream = TcpStream::connect(addr).expect("connect");
+    let session_header = session
+        .map(|session| format!("Mcp-Session-Id: {session}\r\n"))
+        .unwrap_or_default();
+    write!(
+        stream,
+        "{method} {path} HTTP/1.1\r\nHost: {addr}\r\n{session_header}Content-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+        body.len()
+    )
+    .expect("write request");
+
+    let mut raw = String::new();
+    stream.read_to_string(&mut raw).expect("read response");
+    let (head, body) = raw.split_once("\r\n\r\n").expect("response head");
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse().ok())
+        .expect("status code");
+    let session = head.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.eq_ignore_ascii_case("mcp-session-id")
+            .then(|| value.trim().to_string())
+    });
+    Response {
+        status,
+        session,
+        body: body.to_string(),
+    }
+}
+
+/// Send a JSON-RPC call, returning the decoded reply and the session it ran in.
+fn rpc(addr: &str, session: Option<&str>, method: &str, params: Value) -> (Value, String) {
+    let body = json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params});
+    let response = request(addr, "POST", "/rpc", session, &body.to_string());
+    assert_eq!(
+        response.status, 200,
+        "{method} answered {}: {}",
+        response.status, response.body
+    );
+    (
+        serde_json::from_str(&response.body).expect("json-rpc response"),
+        response.session.expect("session header"),
+    )
+}
+
+fn listed_tools(reply: &Value) -> Vec<&str> {
+    reply["result"]["tools"]
+        .as_array()
+        .expect("tools array")
+        .iter()
+        .filter_map(|tool| tool["name"].as_str())
+        .collect()
+}
+
+/// Serve `server` under `config` on a loopback port while `client` runs.
+fn with_server(config: TransportConfig, server: McpServer, client: impl FnOnce(&str) + Send) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+    let addr = listener.local_addr().expect("local addr").to_string();
+    let transport = HttpTransport::new_uninitialized(&config).expect("transport");
+    let shutdown = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        let client = scope.spawn(|| {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| client(&addr)));
+            shutdown.store(true, Ordering::Release);
+            if let Err(panic) = result {
+                std::panic::resume_unwind(panic);
+            }
+        });
+
+        transport
+            .serve_listener(listener, server, &shutdown)
+            .expect("serve");
+        client.join().expect("client assertions");
+    });
+}
+
+fn http_config() -> TransportConfig {
+    TransportConfig {
+        kind: TransportKind::Http {
+            bind_addr: "127.0.0.1".to_string(),
+            port: 0,
+        },
+        ..TransportConfig::default()
+    }
+}
+
+fn builtin_server() -> McpServer {
+    let mut server = McpServer::new();
+    server.register_builtin_tools();
+    server
+}
+
+#[test]
+fn serves_initialize_tools_list_and_health_over_http() {
+    with_server(http_config(), builtin_server(), |addr| {
+        let sse = TcpStream::connect(addr).expect("connect sse");
+        write!(&sse, "GET /sse HTTP/1.1\r\nHost: {addr}\r\n\r\n").expect("open sse");
+        let mut status_line = String::new();
+        BufReader::new(&sse)
+            .read_line(&mut status_line)
+            .expect("sse head");
+        assert!(status_line.starts_with("HTTP/1.1 200"), "{status_line}");
+
+        let (init, session) = rpc(addr, None, "initialize", json!({}));
+        assert!(init["result"]["serverInfo"].is_object(), "{init}");
+
+        let (tools, echoed) = rpc(addr, Some(&session), "tools/list", json!({}));
+        assert_eq!(echoed, session);
+        let listed = listed_tools(&tools);
+        assert!(!listed.is_empty());
+
+        let (fresh, _) = rpc(addr, None, "tools/list", json!({}));
+        assert!(
+            fresh["error"].is_object(),
+            "new sessions must initialize: {fresh}"
+        );
+
+        let health = request(addr, "GET", "/health", None, "");
+        assert_eq!(health.status, 200);
+        let health: Value = serde_json::from_str(&health.body).expect("health json");
+        assert_eq!(health["status"], "ok");
+        assert_eq!(health["tools"].as_u64(), Some(listed.len() as u64));
+        assert!(health["uptime_secs"].is_u64());
+    });
+}
+
+#[test]
+fn shared_secret_and_allowlist_scope_each_http_session() {
+    let config = TransportConfig {
+        allowed_tools: Some(vec!["list_tasks".to_string(), "create_task".to_string()]),
+        shared_secret: Some("s3cret".to_string()),
+        ..http_config()
+    };
+    with_server(config, builtin_server(), |addr| {
+        let (_, anonymous) = rpc(addr, None, "initialize", json!({}));
+        let (_, authed) = rpc(
+            addr,
+            None,
+            "initialize",
+            json!({ "auth": { "secret": "s3cret" } }),
+        );
+        assert_ne!(anonymous, authed);
+
+        let (tools, _) = rpc(addr, Some(&anonymous), "tools/list", json!({}));
+        assert_eq!(listed_tools(&tools), vec!["list_tasks"]);
+        let (tools, _) = rpc(addr, Some(&authed), "tools/list", json!({}));
+        assert_eq!(listed_tools(&tools), vec!["list_tasks", "create_task"]);
+
+        let call = json!({ "name": "create_task", "arguments": { "repo": "r", "title": "t" } });
+        let (rejected, _) = rpc(addr, Some(&anonymous), "tools/call", call.clone());
+        assert_eq!(rejected["error"]["code"], json!(-32001), "{rejected}");
+        let (created, _) = rpc(addr, Some(&authed), "tools/call", call);
+        assert!(created["error"].is_null(), "{created}");
+    });
+}